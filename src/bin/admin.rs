@@ -0,0 +1,160 @@
+use std::{env, fs, process};
+
+use payscribe::core::{
+    delete_chat_data, export_chat_data, recalculate_chat_balances, restore_chat_data,
+    retrieve_chat_ids, retrieve_chat_payments, run_migrations, ChatBackup, Redis,
+};
+
+const USAGE: &str = "PayScribe admin tool
+
+Usage:
+  admin chats                         List the IDs of all chats
+  admin payments <chat ID>            Dump the payments of a chat
+  admin rebuild <chat ID>             Rebuild the balances of a chat from its payments
+  admin delete <chat ID> --yes        Delete a chat and all its records
+  admin export <chat ID> [file]       Export a chat as JSON, to a file or stdout
+  admin import <chat ID> <file>       Import a chat from JSON, overwriting its records
+
+Uses the same REDIS_URL as the bot, from the environment or .env.";
+
+// Prints an error and exits, for any failure of a command
+fn fail(message: String) -> ! {
+    eprintln!("Error: {message}");
+    process::exit(1);
+}
+
+// Gets the chat ID given to a command, or exits with the usage
+fn chat_id_arg(args: &[String]) -> &str {
+    match args.get(2) {
+        Some(chat_id) => chat_id,
+        None => fail(format!("Missing chat ID\n\n{USAGE}")),
+    }
+}
+
+/* Lists the IDs of all chats, one per line.
+ */
+async fn list_chats(redis: &Redis) {
+    let chat_ids = retrieve_chat_ids(redis)
+        .await
+        .unwrap_or_else(|err| fail(err.to_string()));
+    for chat_id in &chat_ids {
+        println!("{chat_id}");
+    }
+    eprintln!("{} chats", chat_ids.len());
+}
+
+/* Dumps the payments of a chat, one per line, with their amounts in base units.
+ */
+async fn dump_payments(redis: &Redis, chat_id: &str) {
+    let payments = retrieve_chat_payments(redis, chat_id)
+        .await
+        .unwrap_or_else(|err| fail(err.to_string()));
+    for entry in &payments {
+        let payment = &entry.payment;
+        let debts: Vec<String> = payment
+            .debts
+            .iter()
+            .map(|(username, amount)| format!("{username}={amount}"))
+            .collect();
+        println!(
+            "{}\t{}\t{}\t{}\t{} {}\t{}",
+            entry.payment_id,
+            payment.datetime,
+            payment.description,
+            payment.creditor,
+            payment.total,
+            payment.currency,
+            debts.join(",")
+        );
+    }
+    eprintln!("{} payments", payments.len());
+}
+
+/* Rebuilds the balances and spendings of a chat and its trips, by replaying their payments.
+ */
+async fn rebuild_balances(redis: &Redis, chat_id: &str) {
+    let count = recalculate_chat_balances(redis, chat_id)
+        .await
+        .unwrap_or_else(|err| fail(err.to_string()));
+    println!("Rebuilt balances of chat {chat_id} from {count} payments");
+}
+
+/* Deletes a chat and all its records, only when confirmed with --yes.
+ */
+async fn delete_chat(redis: &Redis, chat_id: &str, args: &[String]) {
+    if !args.iter().any(|arg| arg == "--yes") {
+        fail(format!(
+            "Deleting chat {chat_id} can't be undone. Run again with --yes to confirm."
+        ));
+    }
+
+    delete_chat_data(redis, chat_id)
+        .await
+        .unwrap_or_else(|err| fail(err.to_string()));
+    println!("Deleted chat {chat_id}");
+}
+
+/* Exports a chat as JSON, in the same format as the backup files that /restore reads.
+ */
+async fn export_chat(redis: &Redis, chat_id: &str, file: Option<&String>) {
+    let backup = export_chat_data(redis, chat_id)
+        .await
+        .unwrap_or_else(|err| fail(err.to_string()));
+    let json = serde_json::to_string_pretty(&backup).unwrap_or_else(|err| fail(err.to_string()));
+    match file {
+        Some(file) => {
+            fs::write(file, json).unwrap_or_else(|err| fail(err.to_string()));
+            eprintln!("Exported chat {chat_id} to {file}");
+        }
+        None => println!("{json}"),
+    }
+}
+
+/* Imports a chat from JSON, overwriting all its current records.
+ */
+async fn import_chat(redis: &Redis, chat_id: &str, file: Option<&String>) {
+    let file = match file {
+        Some(file) => file,
+        None => fail(format!("Missing file to import\n\n{USAGE}")),
+    };
+    let json = fs::read_to_string(file).unwrap_or_else(|err| fail(err.to_string()));
+    let backup: ChatBackup =
+        serde_json::from_str(&json).unwrap_or_else(|err| fail(err.to_string()));
+    restore_chat_data(redis, chat_id, &backup)
+        .await
+        .unwrap_or_else(|err| fail(err.to_string()));
+    println!(
+        "Imported {} payments into chat {chat_id}",
+        backup.payments.len()
+    );
+}
+
+#[tokio::main]
+pub async fn main() {
+    dotenv::dotenv().ok();
+    let redis = Redis::new();
+
+    let args: Vec<String> = env::args().collect();
+    let command = match args.get(1) {
+        Some(command) if !matches!(command.as_str(), "help" | "--help" | "-h") => command.as_str(),
+        _ => {
+            println!("{USAGE}");
+            return;
+        }
+    };
+
+    // Upgrades the database first, as the bot does, so that commands see the latest layout
+    if let Err(err) = run_migrations(&redis).await {
+        fail(format!("Failed to migrate database: {err}"));
+    }
+
+    match command {
+        "chats" => list_chats(&redis).await,
+        "payments" => dump_payments(&redis, chat_id_arg(&args)).await,
+        "rebuild" => rebuild_balances(&redis, chat_id_arg(&args)).await,
+        "delete" => delete_chat(&redis, chat_id_arg(&args), &args).await,
+        "export" => export_chat(&redis, chat_id_arg(&args), args.get(3)).await,
+        "import" => import_chat(&redis, chat_id_arg(&args), args.get(3)).await,
+        _ => fail(format!("Unknown command {command}\n\n{USAGE}")),
+    }
+}
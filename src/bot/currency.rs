@@ -3,6 +3,14 @@ use reqwest::header;
 use serde_json::Value;
 use std::error::Error;
 
+use chrono::Utc;
+
+use super::money::saturating_from_f64;
+use super::redis::{
+    get_cached_rate, get_expiring_rate_pairs, get_last_cached_rate, set_cached_rate, CrudError,
+    Redis,
+};
+
 // Represents a currency with a code and decimal places.
 pub type Currency = (String, i32);
 
@@ -27,6 +35,146 @@ pub fn get_default_currency() -> Currency {
     to_currency(CURRENCY_DEFAULT)
 }
 
+// Normalizes an amount in units of a currency, such as 12.5 dollars, to its base value.
+// Rounds to the decimal places of the currency, such that zero-decimal currencies like JPY
+// never have fractional units. Every amount parsed or converted goes through here.
+pub fn normalize_amount(currency: &Currency, amount: f64) -> i64 {
+    (amount * 10.0_f64.powi(currency.1)).round() as i64
+}
+
+// Counts the decimal places written in an amount, ignoring trailing zeros, such as 1 for 12.50.
+pub fn count_decimal_places(text: &str) -> i32 {
+    match text.trim().split_once('.') {
+        Some((_, fraction)) => fraction.trim_end_matches('0').len() as i32,
+        None => 0,
+    }
+}
+
+// Represents a number format, with an example of it and its decimal separator.
+pub type NumberFormat = (&'static str, char);
+
+pub const NUMBER_FORMAT_DEFAULT: NumberFormat = ("1,234.56", '.');
+pub const NUMBER_FORMATS: [NumberFormat; 2] = [NUMBER_FORMAT_DEFAULT, ("1.234,56", ',')];
+
+// Retrieves the number format given its example, or the name of its decimal separator.
+pub fn get_number_format(text: &str) -> Option<NumberFormat> {
+    let text = text.trim().to_lowercase();
+    match text.as_str() {
+        "point" | "dot" | "period" => Some(NUMBER_FORMATS[0]),
+        "comma" => Some(NUMBER_FORMATS[1]),
+        _ => NUMBER_FORMATS
+            .iter()
+            .find(|number_format| number_format.0 == text)
+            .copied(),
+    }
+}
+
+// Normalizes an amount written in a number format, such that it can be parsed.
+// Thousands can be separated by the other separator, spaces, or apostrophes,
+// but the digits must then be in groups of three.
+pub fn normalize_amount_text(text: &str, number_format: NumberFormat) -> Option<String> {
+    let decimal = number_format.1;
+    let thousands = if decimal == '.' { ',' } else { '.' };
+    let text = text.trim();
+    let (whole, fraction) = match text.split_once(decimal) {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (text, None),
+    };
+
+    let is_separator = |c: char| c == thousands || c == '\'' || c == '’' || c.is_whitespace();
+    let groups: Vec<&str> = whole.split(is_separator).collect();
+    if groups.len() > 1
+        && (groups[0].is_empty()
+            || groups[0].len() > 3
+            || groups[1..].iter().any(|group| group.len() != 3))
+    {
+        return None;
+    }
+
+    let mut amount = groups.concat();
+    if let Some(fraction) = fraction {
+        amount.push('.');
+        amount.push_str(fraction);
+    }
+    Some(amount)
+}
+
+// Represents a currency format, with an example of it,
+// whether the symbol is shown in place of the code, and whether thousands are separated.
+pub type CurrencyFormat = (&'static str, bool, bool);
+
+pub const CURRENCY_FORMAT_DEFAULT: CurrencyFormat = ("12.30 SGD", false, false);
+pub const CURRENCY_FORMATS: [CurrencyFormat; 4] = [
+    CURRENCY_FORMAT_DEFAULT,
+    ("1,234.30 SGD", false, true),
+    ("S$12.30", true, false),
+    ("S$1,234.30", true, true),
+];
+
+// Retrieves the currency format given its example, or a short name for it.
+pub fn get_currency_format(text: &str) -> Option<CurrencyFormat> {
+    let text = text.trim().to_lowercase();
+    match text.as_str() {
+        "code" => Some(CURRENCY_FORMATS[0]),
+        "symbol" => Some(CURRENCY_FORMATS[2]),
+        _ => CURRENCY_FORMATS
+            .iter()
+            .find(|currency_format| currency_format.0.to_lowercase() == text)
+            .copied(),
+    }
+}
+
+// Retrieves the symbol of a currency given its code, if it has a well-known one.
+pub fn get_currency_symbol(code: &str) -> Option<&'static str> {
+    let code = code.to_uppercase();
+    CURRENCY_SYMBOLS
+        .iter()
+        .find(|(symbol_code, _)| *symbol_code == code)
+        .map(|(_, symbol)| *symbol)
+}
+
+// Formats an amount already written with its decimals, together with its currency code.
+// Falls back to showing the code if the currency has no known symbol,
+// and shows the amount alone if there is no currency.
+pub fn format_currency_amount(amount: &str, code: &str, currency_format: CurrencyFormat) -> String {
+    let (sign, digits) = match amount.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", amount),
+    };
+    let digits = if currency_format.2 {
+        separate_thousands(digits)
+    } else {
+        digits.to_string()
+    };
+
+    match get_currency_symbol(code) {
+        Some(symbol) if currency_format.1 => format!("{sign}{symbol}{digits}"),
+        _ if code.is_empty() => format!("{sign}{digits}"),
+        _ => format!("{sign}{digits} {code}"),
+    }
+}
+
+// Separates the thousands of an unsigned amount with commas.
+fn separate_thousands(amount: &str) -> String {
+    let (whole, fraction) = match amount.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (amount, None),
+    };
+
+    let mut separated = String::new();
+    for (index, digit) in whole.chars().enumerate() {
+        if index > 0 && (whole.len() - index) % 3 == 0 {
+            separated.push(',');
+        }
+        separated.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        separated.push('.');
+        separated.push_str(fraction);
+    }
+    separated
+}
+
 // Converts an amount from one currency to another, given the conversion rate.
 pub fn convert_currency_with_rate(
     amount: i64,
@@ -43,9 +191,9 @@ pub fn convert_currency_with_rate(
         None => return amount,
     };
 
-    let result = amount as f64 * 10.0_f64.powi(currency_to.1 - currency_from.1) * conversion_rate;
-
-    result.round() as i64
+    // Extreme rates stop at the largest amount, rather than wrapping around
+    let value = amount as f64 / 10.0_f64.powi(currency_from.1) * conversion_rate;
+    saturating_from_f64(value * 10.0_f64.powi(currency_to.1))
 }
 
 // Main API method that fetches currency conversions
@@ -84,6 +232,154 @@ pub async fn fetch_currency_conversion(
     Err("Currency not found".into())
 }
 
+// Default seconds that a cached conversion rate is kept for, before it expires
+const RATE_CACHE_TTL_DEFAULT: usize = 3600;
+// Seconds that a cached conversion rate is kept refreshed for, after it was last used
+const RATE_CACHE_USED_TTL: usize = 86400;
+// Seconds before expiry at which a cached conversion rate is refreshed in the background
+const RATE_CACHE_REFRESH_THRESHOLD: i64 = 300;
+// Default seconds after being fetched that a conversion rate is too old to be used
+const RATE_MAX_AGE_DEFAULT: i64 = 86400;
+
+// Gets the seconds that a cached conversion rate is kept for, configurable by RATE_CACHE_TTL
+fn get_rate_cache_ttl() -> usize {
+    std::env::var("RATE_CACHE_TTL")
+        .ok()
+        .and_then(|ttl| ttl.parse().ok())
+        .unwrap_or(RATE_CACHE_TTL_DEFAULT)
+}
+
+// Gets the seconds after which a conversion rate is too old to be used, configurable by RATE_MAX_AGE
+fn get_rate_max_age() -> i64 {
+    std::env::var("RATE_MAX_AGE")
+        .ok()
+        .and_then(|max_age| max_age.parse().ok())
+        .unwrap_or(RATE_MAX_AGE_DEFAULT)
+}
+
+// Checks if a conversion rate fetched at some time is too old to be used, as of now
+pub fn is_rate_stale(fetched_at: i64, now: i64, max_age: i64) -> bool {
+    now - fetched_at > max_age
+}
+
+// Gets the conversion rate between two currencies, from the cache if available.
+// Otherwise, fetches the rate from the API, and caches it.
+pub async fn get_rate_cached(
+    redis: &Redis,
+    base_currency: &str,
+    target_currency: &str,
+) -> Result<f64, Box<dyn Error>> {
+    let (rate, _) = get_rate_cached_with_time(redis, base_currency, target_currency).await?;
+    Ok(rate)
+}
+
+// Gets the conversion rate between two currencies, with the timestamp it was fetched at.
+// Served from the cache if available, otherwise fetched from the API, and cached.
+// If the rate cannot be fetched, the last rate fetched is used instead, unless it is too old,
+// in which case the rate is refused, so that balances are not converted with an outdated rate.
+pub async fn get_rate_cached_with_time(
+    redis: &Redis,
+    base_currency: &str,
+    target_currency: &str,
+) -> Result<(f64, i64), Box<dyn Error>> {
+    let base_currency = base_currency.to_uppercase();
+    let target_currency = target_currency.to_uppercase();
+    let now = Utc::now().timestamp();
+    let max_age = get_rate_max_age();
+
+    let last_rate = match get_last_cached_rate(redis, &base_currency, &target_currency).await {
+        Ok(last_rate) => last_rate,
+        Err(err) => {
+            log::error!(
+                "Rate Cache - Failed to get last rate from {base_currency} to {target_currency}: {}",
+                err
+            );
+            None
+        }
+    };
+
+    match get_cached_rate(redis, &base_currency, &target_currency, RATE_CACHE_USED_TTL).await {
+        Ok(Some(rate)) => {
+            // Rates cached before their fetch times were kept are taken as fetched now
+            let fetched_at = last_rate.map_or(now, |(_, fetched_at)| fetched_at);
+            if !is_rate_stale(fetched_at, now, max_age) {
+                return Ok((rate, fetched_at));
+            }
+        }
+        Ok(None) => {}
+        Err(err) => {
+            log::error!(
+                "Rate Cache - Failed to get cached rate from {base_currency} to {target_currency}: {}",
+                err
+            );
+        }
+    }
+
+    let rate = match fetch_currency_conversion(&base_currency, &target_currency).await {
+        Ok(rate) => rate,
+        Err(err) => match last_rate {
+            Some((rate, fetched_at)) if !is_rate_stale(fetched_at, now, max_age) => {
+                log::warn!(
+                    "Rate Cache - Failed to fetch rate from {base_currency} to {target_currency}, using last rate fetched at {fetched_at}: {}",
+                    err
+                );
+                return Ok((rate, fetched_at));
+            }
+            Some(_) => {
+                return Err(format!(
+                    "Rate from {base_currency} to {target_currency} is out of date: {err}"
+                )
+                .into())
+            }
+            None => return Err(err),
+        },
+    };
+    if let Err(err) = set_cached_rate(
+        redis,
+        &base_currency,
+        &target_currency,
+        rate,
+        get_rate_cache_ttl(),
+    )
+    .await
+    {
+        log::error!(
+            "Rate Cache - Failed to cache rate from {base_currency} to {target_currency}: {}",
+            err
+        );
+    }
+
+    Ok((rate, now))
+}
+
+// Refreshes all cached conversion rates that are about to expire, and are still in use.
+// Called in the background, so that rates in use are always served from the cache.
+pub async fn refresh_cached_rates(redis: &Redis) -> Result<(), CrudError> {
+    let pairs = get_expiring_rate_pairs(redis, RATE_CACHE_REFRESH_THRESHOLD).await?;
+    for (base_currency, target_currency) in pairs {
+        let rate = match fetch_currency_conversion(&base_currency, &target_currency).await {
+            Ok(rate) => rate,
+            Err(err) => {
+                log::error!(
+                    "Rate Cache - Failed to refresh rate from {base_currency} to {target_currency}: {}",
+                    err
+                );
+                continue;
+            }
+        };
+        set_cached_rate(
+            redis,
+            &base_currency,
+            &target_currency,
+            rate,
+            get_rate_cache_ttl(),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
 // List of all supported currencies
 pub const CURRENCIES: [(&str, i32); 167] = [
     ("AED", 2),
@@ -256,11 +552,63 @@ pub const CURRENCIES: [(&str, i32); 167] = [
 ];
 pub const CURRENCY_DEFAULT: (&str, i32) = ("NIL", 2);
 
+// Symbols of commonly used currencies. Dollars and others sharing a symbol are prefixed,
+// so that they can be told apart.
+const CURRENCY_SYMBOLS: [(&str, &str); 46] = [
+    ("AED", "AED "),
+    ("ARS", "AR$"),
+    ("AUD", "A$"),
+    ("BDT", "৳"),
+    ("BRL", "R$"),
+    ("CAD", "C$"),
+    ("CHF", "CHF "),
+    ("CLP", "CLP$"),
+    ("CNY", "CN¥"),
+    ("COP", "COL$"),
+    ("CZK", "Kč "),
+    ("DKK", "kr. "),
+    ("EGP", "E£"),
+    ("EUR", "€"),
+    ("GBP", "£"),
+    ("HKD", "HK$"),
+    ("HUF", "Ft "),
+    ("IDR", "Rp"),
+    ("ILS", "₪"),
+    ("INR", "₹"),
+    ("ISK", "kr "),
+    ("JPY", "¥"),
+    ("KHR", "៛"),
+    ("KRW", "₩"),
+    ("KZT", "₸"),
+    ("LAK", "₭"),
+    ("LKR", "Rs "),
+    ("MNT", "₮"),
+    ("MOP", "MOP$"),
+    ("MXN", "MX$"),
+    ("MYR", "RM"),
+    ("NGN", "₦"),
+    ("NOK", "kr "),
+    ("NZD", "NZ$"),
+    ("PHP", "₱"),
+    ("PKR", "Rs "),
+    ("PLN", "zł "),
+    ("RUB", "₽"),
+    ("SAR", "SAR "),
+    ("SEK", "kr "),
+    ("SGD", "S$"),
+    ("THB", "฿"),
+    ("TRY", "₺"),
+    ("TWD", "NT$"),
+    ("USD", "US$"),
+    ("VND", "₫"),
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[tokio::test]
+    #[ignore = "requires network"]
     async fn test_fetch_currencies_api() {
         let fetch = fetch_currency_conversion("usd", "eur").await;
         assert!(fetch.is_ok());
@@ -272,4 +620,138 @@ mod tests {
         let fetch = fetch_currency_conversion("usd", "non_existent_currency").await;
         assert!(fetch.is_err());
     }
+
+    #[test]
+    fn test_is_rate_stale() {
+        let now = 1710237600;
+        assert!(!is_rate_stale(now, now, 3600));
+        assert!(!is_rate_stale(now - 3600, now, 3600));
+        assert!(is_rate_stale(now - 3601, now, 3600));
+        assert!(is_rate_stale(now - 86400 * 2, now, RATE_MAX_AGE_DEFAULT));
+    }
+
+    #[test]
+    fn test_get_number_format() {
+        assert_eq!(get_number_format("1,234.56"), Some(NUMBER_FORMAT_DEFAULT));
+        assert_eq!(get_number_format("Comma"), Some(("1.234,56", ',')));
+        assert_eq!(get_number_format("1 234,56"), None);
+    }
+
+    #[test]
+    fn test_get_currency_format() {
+        assert_eq!(
+            get_currency_format("12.30 SGD"),
+            Some(CURRENCY_FORMAT_DEFAULT)
+        );
+        assert_eq!(get_currency_format("s$1,234.30"), Some(CURRENCY_FORMATS[3]));
+        assert_eq!(get_currency_format("Symbol"), Some(CURRENCY_FORMATS[2]));
+        assert_eq!(get_currency_format("SGD 12.30"), None);
+    }
+
+    #[test]
+    fn test_get_currency_symbol() {
+        assert_eq!(get_currency_symbol("sgd"), Some("S$"));
+        assert_eq!(get_currency_symbol("XPF"), None);
+        for (code, _) in CURRENCY_SYMBOLS {
+            assert!(get_currency_from_code(code).is_some());
+        }
+    }
+
+    #[test]
+    fn test_format_currency_amount() {
+        assert_eq!(
+            format_currency_amount("1234.30", "SGD", CURRENCY_FORMAT_DEFAULT),
+            "1234.30 SGD"
+        );
+        assert_eq!(
+            format_currency_amount("1234567.30", "SGD", CURRENCY_FORMATS[1]),
+            "1,234,567.30 SGD"
+        );
+        assert_eq!(
+            format_currency_amount("-12.30", "SGD", CURRENCY_FORMATS[2]),
+            "-S$12.30"
+        );
+        assert_eq!(
+            format_currency_amount("123456", "JPY", CURRENCY_FORMATS[3]),
+            "¥123,456"
+        );
+        assert_eq!(
+            format_currency_amount("1234.5", "XPF", CURRENCY_FORMATS[3]),
+            "1,234.5 XPF"
+        );
+        assert_eq!(
+            format_currency_amount("1234.50", "", CURRENCY_FORMATS[3]),
+            "1,234.50"
+        );
+    }
+
+    #[test]
+    fn test_normalize_amount_text() {
+        let comma_format = NUMBER_FORMATS[1];
+        assert_eq!(
+            normalize_amount_text("1,234.56", NUMBER_FORMAT_DEFAULT),
+            Some("1234.56".to_string())
+        );
+        assert_eq!(
+            normalize_amount_text("1.234,56", comma_format),
+            Some("1234.56".to_string())
+        );
+        assert_eq!(
+            normalize_amount_text("1 234 567,8", comma_format),
+            Some("1234567.8".to_string())
+        );
+        assert_eq!(
+            normalize_amount_text("1'234.56", NUMBER_FORMAT_DEFAULT),
+            Some("1234.56".to_string())
+        );
+        assert_eq!(
+            normalize_amount_text("12.5", NUMBER_FORMAT_DEFAULT),
+            Some("12.5".to_string())
+        );
+        assert_eq!(
+            normalize_amount_text("1,5", comma_format),
+            Some("1.5".to_string())
+        );
+        assert_eq!(normalize_amount_text("1,5", NUMBER_FORMAT_DEFAULT), None);
+        assert_eq!(
+            normalize_amount_text("12,34.5", NUMBER_FORMAT_DEFAULT),
+            None
+        );
+        assert_eq!(
+            normalize_amount_text("1234,567.8", NUMBER_FORMAT_DEFAULT),
+            None
+        );
+    }
+
+    #[test]
+    fn test_normalize_amount() {
+        let usd = get_currency_from_code("USD").unwrap();
+        let jpy = get_currency_from_code("JPY").unwrap();
+        let krw = get_currency_from_code("KRW").unwrap();
+        assert_eq!(normalize_amount(&usd, 12.5), 1250);
+        assert_eq!(normalize_amount(&usd, 0.125), 13);
+        assert_eq!(normalize_amount(&jpy, 1000.0), 1000);
+        assert_eq!(normalize_amount(&jpy, 333.33), 333);
+        assert_eq!(normalize_amount(&krw, 1500.5), 1501);
+        assert_eq!(normalize_amount(&jpy, -12.5), -13);
+    }
+
+    #[test]
+    fn test_count_decimal_places() {
+        assert_eq!(count_decimal_places("1000"), 0);
+        assert_eq!(count_decimal_places("12.5"), 1);
+        assert_eq!(count_decimal_places("12.50"), 1);
+        assert_eq!(count_decimal_places("1000.00"), 0);
+        assert_eq!(count_decimal_places("0.125"), 3);
+    }
+
+    #[test]
+    fn test_convert_currency_with_rate() {
+        assert_eq!(
+            convert_currency_with_rate(1000, "USD", "JPY", 150.123),
+            1501
+        );
+        assert_eq!(convert_currency_with_rate(1001, "JPY", "USD", 0.00667), 668);
+        assert_eq!(convert_currency_with_rate(1001, "JPY", "KRW", 9.05), 9059);
+    }
 }
@@ -0,0 +1,167 @@
+/* Detect recognises payments mentioned in plain messages, for the passive mode of a chat.
+ * Only messages that closely follow a pattern like "paid 30 for lunch with @bob" are recognised,
+ * starting with the payment, and naming the amount, what it was for, and who it was shared with.
+ * Anything looser, such as questions or long messages, is left alone to avoid false positives.
+ */
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::bot::currency::{get_currency_from_code, Currency};
+
+const DETECT_MESSAGE_MAX_LENGTH: usize = 120;
+const DETECT_USERS_MAX: usize = 10;
+const DETECT_PATTERN: &str = r"(?i)^(?:i\s+)?(?:just\s+)?(?:paid|spent|covered)\s+(?:(?P<symbol>[$€£])\s?)?(?:(?P<prefix>[a-z]{3})\s+)?(?P<amount>\d+(?:[.,]\d+)*)(?:\s?(?P<suffix>[a-z]{3}))?\s+(?:for|on)\s+(?P<description>[^@?\n]{1,50}?)\s+with\s+(?P<users>@\w+(?:(?:\s*,\s*|\s+and\s+|\s*&\s*|\s+)@\w+)*)\s*[.!]*$";
+
+static DETECT_REGEX: OnceLock<Regex> = OnceLock::new();
+
+// Represents a payment recognised in a message, paid by the sender and shared equally.
+// The amount is kept as written, to be read in the number format of the chat.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedPayment {
+    pub description: String,
+    pub amount: String,
+    pub currency: Option<Currency>,
+    pub users: Vec<String>,
+}
+
+// Gets the currency of a detected amount, from its code or symbol
+// Dollar signs are left to the default currency of the chat, as many currencies use them
+fn detect_currency(code: Option<&str>, symbol: Option<&str>) -> Result<Option<Currency>, ()> {
+    match (code, symbol) {
+        (Some(code), _) => match get_currency_from_code(&code.to_uppercase()) {
+            Some(currency) => Ok(Some(currency)),
+            None => Err(()),
+        },
+        (None, Some("€")) => Ok(get_currency_from_code("EUR")),
+        (None, Some("£")) => Ok(get_currency_from_code("GBP")),
+        _ => Ok(None),
+    }
+}
+
+/* Recognises a payment in a plain message, such as "paid 30 for lunch with @bob".
+ * The amount can have a currency code before or after it, or a currency symbol.
+ * Returns None if the message does not closely follow the pattern,
+ * or if it names an unknown currency.
+ */
+pub fn detect_payment(text: &str) -> Option<DetectedPayment> {
+    let text = text.trim();
+    if text.chars().count() > DETECT_MESSAGE_MAX_LENGTH {
+        return None;
+    }
+
+    let regex = DETECT_REGEX.get_or_init(|| Regex::new(DETECT_PATTERN).unwrap());
+    let captures = regex.captures(text)?;
+
+    let code = match (captures.name("prefix"), captures.name("suffix")) {
+        (Some(_), Some(_)) => return None,
+        (Some(code), None) | (None, Some(code)) => Some(code.as_str()),
+        (None, None) => None,
+    };
+    let symbol = captures.name("symbol").map(|symbol| symbol.as_str());
+    let currency = detect_currency(code, symbol).ok()?;
+
+    let description = captures.name("description")?.as_str().trim().to_string();
+    if !description.chars().any(|c| c.is_alphabetic()) {
+        return None;
+    }
+
+    let mut users: Vec<String> = Vec::new();
+    for user in captures.name("users")?.as_str().split('@').skip(1) {
+        let user = user.trim_end_matches(|c: char| !(c.is_alphanumeric() || c == '_'));
+        let user = user
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        if !users
+            .iter()
+            .any(|other| other.to_lowercase() == user.to_lowercase())
+        {
+            users.push(user);
+        }
+    }
+    if users.is_empty() || users.len() > DETECT_USERS_MAX {
+        return None;
+    }
+
+    Some(DetectedPayment {
+        description,
+        amount: captures.name("amount")?.as_str().to_string(),
+        currency,
+        users,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_payment() {
+        assert_eq!(
+            detect_payment("paid 30 for lunch with @bob_smith"),
+            Some(DetectedPayment {
+                description: "lunch".to_string(),
+                amount: "30".to_string(),
+                currency: None,
+                users: vec!["bob_smith".to_string()],
+            })
+        );
+        assert_eq!(
+            detect_payment(
+                "I just paid 45.50 SGD for the taxi with @alice_tan, @bob_smith and @Alice_Tan!"
+            ),
+            Some(DetectedPayment {
+                description: "the taxi".to_string(),
+                amount: "45.50".to_string(),
+                currency: get_currency_from_code("SGD"),
+                users: vec!["alice_tan".to_string(), "bob_smith".to_string()],
+            })
+        );
+        assert_eq!(
+            detect_payment("Spent €12 on coffee with @carol_lim"),
+            Some(DetectedPayment {
+                description: "coffee".to_string(),
+                amount: "12".to_string(),
+                currency: get_currency_from_code("EUR"),
+                users: vec!["carol_lim".to_string()],
+            })
+        );
+        assert_eq!(
+            detect_payment("covered usd 1,200 for the hotel with @dave_lee @erin_ong"),
+            Some(DetectedPayment {
+                description: "the hotel".to_string(),
+                amount: "1,200".to_string(),
+                currency: get_currency_from_code("USD"),
+                users: vec!["dave_lee".to_string(), "erin_ong".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_detect_payment_ignored() {
+        // Questions and chatter
+        assert_eq!(
+            detect_payment("who paid 30 for lunch with @bob_smith?"),
+            None
+        );
+        assert_eq!(detect_payment("paid 30 for lunch with @bob_smith?"), None);
+        assert_eq!(detect_payment("I paid for lunch with @bob_smith"), None);
+        assert_eq!(detect_payment("paid 30 for lunch"), None);
+        assert_eq!(detect_payment("lunch with @bob_smith was 30"), None);
+        // Unknown currencies and descriptions without words
+        assert_eq!(
+            detect_payment("paid 30 abc for lunch with @bob_smith"),
+            None
+        );
+        assert_eq!(
+            detect_payment("paid the 30 for lunch with @bob_smith"),
+            None
+        );
+        assert_eq!(detect_payment("paid 30 for 2 with @bob_smith"), None);
+        // Messages that are too long
+        let long = format!("paid 30 for {} with @bob_smith", "lunch ".repeat(20));
+        assert_eq!(detect_payment(&long), None);
+    }
+}
@@ -1,5 +1,6 @@
 use teloxide::{
     dispatching::dialogue::{self, InMemStorage},
+    error_handlers::LoggingErrorHandler,
     prelude::*,
     types::MessageId,
     utils::command::BotCommands,
@@ -7,7 +8,13 @@ use teloxide::{
 
 use crate::bot::handler::*;
 
-use super::currency::Currency;
+use super::{
+    currency::Currency,
+    listener::ReactionListener,
+    redis::{Debt, Redis},
+    scheduler::run_scheduler,
+    shutdown::Shutdown,
+};
 
 /* Dispatcher handles conversation branches with the user.
  * Bot states, commands, and control flow are defined here.
@@ -18,54 +25,74 @@ pub enum State {
     #[default]
     Start,
     AddDescription {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
     },
     AddCreditor {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         payment: AddPaymentParams,
     },
     AddTotal {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
+        payment: AddPaymentParams,
+    },
+    AddTotalCurrency {
+        messages: MessageTracker,
         payment: AddPaymentParams,
     },
     AddDebtSelection {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         payment: AddPaymentParams,
     },
     AddDebt {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         payment: AddPaymentParams,
         debts_format: AddDebtsFormat,
     },
+    AddDebtPicker {
+        messages: MessageTracker,
+        payment: AddPaymentParams,
+        users: Vec<String>,
+        selected: Vec<String>,
+    },
+    AddDebtItems {
+        messages: MessageTracker,
+        payment: AddPaymentParams,
+        items: Vec<DebtItem>,
+    },
     AddConfirm {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         payment: AddPaymentParams,
     },
     AddEditMenu {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         payment: AddPaymentParams,
     },
     AddEditDebtsMenu {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         payment: AddPaymentParams,
     },
     AddEdit {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         payment: AddPaymentParams,
         edit: AddPaymentEdit,
     },
+    PayBackCreditors {
+        messages: MessageTracker,
+        debts: Vec<Debt>,
+        selected: Vec<usize>,
+    },
     PayBackCurrencyMenu {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
     },
     PayBackCurrency {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
     },
     PayBackDebts {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         currency: Currency,
     },
     PayBackConfirm {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         payment: PayBackParams,
     },
     ViewPayments {
@@ -73,164 +100,520 @@ pub enum State {
         page: usize,
     },
     SelectPayment {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         payments: Vec<Payment>,
         page: usize,
         function: SelectPaymentType,
     },
     EditPayment {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         payment: Payment,
         edited_payment: EditPaymentParams,
         payments: Vec<Payment>,
         page: usize,
     },
     EditPaymentDebtSelection {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         payment: Payment,
         edited_payment: EditPaymentParams,
         payments: Vec<Payment>,
         page: usize,
     },
+    EditPaymentDebtPicker {
+        messages: MessageTracker,
+        payment: Payment,
+        edited_payment: EditPaymentParams,
+        users: Vec<String>,
+        selected: Vec<String>,
+        payments: Vec<Payment>,
+        page: usize,
+    },
     EditPaymentDetails {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
         payment: Payment,
         edited_payment: EditPaymentParams,
         edit: AddPaymentEdit,
         payments: Vec<Payment>,
         page: usize,
     },
+    SelectPaymentsDelete {
+        messages: MessageTracker,
+        payments: Vec<Payment>,
+        page: usize,
+        selected: Vec<usize>,
+    },
     DeletePayment {
-        messages: Vec<MessageId>,
-        payment: Payment,
+        messages: MessageTracker,
+        selected_payments: Vec<Payment>,
+        payments: Vec<Payment>,
+        page: usize,
+    },
+    PaymentCard {
+        messages: MessageTracker,
+        payments: Vec<Payment>,
+        page: usize,
+        index: usize,
+    },
+    PaymentCardDetails {
+        messages: MessageTracker,
         payments: Vec<Payment>,
         page: usize,
+        index: usize,
+        detail: PaymentCardDetail,
     },
     BalancesMenu,
+    BalancesConvertTo {
+        messages: MessageTracker,
+        balances_id: MessageId,
+    },
     SpendingsMenu,
     SettingsMenu {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
     },
     SettingsTimeZoneMenu {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
     },
-    SettingsTimeZone {
-        messages: Vec<MessageId>,
+    SettingsTimeZoneRegion {
+        messages: MessageTracker,
     },
     SettingsDefaultCurrencyMenu {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
     },
     SettingsDefaultCurrency {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
     },
     SettingsCurrencyConversion {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
     },
     SettingsEraseMessages {
-        messages: Vec<MessageId>,
+        messages: MessageTracker,
+    },
+    SettingsMonthlyArchive {
+        messages: MessageTracker,
+    },
+    SettingsWeeklyDigestMenu {
+        messages: MessageTracker,
+    },
+    SettingsWeeklyDigest {
+        messages: MessageTracker,
+    },
+    SettingsDebtReminderMenu {
+        messages: MessageTracker,
     },
+    SettingsDebtReminder {
+        messages: MessageTracker,
+    },
+    SettingsPaymentApprovalMenu {
+        messages: MessageTracker,
+    },
+    SettingsPaymentApproval {
+        messages: MessageTracker,
+    },
+    SettingsLanguageMenu {
+        messages: MessageTracker,
+    },
+    SettingsNumberFormatMenu {
+        messages: MessageTracker,
+    },
+    SettingsCurrencyFormatMenu {
+        messages: MessageTracker,
+    },
+    SettingsVerbosityMenu {
+        messages: MessageTracker,
+    },
+    SettingsRoundingMenu {
+        messages: MessageTracker,
+    },
+    SettingsWeekStartMenu {
+        messages: MessageTracker,
+    },
+    SettingsAdminOnly {
+        messages: MessageTracker,
+    },
+    SettingsTreasurerMenu {
+        messages: MessageTracker,
+    },
+    SettingsTreasurer {
+        messages: MessageTracker,
+    },
+}
+
+impl State {
+    // Gets the messages tracked in the current operation, for states that are part of one
+    pub fn messages_mut(&mut self) -> Option<&mut MessageTracker> {
+        match self {
+            State::AddDescription { messages, .. }
+            | State::AddCreditor { messages, .. }
+            | State::AddTotal { messages, .. }
+            | State::AddTotalCurrency { messages, .. }
+            | State::AddDebtSelection { messages, .. }
+            | State::AddDebt { messages, .. }
+            | State::AddDebtPicker { messages, .. }
+            | State::AddDebtItems { messages, .. }
+            | State::AddConfirm { messages, .. }
+            | State::AddEditMenu { messages, .. }
+            | State::AddEditDebtsMenu { messages, .. }
+            | State::AddEdit { messages, .. }
+            | State::PayBackCreditors { messages, .. }
+            | State::PayBackCurrencyMenu { messages, .. }
+            | State::PayBackCurrency { messages, .. }
+            | State::PayBackDebts { messages, .. }
+            | State::PayBackConfirm { messages, .. }
+            | State::SelectPayment { messages, .. }
+            | State::EditPayment { messages, .. }
+            | State::EditPaymentDebtSelection { messages, .. }
+            | State::EditPaymentDebtPicker { messages, .. }
+            | State::EditPaymentDetails { messages, .. }
+            | State::SelectPaymentsDelete { messages, .. }
+            | State::DeletePayment { messages, .. }
+            | State::PaymentCard { messages, .. }
+            | State::PaymentCardDetails { messages, .. }
+            | State::BalancesConvertTo { messages, .. }
+            | State::SettingsMenu { messages, .. }
+            | State::SettingsTimeZoneMenu { messages, .. }
+            | State::SettingsTimeZoneRegion { messages, .. }
+            | State::SettingsDefaultCurrencyMenu { messages, .. }
+            | State::SettingsDefaultCurrency { messages, .. }
+            | State::SettingsCurrencyConversion { messages, .. }
+            | State::SettingsEraseMessages { messages, .. }
+            | State::SettingsMonthlyArchive { messages, .. }
+            | State::SettingsWeeklyDigestMenu { messages, .. }
+            | State::SettingsWeeklyDigest { messages, .. }
+            | State::SettingsDebtReminderMenu { messages, .. }
+            | State::SettingsDebtReminder { messages, .. }
+            | State::SettingsPaymentApprovalMenu { messages, .. }
+            | State::SettingsPaymentApproval { messages, .. }
+            | State::SettingsLanguageMenu { messages, .. }
+            | State::SettingsNumberFormatMenu { messages, .. }
+            | State::SettingsCurrencyFormatMenu { messages, .. }
+            | State::SettingsVerbosityMenu { messages, .. }
+            | State::SettingsRoundingMenu { messages, .. }
+            | State::SettingsWeekStartMenu { messages, .. }
+            | State::SettingsAdminOnly { messages, .. }
+            | State::SettingsTreasurerMenu { messages, .. }
+            | State::SettingsTreasurer { messages, .. } => Some(messages),
+            _ => None,
+        }
+    }
+}
+
+// Tracks a message sent by a user during an operation, such as a reply to a prompt,
+// so that it can be erased along with the bot messages once the operation ends
+fn track_user_message(mut state: State, msg: Message) -> State {
+    if let Some(messages) = state.messages_mut() {
+        messages.track_user(msg.id);
+    }
+    state
 }
 
 #[derive(BotCommands, Clone)]
 #[command(rename_rule = "lowercase")]
 pub enum Command {
-    #[command(description = "Start me\\!")]
+    #[command(description = "Start me!")]
     Start,
-    #[command(description = "Show this message")]
-    Help,
+    #[command(description = "Show all my commands, or how to use one, like /help addpayment")]
+    Help(String),
     #[command(description = "Add a new payment")]
     AddPayment,
     #[command(description = "Add a record of paying back a debt")]
     PayBack,
-    #[command(description = "View all payment records")]
-    ViewPayments,
+    #[command(description = "View all payment records, or only unsettled ones")]
+    ViewPayments(String),
     #[command(description = "Edit a previous payment")]
     EditPayment,
     #[command(description = "Delete a previous payment")]
     DeletePayment,
+    #[command(description = "Duplicate a previous payment, dated today")]
+    DuplicatePayment,
     #[command(description = "View the current balances for everyone")]
     Balances,
     #[command(description = "View the total spendings for everyone")]
     Spendings,
+    #[command(description = "View where two people stand with each other")]
+    Owed(String),
     #[command(description = "View and edit my settings for everyone")]
-    Settings,
+    Settings(String),
+    #[command(description = "Start, end, or view a trip")]
+    Trip(String),
+    #[command(description = "View monthly spending stats")]
+    Stats,
+    #[command(description = "Remind everyone of their outstanding balances")]
+    Remind,
+    #[command(description = "Set or view monthly spending limits")]
+    Limit(String),
+    #[command(description = "Save or use payment templates")]
+    Template(String),
+    #[command(description = "Set names to show for people")]
+    Alias(String),
+    #[command(description = "Correct someone's balance, for admins")]
+    Adjust(String),
+    #[command(description = "Back up everything in this chat as a file")]
+    Archive,
+    #[command(description = "Restore this chat from a backup file")]
+    Restore(String),
+    #[command(description = "Recalculate all balances from the payments")]
+    Recalculate,
+    #[command(description = "View the latest changes to payments")]
+    Audit,
+    #[command(description = "View deleted payments, and restore them")]
+    Trash,
+    #[command(description = "View the private payments you're in, in a direct message")]
+    Private,
+    #[command(
+        description = "Net your debts with each person across all your groups, in a direct message"
+    )]
+    Consolidate,
+    #[command(description = "Set the link or phone number others can pay you back with")]
+    PayLink(String),
+    #[command(description = "View who paid the most, owes most often, and settles up fastest")]
+    Leaderboard(String),
+    #[command(description = "Create groups of people you often split payments with")]
+    Group(String),
+    #[command(description = "Import payments from a CSV file, such as a Splitwise export")]
+    Import(String),
+    #[command(description = "Split an amount equally right away, like /split 60 USD dinner")]
+    Split(String),
+    #[command(description = "Try me out with practice payments that won't touch your balances")]
+    Tutorial,
+    #[command(description = "off")]
+    DebugBalances(String),
+    #[command(description = "off")]
+    BotStats,
     #[command(description = "Cancel whatever I'm doing")]
     Cancel,
 }
 
 /* Main Dispatch function */
-pub async fn run_dispatcher(bot: Bot) {
+pub async fn run_dispatcher(redis: Redis, bot: Bot) {
     use dptree::case;
 
     let command_handler = teloxide::filter_command::<Command, _>()
         .branch(
             case![State::Start]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(action_cancel))
                 .branch(case![Command::AddPayment].endpoint(action_add_payment))
                 .branch(case![Command::Balances].endpoint(action_view_balances))
+                .branch(case![Command::Owed(args)].endpoint(action_owed))
                 .branch(case![Command::PayBack].endpoint(action_pay_back))
-                .branch(case![Command::ViewPayments].endpoint(action_view_payments))
+                .branch(case![Command::ViewPayments(args)].endpoint(action_view_payments))
                 .branch(case![Command::EditPayment].endpoint(no_edit_payment))
                 .branch(case![Command::DeletePayment].endpoint(no_delete_payment))
-                .branch(case![Command::Settings].endpoint(action_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(no_duplicate_payment))
+                .branch(case![Command::Settings(args)].endpoint(action_settings))
+                .branch(case![Command::Trip(args)].endpoint(action_trip))
+                .branch(case![Command::Stats].endpoint(action_stats))
+                .branch(case![Command::Remind].endpoint(action_remind))
+                .branch(case![Command::Limit(args)].endpoint(action_limit))
+                .branch(case![Command::Adjust(args)].endpoint(action_adjust))
+                .branch(case![Command::Template(args)].endpoint(action_template))
+                .branch(case![Command::Alias(args)].endpoint(action_alias))
+                .branch(case![Command::Archive].endpoint(action_archive))
+                .branch(case![Command::Restore(args)].endpoint(action_restore))
+                .branch(case![Command::Recalculate].endpoint(action_recalculate))
+                .branch(case![Command::Audit].endpoint(action_audit))
+                .branch(case![Command::Trash].endpoint(action_trash))
+                .branch(case![Command::Tutorial].endpoint(action_tutorial))
+                .branch(case![Command::Private].endpoint(action_private))
+                .branch(case![Command::Consolidate].endpoint(action_consolidate))
+                .branch(case![Command::PayLink(args)].endpoint(action_paylink))
+                .branch(case![Command::Leaderboard(args)].endpoint(action_leaderboard))
+                .branch(case![Command::Group(args)].endpoint(action_split_group))
+                .branch(case![Command::Import(args)].endpoint(action_import))
+                .branch(case![Command::Split(args)].endpoint(action_split))
                 .branch(case![Command::Spendings].endpoint(action_view_spendings)),
         )
         .branch(
             case![State::AddDescription { messages }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_add_payment))
                 .branch(case![Command::AddPayment].endpoint(handle_repeated_add_payment))
                 .branch(case![Command::Balances].endpoint(block_add_payment))
+                .branch(case![Command::Owed(args)].endpoint(block_add_payment))
                 .branch(case![Command::PayBack].endpoint(block_add_payment))
-                .branch(case![Command::ViewPayments].endpoint(block_add_payment))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_add_payment))
                 .branch(case![Command::EditPayment].endpoint(block_add_payment))
                 .branch(case![Command::DeletePayment].endpoint(block_add_payment))
-                .branch(case![Command::Settings].endpoint(block_add_payment))
+                .branch(case![Command::DuplicatePayment].endpoint(block_add_payment))
+                .branch(case![Command::Settings(args)].endpoint(block_add_payment))
+                .branch(case![Command::Trip(args)].endpoint(block_add_payment))
+                .branch(case![Command::Stats].endpoint(block_add_payment))
+                .branch(case![Command::Remind].endpoint(block_add_payment))
+                .branch(case![Command::Limit(args)].endpoint(block_add_payment))
+                .branch(case![Command::Adjust(args)].endpoint(block_add_payment))
+                .branch(case![Command::Template(args)].endpoint(block_add_payment))
+                .branch(case![Command::Alias(args)].endpoint(block_add_payment))
+                .branch(case![Command::Archive].endpoint(block_add_payment))
+                .branch(case![Command::Restore(args)].endpoint(block_add_payment))
+                .branch(case![Command::Recalculate].endpoint(block_add_payment))
+                .branch(case![Command::Audit].endpoint(block_add_payment))
+                .branch(case![Command::Trash].endpoint(block_add_payment))
+                .branch(case![Command::Tutorial].endpoint(block_add_payment))
+                .branch(case![Command::Private].endpoint(block_add_payment))
+                .branch(case![Command::Consolidate].endpoint(block_add_payment))
+                .branch(case![Command::PayLink(args)].endpoint(block_add_payment))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_add_payment))
+                .branch(case![Command::Group(args)].endpoint(block_add_payment))
+                .branch(case![Command::Import(args)].endpoint(block_add_payment))
+                .branch(case![Command::Split(args)].endpoint(block_add_payment))
                 .branch(case![Command::Spendings].endpoint(block_add_payment)),
         )
         .branch(
             case![State::AddCreditor { messages, payment }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_add_payment))
                 .branch(case![Command::AddPayment].endpoint(handle_repeated_add_payment))
                 .branch(case![Command::Balances].endpoint(block_add_payment))
+                .branch(case![Command::Owed(args)].endpoint(block_add_payment))
                 .branch(case![Command::PayBack].endpoint(block_add_payment))
-                .branch(case![Command::ViewPayments].endpoint(block_add_payment))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_add_payment))
                 .branch(case![Command::EditPayment].endpoint(block_add_payment))
                 .branch(case![Command::DeletePayment].endpoint(block_add_payment))
-                .branch(case![Command::Settings].endpoint(block_add_payment))
+                .branch(case![Command::DuplicatePayment].endpoint(block_add_payment))
+                .branch(case![Command::Settings(args)].endpoint(block_add_payment))
+                .branch(case![Command::Trip(args)].endpoint(block_add_payment))
+                .branch(case![Command::Stats].endpoint(block_add_payment))
+                .branch(case![Command::Remind].endpoint(block_add_payment))
+                .branch(case![Command::Limit(args)].endpoint(block_add_payment))
+                .branch(case![Command::Adjust(args)].endpoint(block_add_payment))
+                .branch(case![Command::Template(args)].endpoint(block_add_payment))
+                .branch(case![Command::Alias(args)].endpoint(block_add_payment))
+                .branch(case![Command::Archive].endpoint(block_add_payment))
+                .branch(case![Command::Restore(args)].endpoint(block_add_payment))
+                .branch(case![Command::Recalculate].endpoint(block_add_payment))
+                .branch(case![Command::Audit].endpoint(block_add_payment))
+                .branch(case![Command::Trash].endpoint(block_add_payment))
+                .branch(case![Command::Tutorial].endpoint(block_add_payment))
+                .branch(case![Command::Private].endpoint(block_add_payment))
+                .branch(case![Command::Consolidate].endpoint(block_add_payment))
+                .branch(case![Command::PayLink(args)].endpoint(block_add_payment))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_add_payment))
+                .branch(case![Command::Group(args)].endpoint(block_add_payment))
+                .branch(case![Command::Import(args)].endpoint(block_add_payment))
+                .branch(case![Command::Split(args)].endpoint(block_add_payment))
                 .branch(case![Command::Spendings].endpoint(block_add_payment)),
         )
         .branch(
             case![State::AddTotal { messages, payment }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_add_payment))
+                .branch(case![Command::AddPayment].endpoint(handle_repeated_add_payment))
+                .branch(case![Command::Balances].endpoint(block_add_payment))
+                .branch(case![Command::Owed(args)].endpoint(block_add_payment))
+                .branch(case![Command::PayBack].endpoint(block_add_payment))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_add_payment))
+                .branch(case![Command::EditPayment].endpoint(block_add_payment))
+                .branch(case![Command::DeletePayment].endpoint(block_add_payment))
+                .branch(case![Command::DuplicatePayment].endpoint(block_add_payment))
+                .branch(case![Command::Settings(args)].endpoint(block_add_payment))
+                .branch(case![Command::Trip(args)].endpoint(block_add_payment))
+                .branch(case![Command::Stats].endpoint(block_add_payment))
+                .branch(case![Command::Remind].endpoint(block_add_payment))
+                .branch(case![Command::Limit(args)].endpoint(block_add_payment))
+                .branch(case![Command::Adjust(args)].endpoint(block_add_payment))
+                .branch(case![Command::Template(args)].endpoint(block_add_payment))
+                .branch(case![Command::Alias(args)].endpoint(block_add_payment))
+                .branch(case![Command::Archive].endpoint(block_add_payment))
+                .branch(case![Command::Restore(args)].endpoint(block_add_payment))
+                .branch(case![Command::Recalculate].endpoint(block_add_payment))
+                .branch(case![Command::Audit].endpoint(block_add_payment))
+                .branch(case![Command::Trash].endpoint(block_add_payment))
+                .branch(case![Command::Tutorial].endpoint(block_add_payment))
+                .branch(case![Command::Private].endpoint(block_add_payment))
+                .branch(case![Command::Consolidate].endpoint(block_add_payment))
+                .branch(case![Command::PayLink(args)].endpoint(block_add_payment))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_add_payment))
+                .branch(case![Command::Group(args)].endpoint(block_add_payment))
+                .branch(case![Command::Import(args)].endpoint(block_add_payment))
+                .branch(case![Command::Split(args)].endpoint(block_add_payment))
+                .branch(case![Command::Spendings].endpoint(block_add_payment)),
+        )
+        .branch(
+            case![State::AddTotalCurrency { messages, payment }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_add_payment))
                 .branch(case![Command::AddPayment].endpoint(handle_repeated_add_payment))
                 .branch(case![Command::Balances].endpoint(block_add_payment))
+                .branch(case![Command::Owed(args)].endpoint(block_add_payment))
                 .branch(case![Command::PayBack].endpoint(block_add_payment))
-                .branch(case![Command::ViewPayments].endpoint(block_add_payment))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_add_payment))
                 .branch(case![Command::EditPayment].endpoint(block_add_payment))
                 .branch(case![Command::DeletePayment].endpoint(block_add_payment))
-                .branch(case![Command::Settings].endpoint(block_add_payment))
+                .branch(case![Command::DuplicatePayment].endpoint(block_add_payment))
+                .branch(case![Command::Settings(args)].endpoint(block_add_payment))
+                .branch(case![Command::Trip(args)].endpoint(block_add_payment))
+                .branch(case![Command::Stats].endpoint(block_add_payment))
+                .branch(case![Command::Remind].endpoint(block_add_payment))
+                .branch(case![Command::Limit(args)].endpoint(block_add_payment))
+                .branch(case![Command::Adjust(args)].endpoint(block_add_payment))
+                .branch(case![Command::Template(args)].endpoint(block_add_payment))
+                .branch(case![Command::Alias(args)].endpoint(block_add_payment))
+                .branch(case![Command::Archive].endpoint(block_add_payment))
+                .branch(case![Command::Restore(args)].endpoint(block_add_payment))
+                .branch(case![Command::Recalculate].endpoint(block_add_payment))
+                .branch(case![Command::Audit].endpoint(block_add_payment))
+                .branch(case![Command::Trash].endpoint(block_add_payment))
+                .branch(case![Command::Tutorial].endpoint(block_add_payment))
+                .branch(case![Command::Private].endpoint(block_add_payment))
+                .branch(case![Command::Consolidate].endpoint(block_add_payment))
+                .branch(case![Command::PayLink(args)].endpoint(block_add_payment))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_add_payment))
+                .branch(case![Command::Group(args)].endpoint(block_add_payment))
+                .branch(case![Command::Import(args)].endpoint(block_add_payment))
+                .branch(case![Command::Split(args)].endpoint(block_add_payment))
                 .branch(case![Command::Spendings].endpoint(block_add_payment)),
         )
         .branch(
             case![State::AddDebtSelection { messages, payment }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_add_payment))
                 .branch(case![Command::AddPayment].endpoint(handle_repeated_add_payment))
                 .branch(case![Command::Balances].endpoint(block_add_payment))
+                .branch(case![Command::Owed(args)].endpoint(block_add_payment))
                 .branch(case![Command::PayBack].endpoint(block_add_payment))
-                .branch(case![Command::ViewPayments].endpoint(block_add_payment))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_add_payment))
                 .branch(case![Command::EditPayment].endpoint(block_add_payment))
                 .branch(case![Command::DeletePayment].endpoint(block_add_payment))
-                .branch(case![Command::Settings].endpoint(block_add_payment))
+                .branch(case![Command::DuplicatePayment].endpoint(block_add_payment))
+                .branch(case![Command::Settings(args)].endpoint(block_add_payment))
+                .branch(case![Command::Trip(args)].endpoint(block_add_payment))
+                .branch(case![Command::Stats].endpoint(block_add_payment))
+                .branch(case![Command::Remind].endpoint(block_add_payment))
+                .branch(case![Command::Limit(args)].endpoint(block_add_payment))
+                .branch(case![Command::Adjust(args)].endpoint(block_add_payment))
+                .branch(case![Command::Template(args)].endpoint(block_add_payment))
+                .branch(case![Command::Alias(args)].endpoint(block_add_payment))
+                .branch(case![Command::Archive].endpoint(block_add_payment))
+                .branch(case![Command::Restore(args)].endpoint(block_add_payment))
+                .branch(case![Command::Recalculate].endpoint(block_add_payment))
+                .branch(case![Command::Audit].endpoint(block_add_payment))
+                .branch(case![Command::Trash].endpoint(block_add_payment))
+                .branch(case![Command::Tutorial].endpoint(block_add_payment))
+                .branch(case![Command::Private].endpoint(block_add_payment))
+                .branch(case![Command::Consolidate].endpoint(block_add_payment))
+                .branch(case![Command::PayLink(args)].endpoint(block_add_payment))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_add_payment))
+                .branch(case![Command::Group(args)].endpoint(block_add_payment))
+                .branch(case![Command::Import(args)].endpoint(block_add_payment))
+                .branch(case![Command::Split(args)].endpoint(block_add_payment))
                 .branch(case![Command::Spendings].endpoint(block_add_payment)),
         )
         .branch(
@@ -240,43 +623,238 @@ pub async fn run_dispatcher(bot: Bot) {
                 debts_format
             }]
             .branch(case![Command::Start].endpoint(action_start))
-            .branch(case![Command::Help].endpoint(action_help))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
+            .branch(case![Command::Cancel].endpoint(cancel_add_payment))
+            .branch(case![Command::AddPayment].endpoint(handle_repeated_add_payment))
+            .branch(case![Command::Balances].endpoint(block_add_payment))
+            .branch(case![Command::Owed(args)].endpoint(block_add_payment))
+            .branch(case![Command::PayBack].endpoint(block_add_payment))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_add_payment))
+            .branch(case![Command::EditPayment].endpoint(block_add_payment))
+            .branch(case![Command::DeletePayment].endpoint(block_add_payment))
+            .branch(case![Command::DuplicatePayment].endpoint(block_add_payment))
+            .branch(case![Command::Settings(args)].endpoint(block_add_payment))
+            .branch(case![Command::Trip(args)].endpoint(block_add_payment))
+            .branch(case![Command::Stats].endpoint(block_add_payment))
+            .branch(case![Command::Remind].endpoint(block_add_payment))
+            .branch(case![Command::Limit(args)].endpoint(block_add_payment))
+            .branch(case![Command::Adjust(args)].endpoint(block_add_payment))
+            .branch(case![Command::Template(args)].endpoint(block_add_payment))
+            .branch(case![Command::Alias(args)].endpoint(block_add_payment))
+            .branch(case![Command::Archive].endpoint(block_add_payment))
+            .branch(case![Command::Restore(args)].endpoint(block_add_payment))
+            .branch(case![Command::Recalculate].endpoint(block_add_payment))
+            .branch(case![Command::Audit].endpoint(block_add_payment))
+            .branch(case![Command::Trash].endpoint(block_add_payment))
+            .branch(case![Command::Tutorial].endpoint(block_add_payment))
+            .branch(case![Command::Private].endpoint(block_add_payment))
+            .branch(case![Command::Consolidate].endpoint(block_add_payment))
+            .branch(case![Command::PayLink(args)].endpoint(block_add_payment))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_add_payment))
+            .branch(case![Command::Group(args)].endpoint(block_add_payment))
+            .branch(case![Command::Import(args)].endpoint(block_add_payment))
+            .branch(case![Command::Split(args)].endpoint(block_add_payment))
+            .branch(case![Command::Spendings].endpoint(block_add_payment)),
+        )
+        .branch(
+            case![State::AddDebtPicker {
+                messages,
+                payment,
+                users,
+                selected
+            }]
+            .branch(case![Command::Start].endpoint(action_start))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
+            .branch(case![Command::Cancel].endpoint(cancel_add_payment))
+            .branch(case![Command::AddPayment].endpoint(handle_repeated_add_payment))
+            .branch(case![Command::Balances].endpoint(block_add_payment))
+            .branch(case![Command::Owed(args)].endpoint(block_add_payment))
+            .branch(case![Command::PayBack].endpoint(block_add_payment))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_add_payment))
+            .branch(case![Command::EditPayment].endpoint(block_add_payment))
+            .branch(case![Command::DeletePayment].endpoint(block_add_payment))
+            .branch(case![Command::DuplicatePayment].endpoint(block_add_payment))
+            .branch(case![Command::Settings(args)].endpoint(block_add_payment))
+            .branch(case![Command::Trip(args)].endpoint(block_add_payment))
+            .branch(case![Command::Stats].endpoint(block_add_payment))
+            .branch(case![Command::Remind].endpoint(block_add_payment))
+            .branch(case![Command::Limit(args)].endpoint(block_add_payment))
+            .branch(case![Command::Adjust(args)].endpoint(block_add_payment))
+            .branch(case![Command::Template(args)].endpoint(block_add_payment))
+            .branch(case![Command::Alias(args)].endpoint(block_add_payment))
+            .branch(case![Command::Archive].endpoint(block_add_payment))
+            .branch(case![Command::Restore(args)].endpoint(block_add_payment))
+            .branch(case![Command::Recalculate].endpoint(block_add_payment))
+            .branch(case![Command::Audit].endpoint(block_add_payment))
+            .branch(case![Command::Trash].endpoint(block_add_payment))
+            .branch(case![Command::Tutorial].endpoint(block_add_payment))
+            .branch(case![Command::Private].endpoint(block_add_payment))
+            .branch(case![Command::Consolidate].endpoint(block_add_payment))
+            .branch(case![Command::PayLink(args)].endpoint(block_add_payment))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_add_payment))
+            .branch(case![Command::Group(args)].endpoint(block_add_payment))
+            .branch(case![Command::Import(args)].endpoint(block_add_payment))
+            .branch(case![Command::Split(args)].endpoint(block_add_payment))
+            .branch(case![Command::Spendings].endpoint(block_add_payment)),
+        )
+        .branch(
+            case![State::AddDebtItems {
+                messages,
+                payment,
+                items
+            }]
+            .branch(case![Command::Start].endpoint(action_start))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
             .branch(case![Command::Cancel].endpoint(cancel_add_payment))
             .branch(case![Command::AddPayment].endpoint(handle_repeated_add_payment))
             .branch(case![Command::Balances].endpoint(block_add_payment))
+            .branch(case![Command::Owed(args)].endpoint(block_add_payment))
             .branch(case![Command::PayBack].endpoint(block_add_payment))
-            .branch(case![Command::ViewPayments].endpoint(block_add_payment))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_add_payment))
             .branch(case![Command::EditPayment].endpoint(block_add_payment))
             .branch(case![Command::DeletePayment].endpoint(block_add_payment))
-            .branch(case![Command::Settings].endpoint(block_add_payment))
+            .branch(case![Command::DuplicatePayment].endpoint(block_add_payment))
+            .branch(case![Command::Settings(args)].endpoint(block_add_payment))
+            .branch(case![Command::Trip(args)].endpoint(block_add_payment))
+            .branch(case![Command::Stats].endpoint(block_add_payment))
+            .branch(case![Command::Remind].endpoint(block_add_payment))
+            .branch(case![Command::Limit(args)].endpoint(block_add_payment))
+            .branch(case![Command::Adjust(args)].endpoint(block_add_payment))
+            .branch(case![Command::Template(args)].endpoint(block_add_payment))
+            .branch(case![Command::Alias(args)].endpoint(block_add_payment))
+            .branch(case![Command::Archive].endpoint(block_add_payment))
+            .branch(case![Command::Restore(args)].endpoint(block_add_payment))
+            .branch(case![Command::Recalculate].endpoint(block_add_payment))
+            .branch(case![Command::Audit].endpoint(block_add_payment))
+            .branch(case![Command::Trash].endpoint(block_add_payment))
+            .branch(case![Command::Tutorial].endpoint(block_add_payment))
+            .branch(case![Command::Private].endpoint(block_add_payment))
+            .branch(case![Command::Consolidate].endpoint(block_add_payment))
+            .branch(case![Command::PayLink(args)].endpoint(block_add_payment))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_add_payment))
+            .branch(case![Command::Group(args)].endpoint(block_add_payment))
+            .branch(case![Command::Import(args)].endpoint(block_add_payment))
+            .branch(case![Command::Split(args)].endpoint(block_add_payment))
             .branch(case![Command::Spendings].endpoint(block_add_payment)),
         )
         .branch(
             case![State::AddConfirm { messages, payment }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_add_payment))
                 .branch(case![Command::AddPayment].endpoint(handle_repeated_add_payment))
                 .branch(case![Command::Balances].endpoint(block_add_payment))
+                .branch(case![Command::Owed(args)].endpoint(block_add_payment))
                 .branch(case![Command::PayBack].endpoint(block_add_payment))
-                .branch(case![Command::ViewPayments].endpoint(block_add_payment))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_add_payment))
                 .branch(case![Command::EditPayment].endpoint(block_add_payment))
                 .branch(case![Command::DeletePayment].endpoint(block_add_payment))
-                .branch(case![Command::Settings].endpoint(block_add_payment))
+                .branch(case![Command::DuplicatePayment].endpoint(block_add_payment))
+                .branch(case![Command::Settings(args)].endpoint(block_add_payment))
+                .branch(case![Command::Trip(args)].endpoint(block_add_payment))
+                .branch(case![Command::Stats].endpoint(block_add_payment))
+                .branch(case![Command::Remind].endpoint(block_add_payment))
+                .branch(case![Command::Limit(args)].endpoint(block_add_payment))
+                .branch(case![Command::Adjust(args)].endpoint(block_add_payment))
+                .branch(case![Command::Template(args)].endpoint(block_add_payment))
+                .branch(case![Command::Alias(args)].endpoint(block_add_payment))
+                .branch(case![Command::Archive].endpoint(block_add_payment))
+                .branch(case![Command::Restore(args)].endpoint(block_add_payment))
+                .branch(case![Command::Recalculate].endpoint(block_add_payment))
+                .branch(case![Command::Audit].endpoint(block_add_payment))
+                .branch(case![Command::Trash].endpoint(block_add_payment))
+                .branch(case![Command::Tutorial].endpoint(block_add_payment))
+                .branch(case![Command::Private].endpoint(block_add_payment))
+                .branch(case![Command::Consolidate].endpoint(block_add_payment))
+                .branch(case![Command::PayLink(args)].endpoint(block_add_payment))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_add_payment))
+                .branch(case![Command::Group(args)].endpoint(block_add_payment))
+                .branch(case![Command::Import(args)].endpoint(block_add_payment))
+                .branch(case![Command::Split(args)].endpoint(block_add_payment))
                 .branch(case![Command::Spendings].endpoint(block_add_payment)),
         )
         .branch(
             case![State::AddEditMenu { messages, payment }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_add_payment))
+                .branch(case![Command::AddPayment].endpoint(handle_repeated_add_payment))
+                .branch(case![Command::Balances].endpoint(block_add_payment))
+                .branch(case![Command::Owed(args)].endpoint(block_add_payment))
+                .branch(case![Command::PayBack].endpoint(block_add_payment))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_add_payment))
+                .branch(case![Command::EditPayment].endpoint(block_add_payment))
+                .branch(case![Command::DeletePayment].endpoint(block_add_payment))
+                .branch(case![Command::DuplicatePayment].endpoint(block_add_payment))
+                .branch(case![Command::Settings(args)].endpoint(block_add_payment))
+                .branch(case![Command::Trip(args)].endpoint(block_add_payment))
+                .branch(case![Command::Stats].endpoint(block_add_payment))
+                .branch(case![Command::Remind].endpoint(block_add_payment))
+                .branch(case![Command::Limit(args)].endpoint(block_add_payment))
+                .branch(case![Command::Adjust(args)].endpoint(block_add_payment))
+                .branch(case![Command::Template(args)].endpoint(block_add_payment))
+                .branch(case![Command::Alias(args)].endpoint(block_add_payment))
+                .branch(case![Command::Archive].endpoint(block_add_payment))
+                .branch(case![Command::Restore(args)].endpoint(block_add_payment))
+                .branch(case![Command::Recalculate].endpoint(block_add_payment))
+                .branch(case![Command::Audit].endpoint(block_add_payment))
+                .branch(case![Command::Trash].endpoint(block_add_payment))
+                .branch(case![Command::Tutorial].endpoint(block_add_payment))
+                .branch(case![Command::Private].endpoint(block_add_payment))
+                .branch(case![Command::Consolidate].endpoint(block_add_payment))
+                .branch(case![Command::PayLink(args)].endpoint(block_add_payment))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_add_payment))
+                .branch(case![Command::Group(args)].endpoint(block_add_payment))
+                .branch(case![Command::Import(args)].endpoint(block_add_payment))
+                .branch(case![Command::Split(args)].endpoint(block_add_payment))
+                .branch(case![Command::Spendings].endpoint(block_add_payment)),
+        )
+        .branch(
+            case![State::AddEditDebtsMenu { messages, payment }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_add_payment))
                 .branch(case![Command::AddPayment].endpoint(handle_repeated_add_payment))
                 .branch(case![Command::Balances].endpoint(block_add_payment))
+                .branch(case![Command::Owed(args)].endpoint(block_add_payment))
                 .branch(case![Command::PayBack].endpoint(block_add_payment))
-                .branch(case![Command::ViewPayments].endpoint(block_add_payment))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_add_payment))
                 .branch(case![Command::EditPayment].endpoint(block_add_payment))
                 .branch(case![Command::DeletePayment].endpoint(block_add_payment))
-                .branch(case![Command::Settings].endpoint(block_add_payment))
+                .branch(case![Command::DuplicatePayment].endpoint(block_add_payment))
+                .branch(case![Command::Settings(args)].endpoint(block_add_payment))
+                .branch(case![Command::Trip(args)].endpoint(block_add_payment))
+                .branch(case![Command::Stats].endpoint(block_add_payment))
+                .branch(case![Command::Remind].endpoint(block_add_payment))
+                .branch(case![Command::Limit(args)].endpoint(block_add_payment))
+                .branch(case![Command::Adjust(args)].endpoint(block_add_payment))
+                .branch(case![Command::Template(args)].endpoint(block_add_payment))
+                .branch(case![Command::Alias(args)].endpoint(block_add_payment))
+                .branch(case![Command::Archive].endpoint(block_add_payment))
+                .branch(case![Command::Restore(args)].endpoint(block_add_payment))
+                .branch(case![Command::Recalculate].endpoint(block_add_payment))
+                .branch(case![Command::Audit].endpoint(block_add_payment))
+                .branch(case![Command::Trash].endpoint(block_add_payment))
+                .branch(case![Command::Tutorial].endpoint(block_add_payment))
+                .branch(case![Command::Private].endpoint(block_add_payment))
+                .branch(case![Command::Consolidate].endpoint(block_add_payment))
+                .branch(case![Command::PayLink(args)].endpoint(block_add_payment))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_add_payment))
+                .branch(case![Command::Group(args)].endpoint(block_add_payment))
+                .branch(case![Command::Import(args)].endpoint(block_add_payment))
+                .branch(case![Command::Split(args)].endpoint(block_add_payment))
                 .branch(case![Command::Spendings].endpoint(block_add_payment)),
         )
         .branch(
@@ -286,85 +864,271 @@ pub async fn run_dispatcher(bot: Bot) {
                 edit
             }]
             .branch(case![Command::Start].endpoint(action_start))
-            .branch(case![Command::Help].endpoint(action_help))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
             .branch(case![Command::Cancel].endpoint(cancel_add_payment))
             .branch(case![Command::AddPayment].endpoint(handle_repeated_add_payment))
             .branch(case![Command::Balances].endpoint(block_add_payment))
+            .branch(case![Command::Owed(args)].endpoint(block_add_payment))
             .branch(case![Command::PayBack].endpoint(block_add_payment))
-            .branch(case![Command::ViewPayments].endpoint(block_add_payment))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_add_payment))
             .branch(case![Command::EditPayment].endpoint(block_add_payment))
             .branch(case![Command::DeletePayment].endpoint(block_add_payment))
-            .branch(case![Command::Settings].endpoint(block_add_payment))
+            .branch(case![Command::DuplicatePayment].endpoint(block_add_payment))
+            .branch(case![Command::Settings(args)].endpoint(block_add_payment))
+            .branch(case![Command::Trip(args)].endpoint(block_add_payment))
+            .branch(case![Command::Stats].endpoint(block_add_payment))
+            .branch(case![Command::Remind].endpoint(block_add_payment))
+            .branch(case![Command::Limit(args)].endpoint(block_add_payment))
+            .branch(case![Command::Adjust(args)].endpoint(block_add_payment))
+            .branch(case![Command::Template(args)].endpoint(block_add_payment))
+            .branch(case![Command::Alias(args)].endpoint(block_add_payment))
+            .branch(case![Command::Archive].endpoint(block_add_payment))
+            .branch(case![Command::Restore(args)].endpoint(block_add_payment))
+            .branch(case![Command::Recalculate].endpoint(block_add_payment))
+            .branch(case![Command::Audit].endpoint(block_add_payment))
+            .branch(case![Command::Trash].endpoint(block_add_payment))
+            .branch(case![Command::Tutorial].endpoint(block_add_payment))
+            .branch(case![Command::Private].endpoint(block_add_payment))
+            .branch(case![Command::Consolidate].endpoint(block_add_payment))
+            .branch(case![Command::PayLink(args)].endpoint(block_add_payment))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_add_payment))
+            .branch(case![Command::Group(args)].endpoint(block_add_payment))
+            .branch(case![Command::Import(args)].endpoint(block_add_payment))
+            .branch(case![Command::Split(args)].endpoint(block_add_payment))
             .branch(case![Command::Spendings].endpoint(block_add_payment)),
         )
+        .branch(
+            case![State::PayBackCreditors {
+                messages,
+                debts,
+                selected
+            }]
+            .branch(case![Command::Start].endpoint(action_start))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
+            .branch(case![Command::Cancel].endpoint(cancel_pay_back))
+            .branch(case![Command::AddPayment].endpoint(block_pay_back))
+            .branch(case![Command::Balances].endpoint(block_pay_back))
+            .branch(case![Command::Owed(args)].endpoint(block_pay_back))
+            .branch(case![Command::PayBack].endpoint(handle_repeated_pay_back))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_pay_back))
+            .branch(case![Command::EditPayment].endpoint(block_pay_back))
+            .branch(case![Command::DeletePayment].endpoint(block_pay_back))
+            .branch(case![Command::DuplicatePayment].endpoint(block_pay_back))
+            .branch(case![Command::Settings(args)].endpoint(block_pay_back))
+            .branch(case![Command::Trip(args)].endpoint(block_pay_back))
+            .branch(case![Command::Stats].endpoint(block_pay_back))
+            .branch(case![Command::Remind].endpoint(block_pay_back))
+            .branch(case![Command::Limit(args)].endpoint(block_pay_back))
+            .branch(case![Command::Adjust(args)].endpoint(block_pay_back))
+            .branch(case![Command::Template(args)].endpoint(block_pay_back))
+            .branch(case![Command::Alias(args)].endpoint(block_pay_back))
+            .branch(case![Command::Archive].endpoint(block_pay_back))
+            .branch(case![Command::Restore(args)].endpoint(block_pay_back))
+            .branch(case![Command::Recalculate].endpoint(block_pay_back))
+            .branch(case![Command::Audit].endpoint(block_pay_back))
+            .branch(case![Command::Trash].endpoint(block_pay_back))
+            .branch(case![Command::Tutorial].endpoint(block_pay_back))
+            .branch(case![Command::Private].endpoint(block_pay_back))
+            .branch(case![Command::Consolidate].endpoint(block_pay_back))
+            .branch(case![Command::PayLink(args)].endpoint(block_pay_back))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_pay_back))
+            .branch(case![Command::Group(args)].endpoint(block_pay_back))
+            .branch(case![Command::Import(args)].endpoint(block_pay_back))
+            .branch(case![Command::Split(args)].endpoint(block_pay_back))
+            .branch(case![Command::Spendings].endpoint(block_pay_back)),
+        )
         .branch(
             case![State::PayBackCurrencyMenu { messages }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_pay_back))
                 .branch(case![Command::AddPayment].endpoint(block_pay_back))
                 .branch(case![Command::Balances].endpoint(block_pay_back))
+                .branch(case![Command::Owed(args)].endpoint(block_pay_back))
                 .branch(case![Command::PayBack].endpoint(handle_repeated_pay_back))
-                .branch(case![Command::ViewPayments].endpoint(block_pay_back))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_pay_back))
                 .branch(case![Command::EditPayment].endpoint(block_pay_back))
                 .branch(case![Command::DeletePayment].endpoint(block_pay_back))
-                .branch(case![Command::Settings].endpoint(block_pay_back))
+                .branch(case![Command::DuplicatePayment].endpoint(block_pay_back))
+                .branch(case![Command::Settings(args)].endpoint(block_pay_back))
+                .branch(case![Command::Trip(args)].endpoint(block_pay_back))
+                .branch(case![Command::Stats].endpoint(block_pay_back))
+                .branch(case![Command::Remind].endpoint(block_pay_back))
+                .branch(case![Command::Limit(args)].endpoint(block_pay_back))
+                .branch(case![Command::Adjust(args)].endpoint(block_pay_back))
+                .branch(case![Command::Template(args)].endpoint(block_pay_back))
+                .branch(case![Command::Alias(args)].endpoint(block_pay_back))
+                .branch(case![Command::Archive].endpoint(block_pay_back))
+                .branch(case![Command::Restore(args)].endpoint(block_pay_back))
+                .branch(case![Command::Recalculate].endpoint(block_pay_back))
+                .branch(case![Command::Audit].endpoint(block_pay_back))
+                .branch(case![Command::Trash].endpoint(block_pay_back))
+                .branch(case![Command::Tutorial].endpoint(block_pay_back))
+                .branch(case![Command::Private].endpoint(block_pay_back))
+                .branch(case![Command::Consolidate].endpoint(block_pay_back))
+                .branch(case![Command::PayLink(args)].endpoint(block_pay_back))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_pay_back))
+                .branch(case![Command::Group(args)].endpoint(block_pay_back))
+                .branch(case![Command::Import(args)].endpoint(block_pay_back))
+                .branch(case![Command::Split(args)].endpoint(block_pay_back))
                 .branch(case![Command::Spendings].endpoint(block_pay_back)),
         )
         .branch(
             case![State::PayBackCurrency { messages }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_pay_back))
                 .branch(case![Command::AddPayment].endpoint(block_pay_back))
                 .branch(case![Command::Balances].endpoint(block_pay_back))
+                .branch(case![Command::Owed(args)].endpoint(block_pay_back))
                 .branch(case![Command::PayBack].endpoint(handle_repeated_pay_back))
-                .branch(case![Command::ViewPayments].endpoint(block_pay_back))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_pay_back))
                 .branch(case![Command::EditPayment].endpoint(block_pay_back))
                 .branch(case![Command::DeletePayment].endpoint(block_pay_back))
-                .branch(case![Command::Settings].endpoint(block_pay_back))
+                .branch(case![Command::DuplicatePayment].endpoint(block_pay_back))
+                .branch(case![Command::Settings(args)].endpoint(block_pay_back))
+                .branch(case![Command::Trip(args)].endpoint(block_pay_back))
+                .branch(case![Command::Stats].endpoint(block_pay_back))
+                .branch(case![Command::Remind].endpoint(block_pay_back))
+                .branch(case![Command::Limit(args)].endpoint(block_pay_back))
+                .branch(case![Command::Adjust(args)].endpoint(block_pay_back))
+                .branch(case![Command::Template(args)].endpoint(block_pay_back))
+                .branch(case![Command::Alias(args)].endpoint(block_pay_back))
+                .branch(case![Command::Archive].endpoint(block_pay_back))
+                .branch(case![Command::Restore(args)].endpoint(block_pay_back))
+                .branch(case![Command::Recalculate].endpoint(block_pay_back))
+                .branch(case![Command::Audit].endpoint(block_pay_back))
+                .branch(case![Command::Trash].endpoint(block_pay_back))
+                .branch(case![Command::Tutorial].endpoint(block_pay_back))
+                .branch(case![Command::Private].endpoint(block_pay_back))
+                .branch(case![Command::Consolidate].endpoint(block_pay_back))
+                .branch(case![Command::PayLink(args)].endpoint(block_pay_back))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_pay_back))
+                .branch(case![Command::Group(args)].endpoint(block_pay_back))
+                .branch(case![Command::Import(args)].endpoint(block_pay_back))
+                .branch(case![Command::Split(args)].endpoint(block_pay_back))
                 .branch(case![Command::Spendings].endpoint(block_pay_back)),
         )
         .branch(
             case![State::PayBackDebts { messages, currency }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_pay_back))
                 .branch(case![Command::AddPayment].endpoint(block_pay_back))
                 .branch(case![Command::Balances].endpoint(block_pay_back))
+                .branch(case![Command::Owed(args)].endpoint(block_pay_back))
                 .branch(case![Command::PayBack].endpoint(handle_repeated_pay_back))
-                .branch(case![Command::ViewPayments].endpoint(block_pay_back))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_pay_back))
                 .branch(case![Command::EditPayment].endpoint(block_pay_back))
                 .branch(case![Command::DeletePayment].endpoint(block_pay_back))
-                .branch(case![Command::Settings].endpoint(block_pay_back))
+                .branch(case![Command::DuplicatePayment].endpoint(block_pay_back))
+                .branch(case![Command::Settings(args)].endpoint(block_pay_back))
+                .branch(case![Command::Trip(args)].endpoint(block_pay_back))
+                .branch(case![Command::Stats].endpoint(block_pay_back))
+                .branch(case![Command::Remind].endpoint(block_pay_back))
+                .branch(case![Command::Limit(args)].endpoint(block_pay_back))
+                .branch(case![Command::Adjust(args)].endpoint(block_pay_back))
+                .branch(case![Command::Template(args)].endpoint(block_pay_back))
+                .branch(case![Command::Alias(args)].endpoint(block_pay_back))
+                .branch(case![Command::Archive].endpoint(block_pay_back))
+                .branch(case![Command::Restore(args)].endpoint(block_pay_back))
+                .branch(case![Command::Recalculate].endpoint(block_pay_back))
+                .branch(case![Command::Audit].endpoint(block_pay_back))
+                .branch(case![Command::Trash].endpoint(block_pay_back))
+                .branch(case![Command::Tutorial].endpoint(block_pay_back))
+                .branch(case![Command::Private].endpoint(block_pay_back))
+                .branch(case![Command::Consolidate].endpoint(block_pay_back))
+                .branch(case![Command::PayLink(args)].endpoint(block_pay_back))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_pay_back))
+                .branch(case![Command::Group(args)].endpoint(block_pay_back))
+                .branch(case![Command::Import(args)].endpoint(block_pay_back))
+                .branch(case![Command::Split(args)].endpoint(block_pay_back))
                 .branch(case![Command::Spendings].endpoint(block_pay_back)),
         )
         .branch(
             case![State::PayBackConfirm { messages, payment }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_pay_back))
                 .branch(case![Command::AddPayment].endpoint(block_pay_back))
                 .branch(case![Command::Balances].endpoint(block_pay_back))
+                .branch(case![Command::Owed(args)].endpoint(block_pay_back))
                 .branch(case![Command::PayBack].endpoint(handle_repeated_pay_back))
-                .branch(case![Command::ViewPayments].endpoint(block_pay_back))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_pay_back))
                 .branch(case![Command::EditPayment].endpoint(block_pay_back))
                 .branch(case![Command::DeletePayment].endpoint(block_pay_back))
-                .branch(case![Command::Settings].endpoint(block_pay_back))
+                .branch(case![Command::DuplicatePayment].endpoint(block_pay_back))
+                .branch(case![Command::Settings(args)].endpoint(block_pay_back))
+                .branch(case![Command::Trip(args)].endpoint(block_pay_back))
+                .branch(case![Command::Stats].endpoint(block_pay_back))
+                .branch(case![Command::Remind].endpoint(block_pay_back))
+                .branch(case![Command::Limit(args)].endpoint(block_pay_back))
+                .branch(case![Command::Adjust(args)].endpoint(block_pay_back))
+                .branch(case![Command::Template(args)].endpoint(block_pay_back))
+                .branch(case![Command::Alias(args)].endpoint(block_pay_back))
+                .branch(case![Command::Archive].endpoint(block_pay_back))
+                .branch(case![Command::Restore(args)].endpoint(block_pay_back))
+                .branch(case![Command::Recalculate].endpoint(block_pay_back))
+                .branch(case![Command::Audit].endpoint(block_pay_back))
+                .branch(case![Command::Trash].endpoint(block_pay_back))
+                .branch(case![Command::Tutorial].endpoint(block_pay_back))
+                .branch(case![Command::Private].endpoint(block_pay_back))
+                .branch(case![Command::Consolidate].endpoint(block_pay_back))
+                .branch(case![Command::PayLink(args)].endpoint(block_pay_back))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_pay_back))
+                .branch(case![Command::Group(args)].endpoint(block_pay_back))
+                .branch(case![Command::Import(args)].endpoint(block_pay_back))
+                .branch(case![Command::Split(args)].endpoint(block_pay_back))
                 .branch(case![Command::Spendings].endpoint(block_pay_back)),
         )
         .branch(
             case![State::ViewPayments { payments, page }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(action_cancel))
                 .branch(case![Command::AddPayment].endpoint(action_add_payment))
                 .branch(case![Command::Balances].endpoint(action_view_balances))
+                .branch(case![Command::Owed(args)].endpoint(action_owed))
                 .branch(case![Command::PayBack].endpoint(action_pay_back))
-                .branch(case![Command::ViewPayments].endpoint(action_view_payments))
+                .branch(case![Command::ViewPayments(args)].endpoint(action_view_payments))
                 .branch(case![Command::EditPayment].endpoint(action_select_payment_edit))
                 .branch(case![Command::DeletePayment].endpoint(action_select_payment_delete))
-                .branch(case![Command::Settings].endpoint(action_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(action_select_payment_duplicate))
+                .branch(case![Command::Settings(args)].endpoint(action_settings))
+                .branch(case![Command::Trip(args)].endpoint(action_trip))
+                .branch(case![Command::Stats].endpoint(action_stats))
+                .branch(case![Command::Remind].endpoint(action_remind))
+                .branch(case![Command::Limit(args)].endpoint(action_limit))
+                .branch(case![Command::Adjust(args)].endpoint(action_adjust))
+                .branch(case![Command::Template(args)].endpoint(action_template))
+                .branch(case![Command::Alias(args)].endpoint(action_alias))
+                .branch(case![Command::Archive].endpoint(action_archive))
+                .branch(case![Command::Restore(args)].endpoint(action_restore))
+                .branch(case![Command::Recalculate].endpoint(action_recalculate))
+                .branch(case![Command::Audit].endpoint(action_audit))
+                .branch(case![Command::Trash].endpoint(action_trash))
+                .branch(case![Command::Tutorial].endpoint(action_tutorial))
+                .branch(case![Command::Private].endpoint(action_private))
+                .branch(case![Command::Consolidate].endpoint(action_consolidate))
+                .branch(case![Command::PayLink(args)].endpoint(action_paylink))
+                .branch(case![Command::Leaderboard(args)].endpoint(action_leaderboard))
+                .branch(case![Command::Group(args)].endpoint(action_split_group))
+                .branch(case![Command::Import(args)].endpoint(action_import))
+                .branch(case![Command::Split(args)].endpoint(action_split))
                 .branch(case![Command::Spendings].endpoint(action_view_spendings)),
         )
         .branch(
@@ -375,15 +1139,39 @@ pub async fn run_dispatcher(bot: Bot) {
                 function
             }]
             .branch(case![Command::Start].endpoint(action_start))
-            .branch(case![Command::Help].endpoint(action_help))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
             .branch(case![Command::Cancel].endpoint(cancel_select_payment))
             .branch(case![Command::AddPayment].endpoint(block_select_payment))
             .branch(case![Command::Balances].endpoint(block_select_payment))
+            .branch(case![Command::Owed(args)].endpoint(block_select_payment))
             .branch(case![Command::PayBack].endpoint(block_select_payment))
-            .branch(case![Command::ViewPayments].endpoint(block_select_payment))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_select_payment))
             .branch(case![Command::EditPayment].endpoint(handle_repeated_select_payment))
             .branch(case![Command::DeletePayment].endpoint(handle_repeated_select_payment))
-            .branch(case![Command::Settings].endpoint(block_select_payment))
+            .branch(case![Command::DuplicatePayment].endpoint(handle_repeated_select_payment))
+            .branch(case![Command::Settings(args)].endpoint(block_select_payment))
+            .branch(case![Command::Trip(args)].endpoint(block_select_payment))
+            .branch(case![Command::Stats].endpoint(block_select_payment))
+            .branch(case![Command::Remind].endpoint(block_select_payment))
+            .branch(case![Command::Limit(args)].endpoint(block_select_payment))
+            .branch(case![Command::Adjust(args)].endpoint(block_select_payment))
+            .branch(case![Command::Template(args)].endpoint(block_select_payment))
+            .branch(case![Command::Alias(args)].endpoint(block_select_payment))
+            .branch(case![Command::Archive].endpoint(block_select_payment))
+            .branch(case![Command::Restore(args)].endpoint(block_select_payment))
+            .branch(case![Command::Recalculate].endpoint(block_select_payment))
+            .branch(case![Command::Audit].endpoint(block_select_payment))
+            .branch(case![Command::Trash].endpoint(block_select_payment))
+            .branch(case![Command::Tutorial].endpoint(block_select_payment))
+            .branch(case![Command::Private].endpoint(block_select_payment))
+            .branch(case![Command::Consolidate].endpoint(block_select_payment))
+            .branch(case![Command::PayLink(args)].endpoint(block_select_payment))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_select_payment))
+            .branch(case![Command::Group(args)].endpoint(block_select_payment))
+            .branch(case![Command::Import(args)].endpoint(block_select_payment))
+            .branch(case![Command::Split(args)].endpoint(block_select_payment))
             .branch(case![Command::Spendings].endpoint(block_select_payment)),
         )
         .branch(
@@ -395,15 +1183,39 @@ pub async fn run_dispatcher(bot: Bot) {
                 page
             }]
             .branch(case![Command::Start].endpoint(action_start))
-            .branch(case![Command::Help].endpoint(action_help))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
             .branch(case![Command::Cancel].endpoint(cancel_edit_payment))
             .branch(case![Command::AddPayment].endpoint(block_edit_payment))
             .branch(case![Command::Balances].endpoint(block_edit_payment))
+            .branch(case![Command::Owed(args)].endpoint(block_edit_payment))
             .branch(case![Command::PayBack].endpoint(block_edit_payment))
-            .branch(case![Command::ViewPayments].endpoint(block_edit_payment))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_edit_payment))
             .branch(case![Command::EditPayment].endpoint(handle_repeated_edit_payment))
             .branch(case![Command::DeletePayment].endpoint(block_edit_payment))
-            .branch(case![Command::Settings].endpoint(block_edit_payment))
+            .branch(case![Command::DuplicatePayment].endpoint(block_edit_payment))
+            .branch(case![Command::Settings(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Trip(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Stats].endpoint(block_edit_payment))
+            .branch(case![Command::Remind].endpoint(block_edit_payment))
+            .branch(case![Command::Limit(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Adjust(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Template(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Alias(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Archive].endpoint(block_edit_payment))
+            .branch(case![Command::Restore(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Recalculate].endpoint(block_edit_payment))
+            .branch(case![Command::Audit].endpoint(block_edit_payment))
+            .branch(case![Command::Trash].endpoint(block_edit_payment))
+            .branch(case![Command::Tutorial].endpoint(block_edit_payment))
+            .branch(case![Command::Private].endpoint(block_edit_payment))
+            .branch(case![Command::Consolidate].endpoint(block_edit_payment))
+            .branch(case![Command::PayLink(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Group(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Import(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Split(args)].endpoint(block_edit_payment))
             .branch(case![Command::Spendings].endpoint(block_edit_payment)),
         )
         .branch(
@@ -415,15 +1227,85 @@ pub async fn run_dispatcher(bot: Bot) {
                 page
             }]
             .branch(case![Command::Start].endpoint(action_start))
-            .branch(case![Command::Help].endpoint(action_help))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
+            .branch(case![Command::Cancel].endpoint(cancel_edit_payment))
+            .branch(case![Command::AddPayment].endpoint(block_edit_payment))
+            .branch(case![Command::Balances].endpoint(block_edit_payment))
+            .branch(case![Command::Owed(args)].endpoint(block_edit_payment))
+            .branch(case![Command::PayBack].endpoint(block_edit_payment))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_edit_payment))
+            .branch(case![Command::EditPayment].endpoint(handle_repeated_edit_payment))
+            .branch(case![Command::DeletePayment].endpoint(block_edit_payment))
+            .branch(case![Command::DuplicatePayment].endpoint(block_edit_payment))
+            .branch(case![Command::Settings(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Trip(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Stats].endpoint(block_edit_payment))
+            .branch(case![Command::Remind].endpoint(block_edit_payment))
+            .branch(case![Command::Limit(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Adjust(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Template(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Alias(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Archive].endpoint(block_edit_payment))
+            .branch(case![Command::Restore(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Recalculate].endpoint(block_edit_payment))
+            .branch(case![Command::Audit].endpoint(block_edit_payment))
+            .branch(case![Command::Trash].endpoint(block_edit_payment))
+            .branch(case![Command::Tutorial].endpoint(block_edit_payment))
+            .branch(case![Command::Private].endpoint(block_edit_payment))
+            .branch(case![Command::Consolidate].endpoint(block_edit_payment))
+            .branch(case![Command::PayLink(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Group(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Import(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Split(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Spendings].endpoint(block_edit_payment)),
+        )
+        .branch(
+            case![State::EditPaymentDebtPicker {
+                messages,
+                payment,
+                edited_payment,
+                users,
+                selected,
+                payments,
+                page
+            }]
+            .branch(case![Command::Start].endpoint(action_start))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
             .branch(case![Command::Cancel].endpoint(cancel_edit_payment))
             .branch(case![Command::AddPayment].endpoint(block_edit_payment))
             .branch(case![Command::Balances].endpoint(block_edit_payment))
+            .branch(case![Command::Owed(args)].endpoint(block_edit_payment))
             .branch(case![Command::PayBack].endpoint(block_edit_payment))
-            .branch(case![Command::ViewPayments].endpoint(block_edit_payment))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_edit_payment))
             .branch(case![Command::EditPayment].endpoint(handle_repeated_edit_payment))
             .branch(case![Command::DeletePayment].endpoint(block_edit_payment))
-            .branch(case![Command::Settings].endpoint(block_edit_payment))
+            .branch(case![Command::DuplicatePayment].endpoint(block_edit_payment))
+            .branch(case![Command::Settings(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Trip(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Stats].endpoint(block_edit_payment))
+            .branch(case![Command::Remind].endpoint(block_edit_payment))
+            .branch(case![Command::Limit(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Adjust(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Template(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Alias(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Archive].endpoint(block_edit_payment))
+            .branch(case![Command::Restore(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Recalculate].endpoint(block_edit_payment))
+            .branch(case![Command::Audit].endpoint(block_edit_payment))
+            .branch(case![Command::Trash].endpoint(block_edit_payment))
+            .branch(case![Command::Tutorial].endpoint(block_edit_payment))
+            .branch(case![Command::Private].endpoint(block_edit_payment))
+            .branch(case![Command::Consolidate].endpoint(block_edit_payment))
+            .branch(case![Command::PayLink(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Group(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Import(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Split(args)].endpoint(block_edit_payment))
             .branch(case![Command::Spendings].endpoint(block_edit_payment)),
         )
         .branch(
@@ -436,165 +1318,1211 @@ pub async fn run_dispatcher(bot: Bot) {
                 page
             }]
             .branch(case![Command::Start].endpoint(action_start))
-            .branch(case![Command::Help].endpoint(action_help))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
             .branch(case![Command::Cancel].endpoint(cancel_edit_payment))
             .branch(case![Command::AddPayment].endpoint(block_edit_payment))
             .branch(case![Command::Balances].endpoint(block_edit_payment))
+            .branch(case![Command::Owed(args)].endpoint(block_edit_payment))
             .branch(case![Command::PayBack].endpoint(block_edit_payment))
-            .branch(case![Command::ViewPayments].endpoint(block_edit_payment))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_edit_payment))
             .branch(case![Command::EditPayment].endpoint(handle_repeated_edit_payment))
             .branch(case![Command::DeletePayment].endpoint(block_edit_payment))
-            .branch(case![Command::Settings].endpoint(block_edit_payment))
+            .branch(case![Command::DuplicatePayment].endpoint(block_edit_payment))
+            .branch(case![Command::Settings(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Trip(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Stats].endpoint(block_edit_payment))
+            .branch(case![Command::Remind].endpoint(block_edit_payment))
+            .branch(case![Command::Limit(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Adjust(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Template(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Alias(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Archive].endpoint(block_edit_payment))
+            .branch(case![Command::Restore(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Recalculate].endpoint(block_edit_payment))
+            .branch(case![Command::Audit].endpoint(block_edit_payment))
+            .branch(case![Command::Trash].endpoint(block_edit_payment))
+            .branch(case![Command::Tutorial].endpoint(block_edit_payment))
+            .branch(case![Command::Private].endpoint(block_edit_payment))
+            .branch(case![Command::Consolidate].endpoint(block_edit_payment))
+            .branch(case![Command::PayLink(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Group(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Import(args)].endpoint(block_edit_payment))
+            .branch(case![Command::Split(args)].endpoint(block_edit_payment))
             .branch(case![Command::Spendings].endpoint(block_edit_payment)),
         )
+        .branch(
+            case![State::SelectPaymentsDelete {
+                messages,
+                payments,
+                page,
+                selected
+            }]
+            .branch(case![Command::Start].endpoint(action_start))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
+            .branch(case![Command::Cancel].endpoint(cancel_delete_payment))
+            .branch(case![Command::AddPayment].endpoint(block_delete_payment))
+            .branch(case![Command::Balances].endpoint(block_delete_payment))
+            .branch(case![Command::Owed(args)].endpoint(block_delete_payment))
+            .branch(case![Command::PayBack].endpoint(block_delete_payment))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_delete_payment))
+            .branch(case![Command::EditPayment].endpoint(block_delete_payment))
+            .branch(case![Command::DeletePayment].endpoint(handle_repeated_delete_payment))
+            .branch(case![Command::DuplicatePayment].endpoint(block_delete_payment))
+            .branch(case![Command::Settings(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Trip(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Stats].endpoint(block_delete_payment))
+            .branch(case![Command::Remind].endpoint(block_delete_payment))
+            .branch(case![Command::Limit(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Adjust(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Template(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Alias(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Archive].endpoint(block_delete_payment))
+            .branch(case![Command::Restore(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Recalculate].endpoint(block_delete_payment))
+            .branch(case![Command::Audit].endpoint(block_delete_payment))
+            .branch(case![Command::Trash].endpoint(block_delete_payment))
+            .branch(case![Command::Tutorial].endpoint(block_delete_payment))
+            .branch(case![Command::Private].endpoint(block_delete_payment))
+            .branch(case![Command::Consolidate].endpoint(block_delete_payment))
+            .branch(case![Command::PayLink(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Group(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Import(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Split(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Spendings].endpoint(block_delete_payment)),
+        )
         .branch(
             case![State::DeletePayment {
                 messages,
-                payment,
+                selected_payments,
                 payments,
                 page
             }]
             .branch(case![Command::Start].endpoint(action_start))
-            .branch(case![Command::Help].endpoint(action_help))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
             .branch(case![Command::Cancel].endpoint(cancel_delete_payment))
             .branch(case![Command::AddPayment].endpoint(block_delete_payment))
             .branch(case![Command::Balances].endpoint(block_delete_payment))
+            .branch(case![Command::Owed(args)].endpoint(block_delete_payment))
             .branch(case![Command::PayBack].endpoint(block_delete_payment))
-            .branch(case![Command::ViewPayments].endpoint(block_delete_payment))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_delete_payment))
             .branch(case![Command::EditPayment].endpoint(block_delete_payment))
             .branch(case![Command::DeletePayment].endpoint(handle_repeated_delete_payment))
-            .branch(case![Command::Settings].endpoint(block_delete_payment))
+            .branch(case![Command::DuplicatePayment].endpoint(block_delete_payment))
+            .branch(case![Command::Settings(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Trip(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Stats].endpoint(block_delete_payment))
+            .branch(case![Command::Remind].endpoint(block_delete_payment))
+            .branch(case![Command::Limit(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Adjust(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Template(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Alias(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Archive].endpoint(block_delete_payment))
+            .branch(case![Command::Restore(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Recalculate].endpoint(block_delete_payment))
+            .branch(case![Command::Audit].endpoint(block_delete_payment))
+            .branch(case![Command::Trash].endpoint(block_delete_payment))
+            .branch(case![Command::Tutorial].endpoint(block_delete_payment))
+            .branch(case![Command::Private].endpoint(block_delete_payment))
+            .branch(case![Command::Consolidate].endpoint(block_delete_payment))
+            .branch(case![Command::PayLink(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Group(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Import(args)].endpoint(block_delete_payment))
+            .branch(case![Command::Split(args)].endpoint(block_delete_payment))
             .branch(case![Command::Spendings].endpoint(block_delete_payment)),
         )
+        .branch(
+            case![State::PaymentCard {
+                messages,
+                payments,
+                page,
+                index
+            }]
+            .branch(case![Command::Start].endpoint(action_start))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
+            .branch(case![Command::Cancel].endpoint(cancel_payment_card))
+            .branch(case![Command::AddPayment].endpoint(block_payment_card))
+            .branch(case![Command::Balances].endpoint(block_payment_card))
+            .branch(case![Command::Owed(args)].endpoint(block_payment_card))
+            .branch(case![Command::PayBack].endpoint(block_payment_card))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_payment_card))
+            .branch(case![Command::EditPayment].endpoint(block_payment_card))
+            .branch(case![Command::DeletePayment].endpoint(block_payment_card))
+            .branch(case![Command::DuplicatePayment].endpoint(block_payment_card))
+            .branch(case![Command::Settings(args)].endpoint(block_payment_card))
+            .branch(case![Command::Trip(args)].endpoint(block_payment_card))
+            .branch(case![Command::Stats].endpoint(block_payment_card))
+            .branch(case![Command::Remind].endpoint(block_payment_card))
+            .branch(case![Command::Limit(args)].endpoint(block_payment_card))
+            .branch(case![Command::Adjust(args)].endpoint(block_payment_card))
+            .branch(case![Command::Template(args)].endpoint(block_payment_card))
+            .branch(case![Command::Alias(args)].endpoint(block_payment_card))
+            .branch(case![Command::Archive].endpoint(block_payment_card))
+            .branch(case![Command::Restore(args)].endpoint(block_payment_card))
+            .branch(case![Command::Recalculate].endpoint(block_payment_card))
+            .branch(case![Command::Audit].endpoint(block_payment_card))
+            .branch(case![Command::Trash].endpoint(block_payment_card))
+            .branch(case![Command::Tutorial].endpoint(block_payment_card))
+            .branch(case![Command::Private].endpoint(block_payment_card))
+            .branch(case![Command::Consolidate].endpoint(block_payment_card))
+            .branch(case![Command::PayLink(args)].endpoint(block_payment_card))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_payment_card))
+            .branch(case![Command::Group(args)].endpoint(block_payment_card))
+            .branch(case![Command::Import(args)].endpoint(block_payment_card))
+            .branch(case![Command::Split(args)].endpoint(block_payment_card))
+            .branch(case![Command::Spendings].endpoint(block_payment_card)),
+        )
+        .branch(
+            case![State::PaymentCardDetails {
+                messages,
+                payments,
+                page,
+                index,
+                detail
+            }]
+            .branch(case![Command::Start].endpoint(action_start))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
+            .branch(case![Command::Cancel].endpoint(cancel_payment_card))
+            .branch(case![Command::AddPayment].endpoint(block_payment_card))
+            .branch(case![Command::Balances].endpoint(block_payment_card))
+            .branch(case![Command::Owed(args)].endpoint(block_payment_card))
+            .branch(case![Command::PayBack].endpoint(block_payment_card))
+            .branch(case![Command::ViewPayments(args)].endpoint(block_payment_card))
+            .branch(case![Command::EditPayment].endpoint(block_payment_card))
+            .branch(case![Command::DeletePayment].endpoint(block_payment_card))
+            .branch(case![Command::DuplicatePayment].endpoint(block_payment_card))
+            .branch(case![Command::Settings(args)].endpoint(block_payment_card))
+            .branch(case![Command::Trip(args)].endpoint(block_payment_card))
+            .branch(case![Command::Stats].endpoint(block_payment_card))
+            .branch(case![Command::Remind].endpoint(block_payment_card))
+            .branch(case![Command::Limit(args)].endpoint(block_payment_card))
+            .branch(case![Command::Adjust(args)].endpoint(block_payment_card))
+            .branch(case![Command::Template(args)].endpoint(block_payment_card))
+            .branch(case![Command::Alias(args)].endpoint(block_payment_card))
+            .branch(case![Command::Archive].endpoint(block_payment_card))
+            .branch(case![Command::Restore(args)].endpoint(block_payment_card))
+            .branch(case![Command::Recalculate].endpoint(block_payment_card))
+            .branch(case![Command::Audit].endpoint(block_payment_card))
+            .branch(case![Command::Trash].endpoint(block_payment_card))
+            .branch(case![Command::Tutorial].endpoint(block_payment_card))
+            .branch(case![Command::Private].endpoint(block_payment_card))
+            .branch(case![Command::Consolidate].endpoint(block_payment_card))
+            .branch(case![Command::PayLink(args)].endpoint(block_payment_card))
+            .branch(case![Command::Leaderboard(args)].endpoint(block_payment_card))
+            .branch(case![Command::Group(args)].endpoint(block_payment_card))
+            .branch(case![Command::Import(args)].endpoint(block_payment_card))
+            .branch(case![Command::Split(args)].endpoint(block_payment_card))
+            .branch(case![Command::Spendings].endpoint(block_payment_card)),
+        )
         .branch(
             case![State::SettingsMenu { messages }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_settings))
                 .branch(case![Command::AddPayment].endpoint(block_settings))
                 .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
                 .branch(case![Command::PayBack].endpoint(block_settings))
-                .branch(case![Command::ViewPayments].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
                 .branch(case![Command::EditPayment].endpoint(block_settings))
                 .branch(case![Command::DeletePayment].endpoint(block_settings))
-                .branch(case![Command::Settings].endpoint(handle_repeated_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
                 .branch(case![Command::Spendings].endpoint(block_settings)),
         )
         .branch(
             case![State::SettingsTimeZoneMenu { messages }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_settings))
                 .branch(case![Command::AddPayment].endpoint(block_settings))
                 .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
                 .branch(case![Command::PayBack].endpoint(block_settings))
-                .branch(case![Command::ViewPayments].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
                 .branch(case![Command::EditPayment].endpoint(block_settings))
                 .branch(case![Command::DeletePayment].endpoint(block_settings))
-                .branch(case![Command::Settings].endpoint(handle_repeated_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
                 .branch(case![Command::Spendings].endpoint(block_settings)),
         )
         .branch(
-            case![State::SettingsTimeZone { messages }]
+            case![State::SettingsTimeZoneRegion { messages }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_settings))
                 .branch(case![Command::AddPayment].endpoint(block_settings))
                 .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
                 .branch(case![Command::PayBack].endpoint(block_settings))
-                .branch(case![Command::ViewPayments].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
                 .branch(case![Command::EditPayment].endpoint(block_settings))
                 .branch(case![Command::DeletePayment].endpoint(block_settings))
-                .branch(case![Command::Settings].endpoint(handle_repeated_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
                 .branch(case![Command::Spendings].endpoint(block_settings)),
         )
         .branch(
             case![State::SettingsDefaultCurrencyMenu { messages }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_settings))
                 .branch(case![Command::AddPayment].endpoint(block_settings))
                 .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
                 .branch(case![Command::PayBack].endpoint(block_settings))
-                .branch(case![Command::ViewPayments].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
                 .branch(case![Command::EditPayment].endpoint(block_settings))
                 .branch(case![Command::DeletePayment].endpoint(block_settings))
-                .branch(case![Command::Settings].endpoint(handle_repeated_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
                 .branch(case![Command::Spendings].endpoint(block_settings)),
         )
         .branch(
             case![State::SettingsDefaultCurrency { messages }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_settings))
                 .branch(case![Command::AddPayment].endpoint(block_settings))
                 .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
                 .branch(case![Command::PayBack].endpoint(block_settings))
-                .branch(case![Command::ViewPayments].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
                 .branch(case![Command::EditPayment].endpoint(block_settings))
                 .branch(case![Command::DeletePayment].endpoint(block_settings))
-                .branch(case![Command::Settings].endpoint(handle_repeated_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
                 .branch(case![Command::Spendings].endpoint(block_settings)),
         )
         .branch(
             case![State::SettingsCurrencyConversion { messages }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_settings))
                 .branch(case![Command::AddPayment].endpoint(block_settings))
                 .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
                 .branch(case![Command::PayBack].endpoint(block_settings))
-                .branch(case![Command::ViewPayments].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
                 .branch(case![Command::EditPayment].endpoint(block_settings))
                 .branch(case![Command::DeletePayment].endpoint(block_settings))
-                .branch(case![Command::Settings].endpoint(handle_repeated_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
                 .branch(case![Command::Spendings].endpoint(block_settings)),
         )
         .branch(
             case![State::SettingsEraseMessages { messages }]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsMonthlyArchive { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsAdminOnly { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsWeeklyDigestMenu { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsDebtReminderMenu { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsWeeklyDigest { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsDebtReminder { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsTreasurerMenu { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsTreasurer { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(cancel_settings))
                 .branch(case![Command::AddPayment].endpoint(block_settings))
                 .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
                 .branch(case![Command::PayBack].endpoint(block_settings))
-                .branch(case![Command::ViewPayments].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
                 .branch(case![Command::EditPayment].endpoint(block_settings))
                 .branch(case![Command::DeletePayment].endpoint(block_settings))
-                .branch(case![Command::Settings].endpoint(handle_repeated_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsPaymentApprovalMenu { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsPaymentApproval { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsLanguageMenu { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsNumberFormatMenu { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsCurrencyFormatMenu { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsVerbosityMenu { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsRoundingMenu { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
+                .branch(case![Command::Spendings].endpoint(block_settings)),
+        )
+        .branch(
+            case![State::SettingsWeekStartMenu { messages }]
+                .branch(case![Command::Start].endpoint(action_start))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
+                .branch(case![Command::Cancel].endpoint(cancel_settings))
+                .branch(case![Command::AddPayment].endpoint(block_settings))
+                .branch(case![Command::Balances].endpoint(block_settings))
+                .branch(case![Command::Owed(args)].endpoint(block_settings))
+                .branch(case![Command::PayBack].endpoint(block_settings))
+                .branch(case![Command::ViewPayments(args)].endpoint(block_settings))
+                .branch(case![Command::EditPayment].endpoint(block_settings))
+                .branch(case![Command::DeletePayment].endpoint(block_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(block_settings))
+                .branch(case![Command::Settings(args)].endpoint(handle_repeated_settings))
+                .branch(case![Command::Trip(args)].endpoint(block_settings))
+                .branch(case![Command::Stats].endpoint(block_settings))
+                .branch(case![Command::Remind].endpoint(block_settings))
+                .branch(case![Command::Limit(args)].endpoint(block_settings))
+                .branch(case![Command::Adjust(args)].endpoint(block_settings))
+                .branch(case![Command::Template(args)].endpoint(block_settings))
+                .branch(case![Command::Alias(args)].endpoint(block_settings))
+                .branch(case![Command::Archive].endpoint(block_settings))
+                .branch(case![Command::Restore(args)].endpoint(block_settings))
+                .branch(case![Command::Recalculate].endpoint(block_settings))
+                .branch(case![Command::Audit].endpoint(block_settings))
+                .branch(case![Command::Trash].endpoint(block_settings))
+                .branch(case![Command::Tutorial].endpoint(block_settings))
+                .branch(case![Command::Private].endpoint(block_settings))
+                .branch(case![Command::Consolidate].endpoint(block_settings))
+                .branch(case![Command::PayLink(args)].endpoint(block_settings))
+                .branch(case![Command::Leaderboard(args)].endpoint(block_settings))
+                .branch(case![Command::Group(args)].endpoint(block_settings))
+                .branch(case![Command::Import(args)].endpoint(block_settings))
+                .branch(case![Command::Split(args)].endpoint(block_settings))
                 .branch(case![Command::Spendings].endpoint(block_settings)),
         )
         .branch(
             case![State::BalancesMenu]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(action_cancel))
                 .branch(case![Command::AddPayment].endpoint(action_add_payment))
                 .branch(case![Command::Balances].endpoint(action_view_balances))
+                .branch(case![Command::Owed(args)].endpoint(action_owed))
                 .branch(case![Command::PayBack].endpoint(action_pay_back))
-                .branch(case![Command::ViewPayments].endpoint(action_view_payments))
+                .branch(case![Command::ViewPayments(args)].endpoint(action_view_payments))
                 .branch(case![Command::EditPayment].endpoint(no_edit_payment))
                 .branch(case![Command::DeletePayment].endpoint(no_delete_payment))
-                .branch(case![Command::Settings].endpoint(action_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(no_duplicate_payment))
+                .branch(case![Command::Settings(args)].endpoint(action_settings))
+                .branch(case![Command::Trip(args)].endpoint(action_trip))
+                .branch(case![Command::Stats].endpoint(action_stats))
+                .branch(case![Command::Remind].endpoint(action_remind))
+                .branch(case![Command::Limit(args)].endpoint(action_limit))
+                .branch(case![Command::Adjust(args)].endpoint(action_adjust))
+                .branch(case![Command::Template(args)].endpoint(action_template))
+                .branch(case![Command::Alias(args)].endpoint(action_alias))
+                .branch(case![Command::Archive].endpoint(action_archive))
+                .branch(case![Command::Restore(args)].endpoint(action_restore))
+                .branch(case![Command::Recalculate].endpoint(action_recalculate))
+                .branch(case![Command::Audit].endpoint(action_audit))
+                .branch(case![Command::Trash].endpoint(action_trash))
+                .branch(case![Command::Tutorial].endpoint(action_tutorial))
+                .branch(case![Command::Private].endpoint(action_private))
+                .branch(case![Command::Consolidate].endpoint(action_consolidate))
+                .branch(case![Command::PayLink(args)].endpoint(action_paylink))
+                .branch(case![Command::Leaderboard(args)].endpoint(action_leaderboard))
+                .branch(case![Command::Group(args)].endpoint(action_split_group))
+                .branch(case![Command::Import(args)].endpoint(action_import))
+                .branch(case![Command::Split(args)].endpoint(action_split))
                 .branch(case![Command::Spendings].endpoint(action_view_spendings)),
         )
+        .branch(
+            case![State::BalancesConvertTo {
+                messages,
+                balances_id
+            }]
+            .branch(case![Command::Start].endpoint(action_start))
+            .branch(case![Command::Help(command)].endpoint(action_help))
+            .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+            .branch(case![Command::BotStats].endpoint(action_bot_stats))
+            .branch(case![Command::Cancel].endpoint(cancel_balances_convert_to))
+            .branch(case![Command::AddPayment].endpoint(action_add_payment))
+            .branch(case![Command::Balances].endpoint(action_view_balances))
+            .branch(case![Command::Owed(args)].endpoint(action_owed))
+            .branch(case![Command::PayBack].endpoint(action_pay_back))
+            .branch(case![Command::ViewPayments(args)].endpoint(action_view_payments))
+            .branch(case![Command::EditPayment].endpoint(no_edit_payment))
+            .branch(case![Command::DeletePayment].endpoint(no_delete_payment))
+            .branch(case![Command::DuplicatePayment].endpoint(no_duplicate_payment))
+            .branch(case![Command::Settings(args)].endpoint(action_settings))
+            .branch(case![Command::Trip(args)].endpoint(action_trip))
+            .branch(case![Command::Stats].endpoint(action_stats))
+            .branch(case![Command::Remind].endpoint(action_remind))
+            .branch(case![Command::Limit(args)].endpoint(action_limit))
+            .branch(case![Command::Adjust(args)].endpoint(action_adjust))
+            .branch(case![Command::Template(args)].endpoint(action_template))
+            .branch(case![Command::Alias(args)].endpoint(action_alias))
+            .branch(case![Command::Archive].endpoint(action_archive))
+            .branch(case![Command::Restore(args)].endpoint(action_restore))
+            .branch(case![Command::Recalculate].endpoint(action_recalculate))
+            .branch(case![Command::Audit].endpoint(action_audit))
+            .branch(case![Command::Trash].endpoint(action_trash))
+            .branch(case![Command::Tutorial].endpoint(action_tutorial))
+            .branch(case![Command::Private].endpoint(action_private))
+            .branch(case![Command::Consolidate].endpoint(action_consolidate))
+            .branch(case![Command::PayLink(args)].endpoint(action_paylink))
+            .branch(case![Command::Leaderboard(args)].endpoint(action_leaderboard))
+            .branch(case![Command::Group(args)].endpoint(action_split_group))
+            .branch(case![Command::Import(args)].endpoint(action_import))
+            .branch(case![Command::Split(args)].endpoint(action_split))
+            .branch(case![Command::Spendings].endpoint(action_view_spendings)),
+        )
         .branch(
             case![State::SpendingsMenu]
                 .branch(case![Command::Start].endpoint(action_start))
-                .branch(case![Command::Help].endpoint(action_help))
+                .branch(case![Command::Help(command)].endpoint(action_help))
+                .branch(case![Command::DebugBalances(args)].endpoint(action_debug_balances))
+                .branch(case![Command::BotStats].endpoint(action_bot_stats))
                 .branch(case![Command::Cancel].endpoint(action_cancel))
                 .branch(case![Command::AddPayment].endpoint(action_add_payment))
                 .branch(case![Command::Balances].endpoint(action_view_balances))
+                .branch(case![Command::Owed(args)].endpoint(action_owed))
                 .branch(case![Command::PayBack].endpoint(action_pay_back))
-                .branch(case![Command::ViewPayments].endpoint(action_view_payments))
+                .branch(case![Command::ViewPayments(args)].endpoint(action_view_payments))
                 .branch(case![Command::EditPayment].endpoint(no_edit_payment))
                 .branch(case![Command::DeletePayment].endpoint(no_delete_payment))
-                .branch(case![Command::Settings].endpoint(action_settings))
+                .branch(case![Command::DuplicatePayment].endpoint(no_duplicate_payment))
+                .branch(case![Command::Settings(args)].endpoint(action_settings))
+                .branch(case![Command::Trip(args)].endpoint(action_trip))
+                .branch(case![Command::Stats].endpoint(action_stats))
+                .branch(case![Command::Remind].endpoint(action_remind))
+                .branch(case![Command::Limit(args)].endpoint(action_limit))
+                .branch(case![Command::Adjust(args)].endpoint(action_adjust))
+                .branch(case![Command::Template(args)].endpoint(action_template))
+                .branch(case![Command::Alias(args)].endpoint(action_alias))
+                .branch(case![Command::Archive].endpoint(action_archive))
+                .branch(case![Command::Restore(args)].endpoint(action_restore))
+                .branch(case![Command::Recalculate].endpoint(action_recalculate))
+                .branch(case![Command::Audit].endpoint(action_audit))
+                .branch(case![Command::Trash].endpoint(action_trash))
+                .branch(case![Command::Tutorial].endpoint(action_tutorial))
+                .branch(case![Command::Private].endpoint(action_private))
+                .branch(case![Command::Consolidate].endpoint(action_consolidate))
+                .branch(case![Command::PayLink(args)].endpoint(action_paylink))
+                .branch(case![Command::Leaderboard(args)].endpoint(action_leaderboard))
+                .branch(case![Command::Group(args)].endpoint(action_split_group))
+                .branch(case![Command::Import(args)].endpoint(action_import))
+                .branch(case![Command::Split(args)].endpoint(action_split))
                 .branch(case![Command::Spendings].endpoint(action_view_spendings)),
         );
 
     let message_handler = Update::filter_message()
+        .map(track_user_message)
+        .branch(dptree::filter(is_left_chat_member).endpoint(action_left_chat_member))
         .branch(command_handler)
+        .branch(dptree::filter_async(is_restricted_message).endpoint(action_restricted_message))
         .branch(case![State::AddDescription { messages }].endpoint(action_add_description))
         .branch(case![State::AddCreditor { messages, payment }].endpoint(action_add_creditor))
         .branch(case![State::AddTotal { messages, payment }].endpoint(action_add_total))
@@ -606,6 +2534,23 @@ pub async fn run_dispatcher(bot: Bot) {
             }]
             .endpoint(action_add_debt),
         )
+        .branch(
+            case![State::AddDebtPicker {
+                messages,
+                payment,
+                users,
+                selected
+            }]
+            .endpoint(action_add_debt_picker_message),
+        )
+        .branch(
+            case![State::AddDebtItems {
+                messages,
+                payment,
+                items
+            }]
+            .endpoint(action_add_debt_items_message),
+        )
         .branch(
             case![State::AddEdit {
                 messages,
@@ -614,6 +2559,14 @@ pub async fn run_dispatcher(bot: Bot) {
             }]
             .endpoint(action_add_edit),
         )
+        .branch(
+            case![State::PayBackCreditors {
+                messages,
+                debts,
+                selected
+            }]
+            .endpoint(action_pay_back_creditors_message),
+        )
         .branch(case![State::PayBackCurrency { messages }].endpoint(action_pay_back_currency))
         .branch(case![State::PayBackDebts { messages, currency }].endpoint(action_pay_back_debts))
         .branch(
@@ -627,11 +2580,49 @@ pub async fn run_dispatcher(bot: Bot) {
             }]
             .endpoint(action_edit_payment_edit),
         )
-        .branch(case![State::SettingsTimeZone { messages }].endpoint(action_settings_time_zone))
+        .branch(
+            case![State::EditPaymentDebtPicker {
+                messages,
+                payment,
+                edited_payment,
+                users,
+                selected,
+                payments,
+                page
+            }]
+            .endpoint(action_edit_payment_debt_picker_message),
+        )
+        .branch(
+            case![State::PaymentCardDetails {
+                messages,
+                payments,
+                page,
+                index,
+                detail
+            }]
+            .endpoint(action_payment_card_details),
+        )
+        .branch(
+            case![State::SettingsTimeZoneRegion { messages }].endpoint(action_settings_time_zone),
+        )
         .branch(
             case![State::SettingsDefaultCurrency { messages }]
                 .endpoint(action_settings_default_currency),
         )
+        .branch(
+            case![State::SettingsWeeklyDigest { messages }].endpoint(action_settings_weekly_digest),
+        )
+        .branch(
+            case![State::SettingsDebtReminder { messages }].endpoint(action_settings_debt_reminder),
+        )
+        .branch(case![State::SettingsTreasurer { messages }].endpoint(action_settings_treasurer))
+        .branch(
+            case![State::SettingsPaymentApproval { messages }]
+                .endpoint(action_settings_payment_approval),
+        )
+        .branch(
+            case![State::AddTotalCurrency { messages, payment }].endpoint(callback_invalid_message),
+        )
         .branch(
             case![State::AddDebtSelection { messages, payment }].endpoint(callback_invalid_message),
         )
@@ -673,15 +2664,33 @@ pub async fn run_dispatcher(bot: Bot) {
             }]
             .endpoint(callback_invalid_message),
         )
+        .branch(
+            case![State::SelectPaymentsDelete {
+                messages,
+                payments,
+                page,
+                selected
+            }]
+            .endpoint(callback_invalid_message),
+        )
         .branch(
             case![State::DeletePayment {
                 messages,
-                payment,
+                selected_payments,
                 payments,
                 page
             }]
             .endpoint(callback_invalid_message),
         )
+        .branch(
+            case![State::PaymentCard {
+                messages,
+                payments,
+                page,
+                index
+            }]
+            .endpoint(callback_invalid_message),
+        )
         .branch(case![State::SettingsMenu { messages }].endpoint(callback_invalid_message))
         .branch(case![State::SettingsTimeZoneMenu { messages }].endpoint(callback_invalid_message))
         .branch(
@@ -693,22 +2702,100 @@ pub async fn run_dispatcher(bot: Bot) {
                 .endpoint(callback_invalid_message),
         )
         .branch(case![State::SettingsEraseMessages { messages }].endpoint(callback_invalid_message))
+        .branch(
+            case![State::SettingsMonthlyArchive { messages }].endpoint(callback_invalid_message),
+        )
+        .branch(case![State::SettingsAdminOnly { messages }].endpoint(callback_invalid_message))
+        .branch(
+            case![State::SettingsWeeklyDigestMenu { messages }].endpoint(callback_invalid_message),
+        )
+        .branch(
+            case![State::SettingsDebtReminderMenu { messages }].endpoint(callback_invalid_message),
+        )
+        .branch(case![State::SettingsTreasurerMenu { messages }].endpoint(callback_invalid_message))
+        .branch(
+            case![State::SettingsPaymentApprovalMenu { messages }]
+                .endpoint(callback_invalid_message),
+        )
+        .branch(case![State::SettingsLanguageMenu { messages }].endpoint(callback_invalid_message))
+        .branch(
+            case![State::SettingsNumberFormatMenu { messages }].endpoint(callback_invalid_message),
+        )
+        .branch(
+            case![State::SettingsCurrencyFormatMenu { messages }]
+                .endpoint(callback_invalid_message),
+        )
+        .branch(case![State::SettingsVerbosityMenu { messages }].endpoint(callback_invalid_message))
+        .branch(case![State::SettingsRoundingMenu { messages }].endpoint(callback_invalid_message))
+        .branch(case![State::SettingsWeekStartMenu { messages }].endpoint(callback_invalid_message))
         .branch(case![State::ViewPayments { payments, page }].endpoint(invalid_state))
         .branch(case![State::BalancesMenu].endpoint(invalid_state))
+        .branch(
+            case![State::BalancesConvertTo {
+                messages,
+                balances_id
+            }]
+            .endpoint(action_balances_convert_to),
+        )
         .branch(case![State::SpendingsMenu].endpoint(invalid_state))
+        .branch(
+            case![State::Start]
+                .filter_async(is_detected_payment)
+                .endpoint(action_detect_payment),
+        )
         .branch(case![State::Start].endpoint(invalid_state));
 
     let callback_query_handler = Update::filter_callback_query()
+        .branch(dptree::filter(is_approval_query).endpoint(action_resolve_pending_payment))
+        .branch(dptree::filter(is_confirmation_query).endpoint(action_confirm_pending_payment))
+        .branch(dptree::filter(is_payment_dispute_query).endpoint(action_resolve_payment_dispute))
+        .branch(dptree::filter(is_trash_restore_query).endpoint(action_restore_trash))
+        .branch(dptree::filter(is_tutorial_query).endpoint(action_tutorial_step))
+        .branch(dptree::filter(is_detected_payment_query).endpoint(action_resolve_detected_payment))
+        .branch(dptree::filter(is_pin_query).endpoint(action_pin_left_member))
+        .branch(dptree::filter(is_undo_split_query).endpoint(action_undo_split))
+        .branch(dptree::filter(is_help_query).endpoint(action_help_menu))
+        .branch(dptree::filter(is_notification_query).endpoint(action_notification_off))
+        .branch(dptree::filter_async(is_restricted_query).endpoint(action_restricted_query))
+        .branch(
+            case![State::AddTotalCurrency { messages, payment }]
+                .endpoint(action_add_total_currency),
+        )
         .branch(
             case![State::AddDebtSelection { messages, payment }]
                 .endpoint(action_add_debt_selection),
         )
+        .branch(
+            case![State::AddDebtPicker {
+                messages,
+                payment,
+                users,
+                selected
+            }]
+            .endpoint(action_add_debt_picker),
+        )
+        .branch(
+            case![State::AddDebtItems {
+                messages,
+                payment,
+                items
+            }]
+            .endpoint(action_add_debt_items),
+        )
         .branch(case![State::AddConfirm { messages, payment }].endpoint(action_add_confirm))
         .branch(
             case![State::AddEditDebtsMenu { messages, payment }]
                 .endpoint(action_add_debt_selection),
         )
         .branch(case![State::AddEditMenu { messages, payment }].endpoint(action_add_edit_menu))
+        .branch(
+            case![State::PayBackCreditors {
+                messages,
+                debts,
+                selected
+            }]
+            .endpoint(action_pay_back_creditors),
+        )
         .branch(
             case![State::PayBackCurrencyMenu { messages }].endpoint(action_pay_back_currency_menu),
         )
@@ -746,18 +2833,69 @@ pub async fn run_dispatcher(bot: Bot) {
             .endpoint(action_edit_payment_debts),
         )
         .branch(
-            case![State::DeletePayment {
+            case![State::EditPaymentDebtPicker {
                 messages,
                 payment,
+                edited_payment,
+                users,
+                selected,
+                payments,
+                page
+            }]
+            .endpoint(action_edit_payment_debt_picker),
+        )
+        .branch(
+            case![State::SelectPaymentsDelete {
+                messages,
+                payments,
+                page,
+                selected
+            }]
+            .endpoint(action_select_payments_delete),
+        )
+        .branch(
+            case![State::DeletePayment {
+                messages,
+                selected_payments,
                 payments,
                 page
             }]
             .endpoint(action_delete_payment_confirm),
         )
+        .branch(
+            case![State::PaymentCard {
+                messages,
+                payments,
+                page,
+                index
+            }]
+            .endpoint(action_payment_card_menu),
+        )
+        .branch(
+            case![State::PaymentCardDetails {
+                messages,
+                payments,
+                page,
+                index,
+                detail
+            }]
+            .endpoint(action_payment_card_details_menu),
+        )
         .branch(case![State::BalancesMenu].endpoint(action_balances_menu))
+        .branch(
+            case![State::BalancesConvertTo {
+                messages,
+                balances_id
+            }]
+            .endpoint(action_balances_convert_to_menu),
+        )
         .branch(case![State::SpendingsMenu].endpoint(action_spendings_menu))
         .branch(case![State::SettingsMenu { messages }].endpoint(action_settings_menu))
         .branch(case![State::SettingsTimeZoneMenu { messages }].endpoint(action_time_zone_menu))
+        .branch(
+            case![State::SettingsTimeZoneRegion { messages }]
+                .endpoint(action_time_zone_region_menu),
+        )
         .branch(
             case![State::SettingsDefaultCurrencyMenu { messages }]
                 .endpoint(action_default_currency_menu),
@@ -769,16 +2907,68 @@ pub async fn run_dispatcher(bot: Bot) {
         .branch(
             case![State::SettingsEraseMessages { messages }]
                 .endpoint(action_settings_erase_messages),
-        );
+        )
+        .branch(
+            case![State::SettingsMonthlyArchive { messages }]
+                .endpoint(action_settings_monthly_archive),
+        )
+        .branch(case![State::SettingsAdminOnly { messages }].endpoint(action_settings_admin_only))
+        .branch(
+            case![State::SettingsWeeklyDigestMenu { messages }].endpoint(action_weekly_digest_menu),
+        )
+        .branch(
+            case![State::SettingsDebtReminderMenu { messages }].endpoint(action_debt_reminder_menu),
+        )
+        .branch(case![State::SettingsTreasurerMenu { messages }].endpoint(action_treasurer_menu))
+        .branch(
+            case![State::SettingsPaymentApprovalMenu { messages }]
+                .endpoint(action_payment_approval_menu),
+        )
+        .branch(case![State::SettingsLanguageMenu { messages }].endpoint(action_language_menu))
+        .branch(
+            case![State::SettingsNumberFormatMenu { messages }].endpoint(action_number_format_menu),
+        )
+        .branch(
+            case![State::SettingsCurrencyFormatMenu { messages }]
+                .endpoint(action_currency_format_menu),
+        )
+        .branch(case![State::SettingsVerbosityMenu { messages }].endpoint(action_verbosity_menu))
+        .branch(case![State::SettingsRoundingMenu { messages }].endpoint(action_rounding_menu))
+        .branch(case![State::SettingsWeekStartMenu { messages }].endpoint(action_week_start_menu));
+
+    let my_chat_member_handler = Update::filter_my_chat_member()
+        .branch(dptree::filter(is_bot_added).endpoint(action_bot_added));
 
     let schema = dialogue::enter::<Update, InMemStorage<State>, State, _>()
         .branch(message_handler)
-        .branch(callback_query_handler);
+        .branch(callback_query_handler)
+        .branch(my_chat_member_handler);
 
-    Dispatcher::builder(bot, schema)
-        .dependencies(dptree::deps![InMemStorage::<State>::new()])
-        .enable_ctrlc_handler()
-        .build()
-        .dispatch()
+    // Background tasks, such as monthly archival and weekly digests
+    let shutdown = Shutdown::new();
+    let scheduler = tokio::spawn(run_scheduler(
+        redis.clone(),
+        bot.clone(),
+        shutdown.subscribe(),
+    ));
+
+    // Reactions to messages are handled by the listener, as teloxide does not support them yet
+    let listener = ReactionListener::new(bot.clone(), redis.clone()).await;
+    let mut dispatcher = Dispatcher::builder(bot, schema)
+        .dependencies(dptree::deps![InMemStorage::<State>::new(), redis])
+        .build();
+    tokio::spawn(shutdown.listen(dispatcher.shutdown_token()));
+    dispatcher
+        .dispatch_with_listener(
+            listener,
+            LoggingErrorHandler::with_custom_text("An error from the update listener"),
+        )
         .await;
+
+    // Dispatching only ends on shutdown, once all ongoing updates are handled
+    if let Err(err) = scheduler.await {
+        log::error!("Shutdown - Scheduler did not stop cleanly: {}", err);
+    }
+    log::warn!("Shutdown - Ongoing dialogues are kept in memory, and will be reset on restart");
+    log::info!("Shutdown - PayScribe bot shut down cleanly!");
 }
@@ -0,0 +1,362 @@
+/* Export generates documents of the records of a chat, to be sent as files.
+ * Documents are simple PDFs of text, written directly without any external library.
+ * Only the standard Helvetica fonts are used, which cover Latin-1 and no other scripts,
+ * so documents with text in other scripts are written as plain text files instead.
+ * Tables of records can also be written as CSV files, to be opened in a spreadsheet.
+ */
+
+// A4 page size and layout, in points
+const PAGE_WIDTH: f64 = 595.0;
+const PAGE_HEIGHT: f64 = 842.0;
+const PAGE_MARGIN: f64 = 56.0;
+const LINE_SPACING: f64 = 1.5;
+
+const TITLE_SIZE: f64 = 18.0;
+const HEADING_SIZE: f64 = 13.0;
+const TEXT_SIZE: f64 = 11.0;
+
+// Represents a section of a document, with a heading and lines of text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentSection {
+    pub heading: String,
+    pub lines: Vec<String>,
+}
+
+// Represents a line of a document, with its font, size, and text.
+struct DocumentLine {
+    font: &'static str,
+    size: f64,
+    text: String,
+}
+
+// Checks if a character can be written in a PDF, or is left out as decoration
+fn is_printable_char(c: char) -> bool {
+    matches!(c, ' '..='~' | '\u{A0}'..='\u{FF}') || c.is_whitespace() || is_decorative_char(c)
+}
+
+// Emojis and other symbols are left out of PDFs, as they are only decorative
+fn is_decorative_char(c: char) -> bool {
+    (c as u32) >= 0x2000 && !c.is_alphanumeric()
+}
+
+// Escapes text to be written in a PDF string, leaving out decorative symbols
+fn escape_text(text: &str) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut is_after_symbol = false;
+    for c in text.chars() {
+        match c {
+            // Skips the space that separated a symbol from the text after it
+            ' ' if is_after_symbol => {}
+            '\\' | '(' | ')' => {
+                bytes.push(b'\\');
+                bytes.push(c as u8);
+            }
+            ' '..='~' | '\u{A0}'..='\u{FF}' => bytes.push(c as u32 as u8),
+            _ if c.is_whitespace() => bytes.push(b' '),
+            _ if is_decorative_char(c) => {
+                is_after_symbol = true;
+                continue;
+            }
+            _ => bytes.push(b'?'),
+        }
+        is_after_symbol = false;
+    }
+    bytes
+}
+
+// Lays out the title and sections of a document into lines, split into pages
+fn layout_pages(title: &str, subtitle: &str, sections: &[DocumentSection]) -> Vec<Vec<u8>> {
+    let mut lines: Vec<DocumentLine> = vec![
+        DocumentLine {
+            font: "F2",
+            size: TITLE_SIZE,
+            text: title.to_string(),
+        },
+        DocumentLine {
+            font: "F1",
+            size: TEXT_SIZE,
+            text: subtitle.to_string(),
+        },
+    ];
+    for section in sections {
+        lines.push(DocumentLine {
+            font: "F1",
+            size: TEXT_SIZE,
+            text: String::new(),
+        });
+        lines.push(DocumentLine {
+            font: "F2",
+            size: HEADING_SIZE,
+            text: section.heading.clone(),
+        });
+        for line in &section.lines {
+            lines.push(DocumentLine {
+                font: "F1",
+                size: TEXT_SIZE,
+                text: line.clone(),
+            });
+        }
+    }
+
+    let mut pages: Vec<Vec<u8>> = Vec::new();
+    let mut content: Vec<u8> = Vec::new();
+    let mut y = PAGE_HEIGHT - PAGE_MARGIN;
+    for line in lines {
+        let height = line.size * LINE_SPACING;
+        if y - height < PAGE_MARGIN {
+            pages.push(content);
+            content = Vec::new();
+            y = PAGE_HEIGHT - PAGE_MARGIN;
+        }
+        y -= height;
+
+        if line.text.is_empty() {
+            continue;
+        }
+        content.extend_from_slice(
+            format!(
+                "BT /{} {} Tf {} {:.2} Td (",
+                line.font, line.size, PAGE_MARGIN, y
+            )
+            .as_bytes(),
+        );
+        content.extend_from_slice(&escape_text(&line.text));
+        content.extend_from_slice(b") Tj ET\n");
+    }
+    pages.push(content);
+
+    pages
+}
+
+/* Renders a document with a title, subtitle, and sections of text into a PDF.
+ * Content that does not fit on a page continues on the next.
+ * Returns the bytes of the PDF file.
+ */
+pub fn render_pdf(title: &str, subtitle: &str, sections: &[DocumentSection]) -> Vec<u8> {
+    let pages = layout_pages(title, subtitle, sections);
+
+    // Objects 1 to 4 are the catalog, page tree, and fonts, followed by each page and its content
+    let page_ids: Vec<usize> = (0..pages.len()).map(|index| 5 + index * 2).collect();
+    let kids = page_ids
+        .iter()
+        .map(|id| format!("{id} 0 R"))
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!(
+            "<< /Type /Pages /Kids [{kids}] /Count {} >>",
+            page_ids.len()
+        )
+        .into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+    ];
+    for (page_id, content) in page_ids.iter().zip(pages) {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                page_id + 1
+            )
+            .into_bytes(),
+        );
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend_from_slice(&content);
+        stream.extend_from_slice(b"endstream");
+        objects.push(stream);
+    }
+
+    let mut pdf: Vec<u8> = b"%PDF-1.4\n".to_vec();
+    let mut offsets: Vec<usize> = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref_offset = pdf.len();
+    pdf.extend_from_slice(format!("xref\n0 {}\n", objects.len() + 1).as_bytes());
+    pdf.extend_from_slice(b"0000000000 65535 f \n");
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .as_bytes(),
+    );
+
+    pdf
+}
+
+// Checks if a document can be rendered as a PDF, with all of its text in Latin-1
+pub fn is_pdf_printable(title: &str, subtitle: &str, sections: &[DocumentSection]) -> bool {
+    let mut texts = [title, subtitle].into_iter().chain(
+        sections
+            .iter()
+            .flat_map(|section| std::iter::once(&section.heading).chain(&section.lines))
+            .map(String::as_str),
+    );
+    texts.all(|text| text.chars().all(is_printable_char))
+}
+
+/* Renders a document with a title, subtitle, and sections of text into a plain text file.
+ * Used for documents that cannot be rendered as a PDF.
+ * Returns the bytes of the text file.
+ */
+pub fn render_text(title: &str, subtitle: &str, sections: &[DocumentSection]) -> Vec<u8> {
+    let mut text = format!("{title}\n{subtitle}\n");
+    for section in sections {
+        text.push('\n');
+        text.push_str(&section.heading);
+        text.push('\n');
+        for line in &section.lines {
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+
+    text.into_bytes()
+}
+
+// Escapes a field to be written in a CSV file, quoting it if needed
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/* Renders a table as a CSV file, with a header row followed by each row.
+ * Returns the bytes of the CSV file.
+ */
+pub fn render_csv(header: &[&str], rows: &[Vec<String>]) -> Vec<u8> {
+    let mut csv = String::new();
+    let header: Vec<String> = header.iter().map(|field| escape_csv_field(field)).collect();
+    csv.push_str(&header.join(","));
+    csv.push_str("\r\n");
+    for row in rows {
+        let row: Vec<String> = row.iter().map(|field| escape_csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+
+    csv.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_csv_field() {
+        assert_eq!(escape_csv_field("Dinner"), "Dinner");
+        assert_eq!(escape_csv_field("1,234.00"), "\"1,234.00\"");
+        assert_eq!(
+            escape_csv_field("The \"Best\" Ramen"),
+            "\"The \"\"Best\"\" Ramen\""
+        );
+        assert_eq!(escape_csv_field("午餐"), "午餐");
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let rows = vec![
+            vec![
+                "Sushi".to_string(),
+                "@alice".to_string(),
+                "8,000".to_string(),
+            ],
+            vec!["Taxi".to_string(), "@bob".to_string(), "40.00".to_string()],
+        ];
+        let csv = render_csv(&["Description", "Paid By", "Amount"], &rows);
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "Description,Paid By,Amount\r\nSushi,@alice,\"8,000\"\r\nTaxi,@bob,40.00\r\n"
+        );
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("alice owes bob"), b"alice owes bob".to_vec());
+        assert_eq!(escape_text("(a\\b)"), b"\\(a\\\\b\\)".to_vec());
+        assert_eq!(escape_text("café"), vec![b'c', b'a', b'f', 0xE9]);
+        assert_eq!(escape_text("📊 Balances"), b"Balances".to_vec());
+        assert_eq!(escape_text("  Total: 📊 10"), b"  Total: 10".to_vec());
+        assert_eq!(escape_text("午餐"), b"??".to_vec());
+    }
+
+    #[test]
+    fn test_is_pdf_printable() {
+        let sections = vec![DocumentSection {
+            heading: "📊 USD".to_string(),
+            lines: vec!["@alice owes @bob: 10.00".to_string()],
+        }];
+        assert!(is_pdf_printable("Balances of Café", "", &sections));
+        assert!(!is_pdf_printable("Balances of 午餐", "", &sections));
+
+        let sections = vec![DocumentSection {
+            heading: "USD".to_string(),
+            lines: vec!["@alice 欠 @bob: 10.00".to_string()],
+        }];
+        assert!(!is_pdf_printable("Balances", "", &sections));
+    }
+
+    #[test]
+    fn test_render_text() {
+        let sections = vec![DocumentSection {
+            heading: "USD".to_string(),
+            lines: vec![
+                "@alice 欠 @bob: 10.00".to_string(),
+                "  @bob: 10.00".to_string(),
+            ],
+        }];
+        let text = render_text("Balances", "Generated on 1 Mar 2024", &sections);
+        assert_eq!(
+            String::from_utf8(text).unwrap(),
+            "Balances\nGenerated on 1 Mar 2024\n\nUSD\n@alice 欠 @bob: 10.00\n  @bob: 10.00\n"
+        );
+    }
+
+    #[test]
+    fn test_render_pdf() {
+        let sections = vec![DocumentSection {
+            heading: "USD".to_string(),
+            lines: vec!["@alice owes @bob: 10.00".to_string()],
+        }];
+        let pdf = render_pdf("Balances", "Generated on 1 Mar 2024", &sections);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.ends_with("%%EOF\n"));
+        assert!(text.contains("/Count 1"));
+        assert!(text.contains("(@alice owes @bob: 10.00) Tj"));
+
+        // Each object in the cross-reference table is at its recorded offset
+        let xref = text.find("xref\n").unwrap();
+        for (index, line) in text[xref..].lines().skip(3).take(6).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(text[offset..].starts_with(&format!("{} 0 obj", index + 1)));
+        }
+    }
+
+    #[test]
+    fn test_render_pdf_multiple_pages() {
+        let sections = vec![DocumentSection {
+            heading: "USD".to_string(),
+            lines: (0..100).map(|i| format!("Line {i}")).collect(),
+        }];
+        let pdf = render_pdf("Balances", "", &sections);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.contains("/Count 3"));
+        assert!(text.contains("(Line 99) Tj"));
+    }
+}
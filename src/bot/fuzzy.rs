@@ -0,0 +1,113 @@
+/* Fuzzy matches names against those already known, to catch typos in usernames.
+ * Names are compared without case, underscores or a leading @, so that "@Alice_B" and "aliceb" match.
+ * Otherwise, names are compared by their edit distance, which is the number of characters
+ * to insert, delete or change to get from one name to the other.
+ * Shorter names allow fewer edits, as more of them would be close to each other by chance.
+ */
+
+const SHORT_NAME_LENGTH: usize = 6;
+const MAX_DISTANCE_SHORT: usize = 1;
+const MAX_DISTANCE: usize = 2;
+
+// Normalizes a name for comparison, ignoring case, underscores and a leading @
+fn normalize_name(name: &str) -> String {
+    name.trim_start_matches('@')
+        .chars()
+        .filter(|c| *c != '_')
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+// Counts the edits between two names, as their Levenshtein distance
+fn edit_distance(first: &str, second: &str) -> usize {
+    let second: Vec<char> = second.chars().collect();
+    let mut previous: Vec<usize> = (0..=second.len()).collect();
+    for (i, first_char) in first.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, second_char) in second.iter().enumerate() {
+            let change = previous[j] + usize::from(first_char != *second_char);
+            current.push(change.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[second.len()]
+}
+
+/* Finds the known name that a name is most likely a typo of, if any.
+ * Returns None if the name is already known, ignoring case, or if no known name is close enough.
+ * Of names equally close, the first known is chosen.
+ */
+pub fn find_similar_name(name: &str, known: &[String]) -> Option<String> {
+    let name = name.trim_start_matches('@');
+    if known
+        .iter()
+        .any(|known_name| known_name.eq_ignore_ascii_case(name))
+    {
+        return None;
+    }
+
+    let normalized = normalize_name(name);
+    let max_distance = if normalized.chars().count() <= SHORT_NAME_LENGTH {
+        MAX_DISTANCE_SHORT
+    } else {
+        MAX_DISTANCE
+    };
+
+    known
+        .iter()
+        .map(|known_name| {
+            let distance = edit_distance(&normalized, &normalize_name(known_name));
+            (distance, known_name)
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known_name)| known_name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("alice", "alice"), 0);
+        assert_eq!(edit_distance("alice", "alicee"), 1);
+        assert_eq!(edit_distance("alice", "alcie"), 2);
+        assert_eq!(edit_distance("bob", "rob"), 1);
+        assert_eq!(edit_distance("", "bob"), 3);
+    }
+
+    #[test]
+    fn test_find_similar_name() {
+        let known = vec![
+            "alice_b".to_string(),
+            "Bobby".to_string(),
+            "charlie_chaplin".to_string(),
+        ];
+
+        // Known names, in any case, have nothing to correct
+        assert_eq!(find_similar_name("alice_b", &known), None);
+        assert_eq!(find_similar_name("@BOBBY", &known), None);
+
+        // Missing underscores and different cases match
+        assert_eq!(
+            find_similar_name("@AliceB", &known),
+            Some("alice_b".to_string())
+        );
+
+        // Typos match, with fewer allowed for short names
+        assert_eq!(
+            find_similar_name("bobyy", &known),
+            Some("Bobby".to_string())
+        );
+        assert_eq!(find_similar_name("bo", &known), None);
+        assert_eq!(
+            find_similar_name("charliechaplain", &known),
+            Some("charlie_chaplin".to_string())
+        );
+
+        // Names too different are left as new users
+        assert_eq!(find_similar_name("dave", &known), None);
+        assert_eq!(find_similar_name("alice", &Vec::new()), None);
+    }
+}
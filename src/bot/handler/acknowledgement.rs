@@ -0,0 +1,105 @@
+use serde_json::Value;
+use teloxide::types::ChatId;
+
+use crate::bot::{handler::utils::HandlerResult, processor::acknowledge_payment, redis::Redis};
+
+/* Utilities */
+const ACKNOWLEDGEMENT_REACTION: &str = "👍";
+
+// Represents a change in the reactions of a user to a message
+#[derive(Debug, Clone)]
+pub struct MessageReaction {
+    pub chat_id: ChatId,
+    pub message_id: i32,
+    pub username: Option<String>,
+    pub old_reaction: Vec<String>,
+    pub new_reaction: Vec<String>,
+}
+
+// Reads the emojis of a list of reactions, ignoring custom emojis
+fn read_reaction_emojis(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|reactions| {
+            reactions
+                .iter()
+                .filter(|reaction| reaction["type"] == "emoji")
+                .filter_map(|reaction| reaction["emoji"].as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Reads a message reaction from a raw update, as reactions are not supported by teloxide yet
+// Returns None if the update is not a message reaction
+pub fn parse_message_reaction(update: &Value) -> Option<MessageReaction> {
+    let reaction = update.get("message_reaction")?;
+    Some(MessageReaction {
+        chat_id: ChatId(reaction["chat"]["id"].as_i64()?),
+        message_id: i32::try_from(reaction["message_id"].as_i64()?).ok()?,
+        username: reaction["user"]["username"].as_str().map(String::from),
+        old_reaction: read_reaction_emojis(&reaction["old_reaction"]),
+        new_reaction: read_reaction_emojis(&reaction["new_reaction"]),
+    })
+}
+
+/* Acknowledges a payment when a user reacts to the message confirming it with a thumbs up,
+ * or takes it back when they remove the reaction.
+ * Reactions from users without a username, or who are not part of the payment, are ignored.
+ */
+pub async fn action_payment_reaction(redis: &Redis, reaction: MessageReaction) -> HandlerResult {
+    let username = match &reaction.username {
+        Some(username) => username,
+        None => return Ok(()),
+    };
+    let was_acknowledged = reaction
+        .old_reaction
+        .iter()
+        .any(|emoji| emoji == ACKNOWLEDGEMENT_REACTION);
+    let is_acknowledged = reaction
+        .new_reaction
+        .iter()
+        .any(|emoji| emoji == ACKNOWLEDGEMENT_REACTION);
+    if was_acknowledged == is_acknowledged {
+        return Ok(());
+    }
+
+    let chat_id = reaction.chat_id.to_string();
+    match acknowledge_payment(
+        redis,
+        &chat_id,
+        reaction.message_id,
+        username,
+        is_acknowledged,
+    )
+    .await
+    {
+        Ok(Some((acknowledged, participants))) => {
+            // Logging
+            log::info!(
+                "Payment Reaction - User {} {} a payment in chat {}, now confirmed by {}/{}",
+                username,
+                if is_acknowledged {
+                    "acknowledged"
+                } else {
+                    "took back acknowledgement of"
+                },
+                chat_id,
+                acknowledged,
+                participants
+            );
+        }
+        Ok(None) => {}
+        Err(err) => {
+            // Logging
+            log::error!(
+                "Payment Reaction - Processor failed to update acknowledgement of user {} in chat {}: {}",
+                username,
+                chat_id,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -1,33 +1,84 @@
+use chrono::Utc;
 use teloxide::{
     payloads::SendMessageSetters,
     prelude::*,
-    types::{Message, MessageId},
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message, MessageId, User},
 };
 
 use crate::bot::{
-    currency::Currency,
+    currency::{
+        get_default_currency, normalize_amount, Currency, CurrencyFormat, CURRENCY_DEFAULT,
+    },
     dispatcher::State,
+    fuzzy::find_similar_name,
     handler::{
+        approval::{
+            display_approval_request, display_dual_entry_request, get_approvers,
+            make_keyboard_approval, make_keyboard_dual_entry,
+        },
         constants::{
-            COMMAND_CANCEL, DEBT_EQUAL_DESCRIPTION_MESSAGE, DEBT_EQUAL_INSTRUCTIONS_MESSAGE,
-            DEBT_EXACT_DESCRIPTION_MESSAGE, DEBT_EXACT_INSTRUCTIONS_MESSAGE,
-            DEBT_RATIO_DESCRIPTION_MESSAGE, DEBT_RATIO_INSTRUCTIONS_MESSAGE, NO_TEXT_MESSAGE,
-            TOTAL_INSTRUCTIONS_MESSAGE, UNKNOWN_ERROR_MESSAGE,
+            COMMAND_CANCEL, CURRENCY_SUGGESTIONS_MAX, MAKE_EXPENSE_BUTTON, MAKE_TRANSFER_BUTTON,
         },
+        limit::{display_weekly_cap, notify_spending_limits},
+        notification::notify_added_users,
         utils::{
-            display_balance_header, display_balances, display_currency_amount, display_debts,
-            display_username, make_keyboard, make_keyboard_debt_selection, parse_currency_amount,
-            parse_username, process_debts, use_currency, HandlerResult, UserDialogue,
+            display_balance_header, display_balances, display_currency_amount, display_debt_items,
+            display_debts, display_due_date, display_payment_date, display_username,
+            display_usernames, error_to_user_message, get_chat_default_currency, get_currency,
+            get_picker_users, get_topic_id, make_keyboard, make_keyboard_debt_selection,
+            make_keyboard_user_picker, parse_conversion_rate, parse_debt_items, parse_due_date,
+            parse_payment_date, parse_total_amount, parse_username, process_debts,
+            process_debts_equal, retrieve_allowed_currencies, retrieve_chat_split_groups,
+            retrieve_currency_format, retrieve_language, retrieve_number_format, retrieve_rounding,
+            retrieve_time_zone, retrieve_topic_default_currency, share_surcharge, split_debt_items,
+            t, t_prompt, t_text, toggle_picker_users, use_currency, validate_description,
+            validate_payment_count, BotError, HandlerResult, UserDialogue,
         },
     },
-    processor::add_payment,
+    i18n::{
+        translate, Text, ADD_PAYMENT_BLOCKED, ADD_PAYMENT_CANCELLED, ADD_PAYMENT_FAILED,
+        ADD_PAYMENT_NO_USERNAME, ADD_PAYMENT_OVERVIEW, ADD_PAYMENT_REPEATED, APPROVAL_NEEDED,
+        DATE_INSTRUCTIONS, DEBTOR_CORRECTION_BUTTON, DEBTOR_CORRECTION_SUGGESTION,
+        DEBT_ADJUSTED_INSTRUCTIONS, DEBT_ADJUSTMENTS_PROMPT, DEBT_AMOUNTS_PROMPT,
+        DEBT_EQUAL_INSTRUCTIONS, DEBT_EQUAL_PICKER, DEBT_EXACT_INSTRUCTIONS, DEBT_FORMATS,
+        DEBT_ITEMIZED_INSTRUCTIONS, DEBT_ITEMS_PROMPT, DEBT_PERCENTAGES_PROMPT,
+        DEBT_PERCENT_INSTRUCTIONS, DEBT_RATIO_INSTRUCTIONS, DEBT_USERS_PROMPT, DUAL_ENTRY_NEEDED,
+        DUE_DATE_INSTRUCTIONS, EDIT_DATE_PROMPT, EDIT_DUE_DATE_PROMPT, EDIT_SPLIT_PROMPT,
+        EDIT_TOTAL_PROMPT, NO_TEXT, RATE_ALREADY_DEFAULT, RATE_CURRENCY_MISSING,
+        RATE_NO_DEFAULT_CURRENCY, SPLIT_PROMPT, TOTAL_INSTRUCTIONS, TOTAL_PROMPT, UNKNOWN_ERROR,
+        WEEKLY_CAP_OVERRIDE_NOT_ALLOWED,
+    },
+    money::{checked_sum, saturating_add},
+    processor::{
+        add_chat_users, add_payment, add_pending_payment, check_weekly_cap, is_approval_required,
+        is_dual_entry_required, is_username_equal, retrieve_frequent_currencies,
+        retrieve_unknown_users, track_payment_confirmation, update_payment_due_date, ProcessError,
+        PRIVATE_DESCRIPTION,
+    },
+    redis::{PaymentTemplate, Redis},
 };
 
-use super::utils::{
-    assert_handle_request_limit, delete_bot_messages, is_erase_messages, send_bot_message,
+use super::{
+    utils::{
+        assert_handle_request_limit, cleanup_messages, edit_tracked_message, send_bot_message,
+        send_completion_messages, MessageTracker,
+    },
+    view_payments::Payment,
 };
 
 /* Utilities */
+const MAKE_PRIVATE_BUTTON: &str = "Make Private";
+const DUE_DATE_BUTTON: &str = "Due Date";
+const OVERRIDE_CAP_BUTTON: &str = "Override Cap";
+const DID_YOU_MEAN_BUTTON: &str = "Did You Mean";
+const ADD_USERS_BUTTON: &str = "Add Them";
+const MAKE_PUBLIC_BUTTON: &str = "Make Public";
+const TRANSFER_PAYMENT_MESSAGE: &str =
+    "🔁 This payment is a transfer, so it counts towards balances but not spendings!";
+const PRIVATE_PAYMENT_MESSAGE: &str = "🔒 This payment is private, so I'll only show its description to those involved. They can see it anytime by sending me /private in a direct message!";
+
+const NO_CURRENCY_BUTTON: &str = "No Currency";
+
 #[derive(Clone, Debug)]
 pub struct AddPaymentParams {
     chat_id: String,
@@ -39,16 +90,27 @@ pub struct AddPaymentParams {
     currency: Option<Currency>,
     total: Option<i64>,
     debts: Option<Vec<(String, i64)>>,
+    surcharges: Vec<Surcharge>,
+    is_private: bool,
+    due_date: Option<String>,
+    is_transfer: bool,
+    rate: Option<f64>,
 }
 
 #[derive(Clone, Debug)]
 pub enum AddPaymentEdit {
     Description,
+    Date,
+    DueDate,
     Creditor,
     Total,
+    Currency,
     DebtsEqual,
     DebtsExact,
     DebtsRatio,
+    DebtsPercent,
+    DebtsAdjusted,
+    DebtsItemized,
 }
 
 #[derive(Clone, Debug)]
@@ -56,10 +118,40 @@ pub enum AddDebtsFormat {
     Equal,
     Exact,
     Ratio,
+    Percent,
+    Adjusted,
+    Itemized,
+}
+
+// Gets the message key of the instructions for entering debts in a format
+pub fn get_debts_instructions(debts_format: &AddDebtsFormat) -> &'static str {
+    match debts_format {
+        AddDebtsFormat::Equal => DEBT_EQUAL_INSTRUCTIONS,
+        AddDebtsFormat::Exact => DEBT_EXACT_INSTRUCTIONS,
+        AddDebtsFormat::Ratio => DEBT_RATIO_INSTRUCTIONS,
+        AddDebtsFormat::Percent => DEBT_PERCENT_INSTRUCTIONS,
+        AddDebtsFormat::Adjusted => DEBT_ADJUSTED_INSTRUCTIONS,
+        AddDebtsFormat::Itemized => DEBT_ITEMIZED_INSTRUCTIONS,
+    }
+}
+
+// DebtItem is a line item of a bill, shared equally by its users
+// Items without users, such as tax and tip, are shared proportionally by everyone
+#[derive(Clone, Debug)]
+pub struct DebtItem {
+    pub description: String,
+    pub amount: i64,
+    pub users: Vec<String>,
 }
 
-const CANCEL_MESSAGE: &str =
-    "Okay! I've cancelled adding the payment. No changes have been made! 🌟";
+// Surcharge is an amount added on top of the subtotal of a payment, such as tip and tax
+// Surcharges are shared proportionally by everyone, by what they owe
+#[derive(Clone, Debug)]
+pub struct Surcharge {
+    pub description: String,
+    pub percent: Option<f64>,
+    pub amount: i64,
+}
 
 // Controls the state for misc handler actions that return to same state.
 async fn repeat_state(
@@ -69,14 +161,14 @@ async fn repeat_state(
 ) -> HandlerResult {
     match state {
         State::AddDescription { mut messages } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue.update(State::AddDescription { messages }).await?;
         }
         State::AddCreditor {
             mut messages,
             payment,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::AddCreditor { messages, payment })
                 .await?;
@@ -85,16 +177,25 @@ async fn repeat_state(
             mut messages,
             payment,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::AddTotal { messages, payment })
                 .await?;
         }
+        State::AddTotalCurrency {
+            mut messages,
+            payment,
+        } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::AddTotalCurrency { messages, payment })
+                .await?;
+        }
         State::AddDebtSelection {
             mut messages,
             payment,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::AddDebtSelection { messages, payment })
                 .await?;
@@ -104,7 +205,7 @@ async fn repeat_state(
             payment,
             debts_format,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::AddDebt {
                     messages,
@@ -113,11 +214,41 @@ async fn repeat_state(
                 })
                 .await?;
         }
+        State::AddDebtPicker {
+            mut messages,
+            payment,
+            users,
+            selected,
+        } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::AddDebtPicker {
+                    messages,
+                    payment,
+                    users,
+                    selected,
+                })
+                .await?;
+        }
+        State::AddDebtItems {
+            mut messages,
+            payment,
+            items,
+        } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::AddDebtItems {
+                    messages,
+                    payment,
+                    items,
+                })
+                .await?;
+        }
         State::AddConfirm {
             mut messages,
             payment,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::AddConfirm { messages, payment })
                 .await?;
@@ -126,7 +257,7 @@ async fn repeat_state(
             mut messages,
             payment,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::AddEditMenu { messages, payment })
                 .await?;
@@ -136,7 +267,7 @@ async fn repeat_state(
             payment,
             edit,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::AddEdit {
                     messages,
@@ -149,7 +280,7 @@ async fn repeat_state(
             mut messages,
             payment,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::AddEditDebtsMenu { messages, payment })
                 .await?;
@@ -161,48 +292,150 @@ async fn repeat_state(
 
 // Controls the dialogue for ending an add payment operation.
 async fn complete_add_payment(
+    redis: &Redis,
     bot: &Bot,
     dialogue: UserDialogue,
     chat_id: &str,
-    messages: Vec<MessageId>,
+    messages: MessageTracker,
 ) -> HandlerResult {
-    if is_erase_messages(chat_id) {
-        delete_bot_messages(&bot, chat_id, messages).await?;
-    }
+    cleanup_messages(redis, bot, chat_id, messages).await?;
     dialogue.exit().await?;
     Ok(())
 }
 
+// Displays the breakdown of the total into the subtotal and each surcharge, if any
+fn display_surcharges(
+    total: i64,
+    surcharges: &Vec<Surcharge>,
+    currency: Currency,
+    currency_format: CurrencyFormat,
+) -> String {
+    if surcharges.is_empty() {
+        return "".to_string();
+    }
+
+    let subtotal = total - surcharges.iter().map(|s| s.amount).sum::<i64>();
+    let mut display = format!(
+        "  Subtotal: {}\n",
+        display_currency_amount(subtotal, currency.clone(), currency_format)
+    );
+    for surcharge in surcharges {
+        let percent = match surcharge.percent {
+            Some(percent) => format!(" ({percent}%)"),
+            None => "".to_string(),
+        };
+        display.push_str(&format!(
+            "  + {}{}: {}\n",
+            surcharge.description,
+            percent,
+            display_currency_amount(surcharge.amount, currency.clone(), currency_format)
+        ));
+    }
+    display
+}
+
 /* Displays a payment entry (being added) in String format.
 */
-fn display_add_payment(payment: &AddPaymentParams) -> String {
+async fn display_add_payment(redis: &Redis, payment: &AddPaymentParams) -> String {
     let description = match &payment.description {
+        Some(desc) if payment.is_private => format!("Description: {} 🔒\n", desc),
         Some(desc) => format!("Description: {}\n", desc),
         None => "".to_string(),
     };
+    let date = format!(
+        "Date: {}\n",
+        display_payment_date(
+            &payment.datetime,
+            retrieve_time_zone(redis, &payment.chat_id).await
+        )
+    );
+    let due_date = match &payment.due_date {
+        Some(due_date) => format!("Due: {}\n", display_due_date(due_date)),
+        None => "".to_string(),
+    };
+    let transfer = if payment.is_transfer {
+        "Type: Transfer 🔁\n"
+    } else {
+        ""
+    };
     let creditor = match &payment.creditor {
-        Some(cred) => format!("Payer: {}\n", display_username(cred)),
+        Some(cred) => format!(
+            "Payer: {}\n",
+            display_username(redis, &payment.chat_id, cred).await
+        ),
         None => "".to_string(),
     };
     let total = match &payment.total {
         Some(total) => match &payment.currency {
-            Some(currency) => format!(
-                "Total: {}\n",
-                display_currency_amount(*total, use_currency(currency.clone(), &payment.chat_id))
-            ),
+            Some(currency) => {
+                let currency = use_currency(redis, currency.clone(), &payment.chat_id).await;
+                let currency_format = retrieve_currency_format(redis, &payment.chat_id).await;
+                let rate = match payment.rate {
+                    Some(rate) => format!(
+                        "Rate: 1 {} = {} {}\n",
+                        currency.0,
+                        rate,
+                        get_chat_default_currency(redis, &payment.chat_id).await.0
+                    ),
+                    None => "".to_string(),
+                };
+                format!(
+                    "Total: {}\n{}{}",
+                    display_currency_amount(*total, currency.clone(), currency_format),
+                    rate,
+                    display_surcharges(*total, &payment.surcharges, currency, currency_format)
+                )
+            }
             None => "".to_string(),
         },
         None => "".to_string(),
     };
     let debts = match &payment.debts {
         Some(debts) => match &payment.currency {
-            Some(currency) => format!("Split:\n{}", display_debts(&debts, currency.1)),
+            Some(currency) => format!(
+                "Split:\n{}",
+                display_debts(redis, &payment.chat_id, debts, currency.1).await
+            ),
             None => "".to_string(),
         },
         None => "".to_string(),
     };
 
-    format!("{}{}{}{}\n", description, creditor, total, debts)
+    format!(
+        "{}{}{}{}{}{}{}\n",
+        description, date, due_date, transfer, creditor, total, debts
+    )
+}
+
+// Parses the total of a payment with its currency and surcharges, and a conversion rate, if given.
+// A rate is only given for a foreign currency, as it converts into the default currency of the chat.
+async fn parse_total_amount_rate(
+    redis: &Redis,
+    chat_id: &str,
+    text: &str,
+) -> Result<(i64, Currency, Vec<Surcharge>, Option<f64>), BotError> {
+    let number_format = retrieve_number_format(redis, chat_id).await;
+    let (text, rate) = parse_conversion_rate(text, number_format)?;
+    let (total, currency, surcharges) = parse_total_amount(
+        text,
+        number_format,
+        &retrieve_allowed_currencies(redis, chat_id).await,
+    )?;
+
+    if rate.is_some() {
+        let default_currency = get_chat_default_currency(redis, chat_id).await;
+        if currency.0 == CURRENCY_DEFAULT.0 {
+            return Err(BotError::UserError(Text::new(RATE_CURRENCY_MISSING)));
+        } else if default_currency.0 == CURRENCY_DEFAULT.0 {
+            return Err(BotError::UserError(Text::new(RATE_NO_DEFAULT_CURRENCY)));
+        } else if currency.0 == default_currency.0 {
+            return Err(BotError::UserError(
+                Text::new(RATE_ALREADY_DEFAULT).arg("currency", &currency.0),
+            ));
+        }
+    }
+
+    Ok((total, currency, surcharges, rate))
 }
 
 /* Add a payment entry in a group chat.
@@ -210,45 +443,250 @@ fn display_add_payment(payment: &AddPaymentParams) -> String {
  * Is not a normal endpoint function, just a temporary transition function.
  */
 async fn display_add_overview(
+    redis: &Redis,
     bot: &Bot,
     dialogue: &UserDialogue,
     msg: &Message,
-    mut messages: Vec<MessageId>,
+    mut messages: MessageTracker,
     payment: AddPaymentParams,
 ) -> HandlerResult {
     let buttons = vec!["Cancel", "Edit", "Confirm"];
-    let keyboard = make_keyboard(buttons, Some(2));
+    let language = retrieve_language(redis, &payment.chat_id).await;
+    let mut keyboard = make_keyboard(buttons, Some(2), &language);
+
+    // Debtors that look like typos of users known here are pointed out, before they become new users
+    let corrections = get_debtor_corrections(redis, &payment).await;
+    let mut suggestion = String::new();
+    if !corrections.is_empty() {
+        let typos: Vec<String> = corrections
+            .iter()
+            .map(|(typo, _)| format!("@{typo}"))
+            .collect();
+        let similar: Vec<String> = corrections
+            .iter()
+            .map(|(_, similar)| format!("@{similar}"))
+            .collect();
+        let similar = similar.join(", ");
+        suggestion = translate(
+            &language,
+            DEBTOR_CORRECTION_SUGGESTION,
+            &[("typos", &typos.join(", ")), ("similar", &similar)],
+        );
+        keyboard = keyboard.append_row(vec![InlineKeyboardButton::callback(
+            translate(
+                &language,
+                DEBTOR_CORRECTION_BUTTON,
+                &[("similar", &similar)],
+            ),
+            DID_YOU_MEAN_BUTTON,
+        )]);
+    }
 
-    let new_message = send_bot_message(&bot, &msg, format!("Here's what I've got so far! 📝\n\n{}Do you want to confirm this entry or would you like to make any changes?", display_add_payment(&payment)))
+    let overview = translate(
+        &language,
+        ADD_PAYMENT_OVERVIEW,
+        &[
+            ("overview", &display_add_payment(redis, &payment).await),
+            ("suggestion", &suggestion),
+        ],
+    );
+    let new_message = send_bot_message(bot, msg, overview)
         .reply_markup(keyboard)
-        .await?.id;
-    messages.push(new_message);
+        .await?
+        .id;
+    messages.track(new_message);
     dialogue
         .update(State::AddConfirm { messages, payment })
         .await?;
     Ok(())
 }
 
+// Finds the debtors of a payment that are likely typos of users known in the chat,
+// along with the user each was meant to be. The payer is given on its own, so it is left out.
+async fn get_debtor_corrections(
+    redis: &Redis,
+    payment: &AddPaymentParams,
+) -> Vec<(String, String)> {
+    let debts = match &payment.debts {
+        Some(debts) => debts,
+        None => return Vec::new(),
+    };
+    let known = get_picker_users(redis, &payment.chat_id, Vec::new()).await;
+    debts
+        .iter()
+        .filter(|(user, _)| match &payment.creditor {
+            Some(creditor) => !is_username_equal(user, creditor),
+            None => true,
+        })
+        .filter_map(|(user, _)| {
+            find_similar_name(user, &known).map(|similar| (user.clone(), similar))
+        })
+        .collect()
+}
+
+// Replaces debtors with the users they were meant to be,
+// combining their shares if those users are also in the split
+fn apply_debtor_corrections(
+    debts: Vec<(String, i64)>,
+    corrections: &[(String, String)],
+) -> Vec<(String, i64)> {
+    let mut corrected: Vec<(String, i64)> = Vec::new();
+    for (user, amount) in debts {
+        let user = match corrections.iter().find(|(typo, _)| *typo == user) {
+            Some((_, similar)) => similar.clone(),
+            None => user,
+        };
+        match corrected
+            .iter_mut()
+            .find(|(existing, _)| is_username_equal(existing, &user))
+        {
+            Some(existing) => existing.1 += amount,
+            None => corrected.push((user, amount)),
+        }
+    }
+    corrected
+}
+
+// Gets the users of a payment that are not yet in the chat, if the chat has strict members on.
+// If they cannot be retrieved, the payment is still checked when it is added.
+async fn get_unknown_users(redis: &Redis, payment: &AddPaymentParams) -> Vec<String> {
+    match (&payment.creditor, &payment.debts) {
+        (Some(creditor), Some(debts)) => retrieve_unknown_users(
+            redis,
+            &payment.chat_id,
+            &payment.sender_username,
+            creditor,
+            debts,
+        )
+        .await
+        .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+// Confirms a payment, warning first if it would take the chat over its weekly spending cap
+async fn confirm_add_payment(
+    redis: &Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    mut messages: MessageTracker,
+    payment: AddPaymentParams,
+    query: CallbackQuery,
+) -> HandlerResult {
+    match (
+        get_weekly_cap_warning(redis, &payment).await,
+        &query.message,
+    ) {
+        (Some(warning), Some(msg)) => {
+            let language = retrieve_language(redis, &payment.chat_id).await;
+            let keyboard = make_keyboard(
+                vec!["Cancel", "Edit", OVERRIDE_CAP_BUTTON],
+                Some(2),
+                &language,
+            );
+            edit_tracked_message(&bot, msg, &mut messages, warning)
+                .reply_markup(keyboard)
+                .await?;
+        }
+        _ => {
+            call_processor_add_payment(redis, bot, dialogue, messages, payment, query).await?;
+        }
+    }
+    Ok(())
+}
+
+// Checks if adding a payment would take the chat over its weekly spending cap.
+// Returns the warning to show if so. Payments that cannot be checked are let through.
+async fn get_weekly_cap_warning(redis: &Redis, payment: &AddPaymentParams) -> Option<String> {
+    let (currency, total) = payment.currency.as_ref().zip(payment.total)?;
+    let cap = match check_weekly_cap(
+        redis,
+        &payment.chat_id,
+        &currency.0,
+        total,
+        payment.is_transfer,
+    )
+    .await
+    {
+        Ok(cap) => cap?,
+        Err(err) => {
+            log::error!(
+                "Add Payment Confirm - Failed to check weekly cap for chat {}: {}",
+                payment.chat_id,
+                err.to_string()
+            );
+            return None;
+        }
+    };
+
+    let payer = match &payment.creditor {
+        Some(creditor) => display_username(redis, &payment.chat_id, creditor).await,
+        None => "the payer".to_string(),
+    };
+    Some(format!(
+        "🚨🧢 WEEKLY CAP REACHED 🧢🚨\n\nEveryone here has spent {} this week, and this payment goes over the cap!\n\n{}Only {payer}, who paid, can override the cap to add it anyway.",
+        display_weekly_cap(redis, &payment.chat_id, &cap).await,
+        display_add_payment(redis, payment).await
+    ))
+}
+
+// Checks if a callback query comes from the payer of a payment
+fn is_payment_payer(query: &CallbackQuery, payment: &AddPaymentParams) -> bool {
+    match (&query.from.username, &payment.creditor) {
+        (Some(username), Some(creditor)) => is_username_equal(username, creditor),
+        _ => false,
+    }
+}
+
+// Make the menu of payment details to edit,
+// with the options to make the payment private or not, and a transfer or not
+fn make_keyboard_add_edit(payment: &AddPaymentParams, language: &str) -> InlineKeyboardMarkup {
+    let privacy_button = if payment.is_private {
+        MAKE_PUBLIC_BUTTON
+    } else {
+        MAKE_PRIVATE_BUTTON
+    };
+    let transfer_button = if payment.is_transfer {
+        MAKE_EXPENSE_BUTTON
+    } else {
+        MAKE_TRANSFER_BUTTON
+    };
+    let buttons = vec![
+        "Description",
+        "Date",
+        "Payer",
+        "Total",
+        "Split",
+        DUE_DATE_BUTTON,
+        privacy_button,
+        transfer_button,
+        "Back",
+    ];
+    make_keyboard(buttons, Some(2), language)
+}
+
 /* Add a payment entry in a group chat.
  * Displays a button menu for user to choose which part of the payment details to edit.
  */
 async fn display_add_edit_menu(
+    redis: &Redis,
     bot: Bot,
     dialogue: UserDialogue,
     query: CallbackQuery,
-    messages: Vec<MessageId>,
+    mut messages: MessageTracker,
     payment: AddPaymentParams,
 ) -> HandlerResult {
-    let buttons = vec!["Description", "Payer", "Total", "Split", "Back"];
-    let keyboard = make_keyboard(buttons, Some(2));
+    let language = retrieve_language(redis, &payment.chat_id).await;
+    let keyboard = make_keyboard_add_edit(&payment, &language);
 
-    if let Some(Message { id, chat, .. }) = query.message {
-        bot.edit_message_text(
-            chat.id,
-            id,
+    if let Some(msg) = query.message {
+        edit_tracked_message(
+            &bot,
+            &msg,
+            &mut messages,
             format!(
                 "{}Sure! What would you like to edit?",
-                display_add_payment(&payment)
+                display_add_payment(redis, &payment).await
             ),
         )
         .reply_markup(keyboard)
@@ -262,34 +700,55 @@ async fn display_add_edit_menu(
 
 /* Parses a string representing debts, and handles it accordingly
 */
+#[allow(clippy::too_many_arguments)]
 async fn handle_debts(
+    redis: &Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
-    messages: Vec<MessageId>,
+    messages: MessageTracker,
     payment: AddPaymentParams,
     debts_format: AddDebtsFormat,
 ) -> HandlerResult {
-    let error_msg = match debts_format {
-        AddDebtsFormat::Equal => DEBT_EQUAL_INSTRUCTIONS_MESSAGE,
-        AddDebtsFormat::Exact => DEBT_EXACT_INSTRUCTIONS_MESSAGE,
-        AddDebtsFormat::Ratio => DEBT_RATIO_INSTRUCTIONS_MESSAGE,
-    };
+    let error_msg = t(
+        redis,
+        &payment.chat_id,
+        get_debts_instructions(&debts_format),
+        &[],
+    )
+    .await;
     match msg.text() {
         Some(text) => {
-            let debts = process_debts(
-                debts_format,
-                text,
-                &payment.creditor,
-                payment.currency.clone(),
-                payment.total,
-            );
+            // Exact amounts and adjustments are given before surcharges, which are then shared
+            let rounding = retrieve_rounding(redis, &payment.chat_id).await;
+            let groups = retrieve_chat_split_groups(redis, &payment.chat_id).await;
+            let surcharge: i64 = payment.surcharges.iter().map(|s| s.amount).sum();
+            let debts = match debts_format {
+                AddDebtsFormat::Exact | AddDebtsFormat::Adjusted if surcharge > 0 => process_debts(
+                    debts_format,
+                    text,
+                    &payment.creditor,
+                    payment.currency.clone(),
+                    payment.total.map(|total| total - surcharge),
+                    rounding,
+                    &groups,
+                )
+                .map(|debts| share_surcharge(debts, surcharge)),
+                _ => process_debts(
+                    debts_format,
+                    text,
+                    &payment.creditor,
+                    payment.currency.clone(),
+                    payment.total,
+                    rounding,
+                    &groups,
+                ),
+            };
             if let Err(err) = debts {
-                let new_message =
-                    send_bot_message(&bot, &msg, format!("{}\n\n{error_msg}", err.to_string()))
-                        .await?
-                        .id;
+                let new_message = send_bot_message(&bot, &msg, format!("{}\n\n{error_msg}", err))
+                    .await?
+                    .id;
                 repeat_state(dialogue, state, new_message).await?;
                 return Ok(());
             }
@@ -304,9 +763,14 @@ async fn handle_debts(
                 currency: payment.currency,
                 total: payment.total,
                 debts: Some(debts?),
+                surcharges: payment.surcharges,
+                is_private: payment.is_private,
+                due_date: payment.due_date,
+                is_transfer: payment.is_transfer,
+                rate: payment.rate,
             };
 
-            display_add_overview(&bot, &dialogue, &msg, messages, new_payment).await?;
+            display_add_overview(redis, &bot, &dialogue, &msg, messages, new_payment).await?;
         }
         None => {
             let new_message = send_bot_message(&bot, &msg, error_msg.to_string())
@@ -321,9 +785,10 @@ async fn handle_debts(
 /* Calls processor to execute the adding of the payment entry.
 */
 async fn call_processor_add_payment(
+    redis: &Redis,
     bot: Bot,
     dialogue: UserDialogue,
-    messages: Vec<MessageId>,
+    mut messages: MessageTracker,
     payment: AddPaymentParams,
     query: CallbackQuery,
 ) -> HandlerResult {
@@ -337,8 +802,13 @@ async fn call_processor_add_payment(
                     "Add Payment Submission - Description not found for payment: {:?}",
                     payment_clone
                 );
-                send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
-                complete_add_payment(&bot, dialogue, &chat_id.to_string(), messages).await?;
+                send_bot_message(
+                    &bot,
+                    &msg,
+                    t(redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                )
+                .await?;
+                complete_add_payment(redis, &bot, dialogue, &chat_id.to_string(), messages).await?;
                 return Ok(());
             }
         };
@@ -349,8 +819,13 @@ async fn call_processor_add_payment(
                     "Add Payment Submission - Creditor not found for payment: {:?}",
                     payment_clone
                 );
-                send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
-                complete_add_payment(&bot, dialogue, &chat_id.to_string(), messages).await?;
+                send_bot_message(
+                    &bot,
+                    &msg,
+                    t(redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                )
+                .await?;
+                complete_add_payment(redis, &bot, dialogue, &chat_id.to_string(), messages).await?;
                 return Ok(());
             }
         };
@@ -361,8 +836,13 @@ async fn call_processor_add_payment(
                     "Add Payment Submission - Currency not found for payment: {:?}",
                     payment_clone
                 );
-                send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
-                complete_add_payment(&bot, dialogue, &chat_id.to_string(), messages).await?;
+                send_bot_message(
+                    &bot,
+                    &msg,
+                    t(redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                )
+                .await?;
+                complete_add_payment(redis, &bot, dialogue, &chat_id.to_string(), messages).await?;
                 return Ok(());
             }
         };
@@ -373,8 +853,13 @@ async fn call_processor_add_payment(
                     "Add Payment Submission - Total not found for payment: {:?}",
                     payment_clone
                 );
-                send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
-                complete_add_payment(&bot, dialogue, &chat_id.to_string(), messages).await?;
+                send_bot_message(
+                    &bot,
+                    &msg,
+                    t(redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                )
+                .await?;
+                complete_add_payment(redis, &bot, dialogue, &chat_id.to_string(), messages).await?;
                 return Ok(());
             }
         };
@@ -385,13 +870,126 @@ async fn call_processor_add_payment(
                     "Add Payment Submission - Debts not found for payment: {:?}",
                     payment_clone
                 );
-                send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
-                complete_add_payment(&bot, dialogue, &chat_id.to_string(), messages).await?;
+                send_bot_message(
+                    &bot,
+                    &msg,
+                    t(redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                )
+                .await?;
+                complete_add_payment(redis, &bot, dialogue, &chat_id.to_string(), messages).await?;
                 return Ok(());
             }
         };
-        let payment_overview = display_add_payment(&payment_clone);
+        // Descriptions of private payments are hidden in messages that stay in the chat
+        let shown_description = if payment.is_private {
+            PRIVATE_DESCRIPTION.to_string()
+        } else {
+            description.clone()
+        };
+        let payment_overview = display_add_payment(
+            redis,
+            &AddPaymentParams {
+                description: Some(shown_description.clone()),
+                is_private: false,
+                ..payment_clone.clone()
+            },
+        )
+        .await;
+
+        // With dual entry verification, payments need confirmation from the payer and a debtor.
+        // Otherwise, large payments need approval from someone else involved.
+        // Either way, they only affect balances once confirmed or approved.
+        let is_dual_entry = is_dual_entry_required(redis, &payment.chat_id, &creditor, &debts)
+            .await
+            .unwrap_or(false);
+        let approvers = get_approvers(&creditor, &debts, &payment.sender_username);
+        if is_dual_entry
+            || !approvers.is_empty()
+                && is_approval_required(redis, &payment.chat_id, &currency, total)
+                    .await
+                    .unwrap_or(false)
+        {
+            let language = retrieve_language(redis, &payment.chat_id).await;
+            let dual_entry_request = display_dual_entry_request(&creditor, &debts, &language);
+            let pending_payment = add_pending_payment(
+                redis,
+                payment.chat_id.clone(),
+                payment.sender_username,
+                payment.sender_id,
+                payment.datetime,
+                &description,
+                &creditor,
+                &currency.0,
+                total,
+                debts,
+                payment.is_private,
+                payment.is_transfer,
+                payment.rate,
+            )
+            .await;
+            match pending_payment {
+                Ok(payment_id) => {
+                    let (request, keyboard) = if is_dual_entry {
+                        (
+                            format!(
+                                "{}\n\n{}{}",
+                                translate(&language, DUAL_ENTRY_NEEDED, &[]),
+                                payment_overview,
+                                dual_entry_request
+                            ),
+                            make_keyboard_dual_entry(&payment_id, &language),
+                        )
+                    } else {
+                        (
+                            format!(
+                                "{}\n\n{}{}",
+                                translate(&language, APPROVAL_NEEDED, &[]),
+                                payment_overview,
+                                display_approval_request(&approvers, &language)
+                            ),
+                            make_keyboard_approval(&payment_id, &language),
+                        )
+                    };
+                    send_bot_message(&bot, &msg, request)
+                        .reply_markup(keyboard)
+                        .await?;
+
+                    // Logging
+                    log::info!(
+                        "Add Payment Submission - Payment {} pending {} for user {} in chat {}: {:?}",
+                        payment_id,
+                        if is_dual_entry { "confirmation" } else { "approval" },
+                        payment_clone.sender_id,
+                        payment_clone.chat_id,
+                        payment_clone
+                    );
+                }
+                Err(err) => {
+                    let err = BotError::from(err);
+                    let reply = match &err {
+                        BotError::ProcessError(ProcessError::CrudError(_)) => {
+                            t(redis, &payment_clone.chat_id, ADD_PAYMENT_FAILED, &[]).await
+                        }
+                        _ => error_to_user_message(redis, &err, &payment_clone.chat_id).await,
+                    };
+                    send_bot_message(&bot, &msg, reply).await?;
+
+                    // Logging
+                    log::error!(
+                        "Add Payment Submission - Processor failed to add pending payment for user {} in chat {} with payment {:?}: {}",
+                        payment_clone.sender_id,
+                        payment_clone.chat_id,
+                        payment_clone,
+                        err.to_string()
+                    );
+                }
+            }
+            complete_add_payment(redis, &bot, dialogue, &chat_id.to_string(), messages).await?;
+            return Ok(());
+        }
+
         let updated_balances = add_payment(
+            redis,
             payment.chat_id.clone(),
             payment.sender_username,
             payment.sender_id,
@@ -401,26 +999,78 @@ async fn call_processor_add_payment(
             &currency.0,
             total,
             debts,
+            payment.is_private,
+            payment.is_transfer,
+            payment.rate,
         )
         .await;
         match updated_balances {
-            Ok(balances) => {
-                send_bot_message(
-                    &bot,
-                    &msg,
-                    format!("🎉 Yay! Payment added! 🎉\n\n{}", payment_overview,),
-                )
-                .await?;
-                send_bot_message(
-                    &bot,
-                    &msg,
+            Ok((payment_id, balances)) => {
+                if let Some(due_date) = &payment.due_date {
+                    if let Err(err) =
+                        update_payment_due_date(redis, &payment.chat_id, &payment_id, due_date)
+                            .await
+                    {
+                        // Logging
+                        log::error!(
+                            "Add Payment Submission - Processor failed to set due date of payment {} in chat {}: {}",
+                            payment_id,
+                            payment.chat_id,
+                            err.to_string()
+                        );
+                    }
+                }
+                let summary = format!(
+                    "🎉 Payment added: {} — {}",
+                    shown_description,
+                    display_currency_amount(
+                        total,
+                        use_currency(redis, currency.clone(), &payment.chat_id).await,
+                        retrieve_currency_format(redis, &payment.chat_id).await
+                    )
+                );
+                let private_message = if payment.is_private {
+                    format!("{PRIVATE_PAYMENT_MESSAGE}\n\n")
+                } else {
+                    "".to_string()
+                };
+                let transfer_message = if payment.is_transfer {
+                    format!("{TRANSFER_PAYMENT_MESSAGE}\n\n")
+                } else {
+                    "".to_string()
+                };
+                let details = vec![
+                    format!(
+                        "🎉 Yay! Payment added! 🎉\n\n{}{}{}",
+                        payment_overview, private_message, transfer_message
+                    ),
                     format!(
                         "{}{}",
-                        display_balance_header(&payment.chat_id, &currency.0),
-                        display_balances(&balances)
+                        display_balance_header(redis, &payment.chat_id, &currency.0).await,
+                        display_balances(redis, &payment.chat_id, &balances).await
                     ),
+                ];
+                let confirmation = send_completion_messages(
+                    redis,
+                    &bot,
+                    &msg,
+                    Some(&mut messages),
+                    summary,
+                    details,
                 )
                 .await?;
+                if let Err(err) =
+                    track_payment_confirmation(redis, &payment.chat_id, confirmation.0, &payment_id)
+                        .await
+                {
+                    // Logging
+                    log::error!(
+                        "Add Payment Submission - Processor failed to track confirmation of payment {} in chat {}: {}",
+                        payment_id,
+                        payment.chat_id,
+                        err.to_string()
+                    );
+                }
 
                 // Logging
                 log::info!(
@@ -429,16 +1079,28 @@ async fn call_processor_add_payment(
                     payment_clone.chat_id,
                     payment_clone
                     );
-            }
-            Err(err) => {
-                send_bot_message(
+
+                notify_spending_limits(redis, &bot, &msg).await?;
+                notify_added_users(
+                    redis,
                     &bot,
                     &msg,
-                    format!(
-                        "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't add the payment right now. Please try again later!\n\n"
-                    ),
+                    &payment_clone.sender_username,
+                    &description,
+                    currency,
+                    &payment_clone.debts.unwrap_or_default(),
                 )
-                .await?;
+                .await;
+            }
+            Err(err) => {
+                let err = BotError::from(err);
+                let reply = match &err {
+                    BotError::ProcessError(ProcessError::CrudError(_)) => {
+                        t(redis, &payment_clone.chat_id, ADD_PAYMENT_FAILED, &[]).await
+                    }
+                    _ => error_to_user_message(redis, &err, &payment_clone.chat_id).await,
+                };
+                send_bot_message(&bot, &msg, reply).await?;
 
                 // Logging
                 log::error!(
@@ -450,7 +1112,7 @@ async fn call_processor_add_payment(
                     );
             }
         }
-        complete_add_payment(&bot, dialogue, &chat_id.to_string(), messages).await?;
+        complete_add_payment(redis, &bot, dialogue, &chat_id.to_string(), messages).await?;
     }
     Ok(())
 }
@@ -461,19 +1123,24 @@ async fn call_processor_add_payment(
  * Does nothing, simply notifies the user.
  */
 pub async fn handle_repeated_add_payment(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
-    let new_message = send_bot_message(&bot,
-        &msg,
-        format!("🚫 Oops! It seems like you're already in the middle of adding a payment! Please finish or {COMMAND_CANCEL} this before starting another one with me."),
-        ).await?.id;
+    let reply = t(
+        &redis,
+        &msg.chat.id.to_string(),
+        ADD_PAYMENT_REPEATED,
+        &[("cancel", COMMAND_CANCEL)],
+    )
+    .await;
+    let new_message = send_bot_message(&bot, &msg, reply).await?.id;
 
     repeat_state(dialogue, state, new_message).await?;
     Ok(())
@@ -483,28 +1150,34 @@ pub async fn handle_repeated_add_payment(
  * Can be called at any step of the process.
  */
 pub async fn cancel_add_payment(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
-    send_bot_message(&bot, &msg, CANCEL_MESSAGE.to_string()).await?;
+    let reply = t(&redis, &msg.chat.id.to_string(), ADD_PAYMENT_CANCELLED, &[]).await;
+    send_bot_message(&bot, &msg, reply).await?;
 
     match state {
         State::AddDescription { messages }
         | State::AddCreditor { messages, .. }
         | State::AddTotal { messages, .. }
+        | State::AddTotalCurrency { messages, .. }
         | State::AddDebtSelection { messages, .. }
         | State::AddDebt { messages, .. }
+        | State::AddDebtPicker { messages, .. }
+        | State::AddDebtItems { messages, .. }
         | State::AddConfirm { messages, .. }
         | State::AddEditMenu { messages, .. }
         | State::AddEdit { messages, .. }
         | State::AddEditDebtsMenu { messages, .. } => {
-            complete_add_payment(&bot, dialogue, &msg.chat.id.to_string(), messages).await?;
+            complete_add_payment(&redis, &bot, dialogue, &msg.chat.id.to_string(), messages)
+                .await?;
         }
         _ => (),
     }
@@ -515,20 +1188,24 @@ pub async fn cancel_add_payment(
  * Called when user attempts to start another operation in the middle of adding a payment.
  */
 pub async fn block_add_payment(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
-    let new_message = send_bot_message(
-        &bot,
-        &msg,
-        format!("🚫 Oops! It seems like you're in the middle of adding a payment! Please finish or {COMMAND_CANCEL} this before starting something new with me."),
-        ).await?.id;
+    let reply = t(
+        &redis,
+        &msg.chat.id.to_string(),
+        ADD_PAYMENT_BLOCKED,
+        &[("cancel", COMMAND_CANCEL)],
+    )
+    .await;
+    let new_message = send_bot_message(&bot, &msg, reply).await?.id;
 
     repeat_state(dialogue, state, new_message).await?;
     Ok(())
@@ -538,83 +1215,250 @@ pub async fn block_add_payment(
  * Bot will ask for user to send messages to fill in required information,
  * before presenting the compiled information for confirmation with a menu.
  */
-pub async fn action_add_payment(bot: Bot, dialogue: UserDialogue, msg: Message) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+pub async fn action_add_payment(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: Message,
+) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
+    let chat_id = msg.chat.id.to_string();
+    match validate_payment_count(&redis, &chat_id).await {
+        Ok(()) => {}
+        Err(BotError::UserError(err)) => {
+            send_bot_message(&bot, &msg, t_text(&redis, &chat_id, &err).await).await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    }
+
     let new_message = send_bot_message(
         &bot,
         &msg,
-        format!(
-            "Absolutely, 🙌 let's get started! \n\nWhat's the description for this new payment?"
-        ),
+        "Absolutely, 🙌 let's get started! \n\nWhat's the description for this new payment?"
+            .to_string(),
     )
     .await?
     .id;
 
     dialogue
         .update(State::AddDescription {
-            messages: vec![new_message],
+            messages: MessageTracker::new(new_message).with_command(msg.id),
         })
         .await?;
     Ok(())
 }
 
-/* Add a payment entry in a group chat.
- * Bot receives a description string from user, and proceeds to ask for creditor.
+/* Add a payment entry in a group chat, pre-filled with the details of a template.
+ * Skips straight to the overview, where the user can confirm or edit the payment.
  */
-pub async fn action_add_description(
-    bot: Bot,
-    dialogue: UserDialogue,
-    state: State,
-    msg: Message,
-    mut messages: Vec<MessageId>,
+pub async fn action_add_payment_template(
+    redis: &Redis,
+    bot: &Bot,
+    dialogue: &UserDialogue,
+    msg: &Message,
+    template: PaymentTemplate,
 ) -> HandlerResult {
-    match msg.text() {
-        Some(text) => {
-            let user = msg.from();
-            if let Some(user) = user {
-                if let Some(username) = &user.username {
-                    let username = parse_username(username);
+    let chat_id = msg.chat.id.to_string();
+    match validate_payment_count(redis, &chat_id).await {
+        Ok(()) => {}
+        Err(BotError::UserError(err)) => {
+            send_bot_message(bot, msg, t_text(redis, &chat_id, &err).await).await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    }
 
-                    if let Err(err) = &username {
-                        let new_message =
-                            send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string())
-                                .await?
-                                .id;
-                        repeat_state(dialogue.clone(), state, new_message).await?;
+    let username = match msg.from().and_then(|user| user.username.as_ref()) {
+        Some(username) => parse_username(username),
+        None => Err(BotError::UserError(Text::new(ADD_PAYMENT_NO_USERNAME))),
+    };
+    let sender_username = match username {
+        Ok(username) => username,
+        Err(err) => {
+            send_bot_message(
+                bot,
+                msg,
+                t(redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+            )
+            .await?;
 
-                        // Logging
-                        log::error!(
-                            "Add Payment Description - Failed to parse username for user {}: {}",
-                            user.id,
-                            err.to_string()
-                        );
-                    }
+            // Logging
+            log::error!(
+                "Add Payment Template - Failed to parse username for user {:?}: {}",
+                msg.from().map(|user| user.id),
+                err.to_string()
+            );
+            return Ok(());
+        }
+    };
+
+    let payment = AddPaymentParams {
+        chat_id: msg.chat.id.to_string(),
+        sender_id: msg
+            .from()
+            .map(|user| user.id.to_string())
+            .unwrap_or_default(),
+        sender_username,
+        datetime: msg.date.to_string(),
+        description: Some(template.description),
+        creditor: Some(template.creditor),
+        currency: Some(get_currency(&template.currency).unwrap_or(get_default_currency())),
+        total: Some(template.total),
+        debts: Some(template.debts),
+        surcharges: Vec::new(),
+        is_private: false,
+        due_date: None,
+        is_transfer: false,
+        rate: None,
+    };
+    display_add_overview(
+        redis,
+        bot,
+        dialogue,
+        msg,
+        MessageTracker::default(),
+        payment,
+    )
+    .await
+}
+
+/* Add a payment entry in a group chat, duplicating an existing payment.
+ * Bot receives a callback query from the detail card of the payment,
+ * and prefills a new payment with its details, dated now and added by the user who tapped.
+ * Then, proceeds straight to the overview for the user to confirm or edit.
+ */
+pub async fn action_add_payment_duplicate(
+    redis: &Redis,
+    bot: &Bot,
+    dialogue: &UserDialogue,
+    msg: &Message,
+    user: &User,
+    messages: MessageTracker,
+    payment: &Payment,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    match validate_payment_count(redis, &chat_id).await {
+        Ok(()) => {}
+        Err(BotError::UserError(err)) => {
+            send_bot_message(bot, msg, t_text(redis, &chat_id, &err).await).await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    }
+
+    let sender_username = match user.username.as_deref().map(parse_username) {
+        Some(Ok(username)) => username,
+        _ => {
+            send_bot_message(
+                bot,
+                msg,
+                t(redis, &chat_id, ADD_PAYMENT_NO_USERNAME, &[]).await,
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let duplicate = AddPaymentParams {
+        chat_id,
+        sender_id: user.id.to_string(),
+        sender_username,
+        datetime: Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        description: Some(payment.description.clone()),
+        creditor: Some(payment.creditor.clone()),
+        currency: Some(payment.currency.clone()),
+        total: Some(payment.total),
+        debts: Some(payment.debts.clone()),
+        surcharges: Vec::new(),
+        is_private: payment.is_private,
+        due_date: None,
+        is_transfer: payment.is_transfer,
+        rate: None,
+    };
+    display_add_overview(redis, bot, dialogue, msg, messages, duplicate).await
+}
+
+/* Add a payment entry in a group chat.
+ * Bot receives a description string from user, and proceeds to ask for creditor.
+ */
+pub async fn action_add_description(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+    mut messages: MessageTracker,
+) -> HandlerResult {
+    match msg.text() {
+        Some(text) => {
+            let description = match validate_description(text) {
+                Ok(description) => description,
+                Err(err) => {
+                    let new_message = send_bot_message(
+                        &bot,
+                        &msg,
+                        error_to_user_message(&redis, &err, &msg.chat.id.to_string()).await,
+                    )
+                    .await?
+                    .id;
+                    repeat_state(dialogue, state, new_message).await?;
+                    return Ok(());
+                }
+            };
+            let user = msg.from();
+            if let Some(user) = user {
+                if let Some(username) = &user.username {
+                    let username = parse_username(username);
+
+                    if let Err(err) = &username {
+                        let new_message = send_bot_message(
+                            &bot,
+                            &msg,
+                            t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                        )
+                        .await?
+                        .id;
+                        repeat_state(dialogue.clone(), state, new_message).await?;
+
+                        // Logging
+                        log::error!(
+                            "Add Payment Description - Failed to parse username for user {}: {}",
+                            user.id,
+                            err.to_string()
+                        );
+                    }
 
                     let payment = AddPaymentParams {
                         chat_id: msg.chat.id.to_string(),
                         sender_id: user.id.to_string(),
                         sender_username: username?,
                         datetime: msg.date.to_string(),
-                        description: Some(text.to_string()),
+                        description: Some(description),
                         creditor: None,
                         currency: None,
                         total: None,
                         debts: None,
+                        surcharges: Vec::new(),
+                        is_private: false,
+                        due_date: None,
+                        is_transfer: false,
+                        rate: None,
                     };
                     let new_message = send_bot_message(
                         &bot,
                         &msg,
                         format!(
                             "{}Awesome! What's the Telegram username of the one who paid?",
-                            display_add_payment(&payment)
+                            display_add_payment(&redis, &payment).await
                         ),
                     )
                     .await?
                     .id;
-                    messages.push(new_message);
+                    messages.track(new_message);
                     dialogue
                         .update(State::AddCreditor { messages, payment })
                         .await?;
@@ -622,9 +1466,13 @@ pub async fn action_add_description(
             }
         }
         None => {
-            let new_message = send_bot_message(&bot, &msg, format!("{NO_TEXT_MESSAGE}"))
-                .await?
-                .id;
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
             repeat_state(dialogue, state, new_message).await?;
         }
     }
@@ -635,18 +1483,25 @@ pub async fn action_add_description(
  * Bot receives a creditor string from user, and proceeds to ask for total.
  */
 pub async fn action_add_creditor(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
-    (mut messages, payment): (Vec<MessageId>, AddPaymentParams),
+    (mut messages, payment): (MessageTracker, AddPaymentParams),
 ) -> HandlerResult {
     match msg.text() {
         Some(text) => {
             let text = parse_username(text);
 
             if let Err(err) = text {
-                let new_message = send_bot_message(&bot, &msg, err.to_string()).await?.id;
+                let new_message = send_bot_message(
+                    &bot,
+                    &msg,
+                    error_to_user_message(&redis, &err, &msg.chat.id.to_string()).await,
+                )
+                .await?
+                .id;
                 repeat_state(dialogue, state, new_message).await?;
                 return Ok(());
             }
@@ -661,18 +1516,31 @@ pub async fn action_add_creditor(
                 currency: None,
                 total: None,
                 debts: None,
+                surcharges: Vec::new(),
+                is_private: false,
+                due_date: None,
+                is_transfer: false,
+                rate: None,
             };
             let new_message = send_bot_message(
                 &bot,
                 &msg,
                 format!(
-                    "{}Nice! How much was the total amount?\n\n{TOTAL_INSTRUCTIONS_MESSAGE}",
-                    display_add_payment(&new_payment)
+                    "{}{}",
+                    display_add_payment(&redis, &new_payment).await,
+                    t_prompt(
+                        &redis,
+                        &new_payment.chat_id,
+                        TOTAL_PROMPT,
+                        &[],
+                        TOTAL_INSTRUCTIONS
+                    )
+                    .await
                 ),
             )
             .await?
             .id;
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::AddTotal {
                     messages,
@@ -681,31 +1549,77 @@ pub async fn action_add_creditor(
                 .await?;
         }
         None => {
-            let new_message = send_bot_message(&bot, &msg, format!("{NO_TEXT_MESSAGE}"))
-                .await?
-                .id;
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
             repeat_state(dialogue, state, new_message).await?;
         }
     }
     Ok(())
 }
 
+// Gets the currencies to suggest for a total without a currency, if the chat has no default
+async fn get_currency_suggestions(redis: &Redis, chat_id: &str) -> Vec<String> {
+    if get_chat_default_currency(redis, chat_id).await.0 != CURRENCY_DEFAULT.0 {
+        return Vec::new();
+    }
+
+    let allowed_currencies = retrieve_allowed_currencies(redis, chat_id).await;
+    let mut suggestions = retrieve_frequent_currencies(redis, chat_id, CURRENCY_SUGGESTIONS_MAX)
+        .await
+        .unwrap_or_default();
+    if !allowed_currencies.is_empty() {
+        suggestions.retain(|currency| allowed_currencies.contains(currency));
+        for currency in allowed_currencies {
+            if suggestions.len() < CURRENCY_SUGGESTIONS_MAX && !suggestions.contains(&currency) {
+                suggestions.push(currency);
+            }
+        }
+    }
+    suggestions
+}
+
+// Converts the total and surcharges of a payment entered without a currency to a currency
+fn convert_total_currency(payment: AddPaymentParams, currency: Currency) -> AddPaymentParams {
+    let factor = 10.0_f64.powi(CURRENCY_DEFAULT.1);
+    let convert = |amount: i64| normalize_amount(&currency, amount as f64 / factor);
+    AddPaymentParams {
+        total: payment.total.map(convert),
+        surcharges: payment
+            .surcharges
+            .into_iter()
+            .map(|surcharge| Surcharge {
+                amount: convert(surcharge.amount),
+                ..surcharge
+            })
+            .collect(),
+        currency: Some(currency),
+        ..payment
+    }
+}
+
 /* Add a payment entry in a group chat.
  * Bot receives a total f64 from user, and proceeds to ask for debts.
  */
 pub async fn action_add_total(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
-    (mut messages, payment): (Vec<MessageId>, AddPaymentParams),
+    (mut messages, payment): (MessageTracker, AddPaymentParams),
 ) -> HandlerResult {
     match msg.text() {
         Some(text) => {
-            let currency_amount = parse_currency_amount(text);
-            match currency_amount {
-                Ok((total, currency)) => {
-                    let new_payment = AddPaymentParams {
+            let chat_id = msg.chat.id.to_string();
+            match parse_total_amount_rate(&redis, &chat_id, text).await {
+                Ok((total, currency, surcharges, rate)) => {
+                    let mut is_currency_given = currency.0 != CURRENCY_DEFAULT.0;
+                    let mut new_payment = AddPaymentParams {
                         chat_id: payment.chat_id,
                         sender_id: payment.sender_id,
                         sender_username: payment.sender_username,
@@ -715,18 +1629,81 @@ pub async fn action_add_total(
                         currency: Some(currency),
                         total: Some(total),
                         debts: None,
+                        surcharges,
+                        is_private: payment.is_private,
+                        due_date: payment.due_date,
+                        is_transfer: payment.is_transfer,
+                        rate,
                     };
+
+                    // Without a currency, use the currency of the forum topic if it has its own
+                    if !is_currency_given {
+                        if let Some(currency) =
+                            retrieve_topic_default_currency(&redis, &chat_id, get_topic_id(&msg))
+                                .await
+                        {
+                            new_payment = convert_total_currency(new_payment, currency);
+                            is_currency_given = true;
+                        }
+                    }
+
+                    // Without a currency or a default, suggest the currencies often used instead
+                    if !is_currency_given {
+                        let suggestions = get_currency_suggestions(&redis, &chat_id).await;
+                        if !suggestions.is_empty() {
+                            let mut buttons: Vec<&str> = suggestions
+                                .iter()
+                                .map(|currency| currency.as_str())
+                                .collect();
+                            buttons.push(NO_CURRENCY_BUTTON);
+                            let new_message = send_bot_message(
+                                &bot,
+                                &msg,
+                                format!(
+                                    "{}Which currency is this in? 💱 These are the ones used most here!",
+                                    display_add_payment(&redis, &new_payment).await
+                                ),
+                            )
+                            .reply_markup(make_keyboard(
+                                buttons,
+                                Some(CURRENCY_SUGGESTIONS_MAX),
+                                &retrieve_language(&redis, &new_payment.chat_id).await,
+                            ))
+                            .await?
+                            .id;
+                            messages.track(new_message);
+                            dialogue
+                                .update(State::AddTotalCurrency {
+                                    messages,
+                                    payment: new_payment,
+                                })
+                                .await?;
+                            return Ok(());
+                        }
+                    }
+
                     let new_message = send_bot_message(
                         &bot,
                         &msg,
                         format!(
-                            "{}Fantastic! How are we splitting this?\n\n{DEBT_EQUAL_DESCRIPTION_MESSAGE}{DEBT_EXACT_DESCRIPTION_MESSAGE}{DEBT_RATIO_DESCRIPTION_MESSAGE}",
-                            display_add_payment(&new_payment)
-                            ),
+                            "{}{}",
+                            display_add_payment(&redis, &new_payment).await,
+                            t_prompt(
+                                &redis,
+                                &new_payment.chat_id,
+                                SPLIT_PROMPT,
+                                &[],
+                                DEBT_FORMATS
                             )
-                        .reply_markup(make_keyboard_debt_selection())
-                        .await?.id;
-                    messages.push(new_message);
+                            .await
+                        ),
+                    )
+                    .reply_markup(make_keyboard_debt_selection(
+                        &retrieve_language(&redis, &new_payment.chat_id).await,
+                    ))
+                    .await?
+                    .id;
+                    messages.track(new_message);
                     dialogue
                         .update(State::AddDebtSelection {
                             messages,
@@ -738,7 +1715,11 @@ pub async fn action_add_total(
                     let new_message = send_bot_message(
                         &bot,
                         &msg,
-                        format!("{}\n\n{TOTAL_INSTRUCTIONS_MESSAGE}", err.to_string()),
+                        format!(
+                            "{}\n\n{}",
+                            err,
+                            t(&redis, &msg.chat.id.to_string(), TOTAL_INSTRUCTIONS, &[]).await
+                        ),
                     )
                     .await?
                     .id;
@@ -748,89 +1729,605 @@ pub async fn action_add_total(
             }
         }
         None => {
-            let new_message = send_bot_message(&bot, &msg, format!("{NO_TEXT_MESSAGE}"))
-                .await?
-                .id;
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
+            repeat_state(dialogue, state, new_message).await?;
+        }
+    }
+    Ok(())
+}
+
+/* Add a payment entry in a group chat.
+ * Bot receives a callback query with the currency of the total, from the suggested currencies.
+ * Amounts entered without a currency are converted to the decimal places of the chosen currency.
+ */
+pub async fn action_add_total_currency(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    (mut messages, payment): (MessageTracker, AddPaymentParams),
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+
+        let payment = match button.as_str() {
+            NO_CURRENCY_BUTTON => payment,
+            code => match get_currency(code) {
+                Ok(currency) => convert_total_currency(payment, currency),
+                Err(_) => {
+                    log::error!(
+                        "Add Payment Total Currency - Invalid button for user {} in chat {} with payment {:?}: {}",
+                        payment.sender_id,
+                        payment.chat_id,
+                        payment,
+                        button
+                    );
+                    return Ok(());
+                }
+            },
+        };
+
+        if let Some(msg) = query.message {
+            edit_tracked_message(
+                &bot,
+                &msg,
+                &mut messages,
+                format!(
+                    "{}{}",
+                    display_add_payment(&redis, &payment).await,
+                    t_prompt(&redis, &payment.chat_id, SPLIT_PROMPT, &[], DEBT_FORMATS).await
+                ),
+            )
+            .reply_markup(make_keyboard_debt_selection(
+                &retrieve_language(&redis, &payment.chat_id).await,
+            ))
+            .await?;
+            dialogue
+                .update(State::AddDebtSelection { messages, payment })
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/* Add a payment entry in a group chat.
+ * Bot receives a callback query from the user indicating how they want to split.
+ * No Cancel button required.
+ */
+pub async fn action_add_debt_selection(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    (mut messages, payment): (MessageTracker, AddPaymentParams),
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+
+        match button.as_str() {
+            "Equal" => {
+                if let Some(msg) = query.message {
+                    let mut others = vec![payment.sender_username.clone()];
+                    if let Some(creditor) = &payment.creditor {
+                        others.insert(0, creditor.clone());
+                    }
+                    let users = get_picker_users(&redis, &payment.chat_id, others).await;
+                    let groups = retrieve_chat_split_groups(&redis, &payment.chat_id).await;
+                    let selected = match &payment.debts {
+                        Some(debts) => debts.iter().map(|(user, _)| user.clone()).collect(),
+                        None => Vec::new(),
+                    };
+
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        format!(
+                            "{}{}",
+                            display_add_payment(&redis, &payment).await,
+                            t_prompt(
+                                &redis,
+                                &payment.chat_id,
+                                DEBT_USERS_PROMPT,
+                                &[],
+                                DEBT_EQUAL_PICKER
+                            )
+                            .await
+                        ),
+                    )
+                    .reply_markup(make_keyboard_user_picker(
+                        &users,
+                        &selected,
+                        &groups,
+                        &retrieve_language(&redis, &payment.chat_id).await,
+                    ))
+                    .await?;
+                    dialogue
+                        .update(State::AddDebtPicker {
+                            messages,
+                            payment,
+                            users,
+                            selected,
+                        })
+                        .await?;
+                }
+            }
+            "Exact" => {
+                if let Some(msg) = query.message {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        format!(
+                            "{}{}",
+                            display_add_payment(&redis, &payment).await,
+                            t_prompt(
+                                &redis,
+                                &payment.chat_id,
+                                DEBT_AMOUNTS_PROMPT,
+                                &[],
+                                DEBT_EXACT_INSTRUCTIONS
+                            )
+                            .await
+                        ),
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::AddDebt {
+                            messages,
+                            payment,
+                            debts_format: AddDebtsFormat::Exact,
+                        })
+                        .await?;
+                }
+            }
+            "Proportion" => {
+                if let Some(msg) = query.message {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        format!(
+                            "{}{}",
+                            display_add_payment(&redis, &payment).await,
+                            t_prompt(
+                                &redis,
+                                &payment.chat_id,
+                                DEBT_AMOUNTS_PROMPT,
+                                &[],
+                                DEBT_RATIO_INSTRUCTIONS
+                            )
+                            .await
+                        ),
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::AddDebt {
+                            messages,
+                            payment,
+                            debts_format: AddDebtsFormat::Ratio,
+                        })
+                        .await?;
+                }
+            }
+            "Percent" => {
+                if let Some(msg) = query.message {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        format!(
+                            "{}{}",
+                            display_add_payment(&redis, &payment).await,
+                            t_prompt(
+                                &redis,
+                                &payment.chat_id,
+                                DEBT_PERCENTAGES_PROMPT,
+                                &[],
+                                DEBT_PERCENT_INSTRUCTIONS
+                            )
+                            .await
+                        ),
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::AddDebt {
+                            messages,
+                            payment,
+                            debts_format: AddDebtsFormat::Percent,
+                        })
+                        .await?;
+                }
+            }
+            "Adjusted" => {
+                if let Some(msg) = query.message {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        format!(
+                            "{}{}",
+                            display_add_payment(&redis, &payment).await,
+                            t_prompt(
+                                &redis,
+                                &payment.chat_id,
+                                DEBT_ADJUSTMENTS_PROMPT,
+                                &[],
+                                DEBT_ADJUSTED_INSTRUCTIONS
+                            )
+                            .await
+                        ),
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::AddDebt {
+                            messages,
+                            payment,
+                            debts_format: AddDebtsFormat::Adjusted,
+                        })
+                        .await?;
+                }
+            }
+            "Itemized" => {
+                if let Some(msg) = query.message {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        format!(
+                            "{}{}",
+                            display_add_payment(&redis, &payment).await,
+                            t_prompt(
+                                &redis,
+                                &payment.chat_id,
+                                DEBT_ITEMS_PROMPT,
+                                &[],
+                                DEBT_ITEMIZED_INSTRUCTIONS
+                            )
+                            .await
+                        ),
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::AddDebtItems {
+                            messages,
+                            payment,
+                            items: Vec::new(),
+                        })
+                        .await?;
+                }
+            }
+            _ => {
+                log::error!("Add Payment Debt Selection - Invalid button for user {} in chat {} with payment {:?}: {}",
+                            payment.sender_id, payment.chat_id, payment, button);
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Add a payment entry in a group chat.
+ * Bot receives a callback query from the user picking who is involved, for an equal split.
+ * Toggles the users picked, until the user is done.
+ */
+pub async fn action_add_debt_picker(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    (mut messages, payment, users, selected): (
+        MessageTracker,
+        AddPaymentParams,
+        Vec<String>,
+        Vec<String>,
+    ),
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        if let Some(msg) = query.message {
+            match button.as_str() {
+                "Done" => {
+                    let rounding = retrieve_rounding(&redis, &payment.chat_id).await;
+                    let debts = process_debts_equal(
+                        &selected.join(" "),
+                        payment.total,
+                        &payment.creditor,
+                        rounding,
+                    );
+                    if let Err(err) = debts {
+                        bot.answer_callback_query(query.id.to_string())
+                            .text(error_to_user_message(&redis, &err, &payment.chat_id).await)
+                            .await?;
+                        return Ok(());
+                    }
+                    bot.answer_callback_query(query.id.to_string()).await?;
+
+                    let new_payment = AddPaymentParams {
+                        chat_id: payment.chat_id,
+                        sender_id: payment.sender_id,
+                        sender_username: payment.sender_username,
+                        datetime: payment.datetime,
+                        description: payment.description,
+                        creditor: payment.creditor,
+                        currency: payment.currency,
+                        total: payment.total,
+                        debts: Some(debts?),
+                        surcharges: payment.surcharges,
+                        is_private: payment.is_private,
+                        due_date: payment.due_date,
+                        is_transfer: payment.is_transfer,
+                        rate: payment.rate,
+                    };
+
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        format!(
+                            "Okay! Splitting equally between {}! ⚖️",
+                            display_usernames(&redis, &new_payment.chat_id, &selected)
+                                .await
+                                .join(", ")
+                        ),
+                    )
+                    .await?;
+                    display_add_overview(&redis, &bot, &dialogue, &msg, messages, new_payment)
+                        .await?;
+                }
+                _ => {
+                    bot.answer_callback_query(query.id.to_string()).await?;
+
+                    let groups = retrieve_chat_split_groups(&redis, &payment.chat_id).await;
+                    let selected = toggle_picker_users(&users, &selected, button, &groups);
+                    bot.edit_message_reply_markup(msg.chat.id, msg.id)
+                        .reply_markup(make_keyboard_user_picker(
+                            &users,
+                            &selected,
+                            &groups,
+                            &retrieve_language(&redis, &payment.chat_id).await,
+                        ))
+                        .await?;
+                    dialogue
+                        .update(State::AddDebtPicker {
+                            messages,
+                            payment,
+                            users,
+                            selected,
+                        })
+                        .await?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Add a payment entry in a group chat.
+ * Bot receives the usernames involved as text instead, while picking who is involved.
+ */
+pub async fn action_add_debt_picker_message(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+    (messages, payment, _, _): (MessageTracker, AddPaymentParams, Vec<String>, Vec<String>),
+) -> HandlerResult {
+    handle_debts(
+        &redis,
+        bot,
+        dialogue,
+        state,
+        msg,
+        messages,
+        payment,
+        AddDebtsFormat::Equal,
+    )
+    .await
+}
+
+/* Add a payment entry in a group chat.
+ * Bot receives line items from the user, for a split by items.
+ * Once the items add up to the total, it presents an overview. Else, it shows the running subtotal.
+ */
+pub async fn action_add_debt_items_message(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+    (mut messages, payment, mut items): (MessageTracker, AddPaymentParams, Vec<DebtItem>),
+) -> HandlerResult {
+    let text = match msg.text() {
+        Some(text) => text,
+        None => {
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &payment.chat_id, DEBT_ITEMIZED_INSTRUCTIONS, &[]).await,
+            )
+            .await?
+            .id;
+            repeat_state(dialogue, state, new_message).await?;
+            return Ok(());
+        }
+    };
+
+    let subtotal = checked_sum(
+        items
+            .iter()
+            .filter(|item| !item.users.is_empty())
+            .map(|item| item.amount),
+    )
+    .map_err(BotError::from);
+    let new_items = match subtotal.and_then(|subtotal| {
+        let new_items = parse_debt_items(text, payment.currency.clone(), subtotal)?;
+        checked_sum(items.iter().chain(&new_items).map(|item| item.amount))?;
+        Ok(new_items)
+    }) {
+        Ok(new_items) => new_items,
+        Err(err) => {
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                format!(
+                    "{}\n\n{}",
+                    err,
+                    t(&redis, &payment.chat_id, DEBT_ITEMIZED_INSTRUCTIONS, &[]).await
+                ),
+            )
+            .await?
+            .id;
+            repeat_state(dialogue, state, new_message).await?;
+            return Ok(());
+        }
+    };
+    items.extend(new_items);
+
+    // Shows the running subtotal, until the items add up to the total
+    let sum = items
+        .iter()
+        .fold(0, |sum, item| saturating_add(sum, item.amount));
+    if let (Some(currency), Some(total)) = (payment.currency.clone(), payment.total) {
+        if sum < total {
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                format!(
+                    "🧾 Here's the bill so far:\n\n{}\n\nAdd more items, or hit Done and I'll share whatever is remaining as tax and tip!",
+                    display_debt_items(&redis, &payment.chat_id, &items, currency, total).await
+                ),
+            )
+            .reply_markup(make_keyboard(
+                vec!["Done"],
+                None,
+                &retrieve_language(&redis, &payment.chat_id).await,
+            ))
+            .await?
+            .id;
+            messages.track(new_message);
+            dialogue
+                .update(State::AddDebtItems {
+                    messages,
+                    payment,
+                    items,
+                })
+                .await?;
+            return Ok(());
+        }
+    }
+
+    let rounding = retrieve_rounding(&redis, &payment.chat_id).await;
+    let debts = match split_debt_items(&items, payment.total, &payment.creditor, rounding) {
+        Ok(debts) => debts,
+        Err(err) => {
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                format!(
+                    "{}\n\n{}",
+                    err,
+                    t(&redis, &payment.chat_id, DEBT_ITEMIZED_INSTRUCTIONS, &[]).await
+                ),
+            )
+            .await?
+            .id;
             repeat_state(dialogue, state, new_message).await?;
+            return Ok(());
         }
-    }
+    };
+
+    let new_payment = AddPaymentParams {
+        chat_id: payment.chat_id,
+        sender_id: payment.sender_id,
+        sender_username: payment.sender_username,
+        datetime: payment.datetime,
+        description: payment.description,
+        creditor: payment.creditor,
+        currency: payment.currency,
+        total: payment.total,
+        debts: Some(debts),
+        surcharges: payment.surcharges,
+        is_private: payment.is_private,
+        due_date: payment.due_date,
+        is_transfer: payment.is_transfer,
+        rate: payment.rate,
+    };
+
+    display_add_overview(&redis, &bot, &dialogue, &msg, messages, new_payment).await?;
     Ok(())
 }
 
 /* Add a payment entry in a group chat.
- * Bot receives a callback query from the user indicating how they want to split.
- * No Cancel button required.
+ * Bot receives a callback query from the user, once done listing the line items.
+ * Whatever is remaining of the total is shared proportionally, like tax and tip.
  */
-pub async fn action_add_debt_selection(
+pub async fn action_add_debt_items(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     query: CallbackQuery,
-    (messages, payment): (Vec<MessageId>, AddPaymentParams),
+    (mut messages, payment, items): (MessageTracker, AddPaymentParams, Vec<DebtItem>),
 ) -> HandlerResult {
     if let Some(button) = &query.data {
-        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            match button.as_str() {
+                "Done" => {
+                    let rounding = retrieve_rounding(&redis, &payment.chat_id).await;
+                    let debts = match split_debt_items(
+                        &items,
+                        payment.total,
+                        &payment.creditor,
+                        rounding,
+                    ) {
+                        Ok(debts) => debts,
+                        Err(err) => {
+                            bot.answer_callback_query(query.id.to_string())
+                                .text(error_to_user_message(&redis, &err, &payment.chat_id).await)
+                                .await?;
+                            return Ok(());
+                        }
+                    };
+                    bot.answer_callback_query(query.id.to_string()).await?;
 
-        match button.as_str() {
-            "Equal" => {
-                if let Some(Message { id, chat, .. }) = query.message {
-                    bot.edit_message_text(
-                        chat.id,
-                        id,
-                        format!(
-                            "{}Okay! Who is involved in the payment?\n\n{DEBT_EQUAL_INSTRUCTIONS_MESSAGE}",
-                            display_add_payment(&payment)
-                            ),
-                            )
-                        .await?;
-                    dialogue
-                        .update(State::AddDebt {
-                            messages,
-                            payment,
-                            debts_format: AddDebtsFormat::Equal,
-                        })
-                        .await?;
-                }
-            }
-            "Exact" => {
-                if let Some(Message { id, chat, .. }) = query.message {
-                    bot.edit_message_text(
-                        chat.id,
-                        id,
-                        format!(
-                            "{}Okay! Who is involved and how much do they owe?\n\n{DEBT_EXACT_INSTRUCTIONS_MESSAGE}",
-                            display_add_payment(&payment))
-                        ).await?;
-                    dialogue
-                        .update(State::AddDebt {
-                            messages,
-                            payment,
-                            debts_format: AddDebtsFormat::Exact,
-                        })
+                    let new_payment = AddPaymentParams {
+                        chat_id: payment.chat_id,
+                        sender_id: payment.sender_id,
+                        sender_username: payment.sender_username,
+                        datetime: payment.datetime,
+                        description: payment.description,
+                        creditor: payment.creditor,
+                        currency: payment.currency,
+                        total: payment.total,
+                        debts: Some(debts),
+                        surcharges: payment.surcharges,
+                        is_private: payment.is_private,
+                        due_date: payment.due_date,
+                        is_transfer: payment.is_transfer,
+                        rate: payment.rate,
+                    };
+
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        "Okay! Splitting by the items! 🧾",
+                    )
+                    .await?;
+                    display_add_overview(&redis, &bot, &dialogue, &msg, messages, new_payment)
                         .await?;
                 }
-            }
-            "Proportion" => {
-                if let Some(Message { id, chat, .. }) = query.message {
-                    bot.edit_message_text(
-                        chat.id,
-                        id,
-                        format!(
-                            "{}Okay! Who is involved and how much do they owe?\n\n{DEBT_RATIO_INSTRUCTIONS_MESSAGE}",
-                            display_add_payment(&payment))
-                        ).await?;
-                    dialogue
-                        .update(State::AddDebt {
-                            messages,
-                            payment,
-                            debts_format: AddDebtsFormat::Ratio,
-                        })
-                        .await?;
+                _ => {
+                    log::error!("Add Payment Debt Items - Invalid button for user {} in chat {} with payment {:?}: {}",
+                                payment.sender_id, payment.chat_id, payment, button);
                 }
             }
-            _ => {
-                log::error!("Add Payment Debt Selection - Invalid button for user {} in chat {} with payment {:?}: {}",
-                            payment.sender_id, payment.chat_id, payment, button);
-            }
         }
     }
     Ok(())
@@ -841,13 +2338,24 @@ pub async fn action_add_debt_selection(
  * If so, it presents an overview. Else, it asks for more debts.
  */
 pub async fn action_add_debt(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
-    (messages, payment, debts_format): (Vec<MessageId>, AddPaymentParams, AddDebtsFormat),
+    (messages, payment, debts_format): (MessageTracker, AddPaymentParams, AddDebtsFormat),
 ) -> HandlerResult {
-    handle_debts(bot, dialogue, state, msg, messages, payment, debts_format).await
+    handle_debts(
+        &redis,
+        bot,
+        dialogue,
+        state,
+        msg,
+        messages,
+        payment,
+        debts_format,
+    )
+    .await
 }
 
 /* Add a payment entry in a group chat.
@@ -856,26 +2364,119 @@ pub async fn action_add_debt(
  * If user confirms, proceeds to add the payment.
  */
 pub async fn action_add_confirm(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     query: CallbackQuery,
-    (messages, payment): (Vec<MessageId>, AddPaymentParams),
+    (mut messages, payment): (MessageTracker, AddPaymentParams),
 ) -> HandlerResult {
     if let Some(button) = &query.data {
+        if button == OVERRIDE_CAP_BUTTON && !is_payment_payer(&query, &payment) {
+            bot.answer_callback_query(query.id.to_string())
+                .text(
+                    t(
+                        &redis,
+                        &payment.chat_id,
+                        WEEKLY_CAP_OVERRIDE_NOT_ALLOWED,
+                        &[],
+                    )
+                    .await,
+                )
+                .await?;
+            return Ok(());
+        }
         bot.answer_callback_query(query.id.to_string()).await?;
 
         match button.as_str() {
             "Cancel" => {
                 if let Some(msg) = query.message {
-                    cancel_add_payment(bot, dialogue, state, msg).await?;
+                    cancel_add_payment(redis.clone(), bot, dialogue, state, msg).await?;
                 }
             }
             "Edit" => {
-                display_add_edit_menu(bot, dialogue, query, messages, payment).await?;
+                display_add_edit_menu(&redis, bot, dialogue, query, messages, payment).await?;
             }
             "Confirm" => {
-                call_processor_add_payment(bot, dialogue, messages, payment, query).await?;
+                // With strict members, users not yet in the chat are only added when asked to
+                let unknown_users = get_unknown_users(&redis, &payment).await;
+                match &query.message {
+                    Some(msg) if !unknown_users.is_empty() => {
+                        let unknown_users: Vec<String> = unknown_users
+                            .iter()
+                            .map(|user| format!("@{}", user.trim_start_matches('@')))
+                            .collect();
+                        let keyboard = make_keyboard(
+                            vec!["Cancel", "Edit", ADD_USERS_BUTTON],
+                            Some(2),
+                            &retrieve_language(&redis, &payment.chat_id).await,
+                        );
+                        edit_tracked_message(&bot, msg, &mut messages, format!(
+                            "🚪 Only people already in this group can be in a payment, and I don't know {} here yet!\n\nIf they are in this group, I can add them now. Otherwise, you can edit the payment.",
+                            unknown_users.join(", ")
+                        ))
+                        .reply_markup(keyboard)
+                        .await?;
+                    }
+                    _ => {
+                        confirm_add_payment(&redis, bot, dialogue, messages, payment, query)
+                            .await?;
+                    }
+                }
+            }
+            ADD_USERS_BUTTON => {
+                let unknown_users = get_unknown_users(&redis, &payment).await;
+                match add_chat_users(&redis, &payment.chat_id, unknown_users.clone()).await {
+                    Ok(()) => {
+                        // Logging
+                        log::info!(
+                            "Add Payment Confirm - Users {:?} added by user {} in chat {}",
+                            unknown_users,
+                            query.from.id,
+                            payment.chat_id
+                        );
+                    }
+                    Err(err) => {
+                        // Logging
+                        log::error!(
+                            "Add Payment Confirm - Processor failed to add users {:?} in chat {}: {}",
+                            unknown_users,
+                            payment.chat_id,
+                            err.to_string()
+                        );
+                    }
+                }
+                confirm_add_payment(&redis, bot, dialogue, messages, payment, query).await?;
+            }
+            OVERRIDE_CAP_BUTTON => {
+                // Logging
+                log::info!(
+                    "Add Payment Confirm - Weekly cap overridden by user {} in chat {}",
+                    query.from.id,
+                    payment.chat_id
+                );
+                call_processor_add_payment(&redis, bot, dialogue, messages, payment, query).await?;
+            }
+            DID_YOU_MEAN_BUTTON => {
+                if let Some(msg) = query.message {
+                    let corrections = get_debtor_corrections(&redis, &payment).await;
+
+                    // Logging
+                    log::info!(
+                        "Add Payment Confirm - Debtors corrected by user {} in chat {}: {:?}",
+                        query.from.id,
+                        payment.chat_id,
+                        corrections
+                    );
+
+                    let debts = payment
+                        .debts
+                        .clone()
+                        .map(|debts| apply_debtor_corrections(debts, &corrections));
+                    let new_payment = AddPaymentParams { debts, ..payment };
+                    display_add_overview(&redis, &bot, &dialogue, &msg, messages, new_payment)
+                        .await?;
+                }
             }
             _ => {
                 log::error!("Add Payment Confirm - Invalid button for user {} in chat {} with payment {:?}: {}",
@@ -891,23 +2492,23 @@ pub async fn action_add_confirm(
  * If the user chooses to go back, return to confirm page.
  */
 pub async fn action_add_edit_menu(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     query: CallbackQuery,
-    (messages, payment): (Vec<MessageId>, AddPaymentParams),
+    (mut messages, payment): (MessageTracker, AddPaymentParams),
 ) -> HandlerResult {
     if let Some(button) = &query.data {
         bot.answer_callback_query(query.id.to_string()).await?;
 
         if let Some(msg) = query.message {
             let payment_clone = payment.clone();
-            let chat_id = msg.chat.id;
-            let id = msg.id;
             match button.as_str() {
                 "Description" => {
-                    bot.edit_message_text(
-                        chat_id,
-                        id,
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
                         format!(
                             "Current description: {}\n\nWhat should the description be?",
                             payment_clone.description.unwrap()
@@ -922,13 +2523,75 @@ pub async fn action_add_edit_menu(
                         })
                         .await?;
                 }
+                "Date" => {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t_prompt(
+                            &redis,
+                            &payment_clone.chat_id,
+                            EDIT_DATE_PROMPT,
+                            &[(
+                                "date",
+                                &display_payment_date(
+                                    &payment_clone.datetime,
+                                    retrieve_time_zone(&redis, &payment_clone.chat_id).await,
+                                ),
+                            )],
+                            DATE_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::AddEdit {
+                            messages,
+                            payment,
+                            edit: AddPaymentEdit::Date,
+                        })
+                        .await?;
+                }
+                DUE_DATE_BUTTON => {
+                    let current = match &payment_clone.due_date {
+                        Some(due_date) => display_due_date(due_date),
+                        None => "None".to_string(),
+                    };
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t_prompt(
+                            &redis,
+                            &payment_clone.chat_id,
+                            EDIT_DUE_DATE_PROMPT,
+                            &[("date", &current)],
+                            DUE_DATE_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::AddEdit {
+                            messages,
+                            payment,
+                            edit: AddPaymentEdit::DueDate,
+                        })
+                        .await?;
+                }
                 "Payer" => {
-                    bot.edit_message_text(
-                        chat_id,
-                        id,
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
                         format!(
                             "Current payer: {}\n\nWho should the payer be?",
-                            display_username(&payment_clone.creditor.unwrap())
+                            display_username(
+                                &redis,
+                                &payment_clone.chat_id,
+                                &payment_clone.creditor.unwrap()
+                            )
+                            .await
                         ),
                     )
                     .await?;
@@ -941,15 +2604,32 @@ pub async fn action_add_edit_menu(
                         .await?;
                 }
                 "Total" => {
-                    bot.edit_message_text(
-                        chat_id,
-                        id,
-                        format!(
-                            "Current total: {}\n\nWhat should the total be?\n\n{TOTAL_INSTRUCTIONS_MESSAGE}",
-                            display_currency_amount(payment_clone.total.unwrap(), use_currency(payment_clone.currency.unwrap(), &payment_clone.chat_id))
-                            ),
-                            )
-                        .await?;
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t_prompt(
+                            &redis,
+                            &payment_clone.chat_id,
+                            EDIT_TOTAL_PROMPT,
+                            &[(
+                                "total",
+                                &display_currency_amount(
+                                    payment_clone.total.unwrap(),
+                                    use_currency(
+                                        &redis,
+                                        payment_clone.currency.unwrap(),
+                                        &payment_clone.chat_id,
+                                    )
+                                    .await,
+                                    retrieve_currency_format(&redis, &payment_clone.chat_id).await,
+                                ),
+                            )],
+                            TOTAL_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?;
                     dialogue
                         .update(State::AddEdit {
                             messages,
@@ -959,21 +2639,68 @@ pub async fn action_add_edit_menu(
                         .await?;
                 }
                 "Split" => {
-                    bot.edit_message_text(
-                        chat_id,
-                        id,
-                        format!(
-                            "Current split:\n{}\nHow should we split this?\n\n{DEBT_EQUAL_DESCRIPTION_MESSAGE}{DEBT_EXACT_DESCRIPTION_MESSAGE}{DEBT_RATIO_DESCRIPTION_MESSAGE}",
-                            display_debts(&payment_clone.debts.unwrap(), payment_clone.currency.unwrap().1)
-                            ),
-                            ).reply_markup(make_keyboard_debt_selection())
-                        .await?;
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t_prompt(
+                            &redis,
+                            &payment_clone.chat_id,
+                            EDIT_SPLIT_PROMPT,
+                            &[(
+                                "split",
+                                &display_debts(
+                                    &redis,
+                                    &payment_clone.chat_id,
+                                    &payment_clone.debts.clone().unwrap(),
+                                    payment_clone.currency.clone().unwrap().1,
+                                )
+                                .await,
+                            )],
+                            DEBT_FORMATS,
+                        )
+                        .await,
+                    )
+                    .reply_markup(make_keyboard_debt_selection(
+                        &retrieve_language(&redis, &payment_clone.chat_id).await,
+                    ))
+                    .await?;
                     dialogue
                         .update(State::AddDebtSelection { messages, payment })
                         .await?;
                 }
+                MAKE_PRIVATE_BUTTON | MAKE_PUBLIC_BUTTON | MAKE_TRANSFER_BUTTON
+                | MAKE_EXPENSE_BUTTON => {
+                    let payment = match button.as_str() {
+                        MAKE_PRIVATE_BUTTON | MAKE_PUBLIC_BUTTON => AddPaymentParams {
+                            is_private: button == MAKE_PRIVATE_BUTTON,
+                            ..payment
+                        },
+                        _ => AddPaymentParams {
+                            is_transfer: button == MAKE_TRANSFER_BUTTON,
+                            ..payment
+                        },
+                    };
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        format!(
+                            "{}Sure! What would you like to edit?",
+                            display_add_payment(&redis, &payment).await
+                        ),
+                    )
+                    .reply_markup(make_keyboard_add_edit(
+                        &payment,
+                        &retrieve_language(&redis, &payment.chat_id).await,
+                    ))
+                    .await?;
+                    dialogue
+                        .update(State::AddEditMenu { messages, payment })
+                        .await?;
+                }
                 "Back" => {
-                    display_add_overview(&bot, &dialogue, &msg, messages, payment).await?;
+                    display_add_overview(&redis, &bot, &dialogue, &msg, messages, payment).await?;
                 }
                 _ => {
                     log::error!("Add Payment Edit Menu - Invalid button for user {} in chat {} with payment {:?}: {}",
@@ -991,33 +2718,116 @@ pub async fn action_add_edit_menu(
  * If the user chooses to go back, return to confirm page.
  */
 pub async fn action_add_edit(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
-    (mut messages, payment, edit): (Vec<MessageId>, AddPaymentParams, AddPaymentEdit),
+    (mut messages, payment, edit): (MessageTracker, AddPaymentParams, AddPaymentEdit),
 ) -> HandlerResult {
     match msg.text() {
         Some(text) => match edit {
             AddPaymentEdit::Description => {
+                let description = match validate_description(text) {
+                    Ok(description) => description,
+                    Err(err) => {
+                        let new_message = send_bot_message(
+                            &bot,
+                            &msg,
+                            error_to_user_message(&redis, &err, &msg.chat.id.to_string()).await,
+                        )
+                        .await?
+                        .id;
+                        repeat_state(dialogue, state, new_message).await?;
+                        return Ok(());
+                    }
+                };
                 let new_payment = AddPaymentParams {
                     chat_id: payment.chat_id,
                     sender_id: payment.sender_id,
                     sender_username: payment.sender_username,
                     datetime: payment.datetime,
-                    description: Some(text.to_string()),
+                    description: Some(description),
+                    creditor: payment.creditor,
+                    currency: payment.currency,
+                    total: payment.total,
+                    debts: payment.debts,
+                    surcharges: payment.surcharges,
+                    is_private: payment.is_private,
+                    due_date: payment.due_date,
+                    is_transfer: payment.is_transfer,
+                    rate: payment.rate,
+                };
+                display_add_overview(&redis, &bot, &dialogue, &msg, messages, new_payment).await?;
+            }
+            AddPaymentEdit::Date => {
+                let time_zone = retrieve_time_zone(&redis, &payment.chat_id).await;
+                let datetime = parse_payment_date(text, &payment.datetime, time_zone);
+
+                if let Err(err) = datetime {
+                    let new_message = send_bot_message(
+                        &bot,
+                        &msg,
+                        error_to_user_message(&redis, &err, &msg.chat.id.to_string()).await,
+                    )
+                    .await?
+                    .id;
+                    repeat_state(dialogue, state, new_message).await?;
+                    return Ok(());
+                }
+
+                let new_payment = AddPaymentParams {
+                    chat_id: payment.chat_id,
+                    sender_id: payment.sender_id,
+                    sender_username: payment.sender_username,
+                    datetime: datetime?,
+                    description: payment.description,
                     creditor: payment.creditor,
                     currency: payment.currency,
                     total: payment.total,
                     debts: payment.debts,
+                    surcharges: payment.surcharges,
+                    is_private: payment.is_private,
+                    due_date: payment.due_date,
+                    is_transfer: payment.is_transfer,
+                    rate: payment.rate,
+                };
+                display_add_overview(&redis, &bot, &dialogue, &msg, messages, new_payment).await?;
+            }
+            AddPaymentEdit::DueDate => {
+                let time_zone = retrieve_time_zone(&redis, &payment.chat_id).await;
+                let due_date = match parse_due_date(text, time_zone) {
+                    Ok(due_date) => due_date,
+                    Err(err) => {
+                        let new_message = send_bot_message(
+                            &bot,
+                            &msg,
+                            error_to_user_message(&redis, &err, &msg.chat.id.to_string()).await,
+                        )
+                        .await?
+                        .id;
+                        repeat_state(dialogue, state, new_message).await?;
+                        return Ok(());
+                    }
+                };
+
+                let new_payment = AddPaymentParams {
+                    due_date,
+                    ..payment
                 };
-                display_add_overview(&bot, &dialogue, &msg, messages, new_payment).await?;
+                display_add_overview(&redis, &bot, &dialogue, &msg, messages, new_payment).await?;
             }
             AddPaymentEdit::Creditor => {
                 let username = parse_username(text);
 
                 if let Err(err) = username {
-                    let new_message = send_bot_message(&bot, &msg, err.to_string()).await?.id;
+                    let new_message = send_bot_message(
+                        &bot,
+                        &msg,
+                        error_to_user_message(&redis, &err, &msg.chat.id.to_string()).await,
+                    )
+                    .await?
+                    .id;
                     repeat_state(dialogue, state, new_message).await?;
                     return Ok(());
                 }
@@ -1032,13 +2842,17 @@ pub async fn action_add_edit(
                     currency: payment.currency,
                     total: payment.total,
                     debts: payment.debts,
+                    surcharges: payment.surcharges,
+                    is_private: payment.is_private,
+                    due_date: payment.due_date,
+                    is_transfer: payment.is_transfer,
+                    rate: payment.rate,
                 };
-                display_add_overview(&bot, &dialogue, &msg, messages, new_payment).await?;
+                display_add_overview(&redis, &bot, &dialogue, &msg, messages, new_payment).await?;
             }
             AddPaymentEdit::Total => {
-                let currency_amount = parse_currency_amount(text);
-                match currency_amount {
-                    Ok((total, currency)) => {
+                match parse_total_amount_rate(&redis, &payment.chat_id, text).await {
+                    Ok((total, currency, surcharges, rate)) => {
                         let new_payment = AddPaymentParams {
                             chat_id: payment.chat_id,
                             sender_id: payment.sender_id,
@@ -1049,13 +2863,30 @@ pub async fn action_add_edit(
                             currency: Some(currency),
                             total: Some(total),
                             debts: payment.debts,
+                            surcharges,
+                            is_private: payment.is_private,
+                            due_date: payment.due_date,
+                            is_transfer: payment.is_transfer,
+                            rate,
                         };
-                        let new_message = send_bot_message(&bot,
+                        let new_message = send_bot_message(
+                            &bot,
                             &msg,
-                            format!("Fantastic! How are we splitting this?\n\n{DEBT_EQUAL_DESCRIPTION_MESSAGE}{DEBT_EXACT_DESCRIPTION_MESSAGE}{DEBT_RATIO_DESCRIPTION_MESSAGE}",),
-                            ).reply_markup(make_keyboard_debt_selection())
-                            .await?.id;
-                        messages.push(new_message);
+                            t_prompt(
+                                &redis,
+                                &new_payment.chat_id,
+                                SPLIT_PROMPT,
+                                &[],
+                                DEBT_FORMATS,
+                            )
+                            .await,
+                        )
+                        .reply_markup(make_keyboard_debt_selection(
+                            &retrieve_language(&redis, &new_payment.chat_id).await,
+                        ))
+                        .await?
+                        .id;
+                        messages.track(new_message);
                         dialogue
                             .update(State::AddDebtSelection {
                                 messages,
@@ -1067,7 +2898,11 @@ pub async fn action_add_edit(
                         let new_message = send_bot_message(
                             &bot,
                             &msg,
-                            format!("{}\n\n{TOTAL_INSTRUCTIONS_MESSAGE}", err.to_string()),
+                            format!(
+                                "{}\n\n{}",
+                                err,
+                                t(&redis, &msg.chat.id.to_string(), TOTAL_INSTRUCTIONS, &[]).await
+                            ),
                         )
                         .await?
                         .id;
@@ -1077,8 +2912,13 @@ pub async fn action_add_edit(
                     }
                 }
             }
+            // The currency of a new payment is given together with its total instead
+            AddPaymentEdit::Currency => {
+                display_add_overview(&redis, &bot, &dialogue, &msg, messages, payment).await?;
+            }
             AddPaymentEdit::DebtsEqual => {
                 handle_debts(
+                    &redis,
                     bot,
                     dialogue,
                     state,
@@ -1091,6 +2931,7 @@ pub async fn action_add_edit(
             }
             AddPaymentEdit::DebtsExact => {
                 handle_debts(
+                    &redis,
                     bot,
                     dialogue,
                     state,
@@ -1103,6 +2944,7 @@ pub async fn action_add_edit(
             }
             AddPaymentEdit::DebtsRatio => {
                 handle_debts(
+                    &redis,
                     bot,
                     dialogue,
                     state,
@@ -1113,11 +2955,54 @@ pub async fn action_add_edit(
                 )
                 .await?;
             }
+            AddPaymentEdit::DebtsPercent => {
+                handle_debts(
+                    &redis,
+                    bot,
+                    dialogue,
+                    state,
+                    msg,
+                    messages,
+                    payment,
+                    AddDebtsFormat::Percent,
+                )
+                .await?;
+            }
+            AddPaymentEdit::DebtsAdjusted => {
+                handle_debts(
+                    &redis,
+                    bot,
+                    dialogue,
+                    state,
+                    msg,
+                    messages,
+                    payment,
+                    AddDebtsFormat::Adjusted,
+                )
+                .await?;
+            }
+            AddPaymentEdit::DebtsItemized => {
+                handle_debts(
+                    &redis,
+                    bot,
+                    dialogue,
+                    state,
+                    msg,
+                    messages,
+                    payment,
+                    AddDebtsFormat::Itemized,
+                )
+                .await?;
+            }
         },
         None => {
-            let new_message = send_bot_message(&bot, &msg, format!("{NO_TEXT_MESSAGE}"))
-                .await?
-                .id;
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
             repeat_state(dialogue, state, new_message).await?;
         }
     }
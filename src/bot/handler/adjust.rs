@@ -0,0 +1,177 @@
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    currency::{Currency, NumberFormat},
+    handler::utils::{
+        assert_handle_request_limit, display_balance_header, display_balances,
+        display_currency_amount, display_username, error_to_user_message, get_currency,
+        is_chat_admin, parse_currency_amount, parse_username, retrieve_allowed_currencies,
+        retrieve_currency_format, retrieve_number_format, send_bot_message, t, t_text,
+        use_currency, BotError, HandlerResult,
+    },
+    i18n::{
+        Text, ADJUST_DONE, ADJUST_INSTRUCTIONS, ADJUST_REASON_MISSING, ADJUST_RESTRICTED,
+        ADJUST_SIGN_MISSING, NOT_UNDERSTOOD,
+    },
+    processor::{add_balance_adjustment, ProcessError},
+    redis::Redis,
+};
+
+/* Utilities */
+
+// Parses the arguments of an adjustment, as the username, the amount with its sign,
+// the currency and the reason. The currency is optional, and taken only if it is known.
+fn parse_adjust_arguments(
+    args: &str,
+    number_format: NumberFormat,
+    allowed_currencies: &[String],
+) -> Result<(String, i64, Currency, String), BotError> {
+    let mut items = args.split_whitespace();
+    let (username, amount) = match (items.next(), items.next()) {
+        (Some(username), Some(amount)) => (username, amount),
+        _ => {
+            return Err(BotError::UserError(
+                Text::new(NOT_UNDERSTOOD).then(Text::new(ADJUST_INSTRUCTIONS)),
+            ))
+        }
+    };
+    let username = parse_username(username)?;
+
+    let (sign, amount) = if let Some(amount) = amount.strip_prefix('+') {
+        (1, amount)
+    } else if let Some(amount) = amount.strip_prefix('-') {
+        (-1, amount)
+    } else {
+        return Err(BotError::UserError(
+            Text::new(ADJUST_SIGN_MISSING).then(Text::new(ADJUST_INSTRUCTIONS)),
+        ));
+    };
+
+    let rest: Vec<&str> = items.collect();
+    let (amount, reason) = match rest.first() {
+        Some(code) if get_currency(code).is_ok() => (format!("{amount} {code}"), &rest[1..]),
+        _ => (amount.to_string(), &rest[..]),
+    };
+    let (amount, currency) = parse_currency_amount(&amount, number_format, allowed_currencies)?;
+
+    let reason = reason.join(" ");
+    if reason.is_empty() {
+        return Err(BotError::UserError(
+            Text::new(ADJUST_REASON_MISSING).then(Text::new(ADJUST_INSTRUCTIONS)),
+        ));
+    }
+
+    Ok((username, sign * amount, currency, reason))
+}
+
+/* Adjusts the balance of a user in a group chat, to correct a past discrepancy.
+ * Only admins can adjust balances, and each adjustment is recorded with its reason,
+ * so groups can fix mistakes without adding made-up payments.
+ */
+pub async fn action_adjust(redis: Redis, bot: Bot, msg: Message, args: String) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let args = args.trim();
+    if args.is_empty() {
+        send_bot_message(
+            &bot,
+            &msg,
+            t(&redis, &chat_id, ADJUST_INSTRUCTIONS, &[]).await,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if !is_chat_admin(&redis, &bot, &msg).await? {
+        send_bot_message(
+            &bot,
+            &msg,
+            t(&redis, &chat_id, ADJUST_RESTRICTED, &[]).await,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let (sender_username, sender_id) = match msg.from() {
+        Some(user) => (
+            user.username.clone().unwrap_or_default(),
+            user.id.to_string(),
+        ),
+        None => return Ok(()),
+    };
+
+    let number_format = retrieve_number_format(&redis, &chat_id).await;
+    let allowed_currencies = retrieve_allowed_currencies(&redis, &chat_id).await;
+    let (username, amount, currency, reason) =
+        match parse_adjust_arguments(args, number_format, &allowed_currencies) {
+            Ok(arguments) => arguments,
+            Err(BotError::UserError(reply)) => {
+                send_bot_message(&bot, &msg, t_text(&redis, &chat_id, &reply).await).await?;
+                return Ok(());
+            }
+            Err(err) => return Err(err),
+        };
+
+    let adjustment = add_balance_adjustment(
+        &redis,
+        &chat_id,
+        &sender_username,
+        &sender_id,
+        &username,
+        &currency.0,
+        amount,
+        &reason,
+    )
+    .await;
+    let reply = match adjustment {
+        Ok((payment_id, balances)) => {
+            // Logging
+            log::info!(
+                "Adjust - Balance of user {} adjusted by user {} in chat {} with adjustment {}: {} {}",
+                username,
+                sender_id,
+                chat_id,
+                payment_id,
+                amount,
+                currency.0
+            );
+
+            let display_currency = use_currency(&redis, currency.clone(), &chat_id).await;
+            let adjusted = format!(
+                "{}{}",
+                if amount > 0 { "+" } else { "-" },
+                display_currency_amount(
+                    amount.abs(),
+                    display_currency.clone(),
+                    retrieve_currency_format(&redis, &chat_id).await
+                )
+            );
+            format!(
+                "{}\n\n{}{}",
+                t(
+                    &redis,
+                    &chat_id,
+                    ADJUST_DONE,
+                    &[
+                        ("user", &display_username(&redis, &chat_id, &username).await),
+                        ("amount", &adjusted),
+                        ("reason", &reason),
+                    ],
+                )
+                .await,
+                display_balance_header(&redis, &chat_id, &display_currency.0).await,
+                display_balances(&redis, &chat_id, &balances).await
+            )
+        }
+        Err(err @ ProcessError::ValidationError(_)) => {
+            error_to_user_message(&redis, &BotError::from(err), &chat_id).await
+        }
+        Err(err) => return Err(err.into()),
+    };
+    send_bot_message(&bot, &msg, reply).await?;
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    handler::utils::{
+        assert_admin_only, assert_handle_request_limit, error_to_user_message, mention_username,
+        parse_username, send_bot_message, t, t_text, BotError, HandlerResult,
+    },
+    i18n::{
+        Text, ALIASES_HEADER, ALIASES_NONE, ALIAS_INSTRUCTIONS, ALIAS_NOT_SET, ALIAS_REMOVED,
+        ALIAS_SET, NOT_UNDERSTOOD,
+    },
+    processor::{remove_display_name, retrieve_display_names, update_display_name, ProcessError},
+    redis::Redis,
+};
+
+/* Utilities */
+// Displays the display names of all users of a chat
+fn display_aliases(names: &[(String, String)]) -> String {
+    names
+        .iter()
+        .map(|(username, name)| format!("{} → {}", mention_username(username), name))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/* Sets, removes, or views the names shown for users in a group chat.
+ * A display name replaces the username of a user in balances, payments, and other messages,
+ * while mentions in notifications still use the username.
+ */
+pub async fn action_alias(redis: Redis, bot: Bot, msg: Message, args: String) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let args = args.trim();
+
+    if args.is_empty() {
+        let mut names: Vec<(String, String)> = retrieve_display_names(&redis, &chat_id)
+            .await?
+            .into_iter()
+            .collect();
+        names.sort();
+        let reply = if names.is_empty() {
+            t_text(
+                &redis,
+                &chat_id,
+                &Text::new(ALIASES_NONE).then(Text::new(ALIAS_INSTRUCTIONS)),
+            )
+            .await
+        } else {
+            format!(
+                "{}\n\n{}",
+                t(&redis, &chat_id, ALIASES_HEADER, &[]).await,
+                display_aliases(&names)
+            )
+        };
+        send_bot_message(&bot, &msg, reply).await?;
+        return Ok(());
+    }
+
+    if !assert_admin_only(&redis, &bot, &msg).await? {
+        return Ok(());
+    }
+
+    let (first, rest) = match args.split_once(char::is_whitespace) {
+        Some((first, rest)) => (first, rest.trim()),
+        None => (args, ""),
+    };
+
+    if first.eq_ignore_ascii_case("remove") {
+        let username = match parse_username(rest) {
+            Ok(username) => username,
+            Err(BotError::UserError(reply)) => {
+                send_bot_message(&bot, &msg, t_text(&redis, &chat_id, &reply).await).await?;
+                return Ok(());
+            }
+            Err(err) => return Err(err),
+        };
+
+        let reply = if remove_display_name(&redis, &chat_id, &username).await? {
+            // Logging
+            log::info!(
+                "Alias - Display name of user {} removed in chat {}",
+                username,
+                chat_id
+            );
+
+            t(
+                &redis,
+                &chat_id,
+                ALIAS_REMOVED,
+                &[("user", &mention_username(&username))],
+            )
+            .await
+        } else {
+            t(
+                &redis,
+                &chat_id,
+                ALIAS_NOT_SET,
+                &[("user", &mention_username(&username))],
+            )
+            .await
+        };
+        send_bot_message(&bot, &msg, reply).await?;
+        return Ok(());
+    }
+
+    let username = match parse_username(first) {
+        Ok(username) if !rest.is_empty() => username,
+        Ok(_) => {
+            let reply = Text::new(NOT_UNDERSTOOD).then(Text::new(ALIAS_INSTRUCTIONS));
+            send_bot_message(&bot, &msg, t_text(&redis, &chat_id, &reply).await).await?;
+            return Ok(());
+        }
+        Err(BotError::UserError(reply)) => {
+            send_bot_message(&bot, &msg, t_text(&redis, &chat_id, &reply).await).await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+
+    let reply = match update_display_name(&redis, &chat_id, &username, rest).await {
+        Ok(()) => {
+            // Logging
+            log::info!(
+                "Alias - Display name of user {} set in chat {}: {}",
+                username,
+                chat_id,
+                rest
+            );
+
+            t(
+                &redis,
+                &chat_id,
+                ALIAS_SET,
+                &[("user", &mention_username(&username)), ("name", rest)],
+            )
+            .await
+        }
+        Err(err @ ProcessError::ValidationError(_)) => {
+            error_to_user_message(&redis, &BotError::from(err), &chat_id).await
+        }
+        Err(err) => return Err(err.into()),
+    };
+    send_bot_message(&bot, &msg, reply).await?;
+
+    Ok(())
+}
@@ -0,0 +1,552 @@
+use teloxide::{
+    payloads::AnswerCallbackQuerySetters,
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, User},
+};
+
+use crate::bot::{
+    handler::{
+        limit::notify_spending_limits,
+        utils::{
+            display_balance_header, display_balances, display_username, edit_bot_message,
+            mention_username, retrieve_language, retrieve_verbosity, send_bot_message, t,
+            HandlerResult, Verbosity,
+        },
+    },
+    i18n::{
+        translate, ADD_PAYMENT_FAILED, APPROVAL_NOT_ALLOWED, APPROVAL_REQUEST, APPROVAL_RESOLVED,
+        APPROVED_BY, BUTTON_APPROVE, BUTTON_CONFIRM_DEBTOR, BUTTON_CONFIRM_PAYER, BUTTON_DISPUTE,
+        CONFIRMATION_RESOLVED, CONFIRMED_ADDED_DEBTOR, CONFIRMED_ADDED_PAYER, CONFIRMED_BY_DEBTOR,
+        CONFIRMED_BY_PAYER, CONFIRM_DEBTOR_NOT_ALLOWED, CONFIRM_PAYER_NOT_ALLOWED,
+        CONFIRM_PAYMENT_FAILED, DISCARD_PAYMENT_FAILED, DISPUTED_BY, DUAL_ENTRY_REQUEST,
+    },
+    processor::{
+        approve_pending_payment, confirm_pending_payment, discard_pending_payment,
+        is_username_equal, retrieve_chat_member, retrieve_pending_payment,
+    },
+    redis::Redis,
+};
+
+/* Utilities */
+const APPROVE_PREFIX: &str = "Approve:";
+const DISPUTE_PREFIX: &str = "Dispute:";
+const CONFIRM_PAYER_PREFIX: &str = "ConfirmPayer:";
+const CONFIRM_DEBTOR_PREFIX: &str = "ConfirmDebtor:";
+
+// Gets everyone involved in a payment who can approve it, which excludes the sender.
+pub fn get_approvers(
+    creditor: &str,
+    debts: &[(String, i64)],
+    sender_username: &str,
+) -> Vec<String> {
+    let mut approvers: Vec<String> = Vec::new();
+    let users = std::iter::once(creditor).chain(debts.iter().map(|(user, _)| user.as_str()));
+    for user in users {
+        if is_username_equal(user, sender_username)
+            || approvers
+                .iter()
+                .any(|approver| is_username_equal(approver, user))
+        {
+            continue;
+        }
+        approvers.push(user.to_string());
+    }
+    approvers
+}
+
+// Finds the username a user goes by in a payment, if they have the role checked for.
+// Users are matched by their Telegram user ID first, then by their current username,
+// so that requests can still be answered after a user changes their username.
+async fn find_user_in_payment(
+    redis: &Redis,
+    chat_id: &str,
+    user: &User,
+    has_role: impl Fn(&str) -> bool,
+) -> Option<String> {
+    let member = retrieve_chat_member(
+        redis,
+        chat_id,
+        &user.id.to_string(),
+        user.username.as_deref(),
+    )
+    .await
+    .unwrap_or(None);
+    member
+        .into_iter()
+        .chain(user.username.clone())
+        .find(|username| has_role(username))
+}
+
+// Make approval keyboard, with the pending payment ID in the callback data
+pub fn make_keyboard_approval(payment_id: &str, language: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback(
+            translate(language, BUTTON_APPROVE, &[]),
+            format!("{APPROVE_PREFIX}{payment_id}"),
+        ),
+        InlineKeyboardButton::callback(
+            translate(language, BUTTON_DISPUTE, &[]),
+            format!("{DISPUTE_PREFIX}{payment_id}"),
+        ),
+    ]])
+}
+
+// Asks everyone who can approve a payment to approve or dispute it
+pub fn display_approval_request(approvers: &[String], language: &str) -> String {
+    let mentions: Vec<String> = approvers
+        .iter()
+        .map(|approver| mention_username(approver))
+        .collect();
+    translate(
+        language,
+        APPROVAL_REQUEST,
+        &[("approvers", &mentions.join(", "))],
+    )
+}
+
+// Asks the payer and the other debtors of a payment to confirm it with dual entry verification
+pub fn display_dual_entry_request(
+    creditor: &str,
+    debts: &[(String, i64)],
+    language: &str,
+) -> String {
+    let debtors: Vec<String> = debts
+        .iter()
+        .filter(|(user, _)| !is_username_equal(user, creditor))
+        .map(|(user, _)| mention_username(user))
+        .collect();
+    translate(
+        language,
+        DUAL_ENTRY_REQUEST,
+        &[
+            ("payer", &mention_username(creditor)),
+            ("debtors", &debtors.join(", ")),
+        ],
+    )
+}
+
+// Make dual entry keyboard, with a confirmation for the payer and for a debtor
+// Disputes are handled the same way as for approval requests
+pub fn make_keyboard_dual_entry(payment_id: &str, language: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![
+        vec![
+            InlineKeyboardButton::callback(
+                translate(language, BUTTON_CONFIRM_PAYER, &[]),
+                format!("{CONFIRM_PAYER_PREFIX}{payment_id}"),
+            ),
+            InlineKeyboardButton::callback(
+                translate(language, BUTTON_CONFIRM_DEBTOR, &[]),
+                format!("{CONFIRM_DEBTOR_PREFIX}{payment_id}"),
+            ),
+        ],
+        vec![InlineKeyboardButton::callback(
+            translate(language, BUTTON_DISPUTE, &[]),
+            format!("{DISPUTE_PREFIX}{payment_id}"),
+        )],
+    ])
+}
+
+// Checks if a callback query is a confirmation of a payment with dual entry verification.
+// Such queries can come from anyone in the chat, regardless of the current state.
+pub fn is_confirmation_query(query: CallbackQuery) -> bool {
+    match &query.data {
+        Some(data) => {
+            data.starts_with(CONFIRM_PAYER_PREFIX) || data.starts_with(CONFIRM_DEBTOR_PREFIX)
+        }
+        None => false,
+    }
+}
+
+// Checks if a callback query is a response to an approval request.
+// Such queries can come from anyone in the chat, regardless of the current state.
+pub fn is_approval_query(query: CallbackQuery) -> bool {
+    match &query.data {
+        Some(data) => data.starts_with(APPROVE_PREFIX) || data.starts_with(DISPUTE_PREFIX),
+        None => false,
+    }
+}
+
+/* Approves or disputes a pending payment in a group chat.
+ * Bot receives a callback query from the approval request.
+ * Only other users involved in the payment can respond, and only the first response counts.
+ */
+pub async fn action_resolve_pending_payment(
+    redis: Redis,
+    bot: Bot,
+    query: CallbackQuery,
+) -> HandlerResult {
+    let (is_approved, payment_id) = match &query.data {
+        Some(data) => match data.strip_prefix(APPROVE_PREFIX) {
+            Some(payment_id) => (true, payment_id.to_string()),
+            None => match data.strip_prefix(DISPUTE_PREFIX) {
+                Some(payment_id) => (false, payment_id.to_string()),
+                None => return Ok(()),
+            },
+        },
+        None => return Ok(()),
+    };
+
+    let msg = match query.message {
+        Some(msg) => msg,
+        None => return Ok(()),
+    };
+
+    let (pending, payment) = match retrieve_pending_payment(&redis, &payment_id).await? {
+        Some(entry) => entry,
+        None => {
+            bot.answer_callback_query(query.id)
+                .text(t(&redis, &msg.chat.id.to_string(), APPROVAL_RESOLVED, &[]).await)
+                .await?;
+            bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+            return Ok(());
+        }
+    };
+
+    let approvers = get_approvers(&payment.creditor, &payment.debts, &pending.sender_username);
+    let is_approver = |username: &str| {
+        approvers
+            .iter()
+            .any(|approver| is_username_equal(approver, username))
+    };
+    let username =
+        match find_user_in_payment(&redis, &pending.chat_id, &query.from, is_approver).await {
+            Some(username) if query.from.id.to_string() != pending.sender_id => username,
+            _ => {
+                bot.answer_callback_query(query.id)
+                    .text(t(&redis, &pending.chat_id, APPROVAL_NOT_ALLOWED, &[]).await)
+                    .await?;
+                return Ok(());
+            }
+        };
+    bot.answer_callback_query(query.id).await?;
+
+    let request = msg.text().unwrap_or_default().to_string();
+    if is_approved {
+        match approve_pending_payment(&redis, &payment_id).await {
+            Ok(Some(balances)) => {
+                edit_bot_message(
+                    &bot,
+                    msg.chat.id,
+                    msg.id,
+                    format!(
+                        "{request}\n\n{}",
+                        t(
+                            &redis,
+                            &pending.chat_id,
+                            APPROVED_BY,
+                            &[(
+                                "user",
+                                &display_username(&redis, &pending.chat_id, &username).await
+                            )],
+                        )
+                        .await
+                    ),
+                )
+                .await?;
+                // The approval is already shown on the request, so only verbose chats get the balances
+                if retrieve_verbosity(&redis, &pending.chat_id).await == Verbosity::Verbose {
+                    send_bot_message(
+                        &bot,
+                        &msg,
+                        format!(
+                            "{}{}",
+                            display_balance_header(&redis, &pending.chat_id, &payment.currency)
+                                .await,
+                            display_balances(&redis, &pending.chat_id, &balances).await
+                        ),
+                    )
+                    .await?;
+                }
+
+                // Logging
+                log::info!(
+                    "Payment Approval - Payment {} approved by user {} in chat {}: {:?}",
+                    payment_id,
+                    query.from.id,
+                    pending.chat_id,
+                    payment
+                );
+
+                notify_spending_limits(&redis, &bot, &msg).await?;
+            }
+            Ok(None) => {
+                bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+            }
+            Err(err) => {
+                send_bot_message(
+                    &bot,
+                    &msg,
+                    t(&redis, &pending.chat_id, ADD_PAYMENT_FAILED, &[]).await,
+                )
+                .await?;
+
+                // Logging
+                log::error!(
+                    "Payment Approval - Failed to approve payment {} in chat {}: {}",
+                    payment_id,
+                    pending.chat_id,
+                    err.to_string()
+                );
+            }
+        }
+    } else {
+        match discard_pending_payment(&redis, &payment_id).await {
+            Ok(true) => {
+                edit_bot_message(
+                    &bot,
+                    msg.chat.id,
+                    msg.id,
+                    format!(
+                        "{request}\n\n{}",
+                        t(
+                            &redis,
+                            &pending.chat_id,
+                            DISPUTED_BY,
+                            &[(
+                                "user",
+                                &display_username(&redis, &pending.chat_id, &username).await
+                            )],
+                        )
+                        .await
+                    ),
+                )
+                .await?;
+
+                // Logging
+                log::info!(
+                    "Payment Approval - Payment {} disputed by user {} in chat {}: {:?}",
+                    payment_id,
+                    query.from.id,
+                    pending.chat_id,
+                    payment
+                );
+            }
+            Ok(false) => {
+                bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+            }
+            Err(err) => {
+                send_bot_message(
+                    &bot,
+                    &msg,
+                    t(&redis, &pending.chat_id, DISCARD_PAYMENT_FAILED, &[]).await,
+                )
+                .await?;
+
+                // Logging
+                log::error!(
+                    "Payment Approval - Failed to discard payment {} in chat {}: {}",
+                    payment_id,
+                    pending.chat_id,
+                    err.to_string()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/* Confirms a pending payment in a group chat with dual entry verification.
+ * Bot receives a callback query from the confirmation request.
+ * The payment is only added once the payer and at least one debtor have confirmed it.
+ * Only the payer can confirm as the payer, and only others in the split can confirm as a debtor.
+ */
+pub async fn action_confirm_pending_payment(
+    redis: Redis,
+    bot: Bot,
+    query: CallbackQuery,
+) -> HandlerResult {
+    let (is_payer, payment_id) = match &query.data {
+        Some(data) => match data.strip_prefix(CONFIRM_PAYER_PREFIX) {
+            Some(payment_id) => (true, payment_id.to_string()),
+            None => match data.strip_prefix(CONFIRM_DEBTOR_PREFIX) {
+                Some(payment_id) => (false, payment_id.to_string()),
+                None => return Ok(()),
+            },
+        },
+        None => return Ok(()),
+    };
+
+    let msg = match query.message {
+        Some(msg) => msg,
+        None => return Ok(()),
+    };
+
+    let (pending, payment) = match retrieve_pending_payment(&redis, &payment_id).await? {
+        Some(entry) => entry,
+        None => {
+            bot.answer_callback_query(query.id)
+                .text(t(&redis, &msg.chat.id.to_string(), CONFIRMATION_RESOLVED, &[]).await)
+                .await?;
+            bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+            return Ok(());
+        }
+    };
+
+    let is_allowed = |username: &str| {
+        if is_payer {
+            is_username_equal(username, &payment.creditor)
+        } else {
+            !is_username_equal(username, &payment.creditor)
+                && payment
+                    .debts
+                    .iter()
+                    .any(|(user, _)| is_username_equal(user, username))
+        }
+    };
+    let username =
+        match find_user_in_payment(&redis, &pending.chat_id, &query.from, is_allowed).await {
+            Some(username) => username,
+            None => {
+                let reply = if is_payer {
+                    CONFIRM_PAYER_NOT_ALLOWED
+                } else {
+                    CONFIRM_DEBTOR_NOT_ALLOWED
+                };
+                bot.answer_callback_query(query.id)
+                    .text(t(&redis, &pending.chat_id, reply, &[]).await)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+    let confirmations =
+        match confirm_pending_payment(&redis, &payment_id, is_payer, &username).await {
+            Ok(Some(confirmations)) => confirmations,
+            Ok(None) => {
+                bot.answer_callback_query(query.id).await?;
+                bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+                return Ok(());
+            }
+            Err(err) => {
+                bot.answer_callback_query(query.id).await?;
+                send_bot_message(
+                    &bot,
+                    &msg,
+                    t(&redis, &pending.chat_id, CONFIRM_PAYMENT_FAILED, &[]).await,
+                )
+                .await?;
+
+                // Logging
+                log::error!(
+                    "Payment Confirmation - Failed to confirm payment {} in chat {}: {}",
+                    payment_id,
+                    pending.chat_id,
+                    err.to_string()
+                );
+                return Ok(());
+            }
+        };
+    bot.answer_callback_query(query.id).await?;
+
+    // Logging
+    log::info!(
+        "Payment Confirmation - Payment {} confirmed as {} by user {} in chat {}",
+        payment_id,
+        if is_payer { "payer" } else { "debtor" },
+        query.from.id,
+        pending.chat_id
+    );
+
+    let request = msg.text().unwrap_or_default().to_string();
+    let language = retrieve_language(&redis, &pending.chat_id).await;
+    let (payer, debtor) = match confirmations {
+        (Some(payer), Some(debtor)) => (payer, debtor),
+        (payer, debtor) => {
+            let (confirmed, confirmed_by) = if is_payer {
+                (payer, CONFIRMED_BY_PAYER)
+            } else {
+                (debtor, CONFIRMED_BY_DEBTOR)
+            };
+            // Only the first confirmation in each role is shown on the request
+            if let Some(user) = confirmed {
+                let confirmation = translate(
+                    &language,
+                    confirmed_by,
+                    &[(
+                        "user",
+                        &display_username(&redis, &pending.chat_id, &user).await,
+                    )],
+                );
+                if !request.contains(&confirmation) {
+                    edit_bot_message(
+                        &bot,
+                        msg.chat.id,
+                        msg.id,
+                        format!("{request}\n\n{confirmation}"),
+                    )
+                    .reply_markup(make_keyboard_dual_entry(&payment_id, &language))
+                    .await?;
+                }
+            }
+            return Ok(());
+        }
+    };
+
+    match approve_pending_payment(&redis, &payment_id).await {
+        Ok(Some(balances)) => {
+            let (confirmed_by, confirmation) = if is_payer {
+                (
+                    display_username(&redis, &pending.chat_id, &payer).await,
+                    CONFIRMED_ADDED_PAYER,
+                )
+            } else {
+                (
+                    display_username(&redis, &pending.chat_id, &debtor).await,
+                    CONFIRMED_ADDED_DEBTOR,
+                )
+            };
+            edit_bot_message(
+                &bot,
+                msg.chat.id,
+                msg.id,
+                format!(
+                    "{request}\n\n{}",
+                    translate(&language, confirmation, &[("user", &confirmed_by)])
+                ),
+            )
+            .await?;
+            // The confirmation is already shown on the request, so only verbose chats get the balances
+            if retrieve_verbosity(&redis, &pending.chat_id).await == Verbosity::Verbose {
+                send_bot_message(
+                    &bot,
+                    &msg,
+                    format!(
+                        "{}{}",
+                        display_balance_header(&redis, &pending.chat_id, &payment.currency).await,
+                        display_balances(&redis, &pending.chat_id, &balances).await
+                    ),
+                )
+                .await?;
+            }
+
+            // Logging
+            log::info!(
+                "Payment Confirmation - Payment {} added in chat {} after confirmation by payer {} and debtor {}: {:?}",
+                payment_id,
+                pending.chat_id,
+                payer,
+                debtor,
+                payment
+            );
+
+            notify_spending_limits(&redis, &bot, &msg).await?;
+        }
+        Ok(None) => {
+            bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+        }
+        Err(err) => {
+            send_bot_message(&bot, &msg, translate(&language, ADD_PAYMENT_FAILED, &[])).await?;
+
+            // Logging
+            log::error!(
+                "Payment Confirmation - Failed to add confirmed payment {} in chat {}: {}",
+                payment_id,
+                pending.chat_id,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use chrono_tz::Tz;
+use teloxide::{prelude::*, types::ChatId};
+
+use crate::bot::{
+    handler::utils::{
+        display_currency_amount, display_name, get_currency, retrieve_currency_format,
+        retrieve_language, retrieve_time_zone, with_retry, HandlerResult,
+    },
+    i18n::{
+        translate, ARCHIVE_SUMMARY, ARCHIVE_SUMMARY_ONE, BALANCES_NONE, BALANCE_DEBT, MONTH_FORMAT,
+    },
+    period::get_month_key,
+    processor::{
+        archive_monthly_payments, retrieve_display_names, retrieve_last_archive_month,
+        retrieve_monthly_archive_chats, update_last_archive_month, ArchiveSummary,
+    },
+    redis::Redis,
+};
+
+/* Utilities */
+
+async fn display_archive_summary(
+    redis: &Redis,
+    chat_id: &str,
+    summary: &ArchiveSummary,
+    month: &DateTime<Tz>,
+) -> String {
+    let language = retrieve_language(redis, chat_id).await;
+    let names = retrieve_display_names(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    let currency_format = retrieve_currency_format(redis, chat_id).await;
+    let mut balances = String::new();
+    for debt in &summary.debts {
+        let amount = match get_currency(&debt.currency) {
+            Ok(currency) => display_currency_amount(debt.amount, currency, currency_format),
+            Err(_) => debt.amount.to_string(),
+        };
+        balances.push_str(&translate(
+            &language,
+            BALANCE_DEBT,
+            &[
+                ("debtor", &display_name(&debt.debtor, &names)),
+                ("creditor", &display_name(&debt.creditor, &names)),
+                ("amount", &amount),
+            ],
+        ));
+        balances.push('\n');
+    }
+
+    if balances.is_empty() {
+        balances = translate(&language, BALANCES_NONE, &[]);
+    }
+
+    let month = month
+        .format(&translate(&language, MONTH_FORMAT, &[]))
+        .to_string();
+    if summary.payments == 1 {
+        translate(
+            &language,
+            ARCHIVE_SUMMARY_ONE,
+            &[("month", &month), ("balances", &balances)],
+        )
+    } else {
+        translate(
+            &language,
+            ARCHIVE_SUMMARY,
+            &[
+                ("count", &summary.payments.to_string()),
+                ("month", &month),
+                ("balances", &balances),
+            ],
+        )
+    }
+}
+
+// Archives the previous month for a single chat, if it is due
+async fn archive_chat(redis: &Redis, bot: &Bot, chat_id: &str) -> HandlerResult {
+    let time_zone = retrieve_time_zone(redis, chat_id).await;
+    let now = Utc::now().with_timezone(&time_zone);
+    let current_month = get_month_key(&now);
+
+    let last_month = retrieve_last_archive_month(redis, chat_id).await?;
+    match last_month {
+        Some(month) if month == current_month => return Ok(()),
+        Some(_) => {}
+        None => {
+            // Newly enabled, archival starts from the next month
+            update_last_archive_month(redis, chat_id, &current_month).await?;
+            return Ok(());
+        }
+    }
+
+    let month_start = match time_zone
+        .with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .earliest()
+    {
+        Some(month_start) => month_start,
+        None => return Ok(()),
+    };
+    let previous_month = month_start - Duration::days(1);
+    let label = get_month_key(&previous_month);
+
+    let summary = archive_monthly_payments(redis, chat_id, &label, month_start.naive_utc()).await?;
+    update_last_archive_month(redis, chat_id, &current_month).await?;
+
+    if let Some(summary) = summary {
+        if let Ok(id) = chat_id.parse::<i64>() {
+            with_retry(bot.send_message(
+                ChatId(id),
+                display_archive_summary(redis, chat_id, &summary, &previous_month).await,
+            ))
+            .await?;
+        }
+
+        // Logging
+        log::info!(
+            "Monthly Archive - Archived {} payments for chat {} for month {}",
+            summary.payments,
+            chat_id,
+            summary.month
+        );
+    }
+
+    Ok(())
+}
+
+/* Archives the previous month's payments for all chats with monthly archive enabled.
+ * Called periodically by the scheduler, only acts at the start of each month.
+ */
+pub async fn handle_monthly_archive(redis: &Redis, bot: &Bot) -> HandlerResult {
+    let chats = retrieve_monthly_archive_chats(redis).await?;
+    for chat_id in chats {
+        if let Err(err) = archive_chat(redis, bot, &chat_id).await {
+            log::error!(
+                "Monthly Archive - Failed to archive payments for chat {}: {}",
+                chat_id,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
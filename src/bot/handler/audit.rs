@@ -0,0 +1,86 @@
+use chrono_tz::Tz;
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    handler::utils::{
+        assert_handle_request_limit, display_kept_description, display_username, reformat_datetime,
+        retrieve_language, retrieve_time_zone, send_bot_message, t, HandlerResult,
+    },
+    i18n::{
+        translate, AUDIT_ACTION_ADDED, AUDIT_ACTION_ADJUSTED, AUDIT_ACTION_DELETED,
+        AUDIT_ACTION_EDITED, AUDIT_ACTION_RESTORED, AUDIT_HEADER, AUDIT_NONE, AUDIT_SOMEONE,
+    },
+    processor::retrieve_audit_entries,
+    redis::{AuditEntry, Redis, AUDIT_ADDED, AUDIT_ADJUSTED, AUDIT_DELETED, AUDIT_RESTORED},
+};
+
+/* Utilities */
+const AUDIT_DISPLAY_COUNT: usize = 10;
+
+// Displays a change to a payment, with who made it and when
+async fn display_audit_entry(
+    redis: &Redis,
+    chat_id: &str,
+    entry: &AuditEntry,
+    time_zone: Tz,
+    language: &str,
+) -> String {
+    let (emoji, action) = match entry.action.as_str() {
+        AUDIT_ADDED => ("➕", AUDIT_ACTION_ADDED),
+        AUDIT_DELETED => ("🗑️", AUDIT_ACTION_DELETED),
+        AUDIT_RESTORED => ("♻️", AUDIT_ACTION_RESTORED),
+        AUDIT_ADJUSTED => ("🛠️", AUDIT_ACTION_ADJUSTED),
+        _ => ("✏️", AUDIT_ACTION_EDITED),
+    };
+    let username = if entry.username.is_empty() {
+        translate(language, AUDIT_SOMEONE, &[])
+    } else {
+        display_username(redis, chat_id, &entry.username).await
+    };
+    format!(
+        "{} {} — {} {} \"{}\"",
+        emoji,
+        reformat_datetime(&entry.datetime, time_zone).trim(),
+        username,
+        translate(language, action, &[]),
+        display_kept_description(&entry.description, language)
+    )
+}
+
+/* Views the recent changes to payments in a group chat.
+ * Shows who added, edited, deleted or restored which payment, or adjusted balances, and when, starting from the latest.
+ */
+pub async fn action_audit(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let entries = retrieve_audit_entries(&redis, &chat_id).await?;
+    if entries.is_empty() {
+        send_bot_message(&bot, &msg, t(&redis, &chat_id, AUDIT_NONE, &[]).await).await?;
+        return Ok(());
+    }
+
+    let time_zone = retrieve_time_zone(&redis, &chat_id).await;
+    let language = retrieve_language(&redis, &chat_id).await;
+    let mut changes: Vec<String> = Vec::new();
+    for entry in entries.iter().take(AUDIT_DISPLAY_COUNT) {
+        changes.push(display_audit_entry(&redis, &chat_id, entry, time_zone, &language).await);
+    }
+    send_bot_message(
+        &bot,
+        &msg,
+        format!(
+            "{}\n\n{}",
+            translate(&language, AUDIT_HEADER, &[]),
+            changes.join("\n")
+        ),
+    )
+    .await?;
+
+    // Logging
+    log::info!("Audit - Recent changes viewed in chat {}", chat_id);
+
+    Ok(())
+}
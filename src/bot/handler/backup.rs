@@ -0,0 +1,175 @@
+use chrono::Utc;
+use teloxide::{
+    net::Download,
+    prelude::*,
+    types::{InputFile, Message},
+};
+
+use crate::bot::{
+    handler::utils::{
+        assert_handle_request_limit, is_chat_admin, retrieve_language, send_bot_document,
+        send_bot_message, t, BotError, HandlerResult,
+    },
+    i18n::{
+        translate, Text, BACKUP_CAPTION, BACKUP_DOWNLOAD_FAILED, BACKUP_FAILED, BACKUP_INVALID,
+        BACKUP_SUMMARY, BACKUP_TOO_LARGE, BACKUP_VERSION_MISMATCH, RESTORE_DONE,
+        RESTORE_INSTRUCTIONS, RESTORE_PREVIEW, RESTORE_RESTRICTED, RESTORE_WARNING,
+    },
+    processor::{export_chat_data, restore_chat_data},
+    redis::{ChatBackup, Redis, BACKUP_VERSION},
+};
+
+/* Utilities */
+const BACKUP_SIZE_MAX: u32 = 5 * 1024 * 1024;
+
+// Downloads and parses the backup file that a message replies to
+async fn read_backup(bot: &Bot, msg: &Message) -> Result<ChatBackup, BotError> {
+    let document = match msg.reply_to_message().and_then(|reply| reply.document()) {
+        Some(document) => document,
+        None => return Err(BotError::UserError(Text::new(RESTORE_INSTRUCTIONS))),
+    };
+    if document.file.size > BACKUP_SIZE_MAX {
+        return Err(BotError::UserError(Text::new(BACKUP_TOO_LARGE)));
+    }
+
+    let file = bot.get_file(&document.file.id).await?;
+    let mut content: Vec<u8> = Vec::new();
+    if bot.download_file(&file.path, &mut content).await.is_err() {
+        return Err(BotError::UserError(Text::new(BACKUP_DOWNLOAD_FAILED)));
+    }
+
+    let backup: ChatBackup = match serde_json::from_slice(&content) {
+        Ok(backup) => backup,
+        Err(_) => return Err(BotError::UserError(Text::new(BACKUP_INVALID))),
+    };
+    if backup.version != BACKUP_VERSION {
+        return Err(BotError::UserError(
+            Text::new(BACKUP_VERSION_MISMATCH).arg("version", backup.version),
+        ));
+    }
+
+    Ok(backup)
+}
+
+// Displays a summary of the records in a backup
+fn display_backup_summary(backup: &ChatBackup, language: &str) -> String {
+    translate(
+        language,
+        BACKUP_SUMMARY,
+        &[
+            ("users", &backup.users.len().to_string()),
+            ("payments", &backup.payments.len().to_string()),
+            ("trips", &backup.trips.len().to_string()),
+            ("settings", &backup.settings.len().to_string()),
+        ],
+    )
+}
+
+/* Exports all records of a group chat as a JSON backup file, sent to the chat.
+ * The backup can be restored with /restore, such as on another instance of the bot.
+ */
+pub async fn action_archive(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let backup = export_chat_data(&redis, &chat_id).await?;
+    let content = match serde_json::to_vec_pretty(&backup) {
+        Ok(content) => content,
+        Err(err) => {
+            log::error!(
+                "Archive - Failed to serialize backup for chat {}: {}",
+                chat_id,
+                err.to_string()
+            );
+            send_bot_message(&bot, &msg, t(&redis, &chat_id, BACKUP_FAILED, &[]).await).await?;
+            return Ok(());
+        }
+    };
+
+    let file_name = format!("payscribe_{}.json", Utc::now().format("%Y-%m-%d"));
+    let language = retrieve_language(&redis, &chat_id).await;
+    let caption = format!(
+        "{}\n\n{}\n\n{}",
+        translate(&language, BACKUP_CAPTION, &[]),
+        display_backup_summary(&backup, &language),
+        translate(&language, RESTORE_INSTRUCTIONS, &[])
+    );
+    let document = InputFile::memory(content).file_name(file_name);
+    send_bot_document(&bot, &msg, document)
+        .caption(caption)
+        .await?;
+
+    // Logging
+    log::info!("Archive - Backup exported for chat {}", chat_id);
+
+    Ok(())
+}
+
+/* Restores all records of a group chat from a JSON backup file.
+ * Must be a reply to the backup file, and can only be done by admins of the chat.
+ * Shows a summary of the backup first, and only overwrites the chat once confirmed.
+ */
+pub async fn action_restore(redis: Redis, bot: Bot, msg: Message, args: String) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    if !is_chat_admin(&redis, &bot, &msg).await? {
+        send_bot_message(
+            &bot,
+            &msg,
+            t(&redis, &chat_id, RESTORE_RESTRICTED, &[]).await,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let language = retrieve_language(&redis, &chat_id).await;
+    let backup = match read_backup(&bot, &msg).await {
+        Ok(backup) => backup,
+        Err(BotError::UserError(reply)) => {
+            send_bot_message(&bot, &msg, reply.translate(&language)).await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+
+    if args.trim().to_lowercase() != "confirm" {
+        send_bot_message(
+            &bot,
+            &msg,
+            format!(
+                "{}\n\n{}\n\n{}",
+                translate(&language, RESTORE_PREVIEW, &[]),
+                display_backup_summary(&backup, &language),
+                translate(&language, RESTORE_WARNING, &[])
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    restore_chat_data(&redis, &chat_id, &backup).await?;
+    send_bot_message(
+        &bot,
+        &msg,
+        format!(
+            "{}\n\n{}",
+            translate(&language, RESTORE_DONE, &[]),
+            display_backup_summary(&backup, &language)
+        ),
+    )
+    .await?;
+
+    // Logging
+    log::info!(
+        "Restore - Backup restored for chat {} with {} payments",
+        chat_id,
+        backup.payments.len()
+    );
+
+    Ok(())
+}
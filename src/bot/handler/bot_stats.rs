@@ -0,0 +1,85 @@
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    handler::utils::{assert_handle_request_limit, send_bot_message, HandlerResult},
+    processor::{retrieve_bot_stats, BOT_STATS_ACTIVE_DAYS, BOT_STATS_CACHE_MINUTES},
+    redis::{BotStats, Redis},
+};
+
+/* Utilities */
+// Comma-separated Telegram user IDs of the operators who can view the usage of the bot
+const ADMIN_USER_IDS_VAR: &str = "ADMIN_USER_IDS";
+
+// Checks if a user is an operator, as configured by ADMIN_USER_IDS
+// Bot stats are turned off entirely when it is not set
+fn is_admin_user(user_id: &str) -> bool {
+    match std::env::var(ADMIN_USER_IDS_VAR) {
+        Ok(user_ids) => user_ids.split(',').any(|id| id.trim() == user_id),
+        Err(_) => false,
+    }
+}
+
+// Displays an amount of memory in bytes, in megabytes
+fn display_memory(used_memory: Option<u64>) -> String {
+    match used_memory {
+        Some(bytes) => format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0)),
+        None => "Unknown".to_string(),
+    }
+}
+
+// Displays the usage of the bot
+fn display_bot_stats(stats: &BotStats) -> String {
+    format!(
+        "📊 Here's how PayScribe is being used!\n\nChats: {}\nActive in the last {BOT_STATS_ACTIVE_DAYS} days: {}\nPayments stored: {}\n\nRedis keys: {}\nRedis memory: {}\n\nCounted at {}, and refreshed every {BOT_STATS_CACHE_MINUTES} minutes.",
+        stats.chats,
+        stats.active_chats,
+        stats.payments,
+        stats.keys,
+        display_memory(stats.used_memory),
+        stats.computed_at
+    )
+}
+
+/* Views the usage of the whole bot, across all chats.
+ * Shows the number of chats and those active recently, the payments stored,
+ * and estimates of the keys and memory used in Redis.
+ * Only operators configured by ADMIN_USER_IDS can use it, and it is silent to everyone else.
+ */
+pub async fn action_bot_stats(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    let user_id = match msg.from() {
+        Some(user) => user.id.to_string(),
+        None => return Ok(()),
+    };
+    if !is_admin_user(&user_id) {
+        return Ok(());
+    }
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    match retrieve_bot_stats(&redis).await {
+        Ok(stats) => {
+            send_bot_message(&bot, &msg, display_bot_stats(&stats)).await?;
+
+            // Logging
+            log::info!("Bot Stats - Bot stats viewed by user {}", user_id);
+        }
+        Err(err) => {
+            send_bot_message(
+                &bot,
+                &msg,
+                format!("⁉️ I couldn't count the usage of the bot: {err}"),
+            )
+            .await?;
+
+            // Logging
+            log::error!(
+                "Bot Stats - Failed to count bot stats for user {}: {}",
+                user_id,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
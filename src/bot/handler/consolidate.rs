@@ -0,0 +1,98 @@
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    currency::{get_default_currency, CurrencyFormat},
+    handler::utils::{
+        assert_handle_request_limit, display_currency_amount, get_currency, mention_username,
+        retrieve_currency_format, send_bot_message, HandlerResult,
+    },
+    processor::{retrieve_consolidated_debts, ConsolidatedDebt},
+    redis::Redis,
+};
+
+// Displays the net debts of a user with each counterpart, grouped by currency
+fn display_consolidated_debts(
+    debts: &[ConsolidatedDebt],
+    currency_format: CurrencyFormat,
+) -> String {
+    let mut message = String::new();
+    let mut current_currency: Option<&str> = None;
+    for debt in debts {
+        let currency = get_currency(&debt.currency).unwrap_or(get_default_currency());
+        if current_currency != Some(&debt.currency) {
+            if current_currency.is_some() {
+                message.push('\n');
+            }
+            if currency.0 != get_default_currency().0 {
+                message.push_str(&format!("In {}:\n", currency.0));
+            }
+            current_currency = Some(&debt.currency);
+        }
+
+        let amount = display_currency_amount(debt.amount.abs(), currency, currency_format);
+        let chats = if debt.chats > 1 {
+            format!(" (across {} chats)", debt.chats)
+        } else {
+            String::new()
+        };
+        if debt.amount > 0 {
+            message.push_str(&format!(
+                "You pay {} {amount}{chats}\n",
+                mention_username(&debt.counterpart)
+            ));
+        } else {
+            message.push_str(&format!(
+                "{} pays you {amount}{chats}\n",
+                mention_username(&debt.counterpart)
+            ));
+        }
+    }
+    message
+}
+
+/* Views the debts of the sender across all their group chats, netted per person and currency.
+ * Suggests a single amount to settle up with each person, instead of once in every chat.
+ * Only done in a direct message with the bot, as it covers more than one group.
+ */
+pub async fn action_consolidate(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    if !msg.chat.is_private() {
+        send_bot_message(
+            &bot,
+            &msg,
+            "🔗 This covers all your groups, so send me /consolidate in a direct message instead!"
+                .to_string(),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let user = match msg.from() {
+        Some(user) => user,
+        None => return Ok(()),
+    };
+    let sender_id = user.id.to_string();
+    let debts = retrieve_consolidated_debts(&redis, &sender_id, user.username.as_deref()).await?;
+    let currency_format = retrieve_currency_format(&redis, &msg.chat.id.to_string()).await;
+    let reply = if debts.is_empty() {
+        "🤝 You're all square with everyone across your groups!".to_string()
+    } else {
+        format!(
+            "🔗 Here's where you stand with everyone across all your groups!\n\n{}\n⭐️ This is only a suggestion! The balances in each group change once the paybacks are recorded there with /payback.",
+            display_consolidated_debts(&debts, currency_format)
+        )
+    };
+    send_bot_message(&bot, &msg, reply).await?;
+
+    // Logging
+    log::info!(
+        "Consolidate - User {} viewed their debts across {} counterparts",
+        sender_id,
+        debts.len()
+    );
+
+    Ok(())
+}
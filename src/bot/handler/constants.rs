@@ -1,655 +1,720 @@
 use chrono_tz::Tz;
 use std::collections::HashMap;
 
-pub const MAX_VALUE: i64 = 1_000_000_000_000_000_000;
-pub const UNKNOWN_ERROR_MESSAGE: &str =
-    "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't do that right now. Please try again later!\n\n";
-pub const NO_TEXT_MESSAGE: &str =
-    "❓ I'm having a bit of trouble understanding that! Please reply to me in text!\n\n";
-pub const TOTAL_INSTRUCTIONS_MESSAGE: &str =
-"Enter the amount and optionally, the 3-letter currency code. For example: 100.00 USD, 200 JPY, 300.00, etc.\n\n⭐️ If you're unsure of the currency code, you can always check out my User Guide with /help!";
-pub const CURRENCY_INSTRUCTIONS_MESSAGE: &str =
-    "Enter the 3-letter currency code. For example: USD, EUR, JPY, etc.\n\n⭐️ If you're unsure of the currency code, you can always check out my User Guide with /help!";
-pub const TIME_ZONE_INSTRUCTIONS_MESSAGE: &str =
-    "⭐️ Check out my User Guide with /help for all my supported time zones!";
-pub const DEBT_EQUAL_DESCRIPTION_MESSAGE: &str =
-    "Equal — Divide the total amount equally among users\n";
-pub const DEBT_EXACT_DESCRIPTION_MESSAGE: &str =
-    "Exact — Split the total cost by exact amounts for each user\n";
-pub const DEBT_RATIO_DESCRIPTION_MESSAGE: &str =
-    "Proportion — Share the total cost by relative proportions for each user\n";
-pub const DEBT_EQUAL_INSTRUCTIONS_MESSAGE: &str =
-"Enter the Telegram usernames of everyone sharing like this:\n\n@username__1\n@username__2\n@username__3\n...\n\n⭐️ Remember to include the payer if they're chipping in too!";
-pub const DEBT_EXACT_INSTRUCTIONS_MESSAGE: &str =
-"Enter the Telegram usernames and exact amounts like this: \n\n@username__1 amount1\n@username__2 amount2\n@username__3 amount3\n...\n\n⭐️ If there are any leftover amounts, I'll assume it's the payer's!";
-pub const DEBT_RATIO_INSTRUCTIONS_MESSAGE: &str =
-"Enter the Telegram usernames and portions like this: \n\n@username__1 portion1\n@username__2 portion2\n@username__3 portion3\n...\n\n⭐️ I can work with any positive number, whole or decimal!";
-pub const PAY_BACK_INSTRUCTIONS_MESSAGE: &str =
-"Enter the Telegram usernames and exact amounts like this: \n\n@username__1 amount1\n@username__2 amount2\n@username__3 amount3\n...\n\n";
-pub const STATEMENT_INSTRUCTIONS_MESSAGE: &str =
-    "⭐️ I can also present the other currencies/formats below!";
+pub const PERCENT_TOLERANCE: f64 = 0.1;
+pub const ADMIN_CACHE_TTL: usize = 600;
+pub const PERMISSION_CACHE_TTL: usize = 3600;
+pub const RETRY_MAX_ATTEMPTS: u32 = 3;
+pub const RETRY_BASE_DELAY_MS: u64 = 500;
+pub const RETRY_MAX_DELAY_MS: u64 = 30_000;
+pub const COMPLETION_REACTION: &str = "👍";
+pub const PAYMENT_LINK_BUTTONS_MAX: usize = 10;
+pub const DESCRIPTION_MAX_LENGTH: usize = 100;
+pub const NOTE_MAX_LENGTH: usize = 200;
+pub const EXPRESSION_MAX_LENGTH: usize = 50;
+pub const DEBTORS_MAX: usize = 50;
+pub const CHAT_PAYMENTS_MAX: usize = 10_000;
+pub const USERNAME_MIN_LENGTH: usize = 5;
+pub const USERNAME_MAX_LENGTH: usize = 32;
+pub const SPLIT_GROUP_CALLBACK_PREFIX: &str = "Group:";
+pub const SPLIT_GROUPS_MAX: usize = 6;
+pub const SPLIT_GROUP_NAME_MAX_LENGTH: usize = 20;
+pub const CURRENCY_SUGGESTIONS_MAX: usize = 4;
+pub const VERSION_CALLBACK_PREFIX: &str = "Version:";
+pub const MAKE_TRANSFER_BUTTON: &str = "Make Transfer";
+pub const MAKE_EXPENSE_BUTTON: &str = "Make Expense";
+pub const MARKDOWN_RESERVED_CHARACTERS: &str = "\\_*[]()~`>#+-=|{}.!";
 pub const COMMAND_HELP: &str = "/help";
 pub const COMMAND_CANCEL: &str = "/cancel";
 pub const COMMAND_ADD_PAYMENT: &str = "/addpayment";
-pub const COMMAND_PAY_BACK: &str = "/payback";
 pub const COMMAND_VIEW_PAYMENTS: &str = "/viewpayments";
-pub const COMMAND_EDIT_PAYMENT: &str = "/editpayment";
-pub const COMMAND_DELETE_PAYMENT: &str = "/deletepayment";
-pub const COMMAND_BALANCES: &str = "/balances";
-pub const COMMAND_SPENDINGS: &str = "/spendings";
 pub const USER_GUIDE_URL: &str = "https://payscribe.super.site/user-guide";
 pub const FEEDBACK_URL: &str = "https://payscribe.super.site/feedback";
 
-// List of all supported time zones
+// All supported time zones, grouped by region, each with the cities in it
+// Cities are keyed by their lowercase names, which are what users type to set a time zone
+pub const TIME_ZONE_REGIONS: &[(&str, &[(&str, Tz)])] = &[
+    (
+        "Africa",
+        &[
+            ("abidjan", Tz::Africa__Abidjan),
+            ("accra", Tz::Africa__Accra),
+            ("addis ababa", Tz::Africa__Addis_Ababa),
+            ("algiers", Tz::Africa__Algiers),
+            ("asmara", Tz::Africa__Asmara),
+            ("asmera", Tz::Africa__Asmera),
+            ("bamako", Tz::Africa__Bamako),
+            ("bangui", Tz::Africa__Bangui),
+            ("banjul", Tz::Africa__Banjul),
+            ("bissau", Tz::Africa__Bissau),
+            ("blantyre", Tz::Africa__Blantyre),
+            ("brazzaville", Tz::Africa__Brazzaville),
+            ("bujumbura", Tz::Africa__Bujumbura),
+            ("cairo", Tz::Africa__Cairo),
+            ("casablanca", Tz::Africa__Casablanca),
+            ("ceuta", Tz::Africa__Ceuta),
+            ("conakry", Tz::Africa__Conakry),
+            ("dakar", Tz::Africa__Dakar),
+            ("dar es salaam", Tz::Africa__Dar_es_Salaam),
+            ("djibouti", Tz::Africa__Djibouti),
+            ("douala", Tz::Africa__Douala),
+            ("el aaiun", Tz::Africa__El_Aaiun),
+            ("freetown", Tz::Africa__Freetown),
+            ("gaborone", Tz::Africa__Gaborone),
+            ("harare", Tz::Africa__Harare),
+            ("johannesburg", Tz::Africa__Johannesburg),
+            ("juba", Tz::Africa__Juba),
+            ("kampala", Tz::Africa__Kampala),
+            ("khartoum", Tz::Africa__Khartoum),
+            ("kigali", Tz::Africa__Kigali),
+            ("kinshasa", Tz::Africa__Kinshasa),
+            ("lagos", Tz::Africa__Lagos),
+            ("libreville", Tz::Africa__Libreville),
+            ("lome", Tz::Africa__Lome),
+            ("luanda", Tz::Africa__Luanda),
+            ("lubumbashi", Tz::Africa__Lubumbashi),
+            ("lusaka", Tz::Africa__Lusaka),
+            ("malabo", Tz::Africa__Malabo),
+            ("maputo", Tz::Africa__Maputo),
+            ("maseru", Tz::Africa__Maseru),
+            ("mbabane", Tz::Africa__Mbabane),
+            ("mogadishu", Tz::Africa__Mogadishu),
+            ("monrovia", Tz::Africa__Monrovia),
+            ("nairobi", Tz::Africa__Nairobi),
+            ("ndjamena", Tz::Africa__Ndjamena),
+            ("niamey", Tz::Africa__Niamey),
+            ("nouakchott", Tz::Africa__Nouakchott),
+            ("ouagadougou", Tz::Africa__Ouagadougou),
+            ("portonovo", Tz::Africa__PortoNovo),
+            ("sao tome", Tz::Africa__Sao_Tome),
+            ("timbuktu", Tz::Africa__Timbuktu),
+            ("tripoli", Tz::Africa__Tripoli),
+            ("tunis", Tz::Africa__Tunis),
+            ("windhoek", Tz::Africa__Windhoek),
+        ],
+    ),
+    (
+        "America",
+        &[
+            ("adak", Tz::America__Adak),
+            ("anchorage", Tz::America__Anchorage),
+            ("anguilla", Tz::America__Anguilla),
+            ("antigua", Tz::America__Antigua),
+            ("araguaina", Tz::America__Araguaina),
+            ("catamarca", Tz::America__Argentina__Catamarca),
+            ("cordoba", Tz::America__Argentina__Cordoba),
+            ("jujuy", Tz::America__Argentina__Jujuy),
+            ("la rioja", Tz::America__Argentina__La_Rioja),
+            ("mendoza", Tz::America__Argentina__Mendoza),
+            ("salta", Tz::America__Argentina__Salta),
+            ("san juan", Tz::America__Argentina__San_Juan),
+            ("san luis", Tz::America__Argentina__San_Luis),
+            ("tucuman", Tz::America__Argentina__Tucuman),
+            ("ushuaia", Tz::America__Argentina__Ushuaia),
+            ("aruba", Tz::America__Aruba),
+            ("asuncion", Tz::America__Asuncion),
+            ("atikokan", Tz::America__Atikokan),
+            ("atka", Tz::America__Atka),
+            ("bahia", Tz::America__Bahia),
+            ("bahia_banderas", Tz::America__Bahia_Banderas),
+            ("barbados", Tz::America__Barbados),
+            ("belem", Tz::America__Belem),
+            ("belize", Tz::America__Belize),
+            ("blancsablon", Tz::America__BlancSablon),
+            ("boa vista", Tz::America__Boa_Vista),
+            ("bogota", Tz::America__Bogota),
+            ("boise", Tz::America__Boise),
+            ("buenos aires", Tz::America__Buenos_Aires),
+            ("cambridge bay", Tz::America__Cambridge_Bay),
+            ("campo grande", Tz::America__Campo_Grande),
+            ("cancun", Tz::America__Cancun),
+            ("caracas", Tz::America__Caracas),
+            ("catamarca", Tz::America__Catamarca),
+            ("cayenne", Tz::America__Cayenne),
+            ("cayman", Tz::America__Cayman),
+            ("chicago", Tz::America__Chicago),
+            ("chihuahua", Tz::America__Chihuahua),
+            ("ciudad juarez", Tz::America__Ciudad_Juarez),
+            ("coral harbour", Tz::America__Coral_Harbour),
+            ("cordoba", Tz::America__Cordoba),
+            ("costa rica", Tz::America__Costa_Rica),
+            ("creston", Tz::America__Creston),
+            ("cuiaba", Tz::America__Cuiaba),
+            ("curacao", Tz::America__Curacao),
+            ("danmarkshavn", Tz::America__Danmarkshavn),
+            ("dawson", Tz::America__Dawson),
+            ("dawson creek", Tz::America__Dawson_Creek),
+            ("denver", Tz::America__Denver),
+            ("detroit", Tz::America__Detroit),
+            ("dominica", Tz::America__Dominica),
+            ("edmonton", Tz::America__Edmonton),
+            ("eirunepe", Tz::America__Eirunepe),
+            ("el salvador", Tz::America__El_Salvador),
+            ("ensenada", Tz::America__Ensenada),
+            ("fort nelson", Tz::America__Fort_Nelson),
+            ("fort wayne", Tz::America__Fort_Wayne),
+            ("fortaleza", Tz::America__Fortaleza),
+            ("glace bay", Tz::America__Glace_Bay),
+            ("godthab", Tz::America__Godthab),
+            ("goose bay", Tz::America__Goose_Bay),
+            ("grand turk", Tz::America__Grand_Turk),
+            ("grenada", Tz::America__Grenada),
+            ("guadeloupe", Tz::America__Guadeloupe),
+            ("guatemala", Tz::America__Guatemala),
+            ("guayaquil", Tz::America__Guayaquil),
+            ("guyana", Tz::America__Guyana),
+            ("halifax", Tz::America__Halifax),
+            ("havana", Tz::America__Havana),
+            ("hermosillo", Tz::America__Hermosillo),
+            ("knox", Tz::America__Indiana__Knox),
+            ("marengo", Tz::America__Indiana__Marengo),
+            ("petersburg", Tz::America__Indiana__Petersburg),
+            ("tell city", Tz::America__Indiana__Tell_City),
+            ("vevay", Tz::America__Indiana__Vevay),
+            ("vincennes", Tz::America__Indiana__Vincennes),
+            ("winamac", Tz::America__Indiana__Winamac),
+            ("indianapolis", Tz::America__Indianapolis),
+            ("inuvik", Tz::America__Inuvik),
+            ("iqaluit", Tz::America__Iqaluit),
+            ("jamaica", Tz::America__Jamaica),
+            ("jujuy", Tz::America__Jujuy),
+            ("juneau", Tz::America__Juneau),
+            ("louisville", Tz::America__Kentucky__Louisville),
+            ("monticello", Tz::America__Kentucky__Monticello),
+            ("knox in", Tz::America__Knox_IN),
+            ("kralendijk", Tz::America__Kralendijk),
+            ("la paz", Tz::America__La_Paz),
+            ("lima", Tz::America__Lima),
+            ("los angeles", Tz::America__Los_Angeles),
+            ("louisville", Tz::America__Louisville),
+            ("lower princes", Tz::America__Lower_Princes),
+            ("maceio", Tz::America__Maceio),
+            ("managua", Tz::America__Managua),
+            ("manaus", Tz::America__Manaus),
+            ("marigot", Tz::America__Marigot),
+            ("martinique", Tz::America__Martinique),
+            ("matamoros", Tz::America__Matamoros),
+            ("mazatlan", Tz::America__Mazatlan),
+            ("mendoza", Tz::America__Mendoza),
+            ("menominee", Tz::America__Menominee),
+            ("merida", Tz::America__Merida),
+            ("metlakatla", Tz::America__Metlakatla),
+            ("mexico city", Tz::America__Mexico_City),
+            ("miquelon", Tz::America__Miquelon),
+            ("moncton", Tz::America__Moncton),
+            ("monterrey", Tz::America__Monterrey),
+            ("montevideo", Tz::America__Montevideo),
+            ("montreal", Tz::America__Montreal),
+            ("montserrat", Tz::America__Montserrat),
+            ("nassau", Tz::America__Nassau),
+            ("new york", Tz::America__New_York),
+            ("nipigon", Tz::America__Nipigon),
+            ("nome", Tz::America__Nome),
+            ("noronha", Tz::America__Noronha),
+            ("beulah", Tz::America__North_Dakota__Beulah),
+            ("center", Tz::America__North_Dakota__Center),
+            ("nuuk", Tz::America__Nuuk),
+            ("ojinaga", Tz::America__Ojinaga),
+            ("panama", Tz::America__Panama),
+            ("pangnirtung", Tz::America__Pangnirtung),
+            ("paramaribo", Tz::America__Paramaribo),
+            ("phoenix", Tz::America__Phoenix),
+            ("portauprince", Tz::America__PortauPrince),
+            ("port of spain", Tz::America__Port_of_Spain),
+            ("porto acre", Tz::America__Porto_Acre),
+            ("porto velho", Tz::America__Porto_Velho),
+            ("puerto rico", Tz::America__Puerto_Rico),
+            ("punta arenas", Tz::America__Punta_Arenas),
+            ("rainy river", Tz::America__Rainy_River),
+            ("rankin inlet", Tz::America__Rankin_Inlet),
+            ("recife", Tz::America__Recife),
+            ("regina", Tz::America__Regina),
+            ("resolute", Tz::America__Resolute),
+            ("rio branco", Tz::America__Rio_Branco),
+            ("rosario", Tz::America__Rosario),
+            ("santa isabel", Tz::America__Santa_Isabel),
+            ("santarem", Tz::America__Santarem),
+            ("santiago", Tz::America__Santiago),
+            ("santo domingo", Tz::America__Santo_Domingo),
+            ("sao paulo", Tz::America__Sao_Paulo),
+            ("scoresbysund", Tz::America__Scoresbysund),
+            ("shiprock", Tz::America__Shiprock),
+            ("sitka", Tz::America__Sitka),
+            ("st barthelemy", Tz::America__St_Barthelemy),
+            ("st johns", Tz::America__St_Johns),
+            ("st kitts", Tz::America__St_Kitts),
+            ("st lucia", Tz::America__St_Lucia),
+            ("st thomas", Tz::America__St_Thomas),
+            ("st vincent", Tz::America__St_Vincent),
+            ("swift current", Tz::America__Swift_Current),
+            ("tegucigalpa", Tz::America__Tegucigalpa),
+            ("thule", Tz::America__Thule),
+            ("thunder_bay", Tz::America__Thunder_Bay),
+            ("tijuana", Tz::America__Tijuana),
+            ("toronto", Tz::America__Toronto),
+            ("tortola", Tz::America__Tortola),
+            ("vancouver", Tz::America__Vancouver),
+            ("virgin", Tz::America__Virgin),
+            ("whitehorse", Tz::America__Whitehorse),
+            ("winnipeg", Tz::America__Winnipeg),
+            ("yakutat", Tz::America__Yakutat),
+            ("yellowknife", Tz::America__Yellowknife),
+        ],
+    ),
+    (
+        "Antarctica",
+        &[
+            ("casey", Tz::Antarctica__Casey),
+            ("davis", Tz::Antarctica__Davis),
+            ("dumontdurville", Tz::Antarctica__DumontDUrville),
+            ("macquarie", Tz::Antarctica__Macquarie),
+            ("mawson", Tz::Antarctica__Mawson),
+            ("mcmurdo", Tz::Antarctica__McMurdo),
+            ("palmer", Tz::Antarctica__Palmer),
+            ("rothera", Tz::Antarctica__Rothera),
+            ("south pole", Tz::Antarctica__South_Pole),
+            ("syowa", Tz::Antarctica__Syowa),
+            ("troll", Tz::Antarctica__Troll),
+            ("vostok", Tz::Antarctica__Vostok),
+        ],
+    ),
+    ("Arctic", &[("longyearbyen", Tz::Arctic__Longyearbyen)]),
+    (
+        "Asia",
+        &[
+            ("aden", Tz::Asia__Aden),
+            ("almaty", Tz::Asia__Almaty),
+            ("amman", Tz::Asia__Amman),
+            ("anadyr", Tz::Asia__Anadyr),
+            ("aqtau", Tz::Asia__Aqtau),
+            ("aqtobe", Tz::Asia__Aqtobe),
+            ("ashgabat", Tz::Asia__Ashgabat),
+            ("ashkhabad", Tz::Asia__Ashkhabad),
+            ("atyrau", Tz::Asia__Atyrau),
+            ("baghdad", Tz::Asia__Baghdad),
+            ("bahrain", Tz::Asia__Bahrain),
+            ("baku", Tz::Asia__Baku),
+            ("bangkok", Tz::Asia__Bangkok),
+            ("barnaul", Tz::Asia__Barnaul),
+            ("beirut", Tz::Asia__Beirut),
+            ("bishkek", Tz::Asia__Bishkek),
+            ("brunei", Tz::Asia__Brunei),
+            ("calcutta", Tz::Asia__Calcutta),
+            ("chita", Tz::Asia__Chita),
+            ("choibalsan", Tz::Asia__Choibalsan),
+            ("chongqing", Tz::Asia__Chongqing),
+            ("chungking", Tz::Asia__Chungking),
+            ("colombo", Tz::Asia__Colombo),
+            ("dacca", Tz::Asia__Dacca),
+            ("damascus", Tz::Asia__Damascus),
+            ("dhaka", Tz::Asia__Dhaka),
+            ("dili", Tz::Asia__Dili),
+            ("dubai", Tz::Asia__Dubai),
+            ("dushanbe", Tz::Asia__Dushanbe),
+            ("famagusta", Tz::Asia__Famagusta),
+            ("gaza", Tz::Asia__Gaza),
+            ("harbin", Tz::Asia__Harbin),
+            ("hebron", Tz::Asia__Hebron),
+            ("ho chi minh", Tz::Asia__Ho_Chi_Minh),
+            ("hong kong", Tz::Asia__Hong_Kong),
+            ("hovd", Tz::Asia__Hovd),
+            ("irkutsk", Tz::Asia__Irkutsk),
+            ("istanbul", Tz::Asia__Istanbul),
+            ("jakarta", Tz::Asia__Jakarta),
+            ("jayapura", Tz::Asia__Jayapura),
+            ("jerusalem", Tz::Asia__Jerusalem),
+            ("kabul", Tz::Asia__Kabul),
+            ("kamchatka", Tz::Asia__Kamchatka),
+            ("karachi", Tz::Asia__Karachi),
+            ("kashgar", Tz::Asia__Kashgar),
+            ("kathmandu", Tz::Asia__Kathmandu),
+            ("katmandu", Tz::Asia__Katmandu),
+            ("khandyga", Tz::Asia__Khandyga),
+            ("kolkata", Tz::Asia__Kolkata),
+            ("krasnoyarsk", Tz::Asia__Krasnoyarsk),
+            ("kuala lumpur", Tz::Asia__Kuala_Lumpur),
+            ("kuching", Tz::Asia__Kuching),
+            ("kuwait", Tz::Asia__Kuwait),
+            ("macao", Tz::Asia__Macao),
+            ("macau", Tz::Asia__Macau),
+            ("magadan", Tz::Asia__Magadan),
+            ("makassar", Tz::Asia__Makassar),
+            ("manila", Tz::Asia__Manila),
+            ("muscat", Tz::Asia__Muscat),
+            ("nicosia", Tz::Asia__Nicosia),
+            ("novokuznetsk", Tz::Asia__Novokuznetsk),
+            ("novosibirsk", Tz::Asia__Novosibirsk),
+            ("omsk", Tz::Asia__Omsk),
+            ("oral", Tz::Asia__Oral),
+            ("phnom penh", Tz::Asia__Phnom_Penh),
+            ("pontianak", Tz::Asia__Pontianak),
+            ("pyongyang", Tz::Asia__Pyongyang),
+            ("qatar", Tz::Asia__Qatar),
+            ("qostanay", Tz::Asia__Qostanay),
+            ("qyzylorda", Tz::Asia__Qyzylorda),
+            ("rangoon", Tz::Asia__Rangoon),
+            ("riyadh", Tz::Asia__Riyadh),
+            ("saigon", Tz::Asia__Saigon),
+            ("sakhalin", Tz::Asia__Sakhalin),
+            ("samarkand", Tz::Asia__Samarkand),
+            ("seoul", Tz::Asia__Seoul),
+            ("shanghai", Tz::Asia__Shanghai),
+            ("singapore", Tz::Asia__Singapore),
+            ("srednekolymsk", Tz::Asia__Srednekolymsk),
+            ("taipei", Tz::Asia__Taipei),
+            ("tashkent", Tz::Asia__Tashkent),
+            ("tbilisi", Tz::Asia__Tbilisi),
+            ("tehran", Tz::Asia__Tehran),
+            ("tel aviv", Tz::Asia__Tel_Aviv),
+            ("thimbu", Tz::Asia__Thimbu),
+            ("thimphu", Tz::Asia__Thimphu),
+            ("tokyo", Tz::Asia__Tokyo),
+            ("tomsk", Tz::Asia__Tomsk),
+            ("ujung pandang", Tz::Asia__Ujung_Pandang),
+            ("ulaanbaatar", Tz::Asia__Ulaanbaatar),
+            ("ulan bator", Tz::Asia__Ulan_Bator),
+            ("urumqi", Tz::Asia__Urumqi),
+            ("ustnera", Tz::Asia__UstNera),
+            ("vientiane", Tz::Asia__Vientiane),
+            ("vladivostok", Tz::Asia__Vladivostok),
+            ("yakutsk", Tz::Asia__Yakutsk),
+            ("yangon", Tz::Asia__Yangon),
+            ("yekaterinburg", Tz::Asia__Yekaterinburg),
+            ("yerevan", Tz::Asia__Yerevan),
+        ],
+    ),
+    (
+        "Atlantic",
+        &[
+            ("azores", Tz::Atlantic__Azores),
+            ("bermuda", Tz::Atlantic__Bermuda),
+            ("canary", Tz::Atlantic__Canary),
+            ("cape verde", Tz::Atlantic__Cape_Verde),
+            ("faeroe", Tz::Atlantic__Faeroe),
+            ("faroe", Tz::Atlantic__Faroe),
+            ("jan mayen", Tz::Atlantic__Jan_Mayen),
+            ("madeira", Tz::Atlantic__Madeira),
+            ("reykjavik", Tz::Atlantic__Reykjavik),
+            ("south georgia", Tz::Atlantic__South_Georgia),
+            ("st helena", Tz::Atlantic__St_Helena),
+            ("stanley", Tz::Atlantic__Stanley),
+        ],
+    ),
+    (
+        "Australia",
+        &[
+            ("act", Tz::Australia__ACT),
+            ("adelaide", Tz::Australia__Adelaide),
+            ("brisbane", Tz::Australia__Brisbane),
+            ("broken hill", Tz::Australia__Broken_Hill),
+            ("canberra", Tz::Australia__Canberra),
+            ("currie", Tz::Australia__Currie),
+            ("darwin", Tz::Australia__Darwin),
+            ("eucla", Tz::Australia__Eucla),
+            ("hobart", Tz::Australia__Hobart),
+            ("lhi", Tz::Australia__LHI),
+            ("lindeman", Tz::Australia__Lindeman),
+            ("lord howe", Tz::Australia__Lord_Howe),
+            ("melbourne", Tz::Australia__Melbourne),
+            ("nsw", Tz::Australia__NSW),
+            ("north", Tz::Australia__North),
+            ("perth", Tz::Australia__Perth),
+            ("queensland", Tz::Australia__Queensland),
+            ("south", Tz::Australia__South),
+            ("sydney", Tz::Australia__Sydney),
+            ("tasmania", Tz::Australia__Tasmania),
+            ("victoria", Tz::Australia__Victoria),
+            ("west", Tz::Australia__West),
+            ("yancowinna", Tz::Australia__Yancowinna),
+        ],
+    ),
+    (
+        "Brazil",
+        &[
+            ("acre", Tz::Brazil__Acre),
+            ("denoronha", Tz::Brazil__DeNoronha),
+            ("east", Tz::Brazil__East),
+            ("west", Tz::Brazil__West),
+        ],
+    ),
+    (
+        "Canada",
+        &[
+            ("atlantic", Tz::Canada__Atlantic),
+            ("central", Tz::Canada__Central),
+            ("eastern", Tz::Canada__Eastern),
+            ("mountain", Tz::Canada__Mountain),
+            ("newfoundland", Tz::Canada__Newfoundland),
+            ("pacific", Tz::Canada__Pacific),
+            ("saskatchewan", Tz::Canada__Saskatchewan),
+            ("yukon", Tz::Canada__Yukon),
+        ],
+    ),
+    (
+        "Chile",
+        &[
+            ("continental", Tz::Chile__Continental),
+            ("easterisland", Tz::Chile__EasterIsland),
+        ],
+    ),
+    (
+        "Etc",
+        &[
+            ("gmt", Tz::Etc__GMT),
+            ("gmtplus0", Tz::Etc__GMTPlus0),
+            ("gmtplus1", Tz::Etc__GMTPlus1),
+            ("gmtplus10", Tz::Etc__GMTPlus10),
+            ("gmtplus11", Tz::Etc__GMTPlus11),
+            ("gmtplus12", Tz::Etc__GMTPlus12),
+            ("gmtplus2", Tz::Etc__GMTPlus2),
+            ("gmtplus3", Tz::Etc__GMTPlus3),
+            ("gmtplus4", Tz::Etc__GMTPlus4),
+            ("gmtplus5", Tz::Etc__GMTPlus5),
+            ("gmtplus6", Tz::Etc__GMTPlus6),
+            ("gmtplus7", Tz::Etc__GMTPlus7),
+            ("gmtplus8", Tz::Etc__GMTPlus8),
+            ("gmtplus9", Tz::Etc__GMTPlus9),
+            ("gmtminus0", Tz::Etc__GMTMinus0),
+            ("gmtminus1", Tz::Etc__GMTMinus1),
+            ("gmtminus10", Tz::Etc__GMTMinus10),
+            ("gmtminus11", Tz::Etc__GMTMinus11),
+            ("gmtminus12", Tz::Etc__GMTMinus12),
+            ("gmtminus13", Tz::Etc__GMTMinus13),
+            ("gmtminus14", Tz::Etc__GMTMinus14),
+            ("gmtminus2", Tz::Etc__GMTMinus2),
+            ("gmtminus3", Tz::Etc__GMTMinus3),
+            ("gmtminus4", Tz::Etc__GMTMinus4),
+            ("gmtminus5", Tz::Etc__GMTMinus5),
+            ("gmtminus6", Tz::Etc__GMTMinus6),
+            ("gmtminus7", Tz::Etc__GMTMinus7),
+            ("gmtminus8", Tz::Etc__GMTMinus8),
+            ("gmtminus9", Tz::Etc__GMTMinus9),
+            ("gmt0", Tz::Etc__GMT0),
+            ("greenwich", Tz::Etc__Greenwich),
+            ("uct", Tz::Etc__UCT),
+            ("utc", Tz::Etc__UTC),
+            ("universal", Tz::Etc__Universal),
+            ("zulu", Tz::Etc__Zulu),
+        ],
+    ),
+    (
+        "Europe",
+        &[
+            ("amsterdam", Tz::Europe__Amsterdam),
+            ("andorra", Tz::Europe__Andorra),
+            ("astrakhan", Tz::Europe__Astrakhan),
+            ("athens", Tz::Europe__Athens),
+            ("belfast", Tz::Europe__Belfast),
+            ("belgrade", Tz::Europe__Belgrade),
+            ("berlin", Tz::Europe__Berlin),
+            ("bratislava", Tz::Europe__Bratislava),
+            ("brussels", Tz::Europe__Brussels),
+            ("bucharest", Tz::Europe__Bucharest),
+            ("budapest", Tz::Europe__Budapest),
+            ("busingen", Tz::Europe__Busingen),
+            ("chisinau", Tz::Europe__Chisinau),
+            ("copenhagen", Tz::Europe__Copenhagen),
+            ("dublin", Tz::Europe__Dublin),
+            ("gibraltar", Tz::Europe__Gibraltar),
+            ("guernsey", Tz::Europe__Guernsey),
+            ("helsinki", Tz::Europe__Helsinki),
+            ("isle of man", Tz::Europe__Isle_of_Man),
+            ("istanbul", Tz::Europe__Istanbul),
+            ("jersey", Tz::Europe__Jersey),
+            ("kaliningrad", Tz::Europe__Kaliningrad),
+            ("kiev", Tz::Europe__Kiev),
+            ("kirov", Tz::Europe__Kirov),
+            ("kyiv", Tz::Europe__Kyiv),
+            ("lisbon", Tz::Europe__Lisbon),
+            ("ljubljana", Tz::Europe__Ljubljana),
+            ("london", Tz::Europe__London),
+            ("luxembourg", Tz::Europe__Luxembourg),
+            ("madrid", Tz::Europe__Madrid),
+            ("malta", Tz::Europe__Malta),
+            ("mariehamn", Tz::Europe__Mariehamn),
+            ("minsk", Tz::Europe__Minsk),
+            ("monaco", Tz::Europe__Monaco),
+            ("moscow", Tz::Europe__Moscow),
+            ("nicosia", Tz::Europe__Nicosia),
+            ("oslo", Tz::Europe__Oslo),
+            ("paris", Tz::Europe__Paris),
+            ("podgorica", Tz::Europe__Podgorica),
+            ("prague", Tz::Europe__Prague),
+            ("riga", Tz::Europe__Riga),
+            ("rome", Tz::Europe__Rome),
+            ("samara", Tz::Europe__Samara),
+            ("san marino", Tz::Europe__San_Marino),
+            ("sarajevo", Tz::Europe__Sarajevo),
+            ("saratov", Tz::Europe__Saratov),
+            ("simferopol", Tz::Europe__Simferopol),
+            ("skopje", Tz::Europe__Skopje),
+            ("sofia", Tz::Europe__Sofia),
+            ("stockholm", Tz::Europe__Stockholm),
+            ("tallinn", Tz::Europe__Tallinn),
+            ("tirane", Tz::Europe__Tirane),
+            ("tiraspol", Tz::Europe__Tiraspol),
+            ("ulyanovsk", Tz::Europe__Ulyanovsk),
+            ("uzhgorod", Tz::Europe__Uzhgorod),
+            ("vaduz", Tz::Europe__Vaduz),
+            ("vatican", Tz::Europe__Vatican),
+            ("vienna", Tz::Europe__Vienna),
+            ("vilnius", Tz::Europe__Vilnius),
+            ("volgograd", Tz::Europe__Volgograd),
+            ("warsaw", Tz::Europe__Warsaw),
+            ("zagreb", Tz::Europe__Zagreb),
+            ("zaporozhye", Tz::Europe__Zaporozhye),
+            ("zurich", Tz::Europe__Zurich),
+        ],
+    ),
+    (
+        "Indian",
+        &[
+            ("antananarivo", Tz::Indian__Antananarivo),
+            ("chagos", Tz::Indian__Chagos),
+            ("christmas", Tz::Indian__Christmas),
+            ("cocos", Tz::Indian__Cocos),
+            ("comoro", Tz::Indian__Comoro),
+            ("kerguelen", Tz::Indian__Kerguelen),
+            ("mahe", Tz::Indian__Mahe),
+            ("maldives", Tz::Indian__Maldives),
+            ("mauritius", Tz::Indian__Mauritius),
+            ("mayotte", Tz::Indian__Mayotte),
+            ("reunion", Tz::Indian__Reunion),
+        ],
+    ),
+    (
+        "Mexico",
+        &[
+            ("bajanorte", Tz::Mexico__BajaNorte),
+            ("bajasur", Tz::Mexico__BajaSur),
+            ("general", Tz::Mexico__General),
+        ],
+    ),
+    (
+        "Pacific",
+        &[
+            ("apia", Tz::Pacific__Apia),
+            ("auckland", Tz::Pacific__Auckland),
+            ("bougainville", Tz::Pacific__Bougainville),
+            ("chatham", Tz::Pacific__Chatham),
+            ("chuuk", Tz::Pacific__Chuuk),
+            ("easter", Tz::Pacific__Easter),
+            ("efate", Tz::Pacific__Efate),
+            ("enderbury", Tz::Pacific__Enderbury),
+            ("fakaofo", Tz::Pacific__Fakaofo),
+            ("fiji", Tz::Pacific__Fiji),
+            ("funafuti", Tz::Pacific__Funafuti),
+            ("galapagos", Tz::Pacific__Galapagos),
+            ("gambier", Tz::Pacific__Gambier),
+            ("guadalcanal", Tz::Pacific__Guadalcanal),
+            ("guam", Tz::Pacific__Guam),
+            ("honolulu", Tz::Pacific__Honolulu),
+            ("johnston", Tz::Pacific__Johnston),
+            ("kanton", Tz::Pacific__Kanton),
+            ("kiritimati", Tz::Pacific__Kiritimati),
+            ("kosrae", Tz::Pacific__Kosrae),
+            ("kwajalein", Tz::Pacific__Kwajalein),
+            ("majuro", Tz::Pacific__Majuro),
+            ("marquesas", Tz::Pacific__Marquesas),
+            ("midway", Tz::Pacific__Midway),
+            ("nauru", Tz::Pacific__Nauru),
+            ("niue", Tz::Pacific__Niue),
+            ("norfolk", Tz::Pacific__Norfolk),
+            ("noumea", Tz::Pacific__Noumea),
+            ("pago pago", Tz::Pacific__Pago_Pago),
+            ("palau", Tz::Pacific__Palau),
+            ("pitcairn", Tz::Pacific__Pitcairn),
+            ("pohnpei", Tz::Pacific__Pohnpei),
+            ("ponape", Tz::Pacific__Ponape),
+            ("port moresby", Tz::Pacific__Port_Moresby),
+            ("rarotonga", Tz::Pacific__Rarotonga),
+            ("saipan", Tz::Pacific__Saipan),
+            ("samoa", Tz::Pacific__Samoa),
+            ("tahiti", Tz::Pacific__Tahiti),
+            ("tarawa", Tz::Pacific__Tarawa),
+            ("tongatapu", Tz::Pacific__Tongatapu),
+            ("truk", Tz::Pacific__Truk),
+            ("wake", Tz::Pacific__Wake),
+            ("wallis", Tz::Pacific__Wallis),
+            ("yap", Tz::Pacific__Yap),
+        ],
+    ),
+    (
+        "US",
+        &[
+            ("alaska", Tz::US__Alaska),
+            ("aleutian", Tz::US__Aleutian),
+            ("arizona", Tz::US__Arizona),
+            ("central", Tz::US__Central),
+            ("eastindiana", Tz::US__EastIndiana),
+            ("eastern", Tz::US__Eastern),
+            ("hawaii", Tz::US__Hawaii),
+            ("indianastarke", Tz::US__IndianaStarke),
+            ("michigan", Tz::US__Michigan),
+            ("mountain", Tz::US__Mountain),
+            ("pacific", Tz::US__Pacific),
+            ("samoa", Tz::US__Samoa),
+        ],
+    ),
+    (
+        "Other",
+        &[
+            ("cet", Tz::CET),
+            ("cst6cdt", Tz::CST6CDT),
+            ("cuba", Tz::Cuba),
+            ("eet", Tz::EET),
+            ("est", Tz::EST),
+            ("est5edt", Tz::EST5EDT),
+            ("egypt", Tz::Egypt),
+            ("eire", Tz::Eire),
+            ("gb", Tz::GB),
+            ("gbeire", Tz::GBEire),
+            ("gmt", Tz::GMT),
+            ("gmtplus0", Tz::GMTPlus0),
+            ("gmtminus0", Tz::GMTMinus0),
+            ("gmt0", Tz::GMT0),
+            ("greenwich", Tz::Greenwich),
+            ("hst", Tz::HST),
+            ("hongkong", Tz::Hongkong),
+            ("iceland", Tz::Iceland),
+            ("iran", Tz::Iran),
+            ("israel", Tz::Israel),
+            ("jamaica", Tz::Jamaica),
+            ("japan", Tz::Japan),
+            ("kwajalein", Tz::Kwajalein),
+            ("libya", Tz::Libya),
+            ("met", Tz::MET),
+            ("mst", Tz::MST),
+            ("mst7mdt", Tz::MST7MDT),
+            ("nz", Tz::NZ),
+            ("nzchat", Tz::NZCHAT),
+            ("navajo", Tz::Navajo),
+            ("prc", Tz::PRC),
+            ("pst8pdt", Tz::PST8PDT),
+            ("poland", Tz::Poland),
+            ("portugal", Tz::Portugal),
+            ("roc", Tz::ROC),
+            ("rok", Tz::ROK),
+            ("singapore", Tz::Singapore),
+            ("turkey", Tz::Turkey),
+            ("uct", Tz::UCT),
+            ("utc", Tz::UTC),
+            ("universal", Tz::Universal),
+            ("wsu", Tz::WSU),
+            ("wet", Tz::WET),
+            ("zulu", Tz::Zulu),
+        ],
+    ),
+];
+
+// List of all supported time zones, by the lowercase names of their cities
 pub fn all_time_zones() -> HashMap<String, Tz> {
     let mut map: HashMap<String, Tz> = HashMap::new();
-    map.insert("abidjan".to_string(), Tz::Africa__Abidjan);
-    map.insert("accra".to_string(), Tz::Africa__Accra);
-    map.insert("addis ababa".to_string(), Tz::Africa__Addis_Ababa);
-    map.insert("algiers".to_string(), Tz::Africa__Algiers);
-    map.insert("asmara".to_string(), Tz::Africa__Asmara);
-    map.insert("asmera".to_string(), Tz::Africa__Asmera);
-    map.insert("bamako".to_string(), Tz::Africa__Bamako);
-    map.insert("bangui".to_string(), Tz::Africa__Bangui);
-    map.insert("banjul".to_string(), Tz::Africa__Banjul);
-    map.insert("bissau".to_string(), Tz::Africa__Bissau);
-    map.insert("blantyre".to_string(), Tz::Africa__Blantyre);
-    map.insert("brazzaville".to_string(), Tz::Africa__Brazzaville);
-    map.insert("bujumbura".to_string(), Tz::Africa__Bujumbura);
-    map.insert("cairo".to_string(), Tz::Africa__Cairo);
-    map.insert("casablanca".to_string(), Tz::Africa__Casablanca);
-    map.insert("ceuta".to_string(), Tz::Africa__Ceuta);
-    map.insert("conakry".to_string(), Tz::Africa__Conakry);
-    map.insert("dakar".to_string(), Tz::Africa__Dakar);
-    map.insert("dar es salaam".to_string(), Tz::Africa__Dar_es_Salaam);
-    map.insert("djibouti".to_string(), Tz::Africa__Djibouti);
-    map.insert("douala".to_string(), Tz::Africa__Douala);
-    map.insert("el aaiun".to_string(), Tz::Africa__El_Aaiun);
-    map.insert("freetown".to_string(), Tz::Africa__Freetown);
-    map.insert("gaborone".to_string(), Tz::Africa__Gaborone);
-    map.insert("harare".to_string(), Tz::Africa__Harare);
-    map.insert("johannesburg".to_string(), Tz::Africa__Johannesburg);
-    map.insert("juba".to_string(), Tz::Africa__Juba);
-    map.insert("kampala".to_string(), Tz::Africa__Kampala);
-    map.insert("khartoum".to_string(), Tz::Africa__Khartoum);
-    map.insert("kigali".to_string(), Tz::Africa__Kigali);
-    map.insert("kinshasa".to_string(), Tz::Africa__Kinshasa);
-    map.insert("lagos".to_string(), Tz::Africa__Lagos);
-    map.insert("libreville".to_string(), Tz::Africa__Libreville);
-    map.insert("lome".to_string(), Tz::Africa__Lome);
-    map.insert("luanda".to_string(), Tz::Africa__Luanda);
-    map.insert("lubumbashi".to_string(), Tz::Africa__Lubumbashi);
-    map.insert("lusaka".to_string(), Tz::Africa__Lusaka);
-    map.insert("malabo".to_string(), Tz::Africa__Malabo);
-    map.insert("maputo".to_string(), Tz::Africa__Maputo);
-    map.insert("maseru".to_string(), Tz::Africa__Maseru);
-    map.insert("mbabane".to_string(), Tz::Africa__Mbabane);
-    map.insert("mogadishu".to_string(), Tz::Africa__Mogadishu);
-    map.insert("monrovia".to_string(), Tz::Africa__Monrovia);
-    map.insert("nairobi".to_string(), Tz::Africa__Nairobi);
-    map.insert("ndjamena".to_string(), Tz::Africa__Ndjamena);
-    map.insert("niamey".to_string(), Tz::Africa__Niamey);
-    map.insert("nouakchott".to_string(), Tz::Africa__Nouakchott);
-    map.insert("ouagadougou".to_string(), Tz::Africa__Ouagadougou);
-    map.insert("portonovo".to_string(), Tz::Africa__PortoNovo);
-    map.insert("sao tome".to_string(), Tz::Africa__Sao_Tome);
-    map.insert("timbuktu".to_string(), Tz::Africa__Timbuktu);
-    map.insert("tripoli".to_string(), Tz::Africa__Tripoli);
-    map.insert("tunis".to_string(), Tz::Africa__Tunis);
-    map.insert("windhoek".to_string(), Tz::Africa__Windhoek);
-    map.insert("adak".to_string(), Tz::America__Adak);
-    map.insert("anchorage".to_string(), Tz::America__Anchorage);
-    map.insert("anguilla".to_string(), Tz::America__Anguilla);
-    map.insert("antigua".to_string(), Tz::America__Antigua);
-    map.insert("araguaina".to_string(), Tz::America__Araguaina);
-    map.insert("catamarca".to_string(), Tz::America__Argentina__Catamarca);
-    map.insert(
-        "comodrivadavia".to_string(),
-        Tz::America__Argentina__ComodRivadavia,
-    );
-    map.insert("cordoba".to_string(), Tz::America__Argentina__Cordoba);
-    map.insert("jujuy".to_string(), Tz::America__Argentina__Jujuy);
-    map.insert("la rioja".to_string(), Tz::America__Argentina__La_Rioja);
-    map.insert("mendoza".to_string(), Tz::America__Argentina__Mendoza);
-    map.insert(
-        "rio gallegos".to_string(),
-        Tz::America__Argentina__Rio_Gallegos,
-    );
-    map.insert("salta".to_string(), Tz::America__Argentina__Salta);
-    map.insert("san juan".to_string(), Tz::America__Argentina__San_Juan);
-    map.insert("san luis".to_string(), Tz::America__Argentina__San_Luis);
-    map.insert("tucuman".to_string(), Tz::America__Argentina__Tucuman);
-    map.insert("ushuaia".to_string(), Tz::America__Argentina__Ushuaia);
-    map.insert("aruba".to_string(), Tz::America__Aruba);
-    map.insert("asuncion".to_string(), Tz::America__Asuncion);
-    map.insert("atikokan".to_string(), Tz::America__Atikokan);
-    map.insert("atka".to_string(), Tz::America__Atka);
-    map.insert("bahia".to_string(), Tz::America__Bahia);
-    map.insert("bahia_banderas".to_string(), Tz::America__Bahia_Banderas);
-    map.insert("barbados".to_string(), Tz::America__Barbados);
-    map.insert("belem".to_string(), Tz::America__Belem);
-    map.insert("belize".to_string(), Tz::America__Belize);
-    map.insert("blancsablon".to_string(), Tz::America__BlancSablon);
-    map.insert("boa vista".to_string(), Tz::America__Boa_Vista);
-    map.insert("bogota".to_string(), Tz::America__Bogota);
-    map.insert("boise".to_string(), Tz::America__Boise);
-    map.insert("buenos aires".to_string(), Tz::America__Buenos_Aires);
-    map.insert("cambridge bay".to_string(), Tz::America__Cambridge_Bay);
-    map.insert("campo grande".to_string(), Tz::America__Campo_Grande);
-    map.insert("cancun".to_string(), Tz::America__Cancun);
-    map.insert("caracas".to_string(), Tz::America__Caracas);
-    map.insert("catamarca".to_string(), Tz::America__Catamarca);
-    map.insert("cayenne".to_string(), Tz::America__Cayenne);
-    map.insert("cayman".to_string(), Tz::America__Cayman);
-    map.insert("chicago".to_string(), Tz::America__Chicago);
-    map.insert("chihuahua".to_string(), Tz::America__Chihuahua);
-    map.insert("ciudad juarez".to_string(), Tz::America__Ciudad_Juarez);
-    map.insert("coral harbour".to_string(), Tz::America__Coral_Harbour);
-    map.insert("cordoba".to_string(), Tz::America__Cordoba);
-    map.insert("costa rica".to_string(), Tz::America__Costa_Rica);
-    map.insert("creston".to_string(), Tz::America__Creston);
-    map.insert("cuiaba".to_string(), Tz::America__Cuiaba);
-    map.insert("curacao".to_string(), Tz::America__Curacao);
-    map.insert("danmarkshavn".to_string(), Tz::America__Danmarkshavn);
-    map.insert("dawson".to_string(), Tz::America__Dawson);
-    map.insert("dawson creek".to_string(), Tz::America__Dawson_Creek);
-    map.insert("denver".to_string(), Tz::America__Denver);
-    map.insert("detroit".to_string(), Tz::America__Detroit);
-    map.insert("dominica".to_string(), Tz::America__Dominica);
-    map.insert("edmonton".to_string(), Tz::America__Edmonton);
-    map.insert("eirunepe".to_string(), Tz::America__Eirunepe);
-    map.insert("el salvador".to_string(), Tz::America__El_Salvador);
-    map.insert("ensenada".to_string(), Tz::America__Ensenada);
-    map.insert("fort nelson".to_string(), Tz::America__Fort_Nelson);
-    map.insert("fort wayne".to_string(), Tz::America__Fort_Wayne);
-    map.insert("fortaleza".to_string(), Tz::America__Fortaleza);
-    map.insert("glace bay".to_string(), Tz::America__Glace_Bay);
-    map.insert("godthab".to_string(), Tz::America__Godthab);
-    map.insert("goose bay".to_string(), Tz::America__Goose_Bay);
-    map.insert("grand turk".to_string(), Tz::America__Grand_Turk);
-    map.insert("grenada".to_string(), Tz::America__Grenada);
-    map.insert("guadeloupe".to_string(), Tz::America__Guadeloupe);
-    map.insert("guatemala".to_string(), Tz::America__Guatemala);
-    map.insert("guayaquil".to_string(), Tz::America__Guayaquil);
-    map.insert("guyana".to_string(), Tz::America__Guyana);
-    map.insert("halifax".to_string(), Tz::America__Halifax);
-    map.insert("havana".to_string(), Tz::America__Havana);
-    map.insert("hermosillo".to_string(), Tz::America__Hermosillo);
-    map.insert(
-        "indianapolis".to_string(),
-        Tz::America__Indiana__Indianapolis,
-    );
-    map.insert("knox".to_string(), Tz::America__Indiana__Knox);
-    map.insert("marengo".to_string(), Tz::America__Indiana__Marengo);
-    map.insert("petersburg".to_string(), Tz::America__Indiana__Petersburg);
-    map.insert("tell city".to_string(), Tz::America__Indiana__Tell_City);
-    map.insert("vevay".to_string(), Tz::America__Indiana__Vevay);
-    map.insert("vincennes".to_string(), Tz::America__Indiana__Vincennes);
-    map.insert("winamac".to_string(), Tz::America__Indiana__Winamac);
-    map.insert("indianapolis".to_string(), Tz::America__Indianapolis);
-    map.insert("inuvik".to_string(), Tz::America__Inuvik);
-    map.insert("iqaluit".to_string(), Tz::America__Iqaluit);
-    map.insert("jamaica".to_string(), Tz::America__Jamaica);
-    map.insert("jujuy".to_string(), Tz::America__Jujuy);
-    map.insert("juneau".to_string(), Tz::America__Juneau);
-    map.insert("louisville".to_string(), Tz::America__Kentucky__Louisville);
-    map.insert("monticello".to_string(), Tz::America__Kentucky__Monticello);
-    map.insert("knox in".to_string(), Tz::America__Knox_IN);
-    map.insert("kralendijk".to_string(), Tz::America__Kralendijk);
-    map.insert("la paz".to_string(), Tz::America__La_Paz);
-    map.insert("lima".to_string(), Tz::America__Lima);
-    map.insert("los angeles".to_string(), Tz::America__Los_Angeles);
-    map.insert("louisville".to_string(), Tz::America__Louisville);
-    map.insert("lower princes".to_string(), Tz::America__Lower_Princes);
-    map.insert("maceio".to_string(), Tz::America__Maceio);
-    map.insert("managua".to_string(), Tz::America__Managua);
-    map.insert("manaus".to_string(), Tz::America__Manaus);
-    map.insert("marigot".to_string(), Tz::America__Marigot);
-    map.insert("martinique".to_string(), Tz::America__Martinique);
-    map.insert("matamoros".to_string(), Tz::America__Matamoros);
-    map.insert("mazatlan".to_string(), Tz::America__Mazatlan);
-    map.insert("mendoza".to_string(), Tz::America__Mendoza);
-    map.insert("menominee".to_string(), Tz::America__Menominee);
-    map.insert("merida".to_string(), Tz::America__Merida);
-    map.insert("metlakatla".to_string(), Tz::America__Metlakatla);
-    map.insert("mexico city".to_string(), Tz::America__Mexico_City);
-    map.insert("miquelon".to_string(), Tz::America__Miquelon);
-    map.insert("moncton".to_string(), Tz::America__Moncton);
-    map.insert("monterrey".to_string(), Tz::America__Monterrey);
-    map.insert("montevideo".to_string(), Tz::America__Montevideo);
-    map.insert("montreal".to_string(), Tz::America__Montreal);
-    map.insert("montserrat".to_string(), Tz::America__Montserrat);
-    map.insert("nassau".to_string(), Tz::America__Nassau);
-    map.insert("new york".to_string(), Tz::America__New_York);
-    map.insert("nipigon".to_string(), Tz::America__Nipigon);
-    map.insert("nome".to_string(), Tz::America__Nome);
-    map.insert("noronha".to_string(), Tz::America__Noronha);
-    map.insert("beulah".to_string(), Tz::America__North_Dakota__Beulah);
-    map.insert("center".to_string(), Tz::America__North_Dakota__Center);
-    map.insert(
-        "new salem".to_string(),
-        Tz::America__North_Dakota__New_Salem,
-    );
-    map.insert("nuuk".to_string(), Tz::America__Nuuk);
-    map.insert("ojinaga".to_string(), Tz::America__Ojinaga);
-    map.insert("panama".to_string(), Tz::America__Panama);
-    map.insert("pangnirtung".to_string(), Tz::America__Pangnirtung);
-    map.insert("paramaribo".to_string(), Tz::America__Paramaribo);
-    map.insert("phoenix".to_string(), Tz::America__Phoenix);
-    map.insert("portauprince".to_string(), Tz::America__PortauPrince);
-    map.insert("port of spain".to_string(), Tz::America__Port_of_Spain);
-    map.insert("porto acre".to_string(), Tz::America__Porto_Acre);
-    map.insert("porto velho".to_string(), Tz::America__Porto_Velho);
-    map.insert("puerto rico".to_string(), Tz::America__Puerto_Rico);
-    map.insert("punta arenas".to_string(), Tz::America__Punta_Arenas);
-    map.insert("rainy river".to_string(), Tz::America__Rainy_River);
-    map.insert("rankin inlet".to_string(), Tz::America__Rankin_Inlet);
-    map.insert("recife".to_string(), Tz::America__Recife);
-    map.insert("regina".to_string(), Tz::America__Regina);
-    map.insert("resolute".to_string(), Tz::America__Resolute);
-    map.insert("rio branco".to_string(), Tz::America__Rio_Branco);
-    map.insert("rosario".to_string(), Tz::America__Rosario);
-    map.insert("santa isabel".to_string(), Tz::America__Santa_Isabel);
-    map.insert("santarem".to_string(), Tz::America__Santarem);
-    map.insert("santiago".to_string(), Tz::America__Santiago);
-    map.insert("santo domingo".to_string(), Tz::America__Santo_Domingo);
-    map.insert("sao paulo".to_string(), Tz::America__Sao_Paulo);
-    map.insert("scoresbysund".to_string(), Tz::America__Scoresbysund);
-    map.insert("shiprock".to_string(), Tz::America__Shiprock);
-    map.insert("sitka".to_string(), Tz::America__Sitka);
-    map.insert("st barthelemy".to_string(), Tz::America__St_Barthelemy);
-    map.insert("st johns".to_string(), Tz::America__St_Johns);
-    map.insert("st kitts".to_string(), Tz::America__St_Kitts);
-    map.insert("st lucia".to_string(), Tz::America__St_Lucia);
-    map.insert("st thomas".to_string(), Tz::America__St_Thomas);
-    map.insert("st vincent".to_string(), Tz::America__St_Vincent);
-    map.insert("swift current".to_string(), Tz::America__Swift_Current);
-    map.insert("tegucigalpa".to_string(), Tz::America__Tegucigalpa);
-    map.insert("thule".to_string(), Tz::America__Thule);
-    map.insert("thunder_bay".to_string(), Tz::America__Thunder_Bay);
-    map.insert("tijuana".to_string(), Tz::America__Tijuana);
-    map.insert("toronto".to_string(), Tz::America__Toronto);
-    map.insert("tortola".to_string(), Tz::America__Tortola);
-    map.insert("vancouver".to_string(), Tz::America__Vancouver);
-    map.insert("virgin".to_string(), Tz::America__Virgin);
-    map.insert("whitehorse".to_string(), Tz::America__Whitehorse);
-    map.insert("winnipeg".to_string(), Tz::America__Winnipeg);
-    map.insert("yakutat".to_string(), Tz::America__Yakutat);
-    map.insert("yellowknife".to_string(), Tz::America__Yellowknife);
-    map.insert("casey".to_string(), Tz::Antarctica__Casey);
-    map.insert("davis".to_string(), Tz::Antarctica__Davis);
-    map.insert("dumontdurville".to_string(), Tz::Antarctica__DumontDUrville);
-    map.insert("macquarie".to_string(), Tz::Antarctica__Macquarie);
-    map.insert("mawson".to_string(), Tz::Antarctica__Mawson);
-    map.insert("mcmurdo".to_string(), Tz::Antarctica__McMurdo);
-    map.insert("palmer".to_string(), Tz::Antarctica__Palmer);
-    map.insert("rothera".to_string(), Tz::Antarctica__Rothera);
-    map.insert("south pole".to_string(), Tz::Antarctica__South_Pole);
-    map.insert("syowa".to_string(), Tz::Antarctica__Syowa);
-    map.insert("troll".to_string(), Tz::Antarctica__Troll);
-    map.insert("vostok".to_string(), Tz::Antarctica__Vostok);
-    map.insert("longyearbyen".to_string(), Tz::Arctic__Longyearbyen);
-    map.insert("aden".to_string(), Tz::Asia__Aden);
-    map.insert("almaty".to_string(), Tz::Asia__Almaty);
-    map.insert("amman".to_string(), Tz::Asia__Amman);
-    map.insert("anadyr".to_string(), Tz::Asia__Anadyr);
-    map.insert("aqtau".to_string(), Tz::Asia__Aqtau);
-    map.insert("aqtobe".to_string(), Tz::Asia__Aqtobe);
-    map.insert("ashgabat".to_string(), Tz::Asia__Ashgabat);
-    map.insert("ashkhabad".to_string(), Tz::Asia__Ashkhabad);
-    map.insert("atyrau".to_string(), Tz::Asia__Atyrau);
-    map.insert("baghdad".to_string(), Tz::Asia__Baghdad);
-    map.insert("bahrain".to_string(), Tz::Asia__Bahrain);
-    map.insert("baku".to_string(), Tz::Asia__Baku);
-    map.insert("bangkok".to_string(), Tz::Asia__Bangkok);
-    map.insert("barnaul".to_string(), Tz::Asia__Barnaul);
-    map.insert("beirut".to_string(), Tz::Asia__Beirut);
-    map.insert("bishkek".to_string(), Tz::Asia__Bishkek);
-    map.insert("brunei".to_string(), Tz::Asia__Brunei);
-    map.insert("calcutta".to_string(), Tz::Asia__Calcutta);
-    map.insert("chita".to_string(), Tz::Asia__Chita);
-    map.insert("choibalsan".to_string(), Tz::Asia__Choibalsan);
-    map.insert("chongqing".to_string(), Tz::Asia__Chongqing);
-    map.insert("chungking".to_string(), Tz::Asia__Chungking);
-    map.insert("colombo".to_string(), Tz::Asia__Colombo);
-    map.insert("dacca".to_string(), Tz::Asia__Dacca);
-    map.insert("damascus".to_string(), Tz::Asia__Damascus);
-    map.insert("dhaka".to_string(), Tz::Asia__Dhaka);
-    map.insert("dili".to_string(), Tz::Asia__Dili);
-    map.insert("dubai".to_string(), Tz::Asia__Dubai);
-    map.insert("dushanbe".to_string(), Tz::Asia__Dushanbe);
-    map.insert("famagusta".to_string(), Tz::Asia__Famagusta);
-    map.insert("gaza".to_string(), Tz::Asia__Gaza);
-    map.insert("harbin".to_string(), Tz::Asia__Harbin);
-    map.insert("hebron".to_string(), Tz::Asia__Hebron);
-    map.insert("ho chi minh".to_string(), Tz::Asia__Ho_Chi_Minh);
-    map.insert("hong kong".to_string(), Tz::Asia__Hong_Kong);
-    map.insert("hovd".to_string(), Tz::Asia__Hovd);
-    map.insert("irkutsk".to_string(), Tz::Asia__Irkutsk);
-    map.insert("istanbul".to_string(), Tz::Asia__Istanbul);
-    map.insert("jakarta".to_string(), Tz::Asia__Jakarta);
-    map.insert("jayapura".to_string(), Tz::Asia__Jayapura);
-    map.insert("jerusalem".to_string(), Tz::Asia__Jerusalem);
-    map.insert("kabul".to_string(), Tz::Asia__Kabul);
-    map.insert("kamchatka".to_string(), Tz::Asia__Kamchatka);
-    map.insert("karachi".to_string(), Tz::Asia__Karachi);
-    map.insert("kashgar".to_string(), Tz::Asia__Kashgar);
-    map.insert("kathmandu".to_string(), Tz::Asia__Kathmandu);
-    map.insert("katmandu".to_string(), Tz::Asia__Katmandu);
-    map.insert("khandyga".to_string(), Tz::Asia__Khandyga);
-    map.insert("kolkata".to_string(), Tz::Asia__Kolkata);
-    map.insert("krasnoyarsk".to_string(), Tz::Asia__Krasnoyarsk);
-    map.insert("kuala lumpur".to_string(), Tz::Asia__Kuala_Lumpur);
-    map.insert("kuching".to_string(), Tz::Asia__Kuching);
-    map.insert("kuwait".to_string(), Tz::Asia__Kuwait);
-    map.insert("macao".to_string(), Tz::Asia__Macao);
-    map.insert("macau".to_string(), Tz::Asia__Macau);
-    map.insert("magadan".to_string(), Tz::Asia__Magadan);
-    map.insert("makassar".to_string(), Tz::Asia__Makassar);
-    map.insert("manila".to_string(), Tz::Asia__Manila);
-    map.insert("muscat".to_string(), Tz::Asia__Muscat);
-    map.insert("nicosia".to_string(), Tz::Asia__Nicosia);
-    map.insert("novokuznetsk".to_string(), Tz::Asia__Novokuznetsk);
-    map.insert("novosibirsk".to_string(), Tz::Asia__Novosibirsk);
-    map.insert("omsk".to_string(), Tz::Asia__Omsk);
-    map.insert("oral".to_string(), Tz::Asia__Oral);
-    map.insert("phnom penh".to_string(), Tz::Asia__Phnom_Penh);
-    map.insert("pontianak".to_string(), Tz::Asia__Pontianak);
-    map.insert("pyongyang".to_string(), Tz::Asia__Pyongyang);
-    map.insert("qatar".to_string(), Tz::Asia__Qatar);
-    map.insert("qostanay".to_string(), Tz::Asia__Qostanay);
-    map.insert("qyzylorda".to_string(), Tz::Asia__Qyzylorda);
-    map.insert("rangoon".to_string(), Tz::Asia__Rangoon);
-    map.insert("riyadh".to_string(), Tz::Asia__Riyadh);
-    map.insert("saigon".to_string(), Tz::Asia__Saigon);
-    map.insert("sakhalin".to_string(), Tz::Asia__Sakhalin);
-    map.insert("samarkand".to_string(), Tz::Asia__Samarkand);
-    map.insert("seoul".to_string(), Tz::Asia__Seoul);
-    map.insert("shanghai".to_string(), Tz::Asia__Shanghai);
-    map.insert("singapore".to_string(), Tz::Asia__Singapore);
-    map.insert("srednekolymsk".to_string(), Tz::Asia__Srednekolymsk);
-    map.insert("taipei".to_string(), Tz::Asia__Taipei);
-    map.insert("tashkent".to_string(), Tz::Asia__Tashkent);
-    map.insert("tbilisi".to_string(), Tz::Asia__Tbilisi);
-    map.insert("tehran".to_string(), Tz::Asia__Tehran);
-    map.insert("tel aviv".to_string(), Tz::Asia__Tel_Aviv);
-    map.insert("thimbu".to_string(), Tz::Asia__Thimbu);
-    map.insert("thimphu".to_string(), Tz::Asia__Thimphu);
-    map.insert("tokyo".to_string(), Tz::Asia__Tokyo);
-    map.insert("tomsk".to_string(), Tz::Asia__Tomsk);
-    map.insert("ujung pandang".to_string(), Tz::Asia__Ujung_Pandang);
-    map.insert("ulaanbaatar".to_string(), Tz::Asia__Ulaanbaatar);
-    map.insert("ulan bator".to_string(), Tz::Asia__Ulan_Bator);
-    map.insert("urumqi".to_string(), Tz::Asia__Urumqi);
-    map.insert("ustnera".to_string(), Tz::Asia__UstNera);
-    map.insert("vientiane".to_string(), Tz::Asia__Vientiane);
-    map.insert("vladivostok".to_string(), Tz::Asia__Vladivostok);
-    map.insert("yakutsk".to_string(), Tz::Asia__Yakutsk);
-    map.insert("yangon".to_string(), Tz::Asia__Yangon);
-    map.insert("yekaterinburg".to_string(), Tz::Asia__Yekaterinburg);
-    map.insert("yerevan".to_string(), Tz::Asia__Yerevan);
-    map.insert("azores".to_string(), Tz::Atlantic__Azores);
-    map.insert("bermuda".to_string(), Tz::Atlantic__Bermuda);
-    map.insert("canary".to_string(), Tz::Atlantic__Canary);
-    map.insert("cape verde".to_string(), Tz::Atlantic__Cape_Verde);
-    map.insert("faeroe".to_string(), Tz::Atlantic__Faeroe);
-    map.insert("faroe".to_string(), Tz::Atlantic__Faroe);
-    map.insert("jan mayen".to_string(), Tz::Atlantic__Jan_Mayen);
-    map.insert("madeira".to_string(), Tz::Atlantic__Madeira);
-    map.insert("reykjavik".to_string(), Tz::Atlantic__Reykjavik);
-    map.insert("south georgia".to_string(), Tz::Atlantic__South_Georgia);
-    map.insert("st helena".to_string(), Tz::Atlantic__St_Helena);
-    map.insert("stanley".to_string(), Tz::Atlantic__Stanley);
-    map.insert("act".to_string(), Tz::Australia__ACT);
-    map.insert("adelaide".to_string(), Tz::Australia__Adelaide);
-    map.insert("brisbane".to_string(), Tz::Australia__Brisbane);
-    map.insert("broken hill".to_string(), Tz::Australia__Broken_Hill);
-    map.insert("canberra".to_string(), Tz::Australia__Canberra);
-    map.insert("currie".to_string(), Tz::Australia__Currie);
-    map.insert("darwin".to_string(), Tz::Australia__Darwin);
-    map.insert("eucla".to_string(), Tz::Australia__Eucla);
-    map.insert("hobart".to_string(), Tz::Australia__Hobart);
-    map.insert("lhi".to_string(), Tz::Australia__LHI);
-    map.insert("lindeman".to_string(), Tz::Australia__Lindeman);
-    map.insert("lord howe".to_string(), Tz::Australia__Lord_Howe);
-    map.insert("melbourne".to_string(), Tz::Australia__Melbourne);
-    map.insert("nsw".to_string(), Tz::Australia__NSW);
-    map.insert("north".to_string(), Tz::Australia__North);
-    map.insert("perth".to_string(), Tz::Australia__Perth);
-    map.insert("queensland".to_string(), Tz::Australia__Queensland);
-    map.insert("south".to_string(), Tz::Australia__South);
-    map.insert("sydney".to_string(), Tz::Australia__Sydney);
-    map.insert("tasmania".to_string(), Tz::Australia__Tasmania);
-    map.insert("victoria".to_string(), Tz::Australia__Victoria);
-    map.insert("west".to_string(), Tz::Australia__West);
-    map.insert("yancowinna".to_string(), Tz::Australia__Yancowinna);
-    map.insert("acre".to_string(), Tz::Brazil__Acre);
-    map.insert("denoronha".to_string(), Tz::Brazil__DeNoronha);
-    map.insert("east".to_string(), Tz::Brazil__East);
-    map.insert("west".to_string(), Tz::Brazil__West);
-    map.insert("cet".to_string(), Tz::CET);
-    map.insert("cst6cdt".to_string(), Tz::CST6CDT);
-    map.insert("atlantic".to_string(), Tz::Canada__Atlantic);
-    map.insert("central".to_string(), Tz::Canada__Central);
-    map.insert("eastern".to_string(), Tz::Canada__Eastern);
-    map.insert("mountain".to_string(), Tz::Canada__Mountain);
-    map.insert("newfoundland".to_string(), Tz::Canada__Newfoundland);
-    map.insert("pacific".to_string(), Tz::Canada__Pacific);
-    map.insert("saskatchewan".to_string(), Tz::Canada__Saskatchewan);
-    map.insert("yukon".to_string(), Tz::Canada__Yukon);
-    map.insert("continental".to_string(), Tz::Chile__Continental);
-    map.insert("easterisland".to_string(), Tz::Chile__EasterIsland);
-    map.insert("cuba".to_string(), Tz::Cuba);
-    map.insert("eet".to_string(), Tz::EET);
-    map.insert("est".to_string(), Tz::EST);
-    map.insert("est5edt".to_string(), Tz::EST5EDT);
-    map.insert("egypt".to_string(), Tz::Egypt);
-    map.insert("eire".to_string(), Tz::Eire);
-    map.insert("gmt".to_string(), Tz::Etc__GMT);
-    map.insert("gmtplus0".to_string(), Tz::Etc__GMTPlus0);
-    map.insert("gmtplus1".to_string(), Tz::Etc__GMTPlus1);
-    map.insert("gmtplus10".to_string(), Tz::Etc__GMTPlus10);
-    map.insert("gmtplus11".to_string(), Tz::Etc__GMTPlus11);
-    map.insert("gmtplus12".to_string(), Tz::Etc__GMTPlus12);
-    map.insert("gmtplus2".to_string(), Tz::Etc__GMTPlus2);
-    map.insert("gmtplus3".to_string(), Tz::Etc__GMTPlus3);
-    map.insert("gmtplus4".to_string(), Tz::Etc__GMTPlus4);
-    map.insert("gmtplus5".to_string(), Tz::Etc__GMTPlus5);
-    map.insert("gmtplus6".to_string(), Tz::Etc__GMTPlus6);
-    map.insert("gmtplus7".to_string(), Tz::Etc__GMTPlus7);
-    map.insert("gmtplus8".to_string(), Tz::Etc__GMTPlus8);
-    map.insert("gmtplus9".to_string(), Tz::Etc__GMTPlus9);
-    map.insert("gmtminus0".to_string(), Tz::Etc__GMTMinus0);
-    map.insert("gmtminus1".to_string(), Tz::Etc__GMTMinus1);
-    map.insert("gmtminus10".to_string(), Tz::Etc__GMTMinus10);
-    map.insert("gmtminus11".to_string(), Tz::Etc__GMTMinus11);
-    map.insert("gmtminus12".to_string(), Tz::Etc__GMTMinus12);
-    map.insert("gmtminus13".to_string(), Tz::Etc__GMTMinus13);
-    map.insert("gmtminus14".to_string(), Tz::Etc__GMTMinus14);
-    map.insert("gmtminus2".to_string(), Tz::Etc__GMTMinus2);
-    map.insert("gmtminus3".to_string(), Tz::Etc__GMTMinus3);
-    map.insert("gmtminus4".to_string(), Tz::Etc__GMTMinus4);
-    map.insert("gmtminus5".to_string(), Tz::Etc__GMTMinus5);
-    map.insert("gmtminus6".to_string(), Tz::Etc__GMTMinus6);
-    map.insert("gmtminus7".to_string(), Tz::Etc__GMTMinus7);
-    map.insert("gmtminus8".to_string(), Tz::Etc__GMTMinus8);
-    map.insert("gmtminus9".to_string(), Tz::Etc__GMTMinus9);
-    map.insert("gmt0".to_string(), Tz::Etc__GMT0);
-    map.insert("greenwich".to_string(), Tz::Etc__Greenwich);
-    map.insert("uct".to_string(), Tz::Etc__UCT);
-    map.insert("utc".to_string(), Tz::Etc__UTC);
-    map.insert("universal".to_string(), Tz::Etc__Universal);
-    map.insert("zulu".to_string(), Tz::Etc__Zulu);
-    map.insert("amsterdam".to_string(), Tz::Europe__Amsterdam);
-    map.insert("andorra".to_string(), Tz::Europe__Andorra);
-    map.insert("astrakhan".to_string(), Tz::Europe__Astrakhan);
-    map.insert("athens".to_string(), Tz::Europe__Athens);
-    map.insert("belfast".to_string(), Tz::Europe__Belfast);
-    map.insert("belgrade".to_string(), Tz::Europe__Belgrade);
-    map.insert("berlin".to_string(), Tz::Europe__Berlin);
-    map.insert("bratislava".to_string(), Tz::Europe__Bratislava);
-    map.insert("brussels".to_string(), Tz::Europe__Brussels);
-    map.insert("bucharest".to_string(), Tz::Europe__Bucharest);
-    map.insert("budapest".to_string(), Tz::Europe__Budapest);
-    map.insert("busingen".to_string(), Tz::Europe__Busingen);
-    map.insert("chisinau".to_string(), Tz::Europe__Chisinau);
-    map.insert("copenhagen".to_string(), Tz::Europe__Copenhagen);
-    map.insert("dublin".to_string(), Tz::Europe__Dublin);
-    map.insert("gibraltar".to_string(), Tz::Europe__Gibraltar);
-    map.insert("guernsey".to_string(), Tz::Europe__Guernsey);
-    map.insert("helsinki".to_string(), Tz::Europe__Helsinki);
-    map.insert("isle of man".to_string(), Tz::Europe__Isle_of_Man);
-    map.insert("istanbul".to_string(), Tz::Europe__Istanbul);
-    map.insert("jersey".to_string(), Tz::Europe__Jersey);
-    map.insert("kaliningrad".to_string(), Tz::Europe__Kaliningrad);
-    map.insert("kiev".to_string(), Tz::Europe__Kiev);
-    map.insert("kirov".to_string(), Tz::Europe__Kirov);
-    map.insert("kyiv".to_string(), Tz::Europe__Kyiv);
-    map.insert("lisbon".to_string(), Tz::Europe__Lisbon);
-    map.insert("ljubljana".to_string(), Tz::Europe__Ljubljana);
-    map.insert("london".to_string(), Tz::Europe__London);
-    map.insert("luxembourg".to_string(), Tz::Europe__Luxembourg);
-    map.insert("madrid".to_string(), Tz::Europe__Madrid);
-    map.insert("malta".to_string(), Tz::Europe__Malta);
-    map.insert("mariehamn".to_string(), Tz::Europe__Mariehamn);
-    map.insert("minsk".to_string(), Tz::Europe__Minsk);
-    map.insert("monaco".to_string(), Tz::Europe__Monaco);
-    map.insert("moscow".to_string(), Tz::Europe__Moscow);
-    map.insert("nicosia".to_string(), Tz::Europe__Nicosia);
-    map.insert("oslo".to_string(), Tz::Europe__Oslo);
-    map.insert("paris".to_string(), Tz::Europe__Paris);
-    map.insert("podgorica".to_string(), Tz::Europe__Podgorica);
-    map.insert("prague".to_string(), Tz::Europe__Prague);
-    map.insert("riga".to_string(), Tz::Europe__Riga);
-    map.insert("rome".to_string(), Tz::Europe__Rome);
-    map.insert("samara".to_string(), Tz::Europe__Samara);
-    map.insert("san marino".to_string(), Tz::Europe__San_Marino);
-    map.insert("sarajevo".to_string(), Tz::Europe__Sarajevo);
-    map.insert("saratov".to_string(), Tz::Europe__Saratov);
-    map.insert("simferopol".to_string(), Tz::Europe__Simferopol);
-    map.insert("skopje".to_string(), Tz::Europe__Skopje);
-    map.insert("sofia".to_string(), Tz::Europe__Sofia);
-    map.insert("stockholm".to_string(), Tz::Europe__Stockholm);
-    map.insert("tallinn".to_string(), Tz::Europe__Tallinn);
-    map.insert("tirane".to_string(), Tz::Europe__Tirane);
-    map.insert("tiraspol".to_string(), Tz::Europe__Tiraspol);
-    map.insert("ulyanovsk".to_string(), Tz::Europe__Ulyanovsk);
-    map.insert("uzhgorod".to_string(), Tz::Europe__Uzhgorod);
-    map.insert("vaduz".to_string(), Tz::Europe__Vaduz);
-    map.insert("vatican".to_string(), Tz::Europe__Vatican);
-    map.insert("vienna".to_string(), Tz::Europe__Vienna);
-    map.insert("vilnius".to_string(), Tz::Europe__Vilnius);
-    map.insert("volgograd".to_string(), Tz::Europe__Volgograd);
-    map.insert("warsaw".to_string(), Tz::Europe__Warsaw);
-    map.insert("zagreb".to_string(), Tz::Europe__Zagreb);
-    map.insert("zaporozhye".to_string(), Tz::Europe__Zaporozhye);
-    map.insert("zurich".to_string(), Tz::Europe__Zurich);
-    map.insert("gb".to_string(), Tz::GB);
-    map.insert("gbeire".to_string(), Tz::GBEire);
-    map.insert("gmt".to_string(), Tz::GMT);
-    map.insert("gmtplus0".to_string(), Tz::GMTPlus0);
-    map.insert("gmtminus0".to_string(), Tz::GMTMinus0);
-    map.insert("gmt0".to_string(), Tz::GMT0);
-    map.insert("greenwich".to_string(), Tz::Greenwich);
-    map.insert("hst".to_string(), Tz::HST);
-    map.insert("hongkong".to_string(), Tz::Hongkong);
-    map.insert("iceland".to_string(), Tz::Iceland);
-    map.insert("antananarivo".to_string(), Tz::Indian__Antananarivo);
-    map.insert("chagos".to_string(), Tz::Indian__Chagos);
-    map.insert("christmas".to_string(), Tz::Indian__Christmas);
-    map.insert("cocos".to_string(), Tz::Indian__Cocos);
-    map.insert("comoro".to_string(), Tz::Indian__Comoro);
-    map.insert("kerguelen".to_string(), Tz::Indian__Kerguelen);
-    map.insert("mahe".to_string(), Tz::Indian__Mahe);
-    map.insert("maldives".to_string(), Tz::Indian__Maldives);
-    map.insert("mauritius".to_string(), Tz::Indian__Mauritius);
-    map.insert("mayotte".to_string(), Tz::Indian__Mayotte);
-    map.insert("reunion".to_string(), Tz::Indian__Reunion);
-    map.insert("iran".to_string(), Tz::Iran);
-    map.insert("israel".to_string(), Tz::Israel);
-    map.insert("jamaica".to_string(), Tz::Jamaica);
-    map.insert("japan".to_string(), Tz::Japan);
-    map.insert("kwajalein".to_string(), Tz::Kwajalein);
-    map.insert("libya".to_string(), Tz::Libya);
-    map.insert("met".to_string(), Tz::MET);
-    map.insert("mst".to_string(), Tz::MST);
-    map.insert("mst7mdt".to_string(), Tz::MST7MDT);
-    map.insert("bajanorte".to_string(), Tz::Mexico__BajaNorte);
-    map.insert("bajasur".to_string(), Tz::Mexico__BajaSur);
-    map.insert("general".to_string(), Tz::Mexico__General);
-    map.insert("nz".to_string(), Tz::NZ);
-    map.insert("nzchat".to_string(), Tz::NZCHAT);
-    map.insert("navajo".to_string(), Tz::Navajo);
-    map.insert("prc".to_string(), Tz::PRC);
-    map.insert("pst8pdt".to_string(), Tz::PST8PDT);
-    map.insert("apia".to_string(), Tz::Pacific__Apia);
-    map.insert("auckland".to_string(), Tz::Pacific__Auckland);
-    map.insert("bougainville".to_string(), Tz::Pacific__Bougainville);
-    map.insert("chatham".to_string(), Tz::Pacific__Chatham);
-    map.insert("chuuk".to_string(), Tz::Pacific__Chuuk);
-    map.insert("easter".to_string(), Tz::Pacific__Easter);
-    map.insert("efate".to_string(), Tz::Pacific__Efate);
-    map.insert("enderbury".to_string(), Tz::Pacific__Enderbury);
-    map.insert("fakaofo".to_string(), Tz::Pacific__Fakaofo);
-    map.insert("fiji".to_string(), Tz::Pacific__Fiji);
-    map.insert("funafuti".to_string(), Tz::Pacific__Funafuti);
-    map.insert("galapagos".to_string(), Tz::Pacific__Galapagos);
-    map.insert("gambier".to_string(), Tz::Pacific__Gambier);
-    map.insert("guadalcanal".to_string(), Tz::Pacific__Guadalcanal);
-    map.insert("guam".to_string(), Tz::Pacific__Guam);
-    map.insert("honolulu".to_string(), Tz::Pacific__Honolulu);
-    map.insert("johnston".to_string(), Tz::Pacific__Johnston);
-    map.insert("kanton".to_string(), Tz::Pacific__Kanton);
-    map.insert("kiritimati".to_string(), Tz::Pacific__Kiritimati);
-    map.insert("kosrae".to_string(), Tz::Pacific__Kosrae);
-    map.insert("kwajalein".to_string(), Tz::Pacific__Kwajalein);
-    map.insert("majuro".to_string(), Tz::Pacific__Majuro);
-    map.insert("marquesas".to_string(), Tz::Pacific__Marquesas);
-    map.insert("midway".to_string(), Tz::Pacific__Midway);
-    map.insert("nauru".to_string(), Tz::Pacific__Nauru);
-    map.insert("niue".to_string(), Tz::Pacific__Niue);
-    map.insert("norfolk".to_string(), Tz::Pacific__Norfolk);
-    map.insert("noumea".to_string(), Tz::Pacific__Noumea);
-    map.insert("pago pago".to_string(), Tz::Pacific__Pago_Pago);
-    map.insert("palau".to_string(), Tz::Pacific__Palau);
-    map.insert("pitcairn".to_string(), Tz::Pacific__Pitcairn);
-    map.insert("pohnpei".to_string(), Tz::Pacific__Pohnpei);
-    map.insert("ponape".to_string(), Tz::Pacific__Ponape);
-    map.insert("port moresby".to_string(), Tz::Pacific__Port_Moresby);
-    map.insert("rarotonga".to_string(), Tz::Pacific__Rarotonga);
-    map.insert("saipan".to_string(), Tz::Pacific__Saipan);
-    map.insert("samoa".to_string(), Tz::Pacific__Samoa);
-    map.insert("tahiti".to_string(), Tz::Pacific__Tahiti);
-    map.insert("tarawa".to_string(), Tz::Pacific__Tarawa);
-    map.insert("tongatapu".to_string(), Tz::Pacific__Tongatapu);
-    map.insert("truk".to_string(), Tz::Pacific__Truk);
-    map.insert("wake".to_string(), Tz::Pacific__Wake);
-    map.insert("wallis".to_string(), Tz::Pacific__Wallis);
-    map.insert("yap".to_string(), Tz::Pacific__Yap);
-    map.insert("poland".to_string(), Tz::Poland);
-    map.insert("portugal".to_string(), Tz::Portugal);
-    map.insert("roc".to_string(), Tz::ROC);
-    map.insert("rok".to_string(), Tz::ROK);
-    map.insert("singapore".to_string(), Tz::Singapore);
-    map.insert("turkey".to_string(), Tz::Turkey);
-    map.insert("uct".to_string(), Tz::UCT);
-    map.insert("alaska".to_string(), Tz::US__Alaska);
-    map.insert("aleutian".to_string(), Tz::US__Aleutian);
-    map.insert("arizona".to_string(), Tz::US__Arizona);
-    map.insert("central".to_string(), Tz::US__Central);
-    map.insert("eastindiana".to_string(), Tz::US__EastIndiana);
-    map.insert("eastern".to_string(), Tz::US__Eastern);
-    map.insert("hawaii".to_string(), Tz::US__Hawaii);
-    map.insert("indianastarke".to_string(), Tz::US__IndianaStarke);
-    map.insert("michigan".to_string(), Tz::US__Michigan);
-    map.insert("mountain".to_string(), Tz::US__Mountain);
-    map.insert("pacific".to_string(), Tz::US__Pacific);
-    map.insert("samoa".to_string(), Tz::US__Samoa);
-    map.insert("utc".to_string(), Tz::UTC);
-    map.insert("universal".to_string(), Tz::Universal);
-    map.insert("wsu".to_string(), Tz::WSU);
-    map.insert("wet".to_string(), Tz::WET);
-    map.insert("zulu".to_string(), Tz::Zulu);
+    for (_, cities) in TIME_ZONE_REGIONS {
+        for (city, time_zone) in cities.iter() {
+            map.insert(city.to_string(), *time_zone);
+        }
+    }
 
     map
 }
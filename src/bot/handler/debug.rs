@@ -0,0 +1,97 @@
+use chrono::Utc;
+use teloxide::{
+    prelude::*,
+    types::{InputFile, Message},
+};
+
+use crate::bot::{
+    handler::utils::{
+        assert_handle_request_limit, send_bot_document, send_bot_message, HandlerResult,
+    },
+    processor::retrieve_balances_dump,
+    redis::Redis,
+};
+
+/* Utilities */
+// Comma-separated Telegram user IDs of the maintainers who can debug any chat
+const DEBUG_USER_IDS_VAR: &str = "DEBUG_USER_IDS";
+
+// Checks if a user is a maintainer, as configured by DEBUG_USER_IDS
+// Debugging is turned off entirely when it is not set
+fn is_debug_user(user_id: &str) -> bool {
+    match std::env::var(DEBUG_USER_IDS_VAR) {
+        Ok(user_ids) => user_ids.split(',').any(|id| id.trim() == user_id),
+        Err(_) => false,
+    }
+}
+
+/* Dumps the raw balances of a group chat, and the debts optimized from them, as a text file.
+ * Helps to diagnose balances that look wrong, without going into the database.
+ * Only maintainers configured by DEBUG_USER_IDS can use it, and it is silent to everyone else.
+ * Dumps the current chat, or any chat given by ID, such as from a direct message.
+ */
+pub async fn action_debug_balances(
+    redis: Redis,
+    bot: Bot,
+    msg: Message,
+    args: String,
+) -> HandlerResult {
+    let user_id = match msg.from() {
+        Some(user) => user.id.to_string(),
+        None => return Ok(()),
+    };
+    if !is_debug_user(&user_id) {
+        return Ok(());
+    }
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let args = args.trim();
+    let chat_id = if args.is_empty() {
+        msg.chat.id.to_string()
+    } else {
+        args.to_string()
+    };
+
+    let dump = match retrieve_balances_dump(&redis, &chat_id).await {
+        Ok(dump) => dump,
+        Err(err) => {
+            send_bot_message(
+                &bot,
+                &msg,
+                format!("⁉️ I couldn't dump the balances of chat {chat_id}: {err}"),
+            )
+            .await?;
+
+            // Logging
+            log::error!(
+                "Debug Balances - Failed to dump balances of chat {} for user {}: {}",
+                chat_id,
+                user_id,
+                err.to_string()
+            );
+            return Ok(());
+        }
+    };
+
+    let file_name = format!(
+        "balances_{}_{}.txt",
+        chat_id,
+        Utc::now().format("%Y-%m-%d_%H%M%S")
+    );
+    let document = InputFile::memory(dump.into_bytes()).file_name(file_name);
+    let caption = format!("🛠️ Raw balances and optimized debts of chat {chat_id}");
+    send_bot_document(&bot, &msg, document)
+        .caption(caption)
+        .await?;
+
+    // Logging
+    log::info!(
+        "Debug Balances - Balances of chat {} dumped for user {}",
+        chat_id,
+        user_id
+    );
+
+    Ok(())
+}
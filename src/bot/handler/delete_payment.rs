@@ -8,23 +8,26 @@ use crate::bot::{
     handler::{
         constants::{COMMAND_CANCEL, COMMAND_VIEW_PAYMENTS},
         utils::{
-            display_balance_header, display_balances, display_payment, make_keyboard,
-            send_bot_message, HandlerResult, UserDialogue,
+            display_balance_header, display_balances, display_payment, edit_bot_message,
+            make_keyboard, retrieve_language, send_bot_message, t, HandlerResult, UserDialogue,
         },
         Payment,
     },
-    processor::delete_payment,
+    i18n::{
+        translate, DELETED_RESTORE_HINT, DELETE_PAYMENTS_PROMPT, DELETE_PAYMENT_BLOCKED,
+        DELETE_PAYMENT_CANCELLED, DELETE_PAYMENT_FAILED, DELETE_PAYMENT_NO_VIEW,
+        DELETE_PAYMENT_PROMPT, DELETE_PAYMENT_REPEATED, PAYMENTS_DELETED, PAYMENTS_DELETED_SUMMARY,
+        PAYMENT_DELETED, PAYMENT_DELETED_SUMMARY,
+    },
+    processor::{delete_payments, TRASH_RETENTION_DAYS},
+    redis::Redis,
 };
 
 use super::utils::{
-    assert_handle_request_limit, delete_bot_messages, is_erase_messages, retrieve_time_zone,
+    assert_handle_request_limit, cleanup_messages, retrieve_time_zone, send_completion_messages,
+    MessageTracker,
 };
 
-/* Utilities */
-
-const CANCEL_MESSAGE: &str =
-    "Okay! I've cancelled deleting the payment. No changes have been made! 🌟";
-
 /* Action handler functions */
 
 // Controls the state for misc handler actions that return to same state.
@@ -34,17 +37,33 @@ async fn repeat_state(
     new_message: MessageId,
 ) -> HandlerResult {
     match state {
+        State::SelectPaymentsDelete {
+            mut messages,
+            payments,
+            page,
+            selected,
+        } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SelectPaymentsDelete {
+                    messages,
+                    payments,
+                    page,
+                    selected,
+                })
+                .await?;
+        }
         State::DeletePayment {
             mut messages,
-            payment,
+            selected_payments,
             payments,
             page,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::DeletePayment {
                     messages,
-                    payment,
+                    selected_payments,
                     payments,
                     page,
                 })
@@ -58,16 +77,15 @@ async fn repeat_state(
 
 // Controls the dialogue for ending a delete payment operation.
 async fn complete_delete_payment(
+    redis: &Redis,
     bot: &Bot,
     dialogue: UserDialogue,
     chat_id: &str,
-    messages: Vec<MessageId>,
+    messages: MessageTracker,
     payments: Vec<Payment>,
     page: usize,
 ) -> HandlerResult {
-    if is_erase_messages(chat_id) {
-        delete_bot_messages(&bot, chat_id, messages).await?;
-    }
+    cleanup_messages(redis, bot, chat_id, messages).await?;
     dialogue
         .update(State::ViewPayments { payments, page })
         .await?;
@@ -78,16 +96,20 @@ async fn complete_delete_payment(
  * Does nothing, simply notifies the user.
  */
 pub async fn handle_repeated_delete_payment(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    let new_message = send_bot_message(
-        &bot,
-        &msg,
-        format!("🚫 Oops! It seems like you're already in the middle of deleting a payment! Please finish or {COMMAND_CANCEL} this before starting another one with me."),
-        ).await?.id;
+    let reply = t(
+        &redis,
+        &msg.chat.id.to_string(),
+        DELETE_PAYMENT_REPEATED,
+        &[("cancel", COMMAND_CANCEL)],
+    )
+    .await;
+    let new_message = send_bot_message(&bot, &msg, reply).await?.id;
 
     repeat_state(dialogue, state, new_message).await?;
     Ok(())
@@ -97,27 +119,36 @@ pub async fn handle_repeated_delete_payment(
  * Can be called at any step of the process.
  */
 pub async fn cancel_delete_payment(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    send_bot_message(&bot, &msg, CANCEL_MESSAGE.to_string()).await?;
+    let reply = t(
+        &redis,
+        &msg.chat.id.to_string(),
+        DELETE_PAYMENT_CANCELLED,
+        &[],
+    )
+    .await;
+    send_bot_message(&bot, &msg, reply).await?;
 
     match state {
-        State::SelectPayment {
+        State::SelectPaymentsDelete {
             messages,
             payments,
             page,
-            function: _,
+            selected: _,
         }
         | State::DeletePayment {
             messages,
-            payment: _,
+            selected_payments: _,
             payments,
             page,
         } => {
             complete_delete_payment(
+                &redis,
                 &bot,
                 dialogue,
                 &msg.chat.id.to_string(),
@@ -137,16 +168,20 @@ pub async fn cancel_delete_payment(
  * Called when user attempts to start another operation in the middle of deleting a payment.
  */
 pub async fn block_delete_payment(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    let new_message = send_bot_message(
-        &bot,
-        &msg,
-        format!("🚫 Oops! It seems like you're in the middle of deleting a payment! Please finish or {COMMAND_CANCEL} this before starting something new with me."),
-        ).await?.id;
+    let reply = t(
+        &redis,
+        &msg.chat.id.to_string(),
+        DELETE_PAYMENT_BLOCKED,
+        &[("cancel", COMMAND_CANCEL)],
+    )
+    .await;
+    let new_message = send_bot_message(&bot, &msg, reply).await?.id;
 
     repeat_state(dialogue, state, new_message).await?;
     Ok(())
@@ -155,43 +190,75 @@ pub async fn block_delete_payment(
 /* Blocks user command.
  * Called when user attempts to delete payment without first viewing anything.
  */
-pub async fn no_delete_payment(bot: Bot, msg: Message) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+pub async fn no_delete_payment(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
-    send_bot_message(
-        &bot,
-        &msg,
-        format!("Uh-oh! ❌ Sorry, please {COMMAND_VIEW_PAYMENTS} before deleting them!"),
+    let reply = t(
+        &redis,
+        &msg.chat.id.to_string(),
+        DELETE_PAYMENT_NO_VIEW,
+        &[("view", COMMAND_VIEW_PAYMENTS)],
     )
-    .await?;
+    .await;
+    send_bot_message(&bot, &msg, reply).await?;
     Ok(())
 }
 
-/* Deletes a specified payment.
+// Displays the payments selected to be deleted, numbered as in the list of payments
+async fn display_selected_payments(
+    redis: &Redis,
+    payments: &[Payment],
+    indices: &Vec<usize>,
+    chat_id: &str,
+) -> String {
+    let time_zone = retrieve_time_zone(redis, chat_id).await;
+    let mut formatted_payments: Vec<String> = Vec::new();
+    for index in indices {
+        formatted_payments
+            .push(display_payment(redis, &payments[*index], index + 1, time_zone).await);
+    }
+    formatted_payments.join("")
+}
+
+/* Deletes the specified payments.
  * Bot will ask user for confirmation (or cancellation),
  * before confirming the changes and updating the balances.
  */
 pub async fn action_delete_payment(
+    redis: &Redis,
     bot: Bot,
     dialogue: UserDialogue,
     msg: &Message,
     msg_id: MessageId,
-    (messages, payments, page): (Vec<MessageId>, Vec<Payment>, usize),
-    index: usize,
+    (messages, payments, page): (MessageTracker, Vec<Payment>, usize),
+    indices: Vec<usize>,
 ) -> HandlerResult {
-    let payment = payments[index].clone();
-    let keyboard = make_keyboard(vec!["Cancel", "Confirm"], Some(2));
+    let selected_payments: Vec<Payment> = indices
+        .iter()
+        .map(|index| payments[*index].clone())
+        .collect();
     let chat_id = msg.chat.id.to_string();
-    let time_zone = retrieve_time_zone(&chat_id);
+    let language = retrieve_language(redis, &chat_id).await;
+    let keyboard = make_keyboard(vec!["Cancel", "Confirm"], Some(2), &language);
+    let prompt = if selected_payments.len() > 1 {
+        translate(
+            &language,
+            DELETE_PAYMENTS_PROMPT,
+            &[("count", &selected_payments.len().to_string())],
+        )
+    } else {
+        translate(&language, DELETE_PAYMENT_PROMPT, &[])
+    };
 
-    bot.edit_message_text(
-        chat_id,
+    edit_bot_message(
+        &bot,
+        chat_id.clone(),
         msg_id,
         format!(
-            "Do you really, really, want to 🗑 delete this payment? I won't be able to undo this... 🫢\n\n{}",
-            display_payment(&payment, index + 1, time_zone)
+            "{prompt}\n\n{}",
+            display_selected_payments(redis, &payments, &indices, &chat_id).await
         ),
     )
     .reply_markup(keyboard)
@@ -199,7 +266,7 @@ pub async fn action_delete_payment(
     dialogue
         .update(State::DeletePayment {
             messages,
-            payment,
+            selected_payments,
             payments,
             page,
         })
@@ -207,14 +274,21 @@ pub async fn action_delete_payment(
     Ok(())
 }
 
-/* Deletes a specified payment.
+/* Deletes the specified payments.
  * Bot receives a callback query from the user, and will either confirm or cancel the deletion.
+ * All payments are deleted together, with the balances updated once.
  */
 pub async fn action_delete_payment_confirm(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
-    (messages, payment, payments, page): (Vec<MessageId>, Payment, Vec<Payment>, usize),
+    (mut messages, selected_payments, payments, page): (
+        MessageTracker,
+        Vec<Payment>,
+        Vec<Payment>,
+        usize,
+    ),
     query: CallbackQuery,
 ) -> HandlerResult {
     if let Some(button) = &query.data {
@@ -222,46 +296,79 @@ pub async fn action_delete_payment_confirm(
 
         if let Some(msg) = query.message {
             let chat_id = msg.chat.id.to_string();
-            let time_zone = retrieve_time_zone(&chat_id);
+            let time_zone = retrieve_time_zone(&redis, &chat_id).await;
             match button.as_str() {
                 "Cancel" => {
-                    cancel_delete_payment(bot, dialogue, state, msg).await?;
+                    cancel_delete_payment(redis.clone(), bot, dialogue, state, msg).await?;
                 }
                 "Confirm" => {
-                    let payment_id = &payment.payment_id;
-                    let deletion = delete_payment(&chat_id, payment_id).await;
+                    let payment_ids: Vec<String> = selected_payments
+                        .iter()
+                        .map(|payment| payment.payment_id.clone())
+                        .collect();
+                    let mut deleted_payments = String::new();
+                    for (index, payment) in selected_payments.iter().enumerate() {
+                        deleted_payments.push_str(
+                            &display_payment(&redis, payment, index + 1, time_zone).await,
+                        );
+                    }
+                    let sender_username = query.from.username.clone().unwrap_or_default();
+                    let deletion =
+                        delete_payments(&redis, &chat_id, &sender_username, &payment_ids).await;
 
                     match deletion {
                         Ok(balances) => {
-                            send_bot_message(
-                                &bot,
-                                &msg,
-                                format!(
-                                    "🎉 Yay! Payment deleted! 🎉\n\n{}",
-                                    display_payment(&payment, 1, time_zone)
-                                ),
-                            )
-                            .await?;
-                            send_bot_message(
+                            let language = retrieve_language(&redis, &chat_id).await;
+                            let count = selected_payments.len().to_string();
+                            let (header, summary) = if selected_payments.len() > 1 {
+                                (
+                                    translate(&language, PAYMENTS_DELETED, &[]),
+                                    translate(
+                                        &language,
+                                        PAYMENTS_DELETED_SUMMARY,
+                                        &[("count", &count)],
+                                    ),
+                                )
+                            } else {
+                                (
+                                    translate(&language, PAYMENT_DELETED, &[]),
+                                    translate(&language, PAYMENT_DELETED_SUMMARY, &[]),
+                                )
+                            };
+                            let hint = translate(
+                                &language,
+                                DELETED_RESTORE_HINT,
+                                &[("days", &TRASH_RETENTION_DAYS.to_string())],
+                            );
+                            let mut details =
+                                vec![format!("{header}\n\n{deleted_payments}\n{hint}")];
+                            for (currency, debts) in balances {
+                                details.push(format!(
+                                    "{}{}",
+                                    display_balance_header(&redis, &chat_id, &currency).await,
+                                    display_balances(&redis, &chat_id, &debts).await,
+                                ));
+                            }
+                            send_completion_messages(
+                                &redis,
                                 &bot,
                                 &msg,
-                                format!(
-                                    "{}{}",
-                                    display_balance_header(&chat_id, &payment.currency.0),
-                                    display_balances(&balances),
-                                ),
+                                Some(&mut messages),
+                                summary,
+                                details,
                             )
                             .await?;
 
                             // Logging
                             log::info!(
-                                "Delete Payment Submission - payment deleted for chat {} with payment {}",
+                                "Delete Payment Submission - {} payments deleted for chat {} with payments {}",
+                                payment_ids.len(),
                                 chat_id,
-                                display_payment(&payment, 1, time_zone)
+                                deleted_payments
                                 );
 
                             complete_delete_payment(
-                                &bot, dialogue, &chat_id, messages, payments, page,
+                                &redis, &bot, dialogue, &chat_id, messages, payments, page,
                             )
                             .await?;
                         }
@@ -269,20 +376,20 @@ pub async fn action_delete_payment_confirm(
                             send_bot_message(
                                 &bot,
                                 &msg,
-                                format!("⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't delete the payment right now. Please try again later!\n\n" ),
-                                )
-                                .await?;
+                                t(&redis, &chat_id, DELETE_PAYMENT_FAILED, &[]).await,
+                            )
+                            .await?;
 
                             complete_delete_payment(
-                                &bot, dialogue, &chat_id, messages, payments, page,
+                                &redis, &bot, dialogue, &chat_id, messages, payments, page,
                             )
                             .await?;
 
                             // Logging
                             log::error!(
-                                "Delete Payment Submission - Processor failed to delete payment for chat {} with payment {}: {}",
+                                "Delete Payment Submission - Processor failed to delete payments for chat {} with payments {}: {}",
                                 chat_id,
-                                display_payment(&payment, 1, time_zone),
+                                deleted_payments,
                                 err.to_string()
                                 );
                         }
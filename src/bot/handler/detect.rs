@@ -0,0 +1,394 @@
+use teloxide::{
+    payloads::AnswerCallbackQuerySetters,
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message},
+};
+
+use crate::bot::{
+    currency::{get_default_currency, normalize_amount_text, Currency},
+    detect::{detect_payment, DetectedPayment},
+    handler::{
+        approval::{
+            display_approval_request, display_dual_entry_request, get_approvers,
+            make_keyboard_approval, make_keyboard_dual_entry,
+        },
+        limit::notify_spending_limits,
+        utils::{
+            assert_handle_request_limit, display_balance_header, display_balances,
+            display_currency_amount, display_usernames, edit_bot_message, get_currency,
+            parse_amount, parse_currency, parse_username, process_debts_equal,
+            retrieve_allowed_currencies, retrieve_currency_format, retrieve_language,
+            retrieve_number_format, retrieve_rounding, retrieve_verbosity, send_bot_message, t,
+            use_currency, validate_description, validate_payment_count, BotError, HandlerResult,
+            Verbosity,
+        },
+    },
+    i18n::{
+        translate, Text, ADD_PAYMENT_FAILED, APPROVAL_NEEDED, BUTTON_DISMISS, BUTTON_RECORD,
+        DETECT_DISMISSED, DETECT_NOT_ALLOWED, DETECT_RECORDED, DETECT_RESOLVED, DETECT_SUGGESTION,
+        DUAL_ENTRY_NEEDED, INVALID_NUMBER,
+    },
+    processor::{
+        add_pending_payment, approve_pending_payment, discard_pending_payment, get_chat_setting,
+        is_approval_required, is_dual_entry_required, retrieve_pending_payment, ChatSetting,
+    },
+    redis::Redis,
+};
+
+/* Utilities */
+const RECORD_PREFIX: &str = "Record:";
+const DISMISS_PREFIX: &str = "Dismiss:";
+
+// Make keyboard to record or dismiss a detected payment, with its pending ID in the callback data
+fn make_keyboard_detected(payment_id: &str, language: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback(
+            translate(language, BUTTON_RECORD, &[]),
+            format!("{RECORD_PREFIX}{payment_id}"),
+        ),
+        InlineKeyboardButton::callback(
+            translate(language, BUTTON_DISMISS, &[]),
+            format!("{DISMISS_PREFIX}{payment_id}"),
+        ),
+    ]])
+}
+
+// Reads the total and currency of a detected payment, in the formats of the chat
+async fn read_detected_total(
+    redis: &Redis,
+    chat_id: &str,
+    detected: &DetectedPayment,
+) -> Result<(i64, Currency), BotError> {
+    let currency = match &detected.currency {
+        Some(currency) => parse_currency(
+            &currency.0,
+            &retrieve_allowed_currencies(redis, chat_id).await,
+        )?,
+        None => get_default_currency(),
+    };
+    let number_format = retrieve_number_format(redis, chat_id).await;
+    let amount = match normalize_amount_text(&detected.amount, number_format) {
+        Some(amount) => amount,
+        None => return Err(BotError::UserError(Text::new(INVALID_NUMBER))),
+    };
+    let total = parse_amount(&amount, &currency)?;
+    Ok((total, currency))
+}
+
+// Checks if a message in a group mentions a payment, while the chat has passive mode on.
+// Messages are recognised before the setting is checked, so that most messages need no lookup.
+pub async fn is_detected_payment(redis: Redis, msg: Message) -> bool {
+    if msg.chat.is_private() || msg.from().and_then(|user| user.username.as_ref()).is_none() {
+        return false;
+    }
+    if msg.text().and_then(detect_payment).is_none() {
+        return false;
+    }
+
+    matches!(
+        get_chat_setting(
+            &redis,
+            &msg.chat.id.to_string(),
+            ChatSetting::PassiveMode(None)
+        )
+        .await,
+        Ok(ChatSetting::PassiveMode(Some(true)))
+    )
+}
+
+// Checks if a callback query is a response to a detected payment.
+// Such queries can come from anyone in the chat, regardless of the current state.
+pub fn is_detected_payment_query(query: CallbackQuery) -> bool {
+    match &query.data {
+        Some(data) => data.starts_with(RECORD_PREFIX) || data.starts_with(DISMISS_PREFIX),
+        None => false,
+    }
+}
+
+/* Offers to record a payment mentioned in a plain message, when the chat has passive mode on.
+ * The payment is paid by the sender, and split equally with everyone mentioned.
+ * It is kept as a pending payment, until the sender records or dismisses it.
+ * Messages that cannot be read as a valid payment are ignored silently.
+ */
+pub async fn action_detect_payment(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let (user, detected) = match (msg.from(), msg.text().and_then(detect_payment)) {
+        (Some(user), Some(detected)) => (user, detected),
+        _ => return Ok(()),
+    };
+    let chat_id = msg.chat.id.to_string();
+    let sender_id = user.id.to_string();
+    let sender_username = match user.username.as_deref().map(parse_username) {
+        Some(Ok(username)) => username,
+        _ => return Ok(()),
+    };
+
+    let payment = async {
+        validate_payment_count(&redis, &chat_id).await?;
+        let description = validate_description(&detected.description)?;
+        let (total, currency) = read_detected_total(&redis, &chat_id, &detected).await?;
+        let users = format!("{} {}", sender_username, detected.users.join(" "));
+        let debts = process_debts_equal(
+            &users,
+            Some(total),
+            &Some(sender_username.clone()),
+            retrieve_rounding(&redis, &chat_id).await,
+        )?;
+        Ok::<_, BotError>((description, total, currency, debts))
+    }
+    .await;
+    let (description, total, currency, debts) = match payment {
+        Ok(payment) => payment,
+        Err(err) => {
+            // Logging
+            log::info!(
+                "Detect Payment - Ignored payment mentioned by user {} in chat {}: {}",
+                sender_id,
+                chat_id,
+                err.to_string()
+            );
+            return Ok(());
+        }
+    };
+
+    let payment_id = add_pending_payment(
+        &redis,
+        chat_id.clone(),
+        sender_username.clone(),
+        sender_id.clone(),
+        msg.date.to_string(),
+        &description,
+        &sender_username,
+        &currency.0,
+        total,
+        debts.clone(),
+        false,
+        false,
+        None,
+    )
+    .await?;
+
+    let users: Vec<String> = debts.iter().map(|(user, _)| user.clone()).collect();
+    let language = retrieve_language(&redis, &chat_id).await;
+    let suggestion = translate(
+        &language,
+        DETECT_SUGGESTION,
+        &[
+            ("description", &description),
+            (
+                "amount",
+                &display_currency_amount(
+                    total,
+                    use_currency(&redis, currency, &chat_id).await,
+                    retrieve_currency_format(&redis, &chat_id).await,
+                ),
+            ),
+            (
+                "payer",
+                &display_usernames(&redis, &chat_id, &[sender_username])
+                    .await
+                    .join(""),
+            ),
+            (
+                "users",
+                &display_usernames(&redis, &chat_id, &users).await.join(", "),
+            ),
+        ],
+    );
+    send_bot_message(&bot, &msg, suggestion)
+        .reply_markup(make_keyboard_detected(&payment_id, &language))
+        .await?;
+
+    // Logging
+    log::info!(
+        "Detect Payment - Payment {} detected from user {} in chat {}",
+        payment_id,
+        sender_id,
+        chat_id
+    );
+
+    Ok(())
+}
+
+/* Records or dismisses a payment detected in a plain message.
+ * Bot receives a callback query from the suggestion to record the payment.
+ * Only the sender of the message can respond. With dual entry verification, payments are sent
+ * for confirmation by both sides, and payments above the approval threshold are sent for approval,
+ * instead of being recorded directly.
+ */
+pub async fn action_resolve_detected_payment(
+    redis: Redis,
+    bot: Bot,
+    query: CallbackQuery,
+) -> HandlerResult {
+    let (is_recorded, payment_id) = match &query.data {
+        Some(data) => match data.strip_prefix(RECORD_PREFIX) {
+            Some(payment_id) => (true, payment_id.to_string()),
+            None => match data.strip_prefix(DISMISS_PREFIX) {
+                Some(payment_id) => (false, payment_id.to_string()),
+                None => return Ok(()),
+            },
+        },
+        None => return Ok(()),
+    };
+
+    let msg = match query.message {
+        Some(msg) => msg,
+        None => return Ok(()),
+    };
+
+    let (pending, payment) = match retrieve_pending_payment(&redis, &payment_id).await? {
+        Some(entry) => entry,
+        None => {
+            bot.answer_callback_query(query.id)
+                .text(t(&redis, &msg.chat.id.to_string(), DETECT_RESOLVED, &[]).await)
+                .await?;
+            bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+            return Ok(());
+        }
+    };
+    if query.from.id.to_string() != pending.sender_id {
+        bot.answer_callback_query(query.id)
+            .text(t(&redis, &pending.chat_id, DETECT_NOT_ALLOWED, &[]).await)
+            .await?;
+        return Ok(());
+    }
+    bot.answer_callback_query(query.id).await?;
+
+    let suggestion = msg.text().unwrap_or_default().to_string();
+    let language = retrieve_language(&redis, &pending.chat_id).await;
+    if !is_recorded {
+        if discard_pending_payment(&redis, &payment_id).await? {
+            edit_bot_message(
+                &bot,
+                msg.chat.id,
+                msg.id,
+                format!(
+                    "{suggestion}\n\n{}",
+                    translate(&language, DETECT_DISMISSED, &[])
+                ),
+            )
+            .await?;
+
+            // Logging
+            log::info!(
+                "Detect Payment - Payment {} dismissed by user {} in chat {}",
+                payment_id,
+                pending.sender_id,
+                pending.chat_id
+            );
+        } else {
+            bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+        }
+        return Ok(());
+    }
+
+    // With dual entry verification, payments still need confirmation from the payer and a debtor.
+    // Otherwise, payments above the threshold still need someone else to approve them.
+    let is_dual_entry =
+        is_dual_entry_required(&redis, &pending.chat_id, &payment.creditor, &payment.debts)
+            .await
+            .unwrap_or(false);
+    let approvers = get_approvers(&payment.creditor, &payment.debts, &pending.sender_username);
+    let currency = get_currency(&payment.currency).unwrap_or(get_default_currency());
+    if is_dual_entry
+        || !approvers.is_empty()
+            && is_approval_required(&redis, &pending.chat_id, &currency, payment.total)
+                .await
+                .unwrap_or(false)
+    {
+        let (request, keyboard) = if is_dual_entry {
+            (
+                format!(
+                    "{suggestion}\n\n{} {}",
+                    translate(&language, DUAL_ENTRY_NEEDED, &[]),
+                    display_dual_entry_request(&payment.creditor, &payment.debts, &language)
+                ),
+                make_keyboard_dual_entry(&payment_id, &language),
+            )
+        } else {
+            (
+                format!(
+                    "{suggestion}\n\n{} {}",
+                    translate(&language, APPROVAL_NEEDED, &[]),
+                    display_approval_request(&approvers, &language)
+                ),
+                make_keyboard_approval(&payment_id, &language),
+            )
+        };
+        edit_bot_message(&bot, msg.chat.id, msg.id, request)
+            .reply_markup(keyboard)
+            .await?;
+
+        // Logging
+        log::info!(
+            "Detect Payment - Payment {} pending {} for user {} in chat {}",
+            payment_id,
+            if is_dual_entry {
+                "confirmation"
+            } else {
+                "approval"
+            },
+            pending.sender_id,
+            pending.chat_id
+        );
+        return Ok(());
+    }
+
+    match approve_pending_payment(&redis, &payment_id).await {
+        Ok(Some(balances)) => {
+            edit_bot_message(
+                &bot,
+                msg.chat.id,
+                msg.id,
+                format!(
+                    "{suggestion}\n\n{}",
+                    translate(&language, DETECT_RECORDED, &[])
+                ),
+            )
+            .await?;
+            if retrieve_verbosity(&redis, &pending.chat_id).await == Verbosity::Verbose {
+                send_bot_message(
+                    &bot,
+                    &msg,
+                    format!(
+                        "{}{}",
+                        display_balance_header(&redis, &pending.chat_id, &payment.currency).await,
+                        display_balances(&redis, &pending.chat_id, &balances).await
+                    ),
+                )
+                .await?;
+            }
+
+            // Logging
+            log::info!(
+                "Detect Payment - Payment {} recorded by user {} in chat {}: {:?}",
+                payment_id,
+                pending.sender_id,
+                pending.chat_id,
+                payment
+            );
+
+            notify_spending_limits(&redis, &bot, &msg).await?;
+        }
+        Ok(None) => {
+            bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+        }
+        Err(err) => {
+            send_bot_message(&bot, &msg, translate(&language, ADD_PAYMENT_FAILED, &[])).await?;
+
+            // Logging
+            log::error!(
+                "Detect Payment - Failed to record payment {} in chat {}: {}",
+                payment_id,
+                pending.chat_id,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
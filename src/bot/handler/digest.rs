@@ -0,0 +1,162 @@
+use chrono::{Datelike, NaiveDateTime, NaiveTime, Utc};
+use teloxide::{prelude::*, types::ChatId};
+
+use crate::bot::{
+    currency::CURRENCY_DEFAULT,
+    handler::{
+        spendings::display_spendings,
+        utils::{
+            display_balances, get_statement_options, retrieve_time_zone, retrieve_week_start, t,
+            with_retry, HandlerResult,
+        },
+    },
+    i18n::{DIGEST_SPENDINGS_NONE, DIGEST_SUMMARY, DIGEST_WEEK, IN_CURRENCY},
+    period::{get_week_key, get_week_start_date},
+    processor::{
+        get_chat_setting, retrieve_debts, retrieve_last_digest_week, retrieve_spending_data_since,
+        retrieve_valid_currencies, retrieve_weekly_digest_chats, update_last_digest_week,
+        ChatSetting, ProcessError, StatementOption,
+    },
+    redis::Redis,
+};
+
+/* Utilities */
+
+// Composes the weekly digest message for a chat
+async fn display_weekly_digest(
+    redis: &Redis,
+    chat_id: &str,
+    since: NaiveDateTime,
+    week: &str,
+) -> Result<String, ProcessError> {
+    let currencies = retrieve_valid_currencies(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    let options = get_statement_options(redis, chat_id, currencies).await;
+    let multiple = options.len() > 1;
+
+    let mut balances = String::new();
+    for option in options {
+        let debts = retrieve_debts(redis, chat_id, option.clone()).await?;
+        if debts.is_empty() {
+            continue;
+        }
+        if multiple {
+            if let StatementOption::Currency(currency) = &option {
+                balances.push_str(&t(redis, chat_id, IN_CURRENCY, &[("currency", currency)]).await);
+                balances.push('\n');
+            }
+        }
+        balances.push_str(&display_balances(redis, chat_id, &debts).await);
+        if multiple {
+            balances.push('\n');
+        }
+    }
+    if balances.is_empty() {
+        balances = display_balances(redis, chat_id, &Vec::new()).await;
+    }
+
+    let spending_data = retrieve_spending_data_since(redis, chat_id, Some(since)).await?;
+    let mut spendings = String::new();
+    for data in &spending_data {
+        if spending_data.len() > 1 || data.currency != CURRENCY_DEFAULT.0 {
+            spendings
+                .push_str(&t(redis, chat_id, IN_CURRENCY, &[("currency", &data.currency)]).await);
+            spendings.push('\n');
+        }
+        spendings.push_str(&display_spendings(redis, chat_id, data).await);
+        spendings.push('\n');
+    }
+    if spendings.is_empty() {
+        spendings = t(redis, chat_id, DIGEST_SPENDINGS_NONE, &[]).await;
+    }
+
+    Ok(t(
+        redis,
+        chat_id,
+        DIGEST_SUMMARY,
+        &[
+            ("week", week),
+            ("balances", balances.trim_end()),
+            ("spendings", spendings.trim_end()),
+        ],
+    )
+    .await)
+}
+
+// Posts the weekly digest for a single chat, if it is due
+async fn post_digest_chat(redis: &Redis, bot: &Bot, chat_id: &str) -> HandlerResult {
+    let time = match get_chat_setting(redis, chat_id, ChatSetting::WeeklyDigest(None)).await? {
+        ChatSetting::WeeklyDigest(Some(Some(time))) => time,
+        _ => return Ok(()),
+    };
+    let time = match NaiveTime::parse_from_str(&time, "%H:%M") {
+        Ok(time) => time,
+        Err(_) => return Ok(()),
+    };
+
+    let time_zone = retrieve_time_zone(redis, chat_id).await;
+    let week_start = retrieve_week_start(redis, chat_id).await;
+    let now = Utc::now().with_timezone(&time_zone);
+    if now.weekday() != week_start.last_day() || now.time() < time {
+        return Ok(());
+    }
+
+    let current_week = get_week_key(&now, week_start);
+    if retrieve_last_digest_week(redis, chat_id).await?.as_deref() == Some(current_week.as_str()) {
+        return Ok(());
+    }
+
+    // Counts from midnight of the first day of the week, in the time zone of the chat
+    let start_date = get_week_start_date(now.date_naive(), week_start);
+    let since = start_date
+        .and_hms_opt(0, 0, 0)
+        .and_then(|start| start.and_local_timezone(time_zone).earliest())
+        .map(|start| start.naive_utc())
+        .unwrap_or_else(|| now.naive_utc());
+    let week = t(
+        redis,
+        chat_id,
+        DIGEST_WEEK,
+        &[
+            ("start", start_date.format("%e %b").to_string().trim()),
+            ("end", now.format("%e %b %Y").to_string().trim()),
+        ],
+    )
+    .await;
+    let message = display_weekly_digest(redis, chat_id, since, &week).await?;
+
+    // Marked as posted first, so that a failed send is not retried every minute
+    update_last_digest_week(redis, chat_id, &current_week).await?;
+    if let Ok(id) = chat_id.parse::<i64>() {
+        with_retry(bot.send_message(ChatId(id), message)).await?;
+    }
+
+    // Logging
+    log::info!(
+        "Weekly Digest - Posted digest for chat {} for week {}",
+        chat_id,
+        current_week
+    );
+
+    Ok(())
+}
+
+/* Posts the weekly digest for all chats with weekly digest enabled.
+ * Called periodically by the scheduler, only acts on the last day of the week of each chat,
+ * at the time set by the chat.
+ */
+pub async fn handle_weekly_digest(redis: &Redis, bot: &Bot) -> HandlerResult {
+    let chats = retrieve_weekly_digest_chats(redis).await?;
+    for chat_id in chats {
+        if let Err(err) = post_digest_chat(redis, bot, &chat_id).await {
+            log::error!(
+                "Weekly Digest - Failed to post digest for chat {}: {}",
+                chat_id,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
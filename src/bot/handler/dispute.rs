@@ -0,0 +1,269 @@
+use teloxide::{
+    payloads::{AnswerCallbackQuerySetters, SendMessageSetters},
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message},
+};
+
+use crate::bot::{
+    dispatcher::State,
+    handler::{
+        edit_payment::action_edit_disputed_payment,
+        utils::{
+            display_username, edit_bot_message, is_user_allowed, retrieve_language,
+            send_bot_message, with_retry, HandlerResult, UserDialogue,
+        },
+        view_payments::unfold_payment,
+    },
+    i18n::{
+        translate, translate_button, DISPUTE_ALREADY_OPEN, DISPUTE_BUSY, DISPUTE_CHAT_DEFAULT,
+        DISPUTE_DISMISSED, DISPUTE_EDIT_NOT_ALLOWED, DISPUTE_NOTIFICATION, DISPUTE_NOT_ALLOWED,
+        DISPUTE_OPENED, DISPUTE_RESOLVED,
+    },
+    processor::{
+        dismiss_payment_dispute, dispute_payment, is_username_equal, retrieve_notified_users,
+        retrieve_payment_dispute, view_payments,
+    },
+    redis::Redis,
+};
+
+use super::Payment;
+
+/* Utilities */
+const DISPUTE_EDIT_PREFIX: &str = "PaymentDisputeEdit:";
+const DISPUTE_DISMISS_PREFIX: &str = "PaymentDisputeDismiss:";
+
+// Make dispute keyboard, with the payment ID in the callback data
+fn make_keyboard_dispute(payment_id: &str, language: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback(
+            translate_button(language, "✏️ Edit Payment"),
+            format!("{DISPUTE_EDIT_PREFIX}{payment_id}"),
+        ),
+        InlineKeyboardButton::callback(
+            translate_button(language, "Dismiss"),
+            format!("{DISPUTE_DISMISS_PREFIX}{payment_id}"),
+        ),
+    ]])
+}
+
+// Checks if a callback query resolves a disputed payment.
+// Such queries come from the dispute message, regardless of the current state.
+pub fn is_payment_dispute_query(query: CallbackQuery) -> bool {
+    match &query.data {
+        Some(data) => {
+            data.starts_with(DISPUTE_EDIT_PREFIX) || data.starts_with(DISPUTE_DISMISS_PREFIX)
+        }
+        None => false,
+    }
+}
+
+// Checks if a user may dispute a payment, being someone in the split other than the payer
+pub fn is_payment_disputable(payment: &Payment, username: &str) -> bool {
+    !is_username_equal(username, &payment.creditor)
+        && payment
+            .debts
+            .iter()
+            .any(|(user, _)| is_username_equal(user, username))
+}
+
+// Retrieves a payment of a chat by its ID, with its current details
+async fn retrieve_chat_payment(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+    query: &CallbackQuery,
+) -> Option<Payment> {
+    let payments = view_payments(
+        redis,
+        chat_id,
+        &query.from.id.to_string(),
+        query.from.username.as_deref(),
+    )
+    .await
+    .ok()?;
+    payments
+        .into_iter()
+        .find(|payment| payment.payment_id == payment_id)
+        .map(unfold_payment)
+}
+
+/* Opens a dispute on a payment, by one of its debtors.
+ * Bot announces the dispute in the group, mentioning the payer with a menu to edit or dismiss,
+ * and also notifies the payer in a direct message, if they have started the bot.
+ */
+pub async fn action_dispute_payment(
+    redis: &Redis,
+    bot: &Bot,
+    msg: &Message,
+    payment: &Payment,
+    username: &str,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    let language = retrieve_language(redis, &chat_id).await;
+    if !dispute_payment(redis, &chat_id, &payment.payment_id, username).await? {
+        send_bot_message(bot, msg, translate(&language, DISPUTE_ALREADY_OPEN, &[])).await?;
+        return Ok(());
+    }
+
+    // Logging
+    log::info!(
+        "Payment Dispute - Payment {} disputed by user {} in chat {}",
+        payment.payment_id,
+        username,
+        chat_id
+    );
+
+    let disputer = display_username(redis, &chat_id, username).await;
+    let payer = display_username(redis, &chat_id, &payment.creditor).await;
+    send_bot_message(
+        bot,
+        msg,
+        translate(
+            &language,
+            DISPUTE_OPENED,
+            &[
+                ("disputer", &disputer),
+                ("description", &payment.description),
+                ("payer", &payer),
+            ],
+        ),
+    )
+    .reply_markup(make_keyboard_dispute(&payment.payment_id, &language))
+    .await?;
+
+    // Also lets the payer know directly, as they may not be following the group
+    let notified_users =
+        match retrieve_notified_users(redis, std::slice::from_ref(&payment.creditor)).await {
+            Ok(notified_users) => notified_users,
+            Err(err) => {
+                log::error!(
+                    "Payment Dispute - Failed to retrieve payer to notify in chat {}: {}",
+                    chat_id,
+                    err.to_string()
+                );
+                return Ok(());
+            }
+        };
+    if let Some(user_id) = notified_users.get(&payment.creditor.to_lowercase()) {
+        let title = match msg.chat.title() {
+            Some(title) => title.to_string(),
+            None => translate(&language, DISPUTE_CHAT_DEFAULT, &[]),
+        };
+        let notification = translate(
+            &language,
+            DISPUTE_NOTIFICATION,
+            &[
+                ("disputer", &disputer),
+                ("description", &payment.description),
+                ("chat", &title),
+            ],
+        );
+        if let Err(err) = with_retry(bot.send_message(user_id.clone(), notification)).await {
+            log::warn!(
+                "Payment Dispute - Failed to notify user {} of dispute in chat {}: {}",
+                user_id,
+                chat_id,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/* Resolves a disputed payment, by its payer.
+ * Bot receives a callback query from the dispute message, and either dismisses the dispute,
+ * or starts editing the payment, after which the dispute is resolved.
+ */
+pub async fn action_resolve_payment_dispute(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    query: CallbackQuery,
+) -> HandlerResult {
+    let (is_edit, payment_id) = match &query.data {
+        Some(data) => match data.strip_prefix(DISPUTE_EDIT_PREFIX) {
+            Some(payment_id) => (true, payment_id.to_string()),
+            None => match data.strip_prefix(DISPUTE_DISMISS_PREFIX) {
+                Some(payment_id) => (false, payment_id.to_string()),
+                None => return Ok(()),
+            },
+        },
+        None => return Ok(()),
+    };
+
+    let msg = match &query.message {
+        Some(msg) => msg.clone(),
+        None => return Ok(()),
+    };
+    let chat_id = msg.chat.id.to_string();
+    let language = retrieve_language(&redis, &chat_id).await;
+
+    let payment = match retrieve_payment_dispute(&redis, &chat_id, &payment_id).await? {
+        Some(_) => retrieve_chat_payment(&redis, &chat_id, &payment_id, &query).await,
+        None => None,
+    };
+    let payment = match payment {
+        Some(payment) => payment,
+        None => {
+            bot.answer_callback_query(query.id)
+                .text(translate(&language, DISPUTE_RESOLVED, &[]))
+                .await?;
+            bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+            return Ok(());
+        }
+    };
+
+    match &query.from.username {
+        Some(username) if is_username_equal(username, &payment.creditor) => {}
+        _ => {
+            bot.answer_callback_query(query.id)
+                .text(translate(&language, DISPUTE_NOT_ALLOWED, &[]))
+                .await?;
+            return Ok(());
+        }
+    }
+
+    if !is_edit {
+        bot.answer_callback_query(query.id).await?;
+        dismiss_payment_dispute(&redis, &chat_id, &payment_id).await?;
+        edit_bot_message(
+            &bot,
+            msg.chat.id,
+            msg.id,
+            translate(
+                &language,
+                DISPUTE_DISMISSED,
+                &[("message", msg.text().unwrap_or_default())],
+            ),
+        )
+        .await?;
+
+        // Logging
+        log::info!(
+            "Payment Dispute - Dispute of payment {} dismissed by user {} in chat {}",
+            payment_id,
+            query.from.id,
+            chat_id
+        );
+        return Ok(());
+    }
+
+    if !matches!(state, State::Start) {
+        bot.answer_callback_query(query.id)
+            .text(translate(&language, DISPUTE_BUSY, &[]))
+            .await?;
+        return Ok(());
+    }
+    if !is_user_allowed(&redis, &bot, msg.chat.id, query.from.id).await? {
+        bot.answer_callback_query(query.id)
+            .text(translate(&language, DISPUTE_EDIT_NOT_ALLOWED, &[]))
+            .await?;
+        return Ok(());
+    }
+
+    bot.answer_callback_query(query.id).await?;
+    action_edit_disputed_payment(&redis, bot, dialogue, &msg, payment).await?;
+    Ok(())
+}
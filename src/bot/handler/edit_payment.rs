@@ -1,42 +1,72 @@
 use teloxide::{
     payloads::SendMessageSetters,
     prelude::*,
-    types::{Message, MessageId},
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message, MessageId},
 };
 
 use crate::bot::{
-    currency::Currency,
+    currency::{get_default_currency, Currency, CURRENCY_DEFAULT},
     dispatcher::State,
     handler::{
         constants::{
-            COMMAND_CANCEL, COMMAND_VIEW_PAYMENTS, DEBT_EQUAL_DESCRIPTION_MESSAGE,
-            DEBT_EQUAL_INSTRUCTIONS_MESSAGE, DEBT_EXACT_DESCRIPTION_MESSAGE,
-            DEBT_EXACT_INSTRUCTIONS_MESSAGE, DEBT_RATIO_DESCRIPTION_MESSAGE,
-            DEBT_RATIO_INSTRUCTIONS_MESSAGE, NO_TEXT_MESSAGE, TOTAL_INSTRUCTIONS_MESSAGE,
+            COMMAND_CANCEL, COMMAND_VIEW_PAYMENTS, MAKE_EXPENSE_BUTTON, MAKE_TRANSFER_BUTTON,
+            VERSION_CALLBACK_PREFIX,
         },
+        get_debts_instructions,
+        limit::notify_spending_limits,
         utils::{
-            display_balance_header, display_balances, display_currency_amount, display_debts,
-            display_payment, display_username, make_keyboard, make_keyboard_debt_selection,
-            parse_currency_amount, parse_username, process_debts, retrieve_time_zone,
-            send_bot_message, use_currency, HandlerResult, UserDialogue,
+            convert_decimal_places, display_balance_header, display_balances,
+            display_currency_amount, display_debts, display_description, display_payment,
+            display_payment_date, display_username, edit_bot_message, edit_tracked_message,
+            error_to_user_message, get_currency, get_picker_users, make_keyboard,
+            make_keyboard_debt_selection, make_keyboard_user_picker, parse_currency,
+            parse_payment_date, parse_total_amount, parse_username, process_debts,
+            process_debts_equal, retrieve_allowed_currencies, retrieve_chat_split_groups,
+            retrieve_currency_format, retrieve_language, retrieve_number_format, retrieve_rounding,
+            retrieve_time_zone, send_bot_message, t, t_prompt, toggle_picker_users, use_currency,
+            validate_description, HandlerResult, UserDialogue,
         },
         AddDebtsFormat, AddPaymentEdit, Payment,
     },
-    processor::edit_payment,
+    i18n::{
+        translate_button, CURRENCY_INSTRUCTIONS, DATE_INSTRUCTIONS, DEBT_ADJUSTED_INSTRUCTIONS,
+        DEBT_ADJUSTMENTS_PROMPT, DEBT_AMOUNTS_PROMPT, DEBT_EQUAL_PICKER, DEBT_EXACT_INSTRUCTIONS,
+        DEBT_FORMATS, DEBT_ITEMIZED_INSTRUCTIONS, DEBT_ITEMS_PROMPT, DEBT_PERCENTAGES_PROMPT,
+        DEBT_PERCENT_INSTRUCTIONS, DEBT_RATIO_INSTRUCTIONS, DEBT_USERS_PROMPT,
+        EDIT_CURRENCY_PROMPT, EDIT_DATE_PROMPT, EDIT_SPLIT_PROMPT, EDIT_TOTAL_PROMPT, NO_TEXT,
+        SPLIT_PROMPT, TOTAL_INSTRUCTIONS, TOTAL_QUESTION,
+    },
+    processor::{edit_payment, retrieve_payment_versions, PRIVATE_DESCRIPTION},
+    redis::{PaymentVersion, Redis},
 };
 
-use super::utils::{assert_handle_request_limit, delete_bot_messages, is_erase_messages};
+use super::utils::{
+    assert_handle_request_limit, cleanup_messages, send_completion_messages, MessageTracker,
+};
 
 /* Utilities */
 #[derive(Clone, Debug)]
 pub struct EditPaymentParams {
     description: Option<String>,
+    datetime: Option<String>,
     creditor: Option<String>,
     currency: Option<Currency>,
     total: Option<i64>,
     debts: Option<Vec<(String, i64)>>,
+    is_transfer: Option<bool>,
 }
 
+// Data of the dialogue while picking users involved in an equal split
+type DebtPickerData = (
+    MessageTracker,
+    Payment,
+    EditPaymentParams,
+    Vec<String>,
+    Vec<String>,
+    Vec<Payment>,
+    usize,
+);
+
 const CANCEL_MESSAGE: &str = "Okay! I've cancelled the edit. No changes have been made! 🌟";
 
 // Controls the state for misc handler actions that return to same state.
@@ -53,7 +83,7 @@ async fn repeat_state(
             payments,
             page,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::EditPayment {
                     messages,
@@ -72,7 +102,7 @@ async fn repeat_state(
             payments,
             page,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::EditPaymentDetails {
                     messages,
@@ -91,7 +121,7 @@ async fn repeat_state(
             payments,
             page,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::EditPaymentDebtSelection {
                     messages,
@@ -102,6 +132,28 @@ async fn repeat_state(
                 })
                 .await?;
         }
+        State::EditPaymentDebtPicker {
+            mut messages,
+            payment,
+            edited_payment,
+            users,
+            selected,
+            payments,
+            page,
+        } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::EditPaymentDebtPicker {
+                    messages,
+                    payment,
+                    edited_payment,
+                    users,
+                    selected,
+                    payments,
+                    page,
+                })
+                .await?;
+        }
         _ => (),
     }
 
@@ -110,16 +162,15 @@ async fn repeat_state(
 
 // Controls the dialogue for ending a edit payment operation.
 async fn complete_edit_payment(
+    redis: &Redis,
     bot: &Bot,
     dialogue: UserDialogue,
     chat_id: &str,
-    messages: Vec<MessageId>,
+    messages: MessageTracker,
     payments: Vec<Payment>,
     page: usize,
 ) -> HandlerResult {
-    if is_erase_messages(chat_id) {
-        delete_bot_messages(&bot, chat_id, messages).await?;
-    }
+    cleanup_messages(redis, bot, chat_id, messages).await?;
     dialogue
         .update(State::ViewPayments { payments, page })
         .await?;
@@ -128,54 +179,98 @@ async fn complete_edit_payment(
 
 /* Displays a payment entry by combining original entry and edited fields.
 */
-fn display_edit_payment(payment: Payment, edited_payment: EditPaymentParams) -> String {
+async fn display_edit_payment(
+    redis: &Redis,
+    payment: Payment,
+    edited_payment: EditPaymentParams,
+) -> String {
+    let language = retrieve_language(redis, &payment.chat_id).await;
+    let description = edited_payment
+        .description
+        .unwrap_or(display_description(&payment, &language));
     let currency = edited_payment.currency.unwrap_or(payment.currency);
+    let transfer = if edited_payment.is_transfer.unwrap_or(payment.is_transfer) {
+        "Type: Transfer 🔁\n"
+    } else {
+        ""
+    };
     format!(
-        "Description: {}\nPayer: {}\nTotal: {}\nSplit:\n{}",
-        edited_payment.description.unwrap_or(payment.description),
-        display_username(&edited_payment.creditor.unwrap_or(payment.creditor)),
+        "Description: {}\nDate: {}\n{}Payer: {}\nTotal: {}\nSplit:\n{}",
+        description,
+        display_payment_date(
+            &edited_payment.datetime.unwrap_or(payment.datetime),
+            retrieve_time_zone(redis, &payment.chat_id).await
+        ),
+        transfer,
+        display_username(
+            redis,
+            &payment.chat_id,
+            &edited_payment.creditor.unwrap_or(payment.creditor.clone())
+        )
+        .await,
         display_currency_amount(
             edited_payment.total.unwrap_or(payment.total),
-            use_currency(currency.clone(), &payment.chat_id),
+            use_currency(redis, currency.clone(), &payment.chat_id).await,
+            retrieve_currency_format(redis, &payment.chat_id).await,
         ),
         display_debts(
+            redis,
+            &payment.chat_id,
             &edited_payment.debts.unwrap_or(payment.debts.clone()),
             currency.1
         )
+        .await
     )
 }
 
 /* Edit a payment entry in a group chat.
  * Displays an overview of the current details provided.
  */
+// Takes the edited details separately, as each edit step fills in a different one
+#[allow(clippy::too_many_arguments)]
 async fn display_edit_overview(
+    redis: &Redis,
     bot: Bot,
     dialogue: UserDialogue,
     msg: &Message,
     msg_id: Option<MessageId>,
-    mut messages: Vec<MessageId>,
+    mut messages: MessageTracker,
     payment: Payment,
     edited_payment: EditPaymentParams,
     payments: Vec<Payment>,
     page: usize,
 ) -> HandlerResult {
+    let transfer_button = if edited_payment.is_transfer.unwrap_or(payment.is_transfer) {
+        MAKE_EXPENSE_BUTTON
+    } else {
+        MAKE_TRANSFER_BUTTON
+    };
     let options = vec![
         "Description",
+        "Date",
         "Payer",
         "Total",
+        "Currency",
         "Split",
+        transfer_button,
+        "History",
         "Cancel",
         "Confirm",
     ];
-    let keyboard = make_keyboard(options, Some(2));
+    let keyboard = make_keyboard(
+        options,
+        Some(2),
+        &retrieve_language(redis, &payment.chat_id).await,
+    );
     match msg_id {
         Some(id) => {
-            bot.edit_message_text(
+            edit_bot_message(
+                &bot,
                 msg.chat.id,
                 id,
                 format!(
                     "Sure! What would you like to ✏️ edit?\n\n{}",
-                    display_edit_payment(payment.clone(), edited_payment.clone())
+                    display_edit_payment(redis, payment.clone(), edited_payment.clone()).await
                 ),
             )
             .reply_markup(keyboard)
@@ -187,13 +282,13 @@ async fn display_edit_overview(
                 msg,
                 format!(
                     "Sure! What would you like to ✏️ edit?\n\n{}",
-                    display_edit_payment(payment.clone(), edited_payment.clone())
+                    display_edit_payment(redis, payment.clone(), edited_payment.clone()).await
                 ),
             )
             .reply_markup(keyboard)
             .await?
             .id;
-            messages.push(new_message);
+            messages.track(new_message);
         }
     }
 
@@ -209,12 +304,66 @@ async fn display_edit_overview(
     Ok(())
 }
 
+// Displays the previous versions of a payment, from the latest to the earliest
+async fn display_payment_versions(
+    redis: &Redis,
+    payment: &Payment,
+    versions: &[PaymentVersion],
+) -> String {
+    let time_zone = retrieve_time_zone(redis, &payment.chat_id).await;
+    let mut message = String::new();
+    for (index, version) in versions.iter().enumerate() {
+        let description = if payment.is_private {
+            PRIVATE_DESCRIPTION
+        } else {
+            &version.payment.description
+        };
+        let currency = get_currency(&version.payment.currency).unwrap_or(get_default_currency());
+        message.push_str(&format!(
+            "{}. Before {} edited it on {}:
+    {} — {} paid {}
+",
+            index + 1,
+            display_username(redis, &payment.chat_id, &version.edited_by).await,
+            display_payment_date(&version.edited_at, time_zone),
+            description,
+            display_username(redis, &payment.chat_id, &version.payment.creditor).await,
+            display_currency_amount(
+                version.payment.total,
+                use_currency(redis, currency, &payment.chat_id).await,
+                retrieve_currency_format(redis, &payment.chat_id).await
+            ),
+        ));
+    }
+    message
+}
+
+// Makes the keyboard to pick a previous version of a payment
+fn make_keyboard_versions(count: usize, language: &str) -> InlineKeyboardMarkup {
+    let mut buttons: Vec<InlineKeyboardButton> = (0..count)
+        .map(|index| {
+            InlineKeyboardButton::callback(
+                (index + 1).to_string(),
+                format!("{VERSION_CALLBACK_PREFIX}{index}"),
+            )
+        })
+        .collect();
+    buttons.push(InlineKeyboardButton::callback(
+        translate_button(language, "Back"),
+        "Back",
+    ));
+    InlineKeyboardMarkup::new(buttons.chunks(5).map(|row| row.to_vec()))
+}
+
 /* Calls processor to execute the edit of the payment entry.
 */
+// Mirrors the arguments of processor::edit_payment, with the dialogue state on top
+#[allow(clippy::too_many_arguments)]
 async fn call_processor_edit_payment(
+    redis: &Redis,
     bot: Bot,
     dialogue: UserDialogue,
-    messages: Vec<MessageId>,
+    mut messages: MessageTracker,
     payment: Payment,
     edited_payment: EditPaymentParams,
     payments: Vec<Payment>,
@@ -224,84 +373,97 @@ async fn call_processor_edit_payment(
     if let Some(msg) = query.message {
         let chat_id = msg.chat.id.to_string();
         let edited_clone = edited_payment.clone();
-        let user = msg.from();
+        // The sender of the query, as the message itself is from the bot
+        let user = Some(&query.from);
         if let Some(user) = user {
             // Check first if there are any changes at all
             if let EditPaymentParams {
                 description: None,
+                datetime: None,
                 creditor: None,
                 currency: None,
                 total: None,
                 debts: None,
+                is_transfer: None,
             } = edited_payment
             {
                 send_bot_message(
                     &bot,
                     &msg,
-                    format!("Hmm❓ it seems like you didn't make any changes! But that's okay, 🤭 I've cancelled editing the payment for you!"),
+                    "Hmm❓ it seems like you didn't make any changes! But that's okay, 🤭 I've cancelled editing the payment for you!".to_string(),
                 )
                 .await?;
-                complete_edit_payment(&bot, dialogue, &chat_id, messages, payments, page).await?;
+                complete_edit_payment(redis, &bot, dialogue, &chat_id, messages, payments, page)
+                    .await?;
                 return Ok(());
             }
 
             let edited = edit_payment(
+                redis,
                 &chat_id,
                 user.clone().username.unwrap_or("".to_string()),
                 user.id.to_string(),
                 &payment.payment_id,
                 edited_payment.description.as_deref(),
+                edited_payment.datetime.as_deref(),
                 edited_payment.creditor.as_deref(),
                 edited_payment.currency.clone().unzip().0.as_deref(),
                 edited_payment.total.as_ref(),
                 edited_payment.debts,
+                edited_payment.is_transfer,
             )
             .await;
 
             match edited {
                 Ok(balances) => {
-                    let edit_overview = display_edit_payment(payment.clone(), edited_clone);
-                    match balances {
-                        Some(balances) => {
-                            send_bot_message(
-                                &bot,
-                                &msg,
-                                format!("🎉 Yay! Payment edited! 🎉\n\n{}", edit_overview,),
-                            )
-                            .await?;
-                            send_bot_message(
-                                &bot,
-                                &msg,
-                                format!(
-                                    "{}{}",
-                                    display_balance_header(
-                                        &chat_id,
-                                        edited_payment
-                                            .currency
-                                            .unzip()
-                                            .0
-                                            .as_deref()
-                                            .unwrap_or(&payment.currency.0)
-                                    ),
-                                    display_balances(&balances)
-                                ),
-                            )
-                            .await?;
-                        }
-                        None => {
-                            send_bot_message(
-                                &bot,
-                                &msg,
+                    let edit_overview =
+                        display_edit_payment(redis, payment.clone(), edited_clone).await;
+                    let summary = format!(
+                        "✏️ Payment edited: {}",
+                        edited_payment
+                            .description
+                            .as_deref()
+                            .unwrap_or(&display_description(
+                                &payment,
+                                &retrieve_language(redis, &payment.chat_id).await,
+                            ))
+                    );
+                    let details = match balances {
+                        Some(balances) => vec![
+                            format!("🎉 Yay! Payment edited! 🎉\n\n{}", edit_overview),
                             format!(
-                                "🎉 Yay! Payment edited! 🎉\n\n{}\nThere are no changes to the balances! 🥳",
-                                edit_overview
-                                ),
+                                "{}{}",
+                                display_balance_header(redis,
+                                    &chat_id,
+                                    edited_payment
+                                        .currency
+                                        .unzip()
+                                        .0
+                                        .as_deref()
+                                        .unwrap_or(&payment.currency.0)
                                 )
-                            .await?;
-                        }
-                    }
-                    complete_edit_payment(&bot, dialogue, &chat_id, messages, payments, page)
-                        .await?;
+                                .await,
+                                display_balances(redis, &chat_id, &balances).await
+                            ),
+                        ],
+                        None => vec![format!(
+                            "🎉 Yay! Payment edited! 🎉\n\n{}\nThere are no changes to the balances! 🥳",
+                            edit_overview
+                        )],
+                    };
+                    send_completion_messages(
+                        redis,
+                        &bot,
+                        &msg,
+                        Some(&mut messages),
+                        summary,
+                        details,
+                    )
+                    .await?;
+                    complete_edit_payment(
+                        redis, &bot, dialogue, &chat_id, messages, payments, page,
+                    )
+                    .await?;
 
                     // Logging
                     log::info!(
@@ -309,26 +471,28 @@ async fn call_processor_edit_payment(
                         chat_id,
                         edit_overview
                     );
+
+                    notify_spending_limits(redis, &bot, &msg).await?;
                 }
                 Err(err) => {
-                    let time_zone = retrieve_time_zone(&chat_id);
+                    let time_zone = retrieve_time_zone(redis, &chat_id).await;
                     send_bot_message(
                         &bot,
                         &msg,
-                        format!(
-                            "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't edit the payment right now. Please try again later!\n\n"
-                        ),
+                        "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't edit the payment right now. Please try again later!\n\n".to_string(),
                     )
                     .await?;
 
-                    complete_edit_payment(&bot, dialogue, &chat_id, messages, payments, page)
-                        .await?;
+                    complete_edit_payment(
+                        redis, &bot, dialogue, &chat_id, messages, payments, page,
+                    )
+                    .await?;
 
                     // Logging
                     log::error!(
                         "Edit Payment Submission - Processor failed to edit payment for chat {} with payment {}: {}",
                         chat_id,
-                        display_payment(&payment, 1, time_zone),
+                        display_payment(redis, &payment, 1, time_zone).await,
                         err.to_string()
                     );
                 }
@@ -364,6 +528,7 @@ pub async fn handle_repeated_edit_payment(
  * Can be called at any step of the process.
  */
 pub async fn cancel_edit_payment(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
@@ -399,8 +564,15 @@ pub async fn cancel_edit_payment(
             edit: _,
             payments,
             page,
+        }
+        | State::EditPaymentDebtPicker {
+            messages,
+            payments,
+            page,
+            ..
         } => {
             complete_edit_payment(
+                &redis,
                 &bot,
                 dialogue,
                 &msg.chat.id.to_string(),
@@ -437,8 +609,8 @@ pub async fn block_edit_payment(
 /* Blocks user command.
  * Called when user attempts to edit payment without first viewing anything.
  */
-pub async fn no_edit_payment(bot: Bot, msg: Message) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+pub async fn no_edit_payment(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
@@ -457,26 +629,30 @@ pub async fn no_edit_payment(bot: Bot, msg: Message) -> HandlerResult {
  * before confirming the changes and updating the balances.
  */
 pub async fn action_edit_payment(
+    redis: &Redis,
     bot: Bot,
     dialogue: UserDialogue,
     msg: &Message,
     msg_id: MessageId,
-    (messages, payments, page): (Vec<MessageId>, Vec<Payment>, usize),
+    (messages, payments, page): (MessageTracker, Vec<Payment>, usize),
     index: usize,
 ) -> HandlerResult {
     let payment = payments[index].clone();
     let edited_payment = EditPaymentParams {
         description: None,
+        datetime: None,
         creditor: None,
         currency: None,
         total: None,
         debts: None,
+        is_transfer: None,
     };
 
     display_edit_overview(
+        redis,
         bot,
         dialogue,
-        &msg,
+        msg,
         Some(msg_id),
         messages,
         payment,
@@ -488,15 +664,53 @@ pub async fn action_edit_payment(
     Ok(())
 }
 
+/* Edits a disputed payment, from the dispute message in the group.
+ * Bot shows the same menu of edits as from the list of payments.
+ * Confirming the edit resolves the dispute.
+ */
+pub async fn action_edit_disputed_payment(
+    redis: &Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: &Message,
+    payment: Payment,
+) -> HandlerResult {
+    let edited_payment = EditPaymentParams {
+        description: None,
+        datetime: None,
+        creditor: None,
+        currency: None,
+        total: None,
+        debts: None,
+        is_transfer: None,
+    };
+
+    display_edit_overview(
+        redis,
+        bot,
+        dialogue,
+        msg,
+        None,
+        MessageTracker::default(),
+        payment.clone(),
+        edited_payment,
+        vec![payment],
+        0,
+    )
+    .await?;
+    Ok(())
+}
+
 /* Edits a specified payment.
  * Bot receives a callback query to confirm the changes.
  */
 pub async fn action_edit_payment_confirm(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     (mut messages, payment, edited_payment, payments, page): (
-        Vec<MessageId>,
+        MessageTracker,
         Payment,
         EditPaymentParams,
         Vec<Payment>,
@@ -511,10 +725,11 @@ pub async fn action_edit_payment_confirm(
             let chat_id = msg.chat.id.to_string();
             match button.as_str() {
                 "Cancel" => {
-                    cancel_edit_payment(bot, dialogue, state, msg.clone()).await?;
+                    cancel_edit_payment(redis.clone(), bot, dialogue, state, msg.clone()).await?;
                 }
                 "Confirm" => {
                     call_processor_edit_payment(
+                        &redis,
                         bot,
                         dialogue,
                         messages,
@@ -529,18 +744,21 @@ pub async fn action_edit_payment_confirm(
                 "Description" => {
                     let new_message = send_bot_message(
                         &bot,
-                        &msg,
+                        msg,
                         format!(
                             "Current description: {}\n\nWhat should the description be?",
                             edited_payment
                                 .description
-                                .clone()
-                                .unwrap_or(payment.description.clone())
+                                .as_deref()
+                                .unwrap_or(&display_description(
+                                    &payment,
+                                    &retrieve_language(&redis, &payment.chat_id).await,
+                                ))
                         ),
                     )
                     .await?
                     .id;
-                    messages.push(new_message);
+                    messages.track(new_message);
                     dialogue
                         .update(State::EditPaymentDetails {
                             messages,
@@ -552,23 +770,63 @@ pub async fn action_edit_payment_confirm(
                         })
                         .await?;
                 }
+                "Date" => {
+                    let time_zone = retrieve_time_zone(&redis, &payment.chat_id).await;
+                    let new_message = send_bot_message(
+                        &bot,
+                        msg,
+                        t_prompt(
+                            &redis,
+                            &payment.chat_id,
+                            EDIT_DATE_PROMPT,
+                            &[(
+                                "date",
+                                &display_payment_date(
+                                    &edited_payment
+                                        .datetime
+                                        .clone()
+                                        .unwrap_or(payment.datetime.clone()),
+                                    time_zone,
+                                ),
+                            )],
+                            DATE_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?
+                    .id;
+                    messages.track(new_message);
+                    dialogue
+                        .update(State::EditPaymentDetails {
+                            messages,
+                            payment,
+                            edited_payment,
+                            edit: AddPaymentEdit::Date,
+                            payments,
+                            page,
+                        })
+                        .await?;
+                }
                 "Payer" => {
                     let new_message = send_bot_message(
                         &bot,
-                        &msg,
+                        msg,
                         format!(
                             "Current payer: {}\n\nWho should the payer be?",
                             display_username(
+                                &redis,
+                                &payment.chat_id,
                                 &edited_payment
                                     .creditor
                                     .clone()
                                     .unwrap_or(payment.creditor.clone())
                             )
+                            .await
                         ),
                     )
                     .await?
                     .id;
-                    messages.push(new_message);
+                    messages.track(new_message);
                     dialogue
                         .update(State::EditPaymentDetails {
                             messages,
@@ -585,17 +843,30 @@ pub async fn action_edit_payment_confirm(
                         .currency
                         .clone()
                         .unwrap_or(payment.currency.clone());
-                    let actual_currency = use_currency(currency, &payment.chat_id);
+                    let actual_currency = use_currency(&redis, currency, &payment.chat_id).await;
+                    let currency_format = retrieve_currency_format(&redis, &payment.chat_id).await;
                     let new_message = send_bot_message(
                         &bot,
-                        &msg,
-                        format!(
-                            "Current total: {}\n\nWhat should the total be?\n\n{TOTAL_INSTRUCTIONS_MESSAGE}",
-                            display_currency_amount(edited_payment.total.unwrap_or(payment.total), actual_currency)
-                            ),
-                            )
-                        .await?.id;
-                    messages.push(new_message);
+                        msg,
+                        t_prompt(
+                            &redis,
+                            &payment.chat_id,
+                            EDIT_TOTAL_PROMPT,
+                            &[(
+                                "total",
+                                &display_currency_amount(
+                                    edited_payment.total.unwrap_or(payment.total),
+                                    actual_currency,
+                                    currency_format,
+                                ),
+                            )],
+                            TOTAL_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?
+                    .id;
+                    messages.track(new_message);
                     dialogue
                         .update(State::EditPaymentDetails {
                             messages,
@@ -607,17 +878,80 @@ pub async fn action_edit_payment_confirm(
                         })
                         .await?;
                 }
+                "Currency" => {
+                    let currency = edited_payment
+                        .currency
+                        .clone()
+                        .unwrap_or(payment.currency.clone());
+                    let actual_currency = use_currency(&redis, currency, &payment.chat_id).await;
+                    let new_message = send_bot_message(
+                        &bot,
+                        msg,
+                        t_prompt(
+                            &redis,
+                            &payment.chat_id,
+                            EDIT_CURRENCY_PROMPT,
+                            &[(
+                                "currency",
+                                if actual_currency.0 == CURRENCY_DEFAULT.0 {
+                                    "None"
+                                } else {
+                                    &actual_currency.0
+                                },
+                            )],
+                            CURRENCY_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?
+                    .id;
+                    messages.track(new_message);
+                    dialogue
+                        .update(State::EditPaymentDetails {
+                            messages,
+                            payment,
+                            edited_payment,
+                            edit: AddPaymentEdit::Currency,
+                            payments,
+                            page,
+                        })
+                        .await?;
+                }
                 "Split" => {
                     let new_message = send_bot_message(
                         &bot,
-                        &msg,
-                        format!(
-                            "Current split:\n{}\nHow should we split this?\n\n{DEBT_EQUAL_DESCRIPTION_MESSAGE}{DEBT_EXACT_DESCRIPTION_MESSAGE}{DEBT_RATIO_DESCRIPTION_MESSAGE}",
-                            display_debts(&edited_payment.debts.clone().unwrap_or(payment.debts.clone()), edited_payment.currency.clone().unwrap_or(payment.currency.clone()).1)
-                            )
-                            ).reply_markup(make_keyboard_debt_selection())
-                        .await?.id;
-                    messages.push(new_message);
+                        msg,
+                        t_prompt(
+                            &redis,
+                            &payment.chat_id,
+                            EDIT_SPLIT_PROMPT,
+                            &[(
+                                "split",
+                                &display_debts(
+                                    &redis,
+                                    &payment.chat_id,
+                                    &edited_payment
+                                        .debts
+                                        .clone()
+                                        .unwrap_or(payment.debts.clone()),
+                                    edited_payment
+                                        .currency
+                                        .clone()
+                                        .unwrap_or(payment.currency.clone())
+                                        .1,
+                                )
+                                .await,
+                            )],
+                            DEBT_FORMATS,
+                        )
+                        .await,
+                    )
+                    .reply_markup(make_keyboard_debt_selection(
+                        &retrieve_language(&redis, &payment.chat_id).await,
+                    ))
+                    .await?
+                    .id;
+                    messages.track(new_message);
                     dialogue
                         .update(State::EditPaymentDebtSelection {
                             messages,
@@ -628,6 +962,142 @@ pub async fn action_edit_payment_confirm(
                         })
                         .await?;
                 }
+                MAKE_TRANSFER_BUTTON | MAKE_EXPENSE_BUTTON => {
+                    // Left unchanged if toggled back to what the payment already is
+                    let is_transfer = button == MAKE_TRANSFER_BUTTON;
+                    let new_edited_payment = EditPaymentParams {
+                        is_transfer: if is_transfer == payment.is_transfer {
+                            None
+                        } else {
+                            Some(is_transfer)
+                        },
+                        ..edited_payment
+                    };
+                    display_edit_overview(
+                        &redis,
+                        bot,
+                        dialogue,
+                        msg,
+                        Some(msg.id),
+                        messages,
+                        payment,
+                        new_edited_payment,
+                        payments,
+                        page,
+                    )
+                    .await?;
+                }
+                "History" => {
+                    let versions = retrieve_payment_versions(&redis, &payment.payment_id).await?;
+                    if versions.is_empty() {
+                        let new_message = send_bot_message(
+                            &bot,
+                            msg,
+                            "📜 This payment hasn't been edited before, so there's nothing to go back to!"
+                                .to_string(),
+                        )
+                        .await?
+                        .id;
+                        messages.track(new_message);
+                        dialogue
+                            .update(State::EditPayment {
+                                messages,
+                                payment,
+                                edited_payment,
+                                payments,
+                                page,
+                            })
+                            .await?;
+                        return Ok(());
+                    }
+
+                    edit_tracked_message(&bot, msg, &mut messages,
+                        format!(
+                            "📜 Here's how this payment looked before each edit, from the latest!\n\n{}\nWhich version should I go back to?",
+                            display_payment_versions(&redis, &payment, &versions).await
+                        ),
+                    )
+                    .reply_markup(make_keyboard_versions(
+                        versions.len(),
+                        &retrieve_language(&redis, &payment.chat_id).await,
+                    ))
+                    .await?;
+                }
+                "Back" => {
+                    display_edit_overview(
+                        &redis,
+                        bot,
+                        dialogue,
+                        msg,
+                        Some(msg.id),
+                        messages,
+                        payment,
+                        edited_payment,
+                        payments,
+                        page,
+                    )
+                    .await?;
+                }
+                _ if button.starts_with(VERSION_CALLBACK_PREFIX) => {
+                    let versions = retrieve_payment_versions(&redis, &payment.payment_id).await?;
+                    let version = button
+                        .trim_start_matches(VERSION_CALLBACK_PREFIX)
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| versions.get(index));
+                    let version = match version {
+                        Some(version) => version.payment.clone(),
+                        None => {
+                            log::error!(
+                                "Edit Payment History - Invalid version in chat {}: {}",
+                                chat_id,
+                                button
+                            );
+                            return Ok(());
+                        }
+                    };
+
+                    // Reverting fills in every detail from the version, to be confirmed as an edit
+                    // Descriptions of private payments are left as is, to keep them hidden
+                    let description = if payment.is_private {
+                        None
+                    } else {
+                        Some(version.description)
+                    };
+                    let new_edited_payment = EditPaymentParams {
+                        description,
+                        datetime: Some(version.datetime),
+                        creditor: Some(version.creditor),
+                        currency: Some(
+                            get_currency(&version.currency).unwrap_or(get_default_currency()),
+                        ),
+                        total: Some(version.total),
+                        debts: Some(version.debts),
+                        is_transfer: edited_payment.is_transfer,
+                    };
+                    let new_message = send_bot_message(
+                        &bot,
+                        msg,
+                        "⏪ I've filled in the details from that version! Hit Confirm to revert the payment, or keep editing."
+                            .to_string(),
+                    )
+                    .await?
+                    .id;
+                    messages.track(new_message);
+                    display_edit_overview(
+                        &redis,
+                        bot,
+                        dialogue,
+                        msg,
+                        Some(msg.id),
+                        messages,
+                        payment,
+                        new_edited_payment,
+                        payments,
+                        page,
+                    )
+                    .await?;
+                }
                 _ => {
                     log::error!(
                         "Edit Payment Menu - Invalid button in chat {}: {}",
@@ -646,11 +1116,12 @@ pub async fn action_edit_payment_confirm(
  * Bot receives a callback query on how to specify changes to debts.
  */
 pub async fn action_edit_payment_debts(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     query: CallbackQuery,
-    (messages, payment, edited_payment, payments, page): (
-        Vec<MessageId>,
+    (mut messages, payment, edited_payment, payments, page): (
+        MessageTracker,
         Payment,
         EditPaymentParams,
         Vec<Payment>,
@@ -662,21 +1133,48 @@ pub async fn action_edit_payment_debts(
 
         match button.as_str() {
             "Equal" => {
-                if let Some(Message { id, chat, .. }) = query.message {
-                    bot.edit_message_text(
-                        chat.id,
-                        id,
-                        format!(
-                            "Okay! Who is involved in the payment?\n\n{DEBT_EQUAL_INSTRUCTIONS_MESSAGE}",
-                            ),
-                            )
-                        .await?;
+                if let Some(msg) = query.message {
+                    let debts = edited_payment
+                        .debts
+                        .clone()
+                        .unwrap_or(payment.debts.clone());
+                    let creditor = edited_payment
+                        .creditor
+                        .clone()
+                        .unwrap_or(payment.creditor.clone());
+                    let selected: Vec<String> = debts.into_iter().map(|(user, _)| user).collect();
+                    let mut others = vec![creditor];
+                    others.extend(selected.clone());
+                    let users = get_picker_users(&redis, &payment.chat_id, others).await;
+                    let groups = retrieve_chat_split_groups(&redis, &payment.chat_id).await;
+
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t_prompt(
+                            &redis,
+                            &payment.chat_id,
+                            DEBT_USERS_PROMPT,
+                            &[],
+                            DEBT_EQUAL_PICKER,
+                        )
+                        .await,
+                    )
+                    .reply_markup(make_keyboard_user_picker(
+                        &users,
+                        &selected,
+                        &groups,
+                        &retrieve_language(&redis, &payment.chat_id).await,
+                    ))
+                    .await?;
                     dialogue
-                        .update(State::EditPaymentDetails {
+                        .update(State::EditPaymentDebtPicker {
                             messages,
                             payment,
                             edited_payment,
-                            edit: AddPaymentEdit::DebtsEqual,
+                            users,
+                            selected,
                             payments,
                             page,
                         })
@@ -684,13 +1182,21 @@ pub async fn action_edit_payment_debts(
                 }
             }
             "Exact" => {
-                if let Some(Message { id, chat, .. }) = query.message {
-                    bot.edit_message_text(
-                        chat.id,
-                        id,
-                        format!(
-                            "Okay! Who is involved and how much do they owe?\n\n{DEBT_EXACT_INSTRUCTIONS_MESSAGE}",
-                            )).await?;
+                if let Some(msg) = query.message {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t_prompt(
+                            &redis,
+                            &payment.chat_id,
+                            DEBT_AMOUNTS_PROMPT,
+                            &[],
+                            DEBT_EXACT_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?;
                     dialogue
                         .update(State::EditPaymentDetails {
                             messages,
@@ -704,13 +1210,21 @@ pub async fn action_edit_payment_debts(
                 }
             }
             "Proportion" => {
-                if let Some(Message { id, chat, .. }) = query.message {
-                    bot.edit_message_text(
-                        chat.id,
-                        id,
-                        format!(
-                            "Okay! Who is involved and how much do they owe?\n\n{DEBT_RATIO_INSTRUCTIONS_MESSAGE}",
-                            )).await?;
+                if let Some(msg) = query.message {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t_prompt(
+                            &redis,
+                            &payment.chat_id,
+                            DEBT_AMOUNTS_PROMPT,
+                            &[],
+                            DEBT_RATIO_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?;
                     dialogue
                         .update(State::EditPaymentDetails {
                             messages,
@@ -723,6 +1237,90 @@ pub async fn action_edit_payment_debts(
                         .await?;
                 }
             }
+            "Percent" => {
+                if let Some(msg) = query.message {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t_prompt(
+                            &redis,
+                            &payment.chat_id,
+                            DEBT_PERCENTAGES_PROMPT,
+                            &[],
+                            DEBT_PERCENT_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::EditPaymentDetails {
+                            messages,
+                            payment,
+                            edited_payment,
+                            edit: AddPaymentEdit::DebtsPercent,
+                            payments,
+                            page,
+                        })
+                        .await?;
+                }
+            }
+            "Adjusted" => {
+                if let Some(msg) = query.message {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t_prompt(
+                            &redis,
+                            &payment.chat_id,
+                            DEBT_ADJUSTMENTS_PROMPT,
+                            &[],
+                            DEBT_ADJUSTED_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::EditPaymentDetails {
+                            messages,
+                            payment,
+                            edited_payment,
+                            edit: AddPaymentEdit::DebtsAdjusted,
+                            payments,
+                            page,
+                        })
+                        .await?;
+                }
+            }
+            "Itemized" => {
+                if let Some(msg) = query.message {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t_prompt(
+                            &redis,
+                            &payment.chat_id,
+                            DEBT_ITEMS_PROMPT,
+                            &[],
+                            DEBT_ITEMIZED_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::EditPaymentDetails {
+                            messages,
+                            payment,
+                            edited_payment,
+                            edit: AddPaymentEdit::DebtsItemized,
+                            payments,
+                            page,
+                        })
+                        .await?;
+                }
+            }
             _ => {
                 log::error!("Edit Payment Debt Selection - Invalid button for in chat {} with payment {:?}: {}",
                             payment.chat_id, payment, button);
@@ -732,16 +1330,134 @@ pub async fn action_edit_payment_debts(
     Ok(())
 }
 
+/* Edits a specified payment.
+ * Bot receives a callback query from the user picking who is involved, for an equal split.
+ * Toggles the users picked, until the user is done.
+ */
+pub async fn action_edit_payment_debt_picker(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    (messages, payment, edited_payment, users, selected, payments, page): DebtPickerData,
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        if let Some(msg) = query.message {
+            match button.as_str() {
+                "Done" => {
+                    let rounding = retrieve_rounding(&redis, &payment.chat_id).await;
+                    let debts = process_debts_equal(
+                        &selected.join(" "),
+                        edited_payment.total.or(Some(payment.total)),
+                        &edited_payment
+                            .creditor
+                            .clone()
+                            .or(Some(payment.creditor.clone())),
+                        rounding,
+                    );
+                    if let Err(err) = debts {
+                        bot.answer_callback_query(query.id.to_string())
+                            .text(error_to_user_message(&redis, &err, &payment.chat_id).await)
+                            .await?;
+                        return Ok(());
+                    }
+                    bot.answer_callback_query(query.id.to_string()).await?;
+
+                    let new_edited_payment = EditPaymentParams {
+                        description: edited_payment.description,
+                        datetime: edited_payment.datetime,
+                        creditor: edited_payment.creditor,
+                        currency: edited_payment.currency,
+                        total: edited_payment.total,
+                        debts: Some(debts?),
+                        is_transfer: edited_payment.is_transfer,
+                    };
+
+                    display_edit_overview(
+                        &redis,
+                        bot,
+                        dialogue,
+                        &msg,
+                        Some(msg.id),
+                        messages,
+                        payment,
+                        new_edited_payment,
+                        payments,
+                        page,
+                    )
+                    .await?;
+                }
+                _ => {
+                    bot.answer_callback_query(query.id.to_string()).await?;
+
+                    let groups = retrieve_chat_split_groups(&redis, &payment.chat_id).await;
+                    let selected = toggle_picker_users(&users, &selected, button, &groups);
+                    bot.edit_message_reply_markup(msg.chat.id, msg.id)
+                        .reply_markup(make_keyboard_user_picker(
+                            &users,
+                            &selected,
+                            &groups,
+                            &retrieve_language(&redis, &payment.chat_id).await,
+                        ))
+                        .await?;
+                    dialogue
+                        .update(State::EditPaymentDebtPicker {
+                            messages,
+                            payment,
+                            edited_payment,
+                            users,
+                            selected,
+                            payments,
+                            page,
+                        })
+                        .await?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Edits a specified payment.
+ * Bot receives the usernames involved as text instead, while picking who is involved.
+ */
+pub async fn action_edit_payment_debt_picker_message(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+    (messages, payment, edited_payment, _, _, payments, page): DebtPickerData,
+) -> HandlerResult {
+    action_edit_payment_edit(
+        redis.clone(),
+        bot,
+        dialogue,
+        state,
+        msg,
+        (
+            messages,
+            payment,
+            edited_payment,
+            AddPaymentEdit::DebtsEqual,
+            payments,
+            page,
+        ),
+    )
+    .await
+}
+
 /* Edits a specified payment.
  * Bot receives a text message, and depending on the edit enum, edits the corresponding part.
  */
 pub async fn action_edit_payment_edit(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
     (mut messages, payment, edited_payment, edit, payments, page): (
-        Vec<MessageId>,
+        MessageTracker,
         Payment,
         EditPaymentParams,
         AddPaymentEdit,
@@ -752,14 +1468,75 @@ pub async fn action_edit_payment_edit(
     match msg.text() {
         Some(text) => match edit {
             AddPaymentEdit::Description => {
+                let description = match validate_description(text) {
+                    Ok(description) => description,
+                    Err(err) => {
+                        let new_message = send_bot_message(
+                            &bot,
+                            &msg,
+                            error_to_user_message(&redis, &err, &payment.chat_id).await,
+                        )
+                        .await?
+                        .id;
+                        repeat_state(dialogue, state, new_message).await?;
+                        return Ok(());
+                    }
+                };
+                let new_edited_payment = EditPaymentParams {
+                    description: Some(description),
+                    datetime: edited_payment.datetime,
+                    creditor: edited_payment.creditor,
+                    currency: edited_payment.currency,
+                    total: edited_payment.total,
+                    debts: edited_payment.debts,
+                    is_transfer: edited_payment.is_transfer,
+                };
+                display_edit_overview(
+                    &redis,
+                    bot,
+                    dialogue,
+                    &msg,
+                    None,
+                    messages,
+                    payment,
+                    new_edited_payment,
+                    payments,
+                    page,
+                )
+                .await?;
+            }
+            AddPaymentEdit::Date => {
+                let time_zone = retrieve_time_zone(&redis, &payment.chat_id).await;
+                let datetime = parse_payment_date(
+                    text,
+                    &edited_payment
+                        .datetime
+                        .clone()
+                        .unwrap_or(payment.datetime.clone()),
+                    time_zone,
+                );
+                if let Err(err) = datetime {
+                    let new_message = send_bot_message(
+                        &bot,
+                        &msg,
+                        error_to_user_message(&redis, &err, &payment.chat_id).await,
+                    )
+                    .await?
+                    .id;
+                    repeat_state(dialogue, state, new_message).await?;
+                    return Ok(());
+                }
                 let new_edited_payment = EditPaymentParams {
-                    description: Some(text.to_string()),
+                    description: edited_payment.description,
+                    datetime: Some(datetime?),
                     creditor: edited_payment.creditor,
                     currency: edited_payment.currency,
                     total: edited_payment.total,
                     debts: edited_payment.debts,
+                    is_transfer: edited_payment.is_transfer,
                 };
                 display_edit_overview(
+                    &redis,
                     bot,
                     dialogue,
                     &msg,
@@ -775,18 +1552,27 @@ pub async fn action_edit_payment_edit(
             AddPaymentEdit::Creditor => {
                 let username = parse_username(text);
                 if let Err(err) = username {
-                    let new_message = send_bot_message(&bot, &msg, err.to_string()).await?.id;
+                    let new_message = send_bot_message(
+                        &bot,
+                        &msg,
+                        error_to_user_message(&redis, &err, &payment.chat_id).await,
+                    )
+                    .await?
+                    .id;
                     repeat_state(dialogue, state, new_message).await?;
                     return Ok(());
                 }
                 let new_edited_payment = EditPaymentParams {
                     description: edited_payment.description,
+                    datetime: edited_payment.datetime,
                     creditor: Some(username?),
                     currency: edited_payment.currency,
                     total: edited_payment.total,
                     debts: edited_payment.debts,
+                    is_transfer: edited_payment.is_transfer,
                 };
                 display_edit_overview(
+                    &redis,
                     bot,
                     dialogue,
                     &msg,
@@ -800,25 +1586,35 @@ pub async fn action_edit_payment_edit(
                 .await?;
             }
             AddPaymentEdit::Total => {
-                let currency_amount = parse_currency_amount(text);
+                let currency_amount = parse_total_amount(
+                    text,
+                    retrieve_number_format(&redis, &msg.chat.id.to_string()).await,
+                    &retrieve_allowed_currencies(&redis, &msg.chat.id.to_string()).await,
+                );
                 match currency_amount {
-                    Ok((total, currency)) => {
+                    Ok((total, currency, _)) => {
                         let new_edited_payment = EditPaymentParams {
                             description: edited_payment.description,
+                            datetime: edited_payment.datetime,
                             creditor: edited_payment.creditor,
                             currency: Some(currency),
                             total: Some(total),
                             debts: None,
+                            is_transfer: edited_payment.is_transfer,
                         };
 
                         let new_message = send_bot_message(
                             &bot,
                             &msg,
-                            format!("Fantastic! How should we split this?\n\n{DEBT_EQUAL_DESCRIPTION_MESSAGE}{DEBT_EXACT_DESCRIPTION_MESSAGE}{DEBT_RATIO_DESCRIPTION_MESSAGE}")
-                            )
-                            .reply_markup(make_keyboard_debt_selection())
-                            .await?.id;
-                        messages.push(new_message);
+                            t_prompt(&redis, &payment.chat_id, SPLIT_PROMPT, &[], DEBT_FORMATS)
+                                .await,
+                        )
+                        .reply_markup(make_keyboard_debt_selection(
+                            &retrieve_language(&redis, &payment.chat_id).await,
+                        ))
+                        .await?
+                        .id;
+                        messages.track(new_message);
                         dialogue
                             .update(State::EditPaymentDebtSelection {
                                 messages,
@@ -834,8 +1630,16 @@ pub async fn action_edit_payment_edit(
                             &bot,
                             &msg,
                             format!(
-                                "{}\n\nWhat should the total be?\n\n{TOTAL_INSTRUCTIONS_MESSAGE}",
-                                err.to_string()
+                                "{}\n\n{}",
+                                err,
+                                t_prompt(
+                                    &redis,
+                                    &payment.chat_id,
+                                    TOTAL_QUESTION,
+                                    &[],
+                                    TOTAL_INSTRUCTIONS
+                                )
+                                .await
                             ),
                         )
                         .await?
@@ -845,22 +1649,108 @@ pub async fn action_edit_payment_edit(
                     }
                 }
             }
+            AddPaymentEdit::Currency => {
+                let allowed_currencies =
+                    retrieve_allowed_currencies(&redis, &payment.chat_id).await;
+                let currency = match parse_currency(text, &allowed_currencies) {
+                    Ok(currency) => currency,
+                    Err(err) => {
+                        let new_message = send_bot_message(
+                            &bot,
+                            &msg,
+                            format!(
+                                "{}\n\n{}",
+                                err,
+                                t(&redis, &payment.chat_id, CURRENCY_INSTRUCTIONS, &[]).await
+                            ),
+                        )
+                        .await?
+                        .id;
+                        repeat_state(dialogue, state, new_message).await?;
+                        return Ok(());
+                    }
+                };
+
+                // Amounts are kept the same, but stored in the decimal places of the new currency
+                let prev_currency = edited_payment
+                    .currency
+                    .clone()
+                    .unwrap_or(payment.currency.clone());
+                let (total, debts) = convert_decimal_places(
+                    edited_payment.total.unwrap_or(payment.total),
+                    edited_payment
+                        .debts
+                        .clone()
+                        .unwrap_or(payment.debts.clone()),
+                    &prev_currency,
+                    &currency,
+                );
+                let is_converted = prev_currency.1 != currency.1;
+                let new_edited_payment = EditPaymentParams {
+                    description: edited_payment.description,
+                    datetime: edited_payment.datetime,
+                    creditor: edited_payment.creditor,
+                    currency: Some(currency),
+                    total: if is_converted {
+                        Some(total)
+                    } else {
+                        edited_payment.total
+                    },
+                    debts: if is_converted {
+                        Some(debts)
+                    } else {
+                        edited_payment.debts
+                    },
+                    is_transfer: edited_payment.is_transfer,
+                };
+                display_edit_overview(
+                    &redis,
+                    bot,
+                    dialogue,
+                    &msg,
+                    None,
+                    messages,
+                    payment,
+                    new_edited_payment,
+                    payments,
+                    page,
+                )
+                .await?;
+            }
+            // Due dates are only set when adding payments, not in this menu
+            AddPaymentEdit::DueDate => {
+                log::error!(
+                    "Edit Payment - Due date cannot be edited for payment {} in chat {}",
+                    payment.payment_id,
+                    payment.chat_id
+                );
+            }
             AddPaymentEdit::DebtsEqual
             | AddPaymentEdit::DebtsExact
-            | AddPaymentEdit::DebtsRatio => {
+            | AddPaymentEdit::DebtsRatio
+            | AddPaymentEdit::DebtsPercent
+            | AddPaymentEdit::DebtsAdjusted
+            | AddPaymentEdit::DebtsItemized => {
                 let debts_format = match edit {
                     AddPaymentEdit::DebtsEqual => AddDebtsFormat::Equal,
                     AddPaymentEdit::DebtsExact => AddDebtsFormat::Exact,
                     AddPaymentEdit::DebtsRatio => AddDebtsFormat::Ratio,
+                    AddPaymentEdit::DebtsPercent => AddDebtsFormat::Percent,
+                    AddPaymentEdit::DebtsAdjusted => AddDebtsFormat::Adjusted,
+                    AddPaymentEdit::DebtsItemized => AddDebtsFormat::Itemized,
                     _ => AddDebtsFormat::Equal,
                 };
-                let error_msg = match debts_format {
-                    AddDebtsFormat::Equal => DEBT_EQUAL_INSTRUCTIONS_MESSAGE,
-                    AddDebtsFormat::Exact => DEBT_EXACT_INSTRUCTIONS_MESSAGE,
-                    AddDebtsFormat::Ratio => DEBT_RATIO_INSTRUCTIONS_MESSAGE,
-                };
+                let error_msg = t(
+                    &redis,
+                    &payment.chat_id,
+                    get_debts_instructions(&debts_format),
+                    &[],
+                )
+                .await;
                 match msg.text() {
                     Some(text) => {
+                        let rounding = retrieve_rounding(&redis, &payment.chat_id).await;
+                        let groups = retrieve_chat_split_groups(&redis, &payment.chat_id).await;
                         let debts = process_debts(
                             debts_format,
                             text,
@@ -873,28 +1763,30 @@ pub async fn action_edit_payment_edit(
                                 .clone()
                                 .or(Some(payment.currency.clone())),
                             edited_payment.total.or(Some(payment.total)),
+                            rounding,
+                            &groups,
                         );
                         if let Err(err) = debts {
-                            let new_message = send_bot_message(
-                                &bot,
-                                &msg,
-                                format!("{}\n\n{error_msg}", err.to_string()),
-                            )
-                            .await?
-                            .id;
+                            let new_message =
+                                send_bot_message(&bot, &msg, format!("{}\n\n{error_msg}", err))
+                                    .await?
+                                    .id;
                             repeat_state(dialogue, state, new_message).await?;
                             return Ok(());
                         }
 
                         let new_edited_payment = EditPaymentParams {
                             description: edited_payment.description,
+                            datetime: edited_payment.datetime,
                             creditor: edited_payment.creditor,
                             currency: edited_payment.currency,
                             total: edited_payment.total,
                             debts: Some(debts.unwrap()),
+                            is_transfer: edited_payment.is_transfer,
                         };
 
                         display_edit_overview(
+                            &redis,
                             bot,
                             dialogue,
                             &msg,
@@ -908,7 +1800,7 @@ pub async fn action_edit_payment_edit(
                         .await?;
                     }
                     None => {
-                        let new_message = send_bot_message(&bot, &msg, format!("{error_msg}"))
+                        let new_message = send_bot_message(&bot, &msg, error_msg.to_string())
                             .await?
                             .id;
                         repeat_state(dialogue, state, new_message).await?;
@@ -917,9 +1809,13 @@ pub async fn action_edit_payment_edit(
             }
         },
         None => {
-            let new_message = send_bot_message(&bot, &msg, format!("{NO_TEXT_MESSAGE}"))
-                .await?
-                .id;
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
             repeat_state(dialogue, state, new_message).await?;
         }
     }
@@ -0,0 +1,326 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use teloxide::{
+    prelude::*,
+    types::{InputFile, Message},
+};
+
+use crate::bot::{
+    currency::{get_default_currency, CURRENCY_DEFAULT},
+    export::{is_pdf_printable, render_pdf, render_text, DocumentSection},
+    handler::utils::{
+        display_currency_amount, display_name, error_to_user_message, get_currency,
+        get_statement_options, retrieve_currency_format, retrieve_language, retrieve_time_zone,
+        send_bot_document, send_bot_message, t, use_currency, BotError, HandlerResult,
+    },
+    i18n::{
+        STATEMENT_CLOSING, STATEMENT_DEBT, STATEMENT_EMPTY, STATEMENT_FILE_CAPTION, STATEMENT_TITLE,
+    },
+    processor::{
+        retrieve_active_trip, retrieve_debts, retrieve_display_names, retrieve_trip_debts,
+        retrieve_trip_valid_currencies, retrieve_valid_currencies, ProcessError, StatementOption,
+    },
+    redis::{Debt, Redis, Trip},
+    words::amount_to_words,
+};
+
+/* Utilities */
+pub const EXPORT_PDF_BUTTON: &str = "Export PDF";
+pub const FORMAL_STATEMENT_BUTTON: &str = "Formal Statement";
+const STATEMENT_MESSAGE_MAX: usize = 4000;
+
+// Composes the sections of the balances document for the chat, or a trip in it
+// Each currency has a section for the debts, and a section for the net balance of each user
+async fn make_balance_sections(
+    redis: &Redis,
+    chat_id: &str,
+    trip: Option<&Trip>,
+) -> Result<Vec<DocumentSection>, ProcessError> {
+    let currencies = match trip {
+        Some(trip) => retrieve_trip_valid_currencies(redis, chat_id, &trip.0).await,
+        None => retrieve_valid_currencies(redis, chat_id).await,
+    };
+    let options = get_statement_options(redis, chat_id, currencies.unwrap_or_default()).await;
+    let prefix = match trip {
+        Some(trip) => format!("{} - ", trip.1),
+        None => String::new(),
+    };
+
+    let names = retrieve_display_names(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    // Most currency symbols are outside Latin-1, so only the thousands separators are kept
+    let currency_format = retrieve_currency_format(redis, chat_id).await;
+    let currency_format = (currency_format.0, false, currency_format.2);
+    let mut sections: Vec<DocumentSection> = Vec::new();
+    for option in options {
+        let debts: Vec<Debt> = match trip {
+            Some(trip) => retrieve_trip_debts(redis, chat_id, &trip.0, option.clone()).await?,
+            None => retrieve_debts(redis, chat_id, option.clone()).await?,
+        };
+        let currency = match debts.first() {
+            Some(debt) => get_currency(&debt.currency).unwrap_or(get_default_currency()),
+            None => continue,
+        };
+        let currency = use_currency(redis, currency, chat_id).await;
+        let label = match option {
+            StatementOption::ConvertCurrency | StatementOption::ConvertTo(_) => {
+                format!(" (converted to {})", currency.0)
+            }
+            StatementOption::Currency(_) => format!(" ({})", currency.0),
+        };
+
+        let mut net_balances: BTreeMap<String, i64> = BTreeMap::new();
+        for debt in &debts {
+            *net_balances.entry(debt.debtor.clone()).or_insert(0) -= debt.amount;
+            *net_balances.entry(debt.creditor.clone()).or_insert(0) += debt.amount;
+        }
+
+        sections.push(DocumentSection {
+            heading: format!("{prefix}Debts{label}"),
+            lines: debts
+                .iter()
+                .map(|debt| {
+                    format!(
+                        "{} owes {}: {}",
+                        display_name(&debt.debtor, &names),
+                        display_name(&debt.creditor, &names),
+                        display_currency_amount(debt.amount, currency.clone(), currency_format)
+                    )
+                })
+                .collect(),
+        });
+        sections.push(DocumentSection {
+            heading: format!("{prefix}Net Balances{label}"),
+            lines: net_balances
+                .iter()
+                .filter(|(_, balance)| **balance != 0)
+                .map(|(user, balance)| {
+                    let status = if *balance > 0 { "is owed" } else { "owes" };
+                    format!(
+                        "{} {status} {}",
+                        display_name(user, &names),
+                        display_currency_amount(balance.abs(), currency.clone(), currency_format)
+                    )
+                })
+                .collect(),
+        });
+    }
+
+    Ok(sections)
+}
+
+// Composes all sections of the balances document, including the current trip, if any
+async fn make_balances_document(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<DocumentSection>, ProcessError> {
+    let mut sections = make_balance_sections(redis, chat_id, None).await?;
+    if let Some(trip) = retrieve_active_trip(redis, chat_id).await? {
+        sections.extend(make_balance_sections(redis, chat_id, Some(&trip)).await?);
+    }
+
+    if sections.is_empty() {
+        sections.push(DocumentSection {
+            heading: "Balances".to_string(),
+            lines: vec!["No outstanding balances!".to_string()],
+        });
+    }
+
+    Ok(sections)
+}
+
+/* Exports the current balances of a group chat as a PDF document, sent to the chat.
+ * Includes the debts and net balance of each user in each currency,
+ * as well as those of the current trip, if any.
+ * Sent as a text file instead if any text is in a script that the PDF cannot show.
+ */
+pub async fn action_export_balances(
+    redis: &Redis,
+    bot: &Bot,
+    msg: &Message,
+    sender_id: &str,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    let sections = match make_balances_document(redis, &chat_id).await {
+        Ok(sections) => sections,
+        Err(err) => {
+            let err = BotError::from(err);
+            send_bot_message(bot, msg, error_to_user_message(redis, &err, &chat_id).await).await?;
+            log::error!(
+                "Export Balances - User {} failed to export balances for group {}: {}",
+                sender_id,
+                chat_id,
+                err.to_string()
+            );
+            return Ok(());
+        }
+    };
+
+    let time_zone = retrieve_time_zone(redis, &chat_id).await;
+    let now = Utc::now().with_timezone(&time_zone);
+    let title = match msg.chat.title() {
+        Some(title) => format!("Balances of {title}"),
+        None => "Balances".to_string(),
+    };
+    let subtitle = format!(
+        "Generated by PayScribe on {}",
+        now.format("%e %b %Y, %H:%M").to_string().trim()
+    );
+
+    // The fonts of the PDF only cover Latin-1, so names in other scripts are exported as text
+    let (content, extension, caption) = if is_pdf_printable(&title, &subtitle, &sections) {
+        (
+            render_pdf(&title, &subtitle, &sections),
+            "pdf",
+            "🧾 Here are the current balances, all wrapped up in a PDF!",
+        )
+    } else {
+        (
+            render_text(&title, &subtitle, &sections),
+            "txt",
+            "🧾 Here are the current balances, in a text file to keep every name readable!",
+        )
+    };
+
+    let file_name = format!("balances_{}.{extension}", now.format("%Y-%m-%d"));
+    let document = InputFile::memory(content).file_name(file_name);
+    send_bot_document(bot, msg, document)
+        .caption(caption)
+        .await?;
+
+    // Logging
+    log::info!(
+        "Export Balances - User {} exported balances for group {}",
+        sender_id,
+        chat_id
+    );
+
+    Ok(())
+}
+
+// Composes the formal statement of all debts in the chat, in the language of the chat
+// Each debt is stated with its amount in words, as in an IOU
+async fn make_formal_statement(
+    redis: &Redis,
+    chat_id: &str,
+    group: &str,
+    date: &str,
+) -> Result<String, ProcessError> {
+    let currencies = retrieve_valid_currencies(redis, chat_id).await;
+    let options = get_statement_options(redis, chat_id, currencies.unwrap_or_default()).await;
+    let language = retrieve_language(redis, chat_id).await;
+    let names = retrieve_display_names(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    let currency_format = retrieve_currency_format(redis, chat_id).await;
+
+    let mut statements: Vec<String> = Vec::new();
+    for option in options {
+        let debts = retrieve_debts(redis, chat_id, option).await?;
+        let currency = match debts.first() {
+            Some(debt) => get_currency(&debt.currency).unwrap_or(get_default_currency()),
+            None => continue,
+        };
+        let currency = use_currency(redis, currency, chat_id).await;
+        let code = if currency.0 == CURRENCY_DEFAULT.0 {
+            ""
+        } else {
+            &currency.0
+        };
+
+        for debt in &debts {
+            let index = (statements.len() + 1).to_string();
+            let debtor = display_name(&debt.debtor, &names);
+            let creditor = display_name(&debt.creditor, &names);
+            let amount = display_currency_amount(debt.amount, currency.clone(), currency_format);
+            let words = amount_to_words(debt.amount, code, currency.1, &language);
+            statements.push(
+                t(
+                    redis,
+                    chat_id,
+                    STATEMENT_DEBT,
+                    &[
+                        ("index", &index),
+                        ("debtor", &debtor),
+                        ("creditor", &creditor),
+                        ("amount", &amount),
+                        ("words", &words),
+                    ],
+                )
+                .await,
+            );
+        }
+    }
+
+    let title = t(
+        redis,
+        chat_id,
+        STATEMENT_TITLE,
+        &[("group", group), ("date", date)],
+    )
+    .await;
+    let body = if statements.is_empty() {
+        t(redis, chat_id, STATEMENT_EMPTY, &[]).await
+    } else {
+        format!(
+            "{}\n\n{}",
+            statements.join("\n\n"),
+            t(redis, chat_id, STATEMENT_CLOSING, &[]).await
+        )
+    };
+    Ok(format!("{title}\n\n{body}"))
+}
+
+/* Exports the current debts of a group chat as a formal statement, for documenting IOUs.
+ * Each debt is stated with its amount in words, in the language of the chat.
+ * Sent as a message, or as a text file if it is too long for one.
+ */
+pub async fn action_export_statement(
+    redis: &Redis,
+    bot: &Bot,
+    msg: &Message,
+    sender_id: &str,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    let time_zone = retrieve_time_zone(redis, &chat_id).await;
+    let now = Utc::now().with_timezone(&time_zone);
+    let group = msg.chat.title().unwrap_or("PayScribe");
+    let date = now.format("%e %b %Y, %H:%M").to_string();
+
+    let statement = match make_formal_statement(redis, &chat_id, group, date.trim()).await {
+        Ok(statement) => statement,
+        Err(err) => {
+            let err = BotError::from(err);
+            send_bot_message(bot, msg, error_to_user_message(redis, &err, &chat_id).await).await?;
+            log::error!(
+                "Export Statement - User {} failed to export statement for group {}: {}",
+                sender_id,
+                chat_id,
+                err.to_string()
+            );
+            return Ok(());
+        }
+    };
+
+    if statement.chars().count() <= STATEMENT_MESSAGE_MAX {
+        send_bot_message(bot, msg, statement).await?;
+    } else {
+        let file_name = format!("statement_{}.txt", now.format("%Y-%m-%d"));
+        let caption = t(redis, &chat_id, STATEMENT_FILE_CAPTION, &[]).await;
+        let document = InputFile::memory(statement.into_bytes()).file_name(file_name);
+        send_bot_document(bot, msg, document)
+            .caption(caption)
+            .await?;
+    }
+
+    // Logging
+    log::info!(
+        "Export Statement - User {} exported formal statement for group {}",
+        sender_id,
+        chat_id
+    );
+
+    Ok(())
+}
@@ -1,22 +1,92 @@
-use teloxide::{prelude::*, types::ParseMode, utils::command::BotCommands};
+use teloxide::{
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup},
+};
 
-use crate::bot::{dispatcher::Command, processor::init_chat_config};
+use crate::bot::{
+    help::{format_help_topic, get_help_topic, HELP_TOPICS},
+    i18n::{
+        translate_button, CANCEL_NOTHING, HELP_FEEDBACK, HELP_MENU_INFO, HELP_TITLE,
+        HELP_UNKNOWN_COMMAND, HELP_USER_GUIDE, NOTIFICATION_ON, START_ADD_INFO, START_CLOSING,
+        START_INTRODUCTION, START_VIEW_INFO,
+    },
+    processor::{init_chat_config, update_payment_notifications},
+    redis::Redis,
+};
 
 use super::{
-    constants::{
-        COMMAND_ADD_PAYMENT, COMMAND_BALANCES, COMMAND_DELETE_PAYMENT, COMMAND_EDIT_PAYMENT,
-        COMMAND_HELP, COMMAND_PAY_BACK, COMMAND_SPENDINGS, COMMAND_VIEW_PAYMENTS, FEEDBACK_URL,
-        USER_GUIDE_URL,
+    constants::{FEEDBACK_URL, USER_GUIDE_URL},
+    utils::{
+        assert_handle_request_limit, edit_bot_message, edit_markdown_message, escape_markdown,
+        retrieve_language, send_bot_message, send_markdown_message, t, HandlerResult,
     },
-    utils::{assert_handle_request_limit, send_bot_message, HandlerResult},
 };
 
+/* Utilities */
+const HELP_PREFIX: &str = "Help:";
+const HELP_BACK_BUTTON: &str = "⬅️ All Commands";
+const HELP_MENU_COLUMNS: usize = 3;
+
+// Make keyboard listing all commands, each opening its help page
+fn make_keyboard_help_menu() -> InlineKeyboardMarkup {
+    let buttons: Vec<InlineKeyboardButton> = HELP_TOPICS
+        .iter()
+        .map(|topic| {
+            InlineKeyboardButton::callback(
+                format!("/{}", topic.command),
+                format!("{HELP_PREFIX}{}", topic.command),
+            )
+        })
+        .collect();
+
+    InlineKeyboardMarkup::new(buttons.chunks(HELP_MENU_COLUMNS).map(|row| row.to_vec()))
+}
+
+// Make keyboard to go back to the help menu from a help page
+fn make_keyboard_help_topic(language: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
+        translate_button(language, HELP_BACK_BUTTON),
+        HELP_PREFIX,
+    )]])
+}
+
+// Displays the help menu, noting the command asked for if it is unknown
+async fn display_help_menu(redis: &Redis, chat_id: &str, unknown: Option<&str>) -> String {
+    let title = t(redis, chat_id, HELP_TITLE, &[]).await;
+    let menu_info = match unknown {
+        Some(command) => {
+            let command = escape_markdown(command);
+            t(
+                redis,
+                chat_id,
+                HELP_UNKNOWN_COMMAND,
+                &[("command", &command)],
+            )
+            .await
+        }
+        None => t(redis, chat_id, HELP_MENU_INFO, &[]).await,
+    };
+    let user_guide_info = t(redis, chat_id, HELP_USER_GUIDE, &[("url", USER_GUIDE_URL)]).await;
+    let feedback_info = t(redis, chat_id, HELP_FEEDBACK, &[("url", FEEDBACK_URL)]).await;
+
+    format!("{title}\n\n{menu_info}\n\n{user_guide_info}\n\n{feedback_info}")
+}
+
+// Checks if a callback query navigates the help menu.
+// Such queries can come from anyone in the chat, regardless of the current state.
+pub fn is_help_query(query: CallbackQuery) -> bool {
+    match &query.data {
+        Some(data) => data.starts_with(HELP_PREFIX),
+        None => false,
+    }
+}
+
 /* Invalid state.
  * This action is invoked when the bot is in start state, and there is a non-command message
  * addressed to it.
  * Currently, simply does not respond to anything. Reduces spam.
  */
-pub async fn invalid_state(bot: Bot, msg: Message) -> HandlerResult {
+pub async fn invalid_state(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
     // Checks if msg is a service message, ignores it if so
     let is_service_msg = msg.from().is_none();
 
@@ -26,7 +96,7 @@ pub async fn invalid_state(bot: Bot, msg: Message) -> HandlerResult {
         if let Some(new_members) = new_members {
             let bot_id = bot.get_me().send().await?.id;
             if new_members.iter().any(|member| member.id == bot_id) {
-                action_start(bot, msg).await?;
+                action_start(redis.clone(), bot, msg).await?;
             }
         }
 
@@ -55,54 +125,108 @@ pub async fn callback_invalid_message(_bot: Bot, _msg: Message) -> HandlerResult
 /* Start command.
  * Displays a welcome message to the user.
  */
-pub async fn action_start(bot: Bot, msg: Message) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+pub async fn action_start(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
     // Inits chat configs
-    init_chat_config(&msg.chat.id.to_string())?;
-
-    let introduction = format!("👋 Hello! I'm PayScribe! 😊\n\n🧚‍♀️ I'll be tracking your group payments and working my magic 🪄 to simplify your debts, so you won't have to juggle so many payments back to your friends!");
-    let add_info = &format!("✍️ Ready to track together in this group chat? Start with {COMMAND_ADD_PAYMENT}! You can {COMMAND_VIEW_PAYMENTS} anytime, and I'll help to {COMMAND_EDIT_PAYMENT} or {COMMAND_DELETE_PAYMENT} if you'd like!");
-    let view_info = &format!("🙈 Check out {COMMAND_SPENDINGS} to see who's been splurging! Peek at {COMMAND_BALANCES} for who owes what, but don't forget to {COMMAND_PAY_BACK} your friends!");
-    let closing =
-        &format!("🤗 Have fun tracking, and don't hesitate to ask me for {COMMAND_HELP} anytime!");
+    let chat_id = msg.chat.id.to_string();
+    init_chat_config(&redis, &chat_id).await?;
+
+    let introduction = t(&redis, &chat_id, START_INTRODUCTION, &[]).await;
+    let add_info = t(&redis, &chat_id, START_ADD_INFO, &[]).await;
+    let view_info = t(&redis, &chat_id, START_VIEW_INFO, &[]).await;
+    let closing = t(&redis, &chat_id, START_CLOSING, &[]).await;
+    let notification_info = match (msg.chat.is_private(), msg.from()) {
+        (true, Some(user)) => {
+            update_payment_notifications(
+                &redis,
+                &user.id.to_string(),
+                user.username.as_deref(),
+                true,
+            )
+            .await?;
+            format!("\n\n{}", t(&redis, &chat_id, NOTIFICATION_ON, &[]).await)
+        }
+        _ => "".to_string(),
+    };
     send_bot_message(
         &bot,
         &msg,
-        format!("{introduction}\n\n{add_info}\n\n{view_info}\n\n{closing}"),
+        format!("{introduction}\n\n{add_info}\n\n{view_info}\n\n{closing}{notification_info}"),
     )
     .await?;
     Ok(())
 }
 
 /* Help command.
- * Displays a list of commands available to the user.
+ * Displays a menu of all commands, each opening a page on how to use it.
+ * Displays the page of a command directly if one is given, like /help addpayment.
  */
-pub async fn action_help(bot: Bot, msg: Message) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+pub async fn action_help(redis: Redis, bot: Bot, msg: Message, command: String) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
-    let mut commands = Command::descriptions().to_string();
-    commands = commands.replace("–", "\\—");
+    let command = command.trim();
+    if let Some(topic) = get_help_topic(command) {
+        send_bot_message(&bot, &msg, format_help_topic(topic))
+            .reply_markup(make_keyboard_help_topic(
+                &retrieve_language(&redis, &msg.chat.id.to_string()).await,
+            ))
+            .await?;
+        return Ok(());
+    }
 
-    let user_guide_info = &format!("🆘 For all the nitty\\-gritty details on supported 🕔 time zones, 💵 currencies, and more, check out my [User Guide]({USER_GUIDE_URL})\\!");
-    let feedback_info = &format!(
-        "💖 And if you have any [feedback]({FEEDBACK_URL}) for me, I'd love to hear it\\!"
-    );
+    let unknown = if command.is_empty() {
+        None
+    } else {
+        Some(command)
+    };
+    let menu = display_help_menu(&redis, &msg.chat.id.to_string(), unknown).await;
+    send_markdown_message(&bot, &msg, menu)
+        .reply_markup(make_keyboard_help_menu())
+        .await?;
 
-    send_bot_message(
-        &bot,
-        &msg,
-        format!(
-            "⭐️ *My Commands* ⭐️\n\n{}\n\n{user_guide_info}\n\n{feedback_info}",
-            commands
-        ),
-    )
-    .parse_mode(ParseMode::MarkdownV2)
-    .await?;
+    Ok(())
+}
+
+/* Navigates the help menu.
+ * Bot receives a callback query from the help menu or a help page.
+ * Opens the page of the command chosen, or goes back to the menu.
+ */
+pub async fn action_help_menu(redis: Redis, bot: Bot, query: CallbackQuery) -> HandlerResult {
+    bot.answer_callback_query(query.id.clone()).await?;
+
+    let command = match query
+        .data
+        .as_deref()
+        .and_then(|data| data.strip_prefix(HELP_PREFIX))
+    {
+        Some(command) => command.to_string(),
+        None => return Ok(()),
+    };
+    let msg = match query.message {
+        Some(msg) => msg,
+        None => return Ok(()),
+    };
+
+    match get_help_topic(&command) {
+        Some(topic) => {
+            edit_bot_message(&bot, msg.chat.id, msg.id, format_help_topic(topic))
+                .reply_markup(make_keyboard_help_topic(
+                    &retrieve_language(&redis, &msg.chat.id.to_string()).await,
+                ))
+                .await?;
+        }
+        None => {
+            let menu = display_help_menu(&redis, &msg.chat.id.to_string(), None).await;
+            edit_markdown_message(&bot, msg.chat.id, msg.id, menu)
+                .reply_markup(make_keyboard_help_menu())
+                .await?;
+        }
+    }
 
     Ok(())
 }
@@ -110,15 +234,15 @@ pub async fn action_help(bot: Bot, msg: Message) -> HandlerResult {
 /* Cancel command.
  * Called when state is at start, thus nothing to cancel.
  */
-pub async fn action_cancel(bot: Bot, msg: Message) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+pub async fn action_cancel(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
     send_bot_message(
         &bot,
         &msg,
-        format!("❌ I'm not doing anything... 👀\nThere's nothing to cancel!"),
+        t(&redis, &msg.chat.id.to_string(), CANCEL_NOTHING, &[]).await,
     )
     .await?;
     Ok(())
@@ -0,0 +1,312 @@
+use teloxide::{net::Download, prelude::*, types::Message};
+
+use crate::bot::{
+    currency::CurrencyFormat,
+    handler::{
+        constants::CHAT_PAYMENTS_MAX,
+        utils::{
+            assert_admin_only, assert_handle_request_limit, display_currency_amount,
+            edit_bot_message, parse_username, retrieve_allowed_currencies,
+            retrieve_currency_format, retrieve_language, retrieve_number_format, send_bot_message,
+            t, validate_debtors, validate_description, BotError, HandlerResult,
+        },
+    },
+    i18n::{
+        translate, Text, IMPORT_AMOUNT_NOT_POSITIVE, IMPORT_CURRENCY_NOT_ALLOWED, IMPORT_DONE,
+        IMPORT_DONE_ONE, IMPORT_DOWNLOAD_FAILED, IMPORT_FILE_ERROR, IMPORT_INSTRUCTIONS,
+        IMPORT_MORE, IMPORT_NOT_CSV, IMPORT_NO_USERNAME, IMPORT_OVER_LIMIT, IMPORT_PAYMENTS_NONE,
+        IMPORT_PAYMENT_LINE, IMPORT_PREVIEW, IMPORT_PREVIEW_ONE, IMPORT_PROGRESS,
+        IMPORT_ROWS_UNREADABLE, IMPORT_ROW_ERROR, IMPORT_TOO_LARGE, UNKNOWN_ERROR,
+    },
+    import::{read_payments_csv, ImportedPayment},
+    processor::{import_payments, retrieve_payment_count},
+    redis::{Payment, Redis},
+};
+
+/* Utilities */
+const IMPORT_SIZE_MAX: u32 = 1024 * 1024;
+const IMPORT_PREVIEW_MAX: usize = 5;
+const IMPORT_ERRORS_MAX: usize = 5;
+const IMPORT_BATCH_SIZE: usize = 20;
+
+// Downloads and reads the CSV file that a message replies to
+async fn read_import_file(
+    redis: &Redis,
+    bot: &Bot,
+    msg: &Message,
+    language: &str,
+) -> Result<Vec<ImportedPayment>, BotError> {
+    let document = match msg.reply_to_message().and_then(|reply| reply.document()) {
+        Some(document) => document,
+        None => return Err(BotError::UserError(Text::new(IMPORT_INSTRUCTIONS))),
+    };
+    if document.file.size > IMPORT_SIZE_MAX {
+        return Err(BotError::UserError(Text::new(IMPORT_TOO_LARGE)));
+    }
+
+    let file = bot.get_file(&document.file.id).await?;
+    let mut content: Vec<u8> = Vec::new();
+    if bot.download_file(&file.path, &mut content).await.is_err() {
+        return Err(BotError::UserError(Text::new(IMPORT_DOWNLOAD_FAILED)));
+    }
+
+    let text = match String::from_utf8(content) {
+        Ok(text) => text,
+        Err(_) => {
+            return Err(BotError::UserError(
+                Text::new(IMPORT_NOT_CSV).then(Text::new(IMPORT_INSTRUCTIONS)),
+            ))
+        }
+    };
+    let number_format = retrieve_number_format(redis, &msg.chat.id.to_string()).await;
+    let imported = match read_payments_csv(&text, number_format) {
+        Ok(imported) => imported,
+        Err(err) => {
+            return Err(BotError::UserError(
+                Text::new(IMPORT_FILE_ERROR)
+                    .arg("error", err.translate(language))
+                    .then(Text::new(IMPORT_INSTRUCTIONS)),
+            ))
+        }
+    };
+
+    // Rows that cannot be read are reported all at once, so that the file can be fixed
+    let mut errors = imported.errors;
+    let mut payments: Vec<ImportedPayment> = Vec::new();
+    let allowed_currencies = retrieve_allowed_currencies(redis, &msg.chat.id.to_string()).await;
+    for payment in imported.payments {
+        match validate_imported_payment(payment, &allowed_currencies) {
+            Ok(payment) => payments.push(payment),
+            Err((row, err)) => errors.push((row, err)),
+        }
+    }
+    errors.sort_by_key(|(row, _)| *row);
+
+    if !errors.is_empty() {
+        let mut message = String::new();
+        for (row, err) in errors.iter().take(IMPORT_ERRORS_MAX) {
+            message.push_str(&translate(
+                language,
+                IMPORT_ROW_ERROR,
+                &[
+                    ("row", &row.to_string()),
+                    ("error", &err.translate(language)),
+                ],
+            ));
+            message.push('\n');
+        }
+        if errors.len() > IMPORT_ERRORS_MAX {
+            let count = (errors.len() - IMPORT_ERRORS_MAX).to_string();
+            message.push_str(&translate(language, IMPORT_MORE, &[("count", &count)]));
+            message.push('\n');
+        }
+        return Err(BotError::UserError(
+            Text::new(IMPORT_ROWS_UNREADABLE).arg("errors", message),
+        ));
+    }
+    if payments.is_empty() {
+        return Err(BotError::UserError(Text::new(IMPORT_PAYMENTS_NONE)));
+    }
+
+    Ok(payments)
+}
+
+// Validates the description, users and currency of a payment read from a file
+fn validate_imported_payment(
+    payment: ImportedPayment,
+    allowed_currencies: &[String],
+) -> Result<ImportedPayment, (usize, Text)> {
+    let row = payment.row;
+    let reason = |err: BotError| match err {
+        BotError::UserError(text) => (row, text),
+        _ => (row, Text::new(UNKNOWN_ERROR)),
+    };
+
+    let description = validate_description(&payment.description).map_err(reason)?;
+    let creditor = parse_username(&payment.creditor).map_err(reason)?;
+    let mut debts: Vec<(String, i64)> = Vec::new();
+    for (user, amount) in payment.debts {
+        debts.push((parse_username(&user).map_err(reason)?, amount));
+    }
+    validate_debtors(&debts).map_err(reason)?;
+    if !allowed_currencies.is_empty() && !allowed_currencies.contains(&payment.currency.0) {
+        return Err((
+            row,
+            Text::new(IMPORT_CURRENCY_NOT_ALLOWED).arg("currency", &payment.currency.0),
+        ));
+    }
+    if payment.total <= 0 {
+        return Err((row, Text::new(IMPORT_AMOUNT_NOT_POSITIVE)));
+    }
+
+    Ok(ImportedPayment {
+        description,
+        creditor,
+        debts,
+        ..payment
+    })
+}
+
+// Displays a payment read from a file, in a single line
+fn display_imported_payment(
+    payment: &ImportedPayment,
+    currency_format: CurrencyFormat,
+    language: &str,
+) -> String {
+    let users: Vec<&str> = payment
+        .debts
+        .iter()
+        .map(|(user, _)| user.as_str())
+        .collect();
+    translate(
+        language,
+        IMPORT_PAYMENT_LINE,
+        &[
+            ("description", &payment.description),
+            (
+                "amount",
+                &display_currency_amount(payment.total, payment.currency.clone(), currency_format),
+            ),
+            ("payer", &payment.creditor),
+            ("users", &users.join(", @")),
+        ],
+    )
+}
+
+/* Imports payments into a group chat from a CSV file, such as a Splitwise export.
+ * Must be a reply to the file. Shows a preview of the payments first,
+ * and only adds them once confirmed, in batches with progress updates.
+ */
+pub async fn action_import(redis: Redis, bot: Bot, msg: Message, args: String) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+    if !assert_admin_only(&redis, &bot, &msg).await? {
+        return Ok(());
+    }
+
+    let user = match msg.from() {
+        Some(user) => user,
+        None => return Ok(()),
+    };
+    let chat_id = msg.chat.id.to_string();
+    let language = retrieve_language(&redis, &chat_id).await;
+    let sender_id = user.id.to_string();
+    let sender_username = match user.username.as_deref().map(parse_username) {
+        Some(Ok(username)) => username,
+        _ => {
+            send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &chat_id, IMPORT_NO_USERNAME, &[]).await,
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let payments = match read_import_file(&redis, &bot, &msg, &language).await {
+        Ok(payments) => payments,
+        Err(BotError::UserError(reply)) => {
+            send_bot_message(&bot, &msg, reply.translate(&language)).await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+
+    if args.trim().to_lowercase() != "confirm" {
+        let currency_format = retrieve_currency_format(&redis, &chat_id).await;
+        let mut preview = String::new();
+        for (index, payment) in payments.iter().take(IMPORT_PREVIEW_MAX).enumerate() {
+            preview.push_str(&format!(
+                "{}. {}\n",
+                index + 1,
+                display_imported_payment(payment, currency_format, &language)
+            ));
+        }
+        if payments.len() > IMPORT_PREVIEW_MAX {
+            let count = (payments.len() - IMPORT_PREVIEW_MAX).to_string();
+            preview.push_str(&translate(&language, IMPORT_MORE, &[("count", &count)]));
+            preview.push('\n');
+        }
+        let key = if payments.len() == 1 {
+            IMPORT_PREVIEW_ONE
+        } else {
+            IMPORT_PREVIEW
+        };
+        let reply = translate(
+            &language,
+            key,
+            &[
+                ("count", &payments.len().to_string()),
+                ("preview", &preview),
+            ],
+        );
+        send_bot_message(&bot, &msg, reply).await?;
+        return Ok(());
+    }
+
+    if retrieve_payment_count(&redis, &chat_id).await? + payments.len() > CHAT_PAYMENTS_MAX {
+        let reply = translate(
+            &language,
+            IMPORT_OVER_LIMIT,
+            &[("max", &CHAT_PAYMENTS_MAX.to_string())],
+        );
+        send_bot_message(&bot, &msg, reply).await?;
+        return Ok(());
+    }
+
+    let count = payments.len();
+    let total = count.to_string();
+    let progress_message = |done: usize| {
+        translate(
+            &language,
+            IMPORT_PROGRESS,
+            &[("done", &done.to_string()), ("count", &total)],
+        )
+    };
+    let progress = send_bot_message(&bot, &msg, progress_message(0)).await?.id;
+    let mut imported = 0;
+    for batch in payments.chunks(IMPORT_BATCH_SIZE) {
+        let batch: Vec<Payment> = batch
+            .iter()
+            .map(|payment| Payment {
+                description: payment.description.clone(),
+                datetime: payment.datetime.clone().unwrap_or(msg.date.to_string()),
+                creditor: payment.creditor.clone(),
+                currency: payment.currency.0.clone(),
+                total: payment.total,
+                debts: payment.debts.clone(),
+            })
+            .collect();
+        imported += batch.len();
+        import_payments(&redis, &chat_id, &sender_username, &sender_id, batch).await?;
+        edit_bot_message(&bot, msg.chat.id, progress, progress_message(imported)).await?;
+    }
+
+    edit_bot_message(
+        &bot,
+        msg.chat.id,
+        progress,
+        translate(
+            &language,
+            if count == 1 {
+                IMPORT_DONE_ONE
+            } else {
+                IMPORT_DONE
+            },
+            &[("count", &total)],
+        ),
+    )
+    .await?;
+
+    // Logging
+    log::info!(
+        "Import - User {} imported {} payments in chat {}",
+        sender_id,
+        count,
+        chat_id
+    );
+
+    Ok(())
+}
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, Utc};
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    currency::{get_default_currency, CurrencyFormat},
+    handler::utils::{
+        assert_handle_request_limit, display_currency_amount, display_name, get_currency,
+        retrieve_currency_format, retrieve_language, send_bot_message, HandlerResult,
+    },
+    i18n::{
+        translate, LEADERBOARD_DAYS, LEADERBOARD_DAYS_ONE, LEADERBOARD_EMPTY,
+        LEADERBOARD_FASTEST_SETTLERS, LEADERBOARD_FREQUENT_DEBTORS, LEADERBOARD_HOURS,
+        LEADERBOARD_HOURS_ONE, LEADERBOARD_INSTRUCTIONS, LEADERBOARD_MINUTES,
+        LEADERBOARD_MINUTES_ONE, LEADERBOARD_PAYMENTS, LEADERBOARD_PAYMENTS_ONE,
+        LEADERBOARD_PERIOD_ALL, LEADERBOARD_PERIOD_MONTH, LEADERBOARD_PERIOD_UNKNOWN,
+        LEADERBOARD_PERIOD_WEEK, LEADERBOARD_PERIOD_YEAR, LEADERBOARD_SETTLE_TIME,
+        LEADERBOARD_SUMMARY, LEADERBOARD_SUMMARY_ONE, LEADERBOARD_TOP_PAYERS,
+        LEADERBOARD_TOP_PAYERS_CURRENCY,
+    },
+    processor::{retrieve_display_names, retrieve_leaderboard, Leaderboard},
+    redis::Redis,
+};
+
+/* Utilities */
+const LEADERBOARD_PLACES_MAX: usize = 3;
+const LEADERBOARD_MEDALS: [&str; LEADERBOARD_PLACES_MAX] = ["🥇", "🥈", "🥉"];
+
+// Parses the period of the leaderboard, returning the key of its name and number of days, if limited
+fn parse_period(text: &str) -> Option<(&'static str, Option<i64>)> {
+    match text.trim().to_lowercase().as_str() {
+        "week" | "weekly" => Some((LEADERBOARD_PERIOD_WEEK, Some(7))),
+        "" | "month" | "monthly" => Some((LEADERBOARD_PERIOD_MONTH, Some(30))),
+        "year" | "yearly" => Some((LEADERBOARD_PERIOD_YEAR, Some(365))),
+        "all" | "alltime" | "all-time" => Some((LEADERBOARD_PERIOD_ALL, None)),
+        _ => None,
+    }
+}
+
+// Displays a count, with the message for exactly one if it is one
+fn display_count(language: &str, count: i64, one: &str, many: &str) -> String {
+    if count == 1 {
+        translate(language, one, &[])
+    } else {
+        translate(language, many, &[("count", &count.to_string())])
+    }
+}
+
+// Displays a duration in seconds, in the largest sensible unit
+fn display_duration(language: &str, seconds: i64) -> String {
+    if seconds < 3600 {
+        display_count(
+            language,
+            (seconds / 60).max(1),
+            LEADERBOARD_MINUTES_ONE,
+            LEADERBOARD_MINUTES,
+        )
+    } else if seconds < 86400 {
+        display_count(
+            language,
+            seconds / 3600,
+            LEADERBOARD_HOURS_ONE,
+            LEADERBOARD_HOURS,
+        )
+    } else {
+        display_count(
+            language,
+            seconds / 86400,
+            LEADERBOARD_DAYS_ONE,
+            LEADERBOARD_DAYS,
+        )
+    }
+}
+
+// Displays the top places of a ranking, with a medal for each place
+fn display_ranking<F>(
+    ranking: &[(String, i64)],
+    names: &HashMap<String, String>,
+    value: F,
+) -> String
+where
+    F: Fn(i64) -> String,
+{
+    ranking
+        .iter()
+        .take(LEADERBOARD_PLACES_MAX)
+        .zip(LEADERBOARD_MEDALS)
+        .map(|((user, amount), medal)| {
+            format!(
+                "    {medal} {} ({})\n",
+                display_name(user, names),
+                value(*amount)
+            )
+        })
+        .collect()
+}
+
+fn display_leaderboard(
+    leaderboard: &Leaderboard,
+    names: &HashMap<String, String>,
+    currency_format: CurrencyFormat,
+    language: &str,
+) -> String {
+    let mut message = String::new();
+
+    for (currency, payers) in &leaderboard.top_payers {
+        let currency = get_currency(currency).unwrap_or(get_default_currency());
+        let header = if currency.0 == get_default_currency().0 {
+            translate(language, LEADERBOARD_TOP_PAYERS, &[])
+        } else {
+            translate(
+                language,
+                LEADERBOARD_TOP_PAYERS_CURRENCY,
+                &[("currency", &currency.0)],
+            )
+        };
+        message.push_str(&header);
+        message.push_str(&display_ranking(payers, names, |amount| {
+            display_currency_amount(amount, currency.clone(), currency_format)
+        }));
+        message.push('\n');
+    }
+
+    if !leaderboard.frequent_debtors.is_empty() {
+        message.push_str(&translate(language, LEADERBOARD_FREQUENT_DEBTORS, &[]));
+        message.push_str(&display_ranking(
+            &leaderboard.frequent_debtors,
+            names,
+            |count| {
+                display_count(
+                    language,
+                    count,
+                    LEADERBOARD_PAYMENTS_ONE,
+                    LEADERBOARD_PAYMENTS,
+                )
+            },
+        ));
+        message.push('\n');
+    }
+
+    if !leaderboard.fastest_settlers.is_empty() {
+        message.push_str(&translate(language, LEADERBOARD_FASTEST_SETTLERS, &[]));
+        message.push_str(&display_ranking(
+            &leaderboard.fastest_settlers,
+            names,
+            |seconds| {
+                translate(
+                    language,
+                    LEADERBOARD_SETTLE_TIME,
+                    &[("duration", &display_duration(language, seconds))],
+                )
+            },
+        ));
+        message.push('\n');
+    }
+
+    message
+}
+
+/* Views the leaderboard of a group chat over a period.
+ * Ranks who paid the most, who owes most often, and who settles up the fastest.
+ */
+pub async fn action_leaderboard(
+    redis: Redis,
+    bot: Bot,
+    msg: Message,
+    args: String,
+) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let language = retrieve_language(&redis, &chat_id).await;
+    let instructions = translate(&language, LEADERBOARD_INSTRUCTIONS, &[]);
+    let (period, days) = match parse_period(&args) {
+        Some(period) => period,
+        None => {
+            send_bot_message(
+                &bot,
+                &msg,
+                translate(
+                    &language,
+                    LEADERBOARD_PERIOD_UNKNOWN,
+                    &[("instructions", &instructions)],
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+    let period_name = translate(&language, period, &[]);
+
+    let since = days.map(|days| Utc::now().naive_utc() - Duration::days(days));
+    let leaderboard = retrieve_leaderboard(&redis, &chat_id, since).await?;
+
+    let reply = if leaderboard.payments == 0 {
+        translate(&language, LEADERBOARD_EMPTY, &[("period", &period_name)])
+    } else {
+        let names = retrieve_display_names(&redis, &chat_id)
+            .await
+            .unwrap_or_default();
+        let key = if leaderboard.payments == 1 {
+            LEADERBOARD_SUMMARY_ONE
+        } else {
+            LEADERBOARD_SUMMARY
+        };
+        translate(
+            &language,
+            key,
+            &[
+                ("period", &period_name),
+                ("count", &leaderboard.payments.to_string()),
+                (
+                    "leaderboard",
+                    &display_leaderboard(
+                        &leaderboard,
+                        &names,
+                        retrieve_currency_format(&redis, &chat_id).await,
+                        &language,
+                    ),
+                ),
+                ("instructions", &instructions),
+            ],
+        )
+    };
+
+    // Logging
+    log::info!(
+        "Leaderboard - Leaderboard for {} viewed in chat {}",
+        period,
+        chat_id
+    );
+
+    send_bot_message(&bot, &msg, reply).await?;
+    Ok(())
+}
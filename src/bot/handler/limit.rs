@@ -0,0 +1,315 @@
+use chrono::Utc;
+use teloxide::prelude::*;
+
+use crate::bot::{
+    currency::{get_default_currency, Currency, NumberFormat},
+    handler::utils::{
+        assert_admin_only, assert_handle_request_limit, display_currency_amount, display_username,
+        get_currency, parse_currency_amount, parse_username, retrieve_allowed_currencies,
+        retrieve_currency_format, retrieve_language, retrieve_number_format, send_bot_message, t,
+        t_text, use_currency, BotError, HandlerResult,
+    },
+    i18n::{
+        translate, Text, LIMITS_HEADER, LIMITS_NONE, LIMIT_CROSSED, LIMIT_INSTRUCTIONS,
+        LIMIT_REMOVED, LIMIT_SET, MONTH_FORMAT, NOT_UNDERSTOOD, WEEKLY_CAP_REMOVED, WEEKLY_CAP_SET,
+        WEEKLY_CAP_STATUS,
+    },
+    processor::{
+        retrieve_spending_limit_warnings, retrieve_spending_limits, retrieve_weekly_cap,
+        update_spending_limit, update_weekly_cap,
+    },
+    redis::{Redis, SpendingLimit, WeeklyCap},
+};
+
+/* Utilities */
+const WEEKLY_CAP_ARGUMENT: &str = "cap";
+
+// Displays the weekly spending cap of a chat, with the spendings of the chat this week
+pub async fn display_weekly_cap(redis: &Redis, chat_id: &str, cap: &WeeklyCap) -> String {
+    let currency = get_currency(&cap.currency).unwrap_or(get_default_currency());
+    let currency = use_currency(redis, currency, chat_id).await;
+    let currency_format = retrieve_currency_format(redis, chat_id).await;
+    format!(
+        "{} / {}",
+        display_currency_amount(cap.spent, currency.clone(), currency_format),
+        display_currency_amount(cap.cap, currency, currency_format)
+    )
+}
+
+// Parses the arguments to set the weekly spending cap, as the cap, if any
+fn parse_weekly_cap_arguments(
+    args: &str,
+    number_format: NumberFormat,
+    allowed_currencies: &[String],
+) -> Result<Option<(i64, Currency)>, BotError> {
+    if args.is_empty() {
+        return Err(BotError::UserError(
+            Text::new(NOT_UNDERSTOOD).then(Text::new(LIMIT_INSTRUCTIONS)),
+        ));
+    }
+    if args.to_lowercase() == "off" {
+        return Ok(None);
+    }
+
+    let cap = parse_currency_amount(args, number_format, allowed_currencies)?;
+    Ok(Some(cap))
+}
+
+// Gets the currency to display a spending limit in
+async fn get_limit_currency(redis: &Redis, limit: &SpendingLimit, chat_id: &str) -> Currency {
+    let currency = get_currency(&limit.currency).unwrap_or(get_default_currency());
+    use_currency(redis, currency, chat_id).await
+}
+
+// Displays all spending limits of a chat, with the spendings of each user this month
+async fn display_spending_limits(
+    redis: &Redis,
+    chat_id: &str,
+    limits: &Vec<SpendingLimit>,
+) -> String {
+    let currency_format = retrieve_currency_format(redis, chat_id).await;
+    let mut message = String::new();
+    for limit in limits {
+        let currency = get_limit_currency(redis, limit, chat_id).await;
+        let icon = if limit.spent >= limit.limit {
+            "⚠️"
+        } else {
+            "✅"
+        };
+        message.push_str(&format!(
+            "{icon} {}: {} / {}\n",
+            display_username(redis, chat_id, &limit.username).await,
+            display_currency_amount(limit.spent, currency.clone(), currency_format),
+            display_currency_amount(limit.limit, currency, currency_format)
+        ));
+    }
+    message
+}
+
+// Parses the arguments to set a spending limit, as the username and the limit, if any
+fn parse_limit_arguments(
+    args: &str,
+    number_format: NumberFormat,
+    allowed_currencies: &[String],
+) -> Result<(String, Option<(i64, Currency)>), BotError> {
+    let (username, limit) = match args.split_once(char::is_whitespace) {
+        Some((username, limit)) => (username, limit.trim()),
+        None => {
+            return Err(BotError::UserError(
+                Text::new(NOT_UNDERSTOOD).then(Text::new(LIMIT_INSTRUCTIONS)),
+            ))
+        }
+    };
+
+    let username = parse_username(username)?;
+    if limit.to_lowercase() == "off" {
+        return Ok((username, None));
+    }
+
+    let limit = parse_currency_amount(limit, number_format, allowed_currencies)?;
+    Ok((username, Some(limit)))
+}
+
+/* Sets or views the monthly spending limits of users in a group chat.
+ * Without arguments, shows all limits and the spendings of each user this month.
+ * With a username and an amount, sets the limit of that user, or removes it with "off".
+ */
+pub async fn action_limit(redis: Redis, bot: Bot, msg: Message, args: String) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let args = args.trim();
+    if args.is_empty() {
+        let limits = retrieve_spending_limits(&redis, &chat_id).await?;
+        let language = retrieve_language(&redis, &chat_id).await;
+        let mut reply = if limits.is_empty() {
+            Text::new(LIMITS_NONE)
+                .then(Text::new(LIMIT_INSTRUCTIONS))
+                .translate(&language)
+        } else {
+            let month = Utc::now()
+                .format(&translate(&language, MONTH_FORMAT, &[]))
+                .to_string();
+            format!(
+                "{}\n\n{}",
+                translate(&language, LIMITS_HEADER, &[("month", &month)]),
+                display_spending_limits(&redis, &chat_id, &limits).await
+            )
+        };
+        if let Some(cap) = retrieve_weekly_cap(&redis, &chat_id).await? {
+            let icon = if cap.spent > cap.cap {
+                "⚠️"
+            } else {
+                "🧢"
+            };
+            let cap = display_weekly_cap(&redis, &chat_id, &cap).await;
+            reply = format!(
+                "{icon} {}\n\n{reply}",
+                translate(&language, WEEKLY_CAP_STATUS, &[("cap", &cap)])
+            );
+        }
+        send_bot_message(&bot, &msg, reply).await?;
+        return Ok(());
+    }
+
+    if !assert_admin_only(&redis, &bot, &msg).await? {
+        return Ok(());
+    }
+
+    let (argument, cap_args) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    if argument.to_lowercase() == WEEKLY_CAP_ARGUMENT {
+        return set_weekly_cap(&redis, &bot, &msg, cap_args.trim()).await;
+    }
+
+    let number_format = retrieve_number_format(&redis, &chat_id).await;
+    let allowed_currencies = retrieve_allowed_currencies(&redis, &chat_id).await;
+    let (username, limit) = match parse_limit_arguments(args, number_format, &allowed_currencies) {
+        Ok(arguments) => arguments,
+        Err(BotError::UserError(reply)) => {
+            send_bot_message(&bot, &msg, t_text(&redis, &chat_id, &reply).await).await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+
+    let reply = match &limit {
+        Some((amount, currency)) => {
+            update_spending_limit(&redis, &chat_id, &username, Some((&currency.0, *amount)))
+                .await?;
+            let currency = use_currency(&redis, currency.clone(), &chat_id).await;
+            let amount = display_currency_amount(
+                *amount,
+                currency,
+                retrieve_currency_format(&redis, &chat_id).await,
+            );
+            t(
+                &redis,
+                &chat_id,
+                LIMIT_SET,
+                &[
+                    ("user", &display_username(&redis, &chat_id, &username).await),
+                    ("amount", &amount),
+                ],
+            )
+            .await
+        }
+        None => {
+            update_spending_limit(&redis, &chat_id, &username, None).await?;
+            t(
+                &redis,
+                &chat_id,
+                LIMIT_REMOVED,
+                &[("user", &display_username(&redis, &chat_id, &username).await)],
+            )
+            .await
+        }
+    };
+    send_bot_message(&bot, &msg, reply).await?;
+
+    // Logging
+    log::info!(
+        "Spending Limit - Limit of user {} set in chat {}: {:?}",
+        username,
+        chat_id,
+        limit
+    );
+
+    Ok(())
+}
+
+// Sets or removes the weekly spending cap of a group chat
+async fn set_weekly_cap(redis: &Redis, bot: &Bot, msg: &Message, args: &str) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    let number_format = retrieve_number_format(redis, &chat_id).await;
+    let allowed_currencies = retrieve_allowed_currencies(redis, &chat_id).await;
+    let cap = match parse_weekly_cap_arguments(args, number_format, &allowed_currencies) {
+        Ok(cap) => cap,
+        Err(BotError::UserError(reply)) => {
+            send_bot_message(bot, msg, t_text(redis, &chat_id, &reply).await).await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+
+    let reply = match &cap {
+        Some((amount, currency)) => {
+            update_weekly_cap(redis, &chat_id, Some((&currency.0, *amount))).await?;
+            let currency = use_currency(redis, currency.clone(), &chat_id).await;
+            let amount = display_currency_amount(
+                *amount,
+                currency,
+                retrieve_currency_format(redis, &chat_id).await,
+            );
+            t(redis, &chat_id, WEEKLY_CAP_SET, &[("amount", &amount)]).await
+        }
+        None => {
+            update_weekly_cap(redis, &chat_id, None).await?;
+            t(redis, &chat_id, WEEKLY_CAP_REMOVED, &[]).await
+        }
+    };
+    send_bot_message(bot, msg, reply).await?;
+
+    // Logging
+    log::info!(
+        "Spending Limit - Weekly cap set in chat {}: {:?}",
+        chat_id,
+        cap
+    );
+
+    Ok(())
+}
+
+/* Notifies a group chat of users who have just crossed their monthly spending limit.
+ * Called after payments are added or changed, and each user is only notified once a month.
+ */
+pub async fn notify_spending_limits(redis: &Redis, bot: &Bot, msg: &Message) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    let warnings = match retrieve_spending_limit_warnings(redis, &chat_id).await {
+        Ok(warnings) => warnings,
+        Err(err) => {
+            log::error!(
+                "Spending Limit - Failed to check spending limits for chat {}: {}",
+                chat_id,
+                err.to_string()
+            );
+            return Ok(());
+        }
+    };
+
+    let currency_format = retrieve_currency_format(redis, &chat_id).await;
+    for warning in warnings {
+        let currency = get_limit_currency(redis, &warning, &chat_id).await;
+        let reply = t(
+            redis,
+            &chat_id,
+            LIMIT_CROSSED,
+            &[
+                (
+                    "user",
+                    &display_username(redis, &chat_id, &warning.username).await,
+                ),
+                (
+                    "spent",
+                    &display_currency_amount(warning.spent, currency.clone(), currency_format),
+                ),
+                (
+                    "limit",
+                    &display_currency_amount(warning.limit, currency, currency_format),
+                ),
+            ],
+        )
+        .await;
+        send_bot_message(bot, msg, reply).await?;
+
+        // Logging
+        log::info!(
+            "Spending Limit - User {} crossed their spending limit in chat {}",
+            warning.username,
+            chat_id
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use teloxide::{
+    payloads::{AnswerCallbackQuerySetters, PinChatMessageSetters, SendMessageSetters},
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message},
+};
+
+use crate::bot::{
+    handler::utils::{
+        display_balances, display_username, get_statement_options, send_bot_message, HandlerResult,
+    },
+    processor::{
+        is_username_equal, retrieve_chat_member, retrieve_debts, retrieve_valid_currencies,
+        ProcessError, StatementOption,
+    },
+    redis::{Debt, Redis},
+};
+
+/* Utilities */
+const PIN_CALLBACK: &str = "PinLeftMember";
+
+// Checks if a message is a notice of a user leaving the chat.
+pub fn is_left_chat_member(msg: Message) -> bool {
+    msg.left_chat_member().is_some()
+}
+
+// Checks if a callback query is a request to pin the balances of a user who left.
+// Such queries can come from anyone in the chat, regardless of the current state.
+pub fn is_pin_query(query: CallbackQuery) -> bool {
+    query.data.as_deref() == Some(PIN_CALLBACK)
+}
+
+// Composes the outstanding balances involving a user, across all currencies
+// Returns None if the user is settled up
+async fn display_member_balances(
+    redis: &Redis,
+    chat_id: &str,
+    username: &str,
+) -> Result<Option<String>, ProcessError> {
+    let currencies = retrieve_valid_currencies(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    let options = get_statement_options(redis, chat_id, currencies).await;
+    let multiple = options.len() > 1;
+
+    let mut balances = String::new();
+    for option in options {
+        let debts: Vec<Debt> = retrieve_debts(redis, chat_id, option.clone())
+            .await?
+            .into_iter()
+            .filter(|debt| {
+                is_username_equal(&debt.debtor, username)
+                    || is_username_equal(&debt.creditor, username)
+            })
+            .collect();
+        if debts.is_empty() {
+            continue;
+        }
+        if multiple {
+            if let StatementOption::Currency(currency) = &option {
+                balances.push_str(&format!("In {currency}:\n"));
+            }
+        }
+        balances.push_str(&display_balances(redis, chat_id, &debts).await);
+        if multiple {
+            balances.push('\n');
+        }
+    }
+
+    if balances.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(balances.trim_end().to_string()))
+    }
+}
+
+/* Posts the outstanding balances of a user who left a group chat.
+ * Only posts if the user is known in the chat, and is not settled up.
+ * Anyone can then choose to pin the message, so that the balances are not forgotten.
+ */
+pub async fn action_left_chat_member(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    let user = match msg.left_chat_member() {
+        Some(user) if !user.is_bot => user,
+        _ => return Ok(()),
+    };
+
+    let chat_id = msg.chat.id.to_string();
+    let user_id = user.id.to_string();
+    let username =
+        match retrieve_chat_member(&redis, &chat_id, &user_id, user.username.as_deref()).await? {
+            Some(username) => username,
+            None => return Ok(()),
+        };
+
+    let balances = match display_member_balances(&redis, &chat_id, &username).await? {
+        Some(balances) => balances,
+        None => return Ok(()),
+    };
+
+    let keyboard = InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
+        "📌 Pin",
+        PIN_CALLBACK,
+    )]]);
+    send_bot_message(
+        &bot,
+        &msg,
+        format!(
+            "👋 {} has left the chat, but there are still outstanding balances with them:\n\n{}\n\nDon't forget to settle up!",
+            display_username(&redis, &chat_id, &username).await,
+            balances
+        ),
+    )
+    .reply_markup(keyboard)
+    .await?;
+
+    // Logging
+    log::info!(
+        "Left Chat Member - Posted outstanding balances of user {} in chat {}",
+        user_id,
+        chat_id
+    );
+
+    Ok(())
+}
+
+/* Pins the outstanding balances of a user who left a group chat.
+ * Bot receives a callback query from the pin button, which is removed once pinned.
+ */
+pub async fn action_pin_left_member(bot: Bot, query: CallbackQuery) -> HandlerResult {
+    let msg = match query.message {
+        Some(msg) => msg,
+        None => {
+            bot.answer_callback_query(query.id).await?;
+            return Ok(());
+        }
+    };
+
+    match bot
+        .pin_chat_message(msg.chat.id, msg.id)
+        .disable_notification(true)
+        .await
+    {
+        Ok(_) => {
+            bot.answer_callback_query(query.id).await?;
+            bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+
+            // Logging
+            log::info!(
+                "Pin Left Member - Pinned outstanding balances in chat {}",
+                msg.chat.id
+            );
+        }
+        Err(err) => {
+            bot.answer_callback_query(query.id)
+                .text("🥺 Sorry, I need permission to pin messages in this chat first!")
+                .await?;
+
+            // Logging
+            log::error!(
+                "Pin Left Member - Failed to pin message in chat {}: {}",
+                msg.chat.id,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -1,51 +1,147 @@
 // Exported functions
+pub use self::acknowledgement::{action_payment_reaction, parse_message_reaction};
 pub use self::add_payment::{
-    action_add_confirm, action_add_creditor, action_add_debt, action_add_debt_selection,
-    action_add_description, action_add_edit, action_add_edit_menu, action_add_payment,
-    action_add_total, block_add_payment, cancel_add_payment, handle_repeated_add_payment,
-    AddDebtsFormat, AddPaymentEdit, AddPaymentParams,
+    action_add_confirm, action_add_creditor, action_add_debt, action_add_debt_items,
+    action_add_debt_items_message, action_add_debt_picker, action_add_debt_picker_message,
+    action_add_debt_selection, action_add_description, action_add_edit, action_add_edit_menu,
+    action_add_payment, action_add_payment_duplicate, action_add_total, action_add_total_currency,
+    block_add_payment, cancel_add_payment, get_debts_instructions, handle_repeated_add_payment,
+    AddDebtsFormat, AddPaymentEdit, AddPaymentParams, DebtItem, Surcharge,
 };
+pub use self::adjust::action_adjust;
+pub use self::alias::action_alias;
+pub use self::approval::{
+    action_confirm_pending_payment, action_resolve_pending_payment, is_approval_query,
+    is_confirmation_query,
+};
+pub use self::archive::handle_monthly_archive;
+pub use self::audit::action_audit;
+pub use self::backup::{action_archive, action_restore};
+pub use self::bot_stats::action_bot_stats;
+pub use self::consolidate::action_consolidate;
+pub use self::debug::action_debug_balances;
 pub use self::delete_payment::{
     action_delete_payment, action_delete_payment_confirm, block_delete_payment,
     cancel_delete_payment, handle_repeated_delete_payment, no_delete_payment,
 };
+pub use self::detect::{
+    action_detect_payment, action_resolve_detected_payment, is_detected_payment,
+    is_detected_payment_query,
+};
+pub use self::digest::handle_weekly_digest;
+pub use self::dispute::{action_resolve_payment_dispute, is_payment_dispute_query};
 pub use self::edit_payment::{
-    action_edit_payment, action_edit_payment_confirm, action_edit_payment_debts,
-    action_edit_payment_edit, block_edit_payment, cancel_edit_payment,
-    handle_repeated_edit_payment, no_edit_payment, EditPaymentParams,
+    action_edit_payment, action_edit_payment_confirm, action_edit_payment_debt_picker,
+    action_edit_payment_debt_picker_message, action_edit_payment_debts, action_edit_payment_edit,
+    block_edit_payment, cancel_edit_payment, handle_repeated_edit_payment, no_edit_payment,
+    EditPaymentParams,
 };
 pub use self::general::{
-    action_cancel, action_help, action_start, callback_invalid_message, invalid_state,
+    action_cancel, action_help, action_help_menu, action_start, callback_invalid_message,
+    invalid_state, is_help_query,
+};
+pub use self::import::action_import;
+pub use self::leaderboard::action_leaderboard;
+pub use self::limit::action_limit;
+pub use self::member::{
+    action_left_chat_member, action_pin_left_member, is_left_chat_member, is_pin_query,
 };
+pub use self::notification::{action_notification_off, is_notification_query};
+pub use self::onboarding::{action_bot_added, is_bot_added};
+pub use self::owed::action_owed;
 pub use self::pay_back::{
-    action_pay_back, action_pay_back_confirm, action_pay_back_currency,
-    action_pay_back_currency_menu, action_pay_back_debts, block_pay_back, cancel_pay_back,
-    handle_repeated_pay_back, PayBackParams,
+    action_pay_back, action_pay_back_confirm, action_pay_back_creditors,
+    action_pay_back_creditors_message, action_pay_back_currency, action_pay_back_currency_menu,
+    action_pay_back_debts, block_pay_back, cancel_pay_back, handle_repeated_pay_back,
+    PayBackParams,
 };
+pub use self::paylink::action_paylink;
+pub use self::payment_card::{
+    action_payment_card_details, action_payment_card_details_menu, action_payment_card_menu,
+    action_view_payment_card, block_payment_card, cancel_payment_card,
+};
+pub use self::private::action_private;
+pub use self::recalculate::action_recalculate;
+pub use self::reminder::{action_remind, handle_debt_reminder};
+pub use self::retention::handle_data_retention;
 pub use self::settings::{
-    action_default_currency_menu, action_settings, action_settings_currency_conversion,
+    action_currency_format_menu, action_debt_reminder_menu, action_default_currency_menu,
+    action_language_menu, action_number_format_menu, action_payment_approval_menu,
+    action_rounding_menu, action_settings, action_settings_admin_only,
+    action_settings_currency_conversion, action_settings_debt_reminder,
     action_settings_default_currency, action_settings_erase_messages, action_settings_menu,
-    action_settings_time_zone, action_time_zone_menu, block_settings, cancel_settings,
+    action_settings_monthly_archive, action_settings_payment_approval, action_settings_time_zone,
+    action_settings_treasurer, action_settings_weekly_digest, action_time_zone_menu,
+    action_time_zone_region_menu, action_treasurer_menu, action_verbosity_menu,
+    action_week_start_menu, action_weekly_digest_menu, block_settings, cancel_settings,
     handle_repeated_settings,
 };
 pub use self::spendings::{action_spendings_menu, action_view_spendings};
-pub use self::utils::{SelectPaymentType, StatementOption};
-pub use self::view_balances::{action_balances_menu, action_view_balances};
+pub use self::split::{action_split, action_undo_split, is_undo_split_query};
+pub use self::split_group::action_split_group;
+pub use self::stats::action_stats;
+pub use self::template::action_template;
+pub use self::trash::{action_restore_trash, action_trash, is_trash_restore_query};
+pub use self::trip::action_trip;
+pub use self::tutorial::{action_tutorial, action_tutorial_step, is_tutorial_query};
+pub use self::utils::{
+    action_restricted_message, action_restricted_query, is_restricted_message, is_restricted_query,
+    MessageTracker, PaymentCardDetail, SelectPaymentType,
+};
+pub use self::view_balances::{
+    action_balances_convert_to, action_balances_convert_to_menu, action_balances_menu,
+    action_view_balances, cancel_balances_convert_to,
+};
 pub use self::view_payments::{
-    action_select_payment_delete, action_select_payment_edit, action_select_payment_number,
-    action_view_more, action_view_payments, block_select_payment, cancel_select_payment,
-    handle_repeated_select_payment, Payment,
+    action_select_payment_delete, action_select_payment_duplicate, action_select_payment_edit,
+    action_select_payment_number, action_select_payments_delete, action_view_more,
+    action_view_payments, block_select_payment, cancel_select_payment,
+    handle_repeated_select_payment, no_duplicate_payment, Payment,
 };
 
 // Submodules
+mod acknowledgement;
 mod add_payment;
+mod adjust;
+mod alias;
+mod approval;
+mod archive;
+mod audit;
+mod backup;
+mod bot_stats;
+mod consolidate;
 mod constants;
+mod debug;
 mod delete_payment;
+mod detect;
+mod digest;
+mod dispute;
 mod edit_payment;
+mod export;
 mod general;
+mod import;
+mod leaderboard;
+mod limit;
+mod member;
+mod notification;
+mod onboarding;
+mod owed;
 mod pay_back;
+mod paylink;
+mod payment_card;
+mod private;
+mod recalculate;
+mod reminder;
+mod retention;
 mod settings;
 mod spendings;
+mod split;
+mod split_group;
+mod stats;
+mod template;
+mod trash;
+mod trip;
+mod tutorial;
 mod utils;
 mod view_balances;
 mod view_payments;
@@ -0,0 +1,171 @@
+use teloxide::{
+    payloads::{AnswerCallbackQuerySetters, SendMessageSetters},
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message},
+    ApiError, RequestError,
+};
+
+use crate::bot::{
+    currency::Currency,
+    handler::utils::{
+        display_currency_amount, display_username, retrieve_currency_format, retrieve_language, t,
+        use_currency, with_retry, HandlerResult,
+    },
+    i18n::{
+        translate, translate_button, NOTIFICATION_ADDED, NOTIFICATION_CHAT_DEFAULT,
+        NOTIFICATION_OFF, NOTIFICATION_OFF_FAILED,
+    },
+    processor::{is_username_equal, retrieve_notified_users, update_payment_notifications},
+    redis::Redis,
+};
+
+/* Utilities */
+const NOTIFICATION_OFF_CALLBACK: &str = "Notification:off";
+const NOTIFICATION_OFF_BUTTON: &str = "🔕 Stop These Messages";
+
+// Make keyboard to turn off notifications of payments
+fn make_keyboard_notification_off(language: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
+        translate_button(language, NOTIFICATION_OFF_BUTTON),
+        NOTIFICATION_OFF_CALLBACK,
+    )]])
+}
+
+// Checks if a callback query turns off notifications of payments.
+// Such queries come from direct messages, regardless of the current state.
+pub fn is_notification_query(query: CallbackQuery) -> bool {
+    query.data.as_deref() == Some(NOTIFICATION_OFF_CALLBACK)
+}
+
+/* Notifies each user added to a new payment in a direct message, of their share of it.
+ * Only users who have started the bot in a direct message are notified,
+ * and the user who added the payment is left out.
+ * Failing to notify someone does not affect the payment, and is only logged.
+ */
+pub async fn notify_added_users(
+    redis: &Redis,
+    bot: &Bot,
+    msg: &Message,
+    sender_username: &str,
+    description: &str,
+    currency: Currency,
+    debts: &[(String, i64)],
+) {
+    let chat_id = msg.chat.id.to_string();
+    let usernames: Vec<String> = debts
+        .iter()
+        .filter(|(username, _)| !is_username_equal(username, sender_username))
+        .map(|(username, _)| username.clone())
+        .collect();
+    let notified_users = match retrieve_notified_users(redis, &usernames).await {
+        Ok(notified_users) => notified_users,
+        Err(err) => {
+            log::error!(
+                "Payment Notification - Failed to retrieve users to notify in chat {}: {}",
+                chat_id,
+                err.to_string()
+            );
+            return;
+        }
+    };
+    if notified_users.is_empty() {
+        return;
+    }
+
+    let language = retrieve_language(redis, &chat_id).await;
+    let title = match msg.chat.title() {
+        Some(title) => title.to_string(),
+        None => translate(&language, NOTIFICATION_CHAT_DEFAULT, &[]),
+    };
+    let currency = use_currency(redis, currency, &chat_id).await;
+    let currency_format = retrieve_currency_format(redis, &chat_id).await;
+    let sender = display_username(redis, &chat_id, sender_username).await;
+    for (username, amount) in debts {
+        let user_id = match notified_users.get(&username.to_lowercase()) {
+            Some(user_id) => user_id,
+            None => continue,
+        };
+
+        let notification = translate(
+            &language,
+            NOTIFICATION_ADDED,
+            &[
+                ("description", description),
+                (
+                    "amount",
+                    &display_currency_amount(*amount, currency.clone(), currency_format),
+                ),
+                ("chat", &title),
+                ("sender", &sender),
+            ],
+        );
+        let sent = with_retry(
+            bot.send_message(user_id.clone(), notification)
+                .reply_markup(make_keyboard_notification_off(&language)),
+        )
+        .await;
+        match sent {
+            Ok(_) => {}
+            Err(RequestError::Api(ApiError::BotBlocked | ApiError::UserDeactivated)) => {
+                // The user can no longer be messaged, so stop trying
+                if let Err(err) = update_payment_notifications(redis, user_id, None, false).await {
+                    log::error!(
+                        "Payment Notification - Failed to turn off notifications for user {}: {}",
+                        user_id,
+                        err.to_string()
+                    );
+                }
+            }
+            Err(err) => {
+                log::warn!(
+                    "Payment Notification - Failed to notify user {} of payment in chat {}: {}",
+                    user_id,
+                    chat_id,
+                    err.to_string()
+                );
+            }
+        }
+    }
+}
+
+/* Turns off notifications of payments for a user.
+ * Bot receives a callback query from a notification in a direct message.
+ */
+pub async fn action_notification_off(
+    redis: Redis,
+    bot: Bot,
+    query: CallbackQuery,
+) -> HandlerResult {
+    let user_id = query.from.id.to_string();
+    match update_payment_notifications(&redis, &user_id, None, false).await {
+        Ok(_) => {
+            bot.answer_callback_query(query.id)
+                .text(t(&redis, &user_id, NOTIFICATION_OFF, &[]).await)
+                .show_alert(true)
+                .await?;
+            if let Some(msg) = query.message {
+                bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+            }
+
+            // Logging
+            log::info!(
+                "Payment Notification - Notifications turned off for user {}",
+                user_id
+            );
+        }
+        Err(err) => {
+            bot.answer_callback_query(query.id)
+                .text(t(&redis, &user_id, NOTIFICATION_OFF_FAILED, &[]).await)
+                .await?;
+
+            // Logging
+            log::error!(
+                "Payment Notification - Failed to turn off notifications for user {}: {}",
+                user_id,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
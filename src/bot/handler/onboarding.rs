@@ -0,0 +1,285 @@
+use teloxide::{
+    payloads::SendMessageSetters,
+    prelude::*,
+    types::{ChatId, ChatMemberUpdated},
+};
+
+use crate::bot::{
+    currency::CURRENCY_DEFAULT,
+    dispatcher::State,
+    handler::utils::{
+        cleanup_messages, make_keyboard, retrieve_language, retrieve_time_zone, t, with_retry,
+        BotError, HandlerResult, MessageTracker, UserDialogue,
+    },
+    i18n::{
+        translate, ONBOARDING_CONVERSION, ONBOARDING_CONVERSION_LATER, ONBOARDING_CONVERSION_OFF,
+        ONBOARDING_CONVERSION_ON, ONBOARDING_DEFAULT_CURRENCY, ONBOARDING_DEFAULT_CURRENCY_INFO,
+        ONBOARDING_DONE, ONBOARDING_ERASE, ONBOARDING_ERASE_OFF, ONBOARDING_ERASE_ON,
+        ONBOARDING_NO_DEFAULT_CURRENCY, ONBOARDING_PROGRESS, ONBOARDING_TIME_ZONE,
+        ONBOARDING_WELCOME,
+    },
+    processor::{get_chat_setting, retrieve_onboarding_step, update_onboarding_step, ChatSetting},
+    redis::Redis,
+};
+
+/* Utilities */
+const ONBOARDING_TTL: usize = 60 * 60;
+const ONBOARDING_STEPS: [&str; 4] = [
+    "time_zone",
+    "default_currency",
+    "currency_conversion",
+    "erase_messages",
+];
+pub const ONBOARDING_SKIP: &str = "Skip";
+
+// Checks if an update is the bot being added to a group chat.
+pub fn is_bot_added(update: ChatMemberUpdated) -> bool {
+    !update.old_chat_member.is_present()
+        && update.new_chat_member.is_present()
+        && (update.chat.is_group() || update.chat.is_supergroup())
+}
+
+// Gets the settings state that handles a step of the setup wizard
+fn get_onboarding_state(step: &str, messages: MessageTracker) -> Option<State> {
+    match step {
+        "time_zone" => Some(State::SettingsTimeZoneMenu { messages }),
+        "default_currency" => Some(State::SettingsDefaultCurrencyMenu { messages }),
+        "currency_conversion" => Some(State::SettingsCurrencyConversion { messages }),
+        "erase_messages" => Some(State::SettingsEraseMessages { messages }),
+        _ => None,
+    }
+}
+
+// Gets the message and buttons of a step of the setup wizard
+async fn get_onboarding_prompt(
+    redis: &Redis,
+    chat_id: &str,
+    step: &str,
+    language: &str,
+) -> Option<(String, Vec<&'static str>)> {
+    let index = ONBOARDING_STEPS.iter().position(|s| *s == step)?;
+    let progress = translate(
+        language,
+        ONBOARDING_PROGRESS,
+        &[
+            ("step", &(index + 1).to_string()),
+            ("count", &ONBOARDING_STEPS.len().to_string()),
+        ],
+    );
+
+    let default_currency =
+        match get_chat_setting(redis, chat_id, ChatSetting::DefaultCurrency(None)).await {
+            Ok(ChatSetting::DefaultCurrency(Some(currency))) => currency,
+            _ => CURRENCY_DEFAULT.0.to_string(),
+        };
+
+    match step {
+        "time_zone" => {
+            let time_zone = retrieve_time_zone(redis, chat_id).await;
+            Some((
+                translate(
+                    language,
+                    ONBOARDING_TIME_ZONE,
+                    &[
+                        ("progress", &progress),
+                        ("time_zone", &time_zone.to_string()),
+                    ],
+                ),
+                vec![ONBOARDING_SKIP, "Edit"],
+            ))
+        }
+        "default_currency" => {
+            let currency_info = if default_currency == CURRENCY_DEFAULT.0 {
+                translate(language, ONBOARDING_NO_DEFAULT_CURRENCY, &[])
+            } else {
+                translate(
+                    language,
+                    ONBOARDING_DEFAULT_CURRENCY_INFO,
+                    &[("currency", &default_currency)],
+                )
+            };
+            Some((
+                translate(
+                    language,
+                    ONBOARDING_DEFAULT_CURRENCY,
+                    &[("progress", &progress), ("info", &currency_info)],
+                ),
+                vec![ONBOARDING_SKIP, "Edit"],
+            ))
+        }
+        "currency_conversion" => {
+            let convert = matches!(
+                get_chat_setting(redis, chat_id, ChatSetting::CurrencyConversion(None)).await,
+                Ok(ChatSetting::CurrencyConversion(Some(true)))
+            );
+            let (prompt, buttons) = if convert {
+                (ONBOARDING_CONVERSION_ON, vec![ONBOARDING_SKIP, "Turn Off"])
+            } else if default_currency == CURRENCY_DEFAULT.0 {
+                (ONBOARDING_CONVERSION_LATER, vec![ONBOARDING_SKIP])
+            } else {
+                (ONBOARDING_CONVERSION_OFF, vec![ONBOARDING_SKIP, "Turn On"])
+            };
+            Some((
+                translate(
+                    language,
+                    ONBOARDING_CONVERSION,
+                    &[
+                        ("progress", &progress),
+                        ("prompt", &translate(language, prompt, &[])),
+                    ],
+                ),
+                buttons,
+            ))
+        }
+        "erase_messages" => {
+            let erase = matches!(
+                get_chat_setting(redis, chat_id, ChatSetting::EraseMessages(None)).await,
+                Ok(ChatSetting::EraseMessages(Some(true)))
+            );
+            let (prompt, buttons) = if erase {
+                (ONBOARDING_ERASE_ON, vec![ONBOARDING_SKIP, "Turn Off"])
+            } else {
+                (ONBOARDING_ERASE_OFF, vec![ONBOARDING_SKIP, "Turn On"])
+            };
+            Some((
+                translate(
+                    language,
+                    ONBOARDING_ERASE,
+                    &[
+                        ("progress", &progress),
+                        ("prompt", &translate(language, prompt, &[])),
+                    ],
+                ),
+                buttons,
+            ))
+        }
+        _ => None,
+    }
+}
+
+// Displays a step of the setup wizard, moving the dialogue into the matching settings state
+// Returns false if the step does not exist
+async fn display_onboarding_step(
+    redis: &Redis,
+    bot: &Bot,
+    dialogue: &UserDialogue,
+    chat_id: &str,
+    step: &str,
+    mut messages: MessageTracker,
+) -> Result<bool, BotError> {
+    let id = match chat_id.parse::<i64>() {
+        Ok(id) => ChatId(id),
+        Err(_) => return Ok(false),
+    };
+    let language = retrieve_language(redis, chat_id).await;
+    let (text, buttons) = match get_onboarding_prompt(redis, chat_id, step, &language).await {
+        Some(prompt) => prompt,
+        None => return Ok(false),
+    };
+
+    let columns = buttons.len();
+    let new_message = with_retry(bot.send_message(id, text))
+        .reply_markup(make_keyboard(buttons, Some(columns), &language))
+        .await?
+        .id;
+    messages.track(new_message);
+
+    update_onboarding_step(redis, chat_id, Some(step), ONBOARDING_TTL).await?;
+    if let Some(state) = get_onboarding_state(step, messages) {
+        dialogue.update(state).await?;
+    }
+
+    Ok(true)
+}
+
+/* Checks if a group chat is in the middle of the setup wizard.
+ */
+pub async fn is_onboarding(redis: &Redis, chat_id: &str) -> bool {
+    matches!(retrieve_onboarding_step(redis, chat_id).await, Ok(Some(_)))
+}
+
+/* Ends the setup wizard of a group chat, if it is ongoing.
+ * Called when settings are cancelled or opened again, so that the wizard does not resume.
+ */
+pub async fn end_onboarding(redis: &Redis, chat_id: &str) {
+    if let Err(err) = update_onboarding_step(redis, chat_id, None, ONBOARDING_TTL).await {
+        log::error!(
+            "Onboarding - Failed to end setup wizard for chat {}: {}",
+            chat_id,
+            err.to_string()
+        );
+    }
+}
+
+/* Moves the setup wizard of a group chat on to the next step, after a setting is done.
+ * Once all steps are done, the wizard ends, and the dialogue exits.
+ */
+pub async fn continue_onboarding(
+    redis: &Redis,
+    bot: &Bot,
+    dialogue: UserDialogue,
+    chat_id: &str,
+    messages: MessageTracker,
+) -> HandlerResult {
+    let step = retrieve_onboarding_step(redis, chat_id).await?;
+    let next_step = step
+        .and_then(|step| ONBOARDING_STEPS.iter().position(|s| *s == step))
+        .and_then(|index| ONBOARDING_STEPS.get(index + 1));
+
+    if let Some(next_step) = next_step {
+        if display_onboarding_step(redis, bot, &dialogue, chat_id, next_step, messages.clone())
+            .await?
+        {
+            return Ok(());
+        }
+    }
+
+    end_onboarding(redis, chat_id).await;
+    if let Ok(id) = chat_id.parse::<i64>() {
+        with_retry(bot.send_message(ChatId(id), t(redis, chat_id, ONBOARDING_DONE, &[]).await))
+            .await?;
+    }
+    cleanup_messages(redis, bot, chat_id, messages).await?;
+    dialogue.exit().await?;
+
+    // Logging
+    log::info!("Onboarding - Setup wizard completed for chat {}", chat_id);
+
+    Ok(())
+}
+
+/* Greets a group chat when the bot is added to it, and starts the setup wizard.
+ * The wizard goes through a few settings one at a time, each of which can be skipped.
+ */
+pub async fn action_bot_added(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    update: ChatMemberUpdated,
+) -> HandlerResult {
+    let chat_id = update.chat.id.to_string();
+    with_retry(bot.send_message(
+        update.chat.id,
+        t(&redis, &chat_id, ONBOARDING_WELCOME, &[]).await,
+    ))
+    .await?;
+
+    display_onboarding_step(
+        &redis,
+        &bot,
+        &dialogue,
+        &chat_id,
+        ONBOARDING_STEPS[0],
+        MessageTracker::default(),
+    )
+    .await?;
+
+    // Logging
+    log::info!(
+        "Onboarding - Setup wizard started for chat {} by user {}",
+        chat_id,
+        update.from.id
+    );
+
+    Ok(())
+}
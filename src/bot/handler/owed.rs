@@ -0,0 +1,121 @@
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    currency::get_default_currency,
+    handler::utils::{
+        assert_handle_request_limit, display_currency_amount, display_username, get_currency,
+        parse_username, retrieve_currency_format, retrieve_language, send_bot_message, t_text,
+        use_currency, BotError, HandlerResult,
+    },
+    i18n::{
+        translate, Text, BALANCE_DEBT, NOT_UNDERSTOOD, OWED_FOOTER, OWED_HEADER, OWED_INSTRUCTIONS,
+        OWED_SAME_USER, OWED_SENDER_UNKNOWN, OWED_SQUARE,
+    },
+    processor::{is_username_equal, retrieve_pairwise_debts},
+    redis::Redis,
+};
+
+/* Utilities */
+
+// Parses the arguments to view the position between two users
+// If only one username is given, the other is the sender
+fn parse_owed_arguments(args: &str, sender: Option<&str>) -> Result<(String, String), BotError> {
+    let usernames: Vec<&str> = args.split_whitespace().collect();
+    let (first, second) = match usernames[..] {
+        [first, second] => (parse_username(first)?, parse_username(second)?),
+        [other] => match sender {
+            Some(sender) => (parse_username(sender)?, parse_username(other)?),
+            None => return Err(BotError::UserError(Text::new(OWED_SENDER_UNKNOWN))),
+        },
+        _ => {
+            return Err(BotError::UserError(
+                Text::new(NOT_UNDERSTOOD).then(Text::new(OWED_INSTRUCTIONS)),
+            ))
+        }
+    };
+
+    if is_username_equal(&first, &second) {
+        return Err(BotError::UserError(Text::new(OWED_SAME_USER)));
+    }
+    Ok((first, second))
+}
+
+// Displays the position between two users in each currency
+async fn display_pairwise_debts(
+    redis: &Redis,
+    chat_id: &str,
+    first: &str,
+    second: &str,
+    positions: Vec<(String, i64)>,
+) -> String {
+    let first_name = display_username(redis, chat_id, first).await;
+    let second_name = display_username(redis, chat_id, second).await;
+    let language = retrieve_language(redis, chat_id).await;
+    let names = [
+        ("first", first_name.as_str()),
+        ("second", second_name.as_str()),
+    ];
+    if positions.is_empty() {
+        return translate(&language, OWED_SQUARE, &names);
+    }
+
+    let currency_format = retrieve_currency_format(redis, chat_id).await;
+    let mut message = format!("{}\n\n", translate(&language, OWED_HEADER, &names));
+    for (currency, amount) in positions {
+        let currency = get_currency(&currency).unwrap_or(get_default_currency());
+        let currency = use_currency(redis, currency, chat_id).await;
+        let (debtor, creditor) = if amount > 0 {
+            (&first_name, &second_name)
+        } else {
+            (&second_name, &first_name)
+        };
+        let amount = display_currency_amount(amount.abs(), currency, currency_format);
+        message.push_str(&translate(
+            &language,
+            BALANCE_DEBT,
+            &[
+                ("debtor", debtor),
+                ("creditor", creditor),
+                ("amount", &amount),
+            ],
+        ));
+        message.push('\n');
+    }
+    message.push('\n');
+    message.push_str(&translate(&language, OWED_FOOTER, &[]));
+    message
+}
+
+/* Views the net position between two specific users in a group chat.
+ * Without a second username, shows the position between the sender and the given user.
+ */
+pub async fn action_owed(redis: Redis, bot: Bot, msg: Message, args: String) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let sender = msg.from().and_then(|user| user.username.clone());
+    let (first, second) = match parse_owed_arguments(args.trim(), sender.as_deref()) {
+        Ok(usernames) => usernames,
+        Err(BotError::UserError(reply)) => {
+            send_bot_message(&bot, &msg, t_text(&redis, &chat_id, &reply).await).await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+
+    let positions = retrieve_pairwise_debts(&redis, &chat_id, &first, &second).await?;
+    let reply = display_pairwise_debts(&redis, &chat_id, &first, &second, positions).await;
+    send_bot_message(&bot, &msg, reply).await?;
+
+    // Logging
+    log::info!(
+        "Owed - Position between users {} and {} viewed in chat {}",
+        first,
+        second,
+        chat_id
+    );
+
+    Ok(())
+}
@@ -1,28 +1,38 @@
+use chrono::Utc;
 use teloxide::{
     payloads::SendMessageSetters,
     prelude::*,
-    types::{Message, MessageId},
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message, MessageId},
 };
 
 use crate::bot::{
     currency::{get_default_currency, Currency, CURRENCY_DEFAULT},
     dispatcher::State,
     handler::{
-        constants::{
-            COMMAND_CANCEL, COMMAND_HELP, CURRENCY_INSTRUCTIONS_MESSAGE, NO_TEXT_MESSAGE,
-            PAY_BACK_INSTRUCTIONS_MESSAGE, UNKNOWN_ERROR_MESSAGE,
-        },
+        constants::{COMMAND_CANCEL, COMMAND_HELP, COMMAND_VIEW_PAYMENTS},
         utils::{
-            display_balance_header, display_balances, display_debts, display_username,
-            get_chat_default_currency, get_currency, make_keyboard, parse_debts_payback,
-            parse_username, use_currency, HandlerResult, UserDialogue,
+            display_balance_header, display_balances, display_currency_amount, display_debts,
+            display_username, edit_tracked_message, error_to_user_message,
+            get_chat_default_currency, get_currency, make_keyboard, mention_username,
+            parse_debts_payback, parse_username, retrieve_currency_format, retrieve_language, t,
+            t_prompt, use_currency, BotError, HandlerResult, UserDialogue,
         },
     },
-    processor::add_payment,
+    i18n::{
+        translate, ADD_PAYMENT_CANCELLED, ADD_PAYMENT_FAILED, CURRENCY_INSTRUCTIONS, NO_TEXT,
+        PAY_BACK_BLOCKED, PAY_BACK_CREDITORS_NONE_SELECTED, PAY_BACK_CREDITORS_NOT_ALLOWED,
+        PAY_BACK_CREDITORS_PROMPT, PAY_BACK_CURRENCY_MENU, PAY_BACK_CURRENCY_PROMPT,
+        PAY_BACK_CURRENCY_QUESTION, PAY_BACK_INSTRUCTIONS, PAY_BACK_PARTIAL_FAILED,
+        PAY_BACK_PROMPT, PAY_BACK_REPEATED, PAY_BACK_SUMMARY, UNKNOWN_ERROR,
+    },
+    money::saturating_add,
+    processor::{add_payments_batch, payback_description, retrieve_user_debts, ProcessError},
+    redis::{Debt, Redis},
 };
 
 use super::utils::{
-    assert_handle_request_limit, delete_bot_messages, is_erase_messages, send_bot_message,
+    assert_handle_request_limit, cleanup_messages, edit_bot_message, send_bot_message,
+    send_completion_messages, MessageTracker,
 };
 
 /* Utilities */
@@ -32,13 +42,19 @@ pub struct PayBackParams {
     sender_id: String,
     sender_username: String,
     datetime: String,
+    entries: Vec<PayBackEntry>,
+}
+
+// Represents the amounts paid back in a single currency
+// Each entry is added as a separate payment
+#[derive(Clone, Debug)]
+pub struct PayBackEntry {
     currency: Currency,
     total: i64,
     debts: Vec<(String, i64)>,
 }
 
-const CANCEL_MESSAGE: &str =
-    "Okay! I've cancelled adding the payment. No changes have been made! 🌟";
+const CREDITOR_BUTTON_PREFIX: &str = "Creditor:";
 
 // Controls the state for misc handler actions that return to same state.
 async fn repeat_state(
@@ -47,21 +63,35 @@ async fn repeat_state(
     new_message: MessageId,
 ) -> HandlerResult {
     match state {
+        State::PayBackCreditors {
+            mut messages,
+            debts,
+            selected,
+        } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::PayBackCreditors {
+                    messages,
+                    debts,
+                    selected,
+                })
+                .await?;
+        }
         State::PayBackCurrencyMenu { mut messages } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::PayBackCurrencyMenu { messages })
                 .await?;
         }
         State::PayBackCurrency { mut messages } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue.update(State::PayBackCurrency { messages }).await?;
         }
         State::PayBackDebts {
             mut messages,
             currency,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::PayBackDebts { messages, currency })
                 .await?;
@@ -70,7 +100,7 @@ async fn repeat_state(
             mut messages,
             payment,
         } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::PayBackConfirm { messages, payment })
                 .await?;
@@ -82,58 +112,136 @@ async fn repeat_state(
 
 // Controls the dialogue for ending a pay back operation.
 async fn complete_pay_back(
+    redis: &Redis,
     bot: &Bot,
     dialogue: UserDialogue,
     chat_id: &str,
-    messages: Vec<MessageId>,
+    messages: MessageTracker,
 ) -> HandlerResult {
-    if is_erase_messages(chat_id) {
-        delete_bot_messages(&bot, chat_id, messages).await?;
-    }
+    cleanup_messages(redis, bot, chat_id, messages).await?;
     dialogue.exit().await?;
     Ok(())
 }
 
-fn display_pay_back_entry(payment: &PayBackParams) -> String {
-    let currency_info: String;
-    let actual_currency = use_currency(payment.currency.clone(), &payment.chat_id);
-    if actual_currency.0 == CURRENCY_DEFAULT.0 {
-        currency_info = "".to_string();
+// Make creditors keyboard, with a button for each debt owed, marking those selected
+async fn make_keyboard_creditors(
+    redis: &Redis,
+    chat_id: &str,
+    debts: &[Debt],
+    selected: &[usize],
+) -> InlineKeyboardMarkup {
+    let currency_format = retrieve_currency_format(redis, chat_id).await;
+    let mut keyboard: Vec<Vec<InlineKeyboardButton>> = Vec::new();
+    for (index, debt) in debts.iter().enumerate() {
+        let currency = get_currency(&debt.currency).unwrap_or(get_default_currency());
+        let currency = use_currency(redis, currency, chat_id).await;
+        let label = format!(
+            "{}{}: {}",
+            if selected.contains(&index) {
+                "✅ "
+            } else {
+                ""
+            },
+            mention_username(&debt.creditor),
+            display_currency_amount(debt.amount, currency, currency_format)
+        );
+        keyboard.push(vec![InlineKeyboardButton::callback(
+            label,
+            format!("{CREDITOR_BUTTON_PREFIX}{index}"),
+        )]);
+    }
+    keyboard.push(vec![
+        InlineKeyboardButton::callback("Cancel", "Cancel"),
+        InlineKeyboardButton::callback("Enter Manually", "Enter Manually"),
+    ]);
+    keyboard.push(vec![InlineKeyboardButton::callback("Done", "Done")]);
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+// Toggles the selection of a debt, given its index
+fn toggle_creditor(selected: &[usize], index: usize) -> Vec<usize> {
+    if selected.contains(&index) {
+        selected.iter().filter(|i| **i != index).copied().collect()
     } else {
-        currency_info = format!(" in {} ", actual_currency.0);
+        let mut selected = selected.to_vec();
+        selected.push(index);
+        selected.sort();
+        selected
     }
+}
 
-    format!(
-        "You've paid{}:\n{}",
-        currency_info,
-        display_debts(&payment.debts, actual_currency.1)
-    )
+// Groups the debts selected into pay back entries, one for each currency, paying each in full
+fn make_pay_back_entries(debts: &[Debt], selected: &[usize]) -> Vec<PayBackEntry> {
+    let mut entries: Vec<PayBackEntry> = Vec::new();
+    for debt in selected.iter().filter_map(|index| debts.get(*index)) {
+        let currency = get_currency(&debt.currency).unwrap_or(get_default_currency());
+        let repayment = (debt.creditor.clone(), debt.amount);
+        match entries
+            .iter_mut()
+            .find(|entry| entry.currency.0 == currency.0)
+        {
+            Some(entry) => {
+                entry.total = saturating_add(entry.total, debt.amount);
+                entry.debts.push(repayment);
+            }
+            None => entries.push(PayBackEntry {
+                currency,
+                total: debt.amount,
+                debts: vec![repayment],
+            }),
+        }
+    }
+    entries
+}
+
+async fn display_pay_back_entry(redis: &Redis, payment: &PayBackParams) -> String {
+    let mut message = String::new();
+    for entry in &payment.entries {
+        let actual_currency = use_currency(redis, entry.currency.clone(), &payment.chat_id).await;
+        let currency_info = if actual_currency.0 == CURRENCY_DEFAULT.0 {
+            "".to_string()
+        } else {
+            format!(" in {} ", actual_currency.0)
+        };
+
+        message.push_str(&format!(
+            "You've paid{}:\n{}",
+            currency_info,
+            display_debts(redis, &payment.chat_id, &entry.debts, actual_currency.1).await
+        ));
+        message.push('\n');
+    }
+
+    message.trim_end().to_string()
 }
 
 /* Displays an overview of the pay back entry, with a keyboard button menu.
 */
 async fn display_pay_back_overview(
+    redis: &Redis,
     bot: &Bot,
     msg: &Message,
     dialogue: &UserDialogue,
-    mut messages: Vec<MessageId>,
+    mut messages: MessageTracker,
     payment: PayBackParams,
 ) -> HandlerResult {
     let buttons = vec!["Cancel", "Edit", "Confirm"];
-    let keyboard = make_keyboard(buttons, Some(2));
+    let language = retrieve_language(redis, &payment.chat_id).await;
+    let keyboard = make_keyboard(buttons, Some(2), &language);
 
     let new_message = send_bot_message(
-        &bot,
-        &msg,
+        bot,
+        msg,
         format!(
             "Amazing! 💫 Did I get everything right?\n\n{}",
-            display_pay_back_entry(&payment)
+            display_pay_back_entry(redis, &payment).await
         ),
     )
     .reply_markup(keyboard)
     .await?
     .id;
-    messages.push(new_message);
+    messages.track(new_message);
     dialogue
         .update(State::PayBackConfirm { messages, payment })
         .await?;
@@ -141,41 +249,80 @@ async fn display_pay_back_overview(
 }
 
 async fn call_processor_pay_back(
+    redis: &Redis,
     bot: Bot,
     dialogue: UserDialogue,
-    messages: Vec<MessageId>,
+    mut messages: MessageTracker,
     payment: PayBackParams,
     query: CallbackQuery,
 ) -> HandlerResult {
     if let Some(msg) = query.message {
         let chat_id = msg.chat.id;
         let payment_clone = payment.clone();
-        let payment_overview = display_pay_back_entry(&payment);
-        let description = format!("{} paid back!", display_username(&payment.sender_username));
-
-        let updated_balances = add_payment(
+        let payment_overview = display_pay_back_entry(redis, &payment).await;
+        let description = payback_description(&payment.sender_username);
+
+        let is_multiple = payment.entries.len() > 1;
+        let currency_format = retrieve_currency_format(redis, &payment.chat_id).await;
+        let mut amounts: Vec<String> = Vec::new();
+        for entry in &payment.entries {
+            let currency = use_currency(redis, entry.currency.clone(), &payment.chat_id).await;
+            amounts.push(display_currency_amount(
+                entry.total,
+                currency,
+                currency_format,
+            ));
+        }
+        let summary = t(
+            redis,
+            &payment.chat_id,
+            PAY_BACK_SUMMARY,
+            &[
+                (
+                    "user",
+                    &display_username(redis, &payment.chat_id, &payment.sender_username).await,
+                ),
+                ("amounts", &amounts.join(", ")),
+            ],
+        )
+        .await;
+        let entries = payment
+            .entries
+            .into_iter()
+            .map(|entry| (entry.currency.0, entry.total, entry.debts))
+            .collect();
+        let updated_balances = add_payments_batch(
+            redis,
             payment.chat_id,
             payment.sender_username.clone(),
             payment.sender_id,
             payment.datetime,
             &description,
             &payment.sender_username,
-            &payment.currency.0,
-            payment.total,
-            payment.debts,
+            entries,
         )
         .await;
 
         match updated_balances {
             Err(err) => {
-                send_bot_message(
-                    &bot,
-                    &msg,
-                    format!(
-                        "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't add the payment right now. Please try again later!\n\n"
-                    ),
-                )
-                .await?;
+                let err = BotError::from(err);
+                let reply = match &err {
+                    BotError::ProcessError(ProcessError::CrudError(_)) => {
+                        if is_multiple {
+                            t(
+                                redis,
+                                &payment_clone.chat_id,
+                                PAY_BACK_PARTIAL_FAILED,
+                                &[("view", COMMAND_VIEW_PAYMENTS)],
+                            )
+                            .await
+                        } else {
+                            t(redis, &payment_clone.chat_id, ADD_PAYMENT_FAILED, &[]).await
+                        }
+                    }
+                    _ => error_to_user_message(redis, &err, &payment_clone.chat_id).await,
+                };
+                send_bot_message(&bot, &msg, reply).await?;
 
                 // Logging
                 log::error!(
@@ -187,22 +334,20 @@ async fn call_processor_pay_back(
                     );
             }
             Ok(balances) => {
-                send_bot_message(
-                    &bot,
-                    &msg,
-                    format!("🎉 Yay! Payment added! 🎉\n\n{}", payment_overview,),
-                )
-                .await?;
-                send_bot_message(
-                    &bot,
-                    &msg,
-                    format!(
+                let mut details = vec![format!(
+                    "🎉 Yay! {} added! 🎉\n\n{}",
+                    if is_multiple { "Payments" } else { "Payment" },
+                    payment_overview,
+                )];
+                for (currency, balances) in balances {
+                    details.push(format!(
                         "{}{}",
-                        display_balance_header(&chat_id.to_string(), &payment.currency.0),
-                        display_balances(&balances)
-                    ),
-                )
-                .await?;
+                        display_balance_header(redis, &chat_id.to_string(), &currency).await,
+                        display_balances(redis, &chat_id.to_string(), &balances).await
+                    ));
+                }
+                send_completion_messages(redis, &bot, &msg, Some(&mut messages), summary, details)
+                    .await?;
 
                 // Logging
                 log::info!(
@@ -213,7 +358,7 @@ async fn call_processor_pay_back(
                     );
             }
         }
-        complete_pay_back(&bot, dialogue, &chat_id.to_string(), messages).await?;
+        complete_pay_back(redis, &bot, dialogue, &chat_id.to_string(), messages).await?;
     }
     Ok(())
 }
@@ -224,20 +369,24 @@ async fn call_processor_pay_back(
  * Does nothing, simply notifies the user.
  */
 pub async fn handle_repeated_pay_back(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
-    let new_message = send_bot_message(
-        &bot,
-        &msg,
-        format!("🚫 Oops! It seems like you're already in the middle of paying back! Please finish or {COMMAND_CANCEL} this before starting another one with me."),
-        ).await?.id;
+    let reply = t(
+        &redis,
+        &msg.chat.id.to_string(),
+        PAY_BACK_REPEATED,
+        &[("cancel", COMMAND_CANCEL)],
+    )
+    .await;
+    let new_message = send_bot_message(&bot, &msg, reply).await?.id;
 
     repeat_state(dialogue, state, new_message).await?;
     Ok(())
@@ -247,23 +396,26 @@ pub async fn handle_repeated_pay_back(
  * Can be called at any step of the process.
  */
 pub async fn cancel_pay_back(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
-    send_bot_message(&bot, &msg, CANCEL_MESSAGE.to_string()).await?;
+    let reply = t(&redis, &msg.chat.id.to_string(), ADD_PAYMENT_CANCELLED, &[]).await;
+    send_bot_message(&bot, &msg, reply).await?;
 
     match state {
-        State::PayBackCurrencyMenu { messages }
+        State::PayBackCreditors { messages, .. }
+        | State::PayBackCurrencyMenu { messages }
         | State::PayBackCurrency { messages }
         | State::PayBackDebts { messages, .. }
         | State::PayBackConfirm { messages, .. } => {
-            complete_pay_back(&bot, dialogue, &msg.chat.id.to_string(), messages).await?;
+            complete_pay_back(&redis, &bot, dialogue, &msg.chat.id.to_string(), messages).await?;
         }
         _ => (),
     }
@@ -274,20 +426,24 @@ pub async fn cancel_pay_back(
  * Called when user attempts to start another operation in the middle of adding a payment.
  */
 pub async fn block_pay_back(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
-    let new_message = send_bot_message(
-        &bot,
-        &msg,
-        format!("🚫 Oops! It seems like you're in the middle of paying back! Please finish or {COMMAND_CANCEL} this before starting something new with me."),
-        ).await?.id;
+    let reply = t(
+        &redis,
+        &msg.chat.id.to_string(),
+        PAY_BACK_BLOCKED,
+        &[("cancel", COMMAND_CANCEL)],
+    )
+    .await;
+    let new_message = send_bot_message(&bot, &msg, reply).await?.id;
 
     repeat_state(dialogue, state, new_message).await?;
     Ok(())
@@ -295,39 +451,225 @@ pub async fn block_pay_back(
 
 /* Adds a pay back entry.
  * Entrypoint to the dialogue sequence.
+ * If the sender owes anyone, shows who they owe to pick from, with the amounts filled in.
+ * Otherwise, or if they choose to, they enter who and how much they paid back themselves.
  */
-pub async fn action_pay_back(bot: Bot, dialogue: UserDialogue, msg: Message) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+pub async fn action_pay_back(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: Message,
+) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let username = msg.from().and_then(|user| user.username.clone());
+    let debts = match username {
+        Some(username) => retrieve_user_debts(&redis, &chat_id, &username)
+            .await
+            .unwrap_or_else(|err| {
+                log::error!(
+                    "Pay Back - Failed to retrieve debts of user {} in chat {}: {}",
+                    username,
+                    chat_id,
+                    err.to_string()
+                );
+                Vec::new()
+            }),
+        None => Vec::new(),
+    };
+
+    if !debts.is_empty() {
+        let new_message = send_bot_message(
+            &bot,
+            &msg,
+            t_prompt(
+                &redis,
+                &chat_id,
+                PAY_BACK_CREDITORS_PROMPT,
+                &[],
+                PAY_BACK_INSTRUCTIONS,
+            )
+            .await,
+        )
+        .reply_markup(make_keyboard_creditors(&redis, &chat_id, &debts, &[]).await)
+        .await?
+        .id;
+
+        dialogue
+            .update(State::PayBackCreditors {
+                messages: MessageTracker::new(new_message).with_command(msg.id),
+                debts,
+                selected: Vec::new(),
+            })
+            .await?;
         return Ok(());
     }
 
     let buttons = vec!["Cancel", "Skip", "Set Currency"];
-    let keyboard = make_keyboard(buttons, Some(2));
+    let keyboard = make_keyboard(buttons, Some(2), &retrieve_language(&redis, &chat_id).await);
     let new_message = send_bot_message(
         &bot,
         &msg,
-        format!("Absolutely! 🙌 Would you like to set a currency for this payment? You can also choose to skip this step."),
-        )
-        .reply_markup(keyboard)
-        .await?.id;
+        t(&redis, &chat_id, PAY_BACK_CURRENCY_MENU, &[]).await,
+    )
+    .reply_markup(keyboard)
+    .await?
+    .id;
 
     dialogue
         .update(State::PayBackCurrencyMenu {
-            messages: vec![new_message],
+            messages: MessageTracker::new(new_message).with_command(msg.id),
         })
         .await?;
     Ok(())
 }
 
+/* Adds a pay back entry.
+ * Bot receives a callback query picking who the sender has paid back, from those they owe.
+ * Each debt picked is paid back in full, in its currency. Done proceeds to ask for confirmation.
+ */
+pub async fn action_pay_back_creditors(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    query: CallbackQuery,
+    (mut messages, debts, selected): (MessageTracker, Vec<Debt>, Vec<usize>),
+) -> HandlerResult {
+    let (button, msg) = match (&query.data, &query.message) {
+        (Some(button), Some(msg)) => (button.clone(), msg.clone()),
+        _ => return Ok(()),
+    };
+    let chat_id = msg.chat.id.to_string();
+
+    match button.as_str() {
+        "Cancel" => {
+            bot.answer_callback_query(query.id.to_string()).await?;
+            cancel_pay_back(redis.clone(), bot, dialogue, state, msg).await?;
+        }
+        "Enter Manually" => {
+            bot.answer_callback_query(query.id.to_string()).await?;
+            let buttons = vec!["Cancel", "Skip", "Set Currency"];
+            edit_bot_message(
+                &bot,
+                msg.chat.id,
+                msg.id,
+                t(&redis, &chat_id, PAY_BACK_CURRENCY_MENU, &[]).await,
+            )
+            .reply_markup(make_keyboard(
+                buttons,
+                Some(2),
+                &retrieve_language(&redis, &chat_id).await,
+            ))
+            .await?;
+            dialogue
+                .update(State::PayBackCurrencyMenu { messages })
+                .await?;
+        }
+        "Done" => {
+            if selected.is_empty() {
+                bot.answer_callback_query(query.id.to_string())
+                    .text(t(&redis, &chat_id, PAY_BACK_CREDITORS_NONE_SELECTED, &[]).await)
+                    .await?;
+                return Ok(());
+            }
+            let sender_username = match query.from.username.as_deref().map(parse_username) {
+                Some(Ok(username)) => username,
+                _ => {
+                    bot.answer_callback_query(query.id.to_string())
+                        .text(t(&redis, &chat_id, PAY_BACK_CREDITORS_NOT_ALLOWED, &[]).await)
+                        .await?;
+                    return Ok(());
+                }
+            };
+            bot.answer_callback_query(query.id.to_string()).await?;
+
+            let payment = PayBackParams {
+                chat_id,
+                sender_id: query.from.id.to_string(),
+                sender_username,
+                datetime: Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+                entries: make_pay_back_entries(&debts, &selected),
+            };
+            edit_tracked_message(
+                &bot,
+                &msg,
+                &mut messages,
+                "Okay! Paying back everyone you picked in full! 💸",
+            )
+            .await?;
+            display_pay_back_overview(&redis, &bot, &msg, &dialogue, messages, payment).await?;
+        }
+        _ => {
+            bot.answer_callback_query(query.id.to_string()).await?;
+            let index = match button
+                .strip_prefix(CREDITOR_BUTTON_PREFIX)
+                .and_then(|index| index.parse::<usize>().ok())
+            {
+                Some(index) if index < debts.len() => index,
+                _ => {
+                    log::error!(
+                        "Pay Back Creditors - Invalid button for user {} in chat {}: {}",
+                        query.from.id,
+                        chat_id,
+                        button
+                    );
+                    return Ok(());
+                }
+            };
+
+            let selected = toggle_creditor(&selected, index);
+            bot.edit_message_reply_markup(msg.chat.id, msg.id)
+                .reply_markup(make_keyboard_creditors(&redis, &chat_id, &debts, &selected).await)
+                .await?;
+            dialogue
+                .update(State::PayBackCreditors {
+                    messages,
+                    debts,
+                    selected,
+                })
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/* Adds a pay back entry.
+ * Bot receives who and how much the sender paid back as text instead, while picking who they owe.
+ * Amounts are in the default currency, unless another currency is given after them.
+ */
+pub async fn action_pay_back_creditors_message(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+    (messages, _, _): (MessageTracker, Vec<Debt>, Vec<usize>),
+) -> HandlerResult {
+    action_pay_back_debts(
+        redis.clone(),
+        bot,
+        dialogue,
+        state,
+        msg,
+        (messages, get_default_currency()),
+    )
+    .await
+}
+
 /* Adds a pay back entry.
  * Bot receives a callback query indicating to skip or add currency.
  */
 pub async fn action_pay_back_currency_menu(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     query: CallbackQuery,
-    messages: Vec<MessageId>,
+    messages: MessageTracker,
 ) -> HandlerResult {
     if let Some(button) = &query.data {
         bot.answer_callback_query(query.id.to_string()).await?;
@@ -335,19 +677,25 @@ pub async fn action_pay_back_currency_menu(
         match button.as_str() {
             "Cancel" => {
                 if let Some(msg) = query.message {
-                    cancel_pay_back(bot, dialogue, state, msg).await?;
+                    cancel_pay_back(redis.clone(), bot, dialogue, state, msg).await?;
                 }
             }
             "Skip" => {
                 if let Some(Message { id, chat, .. }) = query.message {
-                    bot.edit_message_text(
+                    edit_bot_message(
+                        &bot,
                         chat.id,
                         id,
-                        format!(
-                            "Sure! Who and how much did you pay back?\n\n{PAY_BACK_INSTRUCTIONS_MESSAGE}"
-                            ),
-                            )
-                        .await?;
+                        t_prompt(
+                            &redis,
+                            &chat.id.to_string(),
+                            PAY_BACK_PROMPT,
+                            &[],
+                            PAY_BACK_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?;
                     dialogue
                         .update(State::PayBackDebts {
                             messages,
@@ -358,12 +706,18 @@ pub async fn action_pay_back_currency_menu(
             }
             "Set Currency" => {
                 if let Some(Message { id, chat, .. }) = query.message {
-                    bot.edit_message_text(
+                    edit_bot_message(
+                        &bot,
                         chat.id,
                         id,
-                        format!(
-                            "Sure! What currency did you pay in?\n\n{CURRENCY_INSTRUCTIONS_MESSAGE}"
-                            ),
+                        t_prompt(
+                            &redis,
+                            &chat.id.to_string(),
+                            PAY_BACK_CURRENCY_QUESTION,
+                            &[],
+                            CURRENCY_INSTRUCTIONS,
+                        )
+                        .await,
                     )
                     .await?;
                     dialogue.update(State::PayBackCurrency { messages }).await?;
@@ -390,11 +744,12 @@ pub async fn action_pay_back_currency_menu(
  * Bot receives either a string representing a currency code.
  */
 pub async fn action_pay_back_currency(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
-    mut messages: Vec<MessageId>,
+    mut messages: MessageTracker,
 ) -> HandlerResult {
     match msg.text() {
         Some(text) => {
@@ -405,12 +760,18 @@ pub async fn action_pay_back_currency(
                     let new_message = send_bot_message(
                         &bot,
                         &msg,
-                        format!(
-                            "{}, awesome! Who and how much did you pay back?\n\n{PAY_BACK_INSTRUCTIONS_MESSAGE}",
-                            currency_code
-                            ),
-                            ).await?.id;
-                    messages.push(new_message);
+                        t_prompt(
+                            &redis,
+                            &msg.chat.id.to_string(),
+                            PAY_BACK_CURRENCY_PROMPT,
+                            &[("currency", &currency_code)],
+                            PAY_BACK_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .await?
+                    .id;
+                    messages.track(new_message);
                     dialogue
                         .update(State::PayBackDebts { messages, currency })
                         .await?;
@@ -421,7 +782,7 @@ pub async fn action_pay_back_currency(
                         &msg,
                         format!(
                             "{}\n\n⭐️ If you're unsure of the currency code, you can always check out my User Guide with {COMMAND_HELP}.",
-                            err.to_string()
+                            err
                         ),
                     )
                     .await?.id;
@@ -430,9 +791,13 @@ pub async fn action_pay_back_currency(
             }
         }
         None => {
-            let new_message = send_bot_message(&bot, &msg, format!("{NO_TEXT_MESSAGE}"))
-                .await?
-                .id;
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
             repeat_state(dialogue, state, new_message).await?;
         }
     }
@@ -443,11 +808,12 @@ pub async fn action_pay_back_currency(
  * Bot receives a string representing debts, and proceeds to ask for confirmation.
  */
 pub async fn action_pay_back_debts(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
-    (messages, currency): (Vec<MessageId>, Currency),
+    (messages, currency): (MessageTracker, Currency),
 ) -> HandlerResult {
     let chat_id = msg.chat.id.to_string();
     match msg.text() {
@@ -456,10 +822,13 @@ pub async fn action_pay_back_debts(
                 if let Some(username) = &user.username {
                     let username = parse_username(username);
                     if let Err(err) = &username {
-                        let new_message =
-                            send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string())
-                                .await?
-                                .id;
+                        let new_message = send_bot_message(
+                            &bot,
+                            &msg,
+                            t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                        )
+                        .await?
+                        .id;
 
                         // Logging
                         log::error!(
@@ -473,19 +842,23 @@ pub async fn action_pay_back_debts(
                     }
                     let username = username?;
 
-                    let actual_currency: Currency;
-                    if currency.0 == CURRENCY_DEFAULT.0 {
-                        actual_currency = get_chat_default_currency(&chat_id);
+                    let actual_currency: Currency = if currency.0 == CURRENCY_DEFAULT.0 {
+                        get_chat_default_currency(&redis, &chat_id).await
                     } else {
-                        actual_currency = currency.clone();
-                    }
+                        currency.clone()
+                    };
 
                     let debts = parse_debts_payback(text, actual_currency.clone(), &username);
                     if let Err(err) = debts {
                         let new_message = send_bot_message(
                             &bot,
                             &msg,
-                            format!("{}\n\n{PAY_BACK_INSTRUCTIONS_MESSAGE}", err.to_string()),
+                            format!(
+                                "{}\n\n{}",
+                                err,
+                                t(&redis, &msg.chat.id.to_string(), PAY_BACK_INSTRUCTIONS, &[])
+                                    .await
+                            ),
                         )
                         .await?
                         .id;
@@ -494,25 +867,39 @@ pub async fn action_pay_back_debts(
                         return Ok(());
                     }
 
-                    let debts = debts?;
-                    let total = debts.iter().fold(0, |curr, next| curr + next.1);
+                    // Debts in the chosen currency keep it as chosen, since it may be left blank
+                    let entries = debts?
+                        .into_iter()
+                        .map(|(debt_currency, debts)| PayBackEntry {
+                            currency: if debt_currency.0 == actual_currency.0 {
+                                currency.clone()
+                            } else {
+                                debt_currency
+                            },
+                            total: debts.iter().fold(0, |curr, next| curr + next.1),
+                            debts,
+                        })
+                        .collect();
                     let payment = PayBackParams {
                         chat_id,
                         sender_id: msg.from().as_ref().unwrap().id.to_string(),
                         sender_username: username,
                         datetime: msg.date.to_string(),
-                        currency,
-                        total,
-                        debts,
+                        entries,
                     };
-                    display_pay_back_overview(&bot, &msg, &dialogue, messages, payment).await?;
+                    display_pay_back_overview(&redis, &bot, &msg, &dialogue, messages, payment)
+                        .await?;
                 }
             }
         }
         None => {
-            let new_message = send_bot_message(&bot, &msg, format!("{NO_TEXT_MESSAGE}"))
-                .await?
-                .id;
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
             repeat_state(dialogue, state, new_message).await?;
         }
     }
@@ -525,10 +912,11 @@ pub async fn action_pay_back_debts(
  * If confirm, calls processor.
  */
 pub async fn action_pay_back_confirm(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
-    (messages, payment): (Vec<MessageId>, PayBackParams),
+    (messages, payment): (MessageTracker, PayBackParams),
     query: CallbackQuery,
 ) -> HandlerResult {
     if let Some(button) = &query.data {
@@ -537,29 +925,29 @@ pub async fn action_pay_back_confirm(
         match button.as_str() {
             "Cancel" => {
                 if let Some(msg) = query.message {
-                    cancel_pay_back(bot, dialogue, state, msg).await?;
+                    cancel_pay_back(redis.clone(), bot, dialogue, state, msg).await?;
                 }
             }
             "Edit" => {
                 if let Some(Message { id, chat, .. }) = query.message {
                     let buttons = vec!["Cancel", "Skip", "Set Currency"];
-                    let keyboard = make_keyboard(buttons, Some(2));
-                    bot.edit_message_text(
+                    let language = retrieve_language(&redis, &payment.chat_id).await;
+                    let keyboard = make_keyboard(buttons, Some(2), &language);
+                    edit_bot_message(
+                        &bot,
                         chat.id,
                         id,
-                        format!(
-                            "Absolutely! 🙌 Would you like to set a currency for this payment? You can also choose to skip this step."
-                            ),
-                            )
-                        .reply_markup(keyboard)
-                        .await?;
+                        translate(&language, PAY_BACK_CURRENCY_MENU, &[]),
+                    )
+                    .reply_markup(keyboard)
+                    .await?;
                     dialogue
                         .update(State::PayBackCurrencyMenu { messages })
                         .await?;
                 }
             }
             "Confirm" => {
-                call_processor_pay_back(bot, dialogue, messages, payment, query).await?;
+                call_processor_pay_back(&redis, bot, dialogue, messages, payment, query).await?;
             }
             _ => {
                 log::error!("Pay Back Confirm - Invalid button for user {} in chat {} with payment {:?}: {}",
@@ -0,0 +1,110 @@
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    handler::utils::{
+        assert_handle_request_limit, is_payment_url, retrieve_language, send_bot_message, t,
+        BotError, HandlerResult,
+    },
+    i18n::{
+        translate, Text, PAYLINK_CURRENT, PAYLINK_INSTRUCTIONS, PAYLINK_NONE, PAYLINK_NO_USERNAME,
+        PAYLINK_REMOVED, PAYLINK_SET, PAYLINK_TOO_LONG, PAYLINK_UNRECOGNIZED,
+    },
+    processor::{retrieve_payment_links, update_payment_link},
+    redis::Redis,
+};
+
+/* Utilities */
+const PAYLINK_MAX_LENGTH: usize = 200;
+const PHONE_MIN_DIGITS: usize = 6;
+const PHONE_MAX_DIGITS: usize = 15;
+
+// Parses a payment link, which is either a URL or a phone number
+fn parse_payment_link(text: &str) -> Result<String, BotError> {
+    if text.len() > PAYLINK_MAX_LENGTH {
+        return Err(BotError::UserError(Text::new(PAYLINK_TOO_LONG)));
+    }
+    if is_payment_url(text) {
+        return Ok(text.to_string());
+    }
+
+    // Phone numbers may contain spaces and dashes, which are removed
+    let phone: String = text.chars().filter(|c| *c != ' ' && *c != '-').collect();
+    let digits = phone.trim_start_matches('+');
+    if digits.chars().all(|c| c.is_ascii_digit())
+        && (PHONE_MIN_DIGITS..=PHONE_MAX_DIGITS).contains(&digits.len())
+    {
+        return Ok(phone);
+    }
+
+    Err(BotError::UserError(
+        Text::new(PAYLINK_UNRECOGNIZED).then(Text::new(PAYLINK_INSTRUCTIONS)),
+    ))
+}
+
+/* Sets, removes, or views the payment link of the sender.
+ * A payment link is shown whenever others owe the sender, so that they can pay directly.
+ */
+pub async fn action_paylink(redis: Redis, bot: Bot, msg: Message, args: String) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let user = match msg.from() {
+        Some(user) => user,
+        None => return Ok(()),
+    };
+    let chat_id = msg.chat.id.to_string();
+    let sender_id = user.id.to_string();
+    let username = match &user.username {
+        Some(username) => username.clone(),
+        None => {
+            send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &chat_id, PAYLINK_NO_USERNAME, &[]).await,
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let args = args.trim();
+    let language = retrieve_language(&redis, &chat_id).await;
+    let reply = match args.to_lowercase().as_str() {
+        "" => {
+            let links = retrieve_payment_links(&redis, std::slice::from_ref(&username)).await?;
+            match links.get(&username.to_lowercase()) {
+                Some(link) => Text::new(PAYLINK_CURRENT)
+                    .arg("link", link)
+                    .then(Text::new(PAYLINK_INSTRUCTIONS))
+                    .translate(&language),
+                None => Text::new(PAYLINK_NONE)
+                    .then(Text::new(PAYLINK_INSTRUCTIONS))
+                    .translate(&language),
+            }
+        }
+        "off" | "remove" | "none" => {
+            update_payment_link(&redis, &sender_id, Some(&username), None).await?;
+
+            // Logging
+            log::info!("Pay Link - Payment link removed for user {}", sender_id);
+
+            translate(&language, PAYLINK_REMOVED, &[])
+        }
+        _ => match parse_payment_link(args) {
+            Ok(link) => {
+                update_payment_link(&redis, &sender_id, Some(&username), Some(&link)).await?;
+
+                // Logging
+                log::info!("Pay Link - Payment link set for user {}", sender_id);
+
+                translate(&language, PAYLINK_SET, &[("link", &link)])
+            }
+            Err(BotError::UserError(reply)) => reply.translate(&language),
+            Err(err) => return Err(err),
+        },
+    };
+    send_bot_message(&bot, &msg, reply).await?;
+
+    Ok(())
+}
@@ -0,0 +1,587 @@
+use teloxide::{
+    payloads::{SendMessageSetters, SendPhotoSetters},
+    prelude::*,
+    types::{InputFile, Message, MessageId},
+};
+
+use crate::bot::{
+    dispatcher::State,
+    handler::{
+        constants::{COMMAND_CANCEL, NOTE_MAX_LENGTH},
+        dispute::{action_dispute_payment, is_payment_disputable},
+        utils::{
+            cleanup_messages, display_payment, error_to_user_message, get_topic_id,
+            is_user_allowed, make_keyboard, retrieve_language, retrieve_topic_time_zone,
+            send_bot_message, t, BotError, HandlerResult, MessageTracker, PaymentCardDetail,
+            UserDialogue,
+        },
+    },
+    i18n::{
+        translate, ADMIN_ONLY, PAYMENT_CARD, PAYMENT_CARD_BLOCKED, PAYMENT_CARD_CLOSED,
+        PAYMENT_CARD_NOTE, PAYMENT_CARD_NOTE_INVALID, PAYMENT_CARD_NOTE_PROMPT,
+        PAYMENT_CARD_NOT_DISPUTABLE, PAYMENT_CARD_PRIVATE, PAYMENT_CARD_RECEIPT,
+        PAYMENT_CARD_RECEIPT_CAPTION, PAYMENT_CARD_RECEIPT_INVALID, PAYMENT_CARD_RECEIPT_PROMPT,
+    },
+    processor::{
+        retrieve_payment_note, retrieve_payment_receipt, update_payment_note,
+        update_payment_receipt,
+    },
+    redis::Redis,
+};
+
+use super::{
+    action_add_payment_duplicate, action_delete_payment, action_edit_payment,
+    utils::assert_handle_request_limit, Payment,
+};
+
+/* Utilities */
+const EDIT_BUTTON: &str = "Edit";
+const DELETE_BUTTON: &str = "Delete";
+const DUPLICATE_BUTTON: &str = "Duplicate";
+const DISPUTE_BUTTON: &str = "Dispute";
+const ADD_NOTE_BUTTON: &str = "Add Note";
+const EDIT_NOTE_BUTTON: &str = "Edit Note";
+const SHOW_RECEIPT_BUTTON: &str = "Show Receipt";
+const ADD_RECEIPT_BUTTON: &str = "Add Receipt";
+const CHANGE_RECEIPT_BUTTON: &str = "Change Receipt";
+const BACK_BUTTON: &str = "Back";
+
+// Controls the state for misc handler actions that return to same state.
+async fn repeat_state(
+    dialogue: UserDialogue,
+    state: State,
+    new_message: MessageId,
+) -> HandlerResult {
+    match state {
+        State::PaymentCard {
+            mut messages,
+            payments,
+            page,
+            index,
+        } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::PaymentCard {
+                    messages,
+                    payments,
+                    page,
+                    index,
+                })
+                .await?;
+        }
+        State::PaymentCardDetails {
+            mut messages,
+            payments,
+            page,
+            index,
+            detail,
+        } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::PaymentCardDetails {
+                    messages,
+                    payments,
+                    page,
+                    index,
+                    detail,
+                })
+                .await?;
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+// Returns to viewing the payments, cleaning up the messages of the detail card
+async fn complete_payment_card(
+    redis: &Redis,
+    bot: &Bot,
+    dialogue: UserDialogue,
+    chat_id: &str,
+    messages: MessageTracker,
+    payments: Vec<Payment>,
+    page: usize,
+) -> HandlerResult {
+    cleanup_messages(redis, bot, chat_id, messages).await?;
+    dialogue
+        .update(State::ViewPayments { payments, page })
+        .await?;
+    Ok(())
+}
+
+// Displays the detail card of a payment, with its note and receipt,
+// along with the menu of actions for it
+async fn display_payment_card(
+    redis: &Redis,
+    payment: &Payment,
+    index: usize,
+    topic_id: Option<i32>,
+    language: &str,
+) -> (String, Vec<&'static str>) {
+    let time_zone = retrieve_topic_time_zone(redis, &payment.chat_id, topic_id).await;
+    let note = retrieve_payment_note(redis, &payment.payment_id)
+        .await
+        .unwrap_or(None);
+    let receipt = retrieve_payment_receipt(redis, &payment.payment_id)
+        .await
+        .unwrap_or(None);
+
+    let details = if payment.is_private {
+        String::new()
+    } else {
+        let note = match &note {
+            Some(note) => format!(
+                "\n{}",
+                translate(language, PAYMENT_CARD_NOTE, &[("note", note)])
+            ),
+            None => String::new(),
+        };
+        let receipt = if receipt.is_some() {
+            format!("\n{}", translate(language, PAYMENT_CARD_RECEIPT, &[]))
+        } else {
+            String::new()
+        };
+        format!("{note}{receipt}")
+    };
+    let card = translate(
+        language,
+        PAYMENT_CARD,
+        &[
+            (
+                "payment",
+                &display_payment(redis, payment, index + 1, time_zone).await,
+            ),
+            ("details", &details),
+        ],
+    );
+
+    let note_button = if note.is_some() {
+        EDIT_NOTE_BUTTON
+    } else {
+        ADD_NOTE_BUTTON
+    };
+    let mut buttons = vec![EDIT_BUTTON, DELETE_BUTTON, DUPLICATE_BUTTON, note_button];
+    if receipt.is_some() {
+        buttons.push(SHOW_RECEIPT_BUTTON);
+        buttons.push(CHANGE_RECEIPT_BUTTON);
+    } else {
+        buttons.push(ADD_RECEIPT_BUTTON);
+    }
+    if !payment.is_disputed {
+        buttons.push(DISPUTE_BUTTON);
+    }
+    buttons.push(BACK_BUTTON);
+    (card, buttons)
+}
+
+/* Views the detail card of a payment.
+ * Bot receives a callback query from the payment list, for the payment selected,
+ * and sends its details with a menu of actions: edit, delete, duplicate, dispute,
+ * add a note or receipt.
+ * Points to PaymentCard state.
+ */
+pub async fn action_view_payment_card(
+    redis: &Redis,
+    bot: &Bot,
+    dialogue: &UserDialogue,
+    msg: &Message,
+    (payments, page): (Vec<Payment>, usize),
+    index: usize,
+) -> HandlerResult {
+    let language = retrieve_language(redis, &msg.chat.id.to_string()).await;
+    let (card, buttons) =
+        display_payment_card(redis, &payments[index], index, get_topic_id(msg), &language).await;
+    let new_message = send_bot_message(bot, msg, card)
+        .reply_markup(make_keyboard(buttons, Some(2), &language))
+        .await?
+        .id;
+
+    dialogue
+        .update(State::PaymentCard {
+            messages: MessageTracker::new(new_message),
+            payments,
+            page,
+            index,
+        })
+        .await?;
+    Ok(())
+}
+
+/* Cancels the payment card, returning to the list of payments.
+ * Can be called at any step of the process.
+ */
+pub async fn cancel_payment_card(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    send_bot_message(
+        &bot,
+        &msg,
+        t(&redis, &msg.chat.id.to_string(), PAYMENT_CARD_CLOSED, &[]).await,
+    )
+    .await?;
+
+    match state {
+        State::PaymentCard {
+            messages,
+            payments,
+            page,
+            index: _,
+        }
+        | State::PaymentCardDetails {
+            messages,
+            payments,
+            page,
+            index: _,
+            detail: _,
+        } => {
+            complete_payment_card(
+                &redis,
+                &bot,
+                dialogue,
+                &msg.chat.id.to_string(),
+                messages,
+                payments,
+                page,
+            )
+            .await?;
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/* Blocks user command.
+ * Called when user attempts to start another operation while viewing a payment.
+ */
+pub async fn block_payment_card(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let new_message = send_bot_message(
+        &bot,
+        &msg,
+        t(
+            &redis,
+            &msg.chat.id.to_string(),
+            PAYMENT_CARD_BLOCKED,
+            &[("cancel", COMMAND_CANCEL)],
+        )
+        .await,
+    )
+    .await?
+    .id;
+
+    repeat_state(dialogue, state, new_message).await?;
+    Ok(())
+}
+
+/* Handles the menu of actions on the detail card of a payment.
+ * Bot receives a callback query, and edits, deletes, or duplicates the payment,
+ * or asks for its note or receipt, or shows its receipt.
+ */
+pub async fn action_payment_card_menu(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    (messages, payments, page, index): (MessageTracker, Vec<Payment>, usize, usize),
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+
+        if let Some(msg) = &query.message {
+            let chat_id = msg.chat.id.to_string();
+            let payment = payments[index].clone();
+            match button.as_str() {
+                EDIT_BUTTON | DELETE_BUTTON => {
+                    if !is_user_allowed(&redis, &bot, msg.chat.id, query.from.id).await? {
+                        send_bot_message(&bot, msg, t(&redis, &chat_id, ADMIN_ONLY, &[]).await)
+                            .await?;
+                        return Ok(());
+                    }
+
+                    if button.as_str() == EDIT_BUTTON {
+                        action_edit_payment(
+                            &redis,
+                            bot,
+                            dialogue,
+                            msg,
+                            msg.id,
+                            (messages, payments, page),
+                            index,
+                        )
+                        .await?;
+                    } else {
+                        action_delete_payment(
+                            &redis,
+                            bot,
+                            dialogue,
+                            msg,
+                            msg.id,
+                            (messages, payments, page),
+                            vec![index],
+                        )
+                        .await?;
+                    }
+                }
+                DUPLICATE_BUTTON => {
+                    action_add_payment_duplicate(
+                        &redis,
+                        &bot,
+                        &dialogue,
+                        msg,
+                        &query.from,
+                        messages,
+                        &payment,
+                    )
+                    .await?;
+                }
+                ADD_NOTE_BUTTON | EDIT_NOTE_BUTTON | ADD_RECEIPT_BUTTON | CHANGE_RECEIPT_BUTTON => {
+                    let (detail, prompt) = match button.as_str() {
+                        ADD_NOTE_BUTTON | EDIT_NOTE_BUTTON => (
+                            PaymentCardDetail::Note,
+                            t(
+                                &redis,
+                                &chat_id,
+                                PAYMENT_CARD_NOTE_PROMPT,
+                                &[("max", &NOTE_MAX_LENGTH.to_string())],
+                            )
+                            .await,
+                        ),
+                        _ => (
+                            PaymentCardDetail::Receipt,
+                            t(&redis, &chat_id, PAYMENT_CARD_RECEIPT_PROMPT, &[]).await,
+                        ),
+                    };
+                    let mut messages = messages;
+                    let new_message = send_bot_message(&bot, msg, prompt).await?.id;
+                    messages.track(new_message);
+                    dialogue
+                        .update(State::PaymentCardDetails {
+                            messages,
+                            payments,
+                            page,
+                            index,
+                            detail,
+                        })
+                        .await?;
+                }
+                DISPUTE_BUTTON => {
+                    let username = match &query.from.username {
+                        Some(username) if is_payment_disputable(&payment, username) => username,
+                        _ => {
+                            send_bot_message(
+                                &bot,
+                                msg,
+                                t(&redis, &chat_id, PAYMENT_CARD_NOT_DISPUTABLE, &[]).await,
+                            )
+                            .await?;
+                            return Ok(());
+                        }
+                    };
+
+                    action_dispute_payment(&redis, &bot, msg, &payment, username).await?;
+                    let mut payments = payments;
+                    payments[index].is_disputed = true;
+                    complete_payment_card(
+                        &redis, &bot, dialogue, &chat_id, messages, payments, page,
+                    )
+                    .await?;
+                }
+                SHOW_RECEIPT_BUTTON => {
+                    if payment.is_private {
+                        send_bot_message(
+                            &bot,
+                            msg,
+                            t(&redis, &chat_id, PAYMENT_CARD_PRIVATE, &[]).await,
+                        )
+                        .await?;
+                        return Ok(());
+                    }
+
+                    if let Some(receipt) =
+                        retrieve_payment_receipt(&redis, &payment.payment_id).await?
+                    {
+                        let caption = t(
+                            &redis,
+                            &chat_id,
+                            PAYMENT_CARD_RECEIPT_CAPTION,
+                            &[("description", &payment.description)],
+                        )
+                        .await;
+                        let photo = InputFile::file_id(receipt);
+                        match msg.thread_id {
+                            Some(thread_id) => {
+                                bot.send_photo(msg.chat.id, photo)
+                                    .caption(caption)
+                                    .message_thread_id(thread_id)
+                                    .await?
+                            }
+                            None => bot.send_photo(msg.chat.id, photo).caption(caption).await?,
+                        };
+                    }
+                }
+                BACK_BUTTON => {
+                    complete_payment_card(
+                        &redis, &bot, dialogue, &chat_id, messages, payments, page,
+                    )
+                    .await?;
+                }
+                _ => {
+                    log::error!(
+                        "Payment Card Menu - Invalid button in chat {}: {}",
+                        chat_id,
+                        button
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/* Handles the menu of actions on the detail card of a payment,
+ * while its note or receipt is being asked for.
+ * Bot receives a callback query, and abandons the note or receipt for the new action.
+ */
+pub async fn action_payment_card_details_menu(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    (messages, payments, page, index, _detail): (
+        MessageTracker,
+        Vec<Payment>,
+        usize,
+        usize,
+        PaymentCardDetail,
+    ),
+) -> HandlerResult {
+    action_payment_card_menu(
+        redis.clone(),
+        bot,
+        dialogue,
+        query,
+        (messages, payments, page, index),
+    )
+    .await
+}
+
+/* Adds a note or receipt to a payment.
+ * Bot receives a string for a note, or a photo for a receipt,
+ * and saves it, before showing the updated detail card.
+ */
+pub async fn action_payment_card_details(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+    (messages, payments, page, index, detail): (
+        MessageTracker,
+        Vec<Payment>,
+        usize,
+        usize,
+        PaymentCardDetail,
+    ),
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    let payment = &payments[index];
+
+    let result = match detail {
+        PaymentCardDetail::Note => match msg.text().map(|text| text.trim()) {
+            Some(note) if !note.is_empty() && note.chars().count() <= NOTE_MAX_LENGTH => {
+                update_payment_note(&redis, &payment.payment_id, note).await
+            }
+            _ => {
+                let new_message = send_bot_message(
+                    &bot,
+                    &msg,
+                    t(
+                        &redis,
+                        &chat_id,
+                        PAYMENT_CARD_NOTE_INVALID,
+                        &[("max", &NOTE_MAX_LENGTH.to_string())],
+                    )
+                    .await,
+                )
+                .await?
+                .id;
+                repeat_state(dialogue, state, new_message).await?;
+                return Ok(());
+            }
+        },
+        PaymentCardDetail::Receipt => match msg.photo().and_then(|photos| photos.last()) {
+            Some(photo) => {
+                update_payment_receipt(&redis, &payment.payment_id, &photo.file.id).await
+            }
+            None => {
+                let new_message = send_bot_message(
+                    &bot,
+                    &msg,
+                    t(&redis, &chat_id, PAYMENT_CARD_RECEIPT_INVALID, &[]).await,
+                )
+                .await?
+                .id;
+                repeat_state(dialogue, state, new_message).await?;
+                return Ok(());
+            }
+        },
+    };
+
+    match result {
+        Ok(()) => {
+            // Logging
+            log::info!(
+                "Payment Card - {:?} added to payment {} in chat {}",
+                detail,
+                payment.payment_id,
+                chat_id
+            );
+
+            cleanup_messages(&redis, &bot, &chat_id, messages).await?;
+            action_view_payment_card(&redis, &bot, &dialogue, &msg, (payments, page), index)
+                .await?;
+        }
+        Err(err) => {
+            let err = BotError::from(err);
+            send_bot_message(
+                &bot,
+                &msg,
+                error_to_user_message(&redis, &err, &chat_id).await,
+            )
+            .await?;
+
+            // Logging
+            log::error!(
+                "Payment Card - Failed to add {:?} to payment {} in chat {}: {}",
+                detail,
+                payment.payment_id,
+                chat_id,
+                err.to_string()
+            );
+
+            complete_payment_card(&redis, &bot, dialogue, &chat_id, messages, payments, page)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    handler::{
+        utils::{
+            assert_handle_request_limit, display_payment, retrieve_time_zone, send_bot_message,
+            HandlerResult,
+        },
+        view_payments::unfold_payment,
+    },
+    processor::retrieve_private_payments,
+    redis::{Redis, UserPayment},
+};
+
+/* Utilities */
+const PRIVATE_DISPLAY_COUNT: usize = 10;
+
+// Gets the title of a group chat, if the bot can still see it
+async fn get_chat_title(bot: &Bot, chat_id: &str) -> String {
+    let title = match chat_id.parse::<i64>() {
+        Ok(id) => match bot.get_chat(ChatId(id)).await {
+            Ok(chat) => chat.title().map(|title| title.to_string()),
+            Err(_) => None,
+        },
+        Err(_) => None,
+    };
+    title.unwrap_or("a group".to_string())
+}
+
+// Displays the latest private payments of a group chat in full
+async fn display_private_payments(
+    redis: &Redis,
+    bot: &Bot,
+    chat_id: &str,
+    payments: Vec<UserPayment>,
+) -> String {
+    let time_zone = retrieve_time_zone(redis, chat_id).await;
+    let mut formatted_payments: Vec<String> = Vec::new();
+    for (index, payment) in payments.into_iter().take(PRIVATE_DISPLAY_COUNT).enumerate() {
+        // Shown in full, as only those involved can see this
+        let mut payment = unfold_payment(payment);
+        payment.is_private = false;
+        formatted_payments.push(display_payment(redis, &payment, index + 1, time_zone).await);
+    }
+
+    format!(
+        "🔒 Here are the latest private payments you're in, from {}!\n\n{}",
+        get_chat_title(bot, chat_id).await,
+        formatted_payments.join("")
+    )
+}
+
+/* Views the private payments that the sender is involved in, across all their group chats.
+ * Only done in a direct message with the bot, so that the descriptions stay hidden in the groups.
+ */
+pub async fn action_private(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    if !msg.chat.is_private() {
+        send_bot_message(
+            &bot,
+            &msg,
+            "🔒 To keep private payments private, send me /private in a direct message instead, and I'll show you the ones you're in!".to_string(),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let user = match msg.from() {
+        Some(user) => user,
+        None => return Ok(()),
+    };
+    let sender_id = user.id.to_string();
+    let payments = retrieve_private_payments(&redis, &sender_id, user.username.as_deref()).await?;
+    if payments.is_empty() {
+        send_bot_message(
+            &bot,
+            &msg,
+            "You're not in any private payments yet! 🔒 When someone makes a payment private in a group, I'll only show its description to those involved, right here.".to_string(),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // Group the payments by chat, in the order they were found
+    let mut chats: Vec<(String, Vec<UserPayment>)> = Vec::new();
+    for payment in payments {
+        match chats
+            .iter_mut()
+            .find(|(chat_id, _)| *chat_id == payment.chat_id)
+        {
+            Some((_, chat_payments)) => chat_payments.push(payment),
+            None => chats.push((payment.chat_id.clone(), vec![payment])),
+        }
+    }
+    for (chat_id, chat_payments) in chats {
+        let reply = display_private_payments(&redis, &bot, &chat_id, chat_payments).await;
+        send_bot_message(&bot, &msg, reply).await?;
+    }
+
+    // Logging
+    log::info!("Private - User {} viewed their private payments", sender_id);
+
+    Ok(())
+}
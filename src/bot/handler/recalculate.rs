@@ -0,0 +1,47 @@
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    handler::utils::{assert_handle_request_limit, is_chat_admin, send_bot_message, HandlerResult},
+    processor::recalculate_chat_balances,
+    redis::Redis,
+};
+
+/* Recalculates all balances and spendings of a group chat from its payments.
+ * Fixes balances that no longer match the payments, and can only be done by admins of the chat.
+ */
+pub async fn action_recalculate(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    if !is_chat_admin(&redis, &bot, &msg).await? {
+        send_bot_message(
+            &bot,
+            &msg,
+            "🔒 Only admins of this chat can recalculate the balances!".to_string(),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let count = recalculate_chat_balances(&redis, &chat_id).await?;
+    send_bot_message(
+        &bot,
+        &msg,
+        format!(
+            "✅ All done! I've recalculated all balances and spendings from {count} payment{}, including those of trips.\n\nCheck them out with /balances!",
+            if count == 1 { "" } else { "s" }
+        ),
+    )
+    .await?;
+
+    // Logging
+    log::info!(
+        "Recalculate - Balances recalculated for chat {} from {} payments",
+        chat_id,
+        count
+    );
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use chrono::{NaiveDate, NaiveTime, Utc};
+use teloxide::{
+    payloads::SendMessageSetters,
+    prelude::*,
+    types::{ChatId, InlineKeyboardMarkup},
+};
+
+use crate::bot::{
+    handler::utils::{
+        assert_handle_request_limit, display_balances, get_overdue_debtors, get_statement_options,
+        is_debtor_overdue, make_payment_links, mention_username, retrieve_time_zone,
+        send_bot_message, with_retry, HandlerResult,
+    },
+    processor::{
+        get_chat_setting, is_username_equal, retrieve_debt_reminder_chats, retrieve_debts,
+        retrieve_last_reminder_date, retrieve_valid_currencies, update_last_reminder_date,
+        ChatSetting, ProcessError, StatementOption,
+    },
+    redis::{Debt, Redis},
+};
+
+/* Utilities */
+const REMINDER_TIME: &str = "12:00";
+
+// Composes the reminder message for a chat, mentioning all debtors
+// Includes the payment links of creditors, if any
+// Returns None if there are no outstanding debts
+async fn display_debt_reminder(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<(String, InlineKeyboardMarkup)>, ProcessError> {
+    let currencies = retrieve_valid_currencies(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    let options = get_statement_options(redis, chat_id, currencies).await;
+    let multiple = options.len() > 1;
+
+    let overdue_debtors = get_overdue_debtors(redis, chat_id).await;
+    let mut balances = String::new();
+    let mut all_debts: Vec<Debt> = Vec::new();
+    for option in options {
+        let mut debts = retrieve_debts(redis, chat_id, option.clone()).await?;
+        if debts.is_empty() {
+            continue;
+        }
+
+        // Debts past their due date are listed first
+        debts.sort_by_key(|debt| !is_debtor_overdue(&debt.debtor, &overdue_debtors));
+        if multiple {
+            if let StatementOption::Currency(currency) = &option {
+                balances.push_str(&format!("In {currency}:\n"));
+            }
+        }
+        balances.push_str(&display_balances(redis, chat_id, &debts).await);
+        if multiple {
+            balances.push('\n');
+        }
+        all_debts.extend(debts);
+    }
+
+    if balances.is_empty() {
+        return Ok(None);
+    }
+
+    let (payment_numbers, payment_buttons) = make_payment_links(redis, chat_id, &all_debts).await;
+    if !payment_numbers.is_empty() {
+        balances.push_str(&format!("\n{payment_numbers}"));
+    }
+
+    let mut overdue: Vec<String> = Vec::new();
+    for debt in &all_debts {
+        if is_debtor_overdue(&debt.debtor, &overdue_debtors)
+            && !overdue
+                .iter()
+                .any(|user| is_username_equal(user, &debt.debtor))
+        {
+            overdue.push(debt.debtor.clone());
+        }
+    }
+    let overdue_message = if overdue.is_empty() {
+        "".to_string()
+    } else {
+        let names: Vec<String> = overdue
+            .iter()
+            .map(|debtor| mention_username(debtor))
+            .collect();
+        format!(
+            "⏰ {}, some of your debts are past their due date, so please settle up first!\n\n",
+            names.join(", ")
+        )
+    };
+    Ok(Some((
+        format!(
+            "🔔 Friendly reminder! These balances are still outstanding:\n\n{}{}\n\nUse /payback once you've paid your friends back!",
+            overdue_message,
+            balances.trim_end()
+        ),
+        InlineKeyboardMarkup::new(payment_buttons),
+    )))
+}
+
+// Posts the debt reminder for a single chat, if it is due
+async fn post_reminder_chat(redis: &Redis, bot: &Bot, chat_id: &str) -> HandlerResult {
+    let days = match get_chat_setting(redis, chat_id, ChatSetting::DebtReminder(None)).await? {
+        ChatSetting::DebtReminder(Some(Some(days))) => days,
+        _ => return Ok(()),
+    };
+
+    let time_zone = retrieve_time_zone(redis, chat_id).await;
+    let now = Utc::now().with_timezone(&time_zone);
+    let time = NaiveTime::parse_from_str(REMINDER_TIME, "%H:%M").expect("Reminder time is valid");
+    if now.time() < time {
+        return Ok(());
+    }
+
+    let today = now.date_naive();
+    if let Some(date) = retrieve_last_reminder_date(redis, chat_id).await? {
+        if let Ok(date) = NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+            if (today - date).num_days() < days as i64 {
+                return Ok(());
+            }
+        }
+    }
+
+    // No reminder needed if everyone is settled up
+    let (message, keyboard) = match display_debt_reminder(redis, chat_id).await? {
+        Some(reminder) => reminder,
+        None => return Ok(()),
+    };
+
+    // Marked as posted first, so that a failed send is not retried every minute
+    let today = today.format("%Y-%m-%d").to_string();
+    update_last_reminder_date(redis, chat_id, &today).await?;
+    if let Ok(id) = chat_id.parse::<i64>() {
+        with_retry(bot.send_message(ChatId(id), message).reply_markup(keyboard)).await?;
+    }
+
+    // Logging
+    log::info!(
+        "Debt Reminder - Posted reminder for chat {} on {}",
+        chat_id,
+        today
+    );
+
+    Ok(())
+}
+
+/* Reminds all debtors in a group chat of their outstanding balances.
+ * Can be called by anyone in the chat, such as a creditor waiting to be paid back.
+ */
+pub async fn action_remind(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    match display_debt_reminder(&redis, &chat_id).await? {
+        Some((reply, keyboard)) => {
+            send_bot_message(&bot, &msg, reply)
+                .reply_markup(keyboard)
+                .await?;
+        }
+        None => {
+            send_bot_message(
+                &bot,
+                &msg,
+                "🔔 There's nothing to remind about, everyone is settled up! 🥳".to_string(),
+            )
+            .await?;
+        }
+    }
+
+    // Logging
+    log::info!("Remind - Reminder sent in chat {}", chat_id);
+
+    Ok(())
+}
+
+/* Posts the debt reminder for all chats with debt reminder enabled.
+ * Called periodically by the scheduler, only acts once the interval set by each chat has passed.
+ */
+pub async fn handle_debt_reminder(redis: &Redis, bot: &Bot) -> HandlerResult {
+    let chats = retrieve_debt_reminder_chats(redis).await?;
+    for chat_id in chats {
+        if let Err(err) = post_reminder_chat(redis, bot, &chat_id).await {
+            log::error!(
+                "Debt Reminder - Failed to post reminder for chat {}: {}",
+                chat_id,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
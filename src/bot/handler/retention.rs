@@ -0,0 +1,207 @@
+use chrono::{Months, NaiveTime, TimeZone, Utc};
+use teloxide::{
+    payloads::SendDocumentSetters,
+    prelude::*,
+    types::{ChatId, InputFile},
+};
+
+use crate::bot::{
+    currency::get_default_currency,
+    export::{render_pdf, DocumentSection},
+    handler::utils::{
+        display_currency_amount, display_name, display_payment_date, get_currency,
+        retrieve_currency_format, retrieve_language, retrieve_time_zone, with_retry, HandlerResult,
+    },
+    i18n::{
+        translate, RETENTION_CAPTION, RETENTION_CAPTION_ONE, RETENTION_MONTHS, RETENTION_MONTHS_ONE,
+    },
+    processor::{
+        clear_expired_archives, clear_expired_payments, get_chat_setting,
+        retrieve_data_retention_chats, retrieve_display_names, retrieve_expired_payments,
+        retrieve_last_retention_date, update_last_retention_date, ChatSetting, CleanupSummary,
+    },
+    redis::Redis,
+};
+
+/* Utilities */
+const RETENTION_TIME: &str = "03:00";
+
+// Composes the sections of the summary of cleared payments, exported before they are cleared
+// Each payment has a line, followed by the total spent in each currency
+async fn make_cleanup_sections(
+    redis: &Redis,
+    chat_id: &str,
+    summary: &CleanupSummary,
+) -> Vec<DocumentSection> {
+    let time_zone = retrieve_time_zone(redis, chat_id).await;
+    let names = retrieve_display_names(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    // Most currency symbols are outside Latin-1, so only the thousands separators are kept
+    let currency_format = retrieve_currency_format(redis, chat_id).await;
+    let currency_format = (currency_format.0, false, currency_format.2);
+    let display_total = |currency: &str, amount: i64| {
+        let currency = get_currency(currency).unwrap_or(get_default_currency());
+        display_currency_amount(amount, currency, currency_format)
+    };
+
+    let payments = summary
+        .payments
+        .iter()
+        .map(|entry| {
+            let payment = &entry.payment;
+            let description = if entry.is_private {
+                "Private payment"
+            } else {
+                &payment.description
+            };
+            format!(
+                "{} - {}: {} paid by {}",
+                display_payment_date(&payment.datetime, time_zone),
+                description,
+                display_total(&payment.currency, payment.total),
+                display_name(&payment.creditor, &names)
+            )
+        })
+        .collect();
+    let totals = summary
+        .totals
+        .iter()
+        .map(|(currency, total)| display_total(currency, *total))
+        .collect();
+
+    vec![
+        DocumentSection {
+            heading: "Cleared Payments".to_string(),
+            lines: payments,
+        },
+        DocumentSection {
+            heading: "Total Spent".to_string(),
+            lines: totals,
+        },
+    ]
+}
+
+// Clears the payments of a single chat that are older than its retention period, if due
+async fn clean_up_chat(redis: &Redis, bot: &Bot, chat_id: &str) -> HandlerResult {
+    let months = match get_chat_setting(redis, chat_id, ChatSetting::DataRetention(None)).await? {
+        ChatSetting::DataRetention(Some(Some(months))) => months,
+        _ => return Ok(()),
+    };
+
+    let time_zone = retrieve_time_zone(redis, chat_id).await;
+    let now = Utc::now().with_timezone(&time_zone);
+    let time = NaiveTime::parse_from_str(RETENTION_TIME, "%H:%M").expect("Retention time is valid");
+    let today = now.date_naive().format("%Y-%m-%d").to_string();
+    if now.time() < time
+        || retrieve_last_retention_date(redis, chat_id).await? == Some(today.clone())
+    {
+        return Ok(());
+    }
+
+    let cutoff = match now
+        .date_naive()
+        .checked_sub_months(Months::new(months))
+        .and_then(|date| time_zone.from_local_datetime(&date.into()).earliest())
+    {
+        Some(cutoff) => cutoff,
+        None => return Ok(()),
+    };
+    let label = cutoff.format("%e %b %Y").to_string().trim().to_string();
+    let cutoff = cutoff.naive_utc();
+
+    // Marked as run first, so that a failed cleanup is not retried every minute
+    update_last_retention_date(redis, chat_id, &today).await?;
+
+    // Archived payments already had their balances carried forward, and are cleared quietly
+    let archived = clear_expired_archives(redis, chat_id, cutoff).await?;
+    if archived > 0 {
+        // Logging
+        log::info!(
+            "Data Retention - Cleared {} archived payments for chat {} from before {}",
+            archived,
+            chat_id,
+            label
+        );
+    }
+
+    let summary = match retrieve_expired_payments(redis, chat_id, cutoff).await? {
+        Some(summary) => summary,
+        None => return Ok(()),
+    };
+    let id = match chat_id.parse::<i64>() {
+        Ok(id) => id,
+        Err(_) => return Ok(()),
+    };
+
+    // Summary is exported first, and payments are only cleared once it is sent
+    let sections = make_cleanup_sections(redis, chat_id, &summary).await;
+    let content = render_pdf(
+        "Cleared Payments",
+        &format!("Payments from before {label}, cleared by PayScribe on {today}"),
+        &sections,
+    );
+    let language = retrieve_language(redis, chat_id).await;
+    let months = if months == 1 {
+        translate(&language, RETENTION_MONTHS_ONE, &[])
+    } else {
+        translate(
+            &language,
+            RETENTION_MONTHS,
+            &[("count", &months.to_string())],
+        )
+    };
+    let count = summary.payments.len();
+    let key = if count == 1 {
+        RETENTION_CAPTION_ONE
+    } else {
+        RETENTION_CAPTION
+    };
+    let caption = translate(
+        &language,
+        key,
+        &[
+            ("count", &count.to_string()),
+            ("date", &label),
+            ("months", &months),
+        ],
+    );
+    with_retry(
+        bot.send_document(
+            ChatId(id),
+            InputFile::memory(content).file_name(format!("cleared_payments_{today}.pdf")),
+        )
+        .caption(caption),
+    )
+    .await?;
+
+    clear_expired_payments(redis, chat_id, &summary.payment_ids, cutoff).await?;
+
+    // Logging
+    log::info!(
+        "Data Retention - Cleared {} payments for chat {} from before {}",
+        summary.payment_ids.len(),
+        chat_id,
+        label
+    );
+
+    Ok(())
+}
+
+/* Clears old payments for all chats with data retention enabled.
+ * Called periodically by the scheduler, only acts once a day for each chat.
+ */
+pub async fn handle_data_retention(redis: &Redis, bot: &Bot) -> HandlerResult {
+    let chats = retrieve_data_retention_chats(redis).await?;
+    for chat_id in chats {
+        if let Err(err) = clean_up_chat(redis, bot, &chat_id).await {
+            log::error!(
+                "Data Retention - Failed to clear payments for chat {}: {}",
+                chat_id,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -1,38 +1,1173 @@
+use chrono_tz::Tz;
 use teloxide::{
     payloads::{EditMessageTextSetters, SendMessageSetters},
     prelude::*,
-    types::{Message, MessageId, ParseMode},
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message, MessageId},
 };
 
 use crate::bot::{
-    currency::CURRENCY_DEFAULT,
+    currency::{
+        get_currency_format, get_number_format, CURRENCY_DEFAULT, CURRENCY_FORMATS, NUMBER_FORMATS,
+    },
     dispatcher::State,
     handler::{
-        constants::{
-            COMMAND_CANCEL, CURRENCY_INSTRUCTIONS_MESSAGE, NO_TEXT_MESSAGE,
-            TIME_ZONE_INSTRUCTIONS_MESSAGE,
-        },
+        constants::{all_time_zones, COMMAND_CANCEL, TIME_ZONE_REGIONS},
+        onboarding::{continue_onboarding, end_onboarding, is_onboarding, ONBOARDING_SKIP},
         utils::{
-            get_currency, is_erase_messages, make_keyboard, parse_time_zone, retrieve_time_zone,
-            HandlerResult, UserDialogue,
+            assert_admin_only, error_to_user_message, get_chat_default_currency, get_currency,
+            get_rounding, get_topic_id, get_verbosity, is_chat_admin, is_erase_user_messages,
+            is_user_admin, make_keyboard, parse_digest_time, parse_float, parse_reminder_days,
+            parse_retention_months, parse_time_zone, parse_username, retrieve_allowed_currencies,
+            retrieve_language, retrieve_time_zone, retrieve_topic_default_currency, BotError,
+            HandlerResult, Rounding, UserDialogue, Verbosity,
         },
     },
-    processor::{get_chat_setting, set_chat_setting, update_chat_default_currency, ChatSetting},
+    i18n::{get_language, translate, translate_button, LANGUAGES, LANGUAGE_MENU, LANGUAGE_UPDATED},
+    i18n::{
+        Text, CURRENCY_INSTRUCTIONS, NO_TEXT, SETTINGS_ADMIN_ONLY_OFF,
+        SETTINGS_ADMIN_ONLY_OFF_PROMPT, SETTINGS_ADMIN_ONLY_ON, SETTINGS_ADMIN_ONLY_ON_PROMPT,
+        SETTINGS_ADMIN_ONLY_RESTRICTED, SETTINGS_ADMIN_ONLY_STATUS, SETTINGS_APPROVAL_ENABLED,
+        SETTINGS_APPROVAL_OFF, SETTINGS_APPROVAL_OFF_PROMPT, SETTINGS_APPROVAL_ON,
+        SETTINGS_APPROVAL_ON_PROMPT, SETTINGS_APPROVAL_STATUS, SETTINGS_APPROVAL_THRESHOLD_PROMPT,
+        SETTINGS_ARCHIVE_OFF, SETTINGS_ARCHIVE_OFF_PROMPT, SETTINGS_ARCHIVE_ON,
+        SETTINGS_ARCHIVE_ON_PROMPT, SETTINGS_ARCHIVE_STATUS, SETTINGS_ARGUMENTS, SETTINGS_BLOCKED,
+        SETTINGS_CANCELLED, SETTINGS_CONVERSION_NO_CURRENCY, SETTINGS_CONVERSION_OFF,
+        SETTINGS_CONVERSION_OFF_PROMPT, SETTINGS_CONVERSION_ON, SETTINGS_CONVERSION_ON_PROMPT,
+        SETTINGS_CONVERSION_STATUS, SETTINGS_CURRENCIES_ALL, SETTINGS_CURRENCIES_DEFAULT_MISSING,
+        SETTINGS_CURRENCIES_INVALID, SETTINGS_CURRENCIES_RESTRICTED, SETTINGS_CURRENCIES_UPDATED,
+        SETTINGS_CURRENCY_CURRENT, SETTINGS_CURRENCY_DISABLED, SETTINGS_CURRENCY_FORMAT_MENU,
+        SETTINGS_CURRENCY_FORMAT_UNKNOWN, SETTINGS_CURRENCY_FORMAT_UPDATED, SETTINGS_CURRENCY_MENU,
+        SETTINGS_CURRENCY_NOT_ALLOWED, SETTINGS_CURRENCY_NOT_SET, SETTINGS_CURRENCY_PROMPT,
+        SETTINGS_CURRENCY_UPDATED, SETTINGS_DIGEST_ENABLED, SETTINGS_DIGEST_OFF,
+        SETTINGS_DIGEST_OFF_PROMPT, SETTINGS_DIGEST_ON, SETTINGS_DIGEST_ON_PROMPT,
+        SETTINGS_DIGEST_STATUS, SETTINGS_DIGEST_TIME_PROMPT, SETTINGS_DISABLED,
+        SETTINGS_DUAL_ENTRY_OFF, SETTINGS_DUAL_ENTRY_ON, SETTINGS_ENABLED,
+        SETTINGS_ERASE_ENABLED_ALL, SETTINGS_ERASE_ENABLED_OWN, SETTINGS_ERASE_OFF,
+        SETTINGS_ERASE_OFF_PROMPT, SETTINGS_ERASE_ON, SETTINGS_ERASE_ON_PROMPT,
+        SETTINGS_ERASE_STATUS, SETTINGS_ERASE_USER_OFF, SETTINGS_ERASE_USER_ON,
+        SETTINGS_LANGUAGE_UNKNOWN, SETTINGS_MENU, SETTINGS_NUMBER_FORMAT_MENU,
+        SETTINGS_NUMBER_FORMAT_UNKNOWN, SETTINGS_NUMBER_FORMAT_UPDATED, SETTINGS_PAIRWISE_OFF,
+        SETTINGS_PAIRWISE_ON, SETTINGS_PASSIVE_OFF, SETTINGS_PASSIVE_ON,
+        SETTINGS_REMINDER_DAYS_PROMPT, SETTINGS_REMINDER_ENABLED, SETTINGS_REMINDER_OFF,
+        SETTINGS_REMINDER_OFF_PROMPT, SETTINGS_REMINDER_ON, SETTINGS_REMINDER_ON_PROMPT,
+        SETTINGS_REMINDER_STATUS, SETTINGS_REPEATED, SETTINGS_RETENTION_OFF, SETTINGS_RETENTION_ON,
+        SETTINGS_ROUNDING_FIRST, SETTINGS_ROUNDING_MENU, SETTINGS_ROUNDING_PAYER,
+        SETTINGS_ROUNDING_RANDOM, SETTINGS_ROUNDING_ROUND_ROBIN, SETTINGS_ROUNDING_UNKNOWN,
+        SETTINGS_STRICT_OFF, SETTINGS_STRICT_ON, SETTINGS_TIME_ZONE_MENU,
+        SETTINGS_TIME_ZONE_PROMPT, SETTINGS_TIME_ZONE_UPDATED, SETTINGS_TOGGLE_INVALID,
+        SETTINGS_TOPIC_CURRENCY_UPDATED, SETTINGS_TOPIC_INHERITED, SETTINGS_TOPIC_INVALID,
+        SETTINGS_TOPIC_NONE, SETTINGS_TOPIC_ONLY, SETTINGS_TOPIC_OWN, SETTINGS_TOPIC_RESET,
+        SETTINGS_TOPIC_SUMMARY, SETTINGS_TOPIC_TIME_ZONE_UPDATED, SETTINGS_TREASURER_ENABLED,
+        SETTINGS_TREASURER_OFF, SETTINGS_TREASURER_OFF_PROMPT, SETTINGS_TREASURER_ON,
+        SETTINGS_TREASURER_ON_PROMPT, SETTINGS_TREASURER_STATUS,
+        SETTINGS_TREASURER_USERNAME_PROMPT, SETTINGS_UNKNOWN, SETTINGS_VALUE_MISSING,
+        SETTINGS_VERBOSITY_COMPACT, SETTINGS_VERBOSITY_MENU, SETTINGS_VERBOSITY_SILENT,
+        SETTINGS_VERBOSITY_UNKNOWN, SETTINGS_VERBOSITY_VERBOSE, SETTINGS_WEEK_START_INVALID,
+        SETTINGS_WEEK_START_MENU, SETTINGS_WEEK_START_MONDAY, SETTINGS_WEEK_START_SUNDAY,
+        TIME_ZONE_INSTRUCTIONS, UNKNOWN_ERROR,
+    },
+    period::{parse_week_start, WeekStart},
+    processor::{
+        get_chat_setting, get_topic_chat_setting, reset_topic_chat_settings, set_chat_setting,
+        set_topic_chat_setting, update_chat_default_currency, ChatSetting,
+    },
+    redis::Redis,
 };
 
-use super::{
-    constants::UNKNOWN_ERROR_MESSAGE,
-    utils::{assert_handle_request_limit, delete_bot_messages, send_bot_message},
+use super::utils::{
+    assert_handle_request_limit, cleanup_messages, edit_markdown_message, edit_tracked_message,
+    send_bot_message, send_markdown_message, t, t_prompt, MessageTracker,
 };
 
 /* Utilities */
-const CANCEL_MESSAGE: &str = "Okay! No changes to my settings have been made! 🌟";
-const TIME_ZONE_DESCRIPTION: &str = "*🕔 Time Zone* — Time zone for displaying date and time";
-const DEFAULT_CURRENCY_DESCRIPTION: &str = "💵 *Default Currency* — Currency used if left blank";
-const CURRENCY_CONVERSION_DESCRIPTION: &str =
-    "↔️ *Currency Conversion* — Convert currencies when calculating balances and spendings";
-const ERASE_MESSAGES_DESCRIPTION: &str =
-    "🚮 *Erase Messages* — Keep only the final updates and automatically delete my other messages";
+const ERASE_USER_MESSAGES_BUTTON: &str = "Erase Yours Too";
+const KEEP_USER_MESSAGES_BUTTON: &str = "Keep Yours";
+const WEEKLY_DIGEST_TIME_DEFAULT: &str = "18:00";
+const DEBT_REMINDER_DAYS_DEFAULT: u32 = 3;
+const DATA_RETENTION_MONTHS_DEFAULT: u32 = 12;
+const PAYMENT_APPROVAL_THRESHOLD_DEFAULT: f64 = 100.0;
+const TIME_ZONE_REGION_PREFIX: &str = "TimeZoneRegion:";
+const TIME_ZONE_CITY_PREFIX: &str = "TimeZoneCity:";
+const TIME_ZONE_REGIONS_CALLBACK: &str = "TimeZoneRegions";
+const TIME_ZONE_REGIONS_BUTTON: &str = "⬅️ Regions";
+const TIME_ZONE_CITIES_PER_PAGE: usize = 24;
+const TIME_ZONE_COLUMNS: usize = 3;
+
+// Displays the city of a time zone as a button, like "Buenos Aires"
+fn display_time_zone_city(time_zone: &Tz) -> String {
+    time_zone
+        .name()
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .replace('_', " ")
+}
+
+// Make keyboard listing all regions of time zones
+fn make_keyboard_time_zone_regions(language: &str) -> InlineKeyboardMarkup {
+    let buttons: Vec<InlineKeyboardButton> = TIME_ZONE_REGIONS
+        .iter()
+        .map(|(region, _)| {
+            InlineKeyboardButton::callback(*region, format!("{TIME_ZONE_REGION_PREFIX}{region}:0"))
+        })
+        .collect();
+
+    let mut rows: Vec<Vec<InlineKeyboardButton>> = buttons
+        .chunks(TIME_ZONE_COLUMNS)
+        .map(|row| row.to_vec())
+        .collect();
+    rows.push(vec![InlineKeyboardButton::callback(
+        translate_button(language, "Back"),
+        "Back",
+    )]);
+    InlineKeyboardMarkup::new(rows)
+}
+
+// Make keyboard listing a page of the cities in a region of time zones
+// Returns None if the region is not known
+fn make_keyboard_time_zone_cities(
+    region: &str,
+    page: usize,
+    language: &str,
+) -> Option<InlineKeyboardMarkup> {
+    let cities = TIME_ZONE_REGIONS
+        .iter()
+        .find(|(name, _)| *name == region)
+        .map(|(_, cities)| *cities)?;
+
+    let start = page * TIME_ZONE_CITIES_PER_PAGE;
+    let buttons: Vec<InlineKeyboardButton> = cities
+        .iter()
+        .skip(start)
+        .take(TIME_ZONE_CITIES_PER_PAGE)
+        .map(|(city, time_zone)| {
+            InlineKeyboardButton::callback(
+                display_time_zone_city(time_zone),
+                format!("{TIME_ZONE_CITY_PREFIX}{city}"),
+            )
+        })
+        .collect();
+
+    let mut rows: Vec<Vec<InlineKeyboardButton>> = buttons
+        .chunks(TIME_ZONE_COLUMNS)
+        .map(|row| row.to_vec())
+        .collect();
+    let mut navigation = Vec::new();
+    if page > 0 {
+        navigation.push(InlineKeyboardButton::callback(
+            "◀️",
+            format!("{TIME_ZONE_REGION_PREFIX}{region}:{}", page - 1),
+        ));
+    }
+    navigation.push(InlineKeyboardButton::callback(
+        translate_button(language, TIME_ZONE_REGIONS_BUTTON),
+        TIME_ZONE_REGIONS_CALLBACK,
+    ));
+    if start + TIME_ZONE_CITIES_PER_PAGE < cities.len() {
+        navigation.push(InlineKeyboardButton::callback(
+            "▶️",
+            format!("{TIME_ZONE_REGION_PREFIX}{region}:{}", page + 1),
+        ));
+    }
+    rows.push(navigation);
+    Some(InlineKeyboardMarkup::new(rows))
+}
+
+// Displays whether a setting is on, followed by what can be done with it
+fn display_setting_status(language: &str, key: &str, status: &str, prompt: &str) -> String {
+    format!(
+        "{}\n\n{}",
+        translate(language, key, &[("status", status)]),
+        translate(language, prompt, &[])
+    )
+}
+
+// Sets the time zone of a chat, from the name of a city, and confirms it
+async fn update_time_zone(
+    redis: &Redis,
+    bot: &Bot,
+    msg: &Message,
+    city: &str,
+    time_zone: Tz,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    let setting = ChatSetting::TimeZone(Some(city.to_string()));
+    match set_chat_setting(redis, &chat_id, setting).await {
+        Ok(_) => {
+            send_bot_message(
+                bot,
+                msg,
+                t(
+                    redis,
+                    &chat_id,
+                    SETTINGS_TIME_ZONE_UPDATED,
+                    &[("time_zone", &time_zone.to_string())],
+                )
+                .await,
+            )
+            .await?;
+
+            // Logging
+            log::info!(
+                "Settings Time Zone - Time zone set for chat {}: {}",
+                chat_id,
+                time_zone
+            );
+        }
+        Err(err) => {
+            let err = BotError::from(err);
+            send_bot_message(bot, msg, error_to_user_message(redis, &err, &chat_id).await).await?;
+
+            // Logging
+            log::error!(
+                "Settings Time Zone - Error setting time zone for chat {}: {}",
+                chat_id,
+                err.to_string()
+            );
+        }
+    }
+    Ok(())
+}
+
+// Displays the confirmation of a verbosity being set
+async fn display_verbosity_updated(redis: &Redis, chat_id: &str, verbosity: &str) -> String {
+    let key = match get_verbosity(verbosity) {
+        Some(Verbosity::Compact) => SETTINGS_VERBOSITY_COMPACT,
+        Some(Verbosity::Silent) => SETTINGS_VERBOSITY_SILENT,
+        _ => SETTINGS_VERBOSITY_VERBOSE,
+    };
+    t(redis, chat_id, key, &[]).await
+}
+
+// Displays the confirmation of a rounding strategy being set
+async fn display_rounding_updated(redis: &Redis, chat_id: &str, rounding: &str) -> String {
+    let key = match get_rounding(rounding) {
+        Some(Rounding::Payer) => SETTINGS_ROUNDING_PAYER,
+        Some(Rounding::Random) => SETTINGS_ROUNDING_RANDOM,
+        Some(Rounding::RoundRobin(_)) => SETTINGS_ROUNDING_ROUND_ROBIN,
+        _ => SETTINGS_ROUNDING_FIRST,
+    };
+    t(redis, chat_id, key, &[]).await
+}
+
+// Displays the confirmation of user messages being erased or kept
+async fn display_erase_user_messages_updated(
+    redis: &Redis,
+    chat_id: &str,
+    erase_user_messages: bool,
+) -> String {
+    let key = if erase_user_messages {
+        SETTINGS_ERASE_USER_ON
+    } else {
+        SETTINGS_ERASE_USER_OFF
+    };
+    t(redis, chat_id, key, &[]).await
+}
+
+// Displays the confirmation of a first day of the week being set
+async fn display_week_start_updated(redis: &Redis, chat_id: &str, week_start: &str) -> String {
+    let key = match parse_week_start(week_start) {
+        Some(WeekStart::Sunday) => SETTINGS_WEEK_START_SUNDAY,
+        _ => SETTINGS_WEEK_START_MONDAY,
+    };
+    t(redis, chat_id, key, &[]).await
+}
+
+// Checks that a currency can be the default currency, if the chat only allows some currencies
+async fn check_default_currency_allowed(
+    redis: &Redis,
+    chat_id: &str,
+    currency: &str,
+) -> Result<(), BotError> {
+    let allowed_currencies = retrieve_allowed_currencies(redis, chat_id).await;
+    if currency == CURRENCY_DEFAULT.0
+        || allowed_currencies.is_empty()
+        || allowed_currencies.iter().any(|code| code == currency)
+    {
+        return Ok(());
+    }
+    Err(BotError::UserError(
+        Text::new(SETTINGS_CURRENCY_NOT_ALLOWED).arg("currencies", allowed_currencies.join(", ")),
+    ))
+}
+
+// Parses the currency codes allowed in a chat, separated by spaces or commas
+// "all" allows every currency, returning no codes
+fn parse_allowed_currencies(value: &str) -> Result<Vec<String>, BotError> {
+    if matches!(
+        value.to_lowercase().as_str(),
+        "all" | "any" | "off" | "none" | "disable"
+    ) {
+        return Ok(Vec::new());
+    }
+
+    let mut currencies: Vec<String> = Vec::new();
+    for code in value.split(|c: char| c.is_whitespace() || c == ',') {
+        if code.is_empty() {
+            continue;
+        }
+        let currency = get_currency(code)?;
+        if currency.0 == CURRENCY_DEFAULT.0 {
+            return Err(BotError::UserError(
+                Text::new(SETTINGS_CURRENCIES_INVALID).then(Text::new(SETTINGS_ARGUMENTS)),
+            ));
+        }
+        if !currencies.contains(&currency.0) {
+            currencies.push(currency.0);
+        }
+    }
+    Ok(currencies)
+}
+
+/* Parses the arguments given together with the settings command.
+ * Returns the setting to be updated, with the new value.
+ */
+fn parse_settings_arguments(args: &str) -> Result<ChatSetting, BotError> {
+    let (setting, value) = match args.trim().split_once(char::is_whitespace) {
+        Some((setting, value)) => (setting.to_lowercase(), value.trim()),
+        None => (args.trim().to_lowercase(), ""),
+    };
+
+    if value.is_empty() {
+        return Err(BotError::UserError(
+            Text::new(SETTINGS_VALUE_MISSING).then(Text::new(SETTINGS_ARGUMENTS)),
+        ));
+    }
+
+    let parse_toggle = |value: &str| match value.to_lowercase().as_str() {
+        "on" | "true" | "yes" | "enable" => Ok(true),
+        "off" | "false" | "no" | "disable" => Ok(false),
+        _ => Err(BotError::UserError(
+            Text::new(SETTINGS_TOGGLE_INVALID).then(Text::new(SETTINGS_ARGUMENTS)),
+        )),
+    };
+
+    match setting.as_str() {
+        "timezone" | "time_zone" | "tz" => {
+            parse_time_zone(value)?;
+            Ok(ChatSetting::TimeZone(Some(value.to_string())))
+        }
+        "currency" | "default_currency" => match value.to_lowercase().as_str() {
+            "off" | "none" | "disable" => Ok(ChatSetting::DefaultCurrency(Some(
+                CURRENCY_DEFAULT.0.to_string(),
+            ))),
+            _ => {
+                let currency = get_currency(value)?;
+                Ok(ChatSetting::DefaultCurrency(Some(currency.0)))
+            }
+        },
+        "conversion" | "currency_conversion" => {
+            Ok(ChatSetting::CurrencyConversion(Some(parse_toggle(value)?)))
+        }
+        "erase" | "erase_messages" => Ok(ChatSetting::EraseMessages(Some(parse_toggle(value)?))),
+        "eraseuser" | "erase_user_messages" => {
+            Ok(ChatSetting::EraseUserMessages(Some(parse_toggle(value)?)))
+        }
+        "archive" | "monthly_archive" => {
+            Ok(ChatSetting::MonthlyArchive(Some(parse_toggle(value)?)))
+        }
+        "digest" | "weekly_digest" => match parse_toggle(value) {
+            Ok(true) => Ok(ChatSetting::WeeklyDigest(Some(Some(
+                WEEKLY_DIGEST_TIME_DEFAULT.to_string(),
+            )))),
+            Ok(false) => Ok(ChatSetting::WeeklyDigest(Some(None))),
+            Err(_) => Ok(ChatSetting::WeeklyDigest(Some(Some(parse_digest_time(
+                value,
+            )?)))),
+        },
+        "reminder" | "debt_reminder" => match parse_toggle(value) {
+            Ok(true) => Ok(ChatSetting::DebtReminder(Some(Some(
+                DEBT_REMINDER_DAYS_DEFAULT,
+            )))),
+            Ok(false) => Ok(ChatSetting::DebtReminder(Some(None))),
+            Err(_) => Ok(ChatSetting::DebtReminder(Some(Some(parse_reminder_days(
+                value,
+            )?)))),
+        },
+        "retention" | "data_retention" => match parse_toggle(value) {
+            Ok(true) => Ok(ChatSetting::DataRetention(Some(Some(
+                DATA_RETENTION_MONTHS_DEFAULT,
+            )))),
+            Ok(false) => Ok(ChatSetting::DataRetention(Some(None))),
+            Err(_) => Ok(ChatSetting::DataRetention(Some(Some(
+                parse_retention_months(value)?,
+            )))),
+        },
+        "approval" | "payment_approval" => match parse_toggle(value) {
+            Ok(true) => Ok(ChatSetting::PaymentApproval(Some(Some(
+                PAYMENT_APPROVAL_THRESHOLD_DEFAULT,
+            )))),
+            Ok(false) => Ok(ChatSetting::PaymentApproval(Some(None))),
+            Err(_) => Ok(ChatSetting::PaymentApproval(Some(Some(parse_float(
+                value,
+            )?)))),
+        },
+        "treasurer" => match value.to_lowercase().as_str() {
+            "off" | "none" | "disable" => Ok(ChatSetting::Treasurer(Some(None))),
+            _ => Ok(ChatSetting::Treasurer(Some(Some(parse_username(value)?)))),
+        },
+        "admin" | "admin_only" => Ok(ChatSetting::AdminOnly(Some(parse_toggle(value)?))),
+        "pairwise" | "pairwise_debts" => Ok(ChatSetting::PairwiseDebts(Some(parse_toggle(value)?))),
+        "passive" | "passive_mode" => Ok(ChatSetting::PassiveMode(Some(parse_toggle(value)?))),
+        "dualentry" | "dual_entry" => Ok(ChatSetting::DualEntry(Some(parse_toggle(value)?))),
+        "strict" | "strict_members" => Ok(ChatSetting::StrictMembers(Some(parse_toggle(value)?))),
+        "language" | "lang" => match get_language(value) {
+            Some(language) => Ok(ChatSetting::Language(Some(language.0.to_string()))),
+            None => Err(BotError::UserError(
+                Text::new(SETTINGS_LANGUAGE_UNKNOWN).then(Text::new(SETTINGS_ARGUMENTS)),
+            )),
+        },
+        "number" | "number_format" => match get_number_format(value) {
+            Some(number_format) => Ok(ChatSetting::NumberFormat(Some(number_format.0.to_string()))),
+            None => Err(BotError::UserError(
+                Text::new(SETTINGS_NUMBER_FORMAT_UNKNOWN).then(Text::new(SETTINGS_ARGUMENTS)),
+            )),
+        },
+        "format" | "currency_format" => match get_currency_format(value) {
+            Some(currency_format) => Ok(ChatSetting::CurrencyFormat(Some(
+                currency_format.0.to_string(),
+            ))),
+            None => Err(BotError::UserError(
+                Text::new(SETTINGS_CURRENCY_FORMAT_UNKNOWN).then(Text::new(SETTINGS_ARGUMENTS)),
+            )),
+        },
+        "verbosity" => match get_verbosity(value) {
+            Some(_) => Ok(ChatSetting::Verbosity(Some(value.to_lowercase()))),
+            None => Err(BotError::UserError(
+                Text::new(SETTINGS_VERBOSITY_UNKNOWN).then(Text::new(SETTINGS_ARGUMENTS)),
+            )),
+        },
+        "rounding" => match get_rounding(value) {
+            Some(_) => Ok(ChatSetting::Rounding(Some(
+                value.to_lowercase().replace([' ', '-', '_'], ""),
+            ))),
+            None => Err(BotError::UserError(
+                Text::new(SETTINGS_ROUNDING_UNKNOWN).then(Text::new(SETTINGS_ARGUMENTS)),
+            )),
+        },
+        "weekstart" | "week_start" | "week" => match parse_week_start(value) {
+            Some(week_start) => Ok(ChatSetting::WeekStart(Some(week_start.name().to_string()))),
+            None => Err(BotError::UserError(
+                Text::new(SETTINGS_WEEK_START_INVALID).then(Text::new(SETTINGS_ARGUMENTS)),
+            )),
+        },
+        "currencies" | "allowed_currencies" => Ok(ChatSetting::AllowedCurrencies(Some(
+            parse_allowed_currencies(value)?,
+        ))),
+        _ => Err(BotError::UserError(
+            Text::new(SETTINGS_UNKNOWN).then(Text::new(SETTINGS_ARGUMENTS)),
+        )),
+    }
+}
+
+// Displays the time zone and default currency of a topic, noting where each comes from
+async fn display_topic_settings(redis: &Redis, chat_id: &str, topic_id: i32) -> String {
+    let topic_time_zone =
+        get_topic_chat_setting(redis, chat_id, Some(topic_id), ChatSetting::TimeZone(None)).await;
+    let chat_time_zone = get_chat_setting(redis, chat_id, ChatSetting::TimeZone(None)).await;
+    let time_zone = match (&topic_time_zone, &chat_time_zone) {
+        (Ok(ChatSetting::TimeZone(Some(topic))), Ok(ChatSetting::TimeZone(Some(chat))))
+            if topic != chat =>
+        {
+            t(redis, chat_id, SETTINGS_TOPIC_OWN, &[("value", topic)]).await
+        }
+        (Ok(ChatSetting::TimeZone(Some(topic))), _) => {
+            t(
+                redis,
+                chat_id,
+                SETTINGS_TOPIC_INHERITED,
+                &[("value", topic)],
+            )
+            .await
+        }
+        _ => {
+            t(
+                redis,
+                chat_id,
+                SETTINGS_TOPIC_INHERITED,
+                &[("value", "UTC")],
+            )
+            .await
+        }
+    };
+
+    let currency = match retrieve_topic_default_currency(redis, chat_id, Some(topic_id)).await {
+        Some(currency) => {
+            t(
+                redis,
+                chat_id,
+                SETTINGS_TOPIC_OWN,
+                &[("value", &currency.0)],
+            )
+            .await
+        }
+        None => {
+            let currency = get_chat_default_currency(redis, chat_id).await;
+            if currency.0 == CURRENCY_DEFAULT.0 {
+                t(redis, chat_id, SETTINGS_TOPIC_NONE, &[]).await
+            } else {
+                t(
+                    redis,
+                    chat_id,
+                    SETTINGS_TOPIC_INHERITED,
+                    &[("value", &currency.0)],
+                )
+                .await
+            }
+        }
+    };
+
+    t(
+        redis,
+        chat_id,
+        SETTINGS_TOPIC_SUMMARY,
+        &[("time_zone", &time_zone), ("currency", &currency)],
+    )
+    .await
+}
+
+/* Applies a setting given as command arguments to the current topic of a forum chat.
+ * Settings not overridden by the topic are inherited from the chat.
+ */
+async fn apply_topic_settings_arguments(
+    redis: &Redis,
+    bot: &Bot,
+    msg: &Message,
+    args: &str,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    let topic_id = match get_topic_id(msg) {
+        Some(topic_id) => topic_id,
+        None => {
+            send_bot_message(bot, msg, t(redis, &chat_id, SETTINGS_TOPIC_ONLY, &[]).await).await?;
+            return Ok(());
+        }
+    };
+
+    let (process, reply) = match args.trim().to_lowercase().as_str() {
+        "" | "show" => {
+            send_bot_message(
+                bot,
+                msg,
+                display_topic_settings(redis, &chat_id, topic_id).await,
+            )
+            .await?;
+            return Ok(());
+        }
+        "reset" => (
+            reset_topic_chat_settings(redis, &chat_id, topic_id).await,
+            t(redis, &chat_id, SETTINGS_TOPIC_RESET, &[]).await,
+        ),
+        _ => match parse_settings_arguments(args) {
+            Ok(ChatSetting::TimeZone(Some(text))) => {
+                let time_zone = parse_time_zone(&text)?;
+                (
+                    set_topic_chat_setting(
+                        redis,
+                        &chat_id,
+                        topic_id,
+                        ChatSetting::TimeZone(Some(text)),
+                    )
+                    .await,
+                    t(
+                        redis,
+                        &chat_id,
+                        SETTINGS_TOPIC_TIME_ZONE_UPDATED,
+                        &[("time_zone", &time_zone.to_string())],
+                    )
+                    .await,
+                )
+            }
+            Ok(ChatSetting::DefaultCurrency(Some(currency))) if currency != CURRENCY_DEFAULT.0 => {
+                if let Err(err) = check_default_currency_allowed(redis, &chat_id, &currency).await {
+                    send_bot_message(
+                        bot,
+                        msg,
+                        error_to_user_message(redis, &err, &msg.chat.id.to_string()).await,
+                    )
+                    .await?;
+                    return Ok(());
+                }
+                (
+                    set_topic_chat_setting(
+                        redis,
+                        &chat_id,
+                        topic_id,
+                        ChatSetting::DefaultCurrency(Some(currency.clone())),
+                    )
+                    .await,
+                    t(
+                        redis,
+                        &chat_id,
+                        SETTINGS_TOPIC_CURRENCY_UPDATED,
+                        &[("currency", &currency)],
+                    )
+                    .await,
+                )
+            }
+            Ok(_) => {
+                send_bot_message(
+                    bot,
+                    msg,
+                    t(redis, &chat_id, SETTINGS_TOPIC_INVALID, &[]).await,
+                )
+                .await?;
+                return Ok(());
+            }
+            Err(err) => {
+                send_bot_message(
+                    bot,
+                    msg,
+                    error_to_user_message(redis, &err, &msg.chat.id.to_string()).await,
+                )
+                .await?;
+                return Ok(());
+            }
+        },
+    };
+
+    match process {
+        Ok(_) => {
+            send_bot_message(bot, msg, reply).await?;
+
+            // Logging
+            log::info!(
+                "Settings Arguments - Topic setting updated for chat {} topic {} with arguments: {}",
+                chat_id,
+                topic_id,
+                args
+            );
+        }
+        Err(err) => {
+            let err = BotError::from(err);
+            send_bot_message(bot, msg, error_to_user_message(redis, &err, &chat_id).await).await?;
+
+            // Logging
+            log::error!(
+                "Settings Arguments - Error updating topic setting for chat {} topic {} with arguments {}: {}",
+                chat_id,
+                topic_id,
+                args,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/* Applies a setting given directly as command arguments, without the menu.
+ * Uses the same processor calls as the settings menu.
+ */
+async fn apply_settings_arguments(
+    redis: &Redis,
+    bot: &Bot,
+    msg: &Message,
+    args: &str,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    let (setting, value) = match args.trim().split_once(char::is_whitespace) {
+        Some((setting, value)) => (setting.to_lowercase(), value),
+        None => (args.trim().to_lowercase(), ""),
+    };
+    if setting == "topic" {
+        return apply_topic_settings_arguments(redis, bot, msg, value).await;
+    }
+
+    let setting = match parse_settings_arguments(args) {
+        Ok(setting) => setting,
+        Err(err) => {
+            send_bot_message(
+                bot,
+                msg,
+                error_to_user_message(redis, &err, &msg.chat.id.to_string()).await,
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let (process, reply) = match setting {
+        ChatSetting::TimeZone(Some(text)) => {
+            let time_zone = parse_time_zone(&text)?;
+            (
+                set_chat_setting(redis, &chat_id, ChatSetting::TimeZone(Some(text))).await,
+                t(
+                    redis,
+                    &chat_id,
+                    SETTINGS_TIME_ZONE_UPDATED,
+                    &[("time_zone", &time_zone.to_string())],
+                )
+                .await,
+            )
+        }
+        ChatSetting::DefaultCurrency(Some(currency)) => {
+            if let Err(err) = check_default_currency_allowed(redis, &chat_id, &currency).await {
+                send_bot_message(
+                    bot,
+                    msg,
+                    error_to_user_message(redis, &err, &msg.chat.id.to_string()).await,
+                )
+                .await?;
+                return Ok(());
+            }
+            let reply = if currency == CURRENCY_DEFAULT.0 {
+                t(redis, &chat_id, SETTINGS_CURRENCY_DISABLED, &[]).await
+            } else {
+                t(
+                    redis,
+                    &chat_id,
+                    SETTINGS_CURRENCY_UPDATED,
+                    &[("currency", &currency)],
+                )
+                .await
+            };
+            (
+                update_chat_default_currency(redis, &chat_id, &currency).await,
+                reply,
+            )
+        }
+        ChatSetting::CurrencyConversion(Some(convert)) => {
+            if convert {
+                if let Ok(ChatSetting::DefaultCurrency(Some(currency))) =
+                    get_chat_setting(redis, &chat_id, ChatSetting::DefaultCurrency(None)).await
+                {
+                    if currency == CURRENCY_DEFAULT.0 {
+                        send_bot_message(
+                            bot,
+                            msg,
+                            t(redis, &chat_id, SETTINGS_CONVERSION_NO_CURRENCY, &[]).await,
+                        )
+                        .await?;
+                        return Ok(());
+                    }
+                }
+            }
+            (
+                set_chat_setting(
+                    redis,
+                    &chat_id,
+                    ChatSetting::CurrencyConversion(Some(convert)),
+                )
+                .await,
+                t(
+                    redis,
+                    &chat_id,
+                    if convert {
+                        SETTINGS_CONVERSION_ON
+                    } else {
+                        SETTINGS_CONVERSION_OFF
+                    },
+                    &[],
+                )
+                .await,
+            )
+        }
+        ChatSetting::EraseMessages(Some(erase)) => (
+            set_chat_setting(redis, &chat_id, ChatSetting::EraseMessages(Some(erase))).await,
+            t(
+                redis,
+                &chat_id,
+                if erase {
+                    SETTINGS_ERASE_ON
+                } else {
+                    SETTINGS_ERASE_OFF
+                },
+                &[],
+            )
+            .await,
+        ),
+        ChatSetting::EraseUserMessages(Some(erase_user_messages)) => (
+            set_chat_setting(
+                redis,
+                &chat_id,
+                ChatSetting::EraseUserMessages(Some(erase_user_messages)),
+            )
+            .await,
+            display_erase_user_messages_updated(redis, &chat_id, erase_user_messages).await,
+        ),
+        ChatSetting::MonthlyArchive(Some(archive)) => (
+            set_chat_setting(redis, &chat_id, ChatSetting::MonthlyArchive(Some(archive))).await,
+            t(
+                redis,
+                &chat_id,
+                if archive {
+                    SETTINGS_ARCHIVE_ON
+                } else {
+                    SETTINGS_ARCHIVE_OFF
+                },
+                &[],
+            )
+            .await,
+        ),
+        ChatSetting::WeeklyDigest(Some(time)) => {
+            let reply = match &time {
+                Some(time) => t(redis, &chat_id, SETTINGS_DIGEST_ON, &[("time", time)]).await,
+                None => t(redis, &chat_id, SETTINGS_DIGEST_OFF, &[]).await,
+            };
+            (
+                set_chat_setting(redis, &chat_id, ChatSetting::WeeklyDigest(Some(time))).await,
+                reply,
+            )
+        }
+        ChatSetting::DebtReminder(Some(days)) => {
+            let reply = match &days {
+                Some(days) => {
+                    t(
+                        redis,
+                        &chat_id,
+                        SETTINGS_REMINDER_ON,
+                        &[("days", &days.to_string())],
+                    )
+                    .await
+                }
+                None => t(redis, &chat_id, SETTINGS_REMINDER_OFF, &[]).await,
+            };
+            (
+                set_chat_setting(redis, &chat_id, ChatSetting::DebtReminder(Some(days))).await,
+                reply,
+            )
+        }
+        ChatSetting::DataRetention(Some(months)) => {
+            let reply = match &months {
+                Some(months) => {
+                    t(
+                        redis,
+                        &chat_id,
+                        SETTINGS_RETENTION_ON,
+                        &[("months", &months.to_string())],
+                    )
+                    .await
+                }
+                None => t(redis, &chat_id, SETTINGS_RETENTION_OFF, &[]).await,
+            };
+            (
+                set_chat_setting(redis, &chat_id, ChatSetting::DataRetention(Some(months))).await,
+                reply,
+            )
+        }
+        ChatSetting::PaymentApproval(Some(threshold)) => {
+            let reply = match &threshold {
+                Some(threshold) => {
+                    t(
+                        redis,
+                        &chat_id,
+                        SETTINGS_APPROVAL_ON,
+                        &[("threshold", &threshold.to_string())],
+                    )
+                    .await
+                }
+                None => t(redis, &chat_id, SETTINGS_APPROVAL_OFF, &[]).await,
+            };
+            (
+                set_chat_setting(
+                    redis,
+                    &chat_id,
+                    ChatSetting::PaymentApproval(Some(threshold)),
+                )
+                .await,
+                reply,
+            )
+        }
+        ChatSetting::Treasurer(Some(treasurer)) => {
+            let reply = match &treasurer {
+                Some(treasurer) => {
+                    t(
+                        redis,
+                        &chat_id,
+                        SETTINGS_TREASURER_ON,
+                        &[("treasurer", treasurer)],
+                    )
+                    .await
+                }
+                None => t(redis, &chat_id, SETTINGS_TREASURER_OFF, &[]).await,
+            };
+            (
+                set_chat_setting(redis, &chat_id, ChatSetting::Treasurer(Some(treasurer))).await,
+                reply,
+            )
+        }
+        ChatSetting::AdminOnly(Some(admin_only)) => {
+            if !is_chat_admin(redis, bot, msg).await? {
+                send_bot_message(
+                    bot,
+                    msg,
+                    t(redis, &chat_id, SETTINGS_ADMIN_ONLY_RESTRICTED, &[]).await,
+                )
+                .await?;
+                return Ok(());
+            }
+            (
+                set_chat_setting(redis, &chat_id, ChatSetting::AdminOnly(Some(admin_only))).await,
+                t(
+                    redis,
+                    &chat_id,
+                    if admin_only {
+                        SETTINGS_ADMIN_ONLY_ON
+                    } else {
+                        SETTINGS_ADMIN_ONLY_OFF
+                    },
+                    &[],
+                )
+                .await,
+            )
+        }
+        ChatSetting::Language(Some(language)) => {
+            let name = match get_language(&language) {
+                Some(language) => language.1,
+                None => &language,
+            };
+            let reply = translate(&language, LANGUAGE_UPDATED, &[("language", name)]);
+            (
+                set_chat_setting(
+                    redis,
+                    &chat_id,
+                    ChatSetting::Language(Some(language.clone())),
+                )
+                .await,
+                reply,
+            )
+        }
+        ChatSetting::PairwiseDebts(Some(pairwise_debts)) => (
+            set_chat_setting(
+                redis,
+                &chat_id,
+                ChatSetting::PairwiseDebts(Some(pairwise_debts)),
+            )
+            .await,
+            t(
+                redis,
+                &chat_id,
+                if pairwise_debts {
+                    SETTINGS_PAIRWISE_ON
+                } else {
+                    SETTINGS_PAIRWISE_OFF
+                },
+                &[],
+            )
+            .await,
+        ),
+        ChatSetting::PassiveMode(Some(passive_mode)) => (
+            set_chat_setting(
+                redis,
+                &chat_id,
+                ChatSetting::PassiveMode(Some(passive_mode)),
+            )
+            .await,
+            t(
+                redis,
+                &chat_id,
+                if passive_mode {
+                    SETTINGS_PASSIVE_ON
+                } else {
+                    SETTINGS_PASSIVE_OFF
+                },
+                &[],
+            )
+            .await,
+        ),
+        ChatSetting::DualEntry(Some(dual_entry)) => (
+            set_chat_setting(redis, &chat_id, ChatSetting::DualEntry(Some(dual_entry))).await,
+            t(
+                redis,
+                &chat_id,
+                if dual_entry {
+                    SETTINGS_DUAL_ENTRY_ON
+                } else {
+                    SETTINGS_DUAL_ENTRY_OFF
+                },
+                &[],
+            )
+            .await,
+        ),
+        ChatSetting::StrictMembers(Some(strict_members)) => (
+            set_chat_setting(
+                redis,
+                &chat_id,
+                ChatSetting::StrictMembers(Some(strict_members)),
+            )
+            .await,
+            t(
+                redis,
+                &chat_id,
+                if strict_members {
+                    SETTINGS_STRICT_ON
+                } else {
+                    SETTINGS_STRICT_OFF
+                },
+                &[],
+            )
+            .await,
+        ),
+        ChatSetting::NumberFormat(Some(number_format)) => (
+            set_chat_setting(
+                redis,
+                &chat_id,
+                ChatSetting::NumberFormat(Some(number_format.clone())),
+            )
+            .await,
+            t(
+                redis,
+                &chat_id,
+                SETTINGS_NUMBER_FORMAT_UPDATED,
+                &[("format", &number_format)],
+            )
+            .await,
+        ),
+        ChatSetting::CurrencyFormat(Some(currency_format)) => (
+            set_chat_setting(
+                redis,
+                &chat_id,
+                ChatSetting::CurrencyFormat(Some(currency_format.clone())),
+            )
+            .await,
+            t(
+                redis,
+                &chat_id,
+                SETTINGS_CURRENCY_FORMAT_UPDATED,
+                &[("format", &currency_format)],
+            )
+            .await,
+        ),
+        ChatSetting::Verbosity(Some(verbosity)) => (
+            set_chat_setting(
+                redis,
+                &chat_id,
+                ChatSetting::Verbosity(Some(verbosity.clone())),
+            )
+            .await,
+            display_verbosity_updated(redis, &chat_id, &verbosity).await,
+        ),
+        ChatSetting::Rounding(Some(rounding)) => (
+            set_chat_setting(
+                redis,
+                &chat_id,
+                ChatSetting::Rounding(Some(rounding.clone())),
+            )
+            .await,
+            display_rounding_updated(redis, &chat_id, &rounding).await,
+        ),
+        ChatSetting::WeekStart(Some(week_start)) => (
+            set_chat_setting(
+                redis,
+                &chat_id,
+                ChatSetting::WeekStart(Some(week_start.clone())),
+            )
+            .await,
+            display_week_start_updated(redis, &chat_id, &week_start).await,
+        ),
+        ChatSetting::AllowedCurrencies(Some(currencies)) => {
+            if !is_chat_admin(redis, bot, msg).await? {
+                send_bot_message(
+                    bot,
+                    msg,
+                    t(redis, &chat_id, SETTINGS_CURRENCIES_RESTRICTED, &[]).await,
+                )
+                .await?;
+                return Ok(());
+            }
+            if let Ok(ChatSetting::DefaultCurrency(Some(default_currency))) =
+                get_chat_setting(redis, &chat_id, ChatSetting::DefaultCurrency(None)).await
+            {
+                if default_currency != CURRENCY_DEFAULT.0
+                    && !currencies.is_empty()
+                    && !currencies.contains(&default_currency)
+                {
+                    send_bot_message(
+                        bot,
+                        msg,
+                        t(
+                            redis,
+                            &chat_id,
+                            SETTINGS_CURRENCIES_DEFAULT_MISSING,
+                            &[("currency", &default_currency)],
+                        )
+                        .await,
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            }
+            let reply = if currencies.is_empty() {
+                t(redis, &chat_id, SETTINGS_CURRENCIES_ALL, &[]).await
+            } else {
+                t(
+                    redis,
+                    &chat_id,
+                    SETTINGS_CURRENCIES_UPDATED,
+                    &[("currencies", &currencies.join(", "))],
+                )
+                .await
+            };
+            (
+                set_chat_setting(
+                    redis,
+                    &chat_id,
+                    ChatSetting::AllowedCurrencies(Some(currencies)),
+                )
+                .await,
+                reply,
+            )
+        }
+        // The parser always returns a value, so a setting without one is only asked for again
+        ChatSetting::DefaultCurrency(None)
+        | ChatSetting::CurrencyConversion(None)
+        | ChatSetting::EraseMessages(None)
+        | ChatSetting::EraseUserMessages(None)
+        | ChatSetting::TimeZone(None)
+        | ChatSetting::MonthlyArchive(None)
+        | ChatSetting::WeeklyDigest(None)
+        | ChatSetting::PaymentApproval(None)
+        | ChatSetting::Language(None)
+        | ChatSetting::NumberFormat(None)
+        | ChatSetting::CurrencyFormat(None)
+        | ChatSetting::DebtReminder(None)
+        | ChatSetting::DataRetention(None)
+        | ChatSetting::AdminOnly(None)
+        | ChatSetting::Treasurer(None)
+        | ChatSetting::PairwiseDebts(None)
+        | ChatSetting::PassiveMode(None)
+        | ChatSetting::DualEntry(None)
+        | ChatSetting::StrictMembers(None)
+        | ChatSetting::Verbosity(None)
+        | ChatSetting::Rounding(None)
+        | ChatSetting::AllowedCurrencies(None)
+        | ChatSetting::WeekStart(None) => {
+            let err = BotError::UserError(
+                Text::new(SETTINGS_VALUE_MISSING).then(Text::new(SETTINGS_ARGUMENTS)),
+            );
+            send_bot_message(bot, msg, error_to_user_message(redis, &err, &chat_id).await).await?;
+            return Ok(());
+        }
+    };
+
+    match process {
+        Ok(_) => {
+            send_bot_message(bot, msg, reply).await?;
+
+            // Logging
+            log::info!(
+                "Settings Arguments - Setting updated for chat {} with arguments: {}",
+                chat_id,
+                args
+            );
+        }
+        Err(err) => {
+            let err = BotError::from(err);
+            send_bot_message(bot, msg, error_to_user_message(redis, &err, &chat_id).await).await?;
+
+            // Logging
+            log::error!(
+                "Settings Arguments - Error updating setting for chat {} with arguments {}: {}",
+                chat_id,
+                args,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
 
 // Controls the state for misc handler actions that return to same state.
 async fn repeat_state(
@@ -42,45 +1177,141 @@ async fn repeat_state(
 ) -> HandlerResult {
     match state {
         State::SettingsMenu { mut messages } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue.update(State::SettingsMenu { messages }).await?;
         }
         State::SettingsTimeZoneMenu { mut messages } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::SettingsTimeZoneMenu { messages })
                 .await?;
         }
-        State::SettingsTimeZone { mut messages } => {
-            messages.push(new_message);
+        State::SettingsTimeZoneRegion { mut messages } => {
+            messages.track(new_message);
             dialogue
-                .update(State::SettingsTimeZone { messages })
+                .update(State::SettingsTimeZoneRegion { messages })
                 .await?;
         }
         State::SettingsDefaultCurrencyMenu { mut messages } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::SettingsDefaultCurrencyMenu { messages })
                 .await?;
         }
         State::SettingsDefaultCurrency { mut messages } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::SettingsDefaultCurrency { messages })
                 .await?;
         }
         State::SettingsCurrencyConversion { mut messages } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::SettingsCurrencyConversion { messages })
                 .await?;
         }
         State::SettingsEraseMessages { mut messages } => {
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue
                 .update(State::SettingsEraseMessages { messages })
                 .await?;
         }
+        State::SettingsMonthlyArchive { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsMonthlyArchive { messages })
+                .await?;
+        }
+        State::SettingsWeeklyDigestMenu { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsWeeklyDigestMenu { messages })
+                .await?;
+        }
+        State::SettingsWeeklyDigest { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsWeeklyDigest { messages })
+                .await?;
+        }
+        State::SettingsDebtReminderMenu { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsDebtReminderMenu { messages })
+                .await?;
+        }
+        State::SettingsDebtReminder { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsDebtReminder { messages })
+                .await?;
+        }
+        State::SettingsPaymentApprovalMenu { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsPaymentApprovalMenu { messages })
+                .await?;
+        }
+        State::SettingsPaymentApproval { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsPaymentApproval { messages })
+                .await?;
+        }
+        State::SettingsLanguageMenu { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsLanguageMenu { messages })
+                .await?;
+        }
+        State::SettingsNumberFormatMenu { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsNumberFormatMenu { messages })
+                .await?;
+        }
+        State::SettingsCurrencyFormatMenu { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsCurrencyFormatMenu { messages })
+                .await?;
+        }
+        State::SettingsVerbosityMenu { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsVerbosityMenu { messages })
+                .await?;
+        }
+        State::SettingsRoundingMenu { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsRoundingMenu { messages })
+                .await?;
+        }
+        State::SettingsWeekStartMenu { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsWeekStartMenu { messages })
+                .await?;
+        }
+        State::SettingsAdminOnly { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsAdminOnly { messages })
+                .await?;
+        }
+        State::SettingsTreasurerMenu { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsTreasurerMenu { messages })
+                .await?;
+        }
+        State::SettingsTreasurer { mut messages } => {
+            messages.track(new_message);
+            dialogue
+                .update(State::SettingsTreasurer { messages })
+                .await?;
+        }
         _ => (),
     }
     Ok(())
@@ -88,48 +1319,52 @@ async fn repeat_state(
 
 // Controls the dialogue for ending a settings operation.
 async fn complete_settings(
+    redis: &Redis,
     bot: &Bot,
     dialogue: UserDialogue,
     chat_id: &str,
-    messages: Vec<MessageId>,
+    messages: MessageTracker,
 ) -> HandlerResult {
-    if is_erase_messages(chat_id) {
-        delete_bot_messages(&bot, chat_id, messages).await?;
+    if is_onboarding(redis, chat_id).await {
+        return continue_onboarding(redis, bot, dialogue, chat_id, messages).await;
     }
+
+    cleanup_messages(redis, bot, chat_id, messages).await?;
     dialogue.exit().await?;
     Ok(())
 }
 
 // Displays the first settings menu.
 async fn display_settings_menu(
+    redis: &Redis,
     bot: &Bot,
     dialogue: &UserDialogue,
     msg: &Message,
     msg_id: Option<MessageId>,
-    mut messages: Vec<MessageId>,
+    mut messages: MessageTracker,
 ) -> HandlerResult {
-    let buttons = vec!["💵", "↔️", "🚮", "🕔", "Cancel"];
+    let buttons = vec![
+        "💵", "↔️", "🚮", "🕔", "📦", "📰", "⏰", "🔏", "🏦", "🛡️", "🌐", "🔢", "💱", "💬", "🪙",
+        "📅", "Cancel",
+    ];
 
-    let keyboard = make_keyboard(buttons, Some(2));
-    let message = format!(
-        "With pleasure\\! 😉 Let's see, here are the ⚙️ settings you can customize\\. What would you like to view or edit?\n\n{DEFAULT_CURRENCY_DESCRIPTION}\n\n{CURRENCY_CONVERSION_DESCRIPTION}\n\n{ERASE_MESSAGES_DESCRIPTION}\n\n{TIME_ZONE_DESCRIPTION}",
-        );
+    let language = retrieve_language(redis, &msg.chat.id.to_string()).await;
+    let keyboard = make_keyboard(buttons, Some(2), &language);
+    let message = translate(&language, SETTINGS_MENU, &[]);
 
     match msg_id {
         Some(id) => {
-            bot.edit_message_text(msg.chat.id, id, message)
-                .parse_mode(ParseMode::MarkdownV2)
+            edit_markdown_message(bot, msg.chat.id, id, message)
                 .reply_markup(keyboard)
                 .await?;
             dialogue.update(State::SettingsMenu { messages }).await?;
         }
         None => {
-            let new_message = send_bot_message(&bot, &msg, message)
-                .parse_mode(ParseMode::MarkdownV2)
+            let new_message = send_markdown_message(bot, msg, message)
                 .reply_markup(keyboard)
                 .await?
                 .id;
-            messages.push(new_message);
+            messages.track(new_message);
             dialogue.update(State::SettingsMenu { messages }).await?;
         }
     }
@@ -140,20 +1375,29 @@ async fn display_settings_menu(
  * Does nothing, simply notifies the user.
  */
 pub async fn handle_repeated_settings(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
     let new_message = send_bot_message(
         &bot,
         &msg,
-        format!("🚫 Oops! It seems like you're already in the middle of customizing my settings! Please finish or {COMMAND_CANCEL} this before starting another one with me."),
-        ).await?.id;
+        t(
+            &redis,
+            &msg.chat.id.to_string(),
+            SETTINGS_REPEATED,
+            &[("command", COMMAND_CANCEL)],
+        )
+        .await,
+    )
+    .await?
+    .id;
 
     repeat_state(dialogue, state, new_message).await?;
 
@@ -164,27 +1408,49 @@ pub async fn handle_repeated_settings(
  * Can be called at any step of the process.
  */
 pub async fn cancel_settings(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
-    send_bot_message(&bot, &msg, CANCEL_MESSAGE.to_string())
-        .await?
-        .id;
+    send_bot_message(
+        &bot,
+        &msg,
+        t(&redis, &msg.chat.id.to_string(), SETTINGS_CANCELLED, &[]).await,
+    )
+    .await?;
+    end_onboarding(&redis, &msg.chat.id.to_string()).await;
 
     match state {
         State::SettingsMenu { messages }
         | State::SettingsTimeZoneMenu { messages }
-        | State::SettingsTimeZone { messages }
+        | State::SettingsTimeZoneRegion { messages }
         | State::SettingsDefaultCurrencyMenu { messages }
         | State::SettingsDefaultCurrency { messages }
-        | State::SettingsCurrencyConversion { messages } => {
-            complete_settings(&bot, dialogue, &msg.chat.id.to_string(), messages).await?;
+        | State::SettingsCurrencyConversion { messages }
+        | State::SettingsMonthlyArchive { messages }
+        | State::SettingsWeeklyDigestMenu { messages }
+        | State::SettingsWeeklyDigest { messages }
+        | State::SettingsDebtReminderMenu { messages }
+        | State::SettingsDebtReminder { messages }
+        | State::SettingsPaymentApprovalMenu { messages }
+        | State::SettingsPaymentApproval { messages }
+        | State::SettingsEraseMessages { messages }
+        | State::SettingsLanguageMenu { messages }
+        | State::SettingsNumberFormatMenu { messages }
+        | State::SettingsCurrencyFormatMenu { messages }
+        | State::SettingsVerbosityMenu { messages }
+        | State::SettingsRoundingMenu { messages }
+        | State::SettingsWeekStartMenu { messages }
+        | State::SettingsAdminOnly { messages }
+        | State::SettingsTreasurerMenu { messages }
+        | State::SettingsTreasurer { messages } => {
+            complete_settings(&redis, &bot, dialogue, &msg.chat.id.to_string(), messages).await?;
         }
         _ => (),
     }
@@ -196,20 +1462,29 @@ pub async fn cancel_settings(
  * Called when user attempts to start another operation in the middle of editing/deleting a payment.
  */
 pub async fn block_settings(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
     let new_message = send_bot_message(
         &bot,
         &msg,
-        format!("🚫 Oops! It seems like you're in the middle of customizing my settings! Please finish or {COMMAND_CANCEL} this before starting something new with me."),
-        ).await?.id;
+        t(
+            &redis,
+            &msg.chat.id.to_string(),
+            SETTINGS_BLOCKED,
+            &[("command", COMMAND_CANCEL)],
+        )
+        .await,
+    )
+    .await?
+    .id;
 
     repeat_state(dialogue, state, new_message).await?;
 
@@ -218,13 +1493,38 @@ pub async fn block_settings(
 
 /* Allows user to view and edit chat settings.
  * Bot presents a button menu of setting options.
+ * If arguments are given, the setting is updated directly instead.
  */
-pub async fn action_settings(bot: Bot, dialogue: UserDialogue, msg: Message) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+pub async fn action_settings(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: Message,
+    args: String,
+) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    if !assert_admin_only(&redis, &bot, &msg).await? {
+        return Ok(());
+    }
+
+    if !args.trim().is_empty() {
+        apply_settings_arguments(&redis, &bot, &msg, &args).await?;
         return Ok(());
     }
 
-    display_settings_menu(&bot, &dialogue, &msg, None, Vec::new()).await?;
+    end_onboarding(&redis, &msg.chat.id.to_string()).await;
+    display_settings_menu(
+        &redis,
+        &bot,
+        &dialogue,
+        &msg,
+        None,
+        MessageTracker::default().with_command(msg.id),
+    )
+    .await?;
     Ok(())
 }
 
@@ -232,27 +1532,31 @@ pub async fn action_settings(bot: Bot, dialogue: UserDialogue, msg: Message) ->
  * Bot receives a callback query from the user.
  */
 pub async fn action_settings_menu(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     query: CallbackQuery,
-    messages: Vec<MessageId>,
+    mut messages: MessageTracker,
 ) -> HandlerResult {
     if let Some(button) = &query.data {
         bot.answer_callback_query(query.id.to_string()).await?;
         if let Some(msg) = query.message {
             let chat_id = msg.chat.id.to_string();
+            let language = retrieve_language(&redis, &chat_id).await;
             match button.as_str() {
                 "🕔" => {
-                    let time_zone = retrieve_time_zone(&chat_id);
+                    let time_zone = retrieve_time_zone(&redis, &chat_id).await;
                     let buttons = vec!["Back", "Edit"];
-                    let keyboard = make_keyboard(buttons, Some(2));
-                    bot.edit_message_text(
-                        chat_id,
-                        msg.id,
-                        format!(
-                            "🕔 Time Zone: {}\n\nWould you like to edit the time zone for this chat?",
-                            time_zone
+                    let keyboard = make_keyboard(buttons, Some(2), &language);
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        translate(
+                            &language,
+                            SETTINGS_TIME_ZONE_MENU,
+                            &[("time_zone", &time_zone.to_string())],
                         ),
                     )
                     .reply_markup(keyboard)
@@ -262,27 +1566,36 @@ pub async fn action_settings_menu(
                         .await?;
                 }
                 "💵" => {
-                    let setting = get_chat_setting(&chat_id, ChatSetting::DefaultCurrency(None))?;
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::DefaultCurrency(None))
+                            .await?;
                     if let ChatSetting::DefaultCurrency(Some(currency)) = setting {
                         let currency_info: String;
                         let buttons: Vec<&str>;
                         if currency == CURRENCY_DEFAULT.0 {
-                            currency_info = format!("💵 Default Currency is NOT set.");
+                            currency_info = translate(&language, SETTINGS_CURRENCY_NOT_SET, &[]);
                             buttons = vec!["Back", "Edit"];
                         } else {
-                            currency_info = format!("💵 Default Currency: {}", currency);
+                            currency_info = translate(
+                                &language,
+                                SETTINGS_CURRENCY_CURRENT,
+                                &[("currency", &currency)],
+                            );
                             buttons = vec!["Disable", "Edit", "Back"];
                         }
-                        let keyboard = make_keyboard(buttons, Some(2));
+                        let keyboard = make_keyboard(buttons, Some(2), &language);
 
-                        bot.edit_message_text(
-                            chat_id,
-                            msg.id,
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
                             format!(
-                                "{currency_info}\n\nWould you like to edit the default currency for this chat?",
-                                ))
-                            .reply_markup(keyboard)
-                            .await?;
+                                "{currency_info}\n\n{}",
+                                translate(&language, SETTINGS_CURRENCY_MENU, &[])
+                            ),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
                         dialogue
                             .update(State::SettingsDefaultCurrencyMenu { messages })
                             .await?;
@@ -290,42 +1603,52 @@ pub async fn action_settings_menu(
                 }
                 "↔️" => {
                     let setting =
-                        get_chat_setting(&chat_id, ChatSetting::CurrencyConversion(None))?;
+                        get_chat_setting(&redis, &chat_id, ChatSetting::CurrencyConversion(None))
+                            .await?;
                     if let ChatSetting::CurrencyConversion(Some(convert)) = setting {
-                        let status: &str;
+                        let status: String;
                         let prompt: &str;
                         let buttons: Vec<&str>;
                         if convert {
-                            status = "ENABLED ✅";
+                            status = translate(&language, SETTINGS_ENABLED, &[]);
                             buttons = vec!["Back", "Turn Off"];
-                            prompt =
-                                "Would you like to turn off currency conversion for this chat?";
+                            prompt = SETTINGS_CONVERSION_OFF_PROMPT;
                         } else {
-                            let currency =
-                                get_chat_setting(&chat_id, ChatSetting::DefaultCurrency(None))?;
+                            let currency = get_chat_setting(
+                                &redis,
+                                &chat_id,
+                                ChatSetting::DefaultCurrency(None),
+                            )
+                            .await?;
                             if let ChatSetting::DefaultCurrency(Some(currency)) = currency {
                                 if currency == CURRENCY_DEFAULT.0 {
                                     buttons = vec!["Back"];
-                                    prompt = "⭐️ If you wish to turn on currency conversion, please set a default currency first!";
+                                    prompt = SETTINGS_CONVERSION_NO_CURRENCY;
                                 } else {
                                     buttons = vec!["Back", "Turn On"];
-                                    prompt =
-                                        "Would you like to turn on currency conversion for this chat?";
+                                    prompt = SETTINGS_CONVERSION_ON_PROMPT;
                                 }
                             } else {
                                 // Should not occur, these are placeholder values
                                 buttons = vec!["Back"];
-                                prompt = "⭐️ If you wish to turn on currency conversion, please set a default currency first!";
+                                prompt = SETTINGS_CONVERSION_NO_CURRENCY;
                             }
-                            status = "DISABLED ❌";
+                            status = translate(&language, SETTINGS_DISABLED, &[]);
                         }
 
-                        let keyboard = make_keyboard(buttons.clone(), Some(buttons.len()));
+                        let keyboard =
+                            make_keyboard(buttons.clone(), Some(buttons.len()), &language);
 
-                        bot.edit_message_text(
-                            chat_id,
-                            msg.id,
-                            format!("↔️ Currency Conversion is currently {status}.\n\n{prompt}",),
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            display_setting_status(
+                                &language,
+                                SETTINGS_CONVERSION_STATUS,
+                                &status,
+                                prompt,
+                            ),
                         )
                         .reply_markup(keyboard)
                         .await?;
@@ -335,27 +1658,50 @@ pub async fn action_settings_menu(
                     }
                 }
                 "🚮" => {
-                    let setting = get_chat_setting(&chat_id, ChatSetting::EraseMessages(None))?;
-                    if let ChatSetting::EraseMessages(Some(erase)) = setting {
-                        let status: &str;
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::EraseMessages(None))
+                            .await?;
+                    if let ChatSetting::EraseMessages(Some(erase)) = setting {
+                        let status: String;
                         let prompt: &str;
                         let buttons: Vec<&str>;
                         if erase {
-                            status = "ENABLED ✅";
-                            buttons = vec!["Back", "Turn Off"];
-                            prompt = "Would you like to turn off automatic message erasing for this chat?";
+                            let erase_user_messages =
+                                is_erase_user_messages(&redis, &chat_id).await;
+                            let key = if erase_user_messages {
+                                SETTINGS_ERASE_ENABLED_ALL
+                            } else {
+                                SETTINGS_ERASE_ENABLED_OWN
+                            };
+                            status = translate(&language, key, &[]);
+                            buttons = vec![
+                                "Back",
+                                "Turn Off",
+                                if erase_user_messages {
+                                    KEEP_USER_MESSAGES_BUTTON
+                                } else {
+                                    ERASE_USER_MESSAGES_BUTTON
+                                },
+                            ];
+                            prompt = SETTINGS_ERASE_OFF_PROMPT;
                         } else {
-                            status = "DISABLED ❌";
+                            status = translate(&language, SETTINGS_DISABLED, &[]);
                             buttons = vec!["Back", "Turn On"];
-                            prompt = "Would you like to turn on automatic message erasing for this chat?";
+                            prompt = SETTINGS_ERASE_ON_PROMPT;
                         }
 
-                        let keyboard = make_keyboard(buttons.clone(), Some(buttons.len()));
+                        let keyboard = make_keyboard(buttons.clone(), Some(2), &language);
 
-                        bot.edit_message_text(
-                            chat_id,
-                            msg.id,
-                            format!("🚮 Erase Messages is currently {status}.\n\n{prompt}",),
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            display_setting_status(
+                                &language,
+                                SETTINGS_ERASE_STATUS,
+                                &status,
+                                prompt,
+                            ),
                         )
                         .reply_markup(keyboard)
                         .await?;
@@ -364,8 +1710,438 @@ pub async fn action_settings_menu(
                             .await?;
                     }
                 }
+                "📦" => {
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::MonthlyArchive(None))
+                            .await?;
+                    if let ChatSetting::MonthlyArchive(Some(archive)) = setting {
+                        let status: String;
+                        let prompt: &str;
+                        let buttons: Vec<&str>;
+                        if archive {
+                            status = translate(&language, SETTINGS_ENABLED, &[]);
+                            buttons = vec!["Back", "Turn Off"];
+                            prompt = SETTINGS_ARCHIVE_OFF_PROMPT;
+                        } else {
+                            status = translate(&language, SETTINGS_DISABLED, &[]);
+                            buttons = vec!["Back", "Turn On"];
+                            prompt = SETTINGS_ARCHIVE_ON_PROMPT;
+                        }
+
+                        let keyboard =
+                            make_keyboard(buttons.clone(), Some(buttons.len()), &language);
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            display_setting_status(
+                                &language,
+                                SETTINGS_ARCHIVE_STATUS,
+                                &status,
+                                prompt,
+                            ),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
+                        dialogue
+                            .update(State::SettingsMonthlyArchive { messages })
+                            .await?;
+                    }
+                }
+                "📰" => {
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::WeeklyDigest(None)).await?;
+                    if let ChatSetting::WeeklyDigest(Some(time)) = setting {
+                        let status: String;
+                        let prompt: &str;
+                        let buttons: Vec<&str>;
+                        match time {
+                            Some(time) => {
+                                status = translate(
+                                    &language,
+                                    SETTINGS_DIGEST_ENABLED,
+                                    &[("time", &time)],
+                                );
+                                buttons = vec!["Back", "Edit", "Turn Off"];
+                                prompt = SETTINGS_DIGEST_OFF_PROMPT;
+                            }
+                            None => {
+                                status = translate(&language, SETTINGS_DISABLED, &[]);
+                                buttons = vec!["Back", "Turn On"];
+                                prompt = SETTINGS_DIGEST_ON_PROMPT;
+                            }
+                        }
+
+                        let keyboard =
+                            make_keyboard(buttons.clone(), Some(buttons.len()), &language);
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            display_setting_status(
+                                &language,
+                                SETTINGS_DIGEST_STATUS,
+                                &status,
+                                prompt,
+                            ),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
+                        dialogue
+                            .update(State::SettingsWeeklyDigestMenu { messages })
+                            .await?;
+                    }
+                }
+                "⏰" => {
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::DebtReminder(None)).await?;
+                    if let ChatSetting::DebtReminder(Some(days)) = setting {
+                        let status: String;
+                        let prompt: &str;
+                        let buttons: Vec<&str>;
+                        match days {
+                            Some(days) => {
+                                status = translate(
+                                    &language,
+                                    SETTINGS_REMINDER_ENABLED,
+                                    &[("days", &days.to_string())],
+                                );
+                                buttons = vec!["Back", "Edit", "Turn Off"];
+                                prompt = SETTINGS_REMINDER_OFF_PROMPT;
+                            }
+                            None => {
+                                status = translate(&language, SETTINGS_DISABLED, &[]);
+                                buttons = vec!["Back", "Turn On"];
+                                prompt = SETTINGS_REMINDER_ON_PROMPT;
+                            }
+                        }
+
+                        let keyboard =
+                            make_keyboard(buttons.clone(), Some(buttons.len()), &language);
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            display_setting_status(
+                                &language,
+                                SETTINGS_REMINDER_STATUS,
+                                &status,
+                                prompt,
+                            ),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
+                        dialogue
+                            .update(State::SettingsDebtReminderMenu { messages })
+                            .await?;
+                    }
+                }
+                "🔏" => {
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::PaymentApproval(None))
+                            .await?;
+                    if let ChatSetting::PaymentApproval(Some(threshold)) = setting {
+                        let status: String;
+                        let prompt: &str;
+                        let buttons: Vec<&str>;
+                        match threshold {
+                            Some(threshold) => {
+                                status = translate(
+                                    &language,
+                                    SETTINGS_APPROVAL_ENABLED,
+                                    &[("threshold", &threshold.to_string())],
+                                );
+                                buttons = vec!["Back", "Edit", "Turn Off"];
+                                prompt = SETTINGS_APPROVAL_OFF_PROMPT;
+                            }
+                            None => {
+                                status = translate(&language, SETTINGS_DISABLED, &[]);
+                                buttons = vec!["Back", "Turn On"];
+                                prompt = SETTINGS_APPROVAL_ON_PROMPT;
+                            }
+                        }
+
+                        let keyboard =
+                            make_keyboard(buttons.clone(), Some(buttons.len()), &language);
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            display_setting_status(
+                                &language,
+                                SETTINGS_APPROVAL_STATUS,
+                                &status,
+                                prompt,
+                            ),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
+                        dialogue
+                            .update(State::SettingsPaymentApprovalMenu { messages })
+                            .await?;
+                    }
+                }
+                "🏦" => {
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::Treasurer(None)).await?;
+                    if let ChatSetting::Treasurer(Some(treasurer)) = setting {
+                        let status: String;
+                        let prompt: &str;
+                        let buttons: Vec<&str>;
+                        match treasurer {
+                            Some(treasurer) => {
+                                status = translate(
+                                    &language,
+                                    SETTINGS_TREASURER_ENABLED,
+                                    &[("treasurer", &treasurer)],
+                                );
+                                buttons = vec!["Back", "Edit", "Turn Off"];
+                                prompt = SETTINGS_TREASURER_OFF_PROMPT;
+                            }
+                            None => {
+                                status = translate(&language, SETTINGS_DISABLED, &[]);
+                                buttons = vec!["Back", "Turn On"];
+                                prompt = SETTINGS_TREASURER_ON_PROMPT;
+                            }
+                        }
+
+                        let keyboard =
+                            make_keyboard(buttons.clone(), Some(buttons.len()), &language);
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            display_setting_status(
+                                &language,
+                                SETTINGS_TREASURER_STATUS,
+                                &status,
+                                prompt,
+                            ),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
+                        dialogue
+                            .update(State::SettingsTreasurerMenu { messages })
+                            .await?;
+                    }
+                }
+                "🛡️" => {
+                    if !msg.chat.is_private()
+                        && !is_user_admin(&redis, &bot, msg.chat.id, query.from.id).await?
+                    {
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            translate(&language, SETTINGS_ADMIN_ONLY_RESTRICTED, &[]),
+                        )
+                        .reply_markup(make_keyboard(vec!["Back"], Some(1), &language))
+                        .await?;
+                        dialogue
+                            .update(State::SettingsAdminOnly { messages })
+                            .await?;
+                        return Ok(());
+                    }
+
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::AdminOnly(None)).await?;
+                    if let ChatSetting::AdminOnly(Some(admin_only)) = setting {
+                        let status: String;
+                        let prompt: &str;
+                        let buttons: Vec<&str>;
+                        if admin_only {
+                            status = translate(&language, SETTINGS_ENABLED, &[]);
+                            buttons = vec!["Back", "Turn Off"];
+                            prompt = SETTINGS_ADMIN_ONLY_OFF_PROMPT;
+                        } else {
+                            status = translate(&language, SETTINGS_DISABLED, &[]);
+                            buttons = vec!["Back", "Turn On"];
+                            prompt = SETTINGS_ADMIN_ONLY_ON_PROMPT;
+                        }
+
+                        let keyboard =
+                            make_keyboard(buttons.clone(), Some(buttons.len()), &language);
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            display_setting_status(
+                                &language,
+                                SETTINGS_ADMIN_ONLY_STATUS,
+                                &status,
+                                prompt,
+                            ),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
+                        dialogue
+                            .update(State::SettingsAdminOnly { messages })
+                            .await?;
+                    }
+                }
+                "🌐" => {
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::Language(None)).await?;
+                    if let ChatSetting::Language(Some(language)) = setting {
+                        let name = match get_language(&language) {
+                            Some(language) => language.1,
+                            None => &language,
+                        };
+                        let mut buttons: Vec<&str> =
+                            LANGUAGES.iter().map(|language| language.1).collect();
+                        buttons.push("Back");
+                        let keyboard = make_keyboard(buttons, Some(2), &language);
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            translate(&language, LANGUAGE_MENU, &[("language", name)]),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
+                        dialogue
+                            .update(State::SettingsLanguageMenu { messages })
+                            .await?;
+                    }
+                }
+                "🔢" => {
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::NumberFormat(None)).await?;
+                    if let ChatSetting::NumberFormat(Some(number_format)) = setting {
+                        let mut buttons: Vec<&str> = NUMBER_FORMATS
+                            .iter()
+                            .map(|number_format| number_format.0)
+                            .collect();
+                        buttons.push("Back");
+                        let keyboard = make_keyboard(buttons, Some(2), &language);
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            translate(
+                                &language,
+                                SETTINGS_NUMBER_FORMAT_MENU,
+                                &[("format", &number_format)],
+                            ),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
+                        dialogue
+                            .update(State::SettingsNumberFormatMenu { messages })
+                            .await?;
+                    }
+                }
+                "💱" => {
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::CurrencyFormat(None))
+                            .await?;
+                    if let ChatSetting::CurrencyFormat(Some(currency_format)) = setting {
+                        let mut buttons: Vec<&str> = CURRENCY_FORMATS
+                            .iter()
+                            .map(|currency_format| currency_format.0)
+                            .collect();
+                        buttons.push("Back");
+                        let keyboard = make_keyboard(buttons, Some(2), &language);
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            translate(
+                                &language,
+                                SETTINGS_CURRENCY_FORMAT_MENU,
+                                &[("format", &currency_format)],
+                            ),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
+                        dialogue
+                            .update(State::SettingsCurrencyFormatMenu { messages })
+                            .await?;
+                    }
+                }
+                "💬" => {
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::Verbosity(None)).await?;
+                    if let ChatSetting::Verbosity(Some(verbosity)) = setting {
+                        let buttons = vec!["Verbose", "Compact", "Silent", "Back"];
+                        let keyboard = make_keyboard(buttons, Some(2), &language);
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            translate(
+                                &language,
+                                SETTINGS_VERBOSITY_MENU,
+                                &[("verbosity", &verbosity)],
+                            ),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
+                        dialogue
+                            .update(State::SettingsVerbosityMenu { messages })
+                            .await?;
+                    }
+                }
+                "🪙" => {
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::Rounding(None)).await?;
+                    if let ChatSetting::Rounding(Some(rounding)) = setting {
+                        let buttons = vec!["Payer", "First", "Random", "Round Robin", "Back"];
+                        let keyboard = make_keyboard(buttons, Some(2), &language);
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            translate(
+                                &language,
+                                SETTINGS_ROUNDING_MENU,
+                                &[("rounding", &rounding)],
+                            ),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
+                        dialogue
+                            .update(State::SettingsRoundingMenu { messages })
+                            .await?;
+                    }
+                }
+                "📅" => {
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::WeekStart(None)).await?;
+                    if let ChatSetting::WeekStart(Some(week_start)) = setting {
+                        let buttons = vec!["Monday", "Sunday", "Back"];
+                        let keyboard = make_keyboard(buttons, Some(2), &language);
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            translate(
+                                &language,
+                                SETTINGS_WEEK_START_MENU,
+                                &[("week_start", &week_start)],
+                            ),
+                        )
+                        .reply_markup(keyboard)
+                        .await?;
+                        dialogue
+                            .update(State::SettingsWeekStartMenu { messages })
+                            .await?;
+                    }
+                }
                 "Cancel" => {
-                    cancel_settings(bot, dialogue, state, msg).await?;
+                    cancel_settings(redis.clone(), bot, dialogue, state, msg).await?;
                 }
                 _ => {
                     if let Some(user) = msg.from() {
@@ -387,192 +2163,1617 @@ pub async fn action_settings_menu(
  * Receives a callback query on whether the user wants to edit the time zone.
  */
 pub async fn action_time_zone_menu(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     query: CallbackQuery,
-    messages: Vec<MessageId>,
+    mut messages: MessageTracker,
 ) -> HandlerResult {
     if let Some(button) = &query.data {
         bot.answer_callback_query(query.id.to_string()).await?;
         if let Some(msg) = query.message {
             let chat_id = msg.chat.id;
             match button.as_str() {
+                ONBOARDING_SKIP => {
+                    complete_settings(&redis, &bot, dialogue, &msg.chat.id.to_string(), messages)
+                        .await?;
+                }
                 "Back" => {
-                    display_settings_menu(&bot, &dialogue, &msg, Some(msg.id), messages).await?;
+                    display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                        .await?;
                 }
                 "Edit" => {
-                    let time_zone = retrieve_time_zone(&chat_id.to_string());
-                    bot.edit_message_text(
-                        msg.chat.id,
-                        msg.id,
-                        format!(
-                            "🕔 Time Zone: {}\n\nWhat time zone would you like to set?\n\n{TIME_ZONE_INSTRUCTIONS_MESSAGE}",
-                            time_zone
-                            ),
-                            )
-                        .await?;
+                    let time_zone = retrieve_time_zone(&redis, &chat_id.to_string()).await;
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t_prompt(
+                            &redis,
+                            &chat_id.to_string(),
+                            SETTINGS_TIME_ZONE_PROMPT,
+                            &[("time_zone", &time_zone.to_string())],
+                            TIME_ZONE_INSTRUCTIONS,
+                        )
+                        .await,
+                    )
+                    .reply_markup(make_keyboard_time_zone_regions(
+                        &retrieve_language(&redis, &chat_id.to_string()).await,
+                    ))
+                    .await?;
                     dialogue
-                        .update(State::SettingsTimeZone { messages })
+                        .update(State::SettingsTimeZoneRegion { messages })
+                        .await?;
+                }
+                _ => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Time Zone Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            chat_id,
+                            button
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Presents the regions and cities of time zones for the chat.
+ * Receives a callback query on which region to look in, or which city to set.
+ * Typing the name of a city is handled by action_settings_time_zone instead.
+ */
+pub async fn action_time_zone_region_menu(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    messages: MessageTracker,
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            if button == "Back" {
+                display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                    .await?;
+                return Ok(());
+            }
+            let language = retrieve_language(&redis, &chat_id).await;
+            if button == TIME_ZONE_REGIONS_CALLBACK {
+                bot.edit_message_reply_markup(msg.chat.id, msg.id)
+                    .reply_markup(make_keyboard_time_zone_regions(&language))
+                    .await?;
+                return Ok(());
+            }
+
+            if let Some(region) = button.strip_prefix(TIME_ZONE_REGION_PREFIX) {
+                let (region, page) = region.rsplit_once(':').unwrap_or((region, "0"));
+                let page = page.parse::<usize>().unwrap_or(0);
+                if let Some(keyboard) = make_keyboard_time_zone_cities(region, page, &language) {
+                    bot.edit_message_reply_markup(msg.chat.id, msg.id)
+                        .reply_markup(keyboard)
+                        .await?;
+                    return Ok(());
+                }
+            }
+
+            if let Some(city) = button.strip_prefix(TIME_ZONE_CITY_PREFIX) {
+                if let Some(time_zone) = all_time_zones().get(city) {
+                    update_time_zone(&redis, &bot, &msg, city, *time_zone).await?;
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                    return Ok(());
+                }
+            }
+
+            if let Some(user) = msg.from() {
+                log::error!(
+                    "Settings Time Zone Region Menu - Invalid button for user {} in chat {}: {}",
+                    user.id,
+                    chat_id,
+                    button
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Sets the time zone for the chat.
+ * Bot receives a string representing the time zone code, and calls processor.
+ */
+pub async fn action_settings_time_zone(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+    messages: MessageTracker,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    match msg.text() {
+        Some(text) => {
+            let time_zone = parse_time_zone(text);
+            match time_zone {
+                Ok(time_zone) => {
+                    update_time_zone(&redis, &bot, &msg, text, time_zone).await?;
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                Err(err) => {
+                    let new_message = send_bot_message(
+                        &bot,
+                        &msg,
+                        error_to_user_message(&redis, &err, &msg.chat.id.to_string()).await,
+                    )
+                    .await?
+                    .id;
+                    repeat_state(dialogue, state, new_message).await?;
+                }
+            }
+        }
+        None => {
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
+            repeat_state(dialogue, state, new_message).await?;
+        }
+    }
+    Ok(())
+}
+
+/* Presents the default currency for the chat.
+ * Receives a callback query on whether the user wants to edit the default currency.
+ */
+pub async fn action_default_currency_menu(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    mut messages: MessageTracker,
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            match button.as_str() {
+                "Disable" => {
+                    let process =
+                        update_chat_default_currency(&redis, &chat_id, CURRENCY_DEFAULT.0).await;
+                    match process {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, SETTINGS_CURRENCY_DISABLED, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Default Currency - Default currency disabled for chat {}",
+                                chat_id
+                            );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Default Currency - Error setting default currency for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                                );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+
+                    // Logging
+                    log::info!(
+                        "Settings Default Currency - Default currency disabled for chat {}",
+                        chat_id
+                    );
+                }
+                "Edit" => {
+                    let setting =
+                        get_chat_setting(&redis, &chat_id, ChatSetting::DefaultCurrency(None))
+                            .await?;
+                    if let ChatSetting::DefaultCurrency(Some(currency)) = setting {
+                        let currency_info = if currency == CURRENCY_DEFAULT.0 {
+                            t(&redis, &chat_id, SETTINGS_CURRENCY_NOT_SET, &[]).await
+                        } else {
+                            t(
+                                &redis,
+                                &chat_id,
+                                SETTINGS_CURRENCY_CURRENT,
+                                &[("currency", &currency)],
+                            )
+                            .await
+                        };
+
+                        edit_tracked_message(
+                            &bot,
+                            &msg,
+                            &mut messages,
+                            format!(
+                                "{currency_info}\n\n{}",
+                                t_prompt(
+                                    &redis,
+                                    &chat_id,
+                                    SETTINGS_CURRENCY_PROMPT,
+                                    &[],
+                                    CURRENCY_INSTRUCTIONS
+                                )
+                                .await
+                            ),
+                        )
+                        .await?;
+                        dialogue
+                            .update(State::SettingsDefaultCurrency { messages })
+                            .await?;
+                    }
+                }
+                ONBOARDING_SKIP => {
+                    complete_settings(&redis, &bot, dialogue, &msg.chat.id.to_string(), messages)
+                        .await?;
+                }
+                "Back" => {
+                    display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                        .await?;
+                }
+                _ => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Default Currency Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            chat_id,
+                            button
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Sets the default currency for the chat.
+ * Bot receives a string representing the currency code, and calls processor.
+ */
+pub async fn action_settings_default_currency(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+    messages: MessageTracker,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    match msg.text() {
+        Some(text) => {
+            let currency = match get_currency(text) {
+                Ok(currency) => check_default_currency_allowed(&redis, &chat_id, &currency.0)
+                    .await
+                    .map(|_| currency),
+                Err(err) => Err(err),
+            };
+            match currency {
+                Ok(currency) => {
+                    let process = update_chat_default_currency(&redis, &chat_id, &currency.0).await;
+                    match process {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(
+                                    &redis,
+                                    &chat_id,
+                                    SETTINGS_CURRENCY_UPDATED,
+                                    &[("currency", &currency.0)],
+                                )
+                                .await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Default Currency - Default currency set for chat {}: {}",
+                                chat_id,
+                                currency.0
+                            );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Default Currency - Error setting default currency for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                                );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                Err(err) => {
+                    let new_message = send_bot_message(
+                        &bot,
+                        &msg,
+                        format!(
+                            "{}\n\n{}",
+                            error_to_user_message(&redis, &err, &chat_id).await,
+                            t(&redis, &chat_id, CURRENCY_INSTRUCTIONS, &[]).await
+                        ),
+                    )
+                    .await?
+                    .id;
+                    repeat_state(dialogue, state, new_message).await?;
+                }
+            }
+        }
+        None => {
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
+            repeat_state(dialogue, state, new_message).await?;
+        }
+    }
+    Ok(())
+}
+
+/* Sets whether currency conversion is enabled for the chat.
+ * Bot receives a callback query, and calls processor.
+ */
+pub async fn action_settings_currency_conversion(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    messages: MessageTracker,
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            match button.as_str() {
+                ONBOARDING_SKIP => {
+                    complete_settings(&redis, &bot, dialogue, &msg.chat.id.to_string(), messages)
+                        .await?;
+                }
+                "Back" => {
+                    display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                        .await?;
+                }
+                "Turn On" => {
+                    let setting = ChatSetting::CurrencyConversion(Some(true));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
+                    match process {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, SETTINGS_CONVERSION_ON, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Currency Conversion - Currency conversion enabled for chat {}",
+                                chat_id
+                                );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Currency Conversion - Error setting currency conversion for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                                );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                "Turn Off" => {
+                    let setting = ChatSetting::CurrencyConversion(Some(false));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
+                    match process {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, SETTINGS_CONVERSION_OFF, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Currency Conversion - Currency conversion disabled for chat {}",
+                                chat_id
+                                );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Currency Conversion - Error setting currency conversion for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                                );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                _ => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            msg.chat.id,
+                            button
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Sets whether erase messages is enabled for the chat.
+ * Bot receives a callback query, and calls processor.
+ */
+pub async fn action_settings_erase_messages(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    messages: MessageTracker,
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            match button.as_str() {
+                ONBOARDING_SKIP => {
+                    complete_settings(&redis, &bot, dialogue, &msg.chat.id.to_string(), messages)
+                        .await?;
+                }
+                "Back" => {
+                    display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                        .await?;
+                }
+                "Turn On" => {
+                    let setting = ChatSetting::EraseMessages(Some(true));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
+                    match process {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, SETTINGS_ERASE_ON, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Erase Messages - Erase Messages enabled for chat {}",
+                                chat_id
+                            );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Erase Messages - Error setting message erasure for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                                );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                "Turn Off" => {
+                    let setting = ChatSetting::EraseMessages(Some(false));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
+                    match process {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, SETTINGS_ERASE_OFF, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Erase Messages - Erase Messages disabled for chat {}",
+                                chat_id
+                            );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Erase Messages - Error setting message erasure for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                                );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                ERASE_USER_MESSAGES_BUTTON | KEEP_USER_MESSAGES_BUTTON => {
+                    let erase_user_messages = button.as_str() == ERASE_USER_MESSAGES_BUTTON;
+                    let setting = ChatSetting::EraseUserMessages(Some(erase_user_messages));
+                    match set_chat_setting(&redis, &chat_id, setting).await {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                display_erase_user_messages_updated(
+                                    &redis,
+                                    &chat_id,
+                                    erase_user_messages,
+                                )
+                                .await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Erase Messages - Erase User Messages {} for chat {}",
+                                if erase_user_messages {
+                                    "enabled"
+                                } else {
+                                    "disabled"
+                                },
+                                chat_id
+                            );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Erase Messages - Error setting user message erasure for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                            );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                _ => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            msg.chat.id,
+                            button
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Sets whether monthly archive is enabled for the chat.
+ * Bot receives a callback query, and calls processor.
+ */
+pub async fn action_settings_monthly_archive(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    messages: MessageTracker,
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            match button.as_str() {
+                "Back" => {
+                    display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                        .await?;
+                }
+                "Turn On" | "Turn Off" => {
+                    let archive = button.as_str() == "Turn On";
+                    let setting = ChatSetting::MonthlyArchive(Some(archive));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
+                    match process {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(
+                                    &redis,
+                                    &chat_id,
+                                    if archive {
+                                        SETTINGS_ARCHIVE_ON
+                                    } else {
+                                        SETTINGS_ARCHIVE_OFF
+                                    },
+                                    &[],
+                                )
+                                .await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Monthly Archive - Monthly Archive set to {} for chat {}",
+                                archive,
+                                chat_id
+                            );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Monthly Archive - Error setting monthly archive for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                                );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                _ => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            msg.chat.id,
+                            button
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Sets admin only for the chat.
+ * Receives a callback query on whether the user wants to turn admin only on or off.
+ */
+pub async fn action_settings_admin_only(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    messages: MessageTracker,
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            match button.as_str() {
+                "Back" => {
+                    display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                        .await?;
+                }
+                "Turn On" | "Turn Off" => {
+                    let admin_only = button.as_str() == "Turn On";
+                    let setting = ChatSetting::AdminOnly(Some(admin_only));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
+                    match process {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(
+                                    &redis,
+                                    &chat_id,
+                                    if admin_only {
+                                        SETTINGS_ADMIN_ONLY_ON
+                                    } else {
+                                        SETTINGS_ADMIN_ONLY_OFF
+                                    },
+                                    &[],
+                                )
+                                .await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Admin Only - Admin Only set to {} for chat {}",
+                                admin_only,
+                                chat_id
+                            );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Admin Only - Error setting admin only for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                            );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                _ => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            msg.chat.id,
+                            button
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Presents the weekly digest setting for the chat.
+ * Receives a callback query on whether the user wants to edit or turn off the weekly digest.
+ */
+pub async fn action_weekly_digest_menu(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    mut messages: MessageTracker,
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            match button.as_str() {
+                "Back" => {
+                    display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                        .await?;
+                }
+                "Edit" | "Turn On" => {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t(&redis, &chat_id, SETTINGS_DIGEST_TIME_PROMPT, &[]).await,
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::SettingsWeeklyDigest { messages })
+                        .await?;
+                }
+                "Turn Off" => {
+                    let setting = ChatSetting::WeeklyDigest(Some(None));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
+                    match process {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, SETTINGS_DIGEST_OFF, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Weekly Digest - Weekly Digest disabled for chat {}",
+                                chat_id
+                            );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Weekly Digest - Error disabling weekly digest for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                            );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                _ => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Weekly Digest Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            chat_id,
+                            button
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Sets the time of the weekly digest for the chat, and turns it on.
+ * Bot receives a string representing the time of day, and calls processor.
+ */
+pub async fn action_settings_weekly_digest(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+    messages: MessageTracker,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    match msg.text() {
+        Some(text) => match parse_digest_time(text) {
+            Ok(time) => {
+                let setting = ChatSetting::WeeklyDigest(Some(Some(time.clone())));
+                let process = set_chat_setting(&redis, &chat_id, setting).await;
+                match process {
+                    Ok(_) => {
+                        send_bot_message(
+                            &bot,
+                            &msg,
+                            t(&redis, &chat_id, SETTINGS_DIGEST_ON, &[("time", &time)]).await,
+                        )
+                        .await?;
+
+                        // Logging
+                        log::info!(
+                            "Settings Weekly Digest - Weekly Digest set for chat {}: {}",
+                            chat_id,
+                            time
+                        );
+                    }
+                    Err(err) => {
+                        send_bot_message(
+                            &bot,
+                            &msg,
+                            t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                        )
+                        .await?;
+
+                        // Logging
+                        log::error!(
+                            "Settings Weekly Digest - Error setting weekly digest for chat {}: {}",
+                            chat_id,
+                            err.to_string()
+                        );
+                    }
+                }
+                complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+            }
+            Err(err) => {
+                let new_message = send_bot_message(
+                    &bot,
+                    &msg,
+                    error_to_user_message(&redis, &err, &msg.chat.id.to_string()).await,
+                )
+                .await?
+                .id;
+                repeat_state(dialogue, state, new_message).await?;
+            }
+        },
+        None => {
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
+            repeat_state(dialogue, state, new_message).await?;
+        }
+    }
+    Ok(())
+}
+
+/* Presents the debt reminder setting for the chat.
+ * Receives a callback query on whether the user wants to edit or turn off the debt reminder.
+ */
+pub async fn action_debt_reminder_menu(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    mut messages: MessageTracker,
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            match button.as_str() {
+                "Back" => {
+                    display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                        .await?;
+                }
+                "Edit" | "Turn On" => {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t(&redis, &chat_id, SETTINGS_REMINDER_DAYS_PROMPT, &[]).await,
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::SettingsDebtReminder { messages })
+                        .await?;
+                }
+                "Turn Off" => {
+                    let setting = ChatSetting::DebtReminder(Some(None));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
+                    match process {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, SETTINGS_REMINDER_OFF, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Debt Reminder - Debt Reminder disabled for chat {}",
+                                chat_id
+                            );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Debt Reminder - Error disabling debt reminder for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                            );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                _ => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Debt Reminder Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            chat_id,
+                            button
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Sets the interval of the debt reminder for the chat, and turns it on.
+ * Bot receives a string representing the number of days, and calls processor.
+ */
+pub async fn action_settings_debt_reminder(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+    messages: MessageTracker,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    match msg.text() {
+        Some(text) => match parse_reminder_days(text) {
+            Ok(days) => {
+                let setting = ChatSetting::DebtReminder(Some(Some(days)));
+                let process = set_chat_setting(&redis, &chat_id, setting).await;
+                match process {
+                    Ok(_) => {
+                        send_bot_message(
+                            &bot,
+                            &msg,
+                            t(
+                                &redis,
+                                &chat_id,
+                                SETTINGS_REMINDER_ON,
+                                &[("days", &days.to_string())],
+                            )
+                            .await,
+                        )
+                        .await?;
+
+                        // Logging
+                        log::info!(
+                            "Settings Debt Reminder - Debt Reminder set for chat {}: {}",
+                            chat_id,
+                            days
+                        );
+                    }
+                    Err(err) => {
+                        send_bot_message(
+                            &bot,
+                            &msg,
+                            t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                        )
+                        .await?;
+
+                        // Logging
+                        log::error!(
+                            "Settings Debt Reminder - Error setting debt reminder for chat {}: {}",
+                            chat_id,
+                            err.to_string()
+                        );
+                    }
+                }
+                complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+            }
+            Err(err) => {
+                let new_message = send_bot_message(
+                    &bot,
+                    &msg,
+                    error_to_user_message(&redis, &err, &msg.chat.id.to_string()).await,
+                )
+                .await?
+                .id;
+                repeat_state(dialogue, state, new_message).await?;
+            }
+        },
+        None => {
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
+            repeat_state(dialogue, state, new_message).await?;
+        }
+    }
+    Ok(())
+}
+
+/* Presents the treasurer setting for the chat.
+ * Receives a callback query on whether the user wants to change or turn off the treasurer.
+ */
+pub async fn action_treasurer_menu(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    mut messages: MessageTracker,
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            match button.as_str() {
+                "Back" => {
+                    display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                        .await?;
+                }
+                "Edit" | "Turn On" => {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t(&redis, &chat_id, SETTINGS_TREASURER_USERNAME_PROMPT, &[]).await,
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::SettingsTreasurer { messages })
+                        .await?;
+                }
+                "Turn Off" => {
+                    let setting = ChatSetting::Treasurer(Some(None));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
+                    match process {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, SETTINGS_TREASURER_OFF, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Treasurer - Treasurer disabled for chat {}",
+                                chat_id
+                            );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Treasurer - Error disabling treasurer for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                            );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                _ => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Treasurer Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            chat_id,
+                            button
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Sets the treasurer for the chat, and turns it on.
+ * Bot receives a string representing the username, and calls processor.
+ */
+pub async fn action_settings_treasurer(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+    messages: MessageTracker,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    match msg.text() {
+        Some(text) => match parse_username(text.trim()) {
+            Ok(treasurer) => {
+                let setting = ChatSetting::Treasurer(Some(Some(treasurer.clone())));
+                let process = set_chat_setting(&redis, &chat_id, setting).await;
+                match process {
+                    Ok(_) => {
+                        send_bot_message(
+                            &bot,
+                            &msg,
+                            t(
+                                &redis,
+                                &chat_id,
+                                SETTINGS_TREASURER_ON,
+                                &[("treasurer", &treasurer)],
+                            )
+                            .await,
+                        )
+                        .await?;
+
+                        // Logging
+                        log::info!(
+                            "Settings Treasurer - Treasurer set for chat {}: {}",
+                            chat_id,
+                            treasurer
+                        );
+                    }
+                    Err(err) => {
+                        send_bot_message(
+                            &bot,
+                            &msg,
+                            t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                        )
+                        .await?;
+
+                        // Logging
+                        log::error!(
+                            "Settings Treasurer - Error setting treasurer for chat {}: {}",
+                            chat_id,
+                            err.to_string()
+                        );
+                    }
+                }
+                complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+            }
+            Err(err) => {
+                let new_message = send_bot_message(
+                    &bot,
+                    &msg,
+                    error_to_user_message(&redis, &err, &msg.chat.id.to_string()).await,
+                )
+                .await?
+                .id;
+                repeat_state(dialogue, state, new_message).await?;
+            }
+        },
+        None => {
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
+            repeat_state(dialogue, state, new_message).await?;
+        }
+    }
+    Ok(())
+}
+
+/* Presents the payment approval setting for the chat.
+ * Receives a callback query on whether the user wants to edit or turn off payment approval.
+ */
+pub async fn action_payment_approval_menu(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    mut messages: MessageTracker,
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            match button.as_str() {
+                "Back" => {
+                    display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                        .await?;
+                }
+                "Edit" | "Turn On" => {
+                    edit_tracked_message(
+                        &bot,
+                        &msg,
+                        &mut messages,
+                        t(&redis, &chat_id, SETTINGS_APPROVAL_THRESHOLD_PROMPT, &[]).await,
+                    )
+                    .await?;
+                    dialogue
+                        .update(State::SettingsPaymentApproval { messages })
+                        .await?;
+                }
+                "Turn Off" => {
+                    let setting = ChatSetting::PaymentApproval(Some(None));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
+                    match process {
+                        Ok(_) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, SETTINGS_APPROVAL_OFF, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::info!(
+                                "Settings Payment Approval - Payment Approval disabled for chat {}",
+                                chat_id
+                            );
+                        }
+                        Err(err) => {
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
+
+                            // Logging
+                            log::error!(
+                                "Settings Payment Approval - Error disabling payment approval for chat {}: {}",
+                                chat_id,
+                                err.to_string()
+                            );
+                        }
+                    }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
+                }
+                _ => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Payment Approval Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            chat_id,
+                            button
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Sets the threshold amount for payment approval for the chat, and turns it on.
+ * Bot receives a string representing the amount, and calls processor.
+ */
+pub async fn action_settings_payment_approval(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    state: State,
+    msg: Message,
+    messages: MessageTracker,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    match msg.text() {
+        Some(text) => match parse_float(text.trim()) {
+            Ok(threshold) => {
+                let setting = ChatSetting::PaymentApproval(Some(Some(threshold)));
+                let process = set_chat_setting(&redis, &chat_id, setting).await;
+                match process {
+                    Ok(_) => {
+                        send_bot_message(
+                            &bot,
+                            &msg,
+                            t(
+                                &redis,
+                                &chat_id,
+                                SETTINGS_APPROVAL_ON,
+                                &[("threshold", &threshold.to_string())],
+                            )
+                            .await,
+                        )
+                        .await?;
+
+                        // Logging
+                        log::info!(
+                            "Settings Payment Approval - Payment Approval set for chat {}: {}",
+                            chat_id,
+                            threshold
+                        );
+                    }
+                    Err(err) => {
+                        send_bot_message(
+                            &bot,
+                            &msg,
+                            t(&redis, &msg.chat.id.to_string(), UNKNOWN_ERROR, &[]).await,
+                        )
                         .await?;
-                }
-                _ => {
-                    if let Some(user) = msg.from() {
+
+                        // Logging
                         log::error!(
-                            "Settings Time Zone Menu - Invalid button for user {} in chat {}: {}",
-                            user.id,
+                            "Settings Payment Approval - Error setting payment approval for chat {}: {}",
                             chat_id,
-                            button
+                            err.to_string()
                         );
                     }
                 }
+                complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
             }
+            Err(err) => {
+                let new_message = send_bot_message(
+                    &bot,
+                    &msg,
+                    error_to_user_message(&redis, &err, &msg.chat.id.to_string()).await,
+                )
+                .await?
+                .id;
+                repeat_state(dialogue, state, new_message).await?;
+            }
+        },
+        None => {
+            let new_message = send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &msg.chat.id.to_string(), NO_TEXT, &[]).await,
+            )
+            .await?
+            .id;
+            repeat_state(dialogue, state, new_message).await?;
         }
     }
     Ok(())
 }
 
-/* Sets the time zone for the chat.
- * Bot receives a string representing the time zone code, and calls processor.
+/* Presents the language for the chat.
+ * Receives a callback query on which language the user wants.
  */
-pub async fn action_settings_time_zone(
+pub async fn action_language_menu(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
-    state: State,
-    msg: Message,
-    messages: Vec<MessageId>,
+    query: CallbackQuery,
+    messages: MessageTracker,
 ) -> HandlerResult {
-    let chat_id = msg.chat.id.to_string();
-    match msg.text() {
-        Some(text) => {
-            let time_zone = parse_time_zone(text);
-            match time_zone {
-                Ok(time_zone) => {
-                    let setting = ChatSetting::TimeZone(Some(text.to_string()));
-                    let process = set_chat_setting(&chat_id, setting).await;
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            if button == "Back" {
+                display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                    .await?;
+                return Ok(());
+            }
+
+            match get_language(button) {
+                Some(language) => {
+                    let setting = ChatSetting::Language(Some(language.0.to_string()));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
                     match process {
                         Ok(_) => {
                             send_bot_message(
                                 &bot,
                                 &msg,
-                                format!("You got it! I've set the 🕔 Time Zone to {}!", time_zone),
+                                translate(
+                                    language.0,
+                                    LANGUAGE_UPDATED,
+                                    &[("language", language.1)],
+                                ),
                             )
                             .await?;
 
                             // Logging
                             log::info!(
-                                "Settings Time Zone - Time zone set for chat {}: {}",
+                                "Settings Language - Language set for chat {}: {}",
                                 chat_id,
-                                time_zone
+                                language.0
                             );
                         }
                         Err(err) => {
-                            send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
 
                             // Logging
                             log::error!(
-                                "Settings Time Zone - Error setting time zone for chat {}: {}",
+                                "Settings Language - Error setting language for chat {}: {}",
                                 chat_id,
                                 err.to_string()
                             );
                         }
                     }
-                    complete_settings(&bot, dialogue, &chat_id, messages).await?;
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
                 }
-                Err(err) => {
-                    let new_message = send_bot_message(&bot, &msg, err.to_string()).await?.id;
-                    repeat_state(dialogue, state, new_message).await?;
+                None => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Language Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            chat_id,
+                            button
+                        );
+                    }
                 }
             }
         }
-        None => {
-            let new_message = send_bot_message(&bot, &msg, format!("{NO_TEXT_MESSAGE}"))
-                .await?
-                .id;
-            repeat_state(dialogue, state, new_message).await?;
-        }
     }
     Ok(())
 }
 
-/* Presents the default currency for the chat.
- * Receives a callback query on whether the user wants to edit the default currency.
+/* Presents the number format for the chat.
+ * Receives a callback query on which number format the user wants.
  */
-pub async fn action_default_currency_menu(
+pub async fn action_number_format_menu(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     query: CallbackQuery,
-    messages: Vec<MessageId>,
+    messages: MessageTracker,
 ) -> HandlerResult {
     if let Some(button) = &query.data {
         bot.answer_callback_query(query.id.to_string()).await?;
         if let Some(msg) = query.message {
             let chat_id = msg.chat.id.to_string();
-            match button.as_str() {
-                "Disable" => {
-                    let process = update_chat_default_currency(&chat_id, CURRENCY_DEFAULT.0).await;
+            if button == "Back" {
+                display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                    .await?;
+                return Ok(());
+            }
+
+            match get_number_format(button) {
+                Some(number_format) => {
+                    let setting = ChatSetting::NumberFormat(Some(number_format.0.to_string()));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
                     match process {
                         Ok(_) => {
                             send_bot_message(
                                 &bot,
                                 &msg,
-                                format!("You got it! I've disabled the 💵 Default Currency!"),
+                                t(
+                                    &redis,
+                                    &chat_id,
+                                    SETTINGS_NUMBER_FORMAT_UPDATED,
+                                    &[("format", number_format.0)],
+                                )
+                                .await,
                             )
                             .await?;
 
                             // Logging
                             log::info!(
-                                "Settings Default Currency - Default currency disabled for chat {}",
-                                chat_id
+                                "Settings Number Format - Number format set for chat {}: {}",
+                                chat_id,
+                                number_format.0
                             );
                         }
                         Err(err) => {
-                            send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
 
                             // Logging
                             log::error!(
-                                "Settings Default Currency - Error setting default currency for chat {}: {}",
+                                "Settings Number Format - Error setting number format for chat {}: {}",
                                 chat_id,
                                 err.to_string()
-                                );
-                        }
-                    }
-                    complete_settings(&bot, dialogue, &chat_id, messages).await?;
-
-                    // Logging
-                    log::info!(
-                        "Settings Default Currency - Default currency disabled for chat {}",
-                        chat_id
-                    );
-                }
-                "Edit" => {
-                    let setting = get_chat_setting(&chat_id, ChatSetting::DefaultCurrency(None))?;
-                    if let ChatSetting::DefaultCurrency(Some(currency)) = setting {
-                        let currency_info: String;
-                        if currency == CURRENCY_DEFAULT.0 {
-                            currency_info = format!("💵 Default Currency is NOT set.");
-                        } else {
-                            currency_info = format!("💵 Default Currency: {}", currency);
+                            );
                         }
-
-                        bot.edit_message_text(
-                            chat_id,
-                            msg.id,
-                            format!(
-                                "{currency_info}\n\nWhat would you like to set as the default currency?\n\n{CURRENCY_INSTRUCTIONS_MESSAGE}",
-                                ))
-                            .await?;
-                        dialogue
-                            .update(State::SettingsDefaultCurrency { messages })
-                            .await?;
                     }
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
                 }
-                "Back" => {
-                    display_settings_menu(&bot, &dialogue, &msg, Some(msg.id), messages).await?;
-                }
-                _ => {
+                None => {
                     if let Some(user) = msg.from() {
                         log::error!(
-                            "Settings Default Currency Menu - Invalid button for user {} in chat {}: {}",
+                            "Settings Number Format Menu - Invalid button for user {} in chat {}: {}",
                             user.id,
                             chat_id,
                             button
@@ -585,162 +3786,151 @@ pub async fn action_default_currency_menu(
     Ok(())
 }
 
-/* Sets the default currency for the chat.
- * Bot receives a string representing the currency code, and calls processor.
+/* Presents the currency format for the chat.
+ * Receives a callback query on which currency format the user wants.
  */
-pub async fn action_settings_default_currency(
+pub async fn action_currency_format_menu(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
-    state: State,
-    msg: Message,
-    messages: Vec<MessageId>,
+    query: CallbackQuery,
+    messages: MessageTracker,
 ) -> HandlerResult {
-    let chat_id = msg.chat.id.to_string();
-    match msg.text() {
-        Some(text) => {
-            let currency = get_currency(text);
-            match currency {
-                Ok(currency) => {
-                    let process = update_chat_default_currency(&chat_id, &currency.0).await;
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            if button == "Back" {
+                display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                    .await?;
+                return Ok(());
+            }
+
+            match get_currency_format(button) {
+                Some(currency_format) => {
+                    let setting = ChatSetting::CurrencyFormat(Some(currency_format.0.to_string()));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
                     match process {
                         Ok(_) => {
                             send_bot_message(
                                 &bot,
                                 &msg,
-                                format!(
-                                    "You got it! I've set the 💵 Default Currency to {}!",
-                                    currency.0
-                                ),
+                                t(
+                                    &redis,
+                                    &chat_id,
+                                    SETTINGS_CURRENCY_FORMAT_UPDATED,
+                                    &[("format", currency_format.0)],
+                                )
+                                .await,
                             )
                             .await?;
 
                             // Logging
                             log::info!(
-                                "Settings Default Currency - Default currency set for chat {}: {}",
+                                "Settings Currency Format - Currency format set for chat {}: {}",
                                 chat_id,
-                                currency.0
+                                currency_format.0
                             );
                         }
                         Err(err) => {
-                            send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
 
                             // Logging
                             log::error!(
-                                "Settings Default Currency - Error setting default currency for chat {}: {}",
+                                "Settings Currency Format - Error setting currency format for chat {}: {}",
                                 chat_id,
                                 err.to_string()
-                                );
+                            );
                         }
                     }
-                    complete_settings(&bot, dialogue, &chat_id, messages).await?;
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
                 }
-                Err(err) => {
-                    let new_message = send_bot_message(
-                        &bot,
-                        &msg,
-                        format!("{}\n\n{CURRENCY_INSTRUCTIONS_MESSAGE}", err),
-                    )
-                    .await?
-                    .id;
-                    repeat_state(dialogue, state, new_message).await?;
+                None => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Currency Format Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            chat_id,
+                            button
+                        );
+                    }
                 }
             }
         }
-        None => {
-            let new_message = send_bot_message(&bot, &msg, format!("{NO_TEXT_MESSAGE}"))
-                .await?
-                .id;
-            repeat_state(dialogue, state, new_message).await?;
-        }
     }
     Ok(())
 }
 
-/* Sets whether currency conversion is enabled for the chat.
- * Bot receives a callback query, and calls processor.
+/* Presents the verbosity for the chat.
+ * Receives a callback query on which verbosity the user wants.
  */
-pub async fn action_settings_currency_conversion(
+pub async fn action_verbosity_menu(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     query: CallbackQuery,
-    messages: Vec<MessageId>,
+    messages: MessageTracker,
 ) -> HandlerResult {
     if let Some(button) = &query.data {
         bot.answer_callback_query(query.id.to_string()).await?;
         if let Some(msg) = query.message {
             let chat_id = msg.chat.id.to_string();
-            match button.as_str() {
-                "Back" => {
-                    display_settings_menu(&bot, &dialogue, &msg, Some(msg.id), messages).await?;
-                }
-                "Turn On" => {
-                    let setting = ChatSetting::CurrencyConversion(Some(true));
-                    let process = set_chat_setting(&chat_id, setting).await;
+            if button == "Back" {
+                display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                    .await?;
+                return Ok(());
+            }
+
+            match get_verbosity(button) {
+                Some(_) => {
+                    let verbosity = button.to_lowercase();
+                    let setting = ChatSetting::Verbosity(Some(verbosity.clone()));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
                     match process {
                         Ok(_) => {
                             send_bot_message(
                                 &bot,
                                 &msg,
-                                "You got it! I've turned on ↔️ Currency Conversion!".to_string(),
+                                display_verbosity_updated(&redis, &chat_id, &verbosity).await,
                             )
                             .await?;
 
                             // Logging
                             log::info!(
-                                "Settings Currency Conversion - Currency conversion enabled for chat {}",
-                                chat_id
-                                );
-                        }
-                        Err(err) => {
-                            send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
-
-                            // Logging
-                            log::error!(
-                                "Settings Currency Conversion - Error setting currency conversion for chat {}: {}",
+                                "Settings Verbosity - Verbosity set for chat {}: {}",
                                 chat_id,
-                                err.to_string()
-                                );
+                                verbosity
+                            );
                         }
-                    }
-                    complete_settings(&bot, dialogue, &chat_id, messages).await?;
-                }
-                "Turn Off" => {
-                    let setting = ChatSetting::CurrencyConversion(Some(false));
-                    let process = set_chat_setting(&chat_id, setting).await;
-                    match process {
-                        Ok(_) => {
+                        Err(err) => {
                             send_bot_message(
                                 &bot,
                                 &msg,
-                                "You got it! I've turned off ↔️ Currency Conversion!".to_string(),
+                                t(&redis, &chat_id, UNKNOWN_ERROR, &[]).await,
                             )
                             .await?;
 
-                            // Logging
-                            log::info!(
-                                "Settings Currency Conversion - Currency conversion disabled for chat {}",
-                                chat_id
-                                );
-                        }
-                        Err(err) => {
-                            send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
-
                             // Logging
                             log::error!(
-                                "Settings Currency Conversion - Error setting currency conversion for chat {}: {}",
+                                "Settings Verbosity - Error setting verbosity for chat {}: {}",
                                 chat_id,
                                 err.to_string()
-                                );
+                            );
                         }
                     }
-                    complete_settings(&bot, dialogue, &chat_id, messages).await?;
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
                 }
-                _ => {
+                None => {
                     if let Some(user) = msg.from() {
                         log::error!(
-                            "Settings Menu - Invalid button for user {} in chat {}: {}",
+                            "Settings Verbosity Menu - Invalid button for user {} in chat {}: {}",
                             user.id,
-                            msg.chat.id,
+                            chat_id,
                             button
                         );
                     }
@@ -751,91 +3941,146 @@ pub async fn action_settings_currency_conversion(
     Ok(())
 }
 
-/* Sets whether erase messages is enabled for the chat.
- * Bot receives a callback query, and calls processor.
+/* Presents the rounding strategy for the chat.
+ * Receives a callback query on which rounding strategy the user wants.
  */
-pub async fn action_settings_erase_messages(
+pub async fn action_rounding_menu(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     query: CallbackQuery,
-    messages: Vec<MessageId>,
+    messages: MessageTracker,
 ) -> HandlerResult {
     if let Some(button) = &query.data {
         bot.answer_callback_query(query.id.to_string()).await?;
         if let Some(msg) = query.message {
             let chat_id = msg.chat.id.to_string();
-            match button.as_str() {
-                "Back" => {
-                    display_settings_menu(&bot, &dialogue, &msg, Some(msg.id), messages).await?;
-                }
-                "Turn On" => {
-                    let setting = ChatSetting::EraseMessages(Some(true));
-                    let process = set_chat_setting(&chat_id, setting).await;
+            if button == "Back" {
+                display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                    .await?;
+                return Ok(());
+            }
+
+            match get_rounding(button) {
+                Some(_) => {
+                    let rounding = button.to_lowercase().replace(' ', "");
+                    let setting = ChatSetting::Rounding(Some(rounding.clone()));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
                     match process {
                         Ok(_) => {
                             send_bot_message(
                                 &bot,
                                 &msg,
-                                "You got it! I've turned on 🚮 Erase Messages!".to_string(),
+                                display_rounding_updated(&redis, &chat_id, &rounding).await,
                             )
                             .await?;
 
                             // Logging
                             log::info!(
-                                "Settings Erase Messages - Erase Messages enabled for chat {}",
-                                chat_id
+                                "Settings Rounding - Rounding set for chat {}: {}",
+                                chat_id,
+                                rounding
                             );
                         }
                         Err(err) => {
-                            send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
 
                             // Logging
                             log::error!(
-                                "Settings Erase Messages - Error setting message erasure for chat {}: {}",
+                                "Settings Rounding - Error setting rounding for chat {}: {}",
                                 chat_id,
                                 err.to_string()
-                                );
+                            );
                         }
                     }
-                    complete_settings(&bot, dialogue, &chat_id, messages).await?;
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
                 }
-                "Turn Off" => {
-                    let setting = ChatSetting::EraseMessages(Some(false));
-                    let process = set_chat_setting(&chat_id, setting).await;
+                None => {
+                    if let Some(user) = msg.from() {
+                        log::error!(
+                            "Settings Rounding Menu - Invalid button for user {} in chat {}: {}",
+                            user.id,
+                            chat_id,
+                            button
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Presents the first day of the week for the chat.
+ * Receives a callback query on which day the user wants weeks to start on.
+ */
+pub async fn action_week_start_menu(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    messages: MessageTracker,
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        if let Some(msg) = query.message {
+            let chat_id = msg.chat.id.to_string();
+            if button == "Back" {
+                display_settings_menu(&redis, &bot, &dialogue, &msg, Some(msg.id), messages)
+                    .await?;
+                return Ok(());
+            }
+
+            match parse_week_start(button) {
+                Some(week_start) => {
+                    let week_start = week_start.name().to_string();
+                    let setting = ChatSetting::WeekStart(Some(week_start.clone()));
+                    let process = set_chat_setting(&redis, &chat_id, setting).await;
                     match process {
                         Ok(_) => {
                             send_bot_message(
                                 &bot,
                                 &msg,
-                                "You got it! I've turned off 🚮 Erase Messages!".to_string(),
+                                display_week_start_updated(&redis, &chat_id, &week_start).await,
                             )
                             .await?;
 
                             // Logging
                             log::info!(
-                                "Settings Erase Messages - Erase Messages disabled for chat {}",
-                                chat_id
+                                "Settings Week Start - Week start set for chat {}: {}",
+                                chat_id,
+                                week_start
                             );
                         }
                         Err(err) => {
-                            send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
+                            send_bot_message(
+                                &bot,
+                                &msg,
+                                t(&redis, &chat_id, UNKNOWN_ERROR, &[]).await,
+                            )
+                            .await?;
 
                             // Logging
                             log::error!(
-                                "Settings Erase Messages - Error setting message erasure for chat {}: {}",
+                                "Settings Week Start - Error setting week start for chat {}: {}",
                                 chat_id,
                                 err.to_string()
-                                );
+                            );
                         }
                     }
-                    complete_settings(&bot, dialogue, &chat_id, messages).await?;
+                    complete_settings(&redis, &bot, dialogue, &chat_id, messages).await?;
                 }
-                _ => {
+                None => {
                     if let Some(user) = msg.from() {
                         log::error!(
-                            "Settings Menu - Invalid button for user {} in chat {}: {}",
+                            "Settings Week Start Menu - Invalid button for user {} in chat {}: {}",
                             user.id,
-                            msg.chat.id,
+                            chat_id,
                             button
                         );
                     }
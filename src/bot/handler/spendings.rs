@@ -1,39 +1,388 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, Utc};
 use teloxide::{
     prelude::*,
-    types::{Message, MessageId},
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message, MessageId},
 };
 
 use crate::bot::{
     currency::{Currency, CURRENCY_DEFAULT},
-    handler::{
-        constants::{STATEMENT_INSTRUCTIONS_MESSAGE, UNKNOWN_ERROR_MESSAGE},
-        utils::{
-            display_amount, display_username, get_currency, make_keyboard,
-            process_valid_currencies, send_bot_message, HandlerResult, UserDialogue,
-        },
+    handler::utils::{
+        display_amount, display_name, edit_bot_message, edit_markdown_message,
+        error_to_user_message, escape_markdown, get_currency, make_keyboard,
+        process_valid_currencies, retrieve_language, retrieve_time_zone, send_bot_message,
+        BotError, HandlerResult, UserDialogue,
+    },
+    i18n::{
+        translate, translate_button, FORECAST_GROUP, FORECAST_HEADER, FORECAST_NONE,
+        FORECAST_OVER_LIMIT, FORECAST_USER, FORECAST_WITHIN_LIMIT, IN_CURRENCY, MONTH_NAME_FORMAT,
+        SPENDINGS_CONSUMPTION_HEADER, SPENDINGS_GROUP_TOTAL, SPENDINGS_HEADER,
+        SPENDINGS_HEADER_CONVERTED, SPENDINGS_HEADER_CURRENCY, SPENDINGS_PERIOD_NONE,
+        SPENDINGS_TABLE_CONSUMED, SPENDINGS_TABLE_FRONTED, SPENDINGS_TABLE_NAME,
+        SPENDINGS_TABLE_NET, SPENDINGS_USER, STATEMENT_INSTRUCTIONS,
     },
     processor::{
-        get_chat_setting, retrieve_spending_data, ChatSetting, SpendingData, UserSpending,
+        get_chat_setting, retrieve_display_names, retrieve_spending_data,
+        retrieve_spending_data_since, retrieve_spending_forecast, ChatSetting, SpendingData,
+        SpendingForecast, StatementOption, UserSpending,
     },
+    redis::Redis,
     State,
 };
 
-use super::utils::{assert_handle_request_limit, StatementOption};
+use super::utils::assert_handle_request_limit;
 
 /* Utilities */
+const CONSUMPTION_BUTTON: &str = "Consumed vs Fronted";
+const FORECAST_BUTTON: &str = "Forecast";
+const TOTALS_BUTTON: &str = "Show Totals";
+const CONSUMPTION_PERIODS: [(&str, Option<i64>); 4] = [
+    ("Past Week", Some(7)),
+    ("Past Month", Some(30)),
+    ("Past Year", Some(365)),
+    ("All Time", None),
+];
+const CONSUMPTION_NAME_WIDTH: usize = 12;
 
-fn display_individual_spending(spending: UserSpending, currency: Currency) -> String {
+// Make keyboard to pick the period of the consumption view, with the current period ticked
+fn make_keyboard_consumption(period: &str, language: &str) -> InlineKeyboardMarkup {
+    let periods = CONSUMPTION_PERIODS
+        .iter()
+        .map(|(name, _)| {
+            let text = if *name == period {
+                format!("✅ {}", translate_button(language, name))
+            } else {
+                translate_button(language, name)
+            };
+            InlineKeyboardButton::callback(text, name.to_string())
+        })
+        .collect::<Vec<InlineKeyboardButton>>();
+    let mut keyboard: Vec<Vec<InlineKeyboardButton>> =
+        periods.chunks(2).map(|row| row.to_vec()).collect();
+    keyboard.push(vec![InlineKeyboardButton::callback(
+        translate_button(language, TOTALS_BUTTON),
+        TOTALS_BUTTON,
+    )]);
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+// Displays the amounts consumed and fronted by each user as columns, in a code block
+// Names are cut short to keep the columns aligned, and net amounts are fronted minus consumed
+fn display_consumption_table(
+    spending_data: &SpendingData,
+    names: &HashMap<String, String>,
+    language: &str,
+) -> String {
+    let decimal_places = get_currency(&spending_data.currency)
+        .map(|currency| currency.1)
+        .unwrap_or(2);
+    let mut rows: Vec<[String; 4]> = vec![[
+        translate(language, SPENDINGS_TABLE_NAME, &[]),
+        translate(language, SPENDINGS_TABLE_CONSUMED, &[]),
+        translate(language, SPENDINGS_TABLE_FRONTED, &[]),
+        translate(language, SPENDINGS_TABLE_NET, &[]),
+    ]];
+    for spending in &spending_data.user_spendings {
+        let name: String = display_name(&spending.username, names)
+            .chars()
+            .take(CONSUMPTION_NAME_WIDTH)
+            .collect();
+        let net = spending.fronted - spending.spending;
+        rows.push([
+            name,
+            display_amount(spending.spending, decimal_places),
+            display_amount(spending.fronted, decimal_places),
+            format!(
+                "{}{}",
+                if net > 0 { "+" } else { "" },
+                display_amount(net, decimal_places)
+            ),
+        ]);
+    }
+
+    let mut widths = [0; 4];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let table = rows
+        .iter()
+        .map(|row| {
+            format!(
+                "{:<w0$}  {:>w1$}  {:>w2$}  {:>w3$}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+                w3 = widths[3]
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    // Only backslashes and backticks need escaping within a code block
     format!(
-        "{}\n    Total Spent: {}\n    Total Paid For: {}\n",
-        display_username(&spending.username),
-        display_amount(spending.spending, currency.1),
-        display_amount(spending.paid, currency.1)
+        "```\n{}\n```",
+        table.replace('\\', "\\\\").replace('`', "\\`")
     )
 }
 
-fn display_spendings(spending_data: &SpendingData) -> String {
+// Shows the amounts consumed and fronted by each user over a period, in place of the totals
+// Consumed is each user's share of expenses, and fronted is what they paid upfront as the payer
+async fn handle_consumption_with_period(
+    redis: &Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: Message,
+    sender_id: String,
+    period: &str,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    let days = CONSUMPTION_PERIODS
+        .iter()
+        .find(|(name, _)| *name == period)
+        .and_then(|(_, days)| *days);
+    let since = days.map(|days| (Utc::now() - Duration::days(days)).naive_utc());
+
+    let spending_data = match retrieve_spending_data_since(redis, &chat_id, since).await {
+        Ok(spending_data) => spending_data,
+        Err(err) => {
+            let err = BotError::from(err);
+            let reply = error_to_user_message(redis, &err, &chat_id).await;
+            edit_bot_message(&bot, msg.chat.id, msg.id, reply).await?;
+            log::error!(
+                "View Spendings - User {} failed to view consumption for group {}: {}",
+                sender_id,
+                chat_id,
+                err.to_string()
+            );
+            return Ok(());
+        }
+    };
+
+    let language = retrieve_language(redis, &chat_id).await;
+    let names = retrieve_display_names(redis, &chat_id)
+        .await
+        .unwrap_or_default();
+    let mut tables = String::new();
+    for data in &spending_data {
+        if spending_data.len() > 1 || data.currency != CURRENCY_DEFAULT.0 {
+            let in_currency = translate(&language, IN_CURRENCY, &[("currency", &data.currency)]);
+            tables.push_str(&escape_markdown(&format!("{in_currency}\n")));
+        }
+        tables.push_str(&display_consumption_table(data, &names, &language));
+        tables.push('\n');
+    }
+    if tables.is_empty() {
+        tables = escape_markdown(&translate(&language, SPENDINGS_PERIOD_NONE, &[]));
+    }
+
+    let header = escape_markdown(&translate(
+        &language,
+        SPENDINGS_CONSUMPTION_HEADER,
+        &[(
+            "period",
+            &translate_button(&language, period).to_lowercase(),
+        )],
+    ));
+    edit_markdown_message(
+        &bot,
+        msg.chat.id,
+        msg.id,
+        format!("{header}\n\n{}", tables.trim_end()),
+    )
+    .reply_markup(make_keyboard_consumption(period, &language))
+    .await?;
+    dialogue.update(State::SpendingsMenu).await?;
+
+    // Logging
+    log::info!(
+        "View Spendings - User {} viewed consumption for group {} over {}",
+        sender_id,
+        chat_id,
+        period.to_lowercase()
+    );
+
+    Ok(())
+}
+
+// Displays the spendings so far and projected by the end of the month, for the group and each user
+// Users with a monthly spending limit in the same currency are compared against it
+fn display_forecast(
+    forecast: &SpendingForecast,
+    names: &HashMap<String, String>,
+    language: &str,
+) -> String {
+    let decimal_places = get_currency(&forecast.currency)
+        .map(|currency| currency.1)
+        .unwrap_or(2);
+    let mut user_forecasts = String::new();
+    for user in &forecast.user_forecasts {
+        let budget = match user.limit {
+            Some(limit) if user.projected > limit => format!(
+                "\n    {}",
+                translate(
+                    language,
+                    FORECAST_OVER_LIMIT,
+                    &[
+                        ("limit", &display_amount(limit, decimal_places)),
+                        (
+                            "amount",
+                            &display_amount(user.projected - limit, decimal_places)
+                        ),
+                    ],
+                )
+            ),
+            Some(limit) => format!(
+                "\n    {}",
+                translate(
+                    language,
+                    FORECAST_WITHIN_LIMIT,
+                    &[("limit", &display_amount(limit, decimal_places))],
+                )
+            ),
+            None => String::new(),
+        };
+        user_forecasts.push_str(&translate(
+            language,
+            FORECAST_USER,
+            &[
+                ("name", &display_name(&user.username, names)),
+                ("spent", &display_amount(user.spent, decimal_places)),
+                ("projected", &display_amount(user.projected, decimal_places)),
+                ("limit", &budget),
+            ],
+        ));
+    }
+
+    translate(
+        language,
+        FORECAST_GROUP,
+        &[
+            (
+                "spent",
+                &display_amount(forecast.group_spent, decimal_places),
+            ),
+            (
+                "projected",
+                &display_amount(forecast.group_projected, decimal_places),
+            ),
+            ("users", &user_forecasts),
+        ],
+    )
+}
+
+// Shows the spendings projected by the end of the month, in place of the totals
+// Projections are made from the daily averages of the month so far, in the chat time zone
+async fn handle_forecast(
+    redis: &Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: Message,
+    sender_id: String,
+) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    let time_zone = retrieve_time_zone(redis, &chat_id).await;
+    let forecasts = match retrieve_spending_forecast(redis, &chat_id, time_zone).await {
+        Ok(forecasts) => forecasts,
+        Err(err) => {
+            let err = BotError::from(err);
+            let reply = error_to_user_message(redis, &err, &chat_id).await;
+            edit_bot_message(&bot, msg.chat.id, msg.id, reply).await?;
+            log::error!(
+                "View Spendings - User {} failed to view forecast for group {}: {}",
+                sender_id,
+                chat_id,
+                err.to_string()
+            );
+            return Ok(());
+        }
+    };
+
+    let language = retrieve_language(redis, &chat_id).await;
+    let names = retrieve_display_names(redis, &chat_id)
+        .await
+        .unwrap_or_default();
+    let mut body = String::new();
+    for forecast in &forecasts {
+        if forecasts.len() > 1 || forecast.currency != CURRENCY_DEFAULT.0 {
+            body.push_str(&translate(
+                &language,
+                IN_CURRENCY,
+                &[("currency", &forecast.currency)],
+            ));
+            body.push('\n');
+        }
+        body.push_str(&display_forecast(forecast, &names, &language));
+        body.push('\n');
+    }
+    if body.is_empty() {
+        body = translate(&language, FORECAST_NONE, &[]);
+    }
+
+    let today = Utc::now().with_timezone(&time_zone).date_naive();
+    let month_format = translate(&language, MONTH_NAME_FORMAT, &[]);
+    let header = translate(
+        &language,
+        FORECAST_HEADER,
+        &[
+            ("month", &today.format(&month_format).to_string()),
+            ("days", &today.day().to_string()),
+            ("s", if today.day() == 1 { "" } else { "s" }),
+        ],
+    );
+    edit_bot_message(
+        &bot,
+        msg.chat.id,
+        msg.id,
+        format!("{header}\n\n{}", body.trim_end()),
+    )
+    .reply_markup(InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback(translate_button(&language, TOTALS_BUTTON), TOTALS_BUTTON),
+    ]]))
+    .await?;
+    dialogue.update(State::SpendingsMenu).await?;
+
+    // Logging
+    log::info!(
+        "View Spendings - User {} viewed forecast for group {}",
+        sender_id,
+        chat_id
+    );
+
+    Ok(())
+}
+
+fn display_individual_spending(
+    spending: UserSpending,
+    currency: Currency,
+    names: &HashMap<String, String>,
+    language: &str,
+) -> String {
+    translate(
+        language,
+        SPENDINGS_USER,
+        &[
+            ("name", &display_name(&spending.username, names)),
+            ("spent", &display_amount(spending.spending, currency.1)),
+            ("paid", &display_amount(spending.paid, currency.1)),
+        ],
+    )
+}
+
+pub async fn display_spendings(
+    redis: &Redis,
+    chat_id: &str,
+    spending_data: &SpendingData,
+) -> String {
+    let language = retrieve_language(redis, chat_id).await;
     if spending_data.group_spending == 0 {
-        return format!("Total Group Spending: 0\n");
+        return translate(&language, SPENDINGS_GROUP_TOTAL, &[("amount", "0")]);
     }
 
     let currency = match get_currency(&spending_data.currency) {
@@ -42,22 +391,36 @@ fn display_spendings(spending_data: &SpendingData) -> String {
         Err(_) => ("NIL".to_string(), 2),
     };
 
+    let names = retrieve_display_names(redis, chat_id)
+        .await
+        .unwrap_or_default();
     let mut individual_spendings = String::new();
     for spending in &spending_data.user_spendings {
         individual_spendings.push_str(&display_individual_spending(
             spending.clone(),
             currency.clone(),
+            &names,
+            &language,
         ));
     }
 
     format!(
-        "Total Group Spending: {}\n\n{}",
-        display_amount(spending_data.group_spending, currency.1),
+        "{}\n{}",
+        translate(
+            &language,
+            SPENDINGS_GROUP_TOTAL,
+            &[(
+                "amount",
+                &display_amount(spending_data.group_spending, currency.1)
+            )],
+        ),
         individual_spendings
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_spendings_with_option(
+    redis: &Redis,
     bot: Bot,
     dialogue: UserDialogue,
     msg: Message,
@@ -66,28 +429,31 @@ async fn handle_spendings_with_option(
     mut option: StatementOption,
     id: Option<MessageId>,
 ) -> HandlerResult {
-    let spending_data = retrieve_spending_data(&chat_id, option.clone()).await;
+    let spending_data = retrieve_spending_data(redis, &chat_id, option.clone()).await;
 
     match spending_data {
         Ok(mut spending_data) => {
             let default_currency =
-                match get_chat_setting(&chat_id, ChatSetting::DefaultCurrency(None)) {
+                match get_chat_setting(redis, &chat_id, ChatSetting::DefaultCurrency(None)).await {
                     Ok(ChatSetting::DefaultCurrency(Some(currency))) => currency,
                     _ => CURRENCY_DEFAULT.0.to_string(),
                 };
 
             let mut valid_currencies = process_valid_currencies(
+                redis,
                 &chat_id,
                 &sender_id,
                 option.clone(),
                 default_currency.clone(),
-            );
+            )
+            .await;
 
             // If no default currency, NIL has no balances, but other currencies do
-            if spending_data.group_spending == 0 && valid_currencies.len() > 0 {
+            if spending_data.group_spending == 0 && !valid_currencies.is_empty() {
                 let currency = valid_currencies.first().unwrap().clone();
                 option = StatementOption::Currency(currency.clone());
-                spending_data = match retrieve_spending_data(&chat_id, option.clone()).await {
+                spending_data = match retrieve_spending_data(redis, &chat_id, option.clone()).await
+                {
                     Ok(new_data) => {
                         valid_currencies.retain(|curr| curr != &currency);
                         new_data
@@ -96,40 +462,49 @@ async fn handle_spendings_with_option(
                 };
             }
 
-            let ref_valid_currencies = valid_currencies
+            let mut ref_valid_currencies = valid_currencies
                 .iter()
                 .map(|x| x.as_str())
                 .collect::<Vec<&str>>();
 
-            let has_buttons = valid_currencies.len() > 0;
-            let keyboard = make_keyboard(ref_valid_currencies, Some(2));
+            let has_buttons = !valid_currencies.is_empty();
+            ref_valid_currencies.push(CONSUMPTION_BUTTON);
+            ref_valid_currencies.push(FORECAST_BUTTON);
+            let language = retrieve_language(redis, &chat_id).await;
+            let keyboard = make_keyboard(ref_valid_currencies, Some(2), &language);
 
             let header = if let StatementOption::Currency(curr) = option {
                 if curr == CURRENCY_DEFAULT.0 {
-                    format!("🔥 Here are the total spendings!")
+                    translate(&language, SPENDINGS_HEADER, &[])
                 } else {
-                    format!("🔥 Here are the total spendings for {curr}!")
+                    translate(&language, SPENDINGS_HEADER_CURRENCY, &[("currency", &curr)])
                 }
             } else if has_buttons {
-                format!("🔥 Here are the total spendings, converted to {default_currency}!")
+                translate(
+                    &language,
+                    SPENDINGS_HEADER_CONVERTED,
+                    &[("currency", &default_currency)],
+                )
+            } else {
+                translate(&language, SPENDINGS_HEADER, &[])
+            };
+            let instructions = if has_buttons {
+                translate(&language, STATEMENT_INSTRUCTIONS, &[])
             } else {
-                format!("🔥 Here are the total spendings!")
+                "".to_string()
             };
 
             match id {
                 Some(id) => {
-                    bot.edit_message_text(
+                    edit_bot_message(
+                        &bot,
                         chat_id.clone(),
                         id,
                         format!(
                             "{}\n\n{}\n{}",
                             header,
-                            display_spendings(&spending_data),
-                            if has_buttons {
-                                STATEMENT_INSTRUCTIONS_MESSAGE
-                            } else {
-                                ""
-                            }
+                            display_spendings(redis, &chat_id, &spending_data).await,
+                            instructions
                         ),
                     )
                     .reply_markup(keyboard)
@@ -142,12 +517,8 @@ async fn handle_spendings_with_option(
                         format!(
                             "{}\n\n{}\n{}",
                             header,
-                            display_spendings(&spending_data),
-                            if has_buttons {
-                                STATEMENT_INSTRUCTIONS_MESSAGE
-                            } else {
-                                ""
-                            }
+                            display_spendings(redis, &chat_id, &spending_data).await,
+                            instructions
                         ),
                     )
                     .reply_markup(keyboard)
@@ -160,17 +531,18 @@ async fn handle_spendings_with_option(
                 "View Spendings - User {} viewed spendings for group {}: {}",
                 sender_id,
                 chat_id,
-                display_spendings(&spending_data)
+                display_spendings(redis, &chat_id, &spending_data).await
             );
         }
         Err(err) => {
+            let err = BotError::from(err);
+            let reply = error_to_user_message(redis, &err, &chat_id).await;
             match id {
                 Some(id) => {
-                    bot.edit_message_text(chat_id.clone(), id, UNKNOWN_ERROR_MESSAGE)
-                        .await?;
+                    edit_bot_message(&bot, chat_id.clone(), id, reply).await?;
                 }
                 None => {
-                    send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
+                    send_bot_message(&bot, &msg, reply).await?;
                 }
             }
             log::error!(
@@ -188,24 +560,27 @@ async fn handle_spendings_with_option(
 /* View the spendings for the group.
 */
 pub async fn action_view_spendings(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     msg: Message,
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
     let chat_id = msg.chat.id.to_string();
     let sender_id = msg.from().as_ref().unwrap().id.to_string();
-    let is_convert = match get_chat_setting(&chat_id, ChatSetting::CurrencyConversion(None)) {
-        Ok(ChatSetting::CurrencyConversion(Some(value))) => value,
-        _ => false,
-    };
-    let default_currency = match get_chat_setting(&chat_id, ChatSetting::DefaultCurrency(None)) {
-        Ok(ChatSetting::DefaultCurrency(Some(currency))) => currency,
-        _ => "NIL".to_string(),
-    };
+    let is_convert =
+        match get_chat_setting(&redis, &chat_id, ChatSetting::CurrencyConversion(None)).await {
+            Ok(ChatSetting::CurrencyConversion(Some(value))) => value,
+            _ => false,
+        };
+    let default_currency =
+        match get_chat_setting(&redis, &chat_id, ChatSetting::DefaultCurrency(None)).await {
+            Ok(ChatSetting::DefaultCurrency(Some(currency))) => currency,
+            _ => "NIL".to_string(),
+        };
 
     let option = if is_convert {
         StatementOption::ConvertCurrency
@@ -213,7 +588,8 @@ pub async fn action_view_spendings(
         StatementOption::Currency(default_currency.clone())
     };
 
-    handle_spendings_with_option(bot, dialogue, msg, chat_id, sender_id, option, None).await?;
+    handle_spendings_with_option(&redis, bot, dialogue, msg, chat_id, sender_id, option, None)
+        .await?;
 
     Ok(())
 }
@@ -222,6 +598,7 @@ pub async fn action_view_spendings(
  * Receives a callback query indicating new format change.
  */
 pub async fn action_spendings_menu(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     query: CallbackQuery,
@@ -234,9 +611,63 @@ pub async fn action_spendings_menu(
             let chat_id = msg.chat.id.to_string();
             let id = msg.id;
             match button.as_str() {
+                CONSUMPTION_BUTTON => {
+                    let period = CONSUMPTION_PERIODS[1].0;
+                    handle_consumption_with_period(&redis, bot, dialogue, msg, sender_id, period)
+                        .await?;
+                }
+                FORECAST_BUTTON => {
+                    handle_forecast(&redis, bot, dialogue, msg, sender_id).await?;
+                }
+                _ if CONSUMPTION_PERIODS
+                    .iter()
+                    .any(|(period, _)| button.as_str() == *period) =>
+                {
+                    handle_consumption_with_period(&redis, bot, dialogue, msg, sender_id, button)
+                        .await?;
+                }
+                TOTALS_BUTTON => {
+                    let is_convert = match get_chat_setting(
+                        &redis,
+                        &chat_id,
+                        ChatSetting::CurrencyConversion(None),
+                    )
+                    .await
+                    {
+                        Ok(ChatSetting::CurrencyConversion(Some(value))) => value,
+                        _ => false,
+                    };
+                    let option = if is_convert {
+                        StatementOption::ConvertCurrency
+                    } else {
+                        let default_currency = match get_chat_setting(
+                            &redis,
+                            &chat_id,
+                            ChatSetting::DefaultCurrency(None),
+                        )
+                        .await
+                        {
+                            Ok(ChatSetting::DefaultCurrency(Some(currency))) => currency,
+                            _ => CURRENCY_DEFAULT.0.to_string(),
+                        };
+                        StatementOption::Currency(default_currency)
+                    };
+                    handle_spendings_with_option(
+                        &redis,
+                        bot,
+                        dialogue,
+                        msg,
+                        chat_id,
+                        sender_id,
+                        option,
+                        Some(id),
+                    )
+                    .await?;
+                }
                 _ if button.as_str().starts_with("Convert To ") => {
                     let option = StatementOption::ConvertCurrency;
                     handle_spendings_with_option(
+                        &redis,
                         bot,
                         dialogue,
                         msg,
@@ -250,6 +681,7 @@ pub async fn action_spendings_menu(
                 _ if button.as_str() == "No Currency" => {
                     let option = StatementOption::Currency(CURRENCY_DEFAULT.0.to_string());
                     handle_spendings_with_option(
+                        &redis,
                         bot,
                         dialogue,
                         msg,
@@ -263,6 +695,7 @@ pub async fn action_spendings_menu(
                 _ if button.as_str().len() == 3 => {
                     let option = StatementOption::Currency(button.as_str().to_string());
                     handle_spendings_with_option(
+                        &redis,
                         bot,
                         dialogue,
                         msg,
@@ -0,0 +1,383 @@
+use teloxide::{
+    payloads::AnswerCallbackQuerySetters,
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message},
+};
+
+use crate::bot::{
+    currency::Currency,
+    handler::{
+        approval::{
+            display_approval_request, display_dual_entry_request, get_approvers,
+            make_keyboard_approval, make_keyboard_dual_entry,
+        },
+        limit::notify_spending_limits,
+        notification::notify_added_users,
+        utils::{
+            assert_handle_request_limit, display_currency_amount, display_username,
+            display_usernames, edit_bot_message, get_currency, parse_total_amount, parse_username,
+            process_debts_equal, retrieve_allowed_currencies, retrieve_currency_format,
+            retrieve_language, retrieve_number_format, retrieve_rounding, send_bot_message, t,
+            use_currency, validate_description, validate_payment_count, BotError, HandlerResult,
+        },
+    },
+    i18n::{
+        translate, Text, APPROVAL_NEEDED, BUTTON_UNDO, DUAL_ENTRY_NEEDED, SPLIT_ALREADY_DELETED,
+        SPLIT_DESCRIPTION_DEFAULT, SPLIT_DONE, SPLIT_INSTRUCTIONS, SPLIT_NO_OTHERS,
+        SPLIT_NO_USERNAME, SPLIT_OVERVIEW, SPLIT_UNDONE, SPLIT_UNDO_NOT_ALLOWED, UNDO_SPLIT_FAILED,
+    },
+    processor::{
+        add_payment, add_pending_payment, delete_payments, is_approval_required,
+        is_dual_entry_required, is_username_equal, retrieve_chat_users, retrieve_payment,
+    },
+    redis::Redis,
+};
+
+/* Utilities */
+const UNDO_PREFIX: &str = "Undo:";
+
+// Make keyboard to undo a quick split, with its payment ID in the callback data
+fn make_keyboard_undo(payment_id: &str, language: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
+        translate(language, BUTTON_UNDO, &[]),
+        format!("{UNDO_PREFIX}{payment_id}"),
+    )]])
+}
+
+// Parses the arguments of a quick split into its total, description, and mentioned users.
+// The currency is optional and follows the amount, while mentions can be anywhere.
+async fn parse_split_arguments(
+    redis: &Redis,
+    chat_id: &str,
+    args: &str,
+    language: &str,
+) -> Result<(String, (i64, Currency), Vec<String>), BotError> {
+    let (mentions, words): (Vec<&str>, Vec<&str>) = args
+        .split_whitespace()
+        .partition(|word| word.starts_with('@'));
+    let mut words = words.into_iter();
+    let amount = match words.next() {
+        Some(amount) => amount,
+        None => return Err(BotError::UserError(Text::new(SPLIT_INSTRUCTIONS))),
+    };
+
+    // A currency code directly after the amount is taken as its currency
+    let mut words = words.peekable();
+    let amount = match words.peek() {
+        Some(word) if word.len() == 3 && get_currency(word).is_ok() => {
+            let amount = format!("{amount} {word}");
+            words.next();
+            amount
+        }
+        _ => amount.to_string(),
+    };
+    let (total, currency, _) = parse_total_amount(
+        &amount,
+        retrieve_number_format(redis, chat_id).await,
+        &retrieve_allowed_currencies(redis, chat_id).await,
+    )
+    .map_err(|err| match err {
+        BotError::UserError(reply) => {
+            BotError::UserError(reply.then(Text::new(SPLIT_INSTRUCTIONS)))
+        }
+        err => err,
+    })?;
+
+    let description = words.collect::<Vec<&str>>().join(" ");
+    let description = if description.is_empty() {
+        translate(language, SPLIT_DESCRIPTION_DEFAULT, &[])
+    } else {
+        validate_description(&description)?
+    };
+
+    let mut users: Vec<String> = Vec::new();
+    for mention in mentions {
+        users.push(parse_username(mention)?);
+    }
+    Ok((description, (total, currency), users))
+}
+
+/* Splits a payment equally right away, without going through the dialogue.
+ * The payment is paid by the sender, and split with everyone mentioned,
+ * or with everyone known in the chat if no one is mentioned.
+ * Payments above the approval threshold are sent for approval instead.
+ */
+pub async fn action_split(redis: Redis, bot: Bot, msg: Message, args: String) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let language = retrieve_language(&redis, &chat_id).await;
+    let (sender_id, sender_username) = match msg.from() {
+        Some(user) => match user.username.as_deref().map(parse_username) {
+            Some(Ok(username)) => (user.id.to_string(), username),
+            _ => {
+                send_bot_message(&bot, &msg, translate(&language, SPLIT_NO_USERNAME, &[])).await?;
+                return Ok(());
+            }
+        },
+        None => return Ok(()),
+    };
+
+    let split = async {
+        validate_payment_count(&redis, &chat_id).await?;
+        let (description, (total, currency), mut users) =
+            parse_split_arguments(&redis, &chat_id, &args, &language).await?;
+        if users.is_empty() {
+            users = retrieve_chat_users(&redis, &chat_id).await?;
+        }
+        if !users
+            .iter()
+            .any(|user| is_username_equal(user, &sender_username))
+        {
+            users.insert(0, sender_username.clone());
+        }
+        if users.len() < 2 {
+            return Err(BotError::UserError(Text::new(SPLIT_NO_OTHERS)));
+        }
+        let debts = process_debts_equal(
+            &users.join(" "),
+            Some(total),
+            &Some(sender_username.clone()),
+            retrieve_rounding(&redis, &chat_id).await,
+        )?;
+        Ok::<_, BotError>((description, total, currency, debts))
+    }
+    .await;
+    let (description, total, currency, debts) = match split {
+        Ok(split) => split,
+        Err(BotError::UserError(err)) => {
+            send_bot_message(&bot, &msg, err.translate(&language)).await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+
+    let users: Vec<String> = debts.iter().map(|(user, _)| user.clone()).collect();
+    let overview = translate(
+        &language,
+        SPLIT_OVERVIEW,
+        &[
+            ("description", &description),
+            (
+                "amount",
+                &display_currency_amount(
+                    total,
+                    use_currency(&redis, currency.clone(), &chat_id).await,
+                    retrieve_currency_format(&redis, &chat_id).await,
+                ),
+            ),
+            (
+                "payer",
+                &display_username(&redis, &chat_id, &sender_username).await,
+            ),
+            (
+                "users",
+                &display_usernames(&redis, &chat_id, &users).await.join(", "),
+            ),
+        ],
+    );
+
+    // With dual entry verification, payments need confirmation from the payer and a debtor.
+    // Otherwise, large payments need approval from someone else involved.
+    // Either way, they only affect balances once confirmed or approved.
+    let is_dual_entry = is_dual_entry_required(&redis, &chat_id, &sender_username, &debts)
+        .await
+        .unwrap_or(false);
+    let approvers = get_approvers(&sender_username, &debts, &sender_username);
+    if is_dual_entry
+        || !approvers.is_empty()
+            && is_approval_required(&redis, &chat_id, &currency, total)
+                .await
+                .unwrap_or(false)
+    {
+        let dual_entry_request = display_dual_entry_request(&sender_username, &debts, &language);
+        let payment_id = add_pending_payment(
+            &redis,
+            chat_id.clone(),
+            sender_username.clone(),
+            sender_id.clone(),
+            msg.date.to_string(),
+            &description,
+            &sender_username,
+            &currency.0,
+            total,
+            debts,
+            false,
+            false,
+            None,
+        )
+        .await?;
+        let (request, keyboard) = if is_dual_entry {
+            (
+                format!(
+                    "{}\n\n{overview}\n\n{dual_entry_request}",
+                    translate(&language, DUAL_ENTRY_NEEDED, &[])
+                ),
+                make_keyboard_dual_entry(&payment_id, &language),
+            )
+        } else {
+            (
+                format!(
+                    "{}\n\n{overview}\n\n{}",
+                    translate(&language, APPROVAL_NEEDED, &[]),
+                    display_approval_request(&approvers, &language)
+                ),
+                make_keyboard_approval(&payment_id, &language),
+            )
+        };
+        send_bot_message(&bot, &msg, request)
+            .reply_markup(keyboard)
+            .await?;
+
+        // Logging
+        log::info!(
+            "Split - Payment {} pending {} for user {} in chat {}",
+            payment_id,
+            if is_dual_entry {
+                "confirmation"
+            } else {
+                "approval"
+            },
+            sender_id,
+            chat_id
+        );
+        return Ok(());
+    }
+
+    let (payment_id, _) = add_payment(
+        &redis,
+        chat_id.clone(),
+        sender_username.clone(),
+        sender_id.clone(),
+        msg.date.to_string(),
+        &description,
+        &sender_username,
+        &currency.0,
+        total,
+        debts.clone(),
+        false,
+        false,
+        None,
+    )
+    .await?;
+    send_bot_message(
+        &bot,
+        &msg,
+        format!("{}\n\n{overview}", translate(&language, SPLIT_DONE, &[])),
+    )
+    .reply_markup(make_keyboard_undo(&payment_id, &language))
+    .await?;
+
+    // Logging
+    log::info!(
+        "Split - Payment {} added by user {} in chat {}",
+        payment_id,
+        sender_id,
+        chat_id
+    );
+
+    notify_spending_limits(&redis, &bot, &msg).await?;
+    notify_added_users(
+        &redis,
+        &bot,
+        &msg,
+        &sender_username,
+        &description,
+        currency,
+        &debts,
+    )
+    .await;
+    Ok(())
+}
+
+// Checks if a callback query is to undo a quick split.
+// Such queries can come from anyone in the chat, regardless of the current state.
+pub fn is_undo_split_query(query: CallbackQuery) -> bool {
+    match &query.data {
+        Some(data) => data.starts_with(UNDO_PREFIX),
+        None => false,
+    }
+}
+
+/* Undoes a quick split, deleting its payment.
+ * Bot receives a callback query from the confirmation of the split.
+ * Only the payer of the split can undo it.
+ */
+pub async fn action_undo_split(redis: Redis, bot: Bot, query: CallbackQuery) -> HandlerResult {
+    let payment_id = match query
+        .data
+        .as_deref()
+        .and_then(|data| data.strip_prefix(UNDO_PREFIX))
+    {
+        Some(payment_id) => payment_id.to_string(),
+        None => return Ok(()),
+    };
+    let msg = match query.message {
+        Some(msg) => msg,
+        None => return Ok(()),
+    };
+    let chat_id = msg.chat.id.to_string();
+
+    let payment = match retrieve_payment(&redis, &payment_id).await {
+        Ok(payment) => payment,
+        Err(_) => {
+            bot.answer_callback_query(query.id)
+                .text(t(&redis, &chat_id, SPLIT_ALREADY_DELETED, &[]).await)
+                .await?;
+            bot.edit_message_reply_markup(msg.chat.id, msg.id).await?;
+            return Ok(());
+        }
+    };
+    let username = query.from.username.clone().unwrap_or_default();
+    if !is_username_equal(&username, &payment.creditor) {
+        bot.answer_callback_query(query.id)
+            .text(t(&redis, &chat_id, SPLIT_UNDO_NOT_ALLOWED, &[]).await)
+            .await?;
+        return Ok(());
+    }
+    bot.answer_callback_query(query.id).await?;
+
+    let split = msg.text().unwrap_or_default().to_string();
+    match delete_payments(&redis, &chat_id, &username, &vec![payment_id.clone()]).await {
+        Ok(_) => {
+            edit_bot_message(
+                &bot,
+                msg.chat.id,
+                msg.id,
+                format!(
+                    "{split}\n\n{}",
+                    t(&redis, &chat_id, SPLIT_UNDONE, &[]).await
+                ),
+            )
+            .await?;
+
+            // Logging
+            log::info!(
+                "Split - Payment {} undone by user {} in chat {}",
+                payment_id,
+                query.from.id,
+                chat_id
+            );
+        }
+        Err(err) => {
+            send_bot_message(
+                &bot,
+                &msg,
+                t(&redis, &chat_id, UNDO_SPLIT_FAILED, &[]).await,
+            )
+            .await?;
+
+            // Logging
+            log::error!(
+                "Split - Failed to undo payment {} in chat {}: {}",
+                payment_id,
+                chat_id,
+                err.to_string()
+            );
+        }
+    }
+
+    Ok(())
+}
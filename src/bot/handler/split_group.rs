@@ -0,0 +1,167 @@
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    handler::{
+        constants::{SPLIT_GROUPS_MAX, SPLIT_GROUP_NAME_MAX_LENGTH},
+        utils::{
+            assert_handle_request_limit, display_usernames, parse_username, retrieve_language,
+            send_bot_message, validate_debtors, BotError, HandlerResult,
+        },
+    },
+    i18n::{
+        translate, Text, COMMAND_UNKNOWN, SPLIT_GROUPS_FOOTER, SPLIT_GROUPS_FULL,
+        SPLIT_GROUPS_HEADER, SPLIT_GROUPS_NONE, SPLIT_GROUP_DELETED, SPLIT_GROUP_INSTRUCTIONS,
+        SPLIT_GROUP_MEMBERS_MISSING, SPLIT_GROUP_NAME_INVALID, SPLIT_GROUP_NAME_TOO_LONG,
+        SPLIT_GROUP_NOT_FOUND, SPLIT_GROUP_SAVED,
+    },
+    processor::{remove_split_group, retrieve_split_groups, save_split_group},
+    redis::Redis,
+};
+
+/* Utilities */
+// Displays all split groups of a chat, with their members
+async fn display_split_groups(
+    redis: &Redis,
+    chat_id: &str,
+    language: &str,
+) -> Result<String, BotError> {
+    let groups = retrieve_split_groups(redis, chat_id).await?;
+    if groups.is_empty() {
+        return Ok(Text::new(SPLIT_GROUPS_NONE)
+            .then(Text::new(SPLIT_GROUP_INSTRUCTIONS))
+            .translate(language));
+    }
+
+    let mut message = String::new();
+    for (name, members) in groups {
+        message.push_str(&format!(
+            "👥 {name}: {}\n",
+            display_usernames(redis, chat_id, &members).await.join(", ")
+        ));
+    }
+    Ok(format!(
+        "{}\n\n{message}\n{}",
+        translate(language, SPLIT_GROUPS_HEADER, &[]),
+        translate(language, SPLIT_GROUPS_FOOTER, &[])
+    ))
+}
+
+// Parses the name of a split group, which is shown on its button
+fn parse_group_name(text: &str) -> Result<String, BotError> {
+    let name = text.trim().trim_start_matches('@').to_lowercase();
+    if name.is_empty() || name.chars().count() > SPLIT_GROUP_NAME_MAX_LENGTH {
+        return Err(BotError::UserError(
+            Text::new(SPLIT_GROUP_NAME_TOO_LONG).arg("max", SPLIT_GROUP_NAME_MAX_LENGTH),
+        ));
+    }
+
+    // Names are typed in place of usernames when splitting, so they must look like one word
+    let is_valid = name.starts_with(|c: char| c.is_alphabetic())
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if !is_valid {
+        return Err(BotError::UserError(Text::new(SPLIT_GROUP_NAME_INVALID)));
+    }
+    Ok(name)
+}
+
+// Parses the members of a split group, without duplicates
+fn parse_group_members(text: &str) -> Result<Vec<String>, BotError> {
+    let mut members: Vec<String> = Vec::new();
+    for user in text.split_whitespace() {
+        let username = parse_username(user)?;
+        if !members
+            .iter()
+            .any(|member| member.eq_ignore_ascii_case(&username))
+        {
+            members.push(username);
+        }
+    }
+    if members.is_empty() {
+        return Err(BotError::UserError(Text::new(SPLIT_GROUP_MEMBERS_MISSING)));
+    }
+    let debts: Vec<(String, i64)> = members.iter().map(|user| (user.clone(), 0)).collect();
+    validate_debtors(&debts)?;
+    Ok(members)
+}
+
+/* Creates, deletes, or views the split groups of a group chat.
+ * A split group is a named group of people that often split payments together,
+ * offered as a one-tap option or typed by name when splitting a payment equally.
+ */
+pub async fn action_split_group(
+    redis: Redis,
+    bot: Bot,
+    msg: Message,
+    args: String,
+) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let args = args.trim();
+    let (command, rest) = match args.split_once(char::is_whitespace) {
+        Some((command, rest)) => (command.to_lowercase(), rest.trim()),
+        None => (args.to_lowercase(), ""),
+    };
+
+    let language = retrieve_language(&redis, &chat_id).await;
+    let reply = match command.as_str() {
+        "" | "list" => display_split_groups(&redis, &chat_id, &language).await?,
+        "save" | "create" => {
+            let (name, members) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let parsed = parse_group_name(name)
+                .and_then(|name| parse_group_members(members).map(|members| (name, members)));
+            match parsed {
+                Ok((name, members)) => {
+                    let groups = retrieve_split_groups(&redis, &chat_id).await?;
+                    if groups.len() >= SPLIT_GROUPS_MAX
+                        && !groups.iter().any(|(group, _)| *group == name)
+                    {
+                        translate(
+                            &language,
+                            SPLIT_GROUPS_FULL,
+                            &[("max", &SPLIT_GROUPS_MAX.to_string())],
+                        )
+                    } else {
+                        save_split_group(&redis, &chat_id, &name, &members).await?;
+
+                        // Logging
+                        log::info!("Split Group - Group {} saved in chat {}", name, chat_id);
+
+                        let members = display_usernames(&redis, &chat_id, &members)
+                            .await
+                            .join(", ");
+                        translate(
+                            &language,
+                            SPLIT_GROUP_SAVED,
+                            &[("name", &name), ("members", &members)],
+                        )
+                    }
+                }
+                Err(BotError::UserError(reply)) => reply.translate(&language),
+                Err(err) => return Err(err),
+            }
+        }
+        "delete" => match parse_group_name(rest) {
+            Ok(name) => {
+                if remove_split_group(&redis, &chat_id, &name).await? {
+                    // Logging
+                    log::info!("Split Group - Group {} deleted in chat {}", name, chat_id);
+
+                    translate(&language, SPLIT_GROUP_DELETED, &[("name", &name)])
+                } else {
+                    translate(&language, SPLIT_GROUP_NOT_FOUND, &[("name", &name)])
+                }
+            }
+            Err(BotError::UserError(reply)) => reply.translate(&language),
+            Err(err) => return Err(err),
+        },
+        _ => Text::new(COMMAND_UNKNOWN)
+            .then(Text::new(SPLIT_GROUP_INSTRUCTIONS))
+            .translate(&language),
+    };
+
+    send_bot_message(&bot, &msg, reply).await?;
+    Ok(())
+}
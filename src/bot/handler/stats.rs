@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    currency::CURRENCY_DEFAULT,
+    handler::utils::{
+        assert_handle_request_limit, display_amount, display_name, get_currency, retrieve_language,
+        retrieve_time_zone, send_bot_message, HandlerResult,
+    },
+    i18n::{
+        translate, MONTH_FORMAT, STATS_CHANGE_DOWN, STATS_CHANGE_SAME, STATS_CHANGE_UP,
+        STATS_HEADER_CURRENCY, STATS_MONTH, STATS_MONTH_ONE, STATS_NONE, STATS_SUMMARY,
+    },
+    processor::{retrieve_display_names, retrieve_monthly_stats, MonthlyStats},
+    redis::Redis,
+};
+
+/* Utilities */
+const STATS_MONTHS_MAX: usize = 6;
+
+// Displays the month of the stats, such as "October 2026"
+fn display_month(month: &str, language: &str) -> String {
+    match NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d") {
+        Ok(date) => date
+            .format(&translate(language, MONTH_FORMAT, &[]))
+            .to_string(),
+        Err(_) => month.to_string(),
+    }
+}
+
+// Displays the month-over-month change of the total spent
+fn display_change(change: Option<f64>, language: &str) -> String {
+    match change {
+        Some(change) if change > 0.0 => translate(
+            language,
+            STATS_CHANGE_UP,
+            &[("change", &format!("{change:.0}"))],
+        ),
+        Some(change) if change < 0.0 => translate(
+            language,
+            STATS_CHANGE_DOWN,
+            &[("change", &format!("{:.0}", change.abs()))],
+        ),
+        Some(_) => translate(language, STATS_CHANGE_SAME, &[]),
+        None => "".to_string(),
+    }
+}
+
+fn display_monthly_stats(
+    stats: &MonthlyStats,
+    names: &HashMap<String, String>,
+    language: &str,
+) -> String {
+    let decimal_places = match get_currency(&stats.currency) {
+        Ok(currency) => currency.1,
+        // Should not occur. Currency string is from database, so should exist.
+        Err(_) => CURRENCY_DEFAULT.1,
+    };
+    let header = if stats.currency == CURRENCY_DEFAULT.0 {
+        display_month(&stats.month, language)
+    } else {
+        translate(
+            language,
+            STATS_HEADER_CURRENCY,
+            &[
+                ("month", &display_month(&stats.month, language)),
+                ("currency", &stats.currency),
+            ],
+        )
+    };
+    let key = if stats.count == 1 {
+        STATS_MONTH_ONE
+    } else {
+        STATS_MONTH
+    };
+
+    translate(
+        language,
+        key,
+        &[
+            ("header", &header),
+            ("total", &display_amount(stats.total, decimal_places)),
+            ("change", &display_change(stats.change, language)),
+            ("spender", &display_name(&stats.top_spender.username, names)),
+            (
+                "spending",
+                &display_amount(stats.top_spender.spending, decimal_places),
+            ),
+            ("expense", &stats.largest_expense.0),
+            (
+                "expense_amount",
+                &display_amount(stats.largest_expense.1, decimal_places),
+            ),
+            (
+                "average",
+                &display_amount(stats.total / stats.count as i64, decimal_places),
+            ),
+            ("count", &stats.count.to_string()),
+        ],
+    )
+}
+
+/* Views the monthly stats of a group chat.
+ * Shows the most recent months, with the latest month first.
+ */
+pub async fn action_stats(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let time_zone = retrieve_time_zone(&redis, &chat_id).await;
+    let language = retrieve_language(&redis, &chat_id).await;
+    let stats = retrieve_monthly_stats(&redis, &chat_id, time_zone).await?;
+
+    let mut months: Vec<&str> = Vec::new();
+    for data in stats.iter().rev() {
+        if !months.contains(&data.month.as_str()) {
+            months.push(&data.month);
+        }
+    }
+    months.truncate(STATS_MONTHS_MAX);
+
+    let reply = if months.is_empty() {
+        translate(&language, STATS_NONE, &[])
+    } else {
+        let names = retrieve_display_names(&redis, &chat_id)
+            .await
+            .unwrap_or_default();
+        let mut monthly_stats = String::new();
+        for month in months {
+            for data in stats.iter().filter(|data| data.month == month) {
+                monthly_stats.push_str(&display_monthly_stats(data, &names, &language));
+                monthly_stats.push('\n');
+            }
+        }
+        translate(
+            &language,
+            STATS_SUMMARY,
+            &[("stats", monthly_stats.trim_end())],
+        )
+    };
+
+    // Logging
+    log::info!("Stats - Stats viewed in chat {}", chat_id);
+
+    send_bot_message(&bot, &msg, reply).await?;
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use teloxide::{prelude::*, types::Message};
+
+use crate::bot::{
+    handler::{
+        add_payment::action_add_payment_template,
+        utils::{assert_handle_request_limit, send_bot_message, HandlerResult, UserDialogue},
+    },
+    processor::{
+        remove_payment_template, retrieve_payment_template, retrieve_payment_templates,
+        save_payment_template,
+    },
+    redis::Redis,
+};
+
+/* Utilities */
+const TEMPLATE_INSTRUCTIONS_MESSAGE: &str = "Here's what you can do with templates:\n/template save <name> - Save the last payment as a template\n/template use <name> - Add a new payment from a template\n/template delete <name> - Delete a template\n\nWithout anything else, I'll show all saved templates!";
+
+// Displays the names of all templates of a chat
+async fn display_templates(redis: &Redis, chat_id: &str) -> String {
+    match retrieve_payment_templates(redis, chat_id).await {
+        Ok(names) if !names.is_empty() => format!(
+            "⭐ Here are the saved templates!\n\n{}\n\nAdd a payment from one with /template use <name>!",
+            names.join("\n")
+        ),
+        _ => format!("There are no templates saved yet!\n\n{TEMPLATE_INSTRUCTIONS_MESSAGE}"),
+    }
+}
+
+/* Saves, uses, or views payment templates in a group chat.
+ * A template keeps the description, payer, total, and split of a payment,
+ * so that frequent expenses can be added again without filling them in.
+ */
+pub async fn action_template(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: Message,
+    args: String,
+) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let args = args.trim();
+    let (command, name) = match args.split_once(char::is_whitespace) {
+        Some((command, name)) => (command.to_lowercase(), name.trim().to_lowercase()),
+        None => (args.to_lowercase(), String::new()),
+    };
+
+    if !command.is_empty() && command != "list" && name.is_empty() {
+        let reply = format!(
+            "🤔 What's the name of the template? Let me know like this: /template {command} groceries"
+        );
+        send_bot_message(&bot, &msg, reply).await?;
+        return Ok(());
+    }
+
+    let reply = match command.as_str() {
+        "" | "list" => display_templates(&redis, &chat_id).await,
+        "save" => match save_payment_template(&redis, &chat_id, &name).await? {
+            Some(template) => {
+                // Logging
+                log::info!("Template - Template {} saved in chat {}", name, chat_id);
+
+                format!(
+                    "⭐ Saved! The payment \"{}\" is now the template {name}.\n\nAdd it again anytime with /template use {name}!",
+                    template.description
+                )
+            }
+            None => {
+                "❌ There are no payments to save yet! Add one with /addpayment first.".to_string()
+            }
+        },
+        "use" => match retrieve_payment_template(&redis, &chat_id, &name).await? {
+            Some(template) => {
+                action_add_payment_template(&redis, &bot, &dialogue, &msg, template).await?;
+
+                // Logging
+                log::info!("Template - Template {} used in chat {}", name, chat_id);
+
+                return Ok(());
+            }
+            None => format!("🥺 Sorry, I can't find the template {name}!"),
+        },
+        "delete" => {
+            if remove_payment_template(&redis, &chat_id, &name).await? {
+                // Logging
+                log::info!("Template - Template {} deleted in chat {}", name, chat_id);
+
+                format!("🗑️ The template {name} has been deleted!")
+            } else {
+                format!("🥺 Sorry, I can't find the template {name}!")
+            }
+        }
+        _ => format!("🥺 Sorry, I don't know that!\n\n{TEMPLATE_INSTRUCTIONS_MESSAGE}"),
+    };
+
+    send_bot_message(&bot, &msg, reply).await?;
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use chrono_tz::Tz;
+use teloxide::{
+    payloads::{AnswerCallbackQuerySetters, SendMessageSetters},
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message},
+};
+
+use crate::bot::{
+    currency::get_default_currency,
+    handler::utils::{
+        assert_handle_request_limit, display_balance_header, display_balances,
+        display_currency_amount, display_payment_date, display_username, edit_bot_message,
+        get_currency, is_user_allowed, reformat_datetime, retrieve_currency_format,
+        retrieve_time_zone, send_bot_message, t, use_currency, HandlerResult,
+    },
+    i18n::{
+        TRASH_EMPTY, TRASH_ENTRY, TRASH_LIST, TRASH_RESTORED, TRASH_RESTORE_GONE,
+        TRASH_RESTORE_NOT_ALLOWED,
+    },
+    processor::{restore_payment, retrieve_trash, PRIVATE_DESCRIPTION, TRASH_RETENTION_DAYS},
+    redis::{Redis, TrashEntry},
+};
+
+/* Utilities */
+const TRASH_DISPLAY_COUNT: usize = 10;
+const TRASH_RESTORE_PREFIX: &str = "TrashRestore:";
+
+// Checks if a callback query restores a payment from the trash.
+// Such queries come from the trash message, regardless of the current state.
+pub fn is_trash_restore_query(query: CallbackQuery) -> bool {
+    match &query.data {
+        Some(data) => data.starts_with(TRASH_RESTORE_PREFIX),
+        None => false,
+    }
+}
+
+// Make trash keyboard, with a button to restore each payment shown
+fn make_keyboard_trash(entries: &[TrashEntry]) -> InlineKeyboardMarkup {
+    let buttons: Vec<InlineKeyboardButton> = entries
+        .iter()
+        .take(TRASH_DISPLAY_COUNT)
+        .enumerate()
+        .map(|(index, entry)| {
+            InlineKeyboardButton::callback(
+                format!("♻️ {}", index + 1),
+                format!("{TRASH_RESTORE_PREFIX}{}", entry.payment_id),
+            )
+        })
+        .collect();
+    InlineKeyboardMarkup::new(buttons.chunks(5).map(|row| row.to_vec()))
+}
+
+// Displays a payment in the trash, with its total and when it was deleted
+async fn display_trash_entry(
+    redis: &Redis,
+    chat_id: &str,
+    entry: &TrashEntry,
+    serial_num: usize,
+    time_zone: Tz,
+) -> String {
+    let payment = &entry.payment;
+    let description = if entry.is_private {
+        PRIVATE_DESCRIPTION
+    } else {
+        &payment.description
+    };
+    let currency = get_currency(&payment.currency).unwrap_or(get_default_currency());
+    let currency = use_currency(redis, currency, chat_id).await;
+    t(
+        redis,
+        chat_id,
+        TRASH_ENTRY,
+        &[
+            ("number", &serial_num.to_string()),
+            ("description", description),
+            ("date", &display_payment_date(&payment.datetime, time_zone)),
+            (
+                "total",
+                &display_currency_amount(
+                    payment.total,
+                    currency,
+                    retrieve_currency_format(redis, chat_id).await,
+                ),
+            ),
+            (
+                "payer",
+                &display_username(redis, chat_id, &payment.creditor).await,
+            ),
+            (
+                "deleted",
+                reformat_datetime(&entry.deleted, time_zone).trim(),
+            ),
+        ],
+    )
+    .await
+}
+
+// Displays the payments in the trash of a chat, from the latest deleted
+async fn display_trash(redis: &Redis, chat_id: &str, entries: &[TrashEntry]) -> String {
+    if entries.is_empty() {
+        return t(
+            redis,
+            chat_id,
+            TRASH_EMPTY,
+            &[("days", &TRASH_RETENTION_DAYS.to_string())],
+        )
+        .await;
+    }
+
+    let time_zone = retrieve_time_zone(redis, chat_id).await;
+    let mut lines: Vec<String> = Vec::new();
+    for (index, entry) in entries.iter().take(TRASH_DISPLAY_COUNT).enumerate() {
+        lines.push(display_trash_entry(redis, chat_id, entry, index + 1, time_zone).await);
+    }
+    t(
+        redis,
+        chat_id,
+        TRASH_LIST,
+        &[
+            ("days", &TRASH_RETENTION_DAYS.to_string()),
+            ("payments", &lines.join("\n\n")),
+        ],
+    )
+    .await
+}
+
+/* Views the payments in the trash of a group chat.
+ * Shows the latest deleted payments, with a button to restore each of them.
+ */
+pub async fn action_trash(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let entries = retrieve_trash(&redis, &chat_id).await?;
+    let text = display_trash(&redis, &chat_id, &entries).await;
+    if entries.is_empty() {
+        send_bot_message(&bot, &msg, text).await?;
+    } else {
+        send_bot_message(&bot, &msg, text)
+            .reply_markup(make_keyboard_trash(&entries))
+            .await?;
+    }
+
+    // Logging
+    log::info!("Trash - Trash viewed in chat {}", chat_id);
+
+    Ok(())
+}
+
+/* Restores a payment from the trash of a group chat.
+ * Bot receives a callback query from the trash message, and restores the payment,
+ * reapplying it to the balances, if the user may edit payments.
+ * The trash message is then updated with the payments left.
+ */
+pub async fn action_restore_trash(redis: Redis, bot: Bot, query: CallbackQuery) -> HandlerResult {
+    let payment_id = match query
+        .data
+        .as_deref()
+        .and_then(|data| data.strip_prefix(TRASH_RESTORE_PREFIX))
+    {
+        Some(payment_id) => payment_id.to_string(),
+        None => return Ok(()),
+    };
+    let msg = match &query.message {
+        Some(msg) => msg.clone(),
+        None => return Ok(()),
+    };
+    let chat_id = msg.chat.id.to_string();
+
+    if !is_user_allowed(&redis, &bot, msg.chat.id, query.from.id).await? {
+        bot.answer_callback_query(query.id)
+            .text(t(&redis, &chat_id, TRASH_RESTORE_NOT_ALLOWED, &[]).await)
+            .await?;
+        return Ok(());
+    }
+
+    // Private payments are restored without showing their description
+    let description = retrieve_trash(&redis, &chat_id)
+        .await?
+        .into_iter()
+        .find(|entry| entry.payment_id == payment_id)
+        .map(|entry| {
+            if entry.is_private {
+                PRIVATE_DESCRIPTION.to_string()
+            } else {
+                entry.payment.description
+            }
+        })
+        .unwrap_or_default();
+    let username = query.from.username.clone().unwrap_or_default();
+    let balances = match restore_payment(&redis, &chat_id, &username, &payment_id).await? {
+        Some((_, balances)) => balances,
+        None => {
+            bot.answer_callback_query(query.id)
+                .text(t(&redis, &chat_id, TRASH_RESTORE_GONE, &[]).await)
+                .await?;
+            return Ok(());
+        }
+    };
+    bot.answer_callback_query(query.id).await?;
+
+    let mut details = t(
+        &redis,
+        &chat_id,
+        TRASH_RESTORED,
+        &[("description", &description)],
+    )
+    .await;
+    for (currency, debts) in balances {
+        details.push_str(&format!(
+            "\n\n{}{}",
+            display_balance_header(&redis, &chat_id, &currency).await,
+            display_balances(&redis, &chat_id, &debts).await,
+        ));
+    }
+    send_bot_message(&bot, &msg, details).await?;
+
+    // Updates the trash message, so that the restored payment is no longer shown
+    let entries = retrieve_trash(&redis, &chat_id).await?;
+    let text = display_trash(&redis, &chat_id, &entries).await;
+    if entries.is_empty() {
+        edit_bot_message(&bot, msg.chat.id, msg.id, text).await?;
+    } else {
+        edit_bot_message(&bot, msg.chat.id, msg.id, text)
+            .reply_markup(make_keyboard_trash(&entries))
+            .await?;
+    }
+
+    // Logging
+    log::info!(
+        "Trash - Payment {} restored by user {} in chat {}",
+        payment_id,
+        query.from.id,
+        chat_id
+    );
+
+    Ok(())
+}
@@ -0,0 +1,366 @@
+use chrono::Utc;
+use teloxide::{
+    payloads::PinChatMessageSetters,
+    prelude::*,
+    types::{InputFile, Message},
+};
+
+use crate::bot::{
+    currency::{get_default_currency, CURRENCY_DEFAULT},
+    export::render_csv,
+    handler::{
+        spendings::display_spendings,
+        utils::{
+            assert_handle_request_limit, display_amount, display_balances, display_currency_amount,
+            display_description, get_currency, get_statement_options, reformat_datetime,
+            retrieve_currency_format, retrieve_language, retrieve_time_zone, send_bot_document,
+            send_bot_message, t, use_currency, HandlerResult,
+        },
+        view_payments::unfold_payment,
+    },
+    i18n::{
+        translate, IN_CURRENCY, SPENDINGS_GROUP_TOTAL, TRIP_ALL, TRIP_ALREADY_STARTED,
+        TRIP_BALANCES_HEADER, TRIP_CSV_CAPTION, TRIP_CURRENT, TRIP_ENDED, TRIP_EXPENSES_NONE,
+        TRIP_INSTRUCTIONS, TRIP_NAME_MISSING, TRIP_NONE, TRIP_NOT_FOUND, TRIP_NOT_ON, TRIP_REPORT,
+        TRIP_SPENDINGS_HEADER, TRIP_STARTED, TRIP_STILL_ON, TRIP_UNKNOWN, TRIP_WHICH,
+    },
+    processor::{
+        end_chat_trip, retrieve_active_trip, retrieve_trip_debts, retrieve_trip_largest_expenses,
+        retrieve_trip_payments, retrieve_trip_spending_data, retrieve_trip_valid_currencies,
+        retrieve_trips, start_chat_trip, ProcessError, StatementOption,
+    },
+    redis::{Redis, Trip},
+};
+
+/* Utilities */
+const TRIP_EXPORT_CSV: &str = "csv";
+const TRIP_CSV_HEADER: [&str; 6] = [
+    "Date",
+    "Description",
+    "Paid By",
+    "Currency",
+    "Amount",
+    "Split",
+];
+
+// Finds a trip by name, or the active trip if no name is given
+async fn find_trip(redis: &Redis, chat_id: &str, name: &str) -> Result<Option<Trip>, ProcessError> {
+    if name.is_empty() {
+        return retrieve_active_trip(redis, chat_id).await;
+    }
+
+    let trips = retrieve_trips(redis, chat_id).await?;
+    Ok(trips
+        .into_iter()
+        .find(|(_, trip_name)| trip_name.to_lowercase() == name.to_lowercase()))
+}
+
+// Composes the balances of a trip, being the plan to settle up in each currency
+async fn compose_trip_balances(
+    redis: &Redis,
+    chat_id: &str,
+    trip: &Trip,
+) -> Result<String, ProcessError> {
+    let currencies = retrieve_trip_valid_currencies(redis, chat_id, &trip.0).await?;
+    let options = get_statement_options(redis, chat_id, currencies).await;
+    let multiple = options.len() > 1;
+
+    let mut balances = String::new();
+    for option in options {
+        let debts = retrieve_trip_debts(redis, chat_id, &trip.0, option.clone()).await?;
+        if debts.is_empty() {
+            continue;
+        }
+        if multiple {
+            if let StatementOption::Currency(currency) = &option {
+                balances.push_str(&t(redis, chat_id, IN_CURRENCY, &[("currency", currency)]).await);
+                balances.push('\n');
+            }
+        }
+        balances.push_str(&display_balances(redis, chat_id, &debts).await);
+        if multiple {
+            balances.push('\n');
+        }
+    }
+    if balances.is_empty() {
+        balances = display_balances(redis, chat_id, &Vec::new()).await;
+    }
+
+    Ok(balances.trim_end().to_string())
+}
+
+// Composes the balances of a trip
+async fn display_trip_balances(
+    redis: &Redis,
+    chat_id: &str,
+    trip: &Trip,
+) -> Result<String, ProcessError> {
+    Ok(format!(
+        "{}\n\n{}",
+        t(redis, chat_id, TRIP_BALANCES_HEADER, &[("trip", &trip.1)]).await,
+        compose_trip_balances(redis, chat_id, trip).await?
+    ))
+}
+
+// Composes the total and individual spendings of a trip in each currency
+async fn compose_trip_spendings(
+    redis: &Redis,
+    chat_id: &str,
+    trip: &Trip,
+) -> Result<String, ProcessError> {
+    let currencies = retrieve_trip_valid_currencies(redis, chat_id, &trip.0).await?;
+    let options = get_statement_options(redis, chat_id, currencies).await;
+    let multiple = options.len() > 1;
+
+    let mut spendings = String::new();
+    for option in options {
+        let data = retrieve_trip_spending_data(redis, chat_id, &trip.0, option).await?;
+        if data.group_spending == 0 && multiple {
+            continue;
+        }
+        if multiple || data.currency != CURRENCY_DEFAULT.0 {
+            spendings
+                .push_str(&t(redis, chat_id, IN_CURRENCY, &[("currency", &data.currency)]).await);
+            spendings.push('\n');
+        }
+        spendings.push_str(&display_spendings(redis, chat_id, &data).await);
+        spendings.push('\n');
+    }
+    if spendings.is_empty() {
+        spendings = t(redis, chat_id, SPENDINGS_GROUP_TOTAL, &[("amount", "0")]).await;
+    }
+
+    Ok(spendings.trim_end().to_string())
+}
+
+// Composes the spendings of a trip
+async fn display_trip_spendings(
+    redis: &Redis,
+    chat_id: &str,
+    trip: &Trip,
+) -> Result<String, ProcessError> {
+    Ok(format!(
+        "{}\n\n{}",
+        t(redis, chat_id, TRIP_SPENDINGS_HEADER, &[("trip", &trip.1)]).await,
+        compose_trip_spendings(redis, chat_id, trip).await?
+    ))
+}
+
+// Composes the largest expense of a trip in each currency
+async fn compose_trip_largest_expenses(
+    redis: &Redis,
+    chat_id: &str,
+    trip: &Trip,
+) -> Result<String, ProcessError> {
+    let expenses = retrieve_trip_largest_expenses(redis, chat_id, &trip.0).await?;
+    if expenses.is_empty() {
+        return Ok(t(redis, chat_id, TRIP_EXPENSES_NONE, &[]).await);
+    }
+
+    let currency_format = retrieve_currency_format(redis, chat_id).await;
+    let mut largest_expenses: Vec<String> = Vec::new();
+    for (currency, description, total) in expenses {
+        let currency = get_currency(&currency).unwrap_or(get_default_currency());
+        let currency = use_currency(redis, currency, chat_id).await;
+        largest_expenses.push(format!(
+            "{description}: {}",
+            display_currency_amount(total, currency, currency_format)
+        ));
+    }
+    Ok(largest_expenses.join("\n"))
+}
+
+// Composes the report of a trip, sent when it ends
+async fn display_trip_report(
+    redis: &Redis,
+    chat_id: &str,
+    trip: &Trip,
+) -> Result<String, ProcessError> {
+    let spendings = compose_trip_spendings(redis, chat_id, trip).await?;
+    let expenses = compose_trip_largest_expenses(redis, chat_id, trip).await?;
+    let balances = compose_trip_balances(redis, chat_id, trip).await?;
+    Ok(t(
+        redis,
+        chat_id,
+        TRIP_REPORT,
+        &[
+            ("trip", &trip.1),
+            ("spendings", &spendings),
+            ("expenses", &expenses),
+            ("balances", &balances),
+        ],
+    )
+    .await)
+}
+
+// Pins the report of a trip, which needs the bot to be allowed to pin messages
+// The report is still sent if it cannot be pinned
+async fn pin_trip_report(bot: &Bot, msg: &Message) {
+    if let Err(err) = bot
+        .pin_chat_message(msg.chat.id, msg.id)
+        .disable_notification(true)
+        .await
+    {
+        log::warn!(
+            "Trip - Failed to pin trip report in chat {}: {}",
+            msg.chat.id,
+            err.to_string()
+        );
+    }
+}
+
+// Sends all payments of a trip as a CSV file, from the earliest
+async fn send_trip_csv(redis: &Redis, bot: &Bot, msg: &Message, trip: &Trip) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    let time_zone = retrieve_time_zone(redis, &chat_id).await;
+    let language = retrieve_language(redis, &chat_id).await;
+    let payments = retrieve_trip_payments(redis, &chat_id, &trip.0).await?;
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for payment in payments.into_iter().rev().map(unfold_payment) {
+        let currency = use_currency(redis, payment.currency.clone(), &chat_id).await;
+        let split = payment
+            .debts
+            .iter()
+            .map(|(username, amount)| {
+                format!("@{username}: {}", display_amount(*amount, currency.1))
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+        rows.push(vec![
+            reformat_datetime(&payment.datetime, time_zone)
+                .trim()
+                .to_string(),
+            display_description(&payment, &language),
+            format!("@{}", payment.creditor),
+            currency.0.clone(),
+            display_amount(payment.total, currency.1),
+            split,
+        ]);
+    }
+
+    let file_name = format!(
+        "trip_{}_{}.csv",
+        trip.1.replace(|c: char| !c.is_alphanumeric(), "_"),
+        Utc::now().with_timezone(&time_zone).format("%Y-%m-%d")
+    );
+    let caption = translate(&language, TRIP_CSV_CAPTION, &[("trip", &trip.1)]);
+    let document = InputFile::memory(render_csv(&TRIP_CSV_HEADER, &rows)).file_name(file_name);
+    send_bot_document(bot, msg, document)
+        .caption(caption)
+        .await?;
+
+    Ok(())
+}
+
+// Composes the current trip and all past trips of a chat
+async fn display_trips(redis: &Redis, chat_id: &str) -> Result<String, ProcessError> {
+    let current = match retrieve_active_trip(redis, chat_id).await? {
+        Some(trip) => t(redis, chat_id, TRIP_CURRENT, &[("trip", &trip.1)]).await,
+        None => t(redis, chat_id, TRIP_NONE, &[]).await,
+    };
+
+    let trips = retrieve_trips(redis, chat_id).await?;
+    let all_trips = if trips.is_empty() {
+        "".to_string()
+    } else {
+        let names = trips
+            .iter()
+            .map(|(_, name)| name.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ");
+        format!(
+            "\n\n{}",
+            t(redis, chat_id, TRIP_ALL, &[("trips", &names)]).await
+        )
+    };
+
+    Ok(format!(
+        "{current}{all_trips}\n\n{}",
+        t(redis, chat_id, TRIP_INSTRUCTIONS, &[]).await
+    ))
+}
+
+/* Starts, ends, or views trips in a group chat.
+ * Payments added during a trip are tracked under it, as well as overall.
+ */
+pub async fn action_trip(redis: Redis, bot: Bot, msg: Message, args: String) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let chat_id = msg.chat.id.to_string();
+    let args = args.trim();
+    let (command, name) = match args.split_once(char::is_whitespace) {
+        Some((command, name)) => (command.to_lowercase(), name.trim()),
+        None => (args.to_lowercase(), ""),
+    };
+
+    let reply = match command.as_str() {
+        "" => display_trips(&redis, &chat_id).await?,
+        "start" => {
+            if name.is_empty() {
+                t(&redis, &chat_id, TRIP_NAME_MISSING, &[]).await
+            } else {
+                match retrieve_active_trip(&redis, &chat_id).await? {
+                    Some(trip) if trip.1.to_lowercase() == name.to_lowercase() => {
+                        t(&redis, &chat_id, TRIP_ALREADY_STARTED, &[("trip", &trip.1)]).await
+                    }
+                    Some(trip) => t(&redis, &chat_id, TRIP_STILL_ON, &[("trip", &trip.1)]).await,
+                    None => {
+                        let trip = start_chat_trip(&redis, &chat_id, name).await?;
+
+                        // Logging
+                        log::info!("Trip - Trip {} started in chat {}", trip.1, chat_id);
+
+                        t(&redis, &chat_id, TRIP_STARTED, &[("trip", &trip.1)]).await
+                    }
+                }
+            }
+        }
+        "end" => match end_chat_trip(&redis, &chat_id).await? {
+            Some(trip) => {
+                // Logging
+                log::info!("Trip - Trip {} ended in chat {}", trip.1, chat_id);
+
+                let report = display_trip_report(&redis, &chat_id, &trip).await?;
+                let report = send_bot_message(
+                    &bot,
+                    &msg,
+                    t(
+                        &redis,
+                        &chat_id,
+                        TRIP_ENDED,
+                        &[("trip", &trip.1), ("report", &report)],
+                    )
+                    .await,
+                )
+                .await?;
+                pin_trip_report(&bot, &report).await;
+                if name.eq_ignore_ascii_case(TRIP_EXPORT_CSV) {
+                    send_trip_csv(&redis, &bot, &msg, &trip).await?;
+                }
+                return Ok(());
+            }
+            None => t(&redis, &chat_id, TRIP_NOT_ON, &[]).await,
+        },
+        "balances" | "spendings" => match find_trip(&redis, &chat_id, name).await? {
+            Some(trip) if command == "balances" => {
+                display_trip_balances(&redis, &chat_id, &trip).await?
+            }
+            Some(trip) => display_trip_spendings(&redis, &chat_id, &trip).await?,
+            None if name.is_empty() => {
+                t(&redis, &chat_id, TRIP_WHICH, &[("command", &command)]).await
+            }
+            None => t(&redis, &chat_id, TRIP_NOT_FOUND, &[("trip", name)]).await,
+        },
+        _ => format!(
+            "{}\n\n{}",
+            t(&redis, &chat_id, TRIP_UNKNOWN, &[]).await,
+            t(&redis, &chat_id, TRIP_INSTRUCTIONS, &[]).await
+        ),
+    };
+
+    send_bot_message(&bot, &msg, reply).await?;
+    Ok(())
+}
@@ -0,0 +1,265 @@
+use teloxide::{
+    payloads::{AnswerCallbackQuerySetters, SendMessageSetters},
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message, User},
+};
+
+use crate::bot::{
+    handler::utils::{
+        assert_handle_request_limit, display_balances, display_currency_amount, edit_bot_message,
+        get_chat_default_currency, mention_username, retrieve_currency_format, send_bot_message,
+        HandlerResult,
+    },
+    processor::{
+        add_tutorial_payback, add_tutorial_payment, clear_tutorial, retrieve_tutorial_debts,
+        ProcessError, TUTORIAL_DEBTORS,
+    },
+    redis::{Payment, Redis},
+};
+
+/* Utilities */
+const TUTORIAL_PREFIX: &str = "Tutorial:";
+
+#[derive(Clone, Copy, PartialEq)]
+enum TutorialStep {
+    Payment,
+    Balances,
+    PayBack,
+    Finish,
+    Cancel,
+}
+
+impl TutorialStep {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TutorialStep::Payment => "Payment",
+            TutorialStep::Balances => "Balances",
+            TutorialStep::PayBack => "PayBack",
+            TutorialStep::Finish => "Finish",
+            TutorialStep::Cancel => "Cancel",
+        }
+    }
+
+    fn from_str(step: &str) -> Option<TutorialStep> {
+        match step {
+            "Payment" => Some(TutorialStep::Payment),
+            "Balances" => Some(TutorialStep::Balances),
+            "PayBack" => Some(TutorialStep::PayBack),
+            "Finish" => Some(TutorialStep::Finish),
+            "Cancel" => Some(TutorialStep::Cancel),
+            _ => None,
+        }
+    }
+}
+
+// Checks if a callback query continues a tutorial.
+// Such queries come from the tutorial message, regardless of the current state.
+pub fn is_tutorial_query(query: CallbackQuery) -> bool {
+    match &query.data {
+        Some(data) => data.starts_with(TUTORIAL_PREFIX),
+        None => false,
+    }
+}
+
+// Parses the data of a tutorial query, into the step and the ID of the user who started it
+fn parse_tutorial_query(data: &str) -> Option<(TutorialStep, String)> {
+    let (step, user_id) = data.strip_prefix(TUTORIAL_PREFIX)?.split_once(':')?;
+    Some((TutorialStep::from_str(step)?, user_id.to_string()))
+}
+
+// Gets the name a user goes by in the tutorial, which is their username if they have one
+fn get_tutorial_username(user: &User) -> String {
+    user.username
+        .clone()
+        .unwrap_or_else(|| user.first_name.clone())
+}
+
+// Make tutorial keyboard, with a button to the next step, only for the user who started it
+fn make_keyboard_tutorial(
+    label: &str,
+    next_step: TutorialStep,
+    user_id: &str,
+) -> InlineKeyboardMarkup {
+    let button = |label: &str, step: TutorialStep| {
+        InlineKeyboardButton::callback(
+            label.to_string(),
+            format!("{TUTORIAL_PREFIX}{}:{user_id}", step.as_str()),
+        )
+    };
+    InlineKeyboardMarkup::new(vec![vec![
+        button(label, next_step),
+        button("Stop", TutorialStep::Cancel),
+    ]])
+}
+
+// Displays the total of a practice payment
+async fn display_tutorial_total(redis: &Redis, chat_id: &str, payment: &Payment) -> String {
+    let currency = get_chat_default_currency(redis, chat_id).await;
+    display_currency_amount(
+        payment.total,
+        currency,
+        retrieve_currency_format(redis, chat_id).await,
+    )
+}
+
+// Displays a step of the tutorial, along with the keyboard to continue, if any.
+// Each step writes to the practice ledger only, so the real balances are untouched.
+async fn display_tutorial_step(
+    redis: &Redis,
+    chat_id: &str,
+    username: &str,
+    user_id: &str,
+    step: TutorialStep,
+) -> Result<(String, Option<InlineKeyboardMarkup>), ProcessError> {
+    let debtors = TUTORIAL_DEBTORS
+        .iter()
+        .map(|debtor| mention_username(debtor))
+        .collect::<Vec<String>>()
+        .join(" and ");
+
+    match step {
+        TutorialStep::Payment => {
+            let (payment, debts) = add_tutorial_payment(redis, chat_id, username).await?;
+            let text = format!(
+                "🍕 Step 1: Adding a payment\n\nSay you paid {} for '{}', and split it equally with {debtors}. Here's what I recorded, just like /addpayment would:\n\n{}\nNext, let's see what everyone owes.",
+                display_tutorial_total(redis, chat_id, &payment).await,
+                payment.description,
+                display_balances(redis, chat_id, &debts).await,
+            );
+            let keyboard =
+                make_keyboard_tutorial("View Balances ➡️", TutorialStep::Balances, user_id);
+            Ok((text, Some(keyboard)))
+        }
+        TutorialStep::Balances => {
+            let debts = retrieve_tutorial_debts(redis, chat_id).await?;
+            let text = format!(
+                "📊 Step 2: Viewing balances\n\nThis is what /balances shows, the fewest transfers to settle everything up:\n\n{}\nNext, let's have {} pay you back.",
+                display_balances(redis, chat_id, &debts).await,
+                mention_username(TUTORIAL_DEBTORS[0]),
+            );
+            let keyboard = make_keyboard_tutorial("Pay Back ➡️", TutorialStep::PayBack, user_id);
+            Ok((text, Some(keyboard)))
+        }
+        TutorialStep::PayBack => {
+            let (payback, debts) = add_tutorial_payback(redis, chat_id, username).await?;
+            let text = format!(
+                "💸 Step 3: Paying back\n\n{} paid you back {}, just like /payback would record it. Here are the balances now:\n\n{}",
+                mention_username(&payback.creditor),
+                display_tutorial_total(redis, chat_id, &payback).await,
+                display_balances(redis, chat_id, &debts).await,
+            );
+            let keyboard = make_keyboard_tutorial("Finish ✅", TutorialStep::Finish, user_id);
+            Ok((text, Some(keyboard)))
+        }
+        TutorialStep::Finish => {
+            clear_tutorial(redis, chat_id).await?;
+            let text = "🎉 You're all set! I've cleared the practice payments, and the balances of this chat were never touched.\n\nUse /addpayment to add a real payment, or /help to see everything I can do.".to_string();
+            Ok((text, None))
+        }
+        TutorialStep::Cancel => {
+            clear_tutorial(redis, chat_id).await?;
+            let text = "👌 Tutorial stopped! I've cleared the practice payments. Use /tutorial whenever you'd like to try again.".to_string();
+            Ok((text, None))
+        }
+    }
+}
+
+/* Starts the tutorial of a group chat.
+ * Walks the user through adding a payment, viewing balances, and paying back,
+ * on a practice ledger which is cleared at the end, so the real balances are untouched.
+ */
+pub async fn action_tutorial(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let user_id = match msg.from() {
+        Some(user) => user.id.to_string(),
+        None => return Ok(()),
+    };
+    let chat_id = msg.chat.id.to_string();
+
+    send_bot_message(
+        &bot,
+        &msg,
+        "👋 Let's take a quick tour! I'll show you how to add a payment, view balances, and pay back, with some practice payments.\n\nDon't worry, nothing here touches the real balances of this chat, and I'll clean up after.".to_string(),
+    )
+    .reply_markup(make_keyboard_tutorial("Start ➡️", TutorialStep::Payment, &user_id))
+    .await?;
+
+    // Logging
+    log::info!(
+        "Tutorial - Tutorial started by user {} in chat {}",
+        user_id,
+        chat_id
+    );
+
+    Ok(())
+}
+
+/* Continues the tutorial of a group chat.
+ * Bot receives a callback query from the tutorial message, and moves on to the next step,
+ * editing the message in place. Only the user who started the tutorial can continue it.
+ */
+pub async fn action_tutorial_step(redis: Redis, bot: Bot, query: CallbackQuery) -> HandlerResult {
+    let (step, user_id) = match query.data.as_deref().and_then(parse_tutorial_query) {
+        Some(parsed) => parsed,
+        None => return Ok(()),
+    };
+    let msg = match &query.message {
+        Some(msg) => msg.clone(),
+        None => return Ok(()),
+    };
+    let chat_id = msg.chat.id.to_string();
+
+    if query.from.id.to_string() != user_id {
+        bot.answer_callback_query(query.id)
+            .text("This tutorial belongs to someone else! Use /tutorial to start your own.")
+            .await?;
+        return Ok(());
+    }
+    bot.answer_callback_query(query.id).await?;
+
+    let username = get_tutorial_username(&query.from);
+    match display_tutorial_step(&redis, &chat_id, &username, &user_id, step).await {
+        Ok((text, Some(keyboard))) => {
+            edit_bot_message(&bot, msg.chat.id, msg.id, text)
+                .reply_markup(keyboard)
+                .await?;
+        }
+        Ok((text, None)) => {
+            edit_bot_message(&bot, msg.chat.id, msg.id, text).await?;
+        }
+        Err(err) => {
+            // Practice payments are cleared, so that none are left behind
+            clear_tutorial(&redis, &chat_id).await.ok();
+            edit_bot_message(
+                &bot,
+                msg.chat.id,
+                msg.id,
+                format!("⁉️ Something went wrong with the tutorial: {err}\n\nUse /tutorial to try again."),
+            )
+            .await?;
+
+            // Logging
+            log::error!(
+                "Tutorial - Failed tutorial step {} for user {} in chat {}: {}",
+                step.as_str(),
+                user_id,
+                chat_id,
+                err.to_string()
+            );
+            return Ok(());
+        }
+    }
+
+    // Logging
+    log::info!(
+        "Tutorial - Tutorial step {} completed by user {} in chat {}",
+        step.as_str(),
+        user_id,
+        chat_id
+    );
+
+    Ok(())
+}
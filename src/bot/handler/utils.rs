@@ -1,30 +1,83 @@
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    future::{Future, IntoFuture},
+    pin::Pin,
+    time::Duration,
+};
 
-use chrono::{DateTime, Local, NaiveDateTime};
+use chrono::{
+    DateTime, Datelike, Days, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
 use chrono_tz::Tz;
 use regex::Regex;
+use reqwest::Url;
 use teloxide::{
     dispatching::dialogue::{Dialogue, InMemStorage, InMemStorageError},
-    payloads::SendMessage,
+    payloads::{EditMessageText, SendDocument, SendMessage},
     prelude::*,
-    requests::JsonRequest,
-    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message, MessageId},
-    RequestError,
+    requests::{HasPayload, JsonRequest, MultipartRequest, Output},
+    types::{
+        ChatId, InlineKeyboardButton, InlineKeyboardMarkup, InputFile, Message, MessageCommon,
+        MessageId, MessageKind, ParseMode, Recipient, ReplyMarkup,
+    },
+    ApiError, RequestError,
 };
 
 use crate::bot::{
-    currency::{get_currency_from_code, get_default_currency, Currency, CURRENCY_DEFAULT},
+    currency::{
+        count_decimal_places, format_currency_amount, get_currency_format, get_currency_from_code,
+        get_default_currency, get_number_format, normalize_amount, normalize_amount_text, Currency,
+        CurrencyFormat, NumberFormat, CURRENCY_DEFAULT, CURRENCY_FORMAT_DEFAULT,
+        NUMBER_FORMAT_DEFAULT,
+    },
+    i18n::{
+        translate, translate_button, Text, ADJUSTMENTS_EXCEED_TOTAL, ADJUSTMENTS_NEGATIVE,
+        ADMIN_ONLY, AMOUNTS_CURRENCY_MISMATCH, AMOUNTS_EXCEED_TOTAL, AMOUNT_TOO_LARGE,
+        BALANCES_HEADER, BALANCES_HEADER_CONVERTED, BALANCES_HEADER_CURRENCY, BALANCES_NONE,
+        BALANCE_DEBT, CLEANUP_COMPACT, CONVERSION_ERROR, CURRENCY_MISSING, CURRENCY_NOT_ALLOWED,
+        DATE_INSTRUCTIONS, DATE_IN_FUTURE, DATE_UNRECOGNIZED, DELETE_PERMISSION,
+        DESCRIPTION_MISSING, DESCRIPTION_TOO_LONG, DIVIDE_BY_ZERO, DUE_DATE_INSTRUCTIONS,
+        DUE_DATE_IN_PAST, EXPRESSION_TOO_LONG, EXPRESSION_UNRECOGNIZED, FORMAT_UNRECOGNIZED,
+        INVALID_NUMBER, INVALID_USERNAME, ITEMS_EXCEED_TOTAL, ITEMS_MISSING, ITEMS_SUMMARY,
+        ITEMS_WITHOUT_USERS, ITEM_AMOUNT_MISSING, ITEM_EVERYONE, ITEM_PERCENT_WITH_USERS,
+        LANGUAGE_DEFAULT, NOT_POSITIVE, NO_DECIMAL_PLACES, PAYER_MISSING, PAYMENT_ADDED_BY,
+        PAYMENT_CONFIRMED_BY, PAYMENT_DETAILS, PAYMENT_EDITED_BY, PAYMENT_LINK_NUMBER,
+        PAYMENT_RATE, PAYMENT_TYPE_ADJUSTMENT, PAYMENT_TYPE_TRANSFER, PAY_BACK_SELF,
+        PERCENTAGES_NOT_HUNDRED, PRIVATE_PAYMENT, RATES_AGE, RATES_AS_OF, RATE_AGE_DAY,
+        RATE_AGE_DAYS, RATE_AGE_HOURS, RATE_AGE_MINUTES, RATE_AGE_NOW, RATE_LIMIT_ERROR,
+        RATE_UNRECOGNIZED, REMINDER_DAYS_INVALID, RETENTION_MONTHS_INVALID,
+        SURCHARGE_MISSING_AMOUNT, SURCHARGE_UNRECOGNIZED, TELEGRAM_ERROR, TIME_UNRECOGNIZED,
+        TIME_ZONE_UNRECOGNIZED, TOO_MANY_DEBTORS, TOO_MANY_DECIMAL_PLACES, TOO_MANY_PAYMENTS,
+        TOTAL_MISSING, UNCLOSED_BRACKET, UNKNOWN_CURRENCY, UNKNOWN_ERROR, USERNAMES_MISSING,
+        VALIDATION_ERROR,
+    },
+    money::{
+        checked_add, checked_from_f64, checked_sum, saturating_add, Money, MoneyError, MAX_VALUE,
+    },
+    period::{parse_week_start, WeekStart},
     processor::{
-        assert_rate_limit, get_chat_setting, is_username_equal, retrieve_valid_currencies,
-        ChatSetting, ProcessError,
+        assert_rate_limit, get_chat_setting, get_topic_chat_setting, is_adjustment_payment,
+        is_username_equal, mark_bot_permission_notified, retrieve_bot_can_delete,
+        retrieve_chat_admins, retrieve_chat_users, retrieve_display_names,
+        retrieve_overdue_debtors, retrieve_payment_acknowledgements, retrieve_payment_count,
+        retrieve_payment_links, retrieve_rounding_offset, retrieve_split_groups,
+        retrieve_valid_currencies, update_bot_can_delete, update_chat_admins, ChatSetting,
+        ProcessError, StatementOption, PRIVATE_DESCRIPTION,
     },
-    redis::Debt,
+    redis::{Debt, PaymentAudit, Redis},
     State,
 };
 
 use super::{
-    constants::{all_time_zones, MAX_VALUE},
-    AddDebtsFormat, Payment,
+    constants::{
+        all_time_zones, ADMIN_CACHE_TTL, CHAT_PAYMENTS_MAX, COMPLETION_REACTION, DEBTORS_MAX,
+        DESCRIPTION_MAX_LENGTH, EXPRESSION_MAX_LENGTH, MARKDOWN_RESERVED_CHARACTERS,
+        PAYMENT_LINK_BUTTONS_MAX, PERCENT_TOLERANCE, PERMISSION_CACHE_TTL, RETRY_BASE_DELAY_MS,
+        RETRY_MAX_ATTEMPTS, RETRY_MAX_DELAY_MS, SPLIT_GROUP_CALLBACK_PREFIX, USERNAME_MAX_LENGTH,
+        USERNAME_MIN_LENGTH,
+    },
+    AddDebtsFormat, DebtItem, Payment, Surcharge,
 };
 
 /* Common utilites for handlers. */
@@ -32,23 +85,98 @@ use super::{
 /* Types */
 pub type UserDialogue = Dialogue<State, InMemStorage<State>>;
 pub type HandlerResult = Result<(), BotError>;
+pub type CurrencyDebts = (Currency, Vec<(String, i64)>);
+
+// MessageTracker keeps the IDs of all bot messages sent during an operation,
+// such as prompts and menus, so that they can be erased once the operation ends
+// It also keeps the IDs of user messages, such as the command and replies to prompts,
+// which are only erased if the chat has erase user messages turned on as well
+#[derive(Debug, Clone, Default)]
+pub struct MessageTracker {
+    messages: Vec<MessageId>,
+    user_messages: Vec<MessageId>,
+}
+
+impl MessageTracker {
+    pub fn new(message: MessageId) -> MessageTracker {
+        MessageTracker {
+            messages: vec![message],
+            user_messages: Vec::new(),
+        }
+    }
+
+    // Tracks the user command that started the operation
+    pub fn with_command(mut self, command: MessageId) -> MessageTracker {
+        self.track_user(command);
+        self
+    }
+
+    pub fn track(&mut self, message: MessageId) {
+        self.messages.push(message);
+    }
 
-#[derive(PartialEq, Debug, Clone)]
-pub enum StatementOption {
-    Currency(String),
-    ConvertCurrency,
+    pub fn track_user(&mut self, message: MessageId) {
+        if !self.user_messages.contains(&message) {
+            self.user_messages.push(message);
+        }
+    }
+
+    pub fn untrack(&mut self, message: MessageId) {
+        self.messages.retain(|tracked| *tracked != message);
+        self.user_messages.retain(|tracked| *tracked != message);
+    }
+}
+
+// Verbosity of the messages sent when an action is completed
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Verbosity {
+    Verbose,
+    Compact,
+    Silent,
+}
+
+pub const VERBOSITIES: [(&str, Verbosity); 3] = [
+    ("verbose", Verbosity::Verbose),
+    ("compact", Verbosity::Compact),
+    ("silent", Verbosity::Silent),
+];
+
+// Strategy for assigning the amount left over from an equal split
+// Round robin keeps the offset of the chat, which moves on with every split
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Rounding {
+    Payer,
+    First,
+    Random,
+    RoundRobin(u64),
 }
 
+pub const ROUNDINGS: [(&str, Rounding); 4] = [
+    ("payer", Rounding::Payer),
+    ("first", Rounding::First),
+    ("random", Rounding::Random),
+    ("roundrobin", Rounding::RoundRobin(0)),
+];
+
 #[derive(Debug, Clone)]
 pub enum SelectPaymentType {
     EditPayment,
-    DeletePayment,
+    DuplicatePayment,
+}
+
+// Detail of a payment being added from its detail card
+#[derive(Debug, Clone)]
+pub enum PaymentCardDetail {
+    Note,
+    Receipt,
 }
 
+// Variants are named after the errors they wrap, and are matched on across all handlers
+#[allow(clippy::enum_variant_names)]
 #[derive(thiserror::Error, Debug)]
 pub enum BotError {
     #[error("{0}")]
-    UserError(String),
+    UserError(Text),
     #[error("Process error: {0}")]
     ProcessError(ProcessError),
     #[error("Request error: {0}")]
@@ -63,7 +191,21 @@ impl From<RequestError> for BotError {
 
 impl From<InMemStorageError> for BotError {
     fn from(storage_error: InMemStorageError) -> BotError {
-        BotError::UserError(storage_error.to_string())
+        log::error!("Dialogue storage error: {storage_error}");
+        BotError::UserError(Text::new(UNKNOWN_ERROR))
+    }
+}
+
+impl From<MoneyError> for BotError {
+    fn from(money_error: MoneyError) -> BotError {
+        BotError::UserError(match money_error {
+            MoneyError::OverflowError() => Text::new(AMOUNT_TOO_LARGE),
+            MoneyError::CurrencyMismatchError(first, second) => {
+                Text::new(AMOUNTS_CURRENCY_MISMATCH)
+                    .arg("first", first)
+                    .arg("second", second)
+            }
+        })
     }
 }
 
@@ -75,12 +217,12 @@ impl From<ProcessError> for BotError {
 
 // Checks and asserts the rate limit of 1 request per user per second.
 // Returns true if okay, false if exceeded
-pub fn assert_handle_request_limit(msg: Message) -> bool {
+pub async fn assert_handle_request_limit(redis: &Redis, msg: Message) -> bool {
     if let Some(user) = msg.from() {
         let user_id = user.id.to_string();
         let timestamp = msg.date.timestamp();
-        let request_status = assert_rate_limit(&user_id, timestamp);
-        if let Err(_) = request_status {
+        let request_status = assert_rate_limit(redis, &user_id, timestamp).await;
+        if request_status.is_err() {
             log::error!(
                 "Rate limit exceeded for user: {} in chat: {}, with message timestamp: {}",
                 user_id,
@@ -94,334 +236,1839 @@ pub fn assert_handle_request_limit(msg: Message) -> bool {
     true
 }
 
-// Wrapper function to send bot message to specific thread, if available
-// Only replaces bot::send_message, as bot::edit_message_text edits specific msg ID
-pub fn send_bot_message(bot: &Bot, msg: &Message, text: String) -> JsonRequest<SendMessage> {
-    let thread_id = msg.thread_id;
-    match thread_id {
-        Some(thread_id) => bot
-            .send_message(msg.chat.id, text)
-            .message_thread_id(thread_id),
-        None => bot.send_message(msg.chat.id, text),
+// Checks if the sender of a message is an administrator of the chat
+// Administrators are cached for some time, instead of asking Telegram on every command
+// Private chats have no administrators, so the sender is always allowed
+pub async fn is_chat_admin(redis: &Redis, bot: &Bot, msg: &Message) -> Result<bool, BotError> {
+    if msg.chat.is_private() {
+        return Ok(true);
+    }
+
+    // Anonymous administrators send messages on behalf of the chat itself
+    if let Some(sender_chat) = msg.sender_chat() {
+        if sender_chat.id == msg.chat.id {
+            return Ok(true);
+        }
+    }
+
+    match msg.from() {
+        Some(user) => is_user_admin(redis, bot, msg.chat.id, user.id).await,
+        None => Ok(false),
     }
 }
 
-// Removes all old messages, given a chat and a list of message IDs
-pub async fn delete_bot_messages(
+// Checks if a user is an administrator of a group chat, using the cached administrators
+// Used for callback queries, where the message is sent by the bot instead of the user
+pub async fn is_user_admin(
+    redis: &Redis,
     bot: &Bot,
-    chat_id: &str,
-    messages: Vec<MessageId>,
-) -> Result<(), BotError> {
-    for message in messages {
-        bot.delete_message(chat_id.to_string(), message).await?;
-    }
-    Ok(())
+    chat_id: ChatId,
+    user_id: UserId,
+) -> Result<bool, BotError> {
+    let chat = chat_id.to_string();
+    let admins = match retrieve_chat_admins(redis, &chat).await? {
+        Some(admins) => admins,
+        None => {
+            let admins: Vec<String> = bot
+                .get_chat_administrators(chat_id)
+                .await?
+                .iter()
+                .map(|member| member.user.id.to_string())
+                .collect();
+            update_chat_admins(redis, &chat, &admins, ADMIN_CACHE_TTL).await?;
+            admins
+        }
+    };
+
+    Ok(admins.contains(&user_id.to_string()))
 }
 
-// Checks if Erase Messages setting is enabled
-pub fn is_erase_messages(chat_id: &str) -> bool {
-    let erase = get_chat_setting(chat_id, ChatSetting::EraseMessages(None));
-    if let Ok(ChatSetting::EraseMessages(Some(true))) = erase {
-        true
-    } else {
-        false
+// Checks if a chat restricts editing records and changing settings to its admins
+async fn is_admin_only(redis: &Redis, chat_id: &str) -> Result<bool, BotError> {
+    Ok(matches!(
+        get_chat_setting(redis, chat_id, ChatSetting::AdminOnly(None)).await?,
+        ChatSetting::AdminOnly(Some(true))
+    ))
+}
+
+// Checks if a user may edit records of a chat, which may be restricted to admins
+// Used for callback queries, where the message is sent by the bot instead of the user
+pub async fn is_user_allowed(
+    redis: &Redis,
+    bot: &Bot,
+    chat_id: ChatId,
+    user_id: UserId,
+) -> Result<bool, BotError> {
+    if chat_id.is_user() || !is_admin_only(redis, &chat_id.to_string()).await? {
+        return Ok(true);
     }
+    is_user_admin(redis, bot, chat_id, user_id).await
 }
 
-// Retrieves the currency given a currency code.
-pub fn get_currency(code: &str) -> Result<Currency, BotError> {
-    let currency = get_currency_from_code(code);
-    match currency {
-        Some(currency) => Ok(currency),
-        None => Err(BotError::UserError(
-            "🥺 Sorry, I don't know that currency!".to_string(),
-        )),
+// Checks if the sender of a message may edit records of a chat, which may be restricted to admins
+async fn is_sender_allowed(redis: &Redis, bot: &Bot, msg: &Message) -> Result<bool, BotError> {
+    Ok(!is_admin_only(redis, &msg.chat.id.to_string()).await?
+        || is_chat_admin(redis, bot, msg).await?)
+}
+
+// Checks and asserts that the sender may edit records, if the chat restricts edits to admins.
+// Returns true if okay, false if restricted, after informing the sender
+pub async fn assert_admin_only(redis: &Redis, bot: &Bot, msg: &Message) -> Result<bool, BotError> {
+    let chat_id = msg.chat.id.to_string();
+    if is_sender_allowed(redis, bot, msg).await? {
+        return Ok(true);
     }
+
+    send_bot_message(bot, msg, t(redis, &chat_id, ADMIN_ONLY, &[]).await).await?;
+
+    // Logging
+    log::info!(
+        "Admin Only - Restricted command blocked for non-admin in chat {}",
+        chat_id
+    );
+
+    Ok(false)
 }
 
-// Retrieves the default currency of a chat. Does not return an error, assumes default.
-pub fn get_chat_default_currency(chat_id: &str) -> Currency {
-    let setting = ChatSetting::DefaultCurrency(None);
-    let currency = get_chat_setting(&chat_id, setting);
-    match currency {
-        Ok(ChatSetting::DefaultCurrency(Some(currency))) => {
-            let currency = get_currency(&currency);
-            if let Ok(currency) = currency {
-                return currency;
-            }
+// Checks if a state edits records or settings, which may be restricted to admins
+fn is_admin_only_state(state: &State) -> bool {
+    matches!(
+        state,
+        State::SelectPayment {
+            function: SelectPaymentType::EditPayment,
+            ..
+        } | State::EditPayment { .. }
+            | State::EditPaymentDebtSelection { .. }
+            | State::EditPaymentDebtPicker { .. }
+            | State::EditPaymentDetails { .. }
+            | State::SelectPaymentsDelete { .. }
+            | State::DeletePayment { .. }
+            | State::SettingsMenu { .. }
+            | State::SettingsTimeZoneMenu { .. }
+            | State::SettingsTimeZoneRegion { .. }
+            | State::SettingsDefaultCurrencyMenu { .. }
+            | State::SettingsDefaultCurrency { .. }
+            | State::SettingsCurrencyConversion { .. }
+            | State::SettingsEraseMessages { .. }
+            | State::SettingsMonthlyArchive { .. }
+            | State::SettingsWeeklyDigestMenu { .. }
+            | State::SettingsWeeklyDigest { .. }
+            | State::SettingsDebtReminderMenu { .. }
+            | State::SettingsDebtReminder { .. }
+            | State::SettingsPaymentApprovalMenu { .. }
+            | State::SettingsPaymentApproval { .. }
+            | State::SettingsLanguageMenu { .. }
+            | State::SettingsNumberFormatMenu { .. }
+            | State::SettingsCurrencyFormatMenu { .. }
+            | State::SettingsVerbosityMenu { .. }
+            | State::SettingsRoundingMenu { .. }
+            | State::SettingsWeekStartMenu { .. }
+            | State::SettingsAdminOnly { .. }
+            | State::SettingsTreasurerMenu { .. }
+            | State::SettingsTreasurer { .. }
+    )
+}
+
+/* Checks if a callback query is from a user who may not continue the current dialogue.
+ * Dialogues are kept per chat, so anyone in the chat can tap the menu that an admin opened.
+ * Users are restricted if the dialogue edits records or settings, in a chat restricted to admins.
+ */
+pub async fn is_restricted_query(
+    redis: Redis,
+    bot: Bot,
+    state: State,
+    query: CallbackQuery,
+) -> bool {
+    let chat_id = match &query.message {
+        Some(msg) if is_admin_only_state(&state) => msg.chat.id,
+        _ => return false,
+    };
+    match is_user_allowed(&redis, &bot, chat_id, query.from.id).await {
+        Ok(is_allowed) => !is_allowed,
+        Err(err) => {
+            log::error!("Admin Only - Failed to check user for chat {chat_id}: {err}");
+            true
         }
-        // Skips error, assumes default
-        _ => {}
     }
-    get_default_currency()
 }
 
-// Processes and retrieves appropriate valid currencies for balances and spendings.
-pub fn process_valid_currencies(
-    chat_id: &str,
-    sender_id: &str,
-    option: StatementOption,
-    default_currency: String,
-) -> Vec<String> {
-    let mut valid_currencies = match retrieve_valid_currencies(&chat_id) {
-        Ok(currencies) => currencies,
-        Err(_) => {
+/* Checks if a message is from a user who may not continue the current dialogue.
+ * Same as is_restricted_query, for replies to a dialogue, such as a time zone being typed in.
+ */
+pub async fn is_restricted_message(redis: Redis, bot: Bot, state: State, msg: Message) -> bool {
+    if !is_admin_only_state(&state) {
+        return false;
+    }
+    match is_sender_allowed(&redis, &bot, &msg).await {
+        Ok(is_allowed) => !is_allowed,
+        Err(err) => {
             log::error!(
-                "View Spendings - User {} failed to retrieve valid currencies for group {}",
-                sender_id,
-                chat_id
+                "Admin Only - Failed to check sender for chat {}: {err}",
+                msg.chat.id
             );
-            vec![]
+            true
         }
+    }
+}
+
+// Informs a user that the menu they tapped is restricted to admins
+pub async fn action_restricted_query(
+    redis: Redis,
+    bot: Bot,
+    query: CallbackQuery,
+) -> HandlerResult {
+    let text = match &query.message {
+        Some(msg) => t(&redis, &msg.chat.id.to_string(), ADMIN_ONLY, &[]).await,
+        None => translate(LANGUAGE_DEFAULT.0, ADMIN_ONLY, &[]),
     };
+    bot.answer_callback_query(query.id)
+        .text(text)
+        .show_alert(true)
+        .await?;
+
+    // Logging
+    log::info!(
+        "Admin Only - Restricted menu blocked for non-admin user {}",
+        query.from.id
+    );
+    Ok(())
+}
 
-    valid_currencies.retain(|x| x != CURRENCY_DEFAULT.0 && x != &default_currency);
+// Informs the sender that the dialogue they replied to is restricted to admins
+pub async fn action_restricted_message(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    let chat_id = msg.chat.id.to_string();
+    send_bot_message(&bot, &msg, t(&redis, &chat_id, ADMIN_ONLY, &[]).await).await?;
 
-    if let StatementOption::Currency(ref curr) = option {
-        valid_currencies.retain(|x| x != curr);
+    // Logging
+    log::info!(
+        "Admin Only - Restricted reply blocked for non-admin in chat {}",
+        chat_id
+    );
+    Ok(())
+}
+
+/* Telegram request that is retried when Telegram asks to slow down.
+ * Waits for as long as Telegram asks, plus a jittered backoff that grows with each attempt,
+ * and gives up after a few attempts, or if the wait would be too long.
+ * Can be set up like the request it wraps, and is sent when awaited.
+ */
+#[must_use = "Requests are lazy and do nothing unless sent"]
+pub struct RetryRequest<R> {
+    request: R,
+}
+
+impl<R> HasPayload for RetryRequest<R>
+where
+    R: HasPayload,
+{
+    type Payload = R::Payload;
+
+    fn payload_mut(&mut self) -> &mut Self::Payload {
+        self.request.payload_mut()
     }
 
-    // Add back default currency button if not NIL, and currently not default
-    if default_currency != CURRENCY_DEFAULT.0 {
-        if let StatementOption::Currency(ref curr) = option {
-            if curr != &default_currency {
-                valid_currencies.push(default_currency.clone());
-            }
-        } else if valid_currencies.len() > 0 {
-            // Adds back default currency on convert, only if there are also other
-            // currencies. Else, the converted is already equal to the default.
-            valid_currencies.push(default_currency.clone());
-        }
+    fn payload_ref(&self) -> &Self::Payload {
+        self.request.payload_ref()
     }
+}
 
-    // Special buttons
-    let conversion_button = format!("Convert To {default_currency}");
-    // Add conversion button only if not currently on convert, and have default currency
-    if option != StatementOption::ConvertCurrency
-        && default_currency != CURRENCY_DEFAULT.0
-        && valid_currencies.len() > 0
-    {
-        valid_currencies.push(conversion_button);
-        // Add no currency button if no default currency, and not currently NIL
-    } else if default_currency == CURRENCY_DEFAULT.0 {
-        if let StatementOption::Currency(ref curr) = option {
-            if curr != CURRENCY_DEFAULT.0 {
-                valid_currencies.push("No Currency".to_string());
-            }
-        }
+impl<R> IntoFuture for RetryRequest<R>
+where
+    R: Request<Err = RequestError> + Send + Sync + 'static,
+    Output<R>: Send,
+{
+    type Output = Result<Output<R>, RequestError>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(send_with_retry(self.request))
     }
+}
 
-    valid_currencies
+// Gets the backoff before retrying a request, with up to half of it added as jitter
+fn get_retry_backoff(attempt: u32) -> Duration {
+    let backoff = RETRY_BASE_DELAY_MS.saturating_mul(1 << attempt);
+    let jitter = (uuid::Uuid::new_v4().as_u128() % (backoff as u128 / 2 + 1)) as u64;
+    Duration::from_millis(backoff + jitter)
 }
 
-// Converts an amount from base value to actual representation in currency.
-pub fn display_amount(amount: i64, decimal_places: i32) -> String {
-    if decimal_places == 0 {
-        return amount.to_string();
-    } else if amount == 0 {
-        return "0".to_string();
+// Sends a request, retrying it when Telegram asks to slow down
+async fn send_with_retry<R>(request: R) -> Result<Output<R>, RequestError>
+where
+    R: Request<Err = RequestError>,
+{
+    let mut attempt = 0;
+    loop {
+        let result = request.send_ref().await;
+        let retry_after = match &result {
+            Err(RequestError::RetryAfter(retry_after)) => *retry_after,
+            _ => return result,
+        };
+
+        let delay = retry_after + get_retry_backoff(attempt);
+        if attempt >= RETRY_MAX_ATTEMPTS || delay > Duration::from_millis(RETRY_MAX_DELAY_MS) {
+            log::error!(
+                "Telegram Retry - Giving up on request after {} attempts, with retry after {:?}",
+                attempt + 1,
+                retry_after
+            );
+            return result;
+        }
+
+        log::warn!(
+            "Telegram Retry - Rate limited by Telegram, retrying request in {:?}",
+            delay
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
     }
+}
 
-    // Amount is not 0, and decimal places are not 0
-    let factor = 10.0_f64.powi(decimal_places);
-    let actual_amount = amount as f64 / factor;
-    format!(
-        "{:.decimals$}",
-        actual_amount,
-        decimals = decimal_places as usize
-    )
+// Wraps a request to be retried when Telegram asks to slow down
+pub fn with_retry<R>(request: R) -> RetryRequest<R> {
+    RetryRequest { request }
 }
 
-// Displays an amount together with its currency
-pub fn display_currency_amount(amount: i64, currency: Currency) -> String {
-    if currency.0 == CURRENCY_DEFAULT.0 {
-        format!("{}", display_amount(amount, currency.1))
-    } else {
-        format!("{} {}", display_amount(amount, currency.1), currency.0)
-    }
+// Wrapper function to send bot message to specific thread, if available
+// Only replaces bot::send_message, as bot::edit_message_text edits specific msg ID
+pub fn send_bot_message(
+    bot: &Bot,
+    msg: &Message,
+    text: String,
+) -> RetryRequest<JsonRequest<SendMessage>> {
+    let thread_id = msg.thread_id;
+    with_retry(match thread_id {
+        Some(thread_id) => bot
+            .send_message(msg.chat.id, text)
+            .message_thread_id(thread_id),
+        None => bot.send_message(msg.chat.id, text),
+    })
 }
 
-// Gets the currency to be used when provided with the chosen currency, and the chat ID.
-pub fn use_currency(currency: Currency, chat_id: &str) -> Currency {
-    let default_currency = get_chat_default_currency(chat_id);
-    if currency.0 == CURRENCY_DEFAULT.0 {
-        default_currency
-    } else {
-        currency
-    }
+// Wrapper function to send bot document to specific thread, if available
+pub fn send_bot_document(
+    bot: &Bot,
+    msg: &Message,
+    document: InputFile,
+) -> RetryRequest<MultipartRequest<SendDocument>> {
+    let thread_id = msg.thread_id;
+    with_retry(match thread_id {
+        Some(thread_id) => bot
+            .send_document(msg.chat.id, document)
+            .message_thread_id(thread_id),
+        None => bot.send_document(msg.chat.id, document),
+    })
 }
 
-// Displays the header for the balances, depending on the statement option applied.
-pub fn display_balance_header(chat_id: &str, currency: &str) -> String {
-    let conversion = match get_chat_setting(chat_id, ChatSetting::CurrencyConversion(None)) {
-        Ok(ChatSetting::CurrencyConversion(Some(value))) => value,
-        _ => false,
-    };
-    let default_currency = match get_chat_setting(chat_id, ChatSetting::DefaultCurrency(None)) {
-        Ok(ChatSetting::DefaultCurrency(Some(currency))) => currency,
-        _ => CURRENCY_DEFAULT.0.to_string(),
-    };
+// Wrapper function to edit bot message, retrying when Telegram asks to slow down
+pub fn edit_bot_message<C, T>(
+    bot: &Bot,
+    chat_id: C,
+    message_id: MessageId,
+    text: T,
+) -> RetryRequest<JsonRequest<EditMessageText>>
+where
+    C: Into<Recipient>,
+    T: Into<String>,
+{
+    with_retry(bot.edit_message_text(chat_id, message_id, text))
+}
 
-    if conversion {
-        format!(
-            "✨ Ta-da! Here are the updated balances, all converted to {}!\n\n",
-            default_currency
-        )
-    } else if currency == CURRENCY_DEFAULT.0 {
-        if default_currency != CURRENCY_DEFAULT.0 {
-            format!(
-                "✨ Ta-da! Here are the updated balances in {}!\n\n",
-                default_currency
-            )
-        } else {
-            format!("✨ Ta-da! Here are the updated balances!\n\n")
-        }
-    } else {
-        format!(
-            "✨ Ta-da! Here are the updated balances in {}!\n\n",
-            currency
-        )
-    }
+/* Telegram request to edit a bot message that is tracked in an operation.
+ * If the message is gone, such as when it was deleted in the middle of the operation,
+ * it is sent again as a new message, which takes its place among the tracked messages.
+ * Can be set up like the edit it wraps, and gives the ID of the message shown when awaited.
+ */
+#[must_use = "Requests are lazy and do nothing unless sent"]
+pub struct TrackedEditRequest<'a> {
+    request: RetryRequest<JsonRequest<EditMessageText>>,
+    bot: Bot,
+    chat_id: ChatId,
+    thread_id: Option<i32>,
+    messages: &'a mut MessageTracker,
 }
 
-// Displays balances in a more readable format. Now only shows in one currency.
-pub fn display_balances(debts: &Vec<Debt>) -> String {
-    let mut message = String::new();
-    for debt in debts {
-        let currency = get_currency(&debt.currency);
-        match currency {
-            Ok(currency) => {
-                message.push_str(&format!(
-                    "{} owes {}: {}\n",
-                    display_username(&debt.debtor),
-                    display_username(&debt.creditor),
-                    display_amount(debt.amount, currency.1),
-                ));
-            }
-            // Should not occur, since code is already processed and stored in database
-            Err(_err) => {
-                continue;
-            }
-        }
-    }
+impl HasPayload for TrackedEditRequest<'_> {
+    type Payload = EditMessageText;
 
-    if debts.is_empty() {
-        "No outstanding balances! 🥳\n".to_string()
-    } else {
-        message
+    fn payload_mut(&mut self) -> &mut Self::Payload {
+        self.request.payload_mut()
     }
-}
 
-// Displays debts in a more readable format.
-pub fn display_debts(debts: &Vec<(String, i64)>, decimal_places: i32) -> String {
-    let mut message = String::new();
-    for debt in debts {
-        message.push_str(&format!(
-            "    {}: {}\n",
-            display_username(&debt.0),
-            display_amount(debt.1, decimal_places),
-        ));
+    fn payload_ref(&self) -> &Self::Payload {
+        self.request.payload_ref()
     }
-    message
 }
 
-// Displays a single payment entry in a user-friendly format.
-pub fn display_payment(payment: &Payment, serial_num: usize, time_zone: Tz) -> String {
-    let actual_currency = use_currency(payment.currency.clone(), &payment.chat_id);
-
-    format!(
-        "__________________________\n{}. {}\nDate: {}\nPayer: {}\nTotal: {}\nSplit:\n{}",
-        serial_num,
-        payment.description,
-        reformat_datetime(&payment.datetime, time_zone),
-        display_username(&payment.creditor),
-        display_currency_amount(payment.total, actual_currency.clone()),
-        display_debts(&payment.debts, actual_currency.1)
-    )
+impl<'a> IntoFuture for TrackedEditRequest<'a> {
+    type Output = Result<MessageId, RequestError>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let edit = self.request.payload_ref().clone();
+            match self.request.await {
+                Ok(message) => Ok(message.id),
+                Err(RequestError::Api(
+                    ApiError::MessageToEditNotFound | ApiError::MessageIdInvalid,
+                )) => {
+                    let mut request = self.bot.send_message(self.chat_id, edit.text);
+                    let payload = request.payload_mut();
+                    payload.message_thread_id = self.thread_id;
+                    payload.parse_mode = edit.parse_mode;
+                    payload.entities = edit.entities;
+                    payload.disable_web_page_preview = edit.disable_web_page_preview;
+                    payload.reply_markup = edit.reply_markup.map(ReplyMarkup::InlineKeyboard);
+                    let message = with_retry(request).await?;
+
+                    self.messages.untrack(edit.message_id);
+                    self.messages.track(message.id);
+
+                    // Logging
+                    log::warn!(
+                        "Edit Message - Message {} in chat {} is gone, sent again as message {}",
+                        edit.message_id,
+                        self.chat_id,
+                        message.id
+                    );
+                    Ok(message.id)
+                }
+                Err(err) => Err(err),
+            }
+        })
+    }
 }
 
-// Make a keyboard, button menu.
-pub fn make_keyboard(options: Vec<&str>, columns: Option<usize>) -> InlineKeyboardMarkup {
-    let mut keyboard: Vec<Vec<InlineKeyboardButton>> = Vec::new();
-    if let Some(col) = columns {
-        for chunk in options.chunks(col) {
-            let mut row: Vec<InlineKeyboardButton> = Vec::new();
-            for option in chunk {
-                row.push(InlineKeyboardButton::callback(
-                    option.to_string(),
-                    option.to_string(),
-                ));
-            }
-            keyboard.push(row);
-        }
-    } else {
-        for option in options {
-            keyboard.push(vec![InlineKeyboardButton::callback(option, option)]);
-        }
+// Wrapper function to edit a bot message tracked in an operation, given the message to edit
+// Sends it again if it is gone, keeping the tracked messages up to date
+pub fn edit_tracked_message<'a, T>(
+    bot: &Bot,
+    msg: &Message,
+    messages: &'a mut MessageTracker,
+    text: T,
+) -> TrackedEditRequest<'a>
+where
+    T: Into<String>,
+{
+    TrackedEditRequest {
+        request: edit_bot_message(bot, msg.chat.id, msg.id, text),
+        bot: bot.clone(),
+        chat_id: msg.chat.id,
+        thread_id: msg.thread_id,
+        messages,
     }
+}
 
-    InlineKeyboardMarkup::new(keyboard)
+// Wrapper function to send a bot message formatted with MarkdownV2
+// All formatted messages use MarkdownV2, and all others are sent as plain text
+// Any user content in the text must be escaped with escape_markdown
+pub fn send_markdown_message(
+    bot: &Bot,
+    msg: &Message,
+    text: String,
+) -> RetryRequest<JsonRequest<SendMessage>> {
+    send_bot_message(bot, msg, text).parse_mode(ParseMode::MarkdownV2)
 }
 
-// Make debt selection keyboard
-pub fn make_keyboard_debt_selection() -> InlineKeyboardMarkup {
-    let buttons = vec!["Equal", "Exact", "Proportion"];
-    make_keyboard(buttons, Some(1))
+// Wrapper function to edit a bot message formatted with MarkdownV2
+pub fn edit_markdown_message<C, T>(
+    bot: &Bot,
+    chat_id: C,
+    message_id: MessageId,
+    text: T,
+) -> RetryRequest<JsonRequest<EditMessageText>>
+where
+    C: Into<Recipient>,
+    T: Into<String>,
+{
+    edit_bot_message(bot, chat_id, message_id, text).parse_mode(ParseMode::MarkdownV2)
 }
 
-// Displays a username with the '@' symbol.
-pub fn display_username(username: &str) -> String {
-    format!("@{}", username)
+// Escapes text to be shown as is in a MarkdownV2 message, such as descriptions and usernames
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if MARKDOWN_RESERVED_CHARACTERS.contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
-// Ensures that a username has a leading '@'.
-pub fn parse_username(username: &str) -> Result<String, BotError> {
-    let text: &str;
-    if username.starts_with('@') {
-        text = username.trim_start_matches('@');
-    } else {
-        text = username;
+// Erases all messages tracked in an operation, if the chat has erase messages turned on
+// Called by all handlers when ending an operation, whether completed or cancelled
+// Messages that cannot be deleted, such as those already deleted by users, are skipped
+// If the bot is not allowed to delete messages, they are shrunk into a compact summary instead
+// User messages are only erased with erase user messages on too, and never shrunk
+pub async fn cleanup_messages(
+    redis: &Redis,
+    bot: &Bot,
+    chat_id: &str,
+    messages: MessageTracker,
+) -> Result<(), BotError> {
+    if !is_erase_messages(redis, chat_id).await {
+        return Ok(());
     }
 
-    if text.split_whitespace().count() == 1 && text.len() >= 5 {
-        let re = Regex::new(r"^[a-zA-Z0-9_]+$");
-        if let Ok(re) = re {
+    if !messages.user_messages.is_empty() && is_erase_user_messages(redis, chat_id).await {
+        delete_user_messages(redis, bot, chat_id, &messages.user_messages).await;
+    }
+
+    let cached = retrieve_bot_can_delete(redis, chat_id)
+        .await
+        .unwrap_or(None);
+    let mut can_delete = cached.unwrap_or(true);
+    let compact = t(redis, chat_id, CLEANUP_COMPACT, &[]).await;
+    for message in messages.messages {
+        if can_delete {
+            match bot.delete_message(chat_id.to_string(), message).await {
+                Ok(_) => {
+                    if cached.is_none() {
+                        cache_bot_can_delete(redis, chat_id, true).await;
+                    }
+                    continue;
+                }
+                Err(RequestError::Api(ApiError::MessageCantBeDeleted)) => {
+                    can_delete = false;
+                    cache_bot_can_delete(redis, chat_id, false).await;
+                    notify_missing_delete_permission(redis, bot, chat_id).await;
+                }
+                Err(err) => {
+                    log::warn!(
+                        "Cleanup Messages - Failed to delete message {} in chat {}: {}",
+                        message,
+                        chat_id,
+                        err.to_string()
+                    );
+                    continue;
+                }
+            }
+        }
+
+        if let Err(err) = bot
+            .edit_message_text(chat_id.to_string(), message, &compact)
+            .await
+        {
+            log::warn!(
+                "Cleanup Messages - Failed to compact message {} in chat {}: {}",
+                message,
+                chat_id,
+                err.to_string()
+            );
+        }
+    }
+    Ok(())
+}
+
+// Deletes user messages tracked in an operation, where the bot has permission to
+// Stops at the first message that cannot be deleted for lack of permission,
+// as the bot messages are cleaned up after, and notify the chat if needed
+async fn delete_user_messages(
+    redis: &Redis,
+    bot: &Bot,
+    chat_id: &str,
+    user_messages: &[MessageId],
+) {
+    if retrieve_bot_can_delete(redis, chat_id)
+        .await
+        .unwrap_or(None)
+        == Some(false)
+    {
+        return;
+    }
+
+    for message in user_messages {
+        match bot.delete_message(chat_id.to_string(), *message).await {
+            Ok(_) => {}
+            Err(RequestError::Api(ApiError::MessageCantBeDeleted)) => return,
+            Err(err) => {
+                log::warn!(
+                    "Cleanup Messages - Failed to delete user message {} in chat {}: {}",
+                    message,
+                    chat_id,
+                    err.to_string()
+                );
+            }
+        }
+    }
+}
+
+// Caches whether the bot can delete messages in a chat, logging any failure
+async fn cache_bot_can_delete(redis: &Redis, chat_id: &str, can_delete: bool) {
+    if let Err(err) = update_bot_can_delete(redis, chat_id, can_delete, PERMISSION_CACHE_TTL).await
+    {
+        log::error!(
+            "Cleanup Messages - Failed to cache delete permission for chat {}: {}",
+            chat_id,
+            err.to_string()
+        );
+    }
+}
+
+// Tells a chat how to let the bot delete messages, only the first time it is found missing
+async fn notify_missing_delete_permission(redis: &Redis, bot: &Bot, chat_id: &str) {
+    match mark_bot_permission_notified(redis, chat_id).await {
+        Ok(true) => {
+            log::info!(
+                "Cleanup Messages - Bot lacks delete permission in chat {}, notifying chat",
+                chat_id
+            );
+            if let Err(err) = with_retry(bot.send_message(
+                chat_id.to_string(),
+                t(redis, chat_id, DELETE_PERMISSION, &[]).await,
+            ))
+            .await
+            {
+                log::error!(
+                    "Cleanup Messages - Failed to notify chat {} of delete permission: {}",
+                    chat_id,
+                    err.to_string()
+                );
+            }
+        }
+        Ok(false) => {}
+        Err(err) => {
+            log::error!(
+                "Cleanup Messages - Failed to check delete permission notice for chat {}: {}",
+                chat_id,
+                err.to_string()
+            );
+        }
+    }
+}
+
+// Gets the verbosity from its name, ignoring case
+pub fn get_verbosity(name: &str) -> Option<Verbosity> {
+    VERBOSITIES
+        .iter()
+        .find(|(verbosity, _)| verbosity.eq_ignore_ascii_case(name.trim()))
+        .map(|(_, verbosity)| *verbosity)
+}
+
+// Retrieves the verbosity of a chat, used when completing actions
+pub async fn retrieve_verbosity(redis: &Redis, chat_id: &str) -> Verbosity {
+    let setting = ChatSetting::Verbosity(None);
+    if let Ok(ChatSetting::Verbosity(Some(verbosity))) =
+        get_chat_setting(redis, chat_id, setting).await
+    {
+        if let Some(verbosity) = get_verbosity(&verbosity) {
+            return verbosity;
+        }
+    }
+
+    Verbosity::Verbose
+}
+
+// Gets the rounding strategy from its name, ignoring case and separators
+pub fn get_rounding(name: &str) -> Option<Rounding> {
+    let name = name.replace([' ', '-', '_'], "");
+    ROUNDINGS
+        .iter()
+        .find(|(rounding, _)| rounding.eq_ignore_ascii_case(&name))
+        .map(|(_, rounding)| *rounding)
+}
+
+// Retrieves the rounding strategy of a chat, used when splitting payments
+// For round robin, also moves on the offset of the chat
+pub async fn retrieve_rounding(redis: &Redis, chat_id: &str) -> Rounding {
+    let setting = ChatSetting::Rounding(None);
+    if let Ok(ChatSetting::Rounding(Some(rounding))) =
+        get_chat_setting(redis, chat_id, setting).await
+    {
+        match get_rounding(&rounding) {
+            Some(Rounding::RoundRobin(_)) => match retrieve_rounding_offset(redis, chat_id).await {
+                Ok(offset) => return Rounding::RoundRobin(offset),
+                Err(err) => {
+                    log::error!(
+                        "Rounding - Failed to retrieve round robin offset for chat {}: {}",
+                        chat_id,
+                        err.to_string()
+                    );
+                }
+            },
+            Some(rounding) => return rounding,
+            None => {}
+        }
+    }
+
+    Rounding::First
+}
+
+// Retrieves the first day of the week of a chat, used when grouping by weeks
+pub async fn retrieve_week_start(redis: &Redis, chat_id: &str) -> WeekStart {
+    let setting = ChatSetting::WeekStart(None);
+    if let Ok(ChatSetting::WeekStart(Some(week_start))) =
+        get_chat_setting(redis, chat_id, setting).await
+    {
+        if let Some(week_start) = parse_week_start(&week_start) {
+            return week_start;
+        }
+    }
+
+    WeekStart::Monday
+}
+
+// Reacts to a message with an emoji
+// Called directly through the Bot API, as reactions are not supported by teloxide yet
+// Returns false if the reaction could not be set
+async fn react_to_message(bot: &Bot, msg: &Message, emoji: &str) -> bool {
+    let url = match bot
+        .api_url()
+        .join(&format!("bot{}/setMessageReaction", bot.token()))
+    {
+        Ok(url) => url,
+        Err(_) => return false,
+    };
+    let body = serde_json::json!({
+        "chat_id": msg.chat.id.0,
+        "message_id": msg.id.0,
+        "reaction": [{ "type": "emoji", "emoji": emoji }],
+    });
+
+    match reqwest::Client::new()
+        .post(url.as_str())
+        .json(&body)
+        .send()
+        .await
+    {
+        Ok(response) if response.status().is_success() => true,
+        Ok(response) => {
+            log::warn!(
+                "Reaction - Failed to react to message {} in chat {}: {}",
+                msg.id,
+                msg.chat.id,
+                response.status()
+            );
+            false
+        }
+        Err(err) => {
+            log::warn!(
+                "Reaction - Failed to react to message {} in chat {}: {}",
+                msg.id,
+                msg.chat.id,
+                err.to_string()
+            );
+            false
+        }
+    }
+}
+
+/* Sends the messages of a completed action, according to the verbosity of the chat.
+ * Verbose sends all the given messages, while compact sends only the one-line summary.
+ * Silent only reacts to the message with an emoji, untracking it so that it is not erased,
+ * and falls back to the summary if the reaction fails.
+ * Returns the ID of the message confirming the action, being the first one sent,
+ * or the reacted message if none were sent.
+ */
+pub async fn send_completion_messages(
+    redis: &Redis,
+    bot: &Bot,
+    msg: &Message,
+    messages: Option<&mut MessageTracker>,
+    summary: String,
+    details: Vec<String>,
+) -> Result<MessageId, BotError> {
+    match retrieve_verbosity(redis, &msg.chat.id.to_string()).await {
+        Verbosity::Verbose => {
+            let mut confirmation = None;
+            for detail in details {
+                let sent = send_bot_message(bot, msg, detail).await?;
+                confirmation.get_or_insert(sent.id);
+            }
+            Ok(confirmation.unwrap_or(msg.id))
+        }
+        Verbosity::Compact => Ok(send_bot_message(bot, msg, summary).await?.id),
+        Verbosity::Silent => {
+            if react_to_message(bot, msg, COMPLETION_REACTION).await {
+                if let Some(messages) = messages {
+                    messages.untrack(msg.id);
+                }
+                Ok(msg.id)
+            } else {
+                Ok(send_bot_message(bot, msg, summary).await?.id)
+            }
+        }
+    }
+}
+
+// Retrieves the language code of a chat, or the default if not set
+pub async fn retrieve_language(redis: &Redis, chat_id: &str) -> String {
+    match get_chat_setting(redis, chat_id, ChatSetting::Language(None)).await {
+        Ok(ChatSetting::Language(Some(language))) => language,
+        _ => LANGUAGE_DEFAULT.0.to_string(),
+    }
+}
+
+// Translates a message into the language of a chat, substituting in the arguments
+pub async fn t(redis: &Redis, chat_id: &str, key: &str, args: &[(&str, &str)]) -> String {
+    let language = retrieve_language(redis, chat_id).await;
+    translate(&language, key, args)
+}
+
+// Translates text into the language of a chat
+pub async fn t_text(redis: &Redis, chat_id: &str, text: &Text) -> String {
+    text.translate(&retrieve_language(redis, chat_id).await)
+}
+
+// Translates a question and the instructions for answering it into the language of a chat
+pub async fn t_prompt(
+    redis: &Redis,
+    chat_id: &str,
+    prompt: &str,
+    args: &[(&str, &str)],
+    instructions: &str,
+) -> String {
+    let language = retrieve_language(redis, chat_id).await;
+    format!(
+        "{}\n\n{}",
+        translate(&language, prompt, args),
+        translate(&language, instructions, &[])
+    )
+}
+
+/* Maps an error into a message for the users of a chat, in the chat's language.
+ * Errors that users can act on, such as invalid input, rate limits, or unavailable exchange rates,
+ * each get their own message. Any other error gets a generic one.
+ * Does not log the error, which is left to the handler, as it knows the context.
+ */
+pub async fn error_to_user_message(redis: &Redis, err: &BotError, chat_id: &str) -> String {
+    match err {
+        BotError::UserError(reply) => t_text(redis, chat_id, reply).await,
+        BotError::ProcessError(ProcessError::ValidationError(reason)) => {
+            let language = retrieve_language(redis, chat_id).await;
+            let reason = reason.translate(&language);
+            translate(&language, VALIDATION_ERROR, &[("reason", &reason)])
+        }
+        BotError::ProcessError(ProcessError::RateLimitError()) => {
+            t(redis, chat_id, RATE_LIMIT_ERROR, &[]).await
+        }
+        BotError::ProcessError(ProcessError::ConversionError(_)) => {
+            t(redis, chat_id, CONVERSION_ERROR, &[]).await
+        }
+        BotError::ProcessError(ProcessError::CrudError(_)) => {
+            t(redis, chat_id, UNKNOWN_ERROR, &[]).await
+        }
+        BotError::RequestError(_) => t(redis, chat_id, TELEGRAM_ERROR, &[]).await,
+    }
+}
+
+// Checks if Erase Messages setting is enabled
+pub async fn is_erase_messages(redis: &Redis, chat_id: &str) -> bool {
+    matches!(
+        get_chat_setting(redis, chat_id, ChatSetting::EraseMessages(None)).await,
+        Ok(ChatSetting::EraseMessages(Some(true)))
+    )
+}
+
+// Checks if Erase User Messages setting is enabled
+pub async fn is_erase_user_messages(redis: &Redis, chat_id: &str) -> bool {
+    matches!(
+        get_chat_setting(redis, chat_id, ChatSetting::EraseUserMessages(None)).await,
+        Ok(ChatSetting::EraseUserMessages(Some(true)))
+    )
+}
+
+// Retrieves the currency given a currency code.
+pub fn get_currency(code: &str) -> Result<Currency, BotError> {
+    let currency = get_currency_from_code(code);
+    match currency {
+        Some(currency) => Ok(currency),
+        None => Err(BotError::UserError(Text::new(UNKNOWN_CURRENCY))),
+    }
+}
+
+// Retrieves the default currency of a chat. Does not return an error, assumes default.
+pub async fn get_chat_default_currency(redis: &Redis, chat_id: &str) -> Currency {
+    let setting = ChatSetting::DefaultCurrency(None);
+    let currency = get_chat_setting(redis, chat_id, setting).await;
+    // Skips error, assumes default
+    if let Ok(ChatSetting::DefaultCurrency(Some(currency))) = currency {
+        if let Ok(currency) = get_currency(&currency) {
+            return currency;
+        }
+    }
+    get_default_currency()
+}
+
+// Processes and retrieves appropriate valid currencies for balances and spendings.
+pub async fn process_valid_currencies(
+    redis: &Redis,
+    chat_id: &str,
+    sender_id: &str,
+    option: StatementOption,
+    default_currency: String,
+) -> Vec<String> {
+    let mut valid_currencies = match retrieve_valid_currencies(redis, chat_id).await {
+        Ok(currencies) => currencies,
+        Err(_) => {
+            log::error!(
+                "View Spendings - User {} failed to retrieve valid currencies for group {}",
+                sender_id,
+                chat_id
+            );
+            vec![]
+        }
+    };
+
+    valid_currencies.retain(|x| x != CURRENCY_DEFAULT.0 && x != &default_currency);
+
+    if let StatementOption::Currency(ref curr) = option {
+        valid_currencies.retain(|x| x != curr);
+    }
+
+    // Add back default currency button if not NIL, and currently not default
+    if default_currency != CURRENCY_DEFAULT.0 {
+        if let StatementOption::Currency(ref curr) = option {
+            if curr != &default_currency {
+                valid_currencies.push(default_currency.clone());
+            }
+        } else if !valid_currencies.is_empty() {
+            // Adds back default currency on convert, only if there are also other
+            // currencies. Else, the converted is already equal to the default.
+            valid_currencies.push(default_currency.clone());
+        }
+    }
+
+    // Special buttons
+    let conversion_button = format!("Convert To {default_currency}");
+    // Add conversion button only if not currently on convert, and have default currency
+    if option != StatementOption::ConvertCurrency
+        && default_currency != CURRENCY_DEFAULT.0
+        && !valid_currencies.is_empty()
+    {
+        valid_currencies.push(conversion_button);
+        // Add no currency button if no default currency, and not currently NIL
+    } else if default_currency == CURRENCY_DEFAULT.0 {
+        if let StatementOption::Currency(ref curr) = option {
+            if curr != CURRENCY_DEFAULT.0 {
+                valid_currencies.push("No Currency".to_string());
+            }
+        }
+    }
+
+    valid_currencies
+}
+
+// Determines the statement options to show balances for at once, based on the chat settings.
+// Takes in the valid currencies of what is being shown.
+pub async fn get_statement_options(
+    redis: &Redis,
+    chat_id: &str,
+    mut currencies: Vec<String>,
+) -> Vec<StatementOption> {
+    let conversion =
+        match get_chat_setting(redis, chat_id, ChatSetting::CurrencyConversion(None)).await {
+            Ok(ChatSetting::CurrencyConversion(Some(value))) => value,
+            _ => false,
+        };
+    if conversion {
+        return vec![StatementOption::ConvertCurrency];
+    }
+
+    let default_currency =
+        match get_chat_setting(redis, chat_id, ChatSetting::DefaultCurrency(None)).await {
+            Ok(ChatSetting::DefaultCurrency(Some(currency))) => currency,
+            _ => CURRENCY_DEFAULT.0.to_string(),
+        };
+
+    // Balances without currency are shown together with the default currency, if any
+    if default_currency != CURRENCY_DEFAULT.0 {
+        currencies.retain(|curr| curr != CURRENCY_DEFAULT.0 && curr != &default_currency);
+        currencies.insert(0, default_currency);
+    } else if currencies.is_empty() {
+        currencies.push(default_currency);
+    }
+
+    currencies
+        .into_iter()
+        .map(StatementOption::Currency)
+        .collect()
+}
+
+// Converts an amount from base value to actual representation in currency.
+pub fn display_amount(amount: i64, decimal_places: i32) -> String {
+    if decimal_places == 0 {
+        return amount.to_string();
+    } else if amount == 0 {
+        return "0".to_string();
+    }
+
+    // Amount is not 0, and decimal places are not 0
+    let factor = 10.0_f64.powi(decimal_places);
+    let actual_amount = amount as f64 / factor;
+    format!(
+        "{:.decimals$}",
+        actual_amount,
+        decimals = decimal_places as usize
+    )
+}
+
+// Displays an amount together with its currency, in the currency format of the chat
+pub fn display_currency_amount(
+    amount: i64,
+    currency: Currency,
+    currency_format: CurrencyFormat,
+) -> String {
+    let code = if currency.0 == CURRENCY_DEFAULT.0 {
+        ""
+    } else {
+        &currency.0
+    };
+    format_currency_amount(&display_amount(amount, currency.1), code, currency_format)
+}
+
+// Gets the currency to be used when provided with the chosen currency, and the chat ID.
+pub async fn use_currency(redis: &Redis, currency: Currency, chat_id: &str) -> Currency {
+    let default_currency = get_chat_default_currency(redis, chat_id).await;
+    if currency.0 == CURRENCY_DEFAULT.0 {
+        default_currency
+    } else {
+        currency
+    }
+}
+
+// Displays the header for the balances, depending on the statement option applied.
+pub async fn display_balance_header(redis: &Redis, chat_id: &str, currency: &str) -> String {
+    let conversion =
+        match get_chat_setting(redis, chat_id, ChatSetting::CurrencyConversion(None)).await {
+            Ok(ChatSetting::CurrencyConversion(Some(value))) => value,
+            _ => false,
+        };
+    let default_currency =
+        match get_chat_setting(redis, chat_id, ChatSetting::DefaultCurrency(None)).await {
+            Ok(ChatSetting::DefaultCurrency(Some(currency))) => currency,
+            _ => CURRENCY_DEFAULT.0.to_string(),
+        };
+
+    let language = retrieve_language(redis, chat_id).await;
+    if conversion {
+        translate(
+            &language,
+            BALANCES_HEADER_CONVERTED,
+            &[("currency", &default_currency)],
+        )
+    } else if currency == CURRENCY_DEFAULT.0 {
+        if default_currency != CURRENCY_DEFAULT.0 {
+            translate(
+                &language,
+                BALANCES_HEADER_CURRENCY,
+                &[("currency", &default_currency)],
+            )
+        } else {
+            translate(&language, BALANCES_HEADER, &[])
+        }
+    } else {
+        translate(
+            &language,
+            BALANCES_HEADER_CURRENCY,
+            &[("currency", currency)],
+        )
+    }
+}
+
+// Displays how long ago conversion rates were fetched, such as "2h ago"
+fn display_rate_age(seconds: i64, language: &str) -> String {
+    match seconds {
+        seconds if seconds < 60 => translate(language, RATE_AGE_NOW, &[]),
+        seconds if seconds < 3600 => translate(
+            language,
+            RATE_AGE_MINUTES,
+            &[("count", &(seconds / 60).to_string())],
+        ),
+        seconds if seconds < 86400 => translate(
+            language,
+            RATE_AGE_HOURS,
+            &[("count", &(seconds / 3600).to_string())],
+        ),
+        seconds if seconds < 172800 => translate(language, RATE_AGE_DAY, &[]),
+        seconds => translate(
+            language,
+            RATE_AGE_DAYS,
+            &[("count", &(seconds / 86400).to_string())],
+        ),
+    }
+}
+
+// Displays when the conversion rates used for balances were fetched, and how long ago
+pub async fn display_rates_fetched(redis: &Redis, chat_id: &str, fetched_at: i64) -> String {
+    let language = retrieve_language(redis, chat_id).await;
+    let age = display_rate_age(Utc::now().timestamp() - fetched_at, &language);
+    match Utc.timestamp_opt(fetched_at, 0).single() {
+        Some(datetime) => {
+            let date = datetime
+                .with_timezone(&retrieve_time_zone(redis, chat_id).await)
+                .format("%-d %b %Y %H:%M")
+                .to_string();
+            translate(&language, RATES_AS_OF, &[("date", &date), ("age", &age)])
+        }
+        None => translate(&language, RATES_AGE, &[("age", &age)]),
+    }
+}
+
+// Displays a debt between two users, such as "@alice owes @bob: 10.00"
+fn display_debt(
+    debt: &Debt,
+    decimal_places: i32,
+    names: &HashMap<String, String>,
+    language: &str,
+) -> String {
+    translate(
+        language,
+        BALANCE_DEBT,
+        &[
+            ("debtor", &display_name(&debt.debtor, names)),
+            ("creditor", &display_name(&debt.creditor, names)),
+            ("amount", &display_amount(debt.amount, decimal_places)),
+        ],
+    )
+}
+
+// Displays balances in a more readable format. Now only shows in one currency.
+pub async fn display_balances(redis: &Redis, chat_id: &str, debts: &Vec<Debt>) -> String {
+    let language = retrieve_language(redis, chat_id).await;
+    let names = retrieve_display_names(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    let mut message = String::new();
+    for debt in debts {
+        let currency = get_currency(&debt.currency);
+        match currency {
+            Ok(currency) => {
+                message.push_str(&display_debt(debt, currency.1, &names, &language));
+                message.push('\n');
+            }
+            // Should not occur, since code is already processed and stored in database
+            Err(_err) => {
+                continue;
+            }
+        }
+    }
+
+    if debts.is_empty() {
+        translate(&language, BALANCES_NONE, &[])
+    } else {
+        message
+    }
+}
+
+// Displays balances in MarkdownV2, with the debts of overdue debtors in bold and marked with ⏰
+pub async fn display_balances_overdue(
+    redis: &Redis,
+    chat_id: &str,
+    debts: &Vec<Debt>,
+    overdue_debtors: &[String],
+) -> String {
+    if overdue_debtors.is_empty() {
+        return escape_markdown(&display_balances(redis, chat_id, debts).await);
+    }
+
+    let language = retrieve_language(redis, chat_id).await;
+    let names = retrieve_display_names(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    let mut message = String::new();
+    for debt in debts {
+        let currency = match get_currency(&debt.currency) {
+            Ok(currency) => currency,
+            // Should not occur, since code is already processed and stored in database
+            Err(_err) => continue,
+        };
+        let line = escape_markdown(&display_debt(debt, currency.1, &names, &language));
+        if is_debtor_overdue(&debt.debtor, overdue_debtors) {
+            message.push_str(&format!("*{line}* ⏰\n"));
+        } else {
+            message.push_str(&format!("{line}\n"));
+        }
+    }
+
+    if debts.is_empty() {
+        escape_markdown(&translate(&language, BALANCES_NONE, &[]))
+    } else {
+        message
+    }
+}
+
+// Checks if a debtor has debts from a payment that is past its due date
+pub fn is_debtor_overdue(debtor: &str, overdue_debtors: &[String]) -> bool {
+    overdue_debtors
+        .iter()
+        .any(|user| is_username_equal(user, debtor))
+}
+
+// Retrieves the users with overdue debts in a chat, as of today in the time zone of the chat
+pub async fn get_overdue_debtors(redis: &Redis, chat_id: &str) -> Vec<String> {
+    let time_zone = retrieve_time_zone(redis, chat_id).await;
+    let today = Utc::now()
+        .with_timezone(&time_zone)
+        .format("%Y-%m-%d")
+        .to_string();
+    retrieve_overdue_debtors(redis, chat_id, &today)
+        .await
+        .unwrap_or_default()
+}
+
+// Displays debts in a more readable format.
+pub async fn display_debts(
+    redis: &Redis,
+    chat_id: &str,
+    debts: &Vec<(String, i64)>,
+    decimal_places: i32,
+) -> String {
+    let names = retrieve_display_names(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    let mut message = String::new();
+    for debt in debts {
+        message.push_str(&format!(
+            "    {}: {}\n",
+            display_name(&debt.0, &names),
+            display_amount(debt.1, decimal_places),
+        ));
+    }
+    message
+}
+
+// Displays the description of a payment, which is hidden for private payments.
+pub fn display_description(payment: &Payment, language: &str) -> String {
+    if payment.is_private {
+        translate(language, PRIVATE_PAYMENT, &[])
+    } else {
+        payment.description.clone()
+    }
+}
+
+// Displays a description kept by the bot, where those of private payments are hidden.
+pub fn display_kept_description(description: &str, language: &str) -> String {
+    if description == PRIVATE_DESCRIPTION {
+        translate(language, PRIVATE_PAYMENT, &[])
+    } else {
+        description.to_string()
+    }
+}
+
+// Displays a single payment entry in a user-friendly format.
+pub async fn display_payment(
+    redis: &Redis,
+    payment: &Payment,
+    serial_num: usize,
+    time_zone: Tz,
+) -> String {
+    let language = retrieve_language(redis, &payment.chat_id).await;
+    let actual_currency = use_currency(redis, payment.currency.clone(), &payment.chat_id).await;
+    let mut details = String::new();
+    if let Some((currency, rate)) = &payment.rate {
+        let rate = translate(
+            &language,
+            PAYMENT_RATE,
+            &[
+                ("from", &actual_currency.0),
+                ("rate", &rate.to_string()),
+                ("to", currency),
+            ],
+        );
+        details.push_str(&format!("\n{rate}"));
+    }
+    if payment.is_transfer {
+        details.push_str(&format!(
+            "\n{}",
+            translate(&language, PAYMENT_TYPE_TRANSFER, &[])
+        ));
+    } else if is_adjustment_payment(redis, &payment.payment_id)
+        .await
+        .unwrap_or(false)
+    {
+        details.push_str(&format!(
+            "\n{}",
+            translate(&language, PAYMENT_TYPE_ADJUSTMENT, &[])
+        ));
+    }
+    details.push_str(
+        &display_payment_audit(
+            redis,
+            &payment.chat_id,
+            &payment.audit,
+            time_zone,
+            &language,
+        )
+        .await,
+    );
+    details.push_str(&display_payment_acknowledgements(redis, payment, &language).await);
+
+    let description = format!(
+        "{}{}{}",
+        display_description(payment, &language),
+        if payment.is_settled { " ✅" } else { "" },
+        if payment.is_disputed { " ⚠️" } else { "" },
+    );
+    let payment_details = translate(
+        &language,
+        PAYMENT_DETAILS,
+        &[
+            ("index", &serial_num.to_string()),
+            ("description", &description),
+            ("date", &reformat_datetime(&payment.datetime, time_zone)),
+            (
+                "payer",
+                &display_username(redis, &payment.chat_id, &payment.creditor).await,
+            ),
+            (
+                "total",
+                &display_currency_amount(
+                    payment.total,
+                    actual_currency.clone(),
+                    retrieve_currency_format(redis, &payment.chat_id).await,
+                ),
+            ),
+            ("details", &details),
+            (
+                "split",
+                &display_debts(redis, &payment.chat_id, &payment.debts, actual_currency.1).await,
+            ),
+        ],
+    );
+    format!("__________________________\n{payment_details}")
+}
+
+// Displays how many of those involved in a payment have confirmed it, if anyone has
+async fn display_payment_acknowledgements(
+    redis: &Redis,
+    payment: &Payment,
+    language: &str,
+) -> String {
+    match retrieve_payment_acknowledgements(
+        redis,
+        &payment.payment_id,
+        &payment.creditor,
+        &payment.debts,
+    )
+    .await
+    {
+        Ok((acknowledged, participants)) if acknowledged > 0 => {
+            let confirmed = translate(
+                language,
+                PAYMENT_CONFIRMED_BY,
+                &[
+                    ("count", &acknowledged.to_string()),
+                    ("total", &participants.to_string()),
+                ],
+            );
+            format!("\n{confirmed}")
+        }
+        _ => "".to_string(),
+    }
+}
+
+// Displays who added and last edited a payment, and when, if known
+async fn display_payment_audit(
+    redis: &Redis,
+    chat_id: &str,
+    audit: &PaymentAudit,
+    time_zone: Tz,
+    language: &str,
+) -> String {
+    let mut display = String::new();
+    if let (Some(username), Some(datetime)) = (&audit.created_by, &audit.created_at) {
+        let added = translate(
+            language,
+            PAYMENT_ADDED_BY,
+            &[
+                ("user", &display_username(redis, chat_id, username).await),
+                ("date", reformat_datetime(datetime, time_zone).trim()),
+            ],
+        );
+        display.push_str(&format!("\n{added}"));
+    }
+    if let (Some(username), Some(datetime)) = (&audit.last_edited_by, &audit.last_edited_at) {
+        let edited = translate(
+            language,
+            PAYMENT_EDITED_BY,
+            &[
+                ("user", &display_username(redis, chat_id, username).await),
+                ("date", reformat_datetime(datetime, time_zone).trim()),
+            ],
+        );
+        display.push_str(&format!("\n{edited}"));
+    }
+    display
+}
+
+// Make a keyboard, button menu, with the buttons labelled in a language.
+// Each button keeps its option as its data, so that callbacks are matched in any language.
+pub fn make_keyboard(
+    options: Vec<&str>,
+    columns: Option<usize>,
+    language: &str,
+) -> InlineKeyboardMarkup {
+    let mut keyboard: Vec<Vec<InlineKeyboardButton>> = Vec::new();
+    if let Some(col) = columns {
+        for chunk in options.chunks(col) {
+            let mut row: Vec<InlineKeyboardButton> = Vec::new();
+            for option in chunk {
+                row.push(InlineKeyboardButton::callback(
+                    translate_button(language, option),
+                    option.to_string(),
+                ));
+            }
+            keyboard.push(row);
+        }
+    } else {
+        for option in options {
+            keyboard.push(vec![InlineKeyboardButton::callback(
+                translate_button(language, option),
+                option,
+            )]);
+        }
+    }
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+// Make debt selection keyboard
+pub fn make_keyboard_debt_selection(language: &str) -> InlineKeyboardMarkup {
+    let buttons = vec![
+        "Equal",
+        "Exact",
+        "Proportion",
+        "Percent",
+        "Adjusted",
+        "Itemized",
+    ];
+    make_keyboard(buttons, Some(1), language)
+}
+
+// Make a keyboard for picking users, with selected users ticked.
+// Each user button has the username with '@' as its data.
+pub fn make_keyboard_user_picker(
+    users: &[String],
+    selected: &[String],
+    groups: &[(String, Vec<String>)],
+    language: &str,
+) -> InlineKeyboardMarkup {
+    let mut keyboard: Vec<Vec<InlineKeyboardButton>> = Vec::new();
+    for chunk in users.chunks(2) {
+        let mut row: Vec<InlineKeyboardButton> = Vec::new();
+        for user in chunk {
+            let label = if selected.iter().any(|sel| is_username_equal(sel, user)) {
+                format!("✅ {}", mention_username(user))
+            } else {
+                mention_username(user)
+            };
+            row.push(InlineKeyboardButton::callback(
+                label,
+                mention_username(user),
+            ));
+        }
+        keyboard.push(row);
+    }
+    for chunk in groups.chunks(2) {
+        keyboard.push(
+            chunk
+                .iter()
+                .map(|(name, _)| {
+                    InlineKeyboardButton::callback(
+                        format!("👥 {name}"),
+                        format!("{SPLIT_GROUP_CALLBACK_PREFIX}{name}"),
+                    )
+                })
+                .collect(),
+        );
+    }
+    keyboard.push(vec![
+        InlineKeyboardButton::callback(translate_button(language, "Everyone"), "Everyone"),
+        InlineKeyboardButton::callback(translate_button(language, "Done"), "Done"),
+    ]);
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+// Checks if a payment link is a URL, rather than a phone number
+pub fn is_payment_url(link: &str) -> bool {
+    matches!(Url::parse(link), Ok(url) if url.scheme() == "https" || url.scheme() == "http")
+}
+
+// Checks if a payment link is a PayPal.me link, which takes the amount at the end
+fn is_paypal_url(link: &str) -> bool {
+    match Url::parse(link) {
+        Ok(url) => url.host_str().is_some_and(|host| {
+            host.trim_start_matches("www.")
+                .eq_ignore_ascii_case("paypal.me")
+        }),
+        Err(_) => false,
+    }
+}
+
+// Makes the URL to pay an amount with a payment link, with the amount filled in where possible.
+// Links may mark where the amount and currency go with {amount} and {currency},
+// and PayPal.me links have them added at the end.
+// Returns None if the link is not a URL, such as a phone number.
+pub fn make_payment_url(link: &str, amount: i64, currency: &Currency) -> Option<Url> {
+    if !is_payment_url(link) {
+        return None;
+    }
+
+    let amount = display_amount(amount, currency.1);
+    let code = if currency.0 == CURRENCY_DEFAULT.0 {
+        ""
+    } else {
+        currency.0.as_str()
+    };
+    let url = if link.contains("{amount}") {
+        link.replace("{amount}", &amount)
+            .replace("{currency}", code)
+    } else if is_paypal_url(link) {
+        format!("{}/{}{}", link.trim_end_matches('/'), amount, code)
+    } else {
+        link.to_string()
+    };
+    Url::parse(&url).ok()
+}
+
+// Makes the means to pay each debt with the payment link of its creditor, if any.
+// Returns the lines showing phone numbers to pay, and the buttons to pay with URLs.
+pub async fn make_payment_links(
+    redis: &Redis,
+    chat_id: &str,
+    debts: &[Debt],
+) -> (String, Vec<Vec<InlineKeyboardButton>>) {
+    let mut creditors: Vec<String> = Vec::new();
+    for debt in debts {
+        if !creditors
+            .iter()
+            .any(|creditor| is_username_equal(creditor, &debt.creditor))
+        {
+            creditors.push(debt.creditor.clone());
+        }
+    }
+    let links = retrieve_payment_links(redis, &creditors)
+        .await
+        .unwrap_or_default();
+    if links.is_empty() {
+        return (String::new(), Vec::new());
+    }
+
+    let language = retrieve_language(redis, chat_id).await;
+    let names = retrieve_display_names(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    let mut numbers = String::new();
+    for creditor in &creditors {
+        if let Some(link) = links.get(&creditor.to_lowercase()) {
+            if !is_payment_url(link) {
+                numbers.push_str(&translate(
+                    &language,
+                    PAYMENT_LINK_NUMBER,
+                    &[
+                        ("creditor", &display_name(creditor, &names)),
+                        ("link", link),
+                    ],
+                ));
+                numbers.push('\n');
+            }
+        }
+    }
+
+    let currency_format = retrieve_currency_format(redis, chat_id).await;
+    let mut buttons: Vec<Vec<InlineKeyboardButton>> = Vec::new();
+    for debt in debts {
+        if buttons.len() >= PAYMENT_LINK_BUTTONS_MAX {
+            break;
+        }
+        let currency = match get_currency(&debt.currency) {
+            Ok(currency) => currency,
+            Err(_) => continue,
+        };
+        let url = match links.get(&debt.creditor.to_lowercase()) {
+            Some(link) => make_payment_url(link, debt.amount, &currency),
+            None => None,
+        };
+        if let Some(url) = url {
+            let label = format!(
+                "💳 {} → {}: {}",
+                display_name(&debt.debtor, &names),
+                display_name(&debt.creditor, &names),
+                display_currency_amount(debt.amount, currency, currency_format)
+            );
+            buttons.push(vec![InlineKeyboardButton::url(label, url)]);
+        }
+    }
+
+    (numbers, buttons)
+}
+
+// Gets the users to pick from for a payment, which are all users in the chat,
+// together with any other users already part of the payment.
+pub async fn get_picker_users(
+    redis: &Redis,
+    chat_id: &str,
+    mut others: Vec<String>,
+) -> Vec<String> {
+    let mut users = retrieve_chat_users(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    for (_, members) in retrieve_chat_split_groups(redis, chat_id).await {
+        others.extend(members);
+    }
+    for other in others {
+        if !users.iter().any(|user| is_username_equal(user, &other)) {
+            users.push(other);
+        }
+    }
+    users
+}
+
+// Gets the split groups of a chat to show in the user picker, if any.
+pub async fn retrieve_chat_split_groups(
+    redis: &Redis,
+    chat_id: &str,
+) -> Vec<(String, Vec<String>)> {
+    retrieve_split_groups(redis, chat_id)
+        .await
+        .unwrap_or_default()
+}
+
+// Toggles the selection of a user, or of everyone, based on the button pressed.
+// A split group replaces the selection with the members of the group.
+// Selected users are kept in the same order as all users.
+pub fn toggle_picker_users(
+    users: &[String],
+    selected: &[String],
+    button: &str,
+    groups: &[(String, Vec<String>)],
+) -> Vec<String> {
+    if let Some(name) = button.strip_prefix(SPLIT_GROUP_CALLBACK_PREFIX) {
+        let members = match groups.iter().find(|(group, _)| group == name) {
+            Some((_, members)) => members,
+            None => return selected.to_vec(),
+        };
+        return users
+            .iter()
+            .filter(|user| members.iter().any(|member| is_username_equal(member, user)))
+            .cloned()
+            .collect();
+    }
+
+    let is_selected = |user: &String| selected.iter().any(|sel| is_username_equal(sel, user));
+    if button == "Everyone" {
+        if users.iter().all(is_selected) {
+            return Vec::new();
+        }
+        return users.to_vec();
+    }
+
+    let toggled = button.trim_start_matches('@');
+    users
+        .iter()
+        .filter(|user| {
+            if is_username_equal(user, toggled) {
+                !is_selected(user)
+            } else {
+                is_selected(user)
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+// Displays a username with the '@' symbol, which mentions the user.
+pub fn mention_username(username: &str) -> String {
+    format!("@{}", username)
+}
+
+// Displays a username, using its display name in the chat if it has one.
+pub fn display_name(username: &str, names: &HashMap<String, String>) -> String {
+    match names.get(&username.to_lowercase()) {
+        Some(name) => name.clone(),
+        None => mention_username(username),
+    }
+}
+
+// Displays a username, using its display name in the chat if it has one.
+pub async fn display_username(redis: &Redis, chat_id: &str, username: &str) -> String {
+    let names = retrieve_display_names(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    display_name(username, &names)
+}
+
+// Displays multiple usernames, using their display names in the chat if they have any.
+pub async fn display_usernames(redis: &Redis, chat_id: &str, usernames: &[String]) -> Vec<String> {
+    let names = retrieve_display_names(redis, chat_id)
+        .await
+        .unwrap_or_default();
+    usernames
+        .iter()
+        .map(|username| display_name(username, &names))
+        .collect()
+}
+
+// Ensures that a username has a leading '@'.
+pub fn parse_username(username: &str) -> Result<String, BotError> {
+    let text = if username.starts_with('@') {
+        username.trim_start_matches('@')
+    } else {
+        username
+    };
+
+    if text.split_whitespace().count() == 1
+        && (USERNAME_MIN_LENGTH..=USERNAME_MAX_LENGTH).contains(&text.len())
+    {
+        let re = Regex::new(r"^[a-zA-Z0-9_]+$");
+        if let Ok(re) = re {
             if re.captures(text).is_some() {
                 return Ok(text.to_string());
             }
         }
     }
 
-    Err(BotError::UserError(
-        "Uh-oh! ❌ Please give me a valid username!".to_string(),
-    ))
+    Err(BotError::UserError(Text::new(INVALID_USERNAME)))
+}
+
+// Validates the description of a payment, returning it without surrounding whitespace.
+pub fn validate_description(text: &str) -> Result<String, BotError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(BotError::UserError(Text::new(DESCRIPTION_MISSING)));
+    }
+    if text.chars().count() > DESCRIPTION_MAX_LENGTH {
+        return Err(BotError::UserError(
+            Text::new(DESCRIPTION_TOO_LONG).arg("max", DESCRIPTION_MAX_LENGTH),
+        ));
+    }
+    Ok(text.to_string())
+}
+
+// Validates the number of people splitting a payment.
+pub fn validate_debtors(debts: &[(String, i64)]) -> Result<(), BotError> {
+    if debts.len() > DEBTORS_MAX {
+        return Err(BotError::UserError(
+            Text::new(TOO_MANY_DEBTORS).arg("max", DEBTORS_MAX),
+        ));
+    }
+    Ok(())
+}
+
+// Validates that a chat has room for another payment.
+pub async fn validate_payment_count(redis: &Redis, chat_id: &str) -> Result<(), BotError> {
+    if retrieve_payment_count(redis, chat_id).await? >= CHAT_PAYMENTS_MAX {
+        return Err(BotError::UserError(
+            Text::new(TOO_MANY_PAYMENTS).arg("max", CHAT_PAYMENTS_MAX),
+        ));
+    }
+    Ok(())
 }
 
 // Parse an amount. Reads a string, returns i64 based on currency.
-pub fn parse_amount(text: &str, decimal_places: i32) -> Result<i64, BotError> {
-    let factor = 10.0_f64.powi(decimal_places);
-    let amount = match text.parse::<i64>() {
-        Ok(val) => (val as f64 * factor).round() as i64,
-        Err(_) => match text.parse::<f64>() {
-            Ok(val) => (val * factor).round() as i64,
-            Err(_) => {
-                return Err(BotError::UserError(
-                    "Uh-oh! ❌ Please give me a valid number!".to_string(),
-                ))
-            }
-        },
+// Amounts with more decimal places than the currency has are rejected, rather than rounded.
+pub fn parse_amount(text: &str, currency: &Currency) -> Result<i64, BotError> {
+    let value = match text.parse::<f64>() {
+        Ok(val) if val.is_finite() => val,
+        _ => return Err(BotError::UserError(Text::new(INVALID_NUMBER))),
     };
+    if count_decimal_places(text) > currency.1 {
+        return Err(BotError::UserError(if currency.1 == 0 {
+            Text::new(NO_DECIMAL_PLACES).arg("currency", &currency.0)
+        } else {
+            Text::new(TOO_MANY_DECIMAL_PLACES).arg("decimals", currency.1)
+        }));
+    }
+
+    validate_amount(Money::from_f64(currency, value)?)
+}
 
-    if amount > MAX_VALUE {
-        Err(BotError::UserError(
-            "Uh-oh! 🥺 This number is too large for me to handle!".to_string(),
-        ))
-    } else if amount <= 0 {
-        Err(BotError::UserError(
-            "Uh-oh! ❌ Please give me a positive number!".to_string(),
-        ))
+// Checks that an amount is positive, returning it in base value
+fn validate_amount(money: Money) -> Result<i64, BotError> {
+    if money.amount() <= 0 {
+        Err(BotError::UserError(Text::new(NOT_POSITIVE)))
     } else {
-        Ok(amount)
+        Ok(money.amount())
+    }
+}
+
+// Parse an amount written as an arithmetic expression, such as 12.50+3.20 or 45/3.
+// Supports +, -, *, / and parentheses, and is evaluated before being read in the currency.
+// The result is rounded to the decimal places of the currency, as division may not be exact.
+pub fn parse_amount_expression(text: &str, currency: &Currency) -> Result<i64, BotError> {
+    if text.chars().count() > EXPRESSION_MAX_LENGTH {
+        return Err(BotError::UserError(
+            Text::new(EXPRESSION_TOO_LONG).arg("max", EXPRESSION_MAX_LENGTH),
+        ));
+    }
+
+    let tokens: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut position = 0;
+    let value = evaluate_sum(&tokens, &mut position)?;
+    if position < tokens.len() {
+        return Err(BotError::UserError(
+            Text::new(EXPRESSION_UNRECOGNIZED).arg("amount", text),
+        ));
+    }
+    validate_amount(Money::from_f64(currency, value)?)
+}
+
+// Evaluates terms added or subtracted together, from the given position in an expression
+fn evaluate_sum(tokens: &[char], position: &mut usize) -> Result<f64, BotError> {
+    let mut value = evaluate_product(tokens, position)?;
+    while let Some(operator @ ('+' | '-')) = tokens.get(*position) {
+        *position += 1;
+        let term = evaluate_product(tokens, position)?;
+        if *operator == '+' {
+            value += term;
+        } else {
+            value -= term;
+        }
+    }
+    Ok(value)
+}
+
+// Evaluates factors multiplied or divided together, from the given position in an expression
+fn evaluate_product(tokens: &[char], position: &mut usize) -> Result<f64, BotError> {
+    let mut value = evaluate_factor(tokens, position)?;
+    while let Some(operator @ ('*' | '×' | '/' | '÷')) = tokens.get(*position) {
+        *position += 1;
+        let factor = evaluate_factor(tokens, position)?;
+        if matches!(operator, '/' | '÷') {
+            if factor == 0.0 {
+                return Err(BotError::UserError(Text::new(DIVIDE_BY_ZERO)));
+            }
+            value /= factor;
+        } else {
+            value *= factor;
+        }
+    }
+    Ok(value)
+}
+
+// Evaluates a number, a negated factor, or an expression in brackets, in an expression
+fn evaluate_factor(tokens: &[char], position: &mut usize) -> Result<f64, BotError> {
+    match tokens.get(*position) {
+        Some('-') => {
+            *position += 1;
+            Ok(-evaluate_factor(tokens, position)?)
+        }
+        Some('(') => {
+            *position += 1;
+            let value = evaluate_sum(tokens, position)?;
+            if tokens.get(*position) != Some(&')') {
+                return Err(BotError::UserError(Text::new(UNCLOSED_BRACKET)));
+            }
+            *position += 1;
+            Ok(value)
+        }
+        _ => {
+            let start = *position;
+            while let Some(c) = tokens.get(*position) {
+                if !(c.is_ascii_digit() || *c == '.') {
+                    break;
+                }
+                *position += 1;
+            }
+            let number: String = tokens[start..*position].iter().collect();
+            match number.parse::<f64>() {
+                Ok(number) => Ok(number),
+                Err(_) => Err(BotError::UserError(Text::new(INVALID_NUMBER))),
+            }
+        }
     }
 }
 
@@ -431,60 +2078,199 @@ pub fn parse_float(text: &str) -> Result<f64, BotError> {
         Ok(val) => val,
         Err(_) => match text.parse::<i32>() {
             Ok(val) => val as f64,
-            Err(_) => {
-                return Err(BotError::UserError(
-                    "Uh-oh! ❌ Please give me a valid number!".to_string(),
-                ))
-            }
+            Err(_) => return Err(BotError::UserError(Text::new(INVALID_NUMBER))),
         },
     };
 
     if amount > MAX_VALUE as f64 {
-        Err(BotError::UserError(
-            "Uh-oh! 🥺 This number is too large for me to handle!".to_string(),
-        ))
+        Err(BotError::UserError(Text::new(AMOUNT_TOO_LARGE)))
     } else if amount <= 0.0 {
-        Err(BotError::UserError(
-            "Uh-oh! ❌ Please give me a positive number!".to_string(),
-        ))
+        Err(BotError::UserError(Text::new(NOT_POSITIVE)))
     } else {
         Ok(amount)
     }
 }
 
-// Parse a string representing an amount and a currency
-pub fn parse_currency_amount(text: &str) -> Result<(i64, Currency), BotError> {
-    let items = text.split_whitespace().collect::<Vec<&str>>();
-    if items.len() > 2 {
+// Parse a string representing a currency code.
+// If the chat only allows some currencies, the currency given must be one of them.
+pub fn parse_currency(text: &str, allowed_currencies: &[String]) -> Result<Currency, BotError> {
+    let currency = get_currency(text.trim())?;
+    if !allowed_currencies.is_empty() && !allowed_currencies.contains(&currency.0) {
         return Err(BotError::UserError(
-            "Uh-oh! ❌ I don't understand... Please use the following format!".to_string(),
+            Text::new(CURRENCY_NOT_ALLOWED).arg("currencies", allowed_currencies.join(", ")),
         ));
-    } else if items.len() == 1 {
-        let currency = get_default_currency();
-        let amount = parse_amount(items[0], currency.1)?;
-        Ok((amount, currency))
-    } else {
-        let currency = get_currency(&items[1])?;
-        let amount = parse_amount(items[0], currency.1)?;
-        Ok((amount, currency))
+    }
+    Ok(currency)
+}
+
+// Parse a string representing an amount and a currency, written in a number format.
+// The amount may contain spaces as thousands separators, so the currency is taken from the end.
+// If the chat only allows some currencies, the currency given must be one of them.
+pub fn parse_currency_amount(
+    text: &str,
+    number_format: NumberFormat,
+    allowed_currencies: &[String],
+) -> Result<(i64, Currency), BotError> {
+    let mut items = text.split_whitespace().collect::<Vec<&str>>();
+    let currency = match items.last() {
+        Some(item) if items.len() > 1 && item.chars().all(|c| c.is_ascii_alphabetic()) => {
+            let currency = parse_currency(item, allowed_currencies)?;
+            items.pop();
+            currency
+        }
+        _ => get_default_currency(),
+    };
+
+    let amount = match normalize_amount_text(&items.join(" "), number_format) {
+        Some(amount) => amount,
+        None => return Err(BotError::UserError(Text::new(FORMAT_UNRECOGNIZED))),
+    };
+    let amount = parse_amount(&amount, &currency)?;
+    Ok((amount, currency))
+}
+
+// Parse a string representing a total amount, followed by surcharges such as tip and tax.
+// Surcharges are like "+10% tip" or "+8 tax", with percentages taken of the amount before them.
+// Returns the grand total, together with the currency and surcharges.
+pub fn parse_total_amount(
+    text: &str,
+    number_format: NumberFormat,
+    allowed_currencies: &[String],
+) -> Result<(i64, Currency, Vec<Surcharge>), BotError> {
+    let mut parts = text.split('+');
+    let (subtotal, currency) = parse_currency_amount(
+        parts.next().unwrap_or(""),
+        number_format,
+        allowed_currencies,
+    )?;
+
+    let mut surcharges: Vec<Surcharge> = Vec::new();
+    for part in parts {
+        let words: Vec<&str> = part.split_whitespace().collect();
+        let (amount, description) = match words.split_first() {
+            Some(words) => words,
+            None => return Err(BotError::UserError(Text::new(SURCHARGE_MISSING_AMOUNT))),
+        };
+        let description = if description.is_empty() {
+            "surcharge".to_string()
+        } else {
+            description.join(" ")
+        };
+
+        let (number, percent) = match amount.strip_suffix('%') {
+            Some(number) => (number, true),
+            None => (*amount, false),
+        };
+        let number = match normalize_amount_text(number, number_format) {
+            Some(number) => number,
+            None => {
+                return Err(BotError::UserError(
+                    Text::new(SURCHARGE_UNRECOGNIZED).arg("description", description),
+                ))
+            }
+        };
+
+        let surcharge = if percent {
+            let percent = parse_float(&number)?;
+            Surcharge {
+                description,
+                percent: Some(percent),
+                amount: checked_from_f64(percent / 100.0 * subtotal as f64)?,
+            }
+        } else {
+            Surcharge {
+                description,
+                percent: None,
+                amount: parse_amount(&number, &currency)?,
+            }
+        };
+        surcharges.push(surcharge);
+    }
+
+    let total = checked_add(
+        subtotal,
+        checked_sum(surcharges.iter().map(|surcharge| surcharge.amount))?,
+    )?;
+
+    Ok((total, currency, surcharges))
+}
+
+// Splits off a conversion rate given after an amount with @, such as 50 USD @ 1.37.
+// The rate is the value of one unit of the currency, in the default currency of the chat.
+pub fn parse_conversion_rate(
+    text: &str,
+    number_format: NumberFormat,
+) -> Result<(&str, Option<f64>), BotError> {
+    let (amount, rate) = match text.split_once('@') {
+        Some(parts) => parts,
+        None => return Ok((text, None)),
+    };
+    let rate = match normalize_amount_text(rate, number_format) {
+        Some(rate) => parse_float(&rate)?,
+        None => return Err(BotError::UserError(Text::new(RATE_UNRECOGNIZED))),
+    };
+    Ok((amount.trim(), Some(rate)))
+}
+
+// Assigns the amount left over from an equal split, through smallest denomination.
+// The remainder is less than the number of users, so each user gets at most one more.
+fn assign_remainder(
+    debts: &mut Vec<(String, i64)>,
+    remainder: i64,
+    creditor: &Option<String>,
+    rounding: Rounding,
+) {
+    if remainder <= 0 || debts.is_empty() {
+        return;
+    }
+
+    let count = debts.len();
+    let indices: Vec<usize> = match (rounding, creditor) {
+        (Rounding::Payer, Some(creditor)) => {
+            match debts
+                .iter_mut()
+                .find(|debt| is_username_equal(&debt.0, creditor))
+            {
+                Some(debt) => debt.1 += remainder,
+                None => debts.push((creditor.clone(), remainder)),
+            }
+            return;
+        }
+        (Rounding::Random, _) => {
+            let mut keys: Vec<(u128, usize)> = (0..count)
+                .map(|i| (uuid::Uuid::new_v4().as_u128(), i))
+                .collect();
+            keys.sort();
+            keys.into_iter().map(|(_, i)| i).collect()
+        }
+        (Rounding::RoundRobin(offset), _) => {
+            (0..count).map(|i| (offset as usize + i) % count).collect()
+        }
+        _ => (0..count).collect(),
+    };
+
+    for i in indices.into_iter().cycle().take(remainder as usize) {
+        debts[i].1 += 1;
     }
 }
 
 // Parse and process a string to retrieve a list of debts, for split by equal amount.
-pub fn process_debts_equal(text: &str, total: Option<i64>) -> Result<Vec<(String, i64)>, BotError> {
+// The amount left over is assigned by the rounding strategy of the chat.
+pub fn process_debts_equal(
+    text: &str,
+    total: Option<i64>,
+    creditor: &Option<String>,
+    rounding: Rounding,
+) -> Result<Vec<(String, i64)>, BotError> {
     let mut users = text.split_whitespace().collect::<Vec<&str>>();
-    if users.len() == 0 {
-        return Err(BotError::UserError(
-            "Uh-oh! ❌ Please give me at least one username!".to_string(),
-        ));
+    if users.is_empty() {
+        return Err(BotError::UserError(Text::new(USERNAMES_MISSING)));
     }
 
     let total = match total {
         Some(val) => val,
         None => {
-            return Err(BotError::UserError(
-                "Uh-oh! ❌ The total amount isn't provided.".to_string(),
-            ));
+            return Err(BotError::UserError(Text::new(TOTAL_MISSING)));
         }
     };
 
@@ -498,156 +2284,490 @@ pub fn process_debts_equal(text: &str, total: Option<i64>) -> Result<Vec<(String
             accounted_users.insert(user.to_lowercase());
             i += 1;
         }
-    }
+    }
+
+    let amount = total / users.len() as i64;
+    let remainder = total - amount * users.len() as i64;
+
+    let mut debts: Vec<(String, i64)> = Vec::new();
+    for user in &users {
+        let username = parse_username(user)?;
+        let debt = (username.clone(), amount);
+        debts.push(debt);
+    }
+
+    assign_remainder(&mut debts, remainder, creditor, rounding);
+    validate_debtors(&debts)?;
+
+    Ok(debts)
+}
+
+// Groups the words of exact amounts by user, as usernames start with a letter or @,
+// and each amount may be an expression written over several words, such as 12.50 + 3.20.
+fn group_exact_amounts(text: &str) -> Vec<(&str, String)> {
+    let mut items: Vec<(&str, String)> = Vec::new();
+    for word in text.split_whitespace() {
+        let is_username = word.starts_with(|c: char| c == '@' || c.is_alphabetic());
+        match items.last_mut() {
+            Some((_, amount)) if !is_username => amount.push_str(word),
+            _ => items.push((word, String::new())),
+        }
+    }
+    items
+}
+
+// Parse and process a string to retrieve a list of debts, for split by exact amount.
+// Each amount may be an arithmetic expression, such as 12.50+3.20 or 45/3.
+pub fn process_debts_exact(
+    text: &str,
+    creditor: &Option<String>,
+    currency: Option<Currency>,
+    total: Option<i64>,
+) -> Result<Vec<(String, i64)>, BotError> {
+    if let Some(creditor) = creditor {
+        if let Some(total) = total {
+            if let Some(currency) = currency {
+                let mut debts: Vec<(String, i64)> = Vec::new();
+                let mut sum: i64 = 0;
+                let items = group_exact_amounts(text);
+                if items.is_empty() || items.iter().any(|(_, amount)| amount.is_empty()) {
+                    return Err(BotError::UserError(Text::new(FORMAT_UNRECOGNIZED)));
+                }
+
+                for (user, amount) in items {
+                    let username = parse_username(user)?;
+                    let amount = parse_amount_expression(&amount, &currency)?;
+                    sum = checked_add(sum, amount)?;
+
+                    let mut found = false;
+                    for debt in &mut debts {
+                        if debt.0 == username {
+                            debt.1 += amount;
+                            found = true;
+                            break;
+                        }
+                    }
+
+                    if !found {
+                        debts.push((username, amount));
+                    }
+                }
+
+                if sum > total {
+                    Err(BotError::UserError(Text::new(AMOUNTS_EXCEED_TOTAL)))
+                } else if sum < total {
+                    for debt in &mut debts {
+                        if debt.0 == *creditor {
+                            debt.1 += total - sum;
+                            return Ok(debts);
+                        }
+                    }
+
+                    debts.push((creditor.to_string(), total - sum));
+                    Ok(debts)
+                } else {
+                    Ok(debts)
+                }
+            } else {
+                Err(BotError::UserError(Text::new(CURRENCY_MISSING)))
+            }
+        } else {
+            Err(BotError::UserError(Text::new(TOTAL_MISSING)))
+        }
+    } else {
+        Err(BotError::UserError(Text::new(PAYER_MISSING)))
+    }
+}
+
+// Parses a string of usernames, each followed by a positive number, such as a ratio or percentage.
+// Numbers for the same user are added together.
+fn parse_debts_weighted(text: &str) -> Result<Vec<(String, f64)>, BotError> {
+    let items: Vec<&str> = text.split_whitespace().collect();
+    if !items.len().is_multiple_of(2) {
+        return Err(BotError::UserError(Text::new(FORMAT_UNRECOGNIZED)));
+    }
+
+    let mut users: Vec<String> = Vec::new();
+    let mut weights: Vec<f64> = Vec::new();
+
+    for i in (0..items.len()).step_by(2) {
+        let curr = parse_username(items[i])?;
+        let weight = parse_float(items[i + 1].trim_end_matches('%'))?;
+        let pos = users.iter().position(|u| is_username_equal(u, &curr));
+        match pos {
+            Some(pos) => {
+                weights[pos] += weight;
+            }
+            None => {
+                users.push(curr.to_string());
+                weights.push(weight);
+            }
+        }
+    }
+
+    Ok(users.into_iter().zip(weights).collect())
+}
+
+// Splits the total amount by the relative weight of each user.
+fn split_debts_weighted(debts_weighted: Vec<(String, f64)>, total: i64) -> Vec<(String, i64)> {
+    let sum: f64 = debts_weighted.iter().map(|debt| debt.1).sum();
+    let mut debts: Vec<(String, i64)> = Vec::new();
+
+    let mut exact_sum: i64 = 0;
+    for debt in &debts_weighted {
+        let amount = ((debt.1 / sum) * total as f64).round() as i64;
+        debts.push((debt.0.clone(), amount));
+        exact_sum += amount;
+    }
+
+    // Distribute the difference in amount to as many users as required through smallest denomination
+    let diff = total - exact_sum;
+    for i in 0..(diff).abs() {
+        debts[i as usize].1 += if diff > 0 { 1 } else { -1 };
+    }
+
+    debts
+}
+
+// Shares a surcharge proportionally to the amount each user owes, such as tip and tax.
+pub fn share_surcharge(debts: Vec<(String, i64)>, surcharge: i64) -> Vec<(String, i64)> {
+    let weights = debts
+        .iter()
+        .map(|(user, amount)| (user.clone(), *amount as f64))
+        .collect();
+    let shares = split_debts_weighted(weights, surcharge);
+    debts
+        .into_iter()
+        .zip(shares)
+        .map(|((user, amount), (_, share))| (user, amount + share))
+        .collect()
+}
+
+// Converts a total and its debts to the decimal places of another currency, keeping the same values.
+// When there are fewer decimal places, the debts are rounded such that they still sum to the total.
+pub fn convert_decimal_places(
+    total: i64,
+    debts: Vec<(String, i64)>,
+    from: &Currency,
+    to: &Currency,
+) -> (i64, Vec<(String, i64)>) {
+    if from.1 == to.1 {
+        return (total, debts);
+    }
+
+    let new_total = normalize_amount(to, total as f64 / 10.0_f64.powi(from.1));
+    let weights = debts
+        .into_iter()
+        .map(|(user, amount)| (user, amount as f64))
+        .collect();
+    (new_total, split_debts_weighted(weights, new_total))
+}
+
+// Parse and process a string to retrieve a list of debts, for split by ratio.
+pub fn process_debts_ratio(text: &str, total: Option<i64>) -> Result<Vec<(String, i64)>, BotError> {
+    let debts_ratioed = parse_debts_weighted(text)?;
+
+    let total = match total {
+        Some(val) => val,
+        None => {
+            return Err(BotError::UserError(Text::new(TOTAL_MISSING)));
+        }
+    };
+
+    Ok(split_debts_weighted(debts_ratioed, total))
+}
+
+// Parse and process a string to retrieve a list of debts, for split by percentage.
+// Percentages must add up to 100, with some leeway for rounding, such as 33.3 three times.
+pub fn process_debts_percent(
+    text: &str,
+    total: Option<i64>,
+) -> Result<Vec<(String, i64)>, BotError> {
+    let debts_percented = parse_debts_weighted(text)?;
+
+    let total = match total {
+        Some(val) => val,
+        None => {
+            return Err(BotError::UserError(Text::new(TOTAL_MISSING)));
+        }
+    };
+
+    let sum: f64 = debts_percented.iter().map(|debt| debt.1).sum();
+    if (sum - 100.0).abs() > PERCENT_TOLERANCE {
+        return Err(BotError::UserError(
+            Text::new(PERCENTAGES_NOT_HUNDRED).arg("sum", (sum * 100.0).round() / 100.0),
+        ));
+    }
+
+    Ok(split_debts_weighted(debts_percented, total))
+}
+
+// Parses a string of line items, each on its own line, for split by items.
+// Items are like "pizza 18.50 @username__1 @username__2", shared equally by the users mentioned.
+// Items without users, like "tax 3.20" or "tip 10%", are shared proportionally by everyone.
+// Percentages are taken of the subtotal of all items so far, starting from the given subtotal.
+pub fn parse_debt_items(
+    text: &str,
+    currency: Option<Currency>,
+    subtotal: i64,
+) -> Result<Vec<DebtItem>, BotError> {
+    let currency = match currency {
+        Some(val) => val,
+        None => {
+            return Err(BotError::UserError(Text::new(CURRENCY_MISSING)));
+        }
+    };
+
+    let mut items: Vec<DebtItem> = Vec::new();
+    let mut subtotal = subtotal;
+    for line in text.lines() {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.is_empty() {
+            continue;
+        }
+
+        let pos = words
+            .iter()
+            .position(|word| word.ends_with('%') || parse_amount(word, &currency).is_ok());
+        let pos = match pos {
+            Some(pos) => pos,
+            None => {
+                return Err(BotError::UserError(
+                    Text::new(ITEM_AMOUNT_MISSING).arg("item", line.trim()),
+                ));
+            }
+        };
+
+        let description = if pos == 0 {
+            "Item".to_string()
+        } else {
+            words[..pos].join(" ")
+        };
+        let mut users: Vec<String> = Vec::new();
+        for word in &words[pos + 1..] {
+            users.push(parse_username(word)?);
+        }
+
+        let amount = match words[pos].strip_suffix('%') {
+            Some(_) if !users.is_empty() => {
+                return Err(BotError::UserError(Text::new(ITEM_PERCENT_WITH_USERS)));
+            }
+            Some(percent) => checked_from_f64(parse_float(percent)? / 100.0 * subtotal as f64)?,
+            None => parse_amount(words[pos], &currency)?,
+        };
+        if !users.is_empty() {
+            subtotal = checked_add(subtotal, amount)?;
+        }
+
+        items.push(DebtItem {
+            description,
+            amount,
+            users,
+        });
+    }
+
+    if items.is_empty() {
+        return Err(BotError::UserError(Text::new(ITEMS_MISSING)));
+    }
+    checked_sum(items.iter().map(|item| item.amount))?;
+
+    Ok(items)
+}
+
+// Splits the total amount by line items.
+// Each item is split equally among its users, and everything else is shared
+// proportionally to the items of each user, such as tax and tip.
+pub fn split_debt_items(
+    items: &[DebtItem],
+    total: Option<i64>,
+    creditor: &Option<String>,
+    rounding: Rounding,
+) -> Result<Vec<(String, i64)>, BotError> {
+    let total = match total {
+        Some(val) => val,
+        None => {
+            return Err(BotError::UserError(Text::new(TOTAL_MISSING)));
+        }
+    };
 
-    let amount = (total as f64 / users.len() as f64).round() as i64;
-    let diff = total - amount * users.len() as i64;
+    let sum = checked_sum(items.iter().map(|item| item.amount))?;
+    if sum > total {
+        return Err(BotError::UserError(Text::new(ITEMS_EXCEED_TOTAL)));
+    }
 
     let mut debts: Vec<(String, i64)> = Vec::new();
-    for user in &users {
-        let username = parse_username(user)?;
-        let debt = (username.clone(), amount);
-        debts.push(debt);
+    for item in items.iter().filter(|item| !item.users.is_empty()) {
+        for (user, amount) in
+            process_debts_equal(&item.users.join(" "), Some(item.amount), creditor, rounding)?
+        {
+            match debts
+                .iter_mut()
+                .find(|debt| is_username_equal(&debt.0, &user))
+            {
+                Some(debt) => debt.1 = checked_add(debt.1, amount)?,
+                None => debts.push((user, amount)),
+            }
+        }
     }
 
-    // Distribute the difference in amount to as many users as required through smallest denomination
-    for i in 0..(diff).abs() {
-        debts[i as usize].1 += if diff > 0 { 1 } else { -1 };
+    if debts.is_empty() {
+        return Err(BotError::UserError(Text::new(ITEMS_WITHOUT_USERS)));
+    }
+
+    // Shares the rest proportionally, including tax, tip, and any amount left over
+    let shared = checked_add(total, -checked_sum(debts.iter().map(|debt| debt.1))?)?;
+    let weights = debts
+        .iter()
+        .map(|(user, amount)| (user.clone(), *amount as f64))
+        .collect();
+    for (debt, (_, amount)) in debts.iter_mut().zip(split_debts_weighted(weights, shared)) {
+        debt.1 = checked_add(debt.1, amount)?;
     }
 
     Ok(debts)
 }
 
-// Parse and process a string to retrieve a list of debts, for split by exact amount.
-pub fn process_debts_exact(
+// Parse and process a string to retrieve a list of debts, for split by items.
+pub fn process_debts_itemized(
     text: &str,
     creditor: &Option<String>,
     currency: Option<Currency>,
     total: Option<i64>,
+    rounding: Rounding,
 ) -> Result<Vec<(String, i64)>, BotError> {
-    if let Some(creditor) = creditor {
-        if let Some(total) = total {
-            if let Some(currency) = currency {
-                let mut debts: Vec<(String, i64)> = Vec::new();
-                let mut sum: i64 = 0;
-                let items: Vec<&str> = text.split_whitespace().collect();
-                if items.len() % 2 != 0 {
-                    return Err(BotError::UserError(
-                        "Uh-oh! ❌ I don't understand... Please use the following format!"
-                            .to_string(),
-                    ));
-                }
-
-                for i in (0..items.len()).step_by(2) {
-                    let username = parse_username(items[i])?;
-                    let amount = parse_amount(items[i + 1], currency.1)?;
-                    sum += amount;
-
-                    let mut found = false;
-                    for debt in &mut debts {
-                        if debt.0 == username {
-                            debt.1 += amount;
-                            found = true;
-                            break;
-                        }
-                    }
-
-                    if !found {
-                        debts.push((username, amount));
-                    }
-                }
-
-                if sum > total {
-                    Err(BotError::UserError(
-                        "Uh-oh! ❌ The amounts you gave me are more than the total paid!"
-                            .to_string(),
-                    ))
-                } else if sum < total {
-                    for debt in &mut debts {
-                        if debt.0 == creditor.to_string() {
-                            debt.1 += total - sum;
-                            return Ok(debts);
-                        }
-                    }
+    let items = parse_debt_items(text, currency, 0)?;
+    split_debt_items(&items, total, creditor, rounding)
+}
 
-                    debts.push((creditor.to_string(), total - sum));
-                    Ok(debts)
-                } else {
-                    Ok(debts)
-                }
-            } else {
-                Err(BotError::UserError(
-                    "Uh-oh! ❌ The currency isn't provided.".to_string(),
-                ))
-            }
+// Displays line items, with the subtotal and amount remaining.
+pub async fn display_debt_items(
+    redis: &Redis,
+    chat_id: &str,
+    items: &Vec<DebtItem>,
+    currency: Currency,
+    total: i64,
+) -> String {
+    let language = retrieve_language(redis, chat_id).await;
+    let mut display = String::new();
+    for item in items {
+        let users = if item.users.is_empty() {
+            translate(&language, ITEM_EVERYONE, &[])
         } else {
-            Err(BotError::UserError(
-                "Uh-oh! ❌ The total amount isn't provided.".to_string(),
-            ))
-        }
-    } else {
-        Err(BotError::UserError(
-            "Uh-oh! ❌ The payer isn't provided.".to_string(),
-        ))
+            display_usernames(redis, chat_id, &item.users)
+                .await
+                .join(", ")
+        };
+        display.push_str(&format!(
+            "{} — {} ({})\n",
+            item.description,
+            display_amount(item.amount, currency.1),
+            users
+        ));
     }
-}
 
-// Parse and process a string to retrieve a list of debts, for split by ratio.
-pub fn process_debts_ratio(text: &str, total: Option<i64>) -> Result<Vec<(String, i64)>, BotError> {
-    let items: Vec<&str> = text.split_whitespace().collect();
-    let mut debts_ratioed: Vec<(String, f64)> = Vec::new();
-    let mut debts: Vec<(String, i64)> = Vec::new();
-    let mut sum: f64 = 0.0;
+    // Items are checked against MAX_VALUE when parsed, so this only stops at it for display
+    let sum = items
+        .iter()
+        .fold(0, |sum, item| saturating_add(sum, item.amount));
+    let currency_format = retrieve_currency_format(redis, chat_id).await;
+    display.push('\n');
+    display.push_str(&translate(
+        &language,
+        ITEMS_SUMMARY,
+        &[
+            (
+                "subtotal",
+                &display_currency_amount(sum, currency.clone(), currency_format),
+            ),
+            (
+                "remaining",
+                &display_currency_amount(saturating_add(total, -sum), currency, currency_format),
+            ),
+        ],
+    ));
+
+    display
+}
 
-    if items.len() % 2 != 0 {
-        return Err(BotError::UserError(
-            "Uh-oh! ❌ I don't understand... Please use the following format!".to_string(),
-        ));
-    }
+// Parse and process a string to retrieve a list of debts, for split equally with adjustments.
+// The amount left over is assigned by the rounding strategy of the chat.
+pub fn process_debts_adjusted(
+    text: &str,
+    creditor: &Option<String>,
+    currency: Option<Currency>,
+    total: Option<i64>,
+    rounding: Rounding,
+) -> Result<Vec<(String, i64)>, BotError> {
+    let total = match total {
+        Some(val) => val,
+        None => {
+            return Err(BotError::UserError(Text::new(TOTAL_MISSING)));
+        }
+    };
 
-    let mut users: Vec<String> = Vec::new();
-    let mut ratios: Vec<f64> = Vec::new();
+    let currency = match currency {
+        Some(val) => val,
+        None => {
+            return Err(BotError::UserError(Text::new(CURRENCY_MISSING)));
+        }
+    };
 
-    for i in (0..items.len()).step_by(2) {
-        let curr = parse_username(items[i])?;
-        let pos = users.iter().position(|u| is_username_equal(u, &curr));
-        match pos {
-            Some(pos) => {
-                ratios[pos] += parse_float(items[i + 1])?;
+    // Each user is followed by an optional adjustment, such as +5 or -2.50
+    let mut adjustments: Vec<(String, i64)> = Vec::new();
+    let mut current: Option<usize> = None;
+    for item in text.split_whitespace() {
+        let sign = match item.chars().next() {
+            Some('+') => Some(1),
+            Some('-') => Some(-1),
+            _ => None,
+        };
+
+        match (sign, current) {
+            (Some(sign), Some(index)) => {
+                let amount = sign * parse_amount(&item[1..], &currency)?;
+                adjustments[index].1 = checked_add(adjustments[index].1, amount)?;
             }
-            None => {
-                users.push(curr.to_string());
-                ratios.push(parse_float(items[i + 1])?);
+            (Some(_), None) => {
+                return Err(BotError::UserError(Text::new(FORMAT_UNRECOGNIZED)));
+            }
+            (None, _) => {
+                let username = parse_username(item)?;
+                let index = adjustments
+                    .iter()
+                    .position(|adjustment| adjustment.0.to_lowercase() == username.to_lowercase());
+                current = match index {
+                    Some(index) => Some(index),
+                    None => {
+                        adjustments.push((username, 0));
+                        Some(adjustments.len() - 1)
+                    }
+                };
             }
         }
     }
 
-    for i in 0..users.len() {
-        let username = &users[i];
-        sum += ratios[i];
-        debts_ratioed.push((username.to_string(), ratios[i]));
+    if adjustments.is_empty() {
+        return Err(BotError::UserError(Text::new(USERNAMES_MISSING)));
     }
 
-    let total = match total {
-        Some(val) => val,
-        None => {
-            return Err(BotError::UserError(
-                "Uh-oh! ❌ The total amount isn't provided.".to_string(),
-            ));
-        }
-    };
-
-    let mut exact_sum: i64 = 0;
-    for debt in &mut debts_ratioed {
-        let amount = ((debt.1 / sum) * total as f64).round() as i64;
-        debts.push((debt.0.clone(), amount));
-        exact_sum += amount;
+    let sum = checked_sum(adjustments.iter().map(|adjustment| adjustment.1))?;
+    let remainder = checked_add(total, -sum)?;
+    if remainder < 0 {
+        return Err(BotError::UserError(Text::new(ADJUSTMENTS_EXCEED_TOTAL)));
     }
 
-    // Distribute the difference in amount to as many users as required through smallest denomination
-    let diff = total - exact_sum;
-    for i in 0..(diff).abs() {
-        debts[i as usize].1 += if diff > 0 { 1 } else { -1 };
+    let amount = remainder / adjustments.len() as i64;
+    let leftover = remainder - amount * adjustments.len() as i64;
+
+    let mut debts: Vec<(String, i64)> = adjustments
+        .into_iter()
+        .map(|(username, adjustment)| Ok((username, checked_add(amount, adjustment)?)))
+        .collect::<Result<_, MoneyError>>()?;
+
+    assign_remainder(&mut debts, leftover, creditor, rounding);
+
+    if debts.iter().any(|debt| debt.1 < 0) {
+        return Err(BotError::UserError(Text::new(ADJUSTMENTS_NEGATIVE)));
     }
 
     Ok(debts)
@@ -660,46 +2780,103 @@ pub fn process_debts(
     creditor: &Option<String>,
     currency: Option<Currency>,
     total: Option<i64>,
+    rounding: Rounding,
+    groups: &[(String, Vec<String>)],
 ) -> Result<Vec<(String, i64)>, BotError> {
-    match debts_format {
-        AddDebtsFormat::Equal => process_debts_equal(text, total),
+    let debts = match debts_format {
+        AddDebtsFormat::Equal => process_debts_equal(
+            &expand_split_groups(text, groups),
+            total,
+            creditor,
+            rounding,
+        ),
         AddDebtsFormat::Exact => process_debts_exact(text, creditor, currency, total),
         AddDebtsFormat::Ratio => process_debts_ratio(text, total),
-    }
+        AddDebtsFormat::Percent => process_debts_percent(text, total),
+        AddDebtsFormat::Itemized => {
+            process_debts_itemized(text, creditor, currency, total, rounding)
+        }
+        AddDebtsFormat::Adjusted => {
+            process_debts_adjusted(text, creditor, currency, total, rounding)
+        }
+    }?;
+    validate_debtors(&debts)?;
+    Ok(debts)
 }
 
-// Parses a string of debts and returns a vector of debts
+// Expands the names of split groups in a list of usernames, into the members of each group.
+// Group names may be given with or without the '@' symbol, like usernames.
+fn expand_split_groups(text: &str, groups: &[(String, Vec<String>)]) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            let name = word.trim_start_matches('@');
+            match groups
+                .iter()
+                .find(|(group, _)| group.eq_ignore_ascii_case(name))
+            {
+                Some((_, members)) => members.join(" "),
+                None => word.to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// Parses a string of debts and returns the debts grouped by currency
+// Each amount may be followed by a currency code, otherwise the given currency is used
 pub fn parse_debts_payback(
     text: &str,
     currency: Currency,
     sender: &str,
-) -> Result<Vec<(String, i64)>, BotError> {
-    let mut debts: Vec<(String, i64)> = Vec::new();
+) -> Result<Vec<CurrencyDebts>, BotError> {
+    let mut debts: Vec<CurrencyDebts> = Vec::new();
     let items: Vec<&str> = text.split_whitespace().collect();
-    if items.len() % 2 != 0 {
-        return Err(BotError::UserError(
-            "Uh-oh! ❌ I don't understand... Please use the following format!".to_string(),
-        ));
+    if items.is_empty() {
+        return Err(BotError::UserError(Text::new(FORMAT_UNRECOGNIZED)));
     }
 
-    for i in (0..items.len()).step_by(2) {
+    let mut i = 0;
+    while i < items.len() {
+        if i + 1 >= items.len() {
+            return Err(BotError::UserError(Text::new(FORMAT_UNRECOGNIZED)));
+        }
         let username = parse_username(items[i])?;
-        let amount = parse_amount(items[i + 1], currency.1)?;
         if is_username_equal(&username, sender) {
-            return Err(BotError::UserError(
-                "Uh-oh! ❌ You can't pay back yourself!".to_string(),
-            ));
+            return Err(BotError::UserError(Text::new(PAY_BACK_SELF)));
         }
-        let mut found = false;
-        for debt in &mut debts {
-            if debt.0 == username {
-                debt.1 += amount;
-                found = true;
-                break;
+
+        // Usernames are at least 5 characters long, so a currency code can't be mistaken for one
+        let explicit_currency = match items.get(i + 2) {
+            Some(item) if item.len() == 3 && item.chars().all(|c| c.is_ascii_alphabetic()) => {
+                Some(get_currency(item)?)
             }
-        }
-        if !found {
-            debts.push((username, amount));
+            _ => None,
+        };
+        let debt_currency = match &explicit_currency {
+            Some(explicit_currency) => explicit_currency.clone(),
+            None => currency.clone(),
+        };
+        let amount = Money::new(
+            &debt_currency.0,
+            parse_amount(items[i + 1], &debt_currency)?,
+        )?;
+        i += if explicit_currency.is_some() { 3 } else { 2 };
+
+        let group = match debts.iter_mut().find(|group| group.0 .0 == debt_currency.0) {
+            Some(group) => group,
+            None => {
+                debts.push((debt_currency.clone(), Vec::new()));
+                debts.last_mut().expect("Group was just added")
+            }
+        };
+        // Amounts to the same user are added up, as long as the total is within MAX_VALUE
+        match group.1.iter_mut().find(|debt| debt.0 == username) {
+            Some(debt) => {
+                debt.1 = Money::new(&debt_currency.0, debt.1)?
+                    .checked_add(&amount)?
+                    .amount()
+            }
+            None => group.1.push((username, amount.amount())),
         }
     }
 
@@ -714,17 +2891,226 @@ pub fn parse_time_zone(text: &str) -> Result<Tz, BotError> {
 
     match time_zone {
         Some(time_zone) => Ok(*time_zone),
+        None => Err(BotError::UserError(Text::new(TIME_ZONE_UNRECOGNIZED))),
+    }
+}
+
+// Parses a string representing a time of day in 24-hour format, and returns it as HH:MM
+pub fn parse_digest_time(text: &str) -> Result<String, BotError> {
+    match NaiveTime::parse_from_str(text.trim(), "%H:%M") {
+        Ok(time) => Ok(time.format("%H:%M").to_string()),
+        Err(_) => Err(BotError::UserError(Text::new(TIME_UNRECOGNIZED))),
+    }
+}
+
+// Parses the number of days between debt reminders
+pub fn parse_reminder_days(text: &str) -> Result<u32, BotError> {
+    match text.trim().parse::<u32>() {
+        Ok(days) if days > 0 => Ok(days),
+        _ => Err(BotError::UserError(Text::new(REMINDER_DAYS_INVALID))),
+    }
+}
+
+// Parses the number of months to keep payments for, for data retention
+pub fn parse_retention_months(text: &str) -> Result<u32, BotError> {
+    match text.trim().parse::<u32>() {
+        Ok(months) if months > 0 => Ok(months),
+        _ => Err(BotError::UserError(Text::new(RETENTION_MONTHS_INVALID))),
+    }
+}
+
+// Parses a relative date, such as "3 days ago" or "last friday"
+fn parse_relative_date(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    match words.as_slice() {
+        [count, unit, "ago"] => {
+            let count = count.parse::<u64>().ok()?;
+            let days = match unit.trim_end_matches('s') {
+                "day" => count,
+                "week" => count.checked_mul(7)?,
+                _ => return None,
+            };
+            today.checked_sub_days(Days::new(days))
+        }
+        ["last", weekday] | [weekday] => {
+            let weekday = weekday.parse::<Weekday>().ok()?;
+            let mut days =
+                (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+            if days == 0 && words.len() == 2 {
+                days = 7;
+            }
+            today.checked_sub_days(Days::new(days as u64))
+        }
+        _ => None,
+    }
+}
+
+// Parses an absolute date, such as "2024-03-12", "12/03/2024" or "12 Mar"
+// Dates without a year are taken to be within the past year
+fn parse_absolute_date(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    for format in ["%Y-%m-%d", "%d/%m/%Y", "%d %b %Y", "%d %B %Y"] {
+        if let Ok(date) = NaiveDate::parse_from_str(text, format) {
+            return Some(date);
+        }
+    }
+
+    let text = format!("{text} {}", today.year());
+    for format in ["%d/%m %Y", "%d %b %Y", "%d %B %Y"] {
+        if let Ok(date) = NaiveDate::parse_from_str(&text, format) {
+            if date > today {
+                return date.with_year(date.year() - 1);
+            }
+            return Some(date);
+        }
+    }
+
+    None
+}
+
+// Parses a weekday, such as "friday", as the next such day after today
+fn parse_upcoming_weekday(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let weekday = text.parse::<Weekday>().ok()?;
+    let days = (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    let days = if days == 0 { 7 } else { days };
+    today.checked_add_days(Days::new(days as u64))
+}
+
+// Parses an absolute due date, such as "2024-03-12", "12/03/2024" or "12 Mar"
+// Dates without a year are taken to be within the coming year
+fn parse_absolute_due_date(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    for format in ["%Y-%m-%d", "%d/%m/%Y", "%d %b %Y", "%d %B %Y"] {
+        if let Ok(date) = NaiveDate::parse_from_str(text, format) {
+            return Some(date);
+        }
+    }
+
+    let text = format!("{text} {}", today.year());
+    for format in ["%d/%m %Y", "%d %b %Y", "%d %B %Y"] {
+        if let Ok(date) = NaiveDate::parse_from_str(&text, format) {
+            if date < today {
+                return date.with_year(date.year() + 1);
+            }
+            return Some(date);
+        }
+    }
+
+    None
+}
+
+// Parses the date by which a payment should be paid back, in the time zone of the chat
+// Dates without a year are taken to be within the coming year
+// Returns None if the due date is to be removed, and the date in the format %Y-%m-%d otherwise
+pub fn parse_due_date(text: &str, time_zone: Tz) -> Result<Option<String>, BotError> {
+    let today = Utc::now().with_timezone(&time_zone).date_naive();
+    let text = text.trim().to_lowercase();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let date = match words.as_slice() {
+        ["none"] | ["no"] | ["off"] => return Ok(None),
+        ["today"] => Some(today),
+        ["tomorrow"] => today.succ_opt(),
+        ["in", count, unit] => {
+            count
+                .parse::<u64>()
+                .ok()
+                .and_then(|count| match unit.trim_end_matches('s') {
+                    "day" => today.checked_add_days(Days::new(count)),
+                    "week" => today.checked_add_days(Days::new(count.checked_mul(7)?)),
+                    "month" => today.checked_add_months(Months::new(u32::try_from(count).ok()?)),
+                    _ => None,
+                })
+        }
+        ["next", weekday] | [weekday] => {
+            parse_upcoming_weekday(weekday, today).or_else(|| parse_absolute_due_date(&text, today))
+        }
+        _ => parse_absolute_due_date(&text, today),
+    };
+
+    match date {
+        Some(date) if date < today => Err(BotError::UserError(Text::new(DUE_DATE_IN_PAST))),
+        Some(date) => Ok(Some(date.format("%Y-%m-%d").to_string())),
         None => Err(BotError::UserError(
-            "🥺 Sorry, I don't recognize that time zone!".to_string(),
+            Text::new(DATE_UNRECOGNIZED).then(Text::new(DUE_DATE_INSTRUCTIONS)),
         )),
     }
 }
 
+// Parses a string representing the date of a payment, in the time zone of the chat
+// Keeps the time of day of the original datetime, and returns the new datetime in UTC
+pub fn parse_payment_date(text: &str, datetime: &str, time_zone: Tz) -> Result<String, BotError> {
+    let today = Utc::now().with_timezone(&time_zone).date_naive();
+    let text = text.trim().to_lowercase();
+    let date = match text.as_str() {
+        "today" => Some(today),
+        "yesterday" => today.pred_opt(),
+        _ => parse_relative_date(&text, today).or_else(|| parse_absolute_date(&text, today)),
+    };
+
+    let date = match date {
+        Some(date) => date,
+        None => {
+            return Err(BotError::UserError(
+                Text::new(DATE_UNRECOGNIZED).then(Text::new(DATE_INSTRUCTIONS)),
+            ))
+        }
+    };
+    if date > today {
+        return Err(BotError::UserError(Text::new(DATE_IN_FUTURE)));
+    }
+
+    // Falls back to midday, if the time of day does not exist on that date
+    let time = parse_datetime(datetime, time_zone).time();
+    let local = time_zone
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .or_else(|| {
+            time_zone
+                .from_local_datetime(&date.and_hms_opt(12, 0, 0)?)
+                .earliest()
+        });
+    match local {
+        Some(local) => Ok(local.with_timezone(&Utc).to_string()),
+        None => Err(BotError::UserError(Text::new(DATE_UNRECOGNIZED))),
+    }
+}
+
+// Displays the date of a payment, in the time zone of the chat
+pub fn display_payment_date(datetime: &str, time_zone: Tz) -> String {
+    parse_datetime(datetime, time_zone)
+        .format("%e %b %Y")
+        .to_string()
+        .trim()
+        .to_string()
+}
+
+// Displays a due date, stored in the format %Y-%m-%d, like a payment date
+pub fn display_due_date(date: &str) -> String {
+    match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        Ok(date) => date.format("%e %b %Y").to_string().trim().to_string(),
+        Err(_) => date.to_string(),
+    }
+}
+
 // Retrieves the time zone string from database, converts it to TimeZone object
 // Assumes that time zone is valid, thus does not return any error
-pub fn retrieve_time_zone(chat_id: &str) -> Tz {
+pub async fn retrieve_time_zone(redis: &Redis, chat_id: &str) -> Tz {
+    retrieve_topic_time_zone(redis, chat_id, None).await
+}
+
+// Gets the forum topic that a message was sent in, if any
+pub fn get_topic_id(msg: &Message) -> Option<i32> {
+    match &msg.kind {
+        MessageKind::Common(MessageCommon {
+            is_topic_message: true,
+            ..
+        }) => msg.thread_id,
+        _ => None,
+    }
+}
+
+// Retrieves the time zone of a forum topic, falling back to the time zone of the chat
+pub async fn retrieve_topic_time_zone(redis: &Redis, chat_id: &str, topic_id: Option<i32>) -> Tz {
     let setting = ChatSetting::TimeZone(None);
-    let time_zone = get_chat_setting(&chat_id, setting);
+    let time_zone = get_topic_chat_setting(redis, chat_id, topic_id, setting).await;
     if let Ok(ChatSetting::TimeZone(Some(time_zone))) = time_zone {
         let time_zone = parse_time_zone(&time_zone);
         if let Ok(time_zone) = time_zone {
@@ -735,6 +3121,58 @@ pub fn retrieve_time_zone(chat_id: &str) -> Tz {
     "UTC".parse::<Tz>().expect("UTC is a valid time zone")
 }
 
+// Retrieves the default currency overriding the chat's for a forum topic, if any
+pub async fn retrieve_topic_default_currency(
+    redis: &Redis,
+    chat_id: &str,
+    topic_id: Option<i32>,
+) -> Option<Currency> {
+    topic_id?;
+    let chat_currency = get_chat_default_currency(redis, chat_id).await;
+    let setting = ChatSetting::DefaultCurrency(None);
+    match get_topic_chat_setting(redis, chat_id, topic_id, setting).await {
+        Ok(ChatSetting::DefaultCurrency(Some(currency))) if currency != chat_currency.0 => {
+            get_currency(&currency).ok()
+        }
+        _ => None,
+    }
+}
+
+// Retrieves the number format of a chat, used when parsing amounts
+pub async fn retrieve_number_format(redis: &Redis, chat_id: &str) -> NumberFormat {
+    let setting = ChatSetting::NumberFormat(None);
+    let number_format = get_chat_setting(redis, chat_id, setting).await;
+    if let Ok(ChatSetting::NumberFormat(Some(number_format))) = number_format {
+        if let Some(number_format) = get_number_format(&number_format) {
+            return number_format;
+        }
+    }
+
+    NUMBER_FORMAT_DEFAULT
+}
+
+// Retrieves the currency format of a chat, or the default if it is not set
+pub async fn retrieve_currency_format(redis: &Redis, chat_id: &str) -> CurrencyFormat {
+    let setting = ChatSetting::CurrencyFormat(None);
+    let currency_format = get_chat_setting(redis, chat_id, setting).await;
+    if let Ok(ChatSetting::CurrencyFormat(Some(currency_format))) = currency_format {
+        if let Some(currency_format) = get_currency_format(&currency_format) {
+            return currency_format;
+        }
+    }
+
+    CURRENCY_FORMAT_DEFAULT
+}
+
+// Retrieves the currencies allowed in a chat. No currencies means all are allowed.
+pub async fn retrieve_allowed_currencies(redis: &Redis, chat_id: &str) -> Vec<String> {
+    let setting = ChatSetting::AllowedCurrencies(None);
+    match get_chat_setting(redis, chat_id, setting).await {
+        Ok(ChatSetting::AllowedCurrencies(Some(currencies))) => currencies,
+        _ => Vec::new(),
+    }
+}
+
 // Parses a string representing a datetime, and returns the Datetime object
 fn parse_datetime(text: &str, time_zone: Tz) -> DateTime<Tz> {
     // Checks if text contains "UTC" at the end
@@ -755,6 +3193,120 @@ fn format_datetime(datetime: &DateTime<Tz>) -> String {
 }
 
 // Combines both datetime functions to essentially reformat a string into an easier format
-fn reformat_datetime(text: &str, time_zone: Tz) -> String {
+pub fn reformat_datetime(text: &str, time_zone: Tz) -> String {
     format_datetime(&parse_datetime(text, time_zone))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debt_items_overflow() {
+        let currency = Some(get_default_currency());
+        let max = MAX_VALUE / 100;
+
+        let text = format!("pizza {} @user__1\nsalad {} @user__2", max, max);
+        assert!(matches!(
+            parse_debt_items(&text, currency.clone(), 0),
+            Err(BotError::UserError(text)) if text == Text::new(AMOUNT_TOO_LARGE)
+        ));
+
+        let text = format!("fees {}\ntax {}\ntip {}", max, max, max);
+        assert!(matches!(
+            parse_debt_items(&text, currency.clone(), 0),
+            Err(BotError::UserError(text)) if text == Text::new(AMOUNT_TOO_LARGE)
+        ));
+
+        let text = "tip 200%";
+        assert!(matches!(
+            parse_debt_items(text, currency.clone(), MAX_VALUE),
+            Err(BotError::UserError(text)) if text == Text::new(AMOUNT_TOO_LARGE)
+        ));
+
+        let items: Vec<DebtItem> = (0..4)
+            .map(|_| DebtItem {
+                description: "Item".to_string(),
+                amount: MAX_VALUE,
+                users: vec!["@user__1".to_string()],
+            })
+            .collect();
+        assert!(matches!(
+            split_debt_items(&items, Some(MAX_VALUE), &None, Rounding::First),
+            Err(BotError::UserError(text)) if text == Text::new(AMOUNT_TOO_LARGE)
+        ));
+    }
+
+    #[test]
+    fn test_debts_adjusted_overflow() {
+        let currency = Some(get_default_currency());
+        let max = MAX_VALUE / 100;
+
+        let text = format!("@user__1 +{} +{} @user__2", max, max);
+        assert!(matches!(
+            process_debts_adjusted(&text, &None, currency.clone(), Some(MAX_VALUE), Rounding::First),
+            Err(BotError::UserError(text)) if text == Text::new(AMOUNT_TOO_LARGE)
+        ));
+
+        let text = format!("@user__1 +{} @user__2 +{} @user__3 +{}", max, max, max);
+        assert!(matches!(
+            process_debts_adjusted(&text, &None, currency.clone(), Some(MAX_VALUE), Rounding::First),
+            Err(BotError::UserError(text)) if text == Text::new(AMOUNT_TOO_LARGE)
+        ));
+
+        let text = format!("@user__1 -{} @user__2", max);
+        let debts =
+            process_debts_adjusted(&text, &None, currency, Some(MAX_VALUE), Rounding::First);
+        assert!(matches!(
+            debts,
+            Err(BotError::UserError(text)) if text == Text::new(AMOUNT_TOO_LARGE)
+        ));
+    }
+
+    #[test]
+    fn test_parse_relative_date() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 12).unwrap();
+        assert_eq!(
+            parse_relative_date("2 weeks ago", today),
+            NaiveDate::from_ymd_opt(2024, 2, 27)
+        );
+        assert_eq!(
+            parse_relative_date("3 days ago", today),
+            NaiveDate::from_ymd_opt(2024, 3, 9)
+        );
+        assert_eq!(
+            parse_relative_date("3000000000000000000 weeks ago", today),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_due_date_overflow() {
+        for text in [
+            "in 3000000000000000000 weeks",
+            "in 4294967297 months",
+            "in 18446744073709551615 days",
+        ] {
+            assert!(matches!(
+                parse_due_date(text, Tz::UTC),
+                Err(BotError::UserError(_))
+            ));
+        }
+        assert!(matches!(parse_due_date("in 2 weeks", Tz::UTC), Ok(Some(_))));
+    }
+
+    #[test]
+    fn test_debts_payback_overflow() {
+        let currency = get_default_currency();
+        let max = MAX_VALUE / 100;
+
+        let text = format!("@user__1 {} @user__2 {}", max, max);
+        assert!(parse_debts_payback(&text, currency.clone(), "@user__3").is_ok());
+
+        let text = format!("@user__1 {} @user__1 {}", max, max);
+        assert!(matches!(
+            parse_debts_payback(&text, currency, "@user__3"),
+            Err(BotError::UserError(text)) if text == Text::new(AMOUNT_TOO_LARGE)
+        ));
+    }
+}
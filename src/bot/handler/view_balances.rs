@@ -1,59 +1,147 @@
 use teloxide::{
+    payloads::SendMessageSetters,
     prelude::*,
     types::{Message, MessageId},
 };
 
 use crate::bot::{
     currency::CURRENCY_DEFAULT,
-    handler::{
-        constants::UNKNOWN_ERROR_MESSAGE,
-        utils::{
-            display_balances, process_valid_currencies, send_bot_message, HandlerResult,
-            StatementOption, UserDialogue,
-        },
+    handler::utils::{
+        cleanup_messages, display_balances, display_balances_overdue, display_rates_fetched,
+        edit_bot_message, edit_markdown_message, error_to_user_message, escape_markdown,
+        get_currency, get_overdue_debtors, is_debtor_overdue, is_user_admin, make_payment_links,
+        process_valid_currencies, retrieve_language, send_bot_message, send_markdown_message, t,
+        BotError, HandlerResult, MessageTracker, UserDialogue,
     },
-    processor::{get_chat_setting, retrieve_debts, ChatSetting},
+    i18n::{
+        translate, Text, BALANCES_CONVERSION_REFUSED, BALANCES_DISPLAY_RESTRICTED,
+        BALANCES_NO_USERNAME, BALANCES_ONLY_OWE, BALANCES_ONLY_OWED, BALANCES_OVERDUE,
+        BALANCES_PAIRWISE, CONVERT_TO_CANCELLED, CONVERT_TO_PROMPT, CURRENCY_CODE_INVALID,
+        STATEMENT_INSTRUCTIONS, VIEW_BALANCES_HEADER, VIEW_BALANCES_HEADER_CONVERTED,
+        VIEW_BALANCES_HEADER_CURRENCY,
+    },
+    processor::{
+        get_chat_setting, is_username_equal, retrieve_chat_member, retrieve_debts,
+        retrieve_frequent_currencies, retrieve_rates_fetched, set_chat_setting, ChatSetting,
+        ProcessError, StatementOption,
+    },
+    redis::Redis,
     State,
 };
 
 use super::{
-    constants::STATEMENT_INSTRUCTIONS_MESSAGE,
+    constants::CURRENCY_SUGGESTIONS_MAX,
+    export::{
+        action_export_balances, action_export_statement, EXPORT_PDF_BUTTON, FORMAL_STATEMENT_BUTTON,
+    },
     utils::{assert_handle_request_limit, make_keyboard},
 };
 
 /* Utilities */
+const PAIRWISE_BUTTON: &str = "Show Pairwise";
+const SIMPLIFIED_BUTTON: &str = "Show Simplified";
+const CONVERT_TO_BUTTON: &str = "Convert To…";
+const OWE_BUTTON: &str = "What I Owe";
+const OWED_BUTTON: &str = "What I'm Owed";
+const SHOW_EVERYONE_BUTTON: &str = "Show Everyone";
+const CANCEL_BUTTON: &str = "Cancel";
+
+// Filter for the debts shown, to only those of the user viewing them
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum BalancesFilter {
+    Owe,
+    Owed,
+}
 
+// Checks if the chat shows raw pairwise debts instead of simplified ones
+async fn is_pairwise_debts(redis: &Redis, chat_id: &str) -> bool {
+    matches!(
+        get_chat_setting(redis, chat_id, ChatSetting::PairwiseDebts(None)).await,
+        Ok(ChatSetting::PairwiseDebts(Some(true)))
+    )
+}
+
+// Gets the default option to display balances in, based on chat settings
+async fn get_default_option(redis: &Redis, chat_id: &str) -> StatementOption {
+    let is_convert =
+        match get_chat_setting(redis, chat_id, ChatSetting::CurrencyConversion(None)).await {
+            Ok(ChatSetting::CurrencyConversion(Some(value))) => value,
+            _ => false,
+        };
+    let default_currency =
+        match get_chat_setting(redis, chat_id, ChatSetting::DefaultCurrency(None)).await {
+            Ok(ChatSetting::DefaultCurrency(Some(currency))) => currency,
+            _ => "NIL".to_string(),
+        };
+
+    if is_convert {
+        StatementOption::ConvertCurrency
+    } else {
+        StatementOption::Currency(default_currency)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn handle_balances_with_option(
+    redis: &Redis,
     bot: Bot,
     dialogue: UserDialogue,
     msg: Message,
     sender_id: String,
     mut option: StatementOption,
+    filter: Option<(BalancesFilter, String)>,
     id: Option<MessageId>,
 ) -> HandlerResult {
     let chat_id = msg.chat.id.to_string();
-    let balances_data = retrieve_debts(&chat_id, option.clone()).await;
+    let mut balances_data = retrieve_debts(redis, &chat_id, option.clone()).await;
+
+    // If rates are unavailable or too old, balances are shown in each currency instead
+    let is_conversion_refused = matches!(
+        (&balances_data, &option),
+        (
+            Err(ProcessError::ConversionError(_)),
+            StatementOption::ConvertCurrency | StatementOption::ConvertTo(_)
+        )
+    );
+    if is_conversion_refused {
+        log::warn!(
+            "View Balances - Conversion refused for group {}, showing balances in each currency",
+            chat_id
+        );
+        let default_currency =
+            match get_chat_setting(redis, &chat_id, ChatSetting::DefaultCurrency(None)).await {
+                Ok(ChatSetting::DefaultCurrency(Some(currency))) => currency,
+                _ => CURRENCY_DEFAULT.0.to_string(),
+            };
+        option = StatementOption::Currency(default_currency);
+        balances_data = retrieve_debts(redis, &chat_id, option.clone()).await;
+    }
+    let rates_fetched = retrieve_rates_fetched(redis, &chat_id, option.clone())
+        .await
+        .unwrap_or(None);
 
     match balances_data {
         Ok(mut balances_data) => {
             let default_currency =
-                match get_chat_setting(&chat_id, ChatSetting::DefaultCurrency(None)) {
+                match get_chat_setting(redis, &chat_id, ChatSetting::DefaultCurrency(None)).await {
                     Ok(ChatSetting::DefaultCurrency(Some(currency))) => currency,
                     _ => CURRENCY_DEFAULT.0.to_string(),
                 };
 
             let mut valid_currencies = process_valid_currencies(
+                redis,
                 &chat_id,
                 &sender_id,
                 option.clone(),
                 default_currency.clone(),
-            );
+            )
+            .await;
 
             // If no default currency, NIL has no balances, but other currencies do
-            if balances_data.len() == 0 && valid_currencies.len() > 0 {
+            if balances_data.is_empty() && !valid_currencies.is_empty() {
                 let currency = valid_currencies.first().unwrap().clone();
                 option = StatementOption::Currency(currency.clone());
-                balances_data = match retrieve_debts(&chat_id, option.clone()).await {
+                balances_data = match retrieve_debts(redis, &chat_id, option.clone()).await {
                     Ok(new_data) => {
                         valid_currencies.retain(|curr| curr != &currency);
                         new_data
@@ -62,59 +150,128 @@ async fn handle_balances_with_option(
                 };
             }
 
-            let ref_valid_currencies = valid_currencies
+            // Only debts of the user are shown, if filtered
+            if let Some((filter, username)) = &filter {
+                balances_data.retain(|debt| match filter {
+                    BalancesFilter::Owe => is_username_equal(&debt.debtor, username),
+                    BalancesFilter::Owed => is_username_equal(&debt.creditor, username),
+                });
+            }
+
+            let mut ref_valid_currencies = valid_currencies
                 .iter()
                 .map(|x| x.as_str())
                 .collect::<Vec<&str>>();
 
-            let has_buttons = valid_currencies.len() > 0;
-            let keyboard = make_keyboard(ref_valid_currencies, Some(2));
-
-            let header = if let StatementOption::Currency(curr) = option {
-                if curr == CURRENCY_DEFAULT.0 {
-                    format!("📊 Here are the current balances!")
-                } else {
-                    format!("📊 Here are the current {curr} balances!")
+            let has_buttons = !valid_currencies.is_empty();
+            let is_pairwise = is_pairwise_debts(redis, &chat_id).await;
+            ref_valid_currencies.push(if is_pairwise {
+                SIMPLIFIED_BUTTON
+            } else {
+                PAIRWISE_BUTTON
+            });
+            match filter.as_ref().map(|(filter, _)| filter) {
+                Some(BalancesFilter::Owe) => {
+                    ref_valid_currencies.push(OWED_BUTTON);
+                    ref_valid_currencies.push(SHOW_EVERYONE_BUTTON);
+                }
+                Some(BalancesFilter::Owed) => {
+                    ref_valid_currencies.push(OWE_BUTTON);
+                    ref_valid_currencies.push(SHOW_EVERYONE_BUTTON);
+                }
+                None => {
+                    ref_valid_currencies.push(OWE_BUTTON);
+                    ref_valid_currencies.push(OWED_BUTTON);
                 }
-            } else if has_buttons {
-                format!("📊 Here are the current balances, converted to {default_currency}!")
+            }
+            ref_valid_currencies.push(CONVERT_TO_BUTTON);
+            ref_valid_currencies.push(EXPORT_PDF_BUTTON);
+            ref_valid_currencies.push(FORMAL_STATEMENT_BUTTON);
+            let language = retrieve_language(redis, &chat_id).await;
+            let (payment_numbers, payment_buttons) =
+                make_payment_links(redis, &chat_id, &balances_data).await;
+            let keyboard = payment_buttons.into_iter().fold(
+                make_keyboard(ref_valid_currencies, Some(2), &language),
+                |keyboard, row| keyboard.append_row(row),
+            );
+            let overdue_debtors = get_overdue_debtors(redis, &chat_id).await;
+            let is_overdue = balances_data
+                .iter()
+                .any(|debt| is_debtor_overdue(&debt.debtor, &overdue_debtors));
+            let balances = format!(
+                "{}{}",
+                display_balances_overdue(redis, &chat_id, &balances_data, &overdue_debtors).await,
+                escape_markdown(&payment_numbers)
+            );
+
+            let mut header = match option {
+                StatementOption::Currency(curr) if curr != CURRENCY_DEFAULT.0 => translate(
+                    &language,
+                    VIEW_BALANCES_HEADER_CURRENCY,
+                    &[("currency", &curr)],
+                ),
+                StatementOption::ConvertTo(curr) => translate(
+                    &language,
+                    VIEW_BALANCES_HEADER_CONVERTED,
+                    &[("currency", &curr)],
+                ),
+                StatementOption::ConvertCurrency if has_buttons => translate(
+                    &language,
+                    VIEW_BALANCES_HEADER_CONVERTED,
+                    &[("currency", &default_currency)],
+                ),
+                _ => translate(&language, VIEW_BALANCES_HEADER, &[]),
+            };
+            let mut notes = Vec::new();
+            match filter.as_ref().map(|(filter, _)| filter) {
+                Some(BalancesFilter::Owe) => notes.push(BALANCES_ONLY_OWE),
+                Some(BalancesFilter::Owed) => notes.push(BALANCES_ONLY_OWED),
+                None => {}
+            }
+            if is_pairwise {
+                notes.push(BALANCES_PAIRWISE);
+            }
+            if is_conversion_refused {
+                notes.push(BALANCES_CONVERSION_REFUSED);
+            }
+            for note in notes {
+                header.push_str(&format!("\n{}", translate(&language, note, &[])));
+            }
+            if let Some(rates_fetched) = rates_fetched {
+                header.push_str(&format!(
+                    "\n{}",
+                    display_rates_fetched(redis, &chat_id, rates_fetched).await
+                ));
+            }
+            if is_overdue {
+                header.push_str(&format!(
+                    "\n{}",
+                    translate(&language, BALANCES_OVERDUE, &[])
+                ));
+            }
+            let header = escape_markdown(&header);
+            let instructions = if has_buttons {
+                escape_markdown(&translate(&language, STATEMENT_INSTRUCTIONS, &[]))
             } else {
-                format!("📊 Here are the current balances!")
+                "".to_string()
             };
 
             match id {
                 Some(id) => {
-                    bot.edit_message_text(
+                    edit_markdown_message(
+                        &bot,
                         chat_id.clone(),
                         id,
-                        format!(
-                            "{}\n\n{}\n{}",
-                            header,
-                            display_balances(&balances_data),
-                            if has_buttons {
-                                STATEMENT_INSTRUCTIONS_MESSAGE
-                            } else {
-                                ""
-                            }
-                        ),
+                        format!("{}\n\n{}\n{}", header, balances, instructions),
                     )
                     .reply_markup(keyboard)
                     .await?;
                 }
                 None => {
-                    send_bot_message(
+                    send_markdown_message(
                         &bot,
                         &msg,
-                        format!(
-                            "{}\n\n{}\n{}",
-                            header,
-                            display_balances(&balances_data),
-                            if has_buttons {
-                                STATEMENT_INSTRUCTIONS_MESSAGE
-                            } else {
-                                ""
-                            }
-                        ),
+                        format!("{}\n\n{}\n{}", header, balances, instructions),
                     )
                     .reply_markup(keyboard)
                     .await?;
@@ -126,17 +283,18 @@ async fn handle_balances_with_option(
                 "View Balances - User {} viewed balances for group {}: {}",
                 sender_id,
                 chat_id,
-                display_balances(&balances_data)
+                display_balances(redis, &chat_id, &balances_data).await
             );
         }
         Err(err) => {
+            let err = BotError::from(err);
+            let reply = error_to_user_message(redis, &err, &chat_id).await;
             match id {
                 Some(id) => {
-                    bot.edit_message_text(chat_id.clone(), id, UNKNOWN_ERROR_MESSAGE)
-                        .await?;
+                    edit_bot_message(&bot, chat_id.clone(), id, reply).await?;
                 }
                 None => {
-                    send_bot_message(&bot, &msg, UNKNOWN_ERROR_MESSAGE.to_string()).await?;
+                    send_bot_message(&bot, &msg, reply).await?;
                 }
             }
             log::error!(
@@ -153,29 +311,21 @@ async fn handle_balances_with_option(
 
 /* View the balances for the group.
 */
-pub async fn action_view_balances(bot: Bot, dialogue: UserDialogue, msg: Message) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+pub async fn action_view_balances(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: Message,
+) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
     let chat_id = msg.chat.id.to_string();
     let sender_id = msg.from().as_ref().unwrap().id.to_string();
-    let is_convert = match get_chat_setting(&chat_id, ChatSetting::CurrencyConversion(None)) {
-        Ok(ChatSetting::CurrencyConversion(Some(value))) => value,
-        _ => false,
-    };
-    let default_currency = match get_chat_setting(&chat_id, ChatSetting::DefaultCurrency(None)) {
-        Ok(ChatSetting::DefaultCurrency(Some(currency))) => currency,
-        _ => "NIL".to_string(),
-    };
+    let option = get_default_option(&redis, &chat_id).await;
 
-    let option = if is_convert {
-        StatementOption::ConvertCurrency
-    } else {
-        StatementOption::Currency(default_currency.clone())
-    };
-
-    handle_balances_with_option(bot, dialogue, msg, sender_id, option, None).await?;
+    handle_balances_with_option(&redis, bot, dialogue, msg, sender_id, option, None, None).await?;
 
     Ok(())
 }
@@ -184,6 +334,7 @@ pub async fn action_view_balances(bot: Bot, dialogue: UserDialogue, msg: Message
  * Takes in a callback query representing the user option on format to display.
  */
 pub async fn action_balances_menu(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     query: CallbackQuery,
@@ -196,20 +347,178 @@ pub async fn action_balances_menu(
             let id = msg.id;
             let chat_id = msg.chat.id.to_string();
             match button.as_str() {
+                EXPORT_PDF_BUTTON => {
+                    action_export_balances(&redis, &bot, &msg, &sender_id).await?;
+                }
+                FORMAL_STATEMENT_BUTTON => {
+                    action_export_statement(&redis, &bot, &msg, &sender_id).await?;
+                }
+                PAIRWISE_BUTTON | SIMPLIFIED_BUTTON => {
+                    let admin_only = matches!(
+                        get_chat_setting(&redis, &chat_id, ChatSetting::AdminOnly(None)).await?,
+                        ChatSetting::AdminOnly(Some(true))
+                    );
+                    if admin_only
+                        && !is_user_admin(&redis, &bot, msg.chat.id, query.from.id).await?
+                    {
+                        send_bot_message(
+                            &bot,
+                            &msg,
+                            t(&redis, &chat_id, BALANCES_DISPLAY_RESTRICTED, &[]).await,
+                        )
+                        .await?;
+                        return Ok(());
+                    }
+
+                    let is_pairwise = button.as_str() == PAIRWISE_BUTTON;
+                    set_chat_setting(
+                        &redis,
+                        &chat_id,
+                        ChatSetting::PairwiseDebts(Some(is_pairwise)),
+                    )
+                    .await?;
+
+                    // Logging
+                    log::info!(
+                        "View Balances Menu - User {} set pairwise debts to {} in chat {}",
+                        sender_id,
+                        is_pairwise,
+                        chat_id
+                    );
+
+                    let option = get_default_option(&redis, &chat_id).await;
+                    handle_balances_with_option(
+                        &redis,
+                        bot,
+                        dialogue,
+                        msg,
+                        sender_id,
+                        option,
+                        None,
+                        Some(id),
+                    )
+                    .await?;
+                }
+                OWE_BUTTON | OWED_BUTTON | SHOW_EVERYONE_BUTTON => {
+                    let filter = match button.as_str() {
+                        OWE_BUTTON => Some(BalancesFilter::Owe),
+                        OWED_BUTTON => Some(BalancesFilter::Owed),
+                        _ => None,
+                    };
+                    let filter = match filter {
+                        Some(filter) => {
+                            let username = retrieve_chat_member(
+                                &redis,
+                                &chat_id,
+                                &sender_id,
+                                query.from.username.as_deref(),
+                            )
+                            .await
+                            .unwrap_or(None)
+                            .or(query.from.username.clone());
+                            match username {
+                                Some(username) => Some((filter, username)),
+                                None => {
+                                    send_bot_message(
+                                        &bot,
+                                        &msg,
+                                        t(&redis, &chat_id, BALANCES_NO_USERNAME, &[]).await,
+                                    )
+                                    .await?;
+                                    return Ok(());
+                                }
+                            }
+                        }
+                        None => None,
+                    };
+
+                    // Logging
+                    log::info!(
+                        "View Balances Menu - User {} filtered balances to {:?} in chat {}",
+                        sender_id,
+                        filter.as_ref().map(|(filter, _)| filter),
+                        chat_id
+                    );
+
+                    let option = get_default_option(&redis, &chat_id).await;
+                    handle_balances_with_option(
+                        &redis,
+                        bot,
+                        dialogue,
+                        msg,
+                        sender_id,
+                        option,
+                        filter,
+                        Some(id),
+                    )
+                    .await?;
+                }
+                CONVERT_TO_BUTTON => {
+                    let mut suggestions =
+                        retrieve_frequent_currencies(&redis, &chat_id, CURRENCY_SUGGESTIONS_MAX)
+                            .await
+                            .unwrap_or_default();
+                    suggestions.retain(|currency| currency != CURRENCY_DEFAULT.0);
+                    let mut buttons: Vec<&str> = suggestions
+                        .iter()
+                        .map(|currency| currency.as_str())
+                        .collect();
+                    buttons.push(CANCEL_BUTTON);
+
+                    let language = retrieve_language(&redis, &chat_id).await;
+                    let new_message =
+                        send_bot_message(&bot, &msg, translate(&language, CONVERT_TO_PROMPT, &[]))
+                            .reply_markup(make_keyboard(buttons, Some(2), &language))
+                            .await?
+                            .id;
+                    dialogue
+                        .update(State::BalancesConvertTo {
+                            messages: MessageTracker::new(new_message),
+                            balances_id: id,
+                        })
+                        .await?;
+                }
                 _ if button.as_str().starts_with("Convert To ") => {
                     let option = StatementOption::ConvertCurrency;
-                    handle_balances_with_option(bot, dialogue, msg, sender_id, option, Some(id))
-                        .await?;
+                    handle_balances_with_option(
+                        &redis,
+                        bot,
+                        dialogue,
+                        msg,
+                        sender_id,
+                        option,
+                        None,
+                        Some(id),
+                    )
+                    .await?;
                 }
                 _ if button.as_str() == "No Currency" => {
                     let option = StatementOption::Currency(CURRENCY_DEFAULT.0.to_string());
-                    handle_balances_with_option(bot, dialogue, msg, sender_id, option, Some(id))
-                        .await?;
+                    handle_balances_with_option(
+                        &redis,
+                        bot,
+                        dialogue,
+                        msg,
+                        sender_id,
+                        option,
+                        None,
+                        Some(id),
+                    )
+                    .await?;
                 }
                 _ if button.as_str().len() == 3 => {
                     let option = StatementOption::Currency(button.as_str().to_string());
-                    handle_balances_with_option(bot, dialogue, msg, sender_id, option, Some(id))
-                        .await?;
+                    handle_balances_with_option(
+                        &redis,
+                        bot,
+                        dialogue,
+                        msg,
+                        sender_id,
+                        option,
+                        None,
+                        Some(id),
+                    )
+                    .await?;
                 }
                 _ => {
                     log::error!(
@@ -225,3 +534,164 @@ pub async fn action_balances_menu(
 
     Ok(())
 }
+
+// Shows the balances converted to the chosen currency, in place of the balances shown before
+async fn complete_balances_convert_to(
+    redis: &Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: Message,
+    sender_id: String,
+    (messages, balances_id): (MessageTracker, MessageId),
+    currency: Option<String>,
+) -> HandlerResult {
+    cleanup_messages(redis, &bot, &msg.chat.id.to_string(), messages).await?;
+    match currency {
+        Some(currency) => {
+            let option = StatementOption::ConvertTo(currency);
+            handle_balances_with_option(
+                redis,
+                bot,
+                dialogue,
+                msg,
+                sender_id,
+                option,
+                None,
+                Some(balances_id),
+            )
+            .await?;
+        }
+        None => {
+            dialogue.update(State::BalancesMenu).await?;
+        }
+    }
+    Ok(())
+}
+
+/* Cancels converting the balances to another currency.
+ * Called when the user cancels while being asked for the currency.
+ */
+pub async fn cancel_balances_convert_to(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: Message,
+    (messages, balances_id): (MessageTracker, MessageId),
+) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let sender_id = msg
+        .from()
+        .map(|user| user.id.to_string())
+        .unwrap_or_default();
+    let chat_id = msg.chat.id.to_string();
+    send_bot_message(
+        &bot,
+        &msg,
+        t(&redis, &chat_id, CONVERT_TO_CANCELLED, &[]).await,
+    )
+    .await?;
+    complete_balances_convert_to(
+        &redis,
+        bot,
+        dialogue,
+        msg,
+        sender_id,
+        (messages, balances_id),
+        None,
+    )
+    .await
+}
+
+/* Converts the balances to another currency, without changing any settings.
+ * Bot receives a callback query for one of the suggested currencies.
+ */
+pub async fn action_balances_convert_to_menu(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    (messages, balances_id): (MessageTracker, MessageId),
+) -> HandlerResult {
+    // Buttons on the balances themselves are handled as usual, leaving the conversion
+    if let Some(msg) = query.message.as_ref().filter(|msg| msg.id == balances_id) {
+        cleanup_messages(&redis, &bot, &msg.chat.id.to_string(), messages).await?;
+        dialogue.update(State::BalancesMenu).await?;
+        return action_balances_menu(redis.clone(), bot, dialogue, query).await;
+    }
+
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+        let sender_id = query.from.id.to_string();
+
+        if let Some(msg) = query.message {
+            let currency = match button.as_str() {
+                CANCEL_BUTTON => None,
+                currency => Some(currency.to_string()),
+            };
+            complete_balances_convert_to(
+                &redis,
+                bot,
+                dialogue,
+                msg,
+                sender_id,
+                (messages, balances_id),
+                currency,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/* Converts the balances to another currency, without changing any settings.
+ * Bot receives a string for the currency code, and asks again if it is invalid.
+ */
+pub async fn action_balances_convert_to(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: Message,
+    (mut messages, balances_id): (MessageTracker, MessageId),
+) -> HandlerResult {
+    let sender_id = msg
+        .from()
+        .map(|user| user.id.to_string())
+        .unwrap_or_default();
+    let currency = match msg.text().map(|text| text.trim().to_uppercase()) {
+        Some(code) if code != CURRENCY_DEFAULT.0 => get_currency(&code).map(|_| code),
+        _ => Err(BotError::UserError(Text::new(CURRENCY_CODE_INVALID))),
+    };
+
+    match currency {
+        Ok(currency) => {
+            complete_balances_convert_to(
+                &redis,
+                bot,
+                dialogue,
+                msg,
+                sender_id,
+                (messages, balances_id),
+                Some(currency),
+            )
+            .await?;
+        }
+        Err(err) => {
+            let chat_id = msg.chat.id.to_string();
+            let reply = error_to_user_message(&redis, &err, &chat_id).await;
+            let new_message = send_bot_message(&bot, &msg, reply).await?.id;
+            messages.track(new_message);
+            dialogue
+                .update(State::BalancesConvertTo {
+                    messages,
+                    balances_id,
+                })
+                .await?;
+        }
+    }
+
+    Ok(())
+}
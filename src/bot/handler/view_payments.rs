@@ -1,32 +1,43 @@
 use teloxide::{
     payloads::SendMessageSetters,
     prelude::*,
-    types::{InlineKeyboardMarkup, Message, MessageId},
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message},
 };
 
 use crate::bot::{
     currency::{get_default_currency, Currency},
     dispatcher::State,
     handler::{
-        constants::{COMMAND_ADD_PAYMENT, UNKNOWN_ERROR_MESSAGE},
+        constants::{COMMAND_ADD_PAYMENT, COMMAND_CANCEL, COMMAND_VIEW_PAYMENTS},
         utils::{
-            display_payment, get_currency, make_keyboard, retrieve_time_zone, send_bot_message,
-            HandlerResult, UserDialogue,
+            assert_admin_only, cleanup_messages, display_payment, edit_bot_message,
+            error_to_user_message, get_currency, get_topic_id, make_keyboard, retrieve_language,
+            retrieve_topic_time_zone, send_bot_message, t, BotError, HandlerResult, MessageTracker,
+            UserDialogue,
         },
     },
+    i18n::{DUPLICATE_PAYMENT_BLOCKED, DUPLICATE_PAYMENT_REPEATED},
     processor::{view_payments, ProcessError},
-    redis::{CrudError, UserPayment},
+    redis::{CrudError, PaymentAudit, PaymentRate, Redis, UserPayment},
 };
 
 use super::{
-    action_delete_payment, action_edit_payment, block_delete_payment, block_edit_payment,
-    cancel_delete_payment, cancel_edit_payment, handle_repeated_delete_payment,
+    action_add_payment_duplicate, action_delete_payment, action_edit_payment,
+    action_view_payment_card, block_edit_payment, cancel_delete_payment, cancel_edit_payment,
     handle_repeated_edit_payment, utils::assert_handle_request_limit, SelectPaymentType,
 };
 
 /* Utilities */
 const HEADER_MESSAGE_FRONT: &str = "Anytime! ☺️\nI've recorded ";
-const HEADER_MESSAGE_BACK: &str = " payments. Here are the latest entries!\n\n";
+const HEADER_MESSAGE_BACK: &str =
+    " payments. Here are the latest entries! Tap a payment no. to see more of it!\n\n";
+const HEADER_UNSETTLED_MESSAGE_BACK: &str =
+    " payments that aren't settled yet. Here are the latest entries! Tap a payment no. to see more of it!\n\n";
+const FILTER_UNSETTLED: &str = "unsettled";
+const CANCEL_DUPLICATE_MESSAGE: &str =
+    "Okay! I've cancelled duplicating the payment. No changes have been made! 🌟";
+const SELECT_DELETE_MESSAGE: &str =
+    "🗑 Which payment no. would you like to delete? Tap to select as many as you like, then Delete!\n\n";
 
 #[derive(Clone, Debug)]
 pub struct Payment {
@@ -38,9 +49,15 @@ pub struct Payment {
     pub currency: Currency,
     pub total: i64,
     pub debts: Vec<(String, i64)>,
+    pub rate: Option<PaymentRate>,
+    pub audit: PaymentAudit,
+    pub is_private: bool,
+    pub is_transfer: bool,
+    pub is_settled: bool,
+    pub is_disputed: bool,
 }
 
-fn unfold_payment(payment: UserPayment) -> Payment {
+pub fn unfold_payment(payment: UserPayment) -> Payment {
     let currency = get_currency(&payment.payment.currency);
     match currency {
         Ok(currency) => Payment {
@@ -52,6 +69,12 @@ fn unfold_payment(payment: UserPayment) -> Payment {
             currency,
             total: payment.payment.total,
             debts: payment.payment.debts,
+            rate: payment.rate,
+            audit: payment.audit,
+            is_private: payment.is_private,
+            is_transfer: payment.is_transfer,
+            is_settled: payment.is_settled,
+            is_disputed: payment.is_disputed,
         },
         Err(_) => Payment {
             payment_id: payment.payment_id,
@@ -62,35 +85,67 @@ fn unfold_payment(payment: UserPayment) -> Payment {
             currency: get_default_currency(),
             total: payment.payment.total,
             debts: payment.payment.debts,
+            rate: payment.rate,
+            audit: payment.audit,
+            is_private: payment.is_private,
+            is_transfer: payment.is_transfer,
+            is_settled: payment.is_settled,
+            is_disputed: payment.is_disputed,
         },
     }
 }
 
-fn display_payments_paged(payments: &Vec<Payment>, page: usize, chat_id: &str) -> String {
-    let time_zone = retrieve_time_zone(chat_id);
+async fn display_payments_paged(
+    redis: &Redis,
+    payments: &[Payment],
+    page: usize,
+    chat_id: &str,
+    topic_id: Option<i32>,
+) -> String {
+    let time_zone = retrieve_topic_time_zone(redis, chat_id, topic_id).await;
     let start_index = page * 5;
-    let displayed_payments: &[Payment];
-    if start_index + 5 >= payments.len() {
-        displayed_payments = &payments[start_index..];
+    let displayed_payments = if start_index + 5 >= payments.len() {
+        &payments[start_index..]
     } else {
-        displayed_payments = &payments[start_index..start_index + 5];
-    }
+        &payments[start_index..start_index + 5]
+    };
 
     let serial_num = start_index + 1;
-    let formatted_payments = displayed_payments
-        .iter()
-        .enumerate()
-        .map(|(index, payment)| display_payment(payment, serial_num + index, time_zone));
+    let mut formatted_payments: Vec<String> = Vec::new();
+    for (index, payment) in displayed_payments.iter().enumerate() {
+        formatted_payments
+            .push(display_payment(redis, payment, serial_num + index, time_zone).await);
+    }
 
-    format!("{}", formatted_payments.collect::<Vec<String>>().join(""))
+    formatted_payments.join("").to_string()
 }
 
-fn get_navigation_menu() -> InlineKeyboardMarkup {
-    let buttons = vec!["Newer", "Older"];
-    make_keyboard(buttons, Some(2))
+// Makes the menu for navigating the payments,
+// with a button for each payment on the page to view its detail card
+fn get_navigation_menu(page: usize, payments: &[Payment]) -> InlineKeyboardMarkup {
+    let start_index = page * 5;
+    let end_index = if start_index + 5 >= payments.len() {
+        payments.len()
+    } else {
+        start_index + 5
+    };
+
+    let numbers: Vec<InlineKeyboardButton> = (start_index..end_index)
+        .map(|index| {
+            let serial_num = format!("{}", index + 1);
+            InlineKeyboardButton::callback(serial_num.clone(), serial_num)
+        })
+        .collect();
+    InlineKeyboardMarkup::new(vec![
+        numbers,
+        vec![
+            InlineKeyboardButton::callback("Newer", "Newer"),
+            InlineKeyboardButton::callback("Older", "Older"),
+        ],
+    ])
 }
 
-fn get_select_menu(page: usize, payments: &Vec<Payment>) -> InlineKeyboardMarkup {
+fn get_select_menu(page: usize, payments: &[Payment], language: &str) -> InlineKeyboardMarkup {
     let start_index = page * 5;
     let end_index = if start_index + 5 >= payments.len() {
         payments.len()
@@ -106,25 +161,68 @@ fn get_select_menu(page: usize, payments: &Vec<Payment>) -> InlineKeyboardMarkup
     make_keyboard(
         buttons.iter().map(|option| option.as_str()).collect(),
         Some(3),
+        language,
     )
 }
 
+// Makes the menu for selecting multiple payments on a page, marking those already selected.
+// Selections are kept across pages, which can be navigated within the menu.
+fn get_select_multiple_menu(
+    page: usize,
+    payments: &[Payment],
+    selected: &[usize],
+) -> InlineKeyboardMarkup {
+    let start_index = page * 5;
+    let end_index = if start_index + 5 >= payments.len() {
+        payments.len()
+    } else {
+        start_index + 5
+    };
+
+    let indices: Vec<usize> = (start_index..end_index).collect();
+    let mut keyboard: Vec<Vec<InlineKeyboardButton>> = Vec::new();
+    for chunk in indices.chunks(3) {
+        let mut row: Vec<InlineKeyboardButton> = Vec::new();
+        for index in chunk {
+            let serial_num = format!("{}", index + 1);
+            let label = if selected.contains(index) {
+                format!("✅ {serial_num}")
+            } else {
+                serial_num.clone()
+            };
+            row.push(InlineKeyboardButton::callback(label, serial_num));
+        }
+        keyboard.push(row);
+    }
+    keyboard.push(vec![
+        InlineKeyboardButton::callback("Newer", "Newer"),
+        InlineKeyboardButton::callback("Older", "Older"),
+    ]);
+    keyboard.push(vec![
+        InlineKeyboardButton::callback("Cancel", "Cancel"),
+        InlineKeyboardButton::callback(format!("Delete ({})", selected.len()), "Delete"),
+    ]);
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
 /* Handles a repeated call to edit/delete payment entry.
  * Does nothing, simply notifies the user.
  */
 pub async fn handle_repeated_select_payment(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
-    (_messages, _payments, _page, function): (
-        Vec<MessageId>,
+    (mut messages, payments, page, function): (
+        MessageTracker,
         Vec<Payment>,
         usize,
         SelectPaymentType,
     ),
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
@@ -132,8 +230,24 @@ pub async fn handle_repeated_select_payment(
         SelectPaymentType::EditPayment => {
             handle_repeated_edit_payment(bot, dialogue, state, msg).await?;
         }
-        SelectPaymentType::DeletePayment => {
-            handle_repeated_delete_payment(bot, dialogue, state, msg).await?;
+        SelectPaymentType::DuplicatePayment => {
+            let reply = t(
+                &redis,
+                &msg.chat.id.to_string(),
+                DUPLICATE_PAYMENT_REPEATED,
+                &[("cancel", COMMAND_CANCEL)],
+            )
+            .await;
+            let new_message = send_bot_message(&bot, &msg, reply).await?.id;
+            messages.track(new_message);
+            dialogue
+                .update(State::SelectPayment {
+                    messages,
+                    payments,
+                    page,
+                    function,
+                })
+                .await?;
         }
     }
     Ok(())
@@ -143,22 +257,36 @@ pub async fn handle_repeated_select_payment(
  * Can be called at any step of the process.
  */
 pub async fn cancel_select_payment(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
-    if let State::SelectPayment { ref function, .. } = state {
+    if let State::SelectPayment {
+        ref messages,
+        ref payments,
+        page,
+        ref function,
+    } = state
+    {
         match function {
             SelectPaymentType::EditPayment => {
-                cancel_edit_payment(bot, dialogue, state, msg).await?;
+                cancel_edit_payment(redis.clone(), bot, dialogue, state, msg).await?;
             }
-            SelectPaymentType::DeletePayment => {
-                cancel_delete_payment(bot, dialogue, state, msg).await?;
+            SelectPaymentType::DuplicatePayment => {
+                send_bot_message(&bot, &msg, CANCEL_DUPLICATE_MESSAGE.to_string()).await?;
+                cleanup_messages(&redis, &bot, &msg.chat.id.to_string(), messages.clone()).await?;
+                dialogue
+                    .update(State::ViewPayments {
+                        payments: payments.clone(),
+                        page,
+                    })
+                    .await?;
             }
         }
     }
@@ -170,18 +298,19 @@ pub async fn cancel_select_payment(
  * Called when user attempts to start another operation in the middle of editing/deleting a payment.
  */
 pub async fn block_select_payment(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     state: State,
     msg: Message,
-    (_messages, _payments, _page, function): (
-        Vec<MessageId>,
+    (mut messages, payments, page, function): (
+        MessageTracker,
         Vec<Payment>,
         usize,
         SelectPaymentType,
     ),
 ) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
@@ -189,44 +318,113 @@ pub async fn block_select_payment(
         SelectPaymentType::EditPayment => {
             block_edit_payment(bot, dialogue, state, msg).await?;
         }
-        SelectPaymentType::DeletePayment => {
-            block_delete_payment(bot, dialogue, state, msg).await?;
+        SelectPaymentType::DuplicatePayment => {
+            let reply = t(
+                &redis,
+                &msg.chat.id.to_string(),
+                DUPLICATE_PAYMENT_BLOCKED,
+                &[("cancel", COMMAND_CANCEL)],
+            )
+            .await;
+            let new_message = send_bot_message(&bot, &msg, reply).await?.id;
+            messages.track(new_message);
+            dialogue
+                .update(State::SelectPayment {
+                    messages,
+                    payments,
+                    page,
+                    function,
+                })
+                .await?;
         }
     }
     Ok(())
 }
 
+/* Blocks user command.
+ * Called when user attempts to duplicate payment without first viewing anything.
+ */
+pub async fn no_duplicate_payment(redis: Redis, bot: Bot, msg: Message) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    send_bot_message(
+        &bot,
+        &msg,
+        format!("Uh-oh! ❌ Sorry, please {COMMAND_VIEW_PAYMENTS} before duplicating them!"),
+    )
+    .await?;
+    Ok(())
+}
+
 /* View all payments.
  * Bot retrieves all payments, and displays the most recent 5.
  * Then, presents a previous and next page button for the user to navigate the pagination.
+ * Payments whose currency has since been settled are marked, and can be left out with "unsettled".
  */
-pub async fn action_view_payments(bot: Bot, dialogue: UserDialogue, msg: Message) -> HandlerResult {
-    if !assert_handle_request_limit(msg.clone()) {
+pub async fn action_view_payments(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: Message,
+    args: String,
+) -> HandlerResult {
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
         return Ok(());
     }
 
+    let is_unsettled_only = args.trim().eq_ignore_ascii_case(FILTER_UNSETTLED);
+    let header_back = if is_unsettled_only {
+        HEADER_UNSETTLED_MESSAGE_BACK
+    } else {
+        HEADER_MESSAGE_BACK
+    };
+
     let chat_id = msg.chat.id.to_string();
     let user = msg.from();
     if let Some(user) = user {
         let sender_id = user.id.to_string();
         let sender_username = user.username.clone();
-        let payments = view_payments(&chat_id, &sender_id, sender_username.as_deref());
+        let payments =
+            view_payments(&redis, &chat_id, &sender_id, sender_username.as_deref()).await;
         match payments {
             Ok(payments) => {
                 let payments: Vec<Payment> = payments
                     .into_iter()
-                    .map(|payment| unfold_payment(payment))
+                    .filter(|payment| !is_unsettled_only || !payment.is_settled)
+                    .map(unfold_payment)
                     .collect();
+                if payments.is_empty() {
+                    send_bot_message(
+                        &bot,
+                        &msg,
+                        "🎉 Everything is settled! There aren't any payments left to pay back."
+                            .to_string(),
+                    )
+                    .await?;
+
+                    // Logging
+                    log::info!(
+                        "View Payments - User {} viewed unsettled payments for group {}, but all were settled.",
+                        sender_id,
+                        chat_id,
+                    );
+
+                    dialogue.exit().await?;
+                    return Ok(());
+                }
                 send_bot_message(
                     &bot,
                     &msg,
                     format!(
-                        "{HEADER_MESSAGE_FRONT}{}{HEADER_MESSAGE_BACK}{}",
+                        "{HEADER_MESSAGE_FRONT}{}{header_back}{}",
                         &payments.len(),
-                        display_payments_paged(&payments, 0, &chat_id)
+                        display_payments_paged(&redis, &payments, 0, &chat_id, get_topic_id(&msg))
+                            .await
                     ),
                 )
-                .reply_markup(get_navigation_menu())
+                .reply_markup(get_navigation_menu(0, &payments))
                 .await?;
 
                 // Logging
@@ -255,7 +453,13 @@ pub async fn action_view_payments(bot: Bot, dialogue: UserDialogue, msg: Message
                 dialogue.exit().await?;
             }
             Err(err) => {
-                send_bot_message(&bot, &msg, format!("{UNKNOWN_ERROR_MESSAGE}")).await?;
+                let err = BotError::from(err);
+                send_bot_message(
+                    &bot,
+                    &msg,
+                    error_to_user_message(&redis, &err, &chat_id).await,
+                )
+                .await?;
 
                 // Logging
                 log::error!(
@@ -273,8 +477,10 @@ pub async fn action_view_payments(bot: Bot, dialogue: UserDialogue, msg: Message
 }
 
 /* Navigation function for user to interact with payment pagination menu.
+ * Selecting a payment no. shows the detail card of that payment.
 */
 pub async fn action_view_more(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     (payments, page): (Vec<Payment>, usize),
@@ -283,21 +489,31 @@ pub async fn action_view_more(
     if let Some(button) = &query.data {
         bot.answer_callback_query(query.id.to_string()).await?;
 
-        if let Some(Message { id, chat, .. }) = query.message {
+        let topic_id = query.message.as_ref().and_then(get_topic_id);
+        if let Some(msg) = &query.message {
+            let (id, chat) = (msg.id, &msg.chat);
             let chat_id = chat.id.to_string();
             match button.as_str() {
                 "Newer" => {
                     if page > 0 {
-                        bot.edit_message_text(
+                        edit_bot_message(
+                            &bot,
                             chat_id.clone(),
                             id,
                             format!(
                                 "{HEADER_MESSAGE_FRONT}{}{HEADER_MESSAGE_BACK}{}",
                                 &payments.len(),
-                                display_payments_paged(&payments, page - 1, &chat_id)
+                                display_payments_paged(
+                                    &redis,
+                                    &payments,
+                                    page - 1,
+                                    &chat_id,
+                                    topic_id
+                                )
+                                .await
                             ),
                         )
-                        .reply_markup(get_navigation_menu())
+                        .reply_markup(get_navigation_menu(page - 1, &payments))
                         .await?;
                         dialogue
                             .update(State::ViewPayments {
@@ -309,16 +525,24 @@ pub async fn action_view_more(
                 }
                 "Older" => {
                     if (page + 1) * 5 < payments.len() {
-                        bot.edit_message_text(
+                        edit_bot_message(
+                            &bot,
                             chat_id.clone(),
                             id,
                             format!(
                                 "{HEADER_MESSAGE_FRONT}{}{HEADER_MESSAGE_BACK}{}",
                                 &payments.len(),
-                                display_payments_paged(&payments, page + 1, &chat_id)
+                                display_payments_paged(
+                                    &redis,
+                                    &payments,
+                                    page + 1,
+                                    &chat_id,
+                                    topic_id
+                                )
+                                .await
                             ),
                         )
-                        .reply_markup(get_navigation_menu())
+                        .reply_markup(get_navigation_menu(page + 1, &payments))
                         .await?;
                         dialogue
                             .update(State::ViewPayments {
@@ -328,13 +552,26 @@ pub async fn action_view_more(
                             .await?;
                     }
                 }
-                _ => {
-                    log::error!(
-                        "View Payments Menu - Invalid button in chat {}: {}",
-                        chat.id,
-                        button
-                    );
-                }
+                num => match num.parse::<usize>() {
+                    Ok(serial_num) if serial_num <= payments.len() && serial_num > 0 => {
+                        action_view_payment_card(
+                            &redis,
+                            &bot,
+                            &dialogue,
+                            msg,
+                            (payments, page),
+                            serial_num - 1,
+                        )
+                        .await?;
+                    }
+                    _ => {
+                        log::error!(
+                            "View Payments Menu - Invalid button in chat {}: {}",
+                            chat.id,
+                            button
+                        );
+                    }
+                },
             }
         }
     }
@@ -347,12 +584,18 @@ pub async fn action_view_more(
  * Points to SelectPayment state.
  */
 pub async fn action_select_payment_edit(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     msg: Message,
     (payments, page): (Vec<Payment>, usize),
 ) -> HandlerResult {
-    let keyboard = get_select_menu(page, &payments);
+    if !assert_admin_only(&redis, &bot, &msg).await? {
+        return Ok(());
+    }
+
+    let language = retrieve_language(&redis, &msg.chat.id.to_string()).await;
+    let keyboard = get_select_menu(page, &payments, &language);
 
     let new_message = send_bot_message(
         &bot,
@@ -365,7 +608,7 @@ pub async fn action_select_payment_edit(
 
     dialogue
         .update(State::SelectPayment {
-            messages: vec![new_message],
+            messages: MessageTracker::new(new_message).with_command(msg.id),
             payments,
             page,
             function: SelectPaymentType::EditPayment,
@@ -375,22 +618,28 @@ pub async fn action_select_payment_edit(
     Ok(())
 }
 
-/* Entry point for delete payment function.
+/* Entry point for duplicate payment function.
  * Bot responds by providing button menu of payments to choose from.
  * Points to SelectPayment state.
  */
-pub async fn action_select_payment_delete(
+pub async fn action_select_payment_duplicate(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     msg: Message,
     (payments, page): (Vec<Payment>, usize),
 ) -> HandlerResult {
-    let keyboard = get_select_menu(page, &payments);
+    if !assert_handle_request_limit(&redis, msg.clone()).await {
+        return Ok(());
+    }
+
+    let language = retrieve_language(&redis, &msg.chat.id.to_string()).await;
+    let keyboard = get_select_menu(page, &payments, &language);
 
     let new_message = send_bot_message(
         &bot,
         &msg,
-        "🗑 Which payment no. would you like to delete?".to_string(),
+        "📋 Which payment no. would you like to duplicate? I'll date the copy today!".to_string(),
     )
     .reply_markup(keyboard)
     .await?
@@ -398,25 +647,182 @@ pub async fn action_select_payment_delete(
 
     dialogue
         .update(State::SelectPayment {
-            messages: vec![new_message],
+            messages: MessageTracker::new(new_message).with_command(msg.id),
             payments,
             page,
-            function: SelectPaymentType::DeletePayment,
+            function: SelectPaymentType::DuplicatePayment,
         })
         .await?;
 
     Ok(())
 }
 
+/* Entry point for delete payment function.
+ * Bot responds by providing button menu of payments to choose from,
+ * where multiple payments can be selected to be deleted together.
+ * Points to SelectPaymentsDelete state.
+ */
+pub async fn action_select_payment_delete(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    msg: Message,
+    (payments, page): (Vec<Payment>, usize),
+) -> HandlerResult {
+    if !assert_admin_only(&redis, &bot, &msg).await? {
+        return Ok(());
+    }
+
+    let selected: Vec<usize> = Vec::new();
+    let keyboard = get_select_multiple_menu(page, &payments, &selected);
+
+    let new_message = send_bot_message(
+        &bot,
+        &msg,
+        format!(
+            "{SELECT_DELETE_MESSAGE}{}",
+            display_payments_paged(
+                &redis,
+                &payments,
+                page,
+                &msg.chat.id.to_string(),
+                get_topic_id(&msg)
+            )
+            .await
+        ),
+    )
+    .reply_markup(keyboard)
+    .await?
+    .id;
+
+    dialogue
+        .update(State::SelectPaymentsDelete {
+            messages: MessageTracker::new(new_message).with_command(msg.id),
+            payments,
+            page,
+            selected,
+        })
+        .await?;
+
+    Ok(())
+}
+
+/* Handles user response for selecting payments to delete.
+ * Bot retrieves a callback query, and toggles the selected payment, or moves between pages.
+ * Once the user is done, proceeds to confirm the deletion of all selected payments.
+ */
+pub async fn action_select_payments_delete(
+    redis: Redis,
+    bot: Bot,
+    dialogue: UserDialogue,
+    query: CallbackQuery,
+    state: State,
+    (messages, payments, page, mut selected): (MessageTracker, Vec<Payment>, usize, Vec<usize>),
+) -> HandlerResult {
+    if let Some(button) = &query.data {
+        bot.answer_callback_query(query.id.to_string()).await?;
+
+        if let Some(msg) = &query.message {
+            let chat_id = msg.chat.id.to_string();
+            let id = msg.id;
+            let mut new_page = page;
+            match button.as_str() {
+                "Cancel" => {
+                    cancel_delete_payment(redis.clone(), bot, dialogue, state, msg.clone()).await?;
+                    return Ok(());
+                }
+                "Delete" => {
+                    if !selected.is_empty() {
+                        selected.sort();
+                        action_delete_payment(
+                            &redis,
+                            bot,
+                            dialogue,
+                            msg,
+                            id,
+                            (messages, payments, page),
+                            selected,
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                }
+                "Newer" => {
+                    if page == 0 {
+                        return Ok(());
+                    }
+                    new_page = page - 1;
+                }
+                "Older" => {
+                    if (page + 1) * 5 >= payments.len() {
+                        return Ok(());
+                    }
+                    new_page = page + 1;
+                }
+                num => match num.parse::<usize>() {
+                    Ok(serial_num) if serial_num <= payments.len() && serial_num > 0 => {
+                        let index = serial_num - 1;
+                        match selected.iter().position(|sel| *sel == index) {
+                            Some(position) => {
+                                selected.remove(position);
+                            }
+                            None => selected.push(index),
+                        }
+                    }
+                    _ => {
+                        // Logging
+                        log::error!(
+                            "Select Payments Delete - Invalid serial number {} in chat {}",
+                            num,
+                            chat_id,
+                        );
+                        return Ok(());
+                    }
+                },
+            }
+
+            edit_bot_message(
+                &bot,
+                chat_id.clone(),
+                id,
+                format!(
+                    "{SELECT_DELETE_MESSAGE}{}",
+                    display_payments_paged(
+                        &redis,
+                        &payments,
+                        new_page,
+                        &chat_id,
+                        get_topic_id(msg)
+                    )
+                    .await
+                ),
+            )
+            .reply_markup(get_select_multiple_menu(new_page, &payments, &selected))
+            .await?;
+            dialogue
+                .update(State::SelectPaymentsDelete {
+                    messages,
+                    payments,
+                    page: new_page,
+                    selected,
+                })
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
 /* Handles user response for selecting a payment.
  * Bot retrieves a callback query, and displays the payment.
  */
 pub async fn action_select_payment_number(
+    redis: Redis,
     bot: Bot,
     dialogue: UserDialogue,
     query: CallbackQuery,
     state: State,
-    (messages, payments, page, function): (Vec<MessageId>, Vec<Payment>, usize, SelectPaymentType),
+    (messages, payments, page, function): (MessageTracker, Vec<Payment>, usize, SelectPaymentType),
 ) -> HandlerResult {
     if let Some(button) = &query.data {
         bot.answer_callback_query(query.id.to_string()).await?;
@@ -426,7 +832,14 @@ pub async fn action_select_payment_number(
             let id = msg.id;
             match button.as_str() {
                 "Cancel" => {
-                    cancel_select_payment(bot, dialogue, state, query.message.unwrap()).await?;
+                    cancel_select_payment(
+                        redis.clone(),
+                        bot,
+                        dialogue,
+                        state,
+                        query.message.unwrap(),
+                    )
+                    .await?;
                 }
                 num => {
                     let parsing = num.parse::<usize>();
@@ -437,6 +850,7 @@ pub async fn action_select_payment_number(
                             match function {
                                 SelectPaymentType::EditPayment => {
                                     action_edit_payment(
+                                        &redis,
                                         bot,
                                         dialogue,
                                         msg,
@@ -446,19 +860,21 @@ pub async fn action_select_payment_number(
                                     )
                                     .await?;
                                 }
-                                SelectPaymentType::DeletePayment => {
-                                    action_delete_payment(
-                                        bot,
-                                        dialogue,
+                                SelectPaymentType::DuplicatePayment => {
+                                    action_add_payment_duplicate(
+                                        &redis,
+                                        &bot,
+                                        &dialogue,
                                         msg,
-                                        id,
-                                        (messages, payments, page),
-                                        index,
+                                        &query.from,
+                                        messages,
+                                        &payments[index],
                                     )
                                     .await?;
                                 }
                             }
                         } else {
+                            cleanup_messages(&redis, &bot, &chat_id, messages).await?;
                             dialogue
                                 .update(State::ViewPayments { payments, page })
                                 .await?;
@@ -471,6 +887,7 @@ pub async fn action_select_payment_number(
                             );
                         }
                     } else {
+                        cleanup_messages(&redis, &bot, &chat_id, messages).await?;
                         dialogue
                             .update(State::ViewPayments { payments, page })
                             .await?;
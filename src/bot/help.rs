@@ -0,0 +1,362 @@
+/* Help explains each command in depth, for the help menu.
+ * Each topic gives the syntax of a command, some examples, and the common errors met with it.
+ * Topics are looked up by command name, ignoring case and any leading slash,
+ * so that both "/help addpayment" and "/help /AddPayment" work.
+ */
+
+// Represents the help page of a single command.
+#[derive(Debug, PartialEq)]
+pub struct HelpTopic {
+    pub command: &'static str,
+    pub summary: &'static str,
+    pub syntax: &'static [&'static str],
+    pub examples: &'static [&'static str],
+    pub errors: &'static [&'static str],
+}
+
+pub const HELP_TOPICS: &[HelpTopic] = &[
+    HelpTopic {
+        command: "start",
+        summary: "Introduces me and sets me up for the group. In a direct message with me, also turns on messages whenever you're added to a payment in a group.",
+        syntax: &["/start"],
+        examples: &["/start"],
+        errors: &[],
+    },
+    HelpTopic {
+        command: "help",
+        summary: "Shows all my commands, or how to use one of them.",
+        syntax: &["/help", "/help <command>"],
+        examples: &["/help", "/help addpayment", "/help settings"],
+        errors: &["I only know the commands listed in /help. Leave out anything after the command name, like @PayScribeBot."],
+    },
+    HelpTopic {
+        command: "addpayment",
+        summary: "Adds a new payment, step by step: a description, who paid, the total, and how to split it. Check the overview, edit anything, then confirm.",
+        syntax: &["/addpayment", "Total: <amount> [currency] [+<tip>%] [+<tax>] [@ <rate>]"],
+        examples: &["Total: 100 USD", "Total: 80 USD +10% tip +7% tax", "Total: 50 USD @ 1.37", "Date (from Edit): yesterday, 3 days ago, 2024-03-12"],
+        errors: &[
+            "Descriptions can be at most 100 characters long.",
+            "Usernames need to be 5 to 32 characters long, with only letters, numbers and underscores.",
+            "A payment can be split between at most 50 people.",
+            "Amounts in a split need to add up to the total.",
+            "A rate is only for a currency other than the default currency, and converts into it.",
+        ],
+    },
+    HelpTopic {
+        command: "payback",
+        summary: "Records paying back one or more people in the group, so the balances are settled. Tap who you owe to pay them back in full, or enter the amounts yourself.",
+        syntax: &["/payback", "<@username> <amount> [currency]"],
+        examples: &["@alice 10", "@alice 10 USD @bob 5 EUR"],
+        errors: &["Only one payment can be added or edited at a time. Finish it or /cancel it first."],
+    },
+    HelpTopic {
+        command: "viewpayments",
+        summary: "Lists all payments of the group, newest first. Tap a payment no. to open its card, to edit, delete or duplicate it, or add a note or receipt. Payments are marked ✅ once everyone has been paid back in their currency. Anyone in the split can dispute a payment from its card, marking it ⚠️ until the payer edits it or dismisses the dispute.",
+        syntax: &["/viewpayments", "/viewpayments unsettled"],
+        examples: &["/viewpayments", "/viewpayments unsettled"],
+        errors: &["Notes and receipts of private payments aren't shown in the group."],
+    },
+    HelpTopic {
+        command: "editpayment",
+        summary: "Edits a previous payment. Pick it from the list, change any part of it, then confirm. Tap History to go back to an earlier version.",
+        syntax: &["/editpayment"],
+        examples: &["/editpayment"],
+        errors: &[
+            "In an admin-only group, only admins can edit payments.",
+            "Amounts in a split need to add up to the total.",
+        ],
+    },
+    HelpTopic {
+        command: "deletepayment",
+        summary: "Deletes previous payments. Pick as many as you like from the list, and delete them in one go. Deleted payments go to the /trash for 30 days.",
+        syntax: &["/deletepayment"],
+        examples: &["/deletepayment"],
+        errors: &["In an admin-only group, only admins can delete payments."],
+    },
+    HelpTopic {
+        command: "duplicatepayment",
+        summary: "Adds a copy of a previous payment, dated today. Confirm or edit the copy like any new payment.",
+        syntax: &["/duplicatepayment"],
+        examples: &["/duplicatepayment"],
+        errors: &["The copy doesn't keep the due date of the original."],
+    },
+    HelpTopic {
+        command: "balances",
+        summary: "Shows who owes whom. Use the buttons to export a PDF, get a formal statement, convert the debts to any currency, or see only what you owe or are owed.",
+        syntax: &["/balances"],
+        examples: &["/balances", "/settings pairwise on"],
+        errors: &["Converting needs exchange rates, which may be unavailable for a while."],
+    },
+    HelpTopic {
+        command: "spendings",
+        summary: "Shows how much everyone has spent. Tap Consumed vs Fronted to compare shares of expenses with what was paid upfront, or Forecast to see where this month is headed.",
+        syntax: &["/spendings"],
+        examples: &["/spendings"],
+        errors: &["Transfers don't count towards spendings."],
+    },
+    HelpTopic {
+        command: "owed",
+        summary: "Shows where two people stand with each other, counting only payments between them.",
+        syntax: &["/owed <@username>", "/owed <@username> <@username>"],
+        examples: &["/owed @alice", "/owed @alice @bob"],
+        errors: &["This may differ from /balances, which simplifies debts across everyone."],
+    },
+    HelpTopic {
+        command: "settings",
+        summary: "Shows and edits my settings for the group, from a menu or directly.",
+        syntax: &["/settings", "/settings <setting> <value>", "/settings topic <setting> <value>"],
+        examples: &[
+            "/settings timezone Singapore",
+            "/settings currency SGD",
+            "/settings erase off",
+            "/settings weekstart sunday",
+            "/settings currencies SGD USD",
+            "/settings topic currency JPY",
+        ],
+        errors: &[
+            "Time zones and currencies need to be ones I support. Check the User Guide if unsure.",
+            "Some settings can only be changed by admins.",
+        ],
+    },
+    HelpTopic {
+        command: "trip",
+        summary: "Starts, ends, or shows a trip. Payments added during a trip count towards both the trip and the group.",
+        syntax: &["/trip start <name>", "/trip end", "/trip end csv", "/trip balances <name>", "/trip spendings <name>"],
+        examples: &["/trip start Japan", "/trip balances Japan", "/trip end", "/trip end csv"],
+        errors: &["A trip needs to be going on to end it."],
+    },
+    HelpTopic {
+        command: "stats",
+        summary: "Shows this month's stats, such as the total spent, the top spender, and the change from last month.",
+        syntax: &["/stats"],
+        examples: &["/stats"],
+        errors: &[],
+    },
+    HelpTopic {
+        command: "remind",
+        summary: "Reminds everyone with outstanding balances, mentioning who owes whom.",
+        syntax: &["/remind"],
+        examples: &["/remind", "/settings reminder 3"],
+        errors: &[],
+    },
+    HelpTopic {
+        command: "limit",
+        summary: "Sets, removes, or shows monthly spending limits. I'll let the group know once someone's share this month crosses their limit. A weekly cap for everyone together makes the payer override it to add more payments.",
+        syntax: &[
+            "/limit",
+            "/limit <@username> <amount> <currency>",
+            "/limit <@username> off",
+            "/limit cap <amount> <currency>",
+            "/limit cap off",
+        ],
+        examples: &["/limit @alice 300 USD", "/limit @alice off", "/limit cap 500 USD"],
+        errors: &["Only spendings in the currency of the limit are counted."],
+    },
+    HelpTopic {
+        command: "template",
+        summary: "Saves payments you add often as templates, and adds them again in one go.",
+        syntax: &["/template", "/template save <name>", "/template use <name>", "/template delete <name>"],
+        examples: &["/template save groceries", "/template use groceries"],
+        errors: &["There needs to be a payment to save first. Add one with /addpayment."],
+    },
+    HelpTopic {
+        command: "alias",
+        summary: "Shows a name for someone instead of their username. Notifications still mention their username.",
+        syntax: &["/alias", "/alias <@username> <name>", "/alias remove <@username>"],
+        examples: &["/alias @jsmith1992 John", "/alias remove @jsmith1992"],
+        errors: &[],
+    },
+    HelpTopic {
+        command: "adjust",
+        summary: "Corrects someone's balance without a made-up payment. + means they are owed more, and - means they owe more, with the other side shared by everyone else in the group.",
+        syntax: &["/adjust <@username> <+/-amount> [currency] <reason>"],
+        examples: &["/adjust @alice +15 USD forgot her share of the taxi", "/adjust @bob -4.50 double counted drinks"],
+        errors: &[
+            "Only admins can adjust balances.",
+            "The amount needs a + or - sign, and a reason is needed.",
+            "The user needs to be in a payment in this group already.",
+        ],
+    },
+    HelpTopic {
+        command: "archive",
+        summary: "Backs up everything in the group as a file, to be restored with /restore.",
+        syntax: &["/archive"],
+        examples: &["/archive"],
+        errors: &[],
+    },
+    HelpTopic {
+        command: "restore",
+        summary: "Restores the group from a backup file, replacing all its current records.",
+        syntax: &["/restore (as a reply to the file)", "/restore confirm"],
+        examples: &["/restore", "/restore confirm"],
+        errors: &[
+            "Only admins can restore a backup.",
+            "Reply to the backup file itself with /restore.",
+        ],
+    },
+    HelpTopic {
+        command: "recalculate",
+        summary: "Recalculates all balances and spendings from scratch, by going through every payment again.",
+        syntax: &["/recalculate"],
+        examples: &["/recalculate"],
+        errors: &["Only admins can recalculate the balances."],
+    },
+    HelpTopic {
+        command: "audit",
+        summary: "Shows the latest 50 changes to payments, with who made them and when.",
+        syntax: &["/audit"],
+        examples: &["/audit"],
+        errors: &[],
+    },
+    HelpTopic {
+        command: "trash",
+        summary: "Shows the latest deleted payments, kept for 30 days. Tap a number to restore that payment, along with its balances.",
+        syntax: &["/trash"],
+        examples: &["/trash"],
+        errors: &[
+            "In an admin-only group, only admins can restore payments.",
+            "Restored payments don't keep their due date.",
+        ],
+    },
+    HelpTopic {
+        command: "private",
+        summary: "Shows the private payments you're in, with their descriptions, across all your groups.",
+        syntax: &["/private"],
+        examples: &["/private"],
+        errors: &["Only works in a direct message with me."],
+    },
+    HelpTopic {
+        command: "consolidate",
+        summary: "Nets your debts with each person across all the groups you share, so you can settle up once.",
+        syntax: &["/consolidate"],
+        examples: &["/consolidate"],
+        errors: &[
+            "Only works in a direct message with me.",
+            "Record the paybacks in each group afterwards, so the balances there stay right.",
+        ],
+    },
+    HelpTopic {
+        command: "paylink",
+        summary: "Sets the link or phone number others can pay you back with.",
+        syntax: &["/paylink <link or phone number>", "/paylink off"],
+        examples: &["/paylink https://paypal.me/alice", "/paylink +6591234567", "/paylink off"],
+        errors: &[],
+    },
+    HelpTopic {
+        command: "leaderboard",
+        summary: "Shows the top payers, the most frequent debtors, and the fastest settlers.",
+        syntax: &["/leaderboard [week | month | year | all]"],
+        examples: &["/leaderboard", "/leaderboard year"],
+        errors: &[],
+    },
+    HelpTopic {
+        command: "group",
+        summary: "Creates groups of people you often split payments with, to pick them all in one tap.",
+        syntax: &["/group", "/group create <name> <@username>...", "/group delete <name>"],
+        examples: &["/group create roommates @alice @bob @carol", "/group delete roommates"],
+        errors: &["Group names can't be anyone's username."],
+    },
+    HelpTopic {
+        command: "import",
+        summary: "Imports payments from a CSV file, such as a Splitwise export. A preview is shown first.",
+        syntax: &["/import (as a reply to the file)", "/import confirm"],
+        examples: &["/import", "/import confirm"],
+        errors: &[
+            "Names in a Splitwise export need to be changed to Telegram usernames first.",
+            "Other files need the columns Description, Paid By, Amount and Split With.",
+        ],
+    },
+    HelpTopic {
+        command: "split",
+        summary: "Splits an amount equally right away, with you as the payer. Tap Undo on the confirmation to remove it.",
+        syntax: &["/split <amount> [currency] [description] [@username...]"],
+        examples: &["/split 60 USD dinner", "/split 45.50 taxi @alice @bob"],
+        errors: &["Only the payer can undo a split."],
+    },
+    HelpTopic {
+        command: "tutorial",
+        summary: "Walks you through adding a payment, viewing balances, and paying back, with practice payments that won't touch the balances of the chat.",
+        syntax: &["/tutorial"],
+        examples: &["/tutorial"],
+        errors: &["Only the person who started a tutorial can continue it."],
+    },
+    HelpTopic {
+        command: "cancel",
+        summary: "Cancels whatever I'm doing, such as adding or editing a payment.",
+        syntax: &["/cancel"],
+        examples: &["/cancel"],
+        errors: &[],
+    },
+];
+
+// Gets the help topic of a command, ignoring case and any leading slash
+pub fn get_help_topic(command: &str) -> Option<&'static HelpTopic> {
+    let command = command.trim().trim_start_matches('/').to_lowercase();
+    HELP_TOPICS.iter().find(|topic| topic.command == command)
+}
+
+// Formats the help page of a command, with its syntax, examples and common errors
+pub fn format_help_topic(topic: &HelpTopic) -> String {
+    let mut page = format!("📖 /{}\n\n{}", topic.command, topic.summary);
+
+    let sections = [
+        ("⌨️ Syntax", topic.syntax),
+        ("💡 Examples", topic.examples),
+        ("⚠️ Common Errors", topic.errors),
+    ];
+    for (title, lines) in sections {
+        if lines.is_empty() {
+            continue;
+        }
+        page.push_str(&format!("\n\n{title}"));
+        for line in lines {
+            page.push_str(&format!("\n• {line}"));
+        }
+    }
+
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::Command;
+    use teloxide::utils::command::BotCommands;
+
+    #[test]
+    fn test_get_help_topic() {
+        assert_eq!(get_help_topic("addpayment").unwrap().command, "addpayment");
+        assert_eq!(get_help_topic("/AddPayment").unwrap().command, "addpayment");
+        assert_eq!(get_help_topic(" settings ").unwrap().command, "settings");
+        assert_eq!(get_help_topic("nosuchcommand"), None);
+        assert_eq!(get_help_topic(""), None);
+    }
+
+    #[test]
+    fn test_all_commands_have_topics() {
+        for command in Command::bot_commands() {
+            let name = command.command.trim_start_matches('/');
+            assert!(
+                get_help_topic(name).is_some(),
+                "Command {} has no help topic",
+                name
+            );
+        }
+        assert_eq!(HELP_TOPICS.len(), Command::bot_commands().len());
+    }
+
+    #[test]
+    fn test_format_help_topic() {
+        let topic = HelpTopic {
+            command: "stats",
+            summary: "Shows stats.",
+            syntax: &["/stats"],
+            examples: &["/stats"],
+            errors: &[],
+        };
+        assert_eq!(
+            format_help_topic(&topic),
+            "📖 /stats\n\nShows stats.\n\n⌨️ Syntax\n• /stats\n\n💡 Examples\n• /stats"
+        );
+    }
+}
@@ -0,0 +1,3952 @@
+use std::{collections::HashMap, fmt, sync::OnceLock};
+
+/* Localization of the bot's messages.
+ * Messages are kept in a static catalog, keyed by language code and message key.
+ * Arguments in a message are written as {name}, and substituted when translating.
+ * Falls back to English when a message is not available in a language.
+ */
+
+// Represents a language with a code and its own name.
+pub type Language = (&'static str, &'static str);
+
+pub const LANGUAGE_DEFAULT: Language = ("en", "English");
+pub const LANGUAGES: [Language; 2] = [LANGUAGE_DEFAULT, ("zh", "中文")];
+
+// Message keys
+pub const UNKNOWN_ERROR: &str = "unknown_error";
+pub const NO_TEXT: &str = "no_text";
+pub const START_INTRODUCTION: &str = "start_introduction";
+pub const START_ADD_INFO: &str = "start_add_info";
+pub const START_VIEW_INFO: &str = "start_view_info";
+pub const START_CLOSING: &str = "start_closing";
+pub const HELP_TITLE: &str = "help_title";
+pub const HELP_USER_GUIDE: &str = "help_user_guide";
+pub const HELP_FEEDBACK: &str = "help_feedback";
+pub const HELP_MENU_INFO: &str = "help_menu_info";
+pub const HELP_UNKNOWN_COMMAND: &str = "help_unknown_command";
+pub const CANCEL_NOTHING: &str = "cancel_nothing";
+pub const LANGUAGE_MENU: &str = "language_menu";
+pub const LANGUAGE_UPDATED: &str = "language_updated";
+pub const VALIDATION_ERROR: &str = "validation_error";
+pub const RATE_LIMIT_ERROR: &str = "rate_limit_error";
+pub const CONVERSION_ERROR: &str = "conversion_error";
+pub const TELEGRAM_ERROR: &str = "telegram_error";
+pub const STATEMENT_TITLE: &str = "statement_title";
+pub const STATEMENT_DEBT: &str = "statement_debt";
+pub const STATEMENT_EMPTY: &str = "statement_empty";
+pub const STATEMENT_CLOSING: &str = "statement_closing";
+pub const CLEANUP_COMPACT: &str = "cleanup_compact";
+pub const DELETE_PERMISSION: &str = "delete_permission";
+pub const TOTAL_PROMPT: &str = "total_prompt";
+pub const TOTAL_QUESTION: &str = "total_question";
+pub const TOTAL_INSTRUCTIONS: &str = "total_instructions";
+pub const CURRENCY_INSTRUCTIONS: &str = "currency_instructions";
+pub const DATE_INSTRUCTIONS: &str = "date_instructions";
+pub const DATE_UNRECOGNIZED: &str = "date_unrecognized";
+pub const DATE_IN_FUTURE: &str = "date_in_future";
+pub const DUE_DATE_INSTRUCTIONS: &str = "due_date_instructions";
+pub const DUE_DATE_IN_PAST: &str = "due_date_in_past";
+pub const TIME_ZONE_INSTRUCTIONS: &str = "time_zone_instructions";
+pub const SPLIT_PROMPT: &str = "split_prompt";
+pub const DEBT_FORMATS: &str = "debt_formats";
+pub const DEBT_USERS_PROMPT: &str = "debt_users_prompt";
+pub const DEBT_AMOUNTS_PROMPT: &str = "debt_amounts_prompt";
+pub const DEBT_PERCENTAGES_PROMPT: &str = "debt_percentages_prompt";
+pub const DEBT_ADJUSTMENTS_PROMPT: &str = "debt_adjustments_prompt";
+pub const DEBT_ITEMS_PROMPT: &str = "debt_items_prompt";
+pub const DEBT_EQUAL_INSTRUCTIONS: &str = "debt_equal_instructions";
+pub const DEBT_EQUAL_PICKER: &str = "debt_equal_picker";
+pub const DEBT_EXACT_INSTRUCTIONS: &str = "debt_exact_instructions";
+pub const DEBT_RATIO_INSTRUCTIONS: &str = "debt_ratio_instructions";
+pub const DEBT_PERCENT_INSTRUCTIONS: &str = "debt_percent_instructions";
+pub const DEBT_ADJUSTED_INSTRUCTIONS: &str = "debt_adjusted_instructions";
+pub const DEBT_ITEMIZED_INSTRUCTIONS: &str = "debt_itemized_instructions";
+pub const EDIT_DATE_PROMPT: &str = "edit_date_prompt";
+pub const EDIT_DUE_DATE_PROMPT: &str = "edit_due_date_prompt";
+pub const EDIT_TOTAL_PROMPT: &str = "edit_total_prompt";
+pub const EDIT_CURRENCY_PROMPT: &str = "edit_currency_prompt";
+pub const EDIT_SPLIT_PROMPT: &str = "edit_split_prompt";
+pub const PAY_BACK_CREDITORS_PROMPT: &str = "pay_back_creditors_prompt";
+pub const PAY_BACK_PROMPT: &str = "pay_back_prompt";
+pub const PAY_BACK_CURRENCY_QUESTION: &str = "pay_back_currency_question";
+pub const PAY_BACK_CURRENCY_PROMPT: &str = "pay_back_currency_prompt";
+pub const PAY_BACK_INSTRUCTIONS: &str = "pay_back_instructions";
+pub const SETTINGS_TIME_ZONE_PROMPT: &str = "settings_time_zone_prompt";
+pub const SETTINGS_CURRENCY_PROMPT: &str = "settings_currency_prompt";
+pub const STATEMENT_INSTRUCTIONS: &str = "statement_instructions";
+pub const ADMIN_ONLY: &str = "admin_only";
+
+pub const PRIVATE_PAYMENT: &str = "private_payment";
+pub const UNKNOWN_CURRENCY: &str = "unknown_currency";
+pub const AMOUNT_TOO_LARGE: &str = "amount_too_large";
+pub const AMOUNTS_CURRENCY_MISMATCH: &str = "amounts_currency_mismatch";
+pub const MONEY_OVERFLOW: &str = "money_overflow";
+pub const MONEY_CURRENCY_MISMATCH: &str = "money_currency_mismatch";
+pub const INVALID_USERNAME: &str = "invalid_username";
+pub const DESCRIPTION_MISSING: &str = "description_missing";
+pub const DESCRIPTION_TOO_LONG: &str = "description_too_long";
+pub const TOO_MANY_DEBTORS: &str = "too_many_debtors";
+pub const TOO_MANY_PAYMENTS: &str = "too_many_payments";
+pub const INVALID_NUMBER: &str = "invalid_number";
+pub const NO_DECIMAL_PLACES: &str = "no_decimal_places";
+pub const TOO_MANY_DECIMAL_PLACES: &str = "too_many_decimal_places";
+pub const NOT_POSITIVE: &str = "not_positive";
+pub const EXPRESSION_TOO_LONG: &str = "expression_too_long";
+pub const EXPRESSION_UNRECOGNIZED: &str = "expression_unrecognized";
+pub const DIVIDE_BY_ZERO: &str = "divide_by_zero";
+pub const UNCLOSED_BRACKET: &str = "unclosed_bracket";
+pub const CURRENCY_NOT_ALLOWED: &str = "currency_not_allowed";
+pub const FORMAT_UNRECOGNIZED: &str = "format_unrecognized";
+pub const SURCHARGE_MISSING_AMOUNT: &str = "surcharge_missing_amount";
+pub const SURCHARGE_UNRECOGNIZED: &str = "surcharge_unrecognized";
+pub const RATE_UNRECOGNIZED: &str = "rate_unrecognized";
+pub const USERNAMES_MISSING: &str = "usernames_missing";
+pub const TOTAL_MISSING: &str = "total_missing";
+pub const CURRENCY_MISSING: &str = "currency_missing";
+pub const PAYER_MISSING: &str = "payer_missing";
+pub const AMOUNTS_EXCEED_TOTAL: &str = "amounts_exceed_total";
+pub const PERCENTAGES_NOT_HUNDRED: &str = "percentages_not_hundred";
+pub const ITEM_AMOUNT_MISSING: &str = "item_amount_missing";
+pub const ITEM_PERCENT_WITH_USERS: &str = "item_percent_with_users";
+pub const ITEMS_MISSING: &str = "items_missing";
+pub const ITEMS_EXCEED_TOTAL: &str = "items_exceed_total";
+pub const ITEMS_WITHOUT_USERS: &str = "items_without_users";
+pub const ADJUSTMENTS_EXCEED_TOTAL: &str = "adjustments_exceed_total";
+pub const ADJUSTMENTS_NEGATIVE: &str = "adjustments_negative";
+pub const PAY_BACK_SELF: &str = "pay_back_self";
+pub const TIME_ZONE_UNRECOGNIZED: &str = "time_zone_unrecognized";
+pub const TIME_UNRECOGNIZED: &str = "time_unrecognized";
+pub const REMINDER_DAYS_INVALID: &str = "reminder_days_invalid";
+pub const RETENTION_MONTHS_INVALID: &str = "retention_months_invalid";
+pub const BALANCES_HEADER: &str = "balances_header";
+pub const BALANCES_HEADER_CURRENCY: &str = "balances_header_currency";
+pub const BALANCES_HEADER_CONVERTED: &str = "balances_header_converted";
+pub const RATE_AGE_NOW: &str = "rate_age_now";
+pub const RATE_AGE_MINUTES: &str = "rate_age_minutes";
+pub const RATE_AGE_HOURS: &str = "rate_age_hours";
+pub const RATE_AGE_DAY: &str = "rate_age_day";
+pub const RATE_AGE_DAYS: &str = "rate_age_days";
+pub const RATES_AS_OF: &str = "rates_as_of";
+pub const RATES_AGE: &str = "rates_age";
+pub const BALANCE_DEBT: &str = "balance_debt";
+pub const BALANCES_NONE: &str = "balances_none";
+pub const PAYMENT_RATE: &str = "payment_rate";
+pub const PAYMENT_TYPE_TRANSFER: &str = "payment_type_transfer";
+pub const PAYMENT_TYPE_ADJUSTMENT: &str = "payment_type_adjustment";
+pub const PAYMENT_DETAILS: &str = "payment_details";
+pub const PAYMENT_CONFIRMED_BY: &str = "payment_confirmed_by";
+pub const PAYMENT_ADDED_BY: &str = "payment_added_by";
+pub const PAYMENT_EDITED_BY: &str = "payment_edited_by";
+pub const PAYMENT_LINK_NUMBER: &str = "payment_link_number";
+pub const ITEM_EVERYONE: &str = "item_everyone";
+pub const ITEMS_SUMMARY: &str = "items_summary";
+pub const CONVERT_TO_CURRENCY: &str = "convert_to_currency";
+pub const SPLIT_USERS_MISSING: &str = "split_users_missing";
+pub const DEBTS_NOT_TOTAL: &str = "debts_not_total";
+pub const PAYMENT_USERS_UNKNOWN: &str = "payment_users_unknown";
+pub const ADJUSTMENT_ZERO: &str = "adjustment_zero";
+pub const ADJUSTMENT_USER_UNKNOWN: &str = "adjustment_user_unknown";
+pub const ADJUSTMENT_NO_OTHERS: &str = "adjustment_no_others";
+pub const TOPIC_SETTING_UNSUPPORTED: &str = "topic_setting_unsupported";
+pub const DISPLAY_NAME_LENGTH: &str = "display_name_length";
+pub const NOT_UNDERSTOOD: &str = "not_understood";
+pub const ADJUST_INSTRUCTIONS: &str = "adjust_instructions";
+pub const ADJUST_RESTRICTED: &str = "adjust_restricted";
+pub const ADJUST_SIGN_MISSING: &str = "adjust_sign_missing";
+pub const ADJUST_REASON_MISSING: &str = "adjust_reason_missing";
+pub const ADJUST_DONE: &str = "adjust_done";
+pub const ALIAS_INSTRUCTIONS: &str = "alias_instructions";
+pub const ALIASES_NONE: &str = "aliases_none";
+pub const ALIASES_HEADER: &str = "aliases_header";
+pub const ALIAS_REMOVED: &str = "alias_removed";
+pub const ALIAS_NOT_SET: &str = "alias_not_set";
+pub const ALIAS_SET: &str = "alias_set";
+pub const OWED_INSTRUCTIONS: &str = "owed_instructions";
+pub const OWED_SENDER_UNKNOWN: &str = "owed_sender_unknown";
+pub const OWED_SAME_USER: &str = "owed_same_user";
+pub const OWED_SQUARE: &str = "owed_square";
+pub const OWED_HEADER: &str = "owed_header";
+pub const OWED_FOOTER: &str = "owed_footer";
+pub const AUDIT_ACTION_ADDED: &str = "audit_action_added";
+pub const AUDIT_ACTION_DELETED: &str = "audit_action_deleted";
+pub const AUDIT_ACTION_RESTORED: &str = "audit_action_restored";
+pub const AUDIT_ACTION_ADJUSTED: &str = "audit_action_adjusted";
+pub const AUDIT_ACTION_EDITED: &str = "audit_action_edited";
+pub const AUDIT_SOMEONE: &str = "audit_someone";
+pub const AUDIT_NONE: &str = "audit_none";
+pub const AUDIT_HEADER: &str = "audit_header";
+pub const MONTH_FORMAT: &str = "month_format";
+pub const LIMIT_INSTRUCTIONS: &str = "limit_instructions";
+pub const LIMITS_NONE: &str = "limits_none";
+pub const LIMITS_HEADER: &str = "limits_header";
+pub const WEEKLY_CAP_STATUS: &str = "weekly_cap_status";
+pub const LIMIT_SET: &str = "limit_set";
+pub const LIMIT_REMOVED: &str = "limit_removed";
+pub const WEEKLY_CAP_SET: &str = "weekly_cap_set";
+pub const WEEKLY_CAP_REMOVED: &str = "weekly_cap_removed";
+pub const LIMIT_CROSSED: &str = "limit_crossed";
+pub const PAYLINK_INSTRUCTIONS: &str = "paylink_instructions";
+pub const PAYLINK_TOO_LONG: &str = "paylink_too_long";
+pub const PAYLINK_UNRECOGNIZED: &str = "paylink_unrecognized";
+pub const PAYLINK_NO_USERNAME: &str = "paylink_no_username";
+pub const PAYLINK_CURRENT: &str = "paylink_current";
+pub const PAYLINK_NONE: &str = "paylink_none";
+pub const PAYLINK_REMOVED: &str = "paylink_removed";
+pub const PAYLINK_SET: &str = "paylink_set";
+pub const SPLIT_GROUP_INSTRUCTIONS: &str = "split_group_instructions";
+pub const SPLIT_GROUPS_NONE: &str = "split_groups_none";
+pub const SPLIT_GROUPS_HEADER: &str = "split_groups_header";
+pub const SPLIT_GROUPS_FOOTER: &str = "split_groups_footer";
+pub const SPLIT_GROUP_NAME_TOO_LONG: &str = "split_group_name_too_long";
+pub const SPLIT_GROUP_NAME_INVALID: &str = "split_group_name_invalid";
+pub const SPLIT_GROUP_MEMBERS_MISSING: &str = "split_group_members_missing";
+pub const SPLIT_GROUPS_FULL: &str = "split_groups_full";
+pub const SPLIT_GROUP_SAVED: &str = "split_group_saved";
+pub const SPLIT_GROUP_DELETED: &str = "split_group_deleted";
+pub const SPLIT_GROUP_NOT_FOUND: &str = "split_group_not_found";
+pub const COMMAND_UNKNOWN: &str = "command_unknown";
+pub const RESTORE_INSTRUCTIONS: &str = "restore_instructions";
+pub const BACKUP_TOO_LARGE: &str = "backup_too_large";
+pub const BACKUP_DOWNLOAD_FAILED: &str = "backup_download_failed";
+pub const BACKUP_INVALID: &str = "backup_invalid";
+pub const BACKUP_VERSION_MISMATCH: &str = "backup_version_mismatch";
+pub const BACKUP_SUMMARY: &str = "backup_summary";
+pub const BACKUP_FAILED: &str = "backup_failed";
+pub const BACKUP_CAPTION: &str = "backup_caption";
+pub const RESTORE_RESTRICTED: &str = "restore_restricted";
+pub const RESTORE_PREVIEW: &str = "restore_preview";
+pub const RESTORE_WARNING: &str = "restore_warning";
+pub const RESTORE_DONE: &str = "restore_done";
+pub const IMPORT_AMOUNT_TOO_LARGE: &str = "import_amount_too_large";
+pub const IMPORT_AMOUNT_INVALID: &str = "import_amount_invalid";
+pub const IMPORT_AMOUNT_DECIMALS: &str = "import_amount_decimals";
+pub const IMPORT_CURRENCY_UNKNOWN: &str = "import_currency_unknown";
+pub const IMPORT_DATE_INVALID: &str = "import_date_invalid";
+pub const IMPORT_PAYERS_MULTIPLE: &str = "import_payers_multiple";
+pub const IMPORT_PAYER_MISSING: &str = "import_payer_missing";
+pub const IMPORT_AMOUNTS_MISMATCH: &str = "import_amounts_mismatch";
+pub const IMPORT_DEBTORS_MISSING: &str = "import_debtors_missing";
+pub const IMPORT_FILE_EMPTY: &str = "import_file_empty";
+pub const IMPORT_COLUMNS_MISSING: &str = "import_columns_missing";
+pub const IMPORT_CURRENCY_NOT_ALLOWED: &str = "import_currency_not_allowed";
+pub const IMPORT_AMOUNT_NOT_POSITIVE: &str = "import_amount_not_positive";
+pub const IMPORT_INSTRUCTIONS: &str = "import_instructions";
+pub const IMPORT_TOO_LARGE: &str = "import_too_large";
+pub const IMPORT_DOWNLOAD_FAILED: &str = "import_download_failed";
+pub const IMPORT_NOT_CSV: &str = "import_not_csv";
+pub const IMPORT_FILE_ERROR: &str = "import_file_error";
+pub const IMPORT_ROW_ERROR: &str = "import_row_error";
+pub const IMPORT_MORE: &str = "import_more";
+pub const IMPORT_ROWS_UNREADABLE: &str = "import_rows_unreadable";
+pub const IMPORT_PAYMENTS_NONE: &str = "import_payments_none";
+pub const IMPORT_PAYMENT_LINE: &str = "import_payment_line";
+pub const IMPORT_NO_USERNAME: &str = "import_no_username";
+pub const IMPORT_PREVIEW_ONE: &str = "import_preview_one";
+pub const IMPORT_PREVIEW: &str = "import_preview";
+pub const IMPORT_OVER_LIMIT: &str = "import_over_limit";
+pub const IMPORT_PROGRESS: &str = "import_progress";
+pub const IMPORT_DONE_ONE: &str = "import_done_one";
+pub const IMPORT_DONE: &str = "import_done";
+pub const BUTTON_APPROVE: &str = "button_approve";
+pub const BUTTON_DISPUTE: &str = "button_dispute";
+pub const BUTTON_CONFIRM_PAYER: &str = "button_confirm_payer";
+pub const BUTTON_CONFIRM_DEBTOR: &str = "button_confirm_debtor";
+pub const DUAL_ENTRY_NEEDED: &str = "dual_entry_needed";
+pub const DUAL_ENTRY_REQUEST: &str = "dual_entry_request";
+pub const APPROVAL_NEEDED: &str = "approval_needed";
+pub const APPROVAL_REQUEST: &str = "approval_request";
+pub const APPROVAL_RESOLVED: &str = "approval_resolved";
+pub const APPROVAL_NOT_ALLOWED: &str = "approval_not_allowed";
+pub const APPROVED_BY: &str = "approved_by";
+pub const DISPUTED_BY: &str = "disputed_by";
+pub const ADD_PAYMENT_FAILED: &str = "add_payment_failed";
+pub const DISCARD_PAYMENT_FAILED: &str = "discard_payment_failed";
+pub const CONFIRM_PAYMENT_FAILED: &str = "confirm_payment_failed";
+pub const DELETE_PAYMENT_FAILED: &str = "delete_payment_failed";
+pub const EDIT_PAYMENT_FAILED: &str = "edit_payment_failed";
+pub const UNDO_SPLIT_FAILED: &str = "undo_split_failed";
+pub const CONFIRMATION_RESOLVED: &str = "confirmation_resolved";
+pub const CONFIRM_PAYER_NOT_ALLOWED: &str = "confirm_payer_not_allowed";
+pub const CONFIRM_DEBTOR_NOT_ALLOWED: &str = "confirm_debtor_not_allowed";
+pub const CONFIRMED_BY_PAYER: &str = "confirmed_by_payer";
+pub const CONFIRMED_BY_DEBTOR: &str = "confirmed_by_debtor";
+pub const CONFIRMED_ADDED_PAYER: &str = "confirmed_added_payer";
+pub const CONFIRMED_ADDED_DEBTOR: &str = "confirmed_added_debtor";
+pub const BUTTON_UNDO: &str = "button_undo";
+pub const SPLIT_DESCRIPTION_DEFAULT: &str = "split_description_default";
+pub const SPLIT_INSTRUCTIONS: &str = "split_instructions";
+pub const SPLIT_NO_USERNAME: &str = "split_no_username";
+pub const SPLIT_NO_OTHERS: &str = "split_no_others";
+pub const SPLIT_OVERVIEW: &str = "split_overview";
+pub const SPLIT_DONE: &str = "split_done";
+pub const SPLIT_ALREADY_DELETED: &str = "split_already_deleted";
+pub const SPLIT_UNDO_NOT_ALLOWED: &str = "split_undo_not_allowed";
+pub const SPLIT_UNDONE: &str = "split_undone";
+pub const BUTTON_RECORD: &str = "button_record";
+pub const BUTTON_DISMISS: &str = "button_dismiss";
+pub const DETECT_SUGGESTION: &str = "detect_suggestion";
+pub const DETECT_RESOLVED: &str = "detect_resolved";
+pub const DETECT_NOT_ALLOWED: &str = "detect_not_allowed";
+pub const DETECT_DISMISSED: &str = "detect_dismissed";
+pub const DETECT_RECORDED: &str = "detect_recorded";
+pub const DELETE_PAYMENT_CANCELLED: &str = "delete_payment_cancelled";
+pub const DELETE_PAYMENT_REPEATED: &str = "delete_payment_repeated";
+pub const DELETE_PAYMENT_BLOCKED: &str = "delete_payment_blocked";
+pub const DELETE_PAYMENT_NO_VIEW: &str = "delete_payment_no_view";
+pub const DELETE_PAYMENTS_PROMPT: &str = "delete_payments_prompt";
+pub const DELETE_PAYMENT_PROMPT: &str = "delete_payment_prompt";
+pub const PAYMENTS_DELETED: &str = "payments_deleted";
+pub const PAYMENT_DELETED: &str = "payment_deleted";
+pub const PAYMENTS_DELETED_SUMMARY: &str = "payments_deleted_summary";
+pub const PAYMENT_DELETED_SUMMARY: &str = "payment_deleted_summary";
+pub const DELETED_RESTORE_HINT: &str = "deleted_restore_hint";
+pub const ONBOARDING_PROGRESS: &str = "onboarding_progress";
+pub const ONBOARDING_TIME_ZONE: &str = "onboarding_time_zone";
+pub const ONBOARDING_NO_DEFAULT_CURRENCY: &str = "onboarding_no_default_currency";
+pub const ONBOARDING_DEFAULT_CURRENCY_INFO: &str = "onboarding_default_currency_info";
+pub const ONBOARDING_DEFAULT_CURRENCY: &str = "onboarding_default_currency";
+pub const ONBOARDING_CONVERSION_ON: &str = "onboarding_conversion_on";
+pub const ONBOARDING_CONVERSION_LATER: &str = "onboarding_conversion_later";
+pub const ONBOARDING_CONVERSION_OFF: &str = "onboarding_conversion_off";
+pub const ONBOARDING_CONVERSION: &str = "onboarding_conversion";
+pub const ONBOARDING_ERASE_ON: &str = "onboarding_erase_on";
+pub const ONBOARDING_ERASE_OFF: &str = "onboarding_erase_off";
+pub const ONBOARDING_ERASE: &str = "onboarding_erase";
+pub const ONBOARDING_DONE: &str = "onboarding_done";
+pub const ONBOARDING_WELCOME: &str = "onboarding_welcome";
+pub const PAYMENT_CARD_PRIVATE: &str = "payment_card_private";
+pub const PAYMENT_CARD_NOT_DISPUTABLE: &str = "payment_card_not_disputable";
+pub const PAYMENT_CARD_NOTE: &str = "payment_card_note";
+pub const PAYMENT_CARD_RECEIPT: &str = "payment_card_receipt";
+pub const PAYMENT_CARD: &str = "payment_card";
+pub const PAYMENT_CARD_CLOSED: &str = "payment_card_closed";
+pub const PAYMENT_CARD_BLOCKED: &str = "payment_card_blocked";
+pub const PAYMENT_CARD_NOTE_PROMPT: &str = "payment_card_note_prompt";
+pub const PAYMENT_CARD_RECEIPT_PROMPT: &str = "payment_card_receipt_prompt";
+pub const PAYMENT_CARD_RECEIPT_CAPTION: &str = "payment_card_receipt_caption";
+pub const PAYMENT_CARD_NOTE_INVALID: &str = "payment_card_note_invalid";
+pub const PAYMENT_CARD_RECEIPT_INVALID: &str = "payment_card_receipt_invalid";
+pub const IN_CURRENCY: &str = "in_currency";
+pub const MONTH_NAME_FORMAT: &str = "month_name_format";
+pub const SPENDINGS_TABLE_NAME: &str = "spendings_table_name";
+pub const SPENDINGS_TABLE_CONSUMED: &str = "spendings_table_consumed";
+pub const SPENDINGS_TABLE_FRONTED: &str = "spendings_table_fronted";
+pub const SPENDINGS_TABLE_NET: &str = "spendings_table_net";
+pub const SPENDINGS_PERIOD_NONE: &str = "spendings_period_none";
+pub const SPENDINGS_CONSUMPTION_HEADER: &str = "spendings_consumption_header";
+pub const FORECAST_OVER_LIMIT: &str = "forecast_over_limit";
+pub const FORECAST_WITHIN_LIMIT: &str = "forecast_within_limit";
+pub const FORECAST_USER: &str = "forecast_user";
+pub const FORECAST_GROUP: &str = "forecast_group";
+pub const FORECAST_NONE: &str = "forecast_none";
+pub const FORECAST_HEADER: &str = "forecast_header";
+pub const SPENDINGS_USER: &str = "spendings_user";
+pub const SPENDINGS_GROUP_TOTAL: &str = "spendings_group_total";
+pub const SPENDINGS_HEADER: &str = "spendings_header";
+pub const SPENDINGS_HEADER_CURRENCY: &str = "spendings_header_currency";
+pub const SPENDINGS_HEADER_CONVERTED: &str = "spendings_header_converted";
+pub const VIEW_BALANCES_HEADER: &str = "view_balances_header";
+pub const VIEW_BALANCES_HEADER_CURRENCY: &str = "view_balances_header_currency";
+pub const VIEW_BALANCES_HEADER_CONVERTED: &str = "view_balances_header_converted";
+pub const BALANCES_ONLY_OWE: &str = "balances_only_owe";
+pub const BALANCES_ONLY_OWED: &str = "balances_only_owed";
+pub const BALANCES_PAIRWISE: &str = "balances_pairwise";
+pub const BALANCES_CONVERSION_REFUSED: &str = "balances_conversion_refused";
+pub const BALANCES_OVERDUE: &str = "balances_overdue";
+pub const BALANCES_DISPLAY_RESTRICTED: &str = "balances_display_restricted";
+pub const BALANCES_NO_USERNAME: &str = "balances_no_username";
+pub const CONVERT_TO_PROMPT: &str = "convert_to_prompt";
+pub const CONVERT_TO_CANCELLED: &str = "convert_to_cancelled";
+pub const CURRENCY_CODE_INVALID: &str = "currency_code_invalid";
+pub const RATE_CURRENCY_MISSING: &str = "rate_currency_missing";
+pub const RATE_NO_DEFAULT_CURRENCY: &str = "rate_no_default_currency";
+pub const RATE_ALREADY_DEFAULT: &str = "rate_already_default";
+pub const ADD_PAYMENT_NO_USERNAME: &str = "add_payment_no_username";
+pub const SETTINGS_ARGUMENTS: &str = "settings_arguments";
+pub const SETTINGS_CURRENCY_NOT_ALLOWED: &str = "settings_currency_not_allowed";
+pub const SETTINGS_CURRENCIES_INVALID: &str = "settings_currencies_invalid";
+pub const SETTINGS_VALUE_MISSING: &str = "settings_value_missing";
+pub const SETTINGS_TOGGLE_INVALID: &str = "settings_toggle_invalid";
+pub const SETTINGS_LANGUAGE_UNKNOWN: &str = "settings_language_unknown";
+pub const SETTINGS_NUMBER_FORMAT_UNKNOWN: &str = "settings_number_format_unknown";
+pub const SETTINGS_CURRENCY_FORMAT_UNKNOWN: &str = "settings_currency_format_unknown";
+pub const SETTINGS_VERBOSITY_UNKNOWN: &str = "settings_verbosity_unknown";
+pub const SETTINGS_ROUNDING_UNKNOWN: &str = "settings_rounding_unknown";
+pub const SETTINGS_WEEK_START_INVALID: &str = "settings_week_start_invalid";
+pub const SETTINGS_UNKNOWN: &str = "settings_unknown";
+pub const SETTINGS_CANCELLED: &str = "settings_cancelled";
+pub const SETTINGS_MENU: &str = "settings_menu";
+pub const SETTINGS_REPEATED: &str = "settings_repeated";
+pub const SETTINGS_BLOCKED: &str = "settings_blocked";
+pub const SETTINGS_ENABLED: &str = "settings_enabled";
+pub const SETTINGS_DISABLED: &str = "settings_disabled";
+pub const SETTINGS_TIME_ZONE_MENU: &str = "settings_time_zone_menu";
+pub const SETTINGS_TIME_ZONE_UPDATED: &str = "settings_time_zone_updated";
+pub const SETTINGS_CURRENCY_NOT_SET: &str = "settings_currency_not_set";
+pub const SETTINGS_CURRENCY_CURRENT: &str = "settings_currency_current";
+pub const SETTINGS_CURRENCY_MENU: &str = "settings_currency_menu";
+pub const SETTINGS_CURRENCY_UPDATED: &str = "settings_currency_updated";
+pub const SETTINGS_CURRENCY_DISABLED: &str = "settings_currency_disabled";
+pub const SETTINGS_CONVERSION_STATUS: &str = "settings_conversion_status";
+pub const SETTINGS_CONVERSION_OFF_PROMPT: &str = "settings_conversion_off_prompt";
+pub const SETTINGS_CONVERSION_ON_PROMPT: &str = "settings_conversion_on_prompt";
+pub const SETTINGS_CONVERSION_NO_CURRENCY: &str = "settings_conversion_no_currency";
+pub const SETTINGS_CONVERSION_ON: &str = "settings_conversion_on";
+pub const SETTINGS_CONVERSION_OFF: &str = "settings_conversion_off";
+pub const SETTINGS_ERASE_STATUS: &str = "settings_erase_status";
+pub const SETTINGS_ERASE_ENABLED_ALL: &str = "settings_erase_enabled_all";
+pub const SETTINGS_ERASE_ENABLED_OWN: &str = "settings_erase_enabled_own";
+pub const SETTINGS_ERASE_OFF_PROMPT: &str = "settings_erase_off_prompt";
+pub const SETTINGS_ERASE_ON_PROMPT: &str = "settings_erase_on_prompt";
+pub const SETTINGS_ERASE_ON: &str = "settings_erase_on";
+pub const SETTINGS_ERASE_OFF: &str = "settings_erase_off";
+pub const SETTINGS_ERASE_USER_ON: &str = "settings_erase_user_on";
+pub const SETTINGS_ERASE_USER_OFF: &str = "settings_erase_user_off";
+pub const SETTINGS_ARCHIVE_STATUS: &str = "settings_archive_status";
+pub const SETTINGS_ARCHIVE_OFF_PROMPT: &str = "settings_archive_off_prompt";
+pub const SETTINGS_ARCHIVE_ON_PROMPT: &str = "settings_archive_on_prompt";
+pub const SETTINGS_ARCHIVE_ON: &str = "settings_archive_on";
+pub const SETTINGS_ARCHIVE_OFF: &str = "settings_archive_off";
+pub const SETTINGS_DIGEST_STATUS: &str = "settings_digest_status";
+pub const SETTINGS_DIGEST_ENABLED: &str = "settings_digest_enabled";
+pub const SETTINGS_DIGEST_OFF_PROMPT: &str = "settings_digest_off_prompt";
+pub const SETTINGS_DIGEST_ON_PROMPT: &str = "settings_digest_on_prompt";
+pub const SETTINGS_DIGEST_TIME_PROMPT: &str = "settings_digest_time_prompt";
+pub const SETTINGS_DIGEST_ON: &str = "settings_digest_on";
+pub const SETTINGS_DIGEST_OFF: &str = "settings_digest_off";
+pub const SETTINGS_REMINDER_STATUS: &str = "settings_reminder_status";
+pub const SETTINGS_REMINDER_ENABLED: &str = "settings_reminder_enabled";
+pub const SETTINGS_REMINDER_OFF_PROMPT: &str = "settings_reminder_off_prompt";
+pub const SETTINGS_REMINDER_ON_PROMPT: &str = "settings_reminder_on_prompt";
+pub const SETTINGS_REMINDER_DAYS_PROMPT: &str = "settings_reminder_days_prompt";
+pub const SETTINGS_REMINDER_ON: &str = "settings_reminder_on";
+pub const SETTINGS_REMINDER_OFF: &str = "settings_reminder_off";
+pub const SETTINGS_RETENTION_ON: &str = "settings_retention_on";
+pub const SETTINGS_RETENTION_OFF: &str = "settings_retention_off";
+pub const SETTINGS_APPROVAL_STATUS: &str = "settings_approval_status";
+pub const SETTINGS_APPROVAL_ENABLED: &str = "settings_approval_enabled";
+pub const SETTINGS_APPROVAL_OFF_PROMPT: &str = "settings_approval_off_prompt";
+pub const SETTINGS_APPROVAL_ON_PROMPT: &str = "settings_approval_on_prompt";
+pub const SETTINGS_APPROVAL_THRESHOLD_PROMPT: &str = "settings_approval_threshold_prompt";
+pub const SETTINGS_APPROVAL_ON: &str = "settings_approval_on";
+pub const SETTINGS_APPROVAL_OFF: &str = "settings_approval_off";
+pub const SETTINGS_TREASURER_STATUS: &str = "settings_treasurer_status";
+pub const SETTINGS_TREASURER_ENABLED: &str = "settings_treasurer_enabled";
+pub const SETTINGS_TREASURER_OFF_PROMPT: &str = "settings_treasurer_off_prompt";
+pub const SETTINGS_TREASURER_ON_PROMPT: &str = "settings_treasurer_on_prompt";
+pub const SETTINGS_TREASURER_USERNAME_PROMPT: &str = "settings_treasurer_username_prompt";
+pub const SETTINGS_TREASURER_ON: &str = "settings_treasurer_on";
+pub const SETTINGS_TREASURER_OFF: &str = "settings_treasurer_off";
+pub const SETTINGS_ADMIN_ONLY_RESTRICTED: &str = "settings_admin_only_restricted";
+pub const SETTINGS_ADMIN_ONLY_STATUS: &str = "settings_admin_only_status";
+pub const SETTINGS_ADMIN_ONLY_OFF_PROMPT: &str = "settings_admin_only_off_prompt";
+pub const SETTINGS_ADMIN_ONLY_ON_PROMPT: &str = "settings_admin_only_on_prompt";
+pub const SETTINGS_ADMIN_ONLY_ON: &str = "settings_admin_only_on";
+pub const SETTINGS_ADMIN_ONLY_OFF: &str = "settings_admin_only_off";
+pub const SETTINGS_PAIRWISE_ON: &str = "settings_pairwise_on";
+pub const SETTINGS_PAIRWISE_OFF: &str = "settings_pairwise_off";
+pub const SETTINGS_PASSIVE_ON: &str = "settings_passive_on";
+pub const SETTINGS_PASSIVE_OFF: &str = "settings_passive_off";
+pub const SETTINGS_DUAL_ENTRY_ON: &str = "settings_dual_entry_on";
+pub const SETTINGS_DUAL_ENTRY_OFF: &str = "settings_dual_entry_off";
+pub const SETTINGS_STRICT_ON: &str = "settings_strict_on";
+pub const SETTINGS_STRICT_OFF: &str = "settings_strict_off";
+pub const SETTINGS_NUMBER_FORMAT_MENU: &str = "settings_number_format_menu";
+pub const SETTINGS_NUMBER_FORMAT_UPDATED: &str = "settings_number_format_updated";
+pub const SETTINGS_CURRENCY_FORMAT_MENU: &str = "settings_currency_format_menu";
+pub const SETTINGS_CURRENCY_FORMAT_UPDATED: &str = "settings_currency_format_updated";
+pub const SETTINGS_VERBOSITY_MENU: &str = "settings_verbosity_menu";
+pub const SETTINGS_VERBOSITY_VERBOSE: &str = "settings_verbosity_verbose";
+pub const SETTINGS_VERBOSITY_COMPACT: &str = "settings_verbosity_compact";
+pub const SETTINGS_VERBOSITY_SILENT: &str = "settings_verbosity_silent";
+pub const SETTINGS_ROUNDING_MENU: &str = "settings_rounding_menu";
+pub const SETTINGS_ROUNDING_PAYER: &str = "settings_rounding_payer";
+pub const SETTINGS_ROUNDING_FIRST: &str = "settings_rounding_first";
+pub const SETTINGS_ROUNDING_RANDOM: &str = "settings_rounding_random";
+pub const SETTINGS_ROUNDING_ROUND_ROBIN: &str = "settings_rounding_round_robin";
+pub const SETTINGS_WEEK_START_MENU: &str = "settings_week_start_menu";
+pub const SETTINGS_WEEK_START_MONDAY: &str = "settings_week_start_monday";
+pub const SETTINGS_WEEK_START_SUNDAY: &str = "settings_week_start_sunday";
+pub const SETTINGS_CURRENCIES_RESTRICTED: &str = "settings_currencies_restricted";
+pub const SETTINGS_CURRENCIES_DEFAULT_MISSING: &str = "settings_currencies_default_missing";
+pub const SETTINGS_CURRENCIES_ALL: &str = "settings_currencies_all";
+pub const SETTINGS_CURRENCIES_UPDATED: &str = "settings_currencies_updated";
+pub const SETTINGS_TOPIC_ONLY: &str = "settings_topic_only";
+pub const SETTINGS_TOPIC_INVALID: &str = "settings_topic_invalid";
+pub const SETTINGS_TOPIC_SUMMARY: &str = "settings_topic_summary";
+pub const SETTINGS_TOPIC_OWN: &str = "settings_topic_own";
+pub const SETTINGS_TOPIC_INHERITED: &str = "settings_topic_inherited";
+pub const SETTINGS_TOPIC_NONE: &str = "settings_topic_none";
+pub const SETTINGS_TOPIC_RESET: &str = "settings_topic_reset";
+pub const SETTINGS_TOPIC_TIME_ZONE_UPDATED: &str = "settings_topic_time_zone_updated";
+pub const SETTINGS_TOPIC_CURRENCY_UPDATED: &str = "settings_topic_currency_updated";
+pub const TRIP_INSTRUCTIONS: &str = "trip_instructions";
+pub const TRIP_BALANCES_HEADER: &str = "trip_balances_header";
+pub const TRIP_SPENDINGS_HEADER: &str = "trip_spendings_header";
+pub const TRIP_EXPENSES_NONE: &str = "trip_expenses_none";
+pub const TRIP_REPORT: &str = "trip_report";
+pub const TRIP_CSV_CAPTION: &str = "trip_csv_caption";
+pub const TRIP_CURRENT: &str = "trip_current";
+pub const TRIP_NONE: &str = "trip_none";
+pub const TRIP_ALL: &str = "trip_all";
+pub const TRIP_NAME_MISSING: &str = "trip_name_missing";
+pub const TRIP_ALREADY_STARTED: &str = "trip_already_started";
+pub const TRIP_STILL_ON: &str = "trip_still_on";
+pub const TRIP_STARTED: &str = "trip_started";
+pub const TRIP_ENDED: &str = "trip_ended";
+pub const TRIP_NOT_ON: &str = "trip_not_on";
+pub const TRIP_WHICH: &str = "trip_which";
+pub const TRIP_NOT_FOUND: &str = "trip_not_found";
+pub const TRIP_UNKNOWN: &str = "trip_unknown";
+pub const ARCHIVE_SUMMARY_ONE: &str = "archive_summary_one";
+pub const ARCHIVE_SUMMARY: &str = "archive_summary";
+pub const DIGEST_SPENDINGS_NONE: &str = "digest_spendings_none";
+pub const DIGEST_WEEK: &str = "digest_week";
+pub const DIGEST_SUMMARY: &str = "digest_summary";
+pub const ADD_PAYMENT_OVERVIEW: &str = "add_payment_overview";
+pub const ADD_PAYMENT_CANCELLED: &str = "add_payment_cancelled";
+pub const ADD_PAYMENT_REPEATED: &str = "add_payment_repeated";
+pub const ADD_PAYMENT_BLOCKED: &str = "add_payment_blocked";
+pub const WEEKLY_CAP_OVERRIDE_NOT_ALLOWED: &str = "weekly_cap_override_not_allowed";
+pub const PAY_BACK_CURRENCY_MENU: &str = "pay_back_currency_menu";
+pub const PAY_BACK_PARTIAL_FAILED: &str = "pay_back_partial_failed";
+pub const PAY_BACK_REPEATED: &str = "pay_back_repeated";
+pub const PAY_BACK_BLOCKED: &str = "pay_back_blocked";
+pub const PAY_BACK_CREDITORS_NONE_SELECTED: &str = "pay_back_creditors_none_selected";
+pub const PAY_BACK_CREDITORS_NOT_ALLOWED: &str = "pay_back_creditors_not_allowed";
+pub const DUPLICATE_PAYMENT_REPEATED: &str = "duplicate_payment_repeated";
+pub const DUPLICATE_PAYMENT_BLOCKED: &str = "duplicate_payment_blocked";
+pub const PAY_BACK_SUMMARY: &str = "pay_back_summary";
+pub const DEBTOR_CORRECTION_SUGGESTION: &str = "debtor_correction_suggestion";
+pub const DEBTOR_CORRECTION_BUTTON: &str = "debtor_correction_button";
+pub const TRASH_EMPTY: &str = "trash_empty";
+pub const TRASH_LIST: &str = "trash_list";
+pub const TRASH_ENTRY: &str = "trash_entry";
+pub const TRASH_RESTORE_NOT_ALLOWED: &str = "trash_restore_not_allowed";
+pub const TRASH_RESTORE_GONE: &str = "trash_restore_gone";
+pub const TRASH_RESTORED: &str = "trash_restored";
+pub const LEADERBOARD_INSTRUCTIONS: &str = "leaderboard_instructions";
+pub const LEADERBOARD_PERIOD_UNKNOWN: &str = "leaderboard_period_unknown";
+pub const LEADERBOARD_PERIOD_WEEK: &str = "leaderboard_period_week";
+pub const LEADERBOARD_PERIOD_MONTH: &str = "leaderboard_period_month";
+pub const LEADERBOARD_PERIOD_YEAR: &str = "leaderboard_period_year";
+pub const LEADERBOARD_PERIOD_ALL: &str = "leaderboard_period_all";
+pub const LEADERBOARD_EMPTY: &str = "leaderboard_empty";
+pub const LEADERBOARD_SUMMARY_ONE: &str = "leaderboard_summary_one";
+pub const LEADERBOARD_SUMMARY: &str = "leaderboard_summary";
+pub const LEADERBOARD_TOP_PAYERS: &str = "leaderboard_top_payers";
+pub const LEADERBOARD_TOP_PAYERS_CURRENCY: &str = "leaderboard_top_payers_currency";
+pub const LEADERBOARD_FREQUENT_DEBTORS: &str = "leaderboard_frequent_debtors";
+pub const LEADERBOARD_FASTEST_SETTLERS: &str = "leaderboard_fastest_settlers";
+pub const LEADERBOARD_PAYMENTS_ONE: &str = "leaderboard_payments_one";
+pub const LEADERBOARD_PAYMENTS: &str = "leaderboard_payments";
+pub const LEADERBOARD_SETTLE_TIME: &str = "leaderboard_settle_time";
+pub const LEADERBOARD_MINUTES_ONE: &str = "leaderboard_minutes_one";
+pub const LEADERBOARD_MINUTES: &str = "leaderboard_minutes";
+pub const LEADERBOARD_HOURS_ONE: &str = "leaderboard_hours_one";
+pub const LEADERBOARD_HOURS: &str = "leaderboard_hours";
+pub const LEADERBOARD_DAYS_ONE: &str = "leaderboard_days_one";
+pub const LEADERBOARD_DAYS: &str = "leaderboard_days";
+pub const DISPUTE_ALREADY_OPEN: &str = "dispute_already_open";
+pub const DISPUTE_OPENED: &str = "dispute_opened";
+pub const DISPUTE_NOTIFICATION: &str = "dispute_notification";
+pub const DISPUTE_CHAT_DEFAULT: &str = "dispute_chat_default";
+pub const DISPUTE_RESOLVED: &str = "dispute_resolved";
+pub const DISPUTE_NOT_ALLOWED: &str = "dispute_not_allowed";
+pub const DISPUTE_DISMISSED: &str = "dispute_dismissed";
+pub const DISPUTE_BUSY: &str = "dispute_busy";
+pub const DISPUTE_EDIT_NOT_ALLOWED: &str = "dispute_edit_not_allowed";
+pub const NOTIFICATION_ON: &str = "notification_on";
+pub const NOTIFICATION_ADDED: &str = "notification_added";
+pub const NOTIFICATION_CHAT_DEFAULT: &str = "notification_chat_default";
+pub const NOTIFICATION_OFF: &str = "notification_off";
+pub const NOTIFICATION_OFF_FAILED: &str = "notification_off_failed";
+pub const STATS_CHANGE_UP: &str = "stats_change_up";
+pub const STATS_CHANGE_DOWN: &str = "stats_change_down";
+pub const STATS_CHANGE_SAME: &str = "stats_change_same";
+pub const STATS_HEADER_CURRENCY: &str = "stats_header_currency";
+pub const STATS_MONTH_ONE: &str = "stats_month_one";
+pub const STATS_MONTH: &str = "stats_month";
+pub const STATS_NONE: &str = "stats_none";
+pub const STATS_SUMMARY: &str = "stats_summary";
+pub const STATEMENT_FILE_CAPTION: &str = "statement_file_caption";
+pub const RETENTION_MONTHS_ONE: &str = "retention_months_one";
+pub const RETENTION_MONTHS: &str = "retention_months";
+pub const RETENTION_CAPTION_ONE: &str = "retention_caption_one";
+pub const RETENTION_CAPTION: &str = "retention_caption";
+
+/* Buttons are keyed by their English label, which is also their callback data,
+ * so that callbacks are matched the same, whatever the language of the chat.
+ * Each button is listed with its label in Chinese (Simplified).
+ */
+const BUTTONS: &[(&str, &str)] = &[
+    ("Equal", "平均"),
+    ("Exact", "确切金额"),
+    ("Proportion", "比例"),
+    ("Percent", "百分比"),
+    ("Adjusted", "调整"),
+    ("Itemized", "逐项"),
+    ("Everyone", "所有人"),
+    ("Done", "完成"),
+    ("No Currency", "无货币"),
+    ("Cancel", "取消"),
+    ("Confirm", "确认"),
+    ("Edit", "编辑"),
+    ("Back", "返回"),
+    ("Skip", "跳过"),
+    ("Set Currency", "设置货币"),
+    ("Delete", "删除"),
+    ("Enter Manually", "手动输入"),
+    ("Turn On", "开启"),
+    ("Turn Off", "关闭"),
+    ("Duplicate", "复制"),
+    ("Dispute", "争议"),
+    ("Add Note", "添加备注"),
+    ("Edit Note", "编辑备注"),
+    ("Show Receipt", "查看收据"),
+    ("Add Receipt", "添加收据"),
+    ("Change Receipt", "更换收据"),
+    ("Consumed vs Fronted", "消费与垫付"),
+    ("Forecast", "预测"),
+    ("Show Totals", "显示总额"),
+    ("Past Week", "过去一周"),
+    ("Past Month", "过去一个月"),
+    ("Past Year", "过去一年"),
+    ("All Time", "全部时间"),
+    ("Convert To…", "转换为…"),
+    ("Show Pairwise", "显示逐笔"),
+    ("Show Simplified", "显示简化"),
+    ("What I Owe", "我欠的"),
+    ("What I'm Owed", "欠我的"),
+    ("Show Everyone", "显示所有人"),
+    ("Export PDF", "导出 PDF"),
+    ("Formal Statement", "正式声明"),
+    ("Disable", "停用"),
+    ("Verbose", "详细"),
+    ("Compact", "简洁"),
+    ("Silent", "静默"),
+    ("Payer", "付款人"),
+    ("First", "前几位"),
+    ("Random", "随机"),
+    ("Round Robin", "轮流"),
+    ("Monday", "星期一"),
+    ("Sunday", "星期日"),
+    ("Erase Yours Too", "也删除你们的"),
+    ("Keep Yours", "保留你们的"),
+    ("⬅️ Regions", "⬅️ 地区"),
+    ("⬅️ All Commands", "⬅️ 所有指令"),
+    ("🔕 Stop These Messages", "🔕 不再接收这些消息"),
+    ("✏️ Edit Payment", "✏️ 编辑付款"),
+    ("Dismiss", "忽略"),
+];
+
+static CATALOG: OnceLock<HashMap<(&'static str, &'static str), &'static str>> = OnceLock::new();
+
+fn all_messages() -> HashMap<(&'static str, &'static str), &'static str> {
+    let mut map = HashMap::new();
+
+    // English
+    map.insert(
+        ("en", ADMIN_ONLY),
+        "🔒 Sorry, only admins of this chat can edit or delete payments, or change my settings!",
+    );
+    map.insert(("en", UNKNOWN_ERROR), "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't do that right now. Please try again later!\n\n");
+    map.insert(
+        ("en", NO_TEXT),
+        "❓ I'm having a bit of trouble understanding that! Please reply to me in text!\n\n",
+    );
+    map.insert(("en", START_INTRODUCTION), "👋 Hello! I'm PayScribe! 😊\n\n🧚‍♀️ I'll be tracking your group payments and working my magic 🪄 to simplify your debts, so you won't have to juggle so many payments back to your friends!");
+    map.insert(("en", START_ADD_INFO), "✍️ Ready to track together in this group chat? Start with /addpayment! You can /viewpayments anytime, and I'll help to /editpayment or /deletepayment if you'd like!");
+    map.insert(("en", START_VIEW_INFO), "🙈 Check out /spendings to see who's been splurging! Peek at /balances for who owes what, but don't forget to /payback your friends!");
+    map.insert(
+        ("en", START_CLOSING),
+        "🤗 Have fun tracking, and don't hesitate to ask me for /help anytime!",
+    );
+    map.insert(("en", HELP_TITLE), "⭐️ *My Commands* ⭐️");
+    map.insert(("en", HELP_MENU_INFO), "👇 Tap a command below to see how it works, with examples and common mistakes\\! You can also send /help with a command, like /help addpayment\\.");
+    map.insert(
+        ("en", HELP_UNKNOWN_COMMAND),
+        "🤔 I don't have a command called {command}\\! Here are all of them:",
+    );
+    map.insert(("en", HELP_USER_GUIDE), "🆘 For all the nitty\\-gritty details on supported 🕔 time zones, 💵 currencies, and more, check out my [User Guide]({url})\\!");
+    map.insert(
+        ("en", HELP_FEEDBACK),
+        "💖 And if you have any [feedback]({url}) for me, I'd love to hear it\\!",
+    );
+    map.insert(
+        ("en", CANCEL_NOTHING),
+        "❌ I'm not doing anything... 👀\nThere's nothing to cancel!",
+    );
+    map.insert(
+        ("en", LANGUAGE_MENU),
+        "🌐 Language: {language}\n\nWhich language would you like me to reply in?",
+    );
+    map.insert(
+        ("en", LANGUAGE_UPDATED),
+        "You got it! I'll reply in {language} from now on! 🌐",
+    );
+    map.insert(
+        ("en", VALIDATION_ERROR),
+        "❌ Hmm, that doesn't look right! {reason} Please check and try again!\n\n",
+    );
+    map.insert(
+        ("en", RATE_LIMIT_ERROR),
+        "⏳ Whoa, slow down! You're sending requests too quickly. Please wait a moment and try again!\n\n",
+    );
+    map.insert(("en", CONVERSION_ERROR), "💱 I can't get the exchange rates right now! Please try again later, or turn off currency conversion with /settings to see the original currencies.\n\n");
+    map.insert(
+        ("en", TELEGRAM_ERROR),
+        "📡 I'm having trouble talking to Telegram right now! Please try again in a bit.\n\n",
+    );
+    map.insert(
+        ("en", STATEMENT_TITLE),
+        "FORMAL STATEMENT OF DEBTS\n{group}\nAs of {date}",
+    );
+    map.insert(
+        ("en", STATEMENT_DEBT),
+        "{index}. {debtor} owes {creditor} the sum of {amount}\n    ({words})",
+    );
+    map.insert(("en", STATEMENT_EMPTY), "There are no outstanding debts.");
+    map.insert(
+        ("en", STATEMENT_CLOSING),
+        "Each debtor above acknowledges owing the stated sum to the creditor named.",
+    );
+    map.insert(("en", CLEANUP_COMPACT), "🧹 Tidied up!");
+    map.insert(("en", DELETE_PERMISSION), "🧹 I tried to tidy up my messages, but I'm not allowed to delete them here! I'll shrink them instead.\n\nTo let me clear them away, make me an admin with permission to delete messages. Or, if you'd rather keep them, turn off Erase Messages in /settings.");
+    map.insert(("en", TOTAL_PROMPT), "Nice! How much was the total amount?");
+    map.insert(("en", TOTAL_QUESTION), "What should the total be?");
+    map.insert(("en", TOTAL_INSTRUCTIONS), "Enter the amount and optionally, the 3-letter currency code. For example: 100.00 USD, 200 JPY, 300.00, 1,234.56 EUR, etc.\n\nAdd any tip or tax on top, like this: 80 USD +10% tip +7% tax, or 80 USD +8 tip. I'll work out the grand total!\n\nPaid in a foreign currency? Add the rate you were charged after @, like this: 50 USD @ 1.37.\n\n⭐️ If you're unsure of the currency code, you can always check out my User Guide with /help!");
+    map.insert(("en", CURRENCY_INSTRUCTIONS), "Enter the 3-letter currency code. For example: USD, EUR, JPY, etc.\n\n⭐️ If you're unsure of the currency code, you can always check out my User Guide with /help!");
+    map.insert(("en", DATE_INSTRUCTIONS), "Enter the date of the payment. For example: today, yesterday, 3 days ago, last friday, 2024-03-12, 12 Mar, etc.");
+    map.insert(
+        ("en", DATE_UNRECOGNIZED),
+        "🥺 Sorry, I don't recognize that date!",
+    );
+    map.insert(
+        ("en", DATE_IN_FUTURE),
+        "🥺 Sorry, a payment can't be dated in the future!",
+    );
+    map.insert(("en", DUE_DATE_INSTRUCTIONS), "Enter the date this should be paid back by. For example: tomorrow, in 2 weeks, next friday, 2024-03-12, 12 Mar, etc. Or enter none to remove it.");
+    map.insert(
+        ("en", DUE_DATE_IN_PAST),
+        "🥺 Sorry, a due date can't be in the past!",
+    );
+    map.insert(
+        ("en", TIME_ZONE_INSTRUCTIONS),
+        "⭐️ Check out my User Guide with /help for all my supported time zones!",
+    );
+    map.insert(
+        ("en", SPLIT_PROMPT),
+        "Fantastic! How are we splitting this?",
+    );
+    map.insert(("en", DEBT_FORMATS), "Equal — Divide the total amount equally among users\nExact — Split the total cost by exact amounts for each user\nProportion — Share the total cost by relative proportions for each user\nPercent — Split the total cost by percentages for each user, adding up to 100%\nAdjusted — Divide the total amount equally, with extra or less for some users\nItemized — List the items on the bill, with tax and tip shared proportionally\n");
+    map.insert(
+        ("en", DEBT_USERS_PROMPT),
+        "Okay! Who is involved in the payment?",
+    );
+    map.insert(
+        ("en", DEBT_AMOUNTS_PROMPT),
+        "Okay! Who is involved and how much do they owe?",
+    );
+    map.insert(
+        ("en", DEBT_PERCENTAGES_PROMPT),
+        "Okay! Who is involved and what percentage do they owe?",
+    );
+    map.insert(
+        ("en", DEBT_ADJUSTMENTS_PROMPT),
+        "Okay! Who is involved and what are their adjustments?",
+    );
+    map.insert(("en", DEBT_ITEMS_PROMPT), "Okay! What's on the bill?");
+    map.insert(("en", DEBT_EQUAL_INSTRUCTIONS), "Enter the Telegram usernames of everyone sharing like this:\n\n@username__1\n@username__2\n@username__3\n...\n\n⭐️ Remember to include the payer if they're chipping in too!");
+    map.insert(("en", DEBT_EQUAL_PICKER), "Tap on everyone sharing, then hit Done! You can also type out their usernames instead.\n\n⭐️ Remember to include the payer if they're chipping in too!");
+    map.insert(("en", DEBT_EXACT_INSTRUCTIONS), "Enter the Telegram usernames and exact amounts like this: \n\n@username__1 amount1\n@username__2 amount2\n@username__3 amount3\n...\n\nAmounts can also be sums, like @username__1 12.50+3.20, or shares, like @username__2 45/3.\n\n⭐️ If there are any leftover amounts, I'll assume it's the payer's!");
+    map.insert(("en", DEBT_RATIO_INSTRUCTIONS), "Enter the Telegram usernames and portions like this: \n\n@username__1 portion1\n@username__2 portion2\n@username__3 portion3\n...\n\n⭐️ I can work with any positive number, whole or decimal!");
+    map.insert(("en", DEBT_PERCENT_INSTRUCTIONS), "Enter the Telegram usernames and percentages like this: \n\n@username__1 percent1\n@username__2 percent2\n@username__3 percent3\n...\n\n⭐️ The percentages should add up to 100, like @alice 40 and @bob 60!");
+    map.insert(("en", DEBT_ADJUSTED_INSTRUCTIONS), "Enter the Telegram usernames, each with an optional adjustment, like this: \n\n@username__1 +amount1\n@username__2 -amount2\n@username__3\n...\n\n⭐️ I'll split whatever is left over equally among everyone!");
+    map.insert(("en", DEBT_ITEMIZED_INSTRUCTIONS), "Enter each item on its own line, with the amount and the Telegram usernames sharing it, like this: \n\npizza 18.50 @username__1 @username__2\nbeer 6 @username__3\ntax 2.45\ntip 10%\n...\n\n⭐️ Lines without usernames, like tax and tip, are shared by everyone based on what they had!");
+    map.insert(
+        ("en", EDIT_DATE_PROMPT),
+        "Current date: {date}\n\nWhen did this payment happen?",
+    );
+    map.insert(
+        ("en", EDIT_DUE_DATE_PROMPT),
+        "Current due date: {date}\n\nBy when should this be paid back? ⏰",
+    );
+    map.insert(
+        ("en", EDIT_TOTAL_PROMPT),
+        "Current total: {total}\n\nWhat should the total be?",
+    );
+    map.insert(("en", EDIT_CURRENCY_PROMPT), "Current currency: {currency}\n\nWhat should the currency be? The amounts will stay the same.");
+    map.insert(
+        ("en", EDIT_SPLIT_PROMPT),
+        "Current split:\n{split}\nHow should we split this?",
+    );
+    map.insert(("en", PAY_BACK_CREDITORS_PROMPT), "Absolutely! 🙌 Here's who you owe right now. Tap everyone you've paid back in full, then Done!\n\nPaid back a different amount? Tap Enter Manually, or just tell me who and how much.");
+    map.insert(
+        ("en", PAY_BACK_PROMPT),
+        "Sure! Who and how much did you pay back?",
+    );
+    map.insert(
+        ("en", PAY_BACK_CURRENCY_QUESTION),
+        "Sure! What currency did you pay in?",
+    );
+    map.insert(
+        ("en", PAY_BACK_CURRENCY_PROMPT),
+        "{currency}, awesome! Who and how much did you pay back?",
+    );
+    map.insert(("en", PAY_BACK_INSTRUCTIONS), "Enter the Telegram usernames and exact amounts like this: \n\n@username__1 amount1\n@username__2 amount2\n@username__3 amount3\n...\n\n⭐️ Paid back in more than one currency? Add the currency after the amount, like @username__1 amount1 USD!\n\n");
+    map.insert(("en", SETTINGS_TIME_ZONE_PROMPT), "🕔 Time Zone: {time_zone}\n\nWhich region is your time zone in? You can also type the name of a city instead.");
+    map.insert(
+        ("en", SETTINGS_CURRENCY_PROMPT),
+        "What would you like to set as the default currency?",
+    );
+    map.insert(
+        ("en", STATEMENT_INSTRUCTIONS),
+        "⭐️ I can also present the other currencies/formats below!",
+    );
+
+    map.insert(("en", PRIVATE_PAYMENT), "🔒 Private payment");
+    map.insert(
+        ("en", UNKNOWN_CURRENCY),
+        "🥺 Sorry, I don't know that currency!",
+    );
+    map.insert(
+        ("en", AMOUNT_TOO_LARGE),
+        "Uh-oh! 🥺 This number is too large for me to handle!",
+    );
+    map.insert(
+        ("en", AMOUNTS_CURRENCY_MISMATCH),
+        "Uh-oh! 🥺 Amounts in {first} and {second} can't be added together.",
+    );
+    map.insert(
+        ("en", MONEY_OVERFLOW),
+        "This number is too large for me to handle!",
+    );
+    map.insert(
+        ("en", MONEY_CURRENCY_MISMATCH),
+        "Amounts in {first} and {second} can't be added together.",
+    );
+    map.insert(
+        ("en", INVALID_USERNAME),
+        "Uh-oh! ❌ Please give me a valid username!",
+    );
+    map.insert(
+        ("en", DESCRIPTION_MISSING),
+        "Uh-oh! ❌ Please give me a description!",
+    );
+    map.insert(
+        ("en", DESCRIPTION_TOO_LONG),
+        "Uh-oh! ❌ Please keep the description within {max} characters!",
+    );
+    map.insert(
+        ("en", TOO_MANY_DEBTORS),
+        "Uh-oh! ❌ A payment can be split between at most {max} people!",
+    );
+    map.insert(("en", TOO_MANY_PAYMENTS), "Uh-oh! ❌ This chat has reached the limit of {max} payments! Please /archive or delete some older payments first.");
+    map.insert(
+        ("en", INVALID_NUMBER),
+        "Uh-oh! ❌ Please give me a valid number!",
+    );
+    map.insert(
+        ("en", NO_DECIMAL_PLACES),
+        "Uh-oh! ❌ {currency} doesn't have decimal places! Please give me a whole number.",
+    );
+    map.insert(
+        ("en", TOO_MANY_DECIMAL_PLACES),
+        "Uh-oh! ❌ Please give me an amount with at most {decimals} decimal places!",
+    );
+    map.insert(
+        ("en", NOT_POSITIVE),
+        "Uh-oh! ❌ Please give me a positive number!",
+    );
+    map.insert(
+        ("en", EXPRESSION_TOO_LONG),
+        "Uh-oh! ❌ Please keep each amount within {max} characters!",
+    );
+    map.insert(("en", EXPRESSION_UNRECOGNIZED), "Uh-oh! ❌ I don't understand the amount {amount}! Please only use numbers, + - * / and brackets.");
+    map.insert(("en", DIVIDE_BY_ZERO), "Uh-oh! ❌ I can't divide by zero!");
+    map.insert(
+        ("en", UNCLOSED_BRACKET),
+        "Uh-oh! ❌ Please close every bracket in the amount!",
+    );
+    map.insert(
+        ("en", CURRENCY_NOT_ALLOWED),
+        "🥺 Sorry, this chat only uses these currencies: {currencies}!",
+    );
+    map.insert(
+        ("en", FORMAT_UNRECOGNIZED),
+        "Uh-oh! ❌ I don't understand... Please use the following format!",
+    );
+    map.insert(
+        ("en", SURCHARGE_MISSING_AMOUNT),
+        "Uh-oh! ❌ Please give me an amount after each +, like +10% tip or +8 tax!",
+    );
+    map.insert(
+        ("en", SURCHARGE_UNRECOGNIZED),
+        "Uh-oh! ❌ I don't understand the amount of the {description}!",
+    );
+    map.insert(("en", RATE_UNRECOGNIZED), "Uh-oh! ❌ I don't understand the conversion rate! Please give it after @, like 50 USD @ 1.37.");
+    map.insert(
+        ("en", USERNAMES_MISSING),
+        "Uh-oh! ❌ Please give me at least one username!",
+    );
+    map.insert(
+        ("en", TOTAL_MISSING),
+        "Uh-oh! ❌ The total amount isn't provided.",
+    );
+    map.insert(
+        ("en", CURRENCY_MISSING),
+        "Uh-oh! ❌ The currency isn't provided.",
+    );
+    map.insert(("en", PAYER_MISSING), "Uh-oh! ❌ The payer isn't provided.");
+    map.insert(
+        ("en", AMOUNTS_EXCEED_TOTAL),
+        "Uh-oh! ❌ The amounts you gave me are more than the total paid!",
+    );
+    map.insert(
+        ("en", PERCENTAGES_NOT_HUNDRED),
+        "Uh-oh! ❌ The percentages you gave me add up to {sum}%, but they should add up to 100%!",
+    );
+    map.insert(
+        ("en", ITEM_AMOUNT_MISSING),
+        "Uh-oh! ❌ I can't find the amount for \"{item}\"!",
+    );
+    map.insert(
+        ("en", ITEM_PERCENT_WITH_USERS),
+        "Uh-oh! ❌ Percentages can only be used for tax and tip, without any usernames!",
+    );
+    map.insert(
+        ("en", ITEMS_MISSING),
+        "Uh-oh! ❌ Please give me at least one item!",
+    );
+    map.insert(
+        ("en", ITEMS_EXCEED_TOTAL),
+        "Uh-oh! ❌ The items you gave me are more than the total paid!",
+    );
+    map.insert(
+        ("en", ITEMS_WITHOUT_USERS),
+        "Uh-oh! ❌ Please give me at least one item with usernames!",
+    );
+    map.insert(
+        ("en", ADJUSTMENTS_EXCEED_TOTAL),
+        "Uh-oh! ❌ The adjustments you gave me are more than the total paid!",
+    );
+    map.insert(
+        ("en", ADJUSTMENTS_NEGATIVE),
+        "Uh-oh! ❌ Someone ends up owing a negative amount! Please check the adjustments.",
+    );
+    map.insert(
+        ("en", PAY_BACK_SELF),
+        "Uh-oh! ❌ You can't pay back yourself!",
+    );
+    map.insert(
+        ("en", TIME_ZONE_UNRECOGNIZED),
+        "🥺 Sorry, I don't recognize that time zone!",
+    );
+    map.insert(
+        ("en", TIME_UNRECOGNIZED),
+        "🥺 Sorry, I don't recognize that time! Please use the 24-hour format, like 18:00.",
+    );
+    map.insert(
+        ("en", REMINDER_DAYS_INVALID),
+        "🥺 Sorry, I need a whole number of days, like 3.",
+    );
+    map.insert(
+        ("en", RETENTION_MONTHS_INVALID),
+        "🥺 Sorry, I need a whole number of months, like 12.",
+    );
+    map.insert(
+        ("en", BALANCES_HEADER),
+        "✨ Ta-da! Here are the updated balances!\n\n",
+    );
+    map.insert(
+        ("en", BALANCES_HEADER_CURRENCY),
+        "✨ Ta-da! Here are the updated balances in {currency}!\n\n",
+    );
+    map.insert(
+        ("en", BALANCES_HEADER_CONVERTED),
+        "✨ Ta-da! Here are the updated balances, all converted to {currency}!\n\n",
+    );
+    map.insert(("en", RATE_AGE_NOW), "just now");
+    map.insert(("en", RATE_AGE_MINUTES), "{count} min ago");
+    map.insert(("en", RATE_AGE_HOURS), "{count}h ago");
+    map.insert(("en", RATE_AGE_DAY), "1 day ago");
+    map.insert(("en", RATE_AGE_DAYS), "{count} days ago");
+    map.insert(("en", RATES_AS_OF), "💱 Rates as of {date}, {age}.");
+    map.insert(("en", RATES_AGE), "💱 Rates as of {age}.");
+    map.insert(("en", BALANCE_DEBT), "{debtor} owes {creditor}: {amount}");
+    map.insert(("en", BALANCES_NONE), "No outstanding balances! 🥳\n");
+    map.insert(("en", PAYMENT_RATE), "Rate: 1 {from} = {rate} {to}");
+    map.insert(("en", PAYMENT_TYPE_TRANSFER), "Type: Transfer 🔁");
+    map.insert(("en", PAYMENT_TYPE_ADJUSTMENT), "Type: Adjustment 🛠️");
+    map.insert(("en", PAYMENT_DETAILS), "{index}. {description}\nDate: {date}\nPayer: {payer}\nTotal: {total}{details}\nSplit:\n{split}");
+    map.insert(
+        ("en", PAYMENT_CONFIRMED_BY),
+        "Confirmed by: {count}/{total} 👍",
+    );
+    map.insert(("en", PAYMENT_ADDED_BY), "Added by: {user}, {date}");
+    map.insert(("en", PAYMENT_EDITED_BY), "Edited by: {user}, {date}");
+    map.insert(
+        ("en", PAYMENT_LINK_NUMBER),
+        "💳 {creditor} can be paid at {link}",
+    );
+    map.insert(("en", ITEM_EVERYONE), "everyone");
+    map.insert(
+        ("en", ITEMS_SUMMARY),
+        "Subtotal: {subtotal}\nRemaining: {remaining}",
+    );
+    map.insert(("en", CONVERT_TO_CURRENCY), "Convert To {currency}");
+
+    map.insert(
+        ("en", SPLIT_USERS_MISSING),
+        "A payment needs at least one person to split it with.",
+    );
+    map.insert(
+        ("en", DEBTS_NOT_TOTAL),
+        "The amounts owed don't add up to the total of the payment.",
+    );
+    map.insert(
+        ("en", PAYMENT_USERS_UNKNOWN),
+        "Only people in this group can be in a payment, and I don't know {users} here yet.",
+    );
+    map.insert(
+        ("en", ADJUSTMENT_ZERO),
+        "An adjustment needs an amount that is not zero.",
+    );
+    map.insert(
+        ("en", ADJUSTMENT_USER_UNKNOWN),
+        "@{username} isn't in any payments in this chat yet.",
+    );
+    map.insert(
+        ("en", ADJUSTMENT_NO_OTHERS),
+        "An adjustment needs someone else in the chat to balance it out.",
+    );
+    map.insert(
+        ("en", TOPIC_SETTING_UNSUPPORTED),
+        "Only the time zone and default currency can be set for a topic",
+    );
+    map.insert(
+        ("en", DISPLAY_NAME_LENGTH),
+        "Names must be between 1 and {max} characters long.",
+    );
+
+    map.insert(("en", NOT_UNDERSTOOD), "Uh-oh! ❌ I don't understand...");
+    map.insert(("en", ADJUST_INSTRUCTIONS), "To correct someone's balance, give me their username, the amount with a + or - sign, and the reason, like this:\n/adjust @alice +15 USD forgot her share of the taxi\n\n+ means they are owed more, and - means they owe more. The other side is shared equally by everyone else in the chat.");
+    map.insert(
+        ("en", ADJUST_RESTRICTED),
+        "🔒 Sorry, only admins of this chat can adjust balances!",
+    );
+    map.insert(
+        ("en", ADJUST_SIGN_MISSING),
+        "Uh-oh! ❌ Please start the amount with + or -, so I know which way to adjust!",
+    );
+    map.insert(
+        ("en", ADJUST_REASON_MISSING),
+        "Uh-oh! ❌ Please give a reason for the adjustment, so everyone knows what it's for!",
+    );
+    map.insert(("en", ADJUST_DONE), "🛠️ Got it! I've adjusted the balance of {user} by {amount}, shared by everyone else in the chat.\nReason: {reason}");
+
+    map.insert(("en", ALIAS_INSTRUCTIONS), "Here's what you can do with names:\n/alias @username <name> - Show a name for someone instead of their username\n/alias remove @username - Go back to showing their username\n\nWithout anything else, I'll show all names in this chat!");
+    map.insert(
+        ("en", ALIASES_NONE),
+        "There are no names set in this chat yet!",
+    );
+    map.insert(
+        ("en", ALIASES_HEADER),
+        "🏷️ Here are the names I'm showing for everyone!",
+    );
+    map.insert(
+        ("en", ALIAS_REMOVED),
+        "👍 Got it! I'll show {user} by their username again.",
+    );
+    map.insert(("en", ALIAS_NOT_SET), "🤔 {user} doesn't have a name set!");
+    map.insert(
+        ("en", ALIAS_SET),
+        "🏷️ Got it! I'll show {user} as {name} from now on.",
+    );
+
+    map.insert(("en", OWED_INSTRUCTIONS), "To see where two people stand with each other, give me their usernames, like this:\n/owed @alice @bob\n\nOr just /owed @alice to see where you stand with them!");
+    map.insert(("en", OWED_SENDER_UNKNOWN), "🥺 Sorry, I can't tell who you are without a Telegram username! Give me both usernames instead, like this:\n/owed @alice @bob");
+    map.insert(
+        ("en", OWED_SAME_USER),
+        "🤔 That's the same person! Give me two different usernames.",
+    );
+    map.insert(
+        ("en", OWED_SQUARE),
+        "🤝 {first} and {second} are all square with each other!",
+    );
+    map.insert(
+        ("en", OWED_HEADER),
+        "💸 Here's where {first} and {second} stand with each other!",
+    );
+    map.insert(("en", OWED_FOOTER), "This counts only payments between the two of them, so it may differ from /balances, which simplifies debts across everyone.");
+    map.insert(("en", AUDIT_ACTION_ADDED), "added");
+    map.insert(("en", AUDIT_ACTION_DELETED), "deleted");
+    map.insert(("en", AUDIT_ACTION_RESTORED), "restored");
+    map.insert(("en", AUDIT_ACTION_ADJUSTED), "adjusted balances for");
+    map.insert(("en", AUDIT_ACTION_EDITED), "edited");
+    map.insert(("en", AUDIT_SOMEONE), "Someone");
+    map.insert(("en", AUDIT_NONE), "There are no changes to payments yet! Add one with /addpayment, and I'll keep track of who changes what. 📋");
+    map.insert(
+        ("en", AUDIT_HEADER),
+        "📋 Here are the latest changes to payments!",
+    );
+
+    map.insert(("en", MONTH_FORMAT), "%B %Y");
+    map.insert(("en", LIMIT_INSTRUCTIONS), "To set a monthly spending limit for someone, give me their username and the limit, like this:\n/limit @alice 300 USD\n\nTo remove it, use /limit @alice off\n\nTo cap what everyone spends together each week, use /limit cap 500 USD, and /limit cap off to remove it.");
+    map.insert(
+        ("en", LIMITS_NONE),
+        "There are no spending limits in this chat yet!",
+    );
+    map.insert(
+        ("en", LIMITS_HEADER),
+        "📊 Here are the spending limits for {month}:",
+    );
+    map.insert(("en", WEEKLY_CAP_STATUS), "Weekly cap for everyone: {cap}");
+    map.insert(
+        ("en", LIMIT_SET),
+        "👍 Got it! I'll let you know when {user} spends more than {amount} in a month.",
+    );
+    map.insert(
+        ("en", LIMIT_REMOVED),
+        "👍 Got it! {user} no longer has a spending limit.",
+    );
+    map.insert(("en", WEEKLY_CAP_SET), "🧢 Got it! Once everyone here spends more than {amount} in a week, the payer will need to override the cap to add more payments.");
+    map.insert(
+        ("en", WEEKLY_CAP_REMOVED),
+        "👍 Got it! There's no weekly cap anymore.",
+    );
+    map.insert(("en", LIMIT_CROSSED), "⚠️ Heads up! {user} has spent {spent} this month, crossing their monthly limit of {limit}!");
+
+    map.insert(("en", PAYLINK_INSTRUCTIONS), "Here's what you can do with your payment link:\n/paylink <link> - Get paid back with a link, like https://paypal.me/alice\n/paylink <phone> - Get paid back with a phone number, like +6591234567\n/paylink off - Remove your payment link\n\nLinks may include {amount} and {currency}, and I'll fill them in with how much is owed!");
+    map.insert(
+        ("en", PAYLINK_TOO_LONG),
+        "🥺 Sorry, that link is too long for me!",
+    );
+    map.insert(
+        ("en", PAYLINK_UNRECOGNIZED),
+        "Uh-oh! ❌ That doesn't look like a link or a phone number...",
+    );
+    map.insert(
+        ("en", PAYLINK_NO_USERNAME),
+        "🥺 Sorry, I can only show payment links for people with a Telegram username!",
+    );
+    map.insert(("en", PAYLINK_CURRENT), "💳 Your payment link is {link}");
+    map.insert(("en", PAYLINK_NONE), "You don't have a payment link yet!");
+    map.insert(
+        ("en", PAYLINK_REMOVED),
+        "👍 Got it! I've removed your payment link.",
+    );
+    map.insert(("en", PAYLINK_SET), "💳 Got it! Whenever someone owes you, I'll show them {link} with the balances, so they can pay you right away.");
+    map.insert(("en", SPLIT_GROUP_INSTRUCTIONS), "Here's what you can do with split groups:\n/group create <name> @user1 @user2 ... - Create a group of people you often split with\n/group delete <name> - Delete a split group\n\nWithout anything else, I'll show all saved split groups!\n\n⭐️ When splitting a payment equally, tap on a group to pick everyone in it at once, or type its name with the other usernames, like: roommates @user3");
+    map.insert(
+        ("en", SPLIT_GROUPS_NONE),
+        "There are no split groups saved yet!",
+    );
+    map.insert(
+        ("en", SPLIT_GROUPS_HEADER),
+        "Here are the saved split groups!",
+    );
+    map.insert(("en", SPLIT_GROUPS_FOOTER), "Tap on a group or type its name when splitting a payment equally to include everyone in it!");
+    map.insert(
+        ("en", SPLIT_GROUP_NAME_TOO_LONG),
+        "Uh-oh! ❌ Please keep the group name within {max} characters!",
+    );
+    map.insert(("en", SPLIT_GROUP_NAME_INVALID), "Uh-oh! ❌ Group names can only have letters, numbers and underscores, and must start with a letter!");
+    map.insert(
+        ("en", SPLIT_GROUP_MEMBERS_MISSING),
+        "🤔 Who's in the group? Let me know like this: /group create roommates @user1 @user2",
+    );
+    map.insert(("en", SPLIT_GROUPS_FULL), "🥺 Sorry, a chat can only have up to {max} split groups! Delete one with /group delete <name> first.");
+    map.insert(("en", SPLIT_GROUP_SAVED), "👥 Saved! The group {name} is {members}.\n\nTap on it or type {name} when splitting a payment equally to include everyone in it!");
+    map.insert(
+        ("en", SPLIT_GROUP_DELETED),
+        "🗑️ The split group {name} has been deleted!",
+    );
+    map.insert(
+        ("en", SPLIT_GROUP_NOT_FOUND),
+        "🥺 Sorry, I can't find the split group {name}!",
+    );
+    map.insert(("en", COMMAND_UNKNOWN), "🥺 Sorry, I don't know that!");
+
+    map.insert(("en", RESTORE_INSTRUCTIONS), "To restore a backup, reply to the backup file I sent with /restore. I'll show you what's inside before anything is changed!");
+    map.insert(
+        ("en", BACKUP_TOO_LARGE),
+        "❌ This file is too large to be a backup!",
+    );
+    map.insert(
+        ("en", BACKUP_DOWNLOAD_FAILED),
+        "❌ Sorry, I couldn't download the backup file! Please try again later.",
+    );
+    map.insert(
+        ("en", BACKUP_INVALID),
+        "❌ This file doesn't look like one of my backups!",
+    );
+    map.insert(("en", BACKUP_VERSION_MISMATCH), "❌ This backup is from a different version of PayScribe (version {version}), so I can't restore it!");
+    map.insert(
+        ("en", BACKUP_SUMMARY),
+        "Users: {users}\nPayments: {payments}\nTrips: {trips}\nSettings: {settings}",
+    );
+    map.insert(
+        ("en", BACKUP_FAILED),
+        "❓ Hmm, something went wrong while backing up! Please try again later.",
+    );
+    map.insert(
+        ("en", BACKUP_CAPTION),
+        "🗄️ Here's a backup of everything in this chat!",
+    );
+    map.insert(
+        ("en", RESTORE_RESTRICTED),
+        "🔒 Only admins of this chat can restore a backup!",
+    );
+    map.insert(("en", RESTORE_PREVIEW), "🗄️ Here's what's in this backup:");
+    map.insert(("en", RESTORE_WARNING), "⚠️ Restoring it will replace all current payments, balances, trips and settings in this chat! If you're sure, reply to the backup file again with /restore confirm");
+    map.insert(
+        ("en", RESTORE_DONE),
+        "✅ All done! I've restored this chat from the backup.",
+    );
+
+    map.insert(
+        ("en", IMPORT_AMOUNT_TOO_LARGE),
+        "\"{amount}\" is too large an amount",
+    );
+    map.insert(
+        ("en", IMPORT_AMOUNT_INVALID),
+        "\"{amount}\" is not an amount",
+    );
+    map.insert(
+        ("en", IMPORT_AMOUNT_DECIMALS),
+        "\"{amount}\" has more than {decimals} decimal places for {currency}",
+    );
+    map.insert(
+        ("en", IMPORT_CURRENCY_UNKNOWN),
+        "\"{currency}\" is not a currency I know",
+    );
+    map.insert(("en", IMPORT_DATE_INVALID), "\"{date}\" is not a date");
+    map.insert(
+        ("en", IMPORT_PAYERS_MULTIPLE),
+        "it was paid by more than one person",
+    );
+    map.insert(("en", IMPORT_PAYER_MISSING), "nobody paid for it");
+    map.insert(
+        ("en", IMPORT_AMOUNTS_MISMATCH),
+        "the amounts don't add up to the cost",
+    );
+    map.insert(
+        ("en", IMPORT_DEBTORS_MISSING),
+        "there's nobody to split it with",
+    );
+    map.insert(("en", IMPORT_FILE_EMPTY), "This file is empty!");
+    map.insert(
+        ("en", IMPORT_COLUMNS_MISSING),
+        "I can't find the description, payer, amount and split columns in this file!",
+    );
+    map.insert(
+        ("en", IMPORT_CURRENCY_NOT_ALLOWED),
+        "this chat doesn't use {currency}",
+    );
+    map.insert(
+        ("en", IMPORT_AMOUNT_NOT_POSITIVE),
+        "the amount must be more than 0",
+    );
+    map.insert(("en", IMPORT_INSTRUCTIONS), "To import payments, send me a CSV file, then reply to it with /import. I'll show you what's inside before anything is added!\n\nI can read Splitwise exports, as long as the names of everyone are changed to their Telegram usernames. Other files need these columns: Description, Paid By, Amount and Split With, and optionally Date and Currency. Split With lists the usernames to split the amount equally with, like: @user1 @user2");
+    map.insert(
+        ("en", IMPORT_TOO_LARGE),
+        "❌ This file is too large for me to import!",
+    );
+    map.insert(
+        ("en", IMPORT_DOWNLOAD_FAILED),
+        "❌ Sorry, I couldn't download the file! Please try again later.",
+    );
+    map.insert(
+        ("en", IMPORT_NOT_CSV),
+        "❌ This file doesn't look like a CSV file!",
+    );
+    map.insert(("en", IMPORT_FILE_ERROR), "❌ {error}");
+    map.insert(("en", IMPORT_ROW_ERROR), "Row {row}: {error}");
+    map.insert(("en", IMPORT_MORE), "...and {count} more");
+    map.insert(("en", IMPORT_ROWS_UNREADABLE), "Uh-oh! ❌ I couldn't read some of the rows in this file:\n\n{errors}\nPlease fix them and try again!");
+    map.insert(
+        ("en", IMPORT_PAYMENTS_NONE),
+        "🤔 There are no payments in this file!",
+    );
+    map.insert(
+        ("en", IMPORT_PAYMENT_LINE),
+        "{description}: {amount} paid by @{payer}, split with @{users}",
+    );
+    map.insert(
+        ("en", IMPORT_NO_USERNAME),
+        "🥺 Sorry, I can only import payments for people with a Telegram username!",
+    );
+    map.insert(("en", IMPORT_PREVIEW_ONE), "📥 I found 1 payment in this file!\n\n{preview}\nIf everything looks right, reply to the file again with /import confirm");
+    map.insert(("en", IMPORT_PREVIEW), "📥 I found {count} payments in this file!\n\n{preview}\nIf everything looks right, reply to the file again with /import confirm");
+    map.insert(("en", IMPORT_OVER_LIMIT), "Uh-oh! ❌ Importing these payments would take this chat over the limit of {max} payments! Please /archive or delete some older payments first.");
+    map.insert(
+        ("en", IMPORT_PROGRESS),
+        "⏳ Importing payments... {done}/{count}",
+    );
+    map.insert(
+        ("en", IMPORT_DONE_ONE),
+        "✅ All done! I've imported 1 payment. Check it out with /viewpayments!",
+    );
+    map.insert(
+        ("en", IMPORT_DONE),
+        "✅ All done! I've imported {count} payments. Check them out with /viewpayments!",
+    );
+
+    map.insert(("en", BUTTON_APPROVE), "Approve");
+    map.insert(("en", BUTTON_DISPUTE), "Dispute");
+    map.insert(("en", BUTTON_CONFIRM_PAYER), "✅ Confirm as Payer");
+    map.insert(("en", BUTTON_CONFIRM_DEBTOR), "✅ Confirm as Debtor");
+    map.insert(
+        ("en", DUAL_ENTRY_NEEDED),
+        "🤝 This payment needs to be confirmed by both sides before I add it!",
+    );
+    map.insert(("en", DUAL_ENTRY_REQUEST), "{payer}, please confirm as the payer, and {debtors}, could one of you confirm as a debtor?");
+    map.insert(
+        ("en", APPROVAL_NEEDED),
+        "🔔 This payment needs approval before I add it!",
+    );
+    map.insert(
+        ("en", APPROVAL_REQUEST),
+        "{approvers}, could one of you approve or dispute it?",
+    );
+    map.insert(
+        ("en", APPROVAL_RESOLVED),
+        "This payment has already been approved or disputed!",
+    );
+    map.insert(
+        ("en", APPROVAL_NOT_ALLOWED),
+        "🚫 Only the other people involved in this payment can approve or dispute it!",
+    );
+    map.insert(
+        ("en", APPROVED_BY),
+        "✅ Approved by {user}! The payment has been added.",
+    );
+    map.insert(
+        ("en", DISPUTED_BY),
+        "❌ Disputed by {user}! I've discarded the payment, no changes have been made.",
+    );
+    map.insert(("en", ADD_PAYMENT_FAILED), "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't add the payment right now. Please try again later!");
+    map.insert(("en", DISCARD_PAYMENT_FAILED), "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't discard the payment right now. Please try again later!");
+    map.insert(("en", CONFIRM_PAYMENT_FAILED), "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't confirm the payment right now. Please try again later!");
+    map.insert(("en", DELETE_PAYMENT_FAILED), "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't delete the payment right now. Please try again later!");
+    map.insert(("en", EDIT_PAYMENT_FAILED), "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't edit the payment right now. Please try again later!");
+    map.insert(("en", UNDO_SPLIT_FAILED), "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I can't undo the split right now. Please try again later!");
+    map.insert(
+        ("en", CONFIRMATION_RESOLVED),
+        "This payment has already been confirmed or disputed!",
+    );
+    map.insert(
+        ("en", CONFIRM_PAYER_NOT_ALLOWED),
+        "🚫 Only the payer can confirm this payment as the payer!",
+    );
+    map.insert(
+        ("en", CONFIRM_DEBTOR_NOT_ALLOWED),
+        "🚫 Only the other people in the split can confirm this payment as a debtor!",
+    );
+    map.insert(
+        ("en", CONFIRMED_BY_PAYER),
+        "☑️ Confirmed by {user} as the payer!",
+    );
+    map.insert(
+        ("en", CONFIRMED_BY_DEBTOR),
+        "☑️ Confirmed by {user} as a debtor!",
+    );
+    map.insert(
+        ("en", CONFIRMED_ADDED_PAYER),
+        "✅ Confirmed by {user} as the payer! The payment has been added.",
+    );
+    map.insert(
+        ("en", CONFIRMED_ADDED_DEBTOR),
+        "✅ Confirmed by {user} as a debtor! The payment has been added.",
+    );
+
+    map.insert(("en", BUTTON_UNDO), "↩️ Undo");
+    map.insert(("en", SPLIT_DESCRIPTION_DEFAULT), "Quick split");
+    map.insert(("en", SPLIT_INSTRUCTIONS), "Give me the amount, and optionally the currency, a description and who to split with, like this:\n\n/split 60 USD dinner\n/split 45.50 taxi @alice @bob\n\n⭐️ Without anyone mentioned, I'll split it equally with everyone I know in this chat!");
+    map.insert(
+        ("en", SPLIT_NO_USERNAME),
+        "🥺 Sorry, I can only split payments for people with a Telegram username!",
+    );
+    map.insert(("en", SPLIT_NO_OTHERS), "🥺 Sorry, there's no one else to split with yet! Mention who to split with, like /split 60 USD dinner @alice");
+    map.insert(
+        ("en", SPLIT_OVERVIEW),
+        "{description}: {amount} paid by {payer}, split equally with {users}",
+    );
+    map.insert(("en", SPLIT_DONE), "⚡ Split done!");
+    map.insert(
+        ("en", SPLIT_ALREADY_DELETED),
+        "This payment has already been deleted!",
+    );
+    map.insert(
+        ("en", SPLIT_UNDO_NOT_ALLOWED),
+        "🚫 Only the person who paid can undo this split!",
+    );
+    map.insert(
+        ("en", SPLIT_UNDONE),
+        "↩️ Undone! I've removed this payment.",
+    );
+
+    map.insert(("en", BUTTON_RECORD), "✅ Record");
+    map.insert(("en", BUTTON_DISMISS), "✖️ Dismiss");
+    map.insert(("en", DETECT_SUGGESTION), "💡 Sounds like a payment! Should I record it?\n\n{description}: {amount} paid by {payer}, split equally with {users}");
+    map.insert(
+        ("en", DETECT_RESOLVED),
+        "This payment has already been recorded or dismissed!",
+    );
+    map.insert(
+        ("en", DETECT_NOT_ALLOWED),
+        "🚫 Only the person who paid can record or dismiss this payment!",
+    );
+    map.insert(
+        ("en", DETECT_DISMISSED),
+        "✖️ Dismissed! I won't record this payment.",
+    );
+    map.insert(
+        ("en", DETECT_RECORDED),
+        "✅ Recorded! The payment has been added.",
+    );
+
+    map.insert(
+        ("en", DELETE_PAYMENT_CANCELLED),
+        "Okay! I've cancelled deleting the payment. No changes have been made! 🌟",
+    );
+    map.insert(("en", DELETE_PAYMENT_REPEATED), "🚫 Oops! It seems like you're already in the middle of deleting a payment! Please finish or {cancel} this before starting another one with me.");
+    map.insert(("en", DELETE_PAYMENT_BLOCKED), "🚫 Oops! It seems like you're in the middle of deleting a payment! Please finish or {cancel} this before starting something new with me.");
+    map.insert(
+        ("en", DELETE_PAYMENT_NO_VIEW),
+        "Uh-oh! ❌ Sorry, please {view} before deleting them!",
+    );
+    map.insert(("en", DELETE_PAYMENTS_PROMPT), "Do you really, really, want to 🗑 delete these {count} payments? I won't be able to undo this... 🫢");
+    map.insert(
+        ("en", DELETE_PAYMENT_PROMPT),
+        "Do you really, really, want to 🗑 delete this payment? I won't be able to undo this... 🫢",
+    );
+    map.insert(("en", PAYMENTS_DELETED), "🎉 Yay! Payments deleted! 🎉");
+    map.insert(("en", PAYMENT_DELETED), "🎉 Yay! Payment deleted! 🎉");
+    map.insert(
+        ("en", PAYMENTS_DELETED_SUMMARY),
+        "🗑️ {count} payments deleted",
+    );
+    map.insert(("en", PAYMENT_DELETED_SUMMARY), "🗑️ Payment deleted");
+    map.insert(
+        ("en", DELETED_RESTORE_HINT),
+        "♻️ Changed your mind? You can restore deleted payments with /trash within {days} days.",
+    );
+
+    map.insert(("en", ONBOARDING_PROGRESS), "Step {step} of {count}");
+    map.insert(("en", ONBOARDING_TIME_ZONE), "🕔 {progress} — Time Zone\n\nI show the dates and times of payments in {time_zone}. Would you like to change the time zone?");
+    map.insert(
+        ("en", ONBOARDING_NO_DEFAULT_CURRENCY),
+        "There's no default currency yet, so payments without a currency are just numbers.",
+    );
+    map.insert(
+        ("en", ONBOARDING_DEFAULT_CURRENCY_INFO),
+        "Payments without a currency are in {currency}.",
+    );
+    map.insert(
+        ("en", ONBOARDING_DEFAULT_CURRENCY),
+        "💵 {progress} — Default Currency\n\n{info} Would you like to set the default currency?",
+    );
+    map.insert(
+        ("en", ONBOARDING_CONVERSION_ON),
+        "Currency conversion is turned on. Would you like to turn it off?",
+    );
+    map.insert(("en", ONBOARDING_CONVERSION_LATER), "I can convert all balances and spendings into your default currency, once you've set one. You can turn this on later with /settings!");
+    map.insert(("en", ONBOARDING_CONVERSION_OFF), "I can convert all balances and spendings into your default currency. Would you like to turn this on?");
+    map.insert(
+        ("en", ONBOARDING_CONVERSION),
+        "↔️ {progress} — Currency Conversion\n\n{prompt}",
+    );
+    map.insert(("en", ONBOARDING_ERASE_ON), "I'm keeping only the final updates, and deleting my other messages. Would you like me to stop?");
+    map.insert(("en", ONBOARDING_ERASE_OFF), "I can keep the chat tidy by deleting my other messages, and keeping only the final updates. Would you like to turn this on?");
+    map.insert(
+        ("en", ONBOARDING_ERASE),
+        "🚮 {progress} — Erase Messages\n\n{prompt}",
+    );
+    map.insert(("en", ONBOARDING_DONE), "🎉 All set! You can start adding payments with /addpayment, and change any of these with /settings whenever you like. Use /help to see everything I can do!");
+    map.insert(("en", ONBOARDING_WELCOME), "👋 Hi everyone, I'm PayScribe! I'll help you keep track of who paid for what, and who owes whom.\n\nLet's get set up with a few quick settings! You can skip any of them, and change them later with /settings.");
+
+    map.insert(
+        ("en", PAYMENT_CARD_PRIVATE),
+        "🔒 This payment is private, so I can't show its note or receipt here!",
+    );
+    map.insert(
+        ("en", PAYMENT_CARD_NOT_DISPUTABLE),
+        "🚫 Only the people in the split of this payment, other than the payer, can dispute it!",
+    );
+    map.insert(("en", PAYMENT_CARD_NOTE), "Note: {note} 📝");
+    map.insert(("en", PAYMENT_CARD_RECEIPT), "Receipt: Attached 🧾");
+    map.insert(("en", PAYMENT_CARD), "Here are the details of this payment! 🔎\n{payment}{details}\n\nWhat would you like to do with it?");
+    map.insert(
+        ("en", PAYMENT_CARD_CLOSED),
+        "Okay! I've closed the payment. No changes have been made! 🌟",
+    );
+    map.insert(("en", PAYMENT_CARD_BLOCKED), "🚫 Oops! It seems like you're in the middle of viewing a payment! Please go back or {cancel} this before starting something new with me.");
+    map.insert(("en", PAYMENT_CARD_NOTE_PROMPT), "📝 What note would you like to add to this payment? For example, where or how it was paid. Please keep it within {max} characters!");
+    map.insert(
+        ("en", PAYMENT_CARD_RECEIPT_PROMPT),
+        "🧾 Send me a photo of the receipt for this payment!",
+    );
+    map.insert(
+        ("en", PAYMENT_CARD_RECEIPT_CAPTION),
+        "🧾 Here's the receipt for {description}!",
+    );
+    map.insert(
+        ("en", PAYMENT_CARD_NOTE_INVALID),
+        "Uh-oh! ❌ Please give me a note within {max} characters!",
+    );
+    map.insert(
+        ("en", PAYMENT_CARD_RECEIPT_INVALID),
+        "Uh-oh! ❌ Please send me a photo of the receipt!",
+    );
+
+    map.insert(("en", IN_CURRENCY), "In {currency}:");
+    map.insert(("en", MONTH_NAME_FORMAT), "%B");
+    map.insert(("en", SPENDINGS_TABLE_NAME), "Name");
+    map.insert(("en", SPENDINGS_TABLE_CONSUMED), "Consumed");
+    map.insert(("en", SPENDINGS_TABLE_FRONTED), "Fronted");
+    map.insert(("en", SPENDINGS_TABLE_NET), "Net");
+    map.insert(
+        ("en", SPENDINGS_PERIOD_NONE),
+        "No spendings in this period! 💤",
+    );
+    map.insert(("en", SPENDINGS_CONSUMPTION_HEADER), "🔥 Here's what everyone consumed and fronted over {period}!\n\nConsumed is each person's share of expenses, and fronted is what they paid upfront for the group.");
+    map.insert(
+        ("en", FORECAST_OVER_LIMIT),
+        "⚠️ Over limit of {limit} by {amount}",
+    );
+    map.insert(("en", FORECAST_WITHIN_LIMIT), "✅ Within limit of {limit}");
+    map.insert(
+        ("en", FORECAST_USER),
+        "{name}\n    Spent So Far: {spent}\n    Projected: {projected}{limit}\n",
+    );
+    map.insert(
+        ("en", FORECAST_GROUP),
+        "Group Spent So Far: {spent}\nGroup Projected: {projected}\n\n{users}",
+    );
+    map.insert(("en", FORECAST_NONE), "No spendings this month yet! 💤");
+    map.insert(("en", FORECAST_HEADER), "📈 Here's where spendings are headed by the end of {month}, going by the daily average of the first {days} day{s}!");
+    map.insert(
+        ("en", SPENDINGS_USER),
+        "{name}\n    Total Spent: {spent}\n    Total Paid For: {paid}\n",
+    );
+    map.insert(
+        ("en", SPENDINGS_GROUP_TOTAL),
+        "Total Group Spending: {amount}\n",
+    );
+    map.insert(("en", SPENDINGS_HEADER), "🔥 Here are the total spendings!");
+    map.insert(
+        ("en", SPENDINGS_HEADER_CURRENCY),
+        "🔥 Here are the total spendings for {currency}!",
+    );
+    map.insert(
+        ("en", SPENDINGS_HEADER_CONVERTED),
+        "🔥 Here are the total spendings, converted to {currency}!",
+    );
+    map.insert(
+        ("en", VIEW_BALANCES_HEADER),
+        "📊 Here are the current balances!",
+    );
+    map.insert(
+        ("en", VIEW_BALANCES_HEADER_CURRENCY),
+        "📊 Here are the current {currency} balances!",
+    );
+    map.insert(
+        ("en", VIEW_BALANCES_HEADER_CONVERTED),
+        "📊 Here are the current balances, converted to {currency}!",
+    );
+    map.insert(
+        ("en", BALANCES_ONLY_OWE),
+        "These are only the debts you owe.",
+    );
+    map.insert(
+        ("en", BALANCES_ONLY_OWED),
+        "These are only the debts owed to you.",
+    );
+    map.insert(
+        ("en", BALANCES_PAIRWISE),
+        "These are who owes whom from each payment, without simplifying.",
+    );
+    map.insert(("en", BALANCES_CONVERSION_REFUSED), "⚠️ The exchange rates are out of date right now, so here are the balances in each currency instead.");
+    map.insert(
+        ("en", BALANCES_OVERDUE),
+        "Debts in bold with ⏰ are past their due date!",
+    );
+    map.insert(
+        ("en", BALANCES_DISPLAY_RESTRICTED),
+        "🔒 Sorry, only admins of this chat can change how balances are shown!",
+    );
+    map.insert(
+        ("en", BALANCES_NO_USERNAME),
+        "🥺 Sorry, I can't tell which debts are yours without a Telegram username!",
+    );
+    map.insert(("en", CONVERT_TO_PROMPT), "💱 Which currency would you like to see the balances in? Enter the 3-letter currency code, like EUR or JPY, or pick one below!\n\nThis is just for a look, so I won't change any settings.");
+    map.insert(
+        ("en", CONVERT_TO_CANCELLED),
+        "Okay! I'll leave the balances as they are! 🌟",
+    );
+    map.insert(
+        ("en", CURRENCY_CODE_INVALID),
+        "Uh-oh! ❌ Please enter a 3-letter currency code, like EUR or JPY!",
+    );
+    map.insert(
+        ("en", RATE_CURRENCY_MISSING),
+        "Uh-oh! ❌ Please give me the currency that the rate is for, like 50 USD @ 1.37!",
+    );
+    map.insert(
+        ("en", RATE_NO_DEFAULT_CURRENCY),
+        "Uh-oh! ❌ There's no default currency to convert into! Please set one in /settings first.",
+    );
+    map.insert(
+        ("en", RATE_ALREADY_DEFAULT),
+        "Uh-oh! ❌ {currency} is already the default currency, so there's nothing to convert!",
+    );
+    map.insert(
+        ("en", ADD_PAYMENT_NO_USERNAME),
+        "🥺 Sorry, I need you to have a Telegram username to add a payment!",
+    );
+    map.insert(("en", SETTINGS_ARGUMENTS), "You can also change a setting directly, like this:\n\n/settings timezone Singapore\n/settings currency SGD\n/settings currency off\n/settings conversion on\n/settings erase off\n/settings eraseuser on\n/settings archive on\n/settings digest 18:00\n/settings digest off\n/settings reminder 3\n/settings reminder off\n/settings retention 12\n/settings retention off\n/settings approval 100\n/settings approval off\n/settings treasurer @username\n/settings treasurer off\n/settings admin on\n/settings pairwise on\n/settings passive on\n/settings dualentry on\n/settings strict on\n/settings language zh\n/settings number 1.234,56\n/settings format S$1,234.30\n/settings verbosity compact\n/settings rounding payer\n/settings weekstart sunday\n/settings currencies SGD USD\n/settings currencies all\n\nIn a forum topic, you can also give it its own time zone or currency:\n\n/settings topic\n/settings topic timezone Tokyo\n/settings topic currency JPY\n/settings topic reset");
+    map.insert(("en", SETTINGS_CURRENCY_NOT_ALLOWED), "🥺 Sorry, this chat only uses these currencies: {currencies}! Please pick one of them as the 💵 Default Currency.");
+    map.insert(
+        ("en", SETTINGS_CURRENCIES_INVALID),
+        "🥺 Sorry, please give me real currency codes, like SGD or USD!",
+    );
+    map.insert(
+        ("en", SETTINGS_VALUE_MISSING),
+        "🥺 Sorry, I need a value for that setting!",
+    );
+    map.insert(
+        ("en", SETTINGS_TOGGLE_INVALID),
+        "🥺 Sorry, please use \"on\" or \"off\" for that setting!",
+    );
+    map.insert(
+        ("en", SETTINGS_LANGUAGE_UNKNOWN),
+        "🥺 Sorry, I don't know that language!",
+    );
+    map.insert(
+        ("en", SETTINGS_NUMBER_FORMAT_UNKNOWN),
+        "🥺 Sorry, I don't know that number format! Please use 1,234.56 or 1.234,56.",
+    );
+    map.insert(("en", SETTINGS_CURRENCY_FORMAT_UNKNOWN), "🥺 Sorry, I don't know that currency format! Please use 12.30 SGD, 1,234.30 SGD, S$12.30 or S$1,234.30.");
+    map.insert(
+        ("en", SETTINGS_VERBOSITY_UNKNOWN),
+        "🥺 Sorry, I don't know that verbosity! Please use verbose, compact or silent.",
+    );
+    map.insert(
+        ("en", SETTINGS_ROUNDING_UNKNOWN),
+        "🥺 Sorry, I don't know that rounding! Please use payer, first, random or roundrobin.",
+    );
+    map.insert(
+        ("en", SETTINGS_WEEK_START_INVALID),
+        "🥺 Sorry, weeks can only start on Monday or Sunday!",
+    );
+    map.insert(
+        ("en", SETTINGS_UNKNOWN),
+        "🥺 Sorry, I don't know that setting!",
+    );
+    map.insert(
+        ("en", SETTINGS_CANCELLED),
+        "Okay! No changes to my settings have been made! 🌟",
+    );
+    map.insert(("en", SETTINGS_MENU), "With pleasure\\! 😉 Let's see, here are the ⚙️ settings you can customize\\. What would you like to view or edit?\n\n💵 *Default Currency* — Currency used if left blank\n\n↔️ *Currency Conversion* — Convert currencies when calculating balances and spendings\n\n🚮 *Erase Messages* — Keep only the final updates and automatically delete my other messages, and optionally your commands and replies too\n\n*🕔 Time Zone* — Time zone for displaying date and time\n\n📦 *Monthly Archive* — Archive last month's payments at the start of each month, carrying forward the balances\n\n📰 *Weekly Digest* — Post a summary of the balances and the week's spendings every Sunday\n\n⏰ *Debt Reminder* — Remind everyone of the outstanding balances every few days\n\n🔏 *Payment Approval* — Require someone else involved to approve payments above a set amount\n\n🏦 *Treasurer* — Have everyone settle up with one person, instead of with each other\n\n🛡️ *Admin Only* — Only let admins edit or delete payments, and change my settings\n\n🌐 *Language* — Language that I reply in\n\n🔢 *Number Format* — Separators for thousands and decimals when reading amounts\n\n💱 *Currency Format* — Currency symbols and thousands separators when showing amounts\n\n💬 *Verbosity* — How much I say after adding, editing or deleting payments\n\n🪙 *Rounding* — Who gets the leftover cents when a payment can't be split equally\n\n📅 *Week Start* — First day of the week for digests, spending caps and weekly reports");
+    map.insert(("en", SETTINGS_REPEATED), "🚫 Oops! It seems like you're already in the middle of customizing my settings! Please finish or {command} this before starting another one with me.");
+    map.insert(("en", SETTINGS_BLOCKED), "🚫 Oops! It seems like you're in the middle of customizing my settings! Please finish or {command} this before starting something new with me.");
+    map.insert(("en", SETTINGS_ENABLED), "ENABLED ✅");
+    map.insert(("en", SETTINGS_DISABLED), "DISABLED ❌");
+    map.insert(
+        ("en", SETTINGS_TIME_ZONE_MENU),
+        "🕔 Time Zone: {time_zone}\n\nWould you like to edit the time zone for this chat?",
+    );
+    map.insert(
+        ("en", SETTINGS_TIME_ZONE_UPDATED),
+        "You got it! I've set the 🕔 Time Zone to {time_zone}!",
+    );
+    map.insert(
+        ("en", SETTINGS_CURRENCY_NOT_SET),
+        "💵 Default Currency is NOT set.",
+    );
+    map.insert(
+        ("en", SETTINGS_CURRENCY_CURRENT),
+        "💵 Default Currency: {currency}",
+    );
+    map.insert(
+        ("en", SETTINGS_CURRENCY_MENU),
+        "Would you like to edit the default currency for this chat?",
+    );
+    map.insert(
+        ("en", SETTINGS_CURRENCY_UPDATED),
+        "You got it! I've set the 💵 Default Currency to {currency}!",
+    );
+    map.insert(
+        ("en", SETTINGS_CURRENCY_DISABLED),
+        "You got it! I've disabled the 💵 Default Currency!",
+    );
+    map.insert(
+        ("en", SETTINGS_CONVERSION_STATUS),
+        "↔️ Currency Conversion is currently {status}.",
+    );
+    map.insert(
+        ("en", SETTINGS_CONVERSION_OFF_PROMPT),
+        "Would you like to turn off currency conversion for this chat?",
+    );
+    map.insert(
+        ("en", SETTINGS_CONVERSION_ON_PROMPT),
+        "Would you like to turn on currency conversion for this chat?",
+    );
+    map.insert(
+        ("en", SETTINGS_CONVERSION_NO_CURRENCY),
+        "⭐️ If you wish to turn on currency conversion, please set a default currency first!",
+    );
+    map.insert(
+        ("en", SETTINGS_CONVERSION_ON),
+        "You got it! I've turned on ↔️ Currency Conversion!",
+    );
+    map.insert(
+        ("en", SETTINGS_CONVERSION_OFF),
+        "You got it! I've turned off ↔️ Currency Conversion!",
+    );
+    map.insert(
+        ("en", SETTINGS_ERASE_STATUS),
+        "🚮 Erase Messages is currently {status}.",
+    );
+    map.insert(
+        ("en", SETTINGS_ERASE_ENABLED_ALL),
+        "ENABLED ✅, along with your commands and replies",
+    );
+    map.insert(
+        ("en", SETTINGS_ERASE_ENABLED_OWN),
+        "ENABLED ✅, for my messages only",
+    );
+    map.insert(("en", SETTINGS_ERASE_OFF_PROMPT), "Would you like to turn off automatic message erasing for this chat, or change whether your commands and replies are erased too?");
+    map.insert(
+        ("en", SETTINGS_ERASE_ON_PROMPT),
+        "Would you like to turn on automatic message erasing for this chat?",
+    );
+    map.insert(
+        ("en", SETTINGS_ERASE_ON),
+        "You got it! I've turned on 🚮 Erase Messages!",
+    );
+    map.insert(
+        ("en", SETTINGS_ERASE_OFF),
+        "You got it! I've turned off 🚮 Erase Messages!",
+    );
+    map.insert(("en", SETTINGS_ERASE_USER_ON), "You got it! With 🚮 Erase Messages on, I'll erase your commands and replies too, where I'm allowed to!");
+    map.insert(
+        ("en", SETTINGS_ERASE_USER_OFF),
+        "You got it! I'll keep your commands and replies, and only erase my own messages! 🚮",
+    );
+    map.insert(
+        ("en", SETTINGS_ARCHIVE_STATUS),
+        "📦 Monthly Archive is currently {status}.",
+    );
+    map.insert(
+        ("en", SETTINGS_ARCHIVE_OFF_PROMPT),
+        "Would you like to turn off monthly archiving for this chat?",
+    );
+    map.insert(
+        ("en", SETTINGS_ARCHIVE_ON_PROMPT),
+        "Would you like to turn on monthly archiving for this chat?",
+    );
+    map.insert(
+        ("en", SETTINGS_ARCHIVE_ON),
+        "You got it! I've turned on 📦 Monthly Archive!",
+    );
+    map.insert(
+        ("en", SETTINGS_ARCHIVE_OFF),
+        "You got it! I've turned off 📦 Monthly Archive!",
+    );
+    map.insert(
+        ("en", SETTINGS_DIGEST_STATUS),
+        "📰 Weekly Digest is currently {status}.",
+    );
+    map.insert(
+        ("en", SETTINGS_DIGEST_ENABLED),
+        "ENABLED ✅, every Sunday at {time}",
+    );
+    map.insert(
+        ("en", SETTINGS_DIGEST_OFF_PROMPT),
+        "Would you like to change the time, or turn off the weekly digest for this chat?",
+    );
+    map.insert(
+        ("en", SETTINGS_DIGEST_ON_PROMPT),
+        "Would you like to turn on the weekly digest for this chat?",
+    );
+    map.insert(("en", SETTINGS_DIGEST_TIME_PROMPT), "📰 At what time on Sundays should I post the digest? Please use the 24-hour format, like 18:00.");
+    map.insert(
+        ("en", SETTINGS_DIGEST_ON),
+        "You got it! I'll post the 📰 Weekly Digest every Sunday at {time}!",
+    );
+    map.insert(
+        ("en", SETTINGS_DIGEST_OFF),
+        "You got it! I've turned off the 📰 Weekly Digest!",
+    );
+    map.insert(
+        ("en", SETTINGS_REMINDER_STATUS),
+        "⏰ Debt Reminder is currently {status}.",
+    );
+    map.insert(
+        ("en", SETTINGS_REMINDER_ENABLED),
+        "ENABLED ✅, every {days} day(s)",
+    );
+    map.insert(
+        ("en", SETTINGS_REMINDER_OFF_PROMPT),
+        "Would you like to change the interval, or turn off the debt reminder for this chat?",
+    );
+    map.insert(
+        ("en", SETTINGS_REMINDER_ON_PROMPT),
+        "Would you like to turn on the debt reminder for this chat?",
+    );
+    map.insert(("en", SETTINGS_REMINDER_DAYS_PROMPT), "⏰ Every how many days should I remind everyone of the outstanding balances? For example: 3, 7, etc.");
+    map.insert(("en", SETTINGS_REMINDER_ON), "You got it! I'll post a ⏰ Debt Reminder every {days} day(s) while there are outstanding balances!");
+    map.insert(
+        ("en", SETTINGS_REMINDER_OFF),
+        "You got it! I've turned off the ⏰ Debt Reminder!",
+    );
+    map.insert(("en", SETTINGS_RETENTION_ON), "You got it! I'll clear payments older than {months} month(s), sending a summary first and carrying forward the balances! 🧹");
+    map.insert(
+        ("en", SETTINGS_RETENTION_OFF),
+        "You got it! I've turned off 🧹 Data Retention, so all payments will be kept!",
+    );
+    map.insert(
+        ("en", SETTINGS_APPROVAL_STATUS),
+        "🔏 Payment Approval is currently {status}.",
+    );
+    map.insert(
+        ("en", SETTINGS_APPROVAL_ENABLED),
+        "ENABLED ✅, for payments above {threshold}",
+    );
+    map.insert(
+        ("en", SETTINGS_APPROVAL_OFF_PROMPT),
+        "Would you like to change the amount, or turn off payment approval for this chat?",
+    );
+    map.insert(
+        ("en", SETTINGS_APPROVAL_ON_PROMPT),
+        "Would you like to turn on payment approval for this chat?",
+    );
+    map.insert(("en", SETTINGS_APPROVAL_THRESHOLD_PROMPT), "🔏 Above what amount should payments need approval? For example: 100, 250.50, etc.\n\n⭐️ This applies to the total of each payment, in whichever currency it is in!");
+    map.insert(
+        ("en", SETTINGS_APPROVAL_ON),
+        "You got it! Payments above {threshold} will now need 🔏 Payment Approval!",
+    );
+    map.insert(
+        ("en", SETTINGS_APPROVAL_OFF),
+        "You got it! I've turned off 🔏 Payment Approval!",
+    );
+    map.insert(
+        ("en", SETTINGS_TREASURER_STATUS),
+        "🏦 Treasurer is currently {status}.",
+    );
+    map.insert(
+        ("en", SETTINGS_TREASURER_ENABLED),
+        "ENABLED ✅, with @{treasurer} as the treasurer",
+    );
+    map.insert(
+        ("en", SETTINGS_TREASURER_OFF_PROMPT),
+        "Would you like to change the treasurer, or let everyone settle up with each other again?",
+    );
+    map.insert(
+        ("en", SETTINGS_TREASURER_ON_PROMPT),
+        "Would you like to have everyone settle up with a treasurer for this chat?",
+    );
+    map.insert(("en", SETTINGS_TREASURER_USERNAME_PROMPT), "🏦 Who should be the treasurer? Please enter their username, like @username.\n\n⭐️ Everyone who owes money will pay them, and they will pay everyone who is owed!");
+    map.insert(
+        ("en", SETTINGS_TREASURER_ON),
+        "You got it! Everyone will now settle up with @{treasurer} as the 🏦 Treasurer!",
+    );
+    map.insert(
+        ("en", SETTINGS_TREASURER_OFF),
+        "You got it! I've turned off the 🏦 Treasurer!",
+    );
+    map.insert(
+        ("en", SETTINGS_ADMIN_ONLY_RESTRICTED),
+        "🔒 Sorry, only admins of this chat can change who is allowed to edit records!",
+    );
+    map.insert(
+        ("en", SETTINGS_ADMIN_ONLY_STATUS),
+        "🛡️ Admin Only is currently {status}.",
+    );
+    map.insert(
+        ("en", SETTINGS_ADMIN_ONLY_OFF_PROMPT),
+        "Would you like to let everyone edit or delete payments, and change settings again?",
+    );
+    map.insert(
+        ("en", SETTINGS_ADMIN_ONLY_ON_PROMPT),
+        "Would you like to only let admins edit or delete payments, and change settings?",
+    );
+    map.insert(
+        ("en", SETTINGS_ADMIN_ONLY_ON),
+        "You got it! I've turned on 🛡️ Admin Only!",
+    );
+    map.insert(
+        ("en", SETTINGS_ADMIN_ONLY_OFF),
+        "You got it! I've turned off 🛡️ Admin Only!",
+    );
+    map.insert(("en", SETTINGS_PAIRWISE_ON), "You got it! I'll show balances as who owes whom from each payment, without simplifying them! 🔀");
+    map.insert(
+        ("en", SETTINGS_PAIRWISE_OFF),
+        "You got it! I'll simplify balances into the fewest payments needed to settle up! 🔀",
+    );
+    map.insert(("en", SETTINGS_PASSIVE_ON), "You got it! When someone says something like \"paid 30 for lunch with @bob\", I'll offer to record it! 👂");
+    map.insert(
+        ("en", SETTINGS_PASSIVE_OFF),
+        "You got it! I'll only record payments when asked with /addpayment! 👂",
+    );
+    map.insert(("en", SETTINGS_DUAL_ENTRY_ON), "You got it! New payments will only be added once the payer and someone in the split confirm them! 🤝");
+    map.insert(
+        ("en", SETTINGS_DUAL_ENTRY_OFF),
+        "You got it! New payments will be added right away again! 🤝",
+    );
+    map.insert(("en", SETTINGS_STRICT_ON), "You got it! Only people already in this group can be in payments, and I'll ask before adding anyone new! 🚪");
+    map.insert(
+        ("en", SETTINGS_STRICT_OFF),
+        "You got it! Anyone mentioned in a payment will be added to the group again! 🚪",
+    );
+    map.insert(("en", SETTINGS_NUMBER_FORMAT_MENU), "🔢 Number Format: {format}\n\nHow does this chat write amounts? Spaces and apostrophes can always be used to separate thousands too!");
+    map.insert(
+        ("en", SETTINGS_NUMBER_FORMAT_UPDATED),
+        "You got it! I'll read amounts written like {format} from now on! 🔢",
+    );
+    map.insert(("en", SETTINGS_CURRENCY_FORMAT_MENU), "💱 Currency Format: {format}\n\nHow should I show amounts in this chat? Currencies without a well-known symbol will still be shown with their code!");
+    map.insert(
+        ("en", SETTINGS_CURRENCY_FORMAT_UPDATED),
+        "You got it! I'll show amounts like {format} from now on! 💱",
+    );
+    map.insert(("en", SETTINGS_VERBOSITY_MENU), "💬 Verbosity: {verbosity}\n\nHow much should I say whenever payments are added, edited or deleted?\n\nVerbose — The full details and balances\nCompact — A single line\nSilent — Just a 👍 reaction");
+    map.insert(
+        ("en", SETTINGS_VERBOSITY_VERBOSE),
+        "You got it! I'll show the full details and balances whenever payments change! 💬",
+    );
+    map.insert(
+        ("en", SETTINGS_VERBOSITY_COMPACT),
+        "You got it! I'll keep it to a single line whenever payments change! 💬",
+    );
+    map.insert(
+        ("en", SETTINGS_VERBOSITY_SILENT),
+        "You got it! I'll just react with a 👍 whenever payments change! 💬",
+    );
+    map.insert(("en", SETTINGS_ROUNDING_MENU), "🪙 Rounding: {rounding}\n\nWhen a payment can't be split equally to the cent, who should take the leftover cents?\n\nPayer — The person who paid\nFirst — The first people in the split\nRandom — Random people in the split\nRound Robin — Everyone takes turns");
+    map.insert(
+        ("en", SETTINGS_ROUNDING_PAYER),
+        "You got it! The payer will take any leftover cents from now on! 🪙",
+    );
+    map.insert(
+        ("en", SETTINGS_ROUNDING_FIRST),
+        "You got it! The first people in the split will take any leftover cents from now on! 🪙",
+    );
+    map.insert(
+        ("en", SETTINGS_ROUNDING_RANDOM),
+        "You got it! I'll give any leftover cents to random people from now on! 🪙",
+    );
+    map.insert(
+        ("en", SETTINGS_ROUNDING_ROUND_ROBIN),
+        "You got it! I'll take turns giving out any leftover cents from now on! 🪙",
+    );
+    map.insert(("en", SETTINGS_WEEK_START_MENU), "📅 Week Start: {week_start}\n\nWhich day should weeks start on? This is used for the weekly digest and the weekly spending cap.\n\n⭐️ Weeks and months follow the 🕔 Time Zone of this chat!");
+    map.insert(
+        ("en", SETTINGS_WEEK_START_MONDAY),
+        "You got it! Weeks will start on Monday from now on! 📅",
+    );
+    map.insert(
+        ("en", SETTINGS_WEEK_START_SUNDAY),
+        "You got it! Weeks will start on Sunday from now on! 📅",
+    );
+    map.insert(
+        ("en", SETTINGS_CURRENCIES_RESTRICTED),
+        "🔒 Sorry, only admins of this chat can change which currencies can be used!",
+    );
+    map.insert(("en", SETTINGS_CURRENCIES_DEFAULT_MISSING), "⭐️ The 💵 Default Currency is {currency}, so please allow it too, or change the default currency first!");
+    map.insert(
+        ("en", SETTINGS_CURRENCIES_ALL),
+        "You got it! Any currency can be used in this chat from now on! 💱",
+    );
+    map.insert(
+        ("en", SETTINGS_CURRENCIES_UPDATED),
+        "You got it! Only these currencies can be used in this chat from now on: {currencies}! 💱",
+    );
+    map.insert(
+        ("en", SETTINGS_TOPIC_ONLY),
+        "⭐️ Topic settings can only be changed from within a topic of a forum group!",
+    );
+    map.insert(("en", SETTINGS_TOPIC_INVALID), "🥺 Sorry, only the 🕔 Time Zone and 💵 Default Currency can be set for a topic! Use /settings topic reset to follow the chat again.");
+    map.insert(("en", SETTINGS_TOPIC_SUMMARY), "Here are the settings for this topic! 🗂️\n\n🕔 Time Zone: {time_zone}\n💵 Default Currency: {currency}");
+    map.insert(("en", SETTINGS_TOPIC_OWN), "{value} (this topic)");
+    map.insert(("en", SETTINGS_TOPIC_INHERITED), "{value} (from the chat)");
+    map.insert(("en", SETTINGS_TOPIC_NONE), "None (from the chat)");
+    map.insert(
+        ("en", SETTINGS_TOPIC_RESET),
+        "You got it! This topic will follow the settings of the chat again! 🗂️",
+    );
+    map.insert(
+        ("en", SETTINGS_TOPIC_TIME_ZONE_UPDATED),
+        "You got it! I've set the 🕔 Time Zone of this topic to {time_zone}!",
+    );
+    map.insert(
+        ("en", SETTINGS_TOPIC_CURRENCY_UPDATED),
+        "You got it! Payments in this topic without a currency will be in {currency}! 💵",
+    );
+    map.insert(("en", TRIP_INSTRUCTIONS), "Here's what you can do with trips:\n/trip start <name> - Start or resume a trip\n/trip end - End the current trip, with a report of it\n/trip end csv - Also send all payments of the trip as a CSV file\n/trip balances <name> - View the balances of a trip\n/trip spendings <name> - View the spendings of a trip\n\nIf no name is given, I'll show the current trip!");
+    map.insert(
+        ("en", TRIP_BALANCES_HEADER),
+        "📊 Here are the current balances for the trip {trip}!",
+    );
+    map.insert(
+        ("en", TRIP_SPENDINGS_HEADER),
+        "🔥 Here are the total spendings for the trip {trip}!",
+    );
+    map.insert(("en", TRIP_EXPENSES_NONE), "No expenses on this trip!");
+    map.insert(("en", TRIP_REPORT), "🧾 Trip Report: {trip}\n\n🔥 Spendings\n{spendings}\n\n🏆 Biggest Expense\n{expenses}\n\n💸 Settle-Up Plan\n{balances}");
+    map.insert(
+        ("en", TRIP_CSV_CAPTION),
+        "📎 Here are all the payments of the trip {trip}!",
+    );
+    map.insert(
+        ("en", TRIP_CURRENT),
+        "🧳 You're currently on the trip {trip}!",
+    );
+    map.insert(("en", TRIP_NONE), "🧳 There's no trip going on right now!");
+    map.insert(("en", TRIP_ALL), "All trips: {trips}");
+    map.insert(
+        ("en", TRIP_NAME_MISSING),
+        "🤔 What's the name of the trip? Let me know like this: /trip start Japan Trip",
+    );
+    map.insert(
+        ("en", TRIP_ALREADY_STARTED),
+        "🧳 You're already on the trip {trip}!",
+    );
+    map.insert(
+        ("en", TRIP_STILL_ON),
+        "🧳 You're still on the trip {trip}! Use /trip end to end it before starting a new one.",
+    );
+    map.insert(("en", TRIP_STARTED), "🧳 Off we go! The trip {trip} has started!\n\nI'll track all new payments under this trip, as well as overall. Use /trip end when it's over!");
+    map.insert(("en", TRIP_ENDED), "🏁 Welcome back! The trip {trip} has ended, new payments will only be tracked overall.\n\n{report}\n\nYou can still view /trip balances {trip} anytime!");
+    map.insert(
+        ("en", TRIP_NOT_ON),
+        "❌ There's no trip going on right now!",
+    );
+    map.insert(("en", TRIP_WHICH), "❌ There's no trip going on right now! Which trip would you like to view? Let me know like this: /trip {command} Japan Trip");
+    map.insert(
+        ("en", TRIP_NOT_FOUND),
+        "🥺 Sorry, I can't find the trip {trip}!",
+    );
+    map.insert(("en", TRIP_UNKNOWN), "🥺 Sorry, I don't know that!");
+    map.insert(("en", ARCHIVE_SUMMARY_ONE), "📦 It's a brand new month! I've archived 1 payment from {month}, and carried forward the balances as a single entry.\n\nHere are the opening balances for this month:\n{balances}");
+    map.insert(("en", ARCHIVE_SUMMARY), "📦 It's a brand new month! I've archived {count} payments from {month}, and carried forward the balances as a single entry.\n\nHere are the opening balances for this month:\n{balances}");
+    map.insert(
+        ("en", DIGEST_SPENDINGS_NONE),
+        "No spendings this week! 💤\n",
+    );
+    map.insert(("en", DIGEST_WEEK), "{start} to {end}");
+    map.insert(("en", DIGEST_SUMMARY), "📰 Here's your weekly digest for {week}!\n\n✨ Current balances:\n{balances}\n🔥 Spendings this week:\n{spendings}");
+    map.insert(("en", ADD_PAYMENT_OVERVIEW), "Here's what I've got so far! 📝\n\n{overview}{suggestion}Do you want to confirm this entry or would you like to make any changes?");
+    map.insert(
+        ("en", ADD_PAYMENT_CANCELLED),
+        "Okay! I've cancelled adding the payment. No changes have been made! 🌟",
+    );
+    map.insert(("en", ADD_PAYMENT_REPEATED), "🚫 Oops! It seems like you're already in the middle of adding a payment! Please finish or {cancel} this before starting another one with me.");
+    map.insert(("en", ADD_PAYMENT_BLOCKED), "🚫 Oops! It seems like you're in the middle of adding a payment! Please finish or {cancel} this before starting something new with me.");
+    map.insert(
+        ("en", WEEKLY_CAP_OVERRIDE_NOT_ALLOWED),
+        "🚫 Only the payer can override the weekly cap!",
+    );
+    map.insert(("en", PAY_BACK_CURRENCY_MENU), "Absolutely! 🙌 Would you like to set a currency for this payment? You can also choose to skip this step.");
+    map.insert(("en", PAY_BACK_PARTIAL_FAILED), "⁉️ Oh no! Something went wrong! 🥺 I'm sorry, but I couldn't add all the payments. Please check {view} for the ones that were added, and try again later!");
+    map.insert(("en", PAY_BACK_REPEATED), "🚫 Oops! It seems like you're already in the middle of paying back! Please finish or {cancel} this before starting another one with me.");
+    map.insert(("en", PAY_BACK_BLOCKED), "🚫 Oops! It seems like you're in the middle of paying back! Please finish or {cancel} this before starting something new with me.");
+    map.insert(
+        ("en", PAY_BACK_CREDITORS_NONE_SELECTED),
+        "Tap who you've paid back first!",
+    );
+    map.insert(
+        ("en", PAY_BACK_CREDITORS_NOT_ALLOWED),
+        "Only the one paying back can pick who they've paid!",
+    );
+    map.insert(("en", DUPLICATE_PAYMENT_REPEATED), "🚫 Oops! It seems like you're already in the middle of duplicating a payment! Please finish or {cancel} this before starting another one with me.");
+    map.insert(("en", DUPLICATE_PAYMENT_BLOCKED), "🚫 Oops! It seems like you're in the middle of duplicating a payment! Please finish or {cancel} this before starting something new with me.");
+    map.insert(("en", PAY_BACK_SUMMARY), "🎉 {user} paid back {amounts}");
+    map.insert(
+        ("en", DEBTOR_CORRECTION_SUGGESTION),
+        "🤔 I don't know {typos} here yet. Did you mean {similar}?\n\n",
+    );
+    map.insert(("en", DEBTOR_CORRECTION_BUTTON), "Did you mean {similar}?");
+    map.insert(("en", TRASH_EMPTY), "🗑️ The trash is empty! Deleted payments stay here for {days} days, in case you need them back.");
+    map.insert(("en", TRASH_LIST), "🗑️ Here are the latest deleted payments! They stay here for {days} days, before I delete them for good.\n\n{payments}\n\nTap a number to restore that payment.");
+    map.insert(
+        ("en", TRASH_ENTRY),
+        "{number}. {description} ({date}) — {total} paid by {payer}\nDeleted: {deleted}",
+    );
+    map.insert(
+        ("en", TRASH_RESTORE_NOT_ALLOWED),
+        "🔒 Sorry, only admins of this chat can restore payments!",
+    );
+    map.insert(
+        ("en", TRASH_RESTORE_GONE),
+        "This payment is no longer in the trash!",
+    );
+    map.insert(
+        ("en", TRASH_RESTORED),
+        "♻️ Payment '{description}' restored! I've added it back to the balances.",
+    );
+    map.insert(("en", LEADERBOARD_INSTRUCTIONS), "Pick a period like this: /leaderboard week, /leaderboard month, /leaderboard year, or /leaderboard all.");
+    map.insert(
+        ("en", LEADERBOARD_PERIOD_UNKNOWN),
+        "Uh-oh! ❌ I don't know that period...\n\n{instructions}",
+    );
+    map.insert(("en", LEADERBOARD_PERIOD_WEEK), "the past week");
+    map.insert(("en", LEADERBOARD_PERIOD_MONTH), "the past month");
+    map.insert(("en", LEADERBOARD_PERIOD_YEAR), "the past year");
+    map.insert(("en", LEADERBOARD_PERIOD_ALL), "all time");
+    map.insert(("en", LEADERBOARD_EMPTY), "🏆 There are no payments from {period}, so there's no one to rank yet! Add one with /addpayment.");
+    map.insert(
+        ("en", LEADERBOARD_SUMMARY_ONE),
+        "🏆 Here's the leaderboard for {period}, over 1 payment!\n\n{leaderboard}⭐️ {instructions}",
+    );
+    map.insert(("en", LEADERBOARD_SUMMARY), "🏆 Here's the leaderboard for {period}, over {count} payments!\n\n{leaderboard}⭐️ {instructions}");
+    map.insert(("en", LEADERBOARD_TOP_PAYERS), "💸 Top Payers:\n");
+    map.insert(
+        ("en", LEADERBOARD_TOP_PAYERS_CURRENCY),
+        "💸 Top Payers ({currency}):\n",
+    );
+    map.insert(
+        ("en", LEADERBOARD_FREQUENT_DEBTORS),
+        "🧾 Most Frequent Debtors:\n",
+    );
+    map.insert(
+        ("en", LEADERBOARD_FASTEST_SETTLERS),
+        "⚡️ Fastest Settlers:\n",
+    );
+    map.insert(("en", LEADERBOARD_PAYMENTS_ONE), "1 payment");
+    map.insert(("en", LEADERBOARD_PAYMENTS), "{count} payments");
+    map.insert(("en", LEADERBOARD_SETTLE_TIME), "pays back in {duration}");
+    map.insert(("en", LEADERBOARD_MINUTES_ONE), "1 minute");
+    map.insert(("en", LEADERBOARD_MINUTES), "{count} minutes");
+    map.insert(("en", LEADERBOARD_HOURS_ONE), "1 hour");
+    map.insert(("en", LEADERBOARD_HOURS), "{count} hours");
+    map.insert(("en", LEADERBOARD_DAYS_ONE), "1 day");
+    map.insert(("en", LEADERBOARD_DAYS), "{count} days");
+    map.insert(
+        ("en", DISPUTE_ALREADY_OPEN),
+        "⚠️ This payment is already disputed! The payer has to edit or dismiss it first.",
+    );
+    map.insert(("en", DISPUTE_OPENED), "⚠️ {disputer} has disputed the payment '{description}'!\n\n{payer}, could you check it? You can edit the payment, or dismiss the dispute if it's correct.");
+    map.insert(
+        ("en", DISPUTE_NOTIFICATION),
+        "⚠️ {disputer} has disputed your payment '{description}' in {chat}! Please check it there.",
+    );
+    map.insert(("en", DISPUTE_CHAT_DEFAULT), "a group");
+    map.insert(
+        ("en", DISPUTE_RESOLVED),
+        "This dispute has already been resolved!",
+    );
+    map.insert(
+        ("en", DISPUTE_NOT_ALLOWED),
+        "🚫 Only the payer can resolve this dispute!",
+    );
+    map.insert(
+        ("en", DISPUTE_DISMISSED),
+        "{message}\n\n✅ Dismissed by the payer! The payment stays as it is.",
+    );
+    map.insert(
+        ("en", DISPUTE_BUSY),
+        "🚫 Please finish or cancel what you're doing with me first!",
+    );
+    map.insert(
+        ("en", DISPUTE_EDIT_NOT_ALLOWED),
+        "🔒 Sorry, only admins of this chat can edit payments!",
+    );
+    map.insert(
+        ("en", NOTIFICATION_ON),
+        "🔔 I'll also message you here whenever you're added to a payment in a group!",
+    );
+    map.insert(
+        ("en", NOTIFICATION_ADDED),
+        "🔔 You were added to '{description}' for {amount} in {chat}, by {sender}!",
+    );
+    map.insert(("en", NOTIFICATION_CHAT_DEFAULT), "a group");
+    map.insert(("en", NOTIFICATION_OFF), "🔕 Got it! I won't message you about new payments. Send me /start here anytime to turn them back on!");
+    map.insert(
+        ("en", NOTIFICATION_OFF_FAILED),
+        "⁉️ Oh no! Something went wrong! 🥺 Please try again later!",
+    );
+    map.insert(("en", STATS_CHANGE_UP), " (📈 {change}% from last month)");
+    map.insert(("en", STATS_CHANGE_DOWN), " (📉 {change}% from last month)");
+    map.insert(("en", STATS_CHANGE_SAME), " (same as last month)");
+    map.insert(("en", STATS_HEADER_CURRENCY), "{month} ({currency})");
+    map.insert(("en", STATS_MONTH_ONE), "{header}:\n    Total Spent: {total}{change}\n    Top Spender: {spender} ({spending})\n    Largest Expense: {expense} ({expense_amount})\n    Average Expense: {average} over 1 payment\n");
+    map.insert(("en", STATS_MONTH), "{header}:\n    Total Spent: {total}{change}\n    Top Spender: {spender} ({spending})\n    Largest Expense: {expense} ({expense_amount})\n    Average Expense: {average} over {count} payments\n");
+    map.insert(
+        ("en", STATS_NONE),
+        "📈 There are no payments yet, so there's nothing to show! Add one with /addpayment.",
+    );
+    map.insert(
+        ("en", STATS_SUMMARY),
+        "📈 Here are the stats for the past few months!\n\n{stats}",
+    );
+    map.insert(
+        ("en", STATEMENT_FILE_CAPTION),
+        "📜 Here's the formal statement of debts, in a text file as it's a long one!",
+    );
+    map.insert(("en", RETENTION_MONTHS_ONE), "1 month");
+    map.insert(("en", RETENTION_MONTHS), "{count} months");
+    map.insert(("en", RETENTION_CAPTION_ONE), "🧹 Time for some tidying up! I'm clearing 1 payment from before {date}, as this chat keeps payments for {months}. Here's a summary for your records!\n\nThe balances are carried forward as a single entry, so nobody's balance changes.");
+    map.insert(("en", RETENTION_CAPTION), "🧹 Time for some tidying up! I'm clearing {count} payments from before {date}, as this chat keeps payments for {months}. Here's a summary for your records!\n\nThe balances are carried forward as a single entry, so nobody's balance changes.");
+
+    // Chinese (Simplified)
+    map.insert(
+        ("zh", UNKNOWN_ERROR),
+        "⁉️ 糟糕！出错了！🥺 很抱歉，我现在无法完成这个操作。请稍后再试！\n\n",
+    );
+    map.insert(("zh", NO_TEXT), "❓ 我有点看不懂！请用文字回复我！\n\n");
+    map.insert(("zh", START_INTRODUCTION), "👋 你好！我是 PayScribe！😊\n\n🧚‍♀️ 我会记录你们群里的付款，并施展魔法 🪄 简化你们的债务，让你们不用再为还朋友钱而手忙脚乱！");
+    map.insert(("zh", START_ADD_INFO), "✍️ 准备好在这个群里一起记账了吗？从 /addpayment 开始吧！你们可以随时 /viewpayments 查看记录，如果需要，我也可以帮忙 /editpayment 或 /deletepayment！");
+    map.insert(("zh", START_VIEW_INFO), "🙈 看看 /spendings，就知道谁最会花钱！瞄一眼 /balances 看看谁欠谁多少，但别忘了 /payback 还钱给朋友哦！");
+    map.insert(
+        ("zh", START_CLOSING),
+        "🤗 祝你们记账愉快，有需要随时找我 /help！",
+    );
+    map.insert(("zh", HELP_TITLE), "⭐️ *我的指令* ⭐️");
+    map.insert(
+        ("zh", HELP_MENU_INFO),
+        "👇 点击下面的指令，看看它怎么用，还有例子和常见错误！你也可以发送 /help 加上指令，比如 /help addpayment。",
+    );
+    map.insert(
+        ("zh", HELP_UNKNOWN_COMMAND),
+        "🤔 我没有叫 {command} 的指令！以下是我所有的指令：",
+    );
+    map.insert(
+        ("zh", HELP_USER_GUIDE),
+        "🆘 想了解支持的 🕔 时区、💵 货币等所有细节，请查看我的[用户指南]({url})！",
+    );
+    map.insert(
+        ("zh", HELP_FEEDBACK),
+        "💖 如果你对我有任何[反馈]({url})，我很乐意听听！",
+    );
+    map.insert(
+        ("zh", CANCEL_NOTHING),
+        "❌ 我现在没在做什么… 👀\n没有什么需要取消的！",
+    );
+    map.insert(
+        ("zh", LANGUAGE_MENU),
+        "🌐 语言：{language}\n\n你希望我用哪种语言回复？",
+    );
+    map.insert(
+        ("zh", LANGUAGE_UPDATED),
+        "没问题！从现在起我会用{language}回复！🌐",
+    );
+    map.insert(
+        ("zh", VALIDATION_ERROR),
+        "❌ 嗯，好像不太对！{reason} 请检查后再试一次！\n\n",
+    );
+    map.insert(
+        ("zh", RATE_LIMIT_ERROR),
+        "⏳ 慢一点！你发送请求太快了。请稍等一下再试！\n\n",
+    );
+    map.insert(
+        ("zh", CONVERSION_ERROR),
+        "💱 我现在无法获取汇率！请稍后再试，或者用 /settings 关闭货币转换，查看原始货币。\n\n",
+    );
+    map.insert(
+        ("zh", TELEGRAM_ERROR),
+        "📡 我现在和 Telegram 的连接有点问题！请稍后再试。\n\n",
+    );
+    map.insert(
+        ("zh", STATEMENT_TITLE),
+        "债务正式声明\n{group}\n截至 {date}",
+    );
+    map.insert(
+        ("zh", STATEMENT_DEBT),
+        "{index}. {debtor} 欠 {creditor} {amount}\n    （大写：{words}）",
+    );
+    map.insert(("zh", STATEMENT_EMPTY), "目前没有未结清的债务。");
+    map.insert(
+        ("zh", STATEMENT_CLOSING),
+        "以上各债务人确认欠付所列债权人上述款项。",
+    );
+
+    map.insert(("zh", CLEANUP_COMPACT), "🧹 整理好了！");
+    map.insert(("zh", DELETE_PERMISSION), "🧹 我想整理一下我的消息，但我在这里没有删除消息的权限！我会改为缩短它们。\n\n想让我清理掉它们，请把我设为有删除消息权限的管理员。或者，如果你想保留它们，请在 /settings 中关闭 Erase Messages。");
+    map.insert(("zh", TOTAL_PROMPT), "好的！总金额是多少？");
+    map.insert(("zh", TOTAL_QUESTION), "总金额应该是多少？");
+    map.insert(("zh", TOTAL_INSTRUCTIONS), "输入金额，也可以加上 3 个字母的货币代码。例如：100.00 USD、200 JPY、300.00、1,234.56 EUR 等。\n\n有小费或税？直接加上去，例如：80 USD +10% tip +7% tax，或 80 USD +8 tip。我会算出总额！\n\n用外币付款？在 @ 后面加上你被收取的汇率，例如：50 USD @ 1.37。\n\n⭐️ 如果不确定货币代码，随时可以用 /help 查看我的用户指南！");
+    map.insert(("zh", CURRENCY_INSTRUCTIONS), "输入 3 个字母的货币代码。例如：USD、EUR、JPY 等。\n\n⭐️ 如果不确定货币代码，随时可以用 /help 查看我的用户指南！");
+    map.insert(
+        ("zh", DATE_INSTRUCTIONS),
+        "输入付款日期。例如：today、yesterday、3 days ago、last friday、2024-03-12、12 Mar 等。",
+    );
+    map.insert(("zh", DATE_UNRECOGNIZED), "🥺 抱歉，我看不懂这个日期！");
+    map.insert(("zh", DATE_IN_FUTURE), "🥺 抱歉，付款日期不能在未来！");
+    map.insert(("zh", DUE_DATE_INSTRUCTIONS), "输入应该还款的日期。例如：tomorrow、in 2 weeks、next friday、2024-03-12、12 Mar 等。或者输入 none 来移除它。");
+    map.insert(("zh", DUE_DATE_IN_PAST), "🥺 抱歉，还款日期不能在过去！");
+    map.insert(
+        ("zh", TIME_ZONE_INSTRUCTIONS),
+        "⭐️ 用 /help 查看我的用户指南，了解我支持的所有时区！",
+    );
+    map.insert(("zh", SPLIT_PROMPT), "太棒了！我们要怎么分摊？");
+    map.insert(("zh", DEBT_FORMATS), "Equal — 所有人平均分摊总金额\nExact — 按每个人的确切金额分摊\nProportion — 按每个人的相对比例分摊\nPercent — 按每个人的百分比分摊，合计为 100%\nAdjusted — 平均分摊总金额，部分人多付或少付\nItemized — 列出账单上的项目，税和小费按比例分摊\n");
+    map.insert(("zh", DEBT_USERS_PROMPT), "好的！这笔付款涉及哪些人？");
+    map.insert(
+        ("zh", DEBT_AMOUNTS_PROMPT),
+        "好的！涉及哪些人，他们各欠多少？",
+    );
+    map.insert(
+        ("zh", DEBT_PERCENTAGES_PROMPT),
+        "好的！涉及哪些人，他们各欠多少百分比？",
+    );
+    map.insert(
+        ("zh", DEBT_ADJUSTMENTS_PROMPT),
+        "好的！涉及哪些人，他们各自的调整是多少？",
+    );
+    map.insert(("zh", DEBT_ITEMS_PROMPT), "好的！账单上有什么？");
+    map.insert(("zh", DEBT_EQUAL_INSTRUCTIONS), "像这样输入所有分摊者的 Telegram 用户名：\n\n@username__1\n@username__2\n@username__3\n...\n\n⭐️ 如果付款人也要分摊，记得把他也加上！");
+    map.insert(("zh", DEBT_EQUAL_PICKER), "点选所有分摊者，然后点 Done！你也可以直接输入他们的用户名。\n\n⭐️ 如果付款人也要分摊，记得把他也加上！");
+    map.insert(("zh", DEBT_EXACT_INSTRUCTIONS), "像这样输入 Telegram 用户名和确切金额：\n\n@username__1 amount1\n@username__2 amount2\n@username__3 amount3\n...\n\n金额也可以是加法，例如 @username__1 12.50+3.20，或者是份额，例如 @username__2 45/3。\n\n⭐️ 如果有剩余的金额，我会当作是付款人的！");
+    map.insert(("zh", DEBT_RATIO_INSTRUCTIONS), "像这样输入 Telegram 用户名和比例：\n\n@username__1 portion1\n@username__2 portion2\n@username__3 portion3\n...\n\n⭐️ 任何正数都可以，整数或小数都行！");
+    map.insert(("zh", DEBT_PERCENT_INSTRUCTIONS), "像这样输入 Telegram 用户名和百分比：\n\n@username__1 percent1\n@username__2 percent2\n@username__3 percent3\n...\n\n⭐️ 百分比加起来应该是 100，例如 @alice 40 和 @bob 60！");
+    map.insert(("zh", DEBT_ADJUSTED_INSTRUCTIONS), "像这样输入 Telegram 用户名，每人可以加上调整金额：\n\n@username__1 +amount1\n@username__2 -amount2\n@username__3\n...\n\n⭐️ 剩下的金额我会平均分给所有人！");
+    map.insert(("zh", DEBT_ITEMIZED_INSTRUCTIONS), "每行输入一个项目，加上金额和分摊它的 Telegram 用户名，像这样：\n\npizza 18.50 @username__1 @username__2\nbeer 6 @username__3\ntax 2.45\ntip 10%\n...\n\n⭐️ 没有用户名的行，例如税和小费，会按每个人消费的比例由所有人分摊！");
+    map.insert(
+        ("zh", EDIT_DATE_PROMPT),
+        "目前日期：{date}\n\n这笔付款是什么时候发生的？",
+    );
+    map.insert(
+        ("zh", EDIT_DUE_DATE_PROMPT),
+        "目前还款日期：{date}\n\n应该在什么时候之前还款？⏰",
+    );
+    map.insert(
+        ("zh", EDIT_TOTAL_PROMPT),
+        "目前总金额：{total}\n\n总金额应该是多少？",
+    );
+    map.insert(
+        ("zh", EDIT_CURRENCY_PROMPT),
+        "目前货币：{currency}\n\n货币应该是什么？金额会保持不变。",
+    );
+    map.insert(
+        ("zh", EDIT_SPLIT_PROMPT),
+        "目前分摊：\n{split}\n我们要怎么分摊？",
+    );
+    map.insert(("zh", PAY_BACK_CREDITORS_PROMPT), "没问题！🙌 这是你目前欠钱的人。点选所有你已经全额还清的人，然后点 Done！\n\n还的金额不一样？点 Enter Manually，或者直接告诉我还给谁、还了多少。");
+    map.insert(("zh", PAY_BACK_PROMPT), "好的！你还钱给谁，还了多少？");
+    map.insert(
+        ("zh", PAY_BACK_CURRENCY_QUESTION),
+        "好的！你用什么货币付款？",
+    );
+    map.insert(
+        ("zh", PAY_BACK_CURRENCY_PROMPT),
+        "{currency}，太好了！你还钱给谁，还了多少？",
+    );
+    map.insert(("zh", PAY_BACK_INSTRUCTIONS), "像这样输入 Telegram 用户名和确切金额：\n\n@username__1 amount1\n@username__2 amount2\n@username__3 amount3\n...\n\n⭐️ 用不止一种货币还款？在金额后面加上货币，例如 @username__1 amount1 USD！\n\n");
+    map.insert(
+        ("zh", SETTINGS_TIME_ZONE_PROMPT),
+        "🕔 时区：{time_zone}\n\n你的时区在哪个地区？你也可以直接输入城市名称。",
+    );
+    map.insert(("zh", SETTINGS_CURRENCY_PROMPT), "你想把默认货币设为什么？");
+    map.insert(
+        ("zh", STATEMENT_INSTRUCTIONS),
+        "⭐️ 我也可以用下面的其他货币/格式来显示！",
+    );
+    map.insert(
+        ("zh", ADMIN_ONLY),
+        "🔒 抱歉，只有这个群的管理员才能编辑或删除付款，或者更改我的设置！",
+    );
+
+    map.insert(("zh", PRIVATE_PAYMENT), "🔒 私密付款");
+    map.insert(("zh", UNKNOWN_CURRENCY), "🥺 抱歉，我不认识这个货币！");
+    map.insert(
+        ("zh", AMOUNT_TOO_LARGE),
+        "糟糕！🥺 这个数字太大了，我处理不了！",
+    );
+    map.insert(
+        ("zh", AMOUNTS_CURRENCY_MISMATCH),
+        "糟糕！🥺 {first} 和 {second} 的金额不能相加。",
+    );
+    map.insert(("zh", MONEY_OVERFLOW), "这个数字太大了，我处理不了！");
+    map.insert(
+        ("zh", MONEY_CURRENCY_MISMATCH),
+        "{first} 和 {second} 的金额不能相加。",
+    );
+    map.insert(
+        ("zh", INVALID_USERNAME),
+        "糟糕！❌ 请给我一个有效的用户名！",
+    );
+    map.insert(("zh", DESCRIPTION_MISSING), "糟糕！❌ 请给我一个描述！");
+    map.insert(
+        ("zh", DESCRIPTION_TOO_LONG),
+        "糟糕！❌ 描述请不要超过 {max} 个字符！",
+    );
+    map.insert(
+        ("zh", TOO_MANY_DEBTORS),
+        "糟糕！❌ 一笔付款最多只能由 {max} 个人分摊！",
+    );
+    map.insert(
+        ("zh", TOO_MANY_PAYMENTS),
+        "糟糕！❌ 这个群的付款已达到 {max} 笔的上限！请先 /archive 或删除一些较早的付款。",
+    );
+    map.insert(("zh", INVALID_NUMBER), "糟糕！❌ 请给我一个有效的数字！");
+    map.insert(
+        ("zh", NO_DECIMAL_PLACES),
+        "糟糕！❌ {currency} 没有小数位！请给我一个整数。",
+    );
+    map.insert(
+        ("zh", TOO_MANY_DECIMAL_PLACES),
+        "糟糕！❌ 金额最多只能有 {decimals} 位小数！",
+    );
+    map.insert(("zh", NOT_POSITIVE), "糟糕！❌ 请给我一个正数！");
+    map.insert(
+        ("zh", EXPRESSION_TOO_LONG),
+        "糟糕！❌ 每个金额请不要超过 {max} 个字符！",
+    );
+    map.insert(
+        ("zh", EXPRESSION_UNRECOGNIZED),
+        "糟糕！❌ 我看不懂金额 {amount}！请只使用数字、+ - * / 和括号。",
+    );
+    map.insert(("zh", DIVIDE_BY_ZERO), "糟糕！❌ 我不能除以零！");
+    map.insert(
+        ("zh", UNCLOSED_BRACKET),
+        "糟糕！❌ 请把金额里的每个括号都闭合！",
+    );
+    map.insert(
+        ("zh", CURRENCY_NOT_ALLOWED),
+        "🥺 抱歉，这个群只使用这些货币：{currencies}！",
+    );
+    map.insert(
+        ("zh", FORMAT_UNRECOGNIZED),
+        "糟糕！❌ 我看不懂… 请使用以下格式！",
+    );
+    map.insert(
+        ("zh", SURCHARGE_MISSING_AMOUNT),
+        "糟糕！❌ 每个 + 后面请加上金额，例如 +10% tip 或 +8 tax！",
+    );
+    map.insert(
+        ("zh", SURCHARGE_UNRECOGNIZED),
+        "糟糕！❌ 我看不懂 {description} 的金额！",
+    );
+    map.insert(
+        ("zh", RATE_UNRECOGNIZED),
+        "糟糕！❌ 我看不懂这个汇率！请把它写在 @ 后面，例如 50 USD @ 1.37。",
+    );
+    map.insert(("zh", USERNAMES_MISSING), "糟糕！❌ 请至少给我一个用户名！");
+    map.insert(("zh", TOTAL_MISSING), "糟糕！❌ 没有提供总金额。");
+    map.insert(("zh", CURRENCY_MISSING), "糟糕！❌ 没有提供货币。");
+    map.insert(("zh", PAYER_MISSING), "糟糕！❌ 没有提供付款人。");
+    map.insert(
+        ("zh", AMOUNTS_EXCEED_TOTAL),
+        "糟糕！❌ 你给的金额超过了付款总额！",
+    );
+    map.insert(
+        ("zh", PERCENTAGES_NOT_HUNDRED),
+        "糟糕！❌ 你给的百分比加起来是 {sum}%，但应该是 100%！",
+    );
+    map.insert(
+        ("zh", ITEM_AMOUNT_MISSING),
+        "糟糕！❌ 我找不到“{item}”的金额！",
+    );
+    map.insert(
+        ("zh", ITEM_PERCENT_WITH_USERS),
+        "糟糕！❌ 百分比只能用于税和小费，而且不能带用户名！",
+    );
+    map.insert(("zh", ITEMS_MISSING), "糟糕！❌ 请至少给我一个项目！");
+    map.insert(
+        ("zh", ITEMS_EXCEED_TOTAL),
+        "糟糕！❌ 你给的项目金额超过了付款总额！",
+    );
+    map.insert(
+        ("zh", ITEMS_WITHOUT_USERS),
+        "糟糕！❌ 请至少给我一个带用户名的项目！",
+    );
+    map.insert(
+        ("zh", ADJUSTMENTS_EXCEED_TOTAL),
+        "糟糕！❌ 你给的调整金额超过了付款总额！",
+    );
+    map.insert(
+        ("zh", ADJUSTMENTS_NEGATIVE),
+        "糟糕！❌ 有人最后欠的是负数！请检查一下调整金额。",
+    );
+    map.insert(("zh", PAY_BACK_SELF), "糟糕！❌ 你不能还钱给自己！");
+    map.insert(
+        ("zh", TIME_ZONE_UNRECOGNIZED),
+        "🥺 抱歉，我看不懂这个时区！",
+    );
+    map.insert(
+        ("zh", TIME_UNRECOGNIZED),
+        "🥺 抱歉，我看不懂这个时间！请使用 24 小时制，例如 18:00。",
+    );
+    map.insert(
+        ("zh", REMINDER_DAYS_INVALID),
+        "🥺 抱歉，我需要一个整数天数，例如 3。",
+    );
+    map.insert(
+        ("zh", RETENTION_MONTHS_INVALID),
+        "🥺 抱歉，我需要一个整数月数，例如 12。",
+    );
+    map.insert(("zh", BALANCES_HEADER), "✨ 当当！这是更新后的余额！\n\n");
+    map.insert(
+        ("zh", BALANCES_HEADER_CURRENCY),
+        "✨ 当当！这是更新后的 {currency} 余额！\n\n",
+    );
+    map.insert(
+        ("zh", BALANCES_HEADER_CONVERTED),
+        "✨ 当当！这是更新后的余额，全部已转换为 {currency}！\n\n",
+    );
+    map.insert(("zh", RATE_AGE_NOW), "刚刚");
+    map.insert(("zh", RATE_AGE_MINUTES), "{count} 分钟前");
+    map.insert(("zh", RATE_AGE_HOURS), "{count} 小时前");
+    map.insert(("zh", RATE_AGE_DAY), "1 天前");
+    map.insert(("zh", RATE_AGE_DAYS), "{count} 天前");
+    map.insert(("zh", RATES_AS_OF), "💱 汇率截至 {date}，{age}。");
+    map.insert(("zh", RATES_AGE), "💱 汇率更新于{age}。");
+    map.insert(("zh", BALANCE_DEBT), "{debtor} 欠 {creditor}：{amount}");
+    map.insert(("zh", BALANCES_NONE), "没有未结清的余额！🥳\n");
+    map.insert(("zh", PAYMENT_RATE), "汇率：1 {from} = {rate} {to}");
+    map.insert(("zh", PAYMENT_TYPE_TRANSFER), "类型：转账 🔁");
+    map.insert(("zh", PAYMENT_TYPE_ADJUSTMENT), "类型：调整 🛠️");
+    map.insert(("zh", PAYMENT_DETAILS), "{index}. {description}\n日期：{date}\n付款人：{payer}\n总金额：{total}{details}\n分摊：\n{split}");
+    map.insert(("zh", PAYMENT_CONFIRMED_BY), "已确认：{count}/{total} 👍");
+    map.insert(("zh", PAYMENT_ADDED_BY), "添加者：{user}，{date}");
+    map.insert(("zh", PAYMENT_EDITED_BY), "编辑者：{user}，{date}");
+    map.insert(
+        ("zh", PAYMENT_LINK_NUMBER),
+        "💳 可以通过 {link} 付款给 {creditor}",
+    );
+    map.insert(("zh", ITEM_EVERYONE), "所有人");
+    map.insert(("zh", ITEMS_SUMMARY), "小计：{subtotal}\n剩余：{remaining}");
+    map.insert(("zh", CONVERT_TO_CURRENCY), "转换为 {currency}");
+
+    map.insert(
+        ("zh", SPLIT_USERS_MISSING),
+        "一笔付款至少需要一个人来分摊。",
+    );
+    map.insert(("zh", DEBTS_NOT_TOTAL), "欠款金额加起来不等于付款总额。");
+    map.insert(
+        ("zh", PAYMENT_USERS_UNKNOWN),
+        "只有本群的成员才能加入付款，而我在这里还不认识 {users}。",
+    );
+    map.insert(("zh", ADJUSTMENT_ZERO), "调整的金额不能为零。");
+    map.insert(
+        ("zh", ADJUSTMENT_USER_UNKNOWN),
+        "@{username} 还没有参与本聊天中的任何付款。",
+    );
+    map.insert(
+        ("zh", ADJUSTMENT_NO_OTHERS),
+        "调整需要聊天中的其他人来平衡。",
+    );
+    map.insert(
+        ("zh", TOPIC_SETTING_UNSUPPORTED),
+        "话题只能设置时区和默认货币",
+    );
+    map.insert(
+        ("zh", DISPLAY_NAME_LENGTH),
+        "名字的长度必须在 1 到 {max} 个字符之间。",
+    );
+
+    map.insert(("zh", NOT_UNDERSTOOD), "糟糕！❌ 我看不懂…");
+    map.insert(("zh", ADJUST_INSTRUCTIONS), "要更正某人的余额，请告诉我他们的用户名、带 + 或 - 号的金额以及原因，例如：\n/adjust @alice +15 USD 忘了算她的出租车费\n\n+ 表示别人欠他们更多，- 表示他们欠别人更多。另一边由聊天中的其他所有人平均分摊。");
+    map.insert(
+        ("zh", ADJUST_RESTRICTED),
+        "🔒 抱歉，只有这个群的管理员才能调整余额！",
+    );
+    map.insert(
+        ("zh", ADJUST_SIGN_MISSING),
+        "糟糕！❌ 请在金额前加上 + 或 -，这样我才知道往哪个方向调整！",
+    );
+    map.insert(
+        ("zh", ADJUST_REASON_MISSING),
+        "糟糕！❌ 请给出调整的原因，让大家知道这是为了什么！",
+    );
+    map.insert(
+        ("zh", ADJUST_DONE),
+        "🛠️ 收到！我已将 {user} 的余额调整了 {amount}，由聊天中的其他所有人分摊。\n原因：{reason}",
+    );
+
+    map.insert(("zh", ALIAS_INSTRUCTIONS), "你可以这样设置名字：\n/alias @username <名字> - 显示某人的名字，而不是他们的用户名\n/alias remove @username - 恢复显示他们的用户名\n\n如果不加其他内容，我会显示这个聊天中的所有名字！");
+    map.insert(("zh", ALIASES_NONE), "这个聊天还没有设置任何名字！");
+    map.insert(("zh", ALIASES_HEADER), "🏷️ 这是我为大家显示的名字！");
+    map.insert(
+        ("zh", ALIAS_REMOVED),
+        "👍 收到！我会重新用用户名显示 {user}。",
+    );
+    map.insert(("zh", ALIAS_NOT_SET), "🤔 {user} 还没有设置名字！");
+    map.insert(
+        ("zh", ALIAS_SET),
+        "🏷️ 收到！从现在起我会把 {user} 显示为 {name}。",
+    );
+
+    map.insert(("zh", OWED_INSTRUCTIONS), "要查看两个人之间的账目，请告诉我他们的用户名，例如：\n/owed @alice @bob\n\n或者只用 /owed @alice 查看你和他们之间的账目！");
+    map.insert(
+        ("zh", OWED_SENDER_UNKNOWN),
+        "🥺 抱歉，没有 Telegram 用户名我认不出你是谁！请给我两个用户名，例如：\n/owed @alice @bob",
+    );
+    map.insert(
+        ("zh", OWED_SAME_USER),
+        "🤔 这是同一个人！请给我两个不同的用户名。",
+    );
+    map.insert(("zh", OWED_SQUARE), "🤝 {first} 和 {second} 之间互不相欠！");
+    map.insert(
+        ("zh", OWED_HEADER),
+        "💸 这是 {first} 和 {second} 之间的账目！",
+    );
+    map.insert(
+        ("zh", OWED_FOOTER),
+        "这里只计算他们两人之间的付款，所以可能和 /balances 不同，后者会简化所有人之间的债务。",
+    );
+    map.insert(("zh", AUDIT_ACTION_ADDED), "添加了");
+    map.insert(("zh", AUDIT_ACTION_DELETED), "删除了");
+    map.insert(("zh", AUDIT_ACTION_RESTORED), "恢复了");
+    map.insert(("zh", AUDIT_ACTION_ADJUSTED), "调整了余额：");
+    map.insert(("zh", AUDIT_ACTION_EDITED), "编辑了");
+    map.insert(("zh", AUDIT_SOMEONE), "有人");
+    map.insert(
+        ("zh", AUDIT_NONE),
+        "还没有任何付款变更！用 /addpayment 添加一笔，我会记录谁改了什么。📋",
+    );
+    map.insert(("zh", AUDIT_HEADER), "📋 这是最近的付款变更！");
+
+    map.insert(("zh", MONTH_FORMAT), "%Y年%-m月");
+    map.insert(("zh", LIMIT_INSTRUCTIONS), "要为某人设置每月消费上限，请告诉我他们的用户名和上限，例如：\n/limit @alice 300 USD\n\n要取消上限，请使用 /limit @alice off\n\n要限制大家每周的总消费，请使用 /limit cap 500 USD，使用 /limit cap off 取消。");
+    map.insert(("zh", LIMITS_NONE), "这个聊天还没有设置任何消费上限！");
+    map.insert(("zh", LIMITS_HEADER), "📊 这是 {month} 的消费上限：");
+    map.insert(("zh", WEEKLY_CAP_STATUS), "所有人的每周上限：{cap}");
+    map.insert(
+        ("zh", LIMIT_SET),
+        "👍 收到！当 {user} 一个月的消费超过 {amount} 时，我会通知大家。",
+    );
+    map.insert(("zh", LIMIT_REMOVED), "👍 收到！{user} 不再有消费上限。");
+    map.insert(
+        ("zh", WEEKLY_CAP_SET),
+        "🧢 收到！一旦这里所有人一周的消费超过 {amount}，付款人需要越过上限才能再添加付款。",
+    );
+    map.insert(("zh", WEEKLY_CAP_REMOVED), "👍 收到！不再有每周上限了。");
+    map.insert(
+        ("zh", LIMIT_CROSSED),
+        "⚠️ 注意！{user} 本月已经消费了 {spent}，超过了每月 {limit} 的上限！",
+    );
+
+    map.insert(("zh", PAYLINK_INSTRUCTIONS), "你可以这样设置收款链接：\n/paylink <链接> - 通过链接收款，例如 https://paypal.me/alice\n/paylink <电话> - 通过电话号码收款，例如 +6591234567\n/paylink off - 删除你的收款链接\n\n链接里可以包含 {amount} 和 {currency}，我会填上欠款的金额！");
+    map.insert(
+        ("zh", PAYLINK_TOO_LONG),
+        "🥺 抱歉，这个链接对我来说太长了！",
+    );
+    map.insert(
+        ("zh", PAYLINK_UNRECOGNIZED),
+        "糟糕！❌ 这看起来不像链接或电话号码…",
+    );
+    map.insert(
+        ("zh", PAYLINK_NO_USERNAME),
+        "🥺 抱歉，我只能为有 Telegram 用户名的人显示收款链接！",
+    );
+    map.insert(("zh", PAYLINK_CURRENT), "💳 你的收款链接是 {link}");
+    map.insert(("zh", PAYLINK_NONE), "你还没有收款链接！");
+    map.insert(("zh", PAYLINK_REMOVED), "👍 收到！我已删除你的收款链接。");
+    map.insert(
+        ("zh", PAYLINK_SET),
+        "💳 收到！每当有人欠你钱时，我会在余额旁显示 {link}，让他们可以马上付款给你。",
+    );
+    map.insert(("zh", SPLIT_GROUP_INSTRUCTIONS), "你可以这样使用分摊小组：\n/group create <名字> @user1 @user2 ... - 创建一个经常一起分摊的小组\n/group delete <名字> - 删除一个分摊小组\n\n如果不加其他内容，我会显示所有已保存的分摊小组！\n\n⭐️ 平均分摊付款时，点一下小组就能一次选中组里的所有人，或者把小组名字和其他用户名一起输入，例如：roommates @user3");
+    map.insert(("zh", SPLIT_GROUPS_NONE), "还没有保存任何分摊小组！");
+    map.insert(("zh", SPLIT_GROUPS_HEADER), "这是已保存的分摊小组！");
+    map.insert(
+        ("zh", SPLIT_GROUPS_FOOTER),
+        "平均分摊付款时，点一下小组或输入它的名字，就能把组里的所有人都加进来！",
+    );
+    map.insert(
+        ("zh", SPLIT_GROUP_NAME_TOO_LONG),
+        "糟糕！❌ 小组名字请不要超过 {max} 个字符！",
+    );
+    map.insert(
+        ("zh", SPLIT_GROUP_NAME_INVALID),
+        "糟糕！❌ 小组名字只能包含字母、数字和下划线，并且必须以字母开头！",
+    );
+    map.insert(
+        ("zh", SPLIT_GROUP_MEMBERS_MISSING),
+        "🤔 小组里有谁？请这样告诉我：/group create roommates @user1 @user2",
+    );
+    map.insert(
+        ("zh", SPLIT_GROUPS_FULL),
+        "🥺 抱歉，一个聊天最多只能有 {max} 个分摊小组！请先用 /group delete <名字> 删除一个。",
+    );
+    map.insert(("zh", SPLIT_GROUP_SAVED), "👥 已保存！小组 {name} 的成员是 {members}。\n\n平均分摊付款时，点一下它或输入 {name}，就能把组里的所有人都加进来！");
+    map.insert(("zh", SPLIT_GROUP_DELETED), "🗑️ 分摊小组 {name} 已删除！");
+    map.insert(
+        ("zh", SPLIT_GROUP_NOT_FOUND),
+        "🥺 抱歉，我找不到分摊小组 {name}！",
+    );
+    map.insert(("zh", COMMAND_UNKNOWN), "🥺 抱歉，我不懂这个！");
+
+    map.insert(("zh", RESTORE_INSTRUCTIONS), "要恢复备份，请用 /restore 回复我发送的备份文件。在做任何更改之前，我会先给你看里面的内容！");
+    map.insert(
+        ("zh", BACKUP_TOO_LARGE),
+        "❌ 这个文件太大了，不可能是备份！",
+    );
+    map.insert(
+        ("zh", BACKUP_DOWNLOAD_FAILED),
+        "❌ 抱歉，我无法下载备份文件！请稍后再试。",
+    );
+    map.insert(("zh", BACKUP_INVALID), "❌ 这个文件看起来不像是我的备份！");
+    map.insert(
+        ("zh", BACKUP_VERSION_MISMATCH),
+        "❌ 这个备份来自不同版本的 PayScribe（版本 {version}），所以我无法恢复它！",
+    );
+    map.insert(
+        ("zh", BACKUP_SUMMARY),
+        "用户：{users}\n付款：{payments}\n旅行：{trips}\n设置：{settings}",
+    );
+    map.insert(
+        ("zh", BACKUP_FAILED),
+        "❓ 嗯，备份时出了点问题！请稍后再试。",
+    );
+    map.insert(("zh", BACKUP_CAPTION), "🗄️ 这是这个聊天所有内容的备份！");
+    map.insert(
+        ("zh", RESTORE_RESTRICTED),
+        "🔒 只有这个群的管理员才能恢复备份！",
+    );
+    map.insert(("zh", RESTORE_PREVIEW), "🗄️ 这个备份里有：");
+    map.insert(("zh", RESTORE_WARNING), "⚠️ 恢复它会替换这个聊天当前所有的付款、余额、旅行和设置！如果你确定，请再次用 /restore confirm 回复备份文件");
+    map.insert(
+        ("zh", RESTORE_DONE),
+        "✅ 完成！我已经从备份恢复了这个聊天。",
+    );
+
+    map.insert(("zh", IMPORT_AMOUNT_TOO_LARGE), "\"{amount}\" 金额太大");
+    map.insert(("zh", IMPORT_AMOUNT_INVALID), "\"{amount}\" 不是金额");
+    map.insert(
+        ("zh", IMPORT_AMOUNT_DECIMALS),
+        "\"{amount}\" 的小数位超过了 {currency} 的 {decimals} 位",
+    );
+    map.insert(
+        ("zh", IMPORT_CURRENCY_UNKNOWN),
+        "\"{currency}\" 不是我认识的货币",
+    );
+    map.insert(("zh", IMPORT_DATE_INVALID), "\"{date}\" 不是日期");
+    map.insert(("zh", IMPORT_PAYERS_MULTIPLE), "它由不止一个人付款");
+    map.insert(("zh", IMPORT_PAYER_MISSING), "没有人付款");
+    map.insert(("zh", IMPORT_AMOUNTS_MISMATCH), "金额加起来不等于费用");
+    map.insert(("zh", IMPORT_DEBTORS_MISSING), "没有人可以分摊");
+    map.insert(("zh", IMPORT_FILE_EMPTY), "这个文件是空的！");
+    map.insert(
+        ("zh", IMPORT_COLUMNS_MISSING),
+        "我在这个文件里找不到描述、付款人、金额和分摊这几列！",
+    );
+    map.insert(
+        ("zh", IMPORT_CURRENCY_NOT_ALLOWED),
+        "这个聊天不使用 {currency}",
+    );
+    map.insert(("zh", IMPORT_AMOUNT_NOT_POSITIVE), "金额必须大于 0");
+    map.insert(("zh", IMPORT_INSTRUCTIONS), "要导入付款，请发给我一个 CSV 文件，然后用 /import 回复它。在添加任何内容之前，我会先给你看里面有什么！\n\n我可以读取 Splitwise 的导出文件，只要把每个人的名字改成他们的 Telegram 用户名。其他文件需要这些列：Description、Paid By、Amount 和 Split With，Date 和 Currency 可选。Split With 列出平均分摊金额的用户名，例如：@user1 @user2");
+    map.insert(("zh", IMPORT_TOO_LARGE), "❌ 这个文件太大了，我无法导入！");
+    map.insert(
+        ("zh", IMPORT_DOWNLOAD_FAILED),
+        "❌ 抱歉，我无法下载这个文件！请稍后再试。",
+    );
+    map.insert(("zh", IMPORT_NOT_CSV), "❌ 这个文件看起来不像 CSV 文件！");
+    map.insert(("zh", IMPORT_FILE_ERROR), "❌ {error}");
+    map.insert(("zh", IMPORT_ROW_ERROR), "第 {row} 行：{error}");
+    map.insert(("zh", IMPORT_MORE), "…还有 {count} 个");
+    map.insert(
+        ("zh", IMPORT_ROWS_UNREADABLE),
+        "糟糕！❌ 这个文件里有些行我读不懂：\n\n{errors}\n请修正后再试一次！",
+    );
+    map.insert(("zh", IMPORT_PAYMENTS_NONE), "🤔 这个文件里没有付款！");
+    map.insert(
+        ("zh", IMPORT_PAYMENT_LINE),
+        "{description}：{amount}，由 @{payer} 付款，与 @{users} 分摊",
+    );
+    map.insert(
+        ("zh", IMPORT_NO_USERNAME),
+        "🥺 抱歉，我只能为有 Telegram 用户名的人导入付款！",
+    );
+    map.insert(("zh", IMPORT_PREVIEW_ONE), "📥 我在这个文件里找到了 1 笔付款！\n\n{preview}\n如果一切正确，请再次用 /import confirm 回复这个文件");
+    map.insert(("zh", IMPORT_PREVIEW), "📥 我在这个文件里找到了 {count} 笔付款！\n\n{preview}\n如果一切正确，请再次用 /import confirm 回复这个文件");
+    map.insert(("zh", IMPORT_OVER_LIMIT), "糟糕！❌ 导入这些付款会让这个聊天超过 {max} 笔付款的上限！请先 /archive 或删除一些较早的付款。");
+    map.insert(("zh", IMPORT_PROGRESS), "⏳ 正在导入付款… {done}/{count}");
+    map.insert(
+        ("zh", IMPORT_DONE_ONE),
+        "✅ 完成！我已导入 1 笔付款。用 /viewpayments 查看吧！",
+    );
+    map.insert(
+        ("zh", IMPORT_DONE),
+        "✅ 完成！我已导入 {count} 笔付款。用 /viewpayments 查看吧！",
+    );
+
+    map.insert(("zh", BUTTON_APPROVE), "批准");
+    map.insert(("zh", BUTTON_DISPUTE), "异议");
+    map.insert(("zh", BUTTON_CONFIRM_PAYER), "✅ 以付款人身份确认");
+    map.insert(("zh", BUTTON_CONFIRM_DEBTOR), "✅ 以欠款人身份确认");
+    map.insert(
+        ("zh", DUAL_ENTRY_NEEDED),
+        "🤝 这笔付款需要双方确认后我才会添加！",
+    );
+    map.insert(
+        ("zh", DUAL_ENTRY_REQUEST),
+        "{payer}，请以付款人身份确认；{debtors}，能请你们其中一位以欠款人身份确认吗？",
+    );
+    map.insert(("zh", APPROVAL_NEEDED), "🔔 这笔付款需要批准后我才会添加！");
+    map.insert(
+        ("zh", APPROVAL_REQUEST),
+        "{approvers}，能请你们其中一位批准或提出异议吗？",
+    );
+    map.insert(
+        ("zh", APPROVAL_RESOLVED),
+        "这笔付款已经被批准或提出异议了！",
+    );
+    map.insert(
+        ("zh", APPROVAL_NOT_ALLOWED),
+        "🚫 只有这笔付款涉及的其他人才能批准或提出异议！",
+    );
+    map.insert(("zh", APPROVED_BY), "✅ 已由 {user} 批准！付款已添加。");
+    map.insert(
+        ("zh", DISPUTED_BY),
+        "❌ {user} 提出了异议！我已放弃这笔付款，没有做任何更改。",
+    );
+    map.insert(
+        ("zh", ADD_PAYMENT_FAILED),
+        "⁉️ 糟糕！出错了！🥺 很抱歉，我现在无法添加这笔付款。请稍后再试！",
+    );
+    map.insert(
+        ("zh", DISCARD_PAYMENT_FAILED),
+        "⁉️ 糟糕！出错了！🥺 很抱歉，我现在无法放弃这笔付款。请稍后再试！",
+    );
+    map.insert(
+        ("zh", CONFIRM_PAYMENT_FAILED),
+        "⁉️ 糟糕！出错了！🥺 很抱歉，我现在无法确认这笔付款。请稍后再试！",
+    );
+    map.insert(
+        ("zh", DELETE_PAYMENT_FAILED),
+        "⁉️ 糟糕！出错了！🥺 很抱歉，我现在无法删除这笔付款。请稍后再试！",
+    );
+    map.insert(
+        ("zh", EDIT_PAYMENT_FAILED),
+        "⁉️ 糟糕！出错了！🥺 很抱歉，我现在无法编辑这笔付款。请稍后再试！",
+    );
+    map.insert(
+        ("zh", UNDO_SPLIT_FAILED),
+        "⁉️ 糟糕！出错了！🥺 很抱歉，我现在无法撤销这次分摊。请稍后再试！",
+    );
+    map.insert(
+        ("zh", CONFIRMATION_RESOLVED),
+        "这笔付款已经被确认或提出异议了！",
+    );
+    map.insert(
+        ("zh", CONFIRM_PAYER_NOT_ALLOWED),
+        "🚫 只有付款人才能以付款人身份确认这笔付款！",
+    );
+    map.insert(
+        ("zh", CONFIRM_DEBTOR_NOT_ALLOWED),
+        "🚫 只有分摊中的其他人才能以欠款人身份确认这笔付款！",
+    );
+    map.insert(("zh", CONFIRMED_BY_PAYER), "☑️ {user} 已以付款人身份确认！");
+    map.insert(
+        ("zh", CONFIRMED_BY_DEBTOR),
+        "☑️ {user} 已以欠款人身份确认！",
+    );
+    map.insert(
+        ("zh", CONFIRMED_ADDED_PAYER),
+        "✅ {user} 已以付款人身份确认！付款已添加。",
+    );
+    map.insert(
+        ("zh", CONFIRMED_ADDED_DEBTOR),
+        "✅ {user} 已以欠款人身份确认！付款已添加。",
+    );
+
+    map.insert(("zh", BUTTON_UNDO), "↩️ 撤销");
+    map.insert(("zh", SPLIT_DESCRIPTION_DEFAULT), "快速分摊");
+    map.insert(("zh", SPLIT_INSTRUCTIONS), "请告诉我金额，还可以加上货币、描述以及和谁分摊，例如：\n\n/split 60 USD 晚餐\n/split 45.50 出租车 @alice @bob\n\n⭐️ 如果没有提到任何人，我会和这个聊天里我认识的所有人平均分摊！");
+    map.insert(
+        ("zh", SPLIT_NO_USERNAME),
+        "🥺 抱歉，我只能为有 Telegram 用户名的人分摊付款！",
+    );
+    map.insert(
+        ("zh", SPLIT_NO_OTHERS),
+        "🥺 抱歉，还没有其他人可以分摊！请提到和谁分摊，例如 /split 60 USD 晚餐 @alice",
+    );
+    map.insert(
+        ("zh", SPLIT_OVERVIEW),
+        "{description}：{amount}，由 {payer} 付款，与 {users} 平均分摊",
+    );
+    map.insert(("zh", SPLIT_DONE), "⚡ 分摊完成！");
+    map.insert(("zh", SPLIT_ALREADY_DELETED), "这笔付款已经被删除了！");
+    map.insert(
+        ("zh", SPLIT_UNDO_NOT_ALLOWED),
+        "🚫 只有付款的人才能撤销这次分摊！",
+    );
+    map.insert(("zh", SPLIT_UNDONE), "↩️ 已撤销！我已删除这笔付款。");
+
+    map.insert(("zh", BUTTON_RECORD), "✅ 记录");
+    map.insert(("zh", BUTTON_DISMISS), "✖️ 忽略");
+    map.insert(("zh", DETECT_SUGGESTION), "💡 听起来像是一笔付款！要我记录下来吗？\n\n{description}：{amount}，由 {payer} 付款，与 {users} 平均分摊");
+    map.insert(("zh", DETECT_RESOLVED), "这笔付款已经被记录或忽略了！");
+    map.insert(
+        ("zh", DETECT_NOT_ALLOWED),
+        "🚫 只有付款的人才能记录或忽略这笔付款！",
+    );
+    map.insert(("zh", DETECT_DISMISSED), "✖️ 已忽略！我不会记录这笔付款。");
+    map.insert(("zh", DETECT_RECORDED), "✅ 已记录！付款已添加。");
+
+    map.insert(
+        ("zh", DELETE_PAYMENT_CANCELLED),
+        "好的！我已取消删除付款。没有做任何更改！🌟",
+    );
+    map.insert(
+        ("zh", DELETE_PAYMENT_REPEATED),
+        "🚫 哎呀！你好像已经在删除一笔付款了！请先完成或 {cancel}，再开始新的操作。",
+    );
+    map.insert(
+        ("zh", DELETE_PAYMENT_BLOCKED),
+        "🚫 哎呀！你好像正在删除一笔付款！请先完成或 {cancel}，再开始新的操作。",
+    );
+    map.insert(
+        ("zh", DELETE_PAYMENT_NO_VIEW),
+        "糟糕！❌ 抱歉，请先 {view} 再删除！",
+    );
+    map.insert(
+        ("zh", DELETE_PAYMENTS_PROMPT),
+        "你真的、真的要 🗑 删除这 {count} 笔付款吗？我没法撤销这个操作… 🫢",
+    );
+    map.insert(
+        ("zh", DELETE_PAYMENT_PROMPT),
+        "你真的、真的要 🗑 删除这笔付款吗？我没法撤销这个操作… 🫢",
+    );
+    map.insert(("zh", PAYMENTS_DELETED), "🎉 耶！付款已删除！🎉");
+    map.insert(("zh", PAYMENT_DELETED), "🎉 耶！付款已删除！🎉");
+    map.insert(("zh", PAYMENTS_DELETED_SUMMARY), "🗑️ 已删除 {count} 笔付款");
+    map.insert(("zh", PAYMENT_DELETED_SUMMARY), "🗑️ 付款已删除");
+    map.insert(
+        ("zh", DELETED_RESTORE_HINT),
+        "♻️ 改变主意了？你可以在 {days} 天内用 /trash 恢复已删除的付款。",
+    );
+
+    map.insert(("zh", ONBOARDING_PROGRESS), "第 {step} 步，共 {count} 步");
+    map.insert(
+        ("zh", ONBOARDING_TIME_ZONE),
+        "🕔 {progress} — 时区\n\n我用 {time_zone} 显示付款的日期和时间。你想更改时区吗？",
+    );
+    map.insert(
+        ("zh", ONBOARDING_NO_DEFAULT_CURRENCY),
+        "还没有默认货币，所以没有货币的付款只是数字。",
+    );
+    map.insert(
+        ("zh", ONBOARDING_DEFAULT_CURRENCY_INFO),
+        "没有货币的付款使用 {currency}。",
+    );
+    map.insert(
+        ("zh", ONBOARDING_DEFAULT_CURRENCY),
+        "💵 {progress} — 默认货币\n\n{info} 你想设置默认货币吗？",
+    );
+    map.insert(
+        ("zh", ONBOARDING_CONVERSION_ON),
+        "货币转换已开启。你想关闭它吗？",
+    );
+    map.insert(
+        ("zh", ONBOARDING_CONVERSION_LATER),
+        "设置默认货币后，我可以把所有余额和消费转换为默认货币。你可以稍后用 /settings 开启！",
+    );
+    map.insert(
+        ("zh", ONBOARDING_CONVERSION_OFF),
+        "我可以把所有余额和消费转换为你的默认货币。你想开启吗？",
+    );
+    map.insert(
+        ("zh", ONBOARDING_CONVERSION),
+        "↔️ {progress} — 货币转换\n\n{prompt}",
+    );
+    map.insert(
+        ("zh", ONBOARDING_ERASE_ON),
+        "我只保留最终的更新，并删除我的其他消息。你想让我停止吗？",
+    );
+    map.insert(
+        ("zh", ONBOARDING_ERASE_OFF),
+        "我可以删除我的其他消息，只保留最终的更新，让聊天保持整洁。你想开启吗？",
+    );
+    map.insert(
+        ("zh", ONBOARDING_ERASE),
+        "🚮 {progress} — 删除消息\n\n{prompt}",
+    );
+    map.insert(("zh", ONBOARDING_DONE), "🎉 一切就绪！你可以用 /addpayment 开始添加付款，也可以随时用 /settings 更改这些设置。用 /help 看看我能做的所有事情！");
+    map.insert(("zh", ONBOARDING_WELCOME), "👋 大家好，我是 PayScribe！我会帮你们记录谁付了什么钱，以及谁欠谁。\n\n我们先快速完成几个设置吧！每一项都可以跳过，之后也可以用 /settings 更改。");
+
+    map.insert(
+        ("zh", PAYMENT_CARD_PRIVATE),
+        "🔒 这笔付款是私密的，所以我不能在这里显示它的备注或收据！",
+    );
+    map.insert(
+        ("zh", PAYMENT_CARD_NOT_DISPUTABLE),
+        "🚫 只有参与这笔付款分摊的人（付款人除外）才能提出争议！",
+    );
+    map.insert(("zh", PAYMENT_CARD_NOTE), "备注：{note} 📝");
+    map.insert(("zh", PAYMENT_CARD_RECEIPT), "收据：已附上 🧾");
+    map.insert(
+        ("zh", PAYMENT_CARD),
+        "这是这笔付款的详情！🔎\n{payment}{details}\n\n你想对它做什么？",
+    );
+    map.insert(
+        ("zh", PAYMENT_CARD_CLOSED),
+        "好的！我已关闭这笔付款，没有做任何更改！🌟",
+    );
+    map.insert(
+        ("zh", PAYMENT_CARD_BLOCKED),
+        "🚫 哎呀！你好像正在查看一笔付款！请先返回或 {cancel}，再开始新的操作。",
+    );
+    map.insert(
+        ("zh", PAYMENT_CARD_NOTE_PROMPT),
+        "📝 你想给这笔付款添加什么备注？例如在哪里或怎样付的款。请控制在 {max} 个字符以内！",
+    );
+    map.insert(
+        ("zh", PAYMENT_CARD_RECEIPT_PROMPT),
+        "🧾 请发给我这笔付款的收据照片！",
+    );
+    map.insert(
+        ("zh", PAYMENT_CARD_RECEIPT_CAPTION),
+        "🧾 这是 {description} 的收据！",
+    );
+    map.insert(
+        ("zh", PAYMENT_CARD_NOTE_INVALID),
+        "哎呀！❌ 请给我一个不超过 {max} 个字符的备注！",
+    );
+    map.insert(
+        ("zh", PAYMENT_CARD_RECEIPT_INVALID),
+        "哎呀！❌ 请发给我收据的照片！",
+    );
+
+    map.insert(("zh", IN_CURRENCY), "{currency}：");
+    map.insert(("zh", MONTH_NAME_FORMAT), "%-m月");
+    map.insert(("zh", SPENDINGS_TABLE_NAME), "名字");
+    map.insert(("zh", SPENDINGS_TABLE_CONSUMED), "消费");
+    map.insert(("zh", SPENDINGS_TABLE_FRONTED), "垫付");
+    map.insert(("zh", SPENDINGS_TABLE_NET), "净额");
+    map.insert(("zh", SPENDINGS_PERIOD_NONE), "这段时间没有消费！💤");
+    map.insert(("zh", SPENDINGS_CONSUMPTION_HEADER), "🔥 这是大家在{period}的消费和垫付！\n\n消费是每个人分摊的费用，垫付是他们为大家预先支付的金额。");
+    map.insert(
+        ("zh", FORECAST_OVER_LIMIT),
+        "⚠️ 超出限额 {limit}，多了 {amount}",
+    );
+    map.insert(("zh", FORECAST_WITHIN_LIMIT), "✅ 未超出限额 {limit}");
+    map.insert(
+        ("zh", FORECAST_USER),
+        "{name}\n    目前已花：{spent}\n    预计：{projected}{limit}\n",
+    );
+    map.insert(
+        ("zh", FORECAST_GROUP),
+        "群组目前已花：{spent}\n群组预计：{projected}\n\n{users}",
+    );
+    map.insert(("zh", FORECAST_NONE), "这个月还没有消费！💤");
+    map.insert(
+        ("zh", FORECAST_HEADER),
+        "📈 按照前 {days} 天的日均消费，这是到{month}底的消费预测！",
+    );
+    map.insert(
+        ("zh", SPENDINGS_USER),
+        "{name}\n    总消费：{spent}\n    总垫付：{paid}\n",
+    );
+    map.insert(("zh", SPENDINGS_GROUP_TOTAL), "群组总消费：{amount}\n");
+    map.insert(("zh", SPENDINGS_HEADER), "🔥 这是总消费！");
+    map.insert(
+        ("zh", SPENDINGS_HEADER_CURRENCY),
+        "🔥 这是 {currency} 的总消费！",
+    );
+    map.insert(
+        ("zh", SPENDINGS_HEADER_CONVERTED),
+        "🔥 这是总消费，已全部转换为 {currency}！",
+    );
+    map.insert(("zh", VIEW_BALANCES_HEADER), "📊 这是目前的余额！");
+    map.insert(
+        ("zh", VIEW_BALANCES_HEADER_CURRENCY),
+        "📊 这是目前 {currency} 的余额！",
+    );
+    map.insert(
+        ("zh", VIEW_BALANCES_HEADER_CONVERTED),
+        "📊 这是目前的余额，已全部转换为 {currency}！",
+    );
+    map.insert(("zh", BALANCES_ONLY_OWE), "这些只是你欠别人的债务。");
+    map.insert(("zh", BALANCES_ONLY_OWED), "这些只是别人欠你的债务。");
+    map.insert(
+        ("zh", BALANCES_PAIRWISE),
+        "这些是每笔付款中谁欠谁，没有经过简化。",
+    );
+    map.insert(
+        ("zh", BALANCES_CONVERSION_REFUSED),
+        "⚠️ 目前的汇率已经过时，所以这里按每种货币分别显示余额。",
+    );
+    map.insert(
+        ("zh", BALANCES_OVERDUE),
+        "带有 ⏰ 的粗体债务已经过了还款日期！",
+    );
+    map.insert(
+        ("zh", BALANCES_DISPLAY_RESTRICTED),
+        "🔒 抱歉，只有这个群的管理员才能更改余额的显示方式！",
+    );
+    map.insert(
+        ("zh", BALANCES_NO_USERNAME),
+        "🥺 抱歉，没有 Telegram 用户名的话，我无法知道哪些债务是你的！",
+    );
+    map.insert(("zh", CONVERT_TO_PROMPT), "💱 你想用哪种货币查看余额？请输入 3 个字母的货币代码，例如 EUR 或 JPY，或者在下面选一个！\n\n这只是查看一下，我不会更改任何设置。");
+    map.insert(("zh", CONVERT_TO_CANCELLED), "好的！余额就保持原样吧！🌟");
+    map.insert(
+        ("zh", CURRENCY_CODE_INVALID),
+        "哎呀！❌ 请输入 3 个字母的货币代码，例如 EUR 或 JPY！",
+    );
+    map.insert(
+        ("zh", RATE_CURRENCY_MISSING),
+        "哎呀！❌ 请告诉我这个汇率是哪种货币的，例如 50 USD @ 1.37！",
+    );
+    map.insert(
+        ("zh", RATE_NO_DEFAULT_CURRENCY),
+        "哎呀！❌ 没有可以转换成的默认货币！请先在 /settings 里设置一个。",
+    );
+    map.insert(
+        ("zh", RATE_ALREADY_DEFAULT),
+        "哎呀！❌ {currency} 已经是默认货币了，所以不需要转换！",
+    );
+    map.insert(
+        ("zh", ADD_PAYMENT_NO_USERNAME),
+        "🥺 抱歉，你需要有 Telegram 用户名才能添加付款！",
+    );
+    map.insert(("zh", SETTINGS_ARGUMENTS), "你也可以直接更改设置，像这样：\n\n/settings timezone Singapore\n/settings currency SGD\n/settings currency off\n/settings conversion on\n/settings erase off\n/settings eraseuser on\n/settings archive on\n/settings digest 18:00\n/settings digest off\n/settings reminder 3\n/settings reminder off\n/settings retention 12\n/settings retention off\n/settings approval 100\n/settings approval off\n/settings treasurer @username\n/settings treasurer off\n/settings admin on\n/settings pairwise on\n/settings passive on\n/settings dualentry on\n/settings strict on\n/settings language zh\n/settings number 1.234,56\n/settings format S$1,234.30\n/settings verbosity compact\n/settings rounding payer\n/settings weekstart sunday\n/settings currencies SGD USD\n/settings currencies all\n\n在论坛话题里，你也可以给它设置单独的时区或货币：\n\n/settings topic\n/settings topic timezone Tokyo\n/settings topic currency JPY\n/settings topic reset");
+    map.insert(
+        ("zh", SETTINGS_CURRENCY_NOT_ALLOWED),
+        "🥺 抱歉，这个群只使用这些货币：{currencies}！请从中选一个作为 💵 默认货币。",
+    );
+    map.insert(
+        ("zh", SETTINGS_CURRENCIES_INVALID),
+        "🥺 抱歉，请给我真实的货币代码，例如 SGD 或 USD！",
+    );
+    map.insert(
+        ("zh", SETTINGS_VALUE_MISSING),
+        "🥺 抱歉，这个设置需要一个值！",
+    );
+    map.insert(
+        ("zh", SETTINGS_TOGGLE_INVALID),
+        "🥺 抱歉，这个设置请用 \"on\" 或 \"off\"！",
+    );
+    map.insert(
+        ("zh", SETTINGS_LANGUAGE_UNKNOWN),
+        "🥺 抱歉，我不认识这种语言！",
+    );
+    map.insert(
+        ("zh", SETTINGS_NUMBER_FORMAT_UNKNOWN),
+        "🥺 抱歉，我不认识这种数字格式！请使用 1,234.56 或 1.234,56。",
+    );
+    map.insert(
+        ("zh", SETTINGS_CURRENCY_FORMAT_UNKNOWN),
+        "🥺 抱歉，我不认识这种货币格式！请使用 12.30 SGD、1,234.30 SGD、S$12.30 或 S$1,234.30。",
+    );
+    map.insert(
+        ("zh", SETTINGS_VERBOSITY_UNKNOWN),
+        "🥺 抱歉，我不认识这种详细程度！请使用 verbose、compact 或 silent。",
+    );
+    map.insert(
+        ("zh", SETTINGS_ROUNDING_UNKNOWN),
+        "🥺 抱歉，我不认识这种取整方式！请使用 payer、first、random 或 roundrobin。",
+    );
+    map.insert(
+        ("zh", SETTINGS_WEEK_START_INVALID),
+        "🥺 抱歉，每周只能从星期一或星期日开始！",
+    );
+    map.insert(("zh", SETTINGS_UNKNOWN), "🥺 抱歉，我不认识这个设置！");
+    map.insert(("zh", SETTINGS_CANCELLED), "好的！我的设置没有任何更改！🌟");
+    map.insert(("zh", SETTINGS_MENU), "非常乐意！😉 来看看，这些是你们可以自定义的 ⚙️ 设置。你们想查看或编辑哪一项？\n\n💵 *默认货币* — 未填写货币时使用的货币\n\n↔️ *货币转换* — 计算余额和开销时转换货币\n\n🚮 *删除消息* — 只保留最终的更新，自动删除我的其他消息，也可以选择连你们的指令和回复一起删除\n\n*🕔 时区* — 显示日期和时间所用的时区\n\n📦 *每月归档* — 每月初归档上个月的付款，并结转余额\n\n📰 *每周摘要* — 每周日发布余额和本周开销的摘要\n\n⏰ *债务提醒* — 每隔几天提醒大家未结清的余额\n\n🔏 *付款审批* — 超过设定金额的付款需要其他相关的人批准\n\n🏦 *司库* — 让大家都和同一个人结算，而不是互相结算\n\n🛡️ *仅限管理员* — 只让管理员编辑或删除付款，以及更改我的设置\n\n🌐 *语言* — 我回复所用的语言\n\n🔢 *数字格式* — 读取金额时的千位和小数分隔符\n\n💱 *货币格式* — 显示金额时的货币符号和千位分隔符\n\n💬 *详细程度* — 添加、编辑或删除付款后我说多少\n\n🪙 *取整* — 付款无法平均分摊时，谁来承担剩下的零头\n\n📅 *每周起始日* — 摘要、开销上限和每周报告所用的一周第一天");
+    map.insert(
+        ("zh", SETTINGS_REPEATED),
+        "🚫 哎呀！你好像已经在更改我的设置了！请先完成或 {command}，再开始新的操作。",
+    );
+    map.insert(
+        ("zh", SETTINGS_BLOCKED),
+        "🚫 哎呀！你好像正在更改我的设置！请先完成或 {command}，再开始新的操作。",
+    );
+    map.insert(("zh", SETTINGS_ENABLED), "已开启 ✅");
+    map.insert(("zh", SETTINGS_DISABLED), "已关闭 ❌");
+    map.insert(
+        ("zh", SETTINGS_TIME_ZONE_MENU),
+        "🕔 时区：{time_zone}\n\n要编辑这个群的时区吗？",
+    );
+    map.insert(
+        ("zh", SETTINGS_TIME_ZONE_UPDATED),
+        "没问题！我已经把 🕔 时区设为 {time_zone}！",
+    );
+    map.insert(("zh", SETTINGS_CURRENCY_NOT_SET), "💵 默认货币未设置。");
+    map.insert(("zh", SETTINGS_CURRENCY_CURRENT), "💵 默认货币：{currency}");
+    map.insert(("zh", SETTINGS_CURRENCY_MENU), "要编辑这个群的默认货币吗？");
+    map.insert(
+        ("zh", SETTINGS_CURRENCY_UPDATED),
+        "没问题！我已经把 💵 默认货币设为 {currency}！",
+    );
+    map.insert(
+        ("zh", SETTINGS_CURRENCY_DISABLED),
+        "没问题！我已经停用了 💵 默认货币！",
+    );
+    map.insert(
+        ("zh", SETTINGS_CONVERSION_STATUS),
+        "↔️ 货币转换目前{status}。",
+    );
+    map.insert(
+        ("zh", SETTINGS_CONVERSION_OFF_PROMPT),
+        "要关闭这个群的货币转换吗？",
+    );
+    map.insert(
+        ("zh", SETTINGS_CONVERSION_ON_PROMPT),
+        "要开启这个群的货币转换吗？",
+    );
+    map.insert(
+        ("zh", SETTINGS_CONVERSION_NO_CURRENCY),
+        "⭐️ 如果想开启货币转换，请先设置默认货币！",
+    );
+    map.insert(
+        ("zh", SETTINGS_CONVERSION_ON),
+        "没问题！我已经开启了 ↔️ 货币转换！",
+    );
+    map.insert(
+        ("zh", SETTINGS_CONVERSION_OFF),
+        "没问题！我已经关闭了 ↔️ 货币转换！",
+    );
+    map.insert(("zh", SETTINGS_ERASE_STATUS), "🚮 删除消息目前{status}。");
+    map.insert(
+        ("zh", SETTINGS_ERASE_ENABLED_ALL),
+        "已开启 ✅，连同你们的指令和回复",
+    );
+    map.insert(
+        ("zh", SETTINGS_ERASE_ENABLED_OWN),
+        "已开启 ✅，只删除我的消息",
+    );
+    map.insert(
+        ("zh", SETTINGS_ERASE_OFF_PROMPT),
+        "要关闭这个群的自动删除消息，还是更改是否也删除你们的指令和回复？",
+    );
+    map.insert(
+        ("zh", SETTINGS_ERASE_ON_PROMPT),
+        "要开启这个群的自动删除消息吗？",
+    );
+    map.insert(
+        ("zh", SETTINGS_ERASE_ON),
+        "没问题！我已经开启了 🚮 删除消息！",
+    );
+    map.insert(
+        ("zh", SETTINGS_ERASE_OFF),
+        "没问题！我已经关闭了 🚮 删除消息！",
+    );
+    map.insert(
+        ("zh", SETTINGS_ERASE_USER_ON),
+        "没问题！开启 🚮 删除消息后，只要我有权限，也会删除你们的指令和回复！",
+    );
+    map.insert(
+        ("zh", SETTINGS_ERASE_USER_OFF),
+        "没问题！我会保留你们的指令和回复，只删除我自己的消息！🚮",
+    );
+    map.insert(("zh", SETTINGS_ARCHIVE_STATUS), "📦 每月归档目前{status}。");
+    map.insert(
+        ("zh", SETTINGS_ARCHIVE_OFF_PROMPT),
+        "要关闭这个群的每月归档吗？",
+    );
+    map.insert(
+        ("zh", SETTINGS_ARCHIVE_ON_PROMPT),
+        "要开启这个群的每月归档吗？",
+    );
+    map.insert(
+        ("zh", SETTINGS_ARCHIVE_ON),
+        "没问题！我已经开启了 📦 每月归档！",
+    );
+    map.insert(
+        ("zh", SETTINGS_ARCHIVE_OFF),
+        "没问题！我已经关闭了 📦 每月归档！",
+    );
+    map.insert(("zh", SETTINGS_DIGEST_STATUS), "📰 每周摘要目前{status}。");
+    map.insert(("zh", SETTINGS_DIGEST_ENABLED), "已开启 ✅，每周日 {time}");
+    map.insert(
+        ("zh", SETTINGS_DIGEST_OFF_PROMPT),
+        "要更改时间，还是关闭这个群的每周摘要？",
+    );
+    map.insert(
+        ("zh", SETTINGS_DIGEST_ON_PROMPT),
+        "要开启这个群的每周摘要吗？",
+    );
+    map.insert(
+        ("zh", SETTINGS_DIGEST_TIME_PROMPT),
+        "📰 每周日几点发布摘要？请使用 24 小时制，例如 18:00。",
+    );
+    map.insert(
+        ("zh", SETTINGS_DIGEST_ON),
+        "没问题！我会在每周日 {time} 发布 📰 每周摘要！",
+    );
+    map.insert(
+        ("zh", SETTINGS_DIGEST_OFF),
+        "没问题！我已经关闭了 📰 每周摘要！",
+    );
+    map.insert(
+        ("zh", SETTINGS_REMINDER_STATUS),
+        "⏰ 债务提醒目前{status}。",
+    );
+    map.insert(("zh", SETTINGS_REMINDER_ENABLED), "已开启 ✅，每 {days} 天");
+    map.insert(
+        ("zh", SETTINGS_REMINDER_OFF_PROMPT),
+        "要更改间隔，还是关闭这个群的债务提醒？",
+    );
+    map.insert(
+        ("zh", SETTINGS_REMINDER_ON_PROMPT),
+        "要开启这个群的债务提醒吗？",
+    );
+    map.insert(
+        ("zh", SETTINGS_REMINDER_DAYS_PROMPT),
+        "⏰ 每隔几天提醒大家未结清的余额？例如：3、7 等。",
+    );
+    map.insert(
+        ("zh", SETTINGS_REMINDER_ON),
+        "没问题！只要还有未结清的余额，我会每 {days} 天发布一次 ⏰ 债务提醒！",
+    );
+    map.insert(
+        ("zh", SETTINGS_REMINDER_OFF),
+        "没问题！我已经关闭了 ⏰ 债务提醒！",
+    );
+    map.insert(
+        ("zh", SETTINGS_RETENTION_ON),
+        "没问题！我会清除超过 {months} 个月的付款，先发送摘要并结转余额！🧹",
+    );
+    map.insert(
+        ("zh", SETTINGS_RETENTION_OFF),
+        "没问题！我已经关闭了 🧹 数据保留期限，所有付款都会保留！",
+    );
+    map.insert(
+        ("zh", SETTINGS_APPROVAL_STATUS),
+        "🔏 付款审批目前{status}。",
+    );
+    map.insert(
+        ("zh", SETTINGS_APPROVAL_ENABLED),
+        "已开启 ✅，适用于超过 {threshold} 的付款",
+    );
+    map.insert(
+        ("zh", SETTINGS_APPROVAL_OFF_PROMPT),
+        "要更改金额，还是关闭这个群的付款审批？",
+    );
+    map.insert(
+        ("zh", SETTINGS_APPROVAL_ON_PROMPT),
+        "要开启这个群的付款审批吗？",
+    );
+    map.insert(("zh", SETTINGS_APPROVAL_THRESHOLD_PROMPT), "🔏 超过多少金额的付款需要审批？例如：100、250.50 等。\n\n⭐️ 这适用于每笔付款的总金额，不论是哪种货币！");
+    map.insert(
+        ("zh", SETTINGS_APPROVAL_ON),
+        "没问题！超过 {threshold} 的付款现在需要 🔏 付款审批！",
+    );
+    map.insert(
+        ("zh", SETTINGS_APPROVAL_OFF),
+        "没问题！我已经关闭了 🔏 付款审批！",
+    );
+    map.insert(("zh", SETTINGS_TREASURER_STATUS), "🏦 司库目前{status}。");
+    map.insert(
+        ("zh", SETTINGS_TREASURER_ENABLED),
+        "已开启 ✅，由 @{treasurer} 担任司库",
+    );
+    map.insert(
+        ("zh", SETTINGS_TREASURER_OFF_PROMPT),
+        "要更换司库，还是让大家重新互相结算？",
+    );
+    map.insert(
+        ("zh", SETTINGS_TREASURER_ON_PROMPT),
+        "要让这个群的大家都和一位司库结算吗？",
+    );
+    map.insert(("zh", SETTINGS_TREASURER_USERNAME_PROMPT), "🏦 谁来担任司库？请输入他们的用户名，例如 @username。\n\n⭐️ 欠钱的人都会付给司库，司库再付给被欠钱的人！");
+    map.insert(
+        ("zh", SETTINGS_TREASURER_ON),
+        "没问题！大家现在都会和 🏦 司库 @{treasurer} 结算！",
+    );
+    map.insert(
+        ("zh", SETTINGS_TREASURER_OFF),
+        "没问题！我已经关闭了 🏦 司库！",
+    );
+    map.insert(
+        ("zh", SETTINGS_ADMIN_ONLY_RESTRICTED),
+        "🔒 抱歉，只有这个群的管理员才能更改谁可以编辑记录！",
+    );
+    map.insert(
+        ("zh", SETTINGS_ADMIN_ONLY_STATUS),
+        "🛡️ 仅限管理员目前{status}。",
+    );
+    map.insert(
+        ("zh", SETTINGS_ADMIN_ONLY_OFF_PROMPT),
+        "要重新让所有人都能编辑或删除付款，以及更改设置吗？",
+    );
+    map.insert(
+        ("zh", SETTINGS_ADMIN_ONLY_ON_PROMPT),
+        "要只让管理员编辑或删除付款，以及更改设置吗？",
+    );
+    map.insert(
+        ("zh", SETTINGS_ADMIN_ONLY_ON),
+        "没问题！我已经开启了 🛡️ 仅限管理员！",
+    );
+    map.insert(
+        ("zh", SETTINGS_ADMIN_ONLY_OFF),
+        "没问题！我已经关闭了 🛡️ 仅限管理员！",
+    );
+    map.insert(
+        ("zh", SETTINGS_PAIRWISE_ON),
+        "没问题！我会按每笔付款显示谁欠谁，不做简化！🔀",
+    );
+    map.insert(
+        ("zh", SETTINGS_PAIRWISE_OFF),
+        "没问题！我会把余额简化成结清所需的最少付款！🔀",
+    );
+    map.insert(
+        ("zh", SETTINGS_PASSIVE_ON),
+        "没问题！当有人说类似 \"paid 30 for lunch with @bob\" 的话时，我会主动提出记录！👂",
+    );
+    map.insert(
+        ("zh", SETTINGS_PASSIVE_OFF),
+        "没问题！我只会在用 /addpayment 要求时记录付款！👂",
+    );
+    map.insert(
+        ("zh", SETTINGS_DUAL_ENTRY_ON),
+        "没问题！新的付款要等付款人和分摊中的某个人都确认后才会添加！🤝",
+    );
+    map.insert(
+        ("zh", SETTINGS_DUAL_ENTRY_OFF),
+        "没问题！新的付款会重新立即添加！🤝",
+    );
+    map.insert(
+        ("zh", SETTINGS_STRICT_ON),
+        "没问题！只有已经在这个群里的人才能参与付款，添加新的人之前我会先问！🚪",
+    );
+    map.insert(
+        ("zh", SETTINGS_STRICT_OFF),
+        "没问题！付款中提到的任何人都会重新被加入群组！🚪",
+    );
+    map.insert(
+        ("zh", SETTINGS_NUMBER_FORMAT_MENU),
+        "🔢 数字格式：{format}\n\n这个群怎么写金额？空格和撇号也一直可以用来分隔千位！",
+    );
+    map.insert(
+        ("zh", SETTINGS_NUMBER_FORMAT_UPDATED),
+        "没问题！从现在起我会读取像 {format} 这样写的金额！🔢",
+    );
+    map.insert(
+        ("zh", SETTINGS_CURRENCY_FORMAT_MENU),
+        "💱 货币格式：{format}\n\n我应该怎么在这个群里显示金额？没有常见符号的货币仍会用代码显示！",
+    );
+    map.insert(
+        ("zh", SETTINGS_CURRENCY_FORMAT_UPDATED),
+        "没问题！从现在起我会像 {format} 这样显示金额！💱",
+    );
+    map.insert(("zh", SETTINGS_VERBOSITY_MENU), "💬 详细程度：{verbosity}\n\n添加、编辑或删除付款时，我应该说多少？\n\n详细 — 完整的详情和余额\n简洁 — 只有一行\n静默 — 只回应一个 👍");
+    map.insert(
+        ("zh", SETTINGS_VERBOSITY_VERBOSE),
+        "没问题！付款有变动时，我会显示完整的详情和余额！💬",
+    );
+    map.insert(
+        ("zh", SETTINGS_VERBOSITY_COMPACT),
+        "没问题！付款有变动时，我只说一行！💬",
+    );
+    map.insert(
+        ("zh", SETTINGS_VERBOSITY_SILENT),
+        "没问题！付款有变动时，我只回应一个 👍！💬",
+    );
+    map.insert(("zh", SETTINGS_ROUNDING_MENU), "🪙 取整：{rounding}\n\n付款无法精确平均分摊到分时，谁来承担剩下的零头？\n\n付款人 — 付钱的人\n前几位 — 分摊中排在前面的人\n随机 — 分摊中随机的人\n轮流 — 大家轮流承担");
+    map.insert(
+        ("zh", SETTINGS_ROUNDING_PAYER),
+        "没问题！从现在起由付款人承担剩下的零头！🪙",
+    );
+    map.insert(
+        ("zh", SETTINGS_ROUNDING_FIRST),
+        "没问题！从现在起由分摊中排在前面的人承担剩下的零头！🪙",
+    );
+    map.insert(
+        ("zh", SETTINGS_ROUNDING_RANDOM),
+        "没问题！从现在起我会把剩下的零头随机分给大家！🪙",
+    );
+    map.insert(
+        ("zh", SETTINGS_ROUNDING_ROUND_ROBIN),
+        "没问题！从现在起我会轮流分配剩下的零头！🪙",
+    );
+    map.insert(("zh", SETTINGS_WEEK_START_MENU), "📅 每周起始日：{week_start}\n\n每周应该从哪一天开始？这会用于每周摘要和每周开销上限。\n\n⭐️ 周和月都按这个群的 🕔 时区计算！");
+    map.insert(
+        ("zh", SETTINGS_WEEK_START_MONDAY),
+        "没问题！从现在起每周从星期一开始！📅",
+    );
+    map.insert(
+        ("zh", SETTINGS_WEEK_START_SUNDAY),
+        "没问题！从现在起每周从星期日开始！📅",
+    );
+    map.insert(
+        ("zh", SETTINGS_CURRENCIES_RESTRICTED),
+        "🔒 抱歉，只有这个群的管理员才能更改可以使用哪些货币！",
+    );
+    map.insert(
+        ("zh", SETTINGS_CURRENCIES_DEFAULT_MISSING),
+        "⭐️ 💵 默认货币是 {currency}，请也允许这个货币，或者先更改默认货币！",
+    );
+    map.insert(
+        ("zh", SETTINGS_CURRENCIES_ALL),
+        "没问题！从现在起这个群可以使用任何货币！💱",
+    );
+    map.insert(
+        ("zh", SETTINGS_CURRENCIES_UPDATED),
+        "没问题！从现在起这个群只能使用这些货币：{currencies}！💱",
+    );
+    map.insert(
+        ("zh", SETTINGS_TOPIC_ONLY),
+        "⭐️ 话题设置只能在论坛群组的话题里更改！",
+    );
+    map.insert(("zh", SETTINGS_TOPIC_INVALID), "🥺 抱歉，话题只能设置 🕔 时区和 💵 默认货币！使用 /settings topic reset 重新跟随群组设置。");
+    map.insert(
+        ("zh", SETTINGS_TOPIC_SUMMARY),
+        "这是这个话题的设置！🗂️\n\n🕔 时区：{time_zone}\n💵 默认货币：{currency}",
+    );
+    map.insert(("zh", SETTINGS_TOPIC_OWN), "{value}（这个话题）");
+    map.insert(("zh", SETTINGS_TOPIC_INHERITED), "{value}（来自群组）");
+    map.insert(("zh", SETTINGS_TOPIC_NONE), "无（来自群组）");
+    map.insert(
+        ("zh", SETTINGS_TOPIC_RESET),
+        "没问题！这个话题会重新跟随群组的设置！🗂️",
+    );
+    map.insert(
+        ("zh", SETTINGS_TOPIC_TIME_ZONE_UPDATED),
+        "没问题！我已经把这个话题的 🕔 时区设为 {time_zone}！",
+    );
+    map.insert(
+        ("zh", SETTINGS_TOPIC_CURRENCY_UPDATED),
+        "没问题！这个话题里没有货币的付款会使用 {currency}！💵",
+    );
+    map.insert(("zh", TRIP_INSTRUCTIONS), "旅行可以这样用：\n/trip start <名称> - 开始或继续一次旅行\n/trip end - 结束当前旅行，并附上报告\n/trip end csv - 同时把这次旅行的所有付款以 CSV 文件发送\n/trip balances <名称> - 查看一次旅行的余额\n/trip spendings <名称> - 查看一次旅行的开销\n\n如果没有提供名称，我会显示当前的旅行！");
+    map.insert(
+        ("zh", TRIP_BALANCES_HEADER),
+        "📊 这是旅行 {trip} 目前的余额！",
+    );
+    map.insert(
+        ("zh", TRIP_SPENDINGS_HEADER),
+        "🔥 这是旅行 {trip} 的总开销！",
+    );
+    map.insert(("zh", TRIP_EXPENSES_NONE), "这次旅行没有开销！");
+    map.insert(("zh", TRIP_REPORT), "🧾 旅行报告：{trip}\n\n🔥 开销\n{spendings}\n\n🏆 最大开销\n{expenses}\n\n💸 结算方案\n{balances}");
+    map.insert(("zh", TRIP_CSV_CAPTION), "📎 这是旅行 {trip} 的所有付款！");
+    map.insert(("zh", TRIP_CURRENT), "🧳 你们正在旅行 {trip} 中！");
+    map.insert(("zh", TRIP_NONE), "🧳 现在没有进行中的旅行！");
+    map.insert(("zh", TRIP_ALL), "所有旅行：{trips}");
+    map.insert(
+        ("zh", TRIP_NAME_MISSING),
+        "🤔 这次旅行叫什么名字？像这样告诉我：/trip start 日本之旅",
+    );
+    map.insert(
+        ("zh", TRIP_ALREADY_STARTED),
+        "🧳 你们已经在旅行 {trip} 中了！",
+    );
+    map.insert(
+        ("zh", TRIP_STILL_ON),
+        "🧳 你们还在旅行 {trip} 中！请先用 /trip end 结束它，再开始新的旅行。",
+    );
+    map.insert(("zh", TRIP_STARTED), "🧳 出发啦！旅行 {trip} 开始了！\n\n我会把所有新的付款记在这次旅行下，同时也计入总账。结束时请用 /trip end！");
+    map.insert(("zh", TRIP_ENDED), "🏁 欢迎回来！旅行 {trip} 已经结束，新的付款只会计入总账。\n\n{report}\n\n你们仍然可以随时查看 /trip balances {trip}！");
+    map.insert(("zh", TRIP_NOT_ON), "❌ 现在没有进行中的旅行！");
+    map.insert(
+        ("zh", TRIP_WHICH),
+        "❌ 现在没有进行中的旅行！想查看哪次旅行？像这样告诉我：/trip {command} 日本之旅",
+    );
+    map.insert(("zh", TRIP_NOT_FOUND), "🥺 抱歉，我找不到旅行 {trip}！");
+    map.insert(("zh", TRIP_UNKNOWN), "🥺 抱歉，我不明白！");
+    map.insert(("zh", ARCHIVE_SUMMARY_ONE), "📦 新的一个月开始了！我已经归档了 {month} 的 1 笔付款，并把余额结转为一条记录。\n\n这是本月的期初余额：\n{balances}");
+    map.insert(("zh", ARCHIVE_SUMMARY), "📦 新的一个月开始了！我已经归档了 {month} 的 {count} 笔付款，并把余额结转为一条记录。\n\n这是本月的期初余额：\n{balances}");
+    map.insert(("zh", DIGEST_SPENDINGS_NONE), "这周没有开销！💤\n");
+    map.insert(("zh", DIGEST_WEEK), "{start} 至 {end}");
+    map.insert(("zh", DIGEST_SUMMARY), "📰 这是你们 {week} 的每周摘要！\n\n✨ 目前的余额：\n{balances}\n🔥 本周开销：\n{spendings}");
+    map.insert(
+        ("zh", ADD_PAYMENT_OVERVIEW),
+        "这是我目前记下的内容！📝\n\n{overview}{suggestion}你要确认这笔记录，还是想做些修改？",
+    );
+    map.insert(
+        ("zh", ADD_PAYMENT_CANCELLED),
+        "好的！我已取消添加付款。没有做任何更改！🌟",
+    );
+    map.insert(
+        ("zh", ADD_PAYMENT_REPEATED),
+        "🚫 哎呀！你好像已经在添加一笔付款了！请先完成或 {cancel}，再开始新的操作。",
+    );
+    map.insert(
+        ("zh", ADD_PAYMENT_BLOCKED),
+        "🚫 哎呀！你好像正在添加一笔付款！请先完成或 {cancel}，再开始新的操作。",
+    );
+    map.insert(
+        ("zh", WEEKLY_CAP_OVERRIDE_NOT_ALLOWED),
+        "🚫 只有付款人才能无视每周上限！",
+    );
+    map.insert(
+        ("zh", PAY_BACK_CURRENCY_MENU),
+        "当然！🙌 你想为这笔付款设置货币吗？你也可以跳过这一步。",
+    );
+    map.insert(("zh", PAY_BACK_PARTIAL_FAILED), "⁉️ 糟糕！出错了！🥺 很抱歉，我没能添加所有付款。请查看 {view} 里已经添加的付款，稍后再试！");
+    map.insert(
+        ("zh", PAY_BACK_REPEATED),
+        "🚫 哎呀！你好像已经在还钱了！请先完成或 {cancel}，再开始新的操作。",
+    );
+    map.insert(
+        ("zh", PAY_BACK_BLOCKED),
+        "🚫 哎呀！你好像正在还钱！请先完成或 {cancel}，再开始新的操作。",
+    );
+    map.insert(
+        ("zh", PAY_BACK_CREDITORS_NONE_SELECTED),
+        "请先点选你还钱给谁！",
+    );
+    map.insert(
+        ("zh", PAY_BACK_CREDITORS_NOT_ALLOWED),
+        "只有还钱的人才能选择还给谁！",
+    );
+    map.insert(
+        ("zh", DUPLICATE_PAYMENT_REPEATED),
+        "🚫 哎呀！你好像已经在复制一笔付款了！请先完成或 {cancel}，再开始新的操作。",
+    );
+    map.insert(
+        ("zh", DUPLICATE_PAYMENT_BLOCKED),
+        "🚫 哎呀！你好像正在复制一笔付款！请先完成或 {cancel}，再开始新的操作。",
+    );
+    map.insert(("zh", PAY_BACK_SUMMARY), "🎉 {user} 还了 {amounts}");
+    map.insert(
+        ("zh", DEBTOR_CORRECTION_SUGGESTION),
+        "🤔 我在这里还不认识 {typos}。你是指 {similar} 吗？\n\n",
+    );
+    map.insert(("zh", DEBTOR_CORRECTION_BUTTON), "你是指 {similar} 吗？");
+    map.insert(
+        ("zh", TRASH_EMPTY),
+        "🗑️ 回收站是空的！删除的付款会在这里保留 {days} 天，以防你们需要找回。",
+    );
+    map.insert(("zh", TRASH_LIST), "🗑️ 这是最近删除的付款！它们会在这里保留 {days} 天，之后我会把它们永久删除。\n\n{payments}\n\n点一个数字来恢复那笔付款。");
+    map.insert(
+        ("zh", TRASH_ENTRY),
+        "{number}. {description}（{date}）— {payer} 付了 {total}\n删除于：{deleted}",
+    );
+    map.insert(
+        ("zh", TRASH_RESTORE_NOT_ALLOWED),
+        "🔒 抱歉，只有这个聊天的管理员才能恢复付款！",
+    );
+    map.insert(("zh", TRASH_RESTORE_GONE), "这笔付款已经不在回收站里了！");
+    map.insert(
+        ("zh", TRASH_RESTORED),
+        "♻️ 付款「{description}」已恢复！我已经把它重新计入余额。",
+    );
+    map.insert(("zh", LEADERBOARD_INSTRUCTIONS), "像这样选择一个时段：/leaderboard week、/leaderboard month、/leaderboard year 或 /leaderboard all。");
+    map.insert(
+        ("zh", LEADERBOARD_PERIOD_UNKNOWN),
+        "糟糕！❌ 我不认识这个时段……\n\n{instructions}",
+    );
+    map.insert(("zh", LEADERBOARD_PERIOD_WEEK), "过去一周");
+    map.insert(("zh", LEADERBOARD_PERIOD_MONTH), "过去一个月");
+    map.insert(("zh", LEADERBOARD_PERIOD_YEAR), "过去一年");
+    map.insert(("zh", LEADERBOARD_PERIOD_ALL), "全部时间");
+    map.insert(
+        ("zh", LEADERBOARD_EMPTY),
+        "🏆 {period}没有任何付款，所以还没有人可以排名！用 /addpayment 添加一笔吧。",
+    );
+    map.insert(
+        ("zh", LEADERBOARD_SUMMARY_ONE),
+        "🏆 这是{period}的排行榜，共 1 笔付款！\n\n{leaderboard}⭐️ {instructions}",
+    );
+    map.insert(
+        ("zh", LEADERBOARD_SUMMARY),
+        "🏆 这是{period}的排行榜，共 {count} 笔付款！\n\n{leaderboard}⭐️ {instructions}",
+    );
+    map.insert(("zh", LEADERBOARD_TOP_PAYERS), "💸 付款最多：\n");
+    map.insert(
+        ("zh", LEADERBOARD_TOP_PAYERS_CURRENCY),
+        "💸 付款最多（{currency}）：\n",
+    );
+    map.insert(("zh", LEADERBOARD_FREQUENT_DEBTORS), "🧾 最常欠款：\n");
+    map.insert(("zh", LEADERBOARD_FASTEST_SETTLERS), "⚡️ 还款最快：\n");
+    map.insert(("zh", LEADERBOARD_PAYMENTS_ONE), "1 笔付款");
+    map.insert(("zh", LEADERBOARD_PAYMENTS), "{count} 笔付款");
+    map.insert(("zh", LEADERBOARD_SETTLE_TIME), "{duration}内还款");
+    map.insert(("zh", LEADERBOARD_MINUTES_ONE), "1 分钟");
+    map.insert(("zh", LEADERBOARD_MINUTES), "{count} 分钟");
+    map.insert(("zh", LEADERBOARD_HOURS_ONE), "1 小时");
+    map.insert(("zh", LEADERBOARD_HOURS), "{count} 小时");
+    map.insert(("zh", LEADERBOARD_DAYS_ONE), "1 天");
+    map.insert(("zh", LEADERBOARD_DAYS), "{count} 天");
+    map.insert(
+        ("zh", DISPUTE_ALREADY_OPEN),
+        "⚠️ 这笔付款已经有人提出异议了！付款人需要先编辑或忽略它。",
+    );
+    map.insert(("zh", DISPUTE_OPENED), "⚠️ {disputer} 对付款「{description}」提出了异议！\n\n{payer}，可以检查一下吗？你可以编辑这笔付款，如果它没问题，也可以忽略这个异议。");
+    map.insert(
+        ("zh", DISPUTE_NOTIFICATION),
+        "⚠️ {disputer} 在 {chat} 对你的付款「{description}」提出了异议！请去那里检查一下。",
+    );
+    map.insert(("zh", DISPUTE_CHAT_DEFAULT), "一个群组");
+    map.insert(("zh", DISPUTE_RESOLVED), "这个异议已经处理过了！");
+    map.insert(
+        ("zh", DISPUTE_NOT_ALLOWED),
+        "🚫 只有付款人才能处理这个异议！",
+    );
+    map.insert(
+        ("zh", DISPUTE_DISMISSED),
+        "{message}\n\n✅ 付款人已忽略异议！这笔付款保持不变。",
+    );
+    map.insert(
+        ("zh", DISPUTE_BUSY),
+        "🚫 请先完成或取消你正在和我进行的操作！",
+    );
+    map.insert(
+        ("zh", DISPUTE_EDIT_NOT_ALLOWED),
+        "🔒 抱歉，只有这个聊天的管理员才能编辑付款！",
+    );
+    map.insert(
+        ("zh", NOTIFICATION_ON),
+        "🔔 每当你在群组里被加入一笔付款时，我也会在这里通知你！",
+    );
+    map.insert(
+        ("zh", NOTIFICATION_ADDED),
+        "🔔 {sender} 在 {chat} 把你加入了「{description}」，金额为 {amount}！",
+    );
+    map.insert(("zh", NOTIFICATION_CHAT_DEFAULT), "一个群组");
+    map.insert(
+        ("zh", NOTIFICATION_OFF),
+        "🔕 收到！我不会再通知你新的付款了。随时在这里发送 /start 就能重新开启！",
+    );
+    map.insert(
+        ("zh", NOTIFICATION_OFF_FAILED),
+        "⁉️ 糟糕！出错了！🥺 请稍后再试！",
+    );
+    map.insert(("zh", STATS_CHANGE_UP), "（比上个月 📈 {change}%）");
+    map.insert(("zh", STATS_CHANGE_DOWN), "（比上个月 📉 {change}%）");
+    map.insert(("zh", STATS_CHANGE_SAME), "（与上个月持平）");
+    map.insert(("zh", STATS_HEADER_CURRENCY), "{month}（{currency}）");
+    map.insert(("zh", STATS_MONTH_ONE), "{header}：\n    总开销：{total}{change}\n    花费最多：{spender}（{spending}）\n    最大开销：{expense}（{expense_amount}）\n    平均开销：{average}，共 1 笔付款\n");
+    map.insert(("zh", STATS_MONTH), "{header}：\n    总开销：{total}{change}\n    花费最多：{spender}（{spending}）\n    最大开销：{expense}（{expense_amount}）\n    平均开销：{average}，共 {count} 笔付款\n");
+    map.insert(
+        ("zh", STATS_NONE),
+        "📈 还没有任何付款，所以没有什么可以显示的！用 /addpayment 添加一笔吧。",
+    );
+    map.insert(
+        ("zh", STATS_SUMMARY),
+        "📈 这是过去几个月的统计！\n\n{stats}",
+    );
+    map.insert(
+        ("zh", STATEMENT_FILE_CAPTION),
+        "📜 这是正式的债务声明，因为内容较长，我放在了文本文件里！",
+    );
+    map.insert(("zh", RETENTION_MONTHS_ONE), "1 个月");
+    map.insert(("zh", RETENTION_MONTHS), "{count} 个月");
+    map.insert(("zh", RETENTION_CAPTION_ONE), "🧹 该整理一下了！这个聊天只保留 {months} 的付款，所以我要清除 {date} 之前的 1 笔付款。这是一份摘要，供你们留存！\n\n余额会结转为一条记录，所以大家的余额都不会改变。");
+    map.insert(("zh", RETENTION_CAPTION), "🧹 该整理一下了！这个聊天只保留 {months} 的付款，所以我要清除 {date} 之前的 {count} 笔付款。这是一份摘要，供你们留存！\n\n余额会结转为一条记录，所以大家的余额都不会改变。");
+
+    // Buttons
+    for (label, zh) in BUTTONS {
+        map.insert(("en", label), label);
+        map.insert(("zh", label), zh);
+    }
+
+    map
+}
+
+// Retrieves the language given a language code or name.
+pub fn get_language(text: &str) -> Option<Language> {
+    let text = text.trim().to_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|language| language.0 == text || language.1.to_lowercase() == text)
+        .copied()
+}
+
+// Translates a message into a language, substituting in the arguments.
+pub fn translate(language: &str, key: &str, args: &[(&str, &str)]) -> String {
+    let catalog = CATALOG.get_or_init(all_messages);
+    let message = match catalog
+        .get(&(language, key))
+        .or_else(|| catalog.get(&(LANGUAGE_DEFAULT.0, key)))
+    {
+        Some(message) => message,
+        None => key,
+    };
+
+    substitute(message, args)
+}
+
+// Translates the label of a button into a language.
+// Labels that are not buttons, such as usernames and currencies, are kept as they are.
+pub fn translate_button(language: &str, label: &str) -> String {
+    if let Some(currency) = label.strip_prefix("Convert To ") {
+        return translate(language, CONVERT_TO_CURRENCY, &[("currency", currency)]);
+    }
+    match BUTTONS.iter().find(|(button, _)| *button == label) {
+        Some((button, _)) => translate(language, button, &[]),
+        None => label.to_string(),
+    }
+}
+
+// Substitutes the arguments into a message, in a single pass over the message.
+// Values are never substituted into again, so user text with braces is kept as is.
+fn substitute(message: &str, args: &[(&str, &str)]) -> String {
+    let mut substituted = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        substituted.push_str(&rest[..start]);
+        let placeholder = &rest[start + 1..];
+        let value = placeholder.find('}').and_then(|end| {
+            args.iter()
+                .find(|(name, _)| *name == &placeholder[..end])
+                .map(|(_, value)| (*value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                substituted.push_str(value);
+                rest = &placeholder[end + 1..];
+            }
+            None => {
+                substituted.push('{');
+                rest = placeholder;
+            }
+        }
+    }
+    substituted.push_str(rest);
+    substituted
+}
+
+/* Text for users, kept as message keys with their arguments until it is translated.
+ * Used where text is made without knowing the chat it is for, such as errors from parsing.
+ * Parts of the text, such as an error followed by instructions, are joined by blank lines.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Text {
+    parts: Vec<(&'static str, Vec<(&'static str, String)>)>,
+}
+
+impl Text {
+    // Creates text from a message key
+    pub fn new(key: &'static str) -> Text {
+        Text {
+            parts: vec![(key, Vec::new())],
+        }
+    }
+
+    // Adds an argument to the last part of the text
+    pub fn arg(mut self, name: &'static str, value: impl ToString) -> Text {
+        if let Some((_, args)) = self.parts.last_mut() {
+            args.push((name, value.to_string()));
+        }
+        self
+    }
+
+    // Adds more text after this text, as a new paragraph
+    pub fn then(mut self, other: Text) -> Text {
+        self.parts.extend(other.parts);
+        self
+    }
+
+    // Translates the text into a language
+    pub fn translate(&self, language: &str) -> String {
+        self.parts
+            .iter()
+            .map(|(key, args)| {
+                let args: Vec<(&str, &str)> = args
+                    .iter()
+                    .map(|(name, value)| (*name, value.as_str()))
+                    .collect();
+                translate(language, key, &args)
+            })
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+}
+
+// Text is displayed in English, such as in logs
+impl fmt::Display for Text {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.translate(LANGUAGE_DEFAULT.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_language() {
+        assert_eq!(get_language("zh"), Some(("zh", "中文")));
+        assert_eq!(get_language("English"), Some(LANGUAGE_DEFAULT));
+        assert_eq!(get_language("xx"), None);
+    }
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(
+            translate("zh", LANGUAGE_UPDATED, &[("language", "中文")]),
+            "没问题！从现在起我会用中文回复！🌐"
+        );
+        assert_eq!(
+            translate("xx", LANGUAGE_UPDATED, &[("language", "English")]),
+            "You got it! I'll reply in English from now on! 🌐"
+        );
+        assert_eq!(
+            translate("zh", EDIT_TOTAL_PROMPT, &[("total", "$5.00")]),
+            "目前总金额：$5.00\n\n总金额应该是多少？"
+        );
+        assert_eq!(translate("en", "no_such_key", &[]), "no_such_key");
+    }
+
+    #[test]
+    fn test_translate_single_pass() {
+        // Values with placeholders of other arguments are not substituted into
+        assert_eq!(
+            translate(
+                "en",
+                STATEMENT_DEBT,
+                &[
+                    ("index", "1"),
+                    ("debtor", "{creditor}"),
+                    ("creditor", "@bob"),
+                    ("amount", "{words}"),
+                    ("words", "Ten")
+                ]
+            ),
+            "1. {creditor} owes @bob the sum of {words}\n    (Ten)"
+        );
+        // Braces without a matching argument are kept as is
+        assert_eq!(
+            translate("en", EDIT_TOTAL_PROMPT, &[("total", "{")]),
+            "Current total: {\n\nWhat should the total be?"
+        );
+        assert_eq!(substitute("{a} {b} {", &[("a", "}{b}")]), "}{b} {b} {");
+    }
+
+    #[test]
+    fn test_text() {
+        let text = Text::new(EDIT_TOTAL_PROMPT)
+            .arg("total", "$5.00")
+            .then(Text::new(TOTAL_INSTRUCTIONS));
+        assert_eq!(
+            text.translate("zh"),
+            format!(
+                "目前总金额：$5.00\n\n总金额应该是多少？\n\n{}",
+                translate("zh", TOTAL_INSTRUCTIONS, &[])
+            )
+        );
+        assert_eq!(
+            text.to_string(),
+            format!(
+                "Current total: $5.00\n\nWhat should the total be?\n\n{}",
+                translate("en", TOTAL_INSTRUCTIONS, &[])
+            )
+        );
+    }
+
+    #[test]
+    fn test_translate_button() {
+        assert_eq!(translate_button("zh", "Done"), "完成");
+        assert_eq!(translate_button("zh", "Round Robin"), "轮流");
+        assert_eq!(translate_button("zh", "Convert To USD"), "转换为 USD");
+        assert_eq!(translate_button("en", "Convert To USD"), "Convert To USD");
+        assert_eq!(translate_button("zh", "Convert To…"), "转换为…");
+        assert_eq!(translate_button("zh", "@alice"), "@alice");
+    }
+
+    #[test]
+    fn test_translate_error() {
+        assert_eq!(
+            translate("en", VALIDATION_ERROR, &[("reason", "The total is missing.")]),
+            "❌ Hmm, that doesn't look right! The total is missing. Please check and try again!\n\n"
+        );
+    }
+
+    #[test]
+    fn test_all_languages_complete() {
+        let catalog = all_messages();
+        for (language, key) in catalog.keys() {
+            for other in LANGUAGES {
+                assert!(
+                    catalog.contains_key(&(other.0, key)),
+                    "Message {} in {} is missing in {}",
+                    key,
+                    language,
+                    other.0
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,449 @@
+/* Import reads payments from CSV files, such as an export from Splitwise.
+ * Files are parsed directly without any external library, and each row is mapped into a payment.
+ * Splitwise exports are recognised by their columns, and have a column of net amounts per person.
+ * Other files must name their columns, with the payer, amount, and who to split equally with.
+ */
+use chrono::NaiveDate;
+
+use crate::bot::{
+    currency::{
+        count_decimal_places, get_currency_from_code, get_default_currency, normalize_amount_text,
+        Currency, NumberFormat,
+    },
+    i18n::{
+        Text, IMPORT_AMOUNTS_MISMATCH, IMPORT_AMOUNT_DECIMALS, IMPORT_AMOUNT_INVALID,
+        IMPORT_AMOUNT_TOO_LARGE, IMPORT_COLUMNS_MISSING, IMPORT_CURRENCY_UNKNOWN,
+        IMPORT_DATE_INVALID, IMPORT_DEBTORS_MISSING, IMPORT_FILE_EMPTY, IMPORT_PAYERS_MULTIPLE,
+        IMPORT_PAYER_MISSING,
+    },
+    money::Money,
+};
+
+const SPLITWISE_MEMBERS_START: usize = 5;
+const SPLITWISE_TOTAL_BALANCE: &str = "Total balance";
+const DATE_FORMATS: [&str; 4] = ["%Y-%m-%d", "%Y/%m/%d", "%d/%m/%Y", "%d-%m-%Y"];
+
+// Represents a payment read from a row of a file, before its users are validated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedPayment {
+    pub row: usize,
+    pub datetime: Option<String>,
+    pub description: String,
+    pub creditor: String,
+    pub currency: Currency,
+    pub total: i64,
+    pub debts: Vec<(String, i64)>,
+}
+
+// Represents the result of reading a file, with the rows that could not be read and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedFile {
+    pub payments: Vec<ImportedPayment>,
+    pub errors: Vec<(usize, Text)>,
+}
+
+// Columns of a file of payments, by their position
+struct GenericColumns {
+    date: Option<usize>,
+    description: usize,
+    payer: usize,
+    amount: usize,
+    currency: Option<usize>,
+    split: usize,
+}
+
+// Parses CSV text into rows of fields, with fields optionally quoted
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut is_quoted = false;
+
+    let mut chars = text.trim_start_matches('\u{FEFF}').chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, is_quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => is_quoted = false,
+            ('"', false) if field.is_empty() => is_quoted = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => continue,
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    // Blank lines are left out
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    rows
+}
+
+// Parses an amount into its base value in a currency, written in the number format of the chat
+// Amounts with more decimal places than the currency has are rejected, rather than rounded.
+fn parse_amount(text: &str, currency: &Currency, number_format: NumberFormat) -> Result<i64, Text> {
+    let amount = match normalize_amount_text(text, number_format) {
+        Some(amount) => amount,
+        None => return Err(Text::new(IMPORT_AMOUNT_INVALID).arg("amount", text.trim())),
+    };
+    if count_decimal_places(&amount) > currency.1 {
+        return Err(Text::new(IMPORT_AMOUNT_DECIMALS)
+            .arg("amount", text.trim())
+            .arg("currency", &currency.0)
+            .arg("decimals", currency.1));
+    }
+
+    match amount.parse::<f64>() {
+        Ok(value) if value.is_finite() => Money::from_f64(currency, value)
+            .map(|money| money.amount())
+            .map_err(|_| Text::new(IMPORT_AMOUNT_TOO_LARGE).arg("amount", text.trim())),
+        _ => Err(Text::new(IMPORT_AMOUNT_INVALID).arg("amount", text.trim())),
+    }
+}
+
+// Parses the currency of a row, assuming no currency if it is not given
+fn parse_currency(text: &str) -> Result<Currency, Text> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(get_default_currency());
+    }
+    get_currency_from_code(text).ok_or(Text::new(IMPORT_CURRENCY_UNKNOWN).arg("currency", text))
+}
+
+// Parses the date of a row, as the start of the day in UTC
+fn parse_date(text: &str) -> Result<Option<String>, Text> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    // Splitwise may include the time after the date, which is not needed
+    let date = text.split(['T', ' ']).next().unwrap_or(text);
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(date, format).ok())
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| Some(datetime.and_utc().to_string()))
+        .ok_or(Text::new(IMPORT_DATE_INVALID).arg("date", text))
+}
+
+// Gets a field of a row, which may be missing at the end of the row
+fn get_field(row: &[String], index: usize) -> &str {
+    row.get(index).map(|field| field.trim()).unwrap_or("")
+}
+
+// Checks if the header of a file is that of a Splitwise export
+fn is_splitwise_header(header: &[String]) -> bool {
+    let columns: Vec<String> = header
+        .iter()
+        .take(SPLITWISE_MEMBERS_START)
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+    columns == ["date", "description", "category", "cost", "currency"]
+        && header.len() > SPLITWISE_MEMBERS_START
+}
+
+// Reads a row of a Splitwise export, where each person has their net amount for the payment.
+// The one person with a positive amount paid, and owes the rest of the cost that others do not.
+fn read_splitwise_row(
+    row_num: usize,
+    row: &[String],
+    members: &[String],
+    number_format: NumberFormat,
+) -> Result<Option<ImportedPayment>, Text> {
+    let description = get_field(row, 1);
+    if description == SPLITWISE_TOTAL_BALANCE {
+        return Ok(None);
+    }
+
+    let currency = parse_currency(get_field(row, 4))?;
+    let total = parse_amount(get_field(row, 3), &currency, number_format)?;
+    let mut creditor: Option<(String, i64)> = None;
+    let mut debts: Vec<(String, i64)> = Vec::new();
+    for (index, member) in members.iter().enumerate() {
+        let field = get_field(row, SPLITWISE_MEMBERS_START + index);
+        let amount = if field.is_empty() {
+            0
+        } else {
+            parse_amount(field, &currency, number_format)?
+        };
+
+        if amount > 0 {
+            if creditor.is_some() {
+                return Err(Text::new(IMPORT_PAYERS_MULTIPLE));
+            }
+            creditor = Some((member.clone(), amount));
+        } else if amount < 0 {
+            debts.push((member.clone(), -amount));
+        }
+    }
+
+    let (creditor, paid_for_others) = match creditor {
+        Some(creditor) => creditor,
+        None => return Err(Text::new(IMPORT_PAYER_MISSING)),
+    };
+    let share = total - paid_for_others;
+    if share < 0 {
+        return Err(Text::new(IMPORT_AMOUNTS_MISMATCH));
+    } else if share > 0 {
+        debts.push((creditor.clone(), share));
+    }
+
+    Ok(Some(ImportedPayment {
+        row: row_num,
+        datetime: parse_date(get_field(row, 0))?,
+        description: description.to_string(),
+        creditor,
+        currency,
+        total,
+        debts,
+    }))
+}
+
+// Finds the columns of a file of payments from its header, by their names
+fn find_generic_columns(header: &[String]) -> Option<GenericColumns> {
+    let find = |names: &[&str]| {
+        header
+            .iter()
+            .position(|column| names.contains(&column.trim().to_lowercase().as_str()))
+    };
+    Some(GenericColumns {
+        date: find(&["date"]),
+        description: find(&["description", "item"])?,
+        payer: find(&["payer", "paid by", "creditor"])?,
+        amount: find(&["amount", "total", "cost"])?,
+        currency: find(&["currency"]),
+        split: find(&["split", "split with", "debtors"])?,
+    })
+}
+
+// Reads a row of a file of payments, split equally between the users given
+fn read_generic_row(
+    row_num: usize,
+    row: &[String],
+    columns: &GenericColumns,
+    number_format: NumberFormat,
+) -> Result<ImportedPayment, Text> {
+    let currency = match columns.currency {
+        Some(index) => parse_currency(get_field(row, index))?,
+        None => get_default_currency(),
+    };
+    let total = parse_amount(get_field(row, columns.amount), &currency, number_format)?;
+
+    let users: Vec<&str> = get_field(row, columns.split)
+        .split(|c: char| c.is_whitespace() || c == ';' || c == ',')
+        .filter(|user| !user.is_empty())
+        .collect();
+    if users.is_empty() {
+        return Err(Text::new(IMPORT_DEBTORS_MISSING));
+    }
+
+    // Splits equally, with the remainder going to the first users
+    let count = users.len() as i64;
+    let debts: Vec<(String, i64)> = users
+        .iter()
+        .enumerate()
+        .map(|(index, user)| {
+            let extra = if (index as i64) < total % count { 1 } else { 0 };
+            (user.to_string(), total / count + extra)
+        })
+        .collect();
+
+    Ok(ImportedPayment {
+        row: row_num,
+        datetime: match columns.date {
+            Some(index) => parse_date(get_field(row, index))?,
+            None => None,
+        },
+        description: get_field(row, columns.description).to_string(),
+        creditor: get_field(row, columns.payer).to_string(),
+        currency,
+        total,
+        debts,
+    })
+}
+
+/* Reads the payments of a CSV file, either a Splitwise export, or a file with named columns.
+ * Amounts are read in the number format of the chat.
+ * Rows are numbered as in the file, counting the header as the first row.
+ * Returns an error if the file is not in either format.
+ */
+pub fn read_payments_csv(text: &str, number_format: NumberFormat) -> Result<ImportedFile, Text> {
+    let rows = parse_csv(text);
+    let (header, rows) = match rows.split_first() {
+        Some(rows) => rows,
+        None => return Err(Text::new(IMPORT_FILE_EMPTY)),
+    };
+
+    let mut payments: Vec<ImportedPayment> = Vec::new();
+    let mut errors: Vec<(usize, Text)> = Vec::new();
+    if is_splitwise_header(header) {
+        let members: Vec<String> = header[SPLITWISE_MEMBERS_START..]
+            .iter()
+            .map(|member| member.trim().to_string())
+            .collect();
+        for (index, row) in rows.iter().enumerate() {
+            match read_splitwise_row(index + 2, row, &members, number_format) {
+                Ok(Some(payment)) => payments.push(payment),
+                Ok(None) => continue,
+                Err(err) => errors.push((index + 2, err)),
+            }
+        }
+    } else {
+        let columns = match find_generic_columns(header) {
+            Some(columns) => columns,
+            None => return Err(Text::new(IMPORT_COLUMNS_MISSING)),
+        };
+        for (index, row) in rows.iter().enumerate() {
+            match read_generic_row(index + 2, row, &columns, number_format) {
+                Ok(payment) => payments.push(payment),
+                Err(err) => errors.push((index + 2, err)),
+            }
+        }
+    }
+
+    Ok(ImportedFile { payments, errors })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::currency::{NUMBER_FORMATS, NUMBER_FORMAT_DEFAULT};
+
+    #[test]
+    fn test_parse_csv() {
+        assert_eq!(
+            parse_csv("\u{FEFF}a,b,c\r\n\r\n\"d, e\",\"f \"\"g\"\"\",\n"),
+            vec![
+                vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                vec!["d, e".to_string(), "f \"g\"".to_string(), "".to_string()],
+            ]
+        );
+        assert_eq!(
+            parse_csv("a,b"),
+            vec![vec!["a".to_string(), "b".to_string()]]
+        );
+        assert!(parse_csv("\n,\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_amount() {
+        let usd = get_currency_from_code("USD").unwrap();
+        let jpy = get_currency_from_code("JPY").unwrap();
+        let comma_format = NUMBER_FORMATS[1];
+        assert_eq!(parse_amount("12.50", &usd, NUMBER_FORMAT_DEFAULT), Ok(1250));
+        assert_eq!(
+            parse_amount("-12.5", &usd, NUMBER_FORMAT_DEFAULT),
+            Ok(-1250)
+        );
+        assert_eq!(
+            parse_amount("1,234.56", &usd, NUMBER_FORMAT_DEFAULT),
+            Ok(123456)
+        );
+        assert_eq!(
+            parse_amount("1,500", &usd, NUMBER_FORMAT_DEFAULT),
+            Ok(150000)
+        );
+        assert_eq!(parse_amount("12,5", &usd, comma_format), Ok(1250));
+        assert_eq!(parse_amount("1.234,56", &usd, comma_format), Ok(123456));
+        assert_eq!(parse_amount("1000", &jpy, NUMBER_FORMAT_DEFAULT), Ok(1000));
+        assert!(parse_amount("12,5", &usd, NUMBER_FORMAT_DEFAULT).is_err());
+        assert!(parse_amount("12.505", &usd, NUMBER_FORMAT_DEFAULT).is_err());
+        assert!(parse_amount("1000.5", &jpy, NUMBER_FORMAT_DEFAULT).is_err());
+        assert!(parse_amount("twelve", &usd, NUMBER_FORMAT_DEFAULT).is_err());
+        assert!(parse_amount("1e30", &usd, NUMBER_FORMAT_DEFAULT).is_err());
+    }
+
+    #[test]
+    fn test_parse_date() {
+        let datetime = Ok(Some("2024-03-12 00:00:00 UTC".to_string()));
+        assert_eq!(parse_date("2024-03-12"), datetime);
+        assert_eq!(parse_date("12/03/2024"), datetime);
+        assert_eq!(parse_date("2024-03-12T10:00:00Z"), datetime);
+        assert_eq!(parse_date(""), Ok(None));
+        assert!(parse_date("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_read_payments_csv_splitwise() {
+        let text = "Date,Description,Category,Cost,Currency,alice_1,bob_22,carol_333\n\
+            \n\
+            2024-03-12,Dinner,Dining out,30.00,USD,20.00,-10.00,-10.00\n\
+            2024-03-13,Taxi,Taxi,12.00,USD,-12.00,12.00,0.00\n\
+            2024-03-14,Gift,General,10.00,USD,5.00,5.00,-10.00\n\
+            \n\
+            2024-03-14,Total balance, ,,USD,8.00,-2.00,-10.00\n";
+        let file = read_payments_csv(text, NUMBER_FORMAT_DEFAULT).unwrap();
+        assert_eq!(
+            file.payments,
+            vec![
+                ImportedPayment {
+                    row: 2,
+                    datetime: Some("2024-03-12 00:00:00 UTC".to_string()),
+                    description: "Dinner".to_string(),
+                    creditor: "alice_1".to_string(),
+                    currency: get_currency_from_code("USD").unwrap(),
+                    total: 3000,
+                    debts: vec![
+                        ("bob_22".to_string(), 1000),
+                        ("carol_333".to_string(), 1000),
+                        ("alice_1".to_string(), 1000),
+                    ],
+                },
+                ImportedPayment {
+                    row: 3,
+                    datetime: Some("2024-03-13 00:00:00 UTC".to_string()),
+                    description: "Taxi".to_string(),
+                    creditor: "bob_22".to_string(),
+                    currency: get_currency_from_code("USD").unwrap(),
+                    total: 1200,
+                    debts: vec![("alice_1".to_string(), 1200)],
+                },
+            ]
+        );
+        assert_eq!(file.errors, vec![(4, Text::new(IMPORT_PAYERS_MULTIPLE))]);
+    }
+
+    #[test]
+    fn test_read_payments_csv_generic() {
+        let text = "Description,Paid By,Amount,Split With\n\
+            Lunch,@alice_1,10.00,@alice_1 @bob_22 @carol_333\n\
+            Snacks,@bob_22,abc,@alice_1\n\
+            Drinks,@carol_333,5,\n";
+        let file = read_payments_csv(text, NUMBER_FORMAT_DEFAULT).unwrap();
+        assert_eq!(
+            file.payments,
+            vec![ImportedPayment {
+                row: 2,
+                datetime: None,
+                description: "Lunch".to_string(),
+                creditor: "@alice_1".to_string(),
+                currency: get_default_currency(),
+                total: 1000,
+                debts: vec![
+                    ("@alice_1".to_string(), 334),
+                    ("@bob_22".to_string(), 333),
+                    ("@carol_333".to_string(), 333),
+                ],
+            }]
+        );
+        assert_eq!(
+            file.errors,
+            vec![
+                (3, Text::new(IMPORT_AMOUNT_INVALID).arg("amount", "abc")),
+                (4, Text::new(IMPORT_DEBTORS_MISSING)),
+            ]
+        );
+
+        assert!(read_payments_csv("Name,Value\nLunch,10\n", NUMBER_FORMAT_DEFAULT).is_err());
+        assert!(read_payments_csv("", NUMBER_FORMAT_DEFAULT).is_err());
+    }
+}
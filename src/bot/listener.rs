@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use futures::{future, stream::BoxStream, StreamExt};
+use teloxide::{
+    prelude::*,
+    stop::StopToken,
+    types::{AllowedUpdate, UpdateKind},
+    update_listeners::{AsUpdateStream, Polling, UpdateListener},
+    RequestError,
+};
+
+use crate::bot::{
+    handler::{action_payment_reaction, parse_message_reaction},
+    redis::Redis,
+};
+
+/* Listener receives updates from Telegram by long polling, for the dispatcher.
+ * Message reactions are not supported by teloxide yet, and are dropped by the dispatcher,
+ * so the listener handles them itself before passing on all other updates.
+ * Telegram only sends reactions if they are allowed explicitly, and to admins of the chat.
+ */
+
+// All updates handled by the bot, which must be kept in sync with the dispatcher
+const ALLOWED_UPDATES: [&str; 4] = [
+    "message",
+    "callback_query",
+    "my_chat_member",
+    "message_reaction",
+];
+
+pub struct ReactionListener {
+    polling: Polling<Bot>,
+    redis: Redis,
+}
+
+impl ReactionListener {
+    pub async fn new(bot: Bot, redis: Redis) -> Self {
+        allow_updates(&bot).await;
+        let polling = Polling::builder(bot).delete_webhook().await.build();
+        ReactionListener { polling, redis }
+    }
+}
+
+// Sets the updates that Telegram sends, called directly through the Bot API
+// Does not confirm any updates, so that none are lost
+async fn allow_updates(bot: &Bot) {
+    let url = match bot
+        .api_url()
+        .join(&format!("bot{}/getUpdates", bot.token()))
+    {
+        Ok(url) => url,
+        Err(_) => return,
+    };
+    let body = serde_json::json!({
+        "limit": 1,
+        "timeout": 0,
+        "allowed_updates": ALLOWED_UPDATES,
+    });
+
+    match reqwest::Client::new()
+        .post(url.as_str())
+        .json(&body)
+        .send()
+        .await
+    {
+        Ok(response) if response.status().is_success() => {}
+        Ok(response) => {
+            log::warn!(
+                "Listener - Failed to allow message reactions: {}",
+                response.status()
+            );
+        }
+        Err(err) => {
+            log::warn!(
+                "Listener - Failed to allow message reactions: {}",
+                err.to_string()
+            );
+        }
+    }
+}
+
+impl<'a> AsUpdateStream<'a> for ReactionListener {
+    type StreamErr = RequestError;
+    type Stream = BoxStream<'a, Result<Update, RequestError>>;
+
+    fn as_stream(&'a mut self) -> Self::Stream {
+        let redis = self.redis.clone();
+        self.polling
+            .as_stream()
+            .filter_map(move |update| {
+                if let Ok(Update {
+                    kind: UpdateKind::Error(value),
+                    ..
+                }) = &update
+                {
+                    if let Some(reaction) = parse_message_reaction(value) {
+                        let redis = redis.clone();
+                        tokio::spawn(async move {
+                            if let Err(err) = action_payment_reaction(&redis, reaction).await {
+                                log::error!(
+                                    "Listener - Failed to handle message reaction: {}",
+                                    err
+                                );
+                            }
+                        });
+                        return future::ready(None);
+                    }
+                }
+                future::ready(Some(update))
+            })
+            .boxed()
+    }
+}
+
+impl UpdateListener for ReactionListener {
+    type Err = RequestError;
+
+    fn stop_token(&mut self) -> StopToken {
+        self.polling.stop_token()
+    }
+
+    // Allowed updates are set when the listener is created instead,
+    // as the hint from the dispatcher cannot include message reactions
+    fn hint_allowed_updates(&mut self, _hint: &mut dyn Iterator<Item = AllowedUpdate>) {}
+
+    fn timeout_hint(&self) -> Option<Duration> {
+        self.polling.timeout_hint()
+    }
+}
@@ -2,14 +2,28 @@
 
 // Exported functions
 pub use self::dispatcher::run_dispatcher;
+pub use self::redis::run_migrations;
 
 // Exported structs and types
 pub use self::dispatcher::{Command, State};
+pub use self::redis::Redis;
 
 // Declare submodules
-mod currency;
+pub(crate) mod currency;
+mod detect;
 mod dispatcher;
+mod export;
+mod fuzzy;
 mod handler;
-mod optimizer;
-mod processor;
-mod redis;
+mod help;
+pub(crate) mod i18n;
+mod import;
+mod listener;
+pub(crate) mod money;
+pub(crate) mod optimizer;
+mod period;
+pub(crate) mod processor;
+pub(crate) mod redis;
+mod scheduler;
+mod shutdown;
+mod words;
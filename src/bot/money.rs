@@ -0,0 +1,220 @@
+/* Money is an amount in a currency, in its smallest unit, such as cents.
+ * Amounts are kept within MAX_VALUE either way, which is below the limits of i64,
+ * so that adding two of them never overflows i64.
+ * Amounts entered by users, in commands or imported files, are read as Money, so they start within MAX_VALUE.
+ * Balances add up many amounts, so they are checked against MAX_VALUE before being written.
+ * Operations are checked, returning an error instead of wrapping around on extreme values,
+ * or saturating at MAX_VALUE, for totals that are only displayed.
+ */
+
+use super::currency::Currency;
+
+pub const MAX_VALUE: i64 = 1_000_000_000_000_000_000;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum MoneyError {
+    #[error("This number is too large for me to handle!")]
+    OverflowError(),
+    #[error("Amounts in {0} and {1} can't be added together.")]
+    CurrencyMismatchError(String, String),
+}
+
+// Money is an amount in the smallest unit of its currency, within MAX_VALUE either way
+#[derive(Debug, Clone, PartialEq)]
+pub struct Money {
+    currency: String,
+    amount: i64,
+}
+
+impl Money {
+    // Creates an amount of money, if it is within MAX_VALUE
+    pub fn new(currency: &str, amount: i64) -> Result<Money, MoneyError> {
+        Ok(Money {
+            currency: currency.to_string(),
+            amount: check_amount(amount)?,
+        })
+    }
+
+    // Reads a value in a currency, such as 12.50 dollars, into its smallest unit, if it is within MAX_VALUE
+    // Values that are not finite are rejected as well
+    pub fn from_f64(currency: &Currency, value: f64) -> Result<Money, MoneyError> {
+        Money::new(
+            &currency.0,
+            checked_from_f64(value * 10.0_f64.powi(currency.1))?,
+        )
+    }
+
+    // Returns the amount, in the smallest unit of the currency
+    pub fn amount(&self) -> i64 {
+        self.amount
+    }
+
+    // Adds another amount in the same currency, if the sum is within MAX_VALUE
+    pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        if self.currency != other.currency {
+            return Err(MoneyError::CurrencyMismatchError(
+                self.currency.clone(),
+                other.currency.clone(),
+            ));
+        }
+        Money::new(&self.currency, checked_add(self.amount, other.amount)?)
+    }
+}
+
+// Checks that an amount is within MAX_VALUE either way
+pub fn check_amount(amount: i64) -> Result<i64, MoneyError> {
+    if !(-MAX_VALUE..=MAX_VALUE).contains(&amount) {
+        Err(MoneyError::OverflowError())
+    } else {
+        Ok(amount)
+    }
+}
+
+// Adds two amounts, if the sum is within MAX_VALUE either way
+pub fn checked_add(first: i64, second: i64) -> Result<i64, MoneyError> {
+    first
+        .checked_add(second)
+        .ok_or(MoneyError::OverflowError())
+        .and_then(check_amount)
+}
+
+// Negates an amount, if it is within MAX_VALUE either way
+pub fn checked_neg(amount: i64) -> Result<i64, MoneyError> {
+    check_amount(amount)?
+        .checked_neg()
+        .ok_or(MoneyError::OverflowError())
+}
+
+// Adds up amounts, if the sum and each amount along the way are within MAX_VALUE either way
+pub fn checked_sum<I: IntoIterator<Item = i64>>(amounts: I) -> Result<i64, MoneyError> {
+    amounts
+        .into_iter()
+        .try_fold(0, |sum, amount| checked_add(sum, check_amount(amount)?))
+}
+
+// Adds two amounts, stopping at MAX_VALUE either way
+pub fn saturating_add(first: i64, second: i64) -> i64 {
+    first.saturating_add(second).clamp(-MAX_VALUE, MAX_VALUE)
+}
+
+// Converts a value in the smallest unit of a currency into an amount, stopping at MAX_VALUE either way.
+// Values that are not a number are taken as zero.
+pub fn saturating_from_f64(value: f64) -> i64 {
+    if value.is_nan() {
+        return 0;
+    }
+    value.round().clamp(-MAX_VALUE as f64, MAX_VALUE as f64) as i64
+}
+
+// Converts a value in the smallest unit of a currency into an amount, if it is within MAX_VALUE.
+// Values that are not finite, such as from dividing by zero, are rejected as well.
+pub fn checked_from_f64(value: f64) -> Result<i64, MoneyError> {
+    let value = value.round();
+    if !value.is_finite() || value.abs() > MAX_VALUE as f64 {
+        return Err(MoneyError::OverflowError());
+    }
+    check_amount(value as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_money_checked_add() {
+        let first = Money::new("USD", 1250).unwrap();
+        let second = Money::new("USD", -250).unwrap();
+        assert_eq!(first.checked_add(&second), Money::new("USD", 1000));
+
+        let max = Money::new("USD", MAX_VALUE).unwrap();
+        assert_eq!(max.checked_add(&first), Err(MoneyError::OverflowError()));
+
+        let other = Money::new("EUR", 100).unwrap();
+        assert_eq!(
+            first.checked_add(&other),
+            Err(MoneyError::CurrencyMismatchError(
+                "USD".to_string(),
+                "EUR".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_money_from_f64() {
+        let usd = ("USD".to_string(), 2);
+        let jpy = ("JPY".to_string(), 0);
+        assert_eq!(Money::from_f64(&usd, 12.5), Money::new("USD", 1250));
+        assert_eq!(Money::from_f64(&jpy, 1000.0), Money::new("JPY", 1000));
+        assert_eq!(
+            Money::from_f64(&usd, MAX_VALUE as f64),
+            Err(MoneyError::OverflowError())
+        );
+        assert_eq!(
+            Money::from_f64(&usd, f64::INFINITY),
+            Err(MoneyError::OverflowError())
+        );
+    }
+
+    #[test]
+    fn test_money_new() {
+        assert!(Money::new("USD", MAX_VALUE).is_ok());
+        assert!(Money::new("USD", -MAX_VALUE).is_ok());
+        assert_eq!(
+            Money::new("USD", MAX_VALUE + 1),
+            Err(MoneyError::OverflowError())
+        );
+        assert_eq!(
+            Money::new("USD", i64::MIN),
+            Err(MoneyError::OverflowError())
+        );
+    }
+
+    #[test]
+    fn test_checked_sum() {
+        assert_eq!(checked_sum(vec![1, 2, 3]), Ok(6));
+        assert_eq!(checked_sum(Vec::new()), Ok(0));
+        assert_eq!(checked_sum(vec![MAX_VALUE, -1, 1]), Ok(MAX_VALUE));
+        assert_eq!(
+            checked_sum(vec![MAX_VALUE, 1]),
+            Err(MoneyError::OverflowError())
+        );
+        assert_eq!(
+            checked_sum(vec![i64::MAX, i64::MIN]),
+            Err(MoneyError::OverflowError())
+        );
+    }
+
+    #[test]
+    fn test_checked_neg() {
+        assert_eq!(checked_neg(150), Ok(-150));
+        assert_eq!(checked_neg(-MAX_VALUE), Ok(MAX_VALUE));
+        assert_eq!(checked_neg(i64::MIN), Err(MoneyError::OverflowError()));
+    }
+
+    #[test]
+    fn test_saturating_add() {
+        assert_eq!(saturating_add(1, 2), 3);
+        assert_eq!(saturating_add(MAX_VALUE, 1), MAX_VALUE);
+        assert_eq!(saturating_add(i64::MIN, -1), -MAX_VALUE);
+    }
+
+    #[test]
+    fn test_saturating_from_f64() {
+        assert_eq!(saturating_from_f64(1249.6), 1250);
+        assert_eq!(saturating_from_f64(f64::INFINITY), MAX_VALUE);
+        assert_eq!(saturating_from_f64(-1e30), -MAX_VALUE);
+        assert_eq!(saturating_from_f64(f64::NAN), 0);
+    }
+
+    #[test]
+    fn test_checked_from_f64() {
+        assert_eq!(checked_from_f64(1249.6), Ok(1250));
+        assert_eq!(checked_from_f64(-1.0), Ok(-1));
+        assert_eq!(
+            checked_from_f64(f64::INFINITY),
+            Err(MoneyError::OverflowError())
+        );
+        assert_eq!(checked_from_f64(f64::NAN), Err(MoneyError::OverflowError()));
+        assert_eq!(checked_from_f64(1e19), Err(MoneyError::OverflowError()));
+    }
+}
@@ -1,93 +1,212 @@
 use super::redis::{Debt, UserBalance};
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BinaryHeap},
+};
 
 /* Optimizer is purely for simplifying the debts of a group.
  * It will take in current balances of users in a group chat,
  * simplify them with a greedy algorithm, and return the debts owed.
+ * Alternatively, it can route all debts through a single treasurer.
  */
 
+/* Strategy for settling the debts of a group.
+ * Minimal uses the fewest transactions possible with the greedy algorithm.
+ * Treasurer has everyone settle with the given user only.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettlementStrategy {
+    Minimal,
+    Treasurer(String),
+}
+
+// Amount still owed by a debtor, or owed to a creditor, while settling debts.
+// Ordered by the amount, then by username, so that ties are settled the same way every time.
+#[derive(Debug, PartialEq, Eq)]
+struct Outstanding {
+    username: String,
+    amount: i64,
+}
+
+impl Ord for Outstanding {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.amount
+            .cmp(&other.amount)
+            .then_with(|| other.username.cmp(&self.username))
+    }
+}
+
+impl PartialOrd for Outstanding {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /* Utility Functions */
 
-// Custom comparison function, only to compare the balance amount.
+// Custom comparison function, to compare the balance amount, then the username.
 fn compare(a: &UserBalance, b: &UserBalance) -> Ordering {
-    if a.balance < b.balance {
-        Ordering::Less
-    } else if a.balance > b.balance {
-        Ordering::Greater
-    } else {
-        Ordering::Equal
-    }
+    a.balance
+        .cmp(&b.balance)
+        .then_with(|| a.username.cmp(&b.username))
 }
 
 // Sorts balances in ascending order, from largest debtor, to largest creditor.
-fn sort_balances(balances: &mut Vec<UserBalance>) -> () {
+fn sort_balances(balances: &mut [UserBalance]) {
     balances.sort_by(compare);
 }
 
-/* Main function of Optimizer.
-* Takes in a vector of balances and returns a vector of debts.
-* Important: implicitly assumed that all balances sum up to 0.
-*/
-pub fn optimize_debts(balances: Vec<UserBalance>) -> Vec<Debt> {
-    if balances.len() == 0 {
-        return Vec::new();
+// Groups balances by currency, in order of the currency code.
+fn group_balances(balances: Vec<UserBalance>) -> BTreeMap<String, Vec<UserBalance>> {
+    let mut groups: BTreeMap<String, Vec<UserBalance>> = BTreeMap::new();
+    for balance in balances {
+        groups
+            .entry(balance.currency.clone())
+            .or_default()
+            .push(balance);
     }
+    groups
+}
 
-    let mut sorted_balances = balances.clone();
+// Routes all debts through the treasurer.
+// Debtors owe the treasurer, and the treasurer owes creditors.
+fn route_debts(balances: Vec<UserBalance>, treasurer: &str) -> Vec<Debt> {
+    let mut sorted_balances = balances;
     sort_balances(&mut sorted_balances);
 
-    let mut debts: Vec<Debt> = Vec::new();
-    let mut left = 0;
-    let mut right = sorted_balances.len() - 1;
+    // Uses the treasurer's username as recorded in the balances, if any
+    let treasurer = sorted_balances
+        .iter()
+        .find(|bal| bal.username.to_lowercase() == treasurer.to_lowercase())
+        .map_or(treasurer.to_string(), |bal| bal.username.clone());
 
-    while right > left {
-        // Get the minimum of the amounts
-        let amount = sorted_balances[left]
-            .balance
-            .abs()
-            .min(sorted_balances[right].balance);
-
-        // Add debt to the list
-        if amount > 0 {
-            let debt = Debt {
-                debtor: sorted_balances[left].username.clone(),
-                creditor: sorted_balances[right].username.clone(),
-                currency: sorted_balances[left].currency.clone(),
-                amount,
-            };
-            debts.push(debt);
+    let mut debts: Vec<Debt> = Vec::new();
+    for balance in sorted_balances {
+        if balance.username == treasurer || balance.balance == 0 {
+            continue;
         }
 
-        // If debtor pays in full, move left pointer
-        // If creditor is fully paid, move right pointer
-        // If both, move both pointers
-        // Else, update the amounts remaining
-        if amount == sorted_balances[left].balance.abs() {
-            left += 1;
+        let (debtor, creditor) = if balance.balance < 0 {
+            (balance.username, treasurer.clone())
         } else {
-            sorted_balances[left].balance = amount + sorted_balances[left].balance;
+            (treasurer.clone(), balance.username)
+        };
+        debts.push(Debt {
+            debtor,
+            creditor,
+            currency: balance.currency,
+            amount: balance.balance.abs(),
+        });
+    }
+
+    debts
+}
+
+// Simplifies the debts of a single currency.
+// Matches the largest debtor with the largest creditor, until either is settled,
+// then moves on to the next largest, taken from a heap of debtors and a heap of creditors.
+fn settle_debts(currency: &str, balances: Vec<UserBalance>) -> Vec<Debt> {
+    let mut debtors: BinaryHeap<Outstanding> = BinaryHeap::new();
+    let mut creditors: BinaryHeap<Outstanding> = BinaryHeap::new();
+    for balance in balances {
+        let outstanding = Outstanding {
+            username: balance.username,
+            amount: balance.balance.abs(),
+        };
+        match balance.balance.cmp(&0) {
+            Ordering::Less => debtors.push(outstanding),
+            Ordering::Greater => creditors.push(outstanding),
+            Ordering::Equal => {}
         }
-        if amount == sorted_balances[right].balance {
-            right -= 1;
-        } else {
-            sorted_balances[right].balance -= amount;
+    }
+
+    let mut debts: Vec<Debt> = Vec::with_capacity(debtors.len() + creditors.len());
+    let mut debtor = debtors.pop();
+    let mut creditor = creditors.pop();
+    while let (Some(curr_debtor), Some(curr_creditor)) = (&mut debtor, &mut creditor) {
+        // Get the minimum of the amounts
+        let amount = curr_debtor.amount.min(curr_creditor.amount);
+        debts.push(Debt {
+            debtor: curr_debtor.username.clone(),
+            creditor: curr_creditor.username.clone(),
+            currency: currency.to_string(),
+            amount,
+        });
+
+        // If debtor pays in full, move on to the next debtor
+        // If creditor is fully paid, move on to the next creditor
+        curr_debtor.amount -= amount;
+        curr_creditor.amount -= amount;
+        if curr_debtor.amount == 0 {
+            debtor = debtors.pop();
+        }
+        if curr_creditor.amount == 0 {
+            creditor = creditors.pop();
         }
     }
 
     debts
 }
 
+/* Main function of Optimizer.
+* Takes in a vector of balances and the settlement strategy, returns a vector of debts.
+* Balances of each currency are settled separately, in order of the currency code,
+* and the debts are the same regardless of the order of the balances.
+* Important: implicitly assumed that all balances of each currency sum up to 0.
+*/
+pub fn optimize_debts(balances: Vec<UserBalance>, strategy: &SettlementStrategy) -> Vec<Debt> {
+    if balances.is_empty() {
+        return Vec::new();
+    }
+
+    if let SettlementStrategy::Treasurer(treasurer) = strategy {
+        return group_balances(balances)
+            .into_values()
+            .flat_map(|balances| route_debts(balances, treasurer))
+            .collect();
+    }
+
+    group_balances(balances)
+        .into_iter()
+        .flat_map(|(currency, balances)| settle_debts(&currency, balances))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, time::Instant};
 
     use super::*;
 
+    // Utility function to generate balances of a large group, summing up to 0 in each currency
+    fn generate_balances(users: usize, currencies: &[&str]) -> Vec<UserBalance> {
+        let mut balances: Vec<UserBalance> = Vec::new();
+        for currency in currencies {
+            let mut total = 0;
+            for index in 1..users {
+                // Deterministic spread of amounts, with both debtors and creditors
+                let balance = ((index * 7919) % 20011) as i64 - 10005;
+                total += balance;
+                balances.push(UserBalance {
+                    username: format!("user{index}"),
+                    currency: currency.to_string(),
+                    balance,
+                });
+            }
+            balances.push(UserBalance {
+                username: format!("user{users}"),
+                currency: currency.to_string(),
+                balance: -total,
+            });
+        }
+        balances
+    }
+
     // Utility function to check if the optimized solution is correct
     fn is_solution_correct(balances: Vec<UserBalance>, debts: Vec<Debt>) -> bool {
-        let mut resulting_balances: HashMap<String, i64> = HashMap::new();
+        let mut resulting_balances: HashMap<(String, String), i64> = HashMap::new();
         for balance in balances {
-            resulting_balances.insert(balance.username, balance.balance);
+            resulting_balances.insert((balance.username, balance.currency), balance.balance);
         }
 
         for debt in debts {
@@ -96,12 +215,13 @@ mod tests {
                 return false;
             }
 
-            let new_debtor_balance =
-                resulting_balances.get(&debt.debtor).unwrap_or(&0) + debt.amount;
+            let debtor = (debt.debtor, debt.currency.clone());
+            let creditor = (debt.creditor, debt.currency);
+            let new_debtor_balance = resulting_balances.get(&debtor).unwrap_or(&0) + debt.amount;
             let new_creditor_balance =
-                resulting_balances.get(&debt.creditor).unwrap_or(&0) - debt.amount;
-            resulting_balances.insert(debt.debtor, new_debtor_balance);
-            resulting_balances.insert(debt.creditor, new_creditor_balance);
+                resulting_balances.get(&creditor).unwrap_or(&0) - debt.amount;
+            resulting_balances.insert(debtor, new_debtor_balance);
+            resulting_balances.insert(creditor, new_creditor_balance);
         }
 
         for (_, balance) in resulting_balances {
@@ -115,7 +235,7 @@ mod tests {
 
     #[test]
     fn test_compare() {
-        let balances = vec![
+        let balances = [
             UserBalance {
                 username: "user1".to_string(),
                 currency: "USD".to_string(),
@@ -221,7 +341,10 @@ mod tests {
             amount: 1000,
         }];
 
-        assert_eq!(optimize_debts(balances_1.clone()), solution_1);
+        assert_eq!(
+            optimize_debts(balances_1.clone(), &SettlementStrategy::Minimal),
+            solution_1
+        );
 
         // Test more complex case of equal corresponding balances
         let balances_2 = vec![
@@ -254,7 +377,7 @@ mod tests {
 
         assert!(is_solution_correct(
             balances_2.clone(),
-            optimize_debts(balances_2)
+            optimize_debts(balances_2, &SettlementStrategy::Minimal)
         ));
 
         // Test more complex case of different balances of random amounts
@@ -286,7 +409,7 @@ mod tests {
             },
         ];
 
-        let solution_3 = optimize_debts(balances_3.clone());
+        let solution_3 = optimize_debts(balances_3.clone(), &SettlementStrategy::Minimal);
 
         let expected_solution = vec![
             Debt {
@@ -318,7 +441,7 @@ mod tests {
         assert_eq!(solution_3, expected_solution);
         assert!(is_solution_correct(
             balances_3.clone(),
-            optimize_debts(balances_3)
+            optimize_debts(balances_3, &SettlementStrategy::Minimal)
         ));
 
         // Test more complex example, using my own balances in my groups
@@ -427,7 +550,210 @@ mod tests {
 
         assert!(is_solution_correct(
             balances_4.clone(),
-            optimize_debts(balances_4)
+            optimize_debts(balances_4, &SettlementStrategy::Minimal)
         ));
     }
+
+    #[test]
+    fn test_optimize_balances_treasurer() {
+        let balances = vec![
+            UserBalance {
+                username: "user1".to_string(),
+                currency: "USD".to_string(),
+                balance: 1200,
+            },
+            UserBalance {
+                username: "user2".to_string(),
+                currency: "USD".to_string(),
+                balance: -670,
+            },
+            UserBalance {
+                username: "User3".to_string(),
+                currency: "USD".to_string(),
+                balance: 513,
+            },
+            UserBalance {
+                username: "user4".to_string(),
+                currency: "USD".to_string(),
+                balance: -1043,
+            },
+            UserBalance {
+                username: "user5".to_string(),
+                currency: "USD".to_string(),
+                balance: 0,
+            },
+        ];
+
+        // Everyone settles with the treasurer only, matched case-insensitively
+        let strategy = SettlementStrategy::Treasurer("user3".to_string());
+        let solution = optimize_debts(balances.clone(), &strategy);
+
+        let expected_solution = vec![
+            Debt {
+                debtor: "user4".to_string(),
+                creditor: "User3".to_string(),
+                currency: "USD".to_string(),
+                amount: 1043,
+            },
+            Debt {
+                debtor: "user2".to_string(),
+                creditor: "User3".to_string(),
+                currency: "USD".to_string(),
+                amount: 670,
+            },
+            Debt {
+                debtor: "User3".to_string(),
+                creditor: "user1".to_string(),
+                currency: "USD".to_string(),
+                amount: 1200,
+            },
+        ];
+
+        assert_eq!(solution, expected_solution);
+        assert!(is_solution_correct(balances.clone(), solution));
+
+        // Treasurer without any balance still receives and pays out everything
+        let strategy = SettlementStrategy::Treasurer("user6".to_string());
+        let solution = optimize_debts(balances.clone(), &strategy);
+        assert_eq!(solution.len(), 4);
+        assert!(solution
+            .iter()
+            .all(|debt| debt.debtor == "user6" || debt.creditor == "user6"));
+        assert!(is_solution_correct(balances, solution));
+    }
+
+    #[test]
+    fn test_optimize_balances_deterministic() {
+        // Ties between balances are settled the same way, regardless of order
+        let balances = vec![
+            UserBalance {
+                username: "user3".to_string(),
+                currency: "USD".to_string(),
+                balance: 500,
+            },
+            UserBalance {
+                username: "user1".to_string(),
+                currency: "USD".to_string(),
+                balance: -500,
+            },
+            UserBalance {
+                username: "user2".to_string(),
+                currency: "USD".to_string(),
+                balance: 500,
+            },
+            UserBalance {
+                username: "user4".to_string(),
+                currency: "USD".to_string(),
+                balance: -500,
+            },
+        ];
+
+        let expected_solution = vec![
+            Debt {
+                debtor: "user1".to_string(),
+                creditor: "user2".to_string(),
+                currency: "USD".to_string(),
+                amount: 500,
+            },
+            Debt {
+                debtor: "user4".to_string(),
+                creditor: "user3".to_string(),
+                currency: "USD".to_string(),
+                amount: 500,
+            },
+        ];
+
+        let mut reversed = balances.clone();
+        reversed.reverse();
+        assert_eq!(
+            optimize_debts(balances, &SettlementStrategy::Minimal),
+            expected_solution
+        );
+        assert_eq!(
+            optimize_debts(reversed, &SettlementStrategy::Minimal),
+            expected_solution
+        );
+
+        // Large groups give the same debts for any order of balances
+        let balances = generate_balances(200, &["USD"]);
+        let mut reversed = balances.clone();
+        reversed.reverse();
+        assert_eq!(
+            optimize_debts(balances, &SettlementStrategy::Minimal),
+            optimize_debts(reversed, &SettlementStrategy::Minimal)
+        );
+    }
+
+    #[test]
+    fn test_optimize_balances_currencies() {
+        let balances = vec![
+            UserBalance {
+                username: "user1".to_string(),
+                currency: "USD".to_string(),
+                balance: 1000,
+            },
+            UserBalance {
+                username: "user2".to_string(),
+                currency: "JPY".to_string(),
+                balance: 300,
+            },
+            UserBalance {
+                username: "user2".to_string(),
+                currency: "USD".to_string(),
+                balance: -1000,
+            },
+            UserBalance {
+                username: "user1".to_string(),
+                currency: "JPY".to_string(),
+                balance: -300,
+            },
+        ];
+
+        // Each currency is settled separately, in order of the currency code
+        let expected_solution = vec![
+            Debt {
+                debtor: "user1".to_string(),
+                creditor: "user2".to_string(),
+                currency: "JPY".to_string(),
+                amount: 300,
+            },
+            Debt {
+                debtor: "user2".to_string(),
+                creditor: "user1".to_string(),
+                currency: "USD".to_string(),
+                amount: 1000,
+            },
+        ];
+
+        assert_eq!(
+            optimize_debts(balances.clone(), &SettlementStrategy::Minimal),
+            expected_solution
+        );
+
+        let balances = generate_balances(100, &["EUR", "JPY", "SGD", "USD"]);
+        let solution = optimize_debts(balances.clone(), &SettlementStrategy::Minimal);
+        assert!(solution.len() <= 4 * 99);
+        assert!(is_solution_correct(balances, solution));
+    }
+
+    // Benchmark for large groups, run with `cargo test bench_optimize -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_optimize_debts_large_group() {
+        let currencies = ["EUR", "GBP", "JPY", "SGD", "USD"];
+        for users in [100, 1_000, 10_000, 100_000] {
+            let balances = generate_balances(users, &currencies);
+            let start = Instant::now();
+            let solution = optimize_debts(balances.clone(), &SettlementStrategy::Minimal);
+            let elapsed = start.elapsed();
+            println!(
+                "{} users, {} currencies: {} debts in {:?}",
+                users,
+                currencies.len(),
+                solution.len(),
+                elapsed
+            );
+            assert!(is_solution_correct(balances, solution));
+        }
+    }
 }
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Weekday};
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+/* Period groups dates into the weeks and months that reports and limits are counted by.
+ * Dates are grouped in the time zone of a chat, so a month starts at midnight for the chat.
+ * Weeks start on the first day of the week set by the chat, either Monday or Sunday.
+ * Weeks are named by the ISO week they mostly fall in, like "2024-W05".
+ */
+
+// Finds the time zone of a chat from its city name, as set in the chat settings
+// Falls back to UTC for names that are not known
+pub fn find_time_zone(name: &str) -> Tz {
+    let name = name.trim().to_lowercase().replace('_', " ");
+    TZ_VARIANTS
+        .iter()
+        .find(|time_zone| {
+            let city = time_zone.name().rsplit('/').next().unwrap_or_default();
+            city.to_lowercase().replace('_', " ") == name
+        })
+        .copied()
+        .unwrap_or(Tz::UTC)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeekStart {
+    Monday,
+    Sunday,
+}
+
+pub const WEEK_STARTS: [(&str, WeekStart); 2] =
+    [("monday", WeekStart::Monday), ("sunday", WeekStart::Sunday)];
+
+impl WeekStart {
+    // Gets the name of the first day of the week, as stored in the chat settings
+    pub fn name(&self) -> &'static str {
+        match self {
+            WeekStart::Monday => "monday",
+            WeekStart::Sunday => "sunday",
+        }
+    }
+
+    // Gets the first day of the week
+    pub fn first_day(&self) -> Weekday {
+        match self {
+            WeekStart::Monday => Weekday::Mon,
+            WeekStart::Sunday => Weekday::Sun,
+        }
+    }
+
+    // Gets the last day of the week
+    pub fn last_day(&self) -> Weekday {
+        self.first_day().pred()
+    }
+}
+
+// Gets the first day of the week from its name, ignoring case
+// Also accepts the short names, like "mon" and "sun"
+pub fn parse_week_start(name: &str) -> Option<WeekStart> {
+    let name = name.trim().to_lowercase();
+    WEEK_STARTS
+        .iter()
+        .find(|(week_start, _)| *week_start == name || week_start[..3] == name)
+        .map(|(_, week_start)| *week_start)
+}
+
+// Gets the first date of the week that a date falls in
+pub fn get_week_start_date(date: NaiveDate, week_start: WeekStart) -> NaiveDate {
+    let days = (date.weekday().num_days_from_monday() + 7
+        - week_start.first_day().num_days_from_monday())
+        % 7;
+    date - Duration::days(days as i64)
+}
+
+// Gets the key of the week that a date and time falls in, like "2024-W05"
+// Weeks starting on Sunday take the ISO week of their Monday
+pub fn get_week_key<T: TimeZone>(datetime: &DateTime<T>, week_start: WeekStart) -> String {
+    let date = get_week_start_date(datetime.date_naive(), week_start);
+    let monday = match week_start {
+        WeekStart::Monday => date,
+        WeekStart::Sunday => date + Duration::days(1),
+    };
+    monday.format("%G-W%V").to_string()
+}
+
+// Gets the key of the month that a date and time falls in, like "2024-01"
+pub fn get_month_key<T: TimeZone>(datetime: &DateTime<T>) -> String {
+    datetime.date_naive().format("%Y-%m").to_string()
+}
+
+// Gets the number of days in the month that a date falls in
+pub fn get_days_in_month(date: NaiveDate) -> u32 {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|next_month| next_month.pred_opt())
+        .map(|last_day| last_day.day())
+        .unwrap_or(31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_find_time_zone() {
+        assert!(find_time_zone("Singapore").name().ends_with("Singapore"));
+        assert_eq!(find_time_zone("new york"), Tz::America__New_York);
+        assert_eq!(
+            find_time_zone("bahia_banderas"),
+            Tz::America__Bahia_Banderas
+        );
+        assert!(find_time_zone("UTC").name().ends_with("UTC"));
+        assert_eq!(find_time_zone("atlantis"), Tz::UTC);
+    }
+
+    #[test]
+    fn test_parse_week_start() {
+        assert_eq!(parse_week_start("Monday"), Some(WeekStart::Monday));
+        assert_eq!(parse_week_start("sun"), Some(WeekStart::Sunday));
+        assert_eq!(parse_week_start(" SUNDAY "), Some(WeekStart::Sunday));
+        assert_eq!(parse_week_start("saturday"), None);
+        assert_eq!(parse_week_start(""), None);
+    }
+
+    #[test]
+    fn test_week_start_last_day() {
+        assert_eq!(WeekStart::Monday.last_day(), Weekday::Sun);
+        assert_eq!(WeekStart::Sunday.last_day(), Weekday::Sat);
+    }
+
+    #[test]
+    fn test_get_week_start_date() {
+        // 2024-02-04 is a Sunday
+        let sunday = NaiveDate::from_ymd_opt(2024, 2, 4).unwrap();
+        assert_eq!(
+            get_week_start_date(sunday, WeekStart::Monday),
+            NaiveDate::from_ymd_opt(2024, 1, 29).unwrap()
+        );
+        assert_eq!(get_week_start_date(sunday, WeekStart::Sunday), sunday);
+
+        let saturday = NaiveDate::from_ymd_opt(2024, 2, 3).unwrap();
+        assert_eq!(
+            get_week_start_date(saturday, WeekStart::Sunday),
+            NaiveDate::from_ymd_opt(2024, 1, 28).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_week_key() {
+        // Sunday 2024-02-04, 10:00 UTC
+        let datetime = Utc.with_ymd_and_hms(2024, 2, 4, 10, 0, 0).unwrap();
+        assert_eq!(get_week_key(&datetime, WeekStart::Monday), "2024-W05");
+        assert_eq!(get_week_key(&datetime, WeekStart::Sunday), "2024-W06");
+
+        // Sunday 2023-12-31 starts the first week of 2024
+        let datetime = Utc.with_ymd_and_hms(2023, 12, 31, 10, 0, 0).unwrap();
+        assert_eq!(get_week_key(&datetime, WeekStart::Monday), "2023-W52");
+        assert_eq!(get_week_key(&datetime, WeekStart::Sunday), "2024-W01");
+    }
+
+    #[test]
+    fn test_get_days_in_month() {
+        let days = |year, month, day| {
+            get_days_in_month(NaiveDate::from_ymd_opt(year, month, day).unwrap())
+        };
+        assert_eq!(days(2024, 1, 15), 31);
+        assert_eq!(days(2024, 2, 1), 29);
+        assert_eq!(days(2023, 2, 28), 28);
+        assert_eq!(days(2024, 4, 30), 30);
+        assert_eq!(days(2024, 12, 31), 31);
+    }
+
+    #[test]
+    fn test_get_month_key_time_zone() {
+        // 2024-01-31 20:00 UTC is already February in Singapore
+        let datetime = Utc.with_ymd_and_hms(2024, 1, 31, 20, 0, 0).unwrap();
+        assert_eq!(get_month_key(&datetime), "2024-01");
+
+        let time_zone: Tz = "Asia/Singapore".parse().unwrap();
+        assert_eq!(
+            get_month_key(&datetime.with_timezone(&time_zone)),
+            "2024-02"
+        );
+    }
+}
@@ -1,17 +1,69 @@
-use std::ops::Neg;
+use std::{collections::HashMap, ops::Neg};
+
+use chrono::{Datelike, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
 
 use super::{
-    currency::{convert_currency_with_rate, fetch_currency_conversion},
-    handler::StatementOption,
-    optimizer::optimize_debts,
+    currency::{convert_currency_with_rate, get_currency_from_code, get_rate_cached, Currency},
+    i18n::{
+        Text, ADJUSTMENT_NO_OTHERS, ADJUSTMENT_USER_UNKNOWN, ADJUSTMENT_ZERO, DEBTS_NOT_TOTAL,
+        DISPLAY_NAME_LENGTH, MONEY_CURRENCY_MISMATCH, MONEY_OVERFLOW, PAYMENT_USERS_UNKNOWN,
+        SPLIT_USERS_MISSING, TOPIC_SETTING_UNSUPPORTED,
+    },
+    money::{
+        check_amount, checked_add, checked_neg, checked_sum, saturating_add, Money, MoneyError,
+    },
+    optimizer::{optimize_debts, SettlementStrategy},
+    period::{get_days_in_month, get_month_key},
     redis::{
-        add_payment_entry, delete_payment_entry, get_chat_balances, get_chat_balances_currency,
-        get_chat_payments_details, get_currency_conversion, get_default_currency,
-        get_erase_messages, get_payment_entry, get_time_zone, get_valid_chat_currencies,
-        is_request_limit_exceeded, retrieve_chat_spendings, retrieve_chat_spendings_currency,
-        set_currency_conversion, set_default_currency, set_erase_messages, set_time_zone,
-        update_chat, update_chat_balances, update_chat_spendings, update_payment_entry,
-        update_user, CrudError, Debt, Payment, UserBalance, UserPayment, CURRENCY_CODE_DEFAULT,
+        add_adjustment_entry, add_chat_audit_entry, add_payment_dispute, add_payment_version_entry,
+        add_pending_payment_entry, add_trip_payment_entry, add_tutorial_payment_entry,
+        archive_payment_entries, claim_pending_payment_entry, confirm_pending_payment_entry,
+        delete_acknowledgement_entries, delete_archive_entries, delete_chat_entry,
+        delete_display_name, delete_payment_entries, delete_payment_template, delete_split_group,
+        delete_trip_payment_entry, delete_tutorial_entry, end_trip, export_chat,
+        get_acknowledgement_entries, get_acknowledgement_message_entry, get_admin_only,
+        get_allowed_currencies, get_approval_threshold, get_bot_stats_entry, get_cached_admins,
+        get_cached_bot_can_delete, get_chat_active_trip, get_chat_audit_entries, get_chat_balances,
+        get_chat_ids, get_chat_member_username, get_chat_payments_details, get_chat_trips,
+        get_chat_usernames, get_currency_conversion, get_currency_format, get_data_retention,
+        get_data_retention_chats, get_debt_reminder, get_debt_reminder_chats, get_default_currency,
+        get_display_names, get_dual_entry, get_erase_messages, get_erase_user_messages,
+        get_frequent_currencies, get_language, get_last_archive_month, get_last_cached_rate,
+        get_last_digest_week, get_last_reminder_date, get_last_retention_date,
+        get_latest_payment_entry, get_monthly_archive, get_monthly_archive_chats,
+        get_next_rounding_offset, get_number_format, get_onboarding_step, get_pairwise_debts,
+        get_passive_mode, get_payment_count, get_payment_dispute, get_payment_due_dates,
+        get_payment_entry, get_payment_links, get_payment_note_entry, get_payment_receipt_entry,
+        get_payment_template, get_payment_templates, get_payment_trip_entry,
+        get_payment_version_entries, get_pending_payment_entry, get_private_chats, get_rounding,
+        get_spending_limit_warnings, get_spending_limits, get_split_groups, get_strict_members,
+        get_time_zone, get_topic_default_currency, get_topic_time_zone, get_trash_chat_ids,
+        get_trash_entries, get_treasurer, get_trip_ledger, get_tutorial_ledger,
+        get_user_chat_entries, get_valid_chat_currencies, get_verbosity, get_week_start,
+        get_weekly_digest, get_weekly_digest_chats, get_weekly_spending_cap, import_chat,
+        is_adjustment_entry, is_private_payment_entry, is_request_limit_exceeded,
+        is_transfer_payment_entry, purge_trash_entries, remove_claimed_payment_entry,
+        reset_topic_settings, resolve_payment_dispute, resolve_pending_payment_entry,
+        restore_pending_payment_entry, restore_trash_entry, retrieve_chat_spendings,
+        retrieve_chat_spendings_currency, set_acknowledgement_message_entry, set_admin_only,
+        set_allowed_currencies, set_approval_threshold, set_bot_permission_notified,
+        set_cached_admins, set_cached_bot_can_delete, set_currency_conversion, set_currency_format,
+        set_data_retention, set_debt_reminder, set_default_currency, set_display_name,
+        set_dual_entry, set_erase_messages, set_erase_user_messages, set_language,
+        set_last_archive_month, set_last_digest_week, set_last_reminder_date,
+        set_last_retention_date, set_monthly_archive, set_number_format, set_onboarding_step,
+        set_pairwise_debts, set_passive_mode, set_payment_due_date, set_payment_link,
+        set_payment_note_entry, set_payment_private_entry, set_payment_receipt_entry,
+        set_payment_template, set_private_chat, set_rounding, set_spending_limit, set_split_group,
+        set_strict_members, set_time_zone, set_topic_default_currency, set_topic_time_zone,
+        set_treasurer, set_verbosity, set_week_start, set_weekly_digest, set_weekly_spending_cap,
+        start_trip, update_acknowledgement_entry, update_chat, update_payment_entry,
+        update_payment_rate_entry, update_trip_users, update_user, AuditEntry, BotStats,
+        ChatBackup, CrudError, Debt, LedgerPayment, LedgerStore, Payment, PaymentTemplate,
+        PaymentVersion, PendingPayment, Redis, RedisStore, SpendingLimit, TrashEntry, Trip,
+        UserBalance, UserPayment, WeeklyCap, AUDIT_ADDED, AUDIT_ADJUSTED, AUDIT_DELETED,
+        AUDIT_EDITED, AUDIT_RESTORED, CURRENCY_CODE_DEFAULT,
     },
 };
 
@@ -22,19 +74,53 @@ use super::{
  * and handles exceptions and errors in the back.
  */
 
+const MAX_DISPLAY_NAME_LENGTH: usize = 32;
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+pub const PRIVATE_DESCRIPTION: &str = "🔒 Private payment";
+pub const BOT_STATS_ACTIVE_DAYS: i64 = 7;
+pub const BOT_STATS_CACHE_MINUTES: usize = 10;
+pub const TUTORIAL_DEBTORS: [&str; 2] = ["alice", "bob"];
+const TUTORIAL_DESCRIPTION: &str = "Pizza (practice)";
+
 #[derive(Debug, Clone)]
 pub enum ChatSetting {
     DefaultCurrency(Option<String>),
     CurrencyConversion(Option<bool>),
     EraseMessages(Option<bool>),
+    EraseUserMessages(Option<bool>),
     TimeZone(Option<String>),
+    MonthlyArchive(Option<bool>),
+    WeeklyDigest(Option<Option<String>>),
+    PaymentApproval(Option<Option<f64>>),
+    Language(Option<String>),
+    NumberFormat(Option<String>),
+    CurrencyFormat(Option<String>),
+    DebtReminder(Option<Option<u32>>),
+    DataRetention(Option<Option<u32>>),
+    AdminOnly(Option<bool>),
+    Treasurer(Option<Option<String>>),
+    PairwiseDebts(Option<bool>),
+    PassiveMode(Option<bool>),
+    DualEntry(Option<bool>),
+    StrictMembers(Option<bool>),
+    Verbosity(Option<String>),
+    Rounding(Option<String>),
+    AllowedCurrencies(Option<Vec<String>>),
+    WeekStart(Option<String>),
 }
 
+// Represents a payment added in a batch, with its currency, total, and debts
+pub type BatchPayment = (String, i64, Vec<(String, i64)>);
+
+// Spending of a user, with the amount consumed as their share, and the amount paid.
+// Fronted is the amount paid upfront as the payer of expenses, without paybacks,
+// which is only known when counted from payments, and is 0 otherwise.
 #[derive(Debug, Clone)]
 pub struct UserSpending {
     pub username: String,
     pub spending: i64,
     pub paid: i64,
+    pub fronted: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -44,58 +130,463 @@ pub struct SpendingData {
     pub user_spendings: Vec<UserSpending>,
 }
 
+// Forecast of spendings for the rest of the month, projected from the daily average so far.
+// Limit is the monthly spending limit of a user in the same currency, if any.
+#[derive(Debug, Clone)]
+pub struct SpendingForecast {
+    pub currency: String,
+    pub group_spent: i64,
+    pub group_projected: i64,
+    pub user_forecasts: Vec<UserForecast>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UserForecast {
+    pub username: String,
+    pub spent: i64,
+    pub projected: i64,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MonthlyStats {
+    pub month: String,
+    pub currency: String,
+    pub total: i64,
+    pub count: usize,
+    pub top_spender: UserSpending,
+    pub largest_expense: (String, i64),
+    pub change: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchiveSummary {
+    pub month: String,
+    pub payments: usize,
+    pub debts: Vec<Debt>,
+}
+
+// Payments of a chat due to be cleared by data retention.
+// Expired adjustments are cleared as well, but are left out of the payments and totals,
+// as their balances are carried forward again.
+#[derive(Debug)]
+pub struct CleanupSummary {
+    pub payment_ids: Vec<String>,
+    pub payments: Vec<UserPayment>,
+    pub totals: Vec<(String, i64)>,
+}
+
+// Net debt of a user with a counterpart in a currency, across all chats they share.
+// A positive amount is owed by the user to the counterpart, a negative amount is owed to the user.
+#[derive(Debug, Clone)]
+pub struct ConsolidatedDebt {
+    pub counterpart: String,
+    pub currency: String,
+    pub amount: i64,
+    pub chats: usize,
+}
+
+// Rankings of the members of a chat over a period, each sorted from first place.
+// Amounts paid are ranked per currency, and settle times are in seconds.
+#[derive(Debug, Clone, Default)]
+pub struct Leaderboard {
+    pub payments: usize,
+    pub top_payers: Vec<(String, Vec<(String, i64)>)>,
+    pub frequent_debtors: Vec<(String, i64)>,
+    pub fastest_settlers: Vec<(String, i64)>,
+}
+
+// Portion of a user's balance and spending in a currency, from payments with locked rates.
+// Holds both the original amounts, and the amounts converted with the locked rates.
+#[derive(Debug, Clone)]
+struct LockedAmount {
+    username: String,
+    currency: String,
+    balance: i64,
+    converted_balance: i64,
+    spending: i64,
+    converted_spending: i64,
+}
+
 #[derive(thiserror::Error, Debug, PartialEq)]
 pub enum ProcessError {
     #[error("{0}")]
     CrudError(CrudError),
+    #[error("Validation error: {0}")]
+    ValidationError(Text),
+    #[error("Rate limit exceeded")]
+    RateLimitError(),
+    #[error("Currency conversion error: {0}")]
+    ConversionError(String),
+}
+
+// Implement the From trait to convert from MoneyError to ProcessError
+// Amounts out of bounds come from what users give, so they are shown to them
+impl From<MoneyError> for ProcessError {
+    fn from(money_error: MoneyError) -> ProcessError {
+        let reason = match money_error {
+            MoneyError::OverflowError() => Text::new(MONEY_OVERFLOW),
+            MoneyError::CurrencyMismatchError(first, second) => Text::new(MONEY_CURRENCY_MISMATCH)
+                .arg("first", first)
+                .arg("second", second),
+        };
+        ProcessError::ValidationError(reason)
+    }
 }
 
 // Implement the From trait to convert from CrudError to ProcessError
+// Amounts out of bounds are shown to users, the same as when checked here
 impl From<CrudError> for ProcessError {
     fn from(crud_error: CrudError) -> ProcessError {
-        ProcessError::CrudError(crud_error)
+        match crud_error {
+            CrudError::MoneyError(money_error) => money_error.into(),
+            crud_error => ProcessError::CrudError(crud_error),
+        }
     }
 }
 
+// Option of how to show balances and debts, in a currency or converted to one
+#[derive(PartialEq, Debug, Clone)]
+pub enum StatementOption {
+    Currency(String),
+    ConvertCurrency,
+    ConvertTo(String),
+}
+
 /* Utility functions */
 pub fn is_username_equal(first: &str, second: &str) -> bool {
     first.to_lowercase() == second.to_lowercase()
 }
 
-fn auto_update_user(
+// Description of a payback entry, used to tell paybacks apart from expenses
+pub fn payback_description(username: &str) -> String {
+    format!("@{} paid back!", username)
+}
+
+// Checks if a payment is a payback entry, made by its creditor to pay off debts
+fn is_payback(payment: &Payment) -> bool {
+    payment.description == payback_description(&payment.creditor)
+}
+
+// Checks that the total and debts of a payment are within bounds, and add up without overflowing
+fn validate_amounts(total: i64, debts: &[(String, i64)]) -> Result<(), ProcessError> {
+    check_amount(total)?;
+    checked_sum(debts.iter().map(|(_, amount)| *amount))?;
+    Ok(())
+}
+
+// Checks that a payment is valid, with debts that sum up to its total
+fn validate_payment(
+    currency: &str,
+    total: i64,
+    debts: &Vec<(String, i64)>,
+) -> Result<(), ProcessError> {
+    if debts.is_empty() {
+        return Err(ProcessError::ValidationError(Text::new(
+            SPLIT_USERS_MISSING,
+        )));
+    }
+
+    let total = Money::new(currency, total)?;
+    let mut sum = Money::new(currency, 0)?;
+    for (_, amount) in debts {
+        sum = sum.checked_add(&Money::new(currency, *amount)?)?;
+    }
+    if sum != total {
+        return Err(ProcessError::ValidationError(Text::new(DEBTS_NOT_TOTAL)));
+    }
+    Ok(())
+}
+
+async fn auto_update_user(
+    redis: &Redis,
     chat_id: &str,
     sender_id: &str,
     sender_username: Option<&str>,
 ) -> Result<(), ProcessError> {
     if let Some(username) = sender_username {
-        update_user(&username, chat_id, Some(sender_id))?;
+        update_user(redis, username, chat_id, Some(sender_id)).await?;
     }
     Ok(())
 }
 
-fn update_balances(chat_id: &str, changes: Vec<UserBalance>) -> Result<(), ProcessError> {
+async fn update_balances(
+    redis: &Redis,
+    chat_id: &str,
+    changes: Vec<UserBalance>,
+) -> Result<(), ProcessError> {
     // Update balances
-    update_chat_balances(chat_id, changes)?;
-    Ok(())
+    update_ledger_balances(&RedisStore::new(redis), chat_id, changes, Vec::new()).await
 }
 
 // Utility function required by many commands.
 // Updates the balances based on changes that are given.
 // Returns the debts for the relevant currency.
 async fn update_balances_debts(
+    redis: &Redis,
     chat_id: &str,
     changes: Vec<UserBalance>,
     currency: StatementOption,
 ) -> Result<Vec<Debt>, ProcessError> {
-    update_balances(chat_id, changes)?;
+    let fallback_currency = changes.first().map(|change| change.currency.clone());
+    update_balances(redis, chat_id, changes).await?;
+    retrieve_updated_debts(redis, chat_id, currency, fallback_currency).await
+}
+
+// Retrieves the debts of a group chat after its balances are updated.
+// The changes are already saved, so if rates are unavailable,
+// the balances are shown in the fallback currency instead.
+async fn retrieve_updated_debts(
+    redis: &Redis,
+    chat_id: &str,
+    currency: StatementOption,
+    fallback_currency: Option<String>,
+) -> Result<Vec<Debt>, ProcessError> {
+    match (
+        retrieve_debts(redis, chat_id, currency).await,
+        fallback_currency,
+    ) {
+        (Err(ProcessError::ConversionError(err)), Some(fallback_currency)) => {
+            log::error!("Error converting balances for chat {chat_id}: {err}");
+            retrieve_debts(redis, chat_id, StatementOption::Currency(fallback_currency)).await
+        }
+        (debts, _) => debts,
+    }
+}
+
+// Gets the balance changes of a payment, crediting its creditor and debiting its debtors
+fn get_payment_changes(payment: &Payment) -> Result<Vec<UserBalance>, ProcessError> {
+    let mut changes: Vec<UserBalance> = payment
+        .debts
+        .iter()
+        .map(|(user, amount)| {
+            Ok(UserBalance {
+                username: user.to_string(),
+                currency: payment.currency.clone(),
+                balance: checked_neg(*amount)?,
+            })
+        })
+        .collect::<Result<_, MoneyError>>()?;
 
-    let debts = retrieve_debts(chat_id, currency).await?;
+    changes.push(UserBalance {
+        username: payment.creditor.clone(),
+        currency: payment.currency.clone(),
+        balance: check_amount(payment.total)?,
+    });
+    Ok(changes)
+}
 
-    Ok(debts)
+// Gets the spendings of a payment, being the share of each of its debtors
+fn get_payment_spendings(payment: &Payment) -> Vec<UserBalance> {
+    payment
+        .debts
+        .iter()
+        .map(|(user, amount)| UserBalance {
+            username: user.to_string(),
+            currency: payment.currency.clone(),
+            balance: *amount,
+        })
+        .collect()
+}
+
+// Negates balance changes or spendings, to undo them
+fn negate_amounts(amounts: Vec<UserBalance>) -> Result<Vec<UserBalance>, ProcessError> {
+    Ok(amounts
+        .into_iter()
+        .map(|amount| {
+            Ok(UserBalance {
+                balance: checked_neg(amount.balance)?,
+                ..amount
+            })
+        })
+        .collect::<Result<_, MoneyError>>()?)
+}
+
+// Checks that changes in balances, applied in order, keep every balance of a ledger within MAX_VALUE
+async fn check_ledger_balances<S: LedgerStore>(
+    store: &S,
+    ledger: &str,
+    changes: &[UserBalance],
+) -> Result<(), ProcessError> {
+    let mut currencies: Vec<&str> = Vec::new();
+    let mut balances: Vec<UserBalance> = Vec::new();
+    for change in changes {
+        if !currencies.contains(&change.currency.as_str()) {
+            currencies.push(&change.currency);
+            balances.extend(store.get_balances(ledger, &change.currency).await?);
+        }
+    }
+
+    for change in changes {
+        match balances.iter_mut().find(|bal| {
+            bal.currency == change.currency && is_username_equal(&bal.username, &change.username)
+        }) {
+            Some(balance) => balance.balance = checked_add(balance.balance, change.balance)?,
+            None => balances.push(UserBalance {
+                balance: check_amount(change.balance)?,
+                ..change.clone()
+            }),
+        }
+    }
+    Ok(())
+}
+
+// Writes changes in balances and spendings to a ledger, in a ledger store, without checking them.
+// Spendings go first, so that balances are untouched if a spending would become negative.
+async fn write_ledger_amounts<S: LedgerStore>(
+    store: &S,
+    ledger: &str,
+    changes: Vec<UserBalance>,
+    spendings: Vec<UserBalance>,
+) -> Result<(), ProcessError> {
+    if !spendings.is_empty() {
+        store.update_spendings(ledger, spendings).await?;
+    }
+    store.update_balances(ledger, changes).await?;
+    Ok(())
+}
+
+// Applies changes in balances and spendings to a ledger, in a ledger store.
+// Balances are checked first, so that nothing is written if one would go beyond MAX_VALUE.
+async fn update_ledger_balances<S: LedgerStore>(
+    store: &S,
+    ledger: &str,
+    changes: Vec<UserBalance>,
+    spendings: Vec<UserBalance>,
+) -> Result<(), ProcessError> {
+    check_ledger_balances(store, ledger, &changes).await?;
+    write_ledger_amounts(store, ledger, changes, spendings).await
+}
+
+// Adds a payment to a group chat in a ledger store, updating its balances and spendings.
+// Transfers count towards balances only.
+// Everything is checked before the payment is added, so that no payment is left without its balances.
+// Returns the ID of the payment, with the changes to balances and spendings made.
+async fn add_ledger_payment<S: LedgerStore>(
+    store: &S,
+    chat_id: &str,
+    payment: &Payment,
+    is_transfer: bool,
+) -> Result<(String, Vec<UserBalance>, Vec<UserBalance>), ProcessError> {
+    let changes = get_payment_changes(payment)?;
+    let spendings = if is_transfer {
+        Vec::new()
+    } else {
+        get_payment_spendings(payment)
+    };
+    check_ledger_balances(store, chat_id, &changes).await?;
+
+    let payment_id = store.add_payment(chat_id, payment, is_transfer).await?;
+    write_ledger_amounts(store, chat_id, changes.clone(), spendings.clone()).await?;
+
+    Ok((payment_id, changes, spendings))
+}
+
+// Edits a payment of a group chat in a ledger store, updating its balances and spendings.
+// The changes undo the previous payment, then apply the new one.
+// Adjustments and transfers do not count towards spendings.
+// Everything is checked before the payment is updated, so that it is never left without its balances.
+// Returns the changes to balances and spendings made.
+async fn apply_ledger_edit<S: LedgerStore>(
+    store: &S,
+    chat_id: &str,
+    payment_id: &str,
+    previous: &LedgerPayment,
+    payment: &Payment,
+    is_transfer: bool,
+) -> Result<(Vec<UserBalance>, Vec<UserBalance>), ProcessError> {
+    let mut changes = negate_amounts(get_payment_changes(&previous.payment)?)?;
+    let mut spendings: Vec<UserBalance> = Vec::new();
+    if !previous.adjustment && !previous.transfer {
+        spendings = negate_amounts(get_payment_spendings(&previous.payment))?;
+    }
+    changes.extend(get_payment_changes(payment)?);
+    if !previous.adjustment && !is_transfer {
+        spendings.extend(get_payment_spendings(payment));
+    }
+    check_ledger_balances(store, chat_id, &changes).await?;
+
+    store
+        .update_payment(payment_id, payment, is_transfer)
+        .await?;
+    write_ledger_amounts(store, chat_id, changes.clone(), spendings.clone()).await?;
+
+    Ok((changes, spendings))
+}
+
+// Moves a payment of a group chat to the trash, in a ledger store.
+// Returns the payment, with the changes to balances and spendings that undo it.
+// The changes are left to the caller to apply.
+async fn trash_ledger_payment<S: LedgerStore>(
+    store: &S,
+    chat_id: &str,
+    payment_id: &str,
+    datetime: &str,
+) -> Result<(Payment, Vec<UserBalance>, Vec<UserBalance>), ProcessError> {
+    let entry = store.get_payment(payment_id).await?;
+    store.delete_payment(chat_id, payment_id, datetime).await?;
+
+    // Adjustments and transfers do not count towards spendings
+    let changes = negate_amounts(get_payment_changes(&entry.payment)?)?;
+    let mut spendings: Vec<UserBalance> = Vec::new();
+    if !entry.adjustment && !entry.transfer {
+        spendings = negate_amounts(get_payment_spendings(&entry.payment))?;
+    }
+
+    Ok((entry.payment, changes, spendings))
+}
+
+// Optimizes the debts of a ledger for a currency, from its balances in a ledger store
+async fn optimize_ledger_debts<S: LedgerStore>(
+    store: &S,
+    ledger_id: &str,
+    currency: &str,
+    strategy: &SettlementStrategy,
+) -> Result<Vec<Debt>, ProcessError> {
+    let balances = store.get_balances(ledger_id, currency).await?;
+    Ok(optimize_debts(balances, strategy))
+}
+
+// Finds the users not yet in a chat, of those given, each listed once.
+// The message sender is left out, as they are in the chat by sending the message.
+fn find_unknown_users(known: &[String], sender_username: &str, users: &[String]) -> Vec<String> {
+    let mut unknown_users: Vec<String> = Vec::new();
+    for user in users {
+        if is_username_equal(user, sender_username)
+            || known
+                .iter()
+                .any(|known_user| is_username_equal(known_user, user))
+            || unknown_users
+                .iter()
+                .any(|unknown_user| is_username_equal(unknown_user, user))
+        {
+            continue;
+        }
+        unknown_users.push(user.to_string());
+    }
+    unknown_users
+}
+
+// Finds the users not yet in a chat, of those given, if the chat has strict members on.
+// With strict members off, any user may be added, so none are returned.
+async fn get_unknown_users(
+    redis: &Redis,
+    chat_id: &str,
+    sender_username: &str,
+    users: &[String],
+) -> Result<Vec<String>, ProcessError> {
+    if !get_strict_members(redis, chat_id).await? {
+        return Ok(Vec::new());
+    }
+    let known = get_chat_usernames(redis, chat_id).await?;
+    Ok(find_unknown_users(&known, sender_username, users))
 }
 
 // Updates users and chat given payment details
-fn update_users_chat(
+// With strict members, payments with users not yet in the chat are rejected,
+// so that a typo in a username doesn't create a new user.
+async fn update_users_chat(
+    redis: &Redis,
     chat_id: &str,
     sender_username: &str,
     sender_id: &str,
@@ -117,6 +608,17 @@ fn update_users_chat(
         }
     }
 
+    let unknown_users = get_unknown_users(redis, chat_id, sender_username, &all_users).await?;
+    if !unknown_users.is_empty() {
+        let unknown_users: Vec<String> = unknown_users
+            .iter()
+            .map(|user| format!("@{}", user.trim_start_matches('@')))
+            .collect();
+        return Err(ProcessError::ValidationError(
+            Text::new(PAYMENT_USERS_UNKNOWN).arg("users", unknown_users.join(", ")),
+        ));
+    }
+
     // Update all users included in payment
     let mut is_sender_included = false;
     for user in all_users.iter() {
@@ -124,894 +626,5168 @@ fn update_users_chat(
             is_sender_included = true;
             continue;
         }
-        update_user(user, chat_id, None)?;
+        update_user(redis, user, chat_id, None).await?;
     }
 
     // Add message sender to the list of users
-    update_user(sender_username, chat_id, Some(sender_id))?;
+    update_user(redis, sender_username, chat_id, Some(sender_id)).await?;
     if !is_sender_included {
         all_users.push(sender_username.to_string());
     }
 
     // Update chat
-    update_chat(&chat_id, all_users)?;
+    update_chat(redis, chat_id, all_users).await?;
 
     Ok(())
 }
 
-pub fn init_chat_config(chat_id: &str) -> Result<(), ProcessError> {
-    update_chat(chat_id, Vec::new())?;
+// Locks the conversion rate of a payment into the default currency, at the current live rate.
+// A rate given for the payment, such as the rate actually charged, is locked instead.
+// Only applies if currency conversion is enabled, else any previous rate is removed,
+// though a given rate is still kept, to be used once conversion is enabled.
+// If the rate cannot be fetched, the payment falls back to live rates whenever it is converted.
+async fn lock_payment_rate(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+    currency: &str,
+    rate: Option<f64>,
+) -> Result<(), ProcessError> {
+    let conversion = get_currency_conversion(redis, chat_id).await?;
+    let default_currency = get_default_currency(redis, chat_id).await?;
+    if currency == default_currency
+        || currency == CURRENCY_CODE_DEFAULT
+        || default_currency == CURRENCY_CODE_DEFAULT
+    {
+        update_payment_rate_entry(redis, payment_id, None).await?;
+        return Ok(());
+    }
+    if let Some(rate) = rate {
+        update_payment_rate_entry(redis, payment_id, Some(&(default_currency, rate))).await?;
+        return Ok(());
+    }
+    if !conversion {
+        update_payment_rate_entry(redis, payment_id, None).await?;
+        return Ok(());
+    }
+
+    let rate = match get_rate_cached(redis, currency, &default_currency).await {
+        Ok(rate) => Some((default_currency, rate)),
+        Err(err) => {
+            log::error!(
+                "Error fetching currency conversion from {currency} to {default_currency}: {}",
+                err
+            );
+            None
+        }
+    };
+    update_payment_rate_entry(redis, payment_id, rate.as_ref()).await?;
+
     Ok(())
 }
 
-/* Retrieves all valid currencies for a chat.
- * Valid currencies are currencies with some payments.
- */
-pub fn retrieve_valid_currencies(chat_id: &str) -> Result<Vec<String>, ProcessError> {
-    let currencies = get_valid_chat_currencies(chat_id)?;
-    Ok(currencies)
+// Gets the currency that amounts in a currency are actually in,
+// which is the default currency for amounts without currency
+fn get_source_currency<'a>(currency: &'a str, default_currency: &'a str) -> &'a str {
+    if currency == CURRENCY_CODE_DEFAULT {
+        default_currency
+    } else {
+        currency
+    }
 }
 
-/* Add a new payment entry in a group chat.
- * Execution flow: Updates relevant users, updates chat.
- * Adds payment entry, updates balances, updates group debts.
- * Important: assumes that debts sum up to total. Creditor's share included.
- */
-pub async fn add_payment(
-    chat_id: String,
-    sender_username: String,
-    sender_id: String,
-    datetime: String,
-    description: &str,
-    creditor: &str,
-    currency: &str,
-    total: i64,
-    debts: Vec<(String, i64)>,
-) -> Result<Vec<Debt>, ProcessError> {
-    // Update users and chat
-    update_users_chat(
-        &chat_id,
-        &sender_username,
-        &sender_id,
-        Some(creditor),
-        Some(debts.clone()),
-    )?;
-
-    // Add payment entry
-    let payment = Payment {
-        description: description.to_string(),
-        datetime,
-        creditor: creditor.to_string(),
-        currency: currency.to_string(),
-        total,
-        debts: debts.clone(),
+// Retrieves the balances and spendings of a chat that come from payments with locked rates.
+// Only rates into the current default currency are used, as the rest cannot be applied.
+async fn retrieve_locked_amounts(
+    redis: &Redis,
+    chat_id: &str,
+    default_currency: &str,
+) -> Result<Vec<LockedAmount>, ProcessError> {
+    let payments = match get_chat_payments_details(redis, chat_id).await {
+        Ok(payments) => payments,
+        Err(CrudError::NoPaymentsError()) => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
     };
-    add_payment_entry(&chat_id, &payment)?;
 
-    // Update spendings
-    let spendings: Vec<UserBalance> = debts
-        .iter()
-        .map(|(user, amount)| UserBalance {
-            username: user.to_string(),
-            currency: currency.to_string(),
-            balance: *amount,
-        })
-        .collect();
-    update_chat_spendings(&chat_id, spendings)?;
+    let mut locked_amounts: Vec<LockedAmount> = Vec::new();
+    for user_payment in payments {
+        let rate = match &user_payment.rate {
+            Some((currency, rate)) if currency == default_currency => *rate,
+            _ => continue,
+        };
 
-    // Update balances
-    let mut changes: Vec<UserBalance> = debts
-        .iter()
-        .map(|(user, amount)| UserBalance {
-            username: user.to_string(),
-            currency: currency.to_string(),
-            balance: amount.neg(),
-        })
-        .collect();
+        let payment = user_payment.payment;
+        let mut changes: Vec<(String, i64, i64)> = payment
+            .debts
+            .iter()
+            .map(|(user, amount)| (user.to_string(), amount.neg(), *amount))
+            .collect();
+        changes.push((payment.creditor.clone(), payment.total, 0));
 
-    changes.push(UserBalance {
-        username: creditor.to_string(),
-        currency: currency.to_string(),
-        balance: total,
-    });
+        for (username, balance, spending) in changes {
+            let converted_balance =
+                convert_currency_with_rate(balance, &payment.currency, default_currency, rate);
+            let converted_spending =
+                convert_currency_with_rate(spending, &payment.currency, default_currency, rate);
 
-    let conversion = get_currency_conversion(&chat_id)?;
-    let option = if conversion {
-        StatementOption::ConvertCurrency
-    } else {
-        StatementOption::Currency(currency.to_string())
-    };
+            let user = locked_amounts.iter().position(|locked| {
+                locked.username == username && locked.currency == payment.currency
+            });
+            match user {
+                Some(index) => {
+                    let locked = &mut locked_amounts[index];
+                    locked.balance = saturating_add(locked.balance, balance);
+                    locked.converted_balance =
+                        saturating_add(locked.converted_balance, converted_balance);
+                    locked.spending = saturating_add(locked.spending, spending);
+                    locked.converted_spending =
+                        saturating_add(locked.converted_spending, converted_spending);
+                }
+                None => {
+                    locked_amounts.push(LockedAmount {
+                        username,
+                        currency: payment.currency.clone(),
+                        balance,
+                        converted_balance,
+                        spending,
+                        converted_spending,
+                    });
+                }
+            }
+        }
+    }
 
-    update_balances_debts(&chat_id, changes, option).await
+    Ok(locked_amounts)
 }
 
-/* View all payment entries of a group chat.
- * Execution flow: Retrieve chat payment details.
- * Called only once per command. Pagination handled by Handler.
- */
-pub fn view_payments(
-    chat_id: &str,
-    sender_id: &str,
-    sender_username: Option<&str>,
-) -> Result<Vec<UserPayment>, ProcessError> {
-    auto_update_user(chat_id, sender_id, sender_username)?;
+// Converts an amount, given the portion of it that was already converted with locked rates.
+// The remaining portion is converted with the live rate. Settled amounts stay settled.
+fn convert_currency_with_locked(
+    amount: i64,
+    locked: (i64, i64),
+    currency_from: &str,
+    currency_to: &str,
+    conversion_rate: f64,
+) -> i64 {
+    if amount == 0 {
+        return 0;
+    }
+    let (locked_amount, locked_converted) = locked;
+    saturating_add(
+        locked_converted,
+        convert_currency_with_rate(
+            amount - locked_amount,
+            currency_from,
+            currency_to,
+            conversion_rate,
+        ),
+    )
+}
 
-    let payments = get_chat_payments_details(&chat_id)?;
-    Ok(payments)
+// Finds the locked amount of a user in a currency, if any.
+fn find_locked_amount<'a>(
+    locked_amounts: &'a [LockedAmount],
+    username: &str,
+    currency: &str,
+) -> Option<&'a LockedAmount> {
+    locked_amounts
+        .iter()
+        .find(|locked| locked.username == username && locked.currency == currency)
 }
 
-/* Edit a payment entry in a group chat.
- * Execution flow: Edit payment entry.
- * Update balances, update group debts.
- * Has to be called after self::view_payments.
- */
-pub async fn edit_payment(
+// Applies changes in balances and spendings to the ledger of a trip.
+// Users with changes are also added to the trip.
+async fn update_trip_balances(
+    redis: &Redis,
     chat_id: &str,
-    sender_username: String,
-    sender_id: String,
-    payment_id: &str,
-    description: Option<&str>,
-    creditor: Option<&str>,
-    currency: Option<&str>,
-    total: Option<&i64>,
-    debts: Option<Vec<(String, i64)>>,
-) -> Result<Option<Vec<Debt>>, ProcessError> {
-    // Get current payment entry
-    let current_payment = get_payment_entry(payment_id)?;
-
-    // Update users and chat
-    update_users_chat(
-        &chat_id,
-        &sender_username,
-        &sender_id,
-        creditor,
-        debts.clone(),
-    )?;
-
-    // Edit payment entry
-    update_payment_entry(
-        payment_id,
-        description,
-        creditor,
-        currency,
-        total,
-        debts.clone(),
-    )?;
-
-    // Update balances in two stages: first undo the previous payment, then set the new one
-    if creditor.is_some() || total.is_some() || debts.is_some() {
-        // First round of update
-        let prev_creditor = &current_payment.creditor;
-        let prev_currency = &current_payment.currency;
-        let mut prev_changes: Vec<UserBalance> = current_payment
-            .debts
-            .iter()
-            .map(|debt| UserBalance {
-                username: debt.0.to_string(),
-                currency: prev_currency.to_string(),
-                balance: debt.1,
-            })
-            .collect();
-        prev_changes.push(UserBalance {
-            username: prev_creditor.to_string(),
-            currency: prev_currency.to_string(),
-            balance: current_payment.total.neg(),
-        });
-        update_chat_balances(&chat_id, prev_changes)?;
+    trip_id: &str,
+    changes: Vec<UserBalance>,
+    spendings: Vec<UserBalance>,
+) -> Result<(), ProcessError> {
+    let usernames = changes
+        .iter()
+        .map(|change| change.username.to_string())
+        .collect();
+    update_trip_users(redis, chat_id, trip_id, usernames).await?;
 
-        // Update spendings as well
-        let prev_spendings: Vec<UserBalance> = current_payment
-            .debts
-            .iter()
-            .map(|debt| UserBalance {
-                username: debt.0.to_string(),
-                currency: prev_currency.to_string(),
-                balance: debt.1.neg(),
-            })
-            .collect();
-        update_chat_spendings(&chat_id, prev_spendings)?;
+    let ledger = get_trip_ledger(chat_id, trip_id);
+    update_ledger_balances(&RedisStore::new(redis), &ledger, changes, spendings).await
+}
 
-        // Second round of update
-        let mut changes: Vec<UserBalance> = debts
-            .clone()
-            .unwrap_or(current_payment.debts.clone())
-            .iter()
-            .map(|debt| UserBalance {
-                username: debt.0.to_string(),
-                currency: currency.unwrap_or(prev_currency).to_string(),
-                balance: debt.1.neg(),
-            })
-            .collect();
-        changes.push(UserBalance {
-            username: creditor.unwrap_or(&current_payment.creditor).to_string(),
-            currency: currency.unwrap_or(prev_currency).to_string(),
-            balance: *total.unwrap_or(&current_payment.total),
-        });
+pub async fn init_chat_config(redis: &Redis, chat_id: &str) -> Result<(), ProcessError> {
+    update_chat(redis, chat_id, Vec::new()).await?;
+    Ok(())
+}
 
-        // Update spendings as well
-        let new_spendings: Vec<UserBalance> = debts
-            .unwrap_or(current_payment.debts)
-            .iter()
-            .map(|debt| UserBalance {
-                username: debt.0.to_string(),
-                currency: currency.unwrap_or(prev_currency).to_string(),
-                balance: debt.1,
-            })
-            .collect();
-        update_chat_spendings(&chat_id, new_spendings)?;
+/* Retrieves the usernames of all users in a chat.
+ */
+pub async fn retrieve_chat_users(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<String>, ProcessError> {
+    let users = get_chat_usernames(redis, chat_id).await?;
+    Ok(users)
+}
 
-        let conversion = get_currency_conversion(&chat_id)?;
-        let option = if conversion {
-            StatementOption::ConvertCurrency
-        } else {
-            StatementOption::Currency(currency.unwrap_or(prev_currency).to_string())
-        };
+/* Retrieves the users of a payment that are not yet in a chat, if the chat has strict members on.
+ * The message sender is left out, as they are in the chat by sending the message.
+ * Returns an empty list if strict members is off, as any user may then be added.
+ */
+pub async fn retrieve_unknown_users(
+    redis: &Redis,
+    chat_id: &str,
+    sender_username: &str,
+    creditor: &str,
+    debts: &[(String, i64)],
+) -> Result<Vec<String>, ProcessError> {
+    let mut users = vec![creditor.to_string()];
+    users.extend(debts.iter().map(|(user, _)| user.to_string()));
+    get_unknown_users(redis, chat_id, sender_username, &users).await
+}
 
-        let res = update_balances_debts(&chat_id, changes, option).await?;
-        return Ok(Some(res));
+/* Adds users to a chat explicitly, such as those not yet in a chat with strict members on.
+ */
+pub async fn add_chat_users(
+    redis: &Redis,
+    chat_id: &str,
+    usernames: Vec<String>,
+) -> Result<(), ProcessError> {
+    for username in usernames.iter() {
+        update_user(redis, username, chat_id, None).await?;
     }
-
-    Ok(None)
+    update_chat(redis, chat_id, usernames).await?;
+    Ok(())
 }
 
-/* Delete a payment entry in a group chat.
- * Execution flow: Delete payment entry.
- * Update balances, update group debts.
- * Has to be called after self::view_payments.
+/* Retrieves the username of a user in a chat, if they are in it.
+ * Matches by Telegram user ID first, then by username.
  */
-pub async fn delete_payment(chat_id: &str, payment_id: &str) -> Result<Vec<Debt>, ProcessError> {
-    // Get payment entry
-    let payment = get_payment_entry(payment_id)?;
-
-    // Delete payment entry
-    delete_payment_entry(&chat_id, payment_id)?;
-
-    // Update spendings
-    let spendings: Vec<UserBalance> = payment
-        .debts
-        .iter()
-        .map(|debt| UserBalance {
-            username: debt.0.to_string(),
-            currency: payment.currency.clone(),
-            balance: debt.1.neg(),
-        })
-        .collect();
-    update_chat_spendings(&chat_id, spendings)?;
-
-    // Update balances
-    let mut changes: Vec<UserBalance> = payment
-        .debts
-        .iter()
-        .map(|debt| UserBalance {
-            username: debt.0.to_string(),
-            currency: payment.currency.clone(),
-            balance: debt.1,
-        })
-        .collect();
-    changes.push(UserBalance {
-        username: payment.creditor,
-        currency: payment.currency.clone(),
-        balance: payment.total.neg(),
-    });
-
-    let conversion = get_currency_conversion(&chat_id)?;
-    let option = if conversion {
-        StatementOption::ConvertCurrency
-    } else {
-        StatementOption::Currency(payment.currency.clone())
-    };
+pub async fn retrieve_chat_member(
+    redis: &Redis,
+    chat_id: &str,
+    user_id: &str,
+    username: Option<&str>,
+) -> Result<Option<String>, ProcessError> {
+    let username = get_chat_member_username(redis, chat_id, user_id, username).await?;
+    Ok(username)
+}
 
-    update_balances_debts(&chat_id, changes, option).await
+/* Retrieves all valid currencies for a chat.
+ * Valid currencies are currencies with some payments.
+ */
+pub async fn retrieve_valid_currencies(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<String>, ProcessError> {
+    let currencies = get_valid_chat_currencies(redis, chat_id).await?;
+    Ok(currencies)
 }
 
-/* View balances of a group chat.
- * Takes in a specification of the options for viewing.
- * Which is whether the currency is to be converted, and which currency.
+/* Retrieves the currencies most often used for payments in a chat.
+ * Returns up to the given number of currencies, from the most frequently used.
  */
-pub async fn retrieve_debts(
+pub async fn retrieve_frequent_currencies(
+    redis: &Redis,
     chat_id: &str,
-    option: StatementOption,
-) -> Result<Vec<Debt>, ProcessError> {
-    match option {
-        StatementOption::Currency(currency) => retrieve_debts_by_currency(chat_id, &currency),
-        StatementOption::ConvertCurrency => retrieve_debts_converted(chat_id).await,
-    }
+    count: usize,
+) -> Result<Vec<String>, ProcessError> {
+    let currencies = get_frequent_currencies(redis, chat_id, count).await?;
+    Ok(currencies)
 }
 
-/* View debts of a group chat for a specific currency.
- * Retrieves all balances, optimizes debts, and returns.
+/* Retrieves all valid currencies for a trip in a chat.
+ * Valid currencies are currencies with some payments in the trip.
  */
-fn retrieve_debts_by_currency(chat_id: &str, currency: &str) -> Result<Vec<Debt>, ProcessError> {
-    let default_currency = match get_chat_setting(chat_id, ChatSetting::DefaultCurrency(None))? {
-        ChatSetting::DefaultCurrency(Some(curr)) => curr,
-        _ => CURRENCY_CODE_DEFAULT.to_string(),
-    };
+pub async fn retrieve_trip_valid_currencies(
+    redis: &Redis,
+    chat_id: &str,
+    trip_id: &str,
+) -> Result<Vec<String>, ProcessError> {
+    let currencies = get_valid_chat_currencies(redis, &get_trip_ledger(chat_id, trip_id)).await?;
+    Ok(currencies)
+}
 
-    // If currency is NIL, which is not default currency.
-    if currency == CURRENCY_CODE_DEFAULT && default_currency != CURRENCY_CODE_DEFAULT {
-        return retrieve_debts_by_default_currency(chat_id);
-    }
+/* Starts a trip in a group chat, under which new payments are also tracked.
+ * If a trip of the same name already exists, it is resumed instead.
+ */
+pub async fn start_chat_trip(
+    redis: &Redis,
+    chat_id: &str,
+    name: &str,
+) -> Result<Trip, ProcessError> {
+    let trip = start_trip(redis, chat_id, name).await?;
+    Ok(trip)
+}
 
-    // If currency is default currency, which is not NIL.
-    if default_currency == currency && currency != CURRENCY_CODE_DEFAULT {
-        return retrieve_debts_by_default_currency(chat_id);
-    }
+/* Ends the active trip in a group chat.
+ * Returns the trip ended, if there was one.
+ */
+pub async fn end_chat_trip(redis: &Redis, chat_id: &str) -> Result<Option<Trip>, ProcessError> {
+    let trip = end_trip(redis, chat_id).await?;
+    Ok(trip)
+}
 
-    // If currency is not NIL, and is not default currency.
-    // Also, if currency is NIL, and NIL is default currency.
-    let balances = get_chat_balances_currency(chat_id, currency)?;
-    let debts = optimize_debts(balances);
+/* Retrieves the active trip in a group chat, if any.
+ */
+pub async fn retrieve_active_trip(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<Trip>, ProcessError> {
+    let trip = get_chat_active_trip(redis, chat_id).await?;
+    Ok(trip)
+}
 
-    Ok(debts)
+/* Retrieves all trips in a group chat.
+ */
+pub async fn retrieve_trips(redis: &Redis, chat_id: &str) -> Result<Vec<Trip>, ProcessError> {
+    let trips = get_chat_trips(redis, chat_id).await?;
+    Ok(trips)
 }
 
-/* View debts of a group chat for the default currency.
- * Retrieves all balances, optimizes debts, and returns.
+/* Retrieves all payments of a trip in a group chat, with their details.
+ * Payments are given under the chat, so that they are displayed with its settings.
  */
-fn retrieve_debts_by_default_currency(chat_id: &str) -> Result<Vec<Debt>, ProcessError> {
-    let currency = match get_chat_setting(chat_id, ChatSetting::DefaultCurrency(None))? {
-        ChatSetting::DefaultCurrency(Some(curr)) => curr,
-        _ => CURRENCY_CODE_DEFAULT.to_string(),
+pub async fn retrieve_trip_payments(
+    redis: &Redis,
+    chat_id: &str,
+    trip_id: &str,
+) -> Result<Vec<UserPayment>, ProcessError> {
+    let payments = match get_chat_payments_details(redis, &get_trip_ledger(chat_id, trip_id)).await
+    {
+        Ok(payments) => payments,
+        Err(CrudError::NoPaymentsError()) => Vec::new(),
+        Err(err) => return Err(err.into()),
     };
-    let mut balances_curr = get_chat_balances_currency(chat_id, &currency)?;
-    let balances_nil = get_chat_balances_currency(chat_id, CURRENCY_CODE_DEFAULT)?;
+    Ok(payments
+        .into_iter()
+        .map(|payment| UserPayment {
+            chat_id: chat_id.to_string(),
+            ..payment
+        })
+        .collect())
+}
 
-    for balance in balances_nil {
-        let curr_index = balances_curr
-            .iter()
-            .position(|bal| bal.username == balance.username);
-        match curr_index {
-            Some(index) => {
-                balances_curr[index].balance += balance.balance;
-            }
-            None => {
-                balances_curr.push(balance);
-            }
+// Finds the largest expense in each currency, given as its currency, description and total
+// Keeps the earliest of expenses with the same total, in the order of the currencies found
+fn find_largest_expenses(expenses: Vec<(String, String, i64)>) -> Vec<(String, String, i64)> {
+    let mut largest: Vec<(String, String, i64)> = Vec::new();
+    for expense in expenses {
+        match largest
+            .iter_mut()
+            .find(|(currency, _, _)| *currency == expense.0)
+        {
+            Some(current) if expense.2 > current.2 => *current = expense,
+            Some(_) => {}
+            None => largest.push(expense),
         }
     }
-
-    let debts = optimize_debts(balances_curr);
-
-    Ok(debts)
+    largest
 }
 
-/* View debts of a group chat for all currencies, converted to default currency.
- * Retrieves all balances, optimizes debts, and returns.
+/* Retrieves the largest expense of a trip in a group chat, in each currency.
+ * Adjustment entries, transfers and paybacks are not actual expenses, and are excluded.
+ * Payments without currency are counted under the default currency, if any.
  */
-async fn retrieve_debts_converted(chat_id: &str) -> Result<Vec<Debt>, ProcessError> {
-    let mut balances = get_chat_balances(chat_id)?;
-    let default_currency = get_default_currency(chat_id)?;
-
-    let mut converted_balances: Vec<UserBalance> = Vec::new();
-    for balances_currency in &mut balances {
-        if balances_currency.len() == 0 {
+pub async fn retrieve_trip_largest_expenses(
+    redis: &Redis,
+    chat_id: &str,
+    trip_id: &str,
+) -> Result<Vec<(String, String, i64)>, ProcessError> {
+    let default_currency = get_default_currency(redis, chat_id).await?;
+    let mut expenses: Vec<(String, String, i64)> = Vec::new();
+    for payment in retrieve_trip_payments(redis, chat_id, trip_id).await? {
+        if payment.is_transfer
+            || is_payback(&payment.payment)
+            || is_adjustment_entry(redis, &payment.payment_id).await?
+        {
             continue;
         }
 
-        let currency = balances_currency[0].currency.clone();
-        let should_convert = currency != default_currency && currency != CURRENCY_CODE_DEFAULT;
-
-        let conversion_rate = if should_convert {
-            match fetch_currency_conversion(&currency, &default_currency).await {
-                Ok(rate) => rate,
-                Err(err) => {
-                    log::error!("Error fetching currency conversion from {currency} to {default_currency}: {}", err);
-                    1.0
-                }
-            }
+        let currency = if payment.payment.currency == CURRENCY_CODE_DEFAULT {
+            default_currency.clone()
         } else {
-            1.0
+            payment.payment.currency
         };
-
-        for balance in balances_currency {
-            let mut amount = balance.balance;
-            if should_convert {
-                amount = convert_currency_with_rate(
-                    amount,
-                    &currency,
-                    &default_currency,
-                    conversion_rate,
-                );
-            }
-            let user = converted_balances
-                .iter()
-                .position(|bal| bal.username == balance.username);
-
-            match user {
-                Some(index) => {
-                    converted_balances[index].balance += amount;
-                }
-                None => {
-                    converted_balances.push(UserBalance {
-                        username: balance.username.clone(),
-                        currency: default_currency.clone(),
-                        balance: amount,
-                    });
-                }
-            }
-        }
+        let description = if payment.is_private {
+            PRIVATE_DESCRIPTION.to_string()
+        } else {
+            payment.payment.description
+        };
+        expenses.push((currency, description, payment.payment.total));
     }
 
-    let debts = optimize_debts(converted_balances);
-
-    Ok(debts)
+    Ok(find_largest_expenses(expenses))
 }
 
-/* View spendings of a group chat.
- * Takes in a specification of the options for viewing.
- * Which is whether the currency is to be converted, and which currency.
- */
-pub async fn retrieve_spending_data(
+// Records a change to a payment in the audit trail of a group chat, made just now
+// Descriptions of private payments are left out, as the audit trail is shown to everyone
+async fn record_payment_change(
+    redis: &Redis,
     chat_id: &str,
-    option: StatementOption,
-) -> Result<SpendingData, ProcessError> {
-    match option {
-        StatementOption::Currency(currency) => {
-            retrieve_spending_data_by_currency(chat_id, &currency)
-        }
-        StatementOption::ConvertCurrency => retrieve_spending_data_converted(chat_id).await,
-    }
+    payment_id: &str,
+    description: &str,
+    is_private: bool,
+    username: &str,
+    action: &str,
+) -> Result<(), ProcessError> {
+    let description = if is_private {
+        PRIVATE_DESCRIPTION
+    } else {
+        description
+    };
+    let entry = AuditEntry {
+        action: action.to_string(),
+        payment_id: payment_id.to_string(),
+        description: description.to_string(),
+        username: username.to_string(),
+        datetime: Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+    };
+    add_chat_audit_entry(redis, chat_id, &entry).await?;
+    Ok(())
 }
 
-/* View spendings of a group chat for a specific currency.
- * Retrieves all spendings, gets current balances, and returns:
- * Total group spending, total individual spendings, and total individual payments
+/* Add a new payment entry in a group chat.
+ * Execution flow: Updates relevant users, updates chat.
+ * Adds payment entry, updates balances, updates group debts.
+ * Private payments count towards balances as per normal, but their descriptions are hidden.
+ * Transfers count towards balances as per normal, but not towards spendings.
+ * A conversion rate into the default currency may be given for a payment in a foreign currency,
+ * such as the rate actually charged, to be used instead of live rates when converting it.
+ * Important: assumes that debts sum up to total. Creditor's share included.
+ * Returns the ID of the new payment, with the updated balances.
  */
-fn retrieve_spending_data_by_currency(
-    chat_id: &str,
+// Every detail of a payment is given by the handler as its own argument
+#[allow(clippy::too_many_arguments)]
+pub async fn add_payment(
+    redis: &Redis,
+    chat_id: String,
+    sender_username: String,
+    sender_id: String,
+    datetime: String,
+    description: &str,
+    creditor: &str,
     currency: &str,
-) -> Result<SpendingData, ProcessError> {
-    let default_currency = match get_chat_setting(chat_id, ChatSetting::DefaultCurrency(None))? {
-        ChatSetting::DefaultCurrency(Some(curr)) => curr,
-        _ => CURRENCY_CODE_DEFAULT.to_string(),
-    };
-    if default_currency == currency && currency != CURRENCY_CODE_DEFAULT {
-        return retrieve_spending_data_by_default_currency(chat_id, currency);
-    }
-
-    let spendings = retrieve_chat_spendings_currency(chat_id, currency)?;
-    let balances = get_chat_balances_currency(chat_id, currency)?;
-
-    let mut group_spending = 0;
-    let mut user_spendings: Vec<UserSpending> = Vec::new();
-    for spending in spendings {
-        group_spending += spending.balance;
+    total: i64,
+    debts: Vec<(String, i64)>,
+    is_private: bool,
+    is_transfer: bool,
+    rate: Option<f64>,
+) -> Result<(String, Vec<Debt>), ProcessError> {
+    validate_payment(currency, total, &debts)?;
+
+    // Update users and chat
+    update_users_chat(
+        redis,
+        &chat_id,
+        &sender_username,
+        &sender_id,
+        Some(creditor),
+        Some(debts.clone()),
+    )
+    .await?;
+
+    // Add payment entry, updating balances and spendings
+    let payment = Payment {
+        description: description.to_string(),
+        datetime,
+        creditor: creditor.to_string(),
+        currency: currency.to_string(),
+        total,
+        debts,
+    };
+    let (payment_id, changes, spendings) =
+        add_ledger_payment(&RedisStore::new(redis), &chat_id, &payment, is_transfer).await?;
+    if is_private {
+        set_payment_private_entry(redis, &payment_id).await?;
+    }
+    lock_payment_rate(redis, &chat_id, &payment_id, currency, rate).await?;
+    record_payment_change(
+        redis,
+        &chat_id,
+        &payment_id,
+        description,
+        is_private,
+        &sender_username,
+        AUDIT_ADDED,
+    )
+    .await?;
+
+    // Update the active trip as well, if any
+    if let Some((trip_id, _)) = get_chat_active_trip(redis, &chat_id).await? {
+        add_trip_payment_entry(redis, &chat_id, &trip_id, &payment_id).await?;
+        update_trip_balances(redis, &chat_id, &trip_id, changes, spendings).await?;
+    }
+
+    let conversion = get_currency_conversion(redis, &chat_id).await?;
+    let option = if conversion {
+        StatementOption::ConvertCurrency
+    } else {
+        StatementOption::Currency(currency.to_string())
+    };
+
+    let balances =
+        retrieve_updated_debts(redis, &chat_id, option, Some(currency.to_string())).await?;
+    Ok((payment_id, balances))
+}
+
+// Shares the offset of an adjustment equally among the other users of a chat
+// Leftover cents go to the first users, so that the shares add up to the amount
+fn split_adjustment(amount: i64, others: &[String]) -> Vec<(String, i64)> {
+    let count = others.len() as i64;
+    let share = amount / count;
+    let remainder = amount % count;
+    others
+        .iter()
+        .enumerate()
+        .map(|(index, user)| {
+            let leftover = if (index as i64) < remainder.abs() {
+                remainder.signum()
+            } else {
+                0
+            };
+            (user.clone(), share + leftover)
+        })
+        .collect()
+}
+
+/* Adjusts the balance of a user in a group chat, to correct a past discrepancy.
+ * Execution flow: Adds an adjustment entry, updates balances, updates group debts.
+ * A positive amount means the user is owed more, and a negative amount means they owe more.
+ * The opposite of the amount is shared equally among everyone else in the chat,
+ * so balances still add up to zero.
+ * Adjustments do not count towards spendings, and are not part of trips.
+ * Returns the ID of the adjustment, with the updated balances.
+ */
+#[allow(clippy::too_many_arguments)]
+pub async fn add_balance_adjustment(
+    redis: &Redis,
+    chat_id: &str,
+    sender_username: &str,
+    sender_id: &str,
+    username: &str,
+    currency: &str,
+    amount: i64,
+    reason: &str,
+) -> Result<(String, Vec<Debt>), ProcessError> {
+    if amount == 0 {
+        return Err(ProcessError::ValidationError(Text::new(ADJUSTMENT_ZERO)));
+    }
+
+    let users = get_chat_usernames(redis, chat_id).await?;
+    let username = match users.iter().find(|user| is_username_equal(user, username)) {
+        Some(user) => user.clone(),
+        None => {
+            return Err(ProcessError::ValidationError(
+                Text::new(ADJUSTMENT_USER_UNKNOWN).arg("username", username),
+            ))
+        }
+    };
+    let others: Vec<String> = users
+        .into_iter()
+        .filter(|user| !is_username_equal(user, &username))
+        .collect();
+    if others.is_empty() {
+        return Err(ProcessError::ValidationError(Text::new(
+            ADJUSTMENT_NO_OTHERS,
+        )));
+    }
+
+    let debts = split_adjustment(amount, &others);
+    validate_payment(currency, amount, &debts)?;
+    update_users_chat(redis, chat_id, sender_username, sender_id, None, None).await?;
+
+    // Add adjustment entry
+    let adjustment = Payment {
+        description: reason.to_string(),
+        datetime: Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        creditor: username.clone(),
+        currency: currency.to_string(),
+        total: amount,
+        debts: debts.clone(),
+    };
+    let payment_id = add_adjustment_entry(redis, chat_id, &adjustment).await?;
+    record_payment_change(
+        redis,
+        chat_id,
+        &payment_id,
+        reason,
+        false,
+        sender_username,
+        AUDIT_ADJUSTED,
+    )
+    .await?;
+
+    // Update balances
+    let mut changes: Vec<UserBalance> = debts
+        .iter()
+        .map(|(user, share)| UserBalance {
+            username: user.to_string(),
+            currency: currency.to_string(),
+            balance: share.neg(),
+        })
+        .collect();
+    changes.push(UserBalance {
+        username,
+        currency: currency.to_string(),
+        balance: amount,
+    });
+
+    let conversion = get_currency_conversion(redis, chat_id).await?;
+    let option = if conversion {
+        StatementOption::ConvertCurrency
+    } else {
+        StatementOption::Currency(currency.to_string())
+    };
+
+    let balances = update_balances_debts(redis, chat_id, changes, option).await?;
+    Ok((payment_id, balances))
+}
+
+/* Checks if a payment is an adjustment, instead of an actual expense.
+ */
+pub async fn is_adjustment_payment(redis: &Redis, payment_id: &str) -> Result<bool, ProcessError> {
+    let is_adjustment = is_adjustment_entry(redis, payment_id).await?;
+    Ok(is_adjustment)
+}
+
+/* Add multiple new payments in a group chat at once, one for each currency.
+ * Execution flow: Adds each payment in order, in the same way as a single payment.
+ * Returns the latest balances for each currency,
+ * or only the converted balances if currency conversion is on.
+ */
+#[allow(clippy::too_many_arguments)]
+pub async fn add_payments_batch(
+    redis: &Redis,
+    chat_id: String,
+    sender_username: String,
+    sender_id: String,
+    datetime: String,
+    description: &str,
+    creditor: &str,
+    payments: Vec<BatchPayment>,
+) -> Result<Vec<(String, Vec<Debt>)>, ProcessError> {
+    let mut balances: Vec<(String, Vec<Debt>)> = Vec::new();
+    for (currency, total, debts) in payments {
+        let (_, updated_balances) = add_payment(
+            redis,
+            chat_id.clone(),
+            sender_username.clone(),
+            sender_id.clone(),
+            datetime.clone(),
+            description,
+            creditor,
+            &currency,
+            total,
+            debts,
+            false,
+            false,
+            None,
+        )
+        .await?;
+        balances.push((currency, updated_balances));
+    }
+
+    if get_currency_conversion(redis, &chat_id).await? {
+        balances = balances.pop().into_iter().collect();
+    }
+
+    Ok(balances)
+}
+
+/* Imports payments in a group chat, such as those read from a file.
+ * Execution flow: Adds each payment in order, in the same way as a single payment.
+ * Payments are all added by the sender, and are neither private nor transfers.
+ */
+pub async fn import_payments(
+    redis: &Redis,
+    chat_id: &str,
+    sender_username: &str,
+    sender_id: &str,
+    payments: Vec<Payment>,
+) -> Result<(), ProcessError> {
+    for payment in payments {
+        add_payment(
+            redis,
+            chat_id.to_string(),
+            sender_username.to_string(),
+            sender_id.to_string(),
+            payment.datetime,
+            &payment.description,
+            &payment.creditor,
+            &payment.currency,
+            payment.total,
+            payment.debts,
+            false,
+            false,
+            None,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/* Checks if a new payment requires approval before it is added.
+ * Payments require approval if the chat has a threshold set,
+ * and the total exceeds it in the payment's own currency.
+ */
+pub async fn is_approval_required(
+    redis: &Redis,
+    chat_id: &str,
+    currency: &Currency,
+    total: i64,
+) -> Result<bool, ProcessError> {
+    let threshold = get_approval_threshold(redis, chat_id).await?;
+    match threshold {
+        Some(threshold) => {
+            let amount = total as f64 / 10.0_f64.powi(currency.1);
+            Ok(amount > threshold)
+        }
+        None => Ok(false),
+    }
+}
+
+/* Checks if a payment has someone in the split other than the payer,
+ * such that it can be confirmed by both the payer and a debtor with dual entry verification.
+ */
+pub fn has_other_debtors(creditor: &str, debts: &[(String, i64)]) -> bool {
+    debts
+        .iter()
+        .any(|(user, _)| !is_username_equal(user, creditor))
+}
+
+/* Checks if a new payment requires confirmation from both the payer and a debtor
+ * before it is added. Payments require confirmation if the chat has dual entry verification,
+ * and someone other than the payer is in the split.
+ */
+pub async fn is_dual_entry_required(
+    redis: &Redis,
+    chat_id: &str,
+    creditor: &str,
+    debts: &[(String, i64)],
+) -> Result<bool, ProcessError> {
+    check_dual_entry(&RedisStore::new(redis), chat_id, creditor, debts).await
+}
+
+// Checks if a new payment requires confirmation from both the payer and a debtor, in a ledger store
+async fn check_dual_entry<S: LedgerStore>(
+    store: &S,
+    chat_id: &str,
+    creditor: &str,
+    debts: &[(String, i64)],
+) -> Result<bool, ProcessError> {
+    if !has_other_debtors(creditor, debts) {
+        return Ok(false);
+    }
+    let dual_entry = store.get_dual_entry(chat_id).await?;
+    Ok(dual_entry)
+}
+
+/* Add a new pending payment entry in a group chat.
+ * Execution flow: Adds pending payment entry only.
+ * Does not update users, chat, balances or spendings, until it is approved.
+ * Returns the ID of the pending payment.
+ */
+// Takes the same details as add_payment, which it defers until approval
+#[allow(clippy::too_many_arguments)]
+pub async fn add_pending_payment(
+    redis: &Redis,
+    chat_id: String,
+    sender_username: String,
+    sender_id: String,
+    datetime: String,
+    description: &str,
+    creditor: &str,
+    currency: &str,
+    total: i64,
+    debts: Vec<(String, i64)>,
+    is_private: bool,
+    is_transfer: bool,
+    rate: Option<f64>,
+) -> Result<String, ProcessError> {
+    validate_payment(currency, total, &debts)?;
+
+    let pending = PendingPayment {
+        chat_id,
+        sender_id,
+        sender_username,
+        is_private,
+        is_transfer,
+        rate,
+    };
+    let payment = Payment {
+        description: description.to_string(),
+        datetime,
+        creditor: creditor.to_string(),
+        currency: currency.to_string(),
+        total,
+        debts,
+    };
+
+    let payment_id = add_pending_payment_entry(redis, &pending, &payment).await?;
+    Ok(payment_id)
+}
+
+/* Retrieves a pending payment entry, if it is still pending.
+*/
+pub async fn retrieve_pending_payment(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<Option<(PendingPayment, Payment)>, ProcessError> {
+    match get_pending_payment_entry(redis, payment_id).await {
+        Ok(entry) => Ok(Some(entry)),
+        Err(CrudError::NoSuchPaymentError()) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/* Confirms a pending payment entry in a group chat with dual entry verification.
+ * Takes in whether the user confirms as the payer, or as a debtor.
+ * Returns the users who have confirmed it as the payer and as a debtor so far,
+ * or None if the payment is no longer pending.
+ */
+pub async fn confirm_pending_payment(
+    redis: &Redis,
+    payment_id: &str,
+    is_payer: bool,
+    username: &str,
+) -> Result<Option<(Option<String>, Option<String>)>, ProcessError> {
+    match confirm_pending_payment_entry(redis, payment_id, is_payer, username).await {
+        Ok(confirmations) => Ok(Some(confirmations)),
+        Err(CrudError::NoSuchPaymentError()) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/* Approves a pending payment entry in a group chat.
+ * Execution flow: Claims the pending entry, adds the payment as per normal, then removes the entry.
+ * If the payment can't be added, the entry is kept pending, to be approved again.
+ * Returns None if the payment is no longer pending.
+ */
+pub async fn approve_pending_payment(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<Option<Vec<Debt>>, ProcessError> {
+    let (pending, payment) = match retrieve_pending_payment(redis, payment_id).await? {
+        Some(entry) => entry,
+        None => return Ok(None),
+    };
+
+    // Resolved by someone else in the meantime
+    if !claim_pending_payment_entry(redis, payment_id).await? {
+        return Ok(None);
+    }
+
+    let added = add_payment(
+        redis,
+        pending.chat_id,
+        pending.sender_username,
+        pending.sender_id,
+        payment.datetime,
+        &payment.description,
+        &payment.creditor,
+        &payment.currency,
+        payment.total,
+        payment.debts,
+        pending.is_private,
+        pending.is_transfer,
+        pending.rate,
+    )
+    .await;
+    let debts = match added {
+        Ok((_, debts)) => debts,
+        Err(err) => {
+            restore_pending_payment_entry(redis, payment_id).await?;
+            return Err(err);
+        }
+    };
+
+    remove_claimed_payment_entry(redis, payment_id).await?;
+    Ok(Some(debts))
+}
+
+/* Discards a pending payment entry in a group chat, when it is disputed.
+ * Returns false if the payment is no longer pending.
+ */
+pub async fn discard_pending_payment(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<bool, ProcessError> {
+    let is_discarded = resolve_pending_payment_entry(redis, payment_id).await?;
+    Ok(is_discarded)
+}
+
+/* View all payment entries of a group chat.
+ * Execution flow: Retrieve chat payment details.
+ * Called only once per command. Pagination handled by Handler.
+ */
+pub async fn view_payments(
+    redis: &Redis,
+    chat_id: &str,
+    sender_id: &str,
+    sender_username: Option<&str>,
+) -> Result<Vec<UserPayment>, ProcessError> {
+    auto_update_user(redis, chat_id, sender_id, sender_username).await?;
+
+    let payments = get_chat_payments_details(redis, chat_id).await?;
+    Ok(payments)
+}
+
+/* Retrieves a single payment entry, if it still exists.
+ */
+pub async fn retrieve_payment(redis: &Redis, payment_id: &str) -> Result<Payment, ProcessError> {
+    let payment = get_payment_entry(redis, payment_id).await?;
+    Ok(payment)
+}
+
+/* View all private payment entries that a user is involved in, across all their group chats.
+ * Execution flow: Retrieve chats of the user, then the payment details of each chat.
+ * A user is involved in a payment if they are the payer, or part of the split.
+ * Called only from a direct message with the user, where the details can be shown in full.
+ */
+pub async fn retrieve_private_payments(
+    redis: &Redis,
+    sender_id: &str,
+    sender_username: Option<&str>,
+) -> Result<Vec<UserPayment>, ProcessError> {
+    let username = match sender_username {
+        Some(username) => username,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut private_payments: Vec<UserPayment> = Vec::new();
+    for chat_id in get_user_chat_entries(redis, sender_id, sender_username).await? {
+        let payments = match get_chat_payments_details(redis, &chat_id).await {
+            Ok(payments) => payments,
+            Err(CrudError::NoPaymentsError()) => continue,
+            Err(err) => return Err(err.into()),
+        };
+        private_payments.extend(payments.into_iter().filter(|payment| {
+            payment.is_private
+                && (is_username_equal(&payment.payment.creditor, username)
+                    || payment
+                        .payment
+                        .debts
+                        .iter()
+                        .any(|(debtor, _)| is_username_equal(debtor, username)))
+        }));
+    }
+
+    Ok(private_payments)
+}
+
+/* Retrieves the number of payments in a group chat.
+ */
+pub async fn retrieve_payment_count(redis: &Redis, chat_id: &str) -> Result<usize, ProcessError> {
+    let count = get_payment_count(redis, chat_id).await?;
+    Ok(count)
+}
+
+/* Sets the payment link of a user, or removes it if None.
+ * The link is shown to others when they owe the user, so that they can pay directly.
+ */
+pub async fn update_payment_link(
+    redis: &Redis,
+    sender_id: &str,
+    sender_username: Option<&str>,
+    link: Option<&str>,
+) -> Result<(), ProcessError> {
+    set_payment_link(redis, sender_id, sender_username, link).await?;
+    Ok(())
+}
+
+/* Retrieves the payment links of the given users.
+ * Returns a map of each lowercase username to its link, for users who have one.
+ */
+pub async fn retrieve_payment_links(
+    redis: &Redis,
+    usernames: &[String],
+) -> Result<HashMap<String, String>, ProcessError> {
+    let links = get_payment_links(redis, usernames).await?;
+    Ok(links)
+}
+
+/* Sets whether a user is notified in a direct message of payments they are added to.
+ * Users are notified once they start the bot in a direct message, until they turn it off.
+ */
+pub async fn update_payment_notifications(
+    redis: &Redis,
+    sender_id: &str,
+    sender_username: Option<&str>,
+    is_on: bool,
+) -> Result<(), ProcessError> {
+    set_private_chat(redis, sender_id, sender_username, is_on).await?;
+    Ok(())
+}
+
+/* Retrieves the direct messages of the given users, for those who are notified of payments.
+ * Returns a map of each lowercase username to the ID of their direct message.
+ */
+pub async fn retrieve_notified_users(
+    redis: &Redis,
+    usernames: &[String],
+) -> Result<HashMap<String, String>, ProcessError> {
+    let private_chats = get_private_chats(redis, usernames).await?;
+    Ok(private_chats)
+}
+
+// Gets the lowercase usernames of everyone involved in a payment, being the payer and the split
+fn get_payment_participants(creditor: &str, debts: &[(String, i64)]) -> Vec<String> {
+    let mut participants: Vec<String> = vec![creditor.to_lowercase()];
+    for (debtor, _) in debts {
+        let debtor = debtor.to_lowercase();
+        if !participants.contains(&debtor) {
+            participants.push(debtor);
+        }
+    }
+    participants
+}
+
+/* Tracks the message confirming a payment in a group chat,
+ * so that reactions to it acknowledge the payment.
+ */
+pub async fn track_payment_confirmation(
+    redis: &Redis,
+    chat_id: &str,
+    message_id: i32,
+    payment_id: &str,
+) -> Result<(), ProcessError> {
+    set_acknowledgement_message_entry(redis, chat_id, message_id, payment_id).await?;
+    Ok(())
+}
+
+/* Acknowledges a payment for a user, or takes it back,
+ * when they react to the message confirming the payment.
+ * Only the payer and those in the split can acknowledge a payment.
+ * Returns the number of participants who acknowledged the payment, out of all participants,
+ * or None if the message does not confirm a payment, or the user is not a participant.
+ */
+pub async fn acknowledge_payment(
+    redis: &Redis,
+    chat_id: &str,
+    message_id: i32,
+    username: &str,
+    is_acknowledged: bool,
+) -> Result<Option<(usize, usize)>, ProcessError> {
+    let payment_id = match get_acknowledgement_message_entry(redis, chat_id, message_id).await? {
+        Some(payment_id) => payment_id,
+        None => return Ok(None),
+    };
+    let payment = match get_payment_entry(redis, &payment_id).await {
+        Ok(payment) => payment,
+        Err(CrudError::NoSuchPaymentError()) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+
+    let participants = get_payment_participants(&payment.creditor, &payment.debts);
+    if !participants.contains(&username.to_lowercase()) {
+        return Ok(None);
+    }
+    update_acknowledgement_entry(redis, &payment_id, username, is_acknowledged).await?;
+
+    let acknowledgements =
+        retrieve_payment_acknowledgements(redis, &payment_id, &payment.creditor, &payment.debts)
+            .await?;
+    Ok(Some(acknowledgements))
+}
+
+/* Retrieves the number of participants who acknowledged a payment, out of all participants.
+ * Participants who are no longer part of the payment after an edit are not counted.
+ */
+pub async fn retrieve_payment_acknowledgements(
+    redis: &Redis,
+    payment_id: &str,
+    creditor: &str,
+    debts: &[(String, i64)],
+) -> Result<(usize, usize), ProcessError> {
+    let participants = get_payment_participants(creditor, debts);
+    let acknowledged = get_acknowledgement_entries(redis, payment_id)
+        .await?
+        .iter()
+        .filter(|username| participants.contains(username))
+        .count();
+    Ok((acknowledged, participants.len()))
+}
+
+/* View the debts of a user across all their group chats, netted per counterpart and currency.
+ * Execution flow: Retrieve chats of the user, then the debts of each chat in each currency.
+ * Payments without currency are counted under the default currency of their chat, if any.
+ * Returns the net debts that are not settled, sorted by currency, then counterpart.
+ */
+pub async fn retrieve_consolidated_debts(
+    redis: &Redis,
+    sender_id: &str,
+    sender_username: Option<&str>,
+) -> Result<Vec<ConsolidatedDebt>, ProcessError> {
+    let username = match sender_username {
+        Some(username) => username,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut consolidated: Vec<ConsolidatedDebt> = Vec::new();
+    for chat_id in get_user_chat_entries(redis, sender_id, sender_username).await? {
+        let default_currency = get_default_currency(redis, &chat_id).await?;
+        for currency in get_valid_chat_currencies(redis, &chat_id).await? {
+            // Balances without currency are already merged into the default currency
+            if currency == CURRENCY_CODE_DEFAULT && default_currency != CURRENCY_CODE_DEFAULT {
+                continue;
+            }
+            let debts =
+                retrieve_debts(redis, &chat_id, StatementOption::Currency(currency.clone()))
+                    .await?;
+            let currency = if currency == CURRENCY_CODE_DEFAULT {
+                default_currency.clone()
+            } else {
+                currency
+            };
+
+            for debt in debts {
+                let (counterpart, amount) = if is_username_equal(&debt.debtor, username) {
+                    (debt.creditor, debt.amount)
+                } else if is_username_equal(&debt.creditor, username) {
+                    (debt.debtor, debt.amount.neg())
+                } else {
+                    continue;
+                };
+
+                match consolidated.iter_mut().find(|entry| {
+                    entry.currency == currency
+                        && is_username_equal(&entry.counterpart, &counterpart)
+                }) {
+                    Some(entry) => {
+                        entry.amount = saturating_add(entry.amount, amount);
+                        entry.chats += 1;
+                    }
+                    None => consolidated.push(ConsolidatedDebt {
+                        counterpart,
+                        currency: currency.clone(),
+                        amount,
+                        chats: 1,
+                    }),
+                }
+            }
+        }
+    }
+
+    consolidated.retain(|entry| entry.amount != 0);
+    consolidated.sort_by(|a, b| {
+        a.currency.cmp(&b.currency).then(
+            a.counterpart
+                .to_lowercase()
+                .cmp(&b.counterpart.to_lowercase()),
+        )
+    });
+    Ok(consolidated)
+}
+
+/* Edit a payment entry in a group chat.
+ * Execution flow: Edit payment entry, updating balances. Keep previous version.
+ * Update group debts.
+ * Reverting to a previous version is done as an edit with all of its details.
+ * Marking or unmarking the payment as a transfer moves its amounts in or out of spendings.
+ * Has to be called after self::view_payments.
+ */
+// Each detail of the payment is optional, with None leaving it unchanged
+#[allow(clippy::too_many_arguments)]
+pub async fn edit_payment(
+    redis: &Redis,
+    chat_id: &str,
+    sender_username: String,
+    sender_id: String,
+    payment_id: &str,
+    description: Option<&str>,
+    datetime: Option<&str>,
+    creditor: Option<&str>,
+    currency: Option<&str>,
+    total: Option<&i64>,
+    debts: Option<Vec<(String, i64)>>,
+    is_transfer: Option<bool>,
+) -> Result<Option<Vec<Debt>>, ProcessError> {
+    // Get current payment entry
+    let store = RedisStore::new(redis);
+    let current_entry = store.get_payment(payment_id).await?;
+    let current_payment = &current_entry.payment;
+    let was_transfer = current_entry.transfer;
+    let is_transfer_changed = is_transfer.is_some_and(|is_transfer| is_transfer != was_transfer);
+    validate_amounts(
+        total.copied().unwrap_or(current_payment.total),
+        debts.as_ref().unwrap_or(&current_payment.debts),
+    )?;
+
+    // Update users and chat
+    update_users_chat(
+        redis,
+        chat_id,
+        &sender_username,
+        &sender_id,
+        creditor,
+        debts.clone(),
+    )
+    .await?;
+
+    // Edit payment entry
+    let payment = Payment {
+        description: description
+            .unwrap_or(&current_payment.description)
+            .to_string(),
+        datetime: datetime.unwrap_or(&current_payment.datetime).to_string(),
+        creditor: creditor.unwrap_or(&current_payment.creditor).to_string(),
+        currency: currency.unwrap_or(&current_payment.currency).to_string(),
+        total: *total.unwrap_or(&current_payment.total),
+        debts: debts.clone().unwrap_or(current_payment.debts.clone()),
+    };
+    let is_transfer = is_transfer.unwrap_or(was_transfer);
+    let is_amounts_changed = creditor.is_some()
+        || currency.is_some()
+        || total.is_some()
+        || debts.is_some()
+        || is_transfer_changed;
+
+    // Balances are updated with the payment: the previous payment is undone, then the new one set
+    let ledger_changes = if is_amounts_changed {
+        Some(
+            apply_ledger_edit(
+                &store,
+                chat_id,
+                payment_id,
+                &current_entry,
+                &payment,
+                is_transfer,
+            )
+            .await?,
+        )
+    } else {
+        store
+            .update_payment(payment_id, &payment, is_transfer)
+            .await?;
+        None
+    };
+
+    // Keep the previous version of the payment, so that it can be reverted to
+    let version = PaymentVersion {
+        payment: current_payment.clone(),
+        edited_by: sender_username.clone(),
+        edited_at: Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+    };
+    add_payment_version_entry(redis, payment_id, &version).await?;
+
+    // Acknowledgements were for the previous details, and the edit settles any dispute
+    delete_acknowledgement_entries(redis, payment_id).await?;
+    resolve_payment_dispute(redis, chat_id, payment_id).await?;
+    record_payment_change(
+        redis,
+        chat_id,
+        payment_id,
+        description.unwrap_or(&current_payment.description),
+        is_private_payment_entry(redis, payment_id).await?,
+        &sender_username,
+        AUDIT_EDITED,
+    )
+    .await?;
+
+    // Lock a new conversion rate if the currency has changed
+    if let Some(currency) = currency {
+        if currency != current_payment.currency {
+            lock_payment_rate(redis, chat_id, payment_id, currency, None).await?;
+        }
+    }
+
+    if let Some((changes, spendings)) = ledger_changes {
+        // Same changes are applied to the trip of the payment, if any
+        if let Some(trip_id) = get_payment_trip_entry(redis, payment_id).await? {
+            update_trip_balances(redis, chat_id, &trip_id, changes, spendings).await?;
+        }
+
+        let conversion = get_currency_conversion(redis, chat_id).await?;
+        let option = if conversion {
+            StatementOption::ConvertCurrency
+        } else {
+            StatementOption::Currency(payment.currency.clone())
+        };
+
+        let res = retrieve_updated_debts(redis, chat_id, option, Some(payment.currency)).await?;
+        return Ok(Some(res));
+    }
+
+    Ok(None)
+}
+
+/* Retrieves the previous versions of a payment entry, from the latest to the earliest.
+ */
+pub async fn retrieve_payment_versions(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<Vec<PaymentVersion>, ProcessError> {
+    let mut versions = get_payment_version_entries(redis, payment_id).await?;
+    versions.reverse();
+    Ok(versions)
+}
+
+// Deletes a payment entry, together with its trip entry if any.
+// Returns the changes to balances and spendings that undo the payment, and its trip ID if any.
+// The changes are left to the caller to apply.
+async fn remove_payment(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+) -> Result<(Payment, Vec<UserBalance>, Vec<UserBalance>, Option<String>), ProcessError> {
+    // Move payment entry to the trash, out of its trip as well if any
+    let trip_id = get_payment_trip_entry(redis, payment_id).await?;
+    if let Some(trip_id) = &trip_id {
+        delete_trip_payment_entry(redis, chat_id, trip_id, payment_id).await?;
+    }
+    let datetime = Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string();
+    let (payment, changes, spendings) =
+        trash_ledger_payment(&RedisStore::new(redis), chat_id, payment_id, &datetime).await?;
+
+    Ok((payment, changes, spendings, trip_id))
+}
+
+/* Delete payment entries in a group chat, one or more at once.
+ * Execution flow: Move all payment entries to the trash, to be restored or purged later.
+ * Update balances and spendings for all of them in a single batch, then retrieve group debts.
+ * Each deletion is recorded in the audit trail, under the user deleting them.
+ * Returns the latest balances for each currency of the payments,
+ * or only the converted balances if currency conversion is on.
+ * Has to be called after self::view_payments.
+ */
+pub async fn delete_payments(
+    redis: &Redis,
+    chat_id: &str,
+    sender_username: &str,
+    payment_ids: &Vec<String>,
+) -> Result<Vec<(String, Vec<Debt>)>, ProcessError> {
+    let mut currencies: Vec<String> = Vec::new();
+    let mut all_changes: Vec<UserBalance> = Vec::new();
+    let mut all_spendings: Vec<UserBalance> = Vec::new();
+    let mut trip_updates: HashMap<String, (Vec<UserBalance>, Vec<UserBalance>)> = HashMap::new();
+
+    for payment_id in payment_ids {
+        let is_private = is_private_payment_entry(redis, payment_id).await?;
+        let (payment, changes, spendings, trip_id) =
+            remove_payment(redis, chat_id, payment_id).await?;
+        record_payment_change(
+            redis,
+            chat_id,
+            payment_id,
+            &payment.description,
+            is_private,
+            sender_username,
+            AUDIT_DELETED,
+        )
+        .await?;
+
+        if !currencies.contains(&payment.currency) {
+            currencies.push(payment.currency);
+        }
+        if let Some(trip_id) = trip_id {
+            let trip_update = trip_updates.entry(trip_id).or_default();
+            trip_update.0.extend(changes.clone());
+            trip_update.1.extend(spendings.clone());
+        }
+        all_changes.extend(changes);
+        all_spendings.extend(spendings);
+    }
+
+    // Update spendings and balances, once for all payments
+    for (trip_id, (changes, spendings)) in trip_updates {
+        update_trip_balances(redis, chat_id, &trip_id, changes, spendings).await?;
+    }
+    update_ledger_balances(&RedisStore::new(redis), chat_id, all_changes, all_spendings).await?;
+
+    retrieve_currencies_debts(redis, chat_id, currencies).await
+}
+
+// Retrieves the latest debts of a group chat, for each currency of the payments changed
+// If rates are unavailable, the balances are shown in their original currencies instead
+async fn retrieve_currencies_debts(
+    redis: &Redis,
+    chat_id: &str,
+    mut currencies: Vec<String>,
+) -> Result<Vec<(String, Vec<Debt>)>, ProcessError> {
+    if get_currency_conversion(redis, chat_id).await? {
+        match retrieve_debts(redis, chat_id, StatementOption::ConvertCurrency).await {
+            Ok(debts) => {
+                let currency = currencies
+                    .pop()
+                    .unwrap_or(CURRENCY_CODE_DEFAULT.to_string());
+                return Ok(vec![(currency, debts)]);
+            }
+            Err(ProcessError::ConversionError(err)) => {
+                log::error!("Error converting balances for chat {chat_id}: {err}");
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    let mut balances: Vec<(String, Vec<Debt>)> = Vec::new();
+    for currency in currencies {
+        let debts =
+            retrieve_debts(redis, chat_id, StatementOption::Currency(currency.clone())).await?;
+        balances.push((currency, debts));
+    }
+    Ok(balances)
+}
+
+/* Retrieves the payments in the trash of a group chat, from the latest deleted.
+ * Payments stay in the trash for TRASH_RETENTION_DAYS days, after which they are purged for good.
+ */
+pub async fn retrieve_trash(redis: &Redis, chat_id: &str) -> Result<Vec<TrashEntry>, ProcessError> {
+    let entries = get_trash_entries(redis, chat_id).await?;
+    Ok(entries)
+}
+
+/* Restores a payment from the trash of a group chat.
+ * Execution flow: Add payment entry back to the chat, and to its trip if any.
+ * Reapply its balances and spendings, then retrieve group debts.
+ * The restoration is recorded in the audit trail, under the user restoring it.
+ * Returns the payment with the latest balances, or None if it is no longer in the trash.
+ */
+pub async fn restore_payment(
+    redis: &Redis,
+    chat_id: &str,
+    sender_username: &str,
+    payment_id: &str,
+) -> Result<Option<(Payment, Vec<(String, Vec<Debt>)>)>, ProcessError> {
+    if !restore_trash_entry(redis, chat_id, payment_id).await? {
+        return Ok(None);
+    }
+
+    let payment = get_payment_entry(redis, payment_id).await?;
+    let is_private = is_private_payment_entry(redis, payment_id).await?;
+    let is_adjustment = is_adjustment_entry(redis, payment_id).await?;
+    let is_transfer = is_transfer_payment_entry(redis, payment_id).await?;
+    let trip_id = get_payment_trip_entry(redis, payment_id).await?;
+
+    // Spendings, adjustments and transfers do not count towards spendings
+    let mut spendings: Vec<UserBalance> = Vec::new();
+    if !is_adjustment && !is_transfer {
+        spendings = payment
+            .debts
+            .iter()
+            .map(|debt| UserBalance {
+                username: debt.0.to_string(),
+                currency: payment.currency.clone(),
+                balance: debt.1,
+            })
+            .collect();
+        RedisStore::new(redis)
+            .update_spendings(chat_id, spendings.clone())
+            .await?;
+    }
+
+    // Balances
+    let mut changes: Vec<UserBalance> = payment
+        .debts
+        .iter()
+        .map(|debt| UserBalance {
+            username: debt.0.to_string(),
+            currency: payment.currency.clone(),
+            balance: debt.1.neg(),
+        })
+        .collect();
+    changes.push(UserBalance {
+        username: payment.creditor.clone(),
+        currency: payment.currency.clone(),
+        balance: payment.total,
+    });
+
+    if let Some(trip_id) = &trip_id {
+        update_trip_balances(redis, chat_id, trip_id, changes.clone(), spendings).await?;
+    }
+    update_balances(redis, chat_id, changes).await?;
+    record_payment_change(
+        redis,
+        chat_id,
+        payment_id,
+        &payment.description,
+        is_private,
+        sender_username,
+        AUDIT_RESTORED,
+    )
+    .await?;
+
+    let balances =
+        retrieve_currencies_debts(redis, chat_id, vec![payment.currency.clone()]).await?;
+    Ok(Some((payment, balances)))
+}
+
+/* Purges the payments in the trash of all group chats, that were deleted too long ago.
+ * Balances are unchanged, as they were already updated when the payments were deleted.
+ * Returns the number of payments purged.
+ */
+pub async fn purge_expired_trash(redis: &Redis) -> Result<usize, ProcessError> {
+    let cutoff = (Utc::now() - chrono::Duration::days(TRASH_RETENTION_DAYS))
+        .format("%Y-%m-%d %H:%M:%S UTC")
+        .to_string();
+
+    let mut count = 0;
+    for chat_id in get_trash_chat_ids(redis).await? {
+        let purged = purge_trash_entries(redis, &chat_id, &cutoff).await?;
+        if purged > 0 {
+            // Logging
+            log::info!(
+                "Trash - Purged {} payments for chat {} deleted before {}",
+                purged,
+                chat_id,
+                cutoff
+            );
+        }
+        count += purged;
+    }
+    Ok(count)
+}
+
+/* Retrieves the recent changes to payments in a group chat, from the latest.
+ * Changes are the payments added, edited, deleted and restored, and who made them.
+ */
+pub async fn retrieve_audit_entries(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<AuditEntry>, ProcessError> {
+    let entries = get_chat_audit_entries(redis, chat_id).await?;
+    Ok(entries)
+}
+
+/* Sets the date by which the debts of a payment should be paid back.
+ * Date is in the format %Y-%m-%d, in the time zone of the chat.
+ */
+pub async fn update_payment_due_date(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+    date: &str,
+) -> Result<(), ProcessError> {
+    set_payment_due_date(redis, chat_id, payment_id, date).await?;
+    Ok(())
+}
+
+/* Opens a dispute on a payment by one of its debtors, until the payer edits or dismisses it.
+ * Returns false if the payment is already disputed.
+ */
+pub async fn dispute_payment(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+    username: &str,
+) -> Result<bool, ProcessError> {
+    let is_disputed = add_payment_dispute(redis, chat_id, payment_id, username).await?;
+    Ok(is_disputed)
+}
+
+/* Retrieves the username who disputed a payment, if it is disputed.
+ */
+pub async fn retrieve_payment_dispute(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+) -> Result<Option<String>, ProcessError> {
+    let username = get_payment_dispute(redis, chat_id, payment_id).await?;
+    Ok(username)
+}
+
+/* Dismisses the dispute on a payment, leaving the payment unchanged.
+ * Returns false if the payment was not disputed.
+ */
+pub async fn dismiss_payment_dispute(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+) -> Result<bool, ProcessError> {
+    let is_dismissed = resolve_payment_dispute(redis, chat_id, payment_id).await?;
+    Ok(is_dismissed)
+}
+
+/* Sets the note of a payment, such as where or how it was paid.
+ */
+pub async fn update_payment_note(
+    redis: &Redis,
+    payment_id: &str,
+    note: &str,
+) -> Result<(), ProcessError> {
+    set_payment_note_entry(redis, payment_id, note).await?;
+    Ok(())
+}
+
+/* Retrieves the note of a payment, if any.
+ */
+pub async fn retrieve_payment_note(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<Option<String>, ProcessError> {
+    let note = get_payment_note_entry(redis, payment_id).await?;
+    Ok(note)
+}
+
+/* Sets the receipt of a payment, as the Telegram file ID of its photo.
+ */
+pub async fn update_payment_receipt(
+    redis: &Redis,
+    payment_id: &str,
+    file_id: &str,
+) -> Result<(), ProcessError> {
+    set_payment_receipt_entry(redis, payment_id, file_id).await?;
+    Ok(())
+}
+
+/* Retrieves the receipt of a payment, if any, as the Telegram file ID of its photo.
+ */
+pub async fn retrieve_payment_receipt(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<Option<String>, ProcessError> {
+    let receipt = get_payment_receipt_entry(redis, payment_id).await?;
+    Ok(receipt)
+}
+
+/* Retrieves the users with overdue debts in a group chat.
+ * A user is overdue if they owe in a payment that was due before today.
+ * Today is in the format %Y-%m-%d, in the time zone of the chat.
+ * Whether they still owe anything is left to the balances shown.
+ */
+pub async fn retrieve_overdue_debtors(
+    redis: &Redis,
+    chat_id: &str,
+    today: &str,
+) -> Result<Vec<String>, ProcessError> {
+    let due_dates = get_payment_due_dates(redis, chat_id).await?;
+
+    let mut debtors: Vec<String> = Vec::new();
+    for (payment_id, date) in due_dates {
+        if date.as_str() >= today {
+            continue;
+        }
+
+        // Payments that no longer exist are skipped
+        let payment = match get_payment_entry(redis, &payment_id).await {
+            Ok(payment) => payment,
+            Err(_) => continue,
+        };
+        for (debtor, amount) in payment.debts {
+            if amount > 0
+                && !is_username_equal(&debtor, &payment.creditor)
+                && !debtors.iter().any(|user| is_username_equal(user, &debtor))
+            {
+                debtors.push(debtor);
+            }
+        }
+    }
+
+    Ok(debtors)
+}
+
+/* View balances of a group chat.
+ * Takes in a specification of the options for viewing.
+ * Which is whether the currency is to be converted, and which currency.
+ */
+pub async fn retrieve_debts(
+    redis: &Redis,
+    chat_id: &str,
+    option: StatementOption,
+) -> Result<Vec<Debt>, ProcessError> {
+    retrieve_ledger_debts(redis, chat_id, chat_id, option).await
+}
+
+// Keeps only the debts owed by a user, in the currency they were retrieved for
+fn filter_user_debts(debts: Vec<Debt>, username: &str, currency: &str) -> Vec<Debt> {
+    debts
+        .into_iter()
+        .filter(|debt| is_username_equal(&debt.debtor, username))
+        .map(|debt| Debt {
+            currency: currency.to_string(),
+            ..debt
+        })
+        .collect()
+}
+
+/* View the debts a user owes in a group chat, for paying them back.
+ * Debts are optimized like self::retrieve_debts, but never converted,
+ * as paybacks are in the currency owed. Amounts without currency are owed in the default currency.
+ * Returns the debts in each currency, with the user as the debtor.
+ */
+pub async fn retrieve_user_debts(
+    redis: &Redis,
+    chat_id: &str,
+    username: &str,
+) -> Result<Vec<Debt>, ProcessError> {
+    let default_currency = get_default_currency(redis, chat_id).await?;
+    let mut currencies: Vec<String> = Vec::new();
+    for balances_currency in get_chat_balances(redis, chat_id).await? {
+        if let Some(balance) = balances_currency.first() {
+            let currency = get_source_currency(&balance.currency, &default_currency).to_string();
+            if !currencies.contains(&currency) {
+                currencies.push(currency);
+            }
+        }
+    }
+
+    let mut user_debts: Vec<Debt> = Vec::new();
+    for currency in currencies {
+        let debts =
+            retrieve_debts(redis, chat_id, StatementOption::Currency(currency.clone())).await?;
+        user_debts.extend(filter_user_debts(debts, username, &currency));
+    }
+    Ok(user_debts)
+}
+
+/* Retrieves when the conversion rates used for the balances of a group chat were fetched.
+ * Takes in the same option as self::retrieve_debts, and returns the oldest time of the rates,
+ * as a Unix timestamp, or None if the balances are not converted with any rate.
+ */
+pub async fn retrieve_rates_fetched(
+    redis: &Redis,
+    chat_id: &str,
+    option: StatementOption,
+) -> Result<Option<i64>, ProcessError> {
+    let default_currency = get_default_currency(redis, chat_id).await?;
+    let target_currency = match option {
+        StatementOption::Currency(_) => return Ok(None),
+        StatementOption::ConvertCurrency => default_currency.clone(),
+        StatementOption::ConvertTo(currency) => currency,
+    };
+
+    let mut rates_fetched: Option<i64> = None;
+    for balances_currency in get_chat_balances(redis, chat_id).await? {
+        let currency = match balances_currency.first() {
+            Some(balance) => balance.currency.clone(),
+            None => continue,
+        };
+        let source_currency = get_source_currency(&currency, &default_currency);
+        if source_currency == target_currency || source_currency == CURRENCY_CODE_DEFAULT {
+            continue;
+        }
+
+        let last_rate = get_last_cached_rate(
+            redis,
+            &source_currency.to_uppercase(),
+            &target_currency.to_uppercase(),
+        )
+        .await?;
+        if let Some((_, fetched_at)) = last_rate {
+            rates_fetched = Some(rates_fetched.map_or(fetched_at, |oldest| oldest.min(fetched_at)));
+        }
+    }
+
+    Ok(rates_fetched)
+}
+
+/* View balances of a trip in a group chat.
+ * Takes in a specification of the options for viewing, like self::retrieve_debts.
+ */
+pub async fn retrieve_trip_debts(
+    redis: &Redis,
+    chat_id: &str,
+    trip_id: &str,
+    option: StatementOption,
+) -> Result<Vec<Debt>, ProcessError> {
+    retrieve_ledger_debts(redis, chat_id, &get_trip_ledger(chat_id, trip_id), option).await
+}
+
+/* View the net position between two specific users in a group chat, in each currency.
+ * Attributes each payment to the pair directly, rather than through the optimized debts,
+ * so that the position reflects what the two users have paid for each other.
+ * Payments without currency are counted under the default currency, if any.
+ * Returns the amount the first user owes the second in each currency, negative if owed instead.
+ */
+pub async fn retrieve_pairwise_debts(
+    redis: &Redis,
+    chat_id: &str,
+    first: &str,
+    second: &str,
+) -> Result<Vec<(String, i64)>, ProcessError> {
+    let payments = retrieve_chat_payments(redis, chat_id).await?;
+    let default_currency = get_default_currency(redis, chat_id).await?;
+
+    let mut positions: Vec<(String, i64)> = Vec::new();
+    for payment in payments {
+        let payment = payment.payment;
+        let is_first_creditor = is_username_equal(&payment.creditor, first);
+        let is_second_creditor = is_username_equal(&payment.creditor, second);
+        if !is_first_creditor && !is_second_creditor {
+            continue;
+        }
+
+        // Debts of the other user of the pair, towards the creditor of the payment
+        let amount = checked_sum(
+            payment
+                .debts
+                .iter()
+                .filter(|(debtor, _)| {
+                    if is_first_creditor {
+                        is_username_equal(debtor, second)
+                    } else {
+                        is_username_equal(debtor, first)
+                    }
+                })
+                .map(|(_, amount)| *amount),
+        )?;
+        if amount == 0 {
+            continue;
+        }
+        let amount = if is_first_creditor {
+            amount.neg()
+        } else {
+            amount
+        };
+
+        let currency = if payment.currency == CURRENCY_CODE_DEFAULT {
+            default_currency.clone()
+        } else {
+            payment.currency
+        };
+        match positions.iter_mut().find(|(cur, _)| *cur == currency) {
+            Some(position) => position.1 = checked_add(position.1, amount)?,
+            None => positions.push((currency, amount)),
+        }
+    }
+
+    positions.retain(|(_, amount)| *amount != 0);
+    Ok(positions)
+}
+
+// Views balances of a ledger, which is either the whole chat or a trip in it.
+// Settings are always taken from the chat itself.
+async fn retrieve_ledger_debts(
+    redis: &Redis,
+    chat_id: &str,
+    ledger_id: &str,
+    option: StatementOption,
+) -> Result<Vec<Debt>, ProcessError> {
+    if get_pairwise_debts(redis, chat_id).await? {
+        return retrieve_debts_pairwise(redis, chat_id, ledger_id, option).await;
+    }
+
+    match option {
+        StatementOption::Currency(currency) => {
+            retrieve_debts_by_currency(redis, chat_id, ledger_id, &currency).await
+        }
+        StatementOption::ConvertCurrency => {
+            let default_currency = get_default_currency(redis, chat_id).await?;
+            retrieve_debts_converted(redis, chat_id, ledger_id, &default_currency).await
+        }
+        StatementOption::ConvertTo(currency) => {
+            retrieve_debts_converted(redis, chat_id, ledger_id, &currency).await
+        }
+    }
+}
+
+/* View debts of a group chat for a specific currency.
+ * Retrieves all balances, optimizes debts, and returns.
+ */
+async fn retrieve_debts_by_currency(
+    redis: &Redis,
+    chat_id: &str,
+    ledger_id: &str,
+    currency: &str,
+) -> Result<Vec<Debt>, ProcessError> {
+    let default_currency =
+        match get_chat_setting(redis, chat_id, ChatSetting::DefaultCurrency(None)).await? {
+            ChatSetting::DefaultCurrency(Some(curr)) => curr,
+            _ => CURRENCY_CODE_DEFAULT.to_string(),
+        };
+
+    // If currency is NIL, which is not default currency.
+    if currency == CURRENCY_CODE_DEFAULT && default_currency != CURRENCY_CODE_DEFAULT {
+        return retrieve_debts_by_default_currency(redis, chat_id, ledger_id).await;
+    }
+
+    // If currency is default currency, which is not NIL.
+    if default_currency == currency && currency != CURRENCY_CODE_DEFAULT {
+        return retrieve_debts_by_default_currency(redis, chat_id, ledger_id).await;
+    }
+
+    // If currency is not NIL, and is not default currency.
+    // Also, if currency is NIL, and NIL is default currency.
+    let strategy = retrieve_settlement_strategy(redis, chat_id).await?;
+    optimize_ledger_debts(&RedisStore::new(redis), ledger_id, currency, &strategy).await
+}
+
+// Adds a debt between two users to a list of pairwise debts,
+// netting it against any debt between them in the opposite direction.
+fn add_pairwise_debt(
+    debts: &mut Vec<Debt>,
+    debtor: &str,
+    creditor: &str,
+    currency: &str,
+    amount: i64,
+) {
+    if amount == 0 || is_username_equal(debtor, creditor) {
+        return;
+    }
+
+    let existing = debts.iter_mut().find(|debt| {
+        (is_username_equal(&debt.debtor, debtor) && is_username_equal(&debt.creditor, creditor))
+            || (is_username_equal(&debt.debtor, creditor)
+                && is_username_equal(&debt.creditor, debtor))
+    });
+    match existing {
+        Some(debt) if is_username_equal(&debt.debtor, debtor) => {
+            debt.amount = saturating_add(debt.amount, amount)
+        }
+        Some(debt) => debt.amount = saturating_add(debt.amount, -amount),
+        None => debts.push(Debt {
+            debtor: debtor.to_string(),
+            creditor: creditor.to_string(),
+            currency: currency.to_string(),
+            amount,
+        }),
+    }
+}
+
+/* View debts of a ledger as a raw pairwise ledger, computed directly from its payments.
+ * Each debt in a payment is owed to the creditor of that payment, netted between each pair,
+ * without being simplified by the optimizer.
+ * Payments without currency are counted under the default currency, if any.
+ * When converting, payments with a locked rate are converted with it, the rest with live rates.
+ */
+async fn retrieve_debts_pairwise(
+    redis: &Redis,
+    chat_id: &str,
+    ledger_id: &str,
+    option: StatementOption,
+) -> Result<Vec<Debt>, ProcessError> {
+    let payments = match get_chat_payments_details(redis, ledger_id).await {
+        Ok(payments) => payments,
+        Err(CrudError::NoPaymentsError()) => Vec::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let default_currency = get_default_currency(redis, chat_id).await?;
+    let target_currency = match &option {
+        StatementOption::Currency(_) => None,
+        StatementOption::ConvertCurrency => Some(default_currency.clone()),
+        StatementOption::ConvertTo(currency) => Some(currency.clone()),
+    };
+
+    let mut debts: Vec<Debt> = Vec::new();
+    for user_payment in payments {
+        let payment = user_payment.payment;
+        let currency = if payment.currency == CURRENCY_CODE_DEFAULT {
+            default_currency.clone()
+        } else {
+            payment.currency.clone()
+        };
+
+        // Finds the rate to convert the payment with, if any
+        let conversion_rate = match (&option, &target_currency) {
+            (StatementOption::Currency(option_currency), _) => {
+                let option_currency = if option_currency == CURRENCY_CODE_DEFAULT {
+                    &default_currency
+                } else {
+                    option_currency
+                };
+                if currency != *option_currency {
+                    continue;
+                }
+                None
+            }
+            (_, None) => None,
+            (_, Some(target_currency)) => {
+                if currency == *target_currency || currency == CURRENCY_CODE_DEFAULT {
+                    None
+                } else {
+                    match &user_payment.rate {
+                        Some((rate_currency, rate)) if rate_currency == target_currency => {
+                            Some((target_currency, *rate))
+                        }
+                        _ => match get_rate_cached(redis, &currency, target_currency).await {
+                            Ok(rate) => Some((target_currency, rate)),
+                            Err(err) => {
+                                return Err(ProcessError::ConversionError(format!(
+                                    "{currency} to {target_currency}: {err}"
+                                )))
+                            }
+                        },
+                    }
+                }
+            }
+        };
+
+        let debt_currency = match conversion_rate {
+            Some((target_currency, _)) => target_currency,
+            None => &currency,
+        };
+        for (debtor, amount) in &payment.debts {
+            let amount = match conversion_rate {
+                Some((target_currency, rate)) => {
+                    convert_currency_with_rate(*amount, &currency, target_currency, rate)
+                }
+                None => *amount,
+            };
+            add_pairwise_debt(&mut debts, debtor, &payment.creditor, debt_currency, amount);
+        }
+    }
+
+    // Debts are kept in the direction of what is owed
+    for debt in debts.iter_mut() {
+        if debt.amount < 0 {
+            std::mem::swap(&mut debt.debtor, &mut debt.creditor);
+            debt.amount = debt.amount.neg();
+        }
+    }
+    debts.retain(|debt| debt.amount != 0);
+    debts.sort_by_key(|debt| std::cmp::Reverse(debt.amount));
+
+    Ok(debts)
+}
+
+// Retrieves the settlement strategy of a group chat, based on whether there is a treasurer
+async fn retrieve_settlement_strategy(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<SettlementStrategy, ProcessError> {
+    match get_treasurer(redis, chat_id).await? {
+        Some(treasurer) => Ok(SettlementStrategy::Treasurer(treasurer)),
+        None => Ok(SettlementStrategy::Minimal),
+    }
+}
+
+/* Dumps the raw balances of a group chat and the debts optimized from them, for debugging.
+ * Shows the balances in each currency as stored, before any conversion,
+ * along with the settings that affect how debts are simplified,
+ * and an audit of the amounts of every payment.
+ */
+pub async fn retrieve_balances_dump(redis: &Redis, chat_id: &str) -> Result<String, ProcessError> {
+    let balances = get_chat_balances(redis, chat_id).await?;
+    let strategy = retrieve_settlement_strategy(redis, chat_id).await?;
+    let default_currency = get_default_currency(redis, chat_id).await?;
+    let is_pairwise = get_pairwise_debts(redis, chat_id).await?;
+    let payments = match get_chat_payments_details(redis, chat_id).await {
+        Ok(payments) => payments,
+        Err(CrudError::NoPaymentsError()) => Vec::new(),
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(format!(
+        "Chat: {chat_id}\nDefault currency: {default_currency}\nPairwise debts: {is_pairwise}\nStrategy: {strategy:?}\n\n{}\n\n{}",
+        format_balances_dump(balances, &strategy),
+        format_payments_audit(&payments)
+    ))
+}
+
+// Audits the amounts of payments, flagging those that can't be right.
+// Amounts are stored in the base units of their currency, so they are whole by design,
+// but a split that doesn't add up to the total points to amounts rounded wrongly,
+// such as when converting to a currency with fewer decimal places like JPY.
+fn format_payments_audit(payments: &[UserPayment]) -> String {
+    let mut issues: Vec<String> = Vec::new();
+    for payment in payments {
+        let id = &payment.payment_id;
+        let currency = &payment.payment.currency;
+        if currency != CURRENCY_CODE_DEFAULT && get_currency_from_code(currency).is_none() {
+            issues.push(format!("  {id}: unknown currency {currency}"));
+        }
+
+        let total = payment.payment.total;
+        let sum = payment
+            .payment
+            .debts
+            .iter()
+            .fold(0, |sum, (_, amount)| saturating_add(sum, *amount));
+        if sum != total {
+            issues.push(format!(
+                "  {id} [{currency}]: split sums to {sum}, but total is {total}"
+            ));
+        }
+        if total <= 0 || payment.payment.debts.iter().any(|(_, amount)| *amount < 0) {
+            issues.push(format!("  {id} [{currency}]: negative or zero amounts"));
+        }
+    }
+
+    if issues.is_empty() {
+        format!("Payment audit:\n  All {} payments OK", payments.len())
+    } else {
+        format!("Payment audit:\n{}", issues.join("\n"))
+    }
+}
+
+// Formats the raw balances in each currency, their sum, and the debts optimized from them.
+// Balances in a currency should always sum to zero, so any other sum is flagged.
+fn format_balances_dump(balances: Vec<Vec<UserBalance>>, strategy: &SettlementStrategy) -> String {
+    if balances.is_empty() {
+        return "No balances.".to_string();
+    }
+
+    let mut sections: Vec<String> = Vec::new();
+    for balances_currency in balances {
+        let currency = match balances_currency.first() {
+            Some(balance) => balance.currency.clone(),
+            None => continue,
+        };
+
+        let mut section = format!("[{currency}]\nBalances:");
+        for balance in &balances_currency {
+            section.push_str(&format!("\n  {}: {}", balance.username, balance.balance));
+        }
+        let sum = balances_currency
+            .iter()
+            .fold(0, |sum, balance| saturating_add(sum, balance.balance));
+        let flag = if sum == 0 { "" } else { " (NOT ZERO)" };
+        section.push_str(&format!("\nSum: {sum}{flag}\nOptimized debts:"));
+
+        let debts = optimize_debts(balances_currency, strategy);
+        if debts.is_empty() {
+            section.push_str("\n  None");
+        }
+        for debt in debts {
+            section.push_str(&format!(
+                "\n  {} -> {}: {}",
+                debt.debtor, debt.creditor, debt.amount
+            ));
+        }
+        sections.push(section);
+    }
+
+    sections.join("\n\n")
+}
+
+/* View debts of a group chat for the default currency.
+ * Retrieves all balances, optimizes debts, and returns.
+ */
+async fn retrieve_debts_by_default_currency(
+    redis: &Redis,
+    chat_id: &str,
+    ledger_id: &str,
+) -> Result<Vec<Debt>, ProcessError> {
+    let currency =
+        match get_chat_setting(redis, chat_id, ChatSetting::DefaultCurrency(None)).await? {
+            ChatSetting::DefaultCurrency(Some(curr)) => curr,
+            _ => CURRENCY_CODE_DEFAULT.to_string(),
+        };
+    let mut balances_curr = RedisStore::new(redis)
+        .get_balances(ledger_id, &currency)
+        .await?;
+    let balances_nil = RedisStore::new(redis)
+        .get_balances(ledger_id, CURRENCY_CODE_DEFAULT)
+        .await?;
+
+    for balance in balances_nil {
+        let curr_index = balances_curr
+            .iter()
+            .position(|bal| bal.username == balance.username);
+        match curr_index {
+            Some(index) => {
+                balances_curr[index].balance =
+                    checked_add(balances_curr[index].balance, balance.balance)?;
+            }
+            None => {
+                balances_curr.push(balance);
+            }
+        }
+    }
+
+    let strategy = retrieve_settlement_strategy(redis, chat_id).await?;
+    let debts = optimize_debts(balances_curr, &strategy);
+
+    Ok(debts)
+}
+
+/* View debts of a group chat for all currencies, converted to a target currency.
+ * The target is usually the default currency, but can be any other for a one-off view.
+ * Amounts without currency are taken to be in the default currency.
+ * Payments with a locked rate to the target are converted with it, the rest with live rates.
+ * Retrieves all balances, optimizes debts, and returns.
+ */
+async fn retrieve_debts_converted(
+    redis: &Redis,
+    chat_id: &str,
+    ledger_id: &str,
+    target_currency: &str,
+) -> Result<Vec<Debt>, ProcessError> {
+    let mut balances = get_chat_balances(redis, ledger_id).await?;
+    let default_currency = get_default_currency(redis, chat_id).await?;
+    // Rates are only ever locked to the default currency
+    let locked_amounts = if target_currency == default_currency {
+        retrieve_locked_amounts(redis, ledger_id, &default_currency).await?
+    } else {
+        Vec::new()
+    };
+
+    let mut converted_balances: Vec<UserBalance> = Vec::new();
+    for balances_currency in &mut balances {
+        if balances_currency.is_empty() {
+            continue;
+        }
+
+        let currency = balances_currency[0].currency.clone();
+        let source_currency = get_source_currency(&currency, &default_currency);
+        let should_convert =
+            source_currency != target_currency && source_currency != CURRENCY_CODE_DEFAULT;
+
+        let conversion_rate = if should_convert {
+            match get_rate_cached(redis, source_currency, target_currency).await {
+                Ok(rate) => rate,
+                Err(err) => {
+                    return Err(ProcessError::ConversionError(format!(
+                        "{source_currency} to {target_currency}: {err}"
+                    )))
+                }
+            }
+        } else {
+            1.0
+        };
+
+        for balance in balances_currency {
+            let mut amount = balance.balance;
+            if should_convert {
+                let locked = find_locked_amount(&locked_amounts, &balance.username, &currency)
+                    .map_or((0, 0), |locked| (locked.balance, locked.converted_balance));
+                amount = convert_currency_with_locked(
+                    amount,
+                    locked,
+                    source_currency,
+                    target_currency,
+                    conversion_rate,
+                );
+            }
+            let user = converted_balances
+                .iter()
+                .position(|bal| bal.username == balance.username);
+
+            match user {
+                Some(index) => {
+                    converted_balances[index].balance =
+                        saturating_add(converted_balances[index].balance, amount);
+                }
+                None => {
+                    converted_balances.push(UserBalance {
+                        username: balance.username.clone(),
+                        currency: target_currency.to_string(),
+                        balance: amount,
+                    });
+                }
+            }
+        }
+    }
+
+    let strategy = retrieve_settlement_strategy(redis, chat_id).await?;
+    let debts = optimize_debts(converted_balances, &strategy);
+
+    Ok(debts)
+}
+
+/* View spendings of a group chat.
+ * Takes in a specification of the options for viewing.
+ * Which is whether the currency is to be converted, and which currency.
+ */
+pub async fn retrieve_spending_data(
+    redis: &Redis,
+    chat_id: &str,
+    option: StatementOption,
+) -> Result<SpendingData, ProcessError> {
+    retrieve_ledger_spending_data(redis, chat_id, chat_id, option).await
+}
+
+/* View spendings of a trip in a group chat.
+ * Takes in a specification of the options for viewing, like self::retrieve_spending_data.
+ */
+pub async fn retrieve_trip_spending_data(
+    redis: &Redis,
+    chat_id: &str,
+    trip_id: &str,
+    option: StatementOption,
+) -> Result<SpendingData, ProcessError> {
+    retrieve_ledger_spending_data(redis, chat_id, &get_trip_ledger(chat_id, trip_id), option).await
+}
+
+// Views spendings of a ledger, which is either the whole chat or a trip in it.
+// Settings are always taken from the chat itself.
+async fn retrieve_ledger_spending_data(
+    redis: &Redis,
+    chat_id: &str,
+    ledger_id: &str,
+    option: StatementOption,
+) -> Result<SpendingData, ProcessError> {
+    match option {
+        StatementOption::Currency(currency) => {
+            retrieve_spending_data_by_currency(redis, chat_id, ledger_id, &currency).await
+        }
+        StatementOption::ConvertCurrency => {
+            let default_currency = get_default_currency(redis, chat_id).await?;
+            retrieve_spending_data_converted(redis, chat_id, ledger_id, &default_currency).await
+        }
+        StatementOption::ConvertTo(currency) => {
+            retrieve_spending_data_converted(redis, chat_id, ledger_id, &currency).await
+        }
+    }
+}
+
+/* View spendings of a group chat for a specific currency.
+ * Retrieves all spendings, gets current balances, and returns:
+ * Total group spending, total individual spendings, and total individual payments
+ */
+async fn retrieve_spending_data_by_currency(
+    redis: &Redis,
+    chat_id: &str,
+    ledger_id: &str,
+    currency: &str,
+) -> Result<SpendingData, ProcessError> {
+    let default_currency =
+        match get_chat_setting(redis, chat_id, ChatSetting::DefaultCurrency(None)).await? {
+            ChatSetting::DefaultCurrency(Some(curr)) => curr,
+            _ => CURRENCY_CODE_DEFAULT.to_string(),
+        };
+    if default_currency == currency && currency != CURRENCY_CODE_DEFAULT {
+        return retrieve_spending_data_by_default_currency(redis, ledger_id, currency).await;
+    }
+
+    let spendings = retrieve_chat_spendings_currency(redis, ledger_id, currency).await?;
+    let balances = RedisStore::new(redis)
+        .get_balances(ledger_id, currency)
+        .await?;
+
+    let mut group_spending = 0;
+    let mut user_spendings: Vec<UserSpending> = Vec::new();
+    for spending in spendings {
+        group_spending = checked_add(group_spending, spending.balance)?;
 
         let balance = balances
             .iter()
-            .find(|bal| bal.username == spending.username)
-            .map(|bal| bal.balance)
-            .unwrap_or(0);
-        let paid = spending.balance + balance;
+            .find(|bal| bal.username == spending.username)
+            .map(|bal| bal.balance)
+            .unwrap_or(0);
+        let paid = checked_add(spending.balance, balance)?;
+
+        if spending.balance != 0 || paid != 0 {
+            user_spendings.push(UserSpending {
+                username: spending.username.clone(),
+                spending: spending.balance,
+                paid,
+                fronted: 0,
+            });
+        }
+    }
+
+    // Check through for any balances that aren't accounted for in a spending
+    for balance in balances {
+        let user = user_spendings
+            .iter()
+            .position(|spending| spending.username == balance.username);
+        match user {
+            Some(_) => {
+                continue;
+            }
+            None => {
+                user_spendings.push(UserSpending {
+                    username: balance.username,
+                    spending: 0,
+                    paid: balance.balance,
+                    fronted: 0,
+                });
+            }
+        }
+    }
+
+    Ok(SpendingData {
+        currency: currency.to_string(),
+        group_spending,
+        user_spendings,
+    })
+}
+
+/* View spendings of a group chat for the default currency.
+ * Retrieves all spendings, gets current balances, and returns:
+ * Total group spending, total individual spendings, and total individual payments
+ */
+async fn retrieve_spending_data_by_default_currency(
+    redis: &Redis,
+    ledger_id: &str,
+    currency: &str,
+) -> Result<SpendingData, ProcessError> {
+    let spendings_curr = retrieve_chat_spendings_currency(redis, ledger_id, currency).await?;
+    let spendings_nil =
+        retrieve_chat_spendings_currency(redis, ledger_id, CURRENCY_CODE_DEFAULT).await?;
+    let mut balances_curr = RedisStore::new(redis)
+        .get_balances(ledger_id, currency)
+        .await?;
+    let mut balances_nil = RedisStore::new(redis)
+        .get_balances(ledger_id, CURRENCY_CODE_DEFAULT)
+        .await?;
+
+    let mut group_spending = 0;
+    let mut user_spendings: Vec<UserSpending> = Vec::new();
+    for spending in spendings_curr {
+        group_spending = checked_add(group_spending, spending.balance)?;
+
+        let paid: i64;
+        let balance_index = balances_curr
+            .iter()
+            .position(|bal| bal.username == spending.username);
+        match balance_index {
+            Some(index) => {
+                paid = checked_add(spending.balance, balances_curr[index].balance)?;
+                balances_curr[index].balance = 0;
+            }
+            None => {
+                paid = 0;
+            }
+        }
+
+        user_spendings.push(UserSpending {
+            username: spending.username.clone(),
+            spending: spending.balance,
+            paid,
+            fronted: 0,
+        });
+    }
+
+    for spending in spendings_nil {
+        group_spending = checked_add(group_spending, spending.balance)?;
+
+        let paid: i64;
+        let balance_index = balances_nil
+            .iter()
+            .position(|bal| bal.username == spending.username);
+        match balance_index {
+            Some(index) => {
+                paid = checked_add(spending.balance, balances_nil[index].balance)?;
+                balances_nil[index].balance = 0;
+            }
+            None => {
+                paid = 0;
+            }
+        }
+
+        let user = user_spendings
+            .iter()
+            .position(|user| user.username == spending.username);
+
+        match user {
+            Some(index) => {
+                let user_spending = &mut user_spendings[index];
+                user_spending.spending = checked_add(user_spending.spending, spending.balance)?;
+                user_spending.paid = checked_add(user_spending.paid, paid)?;
+            }
+            None => {
+                user_spendings.push(UserSpending {
+                    username: spending.username.clone(),
+                    spending: spending.balance,
+                    paid,
+                    fronted: 0,
+                });
+            }
+        }
+    }
+
+    // Check through for any balances that aren't accounted for in a spending
+    for balance in balances_curr {
+        if balance.balance != 0 {
+            let user = user_spendings
+                .iter()
+                .position(|spending| spending.username == balance.username);
+            match user {
+                Some(index) => {
+                    user_spendings[index].paid =
+                        checked_add(user_spendings[index].paid, balance.balance)?;
+                }
+                None => {
+                    user_spendings.push(UserSpending {
+                        username: balance.username,
+                        spending: 0,
+                        paid: balance.balance,
+                        fronted: 0,
+                    });
+                }
+            }
+        }
+    }
+
+    for balance in balances_nil {
+        if balance.balance != 0 {
+            let user = user_spendings
+                .iter()
+                .position(|spending| spending.username == balance.username);
+            match user {
+                Some(index) => {
+                    user_spendings[index].paid =
+                        checked_add(user_spendings[index].paid, balance.balance)?;
+                }
+                None => {
+                    user_spendings.push(UserSpending {
+                        username: balance.username,
+                        spending: 0,
+                        paid: balance.balance,
+                        fronted: 0,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(SpendingData {
+        currency: currency.to_string(),
+        group_spending,
+        user_spendings,
+    })
+}
+
+/* View spendings of a group chat, converted to a target currency.
+ * The target is usually the default currency, but can be any other for a one-off view.
+ * Retrieves all spendings, gets current balances, converts them.
+ * Payments with a locked rate to the target are converted with it, the rest with live rates.
+ */
+async fn retrieve_spending_data_converted(
+    redis: &Redis,
+    chat_id: &str,
+    ledger_id: &str,
+    target_currency: &str,
+) -> Result<SpendingData, ProcessError> {
+    let mut spendings = retrieve_chat_spendings(redis, ledger_id).await?;
+    let mut balances = get_chat_balances(redis, ledger_id).await?;
+
+    let default_currency = get_default_currency(redis, chat_id).await?;
+    // Rates are only ever locked to the default currency
+    let locked_amounts = if target_currency == default_currency {
+        retrieve_locked_amounts(redis, ledger_id, &default_currency).await?
+    } else {
+        Vec::new()
+    };
+    let mut group_spending = 0;
+    let mut user_spendings: Vec<UserSpending> = Vec::new();
+    for spending_currency in &mut spendings {
+        if spending_currency.is_empty() {
+            continue;
+        }
+
+        let currency = spending_currency[0].currency.clone();
+        let source_currency = get_source_currency(&currency, &default_currency);
+        let should_convert =
+            source_currency != target_currency && source_currency != CURRENCY_CODE_DEFAULT;
+
+        let conversion_rate = if should_convert {
+            match get_rate_cached(redis, source_currency, target_currency).await {
+                Ok(rate) => rate,
+                Err(err) => {
+                    return Err(ProcessError::ConversionError(format!(
+                        "{source_currency} to {target_currency}: {err}"
+                    )))
+                }
+            }
+        } else {
+            1.0
+        };
+
+        let mut empty_balances: Vec<UserBalance> = Vec::new();
+        let balances_currency: &mut Vec<UserBalance> = match balances
+            .iter_mut()
+            .find(|bal| !bal.is_empty() && bal[0].currency == *currency)
+        {
+            Some(bal) => bal,
+            None => &mut empty_balances,
+        };
+
+        for spending in spending_currency {
+            let balance_index = balances_currency
+                .iter()
+                .position(|bal| bal.username == spending.username);
+
+            let mut balance_amount = 0;
+            if let Some(index) = balance_index {
+                balance_amount = balances_currency[index].balance;
+                balances_currency[index].balance = 0;
+            }
+
+            let mut spending_amount = spending.balance;
+            let mut paid_amount = checked_add(spending_amount, balance_amount)?;
+
+            if should_convert {
+                let locked = find_locked_amount(&locked_amounts, &spending.username, &currency);
+                spending_amount = convert_currency_with_locked(
+                    spending_amount,
+                    locked.map_or((0, 0), |locked| {
+                        (locked.spending, locked.converted_spending)
+                    }),
+                    source_currency,
+                    target_currency,
+                    conversion_rate,
+                );
+                paid_amount = checked_add(
+                    spending_amount,
+                    convert_currency_with_locked(
+                        balance_amount,
+                        locked.map_or((0, 0), |locked| (locked.balance, locked.converted_balance)),
+                        source_currency,
+                        target_currency,
+                        conversion_rate,
+                    ),
+                )?;
+            }
+
+            group_spending = checked_add(group_spending, spending_amount)?;
+
+            let user = user_spendings
+                .iter()
+                .position(|user| user.username == spending.username);
+
+            match user {
+                Some(index) => {
+                    let user_spending = &mut user_spendings[index];
+                    user_spending.spending = checked_add(user_spending.spending, spending_amount)?;
+                    user_spending.paid = checked_add(user_spending.paid, paid_amount)?;
+                }
+                None => {
+                    user_spendings.push(UserSpending {
+                        username: spending.username.clone(),
+                        spending: spending_amount,
+                        paid: paid_amount,
+                        fronted: 0,
+                    });
+                }
+            }
+        }
+
+        // Check through for any balances that aren't accounted for in a spending
+        for balance in balances_currency {
+            if balance.balance != 0 {
+                let user = user_spendings
+                    .iter()
+                    .position(|spending| spending.username == balance.username);
+                let converted_balance = if should_convert {
+                    let locked = find_locked_amount(&locked_amounts, &balance.username, &currency)
+                        .map_or((0, 0), |locked| (locked.balance, locked.converted_balance));
+                    convert_currency_with_locked(
+                        balance.balance,
+                        locked,
+                        source_currency,
+                        target_currency,
+                        conversion_rate,
+                    )
+                } else {
+                    balance.balance
+                };
+                match user {
+                    Some(index) => {
+                        user_spendings[index].paid =
+                            checked_add(user_spendings[index].paid, converted_balance)?;
+                    }
+                    None => {
+                        user_spendings.push(UserSpending {
+                            username: balance.username.clone(),
+                            spending: 0,
+                            paid: converted_balance,
+                            fronted: 0,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(SpendingData {
+        currency: target_currency.to_string(),
+        group_spending,
+        user_spendings,
+    })
+}
+
+/* Retrieves a group chat setting.
+*/
+pub async fn get_chat_setting(
+    redis: &Redis,
+    chat_id: &str,
+    setting: ChatSetting,
+) -> Result<ChatSetting, ProcessError> {
+    match setting {
+        ChatSetting::TimeZone(_) => {
+            let time_zone = get_time_zone(redis, chat_id).await?;
+            Ok(ChatSetting::TimeZone(Some(time_zone)))
+        }
+        ChatSetting::DefaultCurrency(_) => {
+            let currency = get_default_currency(redis, chat_id).await?;
+            Ok(ChatSetting::DefaultCurrency(Some(currency)))
+        }
+        ChatSetting::CurrencyConversion(_) => {
+            let convert = get_currency_conversion(redis, chat_id).await?;
+            Ok(ChatSetting::CurrencyConversion(Some(convert)))
+        }
+        ChatSetting::EraseMessages(_) => {
+            let erase = get_erase_messages(redis, chat_id).await?;
+            Ok(ChatSetting::EraseMessages(Some(erase)))
+        }
+        ChatSetting::EraseUserMessages(_) => {
+            let erase_user_messages = get_erase_user_messages(redis, chat_id).await?;
+            Ok(ChatSetting::EraseUserMessages(Some(erase_user_messages)))
+        }
+        ChatSetting::MonthlyArchive(_) => {
+            let archive = get_monthly_archive(redis, chat_id).await?;
+            Ok(ChatSetting::MonthlyArchive(Some(archive)))
+        }
+        ChatSetting::WeeklyDigest(_) => {
+            let time = get_weekly_digest(redis, chat_id).await?;
+            Ok(ChatSetting::WeeklyDigest(Some(time)))
+        }
+        ChatSetting::PaymentApproval(_) => {
+            let threshold = get_approval_threshold(redis, chat_id).await?;
+            Ok(ChatSetting::PaymentApproval(Some(threshold)))
+        }
+        ChatSetting::Language(_) => {
+            let language = get_language(redis, chat_id).await?;
+            Ok(ChatSetting::Language(Some(language)))
+        }
+        ChatSetting::NumberFormat(_) => {
+            let number_format = get_number_format(redis, chat_id).await?;
+            Ok(ChatSetting::NumberFormat(Some(number_format)))
+        }
+        ChatSetting::CurrencyFormat(_) => {
+            let currency_format = get_currency_format(redis, chat_id).await?;
+            Ok(ChatSetting::CurrencyFormat(Some(currency_format)))
+        }
+        ChatSetting::DebtReminder(_) => {
+            let days = get_debt_reminder(redis, chat_id).await?;
+            Ok(ChatSetting::DebtReminder(Some(days)))
+        }
+        ChatSetting::DataRetention(_) => {
+            let months = get_data_retention(redis, chat_id).await?;
+            Ok(ChatSetting::DataRetention(Some(months)))
+        }
+        ChatSetting::AdminOnly(_) => {
+            let admin_only = get_admin_only(redis, chat_id).await?;
+            Ok(ChatSetting::AdminOnly(Some(admin_only)))
+        }
+        ChatSetting::Treasurer(_) => {
+            let treasurer = get_treasurer(redis, chat_id).await?;
+            Ok(ChatSetting::Treasurer(Some(treasurer)))
+        }
+        ChatSetting::PairwiseDebts(_) => {
+            let pairwise_debts = get_pairwise_debts(redis, chat_id).await?;
+            Ok(ChatSetting::PairwiseDebts(Some(pairwise_debts)))
+        }
+        ChatSetting::PassiveMode(_) => {
+            let passive_mode = get_passive_mode(redis, chat_id).await?;
+            Ok(ChatSetting::PassiveMode(Some(passive_mode)))
+        }
+        ChatSetting::DualEntry(_) => {
+            let dual_entry = get_dual_entry(redis, chat_id).await?;
+            Ok(ChatSetting::DualEntry(Some(dual_entry)))
+        }
+        ChatSetting::StrictMembers(_) => {
+            let strict_members = get_strict_members(redis, chat_id).await?;
+            Ok(ChatSetting::StrictMembers(Some(strict_members)))
+        }
+        ChatSetting::Verbosity(_) => {
+            let verbosity = get_verbosity(redis, chat_id).await?;
+            Ok(ChatSetting::Verbosity(Some(verbosity)))
+        }
+        ChatSetting::Rounding(_) => {
+            let rounding = get_rounding(redis, chat_id).await?;
+            Ok(ChatSetting::Rounding(Some(rounding)))
+        }
+        ChatSetting::AllowedCurrencies(_) => {
+            let currencies = get_allowed_currencies(redis, chat_id).await?;
+            Ok(ChatSetting::AllowedCurrencies(Some(currencies)))
+        }
+        ChatSetting::WeekStart(_) => {
+            let week_start = get_week_start(redis, chat_id).await?;
+            Ok(ChatSetting::WeekStart(Some(week_start)))
+        }
+    }
+}
+
+/* Sets a group chat setting.
+*/
+pub async fn set_chat_setting(
+    redis: &Redis,
+    chat_id: &str,
+    setting: ChatSetting,
+) -> Result<(), ProcessError> {
+    match setting {
+        ChatSetting::TimeZone(time_zone) => {
+            if let Some(time_zone) = time_zone {
+                set_time_zone(redis, chat_id, &time_zone).await?;
+            }
+        }
+        ChatSetting::DefaultCurrency(currency) => {
+            if let Some(currency) = currency {
+                set_default_currency(redis, chat_id, &currency).await?;
+            }
+        }
+        ChatSetting::CurrencyConversion(convert) => {
+            if let Some(convert) = convert {
+                set_currency_conversion(redis, chat_id, convert).await?;
+
+                // If currency conversion is updated, need to update balances
+                update_balances(redis, chat_id, Vec::new()).await?;
+            }
+        }
+        ChatSetting::EraseMessages(erase) => {
+            if let Some(erase) = erase {
+                set_erase_messages(redis, chat_id, erase).await?;
+            }
+        }
+        ChatSetting::EraseUserMessages(erase_user_messages) => {
+            if let Some(erase_user_messages) = erase_user_messages {
+                set_erase_user_messages(redis, chat_id, erase_user_messages).await?;
+            }
+        }
+        ChatSetting::MonthlyArchive(archive) => {
+            if let Some(archive) = archive {
+                set_monthly_archive(redis, chat_id, archive).await?;
+            }
+        }
+        ChatSetting::WeeklyDigest(time) => {
+            if let Some(time) = time {
+                set_weekly_digest(redis, chat_id, time.as_deref()).await?;
+            }
+        }
+        ChatSetting::PaymentApproval(threshold) => {
+            if let Some(threshold) = threshold {
+                set_approval_threshold(redis, chat_id, threshold).await?;
+            }
+        }
+        ChatSetting::Language(language) => {
+            if let Some(language) = language {
+                set_language(redis, chat_id, &language).await?;
+            }
+        }
+        ChatSetting::NumberFormat(number_format) => {
+            if let Some(number_format) = number_format {
+                set_number_format(redis, chat_id, &number_format).await?;
+            }
+        }
+        ChatSetting::CurrencyFormat(currency_format) => {
+            if let Some(currency_format) = currency_format {
+                set_currency_format(redis, chat_id, &currency_format).await?;
+            }
+        }
+        ChatSetting::DebtReminder(days) => {
+            if let Some(days) = days {
+                set_debt_reminder(redis, chat_id, days).await?;
+            }
+        }
+        ChatSetting::DataRetention(months) => {
+            if let Some(months) = months {
+                set_data_retention(redis, chat_id, months).await?;
+            }
+        }
+        ChatSetting::AdminOnly(admin_only) => {
+            if let Some(admin_only) = admin_only {
+                set_admin_only(redis, chat_id, admin_only).await?;
+            }
+        }
+        ChatSetting::Treasurer(treasurer) => {
+            if let Some(treasurer) = treasurer {
+                set_treasurer(redis, chat_id, treasurer.as_deref()).await?;
+            }
+        }
+        ChatSetting::PairwiseDebts(pairwise_debts) => {
+            if let Some(pairwise_debts) = pairwise_debts {
+                set_pairwise_debts(redis, chat_id, pairwise_debts).await?;
+            }
+        }
+        ChatSetting::PassiveMode(passive_mode) => {
+            if let Some(passive_mode) = passive_mode {
+                set_passive_mode(redis, chat_id, passive_mode).await?;
+            }
+        }
+        ChatSetting::DualEntry(dual_entry) => {
+            if let Some(dual_entry) = dual_entry {
+                set_dual_entry(redis, chat_id, dual_entry).await?;
+            }
+        }
+        ChatSetting::StrictMembers(strict_members) => {
+            if let Some(strict_members) = strict_members {
+                set_strict_members(redis, chat_id, strict_members).await?;
+            }
+        }
+        ChatSetting::Verbosity(verbosity) => {
+            if let Some(verbosity) = verbosity {
+                set_verbosity(redis, chat_id, &verbosity).await?;
+            }
+        }
+        ChatSetting::Rounding(rounding) => {
+            if let Some(rounding) = rounding {
+                set_rounding(redis, chat_id, &rounding).await?;
+            }
+        }
+        ChatSetting::AllowedCurrencies(currencies) => {
+            if let Some(currencies) = currencies {
+                set_allowed_currencies(redis, chat_id, &currencies).await?;
+            }
+        }
+        ChatSetting::WeekStart(week_start) => {
+            if let Some(week_start) = week_start {
+                set_week_start(redis, chat_id, &week_start).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/* Retrieves a group chat setting as seen from a topic in a forum chat.
+ * Resolves the time zone and default currency from the topic overrides first,
+ * and falls back to the chat setting if there are none, or for all other settings.
+ */
+pub async fn get_topic_chat_setting(
+    redis: &Redis,
+    chat_id: &str,
+    topic_id: Option<i32>,
+    setting: ChatSetting,
+) -> Result<ChatSetting, ProcessError> {
+    if let Some(topic_id) = topic_id {
+        match setting {
+            ChatSetting::TimeZone(_) => {
+                if let Some(time_zone) = get_topic_time_zone(redis, chat_id, topic_id).await? {
+                    return Ok(ChatSetting::TimeZone(Some(time_zone)));
+                }
+            }
+            ChatSetting::DefaultCurrency(_) => {
+                if let Some(currency) = get_topic_default_currency(redis, chat_id, topic_id).await?
+                {
+                    return Ok(ChatSetting::DefaultCurrency(Some(currency)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    get_chat_setting(redis, chat_id, setting).await
+}
+
+/* Overrides a group chat setting for a topic in a forum chat.
+ * Only the time zone and default currency can be overridden.
+ */
+pub async fn set_topic_chat_setting(
+    redis: &Redis,
+    chat_id: &str,
+    topic_id: i32,
+    setting: ChatSetting,
+) -> Result<(), ProcessError> {
+    match setting {
+        ChatSetting::TimeZone(Some(time_zone)) => {
+            set_topic_time_zone(redis, chat_id, topic_id, &time_zone).await?;
+        }
+        ChatSetting::DefaultCurrency(Some(currency)) => {
+            set_topic_default_currency(redis, chat_id, topic_id, &currency).await?;
+        }
+        _ => {
+            return Err(ProcessError::ValidationError(Text::new(
+                TOPIC_SETTING_UNSUPPORTED,
+            )));
+        }
+    }
+    Ok(())
+}
+
+/* Removes all setting overrides of a topic in a forum chat.
+ * The topic then follows the settings of the chat again.
+ */
+pub async fn reset_topic_chat_settings(
+    redis: &Redis,
+    chat_id: &str,
+    topic_id: i32,
+) -> Result<(), ProcessError> {
+    reset_topic_settings(redis, chat_id, topic_id).await?;
+    Ok(())
+}
+
+/* Changes the default currency of a group chat.
+ * Also handles all the conversion logic for past payments.
+ */
+pub async fn update_chat_default_currency(
+    redis: &Redis,
+    chat_id: &str,
+    currency: &str,
+) -> Result<(), ProcessError> {
+    let old_currency = get_default_currency(redis, chat_id).await?;
+
+    // Update all payments to old currency
+    let payments = get_chat_payments_details(redis, chat_id).await;
+    let mut changes: Vec<UserBalance> = Vec::new();
+
+    match payments {
+        Ok(payments) => {
+            for payment in payments {
+                if payment.payment.currency == CURRENCY_CODE_DEFAULT {
+                    update_payment_entry(
+                        redis,
+                        &payment.payment_id,
+                        None,
+                        None,
+                        None,
+                        Some(&old_currency),
+                        None,
+                        None,
+                    )
+                    .await?;
+                }
+            }
+
+            // Update all balances to old currency
+            let balances = RedisStore::new(redis)
+                .get_balances(chat_id, CURRENCY_CODE_DEFAULT)
+                .await?;
+            for balance in balances {
+                let change_sub = UserBalance {
+                    username: balance.username.clone(),
+                    currency: balance.currency,
+                    balance: balance.balance.neg(),
+                };
+                let change_add = UserBalance {
+                    username: balance.username.clone(),
+                    currency: old_currency.clone(),
+                    balance: balance.balance,
+                };
+                changes.extend(vec![change_sub, change_add]);
+            }
+
+            // Update all spendings to old currency
+            let mut spendings_changes: Vec<UserBalance> = Vec::new();
+            let spendings =
+                retrieve_spending_data_by_currency(redis, chat_id, chat_id, CURRENCY_CODE_DEFAULT)
+                    .await?;
+            for spending in spendings.user_spendings {
+                let change_sub = UserBalance {
+                    username: spending.username.clone(),
+                    currency: CURRENCY_CODE_DEFAULT.to_string(),
+                    balance: spending.spending.neg(),
+                };
+                let change_add = UserBalance {
+                    username: spending.username.clone(),
+                    currency: old_currency.clone(),
+                    balance: spending.spending,
+                };
+                spendings_changes.extend(vec![change_sub, change_add]);
+            }
+
+            RedisStore::new(redis)
+                .update_spendings(chat_id, spendings_changes)
+                .await?;
+        }
+        Err(_) => {
+            // This means that there were no payments found
+        }
+    }
+
+    // Update default currency in settings. If now NIL, disable currency conversion.
+    set_default_currency(redis, chat_id, currency).await?;
+    if currency == CURRENCY_CODE_DEFAULT {
+        set_currency_conversion(redis, chat_id, false).await?;
+    }
+
+    // Finally, update balances and debts
+    update_balances(redis, chat_id, changes).await?;
+
+    Ok(())
+}
+
+/* Retrieves all group chats with monthly archive enabled.
+ */
+pub async fn retrieve_monthly_archive_chats(redis: &Redis) -> Result<Vec<String>, ProcessError> {
+    let chats = get_monthly_archive_chats(redis).await?;
+    Ok(chats)
+}
+
+/* Retrieves the last month archived for a group chat, if any.
+ */
+pub async fn retrieve_last_archive_month(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<String>, ProcessError> {
+    let month = get_last_archive_month(redis, chat_id).await?;
+    Ok(month)
+}
+
+/* Updates the last month archived for a group chat.
+ */
+pub async fn update_last_archive_month(
+    redis: &Redis,
+    chat_id: &str,
+    month: &str,
+) -> Result<(), ProcessError> {
+    set_last_archive_month(redis, chat_id, month).await?;
+    Ok(())
+}
+
+// Parses the datetime of a payment entry, which is always stored in UTC
+fn parse_payment_datetime(datetime: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(datetime.trim_end_matches(" UTC"), "%Y-%m-%d %H:%M:%S").ok()
+}
+
+/* Archives the payments of a group chat from before a cutoff, under the given month.
+ * Execution flow: Retrieve payments, archive those before the cutoff.
+ * Closing balances are carried forward as a single adjustment entry per currency.
+ * Balances and spendings are unchanged, as the carried forward entry replaces the archived ones.
+ * Returns None if there were no payments to archive.
+ */
+pub async fn archive_monthly_payments(
+    redis: &Redis,
+    chat_id: &str,
+    month: &str,
+    cutoff: NaiveDateTime,
+) -> Result<Option<ArchiveSummary>, ProcessError> {
+    let payments = match get_chat_payments_details(redis, chat_id).await {
+        Ok(payments) => payments,
+        Err(CrudError::NoPaymentsError()) => Vec::new(),
+        Err(err) => return Err(err.into()),
+    };
+
+    let (archived, remaining): (Vec<UserPayment>, Vec<UserPayment>) =
+        payments.into_iter().partition(|payment| {
+            match parse_payment_datetime(&payment.payment.datetime) {
+                Some(datetime) => datetime < cutoff,
+                None => true,
+            }
+        });
+
+    if archived.is_empty() {
+        return Ok(None);
+    }
+
+    // Closing balances are checked before anything is archived
+    let closing_balances = get_closing_balances(redis, chat_id, &remaining).await?;
+
+    // Archive payments
+    let payment_ids: Vec<String> = archived
+        .iter()
+        .map(|payment| payment.payment_id.clone())
+        .collect();
+    archive_payment_entries(redis, chat_id, month, payment_ids).await?;
+
+    let description = format!("Balances carried forward from {month}");
+    let debts =
+        carry_forward_balances(redis, chat_id, closing_balances, &description, cutoff).await?;
+
+    Ok(Some(ArchiveSummary {
+        month: month.to_string(),
+        payments: archived.len(),
+        debts,
+    }))
+}
+
+// Gets the closing balances of a group chat from before a cutoff, before older payments are gone.
+// Closing balances are the current balances, without the remaining payments.
+async fn get_closing_balances(
+    redis: &Redis,
+    chat_id: &str,
+    remaining: &[UserPayment],
+) -> Result<Vec<Vec<UserBalance>>, ProcessError> {
+    let mut closing_balances = get_chat_balances(redis, chat_id).await?;
+    for payment in remaining {
+        let payment = &payment.payment;
+        let mut changes: Vec<(String, i64)> = payment
+            .debts
+            .iter()
+            .map(|(user, amount)| (user.to_string(), *amount))
+            .collect();
+        changes.push((payment.creditor.clone(), payment.total.neg()));
+
+        let index = match closing_balances
+            .iter()
+            .position(|bal| !bal.is_empty() && bal[0].currency == payment.currency)
+        {
+            Some(index) => index,
+            None => {
+                closing_balances.push(Vec::new());
+                closing_balances.len() - 1
+            }
+        };
+        for (user, amount) in changes {
+            match closing_balances[index]
+                .iter()
+                .position(|bal| is_username_equal(&bal.username, &user))
+            {
+                Some(user_index) => {
+                    let balance = &mut closing_balances[index][user_index];
+                    balance.balance = checked_add(balance.balance, amount)?;
+                }
+                None => closing_balances[index].push(UserBalance {
+                    username: user,
+                    currency: payment.currency.clone(),
+                    balance: amount,
+                }),
+            }
+        }
+    }
+
+    Ok(closing_balances)
+}
+
+// Carries forward the closing balances of a group chat, once older payments are gone.
+// Closing balances are added as a single adjustment entry per currency, dated at the cutoff.
+// Balances and spendings are unchanged, as the adjustments replace the older payments.
+// Returns the debts carried forward.
+async fn carry_forward_balances(
+    redis: &Redis,
+    chat_id: &str,
+    closing_balances: Vec<Vec<UserBalance>>,
+    description: &str,
+    cutoff: NaiveDateTime,
+) -> Result<Vec<Debt>, ProcessError> {
+    let strategy = retrieve_settlement_strategy(redis, chat_id).await?;
+    let mut debts: Vec<Debt> = Vec::new();
+    for mut balances in closing_balances {
+        balances.retain(|bal| bal.balance != 0);
+        if balances.is_empty() {
+            continue;
+        }
+        balances.sort_by_key(|bal| bal.balance.neg());
+
+        let creditor = balances[0].clone();
+        let adjustment = Payment {
+            description: description.to_string(),
+            datetime: format!("{} UTC", cutoff.format("%Y-%m-%d %H:%M:%S")),
+            creditor: creditor.username.clone(),
+            currency: creditor.currency.clone(),
+            total: creditor.balance,
+            debts: balances
+                .iter()
+                .skip(1)
+                .map(|bal| (bal.username.clone(), bal.balance.neg()))
+                .collect(),
+        };
+        add_adjustment_entry(redis, chat_id, &adjustment).await?;
+
+        debts.extend(optimize_debts(balances, &strategy));
+    }
+
+    Ok(debts)
+}
+
+/* Retrieves all group chats with weekly digest enabled.
+ */
+pub async fn retrieve_weekly_digest_chats(redis: &Redis) -> Result<Vec<String>, ProcessError> {
+    let chats = get_weekly_digest_chats(redis).await?;
+    Ok(chats)
+}
+
+/* Retrieves the last week a digest was posted for a group chat, if any.
+ */
+pub async fn retrieve_last_digest_week(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<String>, ProcessError> {
+    let week = get_last_digest_week(redis, chat_id).await?;
+    Ok(week)
+}
+
+/* Updates the last week a digest was posted for a group chat.
+ */
+pub async fn update_last_digest_week(
+    redis: &Redis,
+    chat_id: &str,
+    week: &str,
+) -> Result<(), ProcessError> {
+    set_last_digest_week(redis, chat_id, week).await?;
+    Ok(())
+}
+
+/* Retrieves all group chats with debt reminder enabled.
+ */
+pub async fn retrieve_debt_reminder_chats(redis: &Redis) -> Result<Vec<String>, ProcessError> {
+    let chats = get_debt_reminder_chats(redis).await?;
+    Ok(chats)
+}
+
+/* Retrieves the last date a reminder was posted for a group chat, if any.
+ */
+pub async fn retrieve_last_reminder_date(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<String>, ProcessError> {
+    let date = get_last_reminder_date(redis, chat_id).await?;
+    Ok(date)
+}
+
+/* Updates the last date a reminder was posted for a group chat.
+ */
+pub async fn update_last_reminder_date(
+    redis: &Redis,
+    chat_id: &str,
+    date: &str,
+) -> Result<(), ProcessError> {
+    set_last_reminder_date(redis, chat_id, date).await?;
+    Ok(())
+}
+
+/* Retrieves all group chats with data retention enabled.
+ */
+pub async fn retrieve_data_retention_chats(redis: &Redis) -> Result<Vec<String>, ProcessError> {
+    let chats = get_data_retention_chats(redis).await?;
+    Ok(chats)
+}
+
+/* Retrieves the last date a cleanup was run for a group chat, if any.
+ */
+pub async fn retrieve_last_retention_date(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<String>, ProcessError> {
+    let date = get_last_retention_date(redis, chat_id).await?;
+    Ok(date)
+}
+
+/* Updates the last date a cleanup was run for a group chat.
+ */
+pub async fn update_last_retention_date(
+    redis: &Redis,
+    chat_id: &str,
+    date: &str,
+) -> Result<(), ProcessError> {
+    set_last_retention_date(redis, chat_id, date).await?;
+    Ok(())
+}
+
+/* Retrieves the payments of a group chat from before a cutoff, due to be cleared.
+ * Execution flow: Retrieve payments, keep those before the cutoff and outside the current trip.
+ * Adds up the total spent in each currency, for the summary exported before clearing.
+ * Returns None if there are no payments to clear, other than adjustments.
+ */
+pub async fn retrieve_expired_payments(
+    redis: &Redis,
+    chat_id: &str,
+    cutoff: NaiveDateTime,
+) -> Result<Option<CleanupSummary>, ProcessError> {
+    let payments = match get_chat_payments_details(redis, chat_id).await {
+        Ok(payments) => payments,
+        Err(CrudError::NoPaymentsError()) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let active_trip = get_chat_active_trip(redis, chat_id)
+        .await?
+        .map(|trip| trip.0);
+
+    let mut payment_ids: Vec<String> = Vec::new();
+    let mut expired: Vec<UserPayment> = Vec::new();
+    let mut totals: Vec<(String, i64)> = Vec::new();
+    for payment in payments {
+        match parse_payment_datetime(&payment.payment.datetime) {
+            Some(datetime) if datetime < cutoff => {}
+            _ => continue,
+        }
+        let trip_id = get_payment_trip_entry(redis, &payment.payment_id).await?;
+        if trip_id.is_some() && trip_id == active_trip {
+            continue;
+        }
+
+        payment_ids.push(payment.payment_id.clone());
+        if is_adjustment_entry(redis, &payment.payment_id).await? {
+            continue;
+        }
+        match totals
+            .iter_mut()
+            .find(|(currency, _)| *currency == payment.payment.currency)
+        {
+            Some((_, total)) => *total = checked_add(*total, payment.payment.total)?,
+            None => totals.push((payment.payment.currency.clone(), payment.payment.total)),
+        }
+        expired.push(payment);
+    }
+
+    if expired.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(CleanupSummary {
+        payment_ids,
+        payments: expired,
+        totals,
+    }))
+}
+
+/* Clears payments of a group chat for good, compacting their balances.
+ * Execution flow: Delete payments, then carry forward the balances as of the cutoff.
+ * Balances and spendings are unchanged, as the carried forward entry replaces the cleared ones.
+ * Returns the debts carried forward.
+ */
+pub async fn clear_expired_payments(
+    redis: &Redis,
+    chat_id: &str,
+    payment_ids: &[String],
+    cutoff: NaiveDateTime,
+) -> Result<Vec<Debt>, ProcessError> {
+    let remaining: Vec<UserPayment> = match get_chat_payments_details(redis, chat_id).await {
+        Ok(payments) => payments
+            .into_iter()
+            .filter(|payment| !payment_ids.contains(&payment.payment_id))
+            .collect(),
+        Err(CrudError::NoPaymentsError()) => Vec::new(),
+        Err(err) => return Err(err.into()),
+    };
+
+    // Closing balances are checked before anything is deleted
+    let closing_balances = get_closing_balances(redis, chat_id, &remaining).await?;
+    delete_payment_entries(redis, chat_id, payment_ids).await?;
+
+    let description = format!(
+        "Balances carried forward from before {}",
+        cutoff.format("%Y-%m-%d")
+    );
+    let debts =
+        carry_forward_balances(redis, chat_id, closing_balances, &description, cutoff).await?;
+    Ok(debts)
+}
+
+/* Clears archived payments of a group chat from before a cutoff, for good.
+ * Archives are kept by month, so only months that ended before the cutoff are cleared.
+ * Returns the number of payments cleared.
+ */
+pub async fn clear_expired_archives(
+    redis: &Redis,
+    chat_id: &str,
+    cutoff: NaiveDateTime,
+) -> Result<usize, ProcessError> {
+    let count = delete_archive_entries(redis, chat_id, &cutoff.format("%Y-%m").to_string()).await?;
+    Ok(count)
+}
+
+/* Sets the monthly spending limit of a user in a group chat.
+ * Takes in the currency and amount of the limit, or None to remove it.
+ */
+pub async fn update_spending_limit(
+    redis: &Redis,
+    chat_id: &str,
+    username: &str,
+    limit: Option<(&str, i64)>,
+) -> Result<(), ProcessError> {
+    set_spending_limit(redis, chat_id, username, limit).await?;
+    Ok(())
+}
+
+/* Retrieves all monthly spending limits of a group chat,
+ * with the spendings of each user for the current month.
+ */
+pub async fn retrieve_spending_limits(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<SpendingLimit>, ProcessError> {
+    let limits = get_spending_limits(redis, chat_id).await?;
+    Ok(limits)
+}
+
+/* Retrieves all users of a group chat who have just crossed their monthly spending limit.
+ */
+pub async fn retrieve_spending_limit_warnings(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<SpendingLimit>, ProcessError> {
+    let warnings = get_spending_limit_warnings(redis, chat_id).await?;
+    Ok(warnings)
+}
+
+/* Sets the weekly spending cap of a group chat.
+ * Takes in the currency and amount of the cap, or None to remove it.
+ */
+pub async fn update_weekly_cap(
+    redis: &Redis,
+    chat_id: &str,
+    cap: Option<(&str, i64)>,
+) -> Result<(), ProcessError> {
+    set_weekly_spending_cap(redis, chat_id, cap).await?;
+    Ok(())
+}
+
+/* Retrieves the weekly spending cap of a group chat, if any,
+ * with the spendings of the chat for the current week.
+ */
+pub async fn retrieve_weekly_cap(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<WeeklyCap>, ProcessError> {
+    let cap = get_weekly_spending_cap(redis, chat_id).await?;
+    Ok(cap)
+}
+
+/* Checks whether adding a payment would take a group chat over its weekly spending cap.
+ * Returns the cap if so, with the spendings of the chat for the current week.
+ * Transfers, and payments in currencies other than that of the cap, do not count towards it.
+ */
+pub async fn check_weekly_cap(
+    redis: &Redis,
+    chat_id: &str,
+    currency: &str,
+    total: i64,
+    is_transfer: bool,
+) -> Result<Option<WeeklyCap>, ProcessError> {
+    let cap = match get_weekly_spending_cap(redis, chat_id).await? {
+        Some(cap) => cap,
+        None => return Ok(None),
+    };
+
+    let default_currency = get_default_currency(redis, chat_id).await?;
+    let is_counted = !is_transfer
+        && get_source_currency(currency, &default_currency)
+            == get_source_currency(&cap.currency, &default_currency);
+    let amount = if is_counted { total } else { 0 };
+
+    if is_over_weekly_cap(&cap, amount) {
+        Ok(Some(cap))
+    } else {
+        Ok(None)
+    }
+}
+
+// Checks if the spendings of a chat are over its weekly cap, after adding an amount.
+// A chat already over its cap stays over it, even for amounts that do not count towards it.
+fn is_over_weekly_cap(cap: &WeeklyCap, amount: i64) -> bool {
+    cap.spent > cap.cap || cap.spent + amount > cap.cap
+}
+
+/* Retrieves the next round-robin offset for assigning leftover amounts of a split.
+ * The offset moves on with every call, and is kept per group chat.
+ */
+pub async fn retrieve_rounding_offset(redis: &Redis, chat_id: &str) -> Result<u64, ProcessError> {
+    let offset = get_next_rounding_offset(redis, chat_id).await?;
+    Ok(offset)
+}
+
+/* Retrieves the current step of the setup wizard of a group chat, if it is ongoing.
+ */
+pub async fn retrieve_onboarding_step(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<String>, ProcessError> {
+    let step = get_onboarding_step(redis, chat_id).await?;
+    Ok(step)
+}
+
+/* Updates the current step of the setup wizard of a group chat, expiring after some time.
+ * Takes in None to end the wizard.
+ */
+pub async fn update_onboarding_step(
+    redis: &Redis,
+    chat_id: &str,
+    step: Option<&str>,
+    ttl: usize,
+) -> Result<(), ProcessError> {
+    set_onboarding_step(redis, chat_id, step, ttl).await?;
+    Ok(())
+}
+
+/* Saves the most recently added payment of a group chat as a template, under a name.
+ * Replaces any existing template of the same name.
+ * Returns None if there are no payments to save.
+ */
+pub async fn save_payment_template(
+    redis: &Redis,
+    chat_id: &str,
+    name: &str,
+) -> Result<Option<PaymentTemplate>, ProcessError> {
+    let payment = match get_latest_payment_entry(redis, chat_id).await? {
+        Some(payment) => payment,
+        None => return Ok(None),
+    };
+
+    let template = PaymentTemplate {
+        name: name.to_string(),
+        description: payment.description,
+        creditor: payment.creditor,
+        currency: payment.currency,
+        total: payment.total,
+        debts: payment.debts,
+    };
+    set_payment_template(redis, chat_id, &template).await?;
+    Ok(Some(template))
+}
+
+/* Retrieves a payment template of a group chat by name, if it exists.
+ */
+pub async fn retrieve_payment_template(
+    redis: &Redis,
+    chat_id: &str,
+    name: &str,
+) -> Result<Option<PaymentTemplate>, ProcessError> {
+    let template = get_payment_template(redis, chat_id, name).await?;
+    Ok(template)
+}
+
+/* Retrieves the names of all payment templates of a group chat.
+ */
+pub async fn retrieve_payment_templates(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<String>, ProcessError> {
+    let names = get_payment_templates(redis, chat_id).await?;
+    Ok(names)
+}
+
+/* Deletes a payment template of a group chat by name.
+ * Returns false if there was no such template.
+ */
+pub async fn remove_payment_template(
+    redis: &Redis,
+    chat_id: &str,
+    name: &str,
+) -> Result<bool, ProcessError> {
+    let is_deleted = delete_payment_template(redis, chat_id, name).await?;
+    Ok(is_deleted)
+}
+
+/* Sets the display name of a user in a group chat, shown in place of the username.
+ * Display names must not be empty, and must be at most 32 characters long.
+ */
+pub async fn update_display_name(
+    redis: &Redis,
+    chat_id: &str,
+    username: &str,
+    name: &str,
+) -> Result<(), ProcessError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_DISPLAY_NAME_LENGTH {
+        return Err(ProcessError::ValidationError(
+            Text::new(DISPLAY_NAME_LENGTH).arg("max", MAX_DISPLAY_NAME_LENGTH),
+        ));
+    }
+
+    set_display_name(redis, chat_id, username, name).await?;
+    Ok(())
+}
+
+/* Retrieves the display names of all users in a group chat, keyed by lowercase username.
+ */
+pub async fn retrieve_display_names(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<HashMap<String, String>, ProcessError> {
+    let names = get_display_names(redis, chat_id).await?;
+    Ok(names)
+}
+
+/* Removes the display name of a user in a group chat.
+ * Returns false if the user had no display name.
+ */
+pub async fn remove_display_name(
+    redis: &Redis,
+    chat_id: &str,
+    username: &str,
+) -> Result<bool, ProcessError> {
+    let is_deleted = delete_display_name(redis, chat_id, username).await?;
+    Ok(is_deleted)
+}
+
+/* Saves a split group of a group chat, for the group to split payments with in one tap.
+ */
+pub async fn save_split_group(
+    redis: &Redis,
+    chat_id: &str,
+    name: &str,
+    usernames: &[String],
+) -> Result<(), ProcessError> {
+    set_split_group(redis, chat_id, name, usernames).await?;
+    Ok(())
+}
+
+/* Retrieves all split groups of a group chat, in alphabetical order of their names.
+ */
+pub async fn retrieve_split_groups(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<(String, Vec<String>)>, ProcessError> {
+    let groups = get_split_groups(redis, chat_id).await?;
+    Ok(groups)
+}
+
+/* Removes a split group of a group chat.
+ * Returns false if there was no such group.
+ */
+pub async fn remove_split_group(
+    redis: &Redis,
+    chat_id: &str,
+    name: &str,
+) -> Result<bool, ProcessError> {
+    let is_deleted = delete_split_group(redis, chat_id, name).await?;
+    Ok(is_deleted)
+}
+
+/* Retrieves the cached administrators of a group chat, if any.
+ */
+pub async fn retrieve_chat_admins(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<Vec<String>>, ProcessError> {
+    let admins = get_cached_admins(redis, chat_id).await?;
+    Ok(admins)
+}
+
+/* Caches the administrators of a group chat, expiring after some time.
+ */
+pub async fn update_chat_admins(
+    redis: &Redis,
+    chat_id: &str,
+    admins: &Vec<String>,
+    ttl: usize,
+) -> Result<(), ProcessError> {
+    set_cached_admins(redis, chat_id, admins, ttl).await?;
+    Ok(())
+}
+
+/* Retrieves whether the bot can delete messages in a group chat, if it is known.
+ */
+pub async fn retrieve_bot_can_delete(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<bool>, ProcessError> {
+    let can_delete = get_cached_bot_can_delete(redis, chat_id).await?;
+    Ok(can_delete)
+}
+
+/* Caches whether the bot can delete messages in a group chat, expiring after some time.
+ */
+pub async fn update_bot_can_delete(
+    redis: &Redis,
+    chat_id: &str,
+    can_delete: bool,
+    ttl: usize,
+) -> Result<(), ProcessError> {
+    set_cached_bot_can_delete(redis, chat_id, can_delete, ttl).await?;
+    Ok(())
+}
+
+/* Marks a group chat as notified of the bot lacking permissions.
+ * Returns true only the first time, so that the chat is notified once.
+ */
+pub async fn mark_bot_permission_notified(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<bool, ProcessError> {
+    let is_first = set_bot_permission_notified(redis, chat_id).await?;
+    Ok(is_first)
+}
+
+/* Exports all records of a group chat as a backup.
+ * Includes payments, balances, spendings, trips and settings.
+ */
+pub async fn export_chat_data(redis: &Redis, chat_id: &str) -> Result<ChatBackup, ProcessError> {
+    let backup = export_chat(redis, chat_id).await?;
+    Ok(backup)
+}
+
+/* Retrieves the IDs of all chats known, for administration.
+ */
+pub async fn retrieve_chat_ids(redis: &Redis) -> Result<Vec<String>, ProcessError> {
+    let chat_ids = get_chat_ids(redis).await?;
+    Ok(chat_ids)
+}
+
+/* Retrieves the usage of the whole bot, for its operators.
+ * Chats are active if their payments were changed in the last BOT_STATS_ACTIVE_DAYS days.
+ * The usage is cached for BOT_STATS_CACHE_MINUTES minutes, as it is counted by scanning the database.
+ */
+pub async fn retrieve_bot_stats(redis: &Redis) -> Result<BotStats, ProcessError> {
+    let active_since = (Utc::now() - chrono::Duration::days(BOT_STATS_ACTIVE_DAYS))
+        .format("%Y-%m-%d %H:%M:%S UTC")
+        .to_string();
+    let stats = get_bot_stats_entry(redis, &active_since, BOT_STATS_CACHE_MINUTES * 60).await?;
+    Ok(stats)
+}
+
+/* Retrieves all payments of a group chat, for administration.
+ * Unlike self::view_payments, no user is updated, as there is no sender.
+ */
+pub async fn retrieve_chat_payments(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<UserPayment>, ProcessError> {
+    match get_chat_payments_details(redis, chat_id).await {
+        Ok(payments) => Ok(payments),
+        Err(CrudError::NoPaymentsError()) => Ok(Vec::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/* Deletes all records of a group chat, for administration.
+ * Users are kept, as they may also be in other chats.
+ */
+pub async fn delete_chat_data(redis: &Redis, chat_id: &str) -> Result<(), ProcessError> {
+    delete_chat_entry(redis, chat_id).await?;
+    Ok(())
+}
+
+/* Restores all records of a group chat from a backup.
+ * Overwrites all current payments, balances, spendings, trips and settings of the chat.
+ */
+pub async fn restore_chat_data(
+    redis: &Redis,
+    chat_id: &str,
+    backup: &ChatBackup,
+) -> Result<(), ProcessError> {
+    import_chat(redis, chat_id, backup).await?;
+    Ok(())
+}
 
-        if spending.balance != 0 || paid != 0 {
-            user_spendings.push(UserSpending {
-                username: spending.username.clone(),
-                spending: spending.balance,
-                paid,
-            });
+// Adds an amount to the balance of a user in a currency, starting from 0 if it does not exist
+fn add_ledger_amount(
+    balances: &mut Vec<UserBalance>,
+    username: &str,
+    currency: &str,
+    amount: i64,
+) -> Result<(), MoneyError> {
+    match balances
+        .iter_mut()
+        .find(|bal| bal.currency == currency && is_username_equal(&bal.username, username))
+    {
+        Some(balance) => balance.balance = checked_add(balance.balance, amount)?,
+        None => balances.push(UserBalance {
+            username: username.to_string(),
+            currency: currency.to_string(),
+            balance: check_amount(amount)?,
+        }),
+    }
+    Ok(())
+}
+
+/* Rebuilds the balances and spendings of a ledger from scratch, by replaying its payments.
+ * Adjustments and transfers count towards balances only, as they are not actual expenses.
+ * Archived payments count towards spendings only, as their balances are carried forward by adjustments.
+ * Returns the balances and spendings, one for each user and currency, without zero amounts.
+ * Fails without any changes if a balance or spending would be out of range.
+ */
+pub fn rebuild_ledger_balances(
+    payments: &[LedgerPayment],
+) -> Result<(Vec<UserBalance>, Vec<UserBalance>), ProcessError> {
+    let mut balances: Vec<UserBalance> = Vec::new();
+    let mut spendings: Vec<UserBalance> = Vec::new();
+
+    for entry in payments {
+        let payment = &entry.payment;
+        if !entry.archived {
+            add_ledger_amount(
+                &mut balances,
+                &payment.creditor,
+                &payment.currency,
+                payment.total,
+            )?;
+            for (user, amount) in &payment.debts {
+                add_ledger_amount(
+                    &mut balances,
+                    user,
+                    &payment.currency,
+                    checked_neg(*amount)?,
+                )?;
+            }
+        }
+        if !entry.adjustment && !entry.transfer {
+            for (user, amount) in &payment.debts {
+                add_ledger_amount(&mut spendings, user, &payment.currency, *amount)?;
+            }
         }
     }
 
-    // Check through for any balances that aren't accounted for in a spending
-    for balance in balances {
-        let user = user_spendings
+    balances.retain(|bal| bal.balance != 0);
+    spendings.retain(|spending| spending.balance != 0);
+    Ok((balances, spendings))
+}
+
+/* Recalculates the balances and spendings of a group chat and all its trips.
+ * Replays all stored payments from scratch, to fix balances that have drifted from the payments.
+ * Returns the number of payments replayed for the chat.
+ */
+pub async fn recalculate_chat_balances(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<usize, ProcessError> {
+    recalculate_balances(&RedisStore::new(redis), chat_id).await
+}
+
+// Recalculates the balances and spendings of a group chat and all its trips, in a ledger store
+async fn recalculate_balances<S: LedgerStore>(
+    store: &S,
+    chat_id: &str,
+) -> Result<usize, ProcessError> {
+    let mut count = 0;
+    for ledger in store.get_ledgers(chat_id).await? {
+        let payments = store.get_payments(chat_id, &ledger).await?;
+        if ledger == chat_id {
+            count = payments.len();
+        }
+
+        let (balances, spendings) = rebuild_ledger_balances(&payments)?;
+        store.replace_balances(&ledger, balances, spendings).await?;
+    }
+
+    Ok(count)
+}
+
+/* View spendings of a group chat from payments made since a given time, if any.
+ * Adjustment entries and transfers are not actual expenses, and are excluded.
+ * Payments without currency are counted under the default currency, if any.
+ * Amounts fronted count expenses only, as paybacks only settle earlier expenses.
+ * Returns the spendings for each currency with payments in the period.
+ */
+pub async fn retrieve_spending_data_since(
+    redis: &Redis,
+    chat_id: &str,
+    since: Option<NaiveDateTime>,
+) -> Result<Vec<SpendingData>, ProcessError> {
+    let payments = match get_chat_payments_details(redis, chat_id).await {
+        Ok(payments) => payments,
+        Err(CrudError::NoPaymentsError()) => Vec::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let default_currency = get_default_currency(redis, chat_id).await?;
+
+    let mut spending_data: Vec<SpendingData> = Vec::new();
+    for payment in payments {
+        match parse_payment_datetime(&payment.payment.datetime) {
+            Some(datetime) if since.is_none_or(|since| datetime >= since) => {}
+            _ => continue,
+        }
+        if payment.is_transfer || is_adjustment_entry(redis, &payment.payment_id).await? {
+            continue;
+        }
+
+        let payment = payment.payment;
+        let currency = if payment.currency == CURRENCY_CODE_DEFAULT {
+            default_currency.clone()
+        } else {
+            payment.currency.clone()
+        };
+
+        let index = match spending_data
             .iter()
-            .position(|spending| spending.username == balance.username);
-        match user {
-            Some(_) => {
-                continue;
-            }
+            .position(|data| data.currency == currency)
+        {
+            Some(index) => index,
             None => {
-                user_spendings.push(UserSpending {
-                    username: balance.username,
-                    spending: 0,
-                    paid: balance.balance,
+                spending_data.push(SpendingData {
+                    currency: currency.clone(),
+                    group_spending: 0,
+                    user_spendings: Vec::new(),
                 });
+                spending_data.len() - 1
+            }
+        };
+        let data = &mut spending_data[index];
+        data.group_spending = checked_add(data.group_spending, payment.total)?;
+
+        let fronted = if is_payback(&payment) {
+            0
+        } else {
+            payment.total
+        };
+        let mut changes: Vec<(String, i64, i64, i64)> = payment
+            .debts
+            .iter()
+            .map(|(user, amount)| (user.to_string(), *amount, 0, 0))
+            .collect();
+        changes.push((payment.creditor.clone(), 0, payment.total, fronted));
+
+        for (user, spending, paid, fronted) in changes {
+            match data
+                .user_spendings
+                .iter()
+                .position(|user_spending| is_username_equal(&user_spending.username, &user))
+            {
+                Some(user_index) => {
+                    let user_spending = &mut data.user_spendings[user_index];
+                    user_spending.spending = checked_add(user_spending.spending, spending)?;
+                    user_spending.paid = checked_add(user_spending.paid, paid)?;
+                    user_spending.fronted = checked_add(user_spending.fronted, fronted)?;
+                }
+                None => data.user_spendings.push(UserSpending {
+                    username: user,
+                    spending,
+                    paid,
+                    fronted,
+                }),
             }
         }
     }
 
-    Ok(SpendingData {
-        currency: currency.to_string(),
-        group_spending,
-        user_spendings,
-    })
+    Ok(spending_data)
 }
 
-/* View spendings of a group chat for the default currency.
- * Retrieves all spendings, gets current balances, and returns:
- * Total group spending, total individual spendings, and total individual payments
+// Projects the spending of a whole month from the spending so far, at the same daily average
+// Days elapsed include the current day, so the projection is never below the amount spent
+fn project_month_spending(spent: i64, days_elapsed: u32, days_in_month: u32) -> i64 {
+    if days_elapsed == 0 || days_elapsed >= days_in_month {
+        return spent;
+    }
+    (spent as f64 * days_in_month as f64 / days_elapsed as f64).round() as i64
+}
+
+/* Forecasts the spendings of a group chat by the end of the current month.
+ * Months are counted in the chat time zone, from payments made since the start of the month.
+ * Projects group and user spendings from their daily averages so far,
+ * along with the monthly spending limit of each user in the same currency, if any.
+ * Returns the forecast for each currency with payments this month.
  */
-fn retrieve_spending_data_by_default_currency(
+pub async fn retrieve_spending_forecast(
+    redis: &Redis,
     chat_id: &str,
-    currency: &str,
-) -> Result<SpendingData, ProcessError> {
-    let spendings_curr = retrieve_chat_spendings_currency(chat_id, currency)?;
-    let spendings_nil = retrieve_chat_spendings_currency(chat_id, CURRENCY_CODE_DEFAULT)?;
-    let mut balances_curr = get_chat_balances_currency(chat_id, currency)?;
-    let mut balances_nil = get_chat_balances_currency(chat_id, CURRENCY_CODE_DEFAULT)?;
+    time_zone: Tz,
+) -> Result<Vec<SpendingForecast>, ProcessError> {
+    let today = Utc::now().with_timezone(&time_zone).date_naive();
+    let month_start = today.with_day(1).unwrap_or(today);
+    let since = time_zone
+        .from_local_datetime(&month_start.and_time(NaiveTime::MIN))
+        .earliest()
+        .map(|datetime| datetime.naive_utc());
+    let days_elapsed = today.day();
+    let days_in_month = get_days_in_month(today);
 
-    let mut group_spending = 0;
-    let mut user_spendings: Vec<UserSpending> = Vec::new();
-    for spending in spendings_curr {
-        group_spending += spending.balance;
+    let spending_data = retrieve_spending_data_since(redis, chat_id, since).await?;
+    let limits = get_spending_limits(redis, chat_id).await?;
 
-        let paid: i64;
-        let balance_index = balances_curr
-            .iter()
-            .position(|bal| bal.username == spending.username);
-        match balance_index {
-            Some(index) => {
-                paid = spending.balance + balances_curr[index].balance;
-                balances_curr[index].balance = 0;
+    let forecasts = spending_data
+        .into_iter()
+        .map(|data| SpendingForecast {
+            group_spent: data.group_spending,
+            group_projected: project_month_spending(
+                data.group_spending,
+                days_elapsed,
+                days_in_month,
+            ),
+            user_forecasts: data
+                .user_spendings
+                .iter()
+                .filter(|spending| spending.spending > 0)
+                .map(|spending| UserForecast {
+                    username: spending.username.clone(),
+                    spent: spending.spending,
+                    projected: project_month_spending(
+                        spending.spending,
+                        days_elapsed,
+                        days_in_month,
+                    ),
+                    limit: limits
+                        .iter()
+                        .find(|limit| {
+                            limit.currency == data.currency
+                                && is_username_equal(&limit.username, &spending.username)
+                        })
+                        .map(|limit| limit.limit),
+                })
+                .collect(),
+            currency: data.currency,
+        })
+        .collect();
+
+    Ok(forecasts)
+}
+
+/* View monthly stats of a group chat, from all payments grouped by month in the chat time zone.
+ * Adjustment entries and transfers are not actual expenses, and are excluded.
+ * Payments without currency are counted under the default currency, if any.
+ * Returns the stats for each month and currency, from the earliest month.
+ */
+pub async fn retrieve_monthly_stats(
+    redis: &Redis,
+    chat_id: &str,
+    time_zone: Tz,
+) -> Result<Vec<MonthlyStats>, ProcessError> {
+    let payments = match get_chat_payments_details(redis, chat_id).await {
+        Ok(payments) => payments,
+        Err(CrudError::NoPaymentsError()) => Vec::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let default_currency = get_default_currency(redis, chat_id).await?;
+
+    let mut stats: Vec<(MonthlyStats, Vec<UserSpending>)> = Vec::new();
+    for payment in payments {
+        let month = match parse_payment_datetime(&payment.payment.datetime) {
+            Some(datetime) => {
+                get_month_key(&Utc.from_utc_datetime(&datetime).with_timezone(&time_zone))
             }
+            None => continue,
+        };
+        if payment.is_transfer || is_adjustment_entry(redis, &payment.payment_id).await? {
+            continue;
+        }
+
+        let is_private = payment.is_private;
+        let payment = payment.payment;
+        let currency = if payment.currency == CURRENCY_CODE_DEFAULT {
+            default_currency.clone()
+        } else {
+            payment.currency.clone()
+        };
+
+        let index = match stats
+            .iter()
+            .position(|(data, _)| data.month == month && data.currency == currency)
+        {
+            Some(index) => index,
             None => {
-                paid = 0;
+                stats.push((
+                    MonthlyStats {
+                        month,
+                        currency,
+                        total: 0,
+                        count: 0,
+                        top_spender: UserSpending {
+                            username: String::new(),
+                            spending: 0,
+                            paid: 0,
+                            fronted: 0,
+                        },
+                        largest_expense: (String::new(), 0),
+                        change: None,
+                    },
+                    Vec::new(),
+                ));
+                stats.len() - 1
             }
+        };
+        let (data, user_spendings) = &mut stats[index];
+        data.total = checked_add(data.total, payment.total)?;
+        data.count += 1;
+        if data.count == 1 || payment.total > data.largest_expense.1 {
+            let description = if is_private {
+                PRIVATE_DESCRIPTION.to_string()
+            } else {
+                payment.description.clone()
+            };
+            data.largest_expense = (description, payment.total);
         }
 
-        user_spendings.push(UserSpending {
-            username: spending.username.clone(),
-            spending: spending.balance,
-            paid,
-        });
+        let fronted = if is_payback(&payment) {
+            0
+        } else {
+            payment.total
+        };
+        let mut changes: Vec<(String, i64, i64, i64)> = payment
+            .debts
+            .iter()
+            .map(|(user, amount)| (user.to_string(), *amount, 0, 0))
+            .collect();
+        changes.push((payment.creditor.clone(), 0, payment.total, fronted));
+
+        for (user, spending, paid, fronted) in changes {
+            match user_spendings
+                .iter()
+                .position(|user_spending| is_username_equal(&user_spending.username, &user))
+            {
+                Some(user_index) => {
+                    let user_spending = &mut user_spendings[user_index];
+                    user_spending.spending = checked_add(user_spending.spending, spending)?;
+                    user_spending.paid = checked_add(user_spending.paid, paid)?;
+                    user_spending.fronted = checked_add(user_spending.fronted, fronted)?;
+                }
+                None => user_spendings.push(UserSpending {
+                    username: user,
+                    spending,
+                    paid,
+                    fronted,
+                }),
+            }
+        }
     }
 
-    for spending in spendings_nil {
-        group_spending += spending.balance;
+    let mut stats: Vec<MonthlyStats> = stats
+        .into_iter()
+        .map(|(mut data, user_spendings)| {
+            if let Some(top_spender) = user_spendings.into_iter().reduce(|top, user| {
+                if user.spending > top.spending {
+                    user
+                } else {
+                    top
+                }
+            }) {
+                data.top_spender = top_spender;
+            }
+            data
+        })
+        .collect();
+    stats.sort_by(|first, second| first.month.cmp(&second.month));
 
-        let paid: i64;
-        let balance_index = balances_nil
+    // Month-over-month change, only against the previous calendar month
+    for index in 0..stats.len() {
+        let previous_month = match NaiveDateTime::parse_from_str(
+            &format!("{}-01 00:00:00", stats[index].month),
+            "%Y-%m-%d %H:%M:%S",
+        ) {
+            Ok(datetime) => (datetime.date() - chrono::Duration::days(1))
+                .format("%Y-%m")
+                .to_string(),
+            Err(_) => continue,
+        };
+        let previous_total = stats
             .iter()
-            .position(|bal| bal.username == spending.username);
-        match balance_index {
-            Some(index) => {
-                paid = spending.balance + balances_nil[index].balance;
-                balances_nil[index].balance = 0;
+            .find(|data| data.month == previous_month && data.currency == stats[index].currency)
+            .map(|data| data.total);
+        stats[index].change = match previous_total {
+            Some(total) if total != 0 => {
+                Some((stats[index].total - total) as f64 / total as f64 * 100.0)
             }
-            None => {
-                paid = 0;
+            _ => None,
+        };
+    }
+
+    Ok(stats)
+}
+
+/* View the leaderboard of a group chat, from payments made since a given time, if any.
+ * Adjustment entries are not actual expenses, and are excluded.
+ * Payments without currency are counted under the default currency, if any.
+ * Expenses rank the top payers and the most frequent debtors, while paybacks rank
+ * the fastest settlers, by the average time from first owing to paying back.
+ */
+pub async fn retrieve_leaderboard(
+    redis: &Redis,
+    chat_id: &str,
+    since: Option<NaiveDateTime>,
+) -> Result<Leaderboard, ProcessError> {
+    let payments = match get_chat_payments_details(redis, chat_id).await {
+        Ok(payments) => payments,
+        Err(CrudError::NoPaymentsError()) => Vec::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let default_currency = get_default_currency(redis, chat_id).await?;
+
+    let mut entries: Vec<(NaiveDateTime, Payment)> = Vec::new();
+    for payment in payments {
+        let datetime = match parse_payment_datetime(&payment.payment.datetime) {
+            Some(datetime) if since.is_none_or(|since| datetime >= since) => datetime,
+            _ => continue,
+        };
+        if is_adjustment_entry(redis, &payment.payment_id).await? {
+            continue;
+        }
+        entries.push((datetime, payment.payment));
+    }
+    entries.sort_by_key(|(datetime, _)| *datetime);
+
+    let mut leaderboard = Leaderboard::default();
+    let mut owing_since: HashMap<String, NaiveDateTime> = HashMap::new();
+    let mut settle_times: HashMap<String, (String, i64, i64)> = HashMap::new();
+    let mut debtor_counts: HashMap<String, (String, i64)> = HashMap::new();
+    for (datetime, payment) in entries {
+        let key = payment.creditor.to_lowercase();
+        if is_payback(&payment) {
+            if let Some(start) = owing_since.remove(&key) {
+                let entry = settle_times
+                    .entry(key)
+                    .or_insert((payment.creditor.clone(), 0, 0));
+                entry.1 += (datetime - start).num_seconds();
+                entry.2 += 1;
             }
+            continue;
         }
 
-        let user = user_spendings
+        leaderboard.payments += 1;
+        let currency = if payment.currency == CURRENCY_CODE_DEFAULT {
+            default_currency.clone()
+        } else {
+            payment.currency.clone()
+        };
+        let index = match leaderboard
+            .top_payers
             .iter()
-            .position(|user| user.username == spending.username);
-
-        match user {
-            Some(index) => {
-                user_spendings[index].spending += spending.balance;
-                user_spendings[index].paid += paid;
-            }
+            .position(|(payer_currency, _)| *payer_currency == currency)
+        {
+            Some(index) => index,
             None => {
-                user_spendings.push(UserSpending {
-                    username: spending.username.clone(),
-                    spending: spending.balance,
-                    paid,
-                });
+                leaderboard.top_payers.push((currency, Vec::new()));
+                leaderboard.top_payers.len() - 1
+            }
+        };
+        let payers = &mut leaderboard.top_payers[index].1;
+        match payers
+            .iter()
+            .position(|(user, _)| is_username_equal(user, &payment.creditor))
+        {
+            Some(user_index) => {
+                payers[user_index].1 = saturating_add(payers[user_index].1, payment.total)
+            }
+            None => payers.push((payment.creditor.clone(), payment.total)),
+        }
+
+        for (debtor, _) in &payment.debts {
+            if is_username_equal(debtor, &payment.creditor) {
+                continue;
             }
+            let key = debtor.to_lowercase();
+            owing_since.entry(key.clone()).or_insert(datetime);
+            debtor_counts.entry(key).or_insert((debtor.clone(), 0)).1 += 1;
+        }
+    }
+
+    for (_, payers) in &mut leaderboard.top_payers {
+        payers.sort_by(|first, second| second.1.cmp(&first.1).then(first.0.cmp(&second.0)));
+    }
+    leaderboard
+        .top_payers
+        .sort_by(|first, second| first.0.cmp(&second.0));
+
+    leaderboard.frequent_debtors = debtor_counts.into_values().collect();
+    leaderboard
+        .frequent_debtors
+        .sort_by(|first, second| second.1.cmp(&first.1).then(first.0.cmp(&second.0)));
+
+    leaderboard.fastest_settlers = settle_times
+        .into_values()
+        .map(|(user, total, count)| (user, total / count))
+        .collect();
+    leaderboard
+        .fastest_settlers
+        .sort_by(|first, second| first.1.cmp(&second.1).then(first.0.cmp(&second.0)));
+
+    Ok(leaderboard)
+}
+
+// Gets the share of each user in the practice payment of the tutorial, in the smallest unit
+fn get_tutorial_share(decimals: i32) -> i64 {
+    10 * 10_i64.pow(decimals.max(0) as u32)
+}
+
+// Builds the practice payment of the tutorial, paid by the user and split with the sample debtors
+fn build_tutorial_payment(
+    username: &str,
+    currency: &str,
+    decimals: i32,
+    datetime: String,
+) -> Payment {
+    let share = get_tutorial_share(decimals);
+    let mut debts = vec![(username.to_string(), share)];
+    debts.extend(
+        TUTORIAL_DEBTORS
+            .iter()
+            .map(|debtor| (debtor.to_string(), share)),
+    );
+
+    Payment {
+        description: TUTORIAL_DESCRIPTION.to_string(),
+        datetime,
+        creditor: username.to_string(),
+        currency: currency.to_string(),
+        total: share * debts.len() as i64,
+        debts,
+    }
+}
+
+// Builds the practice payback of the tutorial, where the first sample debtor pays the user back
+fn build_tutorial_payback(
+    username: &str,
+    currency: &str,
+    decimals: i32,
+    datetime: String,
+) -> Payment {
+    let share = get_tutorial_share(decimals);
+    let debtor = TUTORIAL_DEBTORS[0];
+
+    Payment {
+        description: payback_description(debtor),
+        datetime,
+        creditor: debtor.to_string(),
+        currency: currency.to_string(),
+        total: share,
+        debts: vec![(username.to_string(), share)],
+    }
+}
+
+// Gets the currency of the tutorial, which is the default currency of the chat, with its decimals
+async fn get_tutorial_currency(redis: &Redis, chat_id: &str) -> Result<Currency, ProcessError> {
+    let default_currency = get_default_currency(redis, chat_id).await?;
+    let decimals = get_currency_from_code(&default_currency).map_or(2, |(_, decimals)| decimals);
+    Ok((default_currency, decimals))
+}
+
+// Adds a payment to the practice ledger of the tutorial, and returns its debts after.
+// Only the practice ledger is updated, so the real balances of the chat are untouched.
+async fn add_tutorial_entry(
+    redis: &Redis,
+    chat_id: &str,
+    payment: &Payment,
+) -> Result<Vec<Debt>, ProcessError> {
+    let ledger = get_tutorial_ledger(chat_id);
+    add_tutorial_payment_entry(redis, chat_id, payment).await?;
+    update_ledger_balances(
+        &RedisStore::new(redis),
+        &ledger,
+        get_payment_changes(payment)?,
+        Vec::new(),
+    )
+    .await?;
+
+    retrieve_ledger_debts(
+        redis,
+        chat_id,
+        &ledger,
+        StatementOption::Currency(payment.currency.clone()),
+    )
+    .await
+}
+
+/* Starts the tutorial of a group chat, adding a practice payment to a separate ledger.
+ * The payment is paid by the user, and split equally with the sample TUTORIAL_DEBTORS,
+ * in the default currency of the chat. Any earlier practice ledger is cleared first.
+ * Returns the practice payment, and the debts of the practice ledger after.
+ */
+pub async fn add_tutorial_payment(
+    redis: &Redis,
+    chat_id: &str,
+    username: &str,
+) -> Result<(Payment, Vec<Debt>), ProcessError> {
+    delete_tutorial_entry(redis, chat_id).await?;
+
+    let (currency, decimals) = get_tutorial_currency(redis, chat_id).await?;
+    let datetime = Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string();
+    let payment = build_tutorial_payment(username, &currency, decimals, datetime);
+    let debts = add_tutorial_entry(redis, chat_id, &payment).await?;
+    Ok((payment, debts))
+}
+
+/* Continues the tutorial of a group chat, where the first sample debtor pays the user back.
+ * Returns the practice payback, and the debts of the practice ledger after.
+ */
+pub async fn add_tutorial_payback(
+    redis: &Redis,
+    chat_id: &str,
+    username: &str,
+) -> Result<(Payment, Vec<Debt>), ProcessError> {
+    let (currency, decimals) = get_tutorial_currency(redis, chat_id).await?;
+    let datetime = Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string();
+    let payback = build_tutorial_payback(username, &currency, decimals, datetime);
+    let debts = add_tutorial_entry(redis, chat_id, &payback).await?;
+    Ok((payback, debts))
+}
+
+/* Views the balances of the practice ledger of the tutorial of a group chat.
+ */
+pub async fn retrieve_tutorial_debts(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<Debt>, ProcessError> {
+    let (currency, _) = get_tutorial_currency(redis, chat_id).await?;
+    retrieve_ledger_debts(
+        redis,
+        chat_id,
+        &get_tutorial_ledger(chat_id),
+        StatementOption::Currency(currency),
+    )
+    .await
+}
+
+/* Ends the tutorial of a group chat, clearing its practice ledger.
+ */
+pub async fn clear_tutorial(redis: &Redis, chat_id: &str) -> Result<(), ProcessError> {
+    delete_tutorial_entry(redis, chat_id).await?;
+    Ok(())
+}
+
+/* Asserts that a user has not exceeded the rate limit.
+ */
+pub async fn assert_rate_limit(
+    redis: &Redis,
+    user_id: &str,
+    timestamp: i64,
+) -> Result<(), ProcessError> {
+    let status = is_request_limit_exceeded(redis, user_id, timestamp).await?;
+    if status {
+        Err(ProcessError::RateLimitError())
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::{
+        money::MAX_VALUE,
+        redis::{MemoryStore, PaymentAudit},
+    };
+
+    fn make_payment(creditor: &str, total: i64, debts: Vec<(&str, i64)>) -> Payment {
+        Payment {
+            description: "processor_test_payment".to_string(),
+            datetime: "2024-01-01 00:00:00 UTC".to_string(),
+            creditor: creditor.to_string(),
+            currency: "USD".to_string(),
+            total,
+            debts: debts
+                .into_iter()
+                .map(|(user, amount)| (user.to_string(), amount))
+                .collect(),
         }
     }
 
-    // Check through for any balances that aren't accounted for in a spending
-    for balance in balances_curr {
-        if balance.balance != 0 {
-            let user = user_spendings
-                .iter()
-                .position(|spending| spending.username == balance.username);
-            match user {
-                Some(index) => {
-                    user_spendings[index].paid += balance.balance;
-                }
-                None => {
-                    user_spendings.push(UserSpending {
-                        username: balance.username,
-                        spending: 0,
-                        paid: balance.balance,
-                    });
-                }
-            }
-        }
+    // Makes the payments of a ledger, each with an ID
+    fn make_entries(entries: Vec<LedgerPayment>) -> Vec<(String, LedgerPayment)> {
+        entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| (format!("processor_payment_{index}"), entry))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_unknown_users() {
+        let known = vec!["alice".to_string(), "Bob".to_string()];
+        let users = vec![
+            "alice".to_string(),
+            "bob".to_string(),
+            "carol".to_string(),
+            "Carol".to_string(),
+            "dave".to_string(),
+        ];
+
+        // Known users, in any case, and the sender are left out, and each user is listed once
+        assert_eq!(
+            find_unknown_users(&known, "Dave", &users),
+            vec!["carol".to_string()]
+        );
+        assert!(find_unknown_users(&known, "dave", &known).is_empty());
+    }
+
+    #[test]
+    fn test_rebuild_ledger_balances_overflow() {
+        let payments: Vec<LedgerPayment> = (0..3)
+            .map(|_| LedgerPayment {
+                payment: make_payment("alice", MAX_VALUE, vec![("bob", MAX_VALUE)]),
+                adjustment: false,
+                archived: false,
+                transfer: false,
+            })
+            .collect();
+
+        // Balances out of range fail the rebuild, instead of wrapping around
+        assert!(matches!(
+            rebuild_ledger_balances(&payments),
+            Err(ProcessError::ValidationError(reason)) if reason == Text::new(MONEY_OVERFLOW)
+        ));
+        assert!(rebuild_ledger_balances(&payments[..1]).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_recalculate_balances() {
+        let chat_id = "processor_123456789";
+        let trip = get_trip_ledger(chat_id, "1");
+        let store = MemoryStore::default();
+        store.payments.lock().unwrap().insert(
+            chat_id.to_string(),
+            make_entries(vec![
+                LedgerPayment {
+                    payment: make_payment("alice", 300, vec![("alice", 100), ("Bob", 200)]),
+                    adjustment: false,
+                    archived: true,
+                    transfer: false,
+                },
+                LedgerPayment {
+                    payment: make_payment("bob", 200, vec![("alice", 200)]),
+                    adjustment: true,
+                    archived: false,
+                    transfer: false,
+                },
+                LedgerPayment {
+                    payment: make_payment("bob", 100, vec![("alice", 50), ("bob", 50)]),
+                    adjustment: false,
+                    archived: false,
+                    transfer: false,
+                },
+            ]),
+        );
+        store.payments.lock().unwrap().insert(
+            trip.clone(),
+            make_entries(vec![LedgerPayment {
+                payment: make_payment("alice", 300, vec![("alice", 100), ("Bob", 200)]),
+                adjustment: false,
+                archived: false,
+                transfer: false,
+            }]),
+        );
+
+        assert_eq!(recalculate_balances(&store, chat_id).await.unwrap(), 3);
+
+        // Archived payments count towards spendings only, and adjustments towards balances only
+        let balances = store.balances.lock().unwrap();
+        assert_eq!(
+            balances.get(chat_id).unwrap(),
+            &vec![
+                UserBalance {
+                    username: "bob".to_string(),
+                    currency: "USD".to_string(),
+                    balance: 250,
+                },
+                UserBalance {
+                    username: "alice".to_string(),
+                    currency: "USD".to_string(),
+                    balance: -250,
+                },
+            ]
+        );
+        let spendings = store.spendings.lock().unwrap();
+        assert_eq!(
+            spendings.get(chat_id).unwrap(),
+            &vec![
+                UserBalance {
+                    username: "alice".to_string(),
+                    currency: "USD".to_string(),
+                    balance: 150,
+                },
+                UserBalance {
+                    username: "Bob".to_string(),
+                    currency: "USD".to_string(),
+                    balance: 250,
+                },
+            ]
+        );
+
+        // Trips are recalculated from their own payments
+        assert_eq!(
+            balances.get(&trip).unwrap(),
+            &vec![
+                UserBalance {
+                    username: "alice".to_string(),
+                    currency: "USD".to_string(),
+                    balance: 200,
+                },
+                UserBalance {
+                    username: "Bob".to_string(),
+                    currency: "USD".to_string(),
+                    balance: -200,
+                },
+            ]
+        );
     }
 
-    for balance in balances_nil {
-        if balance.balance != 0 {
-            let user = user_spendings
-                .iter()
-                .position(|spending| spending.username == balance.username);
-            match user {
-                Some(index) => {
-                    user_spendings[index].paid += balance.balance;
-                }
-                None => {
-                    user_spendings.push(UserSpending {
-                        username: balance.username,
-                        spending: 0,
-                        paid: balance.balance,
-                    });
-                }
-            }
-        }
+    #[tokio::test]
+    async fn test_recalculate_balances_transfer() {
+        let chat_id = "processor_transfer_123456789";
+        let store = MemoryStore::default();
+        store.payments.lock().unwrap().insert(
+            chat_id.to_string(),
+            make_entries(vec![
+                LedgerPayment {
+                    payment: make_payment("alice", 100, vec![("bob", 100)]),
+                    adjustment: false,
+                    archived: false,
+                    transfer: true,
+                },
+                LedgerPayment {
+                    payment: make_payment("bob", 60, vec![("alice", 30), ("bob", 30)]),
+                    adjustment: false,
+                    archived: false,
+                    transfer: false,
+                },
+            ]),
+        );
+
+        assert_eq!(recalculate_balances(&store, chat_id).await.unwrap(), 2);
+
+        // Transfers count towards balances only
+        assert_eq!(
+            store.balances.lock().unwrap().get(chat_id).unwrap(),
+            &vec![
+                UserBalance {
+                    username: "alice".to_string(),
+                    currency: "USD".to_string(),
+                    balance: 70,
+                },
+                UserBalance {
+                    username: "bob".to_string(),
+                    currency: "USD".to_string(),
+                    balance: -70,
+                },
+            ]
+        );
+        assert_eq!(
+            store.spendings.lock().unwrap().get(chat_id).unwrap(),
+            &vec![
+                UserBalance {
+                    username: "alice".to_string(),
+                    currency: "USD".to_string(),
+                    balance: 30,
+                },
+                UserBalance {
+                    username: "bob".to_string(),
+                    currency: "USD".to_string(),
+                    balance: 30,
+                },
+            ]
+        );
     }
 
-    Ok(SpendingData {
-        currency: currency.to_string(),
-        group_spending,
-        user_spendings,
-    })
-}
+    #[tokio::test]
+    async fn test_recalculate_balances_negative_spending() {
+        let chat_id = "processor_123456789";
+        let store = MemoryStore::default();
+        store.payments.lock().unwrap().insert(
+            chat_id.to_string(),
+            make_entries(vec![LedgerPayment {
+                payment: make_payment("alice", -100, vec![("bob", -100)]),
+                adjustment: false,
+                archived: false,
+                transfer: false,
+            }]),
+        );
 
-/* View spendings of a group chat, converted to default currency.
- * Only called if currency conversion is enabled.
- * Retrieves all spendings, gets current balances, converts them.
- */
-async fn retrieve_spending_data_converted(chat_id: &str) -> Result<SpendingData, ProcessError> {
-    let mut spendings = retrieve_chat_spendings(chat_id)?;
-    let mut balances = get_chat_balances(chat_id)?;
+        assert!(recalculate_balances(&store, chat_id).await.is_err());
+        assert!(store.balances.lock().unwrap().is_empty());
+    }
 
-    let default_currency = get_default_currency(chat_id)?;
-    let mut group_spending = 0;
-    let mut user_spendings: Vec<UserSpending> = Vec::new();
-    for spending_currency in &mut spendings {
-        if spending_currency.len() == 0 {
-            continue;
-        }
+    #[tokio::test]
+    async fn test_recalculate_balances_other_chat() {
+        let chat_id = "processor_123456789";
+        let other_chat_id = "processor_987654321";
+        let store = MemoryStore::default();
+        store.payments.lock().unwrap().insert(
+            other_chat_id.to_string(),
+            make_entries(vec![LedgerPayment {
+                payment: make_payment("alice", 100, vec![("bob", 100)]),
+                adjustment: false,
+                archived: false,
+                transfer: false,
+            }]),
+        );
+        store.payments.lock().unwrap().insert(
+            get_trip_ledger(other_chat_id, "1"),
+            make_entries(vec![LedgerPayment {
+                payment: make_payment("alice", 100, vec![("bob", 100)]),
+                adjustment: false,
+                archived: false,
+                transfer: false,
+            }]),
+        );
 
-        let currency = spending_currency[0].currency.clone();
-        let should_convert = currency != default_currency && currency != CURRENCY_CODE_DEFAULT;
+        // Ledgers of other chats are left alone
+        assert_eq!(
+            store.get_ledgers(chat_id).await.unwrap(),
+            vec![chat_id.to_string()]
+        );
+        assert_eq!(recalculate_balances(&store, chat_id).await.unwrap(), 0);
+        assert!(store.balances.lock().unwrap().get(other_chat_id).is_none());
+    }
 
-        let conversion_rate = if should_convert {
-            match fetch_currency_conversion(&currency, &default_currency).await {
-                Ok(rate) => rate,
-                Err(err) => {
-                    log::error!("Error fetching currency conversion from {currency} to {default_currency}: {}", err);
-                    1.0
-                }
-            }
-        } else {
-            1.0
+    #[tokio::test]
+    async fn test_ledger_payments() {
+        let chat_id = "processor_ledger_123456789";
+        let store = MemoryStore::default();
+        let get_amount = |amounts: &HashMap<String, Vec<UserBalance>>, username: &str| {
+            amounts
+                .get(chat_id)
+                .and_then(|amounts| amounts.iter().find(|amount| amount.username == username))
+                .map_or(0, |amount| amount.balance)
         };
 
-        let mut empty_balances: Vec<UserBalance> = Vec::new();
-        let balances_currency: &mut Vec<UserBalance> = match balances
-            .iter_mut()
-            .find(|bal| bal.len() > 0 && bal[0].currency == *currency)
-        {
-            Some(bal) => bal,
-            None => &mut empty_balances,
+        // Adding a payment updates balances and spendings
+        let payment = make_payment("alice", 300, vec![("alice", 100), ("bob", 200)]);
+        let (payment_id, _, _) = add_ledger_payment(&store, chat_id, &payment, false)
+            .await
+            .unwrap();
+        assert_eq!(get_amount(&store.balances.lock().unwrap(), "alice"), 200);
+        assert_eq!(get_amount(&store.balances.lock().unwrap(), "bob"), -200);
+        assert_eq!(get_amount(&store.spendings.lock().unwrap(), "bob"), 200);
+
+        // Editing a payment undoes the previous payment before applying the new one
+        let previous = store.get_payment(&payment_id).await.unwrap();
+        let payment = make_payment("alice", 300, vec![("alice", 150), ("bob", 150)]);
+        apply_ledger_edit(&store, chat_id, &payment_id, &previous, &payment, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_payment(&payment_id).await.unwrap().payment,
+            payment
+        );
+        assert_eq!(get_amount(&store.balances.lock().unwrap(), "alice"), 150);
+        assert_eq!(get_amount(&store.spendings.lock().unwrap(), "bob"), 150);
+        assert_eq!(
+            optimize_ledger_debts(&store, chat_id, "USD", &SettlementStrategy::Minimal)
+                .await
+                .unwrap(),
+            vec![Debt {
+                debtor: "bob".to_string(),
+                creditor: "alice".to_string(),
+                currency: "USD".to_string(),
+                amount: 150,
+            }]
+        );
+
+        // Transfers count towards balances only
+        let transfer = make_payment("bob", 150, vec![("alice", 150)]);
+        add_ledger_payment(&store, chat_id, &transfer, true)
+            .await
+            .unwrap();
+        assert_eq!(get_amount(&store.balances.lock().unwrap(), "alice"), 0);
+        assert_eq!(get_amount(&store.spendings.lock().unwrap(), "alice"), 150);
+
+        // Deleting a payment moves it to the trash, and gives the changes that undo it
+        let (deleted, changes, spendings) =
+            trash_ledger_payment(&store, chat_id, &payment_id, "2024-01-02 00:00:00 UTC")
+                .await
+                .unwrap();
+        assert_eq!(deleted, payment);
+        update_ledger_balances(&store, chat_id, changes, spendings)
+            .await
+            .unwrap();
+        assert_eq!(get_amount(&store.balances.lock().unwrap(), "alice"), -150);
+        assert_eq!(get_amount(&store.spendings.lock().unwrap(), "bob"), 0);
+        assert_eq!(store.get_payments(chat_id, chat_id).await.unwrap().len(), 1);
+        assert!(store.get_payment(&payment_id).await.is_err());
+        assert_eq!(
+            store.trash.lock().unwrap().clone(),
+            vec![(chat_id.to_string(), payment_id)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ledger_balances_overflow() {
+        let chat_id = "processor_overflow_123456789";
+        let store = MemoryStore::default();
+
+        // A payment that takes a balance beyond MAX_VALUE is rejected before anything is written
+        let payment = make_payment("alice", MAX_VALUE, vec![("bob", MAX_VALUE)]);
+        add_ledger_payment(&store, chat_id, &payment, false)
+            .await
+            .unwrap();
+        let balances = store.balances.lock().unwrap().clone();
+        let spendings = store.spendings.lock().unwrap().clone();
+        assert!(add_ledger_payment(&store, chat_id, &payment, false)
+            .await
+            .is_err());
+        assert_eq!(store.get_payments(chat_id, chat_id).await.unwrap().len(), 1);
+        assert_eq!(store.balances.lock().unwrap().clone(), balances);
+        assert_eq!(store.spendings.lock().unwrap().clone(), spendings);
+
+        // Payments that settle balances are still accepted
+        let payback = make_payment("bob", MAX_VALUE, vec![("alice", MAX_VALUE)]);
+        add_ledger_payment(&store, chat_id, &payback, true)
+            .await
+            .unwrap();
+        let balances = store.get_balances(chat_id, "USD").await.unwrap();
+        assert!(balances.iter().all(|balance| balance.balance == 0));
+
+        // An edit that takes a balance beyond MAX_VALUE leaves the payment unchanged as well
+        let other_chat_id = "processor_overflow_987654321";
+        let payment = make_payment("alice", 100, vec![("bob", 100)]);
+        let (payment_id, _, _) = add_ledger_payment(&store, other_chat_id, &payment, false)
+            .await
+            .unwrap();
+        let rest = MAX_VALUE - 100;
+        add_ledger_payment(
+            &store,
+            other_chat_id,
+            &make_payment("alice", rest, vec![("bob", rest)]),
+            false,
+        )
+        .await
+        .unwrap();
+        let previous = store.get_payment(&payment_id).await.unwrap();
+        let balances = store.balances.lock().unwrap().clone();
+        let edited = make_payment("alice", 200, vec![("bob", 200)]);
+        assert!(apply_ledger_edit(
+            &store,
+            other_chat_id,
+            &payment_id,
+            &previous,
+            &edited,
+            false
+        )
+        .await
+        .is_err());
+        assert_eq!(store.get_payment(&payment_id).await.unwrap(), previous);
+        assert_eq!(store.balances.lock().unwrap().clone(), balances);
+    }
+
+    #[test]
+    fn test_format_balances_dump() {
+        let balances = vec![vec![
+            UserBalance {
+                username: "alice".to_string(),
+                currency: "USD".to_string(),
+                balance: 1500,
+            },
+            UserBalance {
+                username: "bob".to_string(),
+                currency: "USD".to_string(),
+                balance: -1500,
+            },
+        ]];
+        assert_eq!(
+            format_balances_dump(balances, &SettlementStrategy::Minimal),
+            "[USD]\nBalances:\n  alice: 1500\n  bob: -1500\nSum: 0\nOptimized debts:\n  bob -> alice: 1500"
+        );
+
+        let balances = vec![vec![UserBalance {
+            username: "alice".to_string(),
+            currency: "JPY".to_string(),
+            balance: 100,
+        }]];
+        assert!(format_balances_dump(balances, &SettlementStrategy::Minimal)
+            .contains("Sum: 100 (NOT ZERO)"));
+        assert_eq!(
+            format_balances_dump(Vec::new(), &SettlementStrategy::Minimal),
+            "No balances."
+        );
+    }
+
+    #[test]
+    fn test_format_payments_audit() {
+        let user_payment = |payment_id: &str, payment: Payment| UserPayment {
+            chat_id: "processor_audit_123456789".to_string(),
+            payment_id: payment_id.to_string(),
+            payment,
+            rate: None,
+            audit: PaymentAudit::default(),
+            is_private: false,
+            is_transfer: false,
+            is_settled: false,
+            is_disputed: false,
         };
 
-        for spending in spending_currency {
-            let balance_index = balances_currency
-                .iter()
-                .position(|bal| bal.username == spending.username);
+        let mut jpy = make_payment("alice", 1000, vec![("alice", 333), ("bob", 667)]);
+        jpy.currency = "JPY".to_string();
+        let payments = vec![user_payment("payment_1", jpy.clone())];
+        assert_eq!(
+            format_payments_audit(&payments),
+            "Payment audit:\n  All 1 payments OK"
+        );
 
-            let mut paid_amount = spending.balance;
-            match balance_index {
-                Some(index) => {
-                    paid_amount += balances_currency[index].balance;
-                    balances_currency[index].balance = 0;
-                }
-                None => {}
-            }
+        jpy.total = 1001;
+        let mut unknown = make_payment("alice", 100, vec![("bob", 100)]);
+        unknown.currency = "XYZ".to_string();
+        let payments = vec![
+            user_payment("payment_1", jpy),
+            user_payment("payment_2", unknown),
+        ];
+        assert_eq!(
+            format_payments_audit(&payments),
+            "Payment audit:\n  payment_1 [JPY]: split sums to 1000, but total is 1001\n  payment_2: unknown currency XYZ"
+        );
+    }
 
-            let mut spending_amount = spending.balance.clone();
+    #[test]
+    fn test_project_month_spending() {
+        // 10 days into a 30 day month
+        assert_eq!(project_month_spending(1000, 10, 30), 3000);
+        // Rounds to the nearest unit
+        assert_eq!(project_month_spending(1000, 3, 31), 10333);
+        assert_eq!(project_month_spending(200, 3, 31), 2067);
+        // Last day of the month, or nothing spent
+        assert_eq!(project_month_spending(4500, 31, 31), 4500);
+        assert_eq!(project_month_spending(0, 5, 28), 0);
+        // Negative spendings from refunds are projected the same way
+        assert_eq!(project_month_spending(-300, 15, 30), -600);
+    }
 
-            if should_convert {
-                spending_amount = convert_currency_with_rate(
-                    spending_amount,
-                    &currency,
-                    &default_currency,
-                    conversion_rate,
-                );
-                paid_amount = convert_currency_with_rate(
-                    paid_amount,
-                    &currency,
-                    &default_currency,
-                    conversion_rate,
-                );
-            }
+    #[test]
+    fn test_find_largest_expenses() {
+        let expense = |currency: &str, description: &str, total: i64| {
+            (currency.to_string(), description.to_string(), total)
+        };
+        assert_eq!(
+            find_largest_expenses(vec![
+                expense("JPY", "Sushi", 8000),
+                expense("USD", "Flights", 120000),
+                expense("JPY", "Hotel", 30000),
+                expense("JPY", "Ryokan", 30000),
+                expense("USD", "Taxi", 4000),
+            ]),
+            vec![
+                expense("JPY", "Hotel", 30000),
+                expense("USD", "Flights", 120000),
+            ]
+        );
+        assert!(find_largest_expenses(Vec::new()).is_empty());
+    }
 
-            group_spending += spending_amount;
+    #[test]
+    fn test_split_adjustment() {
+        let others = vec![
+            "alice".to_string(),
+            "bob".to_string(),
+            "charlie".to_string(),
+        ];
+        assert_eq!(
+            split_adjustment(1000, &others),
+            vec![
+                ("alice".to_string(), 334),
+                ("bob".to_string(), 333),
+                ("charlie".to_string(), 333)
+            ]
+        );
+        assert_eq!(
+            split_adjustment(-1001, &others),
+            vec![
+                ("alice".to_string(), -334),
+                ("bob".to_string(), -334),
+                ("charlie".to_string(), -333)
+            ]
+        );
+        assert_eq!(
+            split_adjustment(-1500, &others[..1]),
+            vec![("alice".to_string(), -1500)]
+        );
+    }
 
-            let user = user_spendings
-                .iter()
-                .position(|user| user.username == spending.username);
+    #[test]
+    fn test_is_over_weekly_cap() {
+        let cap = WeeklyCap {
+            currency: "USD".to_string(),
+            cap: 10000,
+            spent: 6000,
+        };
+        assert!(!is_over_weekly_cap(&cap, 4000));
+        assert!(is_over_weekly_cap(&cap, 4001));
 
-            match user {
-                Some(index) => {
-                    user_spendings[index].spending += spending_amount;
-                    user_spendings[index].paid += paid_amount;
-                }
-                None => {
-                    user_spendings.push(UserSpending {
-                        username: spending.username.clone(),
-                        spending: spending_amount,
-                        paid: paid_amount,
-                    });
-                }
-            }
-        }
+        let cap = WeeklyCap {
+            spent: 12000,
+            ..cap
+        };
+        assert!(is_over_weekly_cap(&cap, 0));
+    }
 
-        // Check through for any balances that aren't accounted for in a spending
-        for balance in balances_currency {
-            if balance.balance != 0 {
-                let user = user_spendings
-                    .iter()
-                    .position(|spending| spending.username == balance.username);
-                let converted_balance = convert_currency_with_rate(
-                    balance.balance,
-                    &currency,
-                    &default_currency,
-                    conversion_rate,
-                );
-                match user {
-                    Some(index) => {
-                        user_spendings[index].paid += converted_balance;
-                    }
-                    None => {
-                        user_spendings.push(UserSpending {
-                            username: balance.username.clone(),
-                            spending: 0,
-                            paid: converted_balance,
-                        });
-                    }
-                }
-            }
-        }
+    #[test]
+    fn test_get_source_currency() {
+        assert_eq!(get_source_currency("JPY", "USD"), "JPY");
+        assert_eq!(get_source_currency(CURRENCY_CODE_DEFAULT, "USD"), "USD");
+        assert_eq!(
+            get_source_currency(CURRENCY_CODE_DEFAULT, CURRENCY_CODE_DEFAULT),
+            CURRENCY_CODE_DEFAULT
+        );
     }
 
-    Ok(SpendingData {
-        currency: default_currency.to_string(),
-        group_spending,
-        user_spendings,
-    })
-}
+    #[test]
+    fn test_validate_payment() {
+        let debts = vec![("a".to_string(), 10), ("b".to_string(), 20)];
+        assert!(validate_payment("USD", 30, &debts).is_ok());
+        assert!(validate_payment("USD", 40, &debts).is_err());
+        assert!(validate_payment("USD", 30, &Vec::new()).is_err());
 
-/* Retrieves a group chat setting.
-*/
-pub fn get_chat_setting(chat_id: &str, setting: ChatSetting) -> Result<ChatSetting, ProcessError> {
-    match setting {
-        ChatSetting::TimeZone(_) => {
-            let time_zone = get_time_zone(chat_id)?;
-            Ok(ChatSetting::TimeZone(Some(time_zone)))
-        }
-        ChatSetting::DefaultCurrency(_) => {
-            let currency = get_default_currency(chat_id)?;
-            Ok(ChatSetting::DefaultCurrency(Some(currency)))
-        }
-        ChatSetting::CurrencyConversion(_) => {
-            let convert = get_currency_conversion(chat_id)?;
-            Ok(ChatSetting::CurrencyConversion(Some(convert)))
-        }
-        ChatSetting::EraseMessages(_) => {
-            let erase = get_erase_messages(chat_id)?;
-            Ok(ChatSetting::EraseMessages(Some(erase)))
-        }
+        // Amounts that would overflow are rejected, rather than wrapping around
+        let debts = vec![("a".to_string(), i64::MAX), ("b".to_string(), 1)];
+        assert_eq!(
+            validate_payment("USD", i64::MIN, &debts),
+            Err(ProcessError::ValidationError(Text::new(MONEY_OVERFLOW)))
+        );
+        assert!(validate_amounts(30, &[("a".to_string(), 30)]).is_ok());
+        assert!(validate_amounts(30, &debts).is_err());
     }
-}
 
-/* Sets a group chat setting.
-*/
-pub async fn set_chat_setting(chat_id: &str, setting: ChatSetting) -> Result<(), ProcessError> {
-    match setting {
-        ChatSetting::TimeZone(time_zone) => {
-            if let Some(time_zone) = time_zone {
-                set_time_zone(chat_id, &time_zone)?;
-            }
-        }
-        ChatSetting::DefaultCurrency(currency) => {
-            if let Some(currency) = currency {
-                set_default_currency(chat_id, &currency)?;
-            }
-        }
-        ChatSetting::CurrencyConversion(convert) => {
-            if let Some(convert) = convert {
-                set_currency_conversion(chat_id, convert)?;
+    #[test]
+    fn test_has_other_debtors() {
+        let debts = vec![("alice".to_string(), 100), ("bob".to_string(), 200)];
+        assert!(has_other_debtors("alice", &debts));
+        assert!(has_other_debtors("carol", &debts));
 
-                // If currency conversion is updated, need to update balances
-                update_balances(chat_id, Vec::new())?;
-            }
-        }
-        ChatSetting::EraseMessages(erase) => {
-            if let Some(erase) = erase {
-                set_erase_messages(chat_id, erase)?;
-            }
-        }
+        let debts = vec![("Alice".to_string(), 300)];
+        assert!(!has_other_debtors("alice", &debts));
+        assert!(!has_other_debtors("alice", &[]));
     }
-    Ok(())
-}
 
-/* Changes the default currency of a group chat.
- * Also handles all the conversion logic for past payments.
- */
-pub async fn update_chat_default_currency(
-    chat_id: &str,
-    currency: &str,
-) -> Result<(), ProcessError> {
-    let old_currency = get_default_currency(chat_id)?;
+    #[tokio::test]
+    async fn test_check_dual_entry() {
+        let chat_id = "processor_dual_entry_123456789";
+        let store = MemoryStore::default();
+        store
+            .dual_entry
+            .lock()
+            .unwrap()
+            .insert(chat_id.to_string(), true);
 
-    // Update all payments to old currency
-    let payments = get_chat_payments_details(chat_id);
-    let mut changes: Vec<UserBalance> = Vec::new();
+        // A split with anyone else needs confirming
+        let debts = vec![("alice".to_string(), 3000), ("Bob".to_string(), 3000)];
+        assert!(check_dual_entry(&store, chat_id, "alice", &debts)
+            .await
+            .unwrap());
 
-    match payments {
-        Ok(payments) => {
-            for payment in payments {
-                if payment.payment.currency == CURRENCY_CODE_DEFAULT {
-                    update_payment_entry(
-                        &payment.payment_id,
-                        None,
-                        None,
-                        Some(&old_currency),
-                        None,
-                        None,
-                    )?;
-                }
-            }
+        // A payment for the payer alone has no one else to confirm it, in any case
+        let debts = vec![("Alice".to_string(), 6000)];
+        assert!(!check_dual_entry(&store, chat_id, "alice", &debts)
+            .await
+            .unwrap());
 
-            // Update all balances to old currency
-            let balances = get_chat_balances_currency(chat_id, CURRENCY_CODE_DEFAULT)?;
-            for balance in balances {
-                let change_sub = UserBalance {
-                    username: balance.username.clone(),
-                    currency: balance.currency,
-                    balance: balance.balance.neg(),
-                };
-                let change_add = UserBalance {
-                    username: balance.username.clone(),
-                    currency: old_currency.clone(),
-                    balance: balance.balance,
-                };
-                changes.extend(vec![change_sub, change_add]);
-            }
+        // Chats without dual entry verification add payments right away
+        let debts = vec![("alice".to_string(), 3000), ("bob".to_string(), 3000)];
+        assert!(
+            !check_dual_entry(&store, "processor_other_123456789", "alice", &debts)
+                .await
+                .unwrap()
+        );
+        store
+            .dual_entry
+            .lock()
+            .unwrap()
+            .insert(chat_id.to_string(), false);
+        assert!(!check_dual_entry(&store, chat_id, "alice", &debts)
+            .await
+            .unwrap());
+    }
 
-            // Update all spendings to old currency
-            let mut spendings_changes: Vec<UserBalance> = Vec::new();
-            let spendings = retrieve_spending_data_by_currency(chat_id, CURRENCY_CODE_DEFAULT)?;
-            for spending in spendings.user_spendings {
-                let change_sub = UserBalance {
-                    username: spending.username.clone(),
-                    currency: CURRENCY_CODE_DEFAULT.to_string(),
-                    balance: spending.spending.neg(),
-                };
-                let change_add = UserBalance {
-                    username: spending.username.clone(),
-                    currency: old_currency.clone(),
-                    balance: spending.spending,
-                };
-                spendings_changes.extend(vec![change_sub, change_add]);
-            }
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_is_dual_entry_required() {
+        let redis = Redis::new();
+        let chat_id = "processor_dual_entry_123456789";
+        let debts = vec![("alice".to_string(), 3000), ("bob".to_string(), 3000)];
+        set_chat_setting(&redis, chat_id, ChatSetting::DualEntry(Some(true)))
+            .await
+            .unwrap();
 
-            update_chat_spendings(chat_id, spendings_changes)?;
-        }
-        Err(_) => {
-            // This means that there were no payments found
-        }
+        // A split with anyone else is left pending, without affecting balances
+        assert!(is_dual_entry_required(&redis, chat_id, "alice", &debts)
+            .await
+            .unwrap());
+        let payment_id = add_pending_payment(
+            &redis,
+            chat_id.to_string(),
+            "alice".to_string(),
+            "123".to_string(),
+            "2024-01-01 00:00:00 UTC".to_string(),
+            "Dinner",
+            "alice",
+            "USD",
+            6000,
+            debts,
+            false,
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(retrieve_pending_payment(&redis, &payment_id)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(discard_pending_payment(&redis, &payment_id).await.unwrap());
+
+        // A payment for the payer alone has no one else to confirm it
+        let debts = vec![("alice".to_string(), 6000)];
+        assert!(!is_dual_entry_required(&redis, chat_id, "alice", &debts)
+            .await
+            .unwrap());
+
+        set_chat_setting(&redis, chat_id, ChatSetting::DualEntry(Some(false)))
+            .await
+            .unwrap();
+        let debts = vec![("alice".to_string(), 3000), ("bob".to_string(), 3000)];
+        assert!(!is_dual_entry_required(&redis, chat_id, "alice", &debts)
+            .await
+            .unwrap());
+
+        delete_chat_data(&redis, chat_id).await.unwrap();
     }
 
-    // Update default currency in settings. If now NIL, disable currency conversion.
-    set_default_currency(chat_id, &currency)?;
-    if currency == CURRENCY_CODE_DEFAULT {
-        set_currency_conversion(chat_id, false)?;
+    #[test]
+    fn test_filter_user_debts() {
+        let make_debt = |debtor: &str, creditor: &str| Debt {
+            debtor: debtor.to_string(),
+            creditor: creditor.to_string(),
+            currency: CURRENCY_CODE_DEFAULT.to_string(),
+            amount: 100,
+        };
+        let debts = vec![make_debt("Alice", "bob"), make_debt("bob", "alice")];
+        assert_eq!(
+            filter_user_debts(debts, "alice", "USD"),
+            vec![Debt {
+                debtor: "Alice".to_string(),
+                creditor: "bob".to_string(),
+                currency: "USD".to_string(),
+                amount: 100,
+            }]
+        );
     }
 
-    // Finally, update balances and debts
-    update_balances(chat_id, changes)?;
+    #[test]
+    fn test_build_tutorial_payment() {
+        let datetime = "2024-01-01 00:00:00 UTC".to_string();
+        let payment = build_tutorial_payment("user", "USD", 2, datetime.clone());
+        assert!(validate_payment(&payment.currency, payment.total, &payment.debts).is_ok());
+        assert_eq!(payment.total, 3000);
+        assert_eq!(payment.creditor, "user");
+        assert_eq!(payment.debts.len(), TUTORIAL_DEBTORS.len() + 1);
 
-    Ok(())
-}
+        let payback = build_tutorial_payback("user", "JPY", 0, datetime);
+        assert!(validate_payment(&payback.currency, payback.total, &payback.debts).is_ok());
+        assert!(is_payback(&payback));
+        assert_eq!(payback.total, 10);
+        assert_eq!(payback.debts, vec![("user".to_string(), 10)]);
+    }
 
-/* Asserts that a user has not exceeded the rate limit.
- */
-pub fn assert_rate_limit(user_id: &str, timestamp: i64) -> Result<(), ProcessError> {
-    let status = is_request_limit_exceeded(user_id, timestamp)?;
-    if status {
-        Err(ProcessError::CrudError(
-            CrudError::RequestLimitExceededError(),
-        ))
-    } else {
-        Ok(())
+    #[test]
+    fn test_get_payment_changes() {
+        let payment = make_payment("a", 30, vec![("a", 10), ("b", 20)]);
+        let changes = get_payment_changes(&payment).unwrap();
+        assert_eq!(changes.iter().map(|change| change.balance).sum::<i64>(), 0);
+        assert_eq!(changes.last().unwrap().balance, 30);
     }
 }
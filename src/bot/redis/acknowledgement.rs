@@ -0,0 +1,154 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Acknowledgement CRUD Operations
+ * Acknowledgement represents the people involved in a payment who have confirmed it,
+ * by reacting to the message confirming the payment.
+ * Acknowledgement comprises the usernames who confirmed each payment, as a set,
+ * and the payment that each confirmation message is for, which expires after some time.
+ * Has add, get, and delete operations.
+ */
+
+const ACKNOWLEDGEMENT_KEY: &str = "payment_acknowledgement";
+const ACKNOWLEDGEMENT_MESSAGE_KEY: &str = "acknowledgement_message";
+const ACKNOWLEDGEMENT_MESSAGE_TTL: usize = 2592000;
+
+// Sets the payment that a confirmation message in a chat is for, expiring after some time
+pub async fn set_acknowledgement_message(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    message_id: i32,
+    payment_id: &str,
+) -> RedisResult<()> {
+    con.set_ex(
+        format!("{ACKNOWLEDGEMENT_MESSAGE_KEY}:{chat_id}:{message_id}"),
+        payment_id,
+        ACKNOWLEDGEMENT_MESSAGE_TTL,
+    )
+    .await
+}
+
+// Gets the payment that a confirmation message in a chat is for, if any
+pub async fn get_acknowledgement_message(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    message_id: i32,
+) -> RedisResult<Option<String>> {
+    con.get(format!(
+        "{ACKNOWLEDGEMENT_MESSAGE_KEY}:{chat_id}:{message_id}"
+    ))
+    .await
+}
+
+// Adds a user who confirmed a payment
+pub async fn add_acknowledgement(
+    con: &mut RedisConnection,
+    payment_id: &str,
+    username: &str,
+) -> RedisResult<()> {
+    con.sadd(
+        format!("{ACKNOWLEDGEMENT_KEY}:{payment_id}"),
+        username.to_lowercase(),
+    )
+    .await
+}
+
+// Gets all users who confirmed a payment
+pub async fn get_acknowledgements(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<Vec<String>> {
+    con.smembers(format!("{ACKNOWLEDGEMENT_KEY}:{payment_id}"))
+        .await
+}
+
+// Deletes a user who confirmed a payment, when they take back their confirmation
+pub async fn delete_acknowledgement(
+    con: &mut RedisConnection,
+    payment_id: &str,
+    username: &str,
+) -> RedisResult<()> {
+    con.srem(
+        format!("{ACKNOWLEDGEMENT_KEY}:{payment_id}"),
+        username.to_lowercase(),
+    )
+    .await
+}
+
+// Deletes all confirmations of a payment
+pub async fn delete_acknowledgements(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<()> {
+    con.del(format!("{ACKNOWLEDGEMENT_KEY}:{payment_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_acknowledgement_message() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "acknowledgement_123456789";
+        assert_eq!(
+            get_acknowledgement_message(&mut con, chat_id, 1)
+                .await
+                .unwrap(),
+            None
+        );
+        assert!(
+            set_acknowledgement_message(&mut con, chat_id, 1, "payment_1")
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            get_acknowledgement_message(&mut con, chat_id, 1)
+                .await
+                .unwrap(),
+            Some("payment_1".to_string())
+        );
+
+        con.del::<_, ()>(format!("{ACKNOWLEDGEMENT_MESSAGE_KEY}:{chat_id}:1"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_delete_acknowledgements() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let payment_id = "acknowledgement_payment_123456789";
+        assert!(add_acknowledgement(&mut con, payment_id, "Test_User_1")
+            .await
+            .is_ok());
+        assert!(add_acknowledgement(&mut con, payment_id, "test_user_1")
+            .await
+            .is_ok());
+        assert!(add_acknowledgement(&mut con, payment_id, "test_user_2")
+            .await
+            .is_ok());
+        let mut acknowledgements = get_acknowledgements(&mut con, payment_id).await.unwrap();
+        acknowledgements.sort();
+        assert_eq!(acknowledgements, vec!["test_user_1", "test_user_2"]);
+
+        assert!(delete_acknowledgement(&mut con, payment_id, "TEST_USER_2")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_acknowledgements(&mut con, payment_id).await.unwrap(),
+            vec!["test_user_1"]
+        );
+
+        assert!(delete_acknowledgements(&mut con, payment_id).await.is_ok());
+        assert!(get_acknowledgements(&mut con, payment_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,50 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Adjustment CRUD Operations
+ * Adjustment represents a payment entry that directly adjusts balances,
+ * such as opening balances carried forward, instead of an actual expense.
+ * Adjustments are tracked as a set of payment IDs, and do not count towards spendings.
+ * Has add, exists, and delete operations.
+ */
+
+const ADJUSTMENT_KEY: &str = "adjustment";
+
+// Marks a payment as an adjustment
+pub async fn add_adjustment(con: &mut RedisConnection, payment_id: &str) -> RedisResult<()> {
+    con.sadd(ADJUSTMENT_KEY, payment_id).await
+}
+
+// Checks if a payment is an adjustment
+pub async fn get_adjustment_exists(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<bool> {
+    con.sismember(ADJUSTMENT_KEY, payment_id).await
+}
+
+// Unmarks a payment as an adjustment
+pub async fn delete_adjustment(con: &mut RedisConnection, payment_id: &str) -> RedisResult<()> {
+    con.srem(ADJUSTMENT_KEY, payment_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_delete_adjustment() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let payment_id = "adjustment_123456789";
+        assert!(!get_adjustment_exists(&mut con, payment_id).await.unwrap());
+        assert!(add_adjustment(&mut con, payment_id).await.is_ok());
+        assert!(get_adjustment_exists(&mut con, payment_id).await.unwrap());
+
+        assert!(delete_adjustment(&mut con, payment_id).await.is_ok());
+        assert!(!get_adjustment_exists(&mut con, payment_id).await.unwrap());
+    }
+}
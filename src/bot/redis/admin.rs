@@ -0,0 +1,80 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Admin CRUD Operations
+ * Admin represents a cached list of the administrators of a chat, as given by Telegram.
+ * Admin comprises a set of user IDs, which expires after some time,
+ * so that changes to the administrators are picked up eventually.
+ * Has set, exists, get, and delete operations.
+ */
+
+const ADMIN_KEY: &str = "chat_admin";
+
+// Sets the administrators of a chat, expiring after some time
+pub async fn set_chat_admins(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    admins: &Vec<String>,
+    ttl: usize,
+) -> RedisResult<()> {
+    let key = format!("{ADMIN_KEY}:{chat_id}");
+    con.del::<_, ()>(&key).await?;
+    if admins.is_empty() {
+        return Ok(());
+    }
+
+    con.sadd::<_, _, ()>(&key, admins).await?;
+    con.expire(&key, ttl).await
+}
+
+// Checks if the administrators of a chat are cached
+pub async fn is_exists_chat_admins(con: &mut RedisConnection, chat_id: &str) -> RedisResult<bool> {
+    con.exists(format!("{ADMIN_KEY}:{chat_id}")).await
+}
+
+// Gets the cached administrators of a chat
+pub async fn get_chat_admins(con: &mut RedisConnection, chat_id: &str) -> RedisResult<Vec<String>> {
+    con.smembers(format!("{ADMIN_KEY}:{chat_id}")).await
+}
+
+// Deletes the cached administrators of a chat
+pub async fn delete_chat_admins(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{ADMIN_KEY}:{chat_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_chat_admins() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "admin_123456789";
+        let admins = vec!["111".to_string(), "222".to_string()];
+        assert!(!is_exists_chat_admins(&mut con, chat_id).await.unwrap());
+        assert!(set_chat_admins(&mut con, chat_id, &admins, 600)
+            .await
+            .is_ok());
+        assert!(is_exists_chat_admins(&mut con, chat_id).await.unwrap());
+
+        let mut cached = get_chat_admins(&mut con, chat_id).await.unwrap();
+        cached.sort();
+        assert_eq!(cached, admins);
+
+        let second_admins = vec!["333".to_string()];
+        assert!(set_chat_admins(&mut con, chat_id, &second_admins, 600)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_admins(&mut con, chat_id).await.unwrap(),
+            second_admins
+        );
+
+        assert!(delete_chat_admins(&mut con, chat_id).await.is_ok());
+        assert!(!is_exists_chat_admins(&mut con, chat_id).await.unwrap());
+    }
+}
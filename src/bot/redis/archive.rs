@@ -0,0 +1,221 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Archive CRUD Operations
+ * Archive represents payments of a chat that have been moved out of the active ledger.
+ * Archive comprises a list of archive labels for each chat (such as a month "2024-05"),
+ * and a list of payment IDs for each label.
+ * Also keeps track of chats with monthly archival enabled, and the last month archived.
+ * Has add, get, and delete operations.
+ */
+
+const ARCHIVE_KEY: &str = "archive";
+const ARCHIVE_PAYMENT_KEY: &str = "archive_payment";
+const ARCHIVE_MONTH_KEY: &str = "archive_month";
+const ARCHIVE_SCHEDULE_KEY: &str = "archive_schedule";
+
+// Adds a new archive label to a chat. Automatically checks if already added.
+pub async fn add_archive(con: &mut RedisConnection, chat_id: &str, label: &str) -> RedisResult<()> {
+    let current_archives: Vec<String> = get_archives(con, chat_id).await?;
+    if current_archives.contains(&label.to_string()) {
+        return Ok(());
+    }
+    con.rpush(format!("{ARCHIVE_KEY}:{chat_id}"), label).await
+}
+
+// Gets all archive labels of a chat
+pub async fn get_archives(con: &mut RedisConnection, chat_id: &str) -> RedisResult<Vec<String>> {
+    con.lrange(format!("{ARCHIVE_KEY}:{chat_id}"), 0, -1).await
+}
+
+// Deletes an archive label of a chat
+pub async fn delete_archive(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    label: &str,
+) -> RedisResult<()> {
+    con.lrem(format!("{ARCHIVE_KEY}:{chat_id}"), 0, label).await
+}
+
+// Deletes all archive labels of a chat
+// Mainly for testing purposes
+#[allow(dead_code)]
+pub async fn delete_archives(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{ARCHIVE_KEY}:{chat_id}")).await
+}
+
+// Adds payments to an archive of a chat
+pub async fn add_archive_payments(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    label: &str,
+    payment_ids: &[String],
+) -> RedisResult<()> {
+    for payment_id in payment_ids {
+        con.rpush::<_, _, ()>(
+            format!("{ARCHIVE_PAYMENT_KEY}:{chat_id}:{label}"),
+            payment_id,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+// Gets all payments in an archive of a chat
+pub async fn get_archive_payments(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    label: &str,
+) -> RedisResult<Vec<String>> {
+    con.lrange(format!("{ARCHIVE_PAYMENT_KEY}:{chat_id}:{label}"), 0, -1)
+        .await
+}
+
+// Deletes all payments in an archive of a chat
+pub async fn delete_archive_payments(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    label: &str,
+) -> RedisResult<()> {
+    con.del(format!("{ARCHIVE_PAYMENT_KEY}:{chat_id}:{label}"))
+        .await
+}
+
+// Sets the last month archived for a chat
+pub async fn set_archive_month(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    month: &str,
+) -> RedisResult<()> {
+    con.set(format!("{ARCHIVE_MONTH_KEY}:{chat_id}"), month)
+        .await
+}
+
+// Gets the last month archived for a chat, if any
+pub async fn get_archive_month(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Option<String>> {
+    con.get(format!("{ARCHIVE_MONTH_KEY}:{chat_id}")).await
+}
+
+// Deletes the last month archived for a chat
+pub async fn delete_archive_month(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{ARCHIVE_MONTH_KEY}:{chat_id}")).await
+}
+
+// Adds a chat to the monthly archive schedule
+pub async fn add_archive_schedule(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.sadd(ARCHIVE_SCHEDULE_KEY, chat_id).await
+}
+
+// Gets all chats in the monthly archive schedule
+pub async fn get_archive_schedule(con: &mut RedisConnection) -> RedisResult<Vec<String>> {
+    con.smembers(ARCHIVE_SCHEDULE_KEY).await
+}
+
+// Removes a chat from the monthly archive schedule
+pub async fn delete_archive_schedule(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.srem(ARCHIVE_SCHEDULE_KEY, chat_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_archive() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "archive_123456789";
+        assert!(add_archive(&mut con, chat_id, "2024-05").await.is_ok());
+        assert!(add_archive(&mut con, chat_id, "2024-06").await.is_ok());
+        assert!(add_archive(&mut con, chat_id, "2024-05").await.is_ok());
+        assert_eq!(
+            get_archives(&mut con, chat_id).await.unwrap(),
+            vec!["2024-05".to_string(), "2024-06".to_string()]
+        );
+
+        delete_archives(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_delete_archive() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "archive_1234567893";
+        add_archive(&mut con, chat_id, "2024-05").await.unwrap();
+        add_archive(&mut con, chat_id, "2024-06").await.unwrap();
+        assert!(delete_archive(&mut con, chat_id, "2024-05").await.is_ok());
+        assert_eq!(
+            get_archives(&mut con, chat_id).await.unwrap(),
+            vec!["2024-06".to_string()]
+        );
+
+        delete_archives(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_archive_payments() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "archive_1234567890";
+        let label = "2024-05";
+        let payment_ids = vec!["payment_1".to_string(), "payment_2".to_string()];
+        assert!(add_archive_payments(&mut con, chat_id, label, &payment_ids)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_archive_payments(&mut con, chat_id, label)
+                .await
+                .unwrap(),
+            payment_ids
+        );
+
+        delete_archive_payments(&mut con, chat_id, label)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_archive_month() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "archive_1234567891";
+        assert_eq!(get_archive_month(&mut con, chat_id).await.unwrap(), None);
+        assert!(set_archive_month(&mut con, chat_id, "2024-05")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_archive_month(&mut con, chat_id).await.unwrap(),
+            Some("2024-05".to_string())
+        );
+
+        delete_archive_month(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_delete_archive_schedule() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "archive_1234567892";
+        assert!(add_archive_schedule(&mut con, chat_id).await.is_ok());
+        assert!(get_archive_schedule(&mut con)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+
+        assert!(delete_archive_schedule(&mut con, chat_id).await.is_ok());
+        assert!(!get_archive_schedule(&mut con)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+    }
+}
@@ -0,0 +1,140 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Audit CRUD Operations
+ * Audit represents the recent changes to payments in a chat, such as adding, editing or deleting.
+ * Each change comprises the action, the payment ID and description, the username and the datetime,
+ * stored in order in a list for each chat, of which only the latest changes are kept.
+ * Has add and get operations, and gets when the latest change was made.
+ */
+
+const AUDIT_KEY: &str = "chat_audit";
+const AUDIT_FIELDS: isize = 5;
+const AUDIT_MAX_ENTRIES: isize = 50;
+
+pub const AUDIT_ADDED: &str = "added";
+pub const AUDIT_EDITED: &str = "edited";
+pub const AUDIT_DELETED: &str = "deleted";
+pub const AUDIT_ADJUSTED: &str = "adjusted";
+pub const AUDIT_RESTORED: &str = "restored";
+
+// AuditEntry is a single change to a payment, made by a user at some datetime
+#[derive(Debug, PartialEq, Clone)]
+pub struct AuditEntry {
+    pub action: String,
+    pub payment_id: String,
+    pub description: String,
+    pub username: String,
+    pub datetime: String,
+}
+
+// Adds a change to the audit trail of a chat, keeping only the latest changes
+pub async fn add_audit_entry(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    entry: &AuditEntry,
+) -> RedisResult<()> {
+    let key = format!("{AUDIT_KEY}:{chat_id}");
+    con.rpush::<_, _, ()>(
+        &key,
+        (
+            &entry.action,
+            &entry.payment_id,
+            &entry.description,
+            &entry.username,
+            &entry.datetime,
+        ),
+    )
+    .await?;
+    con.ltrim(&key, -(AUDIT_MAX_ENTRIES * AUDIT_FIELDS), -1)
+        .await
+}
+
+// Gets the audit trail of a chat, from the earliest change to the latest
+pub async fn get_audit_entries(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Vec<AuditEntry>> {
+    let entries: Vec<(String, String, String, String, String)> =
+        con.lrange(format!("{AUDIT_KEY}:{chat_id}"), 0, -1).await?;
+    Ok(entries
+        .into_iter()
+        .map(
+            |(action, payment_id, description, username, datetime)| AuditEntry {
+                action,
+                payment_id,
+                description,
+                username,
+                datetime,
+            },
+        )
+        .collect())
+}
+
+// Gets the datetime of the latest change in a chat, if there is any
+// The datetime is the last field of the latest change, so it is read without the rest
+pub async fn get_latest_audit_datetime(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Option<String>> {
+    con.lindex(format!("{AUDIT_KEY}:{chat_id}"), -1).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_audit_entries() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "audit_123456789";
+        assert!(get_audit_entries(&mut con, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            get_latest_audit_datetime(&mut con, chat_id).await.unwrap(),
+            None
+        );
+
+        let entry = AuditEntry {
+            action: AUDIT_ADDED.to_string(),
+            payment_id: "audit_payment_1".to_string(),
+            description: "Lunch".to_string(),
+            username: "audit_user_1".to_string(),
+            datetime: "2024-03-12 10:00:00 UTC".to_string(),
+        };
+        let edit = AuditEntry {
+            action: AUDIT_EDITED.to_string(),
+            username: "audit_user_2".to_string(),
+            datetime: "2024-03-12 11:00:00 UTC".to_string(),
+            ..entry.clone()
+        };
+        assert!(add_audit_entry(&mut con, chat_id, &entry).await.is_ok());
+        assert!(add_audit_entry(&mut con, chat_id, &edit).await.is_ok());
+        assert_eq!(
+            get_audit_entries(&mut con, chat_id).await.unwrap(),
+            vec![entry.clone(), edit]
+        );
+        assert_eq!(
+            get_latest_audit_datetime(&mut con, chat_id).await.unwrap(),
+            Some("2024-03-12 11:00:00 UTC".to_string())
+        );
+
+        // Only the latest changes are kept
+        for _ in 0..AUDIT_MAX_ENTRIES {
+            add_audit_entry(&mut con, chat_id, &entry).await.unwrap();
+        }
+        let entries = get_audit_entries(&mut con, chat_id).await.unwrap();
+        assert_eq!(entries.len(), AUDIT_MAX_ENTRIES as usize);
+        assert!(entries.iter().all(|e| *e == entry));
+
+        con.del::<_, ()>(format!("{AUDIT_KEY}:{chat_id}"))
+            .await
+            .unwrap();
+    }
+}
@@ -1,17 +1,21 @@
-use redis::{Commands, Connection, RedisResult};
+use redis::{AsyncCommands, RedisResult, Script};
+
+use super::connect::RedisConnection;
 
 /* Balance CRUD Operations
  * Balance represents a stake of a user in a group.
  * Balance comprises of an amount the user owes into the group, and an amount the user
  * is owed from the group.
- * Has add, exists, get, update, and delete operations.
+ * Has add, exists, get, update, replace, move, and delete operations.
+ * Balances of many users and currencies can also be fetched together in a single call.
+ * Changes to a balance are applied atomically, so that concurrent payments are not lost.
  */
 
 const BALANCE_KEY: &str = "balance";
 
 // Adds or updates a balance to Redis, only called when payment is created
-pub fn set_balance(
-    con: &mut Connection,
+pub async fn set_balance(
+    con: &mut RedisConnection,
     chat_id: &str,
     user_id: &str,
     currency: &str,
@@ -21,90 +25,354 @@ pub fn set_balance(
         format!("{BALANCE_KEY}:{chat_id}:{user_id}:{currency}"),
         balance,
     )
+    .await
+}
+
+// Adds a change to a balance atomically, starting from 0 if it does not exist
+// Returns the new balance
+pub async fn add_balance(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    user_id: &str,
+    currency: &str,
+    change: i64,
+) -> RedisResult<i64> {
+    con.incr(
+        format!("{BALANCE_KEY}:{chat_id}:{user_id}:{currency}"),
+        change,
+    )
+    .await
 }
 
 // Checks if balance exists
-pub fn get_balance_exists(
-    con: &mut Connection,
+pub async fn get_balance_exists(
+    con: &mut RedisConnection,
     chat_id: &str,
     user_id: &str,
     currency: &str,
 ) -> RedisResult<bool> {
     con.exists(format!("{BALANCE_KEY}:{chat_id}:{user_id}:{currency}"))
+        .await
 }
 
 // Gets a balance
-pub fn get_balance(
-    con: &mut Connection,
+pub async fn get_balance(
+    con: &mut RedisConnection,
     chat_id: &str,
     user_id: &str,
     currency: &str,
 ) -> RedisResult<i64> {
     con.get(format!("{BALANCE_KEY}:{chat_id}:{user_id}:{currency}"))
+        .await
+}
+
+// Gets the balances of the given users in the given currencies in a single call
+// Returns the balances by currency, then by user, with None for balances that do not exist
+pub async fn get_balances(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    users: &[String],
+    currencies: &[String],
+) -> RedisResult<Vec<Option<i64>>> {
+    let keys: Vec<String> = currencies
+        .iter()
+        .flat_map(|currency| {
+            users
+                .iter()
+                .map(move |user_id| format!("{BALANCE_KEY}:{chat_id}:{user_id}:{currency}"))
+        })
+        .collect();
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    redis::cmd("MGET").arg(keys).query_async(con).await
 }
 
 // Deletes a balance in Redis
-// Mainly for testing purposes
-// In application, no real need to delete keys
-#[allow(dead_code)]
-pub fn delete_balance(
-    con: &mut Connection,
+pub async fn delete_balance(
+    con: &mut RedisConnection,
     chat_id: &str,
     user_id: &str,
     currency: &str,
 ) -> RedisResult<()> {
     con.del(format!("{BALANCE_KEY}:{chat_id}:{user_id}:{currency}"))
+        .await
+}
+
+// Moves a balance from one user to another atomically, adding it to any balance of the other user
+// The balance is read and moved in a single step, so that no concurrent change to it is lost
+pub async fn move_balance(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    user_id: &str,
+    new_user_id: &str,
+    currency: &str,
+) -> RedisResult<()> {
+    let script = Script::new(
+        "local balance = redis.call('GET', KEYS[1])
+        if not balance then return 0 end
+        redis.call('DEL', KEYS[1])
+        redis.call('INCRBY', KEYS[2], balance)
+        return 1",
+    );
+    script
+        .key(format!("{BALANCE_KEY}:{chat_id}:{user_id}:{currency}"))
+        .key(format!("{BALANCE_KEY}:{chat_id}:{new_user_id}:{currency}"))
+        .invoke_async::<_, ()>(con)
+        .await
+}
+
+// Replaces all balances of the given users and currencies in a chat atomically
+// Balances that are not given are deleted
+pub async fn replace_balances(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    users: &[String],
+    currencies: &[String],
+    balances: &[(String, String, i64)],
+) -> RedisResult<()> {
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+    for currency in currencies {
+        for user_id in users {
+            pipe.del(format!("{BALANCE_KEY}:{chat_id}:{user_id}:{currency}"))
+                .ignore();
+        }
+    }
+    for (user_id, currency, balance) in balances {
+        pipe.set(
+            format!("{BALANCE_KEY}:{chat_id}:{user_id}:{currency}"),
+            balance,
+        )
+        .ignore();
+    }
+    pipe.query_async(con).await
 }
 
 // Tests
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bot::redis::connect::connect;
+    use crate::bot::redis::connect::Redis;
 
-    #[test]
-    fn test_add_get_balance() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_balance() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "123456789";
         let user_id = "987654321";
         let currency = "USD";
-        assert!(set_balance(&mut con, chat_id, user_id, currency, 1300).is_ok());
-        assert!(get_balance_exists(&mut con, chat_id, user_id, currency).unwrap());
+        assert!(set_balance(&mut con, chat_id, user_id, currency, 1300)
+            .await
+            .is_ok());
+        assert!(get_balance_exists(&mut con, chat_id, user_id, currency)
+            .await
+            .unwrap());
         assert_eq!(
-            get_balance(&mut con, chat_id, user_id, currency).unwrap(),
+            get_balance(&mut con, chat_id, user_id, currency)
+                .await
+                .unwrap(),
             (1300)
         );
 
-        delete_balance(&mut con, chat_id, user_id, currency).unwrap();
+        delete_balance(&mut con, chat_id, user_id, currency)
+            .await
+            .unwrap();
     }
 
-    #[test]
-    fn test_update_balance() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_balance() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "1234567891";
         let user_id = "9876543211";
         let currency = "USD";
-        set_balance(&mut con, chat_id, user_id, currency, 500).unwrap();
-        assert!(set_balance(&mut con, chat_id, user_id, currency, -4213).is_ok());
+        set_balance(&mut con, chat_id, user_id, currency, 500)
+            .await
+            .unwrap();
+        assert!(set_balance(&mut con, chat_id, user_id, currency, -4213)
+            .await
+            .is_ok());
         assert_eq!(
-            get_balance(&mut con, chat_id, user_id, currency).unwrap(),
+            get_balance(&mut con, chat_id, user_id, currency)
+                .await
+                .unwrap(),
             (-4213)
         );
 
-        delete_balance(&mut con, chat_id, user_id, currency).unwrap();
+        delete_balance(&mut con, chat_id, user_id, currency)
+            .await
+            .unwrap();
     }
 
-    #[test]
-    fn test_delete_balance() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_balance() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "1234567893";
+        let user_id = "9876543213";
+        let currency = "USD";
+        assert_eq!(
+            add_balance(&mut con, chat_id, user_id, currency, 500)
+                .await
+                .unwrap(),
+            500
+        );
+        assert_eq!(
+            add_balance(&mut con, chat_id, user_id, currency, -1200)
+                .await
+                .unwrap(),
+            -700
+        );
+        assert_eq!(
+            get_balance(&mut con, chat_id, user_id, currency)
+                .await
+                .unwrap(),
+            -700
+        );
+
+        delete_balance(&mut con, chat_id, user_id, currency)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_delete_balance() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "1234567892";
         let user_id = "9876543212";
         let currency = "SGD";
-        set_balance(&mut con, chat_id, user_id, currency, 4213).unwrap();
-        assert!(delete_balance(&mut con, chat_id, user_id, currency).is_ok());
-        assert!(!get_balance_exists(&mut con, chat_id, user_id, currency).unwrap());
+        set_balance(&mut con, chat_id, user_id, currency, 4213)
+            .await
+            .unwrap();
+        assert!(delete_balance(&mut con, chat_id, user_id, currency)
+            .await
+            .is_ok());
+        assert!(!get_balance_exists(&mut con, chat_id, user_id, currency)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_replace_balances() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "1234567894";
+        let users = vec!["9876543214".to_string(), "9876543215".to_string()];
+        let currencies = vec!["USD".to_string(), "SGD".to_string()];
+        set_balance(&mut con, chat_id, &users[0], "USD", 300)
+            .await
+            .unwrap();
+        set_balance(&mut con, chat_id, &users[1], "SGD", -700)
+            .await
+            .unwrap();
+
+        let balances = vec![(users[1].clone(), "USD".to_string(), -250)];
+        assert!(
+            replace_balances(&mut con, chat_id, &users, &currencies, &balances)
+                .await
+                .is_ok()
+        );
+        assert!(!get_balance_exists(&mut con, chat_id, &users[0], "USD")
+            .await
+            .unwrap());
+        assert!(!get_balance_exists(&mut con, chat_id, &users[1], "SGD")
+            .await
+            .unwrap());
+        assert_eq!(
+            get_balance(&mut con, chat_id, &users[1], "USD")
+                .await
+                .unwrap(),
+            -250
+        );
+
+        delete_balance(&mut con, chat_id, &users[1], "USD")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_move_balance() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "1234567895";
+        let user_id = "9876543216";
+        let new_user_id = "9876543217";
+        let currency = "USD";
+        set_balance(&mut con, chat_id, user_id, currency, 300)
+            .await
+            .unwrap();
+        set_balance(&mut con, chat_id, new_user_id, currency, -500)
+            .await
+            .unwrap();
+        assert!(
+            move_balance(&mut con, chat_id, user_id, new_user_id, currency)
+                .await
+                .is_ok()
+        );
+        assert!(!get_balance_exists(&mut con, chat_id, user_id, currency)
+            .await
+            .unwrap());
+        assert_eq!(
+            get_balance(&mut con, chat_id, new_user_id, currency)
+                .await
+                .unwrap(),
+            -200
+        );
+
+        delete_balance(&mut con, chat_id, new_user_id, currency)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_balances() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "test_get_balances_chat";
+        let users = vec![
+            "test_get_balances_1".to_string(),
+            "test_get_balances_2".to_string(),
+        ];
+        let currencies = vec!["USD".to_string(), "SGD".to_string()];
+        set_balance(&mut con, chat_id, &users[0], "USD", 300)
+            .await
+            .unwrap();
+        set_balance(&mut con, chat_id, &users[1], "USD", -300)
+            .await
+            .unwrap();
+        set_balance(&mut con, chat_id, &users[1], "SGD", 0)
+            .await
+            .unwrap();
+
+        // Balances are by currency, then by user
+        assert_eq!(
+            get_balances(&mut con, chat_id, &users, &currencies)
+                .await
+                .unwrap(),
+            vec![Some(300), Some(-300), None, Some(0)]
+        );
+        assert!(get_balances(&mut con, chat_id, &[], &currencies)
+            .await
+            .unwrap()
+            .is_empty());
+
+        delete_balance(&mut con, chat_id, &users[0], "USD")
+            .await
+            .unwrap();
+        delete_balance(&mut con, chat_id, &users[1], "USD")
+            .await
+            .unwrap();
+        delete_balance(&mut con, chat_id, &users[1], "SGD")
+            .await
+            .unwrap();
     }
 }
@@ -0,0 +1,112 @@
+use redis::{AsyncCommands, ErrorKind, RedisError, RedisResult};
+use serde::{Deserialize, Serialize};
+
+use super::connect::RedisConnection;
+
+/* Bot Stats CRUD Operations
+ * Bot stats represent the usage of the whole bot, across all chats, for its operators.
+ * Bot stats comprise counts aggregated by scanning the database, which is slow,
+ * so they are cached as JSON, expiring after some time.
+ * Has set, get, and delete operations, along with the scans and memory usage of the database.
+ */
+
+const BOT_STATS_KEY: &str = "bot_stats";
+
+// BotStats is the usage of the bot at some datetime, with the memory used in bytes, if known
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct BotStats {
+    pub chats: usize,
+    pub active_chats: usize,
+    pub payments: usize,
+    pub keys: usize,
+    pub used_memory: Option<u64>,
+    pub computed_at: String,
+}
+
+// Converts an error from reading or writing JSON into a Redis error
+fn to_redis_error(err: serde_json::Error) -> RedisError {
+    RedisError::from((ErrorKind::TypeError, "Invalid bot stats", err.to_string()))
+}
+
+// Sets the bot stats, expiring after some time
+pub async fn set_bot_stats(
+    con: &mut RedisConnection,
+    stats: &BotStats,
+    ttl: usize,
+) -> RedisResult<()> {
+    let value = serde_json::to_string(stats).map_err(to_redis_error)?;
+    con.set_ex(BOT_STATS_KEY, value, ttl).await
+}
+
+// Gets the bot stats, if they are cached
+pub async fn get_bot_stats(con: &mut RedisConnection) -> RedisResult<Option<BotStats>> {
+    let value: Option<String> = con.get(BOT_STATS_KEY).await?;
+    value
+        .map(|value| serde_json::from_str(&value).map_err(to_redis_error))
+        .transpose()
+}
+
+// Deletes the cached bot stats
+// Mainly for testing purposes
+#[allow(dead_code)]
+pub async fn delete_bot_stats(con: &mut RedisConnection) -> RedisResult<()> {
+    con.del(BOT_STATS_KEY).await
+}
+
+// Counts all keys in the database, by scanning through them
+pub async fn count_keys(con: &mut RedisConnection) -> RedisResult<usize> {
+    let mut iter: redis::AsyncIter<String> = con.scan().await?;
+    let mut count = 0;
+    while iter.next_item().await.is_some() {
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+// Gets the memory used by the database in bytes, as reported by INFO
+// Some Redis-compatible databases don't report it, in which case it is None
+pub async fn get_used_memory(con: &mut RedisConnection) -> RedisResult<Option<u64>> {
+    let info: String = redis::cmd("INFO").arg("memory").query_async(con).await?;
+    Ok(info.lines().find_map(|line| {
+        line.strip_prefix("used_memory:")
+            .and_then(|value| value.trim().parse().ok())
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_bot_stats() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let stats = BotStats {
+            chats: 3,
+            active_chats: 1,
+            payments: 12,
+            keys: 80,
+            used_memory: Some(1024),
+            computed_at: "2024-03-12 10:00:00 UTC".to_string(),
+        };
+        assert!(set_bot_stats(&mut con, &stats, 600).await.is_ok());
+        assert_eq!(get_bot_stats(&mut con).await.unwrap(), Some(stats));
+
+        assert!(delete_bot_stats(&mut con).await.is_ok());
+        assert_eq!(get_bot_stats(&mut con).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_count_keys() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let key = "bot_stats_count_keys";
+        con.set::<_, _, ()>(key, "1").await.unwrap();
+        assert!(count_keys(&mut con).await.unwrap() >= 1);
+        con.del::<_, ()>(key).await.unwrap();
+    }
+}
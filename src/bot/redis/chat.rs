@@ -1,6 +1,10 @@
-use redis::{Commands, Connection, RedisResult};
+use std::collections::HashMap;
+
+use redis::{AsyncCommands, RedisResult};
 use serde::{Deserialize, Serialize};
 
+use super::connect::RedisConnection;
+
 /* Chat CRUD Operations
  * Chat represents a chat, most likely a group chat on Telegram.
  * Chat comprises a list of usernames and a list of payments,
@@ -13,6 +17,7 @@ use serde::{Deserialize, Serialize};
 const CHAT_KEY: &str = "chat";
 const CHAT_PAYMENT_KEY: &str = "chat_payment";
 const CHAT_CURRENCY_KEY: &str = "chat_currency";
+const CHAT_CURRENCY_USAGE_KEY: &str = "chat_currency_usage";
 const CHAT_SETTING_KEY: &str = "chat_setting";
 
 // Chat Settings
@@ -20,9 +25,32 @@ const SETTING_TIME_ZONE: &str = "time_zone";
 const SETTING_DEFAULT_CURRENCY: &str = "default_currency";
 const SETTING_CURRENCY_CONVERSION: &str = "currency_conversion";
 const SETTING_ERASE_MESSAGES: &str = "erase_messages";
+const SETTING_MONTHLY_ARCHIVE: &str = "monthly_archive";
+const SETTING_WEEKLY_DIGEST: &str = "weekly_digest";
+const SETTING_APPROVAL_THRESHOLD: &str = "approval_threshold";
+const SETTING_LANGUAGE: &str = "language";
+const SETTING_NUMBER_FORMAT: &str = "number_format";
+const SETTING_CURRENCY_FORMAT: &str = "currency_format";
+const SETTING_DEBT_REMINDER: &str = "debt_reminder";
+const SETTING_DATA_RETENTION: &str = "data_retention";
+const SETTING_ADMIN_ONLY: &str = "admin_only";
+const SETTING_TREASURER: &str = "treasurer";
+const SETTING_PAIRWISE_DEBTS: &str = "pairwise_debts";
+const SETTING_PASSIVE_MODE: &str = "passive_mode";
+const SETTING_DUAL_ENTRY: &str = "dual_entry";
+const SETTING_STRICT_MEMBERS: &str = "strict_members";
+const SETTING_ERASE_USER_MESSAGES: &str = "erase_user_messages";
+const SETTING_VERBOSITY: &str = "verbosity";
+const SETTING_ROUNDING: &str = "rounding";
+const SETTING_ROUNDING_OFFSET: &str = "rounding_offset";
+const SETTING_ALLOWED_CURRENCIES: &str = "allowed_currencies";
+const SETTING_WEEK_START: &str = "week_start";
 
 // Constants
 pub const CURRENCY_CODE_DEFAULT: &str = "NIL";
+pub const VERBOSITY_DEFAULT: &str = "verbose";
+pub const ROUNDING_DEFAULT: &str = "first";
+pub const WEEK_START_DEFAULT: &str = "monday";
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct Debt {
@@ -33,296 +61,1269 @@ pub struct Debt {
 }
 
 // Adds a new chat to Redis
-pub fn add_chat(con: &mut Connection, chat_id: &str, username: &str) -> RedisResult<()> {
-    con.rpush(format!("{CHAT_KEY}:{chat_id}"), username)
+pub async fn add_chat(con: &mut RedisConnection, chat_id: &str, username: &str) -> RedisResult<()> {
+    con.rpush(format!("{CHAT_KEY}:{chat_id}"), username).await
 }
 
 // Gets all users from a chat
 // Returns a vector of usernames
-pub fn get_chat_users(con: &mut Connection, chat_id: &str) -> RedisResult<Vec<String>> {
-    con.lrange(format!("{CHAT_KEY}:{chat_id}"), 0, -1)
+pub async fn get_chat_users(con: &mut RedisConnection, chat_id: &str) -> RedisResult<Vec<String>> {
+    con.lrange(format!("{CHAT_KEY}:{chat_id}"), 0, -1).await
 }
 
 // Checks if chat exists
-pub fn get_chat_exists(con: &mut Connection, chat_id: &str) -> RedisResult<bool> {
-    con.exists(format!("{CHAT_KEY}:{chat_id}"))
+pub async fn get_chat_exists(con: &mut RedisConnection, chat_id: &str) -> RedisResult<bool> {
+    con.exists(format!("{CHAT_KEY}:{chat_id}")).await
 }
 
 // Adds a single new user to the chat. Automatically checks if already added.
 // Not in use in production, prefers add_chat_user_multiple
 #[allow(dead_code)]
-pub fn add_chat_user(con: &mut Connection, chat_id: &str, username: &str) -> RedisResult<()> {
-    let current_users: Vec<String> = get_chat_users(con, chat_id)?;
+pub async fn add_chat_user(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    username: &str,
+) -> RedisResult<()> {
+    let current_users: Vec<String> = get_chat_users(con, chat_id).await?;
     if current_users.contains(&username.to_string()) {
         return Ok(());
     }
-    con.rpush(format!("{CHAT_KEY}:{chat_id}"), username)
+    con.rpush(format!("{CHAT_KEY}:{chat_id}"), username).await
 }
 
 // Adds more users to the chat. Automatically checks if already added.
-pub fn add_chat_user_multiple(
-    con: &mut Connection,
+pub async fn add_chat_user_multiple(
+    con: &mut RedisConnection,
     chat_id: &str,
     users: Vec<String>,
 ) -> RedisResult<()> {
-    let current_users: Vec<String> = get_chat_users(con, chat_id)?;
+    let current_users: Vec<String> = get_chat_users(con, chat_id).await?;
     for user in users {
         if !current_users.contains(&user) {
-            con.rpush(format!("{CHAT_KEY}:{chat_id}"), user)?;
+            con.rpush::<_, _, ()>(format!("{CHAT_KEY}:{chat_id}"), user)
+                .await?;
         }
     }
 
     Ok(())
 }
 
+// Replaces a user in the chat with another, keeping their position in the list
+// If the other user is already added, simply removes the user
+pub async fn replace_chat_user(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    username: &str,
+    new_username: &str,
+) -> RedisResult<()> {
+    let current_users: Vec<String> = get_chat_users(con, chat_id).await?;
+    let position = current_users.iter().position(|user| user == username);
+    match position {
+        Some(_) if current_users.contains(&new_username.to_string()) => {
+            con.lrem(format!("{CHAT_KEY}:{chat_id}"), 0, username).await
+        }
+        Some(index) => {
+            con.lset(
+                format!("{CHAT_KEY}:{chat_id}"),
+                index as isize,
+                new_username,
+            )
+            .await
+        }
+        None => Ok(()),
+    }
+}
+
 // Deletes a chat from Redis
-// Mainly for testing purposes
-// In application, no real need to delete keys
-#[allow(dead_code)]
-pub fn delete_chat(con: &mut Connection, chat_id: &str) -> RedisResult<()> {
-    con.del(format!("{CHAT_KEY}:{chat_id}"))
+pub async fn delete_chat(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{CHAT_KEY}:{chat_id}")).await
 }
 
 /* Chat Payment CRUD Operations */
 
 // Adds a new payment to a chat
-pub fn add_chat_payment(con: &mut Connection, chat_id: &str, payment_id: &str) -> RedisResult<()> {
+pub async fn add_chat_payment(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    payment_id: &str,
+) -> RedisResult<()> {
     con.lpush(format!("{CHAT_PAYMENT_KEY}:{chat_id}"), payment_id)
+        .await
 }
 
 // Checks if payments exist in a chat
-pub fn get_chat_payment_exists(con: &mut Connection, chat_id: &str) -> RedisResult<bool> {
-    con.exists(format!("{CHAT_PAYMENT_KEY}:{chat_id}"))
+pub async fn get_chat_payment_exists(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    con.exists(format!("{CHAT_PAYMENT_KEY}:{chat_id}")).await
 }
 
 // Gets all payments from a chat
-pub fn get_chat_payments(con: &mut Connection, chat_id: &str) -> RedisResult<Vec<String>> {
+pub async fn get_chat_payments(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Vec<String>> {
     con.lrange(format!("{CHAT_PAYMENT_KEY}:{chat_id}"), 0, -1)
+        .await
+}
+
+// Gets the number of payments in a chat
+pub async fn get_chat_payment_count(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<usize> {
+    con.llen(format!("{CHAT_PAYMENT_KEY}:{chat_id}")).await
+}
+
+// Gets all chats with payments, by scanning for their payment lists
+pub async fn get_payment_chats(con: &mut RedisConnection) -> RedisResult<Vec<String>> {
+    let prefix = format!("{CHAT_PAYMENT_KEY}:");
+    let mut iter: redis::AsyncIter<String> = con.scan_match(format!("{prefix}*")).await?;
+    let mut chats: Vec<String> = Vec::new();
+    while let Some(key) = iter.next_item().await {
+        if let Some(chat_id) = key.strip_prefix(&prefix) {
+            chats.push(chat_id.to_string());
+        }
+    }
+
+    Ok(chats)
+}
+
+// Gets all chats known, by scanning for their lists of users
+// Trip ledgers are kept under their chat, so they are left out
+pub async fn get_chats(con: &mut RedisConnection) -> RedisResult<Vec<String>> {
+    let prefix = format!("{CHAT_KEY}:");
+    let mut iter: redis::AsyncIter<String> = con.scan_match(format!("{prefix}*")).await?;
+    let mut chats: Vec<String> = Vec::new();
+    while let Some(key) = iter.next_item().await {
+        match key.strip_prefix(&prefix) {
+            Some(chat_id) if !chat_id.contains(':') => chats.push(chat_id.to_string()),
+            _ => {}
+        }
+    }
+
+    Ok(chats)
 }
 
 // Deletes a payment from a chat
-pub fn delete_chat_payment(
-    con: &mut Connection,
+pub async fn delete_chat_payment(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    payment_id: &str,
+) -> RedisResult<()> {
+    con.lrem(format!("{CHAT_PAYMENT_KEY}:{chat_id}"), 0, payment_id)
+        .await
+}
+
+// Deletes all payments from a chat
+pub async fn delete_all_chat_payment(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{CHAT_PAYMENT_KEY}:{chat_id}")).await
+}
+
+/* Chat Currency CRUD Operations */
+// Adds a currency to a chat, atomically replacing it if it was already added
+pub async fn add_chat_currency(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    currency: &str,
+) -> RedisResult<()> {
+    let key = format!("{CHAT_CURRENCY_KEY}:{chat_id}");
+    redis::pipe()
+        .atomic()
+        .lrem(&key, 0, currency)
+        .ignore()
+        .rpush(&key, currency)
+        .ignore()
+        .query_async(con)
+        .await
+}
+
+// Gets all currencies from a chat
+pub async fn get_chat_currencies(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Vec<String>> {
+    con.lrange(format!("{CHAT_CURRENCY_KEY}:{chat_id}"), 0, -1)
+        .await
+}
+
+// Deletes all currencies from a chat
+pub async fn delete_chat_currencies(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{CHAT_CURRENCY_KEY}:{chat_id}")).await
+}
+
+// Increments the number of payments made in a currency in a chat
+pub async fn incr_chat_currency_usage(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    currency: &str,
+) -> RedisResult<()> {
+    con.hincr(format!("{CHAT_CURRENCY_USAGE_KEY}:{chat_id}"), currency, 1)
+        .await
+}
+
+// Sets the number of payments made in each currency in a chat, all in one step
+pub async fn set_chat_currency_usage(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    usage: &[(String, u64)],
+) -> RedisResult<()> {
+    if usage.is_empty() {
+        return Ok(());
+    }
+
+    redis::pipe()
+        .atomic()
+        .hset_multiple(format!("{CHAT_CURRENCY_USAGE_KEY}:{chat_id}"), usage)
+        .ignore()
+        .query_async(con)
+        .await
+}
+
+// Gets the number of payments made in each currency in a chat
+pub async fn get_chat_currency_usage(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<HashMap<String, u64>> {
+    con.hgetall(format!("{CHAT_CURRENCY_USAGE_KEY}:{chat_id}"))
+        .await
+}
+
+// Deletes the currency usage of a chat
+pub async fn delete_chat_currency_usage(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<()> {
+    con.del(format!("{CHAT_CURRENCY_USAGE_KEY}:{chat_id}"))
+        .await
+}
+
+/* Chat Setting CRUD Operations */
+// Sets time zone for a chat
+pub async fn set_chat_time_zone(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    time_zone: &str,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_TIME_ZONE,
+        time_zone,
+    )
+    .await
+}
+
+// Sets default currency for a chat
+pub async fn set_chat_default_currency(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    currency: &str,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_DEFAULT_CURRENCY,
+        currency,
+    )
+    .await
+}
+
+// Sets currency conversion for a chat
+pub async fn set_chat_currency_conversion(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    currency_conversion: bool,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_CURRENCY_CONVERSION,
+        currency_conversion,
+    )
+    .await
+}
+
+// Sets erase messages for a chat
+pub async fn set_chat_erase_messages(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    erase_messages: bool,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_ERASE_MESSAGES,
+        erase_messages,
+    )
+    .await
+}
+
+// Sets monthly archive for a chat
+pub async fn set_chat_monthly_archive(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    monthly_archive: bool,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_MONTHLY_ARCHIVE,
+        monthly_archive,
+    )
+    .await
+}
+
+// Sets admin only for a chat
+pub async fn set_chat_admin_only(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    admin_only: bool,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_ADMIN_ONLY,
+        admin_only,
+    )
+    .await
+}
+
+// Sets pairwise debts for a chat
+pub async fn set_chat_pairwise_debts(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    pairwise_debts: bool,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_PAIRWISE_DEBTS,
+        pairwise_debts,
+    )
+    .await
+}
+
+// Sets passive mode for a chat
+pub async fn set_chat_passive_mode(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    passive_mode: bool,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_PASSIVE_MODE,
+        passive_mode,
+    )
+    .await
+}
+
+// Sets dual entry verification for a chat
+pub async fn set_chat_dual_entry(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    dual_entry: bool,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_DUAL_ENTRY,
+        dual_entry,
+    )
+    .await
+}
+
+// Sets strict members for a chat
+pub async fn set_chat_strict_members(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    strict_members: bool,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_STRICT_MEMBERS,
+        strict_members,
+    )
+    .await
+}
+
+// Sets whether user messages are erased too for a chat
+pub async fn set_chat_erase_user_messages(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    erase_user_messages: bool,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_ERASE_USER_MESSAGES,
+        erase_user_messages,
+    )
+    .await
+}
+
+// Sets verbosity for a chat
+pub async fn set_chat_verbosity(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    verbosity: &str,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_VERBOSITY,
+        verbosity,
+    )
+    .await
+}
+
+// Sets rounding strategy for a chat
+pub async fn set_chat_rounding(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    rounding: &str,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_ROUNDING,
+        rounding,
+    )
+    .await
+}
+
+// Sets first day of the week for a chat
+pub async fn set_chat_week_start(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    week_start: &str,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_WEEK_START,
+        week_start,
+    )
+    .await
+}
+
+// Sets allowed currencies for a chat, as comma-separated currency codes
+pub async fn set_chat_allowed_currencies(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    currencies: &str,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_ALLOWED_CURRENCIES,
+        currencies,
+    )
+    .await
+}
+
+// Sets treasurer for a chat
+pub async fn set_chat_treasurer(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    treasurer: &str,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_TREASURER,
+        treasurer,
+    )
+    .await
+}
+
+// Sets weekly digest time for a chat
+pub async fn set_chat_weekly_digest(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    time: &str,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_WEEKLY_DIGEST,
+        time,
+    )
+    .await
+}
+
+// Sets payment approval threshold for a chat
+pub async fn set_chat_approval_threshold(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    threshold: f64,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_APPROVAL_THRESHOLD,
+        threshold,
+    )
+    .await
+}
+
+// Sets language for a chat
+pub async fn set_chat_language(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    language: &str,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_LANGUAGE,
+        language,
+    )
+    .await
+}
+
+// Sets number format for a chat
+pub async fn set_chat_number_format(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    number_format: &str,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_NUMBER_FORMAT,
+        number_format,
+    )
+    .await
+}
+
+// Sets currency format for a chat
+pub async fn set_chat_currency_format(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    currency_format: &str,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_CURRENCY_FORMAT,
+        currency_format,
+    )
+    .await
+}
+
+// Sets debt reminder interval in days for a chat
+pub async fn set_chat_debt_reminder(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    days: u32,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_DEBT_REMINDER,
+        days,
+    )
+    .await
+}
+
+// Sets data retention period in months for a chat
+pub async fn set_chat_data_retention(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    months: u32,
+) -> RedisResult<()> {
+    con.hset(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_DATA_RETENTION,
+        months,
+    )
+    .await
+}
+
+// Checks if time zone exists for a chat
+pub async fn is_exists_chat_time_zone(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_TIME_ZONE.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if default currency exists for a chat
+pub async fn is_exists_chat_default_currency(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_DEFAULT_CURRENCY.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if currency conversion exists for a chat
+pub async fn is_exists_chat_currency_conversion(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_CURRENCY_CONVERSION.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if erase messages exists for a chat
+pub async fn is_exists_chat_erase_messages(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_ERASE_MESSAGES.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if monthly archive exists for a chat
+pub async fn is_exists_chat_monthly_archive(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_MONTHLY_ARCHIVE.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if admin only exists for a chat
+pub async fn is_exists_chat_admin_only(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_ADMIN_ONLY.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if pairwise debts exists for a chat
+pub async fn is_exists_chat_pairwise_debts(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_PAIRWISE_DEBTS.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if passive mode exists for a chat
+pub async fn is_exists_chat_passive_mode(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_PASSIVE_MODE.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if dual entry verification exists for a chat
+pub async fn is_exists_chat_dual_entry(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_DUAL_ENTRY.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if strict members exists for a chat
+pub async fn is_exists_chat_strict_members(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_STRICT_MEMBERS.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if erasing user messages exists for a chat
+pub async fn is_exists_chat_erase_user_messages(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_ERASE_USER_MESSAGES.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if verbosity exists for a chat
+pub async fn is_exists_chat_verbosity(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_VERBOSITY.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if rounding strategy exists for a chat
+pub async fn is_exists_chat_rounding(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_ROUNDING.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if first day of the week exists for a chat
+pub async fn is_exists_chat_week_start(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_WEEK_START.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if allowed currencies exist for a chat
+pub async fn is_exists_chat_allowed_currencies(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_ALLOWED_CURRENCIES.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if treasurer exists for a chat
+pub async fn is_exists_chat_treasurer(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_TREASURER.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if weekly digest exists for a chat
+pub async fn is_exists_chat_weekly_digest(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_WEEKLY_DIGEST.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if payment approval threshold exists for a chat
+pub async fn is_exists_chat_approval_threshold(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_APPROVAL_THRESHOLD.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if language exists for a chat
+pub async fn is_exists_chat_language(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_LANGUAGE.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if number format exists for a chat
+pub async fn is_exists_chat_number_format(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_NUMBER_FORMAT.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if currency format exists for a chat
+pub async fn is_exists_chat_currency_format(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_CURRENCY_FORMAT.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Checks if debt reminder exists for a chat
+pub async fn is_exists_chat_debt_reminder(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_DEBT_REMINDER.to_string()) {
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+// Gets time zone for a chat
+pub async fn get_chat_time_zone(con: &mut RedisConnection, chat_id: &str) -> RedisResult<String> {
+    con.hget(format!("{CHAT_SETTING_KEY}:{chat_id}"), SETTING_TIME_ZONE)
+        .await
+}
+
+// Gets default currency for a chat
+pub async fn get_chat_default_currency(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<String> {
+    con.hget(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_DEFAULT_CURRENCY,
+    )
+    .await
+}
+
+// Gets currency conversion for a chat
+pub async fn get_chat_currency_conversion(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    con.hget(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_CURRENCY_CONVERSION,
+    )
+    .await
+}
+
+// Gets erase messages for a chat
+pub async fn get_chat_erase_messages(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    con.hget(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_ERASE_MESSAGES,
+    )
+    .await
+}
+
+// Gets monthly archive for a chat
+pub async fn get_chat_monthly_archive(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    con.hget(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_MONTHLY_ARCHIVE,
+    )
+    .await
+}
+
+// Gets admin only for a chat
+pub async fn get_chat_admin_only(con: &mut RedisConnection, chat_id: &str) -> RedisResult<bool> {
+    con.hget(format!("{CHAT_SETTING_KEY}:{chat_id}"), SETTING_ADMIN_ONLY)
+        .await
+}
+
+// Gets pairwise debts for a chat
+pub async fn get_chat_pairwise_debts(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    con.hget(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_PAIRWISE_DEBTS,
+    )
+    .await
+}
+
+// Gets passive mode for a chat
+pub async fn get_chat_passive_mode(con: &mut RedisConnection, chat_id: &str) -> RedisResult<bool> {
+    con.hget(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_PASSIVE_MODE,
+    )
+    .await
+}
+
+// Gets dual entry verification for a chat
+pub async fn get_chat_dual_entry(con: &mut RedisConnection, chat_id: &str) -> RedisResult<bool> {
+    con.hget(format!("{CHAT_SETTING_KEY}:{chat_id}"), SETTING_DUAL_ENTRY)
+        .await
+}
+
+// Gets strict members for a chat
+pub async fn get_chat_strict_members(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    con.hget(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_STRICT_MEMBERS,
+    )
+    .await
+}
+
+// Gets whether user messages are erased too for a chat
+pub async fn get_chat_erase_user_messages(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    con.hget(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_ERASE_USER_MESSAGES,
+    )
+    .await
+}
+
+// Gets verbosity for a chat
+pub async fn get_chat_verbosity(con: &mut RedisConnection, chat_id: &str) -> RedisResult<String> {
+    con.hget(format!("{CHAT_SETTING_KEY}:{chat_id}"), SETTING_VERBOSITY)
+        .await
+}
+
+// Gets rounding strategy for a chat
+pub async fn get_chat_rounding(con: &mut RedisConnection, chat_id: &str) -> RedisResult<String> {
+    con.hget(format!("{CHAT_SETTING_KEY}:{chat_id}"), SETTING_ROUNDING)
+        .await
+}
+
+// Increments the round-robin rounding offset for a chat
+// Returns the offset before incrementing
+pub async fn incr_chat_rounding_offset(
+    con: &mut RedisConnection,
     chat_id: &str,
-    payment_id: &str,
-) -> RedisResult<()> {
-    con.lrem(format!("{CHAT_PAYMENT_KEY}:{chat_id}"), 0, payment_id)
+) -> RedisResult<u64> {
+    let offset: u64 = con
+        .hincr(
+            format!("{CHAT_SETTING_KEY}:{chat_id}"),
+            SETTING_ROUNDING_OFFSET,
+            1,
+        )
+        .await?;
+    Ok(offset - 1)
 }
 
-// Deletes all payments from a chat
-// Mainly for testing purposes
-// In application, no real need to delete keys
-#[allow(dead_code)]
-pub fn delete_all_chat_payment(con: &mut Connection, chat_id: &str) -> RedisResult<()> {
-    con.del(format!("{CHAT_PAYMENT_KEY}:{chat_id}"))
+// Gets first day of the week for a chat
+pub async fn get_chat_week_start(con: &mut RedisConnection, chat_id: &str) -> RedisResult<String> {
+    con.hget(format!("{CHAT_SETTING_KEY}:{chat_id}"), SETTING_WEEK_START)
+        .await
 }
 
-/* Chat Currency CRUD Operations */
-// Adds a currency to a chat
-pub fn add_chat_currency(con: &mut Connection, chat_id: &str, currency: &str) -> RedisResult<()> {
-    con.rpush(format!("{CHAT_CURRENCY_KEY}:{chat_id}"), currency)
+// Gets allowed currencies for a chat, as comma-separated currency codes
+pub async fn get_chat_allowed_currencies(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<String> {
+    con.hget(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_ALLOWED_CURRENCIES,
+    )
+    .await
 }
 
-// Gets all currencies from a chat
-pub fn get_chat_currencies(con: &mut Connection, chat_id: &str) -> RedisResult<Vec<String>> {
-    con.lrange(format!("{CHAT_CURRENCY_KEY}:{chat_id}"), 0, -1)
+// Gets treasurer for a chat
+pub async fn get_chat_treasurer(con: &mut RedisConnection, chat_id: &str) -> RedisResult<String> {
+    con.hget(format!("{CHAT_SETTING_KEY}:{chat_id}"), SETTING_TREASURER)
+        .await
 }
 
-// Deletes all currencies from a chat
-// Mainly for testing purposes
-// In application, no real need to delete keys
-#[allow(dead_code)]
-pub fn delete_chat_currencies(con: &mut Connection, chat_id: &str) -> RedisResult<()> {
-    con.del(format!("{CHAT_CURRENCY_KEY}:{chat_id}"))
+// Deletes treasurer for a chat
+pub async fn delete_chat_treasurer(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.hdel(format!("{CHAT_SETTING_KEY}:{chat_id}"), SETTING_TREASURER)
+        .await
 }
 
-/* Chat Setting CRUD Operations */
-// Sets time zone for a chat
-pub fn set_chat_time_zone(con: &mut Connection, chat_id: &str, time_zone: &str) -> RedisResult<()> {
-    con.hset(
+// Gets weekly digest time for a chat
+pub async fn get_chat_weekly_digest(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<String> {
+    con.hget(
         format!("{CHAT_SETTING_KEY}:{chat_id}"),
-        SETTING_TIME_ZONE,
-        time_zone,
+        SETTING_WEEKLY_DIGEST,
     )
+    .await
 }
 
-// Sets default currency for a chat
-pub fn set_chat_default_currency(
-    con: &mut Connection,
+// Deletes weekly digest time for a chat
+pub async fn delete_chat_weekly_digest(
+    con: &mut RedisConnection,
     chat_id: &str,
-    currency: &str,
 ) -> RedisResult<()> {
-    con.hset(
+    con.hdel(
         format!("{CHAT_SETTING_KEY}:{chat_id}"),
-        SETTING_DEFAULT_CURRENCY,
-        currency,
+        SETTING_WEEKLY_DIGEST,
     )
+    .await
 }
 
-// Sets currency conversion for a chat
-pub fn set_chat_currency_conversion(
-    con: &mut Connection,
+// Gets payment approval threshold for a chat
+pub async fn get_chat_approval_threshold(
+    con: &mut RedisConnection,
     chat_id: &str,
-    currency_conversion: bool,
-) -> RedisResult<()> {
-    con.hset(
+) -> RedisResult<f64> {
+    con.hget(
         format!("{CHAT_SETTING_KEY}:{chat_id}"),
-        SETTING_CURRENCY_CONVERSION,
-        currency_conversion,
+        SETTING_APPROVAL_THRESHOLD,
     )
+    .await
 }
 
-// Sets erase messages for a chat
-pub fn set_chat_erase_messages(
-    con: &mut Connection,
+// Deletes payment approval threshold for a chat
+pub async fn delete_chat_approval_threshold(
+    con: &mut RedisConnection,
     chat_id: &str,
-    erase_messages: bool,
 ) -> RedisResult<()> {
-    con.hset(
+    con.hdel(
         format!("{CHAT_SETTING_KEY}:{chat_id}"),
-        SETTING_ERASE_MESSAGES,
-        erase_messages,
+        SETTING_APPROVAL_THRESHOLD,
     )
+    .await
 }
 
-// Checks if time zone exists for a chat
-pub fn is_exists_chat_time_zone(con: &mut Connection, chat_id: &str) -> RedisResult<bool> {
-    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}"))?;
-    if keys.contains(&SETTING_TIME_ZONE.to_string()) {
-        Ok(true)
-    } else {
-        Ok(false)
-    }
+// Gets language for a chat
+pub async fn get_chat_language(con: &mut RedisConnection, chat_id: &str) -> RedisResult<String> {
+    con.hget(format!("{CHAT_SETTING_KEY}:{chat_id}"), SETTING_LANGUAGE)
+        .await
 }
 
-// Checks if default currency exists for a chat
-pub fn is_exists_chat_default_currency(con: &mut Connection, chat_id: &str) -> RedisResult<bool> {
-    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}"))?;
-    if keys.contains(&SETTING_DEFAULT_CURRENCY.to_string()) {
-        Ok(true)
-    } else {
-        Ok(false)
-    }
+// Gets number format for a chat
+pub async fn get_chat_number_format(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<String> {
+    con.hget(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_NUMBER_FORMAT,
+    )
+    .await
 }
 
-// Checks if currency conversion exists for a chat
-pub fn is_exists_chat_currency_conversion(
-    con: &mut Connection,
+// Gets currency format for a chat
+pub async fn get_chat_currency_format(
+    con: &mut RedisConnection,
     chat_id: &str,
-) -> RedisResult<bool> {
-    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}"))?;
-    if keys.contains(&SETTING_CURRENCY_CONVERSION.to_string()) {
-        Ok(true)
-    } else {
-        Ok(false)
-    }
+) -> RedisResult<String> {
+    con.hget(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_CURRENCY_FORMAT,
+    )
+    .await
 }
 
-// Checks if erase messages exists for a chat
-pub fn is_exists_chat_erase_messages(con: &mut Connection, chat_id: &str) -> RedisResult<bool> {
-    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}"))?;
-    if keys.contains(&SETTING_ERASE_MESSAGES.to_string()) {
+// Gets debt reminder interval in days for a chat
+pub async fn get_chat_debt_reminder(con: &mut RedisConnection, chat_id: &str) -> RedisResult<u32> {
+    con.hget(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_DEBT_REMINDER,
+    )
+    .await
+}
+
+// Deletes debt reminder for a chat, turning it off
+pub async fn delete_chat_debt_reminder(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<()> {
+    con.hdel(
+        format!("{CHAT_SETTING_KEY}:{chat_id}"),
+        SETTING_DEBT_REMINDER,
+    )
+    .await
+}
+
+// Checks if data retention exists for a chat
+pub async fn is_exists_chat_data_retention(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    let keys: Vec<String> = con.hkeys(format!("{CHAT_SETTING_KEY}:{chat_id}")).await?;
+    if keys.contains(&SETTING_DATA_RETENTION.to_string()) {
         Ok(true)
     } else {
         Ok(false)
     }
 }
 
-// Gets time zone for a chat
-pub fn get_chat_time_zone(con: &mut Connection, chat_id: &str) -> RedisResult<String> {
-    con.hget(format!("{CHAT_SETTING_KEY}:{chat_id}"), SETTING_TIME_ZONE)
-}
-
-// Gets default currency for a chat
-pub fn get_chat_default_currency(con: &mut Connection, chat_id: &str) -> RedisResult<String> {
+// Gets data retention period in months for a chat
+pub async fn get_chat_data_retention(con: &mut RedisConnection, chat_id: &str) -> RedisResult<u32> {
     con.hget(
         format!("{CHAT_SETTING_KEY}:{chat_id}"),
-        SETTING_DEFAULT_CURRENCY,
+        SETTING_DATA_RETENTION,
     )
+    .await
 }
 
-// Gets currency conversion for a chat
-pub fn get_chat_currency_conversion(con: &mut Connection, chat_id: &str) -> RedisResult<bool> {
-    con.hget(
+// Deletes data retention for a chat, keeping all payments
+pub async fn delete_chat_data_retention(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<()> {
+    con.hdel(
         format!("{CHAT_SETTING_KEY}:{chat_id}"),
-        SETTING_CURRENCY_CONVERSION,
+        SETTING_DATA_RETENTION,
     )
+    .await
 }
 
-// Gets erase messages for a chat
-pub fn get_chat_erase_messages(con: &mut Connection, chat_id: &str) -> RedisResult<bool> {
-    con.hget(
-        format!("{CHAT_SETTING_KEY}:{chat_id}"),
-        SETTING_ERASE_MESSAGES,
-    )
+// Gets all settings of a chat, as pairs of setting and value
+pub async fn get_chat_settings(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Vec<(String, String)>> {
+    con.hgetall(format!("{CHAT_SETTING_KEY}:{chat_id}")).await
+}
+
+// Sets all settings of a chat, replacing any existing settings
+pub async fn set_chat_settings(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    settings: &[(String, String)],
+) -> RedisResult<()> {
+    con.del::<_, ()>(format!("{CHAT_SETTING_KEY}:{chat_id}"))
+        .await?;
+    if settings.is_empty() {
+        return Ok(());
+    }
+    con.hset_multiple(format!("{CHAT_SETTING_KEY}:{chat_id}"), settings)
+        .await
 }
 
 // Deletes chat settings
-// Mainly for testing purposes
-// In application, no real need to delete keys
-#[allow(dead_code)]
-pub fn delete_chat_settings(con: &mut Connection, chat_id: &str) -> RedisResult<()> {
-    con.del(format!("{CHAT_SETTING_KEY}:{chat_id}"))
+// Only needed when a whole chat is deleted
+pub async fn delete_chat_settings(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{CHAT_SETTING_KEY}:{chat_id}")).await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bot::redis::connect::connect;
+    use crate::bot::redis::connect::Redis;
 
-    #[test]
-    fn test_add_chat() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_chat() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "123456789";
         let username = "987654321";
-        assert!(add_chat(&mut con, chat_id, username).is_ok());
+        assert!(add_chat(&mut con, chat_id, username).await.is_ok());
 
-        delete_chat(&mut con, chat_id).unwrap();
+        delete_chat(&mut con, chat_id).await.unwrap();
     }
 
-    #[test]
-    fn test_get_chat_exists() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_chat_exists() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "1234567891";
         let username = "9876543211";
-        add_chat(&mut con, chat_id, username).unwrap();
-        assert!(get_chat_exists(&mut con, chat_id).unwrap());
+        add_chat(&mut con, chat_id, username).await.unwrap();
+        assert!(get_chat_exists(&mut con, chat_id).await.unwrap());
+
+        delete_chat(&mut con, chat_id).await.unwrap();
+    }
 
-        delete_chat(&mut con, chat_id).unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_chats() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678933";
+        let trip_ledger = "12345678933:trip:1";
+        add_chat(&mut con, chat_id, "9876543233").await.unwrap();
+        add_chat(&mut con, trip_ledger, "9876543233").await.unwrap();
+
+        let chats = get_chats(&mut con).await.unwrap();
+        assert!(chats.contains(&chat_id.to_string()));
+        assert!(!chats.contains(&trip_ledger.to_string()));
+
+        delete_chat(&mut con, chat_id).await.unwrap();
+        delete_chat(&mut con, trip_ledger).await.unwrap();
+        assert!(!get_chats(&mut con)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
     }
 
-    #[test]
-    fn test_get_chat_users() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_chat_users() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "1234567890";
         let username = "9876543210";
-        add_chat(&mut con, chat_id, username).unwrap();
-        let users = get_chat_users(&mut con, chat_id);
+        add_chat(&mut con, chat_id, username).await.unwrap();
+        let users = get_chat_users(&mut con, chat_id).await;
         assert!(users.is_ok());
         assert_eq!(users.unwrap(), vec![username.to_string()]);
 
-        delete_chat(&mut con, chat_id).unwrap();
+        delete_chat(&mut con, chat_id).await.unwrap();
     }
 
-    #[test]
-    fn test_add_user_to_chat() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_user_to_chat() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "1234567892";
         let username = "9876543212";
         let new_username = "9876543213";
-        add_chat(&mut con, chat_id, username).unwrap();
-        assert!(add_chat_user(&mut con, chat_id, new_username).is_ok());
+        add_chat(&mut con, chat_id, username).await.unwrap();
+        assert!(add_chat_user(&mut con, chat_id, new_username).await.is_ok());
 
-        delete_chat(&mut con, chat_id).unwrap();
+        delete_chat(&mut con, chat_id).await.unwrap();
     }
 
-    #[test]
-    fn test_add_users_to_chat() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_users_to_chat() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "1234567893";
         let first_user = "987654321";
@@ -331,10 +1332,12 @@ mod tests {
             "987654323".to_string(),
             "987654324".to_string(),
         ];
-        add_chat(&mut con, chat_id, first_user).unwrap();
-        assert!(add_chat_user_multiple(&mut con, chat_id, users).is_ok());
+        add_chat(&mut con, chat_id, first_user).await.unwrap();
+        assert!(add_chat_user_multiple(&mut con, chat_id, users)
+            .await
+            .is_ok());
         assert_eq!(
-            get_chat_users(&mut con, chat_id).unwrap(),
+            get_chat_users(&mut con, chat_id).await.unwrap(),
             vec![
                 "987654321".to_string(),
                 "987654322".to_string(),
@@ -343,191 +1346,842 @@ mod tests {
             ]
         );
 
-        delete_chat(&mut con, chat_id).unwrap();
+        delete_chat(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_replace_chat_user() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678909";
+        let users = vec![
+            "987654325".to_string(),
+            "987654326".to_string(),
+            "987654327".to_string(),
+        ];
+        add_chat_user_multiple(&mut con, chat_id, users)
+            .await
+            .unwrap();
+
+        // Replaces in place
+        assert!(
+            replace_chat_user(&mut con, chat_id, "987654326", "987654328")
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            get_chat_users(&mut con, chat_id).await.unwrap(),
+            vec![
+                "987654325".to_string(),
+                "987654328".to_string(),
+                "987654327".to_string(),
+            ]
+        );
+
+        // Removes if already added
+        assert!(
+            replace_chat_user(&mut con, chat_id, "987654325", "987654327")
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            get_chat_users(&mut con, chat_id).await.unwrap(),
+            vec!["987654328".to_string(), "987654327".to_string()]
+        );
+
+        delete_chat(&mut con, chat_id).await.unwrap();
     }
 
-    #[test]
-    fn test_delete_chat() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_delete_chat() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "1234567894";
         let username = "9876543216";
-        add_chat(&mut con, chat_id, username).unwrap();
-        assert!(get_chat_exists(&mut con, chat_id).unwrap());
-        delete_chat(&mut con, chat_id).unwrap();
-        assert!(!get_chat_exists(&mut con, chat_id).unwrap());
+        add_chat(&mut con, chat_id, username).await.unwrap();
+        assert!(get_chat_exists(&mut con, chat_id).await.unwrap());
+        delete_chat(&mut con, chat_id).await.unwrap();
+        assert!(!get_chat_exists(&mut con, chat_id).await.unwrap());
     }
 
-    #[test]
-    fn test_add_get_chat_payment() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_chat_payment() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "1234567895";
         let payment_id = "payment_id_1";
-        assert!(add_chat_payment(&mut con, chat_id, payment_id).is_ok());
-        assert!(get_chat_payment_exists(&mut con, chat_id).is_ok());
-        assert!(get_chat_payments(&mut con, chat_id).unwrap() == vec![payment_id]);
+        assert!(add_chat_payment(&mut con, chat_id, payment_id)
+            .await
+            .is_ok());
+        assert!(get_chat_payment_exists(&mut con, chat_id).await.is_ok());
+        assert!(get_chat_payments(&mut con, chat_id).await.unwrap() == vec![payment_id]);
+        assert!(get_payment_chats(&mut con)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
 
         let second_payment_id = "payment_id_2";
-        assert!(add_chat_payment(&mut con, chat_id, second_payment_id).is_ok());
+        assert!(add_chat_payment(&mut con, chat_id, second_payment_id)
+            .await
+            .is_ok());
         assert!(
-            get_chat_payments(&mut con, chat_id).unwrap() == vec![second_payment_id, payment_id]
+            get_chat_payments(&mut con, chat_id).await.unwrap()
+                == vec![second_payment_id, payment_id]
         );
+        assert_eq!(get_chat_payment_count(&mut con, chat_id).await.unwrap(), 2);
 
-        delete_all_chat_payment(&mut con, chat_id).unwrap();
+        delete_all_chat_payment(&mut con, chat_id).await.unwrap();
+        assert_eq!(get_chat_payment_count(&mut con, chat_id).await.unwrap(), 0);
     }
 
-    #[test]
-    fn test_delete_chat_payment() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_delete_chat_payment() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "1234567896";
         let payment_id = "payment_id_2";
-        add_chat_payment(&mut con, chat_id, payment_id).unwrap();
+        add_chat_payment(&mut con, chat_id, payment_id)
+            .await
+            .unwrap();
         let payment_id_second = "payment_id_3";
-        add_chat_payment(&mut con, chat_id, payment_id_second).unwrap();
+        add_chat_payment(&mut con, chat_id, payment_id_second)
+            .await
+            .unwrap();
         let payment_id_third = "payment_id_4";
-        add_chat_payment(&mut con, chat_id, payment_id_third).unwrap();
-        delete_chat_payment(&mut con, chat_id, payment_id_second).unwrap();
+        add_chat_payment(&mut con, chat_id, payment_id_third)
+            .await
+            .unwrap();
+        delete_chat_payment(&mut con, chat_id, payment_id_second)
+            .await
+            .unwrap();
 
         assert_eq!(
-            get_chat_payments(&mut con, chat_id).unwrap(),
+            get_chat_payments(&mut con, chat_id).await.unwrap(),
             vec![payment_id_third, payment_id]
         );
-        delete_all_chat_payment(&mut con, chat_id).unwrap();
+        delete_all_chat_payment(&mut con, chat_id).await.unwrap();
     }
 
-    #[test]
-    fn test_delete_all_chat_payment() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_delete_all_chat_payment() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "1234567897";
         let payment_id = "payment_id_5";
-        add_chat_payment(&mut con, chat_id, payment_id).unwrap();
-        delete_all_chat_payment(&mut con, chat_id).unwrap();
-        assert!(!get_chat_payment_exists(&mut con, chat_id).unwrap());
+        add_chat_payment(&mut con, chat_id, payment_id)
+            .await
+            .unwrap();
+        delete_all_chat_payment(&mut con, chat_id).await.unwrap();
+        assert!(!get_chat_payment_exists(&mut con, chat_id).await.unwrap());
     }
 
-    #[test]
-    fn test_add_get_chat_currency() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_chat_currency() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "1234567899";
         let currency = "USD";
-        assert!(add_chat_currency(&mut con, chat_id, currency).is_ok());
+        assert!(add_chat_currency(&mut con, chat_id, currency).await.is_ok());
         assert_eq!(
-            get_chat_currencies(&mut con, chat_id).unwrap(),
+            get_chat_currencies(&mut con, chat_id).await.unwrap(),
             vec![currency]
         );
 
         let second_currency = "EUR";
-        assert!(add_chat_currency(&mut con, chat_id, second_currency).is_ok());
+        assert!(add_chat_currency(&mut con, chat_id, second_currency)
+            .await
+            .is_ok());
         assert_eq!(
-            get_chat_currencies(&mut con, chat_id).unwrap(),
+            get_chat_currencies(&mut con, chat_id).await.unwrap(),
             vec![currency, second_currency]
         );
-        assert!(delete_chat_currencies(&mut con, chat_id).is_ok());
+        assert!(delete_chat_currencies(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_incr_get_chat_currency_usage() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "1234567899_usage";
+        assert!(get_chat_currency_usage(&mut con, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+        for currency in ["USD", "EUR", "USD"] {
+            assert!(incr_chat_currency_usage(&mut con, chat_id, currency)
+                .await
+                .is_ok());
+        }
+        assert_eq!(
+            get_chat_currency_usage(&mut con, chat_id).await.unwrap(),
+            HashMap::from([("USD".to_string(), 2), ("EUR".to_string(), 1)])
+        );
+        assert!(delete_chat_currency_usage(&mut con, chat_id).await.is_ok());
+        assert!(get_chat_currency_usage(&mut con, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
     }
 
-    #[test]
-    fn test_set_get_chat_time_zone() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_chat_currency_usage() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "1234567899_usage_set";
+        assert!(set_chat_currency_usage(&mut con, chat_id, &[])
+            .await
+            .is_ok());
+        assert!(get_chat_currency_usage(&mut con, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+        let usage = vec![("USD".to_string(), 2), ("EUR".to_string(), 1)];
+        assert!(set_chat_currency_usage(&mut con, chat_id, &usage)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_currency_usage(&mut con, chat_id).await.unwrap(),
+            HashMap::from([("USD".to_string(), 2), ("EUR".to_string(), 1)])
+        );
+        assert!(delete_chat_currency_usage(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_time_zone() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "12345678900";
         let time_zone = "SST";
 
-        assert!(!is_exists_chat_time_zone(&mut con, chat_id).unwrap());
-        assert!(set_chat_time_zone(&mut con, chat_id, time_zone).is_ok());
+        assert!(!is_exists_chat_time_zone(&mut con, chat_id).await.unwrap());
+        assert!(set_chat_time_zone(&mut con, chat_id, time_zone)
+            .await
+            .is_ok());
         assert_eq!(
-            get_chat_time_zone(&mut con, chat_id).unwrap(),
+            get_chat_time_zone(&mut con, chat_id).await.unwrap(),
             time_zone.to_string()
         );
-        assert!(is_exists_chat_time_zone(&mut con, chat_id).unwrap());
+        assert!(is_exists_chat_time_zone(&mut con, chat_id).await.unwrap());
 
         let second_time_zone = "PST";
-        assert!(set_chat_time_zone(&mut con, chat_id, second_time_zone).is_ok());
+        assert!(set_chat_time_zone(&mut con, chat_id, second_time_zone)
+            .await
+            .is_ok());
         assert_eq!(
-            get_chat_time_zone(&mut con, chat_id).unwrap(),
+            get_chat_time_zone(&mut con, chat_id).await.unwrap(),
             second_time_zone.to_string()
         );
 
-        assert!(delete_chat_settings(&mut con, chat_id).is_ok());
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
     }
 
-    #[test]
-    fn test_set_get_chat_default_currency() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_default_currency() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "12345678901";
         let currency = "USD";
 
-        assert!(!is_exists_chat_default_currency(&mut con, chat_id).unwrap());
-        assert!(set_chat_default_currency(&mut con, chat_id, currency).is_ok());
+        assert!(!is_exists_chat_default_currency(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_default_currency(&mut con, chat_id, currency)
+            .await
+            .is_ok());
         assert_eq!(
-            get_chat_default_currency(&mut con, chat_id).unwrap(),
+            get_chat_default_currency(&mut con, chat_id).await.unwrap(),
             currency.to_string()
         );
-        assert!(is_exists_chat_default_currency(&mut con, chat_id).unwrap());
+        assert!(is_exists_chat_default_currency(&mut con, chat_id)
+            .await
+            .unwrap());
 
         let second_currency = "EUR";
-        assert!(set_chat_default_currency(&mut con, chat_id, second_currency).is_ok());
+        assert!(
+            set_chat_default_currency(&mut con, chat_id, second_currency)
+                .await
+                .is_ok()
+        );
         assert_eq!(
-            get_chat_default_currency(&mut con, chat_id).unwrap(),
+            get_chat_default_currency(&mut con, chat_id).await.unwrap(),
             second_currency.to_string()
         );
 
-        assert!(delete_chat_settings(&mut con, chat_id).is_ok());
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
     }
 
-    #[test]
-    fn test_set_get_chat_currency_conversion() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_currency_conversion() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "12345678902";
         let currency_conversion = true;
 
-        assert!(!is_exists_chat_currency_conversion(&mut con, chat_id).unwrap());
-        assert!(set_chat_currency_conversion(&mut con, chat_id, currency_conversion).is_ok());
+        assert!(!is_exists_chat_currency_conversion(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(
+            set_chat_currency_conversion(&mut con, chat_id, currency_conversion)
+                .await
+                .is_ok()
+        );
         assert_eq!(
-            get_chat_currency_conversion(&mut con, chat_id).unwrap(),
+            get_chat_currency_conversion(&mut con, chat_id)
+                .await
+                .unwrap(),
             currency_conversion
         );
-        assert!(is_exists_chat_currency_conversion(&mut con, chat_id).unwrap());
+        assert!(is_exists_chat_currency_conversion(&mut con, chat_id)
+            .await
+            .unwrap());
 
         let second_currency_conversion = false;
         assert!(
-            set_chat_currency_conversion(&mut con, chat_id, second_currency_conversion).is_ok()
+            set_chat_currency_conversion(&mut con, chat_id, second_currency_conversion)
+                .await
+                .is_ok()
         );
         assert_eq!(
-            get_chat_currency_conversion(&mut con, chat_id).unwrap(),
+            get_chat_currency_conversion(&mut con, chat_id)
+                .await
+                .unwrap(),
             second_currency_conversion
         );
 
-        assert!(delete_chat_settings(&mut con, chat_id).is_ok());
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
     }
 
-    #[test]
-    fn test_set_get_chat_erase_messages() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_erase_messages() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let chat_id = "12345678903";
         let erase_messages = true;
 
-        assert!(!is_exists_chat_erase_messages(&mut con, chat_id).unwrap());
-        assert!(set_chat_erase_messages(&mut con, chat_id, erase_messages).is_ok());
+        assert!(!is_exists_chat_erase_messages(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_erase_messages(&mut con, chat_id, erase_messages)
+            .await
+            .is_ok());
         assert_eq!(
-            get_chat_erase_messages(&mut con, chat_id).unwrap(),
+            get_chat_erase_messages(&mut con, chat_id).await.unwrap(),
             erase_messages
         );
-        assert!(is_exists_chat_erase_messages(&mut con, chat_id).unwrap());
+        assert!(is_exists_chat_erase_messages(&mut con, chat_id)
+            .await
+            .unwrap());
 
         let second_erase_messages = false;
-        assert!(set_chat_erase_messages(&mut con, chat_id, second_erase_messages).is_ok());
+        assert!(
+            set_chat_erase_messages(&mut con, chat_id, second_erase_messages)
+                .await
+                .is_ok()
+        );
         assert_eq!(
-            get_chat_erase_messages(&mut con, chat_id).unwrap(),
+            get_chat_erase_messages(&mut con, chat_id).await.unwrap(),
             second_erase_messages
         );
 
-        assert!(delete_chat_settings(&mut con, chat_id).is_ok());
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_monthly_archive() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678904";
+
+        assert!(!is_exists_chat_monthly_archive(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_monthly_archive(&mut con, chat_id, true)
+            .await
+            .is_ok());
+        assert!(get_chat_monthly_archive(&mut con, chat_id).await.unwrap());
+        assert!(is_exists_chat_monthly_archive(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(set_chat_monthly_archive(&mut con, chat_id, false)
+            .await
+            .is_ok());
+        assert!(!get_chat_monthly_archive(&mut con, chat_id).await.unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_admin_only() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678912";
+
+        assert!(!is_exists_chat_admin_only(&mut con, chat_id).await.unwrap());
+        assert!(set_chat_admin_only(&mut con, chat_id, true).await.is_ok());
+        assert!(get_chat_admin_only(&mut con, chat_id).await.unwrap());
+        assert!(is_exists_chat_admin_only(&mut con, chat_id).await.unwrap());
+
+        assert!(set_chat_admin_only(&mut con, chat_id, false).await.is_ok());
+        assert!(!get_chat_admin_only(&mut con, chat_id).await.unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_pairwise_debts() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678914";
+
+        assert!(!is_exists_chat_pairwise_debts(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_pairwise_debts(&mut con, chat_id, true)
+            .await
+            .is_ok());
+        assert!(get_chat_pairwise_debts(&mut con, chat_id).await.unwrap());
+        assert!(is_exists_chat_pairwise_debts(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(set_chat_pairwise_debts(&mut con, chat_id, false)
+            .await
+            .is_ok());
+        assert!(!get_chat_pairwise_debts(&mut con, chat_id).await.unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_passive_mode() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678919";
+
+        assert!(!is_exists_chat_passive_mode(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_passive_mode(&mut con, chat_id, true).await.is_ok());
+        assert!(get_chat_passive_mode(&mut con, chat_id).await.unwrap());
+        assert!(is_exists_chat_passive_mode(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_dual_entry() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678931";
+
+        assert!(!is_exists_chat_dual_entry(&mut con, chat_id).await.unwrap());
+        assert!(set_chat_dual_entry(&mut con, chat_id, true).await.is_ok());
+        assert!(get_chat_dual_entry(&mut con, chat_id).await.unwrap());
+        assert!(is_exists_chat_dual_entry(&mut con, chat_id).await.unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_strict_members() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678934";
+
+        assert!(!is_exists_chat_strict_members(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_strict_members(&mut con, chat_id, true)
+            .await
+            .is_ok());
+        assert!(get_chat_strict_members(&mut con, chat_id).await.unwrap());
+        assert!(is_exists_chat_strict_members(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_erase_user_messages() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678932";
+
+        assert!(!is_exists_chat_erase_user_messages(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_erase_user_messages(&mut con, chat_id, true)
+            .await
+            .is_ok());
+        assert!(get_chat_erase_user_messages(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(is_exists_chat_erase_user_messages(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_verbosity() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678915";
+
+        assert!(!is_exists_chat_verbosity(&mut con, chat_id).await.unwrap());
+        assert!(set_chat_verbosity(&mut con, chat_id, "silent")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_verbosity(&mut con, chat_id).await.unwrap(),
+            "silent"
+        );
+        assert!(is_exists_chat_verbosity(&mut con, chat_id).await.unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_rounding() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678916";
+
+        assert!(!is_exists_chat_rounding(&mut con, chat_id).await.unwrap());
+        assert!(set_chat_rounding(&mut con, chat_id, "roundrobin")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_rounding(&mut con, chat_id).await.unwrap(),
+            "roundrobin"
+        );
+        assert!(is_exists_chat_rounding(&mut con, chat_id).await.unwrap());
+
+        assert_eq!(
+            incr_chat_rounding_offset(&mut con, chat_id).await.unwrap(),
+            0
+        );
+        assert_eq!(
+            incr_chat_rounding_offset(&mut con, chat_id).await.unwrap(),
+            1
+        );
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_week_start() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678921";
+
+        assert!(!is_exists_chat_week_start(&mut con, chat_id).await.unwrap());
+        assert!(set_chat_week_start(&mut con, chat_id, "sunday")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_week_start(&mut con, chat_id).await.unwrap(),
+            "sunday"
+        );
+        assert!(is_exists_chat_week_start(&mut con, chat_id).await.unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_allowed_currencies() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678917";
+
+        assert!(!is_exists_chat_allowed_currencies(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_allowed_currencies(&mut con, chat_id, "SGD,USD")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_allowed_currencies(&mut con, chat_id)
+                .await
+                .unwrap(),
+            "SGD,USD"
+        );
+        assert!(is_exists_chat_allowed_currencies(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_chat_treasurer() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678913";
+        let treasurer = "alice";
+
+        assert!(!is_exists_chat_treasurer(&mut con, chat_id).await.unwrap());
+        assert!(set_chat_treasurer(&mut con, chat_id, treasurer)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_treasurer(&mut con, chat_id).await.unwrap(),
+            treasurer
+        );
+        assert!(is_exists_chat_treasurer(&mut con, chat_id).await.unwrap());
+
+        assert!(delete_chat_treasurer(&mut con, chat_id).await.is_ok());
+        assert!(!is_exists_chat_treasurer(&mut con, chat_id).await.unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_chat_weekly_digest() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678905";
+        let time = "18:00";
+
+        assert!(!is_exists_chat_weekly_digest(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_weekly_digest(&mut con, chat_id, time)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_weekly_digest(&mut con, chat_id).await.unwrap(),
+            time
+        );
+        assert!(is_exists_chat_weekly_digest(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_weekly_digest(&mut con, chat_id).await.is_ok());
+        assert!(!is_exists_chat_weekly_digest(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_chat_approval_threshold() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678906";
+        let threshold = 100.5;
+
+        assert!(!is_exists_chat_approval_threshold(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_approval_threshold(&mut con, chat_id, threshold)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_approval_threshold(&mut con, chat_id)
+                .await
+                .unwrap(),
+            threshold
+        );
+        assert!(is_exists_chat_approval_threshold(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_approval_threshold(&mut con, chat_id)
+            .await
+            .is_ok());
+        assert!(!is_exists_chat_approval_threshold(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_language() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678907";
+        let language = "zh";
+
+        assert!(!is_exists_chat_language(&mut con, chat_id).await.unwrap());
+        assert!(set_chat_language(&mut con, chat_id, language).await.is_ok());
+        assert_eq!(
+            get_chat_language(&mut con, chat_id).await.unwrap(),
+            language
+        );
+        assert!(is_exists_chat_language(&mut con, chat_id).await.unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_number_format() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678908";
+        let number_format = "1.234,56";
+
+        assert!(!is_exists_chat_number_format(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_number_format(&mut con, chat_id, number_format)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_number_format(&mut con, chat_id).await.unwrap(),
+            number_format
+        );
+        assert!(is_exists_chat_number_format(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_chat_currency_format() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678918";
+        let currency_format = "S$1,234.30";
+
+        assert!(!is_exists_chat_currency_format(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_currency_format(&mut con, chat_id, currency_format)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_currency_format(&mut con, chat_id).await.unwrap(),
+            currency_format
+        );
+        assert!(is_exists_chat_currency_format(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_chat_debt_reminder() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678910";
+        let days = 3;
+
+        assert!(!is_exists_chat_debt_reminder(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_debt_reminder(&mut con, chat_id, days)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_debt_reminder(&mut con, chat_id).await.unwrap(),
+            days
+        );
+        assert!(is_exists_chat_debt_reminder(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_debt_reminder(&mut con, chat_id).await.is_ok());
+        assert!(!is_exists_chat_debt_reminder(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_chat_data_retention() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678920";
+        let months = 6;
+
+        assert!(!is_exists_chat_data_retention(&mut con, chat_id)
+            .await
+            .unwrap());
+        assert!(set_chat_data_retention(&mut con, chat_id, months)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_data_retention(&mut con, chat_id).await.unwrap(),
+            months
+        );
+        assert!(is_exists_chat_data_retention(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_data_retention(&mut con, chat_id).await.is_ok());
+        assert!(!is_exists_chat_data_retention(&mut con, chat_id)
+            .await
+            .unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_set_chat_settings() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "12345678911";
+        set_chat_time_zone(&mut con, chat_id, "UTC").await.unwrap();
+        let settings = vec![
+            (SETTING_DEFAULT_CURRENCY.to_string(), "SGD".to_string()),
+            (SETTING_LANGUAGE.to_string(), "zh".to_string()),
+        ];
+
+        // Replaces existing settings
+        assert!(set_chat_settings(&mut con, chat_id, &settings)
+            .await
+            .is_ok());
+        let mut result = get_chat_settings(&mut con, chat_id).await.unwrap();
+        result.sort();
+        assert_eq!(result, settings);
+        assert!(!is_exists_chat_time_zone(&mut con, chat_id).await.unwrap());
+
+        assert!(delete_chat_settings(&mut con, chat_id).await.is_ok());
+        assert!(get_chat_settings(&mut con, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
     }
 }
@@ -1,4 +1,9 @@
-use redis::{Client, Commands, Connection, RedisError, RedisResult};
+use std::sync::{Arc, RwLock};
+
+use redis::{
+    aio::{ConnectionLike, MultiplexedConnection},
+    AsyncCommands, Client, Cmd, Pipeline, RedisError, RedisFuture, RedisResult, Value,
+};
 
 #[derive(thiserror::Error, Debug, PartialEq)]
 pub enum DBError {
@@ -6,6 +11,8 @@ pub enum DBError {
     RedisClientError(redis::RedisError),
     #[error("Redis connection error: {0}")]
     RedisConnectionError(redis::RedisError),
+    #[error("Redis URL not set")]
+    MissingUrl(),
 }
 
 // Implement the From trait to convert from DBError to RedisError
@@ -18,15 +25,19 @@ impl From<DBError> for RedisError {
             DBError::RedisConnectionError(_) => {
                 RedisError::from((redis::ErrorKind::IoError, "Redis connection error"))
             }
+            DBError::MissingUrl() => {
+                RedisError::from((redis::ErrorKind::ClientError, "Redis URL not set"))
+            }
         }
     }
 }
 
-pub fn connect() -> Result<Connection, DBError> {
+// Opens a new multiplexed connection to Redis
+async fn open_connection() -> Result<MultiplexedConnection, DBError> {
     dotenv::dotenv().ok();
-    let url = std::env::var("REDIS_URL").expect("REDIS_URL token not set");
+    let url = std::env::var("REDIS_URL").map_err(|_| DBError::MissingUrl())?;
     match Client::open(url) {
-        Ok(client) => match client.get_connection() {
+        Ok(client) => match client.get_multiplexed_tokio_connection().await {
             Ok(con) => Ok(con),
             Err(e) => Err(DBError::RedisConnectionError(e)),
         },
@@ -34,25 +45,193 @@ pub fn connect() -> Result<Connection, DBError> {
     }
 }
 
+// Checks if an error shows that the connection to Redis was lost
+fn is_connection_lost(error: &RedisError) -> bool {
+    error.is_io_error() || error.is_connection_dropped() || error.is_connection_refusal()
+}
+
+/* Redis is a handle to the connection to Redis, shared across all requests.
+ * It is created once, and passed to every request that reads or writes data.
+ * The connection is opened on first use, and cloning the handle is cheap, sharing the same connection.
+ */
+#[derive(Clone, Default)]
+pub struct Redis {
+    connection: Arc<RwLock<Option<MultiplexedConnection>>>,
+}
+
+impl Redis {
+    pub fn new() -> Redis {
+        Redis::default()
+    }
+
+    // Returns a connection over the shared connection, opening it if there is none
+    pub async fn connect(&self) -> Result<RedisConnection, DBError> {
+        Ok(RedisConnection {
+            redis: self.clone(),
+            con: self.get().await?,
+        })
+    }
+
+    // Returns a handle to the shared connection, opening it if there is none
+    async fn get(&self) -> Result<MultiplexedConnection, DBError> {
+        if let Some(con) = self.read().as_ref() {
+            return Ok(con.clone());
+        }
+
+        // Opened without holding the lock, keeping whichever connection is stored first
+        let con = open_connection().await?;
+        let mut connection = self.write();
+        Ok(connection.get_or_insert(con).clone())
+    }
+
+    // Clears the shared connection, so that the next attempt opens a new one
+    fn reset(&self) {
+        *self.write() = None;
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Option<MultiplexedConnection>> {
+        self.connection
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Option<MultiplexedConnection>> {
+        self.connection
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/* RedisConnection is a connection to Redis for a single request, over the shared connection.
+ * If the shared connection was lost, such as after Redis restarts, it is replaced by a new one for all requests.
+ * The command is only sent again if it never reached Redis, as most writes are not safe to apply twice,
+ * otherwise the error is returned, and the following commands use the new connection.
+ */
+#[derive(Clone)]
+pub struct RedisConnection {
+    redis: Redis,
+    con: MultiplexedConnection,
+}
+
+impl RedisConnection {
+    // Replaces the connection with a new one if the error shows that it was lost
+    // Returns whether the command can be sent again, only if it was refused without reaching Redis
+    async fn reconnect(&mut self, error: &RedisError) -> bool {
+        if !is_connection_lost(error) {
+            return false;
+        }
+
+        log::warn!(
+            "Redis Connection - Connection lost, reconnecting: {}",
+            error
+        );
+        self.redis.reset();
+        match self.redis.get().await {
+            Ok(con) => self.con = con,
+            Err(err) => {
+                log::error!("Redis Connection - Failed to reconnect: {}", err);
+                return false;
+            }
+        }
+        error.is_connection_refusal()
+    }
+}
+
+impl ConnectionLike for RedisConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        Box::pin(async move {
+            match self.con.req_packed_command(cmd).await {
+                Err(error) if self.reconnect(&error).await => {
+                    self.con.req_packed_command(cmd).await
+                }
+                result => result,
+            }
+        })
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        Box::pin(async move {
+            match self.con.req_packed_commands(cmd, offset, count).await {
+                Err(error) if self.reconnect(&error).await => {
+                    self.con.req_packed_commands(cmd, offset, count).await
+                }
+                result => result,
+            }
+        })
+    }
+
+    fn get_db(&self) -> i64 {
+        self.con.get_db()
+    }
+}
+
 // Tests connection to Redis
 // Only used for testing purposes
 #[allow(dead_code)]
-pub fn test_redis_connection() -> RedisResult<bool> {
-    let mut con = connect()?;
-    con.set("my_key", 42)?;
-    let res: i32 = con.get("my_key")?;
-    con.del("my_key")?;
+pub async fn test_redis_connection() -> RedisResult<bool> {
+    let mut con = Redis::new().connect().await?;
+    con.set::<_, _, ()>("my_key", 42).await?;
+    let res: i32 = con.get("my_key").await?;
+    con.del::<_, ()>("my_key").await?;
 
     Ok(res == 42)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::test_redis_connection;
+    use redis::AsyncCommands;
+
+    use super::{test_redis_connection, Redis};
 
     // Tests working connection
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_connection() {
+        assert!(test_redis_connection().await.unwrap());
+    }
+
+    // Tests that the shared connection is reused, and reopened once lost
     #[test]
-    fn test_connection() {
-        assert!(test_redis_connection().unwrap());
+    #[ignore = "requires Redis"]
+    fn test_shared_connection() {
+        let redis = Redis::new();
+        let runtime = || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+        };
+
+        // The connection stops working with the runtime it was opened on
+        let first_runtime = runtime();
+        first_runtime.block_on(async {
+            let mut con = redis.connect().await.unwrap();
+            con.set::<_, _, ()>("shared_connection_key", 1)
+                .await
+                .unwrap();
+            assert!(redis.read().is_some());
+        });
+        drop(first_runtime);
+
+        // The lost connection is replaced, but the command is not sent again, as it may have been applied
+        runtime().block_on(async {
+            let mut con = redis.connect().await.unwrap();
+            assert!(con.get::<_, i32>("shared_connection_key").await.is_err());
+            let value: i32 = con.get("shared_connection_key").await.unwrap();
+            assert_eq!(value, 1);
+
+            // The new connection is shared with the following requests
+            let mut con = redis.clone().connect().await.unwrap();
+            con.del::<_, ()>("shared_connection_key").await.unwrap();
+            assert!(!con
+                .exists::<_, bool>("shared_connection_key")
+                .await
+                .unwrap());
+        });
     }
 }
@@ -0,0 +1,93 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Digest CRUD Operations
+ * Digest represents the weekly summary of balances and spendings posted in a chat.
+ * Digest comprises a set of chats subscribed to the weekly digest,
+ * and the last week a digest was posted for each chat.
+ * Has add, get, and delete operations.
+ */
+
+const DIGEST_SCHEDULE_KEY: &str = "digest_schedule";
+const DIGEST_WEEK_KEY: &str = "digest_week";
+
+// Adds a chat to the weekly digest schedule
+pub async fn add_digest_schedule(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.sadd(DIGEST_SCHEDULE_KEY, chat_id).await
+}
+
+// Gets all chats in the weekly digest schedule
+pub async fn get_digest_schedule(con: &mut RedisConnection) -> RedisResult<Vec<String>> {
+    con.smembers(DIGEST_SCHEDULE_KEY).await
+}
+
+// Removes a chat from the weekly digest schedule
+pub async fn delete_digest_schedule(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.srem(DIGEST_SCHEDULE_KEY, chat_id).await
+}
+
+// Sets the last week a digest was posted for a chat
+pub async fn set_digest_week(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    week: &str,
+) -> RedisResult<()> {
+    con.set(format!("{DIGEST_WEEK_KEY}:{chat_id}"), week).await
+}
+
+// Gets the last week a digest was posted for a chat, if any
+pub async fn get_digest_week(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Option<String>> {
+    con.get(format!("{DIGEST_WEEK_KEY}:{chat_id}")).await
+}
+
+// Deletes the last week a digest was posted for a chat
+// Mainly for testing purposes
+#[allow(dead_code)]
+pub async fn delete_digest_week(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{DIGEST_WEEK_KEY}:{chat_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_delete_digest_schedule() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "digest_123456789";
+        assert!(add_digest_schedule(&mut con, chat_id).await.is_ok());
+        assert!(get_digest_schedule(&mut con)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+
+        assert!(delete_digest_schedule(&mut con, chat_id).await.is_ok());
+        assert!(!get_digest_schedule(&mut con)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_digest_week() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "digest_1234567890";
+        assert_eq!(get_digest_week(&mut con, chat_id).await.unwrap(), None);
+        assert!(set_digest_week(&mut con, chat_id, "2024-W20").await.is_ok());
+        assert_eq!(
+            get_digest_week(&mut con, chat_id).await.unwrap(),
+            Some("2024-W20".to_string())
+        );
+
+        delete_digest_week(&mut con, chat_id).await.unwrap();
+    }
+}
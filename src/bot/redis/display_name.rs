@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Display Name CRUD Operations
+ * Display name represents the name that a chat uses for a user, in place of the username.
+ * Display names are stored as a hash for each chat, from the lowercase username to the name.
+ * Has set, get, and delete operations.
+ */
+
+const DISPLAY_NAME_KEY: &str = "chat_display_name";
+
+// Sets the display name of a user in a chat
+pub async fn set_chat_display_name(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    username: &str,
+    name: &str,
+) -> RedisResult<()> {
+    con.hset(format!("{DISPLAY_NAME_KEY}:{chat_id}"), username, name)
+        .await
+}
+
+// Gets the display names of all users in a chat
+pub async fn get_chat_display_names(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<HashMap<String, String>> {
+    con.hgetall(format!("{DISPLAY_NAME_KEY}:{chat_id}")).await
+}
+
+// Deletes the display name of a user in a chat
+// Returns the number of display names deleted
+pub async fn delete_chat_display_name(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    username: &str,
+) -> RedisResult<usize> {
+    con.hdel(format!("{DISPLAY_NAME_KEY}:{chat_id}"), username)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_chat_display_name() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "display_name_123456789";
+        let username = "display_name_user_1";
+        assert!(get_chat_display_names(&mut con, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(set_chat_display_name(&mut con, chat_id, username, "John")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_display_names(&mut con, chat_id).await.unwrap(),
+            HashMap::from([(username.to_string(), "John".to_string())])
+        );
+
+        assert_eq!(
+            delete_chat_display_name(&mut con, chat_id, username)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            delete_chat_display_name(&mut con, chat_id, username)
+                .await
+                .unwrap(),
+            0
+        );
+        assert!(get_chat_display_names(&mut con, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Dispute CRUD Operations
+ * Dispute represents a payment that someone in its split has flagged as wrong.
+ * Dispute comprises a hash of payment IDs to the username who disputed them, for each chat.
+ * A payment only has one open dispute at a time, until it is edited or dismissed.
+ * Has add, get, and delete operations.
+ */
+
+const DISPUTE_KEY: &str = "dispute";
+
+// Adds a dispute of a payment in a chat
+// Returns false if the payment is already disputed, such that the first dispute stays open
+pub async fn add_dispute(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    payment_id: &str,
+    username: &str,
+) -> RedisResult<bool> {
+    con.hset_nx(format!("{DISPUTE_KEY}:{chat_id}"), payment_id, username)
+        .await
+}
+
+// Gets the disputes of all disputed payments in a chat
+pub async fn get_disputes(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<HashMap<String, String>> {
+    con.hgetall(format!("{DISPUTE_KEY}:{chat_id}")).await
+}
+
+// Deletes the dispute of a payment in a chat
+// Returns false if the payment was not disputed, such that it is only resolved once
+pub async fn delete_dispute(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    payment_id: &str,
+) -> RedisResult<bool> {
+    let deleted: i64 = con
+        .hdel(format!("{DISPUTE_KEY}:{chat_id}"), payment_id)
+        .await?;
+    Ok(deleted > 0)
+}
+
+// Deletes the disputes of all payments in a chat
+pub async fn delete_disputes(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{DISPUTE_KEY}:{chat_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_delete_dispute() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "dispute_123456789";
+        assert!(add_dispute(&mut con, chat_id, "payment_1", "test_user")
+            .await
+            .unwrap());
+        assert!(!add_dispute(&mut con, chat_id, "payment_1", "test_user_2")
+            .await
+            .unwrap());
+        assert!(add_dispute(&mut con, chat_id, "payment_2", "test_user_2")
+            .await
+            .unwrap());
+
+        let disputes = get_disputes(&mut con, chat_id).await.unwrap();
+        assert_eq!(disputes.len(), 2);
+        assert_eq!(disputes.get("payment_1"), Some(&"test_user".to_string()));
+
+        assert!(delete_dispute(&mut con, chat_id, "payment_1")
+            .await
+            .unwrap());
+        assert!(!delete_dispute(&mut con, chat_id, "payment_1")
+            .await
+            .unwrap());
+        let disputes = get_disputes(&mut con, chat_id).await.unwrap();
+        assert_eq!(disputes.len(), 1);
+
+        assert!(delete_disputes(&mut con, chat_id).await.is_ok());
+        assert!(get_disputes(&mut con, chat_id).await.unwrap().is_empty());
+    }
+}
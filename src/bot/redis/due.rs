@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Due CRUD Operations
+ * Due represents the date by which the debts of a payment should be paid back.
+ * Due comprises a hash of payment IDs to their due dates, for each chat.
+ * Due dates are stored as dates in the format %Y-%m-%d, in the time zone of the chat.
+ * Has set, get, and delete operations.
+ */
+
+const DUE_KEY: &str = "due_date";
+
+// Sets the due date of a payment in a chat
+pub async fn set_due_date(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    payment_id: &str,
+    date: &str,
+) -> RedisResult<()> {
+    con.hset(format!("{DUE_KEY}:{chat_id}"), payment_id, date)
+        .await
+}
+
+// Gets the due dates of all payments with one in a chat
+pub async fn get_due_dates(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<HashMap<String, String>> {
+    con.hgetall(format!("{DUE_KEY}:{chat_id}")).await
+}
+
+// Deletes the due date of a payment in a chat
+pub async fn delete_due_date(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    payment_id: &str,
+) -> RedisResult<()> {
+    con.hdel(format!("{DUE_KEY}:{chat_id}"), payment_id).await
+}
+
+// Deletes the due dates of all payments in a chat
+pub async fn delete_due_dates(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{DUE_KEY}:{chat_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_due_date() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "due_123456789";
+        assert!(set_due_date(&mut con, chat_id, "payment_1", "2024-03-12")
+            .await
+            .is_ok());
+        assert!(set_due_date(&mut con, chat_id, "payment_2", "2024-04-01")
+            .await
+            .is_ok());
+
+        let dates = get_due_dates(&mut con, chat_id).await.unwrap();
+        assert_eq!(dates.len(), 2);
+        assert_eq!(dates.get("payment_1"), Some(&"2024-03-12".to_string()));
+
+        assert!(delete_due_date(&mut con, chat_id, "payment_1")
+            .await
+            .is_ok());
+        let dates = get_due_dates(&mut con, chat_id).await.unwrap();
+        assert_eq!(dates.len(), 1);
+        assert_eq!(dates.get("payment_2"), Some(&"2024-04-01".to_string()));
+
+        assert!(delete_due_dates(&mut con, chat_id).await.is_ok());
+        assert!(get_due_dates(&mut con, chat_id).await.unwrap().is_empty());
+    }
+}
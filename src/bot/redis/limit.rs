@@ -0,0 +1,347 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Limit CRUD Operations
+ * Limit represents the monthly spending limit of a user in a chat.
+ * Limit comprises of the amount and currency of the limit for each user,
+ * the spendings of each user for the month, counted in the currency of their limit,
+ * and the users who have already been warned of crossing their limit for the month.
+ * Monthly records expire some time after the month has passed.
+ * Limit also comprises the weekly spending cap of a chat, with its amount and currency,
+ * and the spendings of everyone in the chat for each week, counted in the currency of the cap.
+ * Weekly records expire some time after the week has passed.
+ * Has set, get, add, and delete operations.
+ */
+
+const LIMIT_KEY: &str = "limit";
+const LIMIT_CURRENCY_KEY: &str = "limit_currency";
+const LIMIT_SPENDING_KEY: &str = "limit_spending";
+const LIMIT_WARNED_KEY: &str = "limit_warned";
+const WEEKLY_CAP_KEY: &str = "weekly_cap";
+const WEEKLY_CAP_SPENDING_KEY: &str = "weekly_cap_spending";
+
+// Monthly records are kept for about two months
+const LIMIT_MONTH_TTL: usize = 62 * 24 * 60 * 60;
+// Weekly records are kept for about two weeks
+const LIMIT_WEEK_TTL: usize = 15 * 24 * 60 * 60;
+
+// Sets the spending limit of a user in a chat
+pub async fn set_limit(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    user_id: &str,
+    currency: &str,
+    amount: i64,
+) -> RedisResult<()> {
+    con.hset::<_, _, _, ()>(format!("{LIMIT_KEY}:{chat_id}"), user_id, amount)
+        .await?;
+    con.hset(format!("{LIMIT_CURRENCY_KEY}:{chat_id}"), user_id, currency)
+        .await
+}
+
+// Gets the spending limit of a user in a chat, as its currency and amount, if any
+pub async fn get_limit(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    user_id: &str,
+) -> RedisResult<Option<(String, i64)>> {
+    let amount: Option<i64> = con.hget(format!("{LIMIT_KEY}:{chat_id}"), user_id).await?;
+    let currency: Option<String> = con
+        .hget(format!("{LIMIT_CURRENCY_KEY}:{chat_id}"), user_id)
+        .await?;
+
+    Ok(currency.zip(amount))
+}
+
+// Gets all users with a spending limit in a chat
+pub async fn get_limit_users(con: &mut RedisConnection, chat_id: &str) -> RedisResult<Vec<String>> {
+    con.hkeys(format!("{LIMIT_KEY}:{chat_id}")).await
+}
+
+// Deletes the spending limit of a user in a chat
+pub async fn delete_limit(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    user_id: &str,
+) -> RedisResult<()> {
+    con.hdel::<_, _, ()>(format!("{LIMIT_KEY}:{chat_id}"), user_id)
+        .await?;
+    con.hdel(format!("{LIMIT_CURRENCY_KEY}:{chat_id}"), user_id)
+        .await
+}
+
+// Adds to the spendings of a user for a month, counted towards their limit
+pub async fn add_limit_spending(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    month: &str,
+    user_id: &str,
+    amount: i64,
+) -> RedisResult<()> {
+    let key = format!("{LIMIT_SPENDING_KEY}:{chat_id}:{month}");
+    con.hincr::<_, _, _, ()>(&key, user_id, amount).await?;
+    con.expire(&key, LIMIT_MONTH_TTL).await
+}
+
+// Gets the spendings of a user for a month, counted towards their limit
+pub async fn get_limit_spending(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    month: &str,
+    user_id: &str,
+) -> RedisResult<i64> {
+    let spending: Option<i64> = con
+        .hget(format!("{LIMIT_SPENDING_KEY}:{chat_id}:{month}"), user_id)
+        .await?;
+    Ok(spending.unwrap_or(0))
+}
+
+// Sets whether a user has been warned of crossing their limit for a month
+pub async fn set_limit_warned(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    month: &str,
+    user_id: &str,
+    warned: bool,
+) -> RedisResult<()> {
+    let key = format!("{LIMIT_WARNED_KEY}:{chat_id}:{month}");
+    if warned {
+        con.sadd::<_, _, ()>(&key, user_id).await?;
+        con.expire(&key, LIMIT_MONTH_TTL).await
+    } else {
+        con.srem(&key, user_id).await
+    }
+}
+
+// Checks if a user has been warned of crossing their limit for a month
+pub async fn is_limit_warned(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    month: &str,
+    user_id: &str,
+) -> RedisResult<bool> {
+    con.sismember(format!("{LIMIT_WARNED_KEY}:{chat_id}:{month}"), user_id)
+        .await
+}
+
+// Deletes the monthly records of spending limits of a chat
+#[allow(dead_code)]
+pub async fn delete_limit_month(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    month: &str,
+) -> RedisResult<()> {
+    con.del::<_, ()>(format!("{LIMIT_SPENDING_KEY}:{chat_id}:{month}"))
+        .await?;
+    con.del(format!("{LIMIT_WARNED_KEY}:{chat_id}:{month}"))
+        .await
+}
+
+// Sets the weekly spending cap of a chat
+pub async fn set_weekly_cap(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    currency: &str,
+    amount: i64,
+) -> RedisResult<()> {
+    con.hset_multiple(
+        format!("{WEEKLY_CAP_KEY}:{chat_id}"),
+        &[
+            ("currency", currency.to_string()),
+            ("amount", amount.to_string()),
+        ],
+    )
+    .await
+}
+
+// Gets the weekly spending cap of a chat, as its currency and amount, if any
+pub async fn get_weekly_cap(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Option<(String, i64)>> {
+    let key = format!("{WEEKLY_CAP_KEY}:{chat_id}");
+    let currency: Option<String> = con.hget(&key, "currency").await?;
+    let amount: Option<i64> = con.hget(&key, "amount").await?;
+
+    Ok(currency.zip(amount))
+}
+
+// Deletes the weekly spending cap of a chat
+pub async fn delete_weekly_cap(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{WEEKLY_CAP_KEY}:{chat_id}")).await
+}
+
+// Adds to the spendings of a chat for a week, counted towards its cap
+pub async fn add_weekly_cap_spending(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    week: &str,
+    amount: i64,
+) -> RedisResult<()> {
+    let key = format!("{WEEKLY_CAP_SPENDING_KEY}:{chat_id}:{week}");
+    con.incr::<_, _, ()>(&key, amount).await?;
+    con.expire(&key, LIMIT_WEEK_TTL).await
+}
+
+// Gets the spendings of a chat for a week, counted towards its cap
+pub async fn get_weekly_cap_spending(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    week: &str,
+) -> RedisResult<i64> {
+    let spending: Option<i64> = con
+        .get(format!("{WEEKLY_CAP_SPENDING_KEY}:{chat_id}:{week}"))
+        .await?;
+    Ok(spending.unwrap_or(0))
+}
+
+// Deletes the weekly records of the spending cap of a chat
+#[allow(dead_code)]
+pub async fn delete_weekly_cap_week(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    week: &str,
+) -> RedisResult<()> {
+    con.del(format!("{WEEKLY_CAP_SPENDING_KEY}:{chat_id}:{week}"))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_limit() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "limit_123456789";
+        let user_id = "limit_user";
+        assert_eq!(get_limit(&mut con, chat_id, user_id).await.unwrap(), None);
+        assert!(set_limit(&mut con, chat_id, user_id, "USD", 30000)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_limit(&mut con, chat_id, user_id).await.unwrap(),
+            Some(("USD".to_string(), 30000))
+        );
+        assert_eq!(
+            get_limit_users(&mut con, chat_id).await.unwrap(),
+            vec![user_id.to_string()]
+        );
+
+        assert!(delete_limit(&mut con, chat_id, user_id).await.is_ok());
+        assert_eq!(get_limit(&mut con, chat_id, user_id).await.unwrap(), None);
+        assert!(get_limit_users(&mut con, chat_id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_limit_spending() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "limit_1234567890";
+        let month = "2024-03";
+        let user_id = "limit_user";
+        assert_eq!(
+            get_limit_spending(&mut con, chat_id, month, user_id)
+                .await
+                .unwrap(),
+            0
+        );
+        assert!(add_limit_spending(&mut con, chat_id, month, user_id, 1000)
+            .await
+            .is_ok());
+        assert!(add_limit_spending(&mut con, chat_id, month, user_id, -400)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_limit_spending(&mut con, chat_id, month, user_id)
+                .await
+                .unwrap(),
+            600
+        );
+
+        assert!(!is_limit_warned(&mut con, chat_id, month, user_id)
+            .await
+            .unwrap());
+        assert!(set_limit_warned(&mut con, chat_id, month, user_id, true)
+            .await
+            .is_ok());
+        assert!(is_limit_warned(&mut con, chat_id, month, user_id)
+            .await
+            .unwrap());
+        assert!(set_limit_warned(&mut con, chat_id, month, user_id, false)
+            .await
+            .is_ok());
+        assert!(!is_limit_warned(&mut con, chat_id, month, user_id)
+            .await
+            .unwrap());
+
+        assert!(delete_limit_month(&mut con, chat_id, month).await.is_ok());
+        assert_eq!(
+            get_limit_spending(&mut con, chat_id, month, user_id)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_weekly_cap() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "limit_weekly_123456789";
+        assert_eq!(get_weekly_cap(&mut con, chat_id).await.unwrap(), None);
+        assert!(set_weekly_cap(&mut con, chat_id, "USD", 50000)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_weekly_cap(&mut con, chat_id).await.unwrap(),
+            Some(("USD".to_string(), 50000))
+        );
+
+        assert!(delete_weekly_cap(&mut con, chat_id).await.is_ok());
+        assert_eq!(get_weekly_cap(&mut con, chat_id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_weekly_cap_spending() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "limit_weekly_1234567890";
+        let week = "2024-W11";
+        assert_eq!(
+            get_weekly_cap_spending(&mut con, chat_id, week)
+                .await
+                .unwrap(),
+            0
+        );
+        assert!(add_weekly_cap_spending(&mut con, chat_id, week, 1000)
+            .await
+            .is_ok());
+        assert!(add_weekly_cap_spending(&mut con, chat_id, week, -400)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_weekly_cap_spending(&mut con, chat_id, week)
+                .await
+                .unwrap(),
+            600
+        );
+
+        assert!(delete_weekly_cap_week(&mut con, chat_id, week)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_weekly_cap_spending(&mut con, chat_id, week)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+}
@@ -1,39 +1,249 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use chrono_tz::Tz;
 use redis::RedisError;
+use serde::{Deserialize, Serialize};
+
+use crate::bot::{
+    currency::{CURRENCY_FORMAT_DEFAULT, NUMBER_FORMAT_DEFAULT},
+    i18n::LANGUAGE_DEFAULT,
+    money::{checked_add, checked_sum, MoneyError},
+    period::{find_time_zone, get_month_key, get_week_key, parse_week_start, WeekStart},
+};
 
 use super::{
-    balance::{get_balance, get_balance_exists, set_balance},
+    acknowledgement::{
+        add_acknowledgement, delete_acknowledgement, delete_acknowledgements,
+        get_acknowledgement_message, get_acknowledgements, set_acknowledgement_message,
+    },
+    adjustment::{add_adjustment, delete_adjustment, get_adjustment_exists},
+    admin::{delete_chat_admins, get_chat_admins, is_exists_chat_admins, set_chat_admins},
+    archive::{
+        add_archive, add_archive_payments, add_archive_schedule, delete_archive,
+        delete_archive_month, delete_archive_payments, delete_archive_schedule, get_archive_month,
+        get_archive_payments, get_archive_schedule, get_archives, set_archive_month,
+    },
+    audit::{
+        add_audit_entry, get_audit_entries, get_latest_audit_datetime, AuditEntry, AUDIT_ADDED,
+        AUDIT_EDITED,
+    },
+    balance::{
+        add_balance, delete_balance, get_balance, get_balance_exists, get_balances, move_balance,
+        replace_balances, set_balance,
+    },
+    bot_stats::{count_keys, get_bot_stats, get_used_memory, set_bot_stats, BotStats},
     chat::{
-        add_chat, add_chat_currency, add_chat_payment, add_chat_user_multiple, delete_chat_payment,
-        get_chat_currencies, get_chat_currency_conversion, get_chat_default_currency,
-        get_chat_erase_messages, get_chat_exists, get_chat_payment_exists, get_chat_payments,
-        get_chat_time_zone, get_chat_users, is_exists_chat_currency_conversion,
-        is_exists_chat_default_currency, is_exists_chat_erase_messages, is_exists_chat_time_zone,
-        set_chat_currency_conversion, set_chat_default_currency, set_chat_erase_messages,
-        set_chat_time_zone,
+        add_chat, add_chat_currency, add_chat_payment, add_chat_user_multiple,
+        delete_all_chat_payment, delete_chat, delete_chat_approval_threshold,
+        delete_chat_currencies, delete_chat_currency_usage, delete_chat_data_retention,
+        delete_chat_debt_reminder, delete_chat_payment, delete_chat_settings,
+        delete_chat_treasurer, delete_chat_weekly_digest, get_chat_admin_only,
+        get_chat_allowed_currencies, get_chat_approval_threshold, get_chat_currencies,
+        get_chat_currency_conversion, get_chat_currency_format, get_chat_currency_usage,
+        get_chat_data_retention, get_chat_debt_reminder, get_chat_default_currency,
+        get_chat_dual_entry, get_chat_erase_messages, get_chat_erase_user_messages,
+        get_chat_exists, get_chat_language, get_chat_monthly_archive, get_chat_number_format,
+        get_chat_pairwise_debts, get_chat_passive_mode, get_chat_payment_count,
+        get_chat_payment_exists, get_chat_payments, get_chat_rounding, get_chat_settings,
+        get_chat_strict_members, get_chat_time_zone, get_chat_treasurer, get_chat_users,
+        get_chat_verbosity, get_chat_week_start, get_chat_weekly_digest, get_chats,
+        incr_chat_currency_usage, incr_chat_rounding_offset, is_exists_chat_admin_only,
+        is_exists_chat_allowed_currencies, is_exists_chat_approval_threshold,
+        is_exists_chat_currency_conversion, is_exists_chat_currency_format,
+        is_exists_chat_data_retention, is_exists_chat_debt_reminder,
+        is_exists_chat_default_currency, is_exists_chat_dual_entry, is_exists_chat_erase_messages,
+        is_exists_chat_erase_user_messages, is_exists_chat_language,
+        is_exists_chat_monthly_archive, is_exists_chat_number_format,
+        is_exists_chat_pairwise_debts, is_exists_chat_passive_mode, is_exists_chat_rounding,
+        is_exists_chat_strict_members, is_exists_chat_time_zone, is_exists_chat_treasurer,
+        is_exists_chat_verbosity, is_exists_chat_week_start, is_exists_chat_weekly_digest,
+        replace_chat_user, set_chat_admin_only, set_chat_allowed_currencies,
+        set_chat_approval_threshold, set_chat_currency_conversion, set_chat_currency_format,
+        set_chat_data_retention, set_chat_debt_reminder, set_chat_default_currency,
+        set_chat_dual_entry, set_chat_erase_messages, set_chat_erase_user_messages,
+        set_chat_language, set_chat_monthly_archive, set_chat_number_format,
+        set_chat_pairwise_debts, set_chat_passive_mode, set_chat_rounding, set_chat_settings,
+        set_chat_strict_members, set_chat_time_zone, set_chat_treasurer, set_chat_verbosity,
+        set_chat_week_start, set_chat_weekly_digest, ROUNDING_DEFAULT, VERBOSITY_DEFAULT,
+        WEEK_START_DEFAULT,
+    },
+    connect::{DBError, Redis, RedisConnection},
+    digest::{
+        add_digest_schedule, delete_digest_schedule, get_digest_schedule, get_digest_week,
+        set_digest_week,
+    },
+    display_name::{delete_chat_display_name, get_chat_display_names, set_chat_display_name},
+    dispute::{add_dispute, delete_dispute, delete_disputes, get_disputes},
+    due::{delete_due_date, delete_due_dates, get_due_dates, set_due_date},
+    limit::{
+        add_limit_spending, add_weekly_cap_spending, delete_limit, delete_weekly_cap, get_limit,
+        get_limit_spending, get_limit_users, get_weekly_cap, get_weekly_cap_spending,
+        is_limit_warned, set_limit, set_limit_warned, set_weekly_cap,
+    },
+    migrations::{apply_migration, get_schema_version, set_schema_version, SCHEMA_VERSION_LATEST},
+    onboarding::{delete_chat_onboarding, get_chat_onboarding, set_chat_onboarding},
+    payment::{
+        add_payment, count_payments, delete_payment, delete_payment_rate, get_payment,
+        get_payment_audit, get_payment_note, get_payment_private, get_payment_rate,
+        get_payment_receipt, get_payment_transfer, get_payment_trip, set_payment_created,
+        set_payment_edited, set_payment_note, set_payment_private, set_payment_rate,
+        set_payment_receipt, set_payment_transfer, set_payment_trip, update_payment, Payment,
+        PaymentAudit, PaymentRate,
+    },
+    pending::{
+        add_pending, claim_pending, delete_claimed_pending, delete_pending, get_pending,
+        get_pending_confirmations, get_pending_exists, restore_pending, set_pending_confirmation,
+        PendingPayment,
+    },
+    permission::{
+        delete_bot_can_delete, delete_permission_notified, get_bot_can_delete, set_bot_can_delete,
+        set_permission_notified,
+    },
+    rate::{
+        add_rate_pair, delete_rate_pair, get_last_rate, get_rate, get_rate_pairs, get_rate_ttl,
+        get_rate_used, set_last_rate, set_rate, set_rate_used,
+    },
+    reminder::{
+        add_reminder_schedule, delete_reminder_date, delete_reminder_schedule, get_reminder_date,
+        get_reminder_schedule, set_reminder_date,
     },
-    connect::{connect, DBError},
-    payment::{add_payment, delete_payment, get_payment, update_payment, Payment},
     request::{get_request, set_request},
-    spending::{get_spending, get_spending_exists, set_spending},
+    retention::{
+        add_retention_schedule, delete_retention_date, delete_retention_schedule,
+        get_retention_date, get_retention_schedule, set_retention_date,
+    },
+    settlement::{delete_settlements, get_settlements, set_settlement},
+    spending::{
+        add_spending, delete_spending, get_spending, get_spending_exists, get_spendings,
+        move_spending, replace_spendings, set_spending,
+    },
+    split_group::{delete_chat_split_group, get_chat_split_groups, set_chat_split_group},
+    template::{
+        delete_template, get_template, get_template_exists, get_template_names, set_template,
+        PaymentTemplate,
+    },
+    topic::{
+        delete_topic_settings, get_topic_setting, set_topic_setting,
+        TOPIC_SETTING_DEFAULT_CURRENCY, TOPIC_SETTING_TIME_ZONE,
+    },
+    trash::{add_trash, delete_all_trash, delete_trash, get_trash, get_trash_chats},
+    trip::{
+        add_trip, delete_active_trip, delete_trips, get_active_trip, get_trip_ledger, get_trips,
+        set_active_trip, Trip,
+    },
+    tutorial::get_tutorial_ledger,
     user::{
-        add_user, get_preferred_username, get_user_chats, get_user_exists, set_preferred_username,
-        update_user_chats,
+        add_user, add_user_private_chat, delete_preferred_username, delete_user,
+        delete_user_payment_link, delete_user_private_chat, get_preferred_username,
+        get_preferred_usernames, get_user_alias, get_user_chats, get_user_exists, get_user_is_init,
+        get_user_payment_links, get_username, initialize_user, is_user_private_chat,
+        set_preferred_username, set_user_alias, set_user_payment_link, update_user_chats,
+        update_username,
     },
+    version::{add_version, delete_versions, get_versions, PaymentVersion},
     CURRENCY_CODE_DEFAULT,
 };
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct UserBalance {
     pub username: String,
     pub currency: String,
     pub balance: i64,
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpendingLimit {
+    pub username: String,
+    pub currency: String,
+    pub limit: i64,
+    pub spent: i64,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct WeeklyCap {
+    pub currency: String,
+    pub cap: i64,
+    pub spent: i64,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct UserPayment {
     pub chat_id: String,
     pub payment_id: String,
     pub payment: Payment,
+    pub rate: Option<PaymentRate>,
+    pub audit: PaymentAudit,
+    pub is_private: bool,
+    pub is_transfer: bool,
+    pub is_settled: bool,
+    pub is_disputed: bool,
+}
+
+// TrashEntry contains a payment deleted from a chat, and when it was deleted
+#[derive(Debug, PartialEq)]
+pub struct TrashEntry {
+    pub payment_id: String,
+    pub payment: Payment,
+    pub is_private: bool,
+    pub deleted: String,
+}
+
+// LedgerPayment contains a payment of a ledger, replayed when recalculating its balances
+#[derive(Debug, PartialEq, Clone)]
+pub struct LedgerPayment {
+    pub payment: Payment,
+    pub adjustment: bool,
+    pub archived: bool,
+    pub transfer: bool,
+}
+
+// Version of the chat backup format, bumped whenever the format changes
+pub const BACKUP_VERSION: u32 = 1;
+
+// LedgerBackup contains all records of a ledger, being a chat or a trip
+// Balances and spendings are keyed by the user key, not the username
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct LedgerBackup {
+    pub users: Vec<String>,
+    pub currencies: Vec<String>,
+    pub balances: Vec<UserBalance>,
+    pub spendings: Vec<UserBalance>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct PaymentBackup {
+    pub payment: Payment,
+    pub rate: Option<PaymentRate>,
+    pub trip: Option<String>,
+    pub adjustment: bool,
+    #[serde(default)]
+    pub is_private: bool,
+    #[serde(default)]
+    pub is_transfer: bool,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub receipt: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct TripBackup {
+    pub name: String,
+    pub ledger: LedgerBackup,
+}
+
+// ChatBackup contains all records of a chat, used to move a chat between bot instances
+// Users are pairs of the user key and the preferred username
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ChatBackup {
+    pub version: u32,
+    pub users: Vec<(String, String)>,
+    pub settings: Vec<(String, String)>,
+    pub ledger: LedgerBackup,
+    pub trips: Vec<TripBackup>,
+    pub active_trip: Option<String>,
+    pub payments: Vec<PaymentBackup>,
 }
 
 #[derive(thiserror::Error, Debug, PartialEq)]
@@ -48,8 +258,8 @@ pub enum CrudError {
     NoSuchPaymentError(),
     #[error("Spending computed to be negative")]
     NegativeSpendingError(),
-    #[error("Request limit exceeded")]
-    RequestLimitExceededError(),
+    #[error("{0}")]
+    MoneyError(MoneyError),
 }
 
 // Implement the From trait to convert from RedisError to CrudError
@@ -66,6 +276,13 @@ impl From<DBError> for CrudError {
     }
 }
 
+// Implement the From trait to convert from MoneyError to CrudError
+impl From<MoneyError> for CrudError {
+    fn from(money_error: MoneyError) -> CrudError {
+        CrudError::MoneyError(money_error)
+    }
+}
+
 /* Redis Manager
  * Manager represents a module that manages all database operations.
  * No external package should call any of the database operations directly,
@@ -74,25 +291,154 @@ impl From<DBError> for CrudError {
  */
 
 /* Checks if a user exists, and if not, adds them.
- * If the user exists, ensures that chats are updated. Inits user if not init.
+ * If the user exists, ensures that chats are updated.
+ * If the user ID is provided, the user is keyed by it, and the username is kept as an alias.
  * Called whenever a new payment is added, and all relevant users are updated with this.
  */
-pub fn update_user(username: &str, chat_id: &str, user_id: Option<&str>) -> Result<(), CrudError> {
-    let mut con = connect()?;
+pub async fn update_user(
+    redis: &Redis,
+    username: &str,
+    chat_id: &str,
+    user_id: Option<&str>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-    let user_key = username.to_lowercase();
+    let user_key = match user_id {
+        Some(user_id) => {
+            identify_user(&mut con, username, user_id).await?;
+            user_id.to_string()
+        }
+        None => get_user_key(&mut con, username).await?,
+    };
 
     // Adds user if not exists
-    if !get_user_exists(&mut con, &user_key)? {
-        add_user(&mut con, &user_key, chat_id, user_id)?;
-        set_preferred_username(&mut con, username, &user_key)?;
+    if !get_user_exists(&mut con, &user_key).await? {
+        add_user(&mut con, &user_key, chat_id).await?;
+        set_preferred_username(&mut con, username, &user_key).await?;
     }
 
     // Adds chat to user list if not already added
-    let current_chats = get_user_chats(&mut con, &user_key)?;
+    let current_chats = get_user_chats(&mut con, &user_key).await?;
     if !current_chats.contains(&chat_id.to_string()) {
-        update_user_chats(&mut con, &user_key, chat_id)?;
+        update_user_chats(&mut con, &user_key, chat_id).await?;
+    }
+
+    Ok(())
+}
+
+// Gets the key of a user from their username
+// The key is the Telegram user ID if known, else the lowercase username
+async fn get_user_key(con: &mut RedisConnection, username: &str) -> Result<String, CrudError> {
+    let username = username.to_lowercase();
+    match get_user_alias(con, &username).await? {
+        Some(user_key) => Ok(user_key),
+        None => Ok(username),
+    }
+}
+
+// Gets the current username of a user, from any username they have used
+// Falls back to the given username if the user has not been identified by ID
+async fn get_current_username(
+    con: &mut RedisConnection,
+    username: &str,
+) -> Result<String, CrudError> {
+    if get_user_alias(con, &username.to_lowercase())
+        .await?
+        .is_none()
+    {
+        return Ok(username.to_string());
+    }
+
+    let user_key = get_user_key(con, username).await?;
+    match get_preferred_username(con, &user_key).await {
+        Ok(current_username) => Ok(current_username),
+        Err(_) => Ok(username.to_string()),
     }
+}
+
+// Links a username to a Telegram user ID, and updates the username of the ID if changed
+// Records kept under the username before the ID was known are moved to the ID
+async fn identify_user(
+    con: &mut RedisConnection,
+    username: &str,
+    user_id: &str,
+) -> Result<(), CrudError> {
+    let alias = username.to_lowercase();
+    let current_key = get_user_alias(con, &alias).await?;
+    if current_key.as_deref() != Some(user_id) {
+        if current_key.is_none() && get_user_exists(con, &alias).await? {
+            merge_user(con, &alias, user_id).await?;
+        }
+        set_user_alias(con, &alias, user_id).await?;
+    }
+
+    if !get_user_is_init(con, user_id).await? {
+        initialize_user(con, user_id, username).await?;
+        set_preferred_username(con, username, user_id).await?;
+    } else if get_username(con, user_id).await? != username {
+        update_username(con, user_id, username).await?;
+        set_preferred_username(con, username, user_id).await?;
+    }
+
+    Ok(())
+}
+
+// Moves all chats, balances and spendings of a user from one key to another
+// Includes the ledgers of all trips in the chats of the user
+async fn merge_user(
+    con: &mut RedisConnection,
+    user_key: &str,
+    new_user_key: &str,
+) -> Result<(), CrudError> {
+    let chats = get_user_chats(con, user_key).await?;
+    let new_chats = get_user_chats(con, new_user_key).await?;
+    for chat_id in chats {
+        let mut ledgers = vec![chat_id.clone()];
+        for (trip_id, _) in get_trips(con, &chat_id).await? {
+            ledgers.push(get_trip_ledger(&chat_id, &trip_id));
+        }
+
+        for ledger in ledgers {
+            if !get_chat_users(con, &ledger)
+                .await?
+                .contains(&user_key.to_string())
+            {
+                continue;
+            }
+
+            for currency in get_chat_currencies(con, &ledger).await? {
+                // Amounts are checked before they are moved, then read and moved in one atomic step
+                if get_balance_exists(con, &ledger, user_key, &currency).await? {
+                    let balance = get_balance(con, &ledger, user_key, &currency).await?;
+                    if get_balance_exists(con, &ledger, new_user_key, &currency).await? {
+                        let new_balance =
+                            get_balance(con, &ledger, new_user_key, &currency).await?;
+                        checked_add(balance, new_balance)?;
+                    }
+                    move_balance(con, &ledger, user_key, new_user_key, &currency).await?;
+                }
+
+                if get_spending_exists(con, &ledger, user_key, &currency).await? {
+                    let spending = get_spending(con, &ledger, user_key, &currency).await?;
+                    if get_spending_exists(con, &ledger, new_user_key, &currency).await? {
+                        let new_spending =
+                            get_spending(con, &ledger, new_user_key, &currency).await?;
+                        checked_sum([spending as i64, new_spending as i64])?;
+                    }
+                    move_spending(con, &ledger, user_key, new_user_key, &currency).await?;
+                }
+            }
+
+            replace_chat_user(con, &ledger, user_key, new_user_key).await?;
+        }
+
+        if !new_chats.contains(&chat_id) {
+            update_user_chats(con, new_user_key, &chat_id).await?;
+        }
+    }
+
+    delete_user(con, user_key).await?;
+    delete_preferred_username(con, user_key).await?;
 
     Ok(())
 }
@@ -101,68 +447,117 @@ pub fn update_user(username: &str, chat_id: &str, user_id: Option<&str>) -> Resu
  * If the chat exists, ensures that it is updated with the usernames.
  * Called whenever a new payment is added.
  */
-pub fn update_chat(chat_id: &str, usernames: Vec<String>) -> Result<(), CrudError> {
-    let mut con = connect()?;
+pub async fn update_chat(
+    redis: &Redis,
+    chat_id: &str,
+    usernames: Vec<String>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut user_keys: Vec<String> = Vec::new();
+    for username in &usernames {
+        user_keys.push(get_user_key(&mut con, username).await?);
+    }
 
     // Adds chat if not exists
-    if !get_chat_exists(&mut con, chat_id)? {
-        if !usernames.is_empty() {
-            add_chat(&mut con, chat_id, &usernames[0].to_lowercase())?;
+    if !get_chat_exists(&mut con, chat_id).await? {
+        if !user_keys.is_empty() {
+            add_chat(&mut con, chat_id, &user_keys[0]).await?;
         }
-        init_chat_settings(chat_id)?;
+        init_chat_settings(redis, chat_id).await?;
     }
 
     // Adds all users, automatically checked if added
-    add_chat_user_multiple(
-        &mut con,
-        chat_id,
-        usernames.iter().map(|user| user.to_lowercase()).collect(),
-    )?;
+    add_chat_user_multiple(&mut con, chat_id, user_keys).await?;
 
     Ok(())
 }
 
+/* Gets the preferred usernames of all users in a chat.
+ * Used when picking users involved in a payment.
+ */
+pub async fn get_chat_usernames(redis: &Redis, chat_id: &str) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let users = get_chat_users(&mut con, chat_id).await?;
+    let mut usernames: Vec<String> = Vec::new();
+    for user in &users {
+        usernames.push(get_preferred_username(&mut con, user).await?);
+    }
+
+    Ok(usernames)
+}
+
+/* Gets the preferred username of a user in a chat, if they are in it.
+ * Users are matched by their Telegram user ID, or by their username if the ID is not known yet.
+ */
+pub async fn get_chat_member_username(
+    redis: &Redis,
+    chat_id: &str,
+    user_id: &str,
+    username: Option<&str>,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let users = get_chat_users(&mut con, chat_id).await?;
+    let user_key = if users.contains(&user_id.to_string()) {
+        user_id.to_string()
+    } else {
+        match username {
+            Some(username) => get_user_key(&mut con, username).await?,
+            None => return Ok(None),
+        }
+    };
+
+    if !users.contains(&user_key) {
+        return Ok(None);
+    }
+
+    let username = get_preferred_username(&mut con, &user_key).await?;
+    Ok(Some(username))
+}
+
 /* Initialises chat settings to default.
  */
-fn init_chat_settings(chat_id: &str) -> Result<(), CrudError> {
-    let mut con = connect()?;
+async fn init_chat_settings(redis: &Redis, chat_id: &str) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
     // Set default time zone
     let default_time_zone = "UTC";
-    set_chat_time_zone(&mut con, chat_id, default_time_zone)?;
+    set_chat_time_zone(&mut con, chat_id, default_time_zone).await?;
 
     // Set default currency
-    set_chat_default_currency(&mut con, chat_id, CURRENCY_CODE_DEFAULT)?;
+    set_chat_default_currency(&mut con, chat_id, CURRENCY_CODE_DEFAULT).await?;
 
     // Set default currency conversion
-    set_chat_currency_conversion(&mut con, chat_id, false)?;
+    set_chat_currency_conversion(&mut con, chat_id, false).await?;
 
     // Set default erase messages
-    set_chat_erase_messages(&mut con, chat_id, true)?;
+    set_chat_erase_messages(&mut con, chat_id, true).await?;
 
     Ok(())
 }
 
 /* Sets time zone for a chat.
  */
-pub fn set_time_zone(chat_id: &str, time_zone: &str) -> Result<(), CrudError> {
-    let mut con = connect()?;
+pub async fn set_time_zone(redis: &Redis, chat_id: &str, time_zone: &str) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-    set_chat_time_zone(&mut con, chat_id, time_zone)?;
+    set_chat_time_zone(&mut con, chat_id, time_zone).await?;
     Ok(())
 }
 
 /* Gets time zone for a chat.
  */
-pub fn get_time_zone(chat_id: &str) -> Result<String, CrudError> {
-    let mut con = connect()?;
+pub async fn get_time_zone(redis: &Redis, chat_id: &str) -> Result<String, CrudError> {
+    let mut con = redis.connect().await?;
 
     // By default, return UTC
-    if !is_exists_chat_time_zone(&mut con, chat_id)? {
+    if !is_exists_chat_time_zone(&mut con, chat_id).await? {
         return Ok("UTC".to_string());
     }
 
-    let time_zone = get_chat_time_zone(&mut con, chat_id);
+    let time_zone = get_chat_time_zone(&mut con, chat_id).await;
     match time_zone {
         Ok(time_zone) => Ok(time_zone),
         Err(_) => Ok("UTC".to_string()),
@@ -171,24 +566,28 @@ pub fn get_time_zone(chat_id: &str) -> Result<String, CrudError> {
 
 /* Sets default currency for a chat.
  */
-pub fn set_default_currency(chat_id: &str, currency: &str) -> Result<(), CrudError> {
-    let mut con = connect()?;
+pub async fn set_default_currency(
+    redis: &Redis,
+    chat_id: &str,
+    currency: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-    set_chat_default_currency(&mut con, chat_id, currency)?;
+    set_chat_default_currency(&mut con, chat_id, currency).await?;
     Ok(())
 }
 
 /* Gets default currency for a chat.
  */
-pub fn get_default_currency(chat_id: &str) -> Result<String, CrudError> {
-    let mut con = connect()?;
+pub async fn get_default_currency(redis: &Redis, chat_id: &str) -> Result<String, CrudError> {
+    let mut con = redis.connect().await?;
 
     // By default, return NIL
-    if !is_exists_chat_default_currency(&mut con, chat_id)? {
+    if !is_exists_chat_default_currency(&mut con, chat_id).await? {
         return Ok(CURRENCY_CODE_DEFAULT.to_string());
     }
 
-    let currency = get_chat_default_currency(&mut con, chat_id);
+    let currency = get_chat_default_currency(&mut con, chat_id).await;
     match currency {
         Ok(currency) => Ok(currency),
         Err(_) => Ok(CURRENCY_CODE_DEFAULT.to_string()),
@@ -197,24 +596,28 @@ pub fn get_default_currency(chat_id: &str) -> Result<String, CrudError> {
 
 /* Sets currency conversion for a chat.
  */
-pub fn set_currency_conversion(chat_id: &str, conversion: bool) -> Result<(), CrudError> {
-    let mut con = connect()?;
+pub async fn set_currency_conversion(
+    redis: &Redis,
+    chat_id: &str,
+    conversion: bool,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-    set_chat_currency_conversion(&mut con, chat_id, conversion)?;
+    set_chat_currency_conversion(&mut con, chat_id, conversion).await?;
     Ok(())
 }
 
 /* Gets currency conversion for a chat.
  */
-pub fn get_currency_conversion(chat_id: &str) -> Result<bool, CrudError> {
-    let mut con = connect()?;
+pub async fn get_currency_conversion(redis: &Redis, chat_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
 
     // By default, return false
-    if !is_exists_chat_currency_conversion(&mut con, chat_id)? {
+    if !is_exists_chat_currency_conversion(&mut con, chat_id).await? {
         return Ok(false);
     }
 
-    let conversion = get_chat_currency_conversion(&mut con, chat_id);
+    let conversion = get_chat_currency_conversion(&mut con, chat_id).await;
     match conversion {
         Ok(conversion) => Ok(conversion),
         Err(_) => Ok(false),
@@ -222,1206 +625,7039 @@ pub fn get_currency_conversion(chat_id: &str) -> Result<bool, CrudError> {
 }
 
 /* Sets erase messages for a chat.
+ * Also clears the cached permission of the bot, so that it is checked again from now on.
  */
-pub fn set_erase_messages(chat_id: &str, erase: bool) -> Result<(), CrudError> {
-    let mut con = connect()?;
+pub async fn set_erase_messages(
+    redis: &Redis,
+    chat_id: &str,
+    erase: bool,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-    set_chat_erase_messages(&mut con, chat_id, erase)?;
+    set_chat_erase_messages(&mut con, chat_id, erase).await?;
+    delete_bot_can_delete(&mut con, chat_id).await?;
     Ok(())
 }
 
 /* Gets erase messages for a chat.
  */
-pub fn get_erase_messages(chat_id: &str) -> Result<bool, CrudError> {
-    let mut con = connect()?;
+pub async fn get_erase_messages(redis: &Redis, chat_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
 
     // By default, return true
-    if !is_exists_chat_erase_messages(&mut con, chat_id)? {
+    if !is_exists_chat_erase_messages(&mut con, chat_id).await? {
         return Ok(true);
     }
 
-    let erase = get_chat_erase_messages(&mut con, chat_id);
+    let erase = get_chat_erase_messages(&mut con, chat_id).await;
     match erase {
         Ok(erase) => Ok(erase),
         Err(_) => Ok(true),
     }
 }
 
-/* Gets all valid currencies for a chat.
- * Valid currencies are currencies with some payments.
+/* Sets monthly archive for a chat.
+ * Also adds or removes the chat from the monthly archive schedule.
+ * When enabled, the last month archived is reset, so archival starts from the next month.
  */
-pub fn get_valid_chat_currencies(chat_id: &str) -> Result<Vec<String>, CrudError> {
-    let mut con = connect()?;
-
-    // Retrieve all currencies
-    let currencies = get_chat_currencies(&mut con, chat_id)?;
+pub async fn set_monthly_archive(
+    redis: &Redis,
+    chat_id: &str,
+    archive: bool,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_chat_monthly_archive(&mut con, chat_id, archive).await?;
+    if archive {
+        delete_archive_month(&mut con, chat_id).await?;
+        add_archive_schedule(&mut con, chat_id).await?;
+    } else {
+        delete_archive_schedule(&mut con, chat_id).await?;
+    }
+    Ok(())
+}
 
-    // Retrieve all users
-    let users = get_chat_users(&mut con, chat_id)?;
+/* Gets monthly archive for a chat.
+ */
+pub async fn get_monthly_archive(redis: &Redis, chat_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
 
-    // For each currency, check that there is at least one spending amongst all users
-    let mut valid_currencies: Vec<String> = Vec::new();
-    for currency in &currencies {
-        for user in &users {
-            if get_spending_exists(&mut con, chat_id, user, &currency)?
-                && get_spending(&mut con, chat_id, user, &currency)? > 0
-            {
-                valid_currencies.push(currency.to_string());
-                break;
-            }
-        }
+    // By default, return false
+    if !is_exists_chat_monthly_archive(&mut con, chat_id).await? {
+        return Ok(false);
     }
 
-    Ok(valid_currencies)
+    let archive = get_chat_monthly_archive(&mut con, chat_id).await;
+    match archive {
+        Ok(archive) => Ok(archive),
+        Err(_) => Ok(false),
+    }
 }
 
-/* Gets all balances for a chat.
- * Used when updating default currencies for a chat.
+/* Gets all chats with monthly archive enabled.
  */
-pub fn get_chat_balances(chat_id: &str) -> Result<Vec<Vec<UserBalance>>, CrudError> {
-    let mut con = connect()?;
+pub async fn get_monthly_archive_chats(redis: &Redis) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let chats = get_archive_schedule(&mut con).await?;
+    Ok(chats)
+}
 
-    // Retrieve all balances
-    let mut balances: Vec<Vec<UserBalance>> = Vec::new();
-    let users = get_chat_users(&mut con, chat_id)?;
-    let currencies = get_chat_currencies(&mut con, chat_id)?;
+/* Sets admin only for a chat.
+ * Also clears the cached administrators, so that the latest ones are used from now on.
+ */
+pub async fn set_admin_only(
+    redis: &Redis,
+    chat_id: &str,
+    admin_only: bool,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-    let mut curr_index = 0;
-    for currency in &currencies {
-        balances.push(Vec::new());
+    set_chat_admin_only(&mut con, chat_id, admin_only).await?;
+    delete_chat_admins(&mut con, chat_id).await?;
+    Ok(())
+}
 
-        for user in &users {
-            if get_balance_exists(&mut con, chat_id, user, &currency)? {
-                let balance = get_balance(&mut con, chat_id, user, &currency)?;
-                if balance != 0 {
-                    let username = get_preferred_username(&mut con, user)?;
-                    balances[curr_index].push(UserBalance {
-                        username,
-                        currency: currency.to_string(),
-                        balance,
-                    });
-                }
-            }
-        }
+/* Gets admin only for a chat.
+ */
+pub async fn get_admin_only(redis: &Redis, chat_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
 
-        curr_index += 1;
+    // By default, return false
+    if !is_exists_chat_admin_only(&mut con, chat_id).await? {
+        return Ok(false);
     }
 
-    Ok(balances)
+    let admin_only = get_chat_admin_only(&mut con, chat_id).await;
+    match admin_only {
+        Ok(admin_only) => Ok(admin_only),
+        Err(_) => Ok(false),
+    }
 }
 
-/* Gets all balances for a chat for a specific currency.
+/* Sets pairwise debts for a chat.
  */
-pub fn get_chat_balances_currency(
+pub async fn set_pairwise_debts(
+    redis: &Redis,
     chat_id: &str,
-    currency: &str,
-) -> Result<Vec<UserBalance>, CrudError> {
-    let mut con = connect()?;
+    pairwise_debts: bool,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-    // Retrieve all balances
-    let mut balances: Vec<UserBalance> = Vec::new();
-    let users = get_chat_users(&mut con, chat_id)?;
+    set_chat_pairwise_debts(&mut con, chat_id, pairwise_debts).await?;
+    Ok(())
+}
 
-    for user in &users {
-        if get_balance_exists(&mut con, chat_id, user, currency)? {
-            let balance = get_balance(&mut con, chat_id, user, currency)?;
-            if balance != 0 {
-                let username = get_preferred_username(&mut con, user)?;
-                balances.push(UserBalance {
-                    username,
-                    currency: currency.to_string(),
-                    balance,
-                });
-            }
-        }
+/* Gets pairwise debts for a chat.
+ */
+pub async fn get_pairwise_debts(redis: &Redis, chat_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // By default, return false
+    if !is_exists_chat_pairwise_debts(&mut con, chat_id).await? {
+        return Ok(false);
     }
 
-    Ok(balances)
+    let pairwise_debts = get_chat_pairwise_debts(&mut con, chat_id).await;
+    match pairwise_debts {
+        Ok(pairwise_debts) => Ok(pairwise_debts),
+        Err(_) => Ok(false),
+    }
 }
 
-/* Updates balances for a chat based on given change amounts.
- * If balance does not exist, creates it.
- * Returns the updated balances.
+/* Sets passive mode for a chat.
  */
-pub fn update_chat_balances(chat_id: &str, changes: Vec<UserBalance>) -> Result<(), CrudError> {
-    let mut con = connect()?;
+pub async fn set_passive_mode(
+    redis: &Redis,
+    chat_id: &str,
+    passive_mode: bool,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-    // Update balances through changes
-    for change in changes {
-        let username = change.username.to_lowercase();
-        let balance = change.balance;
-        let currency = change.currency;
+    set_chat_passive_mode(&mut con, chat_id, passive_mode).await?;
+    Ok(())
+}
 
-        // Update currency into chat first
-        let currencies = get_chat_currencies(&mut con, chat_id)?;
-        if !currencies.contains(&currency) {
-            add_chat_currency(&mut con, chat_id, &currency)?;
-        }
+/* Gets passive mode for a chat.
+ */
+pub async fn get_passive_mode(redis: &Redis, chat_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
 
-        // Update balance
-        if let Ok(false) = get_balance_exists(&mut con, chat_id, &username, &currency) {
-            set_balance(&mut con, chat_id, &username, &currency, balance)?;
-        } else {
-            let current_balance = get_balance(&mut con, chat_id, &username, &currency)?;
-            let new_balance = current_balance + balance;
-            set_balance(&mut con, chat_id, &username, &currency, new_balance)?;
-        }
+    // By default, return false
+    if !is_exists_chat_passive_mode(&mut con, chat_id).await? {
+        return Ok(false);
     }
 
-    Ok(())
+    let passive_mode = get_chat_passive_mode(&mut con, chat_id).await;
+    match passive_mode {
+        Ok(passive_mode) => Ok(passive_mode),
+        Err(_) => Ok(false),
+    }
 }
 
-/* Adds a payment.
- * Sets a new key-value pair for the payment, and updates the payments list in chat.
- * Called whenever a new payment is added.
+/* Sets whether user messages are erased too for a chat.
  */
-pub fn add_payment_entry(chat_id: &str, payment: &Payment) -> Result<(), CrudError> {
-    let mut con = connect()?;
-
-    // Adds payment
-    let payment_id = add_payment(&mut con, &payment)?;
-
-    // Adds payment to chat
-    add_chat_payment(&mut con, chat_id, &payment_id)?;
+pub async fn set_erase_user_messages(
+    redis: &Redis,
+    chat_id: &str,
+    erase_user_messages: bool,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
+    set_chat_erase_user_messages(&mut con, chat_id, erase_user_messages).await?;
     Ok(())
 }
 
-/* Retrieves all payments for a chat and their details.
- * Called whenever a user views past payments.
+/* Gets whether user messages are erased too for a chat.
  */
-pub fn get_chat_payments_details(chat_id: &str) -> Result<Vec<UserPayment>, CrudError> {
-    let mut con = connect()?;
+pub async fn get_erase_user_messages(redis: &Redis, chat_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
 
-    if let Err(_) = get_chat_payment_exists(&mut con, chat_id) {
-        return Err(CrudError::NoPaymentsError());
+    // By default, return false
+    if !is_exists_chat_erase_user_messages(&mut con, chat_id).await? {
+        return Ok(false);
     }
 
-    let payment_ids = get_chat_payments(&mut con, chat_id)?;
-    let mut payments: Vec<UserPayment> = Vec::new();
-
-    if payment_ids.is_empty() {
-        return Err(CrudError::NoPaymentsError());
+    let erase_user_messages = get_chat_erase_user_messages(&mut con, chat_id).await;
+    match erase_user_messages {
+        Ok(erase_user_messages) => Ok(erase_user_messages),
+        Err(_) => Ok(false),
     }
+}
 
-    for payment_id in payment_ids {
-        let payment = get_payment(&mut con, &payment_id)?;
-        let user_payment = UserPayment {
-            chat_id: chat_id.to_string(),
-            payment_id,
-            payment,
-        };
-        payments.push(user_payment);
-    }
+/* Sets dual entry verification for a chat.
+ */
+pub async fn set_dual_entry(
+    redis: &Redis,
+    chat_id: &str,
+    dual_entry: bool,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-    Ok(payments)
+    set_chat_dual_entry(&mut con, chat_id, dual_entry).await?;
+    Ok(())
 }
 
-/* Retrieves a specific payment entry by ID.
- * Called when a user wants to edit or delete a payment.
+/* Gets dual entry verification for a chat.
  */
-pub fn get_payment_entry(payment_id: &str) -> Result<Payment, CrudError> {
-    let mut con = connect()?;
+pub async fn get_dual_entry(redis: &Redis, chat_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
 
-    let payment = get_payment(&mut con, payment_id);
+    // By default, return false
+    if !is_exists_chat_dual_entry(&mut con, chat_id).await? {
+        return Ok(false);
+    }
 
-    match payment {
-        Err(_) => Err(CrudError::NoSuchPaymentError()),
-        Ok(payment) => Ok(payment),
+    let dual_entry = get_chat_dual_entry(&mut con, chat_id).await;
+    match dual_entry {
+        Ok(dual_entry) => Ok(dual_entry),
+        Err(_) => Ok(false),
     }
 }
 
-/* Updates a payment entry.
- * Called when a user edits payment details.
+/* Sets strict members for a chat.
  */
-pub fn update_payment_entry(
-    payment_id: &str,
-    description: Option<&str>,
-    creditor: Option<&str>,
-    currency: Option<&str>,
-    total: Option<&i64>,
-    debts: Option<Vec<(String, i64)>>,
+pub async fn set_strict_members(
+    redis: &Redis,
+    chat_id: &str,
+    strict_members: bool,
 ) -> Result<(), CrudError> {
-    let mut con = connect()?;
+    let mut con = redis.connect().await?;
 
-    if let Err(_) = get_payment(&mut con, payment_id) {
-        return Err(CrudError::NoSuchPaymentError());
-    }
+    set_chat_strict_members(&mut con, chat_id, strict_members).await?;
+    Ok(())
+}
 
-    // Updates payment
-    update_payment(
-        &mut con,
-        payment_id,
-        description,
-        creditor,
-        currency,
-        total,
-        debts,
-    )?;
+/* Gets strict members for a chat.
+ */
+pub async fn get_strict_members(redis: &Redis, chat_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // By default, return false
+    if !is_exists_chat_strict_members(&mut con, chat_id).await? {
+        return Ok(false);
+    }
+
+    let strict_members = get_chat_strict_members(&mut con, chat_id).await;
+    match strict_members {
+        Ok(strict_members) => Ok(strict_members),
+        Err(_) => Ok(false),
+    }
+}
+
+/* Sets verbosity for a chat.
+ */
+pub async fn set_verbosity(redis: &Redis, chat_id: &str, verbosity: &str) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
+    set_chat_verbosity(&mut con, chat_id, verbosity).await?;
     Ok(())
 }
 
-/* Deletes a payment entry.
- * Removes the main payment entry, and also from the list in chat.
- * Called when a user wants to remove a payment.
+/* Gets verbosity for a chat.
  */
-pub fn delete_payment_entry(chat_id: &str, payment_id: &str) -> Result<(), CrudError> {
-    let mut con = connect()?;
+pub async fn get_verbosity(redis: &Redis, chat_id: &str) -> Result<String, CrudError> {
+    let mut con = redis.connect().await?;
 
-    if let Err(_) = get_payment(&mut con, payment_id) {
-        return Err(CrudError::NoSuchPaymentError());
+    // By default, return the default verbosity
+    if !is_exists_chat_verbosity(&mut con, chat_id).await? {
+        return Ok(VERBOSITY_DEFAULT.to_string());
+    }
+
+    let verbosity = get_chat_verbosity(&mut con, chat_id).await;
+    match verbosity {
+        Ok(verbosity) => Ok(verbosity),
+        Err(_) => Ok(VERBOSITY_DEFAULT.to_string()),
     }
+}
 
-    delete_payment(&mut con, payment_id)?;
-    delete_chat_payment(&mut con, chat_id, payment_id)?;
+/* Sets rounding strategy for a chat.
+ */
+pub async fn set_rounding(redis: &Redis, chat_id: &str, rounding: &str) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
+    set_chat_rounding(&mut con, chat_id, rounding).await?;
     Ok(())
 }
 
-/* Updates the spendings of a chat with new changes.
- * If the spending already exists, simply adds the value to the current spending.
- * Else, it creates a new key and sets the value.
- * Does not add currency. As balances and spendings are always updated together,
- * currency will be added by balances instead.
+/* Gets rounding strategy for a chat.
  */
-pub fn update_chat_spendings(chat_id: &str, spendings: Vec<UserBalance>) -> Result<(), CrudError> {
-    let mut con = connect()?;
-
-    for spending in spendings {
-        let mut amount = spending.balance;
-        let username = &spending.username.to_lowercase();
-        let is_exists = get_spending_exists(&mut con, chat_id, username, &spending.currency)?;
-        if is_exists {
-            let current_spending = get_spending(&mut con, chat_id, username, &spending.currency)?;
-            amount += current_spending as i64;
-        }
+pub async fn get_rounding(redis: &Redis, chat_id: &str) -> Result<String, CrudError> {
+    let mut con = redis.connect().await?;
 
-        if amount < 0 {
-            return Err(CrudError::NegativeSpendingError());
-        } else {
-            set_spending(
-                &mut con,
-                chat_id,
-                username,
-                &spending.currency,
-                amount as u64,
-            )?;
-        }
+    // By default, return the default rounding strategy
+    if !is_exists_chat_rounding(&mut con, chat_id).await? {
+        return Ok(ROUNDING_DEFAULT.to_string());
     }
 
-    Ok(())
+    let rounding = get_chat_rounding(&mut con, chat_id).await;
+    match rounding {
+        Ok(rounding) => Ok(rounding),
+        Err(_) => Ok(ROUNDING_DEFAULT.to_string()),
+    }
 }
 
-/* Retrieves all spendings for a chat for all currencies.
- * Returns a vector of UserBalance by user.
+/* Sets the first day of the week for a chat.
  */
-pub fn retrieve_chat_spendings(chat_id: &str) -> Result<Vec<Vec<UserBalance>>, CrudError> {
-    let mut con = connect()?;
+pub async fn set_week_start(
+    redis: &Redis,
+    chat_id: &str,
+    week_start: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-    let mut spendings: Vec<Vec<UserBalance>> = Vec::new();
-    let users = get_chat_users(&mut con, chat_id)?;
-    let currencies = get_chat_currencies(&mut con, chat_id)?;
+    set_chat_week_start(&mut con, chat_id, week_start).await?;
+    Ok(())
+}
 
-    let mut curr_index = 0;
-    for currency in &currencies {
-        spendings.push(Vec::new());
+/* Gets the first day of the week for a chat.
+ */
+pub async fn get_week_start(redis: &Redis, chat_id: &str) -> Result<String, CrudError> {
+    let mut con = redis.connect().await?;
 
-        for user in &users {
-            if get_spending_exists(&mut con, chat_id, user, &currency)? {
-                let spending = get_spending(&mut con, chat_id, user, &currency)?;
-                if spending != 0 {
-                    let username = get_preferred_username(&mut con, user)?;
-                    spendings[curr_index].push(UserBalance {
-                        username,
-                        currency: currency.to_string(),
-                        balance: spending as i64,
-                    });
-                }
-            }
-        }
+    // By default, weeks start on Monday
+    if !is_exists_chat_week_start(&mut con, chat_id).await? {
+        return Ok(WEEK_START_DEFAULT.to_string());
+    }
 
-        curr_index += 1;
+    let week_start = get_chat_week_start(&mut con, chat_id).await;
+    match week_start {
+        Ok(week_start) => Ok(week_start),
+        Err(_) => Ok(WEEK_START_DEFAULT.to_string()),
     }
+}
+
+/* Gets the next round-robin rounding offset for a chat.
+ * Each call returns the offset after the previous one, so that leftover amounts
+ * go to a different person in each split.
+ */
+pub async fn get_next_rounding_offset(redis: &Redis, chat_id: &str) -> Result<u64, CrudError> {
+    let mut con = redis.connect().await?;
 
-    Ok(spendings)
+    let offset = incr_chat_rounding_offset(&mut con, chat_id).await?;
+    Ok(offset)
 }
 
-/* Retrieves all spendings for a chat for specific currency.
- * Returns a vector of UserBalance by user.
+/* Sets the currencies allowed in a chat.
+ * Takes in the currency codes, where no codes allow all currencies.
  */
-pub fn retrieve_chat_spendings_currency(
+pub async fn set_allowed_currencies(
+    redis: &Redis,
     chat_id: &str,
-    currency: &str,
-) -> Result<Vec<UserBalance>, CrudError> {
-    let mut con = connect()?;
+    currencies: &[String],
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-    let mut spendings: Vec<UserBalance> = Vec::new();
-    let users = get_chat_users(&mut con, chat_id)?;
+    set_chat_allowed_currencies(&mut con, chat_id, &currencies.join(",")).await?;
+    Ok(())
+}
 
-    for user in &users {
-        if get_spending_exists(&mut con, chat_id, user, &currency)? {
-            let spending = get_spending(&mut con, chat_id, user, &currency)?;
-            if spending != 0 {
-                let username = get_preferred_username(&mut con, user)?;
-                spendings.push(UserBalance {
-                    username,
-                    currency: currency.to_string(),
-                    balance: spending as i64,
-                });
-            }
-        }
+/* Gets the currencies allowed in a chat.
+ * Returns no codes if all currencies are allowed.
+ */
+pub async fn get_allowed_currencies(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // By default, all currencies are allowed
+    if !is_exists_chat_allowed_currencies(&mut con, chat_id).await? {
+        return Ok(Vec::new());
     }
 
-    Ok(spendings)
+    let currencies = get_chat_allowed_currencies(&mut con, chat_id).await?;
+    Ok(currencies
+        .split(',')
+        .filter(|code| !code.is_empty())
+        .map(|code| code.to_string())
+        .collect())
 }
 
-/* Checks if a user has exceeded the request limit.
- * Returns a boolean representing this status.
- * Automatically updates the request timestamp if not exceeded.
+/* Sets treasurer for a chat.
+ * Takes in the username of the treasurer, or None to disable it.
  */
-pub fn is_request_limit_exceeded(user_id: &str, time_now: i64) -> Result<bool, CrudError> {
-    let mut con = connect()?;
-
-    let timestamp = get_request(&mut con, user_id);
-    let mut status = false;
+pub async fn set_treasurer(
+    redis: &Redis,
+    chat_id: &str,
+    treasurer: Option<&str>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-    match timestamp {
-        Ok(timestamp) => {
-            if time_now <= timestamp {
-                status = true;
-            }
-        }
-        Err(_) => {
-            // By default, assume okay and proceed with request
-        }
+    match treasurer {
+        Some(treasurer) => set_chat_treasurer(&mut con, chat_id, treasurer).await?,
+        None => delete_chat_treasurer(&mut con, chat_id).await?,
     }
+    Ok(())
+}
 
-    if !status {
-        // Updates request timestamp if not exceeded
-        set_request(&mut con, user_id, time_now)?;
+/* Gets treasurer for a chat.
+ * Returns None if there is no treasurer.
+ */
+pub async fn get_treasurer(redis: &Redis, chat_id: &str) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // By default, no treasurer
+    if !is_exists_chat_treasurer(&mut con, chat_id).await? {
+        return Ok(None);
     }
 
-    Ok(status)
+    let treasurer = get_chat_treasurer(&mut con, chat_id).await;
+    match treasurer {
+        Ok(treasurer) => Ok(Some(treasurer)),
+        Err(_) => Ok(None),
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::bot::redis::{
-        balance::delete_balance,
-        chat::{delete_chat, delete_chat_currencies, delete_chat_settings, get_chat_users},
-        request::delete_request,
-        spending::delete_spending,
-        user::{delete_preferred_username, delete_user, get_preferred_username, get_user_chats},
-    };
+/* Retrieves the cached administrators of a chat, if any.
+ */
+pub async fn get_cached_admins(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<Vec<String>>, CrudError> {
+    let mut con = redis.connect().await?;
 
-    use super::*;
+    if !is_exists_chat_admins(&mut con, chat_id).await? {
+        return Ok(None);
+    }
 
-    #[test]
-    fn test_update_user_add_user() {
-        let mut con = connect().unwrap();
+    let admins = get_chat_admins(&mut con, chat_id).await?;
+    Ok(Some(admins))
+}
 
-        let username = "Manager_Test_User";
-        let user_key = username.to_lowercase();
-        let chat_id = "manager_123456789";
+/* Caches the administrators of a chat, expiring after some time.
+ */
+pub async fn set_cached_admins(
+    redis: &Redis,
+    chat_id: &str,
+    admins: &Vec<String>,
+    ttl: usize,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-        // Checks that user does not exist
-        assert!(!get_user_exists(&mut con, &user_key).unwrap());
+    set_chat_admins(&mut con, chat_id, admins, ttl).await?;
+    Ok(())
+}
 
-        // Adds user
-        assert!(update_user(username, chat_id, None).is_ok());
-        assert_eq!(
-            get_preferred_username(&mut con, &user_key).unwrap(),
-            username
-        );
-        assert_eq!(get_user_chats(&mut con, &user_key).unwrap(), vec![chat_id]);
+/* Gets whether the bot can delete messages in a chat, if it is known.
+ */
+pub async fn get_cached_bot_can_delete(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<bool>, CrudError> {
+    let mut con = redis.connect().await?;
 
-        // Performs again, nothing should happen
-        assert!(update_user(username, chat_id, None).is_ok());
-        assert_eq!(
-            get_preferred_username(&mut con, &user_key).unwrap(),
-            username
-        );
-        assert_eq!(get_user_chats(&mut con, &user_key).unwrap(), vec![chat_id]);
+    let can_delete = get_bot_can_delete(&mut con, chat_id).await?;
+    Ok(can_delete)
+}
 
-        // Deletes user
-        delete_user(&mut con, &user_key).unwrap();
-        delete_preferred_username(&mut con, &user_key).unwrap();
+/* Caches whether the bot can delete messages in a chat, expiring after some time.
+ * Regaining the permission allows the chat to be notified again if it is lost later.
+ */
+pub async fn set_cached_bot_can_delete(
+    redis: &Redis,
+    chat_id: &str,
+    can_delete: bool,
+    ttl: usize,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_bot_can_delete(&mut con, chat_id, can_delete, ttl).await?;
+    if can_delete {
+        delete_permission_notified(&mut con, chat_id).await?;
     }
+    Ok(())
+}
 
-    #[test]
-    fn test_update_user_add_chat() {
-        let mut con = connect().unwrap();
+/* Marks a chat as notified of the bot lacking permissions.
+ * Returns true only the first time, so that the chat is notified once.
+ */
+pub async fn set_bot_permission_notified(redis: &Redis, chat_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
 
-        let username = "Manager_Test_User_0";
-        let user_key = username.to_lowercase();
-        let chat_id = "manager_1234567890";
-        let second_chat_id = "manager_1234567891";
+    let is_first = set_permission_notified(&mut con, chat_id).await?;
+    Ok(is_first)
+}
 
-        // Adds user and chat
-        assert!(update_user(username, chat_id, None).is_ok());
-        assert_eq!(get_user_chats(&mut con, &user_key).unwrap(), vec![chat_id]);
+/* Gets the current step of the setup wizard of a chat, if it is ongoing.
+ */
+pub async fn get_onboarding_step(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
 
-        // Calls again, adds a second chat
-        assert!(update_user(username, second_chat_id, None).is_ok());
-        assert_eq!(
-            get_user_chats(&mut con, &user_key).unwrap(),
-            vec![chat_id, second_chat_id]
-        );
+    let step = get_chat_onboarding(&mut con, chat_id).await?;
+    Ok(step)
+}
 
-        // Calls again, nothing should happen
-        assert!(update_user(username, second_chat_id, None).is_ok());
-        assert_eq!(
-            get_user_chats(&mut con, &user_key).unwrap(),
-            vec![chat_id, second_chat_id]
-        );
+/* Sets the current step of the setup wizard of a chat, expiring after some time.
+ * Takes in None to end the wizard.
+ */
+pub async fn set_onboarding_step(
+    redis: &Redis,
+    chat_id: &str,
+    step: Option<&str>,
+    ttl: usize,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-        // Deletes user
-        delete_user(&mut con, &user_key).unwrap();
-        delete_preferred_username(&mut con, &user_key).unwrap();
+    match step {
+        Some(step) => set_chat_onboarding(&mut con, chat_id, step, ttl).await?,
+        None => delete_chat_onboarding(&mut con, chat_id).await?,
     }
+    Ok(())
+}
 
-    /*
-    #[test]
-    fn test_update_user_init_user() {
-        let mut con = connect().unwrap();
+/* Gets the number of payments in a chat.
+ */
+pub async fn get_payment_count(redis: &Redis, chat_id: &str) -> Result<usize, CrudError> {
+    let mut con = redis.connect().await?;
 
-        let username = "manager_test_user_1";
-        let chat_id = "manager_1234567892";
-        let user_id = "manager_987654321";
+    let count = get_chat_payment_count(&mut con, chat_id).await?;
+    Ok(count)
+}
+
+/* Retrieves the most recently added payment entry of a chat, if any.
+ */
+pub async fn get_latest_payment_entry(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<Payment>, CrudError> {
+    let mut con = redis.connect().await?;
 
-        // Adds user and chat, check that init works properly
-        assert!(update_user(username, chat_id, Some(user_id)).is_ok());
-        assert!(get_user_is_init(&mut con, user_id).unwrap());
+    let payment_id = match get_chat_payments(&mut con, chat_id).await?.first() {
+        Some(payment_id) => payment_id.clone(),
+        None => return Ok(None),
+    };
+    let payment = get_payment(&mut con, &payment_id).await?;
+    Ok(Some(payment))
+}
 
-        // Deletes user temporarily
-        delete_user(&mut con, username).unwrap();
-        delete_user_id(&mut con, user_id).unwrap();
-        delete_preferred_username(&mut con, username).unwrap();
+/* Saves a payment template of a chat, replacing any template of the same name.
+ */
+pub async fn set_payment_template(
+    redis: &Redis,
+    chat_id: &str,
+    template: &PaymentTemplate,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-        // Calls again, adds user again but without ID
-        assert!(update_user(username, chat_id, None).is_ok());
-        assert!(!get_user_is_init(&mut con, user_id).unwrap());
+    set_template(&mut con, chat_id, template).await?;
+    Ok(())
+}
 
-        // Calls again, should init user
-        assert!(update_user(username, chat_id, Some(user_id)).is_ok());
-        assert!(get_user_is_init(&mut con, user_id).unwrap());
+/* Retrieves a payment template of a chat by name, if it exists.
+ */
+pub async fn get_payment_template(
+    redis: &Redis,
+    chat_id: &str,
+    name: &str,
+) -> Result<Option<PaymentTemplate>, CrudError> {
+    let mut con = redis.connect().await?;
 
-        // Deletes user
-        delete_user(&mut con, username).unwrap();
-        delete_user_id(&mut con, user_id).unwrap();
-        delete_preferred_username(&mut con, username).unwrap();
+    if !get_template_exists(&mut con, chat_id, name).await? {
+        return Ok(None);
     }
 
-    #[test]
-    fn test_update_user_update_username() {
-        let mut con = connect().unwrap();
+    let mut template = get_template(&mut con, chat_id, name).await?;
 
-        let username = "manager_test_user_2";
-        let chat_id = "manager_1234567893";
-        let user_id = "manager_987654322";
-        let second_username = "manager_test_user_3";
+    // Uses the current usernames of users, in case they have changed
+    template.creditor = get_current_username(&mut con, &template.creditor).await?;
+    for debt in template.debts.iter_mut() {
+        debt.0 = get_current_username(&mut con, &debt.0).await?;
+    }
 
-        // Adds user and chat, check that user is init
-        assert!(update_user(username, chat_id, Some(user_id)).is_ok());
-        assert!(get_user_is_init(&mut con, user_id).unwrap());
+    Ok(Some(template))
+}
 
-        // Calls again, updates username
-        assert!(update_user(second_username, chat_id, Some(user_id)).is_ok());
-        assert!(get_user_is_init(&mut con, user_id).unwrap());
-        assert!(get_username(&mut con, user_id).unwrap() == second_username);
+/* Retrieves the names of all payment templates of a chat, in alphabetical order.
+ */
+pub async fn get_payment_templates(redis: &Redis, chat_id: &str) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
 
-        // Deletes user
-        delete_user(&mut con, username).unwrap();
-        delete_user(&mut con, second_username).unwrap();
-        delete_user_id(&mut con, user_id).unwrap();
-        delete_preferred_username(&mut con, username).unwrap();
-        delete_preferred_username(&mut con, second_username).unwrap();
-    }
-    */
+    let mut names = get_template_names(&mut con, chat_id).await?;
+    names.sort();
+    Ok(names)
+}
 
-    #[test]
-    fn test_update_chat_add_chat_users() {
-        let mut con = connect().unwrap();
+/* Deletes a payment template of a chat by name.
+ * Returns false if there was no such template.
+ */
+pub async fn delete_payment_template(
+    redis: &Redis,
+    chat_id: &str,
+    name: &str,
+) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
 
-        let chat_id = "manager_1234567894";
-        let mut usernames = vec![
-            "manager_test_user_4".to_string(),
-            "manager_test_user_5".to_string(),
-            "manager_test_user_6".to_string(),
-        ];
-        let more_usernames = vec![
-            "manager_test_user_7".to_string(),
-            "manager_test_user_8".to_string(),
-            "manager_test_user_9".to_string(),
-        ];
+    if !get_template_exists(&mut con, chat_id, name).await? {
+        return Ok(false);
+    }
 
-        // Check that chat does not exist
-        assert!(!get_chat_exists(&mut con, chat_id).unwrap());
+    delete_template(&mut con, chat_id, name).await?;
+    Ok(true)
+}
 
-        // Add chat with first group of usernames
-        assert!(update_chat(chat_id, usernames.clone()).is_ok());
-        assert!(get_chat_exists(&mut con, chat_id).unwrap());
-        assert_eq!(
-            get_chat_users(&mut con, chat_id).unwrap(),
-            vec![
-                "manager_test_user_4".to_string(),
-                "manager_test_user_5".to_string(),
-                "manager_test_user_6".to_string(),
-            ]
-        );
-        assert_eq!(get_time_zone(chat_id).unwrap(), "UTC".to_string());
-        assert_eq!(
-            get_default_currency(chat_id).unwrap(),
-            CURRENCY_CODE_DEFAULT.to_string()
-        );
-        assert_eq!(get_currency_conversion(chat_id).unwrap(), false);
+/* Sets the display name of a user in a chat, used in place of the username.
+ */
+pub async fn set_display_name(
+    redis: &Redis,
+    chat_id: &str,
+    username: &str,
+    name: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
 
-        // Call again, add both groups of usernames
-        usernames.extend(more_usernames.clone());
-        assert!(update_chat(chat_id, usernames.clone()).is_ok());
-        assert!(get_chat_exists(&mut con, chat_id).unwrap());
-        assert_eq!(
-            get_chat_users(&mut con, chat_id).unwrap(),
-            vec![
-                "manager_test_user_4".to_string(),
-                "manager_test_user_5".to_string(),
-                "manager_test_user_6".to_string(),
-                "manager_test_user_7".to_string(),
-                "manager_test_user_8".to_string(),
-                "manager_test_user_9".to_string(),
-            ]
-        );
+    set_chat_display_name(&mut con, chat_id, &username.to_lowercase(), name).await?;
+    Ok(())
+}
 
-        // Call again, nothing should happen
-        assert!(update_chat(chat_id, usernames).is_ok());
-        assert!(get_chat_exists(&mut con, chat_id).unwrap());
-        assert_eq!(
-            get_chat_users(&mut con, chat_id).unwrap(),
-            vec![
-                "manager_test_user_4".to_string(),
-                "manager_test_user_5".to_string(),
-                "manager_test_user_6".to_string(),
-                "manager_test_user_7".to_string(),
-                "manager_test_user_8".to_string(),
-                "manager_test_user_9".to_string(),
-            ]
-        );
+/* Retrieves the display names of all users in a chat, keyed by lowercase username.
+ */
+pub async fn get_display_names(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<HashMap<String, String>, CrudError> {
+    let mut con = redis.connect().await?;
 
-        // Deletes chat
-        delete_chat(&mut con, chat_id).unwrap();
-        delete_chat_settings(&mut con, chat_id).unwrap();
-    }
+    let names = get_chat_display_names(&mut con, chat_id).await?;
+    Ok(names)
+}
+
+/* Deletes the display name of a user in a chat.
+ * Returns false if the user had no display name.
+ */
+pub async fn delete_display_name(
+    redis: &Redis,
+    chat_id: &str,
+    username: &str,
+) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let count = delete_chat_display_name(&mut con, chat_id, &username.to_lowercase()).await?;
+    Ok(count > 0)
+}
+
+/* Saves a split group of a chat, replacing any group of the same name.
+ */
+pub async fn set_split_group(
+    redis: &Redis,
+    chat_id: &str,
+    name: &str,
+    usernames: &[String],
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_chat_split_group(&mut con, chat_id, name, usernames).await?;
+    Ok(())
+}
+
+/* Retrieves all split groups of a chat, in alphabetical order of their names.
+ * Usernames are updated to the current usernames of the users.
+ */
+pub async fn get_split_groups(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<(String, Vec<String>)>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for (name, usernames) in get_chat_split_groups(&mut con, chat_id).await? {
+        let mut current_usernames: Vec<String> = Vec::new();
+        for username in usernames {
+            current_usernames.push(get_current_username(&mut con, &username).await?);
+        }
+        groups.push((name, current_usernames));
+    }
+    groups.sort_by(|first, second| first.0.cmp(&second.0));
+    Ok(groups)
+}
+
+/* Deletes a split group of a chat by name.
+ * Returns false if there was no such group.
+ */
+pub async fn delete_split_group(
+    redis: &Redis,
+    chat_id: &str,
+    name: &str,
+) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let count = delete_chat_split_group(&mut con, chat_id, name).await?;
+    Ok(count > 0)
+}
+
+/* Gets the last month archived for a chat, if any.
+ */
+pub async fn get_last_archive_month(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let month = get_archive_month(&mut con, chat_id).await?;
+    Ok(month)
+}
+
+/* Sets the last month archived for a chat.
+ */
+pub async fn set_last_archive_month(
+    redis: &Redis,
+    chat_id: &str,
+    month: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_archive_month(&mut con, chat_id, month).await?;
+    Ok(())
+}
+
+/* Sets weekly digest for a chat.
+ * Takes in the time of day to post the digest, or None to disable it.
+ * Also adds or removes the chat from the weekly digest schedule.
+ */
+pub async fn set_weekly_digest(
+    redis: &Redis,
+    chat_id: &str,
+    time: Option<&str>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    match time {
+        Some(time) => {
+            set_chat_weekly_digest(&mut con, chat_id, time).await?;
+            add_digest_schedule(&mut con, chat_id).await?;
+        }
+        None => {
+            delete_chat_weekly_digest(&mut con, chat_id).await?;
+            delete_digest_schedule(&mut con, chat_id).await?;
+        }
+    }
+    Ok(())
+}
+
+/* Gets weekly digest time for a chat.
+ * Returns None if the weekly digest is disabled.
+ */
+pub async fn get_weekly_digest(redis: &Redis, chat_id: &str) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // By default, disabled
+    if !is_exists_chat_weekly_digest(&mut con, chat_id).await? {
+        return Ok(None);
+    }
+
+    let time = get_chat_weekly_digest(&mut con, chat_id).await;
+    match time {
+        Ok(time) => Ok(Some(time)),
+        Err(_) => Ok(None),
+    }
+}
+
+/* Gets all chats with weekly digest enabled.
+ */
+pub async fn get_weekly_digest_chats(redis: &Redis) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let chats = get_digest_schedule(&mut con).await?;
+    Ok(chats)
+}
+
+/* Gets the last week a digest was posted for a chat, if any.
+ */
+pub async fn get_last_digest_week(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let week = get_digest_week(&mut con, chat_id).await?;
+    Ok(week)
+}
+
+/* Sets the last week a digest was posted for a chat.
+ */
+pub async fn set_last_digest_week(
+    redis: &Redis,
+    chat_id: &str,
+    week: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_digest_week(&mut con, chat_id, week).await?;
+    Ok(())
+}
+
+/* Sets debt reminder for a chat.
+ * Takes in the interval in days between reminders, or None to disable it.
+ * Also adds or removes the chat from the debt reminder schedule.
+ * When enabled, the last reminder date is reset, so reminders start right away.
+ */
+pub async fn set_debt_reminder(
+    redis: &Redis,
+    chat_id: &str,
+    days: Option<u32>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    match days {
+        Some(days) => {
+            set_chat_debt_reminder(&mut con, chat_id, days).await?;
+            delete_reminder_date(&mut con, chat_id).await?;
+            add_reminder_schedule(&mut con, chat_id).await?;
+        }
+        None => {
+            delete_chat_debt_reminder(&mut con, chat_id).await?;
+            delete_reminder_schedule(&mut con, chat_id).await?;
+        }
+    }
+    Ok(())
+}
+
+/* Gets debt reminder interval in days for a chat.
+ * Returns None if the debt reminder is disabled.
+ */
+pub async fn get_debt_reminder(redis: &Redis, chat_id: &str) -> Result<Option<u32>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // By default, disabled
+    if !is_exists_chat_debt_reminder(&mut con, chat_id).await? {
+        return Ok(None);
+    }
+
+    let days = get_chat_debt_reminder(&mut con, chat_id).await;
+    match days {
+        Ok(days) => Ok(Some(days)),
+        Err(_) => Ok(None),
+    }
+}
+
+/* Gets all chats with debt reminder enabled.
+ */
+pub async fn get_debt_reminder_chats(redis: &Redis) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let chats = get_reminder_schedule(&mut con).await?;
+    Ok(chats)
+}
+
+/* Gets the last date a reminder was posted for a chat, if any.
+ */
+pub async fn get_last_reminder_date(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let date = get_reminder_date(&mut con, chat_id).await?;
+    Ok(date)
+}
+
+/* Sets the last date a reminder was posted for a chat.
+ */
+pub async fn set_last_reminder_date(
+    redis: &Redis,
+    chat_id: &str,
+    date: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_reminder_date(&mut con, chat_id, date).await?;
+    Ok(())
+}
+
+/* Sets data retention for a chat.
+ * Takes in the number of months to keep payments for, or None to keep them all.
+ * Also adds or removes the chat from the data retention schedule.
+ * When enabled, the last cleanup date is reset, so the first cleanup runs right away.
+ */
+pub async fn set_data_retention(
+    redis: &Redis,
+    chat_id: &str,
+    months: Option<u32>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    match months {
+        Some(months) => {
+            set_chat_data_retention(&mut con, chat_id, months).await?;
+            delete_retention_date(&mut con, chat_id).await?;
+            add_retention_schedule(&mut con, chat_id).await?;
+        }
+        None => {
+            delete_chat_data_retention(&mut con, chat_id).await?;
+            delete_retention_schedule(&mut con, chat_id).await?;
+        }
+    }
+    Ok(())
+}
+
+/* Gets data retention period in months for a chat.
+ * Returns None if data retention is disabled.
+ */
+pub async fn get_data_retention(redis: &Redis, chat_id: &str) -> Result<Option<u32>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // By default, disabled
+    if !is_exists_chat_data_retention(&mut con, chat_id).await? {
+        return Ok(None);
+    }
+
+    let months = get_chat_data_retention(&mut con, chat_id).await;
+    match months {
+        Ok(months) => Ok(Some(months)),
+        Err(_) => Ok(None),
+    }
+}
+
+/* Gets all chats with data retention enabled.
+ */
+pub async fn get_data_retention_chats(redis: &Redis) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let chats = get_retention_schedule(&mut con).await?;
+    Ok(chats)
+}
+
+/* Gets the last date a cleanup was run for a chat, if any.
+ */
+pub async fn get_last_retention_date(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let date = get_retention_date(&mut con, chat_id).await?;
+    Ok(date)
+}
+
+/* Sets the last date a cleanup was run for a chat.
+ */
+pub async fn set_last_retention_date(
+    redis: &Redis,
+    chat_id: &str,
+    date: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_retention_date(&mut con, chat_id, date).await?;
+    Ok(())
+}
+
+/* Sets time zone override for a topic in a forum chat.
+ */
+pub async fn set_topic_time_zone(
+    redis: &Redis,
+    chat_id: &str,
+    topic_id: i32,
+    time_zone: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_topic_setting(
+        &mut con,
+        chat_id,
+        topic_id,
+        TOPIC_SETTING_TIME_ZONE,
+        time_zone,
+    )
+    .await?;
+    Ok(())
+}
+
+/* Gets time zone override for a topic in a forum chat.
+ * Returns None if the topic follows the chat.
+ */
+pub async fn get_topic_time_zone(
+    redis: &Redis,
+    chat_id: &str,
+    topic_id: i32,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let time_zone = get_topic_setting(&mut con, chat_id, topic_id, TOPIC_SETTING_TIME_ZONE).await?;
+    Ok(time_zone)
+}
+
+/* Sets default currency override for a topic in a forum chat.
+ */
+pub async fn set_topic_default_currency(
+    redis: &Redis,
+    chat_id: &str,
+    topic_id: i32,
+    currency: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_topic_setting(
+        &mut con,
+        chat_id,
+        topic_id,
+        TOPIC_SETTING_DEFAULT_CURRENCY,
+        currency,
+    )
+    .await?;
+    Ok(())
+}
+
+/* Gets default currency override for a topic in a forum chat.
+ * Returns None if the topic follows the chat.
+ */
+pub async fn get_topic_default_currency(
+    redis: &Redis,
+    chat_id: &str,
+    topic_id: i32,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let currency =
+        get_topic_setting(&mut con, chat_id, topic_id, TOPIC_SETTING_DEFAULT_CURRENCY).await?;
+    Ok(currency)
+}
+
+/* Removes all setting overrides for a topic in a forum chat.
+ */
+pub async fn reset_topic_settings(
+    redis: &Redis,
+    chat_id: &str,
+    topic_id: i32,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    delete_topic_settings(&mut con, chat_id, topic_id).await?;
+    Ok(())
+}
+
+/* Sets language for a chat.
+ */
+pub async fn set_language(redis: &Redis, chat_id: &str, language: &str) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_chat_language(&mut con, chat_id, language).await?;
+    Ok(())
+}
+
+/* Gets language for a chat.
+ */
+pub async fn get_language(redis: &Redis, chat_id: &str) -> Result<String, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // By default, return English
+    if !is_exists_chat_language(&mut con, chat_id).await? {
+        return Ok(LANGUAGE_DEFAULT.0.to_string());
+    }
+
+    let language = get_chat_language(&mut con, chat_id).await;
+    match language {
+        Ok(language) => Ok(language),
+        Err(_) => Ok(LANGUAGE_DEFAULT.0.to_string()),
+    }
+}
+
+/* Sets number format for a chat.
+ */
+pub async fn set_number_format(
+    redis: &Redis,
+    chat_id: &str,
+    number_format: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_chat_number_format(&mut con, chat_id, number_format).await?;
+    Ok(())
+}
+
+/* Gets number format for a chat.
+ */
+pub async fn get_number_format(redis: &Redis, chat_id: &str) -> Result<String, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // By default, return the default number format
+    if !is_exists_chat_number_format(&mut con, chat_id).await? {
+        return Ok(NUMBER_FORMAT_DEFAULT.0.to_string());
+    }
+
+    let number_format = get_chat_number_format(&mut con, chat_id).await;
+    match number_format {
+        Ok(number_format) => Ok(number_format),
+        Err(_) => Ok(NUMBER_FORMAT_DEFAULT.0.to_string()),
+    }
+}
+
+/* Sets currency format for a chat.
+ */
+pub async fn set_currency_format(
+    redis: &Redis,
+    chat_id: &str,
+    currency_format: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_chat_currency_format(&mut con, chat_id, currency_format).await?;
+    Ok(())
+}
+
+/* Gets currency format for a chat.
+ */
+pub async fn get_currency_format(redis: &Redis, chat_id: &str) -> Result<String, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // By default, return the default currency format
+    if !is_exists_chat_currency_format(&mut con, chat_id).await? {
+        return Ok(CURRENCY_FORMAT_DEFAULT.0.to_string());
+    }
+
+    let currency_format = get_chat_currency_format(&mut con, chat_id).await;
+    match currency_format {
+        Ok(currency_format) => Ok(currency_format),
+        Err(_) => Ok(CURRENCY_FORMAT_DEFAULT.0.to_string()),
+    }
+}
+
+/* Sets payment approval threshold for a chat.
+ * Takes in the amount above which payments require approval, or None to disable it.
+ */
+pub async fn set_approval_threshold(
+    redis: &Redis,
+    chat_id: &str,
+    threshold: Option<f64>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    match threshold {
+        Some(threshold) => set_chat_approval_threshold(&mut con, chat_id, threshold).await?,
+        None => delete_chat_approval_threshold(&mut con, chat_id).await?,
+    }
+    Ok(())
+}
+
+/* Gets payment approval threshold for a chat.
+ * Returns None if payment approval is disabled.
+ */
+pub async fn get_approval_threshold(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<f64>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // By default, disabled
+    if !is_exists_chat_approval_threshold(&mut con, chat_id).await? {
+        return Ok(None);
+    }
+
+    let threshold = get_chat_approval_threshold(&mut con, chat_id).await;
+    match threshold {
+        Ok(threshold) => Ok(Some(threshold)),
+        Err(_) => Ok(None),
+    }
+}
+
+/* Gets all valid currencies for a chat.
+ * Valid currencies are currencies with some payments.
+ */
+pub async fn get_valid_chat_currencies(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // Retrieve all currencies
+    let currencies = get_chat_currencies(&mut con, chat_id).await?;
+
+    // Retrieve all users
+    let users = get_chat_users(&mut con, chat_id).await?;
+
+    // For each currency, check that there is at least one spending amongst all users
+    let spendings = get_spendings(&mut con, chat_id, &users, &currencies).await?;
+    let valid_currencies: Vec<String> = currencies
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| {
+            spendings[index * users.len()..(index + 1) * users.len()]
+                .iter()
+                .any(|spending| spending.unwrap_or(0) > 0)
+        })
+        .map(|(_, currency)| currency.to_string())
+        .collect();
+
+    Ok(valid_currencies)
+}
+
+// Groups the balances or spendings of users by currency, as fetched in a single call
+// Amounts that do not exist or are 0 are left out
+async fn group_user_amounts(
+    con: &mut RedisConnection,
+    users: &[String],
+    currencies: &[String],
+    amounts: Vec<Option<i64>>,
+) -> Result<Vec<Vec<UserBalance>>, CrudError> {
+    let usernames = get_preferred_usernames(con, users).await?;
+
+    let mut grouped: Vec<Vec<UserBalance>> = Vec::new();
+    for (curr_index, currency) in currencies.iter().enumerate() {
+        let mut balances: Vec<UserBalance> = Vec::new();
+        for (user_index, user) in users.iter().enumerate() {
+            let amount = amounts[curr_index * users.len() + user_index].unwrap_or(0);
+            if amount != 0 {
+                balances.push(UserBalance {
+                    username: usernames[user_index].clone().unwrap_or(user.to_string()),
+                    currency: currency.to_string(),
+                    balance: amount,
+                });
+            }
+        }
+        grouped.push(balances);
+    }
+
+    Ok(grouped)
+}
+
+/* Gets all balances for a chat.
+ * Used when updating default currencies for a chat.
+ */
+pub async fn get_chat_balances(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<Vec<UserBalance>>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // Retrieve all balances in one call
+    let users = get_chat_users(&mut con, chat_id).await?;
+    let currencies = get_chat_currencies(&mut con, chat_id).await?;
+    let balances = get_balances(&mut con, chat_id, &users, &currencies).await?;
+
+    group_user_amounts(&mut con, &users, &currencies, balances).await
+}
+
+/* Gets all balances for a chat for a specific currency.
+ */
+pub async fn get_chat_balances_currency(
+    redis: &Redis,
+    chat_id: &str,
+    currency: &str,
+) -> Result<Vec<UserBalance>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // Retrieve all balances in one call
+    let users = get_chat_users(&mut con, chat_id).await?;
+    let currencies = vec![currency.to_string()];
+    let balances = get_balances(&mut con, chat_id, &users, &currencies).await?;
+
+    let mut balances = group_user_amounts(&mut con, &users, &currencies, balances).await?;
+    Ok(balances.pop().unwrap_or_default())
+}
+
+/* Updates balances for a chat based on given change amounts.
+ * If balance does not exist, creates it.
+ * Returns the updated balances.
+ */
+pub async fn update_chat_balances(
+    redis: &Redis,
+    chat_id: &str,
+    changes: Vec<UserBalance>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    // Update balances through changes
+    let mut currencies_changed: Vec<String> = Vec::new();
+    for change in changes {
+        let username = get_user_key(&mut con, &change.username).await?;
+        let balance = change.balance;
+        let currency = change.currency;
+
+        // Update currency into chat first
+        let currencies = get_chat_currencies(&mut con, chat_id).await?;
+        if !currencies.contains(&currency) {
+            add_chat_currency(&mut con, chat_id, &currency).await?;
+        }
+
+        // Update balance atomically, so that concurrent payments are not lost
+        add_balance(&mut con, chat_id, &username, &currency, balance).await?;
+        if !currencies_changed.contains(&currency) {
+            currencies_changed.push(currency);
+        }
+    }
+
+    update_settlements(&mut con, chat_id, &currencies_changed).await
+}
+
+// Records the settlement of each of the given currencies that now has no balances left
+// Payments in a settled currency last changed before then are shown as settled
+async fn update_settlements(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    currencies: &[String],
+) -> Result<(), CrudError> {
+    let users = get_chat_users(con, chat_id).await?;
+    let balances = get_balances(con, chat_id, &users, currencies).await?;
+    let datetime = Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string();
+    for (index, currency) in currencies.iter().enumerate() {
+        let is_settled = balances
+            .iter()
+            .skip(index * users.len())
+            .take(users.len())
+            .all(|balance| balance.unwrap_or(0) == 0);
+        if is_settled {
+            set_settlement(con, chat_id, currency, &datetime).await?;
+        }
+    }
+
+    Ok(())
+}
+
+// Checks if a payment is settled, having last changed before its currency was last settled
+// Datetimes share the same format, so they are compared as they are
+fn is_payment_settled(
+    payment: &Payment,
+    audit: &PaymentAudit,
+    settlements: &HashMap<String, String>,
+) -> bool {
+    let last_changed = audit
+        .last_edited_at
+        .as_ref()
+        .or(audit.created_at.as_ref())
+        .unwrap_or(&payment.datetime);
+    match settlements.get(&payment.currency) {
+        Some(settlement) => last_changed <= settlement,
+        None => false,
+    }
+}
+
+/* Gets the currencies most often used in a chat.
+ * Returns up to the given number of currencies, from the most frequently used.
+ */
+pub async fn get_frequent_currencies(
+    redis: &Redis,
+    chat_id: &str,
+    count: usize,
+) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut usage: Vec<(String, u64)> = get_chat_currency_usage(&mut con, chat_id)
+        .await?
+        .into_iter()
+        .collect();
+    usage.sort_by(|first, second| second.1.cmp(&first.1).then(first.0.cmp(&second.0)));
+    Ok(usage
+        .into_iter()
+        .take(count)
+        .map(|(currency, _)| currency)
+        .collect())
+}
+
+/* Adds a payment.
+ * Sets a new key-value pair for the payment, and updates the payments list in chat.
+ * Called whenever a new payment is added.
+ * Returns the ID of the payment.
+ */
+pub async fn add_payment_entry(
+    redis: &Redis,
+    chat_id: &str,
+    payment: &Payment,
+) -> Result<String, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // Adds payment
+    let payment_id = add_payment(&mut con, payment).await?;
+
+    // Adds payment to chat
+    add_chat_payment(&mut con, chat_id, &payment_id).await?;
+
+    // Counts the currency used, to suggest it for later payments
+    if payment.currency != CURRENCY_CODE_DEFAULT {
+        incr_chat_currency_usage(&mut con, chat_id, &payment.currency).await?;
+    }
+
+    Ok(payment_id)
+}
+
+/* Adds an adjustment entry.
+ * Adds the payment like any other payment, but marks it as an adjustment.
+ * Does not update balances or spendings, which is left to the caller.
+ * Returns the ID of the new adjustment.
+ */
+pub async fn add_adjustment_entry(
+    redis: &Redis,
+    chat_id: &str,
+    payment: &Payment,
+) -> Result<String, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let payment_id = add_payment(&mut con, payment).await?;
+    add_chat_payment(&mut con, chat_id, &payment_id).await?;
+    add_adjustment(&mut con, &payment_id).await?;
+
+    Ok(payment_id)
+}
+
+/* Marks a payment entry as private.
+ * Private payments still count towards balances and spendings,
+ * but their descriptions are only shown to those involved.
+ */
+pub async fn set_payment_private_entry(redis: &Redis, payment_id: &str) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_payment_private(&mut con, payment_id).await?;
+    Ok(())
+}
+
+/* Checks if a payment entry is private.
+ */
+pub async fn is_private_payment_entry(redis: &Redis, payment_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let is_private = get_payment_private(&mut con, payment_id).await?;
+    Ok(is_private)
+}
+
+/* Marks or unmarks a payment entry as a transfer.
+ * Transfers, such as deposits or reimbursements, count towards balances but not spendings.
+ */
+pub async fn set_payment_transfer_entry(
+    redis: &Redis,
+    payment_id: &str,
+    is_transfer: bool,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_payment_transfer(&mut con, payment_id, is_transfer).await?;
+    Ok(())
+}
+
+/* Checks if a payment entry is a transfer.
+ */
+pub async fn is_transfer_payment_entry(redis: &Redis, payment_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let is_transfer = get_payment_transfer(&mut con, payment_id).await?;
+    Ok(is_transfer)
+}
+
+/* Sets the note of a payment entry, replacing any previous note.
+ */
+pub async fn set_payment_note_entry(
+    redis: &Redis,
+    payment_id: &str,
+    note: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_payment_note(&mut con, payment_id, note).await?;
+    Ok(())
+}
+
+/* Gets the note of a payment entry, if any.
+ */
+pub async fn get_payment_note_entry(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let note = get_payment_note(&mut con, payment_id).await?;
+    Ok(note)
+}
+
+/* Sets the receipt of a payment entry, as the Telegram file ID of its photo.
+ * Any previous receipt is replaced.
+ */
+pub async fn set_payment_receipt_entry(
+    redis: &Redis,
+    payment_id: &str,
+    file_id: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_payment_receipt(&mut con, payment_id, file_id).await?;
+    Ok(())
+}
+
+/* Gets the receipt of a payment entry, if any.
+ */
+pub async fn get_payment_receipt_entry(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let receipt = get_payment_receipt(&mut con, payment_id).await?;
+    Ok(receipt)
+}
+
+/* Checks if a payment entry is an adjustment entry.
+ */
+pub async fn is_adjustment_entry(redis: &Redis, payment_id: &str) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let is_adjustment = get_adjustment_exists(&mut con, payment_id).await?;
+    Ok(is_adjustment)
+}
+
+/* Adds a pending payment entry.
+ * Adds the payment details, but not to the chat, as it is awaiting approval.
+ * Returns the ID of the pending payment.
+ */
+pub async fn add_pending_payment_entry(
+    redis: &Redis,
+    pending: &PendingPayment,
+    payment: &Payment,
+) -> Result<String, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let payment_id = add_payment(&mut con, payment).await?;
+    add_pending(&mut con, &payment_id, pending).await?;
+
+    Ok(payment_id)
+}
+
+/* Retrieves a pending payment entry by ID.
+ * Called when a user approves or disputes a payment.
+ */
+pub async fn get_pending_payment_entry(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<(PendingPayment, Payment), CrudError> {
+    let mut con = redis.connect().await?;
+
+    if !get_pending_exists(&mut con, payment_id).await? {
+        return Err(CrudError::NoSuchPaymentError());
+    }
+
+    let pending = get_pending(&mut con, payment_id).await?;
+    let payment = get_payment(&mut con, payment_id).await?;
+
+    Ok((pending, payment))
+}
+
+/* Confirms a pending payment entry, as the payer or as a debtor, for dual entry verification.
+ * Only the first confirmation in each role counts.
+ * Returns the users who have confirmed it as the payer and as a debtor so far.
+ */
+pub async fn confirm_pending_payment_entry(
+    redis: &Redis,
+    payment_id: &str,
+    is_payer: bool,
+    username: &str,
+) -> Result<(Option<String>, Option<String>), CrudError> {
+    let mut con = redis.connect().await?;
+
+    if !get_pending_exists(&mut con, payment_id).await? {
+        return Err(CrudError::NoSuchPaymentError());
+    }
+
+    set_pending_confirmation(&mut con, payment_id, is_payer, username).await?;
+    let confirmations = get_pending_confirmations(&mut con, payment_id).await?;
+
+    Ok(confirmations)
+}
+
+/* Removes a pending payment entry, once it has been approved or disputed.
+ * Returns false if the payment was already resolved by someone else.
+ */
+pub async fn resolve_pending_payment_entry(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    if !delete_pending(&mut con, payment_id).await? {
+        return Ok(false);
+    }
+    delete_payment(&mut con, payment_id).await?;
+
+    Ok(true)
+}
+
+/* Claims a pending payment entry to be approved, so that no one else can resolve it meanwhile.
+ * Returns false if the payment was already resolved or claimed by someone else.
+ */
+pub async fn claim_pending_payment_entry(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let is_claimed = claim_pending(&mut con, payment_id).await?;
+    Ok(is_claimed)
+}
+
+/* Restores a claimed payment entry as pending, when it could not be approved.
+ */
+pub async fn restore_pending_payment_entry(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    restore_pending(&mut con, payment_id).await?;
+    Ok(())
+}
+
+/* Removes a claimed payment entry, once it has been approved and added as a payment.
+ */
+pub async fn remove_claimed_payment_entry(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    delete_claimed_pending(&mut con, payment_id).await?;
+    delete_payment(&mut con, payment_id).await?;
+    Ok(())
+}
+
+/* Archives payment entries of a chat under a label.
+ * Removes the payments from the list in chat, and adds them to the archive.
+ * The payment entries themselves are kept.
+ */
+pub async fn archive_payment_entries(
+    redis: &Redis,
+    chat_id: &str,
+    label: &str,
+    payment_ids: Vec<String>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    add_archive(&mut con, chat_id, label).await?;
+    add_archive_payments(&mut con, chat_id, label, &payment_ids).await?;
+    for payment_id in &payment_ids {
+        delete_chat_payment(&mut con, chat_id, payment_id).await?;
+    }
+
+    Ok(())
+}
+
+/* Deletes payment entries of a chat for good, to free up space.
+ * Removes each payment from the list in chat and from its trip, if any,
+ * along with everything kept about it, such as its versions and acknowledgements.
+ * Does not update balances or spendings, which is left to the caller.
+ */
+pub async fn delete_payment_entries(
+    redis: &Redis,
+    chat_id: &str,
+    payment_ids: &[String],
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    for payment_id in payment_ids {
+        if let Some(trip_id) = get_payment_trip(&mut con, payment_id).await? {
+            delete_chat_payment(&mut con, &get_trip_ledger(chat_id, &trip_id), payment_id).await?;
+        }
+        delete_chat_payment(&mut con, chat_id, payment_id).await?;
+        delete_payment(&mut con, payment_id).await?;
+        delete_adjustment(&mut con, payment_id).await?;
+        delete_versions(&mut con, payment_id).await?;
+        delete_acknowledgements(&mut con, payment_id).await?;
+        delete_due_date(&mut con, chat_id, payment_id).await?;
+        delete_dispute(&mut con, chat_id, payment_id).await?;
+    }
+
+    Ok(())
+}
+
+/* Moves a payment entry to the trash of a chat, where it can be restored or is later purged.
+ * Removes the payment from the list in chat, but keeps everything else about it.
+ * Its due date and dispute are cleared, as they no longer apply to a deleted payment.
+ * Called when a user wants to remove a payment, after it is removed from its trip, if any.
+ */
+pub async fn trash_payment_entry(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+    datetime: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    if get_payment(&mut con, payment_id).await.is_err() {
+        return Err(CrudError::NoSuchPaymentError());
+    }
+
+    delete_chat_payment(&mut con, chat_id, payment_id).await?;
+    add_trash(&mut con, chat_id, payment_id, datetime).await?;
+    delete_due_date(&mut con, chat_id, payment_id).await?;
+    delete_dispute(&mut con, chat_id, payment_id).await?;
+
+    Ok(())
+}
+
+/* Gets the payment entries in the trash of a chat, from the latest deleted.
+ * Payments that no longer exist are skipped.
+ */
+pub async fn get_trash_entries(redis: &Redis, chat_id: &str) -> Result<Vec<TrashEntry>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut entries: Vec<TrashEntry> = Vec::new();
+    for (payment_id, deleted) in get_trash(&mut con, chat_id).await? {
+        let payment = match get_payment(&mut con, &payment_id).await {
+            Ok(payment) => payment,
+            Err(_) => continue,
+        };
+        let is_private = get_payment_private(&mut con, &payment_id).await?;
+        entries.push(TrashEntry {
+            payment_id,
+            payment,
+            is_private,
+            deleted,
+        });
+    }
+    entries.sort_by(|first, second| second.deleted.cmp(&first.deleted));
+
+    Ok(entries)
+}
+
+/* Restores a payment entry from the trash of a chat.
+ * Adds the payment back to the list in chat as the latest payment, and to its trip, if any.
+ * Returns false if the payment is no longer in the trash, such that it is only restored once.
+ * Does not update balances or spendings, which is left to the caller.
+ */
+pub async fn restore_trash_entry(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    if !delete_trash(&mut con, chat_id, payment_id).await? {
+        return Ok(false);
+    }
+
+    add_chat_payment(&mut con, chat_id, payment_id).await?;
+    if let Some(trip_id) = get_payment_trip(&mut con, payment_id).await? {
+        add_chat_payment(&mut con, &get_trip_ledger(chat_id, &trip_id), payment_id).await?;
+    }
+
+    Ok(true)
+}
+
+/* Deletes the payment entries in the trash of a chat from before a datetime, for good.
+ * Datetimes are in the format %Y-%m-%d %H:%M:%S UTC, so they are compared as strings.
+ * Returns the number of payments purged.
+ */
+pub async fn purge_trash_entries(
+    redis: &Redis,
+    chat_id: &str,
+    before: &str,
+) -> Result<usize, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut count = 0;
+    for (payment_id, deleted) in get_trash(&mut con, chat_id).await? {
+        if deleted.as_str() >= before {
+            continue;
+        }
+
+        delete_payment(&mut con, &payment_id).await?;
+        delete_adjustment(&mut con, &payment_id).await?;
+        delete_versions(&mut con, &payment_id).await?;
+        delete_acknowledgements(&mut con, &payment_id).await?;
+        delete_trash(&mut con, chat_id, &payment_id).await?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/* Gets the IDs of all chats with payments in their trash.
+ */
+pub async fn get_trash_chat_ids(redis: &Redis) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let chats = get_trash_chats(&mut con).await?;
+    Ok(chats)
+}
+
+/* Deletes the archives of a chat from before a month, along with their payment entries.
+ * Archive labels are months such as "2024-05", so they are compared as strings.
+ * Balances are unchanged, as they were carried forward when the payments were archived.
+ * Returns the number of payments deleted.
+ */
+pub async fn delete_archive_entries(
+    redis: &Redis,
+    chat_id: &str,
+    before: &str,
+) -> Result<usize, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut count = 0;
+    for label in get_archives(&mut con, chat_id).await? {
+        if label.as_str() >= before {
+            continue;
+        }
+
+        let payment_ids = get_archive_payments(&mut con, chat_id, &label).await?;
+        for payment_id in &payment_ids {
+            delete_payment(&mut con, payment_id).await?;
+            delete_versions(&mut con, payment_id).await?;
+            delete_acknowledgements(&mut con, payment_id).await?;
+            delete_due_date(&mut con, chat_id, payment_id).await?;
+            delete_dispute(&mut con, chat_id, payment_id).await?;
+        }
+        delete_archive_payments(&mut con, chat_id, &label).await?;
+        delete_archive(&mut con, chat_id, &label).await?;
+        count += payment_ids.len();
+    }
+
+    Ok(count)
+}
+
+/* Retrieves all chats of a user, by their Telegram user ID.
+ * Identifies the user by their username first, if any,
+ * so that chats where they were only mentioned by username are included.
+ */
+pub async fn get_user_chat_entries(
+    redis: &Redis,
+    user_id: &str,
+    username: Option<&str>,
+) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    if let Some(username) = username {
+        identify_user(&mut con, username, user_id).await?;
+    }
+    if !get_user_exists(&mut con, user_id).await? {
+        return Ok(Vec::new());
+    }
+
+    let chats = get_user_chats(&mut con, user_id).await?;
+    Ok(chats)
+}
+
+/* Sets the payment link of a user, or removes it if None.
+ * The link is kept under the user ID, so it stays even if the username changes.
+ */
+pub async fn set_payment_link(
+    redis: &Redis,
+    user_id: &str,
+    username: Option<&str>,
+    link: Option<&str>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    if let Some(username) = username {
+        identify_user(&mut con, username, user_id).await?;
+    }
+    match link {
+        Some(link) => set_user_payment_link(&mut con, user_id, link).await?,
+        None => delete_user_payment_link(&mut con, user_id).await?,
+    }
+    Ok(())
+}
+
+/* Gets the payment links of the given users.
+ * Returns a map of each lowercase username to its link, for users who have one.
+ */
+pub async fn get_payment_links(
+    redis: &Redis,
+    usernames: &[String],
+) -> Result<HashMap<String, String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut user_keys: Vec<String> = Vec::new();
+    for username in usernames {
+        user_keys.push(get_user_key(&mut con, username).await?);
+    }
+    let links = get_user_payment_links(&mut con, &user_keys).await?;
+    Ok(usernames
+        .iter()
+        .zip(links)
+        .filter_map(|(username, link)| link.map(|link| (username.to_lowercase(), link)))
+        .collect())
+}
+
+/* Sets whether a user can be messaged directly, about payments they are added to.
+ * The user is kept by ID, so they can be found by any username they are mentioned with.
+ */
+pub async fn set_private_chat(
+    redis: &Redis,
+    user_id: &str,
+    username: Option<&str>,
+    is_open: bool,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    if let Some(username) = username {
+        identify_user(&mut con, username, user_id).await?;
+    }
+    if is_open {
+        add_user_private_chat(&mut con, user_id).await?;
+    } else {
+        delete_user_private_chat(&mut con, user_id).await?;
+    }
+    Ok(())
+}
+
+/* Gets the direct messages of the given users, for those who can be messaged directly.
+ * Returns a map of each lowercase username to the ID of their direct message.
+ */
+pub async fn get_private_chats(
+    redis: &Redis,
+    usernames: &[String],
+) -> Result<HashMap<String, String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut private_chats: HashMap<String, String> = HashMap::new();
+    for username in usernames {
+        let user_key = get_user_key(&mut con, username).await?;
+        if is_user_private_chat(&mut con, &user_key).await? {
+            private_chats.insert(username.to_lowercase(), user_key);
+        }
+    }
+    Ok(private_chats)
+}
+
+/* Retrieves all payments for a chat and their details.
+ * Called whenever a user views past payments.
+ */
+pub async fn get_chat_payments_details(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<UserPayment>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    if get_chat_payment_exists(&mut con, chat_id).await.is_err() {
+        return Err(CrudError::NoPaymentsError());
+    }
+
+    let payment_ids = get_chat_payments(&mut con, chat_id).await?;
+    let mut payments: Vec<UserPayment> = Vec::new();
+
+    if payment_ids.is_empty() {
+        return Err(CrudError::NoPaymentsError());
+    }
+
+    let settlements = get_settlements(&mut con, chat_id).await?;
+    let disputes = get_disputes(&mut con, chat_id).await?;
+
+    for payment_id in payment_ids {
+        let mut payment = get_payment(&mut con, &payment_id).await?;
+        let rate = get_payment_rate(&mut con, &payment_id).await?;
+        let audit = get_payment_audit(&mut con, &payment_id).await?;
+        let is_private = get_payment_private(&mut con, &payment_id).await?;
+        let is_transfer = get_payment_transfer(&mut con, &payment_id).await?;
+        let is_settled = is_payment_settled(&payment, &audit, &settlements);
+        let is_disputed = disputes.contains_key(&payment_id);
+
+        // Shows the current usernames of users, in case they have changed
+        payment.creditor = get_current_username(&mut con, &payment.creditor).await?;
+        for debt in payment.debts.iter_mut() {
+            debt.0 = get_current_username(&mut con, &debt.0).await?;
+        }
+
+        let user_payment = UserPayment {
+            chat_id: chat_id.to_string(),
+            payment_id,
+            payment,
+            rate,
+            audit,
+            is_private,
+            is_transfer,
+            is_settled,
+            is_disputed,
+        };
+        payments.push(user_payment);
+    }
+
+    Ok(payments)
+}
+
+/* Retrieves a specific payment entry by ID.
+ * Called when a user wants to edit or delete a payment.
+ */
+pub async fn get_payment_entry(redis: &Redis, payment_id: &str) -> Result<Payment, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let payment = get_payment(&mut con, payment_id).await;
+
+    match payment {
+        Err(_) => Err(CrudError::NoSuchPaymentError()),
+        Ok(payment) => Ok(payment),
+    }
+}
+
+/* Sets the date by which the debts of a payment should be paid back.
+ */
+pub async fn set_payment_due_date(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+    date: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_due_date(&mut con, chat_id, payment_id, date).await?;
+    Ok(())
+}
+
+/* Gets the due dates of all payments with one in a chat, by payment ID.
+ */
+pub async fn get_payment_due_dates(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<HashMap<String, String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let dates = get_due_dates(&mut con, chat_id).await?;
+    Ok(dates)
+}
+
+/* Opens a dispute on a payment, flagged by one of its debtors.
+ * Returns false if the payment is already disputed.
+ */
+pub async fn add_payment_dispute(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+    username: &str,
+) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    if get_payment(&mut con, payment_id).await.is_err() {
+        return Err(CrudError::NoSuchPaymentError());
+    }
+
+    let is_added = add_dispute(&mut con, chat_id, payment_id, username).await?;
+    Ok(is_added)
+}
+
+/* Gets the username who disputed a payment, if it is disputed.
+ */
+pub async fn get_payment_dispute(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut disputes = get_disputes(&mut con, chat_id).await?;
+    Ok(disputes.remove(payment_id))
+}
+
+/* Resolves the dispute on a payment, when it is edited or dismissed.
+ * Returns false if the payment was not disputed.
+ */
+pub async fn resolve_payment_dispute(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let is_resolved = delete_dispute(&mut con, chat_id, payment_id).await?;
+    Ok(is_resolved)
+}
+
+/* Updates a payment entry.
+ * Called when a user edits payment details.
+ */
+#[allow(clippy::too_many_arguments)]
+pub async fn update_payment_entry(
+    redis: &Redis,
+    payment_id: &str,
+    description: Option<&str>,
+    datetime: Option<&str>,
+    creditor: Option<&str>,
+    currency: Option<&str>,
+    total: Option<&i64>,
+    debts: Option<Vec<(String, i64)>>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    if get_payment(&mut con, payment_id).await.is_err() {
+        return Err(CrudError::NoSuchPaymentError());
+    }
+
+    // Updates payment
+    update_payment(
+        &mut con,
+        payment_id,
+        description,
+        datetime,
+        creditor,
+        currency,
+        total,
+        debts,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/* Adds a previous version of a payment entry, from before it was edited.
+ */
+pub async fn add_payment_version_entry(
+    redis: &Redis,
+    payment_id: &str,
+    version: &PaymentVersion,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    add_version(&mut con, payment_id, version).await?;
+    Ok(())
+}
+
+/* Retrieves the previous versions of a payment entry, from the earliest to the latest.
+ */
+pub async fn get_payment_version_entries(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<Vec<PaymentVersion>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let versions = get_versions(&mut con, payment_id).await?;
+    Ok(versions)
+}
+
+/* Sets the payment that a confirmation message in a chat is for.
+ * Reactions to the message can then be counted as confirmations of the payment.
+ */
+pub async fn set_acknowledgement_message_entry(
+    redis: &Redis,
+    chat_id: &str,
+    message_id: i32,
+    payment_id: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_acknowledgement_message(&mut con, chat_id, message_id, payment_id).await?;
+    Ok(())
+}
+
+/* Gets the payment that a confirmation message in a chat is for, if any.
+ */
+pub async fn get_acknowledgement_message_entry(
+    redis: &Redis,
+    chat_id: &str,
+    message_id: i32,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let payment_id = get_acknowledgement_message(&mut con, chat_id, message_id).await?;
+    Ok(payment_id)
+}
+
+/* Adds or removes a user who confirmed a payment entry.
+ */
+pub async fn update_acknowledgement_entry(
+    redis: &Redis,
+    payment_id: &str,
+    username: &str,
+    is_acknowledged: bool,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    if is_acknowledged {
+        add_acknowledgement(&mut con, payment_id, username).await?;
+    } else {
+        delete_acknowledgement(&mut con, payment_id, username).await?;
+    }
+    Ok(())
+}
+
+/* Retrieves all users who confirmed a payment entry.
+ */
+pub async fn get_acknowledgement_entries(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let usernames = get_acknowledgements(&mut con, payment_id).await?;
+    Ok(usernames)
+}
+
+/* Removes all confirmations of a payment entry.
+ * Called when a payment is edited, as the confirmations were for its previous details.
+ */
+pub async fn delete_acknowledgement_entries(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    delete_acknowledgements(&mut con, payment_id).await?;
+    Ok(())
+}
+
+/* Updates the conversion rate of a payment entry.
+ * Removes the conversion rate if none is given.
+ * Called when a payment is added or its currency is changed, with currency conversion enabled.
+ */
+pub async fn update_payment_rate_entry(
+    redis: &Redis,
+    payment_id: &str,
+    rate: Option<&PaymentRate>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    match rate {
+        Some(rate) => set_payment_rate(&mut con, payment_id, rate).await?,
+        None => delete_payment_rate(&mut con, payment_id).await?,
+    }
+
+    Ok(())
+}
+
+/* Deletes a payment entry for good.
+ * Removes the main payment entry, and also from the list in chat.
+ * Mainly for testing purposes, as payments removed by users go to the trash instead.
+ */
+#[allow(dead_code)]
+pub async fn delete_payment_entry(
+    redis: &Redis,
+    chat_id: &str,
+    payment_id: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    if get_payment(&mut con, payment_id).await.is_err() {
+        return Err(CrudError::NoSuchPaymentError());
+    }
+
+    delete_payment(&mut con, payment_id).await?;
+    delete_chat_payment(&mut con, chat_id, payment_id).await?;
+    delete_adjustment(&mut con, payment_id).await?;
+    delete_versions(&mut con, payment_id).await?;
+    delete_acknowledgements(&mut con, payment_id).await?;
+    delete_due_date(&mut con, chat_id, payment_id).await?;
+    delete_dispute(&mut con, chat_id, payment_id).await?;
+
+    Ok(())
+}
+
+/* Records a change to a payment entry in the audit trail of a chat.
+ * Who added and last edited the payment, and when, is also kept on the payment itself.
+ */
+pub async fn add_chat_audit_entry(
+    redis: &Redis,
+    chat_id: &str,
+    entry: &AuditEntry,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    match entry.action.as_str() {
+        AUDIT_ADDED => {
+            set_payment_created(
+                &mut con,
+                &entry.payment_id,
+                &entry.username,
+                &entry.datetime,
+            )
+            .await?
+        }
+        AUDIT_EDITED => {
+            set_payment_edited(
+                &mut con,
+                &entry.payment_id,
+                &entry.username,
+                &entry.datetime,
+            )
+            .await?
+        }
+        _ => {}
+    }
+    add_audit_entry(&mut con, chat_id, entry).await?;
+
+    Ok(())
+}
+
+/* Retrieves the recent changes to payment entries of a chat, from the latest.
+ */
+pub async fn get_chat_audit_entries(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<AuditEntry>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut entries = get_audit_entries(&mut con, chat_id).await?;
+    entries.reverse();
+
+    Ok(entries)
+}
+
+/* Starts a trip in a chat, and sets it as the active trip.
+ * If a trip of the same name already exists, it is resumed instead.
+ * Returns the trip started.
+ */
+pub async fn start_trip(redis: &Redis, chat_id: &str, name: &str) -> Result<Trip, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let trips = get_trips(&mut con, chat_id).await?;
+    let trip = match trips
+        .into_iter()
+        .find(|(_, trip_name)| trip_name.to_lowercase() == name.to_lowercase())
+    {
+        Some(trip) => trip,
+        None => (add_trip(&mut con, chat_id, name).await?, name.to_string()),
+    };
+    set_active_trip(&mut con, chat_id, &trip.0).await?;
+
+    Ok(trip)
+}
+
+/* Ends the active trip in a chat.
+ * Returns the trip ended, if there was one.
+ */
+pub async fn end_trip(redis: &Redis, chat_id: &str) -> Result<Option<Trip>, CrudError> {
+    let trip = get_chat_active_trip(redis, chat_id).await?;
+
+    let mut con = redis.connect().await?;
+    delete_active_trip(&mut con, chat_id).await?;
+
+    Ok(trip)
+}
+
+/* Retrieves the active trip of a chat, if any.
+ */
+pub async fn get_chat_active_trip(redis: &Redis, chat_id: &str) -> Result<Option<Trip>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let trip_id = match get_active_trip(&mut con, chat_id).await? {
+        Some(trip_id) => trip_id,
+        None => return Ok(None),
+    };
+    let trip = get_trips(&mut con, chat_id)
+        .await?
+        .into_iter()
+        .find(|(id, _)| *id == trip_id);
+
+    Ok(trip)
+}
+
+/* Retrieves all trips of a chat.
+ */
+pub async fn get_chat_trips(redis: &Redis, chat_id: &str) -> Result<Vec<Trip>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let trips = get_trips(&mut con, chat_id).await?;
+    Ok(trips)
+}
+
+/* Adds a payment entry to a trip.
+ * The payment is added to the payments list of the trip, and marked with the trip.
+ * Called after the payment itself has been added to the chat.
+ */
+pub async fn add_trip_payment_entry(
+    redis: &Redis,
+    chat_id: &str,
+    trip_id: &str,
+    payment_id: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    add_chat_payment(&mut con, &get_trip_ledger(chat_id, trip_id), payment_id).await?;
+    set_payment_trip(&mut con, payment_id, trip_id).await?;
+
+    Ok(())
+}
+
+/* Retrieves the trip ID of a payment entry, if it was added in one.
+ */
+pub async fn get_payment_trip_entry(
+    redis: &Redis,
+    payment_id: &str,
+) -> Result<Option<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let trip_id = get_payment_trip(&mut con, payment_id).await?;
+    Ok(trip_id)
+}
+
+/* Removes a payment entry from a trip.
+ * Called before the payment itself is deleted.
+ */
+pub async fn delete_trip_payment_entry(
+    redis: &Redis,
+    chat_id: &str,
+    trip_id: &str,
+    payment_id: &str,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    delete_chat_payment(&mut con, &get_trip_ledger(chat_id, trip_id), payment_id).await?;
+    Ok(())
+}
+
+/* Adds users to a trip, so that their balances and spendings are tracked in it.
+ */
+pub async fn update_trip_users(
+    redis: &Redis,
+    chat_id: &str,
+    trip_id: &str,
+    usernames: Vec<String>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut user_keys: Vec<String> = Vec::new();
+    for username in &usernames {
+        user_keys.push(get_user_key(&mut con, username).await?);
+    }
+
+    add_chat_user_multiple(&mut con, &get_trip_ledger(chat_id, trip_id), user_keys).await?;
+
+    Ok(())
+}
+
+/* Adds a payment entry to the practice ledger of the tutorial of a chat.
+ * Adds its users to the practice ledger too, but not to the chat itself.
+ * Does not update balances, which is left to the caller.
+ * Returns the ID of the new payment.
+ */
+pub async fn add_tutorial_payment_entry(
+    redis: &Redis,
+    chat_id: &str,
+    payment: &Payment,
+) -> Result<String, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let ledger = get_tutorial_ledger(chat_id);
+    let payment_id = add_payment(&mut con, payment).await?;
+    add_chat_payment(&mut con, &ledger, &payment_id).await?;
+
+    let mut user_keys: Vec<String> = vec![get_user_key(&mut con, &payment.creditor).await?];
+    for (debtor, _) in &payment.debts {
+        user_keys.push(get_user_key(&mut con, debtor).await?);
+    }
+    add_chat_user_multiple(&mut con, &ledger, user_keys).await?;
+
+    Ok(payment_id)
+}
+
+/* Deletes the practice ledger of the tutorial of a chat, with all its payments.
+ */
+pub async fn delete_tutorial_entry(redis: &Redis, chat_id: &str) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    let ledger = get_tutorial_ledger(chat_id);
+    for payment_id in get_chat_payments(&mut con, &ledger).await? {
+        delete_payment(&mut con, &payment_id).await?;
+    }
+    delete_ledger(&mut con, &ledger).await?;
+    delete_settlements(&mut con, &ledger).await?;
+
+    Ok(())
+}
+
+/* Updates the spendings of a chat with new changes.
+ * If the spending already exists, simply adds the value to the current spending.
+ * Else, it creates a new key and sets the value.
+ * Does not add currency. As balances and spendings are always updated together,
+ * currency will be added by balances instead.
+ */
+pub async fn update_chat_spendings(
+    redis: &Redis,
+    chat_id: &str,
+    spendings: Vec<UserBalance>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    for spending in spendings {
+        let username = &get_user_key(&mut con, &spending.username).await?;
+
+        // Update spending atomically, so that concurrent payments are not lost
+        let amount = add_spending(
+            &mut con,
+            chat_id,
+            username,
+            &spending.currency,
+            spending.balance,
+        )
+        .await?;
+        if amount < 0 {
+            // Reverts the change, as spendings cannot be negative
+            add_spending(
+                &mut con,
+                chat_id,
+                username,
+                &spending.currency,
+                -spending.balance,
+            )
+            .await?;
+            return Err(CrudError::NegativeSpendingError());
+        }
+
+        update_limit_spending(&mut con, chat_id, username, &spending).await?;
+        update_weekly_cap_spending(&mut con, chat_id, &spending).await?;
+    }
+
+    Ok(())
+}
+
+// Gets the time zone and first day of the week of a chat, by which spendings are grouped
+async fn get_chat_period(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> Result<(Tz, WeekStart), CrudError> {
+    let time_zone = if is_exists_chat_time_zone(con, chat_id).await? {
+        find_time_zone(&get_chat_time_zone(con, chat_id).await?)
+    } else {
+        Tz::UTC
+    };
+    let week_start = if is_exists_chat_week_start(con, chat_id).await? {
+        parse_week_start(&get_chat_week_start(con, chat_id).await?).unwrap_or(WeekStart::Monday)
+    } else {
+        WeekStart::Monday
+    };
+
+    Ok((time_zone, week_start))
+}
+
+// Gets the current month of a chat, by which spendings are counted towards limits
+async fn get_limit_month(con: &mut RedisConnection, chat_id: &str) -> Result<String, CrudError> {
+    let (time_zone, _) = get_chat_period(con, chat_id).await?;
+    Ok(get_month_key(&Utc::now().with_timezone(&time_zone)))
+}
+
+// Gets the current week of a chat, by which spendings are counted towards the weekly cap
+async fn get_cap_week(con: &mut RedisConnection, chat_id: &str) -> Result<String, CrudError> {
+    let (time_zone, week_start) = get_chat_period(con, chat_id).await?;
+    Ok(get_week_key(
+        &Utc::now().with_timezone(&time_zone),
+        week_start,
+    ))
+}
+
+// Checks if spendings in a currency count towards a limit or cap in another currency
+// Spendings without a currency count towards a limit in the default currency of the chat
+async fn is_spending_counted(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    spending_currency: &str,
+    currency: &str,
+) -> Result<bool, CrudError> {
+    if spending_currency != CURRENCY_CODE_DEFAULT {
+        return Ok(spending_currency == currency);
+    }
+
+    let default_currency = if is_exists_chat_default_currency(con, chat_id).await? {
+        get_chat_default_currency(con, chat_id).await?
+    } else {
+        CURRENCY_CODE_DEFAULT.to_string()
+    };
+    Ok(currency == default_currency || currency == CURRENCY_CODE_DEFAULT)
+}
+
+// Counts a change in spending of a user towards their monthly limit, if they have one
+// Spendings without a currency count towards a limit in the default currency of the chat
+async fn update_limit_spending(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    user_key: &str,
+    spending: &UserBalance,
+) -> Result<(), CrudError> {
+    let currency = match get_limit(con, chat_id, user_key).await? {
+        Some((currency, _)) => currency,
+        None => return Ok(()),
+    };
+
+    if is_spending_counted(con, chat_id, &spending.currency, &currency).await? {
+        let month = get_limit_month(con, chat_id).await?;
+        add_limit_spending(con, chat_id, &month, user_key, spending.balance).await?;
+    }
+
+    Ok(())
+}
+
+// Counts a change in spending of a chat towards its weekly cap, if it has one
+async fn update_weekly_cap_spending(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    spending: &UserBalance,
+) -> Result<(), CrudError> {
+    let currency = match get_weekly_cap(con, chat_id).await? {
+        Some((currency, _)) => currency,
+        None => return Ok(()),
+    };
+
+    if is_spending_counted(con, chat_id, &spending.currency, &currency).await? {
+        let week = get_cap_week(con, chat_id).await?;
+        add_weekly_cap_spending(con, chat_id, &week, spending.balance).await?;
+    }
+
+    Ok(())
+}
+
+/* Sets the monthly spending limit of a user in a chat.
+ * Takes in the currency and amount of the limit, or None to remove it.
+ * Spendings are counted towards the limit from the time it is set.
+ */
+pub async fn set_spending_limit(
+    redis: &Redis,
+    chat_id: &str,
+    username: &str,
+    limit: Option<(&str, i64)>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    let user_key = get_user_key(&mut con, username).await?;
+    match limit {
+        Some((currency, amount)) => {
+            set_limit(&mut con, chat_id, &user_key, currency, amount).await?;
+        }
+        None => {
+            delete_limit(&mut con, chat_id, &user_key).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/* Retrieves all monthly spending limits of a chat.
+ * Includes the spendings of each user counted towards their limit for the current month.
+ */
+pub async fn get_spending_limits(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<SpendingLimit>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let month = get_limit_month(&mut con, chat_id).await?;
+    let mut limits: Vec<SpendingLimit> = Vec::new();
+    for user_key in get_limit_users(&mut con, chat_id).await? {
+        if let Some((currency, limit)) = get_limit(&mut con, chat_id, &user_key).await? {
+            let spent = get_limit_spending(&mut con, chat_id, &month, &user_key).await?;
+            limits.push(SpendingLimit {
+                username: get_preferred_username(&mut con, &user_key)
+                    .await
+                    .unwrap_or(user_key.clone()),
+                currency,
+                limit,
+                spent: spent.max(0),
+            });
+        }
+    }
+    limits.sort_by(|a, b| a.username.cmp(&b.username));
+
+    Ok(limits)
+}
+
+/* Sets the weekly spending cap of a chat.
+ * Takes in the currency and amount of the cap, or None to remove it.
+ * Spendings are counted towards the cap from the time it is set.
+ */
+pub async fn set_weekly_spending_cap(
+    redis: &Redis,
+    chat_id: &str,
+    cap: Option<(&str, i64)>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    match cap {
+        Some((currency, amount)) => {
+            set_weekly_cap(&mut con, chat_id, currency, amount).await?;
+        }
+        None => {
+            delete_weekly_cap(&mut con, chat_id).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/* Gets the weekly spending cap of a chat, if any.
+ * Includes the spendings of the chat counted towards the cap for the current week.
+ */
+pub async fn get_weekly_spending_cap(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Option<WeeklyCap>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let (currency, cap) = match get_weekly_cap(&mut con, chat_id).await? {
+        Some(cap) => cap,
+        None => return Ok(None),
+    };
+    let week = get_cap_week(&mut con, chat_id).await?;
+    let spent = get_weekly_cap_spending(&mut con, chat_id, &week).await?;
+
+    Ok(Some(WeeklyCap {
+        currency,
+        cap,
+        spent: spent.max(0),
+    }))
+}
+
+/* Retrieves all users of a chat who have newly crossed their monthly spending limit.
+ * Each user is only returned once a month, unless they fall back under their limit.
+ */
+pub async fn get_spending_limit_warnings(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<SpendingLimit>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let month = get_limit_month(&mut con, chat_id).await?;
+    let mut warnings: Vec<SpendingLimit> = Vec::new();
+    for user_key in get_limit_users(&mut con, chat_id).await? {
+        let (currency, limit) = match get_limit(&mut con, chat_id, &user_key).await? {
+            Some(limit) => limit,
+            None => continue,
+        };
+        let spent = get_limit_spending(&mut con, chat_id, &month, &user_key).await?;
+        let is_warned = is_limit_warned(&mut con, chat_id, &month, &user_key).await?;
+        if spent >= limit && !is_warned {
+            set_limit_warned(&mut con, chat_id, &month, &user_key, true).await?;
+            warnings.push(SpendingLimit {
+                username: get_preferred_username(&mut con, &user_key)
+                    .await
+                    .unwrap_or(user_key.clone()),
+                currency,
+                limit,
+                spent,
+            });
+        } else if spent < limit && is_warned {
+            set_limit_warned(&mut con, chat_id, &month, &user_key, false).await?;
+        }
+    }
+
+    Ok(warnings)
+}
+
+/* Retrieves all spendings for a chat for all currencies.
+ * Returns a vector of UserBalance by user.
+ */
+pub async fn retrieve_chat_spendings(
+    redis: &Redis,
+    chat_id: &str,
+) -> Result<Vec<Vec<UserBalance>>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // Retrieve all spendings in one call
+    let users = get_chat_users(&mut con, chat_id).await?;
+    let currencies = get_chat_currencies(&mut con, chat_id).await?;
+    let spendings = get_spendings(&mut con, chat_id, &users, &currencies).await?;
+    let spendings = spendings
+        .into_iter()
+        .map(|spending| spending.map(|spending| spending as i64))
+        .collect();
+
+    group_user_amounts(&mut con, &users, &currencies, spendings).await
+}
+
+/* Retrieves all spendings for a chat for specific currency.
+ * Returns a vector of UserBalance by user.
+ */
+pub async fn retrieve_chat_spendings_currency(
+    redis: &Redis,
+    chat_id: &str,
+    currency: &str,
+) -> Result<Vec<UserBalance>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    // Retrieve all spendings in one call
+    let users = get_chat_users(&mut con, chat_id).await?;
+    let currencies = vec![currency.to_string()];
+    let spendings = get_spendings(&mut con, chat_id, &users, &currencies).await?;
+    let spendings = spendings
+        .into_iter()
+        .map(|spending| spending.map(|spending| spending as i64))
+        .collect();
+
+    let mut spendings = group_user_amounts(&mut con, &users, &currencies, spendings).await?;
+    Ok(spendings.pop().unwrap_or_default())
+}
+
+/* Checks if a user has exceeded the request limit.
+ * Returns a boolean representing this status.
+ * Automatically updates the request timestamp if not exceeded.
+ */
+pub async fn is_request_limit_exceeded(
+    redis: &Redis,
+    user_id: &str,
+    time_now: i64,
+) -> Result<bool, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let timestamp = get_request(&mut con, user_id).await;
+    let mut status = false;
+
+    match timestamp {
+        Ok(timestamp) => {
+            if time_now <= timestamp {
+                status = true;
+            }
+        }
+        Err(_) => {
+            // By default, assume okay and proceed with request
+        }
+    }
+
+    if !status {
+        // Updates request timestamp if not exceeded
+        set_request(&mut con, user_id, time_now).await?;
+    }
+
+    Ok(status)
+}
+
+/* Retrieves the cached conversion rate between two currencies, if any.
+ * Also marks the rate as used for some time, so that it is kept refreshed until then.
+ */
+pub async fn get_cached_rate(
+    redis: &Redis,
+    from: &str,
+    to: &str,
+    used_ttl: usize,
+) -> Result<Option<f64>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_rate_used(&mut con, from, to, used_ttl).await?;
+    let rate = get_rate(&mut con, from, to).await?;
+    Ok(rate)
+}
+
+/* Caches the conversion rate between two currencies, expiring after some time.
+ * The rate is also kept as the last rate fetched, with the current time, without expiring.
+ */
+pub async fn set_cached_rate(
+    redis: &Redis,
+    from: &str,
+    to: &str,
+    rate: f64,
+    ttl: usize,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    set_rate(&mut con, from, to, rate, ttl).await?;
+    set_last_rate(&mut con, from, to, rate, Utc::now().timestamp()).await?;
+    add_rate_pair(&mut con, from, to).await?;
+    Ok(())
+}
+
+/* Gets the last conversion rate fetched between two currencies, with when it was fetched.
+ * Unlike cached rates, it does not expire, so it may be out of date.
+ */
+pub async fn get_last_cached_rate(
+    redis: &Redis,
+    from: &str,
+    to: &str,
+) -> Result<Option<(f64, i64)>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let rate = get_last_rate(&mut con, from, to).await?;
+    Ok(rate)
+}
+
+/* Retrieves all cached currency pairs that are about to expire, and are still in use.
+ * Pairs that are no longer in use are removed, and left to expire.
+ */
+pub async fn get_expiring_rate_pairs(
+    redis: &Redis,
+    threshold: i64,
+) -> Result<Vec<(String, String)>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut expiring_pairs: Vec<(String, String)> = Vec::new();
+    for (from, to) in get_rate_pairs(&mut con).await? {
+        if !get_rate_used(&mut con, &from, &to).await? {
+            delete_rate_pair(&mut con, &from, &to).await?;
+            continue;
+        }
+        if get_rate_ttl(&mut con, &from, &to).await? < threshold {
+            expiring_pairs.push((from, to));
+        }
+    }
+
+    Ok(expiring_pairs)
+}
+
+// Gets all records of a ledger, being a chat or a trip
+async fn get_ledger_backup(
+    con: &mut RedisConnection,
+    ledger: &str,
+) -> Result<LedgerBackup, CrudError> {
+    let users = get_chat_users(con, ledger).await?;
+    let currencies = get_chat_currencies(con, ledger).await?;
+
+    let all_balances = get_balances(con, ledger, &users, &currencies).await?;
+    let all_spendings = get_spendings(con, ledger, &users, &currencies).await?;
+
+    let mut balances: Vec<UserBalance> = Vec::new();
+    let mut spendings: Vec<UserBalance> = Vec::new();
+    for (curr_index, currency) in currencies.iter().enumerate() {
+        for (user_index, user) in users.iter().enumerate() {
+            let index = curr_index * users.len() + user_index;
+            if let Some(balance) = all_balances[index] {
+                balances.push(UserBalance {
+                    username: user.to_string(),
+                    currency: currency.to_string(),
+                    balance,
+                });
+            }
+            if let Some(spending) = all_spendings[index] {
+                spendings.push(UserBalance {
+                    username: user.to_string(),
+                    currency: currency.to_string(),
+                    balance: spending as i64,
+                });
+            }
+        }
+    }
+
+    Ok(LedgerBackup {
+        users,
+        currencies,
+        balances,
+        spendings,
+    })
+}
+
+// Sets all records of a ledger, being a chat or a trip
+// Assumes the ledger has already been cleared
+async fn set_ledger_backup(
+    con: &mut RedisConnection,
+    ledger: &str,
+    backup: &LedgerBackup,
+) -> Result<(), CrudError> {
+    add_chat_user_multiple(con, ledger, backup.users.clone()).await?;
+    for currency in &backup.currencies {
+        add_chat_currency(con, ledger, currency).await?;
+    }
+    for balance in &backup.balances {
+        set_balance(
+            con,
+            ledger,
+            &balance.username,
+            &balance.currency,
+            balance.balance,
+        )
+        .await?;
+    }
+    for spending in &backup.spendings {
+        if spending.balance < 0 {
+            return Err(CrudError::NegativeSpendingError());
+        }
+        set_spending(
+            con,
+            ledger,
+            &spending.username,
+            &spending.currency,
+            spending.balance as u64,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+// Deletes all users, currencies, currency usage, balances, spendings and payments of a ledger
+// The payment entries themselves are left to the caller
+async fn delete_ledger(con: &mut RedisConnection, ledger: &str) -> Result<(), CrudError> {
+    let users = get_chat_users(con, ledger).await?;
+    for currency in get_chat_currencies(con, ledger).await? {
+        for user in &users {
+            delete_balance(con, ledger, user, &currency).await?;
+            delete_spending(con, ledger, user, &currency).await?;
+        }
+    }
+
+    delete_chat(con, ledger).await?;
+    delete_chat_currencies(con, ledger).await?;
+    delete_chat_currency_usage(con, ledger).await?;
+    delete_all_chat_payment(con, ledger).await?;
+
+    Ok(())
+}
+
+// Deletes the payments of a chat, and the ledgers of the chat and its trips.
+// Settings, schedules and archives of the chat are kept.
+async fn delete_chat_records(con: &mut RedisConnection, chat_id: &str) -> Result<(), CrudError> {
+    for payment_id in get_chat_payments(con, chat_id).await? {
+        delete_payment(con, &payment_id).await?;
+        delete_adjustment(con, &payment_id).await?;
+        delete_versions(con, &payment_id).await?;
+        delete_acknowledgements(con, &payment_id).await?;
+    }
+    for payment_id in get_trash(con, chat_id).await?.into_keys() {
+        delete_payment(con, &payment_id).await?;
+        delete_adjustment(con, &payment_id).await?;
+        delete_versions(con, &payment_id).await?;
+        delete_acknowledgements(con, &payment_id).await?;
+    }
+    delete_all_trash(con, chat_id).await?;
+    for (trip_id, _) in get_trips(con, chat_id).await? {
+        delete_ledger(con, &get_trip_ledger(chat_id, &trip_id)).await?;
+    }
+    delete_trips(con, chat_id).await?;
+    delete_active_trip(con, chat_id).await?;
+    delete_ledger(con, chat_id).await?;
+    delete_due_dates(con, chat_id).await?;
+    delete_disputes(con, chat_id).await?;
+    delete_settlements(con, chat_id).await?;
+
+    Ok(())
+}
+
+/* Gets the IDs of all chats known, for administration.
+ */
+pub async fn get_chat_ids(redis: &Redis) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut chats = get_chats(&mut con).await?;
+    chats.sort();
+    Ok(chats)
+}
+
+/* Gets the usage of the whole bot, for its operators.
+ * Counts the chats, those with changes to their payments since a datetime, the payments stored,
+ * and the keys and memory used by the database, by scanning through it.
+ * As scanning is slow, the usage is cached, expiring after some time.
+ */
+pub async fn get_bot_stats_entry(
+    redis: &Redis,
+    active_since: &str,
+    ttl: usize,
+) -> Result<BotStats, CrudError> {
+    let mut con = redis.connect().await?;
+
+    if let Some(stats) = get_bot_stats(&mut con).await? {
+        return Ok(stats);
+    }
+
+    let chats = get_chats(&mut con).await?;
+    let mut active_chats = 0;
+    for chat_id in chats.iter() {
+        let latest = get_latest_audit_datetime(&mut con, chat_id).await?;
+        if latest.is_some_and(|datetime| datetime.as_str() >= active_since) {
+            active_chats += 1;
+        }
+    }
+
+    let stats = BotStats {
+        chats: chats.len(),
+        active_chats,
+        payments: count_payments(&mut con).await?,
+        keys: count_keys(&mut con).await?,
+        // Not all databases report the memory they use, in which case it is left out
+        used_memory: get_used_memory(&mut con).await.ok().flatten(),
+        computed_at: Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+    };
+    set_bot_stats(&mut con, &stats, ttl).await?;
+    Ok(stats)
+}
+
+/* Deletes a chat entirely, for administration.
+ * Removes its payments, ledgers, trips, archives, settings and schedules.
+ * Users are kept, as they may also be in other chats.
+ */
+pub async fn delete_chat_entry(redis: &Redis, chat_id: &str) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    delete_chat_records(&mut con, chat_id).await?;
+    for label in get_archives(&mut con, chat_id).await? {
+        for payment_id in get_archive_payments(&mut con, chat_id, &label).await? {
+            delete_payment(&mut con, &payment_id).await?;
+            delete_versions(&mut con, &payment_id).await?;
+            delete_acknowledgements(&mut con, &payment_id).await?;
+        }
+        delete_archive_payments(&mut con, chat_id, &label).await?;
+        delete_archive(&mut con, chat_id, &label).await?;
+    }
+    delete_chat_settings(&mut con, chat_id).await?;
+    delete_archive_schedule(&mut con, chat_id).await?;
+    delete_digest_schedule(&mut con, chat_id).await?;
+    delete_reminder_schedule(&mut con, chat_id).await?;
+    delete_retention_schedule(&mut con, chat_id).await?;
+
+    Ok(())
+}
+
+/* Exports all records of a chat, including its settings, trips and payments.
+ * Archived payments and pending payments are not included.
+ */
+pub async fn export_chat(redis: &Redis, chat_id: &str) -> Result<ChatBackup, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let ledger = get_ledger_backup(&mut con, chat_id).await?;
+
+    let mut trips: Vec<TripBackup> = Vec::new();
+    let mut user_keys = ledger.users.clone();
+    for (trip_id, name) in get_trips(&mut con, chat_id).await? {
+        let trip_ledger = get_ledger_backup(&mut con, &get_trip_ledger(chat_id, &trip_id)).await?;
+        for user in &trip_ledger.users {
+            if !user_keys.contains(user) {
+                user_keys.push(user.to_string());
+            }
+        }
+        trips.push(TripBackup {
+            name,
+            ledger: trip_ledger,
+        });
+    }
+
+    let mut users: Vec<(String, String)> = Vec::new();
+    for user_key in user_keys {
+        let username = match get_preferred_username(&mut con, &user_key).await {
+            Ok(username) => username,
+            Err(_) => user_key.to_string(),
+        };
+        users.push((user_key, username));
+    }
+
+    let mut payments: Vec<PaymentBackup> = Vec::new();
+    for payment_id in get_chat_payments(&mut con, chat_id).await? {
+        payments.push(PaymentBackup {
+            payment: get_payment(&mut con, &payment_id).await?,
+            rate: get_payment_rate(&mut con, &payment_id).await?,
+            trip: get_payment_trip(&mut con, &payment_id).await?,
+            adjustment: get_adjustment_exists(&mut con, &payment_id).await?,
+            is_private: get_payment_private(&mut con, &payment_id).await?,
+            is_transfer: get_payment_transfer(&mut con, &payment_id).await?,
+            note: get_payment_note(&mut con, &payment_id).await?,
+            receipt: get_payment_receipt(&mut con, &payment_id).await?,
+        });
+    }
+
+    Ok(ChatBackup {
+        version: BACKUP_VERSION,
+        users,
+        settings: get_chat_settings(&mut con, chat_id).await?,
+        ledger,
+        trips,
+        active_trip: get_active_trip(&mut con, chat_id).await?,
+        payments,
+    })
+}
+
+/* Imports all records of a chat from a backup, overwriting the current records.
+ * All current payments, trips, balances, spendings and settings of the chat are removed first.
+ * Archived payments and pending payments of the chat are left untouched.
+ */
+pub async fn import_chat(
+    redis: &Redis,
+    chat_id: &str,
+    backup: &ChatBackup,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    // Clears current records
+    delete_chat_records(&mut con, chat_id).await?;
+
+    // Restores settings, and the schedules that depend on them
+    set_chat_settings(&mut con, chat_id, &backup.settings).await?;
+    let monthly_archive = is_exists_chat_monthly_archive(&mut con, chat_id).await?
+        && get_chat_monthly_archive(&mut con, chat_id).await?;
+    if monthly_archive {
+        add_archive_schedule(&mut con, chat_id).await?;
+    } else {
+        delete_archive_schedule(&mut con, chat_id).await?;
+    }
+    if is_exists_chat_weekly_digest(&mut con, chat_id).await? {
+        add_digest_schedule(&mut con, chat_id).await?;
+    } else {
+        delete_digest_schedule(&mut con, chat_id).await?;
+    }
+    if is_exists_chat_debt_reminder(&mut con, chat_id).await? {
+        add_reminder_schedule(&mut con, chat_id).await?;
+    } else {
+        delete_reminder_schedule(&mut con, chat_id).await?;
+    }
+    if is_exists_chat_data_retention(&mut con, chat_id).await? {
+        add_retention_schedule(&mut con, chat_id).await?;
+    } else {
+        delete_retention_schedule(&mut con, chat_id).await?;
+    }
+
+    // Restores users, without overwriting users already known to this instance
+    for (user_key, username) in &backup.users {
+        if !get_user_exists(&mut con, user_key).await? {
+            add_user(&mut con, user_key, chat_id).await?;
+        } else if !get_user_chats(&mut con, user_key)
+            .await?
+            .contains(&chat_id.to_string())
+        {
+            update_user_chats(&mut con, user_key, chat_id).await?;
+        }
+        if get_preferred_username(&mut con, user_key).await.is_err() {
+            set_preferred_username(&mut con, username, user_key).await?;
+        }
+
+        // Users keyed by their Telegram user ID
+        let alias = username.to_lowercase();
+        if *user_key != alias {
+            if !get_user_is_init(&mut con, user_key).await? {
+                initialize_user(&mut con, user_key, username).await?;
+            }
+            if get_user_alias(&mut con, &alias).await?.is_none() {
+                set_user_alias(&mut con, &alias, user_key).await?;
+            }
+        }
+    }
+
+    // Restores ledgers of the chat and its trips
+    set_ledger_backup(&mut con, chat_id, &backup.ledger).await?;
+    for trip in &backup.trips {
+        let trip_id = add_trip(&mut con, chat_id, &trip.name).await?;
+        set_ledger_backup(&mut con, &get_trip_ledger(chat_id, &trip_id), &trip.ledger).await?;
+    }
+    if let Some(trip_id) = &backup.active_trip {
+        set_active_trip(&mut con, chat_id, trip_id).await?;
+    }
+
+    // Restores payments, in their original order
+    for entry in &backup.payments {
+        let payment_id = add_payment(&mut con, &entry.payment).await?;
+        add_chat_payment(&mut con, chat_id, &payment_id).await?;
+        if entry.payment.currency != CURRENCY_CODE_DEFAULT {
+            incr_chat_currency_usage(&mut con, chat_id, &entry.payment.currency).await?;
+        }
+        if let Some(rate) = &entry.rate {
+            set_payment_rate(&mut con, &payment_id, rate).await?;
+        }
+        if entry.adjustment {
+            add_adjustment(&mut con, &payment_id).await?;
+        }
+        if entry.is_private {
+            set_payment_private(&mut con, &payment_id).await?;
+        }
+        if entry.is_transfer {
+            set_payment_transfer(&mut con, &payment_id, true).await?;
+        }
+        if let Some(note) = &entry.note {
+            set_payment_note(&mut con, &payment_id, note).await?;
+        }
+        if let Some(receipt) = &entry.receipt {
+            set_payment_receipt(&mut con, &payment_id, receipt).await?;
+        }
+        if let Some(trip_id) = &entry.trip {
+            add_chat_payment(&mut con, &get_trip_ledger(chat_id, trip_id), &payment_id).await?;
+            set_payment_trip(&mut con, &payment_id, trip_id).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/* Gets all ledgers of a chat, being the chat itself and each of its trips.
+ */
+pub async fn get_chat_ledgers(redis: &Redis, chat_id: &str) -> Result<Vec<String>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut ledgers = vec![chat_id.to_string()];
+    for (trip_id, _) in get_trips(&mut con, chat_id).await? {
+        ledgers.push(get_trip_ledger(chat_id, &trip_id));
+    }
+
+    Ok(ledgers)
+}
+
+/* Gets all payments of a ledger, in their original order, to recalculate its balances from.
+ * For a chat, archived payments are included as well, marked as archived.
+ * Trips keep their payments even when archived, so none of their payments are marked.
+ */
+pub async fn get_ledger_payments(
+    redis: &Redis,
+    chat_id: &str,
+    ledger: &str,
+) -> Result<Vec<LedgerPayment>, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut payment_ids: Vec<(String, bool)> = Vec::new();
+    if ledger == chat_id {
+        for label in get_archives(&mut con, chat_id).await? {
+            for payment_id in get_archive_payments(&mut con, chat_id, &label).await? {
+                payment_ids.push((payment_id, true));
+            }
+        }
+    }
+    for payment_id in get_chat_payments(&mut con, ledger).await? {
+        payment_ids.push((payment_id, false));
+    }
+
+    let mut payments: Vec<LedgerPayment> = Vec::new();
+    for (payment_id, archived) in payment_ids {
+        payments.push(LedgerPayment {
+            payment: get_payment(&mut con, &payment_id).await?,
+            adjustment: get_adjustment_exists(&mut con, &payment_id).await?,
+            archived,
+            transfer: get_payment_transfer(&mut con, &payment_id).await?,
+        });
+    }
+
+    Ok(payments)
+}
+
+/* Replaces all balances and spendings of a ledger, being a chat or a trip.
+ * Balances and spendings are each swapped in atomically, and any not given are removed.
+ * Usernames are resolved to their user keys, combining the amounts of the same user.
+ */
+pub async fn replace_ledger_balances(
+    redis: &Redis,
+    ledger: &str,
+    balances: Vec<UserBalance>,
+    spendings: Vec<UserBalance>,
+) -> Result<(), CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut new_balances: HashMap<(String, String), i64> = HashMap::new();
+    for balance in balances {
+        let user_key = get_user_key(&mut con, &balance.username).await?;
+        *new_balances
+            .entry((user_key, balance.currency))
+            .or_insert(0) += balance.balance;
+    }
+    let mut new_spendings: HashMap<(String, String), i64> = HashMap::new();
+    for spending in spendings {
+        let user_key = get_user_key(&mut con, &spending.username).await?;
+        *new_spendings
+            .entry((user_key, spending.currency))
+            .or_insert(0) += spending.balance;
+    }
+    if new_spendings.values().any(|spending| *spending < 0) {
+        return Err(CrudError::NegativeSpendingError());
+    }
+
+    // Adds any users and currencies that are not in the ledger yet
+    let mut users = get_chat_users(&mut con, ledger).await?;
+    let mut currencies = get_chat_currencies(&mut con, ledger).await?;
+    let mut new_users: Vec<String> = Vec::new();
+    for (user_key, currency) in new_balances.keys().chain(new_spendings.keys()) {
+        if !users.contains(user_key) {
+            users.push(user_key.to_string());
+            new_users.push(user_key.to_string());
+        }
+        if !currencies.contains(currency) {
+            add_chat_currency(&mut con, ledger, currency).await?;
+            currencies.push(currency.to_string());
+        }
+    }
+    if !new_users.is_empty() {
+        add_chat_user_multiple(&mut con, ledger, new_users).await?;
+    }
+
+    let new_balances: Vec<(String, String, i64)> = new_balances
+        .into_iter()
+        .map(|((user_key, currency), balance)| (user_key, currency, balance))
+        .collect();
+    replace_balances(&mut con, ledger, &users, &currencies, &new_balances).await?;
+    update_settlements(&mut con, ledger, &currencies).await?;
+
+    let new_spendings: Vec<(String, String, u64)> = new_spendings
+        .into_iter()
+        .map(|((user_key, currency), spending)| (user_key, currency, spending as u64))
+        .collect();
+    replace_spendings(&mut con, ledger, &users, &currencies, &new_spendings).await?;
+
+    Ok(())
+}
+
+/* Runs all pending migrations, upgrading the schema of the deployment to the latest version.
+ * Each migration is recorded as soon as it is done, so that it is never run again.
+ * Returns the schema version of the deployment, after running the migrations.
+ */
+pub async fn run_migrations(redis: &Redis) -> Result<u64, CrudError> {
+    let mut con = redis.connect().await?;
+
+    let mut version = get_schema_version(&mut con).await?;
+    while version < SCHEMA_VERSION_LATEST {
+        version += 1;
+        apply_migration(&mut con, version).await?;
+        set_schema_version(&mut con, version).await?;
+        log::info!("Migration - Upgraded schema to version {}", version);
+    }
+
+    Ok(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bot::redis::{
+        archive::delete_archives,
+        balance::delete_balance,
+        chat::get_chat_users,
+        digest::delete_digest_week,
+        limit::{delete_limit_month, delete_weekly_cap_week},
+        rate::{delete_last_rate, delete_rate},
+        request::delete_request,
+        spending::delete_spending,
+        trip::delete_trips,
+        user::{delete_user_alias, delete_user_id},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_user_add_user() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let username = "Manager_Test_User";
+        let user_key = username.to_lowercase();
+        let chat_id = "manager_123456789";
+
+        // Checks that user does not exist
+        assert!(!get_user_exists(&mut con, &user_key).await.unwrap());
+
+        // Adds user
+        assert!(update_user(&redis, username, chat_id, None).await.is_ok());
+        assert_eq!(
+            get_preferred_username(&mut con, &user_key).await.unwrap(),
+            username
+        );
+        assert_eq!(
+            get_user_chats(&mut con, &user_key).await.unwrap(),
+            vec![chat_id]
+        );
+
+        // Performs again, nothing should happen
+        assert!(update_user(&redis, username, chat_id, None).await.is_ok());
+        assert_eq!(
+            get_preferred_username(&mut con, &user_key).await.unwrap(),
+            username
+        );
+        assert_eq!(
+            get_user_chats(&mut con, &user_key).await.unwrap(),
+            vec![chat_id]
+        );
+
+        // Deletes user
+        delete_user(&mut con, &user_key).await.unwrap();
+        delete_preferred_username(&mut con, &user_key)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_user_add_chat() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let username = "Manager_Test_User_0";
+        let user_key = username.to_lowercase();
+        let chat_id = "manager_1234567890";
+        let second_chat_id = "manager_1234567891";
+
+        // Adds user and chat
+        assert!(update_user(&redis, username, chat_id, None).await.is_ok());
+        assert_eq!(
+            get_user_chats(&mut con, &user_key).await.unwrap(),
+            vec![chat_id]
+        );
+
+        // Calls again, adds a second chat
+        assert!(update_user(&redis, username, second_chat_id, None)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_user_chats(&mut con, &user_key).await.unwrap(),
+            vec![chat_id, second_chat_id]
+        );
+
+        // Calls again, nothing should happen
+        assert!(update_user(&redis, username, second_chat_id, None)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_user_chats(&mut con, &user_key).await.unwrap(),
+            vec![chat_id, second_chat_id]
+        );
+
+        // Deletes user
+        delete_user(&mut con, &user_key).await.unwrap();
+        delete_preferred_username(&mut con, &user_key)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_user_init_user() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let username = "Manager_Test_User_1";
+        let chat_id = "manager_1234567892";
+        let user_id = "manager_987654321";
+
+        // Adds user and chat, check that user is keyed by ID
+        assert!(update_user(&redis, username, chat_id, Some(user_id))
+            .await
+            .is_ok());
+        assert!(get_user_is_init(&mut con, user_id).await.unwrap());
+        assert_eq!(
+            get_user_alias(&mut con, &username.to_lowercase())
+                .await
+                .unwrap(),
+            Some(user_id.to_string())
+        );
+        assert_eq!(
+            get_preferred_username(&mut con, user_id).await.unwrap(),
+            username
+        );
+        assert_eq!(
+            get_user_chats(&mut con, user_id).await.unwrap(),
+            vec![chat_id]
+        );
+
+        // Calls again without ID, should still resolve to the same user
+        assert!(update_user(&redis, username, chat_id, None).await.is_ok());
+        assert!(!get_user_exists(&mut con, &username.to_lowercase())
+            .await
+            .unwrap());
+
+        // Deletes user
+        delete_user(&mut con, user_id).await.unwrap();
+        delete_user_id(&mut con, user_id).await.unwrap();
+        delete_user_alias(&mut con, &username.to_lowercase())
+            .await
+            .unwrap();
+        delete_preferred_username(&mut con, user_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_user_update_username() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let username = "manager_test_user_2";
+        let chat_id = "manager_1234567893";
+        let user_id = "manager_987654322";
+        let second_username = "Manager_Test_User_3";
+
+        // Adds user and chat, check that user is init
+        assert!(update_user(&redis, username, chat_id, Some(user_id))
+            .await
+            .is_ok());
+        assert!(get_user_is_init(&mut con, user_id).await.unwrap());
+
+        // Calls again, updates username
+        assert!(update_user(&redis, second_username, chat_id, Some(user_id))
+            .await
+            .is_ok());
+        assert_eq!(
+            get_username(&mut con, user_id).await.unwrap(),
+            second_username
+        );
+        assert_eq!(
+            get_preferred_username(&mut con, user_id).await.unwrap(),
+            second_username
+        );
+
+        // Both usernames refer to the same user
+        assert_eq!(
+            get_user_alias(&mut con, username).await.unwrap(),
+            Some(user_id.to_string())
+        );
+        assert_eq!(
+            get_user_alias(&mut con, &second_username.to_lowercase())
+                .await
+                .unwrap(),
+            Some(user_id.to_string())
+        );
+
+        // Deletes user
+        delete_user(&mut con, user_id).await.unwrap();
+        delete_user_id(&mut con, user_id).await.unwrap();
+        delete_user_alias(&mut con, username).await.unwrap();
+        delete_user_alias(&mut con, &second_username.to_lowercase())
+            .await
+            .unwrap();
+        delete_preferred_username(&mut con, user_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_user_migrate_username() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let username = "manager_test_user_4";
+        let other_username = "manager_test_user_5";
+        let new_username = "manager_test_user_6";
+        let chat_id = "manager_12345678919";
+        let user_id = "manager_987654323";
+        let currency = "USD";
+
+        // Adds balances and spendings before the user ID is known
+        update_user(&redis, username, chat_id, None).await.unwrap();
+        update_user(&redis, other_username, chat_id, None)
+            .await
+            .unwrap();
+        update_chat(
+            &redis,
+            chat_id,
+            vec![username.to_string(), other_username.to_string()],
+        )
+        .await
+        .unwrap();
+        let changes = vec![
+            UserBalance {
+                username: username.to_string(),
+                currency: currency.to_string(),
+                balance: 1000,
+            },
+            UserBalance {
+                username: other_username.to_string(),
+                currency: currency.to_string(),
+                balance: -1000,
+            },
+        ];
+        update_chat_balances(&redis, chat_id, changes)
+            .await
+            .unwrap();
+        let spendings = vec![UserBalance {
+            username: username.to_string(),
+            currency: currency.to_string(),
+            balance: 1000,
+        }];
+        update_chat_spendings(&redis, chat_id, spendings)
+            .await
+            .unwrap();
+
+        // User is identified, records are moved to the user ID
+        update_user(&redis, username, chat_id, Some(user_id))
+            .await
+            .unwrap();
+        assert!(!get_user_exists(&mut con, username).await.unwrap());
+        assert_eq!(
+            get_chat_users(&mut con, chat_id).await.unwrap(),
+            vec![user_id.to_string(), other_username.to_string()]
+        );
+        assert_eq!(
+            get_balance(&mut con, chat_id, user_id, currency)
+                .await
+                .unwrap(),
+            1000
+        );
+        assert!(!get_balance_exists(&mut con, chat_id, username, currency)
+            .await
+            .unwrap());
+        assert_eq!(
+            get_spending(&mut con, chat_id, user_id, currency)
+                .await
+                .unwrap(),
+            1000
+        );
+
+        // User changes username, balances still shown under the new username
+        update_user(&redis, new_username, chat_id, Some(user_id))
+            .await
+            .unwrap();
+        let balances = get_chat_balances_currency(&redis, chat_id, currency)
+            .await
+            .unwrap();
+        assert!(balances.contains(&UserBalance {
+            username: new_username.to_string(),
+            currency: currency.to_string(),
+            balance: 1000,
+        }));
+
+        // Deletes everything
+        delete_chat(&mut con, chat_id).await.unwrap();
+        delete_chat_currencies(&mut con, chat_id).await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+        delete_balance(&mut con, chat_id, user_id, currency)
+            .await
+            .unwrap();
+        delete_balance(&mut con, chat_id, other_username, currency)
+            .await
+            .unwrap();
+        delete_spending(&mut con, chat_id, user_id, currency)
+            .await
+            .unwrap();
+        delete_user(&mut con, user_id).await.unwrap();
+        delete_user(&mut con, other_username).await.unwrap();
+        delete_user_id(&mut con, user_id).await.unwrap();
+        delete_user_alias(&mut con, username).await.unwrap();
+        delete_user_alias(&mut con, new_username).await.unwrap();
+        delete_preferred_username(&mut con, user_id).await.unwrap();
+        delete_preferred_username(&mut con, other_username)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_chat_member_username() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let chat_id = "manager_12345678994";
+        let user_id = "manager_987654324";
+        let username = "Manager_Member_User";
+        let other_username = "manager_member_user_0";
+
+        assert!(update_user(&redis, username, chat_id, Some(user_id))
+            .await
+            .is_ok());
+        assert!(update_user(&redis, other_username, chat_id, None)
+            .await
+            .is_ok());
+        assert!(update_chat(
+            &redis,
+            chat_id,
+            vec![username.to_string(), other_username.to_string()]
+        )
+        .await
+        .is_ok());
+
+        // Matched by user ID, regardless of username
+        assert_eq!(
+            get_chat_member_username(&redis, chat_id, user_id, None)
+                .await
+                .unwrap(),
+            Some(username.to_string())
+        );
+
+        // Matched by username, if user ID is not known
+        assert_eq!(
+            get_chat_member_username(&redis, chat_id, "manager_987654325", Some(other_username))
+                .await
+                .unwrap(),
+            Some(other_username.to_string())
+        );
+
+        // Not in chat
+        assert_eq!(
+            get_chat_member_username(
+                &redis,
+                chat_id,
+                "manager_987654325",
+                Some("manager_stranger")
+            )
+            .await
+            .unwrap(),
+            None
+        );
+        assert_eq!(
+            get_chat_member_username(&redis, chat_id, "manager_987654325", None)
+                .await
+                .unwrap(),
+            None
+        );
+
+        delete_chat(&mut con, chat_id).await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+        for user_key in [user_id, other_username] {
+            delete_user(&mut con, user_key).await.unwrap();
+            delete_preferred_username(&mut con, user_key).await.unwrap();
+        }
+        delete_user_id(&mut con, user_id).await.unwrap();
+        delete_user_alias(&mut con, &username.to_lowercase())
+            .await
+            .unwrap();
+        delete_user_alias(&mut con, other_username).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_chat_add_chat_users() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let chat_id = "manager_1234567894";
+        let mut usernames = vec![
+            "manager_test_user_4".to_string(),
+            "manager_test_user_5".to_string(),
+            "manager_test_user_6".to_string(),
+        ];
+        let more_usernames = vec![
+            "manager_test_user_7".to_string(),
+            "manager_test_user_8".to_string(),
+            "manager_test_user_9".to_string(),
+        ];
+
+        // Check that chat does not exist
+        assert!(!get_chat_exists(&mut con, chat_id).await.unwrap());
+
+        // Add chat with first group of usernames
+        assert!(update_chat(&redis, chat_id, usernames.clone())
+            .await
+            .is_ok());
+        assert!(get_chat_exists(&mut con, chat_id).await.unwrap());
+        assert_eq!(
+            get_chat_users(&mut con, chat_id).await.unwrap(),
+            vec![
+                "manager_test_user_4".to_string(),
+                "manager_test_user_5".to_string(),
+                "manager_test_user_6".to_string(),
+            ]
+        );
+        assert_eq!(
+            get_time_zone(&redis, chat_id).await.unwrap(),
+            "UTC".to_string()
+        );
+        assert_eq!(
+            get_default_currency(&redis, chat_id).await.unwrap(),
+            CURRENCY_CODE_DEFAULT.to_string()
+        );
+        assert!(!get_currency_conversion(&redis, chat_id).await.unwrap());
+
+        // Call again, add both groups of usernames
+        usernames.extend(more_usernames.clone());
+        assert!(update_chat(&redis, chat_id, usernames.clone())
+            .await
+            .is_ok());
+        assert!(get_chat_exists(&mut con, chat_id).await.unwrap());
+        assert_eq!(
+            get_chat_users(&mut con, chat_id).await.unwrap(),
+            vec![
+                "manager_test_user_4".to_string(),
+                "manager_test_user_5".to_string(),
+                "manager_test_user_6".to_string(),
+                "manager_test_user_7".to_string(),
+                "manager_test_user_8".to_string(),
+                "manager_test_user_9".to_string(),
+            ]
+        );
+
+        // Call again, nothing should happen
+        assert!(update_chat(&redis, chat_id, usernames).await.is_ok());
+        assert!(get_chat_exists(&mut con, chat_id).await.unwrap());
+        assert_eq!(
+            get_chat_users(&mut con, chat_id).await.unwrap(),
+            vec![
+                "manager_test_user_4".to_string(),
+                "manager_test_user_5".to_string(),
+                "manager_test_user_6".to_string(),
+                "manager_test_user_7".to_string(),
+                "manager_test_user_8".to_string(),
+                "manager_test_user_9".to_string(),
+            ]
+        );
+
+        // Deletes chat
+        delete_chat(&mut con, chat_id).await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_chat_usernames() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let chat_id = "manager_12345678917";
+        let usernames = vec![
+            "Manager_Test_User_20".to_string(),
+            "Manager_Test_User_21".to_string(),
+        ];
+
+        // Adds users and chat
+        for username in &usernames {
+            assert!(update_user(&redis, username, chat_id, None).await.is_ok());
+        }
+        assert!(update_chat(&redis, chat_id, usernames.clone())
+            .await
+            .is_ok());
+
+        // Preferred usernames are retrieved
+        assert_eq!(
+            get_chat_usernames(&redis, chat_id).await.unwrap(),
+            usernames
+        );
+
+        // Deletes users and chat
+        for username in &usernames {
+            let user_key = username.to_lowercase();
+            delete_user(&mut con, &user_key).await.unwrap();
+            delete_preferred_username(&mut con, &user_key)
+                .await
+                .unwrap();
+        }
+        delete_chat(&mut con, chat_id).await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_update_delete_payment_details() {
+        let redis = Redis::new();
+        let chat_id = "manager_1234567895";
+        let payment = Payment {
+            description: "manager_test_payment".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user_10".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![
+                ("manager_test_user_11".to_string(), 5000),
+                ("manager_test_user_12".to_string(), 5000),
+            ],
+        };
+
+        // Adds payment
+        assert!(add_payment_entry(&redis, chat_id, &payment).await.is_ok());
+
+        let second_payment = Payment {
+            description: "manager_test_payment_2".to_string(),
+            datetime: "2021-01-01T00:00:01".to_string(),
+            creditor: "manager_test_user_13".to_string(),
+            currency: "USD".to_string(),
+            total: 20000,
+            debts: vec![
+                ("manager_test_user_14".to_string(), 10000),
+                ("manager_test_user_15".to_string(), 10000),
+            ],
+        };
+
+        // Adds second payment
+        assert!(add_payment_entry(&redis, chat_id, &second_payment)
+            .await
+            .is_ok());
+
+        // Gets both payments
+        let payments = get_chat_payments_details(&redis, chat_id).await.unwrap();
+        let second_id = payments[0].payment_id.clone();
+
+        // Gets second payment by ID
+        let second_payment_values = get_payment_entry(&redis, &second_id).await;
+        assert_eq!(second_payment_values.unwrap(), second_payment);
+
+        // Updates second payment
+        let updated_description = "manager_test_payment_3";
+        let updated_creditor = "manager_test_user_16";
+        let updated_currency = "JPY";
+        let updated_total = 30000;
+        let updated_debts = vec![
+            ("manager_test_user_17".to_string(), 15000),
+            ("manager_test_user_18".to_string(), 15000),
+        ];
+
+        assert!(update_payment_entry(
+            &redis,
+            &second_id,
+            Some(updated_description),
+            None,
+            Some(updated_creditor),
+            Some(updated_currency),
+            Some(&updated_total),
+            Some(updated_debts.clone()),
+        )
+        .await
+        .is_ok());
+
+        // Gets both payments again
+        let payments = get_chat_payments_details(&redis, chat_id).await.unwrap();
+        assert_eq!(
+            payments,
+            vec![
+                UserPayment {
+                    chat_id: chat_id.to_string(),
+                    payment_id: second_id.clone(),
+                    payment: Payment {
+                        description: updated_description.to_string(),
+                        datetime: "2021-01-01T00:00:01".to_string(),
+                        creditor: updated_creditor.to_string(),
+                        currency: updated_currency.to_string(),
+                        total: updated_total,
+                        debts: updated_debts.clone(),
+                    },
+                    rate: None,
+                    audit: PaymentAudit::default(),
+                    is_private: false,
+                    is_transfer: false,
+                    is_settled: false,
+                    is_disputed: false,
+                },
+                UserPayment {
+                    chat_id: chat_id.to_string(),
+                    payment_id: payments[1].payment_id.clone(),
+                    payment,
+                    rate: None,
+                    audit: PaymentAudit::default(),
+                    is_private: false,
+                    is_transfer: false,
+                    is_settled: false,
+                    is_disputed: false,
+                },
+            ]
+        );
+
+        // Deletes both payments
+        assert!(
+            delete_payment_entry(&redis, chat_id, &payments[0].payment_id)
+                .await
+                .is_ok()
+        );
+        assert!(
+            delete_payment_entry(&redis, chat_id, &payments[1].payment_id)
+                .await
+                .is_ok()
+        );
+    }
+
+    // Stress test for balances updated by many payments at once
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore = "requires Redis"]
+    async fn test_update_chat_balances_concurrent() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678998";
+        let creditor = "manager_test_user_61";
+        let debtor = "manager_test_user_62";
+
+        let tasks: Vec<_> = (0..50)
+            .map(|_| {
+                let redis = redis.clone();
+                tokio::spawn(async move {
+                    let changes = vec![
+                        UserBalance {
+                            username: creditor.to_string(),
+                            currency: "USD".to_string(),
+                            balance: 100,
+                        },
+                        UserBalance {
+                            username: debtor.to_string(),
+                            currency: "USD".to_string(),
+                            balance: -100,
+                        },
+                    ];
+                    update_chat_balances(&redis, chat_id, changes).await
+                })
+            })
+            .collect();
+        for task in tasks {
+            assert!(task.await.unwrap().is_ok());
+        }
+
+        // No updates are lost, and the currency is only added once
+        let mut con = redis.connect().await.unwrap();
+        assert_eq!(
+            get_balance(&mut con, chat_id, creditor, "USD")
+                .await
+                .unwrap(),
+            5000
+        );
+        assert_eq!(
+            get_balance(&mut con, chat_id, debtor, "USD").await.unwrap(),
+            -5000
+        );
+        assert_eq!(
+            get_chat_currencies(&mut con, chat_id).await.unwrap(),
+            vec!["USD".to_string()]
+        );
+
+        // Deletes balances and currencies
+        delete_balance(&mut con, chat_id, creditor, "USD")
+            .await
+            .unwrap();
+        delete_balance(&mut con, chat_id, debtor, "USD")
+            .await
+            .unwrap();
+        delete_chat_currencies(&mut con, chat_id).await.unwrap();
+    }
+
+    // Stress test for payments, balances and spendings added by many users at once
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore = "requires Redis"]
+    async fn test_add_payments_concurrent() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678999";
+        let creditor = "manager_test_user_63";
+        let debtor = "manager_test_user_64";
+
+        let tasks: Vec<_> = (0..50)
+            .map(|i| {
+                let redis = redis.clone();
+                tokio::spawn(async move {
+                    let payment = Payment {
+                        description: format!("manager_test_payment_{i}"),
+                        datetime: "2021-01-01T00:00:00".to_string(),
+                        creditor: creditor.to_string(),
+                        currency: "USD".to_string(),
+                        total: 200,
+                        debts: vec![(creditor.to_string(), 100), (debtor.to_string(), 100)],
+                    };
+                    add_payment_entry(&redis, chat_id, &payment).await?;
+
+                    let spendings: Vec<UserBalance> = payment
+                        .debts
+                        .iter()
+                        .map(|(user, amount)| UserBalance {
+                            username: user.to_string(),
+                            currency: payment.currency.clone(),
+                            balance: *amount,
+                        })
+                        .collect();
+                    update_chat_spendings(&redis, chat_id, spendings).await?;
+
+                    let changes = vec![UserBalance {
+                        username: debtor.to_string(),
+                        currency: payment.currency.clone(),
+                        balance: -100,
+                    }];
+                    update_chat_balances(&redis, chat_id, changes).await
+                })
+            })
+            .collect();
+        for task in tasks {
+            assert!(task.await.unwrap().is_ok());
+        }
+
+        // All payments are added, with no updates to balances and spendings lost
+        let payments = get_chat_payments_details(&redis, chat_id).await.unwrap();
+        assert_eq!(payments.len(), 50);
+        let mut con = redis.connect().await.unwrap();
+        assert_eq!(
+            get_balance(&mut con, chat_id, debtor, "USD").await.unwrap(),
+            -5000
+        );
+        assert_eq!(
+            get_spending(&mut con, chat_id, creditor, "USD")
+                .await
+                .unwrap(),
+            5000
+        );
+        assert_eq!(
+            get_spending(&mut con, chat_id, debtor, "USD")
+                .await
+                .unwrap(),
+            5000
+        );
+
+        // A spending that would become negative is not applied
+        let spendings = vec![UserBalance {
+            username: debtor.to_string(),
+            currency: "USD".to_string(),
+            balance: -6000,
+        }];
+        assert!(update_chat_spendings(&redis, chat_id, spendings)
+            .await
+            .is_err());
+        assert_eq!(
+            get_spending(&mut con, chat_id, debtor, "USD")
+                .await
+                .unwrap(),
+            5000
+        );
+
+        // Deletes payments, balances, spendings and currencies
+        for payment in payments {
+            delete_payment_entry(&redis, chat_id, &payment.payment_id)
+                .await
+                .unwrap();
+        }
+        delete_balance(&mut con, chat_id, debtor, "USD")
+            .await
+            .unwrap();
+        delete_spending(&mut con, chat_id, creditor, "USD")
+            .await
+            .unwrap();
+        delete_spending(&mut con, chat_id, debtor, "USD")
+            .await
+            .unwrap();
+        delete_chat_currencies(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_frequent_currencies() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678_frequent";
+        let creditor = "manager_test_user_81";
+
+        for currency in ["EUR", "USD", CURRENCY_CODE_DEFAULT, "USD", "JPY"] {
+            let payment = Payment {
+                description: "manager_test_payment".to_string(),
+                datetime: "2021-01-01T00:00:00".to_string(),
+                creditor: creditor.to_string(),
+                currency: currency.to_string(),
+                total: 100,
+                debts: vec![(creditor.to_string(), 100)],
+            };
+            add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        }
+
+        // Currencies are ranked by usage, without the default currency
+        assert_eq!(
+            get_frequent_currencies(&redis, chat_id, 5).await.unwrap(),
+            vec!["USD", "EUR", "JPY"]
+        );
+        assert_eq!(
+            get_frequent_currencies(&redis, chat_id, 1).await.unwrap(),
+            vec!["USD"]
+        );
+
+        for payment in get_chat_payments_details(&redis, chat_id).await.unwrap() {
+            delete_payment_entry(&redis, chat_id, &payment.payment_id)
+                .await
+                .unwrap();
+        }
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_currency_usage(&mut con, chat_id).await.unwrap();
+    }
+
+    // Test for empty payments
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_no_payments_found() {
+        let redis = Redis::new();
+        let chat_id = "manager_1234567898";
+
+        let payment = Payment {
+            description: "manager_test_user_20".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user_21".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![
+                ("manager_test_user_22".to_string(), 5000),
+                ("manager_test_user_23".to_string(), 5000),
+            ],
+        };
+
+        // Checks that payments don't exist
+        assert_eq!(
+            get_chat_payments_details(&redis, chat_id)
+                .await
+                .unwrap_err(),
+            CrudError::NoPaymentsError()
+        );
+
+        // Adds chat payment
+        assert!(add_payment_entry(&redis, chat_id, &payment).await.is_ok());
+
+        // Updates fake payment, should fail
+        assert_eq!(
+            update_payment_entry(
+                &redis,
+                "nonexistent_payment",
+                Some("manager_test_payment_3"),
+                None,
+                Some("manager_test_user_16"),
+                Some("JPY"),
+                Some(&30000),
+                Some(vec![
+                    ("manager_test_user_17".to_string(), 15000),
+                    ("manager_test_user_18".to_string(), 15000),
+                ]),
+            )
+            .await
+            .unwrap_err(),
+            CrudError::NoSuchPaymentError()
+        );
+
+        // Deletes fake payment, should fail
+        assert_eq!(
+            delete_payment_entry(&redis, chat_id, "nonexistent_payment")
+                .await
+                .unwrap_err(),
+            CrudError::NoSuchPaymentError()
+        );
+
+        // Deletes actual payment
+        let payments = get_chat_payments_details(&redis, chat_id).await.unwrap();
+        assert!(
+            delete_payment_entry(&redis, chat_id, &payments[0].payment_id)
+                .await
+                .is_ok()
+        );
+
+        // Checks that payments don't exist
+        assert_eq!(
+            get_chat_payments_details(&redis, chat_id)
+                .await
+                .unwrap_err(),
+            CrudError::NoPaymentsError()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_retrieve_balances() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let chat_id = "manager_1234567898";
+
+        // Add users to chat
+        let usernames = vec![
+            "manager_test_user_20".to_string(),
+            "manager_test_user_21".to_string(),
+            "manager_test_user_22".to_string(),
+        ];
+        update_chat(&redis, chat_id, usernames.clone())
+            .await
+            .unwrap();
+
+        for username in &usernames {
+            update_user(&redis, username, chat_id, None).await.unwrap();
+        }
+
+        let changes = vec![
+            UserBalance {
+                username: "manager_test_user_20".to_string(),
+                balance: 10000,
+                currency: "USD".to_string(),
+            },
+            UserBalance {
+                username: "manager_test_user_21".to_string(),
+                balance: -5000,
+                currency: "USD".to_string(),
+            },
+            UserBalance {
+                username: "manager_test_user_22".to_string(),
+                balance: -5000,
+                currency: "USD".to_string(),
+            },
+        ];
+
+        // Adds initial balances
+        assert!(update_chat_balances(&redis, chat_id, changes.clone())
+            .await
+            .is_ok());
+        let initial_balances = get_chat_balances(&redis, chat_id).await.unwrap();
+
+        // Checks that balances are correct
+        assert_eq!(
+            initial_balances,
+            vec![vec![
+                UserBalance {
+                    username: "manager_test_user_20".to_string(),
+                    balance: 10000,
+                    currency: "USD".to_string(),
+                },
+                UserBalance {
+                    username: "manager_test_user_21".to_string(),
+                    balance: -5000,
+                    currency: "USD".to_string(),
+                },
+                UserBalance {
+                    username: "manager_test_user_22".to_string(),
+                    balance: -5000,
+                    currency: "USD".to_string(),
+                },
+            ]]
+        );
+
+        // Updates balances
+        let new_changes = vec![
+            UserBalance {
+                username: "manager_test_user_20".to_string(),
+                balance: -5000,
+                currency: "USD".to_string(),
+            },
+            UserBalance {
+                username: "manager_test_user_21".to_string(),
+                balance: -5000,
+                currency: "USD".to_string(),
+            },
+            UserBalance {
+                username: "manager_test_user_22".to_string(),
+                balance: 5000,
+                currency: "USD".to_string(),
+            },
+        ];
+
+        assert!(update_chat_balances(&redis, chat_id, new_changes.clone())
+            .await
+            .is_ok());
+        let new_balances = get_chat_balances(&redis, chat_id).await.unwrap();
+
+        // Checks that balances are correct
+        assert_eq!(
+            new_balances,
+            vec![vec![
+                UserBalance {
+                    username: "manager_test_user_20".to_string(),
+                    balance: 5000,
+                    currency: "USD".to_string(),
+                },
+                UserBalance {
+                    username: "manager_test_user_21".to_string(),
+                    balance: -10000,
+                    currency: "USD".to_string(),
+                },
+            ]]
+        );
+
+        // Deletes balances
+        for balance in &initial_balances[0] {
+            delete_balance(&mut con, chat_id, &balance.username, "USD")
+                .await
+                .unwrap();
+        }
+
+        // Deletes usernames
+        for username in &usernames {
+            delete_user(&mut con, username).await.unwrap();
+            delete_preferred_username(&mut con, username).await.unwrap();
+        }
+
+        // Deletes chat
+        delete_chat(&mut con, chat_id).await.unwrap();
+        delete_chat_currencies(&mut con, chat_id).await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_chat_balances_settlement() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let chat_id = "manager_settlement_123456789";
+        let usernames = vec![
+            "manager_test_user_settle_1".to_string(),
+            "manager_test_user_settle_2".to_string(),
+        ];
+        update_chat(&redis, chat_id, usernames.clone())
+            .await
+            .unwrap();
+        for username in &usernames {
+            update_user(&redis, username, chat_id, None).await.unwrap();
+        }
+
+        let change = |username: &str, balance: i64| UserBalance {
+            username: username.to_string(),
+            balance,
+            currency: "USD".to_string(),
+        };
+
+        // Owing each other is not settled
+        update_chat_balances(
+            &redis,
+            chat_id,
+            vec![change(&usernames[0], 2000), change(&usernames[1], -2000)],
+        )
+        .await
+        .unwrap();
+        assert!(get_settlements(&mut con, chat_id).await.unwrap().is_empty());
+
+        // Paying back settles the currency
+        update_chat_balances(
+            &redis,
+            chat_id,
+            vec![change(&usernames[0], -2000), change(&usernames[1], 2000)],
+        )
+        .await
+        .unwrap();
+        let settlements = get_settlements(&mut con, chat_id).await.unwrap();
+        assert_eq!(settlements.len(), 1);
+        assert!(settlements.contains_key("USD"));
+
+        // Deletes balances, settlements and usernames
+        for username in &usernames {
+            delete_balance(&mut con, chat_id, username, "USD")
+                .await
+                .unwrap();
+            delete_user(&mut con, username).await.unwrap();
+            delete_preferred_username(&mut con, username).await.unwrap();
+        }
+        delete_settlements(&mut con, chat_id).await.unwrap();
+
+        // Deletes chat
+        delete_chat(&mut con, chat_id).await.unwrap();
+        delete_chat_currencies(&mut con, chat_id).await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
 
     #[test]
-    fn test_add_get_update_delete_payment_details() {
-        let chat_id = "manager_1234567895";
+    fn test_is_payment_settled() {
+        let payment = Payment {
+            description: "Dinner".to_string(),
+            datetime: "2024-03-10 12:00:00 UTC".to_string(),
+            creditor: "alice".to_string(),
+            currency: "USD".to_string(),
+            total: 2000,
+            debts: vec![("bob".to_string(), 2000)],
+        };
+        let settlements =
+            HashMap::from([("USD".to_string(), "2024-03-12 10:00:00 UTC".to_string())]);
+
+        // Payments without an audit fall back to their date
+        let audit = PaymentAudit::default();
+        assert!(is_payment_settled(&payment, &audit, &settlements));
+        assert!(!is_payment_settled(&payment, &audit, &HashMap::new()));
+
+        // Payments added after the settlement are not settled
+        let audit = PaymentAudit {
+            created_at: Some("2024-03-12 11:00:00 UTC".to_string()),
+            ..PaymentAudit::default()
+        };
+        assert!(!is_payment_settled(&payment, &audit, &settlements));
+
+        // Payments edited after the settlement are not settled
+        let audit = PaymentAudit {
+            created_at: Some("2024-03-11 11:00:00 UTC".to_string()),
+            last_edited_at: Some("2024-03-13 09:00:00 UTC".to_string()),
+            ..PaymentAudit::default()
+        };
+        assert!(!is_payment_settled(&payment, &audit, &settlements));
+
+        // Payments in other currencies are not settled
+        let payment = Payment {
+            currency: "SGD".to_string(),
+            ..payment
+        };
+        assert!(!is_payment_settled(
+            &payment,
+            &PaymentAudit::default(),
+            &settlements
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_multiple_currencies_balances() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let chat_id = "manager_1234567899";
+
+        // Add users to chat
+        let usernames = vec![
+            "manager_test_user_26".to_string(),
+            "manager_test_user_27".to_string(),
+            "manager_test_user_28".to_string(),
+        ];
+        update_chat(&redis, chat_id, usernames.clone())
+            .await
+            .unwrap();
+
+        for username in &usernames {
+            update_user(&redis, username, chat_id, None).await.unwrap();
+        }
+
+        // Add first changes
+        let changes = vec![
+            UserBalance {
+                username: "manager_test_user_26".to_string(),
+                balance: 10000,
+                currency: "USD".to_string(),
+            },
+            UserBalance {
+                username: "manager_test_user_27".to_string(),
+                balance: -5000,
+                currency: "JPY".to_string(),
+            },
+            UserBalance {
+                username: "manager_test_user_28".to_string(),
+                balance: 10000,
+                currency: "JPY".to_string(),
+            },
+        ];
+
+        update_chat_balances(&redis, chat_id, changes.clone())
+            .await
+            .unwrap();
+
+        // Add second changes
+        let new_changes = vec![
+            UserBalance {
+                username: "manager_test_user_26".to_string(),
+                balance: -5000,
+                currency: "JPY".to_string(),
+            },
+            UserBalance {
+                username: "manager_test_user_27".to_string(),
+                balance: -5000,
+                currency: "USD".to_string(),
+            },
+            UserBalance {
+                username: "manager_test_user_28".to_string(),
+                balance: -5000,
+                currency: "USD".to_string(),
+            },
+        ];
+
+        assert!(update_chat_balances(&redis, chat_id, new_changes.clone())
+            .await
+            .is_ok());
+        let new_balances = get_chat_balances(&redis, chat_id).await.unwrap();
+
+        // Check balances
+        let balances = vec![
+            vec![
+                UserBalance {
+                    username: "manager_test_user_26".to_string(),
+                    balance: 10000,
+                    currency: "USD".to_string(),
+                },
+                UserBalance {
+                    username: "manager_test_user_27".to_string(),
+                    balance: -5000,
+                    currency: "USD".to_string(),
+                },
+                UserBalance {
+                    username: "manager_test_user_28".to_string(),
+                    balance: -5000,
+                    currency: "USD".to_string(),
+                },
+            ],
+            vec![
+                UserBalance {
+                    username: "manager_test_user_26".to_string(),
+                    balance: -5000,
+                    currency: "JPY".to_string(),
+                },
+                UserBalance {
+                    username: "manager_test_user_27".to_string(),
+                    balance: -5000,
+                    currency: "JPY".to_string(),
+                },
+                UserBalance {
+                    username: "manager_test_user_28".to_string(),
+                    balance: 10000,
+                    currency: "JPY".to_string(),
+                },
+            ],
+        ];
+
+        assert_eq!(new_balances, balances);
+
+        // Checks balances for individual currency
+        let balances_usd = get_chat_balances_currency(&redis, chat_id, "USD")
+            .await
+            .unwrap();
+        assert_eq!(
+            balances_usd,
+            vec![
+                UserBalance {
+                    username: "manager_test_user_26".to_string(),
+                    balance: 10000,
+                    currency: "USD".to_string(),
+                },
+                UserBalance {
+                    username: "manager_test_user_27".to_string(),
+                    balance: -5000,
+                    currency: "USD".to_string(),
+                },
+                UserBalance {
+                    username: "manager_test_user_28".to_string(),
+                    balance: -5000,
+                    currency: "USD".to_string(),
+                },
+            ]
+        );
+        let balances_jpy = get_chat_balances_currency(&redis, chat_id, "JPY")
+            .await
+            .unwrap();
+        assert_eq!(
+            balances_jpy,
+            vec![
+                UserBalance {
+                    username: "manager_test_user_26".to_string(),
+                    balance: -5000,
+                    currency: "JPY".to_string(),
+                },
+                UserBalance {
+                    username: "manager_test_user_27".to_string(),
+                    balance: -5000,
+                    currency: "JPY".to_string(),
+                },
+                UserBalance {
+                    username: "manager_test_user_28".to_string(),
+                    balance: 10000,
+                    currency: "JPY".to_string(),
+                },
+            ]
+        );
+
+        // Deletes balances
+        for current in balances {
+            for balance in current {
+                delete_balance(&mut con, chat_id, &balance.username, &balance.currency)
+                    .await
+                    .unwrap();
+            }
+        }
+
+        // Deletes usernames
+        for username in &usernames {
+            delete_user(&mut con, username).await.unwrap();
+            delete_preferred_username(&mut con, username).await.unwrap();
+        }
+
+        // Deletes chat
+        delete_chat(&mut con, chat_id).await.unwrap();
+        delete_chat_currencies(&mut con, chat_id).await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_set_chat_settings() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let chat_id = "manager_12345678991";
+        let usernames = vec![
+            "manager_test_user_29".to_string(),
+            "manager_test_user_30".to_string(),
+            "manager_test_user_31".to_string(),
+        ];
+
+        // Checks "default" chat settings
+        assert_eq!(
+            get_time_zone(&redis, chat_id).await.unwrap(),
+            "UTC".to_string()
+        );
+        assert_eq!(
+            get_default_currency(&redis, chat_id).await.unwrap(),
+            CURRENCY_CODE_DEFAULT.to_string()
+        );
+        assert!(!get_currency_conversion(&redis, chat_id).await.unwrap());
+
+        // Adds chat
+        assert!(update_chat(&redis, chat_id, usernames.clone())
+            .await
+            .is_ok());
+        assert_eq!(
+            get_time_zone(&redis, chat_id).await.unwrap(),
+            "UTC".to_string()
+        );
+        assert_eq!(
+            get_default_currency(&redis, chat_id).await.unwrap(),
+            CURRENCY_CODE_DEFAULT.to_string()
+        );
+        assert!(!get_currency_conversion(&redis, chat_id).await.unwrap());
+
+        // Sets various chat settings
+        let time_zone = "GMT";
+        let currency = "USD";
+        let conversion = true;
+
+        assert!(set_time_zone(&redis, chat_id, time_zone).await.is_ok());
+        assert_eq!(get_time_zone(&redis, chat_id).await.unwrap(), time_zone);
+        assert!(set_default_currency(&redis, chat_id, currency)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_default_currency(&redis, chat_id).await.unwrap(),
+            currency
+        );
+        assert!(set_currency_conversion(&redis, chat_id, conversion)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_currency_conversion(&redis, chat_id).await.unwrap(),
+            conversion
+        );
+
+        // Deletes chat
+        delete_chat(&mut con, chat_id).await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_retrieve_chat_spendings() {
+        let redis = Redis::new();
+        let mut con = redis.connect().await.unwrap();
+
+        let chat_id = "manager_12345678992";
+        let usernames = vec![
+            "manager_test_user_32".to_string(),
+            "manager_test_user_33".to_string(),
+            "manager_test_user_34".to_string(),
+        ];
+
+        // Adds chat
+        assert!(update_chat(&redis, chat_id, usernames.clone())
+            .await
+            .is_ok());
+
+        for username in &usernames {
+            update_user(&redis, username, chat_id, None).await.unwrap();
+        }
+
+        // Adds spendings
+        let spendings = vec![
+            UserBalance {
+                username: "manager_test_user_32".to_string(),
+                balance: 10000,
+                currency: "USD".to_string(),
+            },
+            UserBalance {
+                username: "manager_test_user_33".to_string(),
+                balance: 5000,
+                currency: "USD".to_string(),
+            },
+            UserBalance {
+                username: "manager_test_user_34".to_string(),
+                balance: 3000,
+                currency: "USD".to_string(),
+            },
+        ];
+
+        assert!(update_chat_spendings(&redis, chat_id, spendings.clone())
+            .await
+            .is_ok());
+
+        // Manually add currency
+        assert!(add_chat_currency(&mut con, chat_id, "USD").await.is_ok());
+
+        // Retrieves spendings
+        let retrieved_spendings = retrieve_chat_spendings_currency(&redis, chat_id, "USD").await;
+        assert!(retrieved_spendings.is_ok());
+        assert_eq!(retrieved_spendings.unwrap(), spendings.clone());
+
+        // Updates spendings with another currency
+        let new_spendings = vec![
+            UserBalance {
+                username: "manager_test_user_32".to_string(),
+                balance: 500,
+                currency: "JPY".to_string(),
+            },
+            UserBalance {
+                username: "manager_test_user_33".to_string(),
+                balance: 1000,
+                currency: "JPY".to_string(),
+            },
+            UserBalance {
+                username: "manager_test_user_34".to_string(),
+                balance: 1500,
+                currency: "JPY".to_string(),
+            },
+        ];
+
+        assert!(
+            update_chat_spendings(&redis, chat_id, new_spendings.clone())
+                .await
+                .is_ok()
+        );
+
+        // Manually add currency
+        assert!(add_chat_currency(&mut con, chat_id, "JPY").await.is_ok());
+
+        // Retrieves and checks spendings again
+        let retrieved_spendings = retrieve_chat_spendings_currency(&redis, chat_id, "JPY").await;
+        assert!(retrieved_spendings.is_ok());
+        assert_eq!(retrieved_spendings.unwrap(), new_spendings.clone());
+        let retrieved_spendings = retrieve_chat_spendings_currency(&redis, chat_id, "USD").await;
+        assert_eq!(retrieved_spendings.unwrap(), spendings.clone());
+        let retrieved_spendings = retrieve_chat_spendings(&redis, chat_id).await;
+        assert_eq!(
+            retrieved_spendings.unwrap(),
+            vec![spendings.clone(), new_spendings.clone()]
+        );
+
+        // Checks validity of currencies
+        let valid_currencies = get_valid_chat_currencies(&redis, chat_id).await;
+        assert!(valid_currencies.is_ok());
+        assert_eq!(
+            valid_currencies.unwrap(),
+            vec!["USD".to_string(), "JPY".to_string()]
+        );
+
+        // Deletes spendings
+        for spending in spendings {
+            assert!(
+                delete_spending(&mut con, chat_id, &spending.username, &spending.currency)
+                    .await
+                    .is_ok()
+            );
+        }
+
+        for spending in new_spendings {
+            assert!(
+                delete_spending(&mut con, chat_id, &spending.username, &spending.currency)
+                    .await
+                    .is_ok()
+            );
+        }
+
+        // Deletes usernames
+        for username in &usernames {
+            delete_user(&mut con, username).await.unwrap();
+            delete_preferred_username(&mut con, username).await.unwrap();
+        }
+
+        // Deletes chat
+        delete_chat(&mut con, chat_id).await.unwrap();
+        delete_chat_currencies(&mut con, chat_id).await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_request_limit() {
+        let redis = Redis::new();
+        let user_id = "manager_test_user_35";
+
+        // Checks that request limit is not exceeded
+        assert!(!is_request_limit_exceeded(&redis, user_id, 1).await.unwrap());
+
+        // Checks that request limit is exceeded
+        assert!(is_request_limit_exceeded(&redis, user_id, 1).await.unwrap());
+
+        // Checks that request limit is not exceeded
+        assert!(!is_request_limit_exceeded(&redis, user_id, 2).await.unwrap());
+
+        // Deletes request
+        let mut con = redis.connect().await.unwrap();
+        delete_request(&mut con, user_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_archive_payment_entries_adjustment() {
+        let redis = Redis::new();
+        let chat_id = "manager_1234567898";
         let payment = Payment {
             description: "manager_test_payment".to_string(),
             datetime: "2021-01-01T00:00:00".to_string(),
-            creditor: "manager_test_user_10".to_string(),
+            creditor: "manager_test_user_30".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![("manager_test_user_31".to_string(), 10000)],
+        };
+
+        // Adds payment, then archives it
+        assert!(add_payment_entry(&redis, chat_id, &payment).await.is_ok());
+        let payment_id = get_chat_payments_details(&redis, chat_id).await.unwrap()[0]
+            .payment_id
+            .clone();
+        assert!(!is_adjustment_entry(&redis, &payment_id).await.unwrap());
+        assert!(
+            archive_payment_entries(&redis, chat_id, "2021-01", vec![payment_id.clone()])
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            get_chat_payments_details(&redis, chat_id).await,
+            Err(CrudError::NoPaymentsError())
+        );
+
+        let mut con = redis.connect().await.unwrap();
+        assert_eq!(
+            get_archive_payments(&mut con, chat_id, "2021-01")
+                .await
+                .unwrap(),
+            vec![payment_id.clone()]
+        );
+
+        // Adds adjustment entry
+        assert!(add_adjustment_entry(&redis, chat_id, &payment)
+            .await
+            .is_ok());
+        let adjustment_id = get_chat_payments_details(&redis, chat_id).await.unwrap()[0]
+            .payment_id
+            .clone();
+        assert!(is_adjustment_entry(&redis, &adjustment_id).await.unwrap());
+
+        // Deletes everything
+        assert!(delete_payment_entry(&redis, chat_id, &adjustment_id)
+            .await
+            .is_ok());
+        assert!(!is_adjustment_entry(&redis, &adjustment_id).await.unwrap());
+        delete_payment(&mut con, &payment_id).await.unwrap();
+        delete_archives(&mut con, chat_id).await.unwrap();
+        delete_archive_payments(&mut con, chat_id, "2021-01")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_ledger_payments_replace_ledger_balances() {
+        let redis = Redis::new();
+        let chat_id = "manager_recalculate_123456789";
+        let creditor = "manager_test_user_68";
+        let debtor = "manager_test_user_69";
+        let payment = Payment {
+            description: "manager_test_payment".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: creditor.to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![(debtor.to_string(), 10000)],
+        };
+
+        // Adds a payment and archives it, then carries it forward with an adjustment
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        archive_payment_entries(&redis, chat_id, "2021-01", vec![payment_id.clone()])
+            .await
+            .unwrap();
+        add_adjustment_entry(&redis, chat_id, &payment)
+            .await
+            .unwrap();
+        let adjustment_id = get_chat_payments_details(&redis, chat_id).await.unwrap()[0]
+            .payment_id
+            .clone();
+
+        assert_eq!(
+            get_chat_ledgers(&redis, chat_id).await.unwrap(),
+            vec![chat_id.to_string()]
+        );
+        assert_eq!(
+            get_ledger_payments(&redis, chat_id, chat_id).await.unwrap(),
+            vec![
+                LedgerPayment {
+                    payment: payment.clone(),
+                    adjustment: false,
+                    archived: true,
+                    transfer: false,
+                },
+                LedgerPayment {
+                    payment: payment.clone(),
+                    adjustment: true,
+                    archived: false,
+                    transfer: false,
+                },
+            ]
+        );
+
+        // Replaces balances and spendings, combining amounts of the same user
+        let mut con = redis.connect().await.unwrap();
+        set_balance(&mut con, chat_id, debtor, "EUR", 500)
+            .await
+            .unwrap();
+        let balances = vec![
+            UserBalance {
+                username: creditor.to_string(),
+                currency: "USD".to_string(),
+                balance: 10000,
+            },
+            UserBalance {
+                username: debtor.to_uppercase(),
+                currency: "USD".to_string(),
+                balance: -4000,
+            },
+            UserBalance {
+                username: debtor.to_string(),
+                currency: "USD".to_string(),
+                balance: -6000,
+            },
+        ];
+        let spendings = vec![UserBalance {
+            username: debtor.to_string(),
+            currency: "USD".to_string(),
+            balance: 10000,
+        }];
+        assert!(
+            replace_ledger_balances(&redis, chat_id, balances, spendings)
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            get_balance(&mut con, chat_id, creditor, "USD")
+                .await
+                .unwrap(),
+            10000
+        );
+        assert_eq!(
+            get_balance(&mut con, chat_id, debtor, "USD").await.unwrap(),
+            -10000
+        );
+        assert_eq!(
+            get_spending(&mut con, chat_id, debtor, "USD")
+                .await
+                .unwrap(),
+            10000
+        );
+        assert!(!get_spending_exists(&mut con, chat_id, creditor, "USD")
+            .await
+            .unwrap());
+
+        // Deletes everything
+        delete_ledger(&mut con, chat_id).await.unwrap();
+        delete_balance(&mut con, chat_id, debtor, "EUR")
+            .await
+            .unwrap();
+        delete_payment(&mut con, &payment_id).await.unwrap();
+        delete_payment(&mut con, &adjustment_id).await.unwrap();
+        delete_adjustment(&mut con, &adjustment_id).await.unwrap();
+        delete_archives(&mut con, chat_id).await.unwrap();
+        delete_archive_payments(&mut con, chat_id, "2021-01")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_admin_only() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678993";
+        let admins = vec!["111".to_string()];
+
+        assert!(!get_admin_only(&redis, chat_id).await.unwrap());
+        assert!(set_cached_admins(&redis, chat_id, &admins, 600)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_cached_admins(&redis, chat_id).await.unwrap(),
+            Some(admins)
+        );
+
+        // Toggling clears the cached administrators
+        assert!(set_admin_only(&redis, chat_id, true).await.is_ok());
+        assert!(get_admin_only(&redis, chat_id).await.unwrap());
+        assert_eq!(get_cached_admins(&redis, chat_id).await.unwrap(), None);
+
+        assert!(set_admin_only(&redis, chat_id, false).await.is_ok());
+        assert!(!get_admin_only(&redis, chat_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_cached_bot_can_delete() {
+        let redis = Redis::new();
+        let chat_id = "manager_permission_123456789";
+
+        assert!(set_cached_bot_can_delete(&redis, chat_id, true, 600)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_cached_bot_can_delete(&redis, chat_id).await.unwrap(),
+            Some(true)
+        );
+
+        // Losing the permission notifies the chat once
+        assert!(set_cached_bot_can_delete(&redis, chat_id, false, 600)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_cached_bot_can_delete(&redis, chat_id).await.unwrap(),
+            Some(false)
+        );
+        assert!(set_bot_permission_notified(&redis, chat_id).await.unwrap());
+        assert!(!set_bot_permission_notified(&redis, chat_id).await.unwrap());
+
+        // Regaining the permission allows notifying again
+        assert!(set_cached_bot_can_delete(&redis, chat_id, true, 600)
+            .await
+            .is_ok());
+        assert!(set_bot_permission_notified(&redis, chat_id).await.unwrap());
+        assert!(set_cached_bot_can_delete(&redis, chat_id, true, 600)
+            .await
+            .is_ok());
+
+        // Toggling erase messages clears the cached permission
+        assert!(set_erase_messages(&redis, chat_id, true).await.is_ok());
+        assert_eq!(
+            get_cached_bot_can_delete(&redis, chat_id).await.unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_pairwise_debts() {
+        let redis = Redis::new();
+        let chat_id = "manager_pairwise_123456789";
+
+        assert!(!get_pairwise_debts(&redis, chat_id).await.unwrap());
+        assert!(set_pairwise_debts(&redis, chat_id, true).await.is_ok());
+        assert!(get_pairwise_debts(&redis, chat_id).await.unwrap());
+
+        assert!(set_pairwise_debts(&redis, chat_id, false).await.is_ok());
+        assert!(!get_pairwise_debts(&redis, chat_id).await.unwrap());
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_passive_mode() {
+        let redis = Redis::new();
+        let chat_id = "manager_passive_123456789";
+
+        assert!(!get_passive_mode(&redis, chat_id).await.unwrap());
+        assert!(set_passive_mode(&redis, chat_id, true).await.is_ok());
+        assert!(get_passive_mode(&redis, chat_id).await.unwrap());
+
+        assert!(set_passive_mode(&redis, chat_id, false).await.is_ok());
+        assert!(!get_passive_mode(&redis, chat_id).await.unwrap());
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_chat_audit_entries() {
+        let redis = Redis::new();
+        let chat_id = "manager_audit_123456789";
+        let payment = Payment {
+            description: "manager_audit_payment".to_string(),
+            datetime: "2024-03-12 10:00:00 UTC".to_string(),
+            creditor: "manager_test_user_70".to_string(),
+            currency: "USD".to_string(),
+            total: 1000,
+            debts: vec![("manager_test_user_71".to_string(), 1000)],
+        };
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+
+        let added = AuditEntry {
+            action: AUDIT_ADDED.to_string(),
+            payment_id: payment_id.clone(),
+            description: payment.description.clone(),
+            username: "manager_test_user_70".to_string(),
+            datetime: "2024-03-12 10:00:00 UTC".to_string(),
+        };
+        let edited = AuditEntry {
+            action: AUDIT_EDITED.to_string(),
+            username: "manager_test_user_71".to_string(),
+            datetime: "2024-03-12 11:00:00 UTC".to_string(),
+            ..added.clone()
+        };
+        assert!(add_chat_audit_entry(&redis, chat_id, &added).await.is_ok());
+        assert!(add_chat_audit_entry(&redis, chat_id, &edited).await.is_ok());
+
+        // Latest changes come first
+        let entries = get_chat_audit_entries(&redis, chat_id).await.unwrap();
+        assert_eq!(entries[..2], [edited, added]);
+
+        // Kept on the payment as well
+        let payments = get_chat_payments_details(&redis, chat_id).await.unwrap();
+        assert_eq!(
+            payments[0].audit,
+            PaymentAudit {
+                created_by: Some("manager_test_user_70".to_string()),
+                created_at: Some("2024-03-12 10:00:00 UTC".to_string()),
+                last_edited_by: Some("manager_test_user_71".to_string()),
+                last_edited_at: Some("2024-03-12 11:00:00 UTC".to_string()),
+            }
+        );
+
+        delete_payment_entry(&redis, chat_id, &payment_id)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_is_private_payment_entry() {
+        let redis = Redis::new();
+        let chat_id = "manager_private_123456789";
+        let payment = Payment {
+            description: "manager_private_payment".to_string(),
+            datetime: "2024-03-12 10:00:00 UTC".to_string(),
+            creditor: "manager_test_user_72".to_string(),
+            currency: "USD".to_string(),
+            total: 1000,
+            debts: vec![("manager_test_user_73".to_string(), 1000)],
+        };
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        assert!(!is_private_payment_entry(&redis, &payment_id).await.unwrap());
+
+        assert!(set_payment_private_entry(&redis, &payment_id).await.is_ok());
+        assert!(is_private_payment_entry(&redis, &payment_id).await.unwrap());
+        assert!(get_chat_payments_details(&redis, chat_id).await.unwrap()[0].is_private);
+
+        delete_payment_entry(&redis, chat_id, &payment_id)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_is_transfer_payment_entry() {
+        let redis = Redis::new();
+        let chat_id = "manager_transfer_123456789";
+        let payment = Payment {
+            description: "manager_transfer_payment".to_string(),
+            datetime: "2024-03-12 10:00:00 UTC".to_string(),
+            creditor: "manager_test_user_88".to_string(),
+            currency: "USD".to_string(),
+            total: 1000,
+            debts: vec![("manager_test_user_89".to_string(), 1000)],
+        };
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        assert!(!is_transfer_payment_entry(&redis, &payment_id)
+            .await
+            .unwrap());
+
+        assert!(set_payment_transfer_entry(&redis, &payment_id, true)
+            .await
+            .is_ok());
+        assert!(is_transfer_payment_entry(&redis, &payment_id)
+            .await
+            .unwrap());
+        assert!(get_chat_payments_details(&redis, chat_id).await.unwrap()[0].is_transfer);
+        assert!(get_ledger_payments(&redis, chat_id, chat_id).await.unwrap()[0].transfer);
+
+        assert!(set_payment_transfer_entry(&redis, &payment_id, false)
+            .await
+            .is_ok());
+        assert!(!is_transfer_payment_entry(&redis, &payment_id)
+            .await
+            .unwrap());
+
+        delete_payment_entry(&redis, chat_id, &payment_id)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_payment_note_receipt_entry() {
+        let redis = Redis::new();
+        let chat_id = "manager_note_123456789";
+        let payment = Payment {
+            description: "manager_note_payment".to_string(),
+            datetime: "2024-03-12 10:00:00 UTC".to_string(),
+            creditor: "manager_test_user_90".to_string(),
+            currency: "USD".to_string(),
+            total: 1000,
+            debts: vec![("manager_test_user_91".to_string(), 1000)],
+        };
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        assert_eq!(
+            get_payment_note_entry(&redis, &payment_id).await.unwrap(),
+            None
+        );
+        assert_eq!(
+            get_payment_receipt_entry(&redis, &payment_id)
+                .await
+                .unwrap(),
+            None
+        );
+
+        assert!(set_payment_note_entry(&redis, &payment_id, "Booked online")
+            .await
+            .is_ok());
+        assert!(set_payment_receipt_entry(&redis, &payment_id, "file_id_1")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_payment_note_entry(&redis, &payment_id).await.unwrap(),
+            Some("Booked online".to_string())
+        );
+        assert_eq!(
+            get_payment_receipt_entry(&redis, &payment_id)
+                .await
+                .unwrap(),
+            Some("file_id_1".to_string())
+        );
+
+        delete_payment_entry(&redis, chat_id, &payment_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_payment_note_entry(&redis, &payment_id).await.unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_payment_version_entries() {
+        let redis = Redis::new();
+        let chat_id = "manager_version_123456789";
+        let payment = Payment {
+            description: "manager_version_payment".to_string(),
+            datetime: "2024-03-12 10:00:00 UTC".to_string(),
+            creditor: "manager_test_user_79".to_string(),
+            currency: "USD".to_string(),
+            total: 1000,
+            debts: vec![("manager_test_user_80".to_string(), 1000)],
+        };
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        assert!(get_payment_version_entries(&redis, &payment_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let version = PaymentVersion {
+            payment,
+            edited_by: "manager_test_user_79".to_string(),
+            edited_at: "2024-03-13 10:00:00 UTC".to_string(),
+        };
+        assert!(add_payment_version_entry(&redis, &payment_id, &version)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_payment_version_entries(&redis, &payment_id)
+                .await
+                .unwrap(),
+            vec![version]
+        );
+
+        // Versions are deleted together with the payment
+        delete_payment_entry(&redis, chat_id, &payment_id)
+            .await
+            .unwrap();
+        assert!(get_payment_version_entries(&redis, &payment_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_user_chat_entries() {
+        let redis = Redis::new();
+        let chat_id = "manager_private_987654321";
+        let user_id = "manager_private_user_id";
+        let username = "manager_test_user_74";
+
+        // Users mentioned only by username are identified by their ID
+        assert!(get_user_chat_entries(&redis, user_id, None)
+            .await
+            .unwrap()
+            .is_empty());
+        update_user(&redis, username, chat_id, None).await.unwrap();
+        assert_eq!(
+            get_user_chat_entries(&redis, user_id, Some(username))
+                .await
+                .unwrap(),
+            vec![chat_id.to_string()]
+        );
+
+        let mut con = redis.connect().await.unwrap();
+        delete_user(&mut con, user_id).await.unwrap();
+        delete_user_alias(&mut con, username).await.unwrap();
+        delete_user_id(&mut con, user_id).await.unwrap();
+        delete_preferred_username(&mut con, user_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_payment_count() {
+        let redis = Redis::new();
+        let chat_id = "manager_payment_count_123456789";
+
+        assert_eq!(get_payment_count(&redis, chat_id).await.unwrap(), 0);
+
+        let mut con = redis.connect().await.unwrap();
+        add_chat_payment(&mut con, chat_id, "manager_payment_count_1")
+            .await
+            .unwrap();
+        add_chat_payment(&mut con, chat_id, "manager_payment_count_2")
+            .await
+            .unwrap();
+        assert_eq!(get_payment_count(&redis, chat_id).await.unwrap(), 2);
+
+        delete_all_chat_payment(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_payment_links() {
+        let redis = Redis::new();
+        let user_id = "manager_payment_link_user_id";
+        let username = "Manager_Test_User_75";
+        let other = "manager_test_user_76".to_string();
+        let link = "https://paypal.me/manager_test_user_75";
+
+        assert!(
+            set_payment_link(&redis, user_id, Some(username), Some(link))
+                .await
+                .is_ok()
+        );
+        let links = get_payment_links(&redis, &[username.to_string(), other.clone()])
+            .await
+            .unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links.get(&username.to_lowercase()), Some(&link.to_string()));
+
+        assert!(set_payment_link(&redis, user_id, None, None).await.is_ok());
+        assert!(get_payment_links(&redis, &[username.to_string()])
+            .await
+            .unwrap()
+            .is_empty());
+
+        let mut con = redis.connect().await.unwrap();
+        delete_user_alias(&mut con, &username.to_lowercase())
+            .await
+            .unwrap();
+        delete_user_id(&mut con, user_id).await.unwrap();
+        delete_preferred_username(&mut con, user_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_private_chats() {
+        let redis = Redis::new();
+        let user_id = "manager_private_chat_user_id";
+        let username = "Manager_Test_User_77";
+        let other = "manager_test_user_78".to_string();
+
+        assert!(set_private_chat(&redis, user_id, Some(username), true)
+            .await
+            .is_ok());
+        let private_chats = get_private_chats(&redis, &[username.to_string(), other.clone()])
+            .await
+            .unwrap();
+        assert_eq!(private_chats.len(), 1);
+        assert_eq!(
+            private_chats.get(&username.to_lowercase()),
+            Some(&user_id.to_string())
+        );
+
+        assert!(set_private_chat(&redis, user_id, None, false).await.is_ok());
+        assert!(get_private_chats(&redis, &[username.to_string()])
+            .await
+            .unwrap()
+            .is_empty());
+
+        let mut con = redis.connect().await.unwrap();
+        delete_user_alias(&mut con, &username.to_lowercase())
+            .await
+            .unwrap();
+        delete_user_id(&mut con, user_id).await.unwrap();
+        delete_preferred_username(&mut con, user_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_week_start() {
+        let redis = Redis::new();
+        let chat_id = "manager_week_start_123456789";
+
+        assert_eq!(
+            get_week_start(&redis, chat_id).await.unwrap(),
+            WEEK_START_DEFAULT
+        );
+        assert!(set_week_start(&redis, chat_id, "sunday").await.is_ok());
+        assert_eq!(get_week_start(&redis, chat_id).await.unwrap(), "sunday");
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_rounding() {
+        let redis = Redis::new();
+        let chat_id = "manager_rounding_123456789";
+
+        assert_eq!(
+            get_rounding(&redis, chat_id).await.unwrap(),
+            ROUNDING_DEFAULT
+        );
+        assert!(set_rounding(&redis, chat_id, "roundrobin").await.is_ok());
+        assert_eq!(get_rounding(&redis, chat_id).await.unwrap(), "roundrobin");
+
+        assert_eq!(get_next_rounding_offset(&redis, chat_id).await.unwrap(), 0);
+        assert_eq!(get_next_rounding_offset(&redis, chat_id).await.unwrap(), 1);
+        assert_eq!(get_next_rounding_offset(&redis, chat_id).await.unwrap(), 2);
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_allowed_currencies() {
+        let redis = Redis::new();
+        let chat_id = "manager_allowed_currencies_123456789";
+
+        assert!(get_allowed_currencies(&redis, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let currencies = vec!["SGD".to_string(), "USD".to_string()];
+        assert!(set_allowed_currencies(&redis, chat_id, &currencies)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_allowed_currencies(&redis, chat_id).await.unwrap(),
+            currencies
+        );
+
+        assert!(set_allowed_currencies(&redis, chat_id, &[]).await.is_ok());
+        assert!(get_allowed_currencies(&redis, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_verbosity() {
+        let redis = Redis::new();
+        let chat_id = "manager_verbosity_123456789";
+
+        assert_eq!(
+            get_verbosity(&redis, chat_id).await.unwrap(),
+            VERBOSITY_DEFAULT
+        );
+        assert!(set_verbosity(&redis, chat_id, "compact").await.is_ok());
+        assert_eq!(get_verbosity(&redis, chat_id).await.unwrap(), "compact");
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_treasurer() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678995";
+
+        assert_eq!(get_treasurer(&redis, chat_id).await.unwrap(), None);
+        assert!(set_treasurer(&redis, chat_id, Some("alice")).await.is_ok());
+        assert_eq!(
+            get_treasurer(&redis, chat_id).await.unwrap(),
+            Some("alice".to_string())
+        );
+
+        assert!(set_treasurer(&redis, chat_id, None).await.is_ok());
+        assert_eq!(get_treasurer(&redis, chat_id).await.unwrap(), None);
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_spending_limits() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678996";
+        let username = "manager_test_user_60";
+
+        // Adds chat
+        assert!(update_chat(&redis, chat_id, vec![username.to_string()])
+            .await
+            .is_ok());
+        update_user(&redis, username, chat_id, None).await.unwrap();
+
+        assert!(get_spending_limits(&redis, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(
+            set_spending_limit(&redis, chat_id, username, Some(("USD", 10000)))
+                .await
+                .is_ok()
+        );
+
+        // Only spendings in the currency of the limit are counted
+        let spendings = vec![
+            UserBalance {
+                username: username.to_string(),
+                currency: "USD".to_string(),
+                balance: 6000,
+            },
+            UserBalance {
+                username: username.to_string(),
+                currency: "JPY".to_string(),
+                balance: 50000,
+            },
+        ];
+        assert!(update_chat_spendings(&redis, chat_id, spendings)
+            .await
+            .is_ok());
+        let limit = SpendingLimit {
+            username: username.to_string(),
             currency: "USD".to_string(),
-            total: 10000,
-            debts: vec![
-                ("manager_test_user_11".to_string(), 5000),
-                ("manager_test_user_12".to_string(), 5000),
-            ],
+            limit: 10000,
+            spent: 6000,
+        };
+        assert_eq!(
+            get_spending_limits(&redis, chat_id).await.unwrap(),
+            vec![limit.clone()]
+        );
+        assert!(get_spending_limit_warnings(&redis, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Warns only once after crossing the limit
+        let spendings = vec![UserBalance {
+            username: username.to_string(),
+            currency: "USD".to_string(),
+            balance: 5000,
+        }];
+        assert!(update_chat_spendings(&redis, chat_id, spendings)
+            .await
+            .is_ok());
+        let limit = SpendingLimit {
+            spent: 11000,
+            ..limit
         };
+        assert_eq!(
+            get_spending_limit_warnings(&redis, chat_id).await.unwrap(),
+            vec![limit]
+        );
+        assert!(get_spending_limit_warnings(&redis, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert!(set_spending_limit(&redis, chat_id, username, None)
+            .await
+            .is_ok());
+        assert!(get_spending_limits(&redis, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Deletes chat, user, spendings and monthly records
+        let mut con = redis.connect().await.unwrap();
+        let month = get_limit_month(&mut con, chat_id).await.unwrap();
+        delete_chat(&mut con, chat_id).await.unwrap();
+        delete_user(&mut con, username).await.unwrap();
+        delete_preferred_username(&mut con, username).await.unwrap();
+        delete_spending(&mut con, chat_id, username, "USD")
+            .await
+            .unwrap();
+        delete_spending(&mut con, chat_id, username, "JPY")
+            .await
+            .unwrap();
+        delete_limit_month(&mut con, chat_id, &month).await.unwrap();
+    }
 
-        // Adds payment
-        assert!(add_payment_entry(chat_id, &payment).is_ok());
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_weekly_spending_cap() {
+        let redis = Redis::new();
+        let chat_id = "manager_weekly_cap_123456789";
+        let username = "manager_test_user_weekly_cap";
 
-        let second_payment = Payment {
-            description: "manager_test_payment_2".to_string(),
-            datetime: "2021-01-01T00:00:01".to_string(),
-            creditor: "manager_test_user_13".to_string(),
+        // Adds chat
+        assert!(update_chat(&redis, chat_id, vec![username.to_string()])
+            .await
+            .is_ok());
+        update_user(&redis, username, chat_id, None).await.unwrap();
+
+        assert_eq!(
+            get_weekly_spending_cap(&redis, chat_id).await.unwrap(),
+            None
+        );
+        assert!(
+            set_weekly_spending_cap(&redis, chat_id, Some(("USD", 10000)))
+                .await
+                .is_ok()
+        );
+
+        // Only spendings in the currency of the cap are counted
+        let spendings = vec![
+            UserBalance {
+                username: username.to_string(),
+                currency: "USD".to_string(),
+                balance: 6000,
+            },
+            UserBalance {
+                username: username.to_string(),
+                currency: "JPY".to_string(),
+                balance: 50000,
+            },
+        ];
+        assert!(update_chat_spendings(&redis, chat_id, spendings)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_weekly_spending_cap(&redis, chat_id).await.unwrap(),
+            Some(WeeklyCap {
+                currency: "USD".to_string(),
+                cap: 10000,
+                spent: 6000,
+            })
+        );
+
+        assert!(set_weekly_spending_cap(&redis, chat_id, None).await.is_ok());
+        assert_eq!(
+            get_weekly_spending_cap(&redis, chat_id).await.unwrap(),
+            None
+        );
+
+        // Deletes chat, user, spendings and weekly records
+        let mut con = redis.connect().await.unwrap();
+        let week = get_cap_week(&mut con, chat_id).await.unwrap();
+        delete_chat(&mut con, chat_id).await.unwrap();
+        delete_user(&mut con, username).await.unwrap();
+        delete_preferred_username(&mut con, username).await.unwrap();
+        delete_spending(&mut con, chat_id, username, "USD")
+            .await
+            .unwrap();
+        delete_spending(&mut con, chat_id, username, "JPY")
+            .await
+            .unwrap();
+        delete_weekly_cap_week(&mut con, chat_id, &week)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_onboarding_step() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678997";
+
+        assert_eq!(get_onboarding_step(&redis, chat_id).await.unwrap(), None);
+        assert!(set_onboarding_step(&redis, chat_id, Some("time_zone"), 600)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_onboarding_step(&redis, chat_id).await.unwrap(),
+            Some("time_zone".to_string())
+        );
+
+        assert!(set_onboarding_step(&redis, chat_id, None, 600)
+            .await
+            .is_ok());
+        assert_eq!(get_onboarding_step(&redis, chat_id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_payment_template() {
+        let redis = Redis::new();
+        let chat_id = "manager_template_123456789";
+
+        assert_eq!(
+            get_latest_payment_entry(&redis, chat_id).await.unwrap(),
+            None
+        );
+        let payment = Payment {
+            description: "Weekly groceries".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user_65".to_string(),
             currency: "USD".to_string(),
-            total: 20000,
+            total: 6000,
             debts: vec![
-                ("manager_test_user_14".to_string(), 10000),
-                ("manager_test_user_15".to_string(), 10000),
+                ("manager_test_user_65".to_string(), 3000),
+                ("manager_test_user_66".to_string(), 3000),
             ],
         };
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        assert_eq!(
+            get_latest_payment_entry(&redis, chat_id).await.unwrap(),
+            Some(payment.clone())
+        );
 
-        // Adds second payment
-        assert!(add_payment_entry(chat_id, &second_payment).is_ok());
+        let template = PaymentTemplate {
+            name: "groceries".to_string(),
+            description: payment.description,
+            creditor: payment.creditor,
+            currency: payment.currency,
+            total: payment.total,
+            debts: payment.debts,
+        };
+        assert_eq!(
+            get_payment_template(&redis, chat_id, "groceries")
+                .await
+                .unwrap(),
+            None
+        );
+        assert!(set_payment_template(&redis, chat_id, &template)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_payment_template(&redis, chat_id, "groceries")
+                .await
+                .unwrap(),
+            Some(template)
+        );
+        assert_eq!(
+            get_payment_templates(&redis, chat_id).await.unwrap(),
+            vec!["groceries".to_string()]
+        );
 
-        // Gets both payments
-        let payments = get_chat_payments_details(chat_id).unwrap();
-        let second_id = payments[0].payment_id.clone();
+        assert!(delete_payment_template(&redis, chat_id, "groceries")
+            .await
+            .unwrap());
+        assert!(!delete_payment_template(&redis, chat_id, "groceries")
+            .await
+            .unwrap());
+        assert!(get_payment_templates(&redis, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        delete_payment_entry(&redis, chat_id, &payment_id)
+            .await
+            .unwrap();
+    }
 
-        // Gets second payment by ID
-        let second_payment_values = get_payment_entry(&second_id);
-        assert_eq!(second_payment_values.unwrap(), second_payment);
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_display_name() {
+        let redis = Redis::new();
+        let chat_id = "manager_display_name_123456789";
 
-        // Updates second payment
-        let updated_description = "manager_test_payment_3";
-        let updated_creditor = "manager_test_user_16";
-        let updated_currency = "JPY";
-        let updated_total = 30000;
-        let updated_debts = vec![
-            ("manager_test_user_17".to_string(), 15000),
-            ("manager_test_user_18".to_string(), 15000),
-        ];
+        assert!(
+            set_display_name(&redis, chat_id, "Manager_Test_User_67", "John")
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            get_display_names(&redis, chat_id).await.unwrap(),
+            HashMap::from([("manager_test_user_67".to_string(), "John".to_string())])
+        );
 
-        assert!(update_payment_entry(
-            &second_id,
-            Some(updated_description),
-            Some(updated_creditor),
-            Some(updated_currency),
-            Some(&updated_total),
-            Some(updated_debts.clone()),
-        )
-        .is_ok());
+        assert!(delete_display_name(&redis, chat_id, "manager_test_user_67")
+            .await
+            .unwrap());
+        assert!(
+            !delete_display_name(&redis, chat_id, "manager_test_user_67")
+                .await
+                .unwrap()
+        );
+        assert!(get_display_names(&redis, chat_id).await.unwrap().is_empty());
+    }
 
-        // Gets both payments again
-        let payments = get_chat_payments_details(chat_id).unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_split_group() {
+        let redis = Redis::new();
+        let chat_id = "manager_split_group_123456789";
+        let usernames = vec![
+            "manager_test_user_77".to_string(),
+            "manager_test_user_78".to_string(),
+        ];
+
+        assert!(get_split_groups(&redis, chat_id).await.unwrap().is_empty());
+        assert!(set_split_group(&redis, chat_id, "flatmates", &usernames)
+            .await
+            .is_ok());
+        assert!(set_split_group(&redis, chat_id, "couple", &usernames[..1])
+            .await
+            .is_ok());
         assert_eq!(
-            payments,
+            get_split_groups(&redis, chat_id).await.unwrap(),
             vec![
-                UserPayment {
-                    chat_id: chat_id.to_string(),
-                    payment_id: second_id.clone(),
-                    payment: Payment {
-                        description: updated_description.to_string(),
-                        datetime: "2021-01-01T00:00:01".to_string(),
-                        creditor: updated_creditor.to_string(),
-                        currency: updated_currency.to_string(),
-                        total: updated_total,
-                        debts: updated_debts.clone(),
-                    },
-                },
-                UserPayment {
-                    chat_id: chat_id.to_string(),
-                    payment_id: payments[1].payment_id.clone(),
-                    payment: payment,
-                },
+                ("couple".to_string(), usernames[..1].to_vec()),
+                ("flatmates".to_string(), usernames.clone()),
             ]
         );
 
-        // Deletes both payments
-        assert!(delete_payment_entry(chat_id, &payments[0].payment_id).is_ok());
-        assert!(delete_payment_entry(chat_id, &payments[1].payment_id).is_ok());
-    }
+        assert!(delete_split_group(&redis, chat_id, "couple").await.unwrap());
+        assert!(!delete_split_group(&redis, chat_id, "couple").await.unwrap());
+        assert_eq!(
+            get_split_groups(&redis, chat_id).await.unwrap(),
+            vec![("flatmates".to_string(), usernames)]
+        );
+        assert!(delete_split_group(&redis, chat_id, "flatmates")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_monthly_archive() {
+        let redis = Redis::new();
+        let chat_id = "manager_1234567899";
+
+        assert!(!get_monthly_archive(&redis, chat_id).await.unwrap());
+        assert!(set_monthly_archive(&redis, chat_id, true).await.is_ok());
+        assert!(get_monthly_archive(&redis, chat_id).await.unwrap());
+        assert!(get_monthly_archive_chats(&redis)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+        assert_eq!(get_last_archive_month(&redis, chat_id).await.unwrap(), None);
+
+        assert!(set_last_archive_month(&redis, chat_id, "2021-01")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_last_archive_month(&redis, chat_id).await.unwrap(),
+            Some("2021-01".to_string())
+        );
+
+        assert!(set_monthly_archive(&redis, chat_id, false).await.is_ok());
+        assert!(!get_monthly_archive_chats(&redis)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+        delete_archive_month(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_weekly_digest() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678910";
+
+        assert_eq!(get_weekly_digest(&redis, chat_id).await.unwrap(), None);
+        assert!(set_weekly_digest(&redis, chat_id, Some("18:00"))
+            .await
+            .is_ok());
+        assert_eq!(
+            get_weekly_digest(&redis, chat_id).await.unwrap(),
+            Some("18:00".to_string())
+        );
+        assert!(get_weekly_digest_chats(&redis)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+        assert_eq!(get_last_digest_week(&redis, chat_id).await.unwrap(), None);
+
+        assert!(set_last_digest_week(&redis, chat_id, "2021-W01")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_last_digest_week(&redis, chat_id).await.unwrap(),
+            Some("2021-W01".to_string())
+        );
+
+        assert!(set_weekly_digest(&redis, chat_id, None).await.is_ok());
+        assert_eq!(get_weekly_digest(&redis, chat_id).await.unwrap(), None);
+        assert!(!get_weekly_digest_chats(&redis)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+        delete_digest_week(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_debt_reminder() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678920";
+
+        assert_eq!(get_debt_reminder(&redis, chat_id).await.unwrap(), None);
+        assert!(set_debt_reminder(&redis, chat_id, Some(3)).await.is_ok());
+        assert_eq!(get_debt_reminder(&redis, chat_id).await.unwrap(), Some(3));
+        assert!(get_debt_reminder_chats(&redis)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+        assert_eq!(get_last_reminder_date(&redis, chat_id).await.unwrap(), None);
+
+        assert!(set_last_reminder_date(&redis, chat_id, "2021-01-01")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_last_reminder_date(&redis, chat_id).await.unwrap(),
+            Some("2021-01-01".to_string())
+        );
+
+        assert!(set_debt_reminder(&redis, chat_id, None).await.is_ok());
+        assert_eq!(get_debt_reminder(&redis, chat_id).await.unwrap(), None);
+        assert!(!get_debt_reminder_chats(&redis)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+        delete_reminder_date(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_data_retention() {
+        let redis = Redis::new();
+        let chat_id = "manager_retention_123456789";
+
+        assert_eq!(get_data_retention(&redis, chat_id).await.unwrap(), None);
+        assert!(set_data_retention(&redis, chat_id, Some(6)).await.is_ok());
+        assert_eq!(get_data_retention(&redis, chat_id).await.unwrap(), Some(6));
+        assert!(get_data_retention_chats(&redis)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+        assert_eq!(
+            get_last_retention_date(&redis, chat_id).await.unwrap(),
+            None
+        );
+
+        assert!(set_last_retention_date(&redis, chat_id, "2021-01-01")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_last_retention_date(&redis, chat_id).await.unwrap(),
+            Some("2021-01-01".to_string())
+        );
+
+        assert!(set_data_retention(&redis, chat_id, None).await.is_ok());
+        assert_eq!(get_data_retention(&redis, chat_id).await.unwrap(), None);
+        assert!(!get_data_retention_chats(&redis)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+        delete_retention_date(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_reset_topic_settings() {
+        let redis = Redis::new();
+        let chat_id = "manager_topic_123456789";
+        let topic_id = 7;
+
+        assert_eq!(
+            get_topic_time_zone(&redis, chat_id, topic_id)
+                .await
+                .unwrap(),
+            None
+        );
+        assert!(set_topic_time_zone(&redis, chat_id, topic_id, "Asia/Tokyo")
+            .await
+            .is_ok());
+        assert!(set_topic_default_currency(&redis, chat_id, topic_id, "JPY")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_topic_time_zone(&redis, chat_id, topic_id)
+                .await
+                .unwrap(),
+            Some("Asia/Tokyo".to_string())
+        );
+        assert_eq!(
+            get_topic_default_currency(&redis, chat_id, topic_id)
+                .await
+                .unwrap(),
+            Some("JPY".to_string())
+        );
+
+        assert!(reset_topic_settings(&redis, chat_id, topic_id)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_topic_time_zone(&redis, chat_id, topic_id)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            get_topic_default_currency(&redis, chat_id, topic_id)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_payment_due_dates() {
+        let redis = Redis::new();
+        let chat_id = "manager_due_123456789";
+        let payment = Payment {
+            description: "manager_test_payment".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user_86".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![("manager_test_user_87".to_string(), 10000)],
+        };
+
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        assert!(get_payment_due_dates(&redis, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(
+            set_payment_due_date(&redis, chat_id, &payment_id, "2021-01-15")
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            get_payment_due_dates(&redis, chat_id)
+                .await
+                .unwrap()
+                .get(&payment_id),
+            Some(&"2021-01-15".to_string())
+        );
+
+        // Deleting the payment also deletes its due date
+        assert!(delete_payment_entry(&redis, chat_id, &payment_id)
+            .await
+            .is_ok());
+        assert!(get_payment_due_dates(&redis, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_resolve_payment_dispute() {
+        let redis = Redis::new();
+        let chat_id = "manager_dispute_123456789";
+        let payment = Payment {
+            description: "manager_test_payment".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user_88".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![("manager_test_user_89".to_string(), 10000)],
+        };
+
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        assert_eq!(
+            get_payment_dispute(&redis, chat_id, &payment_id)
+                .await
+                .unwrap(),
+            None
+        );
+        assert!(
+            add_payment_dispute(&redis, chat_id, &payment_id, "manager_test_user_89")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !add_payment_dispute(&redis, chat_id, &payment_id, "manager_test_user_89")
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            get_payment_dispute(&redis, chat_id, &payment_id)
+                .await
+                .unwrap(),
+            Some("manager_test_user_89".to_string())
+        );
+        assert!(get_chat_payments_details(&redis, chat_id).await.unwrap()[0].is_disputed);
+
+        assert!(resolve_payment_dispute(&redis, chat_id, &payment_id)
+            .await
+            .unwrap());
+        assert!(!resolve_payment_dispute(&redis, chat_id, &payment_id)
+            .await
+            .unwrap());
+        assert!(!get_chat_payments_details(&redis, chat_id).await.unwrap()[0].is_disputed);
+
+        // Deleting the payment also deletes its dispute
+        assert!(
+            add_payment_dispute(&redis, chat_id, &payment_id, "manager_test_user_89")
+                .await
+                .unwrap()
+        );
+        assert!(delete_payment_entry(&redis, chat_id, &payment_id)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_payment_dispute(&redis, chat_id, &payment_id)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_trash_restore_purge_payment_entry() {
+        let redis = Redis::new();
+        let chat_id = "manager_trash_123456789";
+        let payment = Payment {
+            description: "manager_test_payment".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user_90".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![("manager_test_user_91".to_string(), 10000)],
+        };
+
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        let other_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        assert!(
+            add_payment_dispute(&redis, chat_id, &payment_id, "manager_test_user_91")
+                .await
+                .unwrap()
+        );
+        assert!(
+            trash_payment_entry(&redis, chat_id, &payment_id, "2024-03-12 10:00:00 UTC")
+                .await
+                .is_ok()
+        );
+        assert!(
+            trash_payment_entry(&redis, chat_id, &other_id, "2024-03-13 10:00:00 UTC")
+                .await
+                .is_ok()
+        );
+        assert!(trash_payment_entry(
+            &redis,
+            chat_id,
+            "nonexistent_payment",
+            "2024-03-13 10:00:00 UTC"
+        )
+        .await
+        .is_err());
+
+        // Trashed payments are kept, but out of the chat
+        assert!(get_chat_payments_details(&redis, chat_id).await.is_err());
+        assert_eq!(
+            get_payment_dispute(&redis, chat_id, &payment_id)
+                .await
+                .unwrap(),
+            None
+        );
+        let entries = get_trash_entries(&redis, chat_id).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].payment_id, other_id);
+        assert_eq!(entries[1].payment_id, payment_id);
+        assert_eq!(entries[1].payment, payment);
+        assert!(get_trash_chat_ids(&redis)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+
+        // Restoring adds the payment back, only once
+        assert!(restore_trash_entry(&redis, chat_id, &payment_id)
+            .await
+            .unwrap());
+        assert!(!restore_trash_entry(&redis, chat_id, &payment_id)
+            .await
+            .unwrap());
+        let payments = get_chat_payments_details(&redis, chat_id).await.unwrap();
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].payment_id, payment_id);
+
+        // Purging deletes only the payments trashed before the cutoff, for good
+        assert_eq!(
+            purge_trash_entries(&redis, chat_id, "2024-03-13 00:00:00 UTC")
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            purge_trash_entries(&redis, chat_id, "2024-03-14 00:00:00 UTC")
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(get_trash_entries(&redis, chat_id).await.unwrap().is_empty());
+        assert!(get_payment_entry(&redis, &other_id).await.is_err());
 
-    // Test for empty payments
-    #[test]
-    fn test_no_payments_found() {
-        let chat_id = "manager_1234567898";
+        assert!(delete_payment_entry(&redis, chat_id, &payment_id)
+            .await
+            .is_ok());
+    }
 
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_delete_payment_archive_entries() {
+        let redis = Redis::new();
+        let chat_id = "manager_retention_1234567890";
         let payment = Payment {
-            description: "manager_test_user_20".to_string(),
+            description: "manager_test_payment".to_string(),
             datetime: "2021-01-01T00:00:00".to_string(),
-            creditor: "manager_test_user_21".to_string(),
+            creditor: "manager_test_user_84".to_string(),
             currency: "USD".to_string(),
             total: 10000,
-            debts: vec![
-                ("manager_test_user_22".to_string(), 5000),
-                ("manager_test_user_23".to_string(), 5000),
-            ],
+            debts: vec![("manager_test_user_85".to_string(), 10000)],
         };
 
-        // Checks that payments don't exist
+        // Deletes payments in the chat, and in their trips
+        let first_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        let second_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        add_trip_payment_entry(&redis, chat_id, "1", &second_id)
+            .await
+            .unwrap();
+        assert!(
+            delete_payment_entries(&redis, chat_id, &[first_id.clone(), second_id.clone()])
+                .await
+                .is_ok()
+        );
         assert_eq!(
-            get_chat_payments_details(chat_id).unwrap_err(),
-            CrudError::NoPaymentsError()
+            get_chat_payments_details(&redis, chat_id).await,
+            Err(CrudError::NoPaymentsError())
+        );
+        assert!(
+            get_chat_payments_details(&redis, &get_trip_ledger(chat_id, "1"))
+                .await
+                .is_err()
         );
-
-        // Adds chat payment
-        assert!(add_payment_entry(chat_id, &payment).is_ok());
-
-        // Updates fake payment, should fail
         assert_eq!(
-            update_payment_entry(
-                "nonexistent_payment",
-                Some("manager_test_payment_3"),
-                Some("manager_test_user_16"),
-                Some("JPY"),
-                Some(&30000),
-                Some(vec![
-                    ("manager_test_user_17".to_string(), 15000),
-                    ("manager_test_user_18".to_string(), 15000),
-                ]),
-            )
-            .unwrap_err(),
-            CrudError::NoSuchPaymentError()
+            get_payment_entry(&redis, &second_id).await,
+            Err(CrudError::NoSuchPaymentError())
         );
 
-        // Deletes fake payment, should fail
+        // Deletes archives from before a month only
+        let old_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        let new_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        archive_payment_entries(&redis, chat_id, "2021-01", vec![old_id.clone()])
+            .await
+            .unwrap();
+        archive_payment_entries(&redis, chat_id, "2021-02", vec![new_id.clone()])
+            .await
+            .unwrap();
         assert_eq!(
-            delete_payment_entry(chat_id, "nonexistent_payment").unwrap_err(),
-            CrudError::NoSuchPaymentError()
+            delete_archive_entries(&redis, chat_id, "2021-02")
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            get_payment_entry(&redis, &old_id).await,
+            Err(CrudError::NoSuchPaymentError())
         );
+        assert!(get_payment_entry(&redis, &new_id).await.is_ok());
 
-        // Deletes actual payment
-        let payments = get_chat_payments_details(chat_id).unwrap();
-        assert!(delete_payment_entry(chat_id, &payments[0].payment_id).is_ok());
+        let mut con = redis.connect().await.unwrap();
+        assert_eq!(
+            get_archives(&mut con, chat_id).await.unwrap(),
+            vec!["2021-02".to_string()]
+        );
 
-        // Checks that payments don't exist
+        // Deletes everything
         assert_eq!(
-            get_chat_payments_details(chat_id).unwrap_err(),
-            CrudError::NoPaymentsError()
+            delete_archive_entries(&redis, chat_id, "2021-03")
+                .await
+                .unwrap(),
+            1
         );
+        assert!(get_archives(&mut con, chat_id).await.unwrap().is_empty());
     }
 
-    #[test]
-    fn test_update_retrieve_balances() {
-        let mut con = connect().unwrap();
-
-        let chat_id = "manager_1234567898";
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_approval_threshold() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678911";
 
-        // Add users to chat
-        let usernames = vec![
-            "manager_test_user_20".to_string(),
-            "manager_test_user_21".to_string(),
-            "manager_test_user_22".to_string(),
-        ];
-        update_chat(chat_id, usernames.clone()).unwrap();
+        assert_eq!(get_approval_threshold(&redis, chat_id).await.unwrap(), None);
+        assert!(set_approval_threshold(&redis, chat_id, Some(100.0))
+            .await
+            .is_ok());
+        assert_eq!(
+            get_approval_threshold(&redis, chat_id).await.unwrap(),
+            Some(100.0)
+        );
 
-        for username in &usernames {
-            update_user(&username, chat_id, None).unwrap();
-        }
+        assert!(set_approval_threshold(&redis, chat_id, None).await.is_ok());
+        assert_eq!(get_approval_threshold(&redis, chat_id).await.unwrap(), None);
 
-        let changes = vec![
-            UserBalance {
-                username: "manager_test_user_20".to_string(),
-                balance: 10000,
-                currency: "USD".to_string(),
-            },
-            UserBalance {
-                username: "manager_test_user_21".to_string(),
-                balance: -5000,
-                currency: "USD".to_string(),
-            },
-            UserBalance {
-                username: "manager_test_user_22".to_string(),
-                balance: -5000,
-                currency: "USD".to_string(),
-            },
-        ];
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
 
-        // Adds initial balances
-        assert!(update_chat_balances(chat_id, changes.clone()).is_ok());
-        let initial_balances = get_chat_balances(chat_id).unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_resolve_pending_payment_entry() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678912";
+        let pending = PendingPayment {
+            chat_id: chat_id.to_string(),
+            sender_id: "123456789".to_string(),
+            sender_username: "manager_test_user".to_string(),
+            is_private: false,
+            is_transfer: false,
+            rate: None,
+        };
+        let payment = Payment {
+            description: "test_payment".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![("manager_test_user_2".to_string(), 10000)],
+        };
 
-        // Checks that balances are correct
+        let payment_id = add_pending_payment_entry(&redis, &pending, &payment)
+            .await
+            .unwrap();
         assert_eq!(
-            initial_balances,
-            vec![vec![
-                UserBalance {
-                    username: "manager_test_user_20".to_string(),
-                    balance: 10000,
-                    currency: "USD".to_string(),
-                },
-                UserBalance {
-                    username: "manager_test_user_21".to_string(),
-                    balance: -5000,
-                    currency: "USD".to_string(),
-                },
-                UserBalance {
-                    username: "manager_test_user_22".to_string(),
-                    balance: -5000,
-                    currency: "USD".to_string(),
-                },
-            ]]
+            get_pending_payment_entry(&redis, &payment_id)
+                .await
+                .unwrap(),
+            (pending, payment)
         );
 
-        // Updates balances
-        let new_changes = vec![
-            UserBalance {
-                username: "manager_test_user_20".to_string(),
-                balance: -5000,
-                currency: "USD".to_string(),
-            },
-            UserBalance {
-                username: "manager_test_user_21".to_string(),
-                balance: -5000,
-                currency: "USD".to_string(),
-            },
-            UserBalance {
-                username: "manager_test_user_22".to_string(),
-                balance: 5000,
-                currency: "USD".to_string(),
-            },
-        ];
-
-        assert!(update_chat_balances(chat_id, new_changes.clone()).is_ok());
-        let new_balances = get_chat_balances(chat_id).unwrap();
-
-        // Checks that balances are correct
+        // Pending payments are not added to the chat
         assert_eq!(
-            new_balances,
-            vec![vec![
-                UserBalance {
-                    username: "manager_test_user_20".to_string(),
-                    balance: 5000,
-                    currency: "USD".to_string(),
-                },
-                UserBalance {
-                    username: "manager_test_user_21".to_string(),
-                    balance: -10000,
-                    currency: "USD".to_string(),
-                },
-            ]]
+            get_chat_payments_details(&redis, chat_id).await,
+            Err(CrudError::NoPaymentsError())
         );
 
-        // Deletes balances
-        for balance in &initial_balances[0] {
-            delete_balance(&mut con, chat_id, &balance.username, "USD").unwrap();
-        }
-
-        // Deletes usernames
-        for username in &usernames {
-            delete_user(&mut con, &username).unwrap();
-            delete_preferred_username(&mut con, username).unwrap();
-        }
-
-        // Deletes chat
-        delete_chat(&mut con, chat_id).unwrap();
-        delete_chat_currencies(&mut con, chat_id).unwrap();
-        delete_chat_settings(&mut con, chat_id).unwrap();
+        assert!(resolve_pending_payment_entry(&redis, &payment_id)
+            .await
+            .unwrap());
+        assert!(!resolve_pending_payment_entry(&redis, &payment_id)
+            .await
+            .unwrap());
+        assert_eq!(
+            get_pending_payment_entry(&redis, &payment_id).await,
+            Err(CrudError::NoSuchPaymentError())
+        );
     }
 
-    #[test]
-    fn test_multiple_currencies_balances() {
-        let mut con = connect().unwrap();
-
-        let chat_id = "manager_1234567899";
-
-        // Add users to chat
-        let usernames = vec![
-            "manager_test_user_26".to_string(),
-            "manager_test_user_27".to_string(),
-            "manager_test_user_28".to_string(),
-        ];
-        update_chat(chat_id, usernames.clone()).unwrap();
-
-        for username in &usernames {
-            update_user(&username, chat_id, None).unwrap();
-        }
-
-        // Add first changes
-        let changes = vec![
-            UserBalance {
-                username: "manager_test_user_26".to_string(),
-                balance: 10000,
-                currency: "USD".to_string(),
-            },
-            UserBalance {
-                username: "manager_test_user_27".to_string(),
-                balance: -5000,
-                currency: "JPY".to_string(),
-            },
-            UserBalance {
-                username: "manager_test_user_28".to_string(),
-                balance: 10000,
-                currency: "JPY".to_string(),
-            },
-        ];
-
-        update_chat_balances(chat_id, changes.clone()).unwrap();
-
-        // Add second changes
-        let new_changes = vec![
-            UserBalance {
-                username: "manager_test_user_26".to_string(),
-                balance: -5000,
-                currency: "JPY".to_string(),
-            },
-            UserBalance {
-                username: "manager_test_user_27".to_string(),
-                balance: -5000,
-                currency: "USD".to_string(),
-            },
-            UserBalance {
-                username: "manager_test_user_28".to_string(),
-                balance: -5000,
-                currency: "USD".to_string(),
-            },
-        ];
-
-        assert!(update_chat_balances(chat_id, new_changes.clone()).is_ok());
-        let new_balances = get_chat_balances(chat_id).unwrap();
-
-        // Check balances
-        let balances = vec![
-            vec![
-                UserBalance {
-                    username: "manager_test_user_26".to_string(),
-                    balance: 10000,
-                    currency: "USD".to_string(),
-                },
-                UserBalance {
-                    username: "manager_test_user_27".to_string(),
-                    balance: -5000,
-                    currency: "USD".to_string(),
-                },
-                UserBalance {
-                    username: "manager_test_user_28".to_string(),
-                    balance: -5000,
-                    currency: "USD".to_string(),
-                },
-            ],
-            vec![
-                UserBalance {
-                    username: "manager_test_user_26".to_string(),
-                    balance: -5000,
-                    currency: "JPY".to_string(),
-                },
-                UserBalance {
-                    username: "manager_test_user_27".to_string(),
-                    balance: -5000,
-                    currency: "JPY".to_string(),
-                },
-                UserBalance {
-                    username: "manager_test_user_28".to_string(),
-                    balance: 10000,
-                    currency: "JPY".to_string(),
-                },
-            ],
-        ];
-
-        assert_eq!(new_balances, balances);
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_confirm_pending_payment_entry() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678933";
+        let pending = PendingPayment {
+            chat_id: chat_id.to_string(),
+            sender_id: "123456789".to_string(),
+            sender_username: "manager_test_user".to_string(),
+            is_private: false,
+            is_transfer: false,
+            rate: None,
+        };
+        let payment = Payment {
+            description: "test_payment".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![("manager_test_user_2".to_string(), 10000)],
+        };
 
-        // Checks balances for individual currency
-        let balances_usd = get_chat_balances_currency(chat_id, "USD").unwrap();
+        let payment_id = add_pending_payment_entry(&redis, &pending, &payment)
+            .await
+            .unwrap();
         assert_eq!(
-            balances_usd,
-            vec![
-                UserBalance {
-                    username: "manager_test_user_26".to_string(),
-                    balance: 10000,
-                    currency: "USD".to_string(),
-                },
-                UserBalance {
-                    username: "manager_test_user_27".to_string(),
-                    balance: -5000,
-                    currency: "USD".to_string(),
-                },
-                UserBalance {
-                    username: "manager_test_user_28".to_string(),
-                    balance: -5000,
-                    currency: "USD".to_string(),
-                },
-            ]
+            confirm_pending_payment_entry(&redis, &payment_id, true, "manager_test_user")
+                .await
+                .unwrap(),
+            (Some("manager_test_user".to_string()), None)
         );
-        let balances_jpy = get_chat_balances_currency(chat_id, "JPY").unwrap();
         assert_eq!(
-            balances_jpy,
-            vec![
-                UserBalance {
-                    username: "manager_test_user_26".to_string(),
-                    balance: -5000,
-                    currency: "JPY".to_string(),
-                },
-                UserBalance {
-                    username: "manager_test_user_27".to_string(),
-                    balance: -5000,
-                    currency: "JPY".to_string(),
-                },
-                UserBalance {
-                    username: "manager_test_user_28".to_string(),
-                    balance: 10000,
-                    currency: "JPY".to_string(),
-                },
-            ]
+            confirm_pending_payment_entry(&redis, &payment_id, false, "manager_test_user_2")
+                .await
+                .unwrap(),
+            (
+                Some("manager_test_user".to_string()),
+                Some("manager_test_user_2".to_string())
+            )
         );
 
-        // Deletes balances
-        for current in balances {
-            for balance in current {
-                delete_balance(&mut con, chat_id, &balance.username, &balance.currency).unwrap();
-            }
-        }
+        assert!(resolve_pending_payment_entry(&redis, &payment_id)
+            .await
+            .unwrap());
+        assert_eq!(
+            confirm_pending_payment_entry(&redis, &payment_id, true, "manager_test_user").await,
+            Err(CrudError::NoSuchPaymentError())
+        );
+    }
 
-        // Deletes usernames
-        for username in &usernames {
-            delete_user(&mut con, &username).unwrap();
-            delete_preferred_username(&mut con, username).unwrap();
-        }
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_erase_user_messages() {
+        let redis = Redis::new();
+        let chat_id = "manager_erase_user_123456789";
 
-        // Deletes chat
-        delete_chat(&mut con, chat_id).unwrap();
-        delete_chat_currencies(&mut con, chat_id).unwrap();
-        delete_chat_settings(&mut con, chat_id).unwrap();
+        assert!(!get_erase_user_messages(&redis, chat_id).await.unwrap());
+        assert!(set_erase_user_messages(&redis, chat_id, true).await.is_ok());
+        assert!(get_erase_user_messages(&redis, chat_id).await.unwrap());
+
+        assert!(set_erase_user_messages(&redis, chat_id, false)
+            .await
+            .is_ok());
+        assert!(!get_erase_user_messages(&redis, chat_id).await.unwrap());
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
     }
 
-    #[test]
-    fn test_get_set_chat_settings() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_strict_members() {
+        let redis = Redis::new();
+        let chat_id = "manager_strict_members_123456789";
 
-        let chat_id = "manager_12345678991";
-        let usernames = vec![
-            "manager_test_user_29".to_string(),
-            "manager_test_user_30".to_string(),
-            "manager_test_user_31".to_string(),
-        ];
+        assert!(!get_strict_members(&redis, chat_id).await.unwrap());
+        assert!(set_strict_members(&redis, chat_id, true).await.is_ok());
+        assert!(get_strict_members(&redis, chat_id).await.unwrap());
+
+        assert!(set_strict_members(&redis, chat_id, false).await.is_ok());
+        assert!(!get_strict_members(&redis, chat_id).await.unwrap());
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_dual_entry() {
+        let redis = Redis::new();
+        let chat_id = "manager_dual_entry_123456789";
+
+        assert!(!get_dual_entry(&redis, chat_id).await.unwrap());
+        assert!(set_dual_entry(&redis, chat_id, true).await.is_ok());
+        assert!(get_dual_entry(&redis, chat_id).await.unwrap());
+
+        assert!(set_dual_entry(&redis, chat_id, false).await.is_ok());
+        assert!(!get_dual_entry(&redis, chat_id).await.unwrap());
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_number_format() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678918";
 
-        // Checks "default" chat settings
-        assert_eq!(get_time_zone(chat_id).unwrap(), "UTC".to_string());
         assert_eq!(
-            get_default_currency(chat_id).unwrap(),
-            CURRENCY_CODE_DEFAULT.to_string()
+            get_number_format(&redis, chat_id).await.unwrap(),
+            "1,234.56"
+        );
+        assert!(set_number_format(&redis, chat_id, "1.234,56").await.is_ok());
+        assert_eq!(
+            get_number_format(&redis, chat_id).await.unwrap(),
+            "1.234,56"
         );
-        assert_eq!(get_currency_conversion(chat_id).unwrap(), false);
 
-        // Adds chat
-        assert!(update_chat(chat_id, usernames.clone()).is_ok());
-        assert_eq!(get_time_zone(chat_id).unwrap(), "UTC".to_string());
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_currency_format() {
+        let redis = Redis::new();
+        let chat_id = "manager_currency_format_123456789";
+
         assert_eq!(
-            get_default_currency(chat_id).unwrap(),
-            CURRENCY_CODE_DEFAULT.to_string()
+            get_currency_format(&redis, chat_id).await.unwrap(),
+            "12.30 SGD"
+        );
+        assert!(set_currency_format(&redis, chat_id, "S$12.30")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_currency_format(&redis, chat_id).await.unwrap(),
+            "S$12.30"
         );
-        assert_eq!(get_currency_conversion(chat_id).unwrap(), false);
 
-        // Sets various chat settings
-        let time_zone = "GMT";
-        let currency = "USD";
-        let conversion = true;
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
+    }
 
-        assert!(set_time_zone(chat_id, time_zone).is_ok());
-        assert_eq!(get_time_zone(chat_id).unwrap(), time_zone);
-        assert!(set_default_currency(chat_id, currency).is_ok());
-        assert_eq!(get_default_currency(chat_id).unwrap(), currency);
-        assert!(set_currency_conversion(chat_id, conversion).is_ok());
-        assert_eq!(get_currency_conversion(chat_id).unwrap(), conversion);
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_language() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678913";
 
-        // Deletes chat
-        delete_chat(&mut con, chat_id).unwrap();
-        delete_chat_settings(&mut con, chat_id).unwrap();
+        assert_eq!(get_language(&redis, chat_id).await.unwrap(), "en");
+        assert!(set_language(&redis, chat_id, "zh").await.is_ok());
+        assert_eq!(get_language(&redis, chat_id).await.unwrap(), "zh");
+
+        // Deletes settings
+        let mut con = redis.connect().await.unwrap();
+        delete_chat_settings(&mut con, chat_id).await.unwrap();
     }
 
-    #[test]
-    fn test_update_retrieve_chat_spendings() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_payment_rate_entry() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678914";
+        let payment = Payment {
+            description: "manager_test_payment".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user_10".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![("manager_test_user_11".to_string(), 10000)],
+        };
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+
+        let rate = ("SGD".to_string(), 1.35);
+        assert!(update_payment_rate_entry(&redis, &payment_id, Some(&rate))
+            .await
+            .is_ok());
+        let payments = get_chat_payments_details(&redis, chat_id).await.unwrap();
+        assert_eq!(payments[0].rate, Some(rate));
+
+        assert!(update_payment_rate_entry(&redis, &payment_id, None)
+            .await
+            .is_ok());
+        let payments = get_chat_payments_details(&redis, chat_id).await.unwrap();
+        assert_eq!(payments[0].rate, None);
+
+        // Deletes payment
+        assert!(delete_payment_entry(&redis, chat_id, &payment_id)
+            .await
+            .is_ok());
+    }
 
-        let chat_id = "manager_12345678992";
-        let usernames = vec![
-            "manager_test_user_32".to_string(),
-            "manager_test_user_33".to_string(),
-            "manager_test_user_34".to_string(),
-        ];
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_acknowledgement_entries() {
+        let redis = Redis::new();
+        let chat_id = "manager_acknowledgement_123456789";
+        let payment = Payment {
+            description: "manager_test_payment".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user_82".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![
+                ("manager_test_user_82".to_string(), 5000),
+                ("manager_test_user_83".to_string(), 5000),
+            ],
+        };
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
 
-        // Adds chat
-        assert!(update_chat(chat_id, usernames.clone()).is_ok());
+        assert!(
+            set_acknowledgement_message_entry(&redis, chat_id, 1, &payment_id)
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            get_acknowledgement_message_entry(&redis, chat_id, 1)
+                .await
+                .unwrap(),
+            Some(payment_id.clone())
+        );
+        assert_eq!(
+            get_acknowledgement_message_entry(&redis, chat_id, 2)
+                .await
+                .unwrap(),
+            None
+        );
 
-        for username in &usernames {
-            update_user(&username, chat_id, None).unwrap();
-        }
+        assert!(
+            update_acknowledgement_entry(&redis, &payment_id, "Manager_Test_User_82", true)
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            get_acknowledgement_entries(&redis, &payment_id)
+                .await
+                .unwrap(),
+            vec!["manager_test_user_82"]
+        );
+        assert!(
+            update_acknowledgement_entry(&redis, &payment_id, "manager_test_user_82", false)
+                .await
+                .is_ok()
+        );
+        assert!(get_acknowledgement_entries(&redis, &payment_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Deleting the payment deletes its acknowledgements
+        assert!(
+            update_acknowledgement_entry(&redis, &payment_id, "manager_test_user_83", true)
+                .await
+                .is_ok()
+        );
+        assert!(delete_payment_entry(&redis, chat_id, &payment_id)
+            .await
+            .is_ok());
+        assert!(get_acknowledgement_entries(&redis, &payment_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
 
-        // Adds spendings
-        let spendings = vec![
-            UserBalance {
-                username: "manager_test_user_32".to_string(),
-                balance: 10000,
-                currency: "USD".to_string(),
-            },
-            UserBalance {
-                username: "manager_test_user_33".to_string(),
-                balance: 5000,
-                currency: "USD".to_string(),
-            },
-            UserBalance {
-                username: "manager_test_user_34".to_string(),
-                balance: 3000,
-                currency: "USD".to_string(),
-            },
-        ];
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_cached_rate() {
+        let redis = Redis::new();
+        let (from, to) = ("MANAGER_USD", "MANAGER_SGD");
 
-        assert!(update_chat_spendings(chat_id, spendings.clone()).is_ok());
+        assert_eq!(get_cached_rate(&redis, from, to, 3600).await.unwrap(), None);
+        assert!(set_cached_rate(&redis, from, to, 1.35, 60).await.is_ok());
+        assert_eq!(
+            get_cached_rate(&redis, from, to, 3600).await.unwrap(),
+            Some(1.35)
+        );
 
-        // Manually add currency
-        assert!(add_chat_currency(&mut con, chat_id, "USD").is_ok());
+        // Kept as the last rate fetched, as of now
+        let (rate, fetched_at) = get_last_cached_rate(&redis, from, to)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(rate, 1.35);
+        assert!(Utc::now().timestamp() - fetched_at < 60);
+
+        // Expiring soon, and still in use
+        assert!(get_expiring_rate_pairs(&redis, 300)
+            .await
+            .unwrap()
+            .contains(&(from.to_string(), to.to_string())));
+        assert!(!get_expiring_rate_pairs(&redis, 30)
+            .await
+            .unwrap()
+            .contains(&(from.to_string(), to.to_string())));
+
+        // Deletes rate
+        let mut con = redis.connect().await.unwrap();
+        delete_rate(&mut con, from, to).await.unwrap();
+        delete_last_rate(&mut con, from, to).await.unwrap();
+        delete_rate_pair(&mut con, from, to).await.unwrap();
+    }
 
-        // Retrieves spendings
-        let retrieved_spendings = retrieve_chat_spendings_currency(chat_id, "USD");
-        assert!(retrieved_spendings.is_ok());
-        assert_eq!(retrieved_spendings.unwrap(), spendings.clone());
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_delete_tutorial_entry() {
+        let redis = Redis::new();
+        let chat_id = "manager_tutorial_123456789";
+        let ledger = get_tutorial_ledger(chat_id);
 
-        // Updates spendings with another currency
-        let new_spendings = vec![
-            UserBalance {
-                username: "manager_test_user_32".to_string(),
-                balance: 500,
-                currency: "JPY".to_string(),
-            },
-            UserBalance {
-                username: "manager_test_user_33".to_string(),
-                balance: 1000,
-                currency: "JPY".to_string(),
-            },
-            UserBalance {
-                username: "manager_test_user_34".to_string(),
-                balance: 1500,
-                currency: "JPY".to_string(),
-            },
-        ];
+        let payment = Payment {
+            description: "Pizza".to_string(),
+            datetime: "2024-03-12 10:00:00 UTC".to_string(),
+            creditor: "tutorial_user".to_string(),
+            currency: "USD".to_string(),
+            total: 3000,
+            debts: vec![
+                ("tutorial_user".to_string(), 1000),
+                ("tutorial_alice".to_string(), 2000),
+            ],
+        };
+        let payment_id = add_tutorial_payment_entry(&redis, chat_id, &payment)
+            .await
+            .unwrap();
+        update_chat_balances(
+            &redis,
+            &ledger,
+            vec![
+                UserBalance {
+                    username: "tutorial_user".to_string(),
+                    currency: "USD".to_string(),
+                    balance: 2000,
+                },
+                UserBalance {
+                    username: "tutorial_alice".to_string(),
+                    currency: "USD".to_string(),
+                    balance: -2000,
+                },
+            ],
+        )
+        .await
+        .unwrap();
 
-        assert!(update_chat_spendings(chat_id, new_spendings.clone()).is_ok());
+        // Kept in the practice ledger only
+        assert!(!get_chat_balances(&redis, &ledger).await.unwrap().is_empty());
+        assert!(get_chat_balances(&redis, chat_id).await.unwrap().is_empty());
 
-        // Manually add currency
-        assert!(add_chat_currency(&mut con, chat_id, "JPY").is_ok());
+        assert!(delete_tutorial_entry(&redis, chat_id).await.is_ok());
+        assert!(get_chat_balances(&redis, &ledger).await.unwrap().is_empty());
+        assert!(get_payment_entry(&redis, &payment_id).await.is_err());
+    }
 
-        // Retrieves and checks spendings again
-        let retrieved_spendings = retrieve_chat_spendings_currency(chat_id, "JPY");
-        assert!(retrieved_spendings.is_ok());
-        assert_eq!(retrieved_spendings.unwrap(), new_spendings.clone());
-        let retrieved_spendings = retrieve_chat_spendings_currency(chat_id, "USD");
-        assert_eq!(retrieved_spendings.unwrap(), spendings.clone());
-        let retrieved_spendings = retrieve_chat_spendings(chat_id);
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_start_end_trip() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678915";
+
+        assert_eq!(get_chat_active_trip(&redis, chat_id).await.unwrap(), None);
+        let trip = start_trip(&redis, chat_id, "Japan").await.unwrap();
+        assert_eq!(trip, ("1".to_string(), "Japan".to_string()));
         assert_eq!(
-            retrieved_spendings.unwrap(),
-            vec![spendings.clone(), new_spendings.clone()]
+            get_chat_active_trip(&redis, chat_id).await.unwrap(),
+            Some(trip.clone())
         );
 
-        // Checks validity of currencies
-        let valid_currencies = get_valid_chat_currencies(chat_id);
-        assert!(valid_currencies.is_ok());
+        assert_eq!(end_trip(&redis, chat_id).await.unwrap(), Some(trip.clone()));
+        assert_eq!(get_chat_active_trip(&redis, chat_id).await.unwrap(), None);
+        assert_eq!(end_trip(&redis, chat_id).await.unwrap(), None);
+
+        // Resumes the same trip
+        assert_eq!(start_trip(&redis, chat_id, "japan").await.unwrap(), trip);
+        assert_eq!(start_trip(&redis, chat_id, "Korea").await.unwrap().0, "2");
+        assert_eq!(get_chat_trips(&redis, chat_id).await.unwrap().len(), 2);
+
+        // Deletes trips
+        let mut con = redis.connect().await.unwrap();
+        delete_trips(&mut con, chat_id).await.unwrap();
+        end_trip(&redis, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_delete_trip_payment_entry() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678916";
+        let payment = Payment {
+            description: "manager_test_payment".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user_10".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![("manager_test_user_11".to_string(), 10000)],
+        };
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
         assert_eq!(
-            valid_currencies.unwrap(),
-            vec!["USD".to_string(), "JPY".to_string()]
+            get_payment_trip_entry(&redis, &payment_id).await.unwrap(),
+            None
         );
 
-        // Deletes spendings
-        for spending in spendings {
-            assert!(
-                delete_spending(&mut con, chat_id, &spending.username, &spending.currency).is_ok()
-            );
-        }
-
-        for spending in new_spendings {
-            assert!(
-                delete_spending(&mut con, chat_id, &spending.username, &spending.currency).is_ok()
-            );
-        }
+        assert!(add_trip_payment_entry(&redis, chat_id, "1", &payment_id)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_payment_trip_entry(&redis, &payment_id).await.unwrap(),
+            Some("1".to_string())
+        );
+        let ledger = get_trip_ledger(chat_id, "1");
+        assert_eq!(
+            get_chat_payments_details(&redis, &ledger).await.unwrap()[0].payment_id,
+            payment_id
+        );
 
-        // Deletes usernames
-        for username in &usernames {
-            delete_user(&mut con, &username).unwrap();
-            delete_preferred_username(&mut con, username).unwrap();
-        }
+        assert!(delete_trip_payment_entry(&redis, chat_id, "1", &payment_id)
+            .await
+            .is_ok());
+        assert!(get_chat_payments_details(&redis, &ledger).await.is_err());
 
-        // Deletes chat
-        delete_chat(&mut con, chat_id).unwrap();
-        delete_chat_currencies(&mut con, chat_id).unwrap();
-        delete_chat_settings(&mut con, chat_id).unwrap();
+        // Deletes payment
+        assert!(delete_payment_entry(&redis, chat_id, &payment_id)
+            .await
+            .is_ok());
     }
 
-    #[test]
-    fn test_request_limit() {
-        let user_id = "manager_test_user_35";
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_delete_chat_entry() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678935";
+        let payment = Payment {
+            description: "manager_test_payment".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user_90".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![("manager_test_user_91".to_string(), 10000)],
+        };
 
-        // Checks that request limit is not exceeded
-        assert!(!is_request_limit_exceeded(user_id, 1).unwrap());
+        update_chat(&redis, chat_id, vec!["manager_test_user_90".to_string()])
+            .await
+            .unwrap();
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        start_trip(&redis, chat_id, "Japan").await.unwrap();
+        set_time_zone(&redis, chat_id, "Asia/Tokyo").await.unwrap();
+        assert!(get_chat_ids(&redis)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+
+        assert!(delete_chat_entry(&redis, chat_id).await.is_ok());
+        assert!(!get_chat_ids(&redis)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+        assert_eq!(
+            get_chat_payments_details(&redis, chat_id).await,
+            Err(CrudError::NoPaymentsError())
+        );
+        assert_eq!(
+            get_payment_entry(&redis, &payment_id).await,
+            Err(CrudError::NoSuchPaymentError())
+        );
+        assert!(get_chat_trips(&redis, chat_id).await.unwrap().is_empty());
+        assert_eq!(get_time_zone(&redis, chat_id).await.unwrap(), "UTC");
+    }
 
-        // Checks that request limit is exceeded
-        assert!(is_request_limit_exceeded(user_id, 1).unwrap());
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_export_import_chat() {
+        let redis = Redis::new();
+        let chat_id = "manager_12345678917";
+        let new_chat_id = "manager_12345678918";
+        let payment = Payment {
+            description: "manager_test_payment".to_string(),
+            datetime: "2021-01-01T00:00:00".to_string(),
+            creditor: "manager_test_user_12".to_string(),
+            currency: "USD".to_string(),
+            total: 10000,
+            debts: vec![("manager_test_user_13".to_string(), 10000)],
+        };
+        let users = vec![
+            "manager_test_user_12".to_string(),
+            "manager_test_user_13".to_string(),
+        ];
 
-        // Checks that request limit is not exceeded
-        assert!(!is_request_limit_exceeded(user_id, 2).unwrap());
+        update_chat(&redis, chat_id, users.clone()).await.unwrap();
+        let payment_id = add_payment_entry(&redis, chat_id, &payment).await.unwrap();
+        let trip = start_trip(&redis, chat_id, "Japan").await.unwrap();
+        add_trip_payment_entry(&redis, chat_id, &trip.0, &payment_id)
+            .await
+            .unwrap();
+        update_chat_balances(
+            &redis,
+            chat_id,
+            vec![UserBalance {
+                username: users[1].clone(),
+                currency: "USD".to_string(),
+                balance: -10000,
+            }],
+        )
+        .await
+        .unwrap();
+
+        let backup = export_chat(&redis, chat_id).await.unwrap();
+        assert_eq!(backup.version, BACKUP_VERSION);
+        assert_eq!(backup.payments[0].payment, payment);
+        assert_eq!(backup.payments[0].trip, Some(trip.0.clone()));
+        assert_eq!(backup.active_trip, Some(trip.0.clone()));
+
+        // Imports into another chat, which should then export the same backup
+        assert!(import_chat(&redis, new_chat_id, &backup).await.is_ok());
+        let new_backup = export_chat(&redis, new_chat_id).await.unwrap();
+        assert_eq!(new_backup, backup);
+        assert_eq!(
+            get_chat_balances_currency(&redis, new_chat_id, "USD")
+                .await
+                .unwrap()[0]
+                .balance,
+            -10000
+        );
 
-        // Deletes request
-        let mut con = connect().unwrap();
-        delete_request(&mut con, user_id).unwrap();
+        // Importing again overwrites, instead of duplicating
+        assert!(import_chat(&redis, new_chat_id, &backup).await.is_ok());
+        assert_eq!(export_chat(&redis, new_chat_id).await.unwrap(), backup);
+
+        // Deletes chats
+        let mut con = redis.connect().await.unwrap();
+        for chat in [chat_id, new_chat_id] {
+            for payment_id in get_chat_payments(&mut con, chat).await.unwrap() {
+                delete_payment(&mut con, &payment_id).await.unwrap();
+            }
+            for (trip_id, _) in get_trips(&mut con, chat).await.unwrap() {
+                delete_ledger(&mut con, &get_trip_ledger(chat, &trip_id))
+                    .await
+                    .unwrap();
+            }
+            delete_trips(&mut con, chat).await.unwrap();
+            delete_active_trip(&mut con, chat).await.unwrap();
+            delete_ledger(&mut con, chat).await.unwrap();
+            delete_chat_settings(&mut con, chat).await.unwrap();
+        }
+        for user in &users {
+            delete_user(&mut con, user).await.unwrap();
+            delete_preferred_username(&mut con, user).await.unwrap();
+        }
     }
 }
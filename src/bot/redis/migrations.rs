@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use redis::{AsyncCommands, RedisResult};
+
+use super::{
+    chat::{
+        get_chat_currency_usage, get_chat_payments, get_payment_chats, set_chat_currency_usage,
+    },
+    connect::RedisConnection,
+    payment::get_payment,
+};
+
+/* Migration Operations
+ * Migration represents a change in how data is laid out in Redis, between versions of the bot.
+ * The schema version of the deployment is stored, and each migration upgrades it by one.
+ * Migrations must be idempotent, as a deployment may stop partway through one,
+ * and run it again on the next startup.
+ * Has get and set operations for the schema version, and the migration steps themselves.
+ */
+
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+// Latest schema version, to be bumped with every new migration step
+pub const SCHEMA_VERSION_LATEST: u64 = 1;
+
+// Sets the schema version of the deployment
+pub async fn set_schema_version(con: &mut RedisConnection, version: u64) -> RedisResult<()> {
+    con.set(SCHEMA_VERSION_KEY, version).await
+}
+
+// Gets the schema version of the deployment, which is 0 if never migrated
+pub async fn get_schema_version(con: &mut RedisConnection) -> RedisResult<u64> {
+    let version: Option<u64> = con.get(SCHEMA_VERSION_KEY).await?;
+    Ok(version.unwrap_or(0))
+}
+
+// Applies the migration step that upgrades the schema to the given version
+pub async fn apply_migration(con: &mut RedisConnection, version: u64) -> RedisResult<()> {
+    match version {
+        1 => backfill_currency_usage(con).await,
+        _ => Ok(()),
+    }
+}
+
+/* Version 1
+ * Counts the payments made in each currency, for chats from before these were tracked.
+ * Chats that already have their currency usage are left as they are.
+ */
+async fn backfill_currency_usage(con: &mut RedisConnection) -> RedisResult<()> {
+    for chat_id in get_payment_chats(con).await? {
+        backfill_chat_currency_usage(con, &chat_id).await?;
+    }
+
+    Ok(())
+}
+
+// Counts the payments made in each currency for a single chat, if not already counted
+// All counts are written at once, so that a chat is never left partly counted
+async fn backfill_chat_currency_usage(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    if !get_chat_currency_usage(con, chat_id).await?.is_empty() {
+        return Ok(());
+    }
+
+    let mut usage: HashMap<String, u64> = HashMap::new();
+    for payment_id in get_chat_payments(con, chat_id).await? {
+        // Skips payments that no longer exist
+        if let Ok(payment) = get_payment(con, &payment_id).await {
+            *usage.entry(payment.currency).or_insert(0) += 1;
+        }
+    }
+    let usage: Vec<(String, u64)> = usage.into_iter().collect();
+    set_chat_currency_usage(con, chat_id, &usage).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::{
+        chat::{add_chat_payment, delete_all_chat_payment, delete_chat_currency_usage},
+        connect::Redis,
+        payment::{add_payment, delete_payment, Payment},
+    };
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_backfill_chat_currency_usage() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "migration_123456789";
+        let mut payment_ids: Vec<String> = Vec::new();
+        for currency in ["USD", "JPY", "USD"] {
+            let payment = Payment {
+                description: "test_migration".to_string(),
+                datetime: "2024-03-12T00:00:00Z".to_string(),
+                creditor: "test_creditor".to_string(),
+                currency: currency.to_string(),
+                total: 1000,
+                debts: vec![("test_debtor".to_string(), 1000)],
+            };
+            let payment_id = add_payment(&mut con, &payment).await.unwrap();
+            add_chat_payment(&mut con, chat_id, &payment_id)
+                .await
+                .unwrap();
+            payment_ids.push(payment_id);
+        }
+
+        backfill_chat_currency_usage(&mut con, chat_id)
+            .await
+            .unwrap();
+        let usage = get_chat_currency_usage(&mut con, chat_id).await.unwrap();
+        assert_eq!(usage.get("USD"), Some(&2));
+        assert_eq!(usage.get("JPY"), Some(&1));
+
+        // Running again leaves the counts as they are
+        backfill_chat_currency_usage(&mut con, chat_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_chat_currency_usage(&mut con, chat_id).await.unwrap(),
+            usage
+        );
+
+        for payment_id in payment_ids {
+            delete_payment(&mut con, &payment_id).await.unwrap();
+        }
+        delete_all_chat_payment(&mut con, chat_id).await.unwrap();
+        delete_chat_currency_usage(&mut con, chat_id).await.unwrap();
+    }
+}
@@ -1,24 +1,104 @@
 // Exported functions
 pub use self::manager::{
-    add_payment_entry, delete_payment_entry, get_chat_balances, get_chat_balances_currency,
-    get_chat_payments_details, get_currency_conversion, get_default_currency, get_erase_messages,
-    get_payment_entry, get_time_zone, get_valid_chat_currencies, is_request_limit_exceeded,
-    retrieve_chat_spendings, retrieve_chat_spendings_currency, set_currency_conversion,
-    set_default_currency, set_erase_messages, set_time_zone, update_chat, update_chat_balances,
-    update_chat_spendings, update_payment_entry, update_user,
+    add_adjustment_entry, add_chat_audit_entry, add_payment_dispute, add_payment_version_entry,
+    add_pending_payment_entry, add_trip_payment_entry, add_tutorial_payment_entry,
+    archive_payment_entries, claim_pending_payment_entry, confirm_pending_payment_entry,
+    delete_acknowledgement_entries, delete_archive_entries, delete_chat_entry, delete_display_name,
+    delete_payment_entries, delete_payment_template, delete_split_group, delete_trip_payment_entry,
+    delete_tutorial_entry, end_trip, export_chat, get_acknowledgement_entries,
+    get_acknowledgement_message_entry, get_admin_only, get_allowed_currencies,
+    get_approval_threshold, get_bot_stats_entry, get_cached_admins, get_cached_bot_can_delete,
+    get_cached_rate, get_chat_active_trip, get_chat_audit_entries, get_chat_balances, get_chat_ids,
+    get_chat_member_username, get_chat_payments_details, get_chat_trips, get_chat_usernames,
+    get_currency_conversion, get_currency_format, get_data_retention, get_data_retention_chats,
+    get_debt_reminder, get_debt_reminder_chats, get_default_currency, get_display_names,
+    get_dual_entry, get_erase_messages, get_erase_user_messages, get_expiring_rate_pairs,
+    get_frequent_currencies, get_language, get_last_archive_month, get_last_cached_rate,
+    get_last_digest_week, get_last_reminder_date, get_last_retention_date,
+    get_latest_payment_entry, get_monthly_archive, get_monthly_archive_chats,
+    get_next_rounding_offset, get_number_format, get_onboarding_step, get_pairwise_debts,
+    get_passive_mode, get_payment_count, get_payment_dispute, get_payment_due_dates,
+    get_payment_entry, get_payment_links, get_payment_note_entry, get_payment_receipt_entry,
+    get_payment_template, get_payment_templates, get_payment_trip_entry,
+    get_payment_version_entries, get_pending_payment_entry, get_private_chats, get_rounding,
+    get_spending_limit_warnings, get_spending_limits, get_split_groups, get_strict_members,
+    get_time_zone, get_topic_default_currency, get_topic_time_zone, get_trash_chat_ids,
+    get_trash_entries, get_treasurer, get_user_chat_entries, get_valid_chat_currencies,
+    get_verbosity, get_week_start, get_weekly_digest, get_weekly_digest_chats,
+    get_weekly_spending_cap, import_chat, is_adjustment_entry, is_private_payment_entry,
+    is_request_limit_exceeded, is_transfer_payment_entry, purge_trash_entries,
+    remove_claimed_payment_entry, reset_topic_settings, resolve_payment_dispute,
+    resolve_pending_payment_entry, restore_pending_payment_entry, restore_trash_entry,
+    retrieve_chat_spendings, retrieve_chat_spendings_currency, run_migrations,
+    set_acknowledgement_message_entry, set_admin_only, set_allowed_currencies,
+    set_approval_threshold, set_bot_permission_notified, set_cached_admins,
+    set_cached_bot_can_delete, set_cached_rate, set_currency_conversion, set_currency_format,
+    set_data_retention, set_debt_reminder, set_default_currency, set_display_name, set_dual_entry,
+    set_erase_messages, set_erase_user_messages, set_language, set_last_archive_month,
+    set_last_digest_week, set_last_reminder_date, set_last_retention_date, set_monthly_archive,
+    set_number_format, set_onboarding_step, set_pairwise_debts, set_passive_mode,
+    set_payment_due_date, set_payment_link, set_payment_note_entry, set_payment_private_entry,
+    set_payment_receipt_entry, set_payment_template, set_private_chat, set_rounding,
+    set_spending_limit, set_split_group, set_strict_members, set_time_zone,
+    set_topic_default_currency, set_topic_time_zone, set_treasurer, set_verbosity, set_week_start,
+    set_weekly_digest, set_weekly_spending_cap, start_trip, update_acknowledgement_entry,
+    update_chat, update_payment_entry, update_payment_rate_entry, update_trip_users, update_user,
 };
 
 // Exported structs and types
+pub use self::audit::{
+    AuditEntry, AUDIT_ADDED, AUDIT_ADJUSTED, AUDIT_DELETED, AUDIT_EDITED, AUDIT_RESTORED,
+};
+pub use self::bot_stats::BotStats;
 pub use self::chat::{Debt, CURRENCY_CODE_DEFAULT};
-pub use self::manager::{CrudError, UserBalance, UserPayment};
-pub use self::payment::Payment;
+pub use self::connect::Redis;
+pub use self::manager::{
+    ChatBackup, CrudError, LedgerPayment, SpendingLimit, TrashEntry, UserBalance, UserPayment,
+    WeeklyCap, BACKUP_VERSION,
+};
+pub use self::payment::{Payment, PaymentAudit, PaymentRate};
+pub use self::pending::PendingPayment;
+#[cfg(test)]
+pub use self::store::MemoryStore;
+pub use self::store::{LedgerStore, RedisStore};
+pub use self::template::PaymentTemplate;
+pub use self::trip::{get_trip_ledger, Trip};
+pub use self::tutorial::get_tutorial_ledger;
+pub use self::version::PaymentVersion;
 
 // Submodules
+mod acknowledgement;
+mod adjustment;
+mod admin;
+mod archive;
+mod audit;
 mod balance;
+mod bot_stats;
 mod chat;
 mod connect;
+mod digest;
+mod display_name;
+mod dispute;
+mod due;
+mod limit;
 mod manager;
+mod migrations;
+mod onboarding;
 mod payment;
+mod pending;
+mod permission;
+mod rate;
+mod reminder;
 mod request;
+mod retention;
+mod settlement;
 mod spending;
+mod split_group;
+mod store;
+mod template;
+mod topic;
+mod trash;
+mod trip;
+mod tutorial;
 mod user;
+mod version;
@@ -0,0 +1,61 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Onboarding CRUD Operations
+ * Onboarding represents the setup wizard of a chat, shown when the bot is added to it.
+ * Onboarding comprises the current step of the wizard, which expires after some time,
+ * so that an abandoned wizard does not resume much later.
+ * Has set, get, and delete operations.
+ */
+
+const ONBOARDING_KEY: &str = "chat_onboarding";
+
+// Sets the current onboarding step of a chat, expiring after some time
+pub async fn set_chat_onboarding(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    step: &str,
+    ttl: usize,
+) -> RedisResult<()> {
+    con.set_ex(format!("{ONBOARDING_KEY}:{chat_id}"), step, ttl)
+        .await
+}
+
+// Gets the current onboarding step of a chat, if any
+pub async fn get_chat_onboarding(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Option<String>> {
+    con.get(format!("{ONBOARDING_KEY}:{chat_id}")).await
+}
+
+// Deletes the current onboarding step of a chat
+pub async fn delete_chat_onboarding(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{ONBOARDING_KEY}:{chat_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_chat_onboarding() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "onboarding_123456789";
+        assert_eq!(get_chat_onboarding(&mut con, chat_id).await.unwrap(), None);
+        assert!(set_chat_onboarding(&mut con, chat_id, "time_zone", 600)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_chat_onboarding(&mut con, chat_id).await.unwrap(),
+            Some("time_zone".to_string())
+        );
+
+        assert!(delete_chat_onboarding(&mut con, chat_id).await.is_ok());
+        assert_eq!(get_chat_onboarding(&mut con, chat_id).await.unwrap(), None);
+    }
+}
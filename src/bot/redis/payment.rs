@@ -1,10 +1,19 @@
-use redis::{Commands, Connection, RedisResult};
+use redis::{AsyncCommands, RedisResult};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::connect::RedisConnection;
+
 /* Payment CRUD Operations
  * Payment represents a payment entry, used in groups.
- * Payment comprises of a description, immutable datetime, creditor, numeric total,
+ * Payment comprises of a description, datetime, creditor, numeric total,
  * and a list of debts (stored under a different key).
+ * May also have the conversion rate into the chat's default currency, fixed when added,
+ * and the ID of the trip it was added in.
+ * Also keeps who added and last edited it, and when, for payments added since this was tracked.
+ * May also be private, in which case its description is only shown to those involved.
+ * May also be a transfer, in which case it counts towards balances but not spendings.
+ * May also have a note, and a receipt, being the Telegram file ID of a photo.
  * Has add, exists, get, update, and delete operations.
  */
 
@@ -14,8 +23,20 @@ const PAYMENT_DEBT_KEY: &str = "payment_debt";
 // Debt is an abstraction containing a debtor (String) and the owed amount (i64)
 pub type Debt = (String, i64);
 
+// PaymentRate is the currency converted into (String), and the conversion rate (f64)
+pub type PaymentRate = (String, f64);
+
+// PaymentAudit contains who added and last edited a payment, and when, if known
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct PaymentAudit {
+    pub created_by: Option<String>,
+    pub created_at: Option<String>,
+    pub last_edited_by: Option<String>,
+    pub last_edited_at: Option<String>,
+}
+
 // Payment contains all fields stored in Redis related to a single payment entry
-#[derive(Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct Payment {
     pub description: String,
     pub datetime: String,
@@ -26,34 +47,39 @@ pub struct Payment {
 }
 
 // Adds a new payment to Redis
-pub fn add_payment(con: &mut Connection, payment: &Payment) -> RedisResult<String> {
+pub async fn add_payment(con: &mut RedisConnection, payment: &Payment) -> RedisResult<String> {
     let id = Uuid::new_v4().to_string();
     let main_key = format!("{PAYMENT_KEY}:{id}");
-    con.hset(&main_key, "description", &payment.description)?;
-    con.hset(&main_key, "datetime", &payment.datetime)?;
-    con.hset(&main_key, "creditor", &payment.creditor)?;
-    con.hset(&main_key, "currency", &payment.currency)?;
-    con.hset(&main_key, "total", &payment.total)?;
+    con.hset::<_, _, _, ()>(&main_key, "description", &payment.description)
+        .await?;
+    con.hset::<_, _, _, ()>(&main_key, "datetime", &payment.datetime)
+        .await?;
+    con.hset::<_, _, _, ()>(&main_key, "creditor", &payment.creditor)
+        .await?;
+    con.hset::<_, _, _, ()>(&main_key, "currency", &payment.currency)
+        .await?;
+    con.hset::<_, _, _, ()>(&main_key, "total", &payment.total)
+        .await?;
 
     let debt_key = format!("{PAYMENT_DEBT_KEY}:{id}");
     for debt in &payment.debts {
-        con.rpush(&debt_key, debt)?;
+        con.rpush::<_, _, ()>(&debt_key, debt).await?;
     }
 
     Ok(id)
 }
 
 // Gets a payment from Redis
-pub fn get_payment(con: &mut Connection, payment_id: &str) -> RedisResult<Payment> {
+pub async fn get_payment(con: &mut RedisConnection, payment_id: &str) -> RedisResult<Payment> {
     let main_key = format!("{PAYMENT_KEY}:{payment_id}");
-    let description: String = con.hget(&main_key, "description")?;
-    let datetime: String = con.hget(&main_key, "datetime")?;
-    let creditor: String = con.hget(&main_key, "creditor")?;
-    let currency: String = con.hget(&main_key, "currency")?;
-    let total: i64 = con.hget(&main_key, "total")?;
+    let description: String = con.hget(&main_key, "description").await?;
+    let datetime: String = con.hget(&main_key, "datetime").await?;
+    let creditor: String = con.hget(&main_key, "creditor").await?;
+    let currency: String = con.hget(&main_key, "currency").await?;
+    let total: i64 = con.hget(&main_key, "total").await?;
 
     let debt_key = format!("{PAYMENT_DEBT_KEY}:{payment_id}");
-    let debts: Vec<Debt> = con.lrange(&debt_key, 0, -1)?;
+    let debts: Vec<Debt> = con.lrange(&debt_key, 0, -1).await?;
 
     let payment = Payment {
         description,
@@ -68,10 +94,13 @@ pub fn get_payment(con: &mut Connection, payment_id: &str) -> RedisResult<Paymen
 }
 
 // Updates a payment in Redis
-pub fn update_payment(
-    con: &mut Connection,
+// Each field is optional, with None leaving it unchanged
+#[allow(clippy::too_many_arguments)]
+pub async fn update_payment(
+    con: &mut RedisConnection,
     payment_id: &str,
     description: Option<&str>,
+    datetime: Option<&str>,
     creditor: Option<&str>,
     currency: Option<&str>,
     total: Option<&i64>,
@@ -79,34 +108,238 @@ pub fn update_payment(
 ) -> RedisResult<()> {
     let main_key = format!("{PAYMENT_KEY}:{payment_id}");
     if let Some(desc) = description {
-        con.hset(&main_key, "description", desc)?;
+        con.hset::<_, _, _, ()>(&main_key, "description", desc)
+            .await?;
+    }
+    if let Some(time) = datetime {
+        con.hset::<_, _, _, ()>(&main_key, "datetime", time).await?;
     }
     if let Some(cred) = creditor {
-        con.hset(&main_key, "creditor", cred)?;
+        con.hset::<_, _, _, ()>(&main_key, "creditor", cred).await?;
     }
     if let Some(curr) = currency {
-        con.hset(&main_key, "currency", curr)?;
+        con.hset::<_, _, _, ()>(&main_key, "currency", curr).await?;
     }
     if let Some(tot) = total {
-        con.hset(&main_key, "total", tot)?;
+        con.hset::<_, _, _, ()>(&main_key, "total", tot).await?;
     }
     if let Some(debt) = debts {
         let debt_key = format!("{PAYMENT_DEBT_KEY}:{payment_id}");
-        con.del(&debt_key)?;
+        con.del::<_, ()>(&debt_key).await?;
         for d in debt {
-            con.rpush(&debt_key, d)?;
+            con.rpush::<_, _, ()>(&debt_key, d).await?;
         }
     }
 
     Ok(())
 }
 
+// Sets the conversion rate of a payment in Redis
+pub async fn set_payment_rate(
+    con: &mut RedisConnection,
+    payment_id: &str,
+    rate: &PaymentRate,
+) -> RedisResult<()> {
+    let main_key = format!("{PAYMENT_KEY}:{payment_id}");
+    con.hset::<_, _, _, ()>(&main_key, "rate_currency", &rate.0)
+        .await?;
+    con.hset::<_, _, _, ()>(&main_key, "rate", rate.1).await?;
+
+    Ok(())
+}
+
+// Gets the conversion rate of a payment from Redis, if any
+pub async fn get_payment_rate(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<Option<PaymentRate>> {
+    let main_key = format!("{PAYMENT_KEY}:{payment_id}");
+    let currency: Option<String> = con.hget(&main_key, "rate_currency").await?;
+    let rate: Option<f64> = con.hget(&main_key, "rate").await?;
+
+    Ok(currency.zip(rate))
+}
+
+// Deletes the conversion rate of a payment from Redis
+pub async fn delete_payment_rate(con: &mut RedisConnection, payment_id: &str) -> RedisResult<()> {
+    let main_key = format!("{PAYMENT_KEY}:{payment_id}");
+    con.hdel::<_, _, ()>(&main_key, &["rate_currency", "rate"])
+        .await?;
+
+    Ok(())
+}
+
+// Sets the trip of a payment in Redis
+pub async fn set_payment_trip(
+    con: &mut RedisConnection,
+    payment_id: &str,
+    trip_id: &str,
+) -> RedisResult<()> {
+    con.hset(format!("{PAYMENT_KEY}:{payment_id}"), "trip", trip_id)
+        .await
+}
+
+// Gets the trip of a payment from Redis, if any
+pub async fn get_payment_trip(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<Option<String>> {
+    con.hget(format!("{PAYMENT_KEY}:{payment_id}"), "trip")
+        .await
+}
+
+// Marks a payment as private in Redis
+pub async fn set_payment_private(con: &mut RedisConnection, payment_id: &str) -> RedisResult<()> {
+    con.hset(format!("{PAYMENT_KEY}:{payment_id}"), "private", true)
+        .await
+}
+
+// Checks if a payment is private
+pub async fn get_payment_private(con: &mut RedisConnection, payment_id: &str) -> RedisResult<bool> {
+    let is_private: Option<bool> = con
+        .hget(format!("{PAYMENT_KEY}:{payment_id}"), "private")
+        .await?;
+    Ok(is_private.unwrap_or(false))
+}
+
+// Marks or unmarks a payment as a transfer in Redis
+pub async fn set_payment_transfer(
+    con: &mut RedisConnection,
+    payment_id: &str,
+    is_transfer: bool,
+) -> RedisResult<()> {
+    let main_key = format!("{PAYMENT_KEY}:{payment_id}");
+    if is_transfer {
+        con.hset(&main_key, "transfer", true).await
+    } else {
+        con.hdel(&main_key, "transfer").await
+    }
+}
+
+// Checks if a payment is a transfer
+pub async fn get_payment_transfer(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<bool> {
+    let is_transfer: Option<bool> = con
+        .hget(format!("{PAYMENT_KEY}:{payment_id}"), "transfer")
+        .await?;
+    Ok(is_transfer.unwrap_or(false))
+}
+
+// Sets the note of a payment in Redis
+pub async fn set_payment_note(
+    con: &mut RedisConnection,
+    payment_id: &str,
+    note: &str,
+) -> RedisResult<()> {
+    con.hset(format!("{PAYMENT_KEY}:{payment_id}"), "note", note)
+        .await
+}
+
+// Gets the note of a payment from Redis, if any
+pub async fn get_payment_note(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<Option<String>> {
+    con.hget(format!("{PAYMENT_KEY}:{payment_id}"), "note")
+        .await
+}
+
+// Sets the receipt of a payment in Redis, as the file ID of its photo
+pub async fn set_payment_receipt(
+    con: &mut RedisConnection,
+    payment_id: &str,
+    file_id: &str,
+) -> RedisResult<()> {
+    con.hset(format!("{PAYMENT_KEY}:{payment_id}"), "receipt", file_id)
+        .await
+}
+
+// Gets the receipt of a payment from Redis, if any
+pub async fn get_payment_receipt(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<Option<String>> {
+    con.hget(format!("{PAYMENT_KEY}:{payment_id}"), "receipt")
+        .await
+}
+
+// Sets who added a payment in Redis, and when
+pub async fn set_payment_created(
+    con: &mut RedisConnection,
+    payment_id: &str,
+    username: &str,
+    datetime: &str,
+) -> RedisResult<()> {
+    let main_key = format!("{PAYMENT_KEY}:{payment_id}");
+    con.hset::<_, _, _, ()>(&main_key, "created_by", username)
+        .await?;
+    con.hset::<_, _, _, ()>(&main_key, "created_at", datetime)
+        .await?;
+
+    Ok(())
+}
+
+// Sets who last edited a payment in Redis, and when
+pub async fn set_payment_edited(
+    con: &mut RedisConnection,
+    payment_id: &str,
+    username: &str,
+    datetime: &str,
+) -> RedisResult<()> {
+    let main_key = format!("{PAYMENT_KEY}:{payment_id}");
+    con.hset::<_, _, _, ()>(&main_key, "last_edited_by", username)
+        .await?;
+    con.hset::<_, _, _, ()>(&main_key, "last_edited_at", datetime)
+        .await?;
+
+    Ok(())
+}
+
+// Gets who added and last edited a payment from Redis, and when
+// Fields are None if not known, such as for payments added before this was tracked
+pub async fn get_payment_audit(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<PaymentAudit> {
+    let main_key = format!("{PAYMENT_KEY}:{payment_id}");
+    let created_by: Option<String> = con.hget(&main_key, "created_by").await?;
+    let created_at: Option<String> = con.hget(&main_key, "created_at").await?;
+    let last_edited_by: Option<String> = con.hget(&main_key, "last_edited_by").await?;
+    let last_edited_at: Option<String> = con.hget(&main_key, "last_edited_at").await?;
+
+    Ok(PaymentAudit {
+        created_by,
+        created_at,
+        last_edited_by,
+        last_edited_at,
+    })
+}
+
+// Counts all payments stored, across all chats, by scanning for them
+pub async fn count_payments(con: &mut RedisConnection) -> RedisResult<usize> {
+    let prefix = format!("{PAYMENT_KEY}:");
+    let mut iter: redis::AsyncIter<String> = con.scan_match(format!("{prefix}*")).await?;
+    let mut count = 0;
+    while let Some(key) = iter.next_item().await {
+        if key
+            .strip_prefix(&prefix)
+            .is_some_and(|id| !id.contains(':'))
+        {
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
 // Deletes a payment from Redis
-pub fn delete_payment(con: &mut Connection, payment_id: &str) -> RedisResult<()> {
+pub async fn delete_payment(con: &mut RedisConnection, payment_id: &str) -> RedisResult<()> {
     let main_key = format!("{PAYMENT_KEY}:{payment_id}");
     let debt_key = format!("{PAYMENT_DEBT_KEY}:{payment_id}");
-    con.del(&main_key)?;
-    con.del(&debt_key)?;
+    con.del::<_, ()>(&main_key).await?;
+    con.del::<_, ()>(&debt_key).await?;
 
     Ok(())
 }
@@ -115,11 +348,12 @@ pub fn delete_payment(con: &mut Connection, payment_id: &str) -> RedisResult<()>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bot::redis::connect::connect;
+    use crate::bot::redis::connect::Redis;
 
-    #[test]
-    fn test_add_get_payment() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_payment() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let description = "test_payment";
         let datetime = "2020-01-01T00:00:00Z";
@@ -135,20 +369,22 @@ mod tests {
             total,
             debts: debts.clone(),
         };
-        let payment_op = add_payment(&mut con, &first_payment);
+        let payment_op = add_payment(&mut con, &first_payment).await;
 
         assert!(payment_op.is_ok());
 
         let payment_id = payment_op.unwrap();
-        let payment = get_payment(&mut con, &payment_id);
+        let payment = get_payment(&mut con, &payment_id).await;
         assert_eq!(payment.unwrap(), first_payment);
+        assert!(count_payments(&mut con).await.unwrap() >= 1);
 
-        delete_payment(&mut con, &payment_id).unwrap();
+        delete_payment(&mut con, &payment_id).await.unwrap();
     }
 
-    #[test]
-    fn test_update_payment() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_payment() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let description = "test_payment";
         let datetime = "2020-01-01T00:00:00Z";
@@ -164,9 +400,10 @@ mod tests {
             total,
             debts: debts.clone(),
         };
-        let payment_id = add_payment(&mut con, &first_payment).unwrap();
+        let payment_id = add_payment(&mut con, &first_payment).await.unwrap();
 
         let new_description = "new_test_payment";
+        let new_datetime = "2021-12-31 12:00:00 UTC";
         let new_creditor = "new_test_creditor";
         let new_currency = "USD";
         let new_total = 20000;
@@ -176,20 +413,22 @@ mod tests {
             &mut con,
             &payment_id,
             Some(new_description),
+            Some(new_datetime),
             Some(new_creditor),
             Some(new_currency),
             Some(&new_total),
             Some(new_debts.clone()),
-        );
+        )
+        .await;
 
         assert!(update_op.is_ok());
 
-        let payment = get_payment(&mut con, &payment_id);
+        let payment = get_payment(&mut con, &payment_id).await;
         assert_eq!(
             payment.unwrap(),
             Payment {
                 description: new_description.to_string(),
-                datetime: datetime.to_string(),
+                datetime: new_datetime.to_string(),
                 creditor: new_creditor.to_string(),
                 currency: new_currency.to_string(),
                 total: new_total,
@@ -197,12 +436,224 @@ mod tests {
             }
         );
 
-        delete_payment(&mut con, &payment_id).unwrap();
+        delete_payment(&mut con, &payment_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_payment_rate() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let payment_id = add_payment(
+            &mut con,
+            &Payment {
+                description: "test_payment".to_string(),
+                datetime: "2020-01-01T00:00:00Z".to_string(),
+                creditor: "test_creditor".to_string(),
+                currency: "USD".to_string(),
+                total: 10000,
+                debts: vec![("test_debtor".to_string(), 10000)],
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(get_payment_rate(&mut con, &payment_id).await.unwrap(), None);
+
+        let rate = ("SGD".to_string(), 1.35);
+        assert!(set_payment_rate(&mut con, &payment_id, &rate).await.is_ok());
+        assert_eq!(
+            get_payment_rate(&mut con, &payment_id).await.unwrap(),
+            Some(rate)
+        );
+
+        assert!(delete_payment_rate(&mut con, &payment_id).await.is_ok());
+        assert_eq!(get_payment_rate(&mut con, &payment_id).await.unwrap(), None);
+
+        delete_payment(&mut con, &payment_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_payment_trip() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let payment_id = add_payment(
+            &mut con,
+            &Payment {
+                description: "test_payment".to_string(),
+                datetime: "2020-01-01T00:00:00Z".to_string(),
+                creditor: "test_creditor".to_string(),
+                currency: "USD".to_string(),
+                total: 10000,
+                debts: vec![("test_debtor".to_string(), 10000)],
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(get_payment_trip(&mut con, &payment_id).await.unwrap(), None);
+
+        assert!(set_payment_trip(&mut con, &payment_id, "1").await.is_ok());
+        assert_eq!(
+            get_payment_trip(&mut con, &payment_id).await.unwrap(),
+            Some("1".to_string())
+        );
+
+        delete_payment(&mut con, &payment_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_payment_private() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let payment_id = add_payment(
+            &mut con,
+            &Payment {
+                description: "test_payment".to_string(),
+                datetime: "2020-01-01T00:00:00Z".to_string(),
+                creditor: "test_creditor".to_string(),
+                currency: "USD".to_string(),
+                total: 10000,
+                debts: vec![("test_debtor".to_string(), 10000)],
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!get_payment_private(&mut con, &payment_id).await.unwrap());
+
+        assert!(set_payment_private(&mut con, &payment_id).await.is_ok());
+        assert!(get_payment_private(&mut con, &payment_id).await.unwrap());
+
+        delete_payment(&mut con, &payment_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_payment_transfer() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let payment_id = add_payment(
+            &mut con,
+            &Payment {
+                description: "test_payment".to_string(),
+                datetime: "2020-01-01T00:00:00Z".to_string(),
+                creditor: "test_creditor".to_string(),
+                currency: "USD".to_string(),
+                total: 10000,
+                debts: vec![("test_debtor".to_string(), 10000)],
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!get_payment_transfer(&mut con, &payment_id).await.unwrap());
+
+        assert!(set_payment_transfer(&mut con, &payment_id, true)
+            .await
+            .is_ok());
+        assert!(get_payment_transfer(&mut con, &payment_id).await.unwrap());
+
+        assert!(set_payment_transfer(&mut con, &payment_id, false)
+            .await
+            .is_ok());
+        assert!(!get_payment_transfer(&mut con, &payment_id).await.unwrap());
+
+        delete_payment(&mut con, &payment_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_payment_note_receipt() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let payment_id = add_payment(
+            &mut con,
+            &Payment {
+                description: "test_payment".to_string(),
+                datetime: "2020-01-01T00:00:00Z".to_string(),
+                creditor: "test_creditor".to_string(),
+                currency: "USD".to_string(),
+                total: 10000,
+                debts: vec![("test_debtor".to_string(), 10000)],
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(get_payment_note(&mut con, &payment_id).await.unwrap(), None);
+        assert_eq!(
+            get_payment_receipt(&mut con, &payment_id).await.unwrap(),
+            None
+        );
+
+        assert!(set_payment_note(&mut con, &payment_id, "Paid in cash")
+            .await
+            .is_ok());
+        assert!(set_payment_receipt(&mut con, &payment_id, "file_id_1")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_payment_note(&mut con, &payment_id).await.unwrap(),
+            Some("Paid in cash".to_string())
+        );
+        assert_eq!(
+            get_payment_receipt(&mut con, &payment_id).await.unwrap(),
+            Some("file_id_1".to_string())
+        );
+
+        delete_payment(&mut con, &payment_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_payment_audit() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let payment_id = add_payment(
+            &mut con,
+            &Payment {
+                description: "test_payment".to_string(),
+                datetime: "2020-01-01T00:00:00Z".to_string(),
+                creditor: "test_creditor".to_string(),
+                currency: "USD".to_string(),
+                total: 10000,
+                debts: vec![("test_debtor".to_string(), 10000)],
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            get_payment_audit(&mut con, &payment_id).await.unwrap(),
+            PaymentAudit::default()
+        );
+
+        let created_at = "2020-01-01 00:00:00 UTC";
+        let edited_at = "2020-01-02 00:00:00 UTC";
+        assert!(
+            set_payment_created(&mut con, &payment_id, "test_creditor", created_at)
+                .await
+                .is_ok()
+        );
+        assert!(
+            set_payment_edited(&mut con, &payment_id, "test_debtor", edited_at)
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            get_payment_audit(&mut con, &payment_id).await.unwrap(),
+            PaymentAudit {
+                created_by: Some("test_creditor".to_string()),
+                created_at: Some(created_at.to_string()),
+                last_edited_by: Some("test_debtor".to_string()),
+                last_edited_at: Some(edited_at.to_string()),
+            }
+        );
+
+        delete_payment(&mut con, &payment_id).await.unwrap();
     }
 
-    #[test]
-    fn test_delete_payment() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_delete_payment() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let description = "test_payment";
         let datetime = "2020-01-01T00:00:00Z";
@@ -221,7 +672,8 @@ mod tests {
                 debts: debts.clone(),
             },
         )
+        .await
         .unwrap();
-        assert!(delete_payment(&mut con, &payment_id).is_ok());
+        assert!(delete_payment(&mut con, &payment_id).await.is_ok());
     }
 }
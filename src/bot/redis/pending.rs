@@ -0,0 +1,275 @@
+use redis::{AsyncCommands, RedisResult, Script};
+
+use super::connect::RedisConnection;
+
+/* Pending CRUD Operations
+ * Pending represents a payment entry that is awaiting approval, and does not affect balances yet.
+ * The payment details are stored as a normal payment, under the same ID.
+ * Pending comprises the chat of the payment, the user who added it,
+ * whether it is private or a transfer, and the conversion rate given for it, if any.
+ * With dual entry verification, it also comprises who confirmed it as the payer and as a debtor.
+ * While it is being approved, it is claimed, such that it is only approved once.
+ * Has add, exists, get, confirm, claim, restore, and delete operations.
+ */
+
+const PENDING_KEY: &str = "pending";
+const CLAIMED_KEY: &str = "pending_claimed";
+const PAYER_CONFIRMATION: &str = "payer_confirmation";
+const DEBTOR_CONFIRMATION: &str = "debtor_confirmation";
+
+// PendingPayment contains the details of who added a pending payment, and where
+#[derive(Debug, PartialEq, Clone)]
+pub struct PendingPayment {
+    pub chat_id: String,
+    pub sender_id: String,
+    pub sender_username: String,
+    pub is_private: bool,
+    pub is_transfer: bool,
+    pub rate: Option<f64>,
+}
+
+// Adds a new pending payment to Redis
+pub async fn add_pending(
+    con: &mut RedisConnection,
+    payment_id: &str,
+    pending: &PendingPayment,
+) -> RedisResult<()> {
+    let key = format!("{PENDING_KEY}:{payment_id}");
+    con.hset::<_, _, _, ()>(&key, "chat_id", &pending.chat_id)
+        .await?;
+    con.hset::<_, _, _, ()>(&key, "sender_id", &pending.sender_id)
+        .await?;
+    con.hset::<_, _, _, ()>(&key, "sender_username", &pending.sender_username)
+        .await?;
+    con.hset::<_, _, _, ()>(&key, "is_private", pending.is_private)
+        .await?;
+    con.hset::<_, _, _, ()>(&key, "is_transfer", pending.is_transfer)
+        .await?;
+    if let Some(rate) = pending.rate {
+        con.hset::<_, _, _, ()>(&key, "rate", rate).await?;
+    }
+    Ok(())
+}
+
+// Checks if a payment is pending
+pub async fn get_pending_exists(con: &mut RedisConnection, payment_id: &str) -> RedisResult<bool> {
+    con.exists(format!("{PENDING_KEY}:{payment_id}")).await
+}
+
+// Gets a pending payment from Redis
+pub async fn get_pending(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<PendingPayment> {
+    let key = format!("{PENDING_KEY}:{payment_id}");
+    let chat_id: String = con.hget(&key, "chat_id").await?;
+    let sender_id: String = con.hget(&key, "sender_id").await?;
+    let sender_username: String = con.hget(&key, "sender_username").await?;
+    let is_private: Option<bool> = con.hget(&key, "is_private").await?;
+    let is_transfer: Option<bool> = con.hget(&key, "is_transfer").await?;
+    let rate: Option<f64> = con.hget(&key, "rate").await?;
+    Ok(PendingPayment {
+        chat_id,
+        sender_id,
+        sender_username,
+        is_private: is_private.unwrap_or(false),
+        is_transfer: is_transfer.unwrap_or(false),
+        rate,
+    })
+}
+
+// Sets the user who confirmed a pending payment, either as the payer or as a debtor
+// Returns false if it was already confirmed in that role, such that the first confirmation counts
+pub async fn set_pending_confirmation(
+    con: &mut RedisConnection,
+    payment_id: &str,
+    is_payer: bool,
+    username: &str,
+) -> RedisResult<bool> {
+    let field = if is_payer {
+        PAYER_CONFIRMATION
+    } else {
+        DEBTOR_CONFIRMATION
+    };
+    con.hset_nx(format!("{PENDING_KEY}:{payment_id}"), field, username)
+        .await
+}
+
+// Gets the users who confirmed a pending payment, as the payer and as a debtor, if any
+pub async fn get_pending_confirmations(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<(Option<String>, Option<String>)> {
+    let key = format!("{PENDING_KEY}:{payment_id}");
+    let payer: Option<String> = con.hget(&key, PAYER_CONFIRMATION).await?;
+    let debtor: Option<String> = con.hget(&key, DEBTOR_CONFIRMATION).await?;
+    Ok((payer, debtor))
+}
+
+// Deletes a pending payment from Redis
+// Returns false if it was no longer pending, such that it is only resolved once
+pub async fn delete_pending(con: &mut RedisConnection, payment_id: &str) -> RedisResult<bool> {
+    let deleted: i64 = con.del(format!("{PENDING_KEY}:{payment_id}")).await?;
+    Ok(deleted > 0)
+}
+
+// Claims a pending payment to be approved, setting it aside with all its details
+// Returns false if it was no longer pending, such that it is only claimed once
+pub async fn claim_pending(con: &mut RedisConnection, payment_id: &str) -> RedisResult<bool> {
+    let script = Script::new(
+        "if redis.call('EXISTS', KEYS[1]) == 0 then return 0 end
+        redis.call('RENAME', KEYS[1], KEYS[2])
+        return 1",
+    );
+    script
+        .key(format!("{PENDING_KEY}:{payment_id}"))
+        .key(format!("{CLAIMED_KEY}:{payment_id}"))
+        .invoke_async(con)
+        .await
+}
+
+// Restores a claimed payment as pending, with all its details, when it could not be approved
+pub async fn restore_pending(con: &mut RedisConnection, payment_id: &str) -> RedisResult<()> {
+    con.rename(
+        format!("{CLAIMED_KEY}:{payment_id}"),
+        format!("{PENDING_KEY}:{payment_id}"),
+    )
+    .await
+}
+
+// Deletes a claimed payment from Redis, once it has been approved
+pub async fn delete_claimed_pending(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<()> {
+    con.del(format!("{CLAIMED_KEY}:{payment_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_delete_pending() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let payment_id = "pending_123456789";
+        let pending = PendingPayment {
+            chat_id: "123456789".to_string(),
+            sender_id: "987654321".to_string(),
+            sender_username: "test_user".to_string(),
+            is_private: true,
+            is_transfer: false,
+            rate: Some(1.37),
+        };
+
+        assert!(!get_pending_exists(&mut con, payment_id).await.unwrap());
+        assert!(add_pending(&mut con, payment_id, &pending).await.is_ok());
+        assert!(get_pending_exists(&mut con, payment_id).await.unwrap());
+        assert_eq!(get_pending(&mut con, payment_id).await.unwrap(), pending);
+
+        assert!(delete_pending(&mut con, payment_id).await.unwrap());
+        assert!(!delete_pending(&mut con, payment_id).await.unwrap());
+        assert!(!get_pending_exists(&mut con, payment_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_pending_confirmation() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let payment_id = "pending_987654321";
+        let pending = PendingPayment {
+            chat_id: "123456789".to_string(),
+            sender_id: "987654321".to_string(),
+            sender_username: "test_user".to_string(),
+            is_private: false,
+            is_transfer: false,
+            rate: None,
+        };
+        assert!(add_pending(&mut con, payment_id, &pending).await.is_ok());
+        assert_eq!(
+            get_pending_confirmations(&mut con, payment_id)
+                .await
+                .unwrap(),
+            (None, None)
+        );
+
+        assert!(
+            set_pending_confirmation(&mut con, payment_id, true, "test_user")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !set_pending_confirmation(&mut con, payment_id, true, "test_user_2")
+                .await
+                .unwrap()
+        );
+        assert!(
+            set_pending_confirmation(&mut con, payment_id, false, "test_user_2")
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            get_pending_confirmations(&mut con, payment_id)
+                .await
+                .unwrap(),
+            (
+                Some("test_user".to_string()),
+                Some("test_user_2".to_string())
+            )
+        );
+        assert_eq!(get_pending(&mut con, payment_id).await.unwrap(), pending);
+
+        assert!(delete_pending(&mut con, payment_id).await.unwrap());
+        assert_eq!(
+            get_pending_confirmations(&mut con, payment_id)
+                .await
+                .unwrap(),
+            (None, None)
+        );
+    }
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_claim_restore_pending() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let payment_id = "pending_123456780";
+        let pending = PendingPayment {
+            chat_id: "123456789".to_string(),
+            sender_id: "987654321".to_string(),
+            sender_username: "test_user".to_string(),
+            is_private: false,
+            is_transfer: true,
+            rate: None,
+        };
+        assert!(add_pending(&mut con, payment_id, &pending).await.is_ok());
+        assert!(
+            set_pending_confirmation(&mut con, payment_id, true, "test_user")
+                .await
+                .unwrap()
+        );
+
+        // Only claimed once, and no longer pending while claimed
+        assert!(claim_pending(&mut con, payment_id).await.unwrap());
+        assert!(!claim_pending(&mut con, payment_id).await.unwrap());
+        assert!(!get_pending_exists(&mut con, payment_id).await.unwrap());
+
+        // Restored with all its details
+        assert!(restore_pending(&mut con, payment_id).await.is_ok());
+        assert_eq!(get_pending(&mut con, payment_id).await.unwrap(), pending);
+        assert_eq!(
+            get_pending_confirmations(&mut con, payment_id)
+                .await
+                .unwrap(),
+            (Some("test_user".to_string()), None)
+        );
+
+        assert!(claim_pending(&mut con, payment_id).await.unwrap());
+        assert!(delete_claimed_pending(&mut con, payment_id).await.is_ok());
+        assert!(!claim_pending(&mut con, payment_id).await.unwrap());
+        assert!(!get_pending_exists(&mut con, payment_id).await.unwrap());
+    }
+}
@@ -0,0 +1,107 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Permission CRUD Operations
+ * Permission represents what the bot is allowed to do in a chat, as learnt from Telegram.
+ * Permission comprises whether the bot can delete messages, which expires after some time,
+ * so that permissions granted later are picked up eventually.
+ * Also tracks whether the chat has been told about a missing permission, so it is told once.
+ * Has set, get, and delete operations.
+ */
+
+const PERMISSION_KEY: &str = "bot_permission";
+const PERMISSION_NOTIFIED_KEY: &str = "bot_permission_notified";
+
+// Sets whether the bot can delete messages in a chat, expiring after some time
+pub async fn set_bot_can_delete(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    can_delete: bool,
+    ttl: usize,
+) -> RedisResult<()> {
+    con.set_ex(format!("{PERMISSION_KEY}:{chat_id}"), can_delete, ttl)
+        .await
+}
+
+// Gets whether the bot can delete messages in a chat, if known
+pub async fn get_bot_can_delete(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Option<bool>> {
+    con.get(format!("{PERMISSION_KEY}:{chat_id}")).await
+}
+
+// Deletes the cached permission of the bot in a chat
+pub async fn delete_bot_can_delete(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{PERMISSION_KEY}:{chat_id}")).await
+}
+
+// Marks a chat as notified of a missing permission
+// Returns true only if the chat had not been notified before
+pub async fn set_permission_notified(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<bool> {
+    con.set_nx(format!("{PERMISSION_NOTIFIED_KEY}:{chat_id}"), true)
+        .await
+}
+
+// Clears the notified mark of a chat, so a later missing permission is notified again
+pub async fn delete_permission_notified(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<()> {
+    con.del(format!("{PERMISSION_NOTIFIED_KEY}:{chat_id}"))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_bot_can_delete() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "permission_123456789";
+        assert_eq!(get_bot_can_delete(&mut con, chat_id).await.unwrap(), None);
+
+        assert!(set_bot_can_delete(&mut con, chat_id, false, 600)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_bot_can_delete(&mut con, chat_id).await.unwrap(),
+            Some(false)
+        );
+
+        assert!(set_bot_can_delete(&mut con, chat_id, true, 600)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_bot_can_delete(&mut con, chat_id).await.unwrap(),
+            Some(true)
+        );
+
+        assert!(delete_bot_can_delete(&mut con, chat_id).await.is_ok());
+        assert_eq!(get_bot_can_delete(&mut con, chat_id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_delete_permission_notified() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "permission_notified_123456789";
+        assert!(delete_permission_notified(&mut con, chat_id).await.is_ok());
+
+        assert!(set_permission_notified(&mut con, chat_id).await.unwrap());
+        assert!(!set_permission_notified(&mut con, chat_id).await.unwrap());
+
+        assert!(delete_permission_notified(&mut con, chat_id).await.is_ok());
+        assert!(set_permission_notified(&mut con, chat_id).await.unwrap());
+        assert!(delete_permission_notified(&mut con, chat_id).await.is_ok());
+    }
+}
@@ -0,0 +1,198 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Rate CRUD Operations
+ * Rate represents a cached currency conversion rate, from one currency to another.
+ * Rate comprises the conversion rate, which expires after some time,
+ * and a set of all currency pairs cached, each with a marker of when it was last used.
+ * The last rate fetched for each pair is also kept, with when it was fetched, without expiring,
+ * so that a rate can still be used for a while if it cannot be fetched again.
+ * Has add, get, and delete operations.
+ */
+
+const RATE_KEY: &str = "rate";
+const RATE_USED_KEY: &str = "rate_used";
+const RATE_PAIRS_KEY: &str = "rate_pairs";
+const RATE_LAST_KEY: &str = "rate_last";
+
+// Sets the conversion rate between two currencies, expiring after some time
+pub async fn set_rate(
+    con: &mut RedisConnection,
+    from: &str,
+    to: &str,
+    rate: f64,
+    ttl: usize,
+) -> RedisResult<()> {
+    con.set_ex(format!("{RATE_KEY}:{from}:{to}"), rate, ttl)
+        .await
+}
+
+// Gets the conversion rate between two currencies, if it is cached
+pub async fn get_rate(con: &mut RedisConnection, from: &str, to: &str) -> RedisResult<Option<f64>> {
+    con.get(format!("{RATE_KEY}:{from}:{to}")).await
+}
+
+// Gets the seconds left before the conversion rate between two currencies expires
+// Negative if the rate is not cached
+pub async fn get_rate_ttl(con: &mut RedisConnection, from: &str, to: &str) -> RedisResult<i64> {
+    con.ttl(format!("{RATE_KEY}:{from}:{to}")).await
+}
+
+// Deletes the conversion rate between two currencies
+// Mainly for testing purposes
+#[allow(dead_code)]
+pub async fn delete_rate(con: &mut RedisConnection, from: &str, to: &str) -> RedisResult<()> {
+    con.del(format!("{RATE_KEY}:{from}:{to}")).await
+}
+
+// Marks the conversion rate between two currencies as used, for some time
+pub async fn set_rate_used(
+    con: &mut RedisConnection,
+    from: &str,
+    to: &str,
+    ttl: usize,
+) -> RedisResult<()> {
+    con.set_ex(format!("{RATE_USED_KEY}:{from}:{to}"), true, ttl)
+        .await
+}
+
+// Checks if the conversion rate between two currencies has been used recently
+pub async fn get_rate_used(con: &mut RedisConnection, from: &str, to: &str) -> RedisResult<bool> {
+    con.exists(format!("{RATE_USED_KEY}:{from}:{to}")).await
+}
+
+// Deletes the marker of the conversion rate between two currencies being used
+// Mainly for testing purposes
+#[allow(dead_code)]
+pub async fn delete_rate_used(con: &mut RedisConnection, from: &str, to: &str) -> RedisResult<()> {
+    con.del(format!("{RATE_USED_KEY}:{from}:{to}")).await
+}
+
+// Adds a currency pair to the set of cached pairs
+pub async fn add_rate_pair(con: &mut RedisConnection, from: &str, to: &str) -> RedisResult<()> {
+    con.sadd(RATE_PAIRS_KEY, format!("{from}:{to}")).await
+}
+
+// Gets all cached currency pairs
+pub async fn get_rate_pairs(con: &mut RedisConnection) -> RedisResult<Vec<(String, String)>> {
+    let pairs: Vec<String> = con.smembers(RATE_PAIRS_KEY).await?;
+    Ok(pairs
+        .iter()
+        .filter_map(|pair| pair.split_once(':'))
+        .map(|(from, to)| (from.to_string(), to.to_string()))
+        .collect())
+}
+
+// Removes a currency pair from the set of cached pairs
+pub async fn delete_rate_pair(con: &mut RedisConnection, from: &str, to: &str) -> RedisResult<()> {
+    con.srem(RATE_PAIRS_KEY, format!("{from}:{to}")).await
+}
+
+// Sets the last conversion rate fetched between two currencies, with when it was fetched
+pub async fn set_last_rate(
+    con: &mut RedisConnection,
+    from: &str,
+    to: &str,
+    rate: f64,
+    fetched_at: i64,
+) -> RedisResult<()> {
+    con.hset_multiple(
+        format!("{RATE_LAST_KEY}:{from}:{to}"),
+        &[
+            ("rate", rate.to_string()),
+            ("fetched_at", fetched_at.to_string()),
+        ],
+    )
+    .await
+}
+
+// Gets the last conversion rate fetched between two currencies, with when it was fetched
+pub async fn get_last_rate(
+    con: &mut RedisConnection,
+    from: &str,
+    to: &str,
+) -> RedisResult<Option<(f64, i64)>> {
+    let (rate, fetched_at): (Option<f64>, Option<i64>) = con
+        .hget(
+            format!("{RATE_LAST_KEY}:{from}:{to}"),
+            &["rate", "fetched_at"],
+        )
+        .await?;
+    Ok(rate.zip(fetched_at))
+}
+
+// Deletes the last conversion rate fetched between two currencies
+// Mainly for testing purposes
+#[allow(dead_code)]
+pub async fn delete_last_rate(con: &mut RedisConnection, from: &str, to: &str) -> RedisResult<()> {
+    con.del(format!("{RATE_LAST_KEY}:{from}:{to}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_rate() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let (from, to) = ("RATE_USD", "RATE_SGD");
+        assert_eq!(get_rate(&mut con, from, to).await.unwrap(), None);
+        assert!(get_rate_ttl(&mut con, from, to).await.unwrap() < 0);
+
+        assert!(set_rate(&mut con, from, to, 1.35, 3600).await.is_ok());
+        assert_eq!(get_rate(&mut con, from, to).await.unwrap(), Some(1.35));
+        assert!(get_rate_ttl(&mut con, from, to).await.unwrap() > 0);
+
+        assert!(delete_rate(&mut con, from, to).await.is_ok());
+        assert_eq!(get_rate(&mut con, from, to).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_last_rate() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let (from, to) = ("RATE_GBP", "RATE_AUD");
+        assert_eq!(get_last_rate(&mut con, from, to).await.unwrap(), None);
+
+        assert!(set_last_rate(&mut con, from, to, 1.92, 1710237600)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_last_rate(&mut con, from, to).await.unwrap(),
+            Some((1.92, 1710237600))
+        );
+
+        assert!(delete_last_rate(&mut con, from, to).await.is_ok());
+        assert_eq!(get_last_rate(&mut con, from, to).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_delete_rate_pair() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let (from, to) = ("RATE_EUR", "RATE_JPY");
+        assert!(!get_rate_used(&mut con, from, to).await.unwrap());
+        assert!(set_rate_used(&mut con, from, to, 3600).await.is_ok());
+        assert!(get_rate_used(&mut con, from, to).await.unwrap());
+        assert!(delete_rate_used(&mut con, from, to).await.is_ok());
+        assert!(!get_rate_used(&mut con, from, to).await.unwrap());
+
+        assert!(add_rate_pair(&mut con, from, to).await.is_ok());
+        assert!(get_rate_pairs(&mut con)
+            .await
+            .unwrap()
+            .contains(&(from.to_string(), to.to_string())));
+
+        assert!(delete_rate_pair(&mut con, from, to).await.is_ok());
+        assert!(!get_rate_pairs(&mut con)
+            .await
+            .unwrap()
+            .contains(&(from.to_string(), to.to_string())));
+    }
+}
@@ -0,0 +1,95 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Reminder CRUD Operations
+ * Reminder represents the automatic nudges posted in a chat, mentioning all debtors.
+ * Reminder comprises a set of chats with debt reminders turned on,
+ * and the last date a reminder was posted for each chat.
+ * Has add, get, and delete operations.
+ */
+
+const REMINDER_SCHEDULE_KEY: &str = "reminder_schedule";
+const REMINDER_DATE_KEY: &str = "reminder_date";
+
+// Adds a chat to the debt reminder schedule
+pub async fn add_reminder_schedule(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.sadd(REMINDER_SCHEDULE_KEY, chat_id).await
+}
+
+// Gets all chats in the debt reminder schedule
+pub async fn get_reminder_schedule(con: &mut RedisConnection) -> RedisResult<Vec<String>> {
+    con.smembers(REMINDER_SCHEDULE_KEY).await
+}
+
+// Removes a chat from the debt reminder schedule
+pub async fn delete_reminder_schedule(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.srem(REMINDER_SCHEDULE_KEY, chat_id).await
+}
+
+// Sets the last date a reminder was posted for a chat
+pub async fn set_reminder_date(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    date: &str,
+) -> RedisResult<()> {
+    con.set(format!("{REMINDER_DATE_KEY}:{chat_id}"), date)
+        .await
+}
+
+// Gets the last date a reminder was posted for a chat, if any
+pub async fn get_reminder_date(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Option<String>> {
+    con.get(format!("{REMINDER_DATE_KEY}:{chat_id}")).await
+}
+
+// Deletes the last date a reminder was posted for a chat
+pub async fn delete_reminder_date(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{REMINDER_DATE_KEY}:{chat_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_delete_reminder_schedule() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "reminder_123456789";
+        assert!(add_reminder_schedule(&mut con, chat_id).await.is_ok());
+        assert!(get_reminder_schedule(&mut con)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+
+        assert!(delete_reminder_schedule(&mut con, chat_id).await.is_ok());
+        assert!(!get_reminder_schedule(&mut con)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_reminder_date() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "reminder_1234567890";
+        assert_eq!(get_reminder_date(&mut con, chat_id).await.unwrap(), None);
+        assert!(set_reminder_date(&mut con, chat_id, "2024-05-20")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_reminder_date(&mut con, chat_id).await.unwrap(),
+            Some("2024-05-20".to_string())
+        );
+
+        assert!(delete_reminder_date(&mut con, chat_id).await.is_ok());
+        assert_eq!(get_reminder_date(&mut con, chat_id).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,98 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Retention CRUD Operations
+ * Retention represents the automatic cleanup of old payments in a chat, to bound its growth.
+ * Retention comprises a set of chats with data retention turned on,
+ * and the last date a cleanup was run for each chat.
+ * Has add, get, and delete operations.
+ */
+
+const RETENTION_SCHEDULE_KEY: &str = "retention_schedule";
+const RETENTION_DATE_KEY: &str = "retention_date";
+
+// Adds a chat to the data retention schedule
+pub async fn add_retention_schedule(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.sadd(RETENTION_SCHEDULE_KEY, chat_id).await
+}
+
+// Gets all chats in the data retention schedule
+pub async fn get_retention_schedule(con: &mut RedisConnection) -> RedisResult<Vec<String>> {
+    con.smembers(RETENTION_SCHEDULE_KEY).await
+}
+
+// Removes a chat from the data retention schedule
+pub async fn delete_retention_schedule(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<()> {
+    con.srem(RETENTION_SCHEDULE_KEY, chat_id).await
+}
+
+// Sets the last date a cleanup was run for a chat
+pub async fn set_retention_date(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    date: &str,
+) -> RedisResult<()> {
+    con.set(format!("{RETENTION_DATE_KEY}:{chat_id}"), date)
+        .await
+}
+
+// Gets the last date a cleanup was run for a chat, if any
+pub async fn get_retention_date(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Option<String>> {
+    con.get(format!("{RETENTION_DATE_KEY}:{chat_id}")).await
+}
+
+// Deletes the last date a cleanup was run for a chat
+pub async fn delete_retention_date(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{RETENTION_DATE_KEY}:{chat_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_delete_retention_schedule() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "retention_123456789";
+        assert!(add_retention_schedule(&mut con, chat_id).await.is_ok());
+        assert!(get_retention_schedule(&mut con)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+
+        assert!(delete_retention_schedule(&mut con, chat_id).await.is_ok());
+        assert!(!get_retention_schedule(&mut con)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_retention_date() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "retention_1234567890";
+        assert_eq!(get_retention_date(&mut con, chat_id).await.unwrap(), None);
+        assert!(set_retention_date(&mut con, chat_id, "2024-05-20")
+            .await
+            .is_ok());
+        assert_eq!(
+            get_retention_date(&mut con, chat_id).await.unwrap(),
+            Some("2024-05-20".to_string())
+        );
+
+        assert!(delete_retention_date(&mut con, chat_id).await.is_ok());
+        assert_eq!(get_retention_date(&mut con, chat_id).await.unwrap(), None);
+    }
+}
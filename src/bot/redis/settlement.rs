@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Settlement CRUD Operations
+ * Settlement represents the last time that all balances of a chat in a currency were zero.
+ * Settlement comprises a hash of currencies to datetimes, for each chat.
+ * Datetimes are stored in the format %Y-%m-%d %H:%M:%S UTC, same as when payments are added.
+ * Payments in a currency last changed before its settlement are settled.
+ * Has set, get, and delete operations.
+ */
+
+const SETTLEMENT_KEY: &str = "settlement";
+
+// Sets the last time that all balances of a chat in a currency were zero
+pub async fn set_settlement(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    currency: &str,
+    datetime: &str,
+) -> RedisResult<()> {
+    con.hset(format!("{SETTLEMENT_KEY}:{chat_id}"), currency, datetime)
+        .await
+}
+
+// Gets the settlements of all currencies with one in a chat
+pub async fn get_settlements(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<HashMap<String, String>> {
+    con.hgetall(format!("{SETTLEMENT_KEY}:{chat_id}")).await
+}
+
+// Deletes the settlements of all currencies in a chat
+pub async fn delete_settlements(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{SETTLEMENT_KEY}:{chat_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_settlement() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "settlement_123456789";
+        assert!(
+            set_settlement(&mut con, chat_id, "USD", "2024-03-12 10:00:00 UTC")
+                .await
+                .is_ok()
+        );
+        assert!(
+            set_settlement(&mut con, chat_id, "SGD", "2024-03-13 10:00:00 UTC")
+                .await
+                .is_ok()
+        );
+        assert!(
+            set_settlement(&mut con, chat_id, "USD", "2024-04-01 08:30:00 UTC")
+                .await
+                .is_ok()
+        );
+
+        let settlements = get_settlements(&mut con, chat_id).await.unwrap();
+        assert_eq!(settlements.len(), 2);
+        assert_eq!(
+            settlements.get("USD"),
+            Some(&"2024-04-01 08:30:00 UTC".to_string())
+        );
+
+        assert!(delete_settlements(&mut con, chat_id).await.is_ok());
+        assert!(get_settlements(&mut con, chat_id).await.unwrap().is_empty());
+    }
+}
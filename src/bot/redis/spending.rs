@@ -1,15 +1,19 @@
-use redis::{Commands, Connection, RedisResult};
+use redis::{AsyncCommands, RedisResult, Script};
+
+use super::connect::RedisConnection;
 
 /* Spending CRUD Operations
  * Spending represents the total expenses incurred by a user in a group.
- * Has get, set, exists, and delete operations.
+ * Has get, set, add, exists, replace, move, and delete operations.
+ * Spendings of many users and currencies can also be fetched together in a single call.
+ * Changes to a spending are applied atomically, so that concurrent payments are not lost.
  */
 
 const SPENDING_KEY: &str = "spending";
 
 // Adds or updates a spending to Redis
-pub fn set_spending(
-    con: &mut Connection,
+pub async fn set_spending(
+    con: &mut RedisConnection,
     chat_id: &str,
     user_id: &str,
     currency: &str,
@@ -19,82 +23,325 @@ pub fn set_spending(
         format!("{SPENDING_KEY}:{chat_id}:{user_id}:{currency}"),
         spending,
     )
+    .await
+}
+
+// Adds a change to a spending atomically, starting from 0 if it does not exist
+// Returns the new spending, which may be negative if the change is invalid
+pub async fn add_spending(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    user_id: &str,
+    currency: &str,
+    change: i64,
+) -> RedisResult<i64> {
+    con.incr(
+        format!("{SPENDING_KEY}:{chat_id}:{user_id}:{currency}"),
+        change,
+    )
+    .await
 }
 
 // Checks if spending exists
-pub fn get_spending_exists(
-    con: &mut Connection,
+pub async fn get_spending_exists(
+    con: &mut RedisConnection,
     chat_id: &str,
     user_id: &str,
     currency: &str,
 ) -> RedisResult<bool> {
     con.exists(format!("{SPENDING_KEY}:{chat_id}:{user_id}:{currency}"))
+        .await
 }
 
 // Gets a spending
-pub fn get_spending(
-    con: &mut Connection,
+pub async fn get_spending(
+    con: &mut RedisConnection,
     chat_id: &str,
     user_id: &str,
     currency: &str,
 ) -> RedisResult<u64> {
     con.get(format!("{SPENDING_KEY}:{chat_id}:{user_id}:{currency}"))
+        .await
+}
+
+// Gets the spendings of the given users in the given currencies in a single call
+// Returns the spendings by currency, then by user, with None for spendings that do not exist
+pub async fn get_spendings(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    users: &[String],
+    currencies: &[String],
+) -> RedisResult<Vec<Option<u64>>> {
+    let keys: Vec<String> = currencies
+        .iter()
+        .flat_map(|currency| {
+            users
+                .iter()
+                .map(move |user_id| format!("{SPENDING_KEY}:{chat_id}:{user_id}:{currency}"))
+        })
+        .collect();
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    redis::cmd("MGET").arg(keys).query_async(con).await
 }
 
 // Deletes a spending in Redis
-// Mainly for testing purposes
-// In application, no real need to delete keys
-#[allow(dead_code)]
-pub fn delete_spending(
-    con: &mut Connection,
+pub async fn delete_spending(
+    con: &mut RedisConnection,
     chat_id: &str,
     user_id: &str,
     currency: &str,
 ) -> RedisResult<()> {
     con.del(format!("{SPENDING_KEY}:{chat_id}:{user_id}:{currency}"))
+        .await
+}
+
+// Moves a spending from one user to another atomically, adding it to any spending of the other user
+// The spending is read and moved in a single step, so that no concurrent change to it is lost
+pub async fn move_spending(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    user_id: &str,
+    new_user_id: &str,
+    currency: &str,
+) -> RedisResult<()> {
+    let script = Script::new(
+        "local spending = redis.call('GET', KEYS[1])
+        if not spending then return 0 end
+        redis.call('DEL', KEYS[1])
+        redis.call('INCRBY', KEYS[2], spending)
+        return 1",
+    );
+    script
+        .key(format!("{SPENDING_KEY}:{chat_id}:{user_id}:{currency}"))
+        .key(format!("{SPENDING_KEY}:{chat_id}:{new_user_id}:{currency}"))
+        .invoke_async::<_, ()>(con)
+        .await
+}
+
+// Replaces all spendings of the given users and currencies in a chat atomically
+// Spendings that are not given are deleted
+pub async fn replace_spendings(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    users: &[String],
+    currencies: &[String],
+    spendings: &[(String, String, u64)],
+) -> RedisResult<()> {
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+    for currency in currencies {
+        for user_id in users {
+            pipe.del(format!("{SPENDING_KEY}:{chat_id}:{user_id}:{currency}"))
+                .ignore();
+        }
+    }
+    for (user_id, currency, spending) in spendings {
+        pipe.set(
+            format!("{SPENDING_KEY}:{chat_id}:{user_id}:{currency}"),
+            spending,
+        )
+        .ignore();
+    }
+    pipe.query_async(con).await
 }
 
 // Tests
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bot::redis::connect::connect;
+    use crate::bot::redis::connect::Redis;
 
-    #[test]
-    fn test_add_get_spending() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_spending() {
+        let mut con = Redis::new().connect().await.unwrap();
         let chat_id = "test_spending_chat";
         let user_id = "test_spending_user";
         let currency = "USD";
         let balance = 100;
 
-        assert!(set_spending(&mut con, chat_id, user_id, currency, balance).is_ok());
-        assert!(get_spending_exists(&mut con, chat_id, user_id, currency).unwrap());
+        assert!(set_spending(&mut con, chat_id, user_id, currency, balance)
+            .await
+            .is_ok());
+        assert!(get_spending_exists(&mut con, chat_id, user_id, currency)
+            .await
+            .unwrap());
         assert_eq!(
-            get_spending(&mut con, chat_id, user_id, currency).unwrap(),
+            get_spending(&mut con, chat_id, user_id, currency)
+                .await
+                .unwrap(),
             balance
         );
 
-        assert!(delete_spending(&mut con, chat_id, user_id, currency).is_ok());
+        assert!(delete_spending(&mut con, chat_id, user_id, currency)
+            .await
+            .is_ok());
     }
 
-    #[test]
-    fn test_update_spending() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_spending() {
+        let mut con = Redis::new().connect().await.unwrap();
         let chat_id = "test_spending_chat_2";
         let user_id = "test_spending_user_2";
         let currency = "USD";
         let balance = 100;
 
-        assert!(set_spending(&mut con, chat_id, user_id, currency, balance).is_ok());
+        assert!(set_spending(&mut con, chat_id, user_id, currency, balance)
+            .await
+            .is_ok());
 
         let updated_balance = 200;
-        assert!(set_spending(&mut con, chat_id, user_id, currency, updated_balance).is_ok());
+        assert!(
+            set_spending(&mut con, chat_id, user_id, currency, updated_balance)
+                .await
+                .is_ok()
+        );
         assert_eq!(
-            get_spending(&mut con, chat_id, user_id, currency).unwrap(),
+            get_spending(&mut con, chat_id, user_id, currency)
+                .await
+                .unwrap(),
             updated_balance
         );
 
-        assert!(delete_spending(&mut con, chat_id, user_id, currency).is_ok());
+        assert!(delete_spending(&mut con, chat_id, user_id, currency)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_spending() {
+        let mut con = Redis::new().connect().await.unwrap();
+        let chat_id = "test_spending_chat_3";
+        let user_id = "test_spending_user_3";
+        let currency = "USD";
+
+        assert_eq!(
+            add_spending(&mut con, chat_id, user_id, currency, 300)
+                .await
+                .unwrap(),
+            300
+        );
+        assert_eq!(
+            add_spending(&mut con, chat_id, user_id, currency, -100)
+                .await
+                .unwrap(),
+            200
+        );
+        assert_eq!(
+            get_spending(&mut con, chat_id, user_id, currency)
+                .await
+                .unwrap(),
+            200
+        );
+
+        assert!(delete_spending(&mut con, chat_id, user_id, currency)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_replace_spendings() {
+        let mut con = Redis::new().connect().await.unwrap();
+        let chat_id = "test_spending_chat_4";
+        let users = vec!["test_spending_user_4".to_string()];
+        let currencies = vec!["USD".to_string(), "EUR".to_string()];
+
+        set_spending(&mut con, chat_id, &users[0], "EUR", 500)
+            .await
+            .unwrap();
+        let spendings = vec![(users[0].clone(), "USD".to_string(), 800)];
+        assert!(
+            replace_spendings(&mut con, chat_id, &users, &currencies, &spendings)
+                .await
+                .is_ok()
+        );
+        assert!(!get_spending_exists(&mut con, chat_id, &users[0], "EUR")
+            .await
+            .unwrap());
+        assert_eq!(
+            get_spending(&mut con, chat_id, &users[0], "USD")
+                .await
+                .unwrap(),
+            800
+        );
+
+        assert!(delete_spending(&mut con, chat_id, &users[0], "USD")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_move_spending() {
+        let mut con = Redis::new().connect().await.unwrap();
+        let chat_id = "test_spending_chat_5";
+        let user_id = "test_spending_user_5";
+        let new_user_id = "test_spending_user_6";
+        let currency = "USD";
+
+        set_spending(&mut con, chat_id, user_id, currency, 300)
+            .await
+            .unwrap();
+        assert!(
+            move_spending(&mut con, chat_id, user_id, new_user_id, currency)
+                .await
+                .is_ok()
+        );
+        assert!(!get_spending_exists(&mut con, chat_id, user_id, currency)
+            .await
+            .unwrap());
+        assert_eq!(
+            get_spending(&mut con, chat_id, new_user_id, currency)
+                .await
+                .unwrap(),
+            300
+        );
+
+        assert!(delete_spending(&mut con, chat_id, new_user_id, currency)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_spendings() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "test_get_spendings_chat";
+        let users = vec![
+            "test_get_spendings_1".to_string(),
+            "test_get_spendings_2".to_string(),
+        ];
+        let currencies = vec!["USD".to_string(), "SGD".to_string()];
+        set_spending(&mut con, chat_id, &users[0], "USD", 300)
+            .await
+            .unwrap();
+        set_spending(&mut con, chat_id, &users[1], "SGD", 150)
+            .await
+            .unwrap();
+
+        // Spendings are by currency, then by user
+        assert_eq!(
+            get_spendings(&mut con, chat_id, &users, &currencies)
+                .await
+                .unwrap(),
+            vec![Some(300), None, None, Some(150)]
+        );
+        assert!(get_spendings(&mut con, chat_id, &users, &[])
+            .await
+            .unwrap()
+            .is_empty());
+
+        delete_spending(&mut con, chat_id, &users[0], "USD")
+            .await
+            .unwrap();
+        delete_spending(&mut con, chat_id, &users[1], "SGD")
+            .await
+            .unwrap();
     }
 }
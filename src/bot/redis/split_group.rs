@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Split Group CRUD Operations
+ * Split group represents a named group of users that a chat often splits payments with.
+ * Split groups are stored as a hash for each chat, from the name to the usernames,
+ * separated by spaces.
+ * Has set, get, and delete operations.
+ */
+
+const SPLIT_GROUP_KEY: &str = "chat_split_group";
+
+// Sets a split group of a chat, replacing any existing group of the same name
+pub async fn set_chat_split_group(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    name: &str,
+    usernames: &[String],
+) -> RedisResult<()> {
+    con.hset(
+        format!("{SPLIT_GROUP_KEY}:{chat_id}"),
+        name,
+        usernames.join(" "),
+    )
+    .await
+}
+
+// Gets all split groups of a chat
+pub async fn get_chat_split_groups(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<HashMap<String, Vec<String>>> {
+    let groups: HashMap<String, String> =
+        con.hgetall(format!("{SPLIT_GROUP_KEY}:{chat_id}")).await?;
+    Ok(groups
+        .into_iter()
+        .map(|(name, usernames)| {
+            let usernames = usernames.split_whitespace().map(String::from).collect();
+            (name, usernames)
+        })
+        .collect())
+}
+
+// Deletes a split group of a chat
+// Returns the number of split groups deleted
+pub async fn delete_chat_split_group(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    name: &str,
+) -> RedisResult<usize> {
+    con.hdel(format!("{SPLIT_GROUP_KEY}:{chat_id}"), name).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_chat_split_group() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "split_group_123456789";
+        let usernames = vec![
+            "split_group_user_1".to_string(),
+            "split_group_user_2".to_string(),
+        ];
+        assert!(get_chat_split_groups(&mut con, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(
+            set_chat_split_group(&mut con, chat_id, "flatmates", &usernames)
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            get_chat_split_groups(&mut con, chat_id).await.unwrap(),
+            HashMap::from([("flatmates".to_string(), usernames)])
+        );
+
+        assert_eq!(
+            delete_chat_split_group(&mut con, chat_id, "flatmates")
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            delete_chat_split_group(&mut con, chat_id, "flatmates")
+                .await
+                .unwrap(),
+            0
+        );
+        assert!(get_chat_split_groups(&mut con, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,436 @@
+use std::future::Future;
+
+#[cfg(test)]
+use std::{collections::HashMap, sync::Mutex};
+
+#[cfg(test)]
+use super::trip::get_trip_ledger;
+use super::{
+    connect::Redis,
+    manager::{
+        add_payment_entry, get_chat_balances_currency, get_chat_ledgers, get_dual_entry,
+        get_ledger_payments, get_payment_entry, is_adjustment_entry, is_transfer_payment_entry,
+        replace_ledger_balances, set_payment_transfer_entry, trash_payment_entry,
+        update_chat_balances, update_chat_spendings, update_payment_entry, CrudError,
+        LedgerPayment, UserBalance,
+    },
+    payment::Payment,
+};
+
+/* Ledger Store
+ * LedgerStore is the storage of ledgers, being a chat and each of its trips,
+ * with their payments, balances and spendings, and the settings deciding how payments are added.
+ * Processor logic written over a store can be run against Redis,
+ * or against an in-memory store in unit tests, without a live Redis instance.
+ */
+pub trait LedgerStore {
+    // Gets all ledgers of a chat, being the chat itself and each of its trips
+    fn get_ledgers(
+        &self,
+        chat_id: &str,
+    ) -> impl Future<Output = Result<Vec<String>, CrudError>> + Send;
+
+    // Gets all payments of a ledger, in their original order
+    fn get_payments(
+        &self,
+        chat_id: &str,
+        ledger: &str,
+    ) -> impl Future<Output = Result<Vec<LedgerPayment>, CrudError>> + Send;
+
+    // Replaces all balances and spendings of a ledger
+    fn replace_balances(
+        &self,
+        ledger: &str,
+        balances: Vec<UserBalance>,
+        spendings: Vec<UserBalance>,
+    ) -> impl Future<Output = Result<(), CrudError>> + Send;
+
+    // Adds a payment to a chat, marked as a transfer if given, and returns its ID
+    fn add_payment(
+        &self,
+        chat_id: &str,
+        payment: &Payment,
+        is_transfer: bool,
+    ) -> impl Future<Output = Result<String, CrudError>> + Send;
+
+    // Gets a payment of a chat by its ID, being one that is not archived
+    fn get_payment(
+        &self,
+        payment_id: &str,
+    ) -> impl Future<Output = Result<LedgerPayment, CrudError>> + Send;
+
+    // Replaces the details of a payment, marked as a transfer if given
+    fn update_payment(
+        &self,
+        payment_id: &str,
+        payment: &Payment,
+        is_transfer: bool,
+    ) -> impl Future<Output = Result<(), CrudError>> + Send;
+
+    // Moves a payment of a chat to the trash, out of the payments of the chat
+    fn delete_payment(
+        &self,
+        chat_id: &str,
+        payment_id: &str,
+        datetime: &str,
+    ) -> impl Future<Output = Result<(), CrudError>> + Send;
+
+    // Gets the balances of a ledger for a currency
+    fn get_balances(
+        &self,
+        ledger: &str,
+        currency: &str,
+    ) -> impl Future<Output = Result<Vec<UserBalance>, CrudError>> + Send;
+
+    // Adds changes to the balances of a ledger
+    fn update_balances(
+        &self,
+        ledger: &str,
+        changes: Vec<UserBalance>,
+    ) -> impl Future<Output = Result<(), CrudError>> + Send;
+
+    // Adds changes to the spendings of a ledger, which cannot become negative
+    fn update_spendings(
+        &self,
+        ledger: &str,
+        spendings: Vec<UserBalance>,
+    ) -> impl Future<Output = Result<(), CrudError>> + Send;
+
+    // Gets whether new payments of a chat are confirmed by both the payer and a debtor
+    fn get_dual_entry(&self, chat_id: &str)
+        -> impl Future<Output = Result<bool, CrudError>> + Send;
+}
+
+// RedisStore is the ledger store backed by Redis, used by the bot
+pub struct RedisStore {
+    redis: Redis,
+}
+
+impl RedisStore {
+    pub fn new(redis: &Redis) -> RedisStore {
+        RedisStore {
+            redis: redis.clone(),
+        }
+    }
+}
+
+impl LedgerStore for RedisStore {
+    async fn get_ledgers(&self, chat_id: &str) -> Result<Vec<String>, CrudError> {
+        get_chat_ledgers(&self.redis, chat_id).await
+    }
+
+    async fn get_payments(
+        &self,
+        chat_id: &str,
+        ledger: &str,
+    ) -> Result<Vec<LedgerPayment>, CrudError> {
+        get_ledger_payments(&self.redis, chat_id, ledger).await
+    }
+
+    async fn replace_balances(
+        &self,
+        ledger: &str,
+        balances: Vec<UserBalance>,
+        spendings: Vec<UserBalance>,
+    ) -> Result<(), CrudError> {
+        replace_ledger_balances(&self.redis, ledger, balances, spendings).await
+    }
+
+    async fn add_payment(
+        &self,
+        chat_id: &str,
+        payment: &Payment,
+        is_transfer: bool,
+    ) -> Result<String, CrudError> {
+        let payment_id = add_payment_entry(&self.redis, chat_id, payment).await?;
+        if is_transfer {
+            set_payment_transfer_entry(&self.redis, &payment_id, true).await?;
+        }
+        Ok(payment_id)
+    }
+
+    async fn get_payment(&self, payment_id: &str) -> Result<LedgerPayment, CrudError> {
+        Ok(LedgerPayment {
+            payment: get_payment_entry(&self.redis, payment_id).await?,
+            adjustment: is_adjustment_entry(&self.redis, payment_id).await?,
+            archived: false,
+            transfer: is_transfer_payment_entry(&self.redis, payment_id).await?,
+        })
+    }
+
+    async fn update_payment(
+        &self,
+        payment_id: &str,
+        payment: &Payment,
+        is_transfer: bool,
+    ) -> Result<(), CrudError> {
+        update_payment_entry(
+            &self.redis,
+            payment_id,
+            Some(&payment.description),
+            Some(&payment.datetime),
+            Some(&payment.creditor),
+            Some(&payment.currency),
+            Some(&payment.total),
+            Some(payment.debts.clone()),
+        )
+        .await?;
+        if is_transfer != is_transfer_payment_entry(&self.redis, payment_id).await? {
+            set_payment_transfer_entry(&self.redis, payment_id, is_transfer).await?;
+        }
+        Ok(())
+    }
+
+    async fn delete_payment(
+        &self,
+        chat_id: &str,
+        payment_id: &str,
+        datetime: &str,
+    ) -> Result<(), CrudError> {
+        trash_payment_entry(&self.redis, chat_id, payment_id, datetime).await
+    }
+
+    async fn get_balances(
+        &self,
+        ledger: &str,
+        currency: &str,
+    ) -> Result<Vec<UserBalance>, CrudError> {
+        get_chat_balances_currency(&self.redis, ledger, currency).await
+    }
+
+    async fn update_balances(
+        &self,
+        ledger: &str,
+        changes: Vec<UserBalance>,
+    ) -> Result<(), CrudError> {
+        update_chat_balances(&self.redis, ledger, changes).await
+    }
+
+    async fn update_spendings(
+        &self,
+        ledger: &str,
+        spendings: Vec<UserBalance>,
+    ) -> Result<(), CrudError> {
+        update_chat_spendings(&self.redis, ledger, spendings).await
+    }
+
+    async fn get_dual_entry(&self, chat_id: &str) -> Result<bool, CrudError> {
+        get_dual_entry(&self.redis, chat_id).await
+    }
+}
+
+// MemoryStore is a ledger store kept in memory, for unit tests
+// Ledgers are keyed by their ID, with trips being the ledgers other than the chat itself
+// Payments of a ledger are kept in their original order, each with its ID
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryStore {
+    pub payments: Mutex<HashMap<String, Vec<(String, LedgerPayment)>>>,
+    pub trash: Mutex<Vec<(String, String)>>,
+    pub balances: Mutex<HashMap<String, Vec<UserBalance>>>,
+    pub spendings: Mutex<HashMap<String, Vec<UserBalance>>>,
+    pub dual_entry: Mutex<HashMap<String, bool>>,
+}
+
+#[cfg(test)]
+impl MemoryStore {
+    // Combines the amounts of the same user and currency, as usernames are case-insensitive
+    fn combine_amounts(amounts: Vec<UserBalance>) -> Vec<UserBalance> {
+        let mut combined: Vec<UserBalance> = Vec::new();
+        for amount in amounts {
+            match combined.iter_mut().find(|existing| {
+                existing.currency == amount.currency
+                    && existing.username.to_lowercase() == amount.username.to_lowercase()
+            }) {
+                Some(existing) => existing.balance += amount.balance,
+                None => combined.push(amount),
+            }
+        }
+        combined
+    }
+
+    // Finds a payment by its ID, in any ledger
+    fn find_payment(&self, payment_id: &str) -> Option<LedgerPayment> {
+        self.payments
+            .lock()
+            .unwrap()
+            .values()
+            .flatten()
+            .find(|(id, _)| id == payment_id)
+            .map(|(_, entry)| entry.clone())
+    }
+}
+
+#[cfg(test)]
+impl LedgerStore for MemoryStore {
+    async fn get_ledgers(&self, chat_id: &str) -> Result<Vec<String>, CrudError> {
+        let mut ledgers: Vec<String> = self
+            .payments
+            .lock()
+            .unwrap()
+            .keys()
+            .filter(|ledger| ledger.starts_with(&get_trip_ledger(chat_id, "")))
+            .cloned()
+            .collect();
+        ledgers.sort();
+        ledgers.insert(0, chat_id.to_string());
+        Ok(ledgers)
+    }
+
+    async fn get_payments(
+        &self,
+        _chat_id: &str,
+        ledger: &str,
+    ) -> Result<Vec<LedgerPayment>, CrudError> {
+        let payments = self.payments.lock().unwrap();
+        Ok(payments
+            .get(ledger)
+            .map(|payments| payments.iter().map(|(_, entry)| entry.clone()).collect())
+            .unwrap_or_default())
+    }
+
+    async fn replace_balances(
+        &self,
+        ledger: &str,
+        balances: Vec<UserBalance>,
+        spendings: Vec<UserBalance>,
+    ) -> Result<(), CrudError> {
+        let spendings = MemoryStore::combine_amounts(spendings);
+        if spendings.iter().any(|spending| spending.balance < 0) {
+            return Err(CrudError::NegativeSpendingError());
+        }
+
+        self.balances
+            .lock()
+            .unwrap()
+            .insert(ledger.to_string(), MemoryStore::combine_amounts(balances));
+        self.spendings
+            .lock()
+            .unwrap()
+            .insert(ledger.to_string(), spendings);
+        Ok(())
+    }
+
+    async fn add_payment(
+        &self,
+        chat_id: &str,
+        payment: &Payment,
+        is_transfer: bool,
+    ) -> Result<String, CrudError> {
+        let mut payments = self.payments.lock().unwrap();
+        let payment_id = format!("{chat_id}_{}", payments.values().flatten().count() + 1);
+        payments.entry(chat_id.to_string()).or_default().push((
+            payment_id.clone(),
+            LedgerPayment {
+                payment: payment.clone(),
+                adjustment: false,
+                archived: false,
+                transfer: is_transfer,
+            },
+        ));
+        Ok(payment_id)
+    }
+
+    async fn get_payment(&self, payment_id: &str) -> Result<LedgerPayment, CrudError> {
+        self.find_payment(payment_id)
+            .ok_or(CrudError::NoSuchPaymentError())
+    }
+
+    async fn update_payment(
+        &self,
+        payment_id: &str,
+        payment: &Payment,
+        is_transfer: bool,
+    ) -> Result<(), CrudError> {
+        let mut payments = self.payments.lock().unwrap();
+        let mut entries = payments
+            .values_mut()
+            .flatten()
+            .filter(|(id, _)| id == payment_id)
+            .peekable();
+        if entries.peek().is_none() {
+            return Err(CrudError::NoSuchPaymentError());
+        }
+
+        // Payments in trips are the same payments as in their chat
+        for (_, entry) in entries {
+            entry.payment = payment.clone();
+            entry.transfer = is_transfer;
+        }
+        Ok(())
+    }
+
+    async fn delete_payment(
+        &self,
+        chat_id: &str,
+        payment_id: &str,
+        _datetime: &str,
+    ) -> Result<(), CrudError> {
+        let mut payments = self.payments.lock().unwrap();
+        let payments = payments
+            .get_mut(chat_id)
+            .ok_or(CrudError::NoSuchPaymentError())?;
+        let index = payments
+            .iter()
+            .position(|(id, _)| id == payment_id)
+            .ok_or(CrudError::NoSuchPaymentError())?;
+        payments.remove(index);
+
+        self.trash
+            .lock()
+            .unwrap()
+            .push((chat_id.to_string(), payment_id.to_string()));
+        Ok(())
+    }
+
+    async fn get_balances(
+        &self,
+        ledger: &str,
+        currency: &str,
+    ) -> Result<Vec<UserBalance>, CrudError> {
+        let balances = self.balances.lock().unwrap();
+        Ok(balances
+            .get(ledger)
+            .map(|balances| {
+                balances
+                    .iter()
+                    .filter(|balance| balance.currency == currency)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn update_balances(
+        &self,
+        ledger: &str,
+        changes: Vec<UserBalance>,
+    ) -> Result<(), CrudError> {
+        let mut balances = self.balances.lock().unwrap();
+        let current = balances.remove(ledger).unwrap_or_default();
+        let combined = MemoryStore::combine_amounts(current.into_iter().chain(changes).collect());
+        balances.insert(ledger.to_string(), combined);
+        Ok(())
+    }
+
+    async fn update_spendings(
+        &self,
+        ledger: &str,
+        spendings: Vec<UserBalance>,
+    ) -> Result<(), CrudError> {
+        let mut all_spendings = self.spendings.lock().unwrap();
+        let current = all_spendings.get(ledger).cloned().unwrap_or_default();
+        let combined = MemoryStore::combine_amounts(current.into_iter().chain(spendings).collect());
+        if combined.iter().any(|spending| spending.balance < 0) {
+            return Err(CrudError::NegativeSpendingError());
+        }
+
+        all_spendings.insert(ledger.to_string(), combined);
+        Ok(())
+    }
+
+    async fn get_dual_entry(&self, chat_id: &str) -> Result<bool, CrudError> {
+        let dual_entry = self.dual_entry.lock().unwrap();
+        Ok(dual_entry.get(chat_id).copied().unwrap_or(false))
+    }
+}
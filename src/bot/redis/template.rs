@@ -0,0 +1,175 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::{connect::RedisConnection, payment::Debt};
+
+/* Template CRUD Operations
+ * Template represents a saved payment of a chat, used to quickly add a frequent expense again.
+ * Template comprises a description, creditor, currency, numeric total,
+ * and a list of debts (stored under a different key), identified by its name in the chat.
+ * Each chat also has a set of the names of all its templates.
+ * Has set, exists, get, and delete operations.
+ */
+
+const TEMPLATE_KEY: &str = "template";
+const TEMPLATE_DEBT_KEY: &str = "template_debt";
+const CHAT_TEMPLATE_KEY: &str = "chat_template";
+
+// PaymentTemplate contains all fields stored in Redis related to a single payment template
+#[derive(Debug, PartialEq, Clone)]
+pub struct PaymentTemplate {
+    pub name: String,
+    pub description: String,
+    pub creditor: String,
+    pub currency: String,
+    pub total: i64,
+    pub debts: Vec<Debt>,
+}
+
+// Sets a template of a chat, replacing any existing template of the same name
+pub async fn set_template(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    template: &PaymentTemplate,
+) -> RedisResult<()> {
+    let name = &template.name;
+    let main_key = format!("{TEMPLATE_KEY}:{chat_id}:{name}");
+    con.hset::<_, _, _, ()>(&main_key, "description", &template.description)
+        .await?;
+    con.hset::<_, _, _, ()>(&main_key, "creditor", &template.creditor)
+        .await?;
+    con.hset::<_, _, _, ()>(&main_key, "currency", &template.currency)
+        .await?;
+    con.hset::<_, _, _, ()>(&main_key, "total", &template.total)
+        .await?;
+
+    let debt_key = format!("{TEMPLATE_DEBT_KEY}:{chat_id}:{name}");
+    con.del::<_, ()>(&debt_key).await?;
+    for debt in &template.debts {
+        con.rpush::<_, _, ()>(&debt_key, debt).await?;
+    }
+
+    con.sadd(format!("{CHAT_TEMPLATE_KEY}:{chat_id}"), name)
+        .await
+}
+
+// Checks if a template of a chat exists
+pub async fn get_template_exists(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    name: &str,
+) -> RedisResult<bool> {
+    con.exists(format!("{TEMPLATE_KEY}:{chat_id}:{name}")).await
+}
+
+// Gets a template of a chat
+pub async fn get_template(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    name: &str,
+) -> RedisResult<PaymentTemplate> {
+    let main_key = format!("{TEMPLATE_KEY}:{chat_id}:{name}");
+    let description: String = con.hget(&main_key, "description").await?;
+    let creditor: String = con.hget(&main_key, "creditor").await?;
+    let currency: String = con.hget(&main_key, "currency").await?;
+    let total: i64 = con.hget(&main_key, "total").await?;
+
+    let debt_key = format!("{TEMPLATE_DEBT_KEY}:{chat_id}:{name}");
+    let debts: Vec<Debt> = con.lrange(&debt_key, 0, -1).await?;
+
+    Ok(PaymentTemplate {
+        name: name.to_string(),
+        description,
+        creditor,
+        currency,
+        total,
+        debts,
+    })
+}
+
+// Gets the names of all templates of a chat
+pub async fn get_template_names(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Vec<String>> {
+    con.smembers(format!("{CHAT_TEMPLATE_KEY}:{chat_id}")).await
+}
+
+// Deletes a template of a chat
+pub async fn delete_template(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    name: &str,
+) -> RedisResult<()> {
+    con.del::<_, ()>(format!("{TEMPLATE_KEY}:{chat_id}:{name}"))
+        .await?;
+    con.del::<_, ()>(format!("{TEMPLATE_DEBT_KEY}:{chat_id}:{name}"))
+        .await?;
+    con.srem(format!("{CHAT_TEMPLATE_KEY}:{chat_id}"), name)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_template() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "template_123456789";
+        let template = PaymentTemplate {
+            name: "groceries".to_string(),
+            description: "Weekly groceries".to_string(),
+            creditor: "test_user_1".to_string(),
+            currency: "USD".to_string(),
+            total: 6000,
+            debts: vec![
+                ("test_user_1".to_string(), 3000),
+                ("test_user_2".to_string(), 3000),
+            ],
+        };
+
+        assert!(!get_template_exists(&mut con, chat_id, "groceries")
+            .await
+            .unwrap());
+        assert!(set_template(&mut con, chat_id, &template).await.is_ok());
+        assert!(get_template_exists(&mut con, chat_id, "groceries")
+            .await
+            .unwrap());
+        assert_eq!(
+            get_template(&mut con, chat_id, "groceries").await.unwrap(),
+            template
+        );
+
+        // Replaces the debts of an existing template
+        let updated_template = PaymentTemplate {
+            total: 4000,
+            debts: vec![("test_user_2".to_string(), 4000)],
+            ..template
+        };
+        assert!(set_template(&mut con, chat_id, &updated_template)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_template(&mut con, chat_id, "groceries").await.unwrap(),
+            updated_template
+        );
+        assert_eq!(
+            get_template_names(&mut con, chat_id).await.unwrap(),
+            vec!["groceries".to_string()]
+        );
+
+        assert!(delete_template(&mut con, chat_id, "groceries")
+            .await
+            .is_ok());
+        assert!(!get_template_exists(&mut con, chat_id, "groceries")
+            .await
+            .unwrap());
+        assert!(get_template_names(&mut con, chat_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Topic CRUD Operations
+ * Topic represents the setting overrides of a topic in a forum chat.
+ * Topic comprises a hash of settings for each topic, keyed by the chat and topic IDs,
+ * which take precedence over the settings of the chat.
+ * Has set, get, and delete operations.
+ */
+
+const TOPIC_KEY: &str = "topic_setting";
+
+pub const TOPIC_SETTING_TIME_ZONE: &str = "time_zone";
+pub const TOPIC_SETTING_DEFAULT_CURRENCY: &str = "default_currency";
+
+// Sets a setting override for a topic in a chat
+pub async fn set_topic_setting(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    topic_id: i32,
+    setting: &str,
+    value: &str,
+) -> RedisResult<()> {
+    con.hset(format!("{TOPIC_KEY}:{chat_id}:{topic_id}"), setting, value)
+        .await
+}
+
+// Gets a setting override for a topic in a chat, if any
+pub async fn get_topic_setting(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    topic_id: i32,
+    setting: &str,
+) -> RedisResult<Option<String>> {
+    con.hget(format!("{TOPIC_KEY}:{chat_id}:{topic_id}"), setting)
+        .await
+}
+
+// Deletes all setting overrides for a topic in a chat
+pub async fn delete_topic_settings(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    topic_id: i32,
+) -> RedisResult<()> {
+    con.del(format!("{TOPIC_KEY}:{chat_id}:{topic_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_topic_setting() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "topic_123456789";
+        let topic_id = 42;
+        assert!(set_topic_setting(
+            &mut con,
+            chat_id,
+            topic_id,
+            TOPIC_SETTING_TIME_ZONE,
+            "Asia/Tokyo"
+        )
+        .await
+        .is_ok());
+        assert_eq!(
+            get_topic_setting(&mut con, chat_id, topic_id, TOPIC_SETTING_TIME_ZONE)
+                .await
+                .unwrap(),
+            Some("Asia/Tokyo".to_string())
+        );
+        assert_eq!(
+            get_topic_setting(&mut con, chat_id, topic_id, TOPIC_SETTING_DEFAULT_CURRENCY)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            get_topic_setting(&mut con, chat_id, topic_id + 1, TOPIC_SETTING_TIME_ZONE)
+                .await
+                .unwrap(),
+            None
+        );
+
+        assert!(delete_topic_settings(&mut con, chat_id, topic_id)
+            .await
+            .is_ok());
+        assert_eq!(
+            get_topic_setting(&mut con, chat_id, topic_id, TOPIC_SETTING_TIME_ZONE)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Trash CRUD Operations
+ * Trash represents the payments deleted from a chat, kept for a while so that they can be restored.
+ * Trash comprises a hash of payment IDs to the datetime they were deleted, for each chat.
+ * The payments themselves are kept as they were, only removed from the list in chat.
+ * Has add, get, and delete operations.
+ */
+
+const TRASH_KEY: &str = "trash";
+
+// Adds a payment to the trash of a chat, with the datetime it was deleted
+pub async fn add_trash(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    payment_id: &str,
+    datetime: &str,
+) -> RedisResult<()> {
+    con.hset(format!("{TRASH_KEY}:{chat_id}"), payment_id, datetime)
+        .await
+}
+
+// Gets all payments in the trash of a chat, with the datetime each was deleted
+pub async fn get_trash(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<HashMap<String, String>> {
+    con.hgetall(format!("{TRASH_KEY}:{chat_id}")).await
+}
+
+// Gets the IDs of all chats with payments in their trash
+pub async fn get_trash_chats(con: &mut RedisConnection) -> RedisResult<Vec<String>> {
+    let prefix = format!("{TRASH_KEY}:");
+    let mut iter: redis::AsyncIter<String> = con.scan_match(format!("{prefix}*")).await?;
+    let mut chats: Vec<String> = Vec::new();
+    while let Some(key) = iter.next_item().await {
+        if let Some(chat_id) = key.strip_prefix(&prefix) {
+            chats.push(chat_id.to_string());
+        }
+    }
+
+    Ok(chats)
+}
+
+// Deletes a payment from the trash of a chat
+// Returns false if the payment was not in the trash, such that it is only restored once
+pub async fn delete_trash(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    payment_id: &str,
+) -> RedisResult<bool> {
+    let deleted: i64 = con
+        .hdel(format!("{TRASH_KEY}:{chat_id}"), payment_id)
+        .await?;
+    Ok(deleted > 0)
+}
+
+// Deletes the whole trash of a chat
+pub async fn delete_all_trash(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{TRASH_KEY}:{chat_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_delete_trash() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "trash_123456789";
+        add_trash(&mut con, chat_id, "payment_1", "2024-03-12 10:00:00 UTC")
+            .await
+            .unwrap();
+        add_trash(&mut con, chat_id, "payment_2", "2024-03-13 10:00:00 UTC")
+            .await
+            .unwrap();
+
+        let trash = get_trash(&mut con, chat_id).await.unwrap();
+        assert_eq!(trash.len(), 2);
+        assert_eq!(
+            trash.get("payment_1"),
+            Some(&"2024-03-12 10:00:00 UTC".to_string())
+        );
+        assert!(get_trash_chats(&mut con)
+            .await
+            .unwrap()
+            .contains(&chat_id.to_string()));
+
+        assert!(delete_trash(&mut con, chat_id, "payment_1").await.unwrap());
+        assert!(!delete_trash(&mut con, chat_id, "payment_1").await.unwrap());
+        assert_eq!(get_trash(&mut con, chat_id).await.unwrap().len(), 1);
+
+        assert!(delete_all_trash(&mut con, chat_id).await.is_ok());
+        assert!(get_trash(&mut con, chat_id).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
+
+/* Trip CRUD Operations
+ * Trip represents a named sub-ledger within a chat, such as "Japan Trip".
+ * Trip comprises a list of trip names for each chat, where the ID of a trip is its
+ * position in the list, and the ID of the active trip of each chat, if any.
+ * Payments, balances and spendings of a trip are kept under its own ledger ID,
+ * which takes the place of the chat ID in their keys.
+ * Has add, get, and delete operations.
+ */
+
+const TRIP_KEY: &str = "trip";
+const TRIP_ACTIVE_KEY: &str = "trip_active";
+
+// Trip is an abstraction containing a trip ID (String) and the trip name (String)
+pub type Trip = (String, String);
+
+// Gets the ledger ID of a trip, used in place of the chat ID for trip records
+pub fn get_trip_ledger(chat_id: &str, trip_id: &str) -> String {
+    format!("{chat_id}:{TRIP_KEY}:{trip_id}")
+}
+
+// Adds a new trip to a chat, and returns its ID
+pub async fn add_trip(con: &mut RedisConnection, chat_id: &str, name: &str) -> RedisResult<String> {
+    let count: usize = con.rpush(format!("{TRIP_KEY}:{chat_id}"), name).await?;
+    Ok(count.to_string())
+}
+
+// Gets all trips of a chat
+pub async fn get_trips(con: &mut RedisConnection, chat_id: &str) -> RedisResult<Vec<Trip>> {
+    let names: Vec<String> = con.lrange(format!("{TRIP_KEY}:{chat_id}"), 0, -1).await?;
+    Ok(names
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| ((index + 1).to_string(), name))
+        .collect())
+}
+
+// Deletes all trips of a chat
+pub async fn delete_trips(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{TRIP_KEY}:{chat_id}")).await
+}
+
+// Sets the active trip of a chat
+pub async fn set_active_trip(
+    con: &mut RedisConnection,
+    chat_id: &str,
+    trip_id: &str,
+) -> RedisResult<()> {
+    con.set(format!("{TRIP_ACTIVE_KEY}:{chat_id}"), trip_id)
+        .await
+}
+
+// Gets the ID of the active trip of a chat, if any
+pub async fn get_active_trip(
+    con: &mut RedisConnection,
+    chat_id: &str,
+) -> RedisResult<Option<String>> {
+    con.get(format!("{TRIP_ACTIVE_KEY}:{chat_id}")).await
+}
+
+// Deletes the active trip of a chat
+pub async fn delete_active_trip(con: &mut RedisConnection, chat_id: &str) -> RedisResult<()> {
+    con.del(format!("{TRIP_ACTIVE_KEY}:{chat_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_trips() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "trip_123456789";
+        assert_eq!(add_trip(&mut con, chat_id, "Japan").await.unwrap(), "1");
+        assert_eq!(add_trip(&mut con, chat_id, "Korea").await.unwrap(), "2");
+        assert_eq!(
+            get_trips(&mut con, chat_id).await.unwrap(),
+            vec![
+                ("1".to_string(), "Japan".to_string()),
+                ("2".to_string(), "Korea".to_string())
+            ]
+        );
+        assert_eq!(get_trip_ledger(chat_id, "2"), "trip_123456789:trip:2");
+
+        delete_trips(&mut con, chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_active_trip() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let chat_id = "trip_1234567890";
+        assert_eq!(get_active_trip(&mut con, chat_id).await.unwrap(), None);
+        assert!(set_active_trip(&mut con, chat_id, "1").await.is_ok());
+        assert_eq!(
+            get_active_trip(&mut con, chat_id).await.unwrap(),
+            Some("1".to_string())
+        );
+
+        assert!(delete_active_trip(&mut con, chat_id).await.is_ok());
+        assert_eq!(get_active_trip(&mut con, chat_id).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,13 @@
+/* Tutorial Keyspace
+ * Tutorial represents the practice ledger of a chat, used by the tutorial to try out the bot.
+ * Payments, balances and users of the practice ledger are kept under its own ledger ID,
+ * which takes the place of the chat ID in their keys, like the ledger of a trip.
+ * As the ledger ID is never a chat ID, real balances are never touched by the tutorial.
+ */
+
+const TUTORIAL_KEY: &str = "tutorial";
+
+// Gets the ledger ID of the practice ledger of a chat, used in place of the chat ID
+pub fn get_tutorial_ledger(chat_id: &str) -> String {
+    format!("{chat_id}:{TUTORIAL_KEY}")
+}
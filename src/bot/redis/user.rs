@@ -1,96 +1,102 @@
-use redis::{Commands, Connection, RedisResult};
+use redis::{AsyncCommands, RedisResult};
+
+use super::connect::RedisConnection;
 
 const USER_KEY: &str = "user";
 const USER_ID_KEY: &str = "user_id";
 const USERNAME_KEY: &str = "username";
+const USER_ALIAS_KEY: &str = "user_alias";
+const USER_PAYMENT_LINK_KEY: &str = "user_payment_link";
+const USER_PRIVATE_CHAT_KEY: &str = "user_private_chat";
 
-/* user.rs contains CRUD operations for both `user` and `user_id`.
+/* user.rs contains CRUD operations for `user`, `user_id`, `username`, and `user_alias`.
  * `user` is the main table used for normal operations.
- * `user_id` is used only to ensure the correctness of `user.username`
+ * `user_id` tracks the latest username of each Telegram user ID.
+ * `user_alias` maps each username to the user key it belongs to.
+ * `user_payment_link` keeps the payment link that each user gets paid back with.
+ * `user_private_chat` keeps the users who have started the bot in a direct message.
+ *
+ * A user key is the Telegram user ID once it is known, and otherwise the lowercase
+ * username, for users who have only been mentioned and have not used the bot themselves.
  */
 
 /* User CRUD Operations
- * NOTE: Currently, not really being used.
- *
  * User represents a user, most likely in a group chat on Telegram.
  * User comprises a list of chats they are using PayScribe in.
  * Has add, exists, get, update, and delete operations.
  */
 
 // Adds a new user to Redis
-// Initialises user_id if provided
-pub fn add_user(
-    con: &mut Connection,
-    username: &str,
-    chat_id: &str,
-    _user_id: Option<&str>,
-) -> RedisResult<()> {
-    con.rpush(format!("{USER_KEY}:{username}"), chat_id)
+pub async fn add_user(con: &mut RedisConnection, username: &str, chat_id: &str) -> RedisResult<()> {
+    con.rpush(format!("{USER_KEY}:{username}"), chat_id).await
 }
 
 // Checks if user exists
-pub fn get_user_exists(con: &mut Connection, username: &str) -> RedisResult<bool> {
-    con.exists(format!("{USER_KEY}:{username}"))
+pub async fn get_user_exists(con: &mut RedisConnection, username: &str) -> RedisResult<bool> {
+    con.exists(format!("{USER_KEY}:{username}")).await
 }
 
 // Gets user chats from a specified user
-pub fn get_user_chats(con: &mut Connection, username: &str) -> RedisResult<Vec<String>> {
-    con.lrange(format!("{USER_KEY}:{username}"), 0, -1)
+pub async fn get_user_chats(con: &mut RedisConnection, username: &str) -> RedisResult<Vec<String>> {
+    con.lrange(format!("{USER_KEY}:{username}"), 0, -1).await
 }
 
 // Update user chats with a new chat
 // Automatically checks if chat is already inside
-pub fn update_user_chats(con: &mut Connection, username: &str, chat_id: &str) -> RedisResult<()> {
-    con.rpush(format!("{USER_KEY}:{username}"), chat_id)
+pub async fn update_user_chats(
+    con: &mut RedisConnection,
+    username: &str,
+    chat_id: &str,
+) -> RedisResult<()> {
+    con.rpush(format!("{USER_KEY}:{username}"), chat_id).await
 }
 
 // Deletes a user from Redis
-// Mainly for testing purposes
-// In application, no real need to delete keys
-#[allow(dead_code)]
-pub fn delete_user(con: &mut Connection, username: &str) -> RedisResult<()> {
-    con.del(format!("{USER_KEY}:{username}"))
+pub async fn delete_user(con: &mut RedisConnection, username: &str) -> RedisResult<()> {
+    con.del(format!("{USER_KEY}:{username}")).await
 }
 
-/* NOTE: Currently, User ID operations are not supported. The bot will not track User ID.
- *
- * User ID CRUD Operations
+/* User ID CRUD Operations
  * User ID represents a mapping of user_id to username.
  * Has add, exists, get, update, and delete operations.
  */
 
 // Initialises user with user_id
-#[allow(dead_code)]
-pub fn initialize_user(con: &mut Connection, user_id: &str, username: &str) -> RedisResult<()> {
-    con.set(format!("{USER_ID_KEY}:{user_id}"), username)
+pub async fn initialize_user(
+    con: &mut RedisConnection,
+    user_id: &str,
+    username: &str,
+) -> RedisResult<()> {
+    con.set(format!("{USER_ID_KEY}:{user_id}"), username).await
 }
 
 // Checks if user is initialised
-#[allow(dead_code)]
-pub fn get_user_is_init(con: &mut Connection, user_id: &str) -> RedisResult<bool> {
-    con.exists(format!("{USER_ID_KEY}:{user_id}"))
+pub async fn get_user_is_init(con: &mut RedisConnection, user_id: &str) -> RedisResult<bool> {
+    con.exists(format!("{USER_ID_KEY}:{user_id}")).await
 }
 
 // Gets username from a specified user_id
-#[allow(dead_code)]
-pub fn get_username(con: &mut Connection, user_id: &str) -> RedisResult<String> {
-    con.get(format!("{USER_ID_KEY}:{user_id}"))
+pub async fn get_username(con: &mut RedisConnection, user_id: &str) -> RedisResult<String> {
+    con.get(format!("{USER_ID_KEY}:{user_id}")).await
 }
 
 // Updates username for a specified user_id
 // Only used when user_id is provided, activated when a change in username is detected
 // Otherwise, impossible to detect change in username without user_id
-#[allow(dead_code)]
-pub fn update_username(con: &mut Connection, user_id: &str, username: &str) -> RedisResult<()> {
-    con.set(format!("{USER_ID_KEY}:{user_id}"), username)
+pub async fn update_username(
+    con: &mut RedisConnection,
+    user_id: &str,
+    username: &str,
+) -> RedisResult<()> {
+    con.set(format!("{USER_ID_KEY}:{user_id}"), username).await
 }
 
 // Deletes a user_id from Redis
 // Mainly for testing purposes
 // In application, no real need to delete keys
 #[allow(dead_code)]
-pub fn delete_user_id(con: &mut Connection, user_id: &str) -> RedisResult<()> {
-    con.del(format!("{USER_ID_KEY}:{user_id}"))
+pub async fn delete_user_id(con: &mut RedisConnection, user_id: &str) -> RedisResult<()> {
+    con.del(format!("{USER_ID_KEY}:{user_id}")).await
 }
 
 /* Username CRUD Operations
@@ -100,180 +106,354 @@ pub fn delete_user_id(con: &mut Connection, user_id: &str) -> RedisResult<()> {
  */
 
 // Sets the preferred username of a user
-pub fn set_preferred_username(
-    con: &mut Connection,
+pub async fn set_preferred_username(
+    con: &mut RedisConnection,
     username: &str,
     user_key: &str,
 ) -> RedisResult<()> {
     con.set(format!("{USERNAME_KEY}:{user_key}"), username)
+        .await
 }
 
 // Gets the preferred username of a user
-pub fn get_preferred_username(con: &mut Connection, user_key: &str) -> RedisResult<String> {
-    con.get(format!("{USERNAME_KEY}:{user_key}"))
+pub async fn get_preferred_username(
+    con: &mut RedisConnection,
+    user_key: &str,
+) -> RedisResult<String> {
+    con.get(format!("{USERNAME_KEY}:{user_key}")).await
+}
+
+// Gets the preferred usernames of the given users in a single call
+// Returns None for users without a preferred username
+pub async fn get_preferred_usernames(
+    con: &mut RedisConnection,
+    user_keys: &[String],
+) -> RedisResult<Vec<Option<String>>> {
+    if user_keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let keys: Vec<String> = user_keys
+        .iter()
+        .map(|user_key| format!("{USERNAME_KEY}:{user_key}"))
+        .collect();
+    redis::cmd("MGET").arg(keys).query_async(con).await
 }
 
 // Deletes the preferred username of a user
+pub async fn delete_preferred_username(
+    con: &mut RedisConnection,
+    user_key: &str,
+) -> RedisResult<()> {
+    con.del(format!("{USERNAME_KEY}:{user_key}")).await
+}
+
+/* User Alias CRUD Operations
+ * User Alias represents a mapping of a lowercase username to the user key it belongs to.
+ * Previous usernames of a user are kept, so that older payments still point to them.
+ * Has set, get, and delete operations.
+ */
+
+// Sets the user key of a username
+pub async fn set_user_alias(
+    con: &mut RedisConnection,
+    username: &str,
+    user_key: &str,
+) -> RedisResult<()> {
+    con.set(format!("{USER_ALIAS_KEY}:{username}"), user_key)
+        .await
+}
+
+// Gets the user key of a username, if any
+pub async fn get_user_alias(
+    con: &mut RedisConnection,
+    username: &str,
+) -> RedisResult<Option<String>> {
+    con.get(format!("{USER_ALIAS_KEY}:{username}")).await
+}
+
+// Deletes the user key of a username
 // Mainly for testing purposes
 // In application, no real need to delete keys
 #[allow(dead_code)]
-pub fn delete_preferred_username(con: &mut Connection, user_key: &str) -> RedisResult<()> {
-    con.del(format!("{USERNAME_KEY}:{user_key}"))
+pub async fn delete_user_alias(con: &mut RedisConnection, username: &str) -> RedisResult<()> {
+    con.del(format!("{USER_ALIAS_KEY}:{username}")).await
+}
+
+/* User Payment Link CRUD Operations
+ * User Payment Link represents where a user can be paid back, as a URL or a phone number.
+ * Has set, get, and delete operations.
+ */
+
+// Sets the payment link of a user
+pub async fn set_user_payment_link(
+    con: &mut RedisConnection,
+    user_key: &str,
+    link: &str,
+) -> RedisResult<()> {
+    con.set(format!("{USER_PAYMENT_LINK_KEY}:{user_key}"), link)
+        .await
+}
+
+// Gets the payment links of the given users in a single call
+// Returns None for users without a payment link
+pub async fn get_user_payment_links(
+    con: &mut RedisConnection,
+    user_keys: &[String],
+) -> RedisResult<Vec<Option<String>>> {
+    if user_keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let keys: Vec<String> = user_keys
+        .iter()
+        .map(|user_key| format!("{USER_PAYMENT_LINK_KEY}:{user_key}"))
+        .collect();
+    redis::cmd("MGET").arg(keys).query_async(con).await
+}
+
+// Deletes the payment link of a user
+pub async fn delete_user_payment_link(
+    con: &mut RedisConnection,
+    user_key: &str,
+) -> RedisResult<()> {
+    con.del(format!("{USER_PAYMENT_LINK_KEY}:{user_key}")).await
+}
+
+/* User Private Chat CRUD Operations
+ * User Private Chat represents the users who can be messaged directly by the bot,
+ * having started it in a direct message and not turned off notifications.
+ * Comprises a set of user IDs, which are also the IDs of their direct messages.
+ * Has add, exists, and delete operations.
+ */
+
+// Adds a user who can be messaged directly
+pub async fn add_user_private_chat(con: &mut RedisConnection, user_id: &str) -> RedisResult<()> {
+    con.sadd(USER_PRIVATE_CHAT_KEY, user_id).await
+}
+
+// Checks if a user can be messaged directly
+pub async fn is_user_private_chat(con: &mut RedisConnection, user_id: &str) -> RedisResult<bool> {
+    con.sismember(USER_PRIVATE_CHAT_KEY, user_id).await
+}
+
+// Deletes a user who can no longer be messaged directly
+pub async fn delete_user_private_chat(con: &mut RedisConnection, user_id: &str) -> RedisResult<()> {
+    con.srem(USER_PRIVATE_CHAT_KEY, user_id).await
 }
 
 // Tests
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bot::redis::connect::connect;
+    use crate::bot::redis::connect::Redis;
 
-    #[test]
-    fn test_add_user_all() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_user_all() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let username = "test_user_all";
-        let user_id = "123456789";
         let chat_id = "9876543210";
-        assert!(add_user(&mut con, username, chat_id, Some(user_id),).is_ok());
+        assert!(add_user(&mut con, username, chat_id).await.is_ok());
 
-        delete_user(&mut con, username).unwrap();
+        delete_user(&mut con, username).await.unwrap();
     }
 
-    #[test]
-    fn test_add_user_no_id() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_user_no_id() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let username = "test_user_no_id";
         let chat_id = "9876543211";
-        assert!(add_user(&mut con, username, chat_id, None).is_ok());
+        assert!(add_user(&mut con, username, chat_id).await.is_ok());
 
-        delete_user(&mut con, username).unwrap();
+        delete_user(&mut con, username).await.unwrap();
     }
 
-    #[test]
-    fn test_get_user_exists_chat() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_user_exists_chat() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let username = "test_user_exists";
         let chat_id = "9876543212";
-        add_user(&mut con, username, chat_id, None).unwrap();
-        assert!(get_user_exists(&mut con, username).unwrap());
-        assert!(get_user_chats(&mut con, username).unwrap() == vec![chat_id]);
+        add_user(&mut con, username, chat_id).await.unwrap();
+        assert!(get_user_exists(&mut con, username).await.unwrap());
+        assert!(get_user_chats(&mut con, username).await.unwrap() == vec![chat_id]);
 
-        delete_user(&mut con, username).unwrap();
+        delete_user(&mut con, username).await.unwrap();
     }
 
-    #[test]
-    fn test_update_chats() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_chats() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let username = "test_user_update_chats";
         let chat_id = "9876543214";
         let new_chat_id = "9876543215";
-        add_user(&mut con, username, chat_id, None).unwrap();
-        assert_eq!(get_user_chats(&mut con, username).unwrap(), vec![chat_id]);
+        add_user(&mut con, username, chat_id).await.unwrap();
+        assert_eq!(
+            get_user_chats(&mut con, username).await.unwrap(),
+            vec![chat_id]
+        );
 
-        update_user_chats(&mut con, username, new_chat_id).unwrap();
+        update_user_chats(&mut con, username, new_chat_id)
+            .await
+            .unwrap();
         assert_eq!(
-            get_user_chats(&mut con, username).unwrap(),
+            get_user_chats(&mut con, username).await.unwrap(),
             vec![chat_id, new_chat_id]
         );
 
-        delete_user(&mut con, username).unwrap();
+        delete_user(&mut con, username).await.unwrap();
     }
 
-    #[test]
-    fn test_delete_user() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_delete_user() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let username = "test_user_delete";
         let chat_id = "9876543216";
-        add_user(&mut con, username, chat_id, None).unwrap();
-        assert!(get_user_exists(&mut con, username).unwrap());
-        delete_user(&mut con, username).unwrap();
-        assert!(!get_user_exists(&mut con, username).unwrap());
+        add_user(&mut con, username, chat_id).await.unwrap();
+        assert!(get_user_exists(&mut con, username).await.unwrap());
+        delete_user(&mut con, username).await.unwrap();
+        assert!(!get_user_exists(&mut con, username).await.unwrap());
     }
 
-    /*
-    #[test]
-    fn test_initialize_get_user() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_initialize_get_user() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let username = "test_user_initialize";
         let user_id = "1234567895";
-        assert!(initialize_user(&mut con, user_id, username).is_ok());
-        assert!(get_username(&mut con, user_id).unwrap() == username);
-        assert!(get_user_is_init(&mut con, user_id).unwrap());
-
-        delete_user_id(&mut con, user_id).unwrap();
-    }
-
-    #[test]
-    fn test_get_user_is_not_init() {
-        let mut con = connect().unwrap();
-
-        let username = "test_user_get_is_not_init";
-        let chat_id = "9876543217";
-        add_user(&mut con, username, chat_id, None).unwrap();
-        assert!(!get_user_is_init(&mut con, username).unwrap());
-
-        delete_user(&mut con, username).unwrap();
-    }
+        assert!(initialize_user(&mut con, user_id, username).await.is_ok());
+        assert!(get_username(&mut con, user_id).await.unwrap() == username);
+        assert!(get_user_is_init(&mut con, user_id).await.unwrap());
 
-    #[test]
-    fn test_get_user_auto_init() {
-        let mut con = connect().unwrap();
-
-        let username = "test_user_auto_init";
-        let user_id = "1234567894";
-        let chat_id = "9876543218";
-        add_user(&mut con, username, chat_id, Some(user_id)).unwrap();
-        assert!(get_user_is_init(&mut con, user_id).unwrap());
-
-        delete_user_id(&mut con, user_id).unwrap();
-        delete_user(&mut con, username).unwrap();
+        delete_user_id(&mut con, user_id).await.unwrap();
     }
 
-    #[test]
-    fn test_update_username() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_update_username() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let user_id = "1234567896";
         let old_username = "test_user_update_username";
         let new_username = "test_user_update_username_new";
-        initialize_user(&mut con, user_id, old_username).unwrap();
-        assert_eq!(get_username(&mut con, user_id).unwrap(), old_username);
+        initialize_user(&mut con, user_id, old_username)
+            .await
+            .unwrap();
+        assert_eq!(get_username(&mut con, user_id).await.unwrap(), old_username);
 
-        update_username(&mut con, user_id, new_username).unwrap();
-        assert_eq!(get_username(&mut con, user_id).unwrap(), new_username);
+        update_username(&mut con, user_id, new_username)
+            .await
+            .unwrap();
+        assert_eq!(get_username(&mut con, user_id).await.unwrap(), new_username);
 
-        delete_user_id(&mut con, user_id).unwrap();
+        delete_user_id(&mut con, user_id).await.unwrap();
     }
 
-    #[test]
-    fn test_delete_user_id() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_delete_user_id() {
+        let mut con = Redis::new().connect().await.unwrap();
 
         let user_id = "1234567897";
         let username = "test_user_delete_user_id";
-        initialize_user(&mut con, user_id, username).unwrap();
-        assert!(get_user_is_init(&mut con, user_id).unwrap());
-        delete_user_id(&mut con, user_id).unwrap();
-        assert!(!get_user_is_init(&mut con, user_id).unwrap());
+        initialize_user(&mut con, user_id, username).await.unwrap();
+        assert!(get_user_is_init(&mut con, user_id).await.unwrap());
+        delete_user_id(&mut con, user_id).await.unwrap();
+        assert!(!get_user_is_init(&mut con, user_id).await.unwrap());
     }
-    */
 
-    #[test]
-    fn test_set_get_delete_preferred_username() {
-        let mut con = connect().unwrap();
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_preferred_username() {
+        let mut con = Redis::new().connect().await.unwrap();
         let username = "Test_User_Preferred_Username";
         let user_key = username.to_lowercase();
-        assert!(set_preferred_username(&mut con, username, &user_key).is_ok());
+        assert!(set_preferred_username(&mut con, username, &user_key)
+            .await
+            .is_ok());
         assert_eq!(
-            get_preferred_username(&mut con, &user_key).unwrap(),
+            get_preferred_username(&mut con, &user_key).await.unwrap(),
             username
         );
 
-        delete_preferred_username(&mut con, &user_key).unwrap();
+        // Users without a preferred username are None
+        let user_keys = vec![user_key.clone(), "test_user_no_preferred".to_string()];
+        assert_eq!(
+            get_preferred_usernames(&mut con, &user_keys).await.unwrap(),
+            vec![Some(username.to_string()), None]
+        );
+        assert!(get_preferred_usernames(&mut con, &[])
+            .await
+            .unwrap()
+            .is_empty());
+
+        delete_preferred_username(&mut con, &user_key)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_user_alias() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let username = "test_user_alias";
+        let user_key = "1234567898";
+        assert_eq!(get_user_alias(&mut con, username).await.unwrap(), None);
+        assert!(set_user_alias(&mut con, username, user_key).await.is_ok());
+        assert_eq!(
+            get_user_alias(&mut con, username).await.unwrap(),
+            Some(user_key.to_string())
+        );
+
+        assert!(delete_user_alias(&mut con, username).await.is_ok());
+        assert_eq!(get_user_alias(&mut con, username).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_set_get_delete_user_payment_link() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let user_key = "1234567810";
+        let link = "https://paypal.me/testuser";
+        assert!(set_user_payment_link(&mut con, user_key, link)
+            .await
+            .is_ok());
+
+        // Users without a payment link are None
+        let user_keys = vec![user_key.to_string(), "test_user_no_link".to_string()];
+        assert_eq!(
+            get_user_payment_links(&mut con, &user_keys).await.unwrap(),
+            vec![Some(link.to_string()), None]
+        );
+
+        assert!(delete_user_payment_link(&mut con, user_key).await.is_ok());
+        assert_eq!(
+            get_user_payment_links(&mut con, &user_keys).await.unwrap(),
+            vec![None, None]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_exists_delete_user_private_chat() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let user_id = "1234567811";
+        assert!(!is_user_private_chat(&mut con, user_id).await.unwrap());
+        assert!(add_user_private_chat(&mut con, user_id).await.is_ok());
+        assert!(is_user_private_chat(&mut con, user_id).await.unwrap());
+
+        assert!(delete_user_private_chat(&mut con, user_id).await.is_ok());
+        assert!(!is_user_private_chat(&mut con, user_id).await.unwrap());
     }
 }
@@ -0,0 +1,110 @@
+use redis::{AsyncCommands, ErrorKind, RedisError, RedisResult};
+use serde::{Deserialize, Serialize};
+
+use super::{connect::RedisConnection, payment::Payment};
+
+/* Version CRUD Operations
+ * Version represents a previous version of a payment, kept each time the payment is edited.
+ * Each version comprises the payment as it was before the edit, with who edited it and when,
+ * stored as JSON in order in a list for each payment, of which only the latest versions are kept.
+ * Has add, get, and delete operations.
+ */
+
+const VERSION_KEY: &str = "payment_version";
+const VERSION_MAX_ENTRIES: isize = 10;
+
+// PaymentVersion is a payment as it was, before it was edited by a user at some datetime
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct PaymentVersion {
+    pub payment: Payment,
+    pub edited_by: String,
+    pub edited_at: String,
+}
+
+// Converts an error from reading or writing JSON into a Redis error
+fn to_redis_error(err: serde_json::Error) -> RedisError {
+    RedisError::from((
+        ErrorKind::TypeError,
+        "Invalid payment version",
+        err.to_string(),
+    ))
+}
+
+// Adds a version of a payment, keeping only the latest versions
+pub async fn add_version(
+    con: &mut RedisConnection,
+    payment_id: &str,
+    version: &PaymentVersion,
+) -> RedisResult<()> {
+    let key = format!("{VERSION_KEY}:{payment_id}");
+    let value = serde_json::to_string(version).map_err(to_redis_error)?;
+    con.rpush::<_, _, ()>(&key, value).await?;
+    con.ltrim(&key, -VERSION_MAX_ENTRIES, -1).await
+}
+
+// Gets the versions of a payment, from the earliest to the latest
+pub async fn get_versions(
+    con: &mut RedisConnection,
+    payment_id: &str,
+) -> RedisResult<Vec<PaymentVersion>> {
+    let values: Vec<String> = con
+        .lrange(format!("{VERSION_KEY}:{payment_id}"), 0, -1)
+        .await?;
+    values
+        .iter()
+        .map(|value| serde_json::from_str(value).map_err(to_redis_error))
+        .collect()
+}
+
+// Deletes all versions of a payment
+pub async fn delete_versions(con: &mut RedisConnection, payment_id: &str) -> RedisResult<()> {
+    con.del(format!("{VERSION_KEY}:{payment_id}")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::redis::connect::Redis;
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_add_get_delete_versions() {
+        let mut con = Redis::new().connect().await.unwrap();
+
+        let payment_id = "version_payment_123456789";
+        let version = PaymentVersion {
+            payment: Payment {
+                description: "test_payment".to_string(),
+                datetime: "2020-01-01 00:00:00 UTC".to_string(),
+                creditor: "test_creditor".to_string(),
+                currency: "USD".to_string(),
+                total: 10000,
+                debts: vec![("test_debtor".to_string(), 10000)],
+            },
+            edited_by: "test_editor".to_string(),
+            edited_at: "2020-01-02 00:00:00 UTC".to_string(),
+        };
+
+        assert!(delete_versions(&mut con, payment_id).await.is_ok());
+        assert!(get_versions(&mut con, payment_id).await.unwrap().is_empty());
+        for _ in 0..VERSION_MAX_ENTRIES {
+            assert!(add_version(&mut con, payment_id, &version).await.is_ok());
+        }
+
+        // Only the latest versions are kept
+        let latest_version = PaymentVersion {
+            edited_by: "test_editor_2".to_string(),
+            ..version.clone()
+        };
+        assert!(add_version(&mut con, payment_id, &latest_version)
+            .await
+            .is_ok());
+        let versions = get_versions(&mut con, payment_id).await.unwrap();
+        assert_eq!(versions.len(), VERSION_MAX_ENTRIES as usize);
+        assert_eq!(versions.first(), Some(&version));
+        assert_eq!(versions.last(), Some(&latest_version));
+
+        assert!(delete_versions(&mut con, payment_id).await.is_ok());
+        assert!(get_versions(&mut con, payment_id).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use teloxide::Bot;
+use tokio::sync::watch;
+
+use super::{
+    currency::refresh_cached_rates,
+    handler::{
+        handle_data_retention, handle_debt_reminder, handle_monthly_archive, handle_weekly_digest,
+    },
+    processor::purge_expired_trash,
+    redis::Redis,
+};
+
+/* Scheduler runs the background tasks of the bot.
+ * It wakes up at a fixed interval, and each task checks for itself
+ * whether anything is due, based on the settings of each chat.
+ * On shutdown, the current run is finished before stopping.
+ */
+
+const SCHEDULER_INTERVAL_SECS: u64 = 60;
+
+pub async fn run_scheduler(redis: Redis, bot: Bot, mut shutdown: watch::Receiver<bool>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(SCHEDULER_INTERVAL_SECS));
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown.changed() => break,
+        }
+
+        if let Err(err) = handle_monthly_archive(&redis, &bot).await {
+            log::error!("Scheduler - Monthly archive failed: {}", err.to_string());
+        }
+        if let Err(err) = handle_weekly_digest(&redis, &bot).await {
+            log::error!("Scheduler - Weekly digest failed: {}", err.to_string());
+        }
+        if let Err(err) = handle_debt_reminder(&redis, &bot).await {
+            log::error!("Scheduler - Debt reminder failed: {}", err.to_string());
+        }
+        if let Err(err) = handle_data_retention(&redis, &bot).await {
+            log::error!("Scheduler - Data retention failed: {}", err.to_string());
+        }
+        if let Err(err) = purge_expired_trash(&redis).await {
+            log::error!("Scheduler - Trash purge failed: {}", err.to_string());
+        }
+        if let Err(err) = refresh_cached_rates(&redis).await {
+            log::error!("Scheduler - Rate cache refresh failed: {}", err.to_string());
+        }
+    }
+
+    log::info!("Scheduler - Stopped for shutdown");
+}
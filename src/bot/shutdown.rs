@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use teloxide::dispatching::ShutdownToken;
+use tokio::sync::watch;
+
+/* Shutdown coordinates a graceful shutdown of the bot.
+ * On ctrl-c or SIGTERM, the dispatcher stops accepting new updates and finishes the updates
+ * already being handled, while the scheduler finishes its current run before stopping.
+ * A second signal stops the bot immediately, without waiting.
+ */
+
+// Interval to retry shutting down the dispatcher, if it has not started dispatching yet
+const SHUTDOWN_RETRY_MILLIS: u64 = 500;
+
+pub struct Shutdown {
+    sender: watch::Sender<bool>,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (sender, _) = watch::channel(false);
+        Shutdown { sender }
+    }
+
+    // Subscribes to the shutdown, for background tasks to stop once it begins
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.sender.subscribe()
+    }
+
+    // Waits for a shutdown signal, then shuts down the dispatcher and all subscribers
+    pub async fn listen(self, token: ShutdownToken) {
+        wait_for_signal().await;
+        log::info!("Shutdown - Signal received, finishing ongoing updates before shutting down...");
+        self.sender.send_replace(true);
+
+        // The dispatcher may still be starting up, in which case it can't be shut down yet
+        loop {
+            match token.shutdown() {
+                Ok(_) => break,
+                Err(_) => tokio::time::sleep(Duration::from_millis(SHUTDOWN_RETRY_MILLIS)).await,
+            }
+        }
+
+        wait_for_signal().await;
+        log::warn!("Shutdown - Second signal received, shutting down immediately!");
+        std::process::exit(1);
+    }
+}
+
+// Waits for either ctrl-c, or SIGTERM on Unix
+async fn wait_for_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+            }
+            Err(err) => {
+                log::error!("Shutdown - Failed to listen for SIGTERM: {}", err);
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
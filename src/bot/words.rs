@@ -0,0 +1,275 @@
+/* Words spells out amounts of money in words, as on cheques and IOUs.
+ * Amounts are spelt out in a supported language, falling back to English.
+ * English gives the whole amount in words, and the fraction as a number over its base,
+ * like "One hundred twenty-three and 45/100".
+ * Chinese uses the formal numerals of financial documents, like "壹佰贰拾叁元肆角伍分".
+ */
+
+const ONES_EN: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS_EN: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const SCALES_EN: [&str; 7] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+];
+
+const DIGITS_ZH: [&str; 10] = ["零", "壹", "贰", "叁", "肆", "伍", "陆", "柒", "捌", "玖"];
+const UNITS_ZH: [&str; 4] = ["", "拾", "佰", "仟"];
+const SCALES_ZH: [&str; 5] = ["", "万", "亿", "万亿", "亿亿"];
+
+// Spells out a number below one thousand in English
+fn hundreds_to_words_en(number: u64) -> String {
+    let mut words: Vec<String> = Vec::new();
+    if number >= 100 {
+        words.push(format!("{} hundred", ONES_EN[(number / 100) as usize]));
+    }
+    let rest = number % 100;
+    if rest >= 20 {
+        match rest % 10 {
+            0 => words.push(TENS_EN[(rest / 10) as usize].to_string()),
+            ones => words.push(format!(
+                "{}-{}",
+                TENS_EN[(rest / 10) as usize],
+                ONES_EN[ones as usize]
+            )),
+        }
+    } else if rest > 0 {
+        words.push(ONES_EN[rest as usize].to_string());
+    }
+    words.join(" ")
+}
+
+// Spells out a whole number in English, in groups of thousands
+fn number_to_words_en(number: u64) -> String {
+    if number == 0 {
+        return ONES_EN[0].to_string();
+    }
+
+    let mut groups: Vec<String> = Vec::new();
+    let mut rest = number;
+    let mut scale = 0;
+    while rest > 0 {
+        let group = rest % 1000;
+        if group > 0 {
+            let words = hundreds_to_words_en(group);
+            groups.push(match SCALES_EN[scale] {
+                "" => words,
+                name => format!("{words} {name}"),
+            });
+        }
+        rest /= 1000;
+        scale += 1;
+    }
+    groups.reverse();
+    groups.join(" ")
+}
+
+// Spells out a number below ten thousand in Chinese formal numerals
+// Zeros between digits are read once, while trailing zeros are left out
+fn thousands_to_words_zh(number: u64) -> String {
+    let mut words = String::new();
+    let mut is_zero_pending = false;
+    for position in (0..4).rev() {
+        let digit = (number / 10_u64.pow(position)) % 10;
+        if digit == 0 {
+            is_zero_pending = !words.is_empty();
+            continue;
+        }
+        if is_zero_pending {
+            words.push_str(DIGITS_ZH[0]);
+            is_zero_pending = false;
+        }
+        words.push_str(DIGITS_ZH[digit as usize]);
+        words.push_str(UNITS_ZH[position as usize]);
+    }
+    words
+}
+
+// Spells out a whole number in Chinese formal numerals, in groups of ten thousands
+fn number_to_words_zh(number: u64) -> String {
+    if number == 0 {
+        return DIGITS_ZH[0].to_string();
+    }
+
+    let mut groups: Vec<u64> = Vec::new();
+    let mut rest = number;
+    while rest > 0 {
+        groups.push(rest % 10_000);
+        rest /= 10_000;
+    }
+
+    let mut words = String::new();
+    let mut is_zero_pending = false;
+    for (scale, group) in groups.iter().enumerate().rev() {
+        if *group == 0 {
+            is_zero_pending = !words.is_empty();
+            continue;
+        }
+        if !words.is_empty() && (is_zero_pending || *group < 1000) {
+            words.push_str(DIGITS_ZH[0]);
+        }
+        is_zero_pending = false;
+        words.push_str(&thousands_to_words_zh(*group));
+        words.push_str(SCALES_ZH[scale]);
+    }
+    words
+}
+
+// Spells out an amount in English, with the fraction as a number over its base
+fn amount_to_words_en(whole: u64, fraction: u64, decimal_places: u32) -> String {
+    let words = number_to_words_en(whole);
+    let mut words = match words.chars().next() {
+        Some(first) => format!("{}{}", first.to_uppercase(), &words[first.len_utf8()..]),
+        None => words,
+    };
+    if decimal_places > 0 {
+        words.push_str(&format!(
+            " and {:0width$}/{}",
+            fraction,
+            10_u64.pow(decimal_places),
+            width = decimal_places as usize
+        ));
+    }
+    words
+}
+
+// Spells out an amount in Chinese formal numerals, in yuan, jiao and fen where possible
+fn amount_to_words_zh(whole: u64, fraction: u64, decimal_places: u32) -> String {
+    let mut words = number_to_words_zh(whole);
+    if fraction == 0 {
+        words.push_str("元整");
+    } else if decimal_places == 2 {
+        words.push('元');
+        let (jiao, fen) = (fraction / 10, fraction % 10);
+        if jiao > 0 {
+            words.push_str(&format!("{}角", DIGITS_ZH[jiao as usize]));
+        } else {
+            words.push_str(DIGITS_ZH[0]);
+        }
+        if fen > 0 {
+            words.push_str(&format!("{}分", DIGITS_ZH[fen as usize]));
+        }
+    } else {
+        words.push('点');
+        let digits = format!("{:0width$}", fraction, width = decimal_places as usize);
+        for digit in digits.trim_end_matches('0').chars() {
+            words.push_str(DIGITS_ZH[digit.to_digit(10).unwrap_or(0) as usize]);
+        }
+        words.push('元');
+    }
+    words
+}
+
+/* Spells out an amount of money in words, in the given language.
+ * The amount is in the smallest unit of its currency, with the given number of decimal places.
+ * The currency code is written as is, and can be empty for amounts without currency.
+ * Falls back to English for languages that are not supported.
+ */
+pub fn amount_to_words(amount: i64, code: &str, decimal_places: i32, language: &str) -> String {
+    let decimal_places = decimal_places.max(0) as u32;
+    let base = 10_u64.pow(decimal_places);
+    let (whole, fraction) = (amount.unsigned_abs() / base, amount.unsigned_abs() % base);
+
+    match language {
+        "zh" => {
+            let words = amount_to_words_zh(whole, fraction, decimal_places);
+            let sign = if amount < 0 { "负" } else { "" };
+            format!("{code} {sign}{words}").trim_start().to_string()
+        }
+        _ => {
+            let words = amount_to_words_en(whole, fraction, decimal_places);
+            let words = if amount < 0 {
+                format!("Minus {}", words.to_lowercase())
+            } else {
+                words
+            };
+            format!("{words} {code}").trim_end().to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_to_words_en() {
+        assert_eq!(number_to_words_en(0), "zero");
+        assert_eq!(number_to_words_en(13), "thirteen");
+        assert_eq!(number_to_words_en(40), "forty");
+        assert_eq!(number_to_words_en(123), "one hundred twenty-three");
+        assert_eq!(number_to_words_en(1_000_005), "one million five");
+        assert_eq!(
+            number_to_words_en(12_345),
+            "twelve thousand three hundred forty-five"
+        );
+    }
+
+    #[test]
+    fn test_number_to_words_zh() {
+        assert_eq!(number_to_words_zh(0), "零");
+        assert_eq!(number_to_words_zh(10), "壹拾");
+        assert_eq!(number_to_words_zh(105), "壹佰零伍");
+        assert_eq!(number_to_words_zh(1_010), "壹仟零壹拾");
+        assert_eq!(number_to_words_zh(10_500), "壹万零伍佰");
+        assert_eq!(number_to_words_zh(120_000), "壹拾贰万");
+        assert_eq!(number_to_words_zh(100_001_000), "壹亿零壹仟");
+        assert_eq!(number_to_words_zh(100_010_000), "壹亿零壹万");
+    }
+
+    #[test]
+    fn test_amount_to_words() {
+        assert_eq!(
+            amount_to_words(12345, "SGD", 2, "en"),
+            "One hundred twenty-three and 45/100 SGD"
+        );
+        assert_eq!(amount_to_words(500, "", 2, "en"), "Five and 00/100");
+        assert_eq!(amount_to_words(1000, "JPY", 0, "en"), "One thousand JPY");
+        assert_eq!(
+            amount_to_words(1005, "KWD", 3, "en"),
+            "One and 005/1000 KWD"
+        );
+        assert_eq!(amount_to_words(-250, "", 2, "en"), "Minus two and 50/100");
+        assert_eq!(
+            amount_to_words(12345, "SGD", 2, "xx"),
+            amount_to_words(12345, "SGD", 2, "en")
+        );
+
+        assert_eq!(
+            amount_to_words(12345, "CNY", 2, "zh"),
+            "CNY 壹佰贰拾叁元肆角伍分"
+        );
+        assert_eq!(amount_to_words(10005, "", 2, "zh"), "壹佰元零伍分");
+        assert_eq!(amount_to_words(10040, "", 2, "zh"), "壹佰元肆角");
+        assert_eq!(amount_to_words(10000, "", 2, "zh"), "壹佰元整");
+        assert_eq!(amount_to_words(1050, "", 3, "zh"), "壹点零伍元");
+        assert_eq!(amount_to_words(-100, "JPY", 0, "zh"), "JPY 负壹佰元整");
+    }
+}
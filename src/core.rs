@@ -0,0 +1,77 @@
+// core.rs
+
+/* Core is the ledger of PayScribe, as a library independent of Telegram.
+ * It exposes the logic behind the bot, for other frontends such as a CLI or web app to reuse.
+ * Nothing here takes or returns Telegram types: chats and users are identified by strings,
+ * such as the chat ID and the username, and amounts are in the base units of their currency.
+ * Data is kept in the same Redis database as the bot, given by the REDIS_URL environment variable,
+ * so a frontend and the bot can share the same ledgers. Create a Redis handle once, and pass it to every call,
+ * so that they share the same connection. Call run_migrations once before anything else.
+ */
+
+// Payments, from adding them to editing, deleting, and viewing their history
+pub use crate::bot::processor::{
+    add_balance_adjustment, add_payment, add_payments_batch, delete_payments, edit_payment,
+    import_payments, payback_description, retrieve_audit_entries, retrieve_payment,
+    retrieve_payment_count, retrieve_payment_versions, view_payments, BatchPayment,
+};
+
+// Deleted payments, kept in the trash of each chat until restored or purged
+pub use crate::bot::processor::{
+    purge_expired_trash, restore_payment, retrieve_trash, TRASH_RETENTION_DAYS,
+};
+
+// Balances and debts, simplified according to the settings of each chat
+pub use crate::bot::processor::{
+    recalculate_chat_balances, retrieve_consolidated_debts, retrieve_debts,
+    retrieve_pairwise_debts, ConsolidatedDebt, StatementOption,
+};
+
+// Balances and spendings of a ledger, rebuilt from its payments without reading or writing data
+pub use crate::bot::processor::rebuild_ledger_balances;
+
+// Spendings, over the whole chat or a period of time
+pub use crate::bot::processor::{
+    retrieve_leaderboard, retrieve_monthly_stats, retrieve_spending_data,
+    retrieve_spending_data_since, retrieve_spending_forecast, Leaderboard, MonthlyStats,
+    SpendingData, SpendingForecast, UserForecast, UserSpending,
+};
+
+// Trips, kept in their own ledgers within a chat
+pub use crate::bot::processor::{
+    end_chat_trip, retrieve_active_trip, retrieve_trip_debts, retrieve_trip_payments,
+    retrieve_trip_spending_data, retrieve_trips, start_chat_trip,
+};
+
+// Members of chats, which only they can be in payments with strict members on
+pub use crate::bot::processor::{add_chat_users, retrieve_chat_users, retrieve_unknown_users};
+
+// Settings of chats, and backups of all their data
+pub use crate::bot::processor::{
+    export_chat_data, get_chat_setting, restore_chat_data, set_chat_setting, ChatSetting,
+};
+
+// Administration of chats, regardless of their users
+pub use crate::bot::processor::{delete_chat_data, retrieve_chat_ids, retrieve_chat_payments};
+
+// Errors, and how usernames are compared
+pub use crate::bot::processor::{is_username_equal, ProcessError};
+
+// Text for users, such as the reasons of validation errors, and its translation into each language
+pub use crate::bot::i18n::{translate, Language, Text, LANGUAGES, LANGUAGE_DEFAULT};
+
+// Debt simplification
+pub use crate::bot::optimizer::{optimize_debts, SettlementStrategy};
+
+// Currencies, their decimal places, and conversions between them
+pub use crate::bot::currency::{
+    convert_currency_with_rate, fetch_currency_conversion, get_currency_from_code,
+    get_default_currency, normalize_amount, Currency, CURRENCIES,
+};
+
+// Data as stored, and the database schema
+pub use crate::bot::redis::{
+    run_migrations, AuditEntry, ChatBackup, CrudError, Debt, LedgerPayment, Payment, PaymentAudit,
+    PaymentRate, PaymentVersion, Redis, TrashEntry, Trip, UserBalance, UserPayment,
+    CURRENCY_CODE_DEFAULT,
+};
@@ -1,3 +1,4 @@
 // lib.rs
 
 pub mod bot;
+pub mod core;
@@ -1,4 +1,4 @@
-use payscribe::bot::run_dispatcher;
+use payscribe::bot::{run_dispatcher, run_migrations, Redis};
 
 #[tokio::main]
 pub async fn main() {
@@ -7,8 +7,18 @@ pub async fn main() {
     log::info!("Main - Starting PayScribe bot...");
 
     let bot = teloxide::Bot::from_env();
+    let redis = Redis::new();
+
+    // Upgrades the database before handling any requests, so that they see the latest layout
+    match run_migrations(&redis).await {
+        Ok(version) => log::info!("Main - Database schema is at version {}", version),
+        Err(err) => {
+            log::error!("Main - Failed to migrate database: {}", err.to_string());
+            std::process::exit(1);
+        }
+    }
 
     log::info!("Main - PayScribe bot started successfully!");
 
-    run_dispatcher(bot).await;
+    run_dispatcher(redis, bot).await;
 }